@@ -0,0 +1,83 @@
+/*!
+The app can be served from a path below the root of its domain, such as `https://example.com/modelfox/`, when it runs behind a reverse proxy. Routes are matched as if the app were served from the root, because the base path is stripped from each request before it is routed. Links, redirects, cookies, and static asset URLs pass their paths through [`app_path`] to add the base path back.
+*/
+
+use anyhow::{bail, Result};
+use once_cell::sync::OnceCell;
+
+static BASE_PATH: OnceCell<String> = OnceCell::new();
+
+/// Set the base path for the rest of the process. This must be called before the app handles any requests, and may only be called once.
+pub fn set_base_path(base_path: &str) -> Result<()> {
+	let base_path = normalize_base_path(base_path)?;
+	if BASE_PATH.set(base_path).is_err() {
+		bail!("The base path was already set.");
+	}
+	Ok(())
+}
+
+/// Retrieve the base path, without a trailing slash. This is empty if the app is served from the root.
+pub fn base_path() -> &'static str {
+	BASE_PATH
+		.get()
+		.map(|base_path| base_path.as_str())
+		.unwrap_or("")
+}
+
+/// Add the base path to `path`, which is a path starting with a slash as if the app were served from the root.
+pub fn app_path(path: impl AsRef<str>) -> String {
+	join_base_path(base_path(), path.as_ref())
+}
+
+/// Remove the base path from the path of a request. This returns `None` if the path is not below the base path.
+pub fn strip_base_path(path: &str) -> Option<&str> {
+	strip_base_path_inner(base_path(), path)
+}
+
+fn normalize_base_path(base_path: &str) -> Result<String> {
+	if !base_path.is_empty() && !base_path.starts_with('/') {
+		bail!("The base path must start with a slash, like /modelfox.");
+	}
+	if base_path.contains(|c| c == '?' || c == '#') {
+		bail!("The base path must not contain a query or fragment.");
+	}
+	Ok(base_path.trim_end_matches('/').to_owned())
+}
+
+fn join_base_path(base_path: &str, path: &str) -> String {
+	format!("{}{}", base_path, path)
+}
+
+fn strip_base_path_inner<'a>(base_path: &str, path: &'a str) -> Option<&'a str> {
+	match path.strip_prefix(base_path)? {
+		"" => Some("/"),
+		path if path.starts_with('/') => Some(path),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_base_path() {
+		assert_eq!(normalize_base_path("/modelfox/").unwrap(), "/modelfox");
+		assert_eq!(normalize_base_path("/").unwrap(), "");
+		assert_eq!(normalize_base_path("").unwrap(), "");
+		assert!(normalize_base_path("modelfox").is_err());
+		assert_eq!(
+			join_base_path("/modelfox", "/repos/new"),
+			"/modelfox/repos/new"
+		);
+		assert_eq!(join_base_path("", "/repos/new"), "/repos/new");
+		assert_eq!(
+			strip_base_path_inner("/modelfox", "/modelfox/repos/new"),
+			Some("/repos/new")
+		);
+		assert_eq!(strip_base_path_inner("/modelfox", "/modelfox"), Some("/"));
+		assert_eq!(strip_base_path_inner("/modelfox", "/modelfoxes"), None);
+		assert_eq!(strip_base_path_inner("/modelfox", "/repos/new"), None);
+		assert_eq!(strip_base_path_inner("", "/repos/new"), Some("/repos/new"));
+	}
+}
@@ -0,0 +1,353 @@
+/*!
+This module implements the maintenance operations behind the `modelfox admin` command. They talk directly to the app database, so operators can script them without going through the UI. Operations that would remove model files from storage, like deleting a repo, are not included, because the admin command does not have access to the app's storage.
+*/
+
+use crate::{sessions::revoke_all_sessions, CreateDatabasePoolOptions};
+use anyhow::{bail, Result};
+use modelfox_id::Id;
+use num::ToPrimitive;
+use sqlx::prelude::*;
+use std::borrow::BorrowMut;
+use url::Url;
+
+/// Connect to the app database for admin commands. Unlike starting the app, this does not run migrations, and fails if the database has not run all of them.
+pub async fn connect_admin_database(database_url: Url) -> Result<sqlx::AnyPool> {
+	let database_pool = crate::create_database_pool(CreateDatabasePoolOptions {
+		database_max_connections: Some(1),
+		database_url,
+	})
+	.await?;
+	if modelfox_app_migrations::empty(&database_pool).await? {
+		bail!("The database has not been migrated. Run `modelfox migrate` first.");
+	}
+	modelfox_app_migrations::verify(&database_pool).await?;
+	Ok(database_pool)
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct AdminUser {
+	pub id: Id,
+	pub email: String,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct AdminOrganization {
+	pub id: Id,
+	pub name: String,
+	pub member_count: u64,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct AdminRepo {
+	pub id: Id,
+	pub title: String,
+	pub owner: Option<RepoOwner>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum RepoOwner {
+	User(Id),
+	Organization(Id),
+}
+
+/// This is the number of rows removed by purging predictions.
+#[derive(Debug, serde::Serialize)]
+pub struct PurgePredictionsOutput {
+	pub prediction_count: u64,
+	pub true_value_count: u64,
+}
+
+pub async fn list_users(txn: &mut sqlx::Transaction<'_, sqlx::Any>) -> Result<Vec<AdminUser>> {
+	let rows = sqlx::query(
+		"
+			select
+				id,
+				email
+			from users
+			order by email
+		",
+	)
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let id: String = row.get(0);
+			Ok(AdminUser {
+				id: id.parse()?,
+				email: row.get(1),
+			})
+		})
+		.collect()
+}
+
+/// Look up the id of the user with `email`.
+pub async fn get_user_id_by_email(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	email: &str,
+) -> Result<Id> {
+	let row = sqlx::query(
+		"
+			select
+				id
+			from users
+			where email = $1
+		",
+	)
+	.bind(email)
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	let row = match row {
+		Some(row) => row,
+		None => bail!("There is no user with the email {}.", email),
+	};
+	let id: String = row.get(0);
+	Ok(id.parse()?)
+}
+
+pub async fn create_user(txn: &mut sqlx::Transaction<'_, sqlx::Any>, email: &str) -> Result<Id> {
+	if get_user_id_by_email(txn, email).await.is_ok() {
+		bail!("A user with the email {} already exists.", email);
+	}
+	let user_id = Id::generate();
+	sqlx::query(
+		"
+			insert into users (
+				id, email
+			) values (
+				$1, $2
+			)
+		",
+	)
+	.bind(&user_id.to_string())
+	.bind(email)
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(user_id)
+}
+
+/// Delete a user, along with their login codes, sessions, and organization memberships. Deleting a user who owns repos is an error, because deleting their repos would leave their models behind in storage. Transfer or delete the repos first.
+pub async fn delete_user(txn: &mut sqlx::Transaction<'_, sqlx::Any>, user_id: Id) -> Result<()> {
+	let row = sqlx::query(
+		"
+			select
+				count(*)
+			from repos
+			where user_id = $1
+		",
+	)
+	.bind(&user_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?;
+	let repo_count: i64 = row.get(0);
+	if repo_count > 0 {
+		bail!(
+			"The user owns {} repos. Transfer or delete them before deleting the user.",
+			repo_count
+		);
+	}
+	for query in [
+		"delete from codes where user_id = $1",
+		"delete from tokens where user_id = $1",
+		"delete from teams_users where user_id = $1",
+		"delete from organizations_users where user_id = $1",
+		"delete from users where id = $1",
+	] {
+		sqlx::query(query)
+			.bind(&user_id.to_string())
+			.execute(txn.borrow_mut())
+			.await?;
+	}
+	Ok(())
+}
+
+/// Reset a user's authentication by revoking all of their sessions and deleting their unused login codes, so they have to log in again.
+pub async fn reset_user_auth(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user_id: Id,
+	now: i64,
+) -> Result<()> {
+	revoke_all_sessions(txn, user_id, now).await?;
+	sqlx::query(
+		"
+			delete from codes
+			where
+				user_id = $1
+				and not used
+		",
+	)
+	.bind(&user_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn list_organizations(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Vec<AdminOrganization>> {
+	let rows = sqlx::query(
+		"
+			select
+				organizations.id,
+				organizations.name,
+				count(organizations_users.user_id)
+			from organizations
+			left join organizations_users
+				on organizations_users.organization_id = organizations.id
+			group by organizations.id, organizations.name
+			order by organizations.name
+		",
+	)
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let id: String = row.get(0);
+			let name: Option<String> = row.get(1);
+			let member_count: i64 = row.get(2);
+			Ok(AdminOrganization {
+				id: id.parse()?,
+				name: name.unwrap_or_default(),
+				member_count: member_count.to_u64().unwrap(),
+			})
+		})
+		.collect()
+}
+
+/// Create an organization with `admin_user_id` as its first admin.
+pub async fn create_organization(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	name: &str,
+	admin_user_id: Id,
+) -> Result<Id> {
+	let organization_id = Id::generate();
+	sqlx::query(
+		"
+			insert into organizations
+				(id, name)
+			values
+				($1, $2)
+		",
+	)
+	.bind(&organization_id.to_string())
+	.bind(name)
+	.execute(txn.borrow_mut())
+	.await?;
+	sqlx::query(
+		"
+			insert into organizations_users
+				(organization_id, user_id, is_admin)
+			values
+				($1, $2, true)
+		",
+	)
+	.bind(&organization_id.to_string())
+	.bind(&admin_user_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(organization_id)
+}
+
+pub async fn list_repos(txn: &mut sqlx::Transaction<'_, sqlx::Any>) -> Result<Vec<AdminRepo>> {
+	let rows = sqlx::query(
+		"
+			select
+				id,
+				title,
+				user_id,
+				organization_id
+			from repos
+			order by created_at
+		",
+	)
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let id: String = row.get(0);
+			let user_id: Option<String> = row.get(2);
+			let organization_id: Option<String> = row.get(3);
+			let owner = match (user_id, organization_id) {
+				(Some(user_id), _) => Some(RepoOwner::User(user_id.parse()?)),
+				(None, Some(organization_id)) => {
+					Some(RepoOwner::Organization(organization_id.parse()?))
+				}
+				(None, None) => None,
+			};
+			Ok(AdminRepo {
+				id: id.parse()?,
+				title: row.get(1),
+				owner,
+			})
+		})
+		.collect()
+}
+
+/// Transfer a repo to a new owner. Its models, monitors, and production data move with it.
+pub async fn transfer_repo(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+	owner: RepoOwner,
+) -> Result<()> {
+	let (user_id, organization_id) = match owner {
+		RepoOwner::User(user_id) => (Some(user_id.to_string()), None),
+		RepoOwner::Organization(organization_id) => (None, Some(organization_id.to_string())),
+	};
+	let result = sqlx::query(
+		"
+			update repos
+			set
+				user_id = $1,
+				organization_id = $2
+			where id = $3
+		",
+	)
+	.bind(user_id)
+	.bind(organization_id)
+	.bind(&repo_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	if result.rows_affected() == 0 {
+		bail!("There is no repo with the id {}.", repo_id);
+	}
+	Ok(())
+}
+
+/// Delete the predictions and true values logged before `before`, for one model or for every model if `model_id` is `None`. The production stats and metrics already computed from them are kept, so the charts in the app do not change. If `dry_run` is true, nothing is deleted and only the counts are returned.
+pub async fn purge_predictions(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Option<Id>,
+	before: i64,
+	dry_run: bool,
+) -> Result<PurgePredictionsOutput> {
+	let model_id_filter = if model_id.is_some() {
+		"and model_id = $2"
+	} else {
+		""
+	};
+	let mut counts = Vec::with_capacity(2);
+	for table in ["predictions", "true_values"] {
+		let count_query = format!(
+			"select count(*) from {} where date < $1 {}",
+			table, model_id_filter
+		);
+		let delete_query = format!("delete from {} where date < $1 {}", table, model_id_filter);
+		let mut query = sqlx::query(&count_query).bind(before);
+		if let Some(model_id) = model_id {
+			query = query.bind(model_id.to_string());
+		}
+		let count: i64 = query.fetch_one(txn.borrow_mut()).await?.get(0);
+		counts.push(count.to_u64().unwrap());
+		if !dry_run {
+			let mut query = sqlx::query(&delete_query).bind(before);
+			if let Some(model_id) = model_id {
+				query = query.bind(model_id.to_string());
+			}
+			query.execute(txn.borrow_mut()).await?;
+		}
+	}
+	Ok(PurgePredictionsOutput {
+		prediction_count: counts[0],
+		true_value_count: counts[1],
+	})
+}
@@ -1,14 +1,18 @@
 use crate::{
 	alert_sender::create_alert_send,
+	heuristics::{METRIC_PERCENT_DECIMAL_PLACES, METRIC_SIGNIFICANT_DIGITS},
 	monitor::{AlertModelType, Monitor},
 	App, AppState,
 };
 use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use chrono_tz::Tz;
 use modelfox_id::Id;
+use modelfox_number_formatter::NumberFormatter;
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::*;
 use std::{borrow::BorrowMut, fmt, io, str::FromStr};
-use time::{macros::format_description, OffsetDateTime};
+use time::OffsetDateTime;
 use url::Url;
 
 /// Collection for the alert results from a single run
@@ -27,11 +31,17 @@ impl Alert {
 		format!("{cadence} {metric}")
 	}
 
-	pub fn formated_time_range(&self) -> String {
+	/// Format the time period this alert covers in the given timezone.
+	pub fn formated_time_range(&self, timezone: Tz) -> String {
 		let (begin_time, end_time) = self.time_range().unwrap();
-		let dt_format = format_description!("[year]-[month]-[day] [hour]:[minute]");
-		let begin_time = begin_time.format(dt_format).unwrap();
-		let end_time = end_time.format(dt_format).unwrap();
+		let format = |time: OffsetDateTime| {
+			Utc.timestamp(time.unix_timestamp(), 0)
+				.with_timezone(&timezone)
+				.format("%Y-%m-%d %H:%M")
+				.to_string()
+		};
+		let begin_time = format(begin_time);
+		let end_time = format(end_time);
 
 		format!("{begin_time} to {end_time}")
 	}
@@ -44,9 +54,9 @@ impl Alert {
 		self.result.production_value
 	}
 
-	pub fn title(&self) -> String {
+	pub fn title(&self, timezone: Tz) -> String {
 		let cadence_and_metric = self.cadence_and_metric();
-		let time_range = self.formated_time_range();
+		let time_range = self.formated_time_range(timezone);
 		format!("{cadence_and_metric} Alert: {time_range}")
 	}
 
@@ -77,6 +87,9 @@ pub enum AlertMethod {
 	/// POST the alert to the given URL as a webhook
 	#[serde(rename = "webhook")]
 	Webhook(AlertMethodWebhook),
+	/// Send the alert using the organization's notification channel with the given id
+	#[serde(rename = "notification_channel")]
+	NotificationChannel(AlertMethodNotificationChannel),
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -126,12 +139,26 @@ impl fmt::Display for AlertMethodWebhook {
 	}
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AlertMethodNotificationChannel {
+	pub notification_channel_id: Id,
+}
+
+impl fmt::Display for AlertMethodNotificationChannel {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Notification Channel: {}", self.notification_channel_id)
+	}
+}
+
 impl fmt::Display for AlertMethod {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		let s = match self {
 			AlertMethod::Email(email) => email.to_string(),
 			AlertMethod::Stdout => "stdout".to_owned(),
 			AlertMethod::Webhook(webhook) => webhook.to_string(),
+			AlertMethod::NotificationChannel(notification_channel) => {
+				notification_channel.to_string()
+			}
 		};
 		write!(f, "{}", s)
 	}
@@ -147,6 +174,26 @@ pub enum AlertMetric {
 	MeanSquaredError,
 	#[serde(rename = "root_mean_squared_error")]
 	RootMeanSquaredError,
+	#[serde(rename = "mean_absolute_error")]
+	MeanAbsoluteError,
+	/// The mean of the regression predictions minus their true values. Its training value is zero, the mean residual of an unbiased model.
+	#[serde(rename = "mean_residual")]
+	MeanResidual,
+	/// The fraction of regression predictions within the tolerance the model was trained with.
+	#[serde(rename = "tolerance_accuracy")]
+	ToleranceAccuracy,
+	/// The largest fraction of production values outside the training range across all number columns.
+	#[serde(rename = "out_of_range_fraction")]
+	OutOfRangeFraction,
+	/// The largest fraction of production tokens not in the training vocabulary across all text columns.
+	#[serde(rename = "unseen_token_fraction")]
+	UnseenTokenFraction,
+	/// The number of days since the newest model in the repo was uploaded.
+	#[serde(rename = "model_age")]
+	ModelAge,
+	/// A metric computed by the uploaded WebAssembly module with the given id. See [`crate::custom_metrics`].
+	#[serde(rename = "custom")]
+	Custom { custom_metric_id: Id },
 }
 
 impl AlertMetric {
@@ -155,16 +202,67 @@ impl AlertMetric {
 			AlertMetric::Accuracy => "accuracy".to_owned(),
 			AlertMetric::MeanSquaredError => "mse".to_owned(),
 			AlertMetric::RootMeanSquaredError => "rmse".to_owned(),
+			AlertMetric::MeanAbsoluteError => "mae".to_owned(),
+			AlertMetric::MeanResidual => "mean_residual".to_owned(),
+			AlertMetric::ToleranceAccuracy => "tolerance_accuracy".to_owned(),
+			AlertMetric::OutOfRangeFraction => "out_of_range".to_owned(),
+			AlertMetric::UnseenTokenFraction => "unseen_tokens".to_owned(),
+			AlertMetric::ModelAge => "model_age".to_owned(),
+			AlertMetric::Custom { custom_metric_id } => format!("custom:{}", custom_metric_id),
+		}
+	}
+
+	/// Get the formatter for values of this metric. Fractions are shown as percentages, and errors are rounded to a few significant digits so they are readable at a glance.
+	pub fn number_formatter(&self) -> NumberFormatter {
+		match self {
+			AlertMetric::Accuracy
+			| AlertMetric::ToleranceAccuracy
+			| AlertMetric::OutOfRangeFraction
+			| AlertMetric::UnseenTokenFraction => NumberFormatter::percent(METRIC_PERCENT_DECIMAL_PLACES),
+			AlertMetric::MeanSquaredError
+			| AlertMetric::RootMeanSquaredError
+			| AlertMetric::MeanAbsoluteError
+			| AlertMetric::MeanResidual
+			| AlertMetric::Custom { .. } => NumberFormatter::float(METRIC_SIGNIFICANT_DIGITS),
+			AlertMetric::ModelAge => {
+				NumberFormatter::float(METRIC_SIGNIFICANT_DIGITS).with_unit(Some("days".to_owned()))
+			}
 		}
 	}
 
+	/// Whether this metric is computed from tracked predictions and true values. Monitors on metrics that are not, like the model age, are checked even if nothing has been tracked, and ignore the minimum sample size.
+	pub fn uses_production_data(&self) -> bool {
+		!matches!(self, AlertMetric::ModelAge)
+	}
+
+	/// Check if a confidence interval is computed for this metric's production value. See [`modelfox_app_production_metrics::ProductionMetricsConfidenceIntervals`].
+	pub fn has_confidence_interval(&self) -> bool {
+		matches!(
+			self,
+			AlertMetric::Accuracy
+				| AlertMetric::MeanSquaredError
+				| AlertMetric::RootMeanSquaredError
+				| AlertMetric::MeanAbsoluteError
+				| AlertMetric::MeanResidual
+				| AlertMetric::ToleranceAccuracy
+		)
+	}
+
 	/// Check if the given AlertModelType is applicable to this AlertMetric
 	pub fn validate(&self, model_type: AlertModelType) -> bool {
 		match self {
 			AlertMetric::Accuracy => matches!(model_type, AlertModelType::Classifier),
-			AlertMetric::MeanSquaredError | &AlertMetric::RootMeanSquaredError => {
+			AlertMetric::MeanSquaredError
+			| AlertMetric::RootMeanSquaredError
+			| AlertMetric::MeanAbsoluteError
+			| AlertMetric::MeanResidual
+			| AlertMetric::ToleranceAccuracy => {
 				matches!(model_type, AlertModelType::Regressor)
 			}
+			AlertMetric::OutOfRangeFraction
+			| AlertMetric::UnseenTokenFraction
+			| AlertMetric::ModelAge
+			| AlertMetric::Custom { .. } => true,
 		}
 	}
 }
@@ -175,6 +273,13 @@ impl fmt::Display for AlertMetric {
 			AlertMetric::Accuracy => "Accuracy",
 			AlertMetric::MeanSquaredError => "Mean Squared Error",
 			AlertMetric::RootMeanSquaredError => "Root Mean Squared Error",
+			AlertMetric::MeanAbsoluteError => "Mean Absolute Error",
+			AlertMetric::MeanResidual => "Mean Residual",
+			AlertMetric::ToleranceAccuracy => "Tolerance Accuracy",
+			AlertMetric::OutOfRangeFraction => "Out of Range Fraction",
+			AlertMetric::UnseenTokenFraction => "Unseen Token Fraction",
+			AlertMetric::ModelAge => "Model Age",
+			AlertMetric::Custom { .. } => "Custom Metric",
 		};
 		write!(f, "{}", s)
 	}
@@ -183,10 +288,22 @@ impl fmt::Display for AlertMetric {
 impl FromStr for AlertMetric {
 	type Err = io::Error;
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some(custom_metric_id) = s.strip_prefix("custom:") {
+			let custom_metric_id = custom_metric_id.parse().map_err(|_| {
+				io::Error::new(io::ErrorKind::InvalidInput, "Invalid custom metric id")
+			})?;
+			return Ok(AlertMetric::Custom { custom_metric_id });
+		}
 		match s.to_lowercase().as_str() {
 			"accuracy" => Ok(AlertMetric::Accuracy),
 			"mse" | "mean_squared_error" => Ok(AlertMetric::MeanSquaredError),
 			"rmse" | "root_mean_squared_error" => Ok(AlertMetric::RootMeanSquaredError),
+			"mae" | "mean_absolute_error" => Ok(AlertMetric::MeanAbsoluteError),
+			"mean_residual" => Ok(AlertMetric::MeanResidual),
+			"tolerance_accuracy" => Ok(AlertMetric::ToleranceAccuracy),
+			"out_of_range" | "out_of_range_fraction" => Ok(AlertMetric::OutOfRangeFraction),
+			"unseen_tokens" | "unseen_token_fraction" => Ok(AlertMetric::UnseenTokenFraction),
+			"model_age" => Ok(AlertMetric::ModelAge),
 			_ => Err(io::Error::new(
 				io::ErrorKind::InvalidInput,
 				"Unsupported alert metric",
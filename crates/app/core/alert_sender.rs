@@ -1,18 +1,29 @@
 use crate::{
-	alert::{Alert, AlertMethod},
+	alert::{Alert, AlertMethod, AlertResult},
+	alert_template::{render_alert_template, AlertTemplateContext},
+	cluster::{BackgroundTask, Leadership},
 	heuristics::{
 		ALERT_SENDER_HEARTBEAT_DURATION_PRODUCTION, ALERT_SENDER_HEARTBEAT_DURATION_TESTING,
 		ALERT_SENDER_MAXIMUM_RETRY_PERIODS, ALERT_SENDER_RETRY_DECAY_FACTOR,
 		ALERT_SENDER_RETRY_INITIAL_PERIOD,
 	},
+	monitor::Monitor,
+	notification_channels::{get_notification_channel, NotificationChannel},
+	repos::{get_model_repo_id, get_repo},
+	timezone::get_model_owner_timezone,
+	webhooks::send_webhook_deliveries,
 	App, AppState,
 };
 use anyhow::Result;
+use chrono_tz::Tz;
 use futures::{select, FutureExt};
 use modelfox_id::Id;
+use num::ToPrimitive;
+use serde::Serialize;
 use sqlx::prelude::*;
 use std::{borrow::BorrowMut, io, str::FromStr, sync::Arc};
 use tokio::sync::{mpsc, oneshot};
+use url::Url;
 
 #[derive(Debug)]
 pub enum AlertSenderMessage {
@@ -44,16 +55,35 @@ pub async fn alert_sender(
 				Some(message) => Event::Message(message),
 			}
 		};
-		tracing::info!("Begin alert_sender heartbeat");
-		let mut txn = app_state.begin_transaction().await?;
-		// First, find any orphaned tasks still marked Sending and reset them
-		reset_dropped_sends(&app_state, txn.borrow_mut()).await?;
-		let unsent_alerts = get_all_unsent_alert_sends(&app_state, txn.borrow_mut()).await?;
-		for alert_send in unsent_alerts {
-			handle_alert_send_with_decay(&app_state, alert_send, txn.borrow_mut()).await?;
+		if let Some(leadership) =
+			Leadership::acquire(&app_state, BackgroundTask::AlertSender).await?
+		{
+			tracing::info!("Begin alert_sender heartbeat");
+			let mut txn = app_state.begin_transaction().await?;
+			// First, find any orphaned tasks still marked Sending and reset them
+			reset_dropped_sends(&app_state, txn.borrow_mut()).await?;
+			if app_state.options.pause_notifications {
+				// Leave alert sends and webhook deliveries pending, so they are sent once notifications are resumed.
+				tracing::info!("Notifications are paused");
+			} else {
+				let unsent_alerts =
+					get_all_unsent_alert_sends(&app_state, txn.borrow_mut()).await?;
+				let notifications =
+					rate_limit_alert_sends(&app_state, unsent_alerts, txn.borrow_mut()).await?;
+				for notification in notifications {
+					handle_alert_notification_with_decay(
+						&app_state,
+						notification,
+						txn.borrow_mut(),
+					)
+					.await?;
+				}
+				send_webhook_deliveries(&app_state, txn.borrow_mut()).await?;
+			}
+			app_state.commit_transaction(txn).await?;
+			leadership.release().await?;
+			tracing::info!("End alert_sender heartbeat");
 		}
-		app_state.commit_transaction(txn).await?;
-		tracing::info!("End alert_sender heartbeat");
 		if let Event::Message(AlertSenderMessage::Run(sender)) = event {
 			sender.send(()).unwrap();
 		}
@@ -76,16 +106,51 @@ pub async fn handle_alert_send(
 	increment_attempt_count(alert_send.id, txn.borrow_mut()).await?;
 	let exceeded_thresholds = alert_send.alert.result;
 
-	match &alert_send.method {
+	let method = match resolve_alert_send_method(app_state, alert_send, txn.borrow_mut()).await? {
+		Some(method) => method,
+		None => {
+			let status = AlertSendStatus::Failed;
+			set_alert_send_status(app_state, alert_send.id, status, txn.borrow_mut()).await?;
+			return Ok(status);
+		}
+	};
+
+	let timezone = get_model_owner_timezone(txn.borrow_mut(), alert_send.alert.monitor.model_id)
+		.await?
+		.unwrap_or(Tz::UTC);
+	let message = match &alert_send.alert.monitor.message_template {
+		Some(message_template) => Some(
+			render_alert_message(
+				app_state,
+				&alert_send.alert,
+				message_template,
+				timezone,
+				txn.borrow_mut(),
+			)
+			.await?,
+		),
+		None => None,
+	};
+
+	match &method {
 		AlertMethod::Email(email) => {
+			let body = match &message {
+				Some(message) => message.clone(),
+				None => format!(
+					"{}\nTimes are in {}.\nExceeded alert thresholds: {:?}",
+					alert_send.alert.title(timezone),
+					timezone.name(),
+					exceeded_thresholds
+				),
+			};
 			let email = lettre::Message::builder()
 				.from("ModelFox <noreply@modelfox.dev>".parse()?)
 				.to(email.email.parse()?)
-				.subject("ModelFox Metrics Alert")
-				.body(format!(
-					"Exceeded alert thresholds: {:?}",
-					exceeded_thresholds
-				))?;
+				.subject(format!(
+					"ModelFox Metrics Alert: {}",
+					alert_send.alert.title(timezone)
+				))
+				.body(body)?;
 			let status = match app_state.send_email(email).await {
 				Ok(_) => AlertSendStatus::Succeeded,
 				Err(_) => AlertSendStatus::Retrying,
@@ -95,27 +160,187 @@ pub async fn handle_alert_send(
 		}
 		AlertMethod::Stdout => {
 			println!("exceeded thresholds: {:?}", exceeded_thresholds);
+			if let Some(message) = &message {
+				println!("{}", message);
+			}
 			Ok(AlertSendStatus::Succeeded)
 		}
 		AlertMethod::Webhook(url) => {
-			let url = &url.url;
-			let status = match app_state
-				.http_sender
-				.post_payload(exceeded_thresholds, url.clone())
-				.await
-			{
-				Ok(response) => {
-					if matches!(response.status(), http::StatusCode::OK) {
-						AlertSendStatus::Succeeded
-					} else {
-						AlertSendStatus::Retrying
-					}
-				}
-				Err(_) => AlertSendStatus::Retrying,
+			let payload = AlertWebhookPayload {
+				result: exceeded_thresholds,
+				message,
 			};
+			let status = send_alert_webhook(app_state, payload, &url.url).await;
 			set_alert_send_status(app_state, alert_send.id, status, txn.borrow_mut()).await?;
 			Ok(status)
 		}
+		AlertMethod::NotificationChannel(_) => unreachable!(),
+	}
+}
+
+/// Send one notification that summarizes `alert_send` and the `summarized` alert sends, which all have the same method. This is sent in place of the individual notifications when an organization exceeds its limit of alert notifications per hour.
+async fn handle_alert_summary_send(
+	app_state: &AppState,
+	alert_send: &AlertSend,
+	summarized: &[AlertSend],
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<AlertSendStatus> {
+	set_alert_send_status(
+		app_state,
+		alert_send.id,
+		AlertSendStatus::Sending,
+		txn.borrow_mut(),
+	)
+	.await?;
+	increment_attempt_count(alert_send.id, txn.borrow_mut()).await?;
+	let method = match resolve_alert_send_method(app_state, alert_send, txn.borrow_mut()).await? {
+		Some(method) => method,
+		None => {
+			let status = AlertSendStatus::Failed;
+			set_alert_send_status(app_state, alert_send.id, status, txn.borrow_mut()).await?;
+			return Ok(status);
+		}
+	};
+	let timezone = get_model_owner_timezone(txn.borrow_mut(), alert_send.alert.monitor.model_id)
+		.await?
+		.unwrap_or(Tz::UTC);
+	let alerts = std::iter::once(alert_send)
+		.chain(summarized.iter())
+		.map(|alert_send| &alert_send.alert)
+		.collect::<Vec<_>>();
+	let status = match &method {
+		AlertMethod::Email(email) => {
+			let titles = alerts
+				.iter()
+				.map(|alert| format!("- {}", alert.title(timezone)))
+				.collect::<Vec<_>>()
+				.join("\n");
+			let body = format!(
+				"Your organization reached its limit of alert notifications per hour, so these {} alerts were combined into one summary.\nTimes are in {}.\n\n{}",
+				alerts.len(),
+				timezone.name(),
+				titles,
+			);
+			let email = lettre::Message::builder()
+				.from("ModelFox <noreply@modelfox.dev>".parse()?)
+				.to(email.email.parse()?)
+				.subject(format!("ModelFox Metrics Alerts: {} alerts", alerts.len()))
+				.body(body)?;
+			match app_state.send_email(email).await {
+				Ok(_) => AlertSendStatus::Succeeded,
+				Err(_) => AlertSendStatus::Retrying,
+			}
+		}
+		AlertMethod::Stdout => {
+			for alert in alerts.iter() {
+				println!("exceeded thresholds: {:?}", alert.result);
+			}
+			AlertSendStatus::Succeeded
+		}
+		AlertMethod::Webhook(url) => {
+			let payload = AlertSummaryWebhookPayload {
+				results: alerts.iter().map(|alert| alert.result).collect(),
+			};
+			send_alert_webhook(app_state, payload, &url.url).await
+		}
+		AlertMethod::NotificationChannel(_) => unreachable!(),
+	};
+	set_alert_send_status(app_state, alert_send.id, status, txn.borrow_mut()).await?;
+	Ok(status)
+}
+
+/// Retrieve the method to send an alert with. Notification channels are resolved when sending rather than when the monitor is created, so that changes to a channel apply to every monitor that references it. This returns `None` if the channel was deleted or cannot be decrypted, in which case the send cannot succeed.
+async fn resolve_alert_send_method(
+	app_state: &AppState,
+	alert_send: &AlertSend,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Option<AlertMethod>> {
+	match &alert_send.method {
+		AlertMethod::NotificationChannel(notification_channel) => {
+			let notification_channel = get_alert_send_notification_channel(
+				app_state,
+				notification_channel.notification_channel_id,
+				txn,
+			)
+			.await?;
+			Ok(notification_channel.map(|notification_channel| notification_channel.method))
+		}
+		method => Ok(Some(method.clone())),
+	}
+}
+
+/// POST `payload` to an alert webhook. The send is retried unless the webhook responds with 200 OK.
+async fn send_alert_webhook<S: Serialize>(
+	app_state: &AppState,
+	payload: S,
+	url: &Url,
+) -> AlertSendStatus {
+	match app_state
+		.http_sender
+		.post_payload(payload, url.clone())
+		.await
+	{
+		Ok(response) if response.status() == http::StatusCode::OK => AlertSendStatus::Succeeded,
+		_ => AlertSendStatus::Retrying,
+	}
+}
+
+/// The body of an alert webhook. The message is only included if the monitor has a message template, so payloads for other monitors are unchanged.
+#[derive(serde::Serialize)]
+struct AlertWebhookPayload {
+	#[serde(flatten)]
+	result: AlertResult,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	message: Option<String>,
+}
+
+/// The body of an alert webhook that summarizes several alerts.
+#[derive(serde::Serialize)]
+struct AlertSummaryWebhookPayload {
+	results: Vec<AlertResult>,
+}
+
+/// Render the monitor's message template for `alert`.
+async fn render_alert_message(
+	app_state: &AppState,
+	alert: &Alert,
+	message_template: &str,
+	timezone: Tz,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<String> {
+	let repo_id = get_model_repo_id(txn, alert.monitor.model_id).await?;
+	let repo = get_repo(txn, repo_id).await?;
+	let context = AlertTemplateContext::new(
+		alert,
+		&repo.title,
+		repo_id,
+		app_state.options.url.as_ref(),
+		timezone,
+	)?;
+	render_alert_template(message_template, &context)
+}
+
+/// Retrieve the notification channel an alert send references. This returns `None` if the channel was deleted or cannot be decrypted, in which case the send cannot succeed.
+async fn get_alert_send_notification_channel(
+	app_state: &AppState,
+	notification_channel_id: Id,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Option<NotificationChannel>> {
+	let secret_key = match app_state.options.secret_key.as_ref() {
+		Some(secret_key) => secret_key,
+		None => {
+			tracing::error!(
+				"An alert references a notification channel, but no secret key is configured."
+			);
+			return Ok(None);
+		}
+	};
+	match get_notification_channel(txn, secret_key, notification_channel_id).await {
+		Ok(notification_channel) => Ok(notification_channel),
+		Err(error) => {
+			tracing::error!(%error, "Failed to read notification channel.");
+			Ok(None)
+		}
 	}
 }
 
@@ -151,18 +376,187 @@ async fn reset_dropped_sends(
 	Ok(())
 }
 
-async fn handle_alert_send_with_decay(
-	app_state: &AppState,
+/// An alert notification sends one alert send. When an organization exceeds its limit of alert notifications per hour, it instead sends a summary of several alert sends with the same method, and the `summarized` alert sends are not sent on their own.
+struct AlertNotification {
 	alert_send: AlertSend,
+	summarized: Vec<AlertSend>,
+}
+
+/// Decide how to send the unsent alert sends. Alert sends for organizations without a limit of alert notifications per hour are each sent on their own. For each organization with a limit, alert sends are sent on their own while the organization is under its limit for the past hour. If there are more alert sends than notifications remaining, the last notification remaining is used for a summary of the rest, with one summary for each method. If the organization has no notifications remaining, its alert sends wait until it does.
+async fn rate_limit_alert_sends(
+	app_state: &AppState,
+	alert_sends: Vec<AlertSend>,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Vec<AlertNotification>> {
+	let mut notifications = Vec::new();
+	let mut organizations: Vec<(Id, i64, Vec<AlertSend>)> = Vec::new();
+	for alert_send in alert_sends {
+		let limit = get_model_organization_alert_notifications_per_hour(
+			txn.borrow_mut(),
+			alert_send.alert.monitor.model_id,
+		)
+		.await?;
+		match limit {
+			Some((organization_id, limit)) => {
+				match organizations
+					.iter_mut()
+					.find(|(id, _, _)| *id == organization_id)
+				{
+					Some((_, _, alert_sends)) => alert_sends.push(alert_send),
+					None => organizations.push((organization_id, limit, vec![alert_send])),
+				}
+			}
+			None => notifications.push(AlertNotification {
+				alert_send,
+				summarized: Vec::new(),
+			}),
+		}
+	}
+	let one_hour_ago = (app_state.clock().now_utc() - time::Duration::hours(1)).unix_timestamp();
+	for (organization_id, limit, alert_sends) in organizations {
+		let sent_count = get_organization_alert_notification_count(
+			txn.borrow_mut(),
+			organization_id,
+			one_hour_ago,
+		)
+		.await?;
+		let remaining_count = (limit - sent_count).max(0).to_usize().unwrap();
+		let (individual_count, summarized_count) =
+			rate_limit_alert_send_counts(alert_sends.len(), remaining_count);
+		let mut alert_sends = alert_sends.into_iter();
+		for alert_send in alert_sends.by_ref().take(individual_count) {
+			notifications.push(AlertNotification {
+				alert_send,
+				summarized: Vec::new(),
+			});
+		}
+		let mut summaries: Vec<AlertNotification> = Vec::new();
+		for alert_send in alert_sends.take(summarized_count) {
+			match summaries
+				.iter_mut()
+				.find(|summary| summary.alert_send.method == alert_send.method)
+			{
+				Some(summary) => summary.summarized.push(alert_send),
+				None => summaries.push(AlertNotification {
+					alert_send,
+					summarized: Vec::new(),
+				}),
+			}
+		}
+		notifications.extend(summaries);
+	}
+	Ok(notifications)
+}
+
+/// Given the number of unsent alert sends for an organization and the number of notifications it has remaining this hour, return the number of alert sends to send on their own and the number to combine into a summary. The rest wait for a later heartbeat.
+fn rate_limit_alert_send_counts(alert_send_count: usize, remaining_count: usize) -> (usize, usize) {
+	if alert_send_count <= remaining_count {
+		(alert_send_count, 0)
+	} else if remaining_count == 0 {
+		(0, 0)
+	} else {
+		(remaining_count - 1, alert_send_count - remaining_count + 1)
+	}
+}
+
+/// Retrieve the organization that owns the model's repo and its limit of alert notifications per hour, if the model is owned by an organization with a limit.
+async fn get_model_organization_alert_notifications_per_hour(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Option<(Id, i64)>> {
+	let row = sqlx::query(
+		"
+			select
+				organizations.id,
+				organizations.alert_notifications_per_hour
+			from models
+			join repos
+				on repos.id = models.repo_id
+			join organizations
+				on organizations.id = repos.organization_id
+			where
+				models.id = $1
+			and
+				organizations.alert_notifications_per_hour is not null
+		",
+	)
+	.bind(model_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	let row = match row {
+		Some(row) => row,
+		None => return Ok(None),
+	};
+	let organization_id: String = row.get(0);
+	let limit: i64 = row.get(1);
+	Ok(Some((organization_id.parse()?, limit)))
+}
+
+/// Count the alert notifications sent successfully for an organization's repos since `since`. Alert sends that were summarized are not counted, because their summary is.
+async fn get_organization_alert_notification_count(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+	since: i64,
+) -> Result<i64> {
+	let count = sqlx::query(
+		"
+			select
+				count(*)
+			from alert_sends
+			join alerts
+				on alerts.id = alert_sends.alert_id
+			join monitors
+				on monitors.id = alerts.monitor_id
+			join models
+				on models.id = monitors.model_id
+			join repos
+				on repos.id = models.repo_id
+			where
+				repos.organization_id = $1
+			and
+				alert_sends.status = $2
+			and
+				alert_sends.completed_date >= $3
+		",
+	)
+	.bind(organization_id.to_string())
+	.bind(u8::from(AlertSendStatus::Succeeded) as i64)
+	.bind(since)
+	.fetch_one(txn.borrow_mut())
+	.await?
+	.get(0);
+	Ok(count)
+}
+
+async fn handle_alert_notification_with_decay(
+	app_state: &AppState,
+	notification: AlertNotification,
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
 ) -> Result<()> {
+	let AlertNotification {
+		alert_send,
+		summarized,
+	} = notification;
+	for summarized_alert_send in summarized.iter() {
+		set_alert_send_status(
+			app_state,
+			summarized_alert_send.id,
+			AlertSendStatus::Summarized,
+			txn.borrow_mut(),
+		)
+		.await?;
+	}
 	// this should either spawn a task, or be spawned in a task?
 	let mut retry_count = 0u32;
 	while retry_count < ALERT_SENDER_MAXIMUM_RETRY_PERIODS {
 		// attempt the send
-		let status = handle_alert_send(app_state, &alert_send, txn.borrow_mut()).await?;
+		let status = if summarized.is_empty() {
+			handle_alert_send(app_state, &alert_send, txn.borrow_mut()).await?
+		} else {
+			handle_alert_summary_send(app_state, &alert_send, &summarized, txn.borrow_mut()).await?
+		};
 		set_alert_send_status(app_state, alert_send.id, status, txn.borrow_mut()).await?;
-		if status == AlertSendStatus::Succeeded {
+		if status == AlertSendStatus::Succeeded || status == AlertSendStatus::Failed {
 			return Ok(());
 		} else {
 			// If we failed, set back to retrying
@@ -194,6 +588,8 @@ pub enum AlertSendStatus {
 	Retrying,
 	Succeeded,
 	Failed,
+	/// The alert was included in a summary sent with another alert send, because the organization exceeded its limit of alert notifications per hour.
+	Summarized,
 }
 
 impl TryFrom<u8> for AlertSendStatus {
@@ -205,6 +601,7 @@ impl TryFrom<u8> for AlertSendStatus {
 			2 => Ok(AlertSendStatus::Retrying),
 			3 => Ok(AlertSendStatus::Succeeded),
 			4 => Ok(AlertSendStatus::Failed),
+			5 => Ok(AlertSendStatus::Summarized),
 			_ => Err(io::Error::new(
 				io::ErrorKind::InvalidInput,
 				"Unrecognized alert attempt status",
@@ -221,6 +618,7 @@ impl From<AlertSendStatus> for u8 {
 			AlertSendStatus::Retrying => 2,
 			AlertSendStatus::Succeeded => 3,
 			AlertSendStatus::Failed => 4,
+			AlertSendStatus::Summarized => 5,
 		}
 	}
 }
@@ -294,10 +692,11 @@ pub async fn set_alert_send_status(
 	.bind(alert_send_id.to_string())
 	.execute(txn.borrow_mut())
 	.await?;
-	// If the attempt status is either Succeeded or Failed, also set the completed time
-	if matches!(new_status, AlertSendStatus::Succeeded)
-		|| matches!(new_status, AlertSendStatus::Failed)
-	{
+	// If the attempt status is either Succeeded, Failed, or Summarized, also set the completed time
+	if matches!(
+		new_status,
+		AlertSendStatus::Succeeded | AlertSendStatus::Failed | AlertSendStatus::Summarized
+	) {
 		let now = app_state.clock().now_utc().unix_timestamp();
 		sqlx::query(
 			"
@@ -336,6 +735,7 @@ pub async fn get_all_unsent_alert_sends(
 		from alert_sends
 		where
 			status = $1
+		order by initiated_date
 	",
 	)
 	.bind(u8::from(AlertSendStatus::Unsent) as i64)
@@ -402,6 +802,122 @@ pub async fn set_alert_sent(
 	Ok(())
 }
 
+/// The outcome of sending a test alert with one of a monitor's methods. `error` is `None` if the test alert was sent.
+pub struct TestAlertSend {
+	pub method: AlertMethod,
+	pub error: Option<String>,
+}
+
+/// The body of a test alert webhook. `test` is always `true`, so receivers can tell test alerts from real ones.
+#[derive(serde::Serialize)]
+struct TestAlertWebhookPayload<'a> {
+	test: bool,
+	monitor_id: Id,
+	monitor_title: &'a str,
+	message: String,
+}
+
+/// Send a test alert with each of the monitor's methods right away, instead of queueing it for the alert sender. Failed test alerts are reported rather than retried, and nothing is recorded in `alert_sends`.
+async fn send_test_alerts(
+	app_state: &AppState,
+	monitor: &Monitor,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Vec<TestAlertSend>> {
+	let message = format!(
+		"This is a test alert for the monitor \"{}\". It was sent from the monitor's edit page to check that alerts can be delivered. No thresholds were exceeded.",
+		monitor.title
+	);
+	let mut test_alert_sends = Vec::with_capacity(monitor.methods.len());
+	for method in monitor.methods.iter() {
+		let error = if app_state.options.pause_notifications {
+			Some("Notifications are paused.".to_owned())
+		} else {
+			send_test_alert(app_state, monitor, method, &message, txn.borrow_mut()).await?
+		};
+		test_alert_sends.push(TestAlertSend {
+			method: method.clone(),
+			error,
+		});
+	}
+	Ok(test_alert_sends)
+}
+
+/// Send a test alert with one method. This returns a description of the error if it could not be sent.
+async fn send_test_alert(
+	app_state: &AppState,
+	monitor: &Monitor,
+	method: &AlertMethod,
+	message: &str,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Option<String>> {
+	let method = match method {
+		AlertMethod::NotificationChannel(notification_channel) => {
+			match get_alert_send_notification_channel(
+				app_state,
+				notification_channel.notification_channel_id,
+				txn,
+			)
+			.await?
+			{
+				Some(notification_channel) => notification_channel.method,
+				None => {
+					return Ok(Some(
+						"The notification channel was deleted or could not be decrypted."
+							.to_owned(),
+					))
+				}
+			}
+		}
+		method => method.clone(),
+	};
+	match method {
+		AlertMethod::Email(email) => {
+			if app_state.smtp_transport.is_none() {
+				return Ok(Some("No SMTP server is configured.".to_owned()));
+			}
+			let to = match email.email.parse() {
+				Ok(to) => to,
+				Err(_) => return Ok(Some("The email address is invalid.".to_owned())),
+			};
+			let email = lettre::Message::builder()
+				.from("ModelFox <noreply@modelfox.dev>".parse()?)
+				.to(to)
+				.subject(format!("ModelFox Test Alert: {}", monitor.title))
+				.body(message.to_owned())?;
+			Ok(app_state
+				.send_email(email)
+				.await
+				.err()
+				.map(|error| error.to_string()))
+		}
+		AlertMethod::Stdout => {
+			println!("{}", message);
+			Ok(None)
+		}
+		AlertMethod::Webhook(webhook) => {
+			let payload = TestAlertWebhookPayload {
+				test: true,
+				monitor_id: monitor.id,
+				monitor_title: &monitor.title,
+				message: message.to_owned(),
+			};
+			match app_state
+				.http_sender
+				.post_payload(payload, webhook.url)
+				.await
+			{
+				Ok(response) if response.status() == http::StatusCode::OK => Ok(None),
+				Ok(response) => Ok(Some(format!(
+					"The webhook responded with status {}.",
+					response.status()
+				))),
+				Err(error) => Ok(Some(format!("The webhook request failed: {}", error))),
+			}
+		}
+		AlertMethod::NotificationChannel(_) => unreachable!(),
+	}
+}
+
 impl App {
 	/// Send a message to the alert sender and wait for it to reply back indicating it has run.
 	pub async fn send_alerts(&self) -> Result<()> {
@@ -411,6 +927,15 @@ impl App {
 		receiver.await?;
 		Ok(())
 	}
+
+	/// Send a test alert with each of the monitor's methods. See [`TestAlertSend`].
+	pub async fn send_test_alerts(
+		&self,
+		monitor: &Monitor,
+		txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	) -> Result<Vec<TestAlertSend>> {
+		send_test_alerts(&self.state, monitor, txn).await
+	}
 }
 
 #[cfg(test)]
@@ -463,6 +988,11 @@ mod test {
 			},
 			title: None,
 			methods: vec![AlertMethod::Email("ben@modelfox.dev".to_owned().into())],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		};
 		seed_single_monitor(&app, &test_monitor, model_id)
 			.await
@@ -528,6 +1058,11 @@ mod test {
 			methods: vec![AlertMethod::Webhook(
 				AlertMethodWebhook::try_from("http://0.0.0.0:8085/webhook".to_owned()).unwrap(),
 			)],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		};
 		seed_single_monitor(&app, &test_monitor, model_id)
 			.await
@@ -594,6 +1129,11 @@ mod test {
 					AlertMethodWebhook::try_from("http://0.0.0.0:8085/webhook".to_owned()).unwrap(),
 				),
 			],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		};
 		seed_single_monitor(&app, &test_monitor, model_id)
 			.await
@@ -636,4 +1176,62 @@ mod test {
 		assert_eq!(num_failed, 0);
 		app.commit_transaction(txn).await.unwrap();
 	}
+
+	#[test]
+	fn test_rate_limit_alert_send_counts() {
+		assert_eq!(rate_limit_alert_send_counts(3, 5), (3, 0));
+		assert_eq!(rate_limit_alert_send_counts(5, 5), (5, 0));
+		assert_eq!(rate_limit_alert_send_counts(8, 5), (4, 4));
+		assert_eq!(rate_limit_alert_send_counts(8, 1), (0, 8));
+		assert_eq!(rate_limit_alert_send_counts(8, 0), (0, 0));
+	}
+
+	#[tokio::test]
+	#[traced_test]
+	async fn test_send_test_alerts() {
+		let app = init_test_app().await.unwrap();
+		app.set_mocked_http_success_mode(false);
+		let monitor = Monitor {
+			cadence: MonitorCadence::Hourly,
+			id: Id::generate(),
+			methods: vec![
+				AlertMethod::Email("ben@modelfox.dev".to_owned().into()),
+				AlertMethod::Webhook(
+					AlertMethodWebhook::try_from("http://0.0.0.0:8085/webhook".to_owned()).unwrap(),
+				),
+			],
+			model_id: Id::generate(),
+			threshold: MonitorThreshold {
+				metric: AlertMetric::Accuracy,
+				mode: MonitorThresholdMode::Absolute,
+				difference_lower: Some(0.05),
+				difference_upper: Some(0.05),
+			},
+			title: "Accuracy".to_owned(),
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
+			enabled: true,
+		};
+		let mut txn = app.begin_transaction().await.unwrap();
+		let test_alert_sends = app.send_test_alerts(&monitor, &mut txn).await.unwrap();
+		app.commit_transaction(txn).await.unwrap();
+		assert_eq!(test_alert_sends.len(), 2);
+		assert!(test_alert_sends[0].error.is_none());
+		assert_eq!(
+			test_alert_sends[1].error.as_deref(),
+			Some("The webhook responded with status 404 Not Found.")
+		);
+		// Test alerts are not recorded as alert sends.
+		let mut txn = app.begin_transaction().await.unwrap();
+		for status in [AlertSendStatus::Succeeded, AlertSendStatus::Failed] {
+			let count = get_total_sends_with_status(txn.borrow_mut(), status)
+				.await
+				.unwrap();
+			assert_eq!(count, 0);
+		}
+		app.commit_transaction(txn).await.unwrap();
+	}
 }
@@ -0,0 +1,219 @@
+/*!
+This module renders the message templates that monitor owners can set to customize the emails and webhook payloads sent for their alerts. A template is plain text with variables written like `{{metric}}`. Only the variables in [`ALERT_TEMPLATE_VARIABLES`] are allowed, and rendering only substitutes their values, so a template cannot run code or read anything outside of the alert it is rendered for.
+*/
+
+use crate::{
+	alert::Alert,
+	monitor::{Monitor, MonitorThreshold},
+};
+use anyhow::{anyhow, bail, Result};
+use chrono::{TimeZone, Utc};
+use chrono_tz::Tz;
+use modelfox_app_base_path::app_path;
+use modelfox_id::Id;
+use time::OffsetDateTime;
+use url::Url;
+
+/// These are the variables a message template may reference.
+pub const ALERT_TEMPLATE_VARIABLES: &[&str] = &[
+	"model_title",
+	"metric",
+	"value",
+	"training_value",
+	"threshold",
+	"window_start",
+	"window_end",
+	"url",
+];
+
+/// The values substituted for each variable when rendering a message template.
+#[derive(Clone, Debug)]
+pub struct AlertTemplateContext {
+	pub model_title: String,
+	pub metric: String,
+	pub value: String,
+	pub training_value: String,
+	pub threshold: String,
+	pub window_start: String,
+	pub window_end: String,
+	pub url: String,
+}
+
+impl AlertTemplateContext {
+	/// Create the context for `alert`. Times are formatted in `timezone`. The `url` links to the alert in the app, and is empty if the app's url is not configured.
+	pub fn new(
+		alert: &Alert,
+		model_title: &str,
+		repo_id: Id,
+		app_url: Option<&Url>,
+		timezone: Tz,
+	) -> Result<AlertTemplateContext> {
+		let (window_start, window_end) = alert.time_range()?;
+		let url = app_url
+			.map(|app_url| {
+				let mut url = app_url.clone();
+				url.set_path(&app_path(format!(
+					"/repos/{}/models/{}/alerts/{}",
+					repo_id, alert.monitor.model_id, alert.id
+				)));
+				url.to_string()
+			})
+			.unwrap_or_default();
+		let number_formatter = alert.metric().number_formatter();
+		Ok(AlertTemplateContext {
+			model_title: model_title.to_owned(),
+			metric: alert.metric().to_string(),
+			value: number_formatter.format(alert.production_value()),
+			training_value: number_formatter.format(alert.training_value()),
+			threshold: format_threshold(&alert.monitor.threshold),
+			window_start: format_time(window_start, timezone),
+			window_end: format_time(window_end, timezone),
+			url,
+		})
+	}
+
+	/// Create a context with example values for previewing a template for `monitor`.
+	pub fn example(monitor: &Monitor, model_title: &str) -> AlertTemplateContext {
+		AlertTemplateContext {
+			model_title: model_title.to_owned(),
+			metric: monitor.threshold.metric.to_string(),
+			value: "0.75".to_owned(),
+			training_value: "0.9".to_owned(),
+			threshold: format_threshold(&monitor.threshold),
+			window_start: "2022-01-01 00:00".to_owned(),
+			window_end: "2022-01-02 00:00".to_owned(),
+			url: "https://app.modelfox.dev/".to_owned(),
+		}
+	}
+
+	fn get(&self, variable: &str) -> Option<&str> {
+		let value = match variable {
+			"model_title" => &self.model_title,
+			"metric" => &self.metric,
+			"value" => &self.value,
+			"training_value" => &self.training_value,
+			"threshold" => &self.threshold,
+			"window_start" => &self.window_start,
+			"window_end" => &self.window_end,
+			"url" => &self.url,
+			_ => return None,
+		};
+		Some(value.as_str())
+	}
+}
+
+enum Segment<'a> {
+	Text(&'a str),
+	Variable(&'a str),
+}
+
+fn parse(template: &str) -> Result<Vec<Segment>> {
+	let mut segments = Vec::new();
+	let mut rest = template;
+	while let Some(start) = rest.find("{{") {
+		if start > 0 {
+			segments.push(Segment::Text(&rest[..start]));
+		}
+		let after_start = &rest[start + 2..];
+		let end = after_start.find("}}").ok_or_else(|| {
+			anyhow!("The message template has a \"{{{{\" without a matching \"}}}}\".")
+		})?;
+		let variable = after_start[..end].trim();
+		if !ALERT_TEMPLATE_VARIABLES.contains(&variable) {
+			bail!(
+				"The message template references the unknown variable \"{}\". The available variables are {}.",
+				variable,
+				ALERT_TEMPLATE_VARIABLES.join(", "),
+			);
+		}
+		segments.push(Segment::Variable(variable));
+		rest = &after_start[end + 2..];
+	}
+	if !rest.is_empty() {
+		segments.push(Segment::Text(rest));
+	}
+	Ok(segments)
+}
+
+/// Check that `template` only references known variables and has no unclosed braces.
+pub fn validate_alert_template(template: &str) -> Result<()> {
+	parse(template)?;
+	Ok(())
+}
+
+/// Render `template`, substituting the value of each variable from `context`.
+pub fn render_alert_template(template: &str, context: &AlertTemplateContext) -> Result<String> {
+	let mut output = String::with_capacity(template.len());
+	for segment in parse(template)? {
+		match segment {
+			Segment::Text(text) => output.push_str(text),
+			Segment::Variable(variable) => output.push_str(context.get(variable).unwrap()),
+		}
+	}
+	Ok(output)
+}
+
+fn format_threshold(threshold: &MonitorThreshold) -> String {
+	let format_bound = |bound: Option<f32>| {
+		bound
+			.map(|bound| bound.to_string())
+			.unwrap_or_else(|| "none".to_owned())
+	};
+	format!(
+		"lower {}, upper {} ({})",
+		format_bound(threshold.difference_lower),
+		format_bound(threshold.difference_upper),
+		threshold.mode,
+	)
+}
+
+fn format_time(time: OffsetDateTime, timezone: Tz) -> String {
+	Utc.timestamp(time.unix_timestamp(), 0)
+		.with_timezone(&timezone)
+		.format("%Y-%m-%d %H:%M")
+		.to_string()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::monitor::Monitor;
+
+	fn context() -> AlertTemplateContext {
+		let monitor = Monitor {
+			cadence: crate::monitor::MonitorCadence::Daily,
+			id: Id::generate(),
+			methods: Vec::new(),
+			model_id: Id::generate(),
+			threshold: MonitorThreshold::default(),
+			title: "Accuracy".to_owned(),
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
+			enabled: true,
+		};
+		AlertTemplateContext::example(&monitor, "Heart Disease")
+	}
+
+	#[test]
+	fn test_render_alert_template() {
+		let output = render_alert_template(
+			"{{model_title}}: {{ metric }} is {{value}}, see {{url}}",
+			&context(),
+		)
+		.unwrap();
+		assert_eq!(
+			output,
+			"Heart Disease: Accuracy is 0.75, see https://app.modelfox.dev/"
+		);
+	}
+
+	#[test]
+	fn test_validate_alert_template() {
+		assert!(validate_alert_template("No variables { at all }").is_ok());
+		assert!(validate_alert_template("{{secret_key}}").is_err());
+		assert!(validate_alert_template("{{metric").is_err());
+	}
+}
@@ -0,0 +1,575 @@
+/*!
+This module implements batch predict jobs, which score an uploaded CSV or NDJSON file with a model in the background. Creating a job stores the file and queues the job. The batch predictor runs queued jobs one at a time, stores each job's output file, and records whether it succeeded.
+*/
+
+use crate::{
+	clock::Clock,
+	cluster::{notify_storage_invalidation, BackgroundTask, Leadership},
+	heuristics::{
+		BATCH_PREDICTOR_HEARTBEAT_DURATION_PRODUCTION, BATCH_PREDICTOR_HEARTBEAT_DURATION_TESTING,
+		BATCH_PREDICT_CHUNK_SIZE,
+	},
+	model::{get_model_bytes, get_model_class_thresholds, get_model_decision_threshold},
+	storage::{BytesOrFilePath, Storage, StorageEntity},
+	App, AppState,
+};
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use futures::{select, FutureExt};
+use itertools::Itertools;
+use modelfox_core::{
+	flatten::{flatten_predict_input, FlattenOptions, NestedPredictInputValue},
+	predict::{try_predict, Model, PredictInput, PredictInputValue, PredictOptions, PredictOutput},
+};
+use modelfox_id::Id;
+use modelfox_zip::zip;
+use sqlx::prelude::*;
+use std::{borrow::BorrowMut, collections::BTreeMap, path::Path, sync::Arc};
+use tokio::sync::{mpsc, oneshot};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchPredictFormat {
+	Csv,
+	Ndjson,
+}
+
+impl BatchPredictFormat {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			BatchPredictFormat::Csv => "csv",
+			BatchPredictFormat::Ndjson => "ndjson",
+		}
+	}
+
+	pub fn parse(value: &str) -> Option<BatchPredictFormat> {
+		match value {
+			"csv" => Some(BatchPredictFormat::Csv),
+			"ndjson" => Some(BatchPredictFormat::Ndjson),
+			_ => None,
+		}
+	}
+
+	/// This is the content type of the format's files. The output of a job has the same format as its input.
+	pub fn content_type(&self) -> &'static str {
+		match self {
+			BatchPredictFormat::Csv => "text/csv",
+			BatchPredictFormat::Ndjson => "application/x-ndjson",
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchPredictJobStatus {
+	Queued,
+	Running,
+	Succeeded,
+	Failed,
+}
+
+impl BatchPredictJobStatus {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			BatchPredictJobStatus::Queued => "queued",
+			BatchPredictJobStatus::Running => "running",
+			BatchPredictJobStatus::Succeeded => "succeeded",
+			BatchPredictJobStatus::Failed => "failed",
+		}
+	}
+
+	pub fn parse(value: &str) -> Option<BatchPredictJobStatus> {
+		match value {
+			"queued" => Some(BatchPredictJobStatus::Queued),
+			"running" => Some(BatchPredictJobStatus::Running),
+			"succeeded" => Some(BatchPredictJobStatus::Succeeded),
+			"failed" => Some(BatchPredictJobStatus::Failed),
+			_ => None,
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct BatchPredictJob {
+	pub id: Id,
+	pub model_id: Id,
+	pub status: BatchPredictJobStatus,
+	pub format: BatchPredictFormat,
+	pub created_at: i64,
+	pub started_at: Option<i64>,
+	pub finished_at: Option<i64>,
+	/// This is the number of rows in the input. It is set when the job succeeds.
+	pub row_count: Option<i64>,
+	/// This is the reason the job failed.
+	pub error: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum BatchPredictorMessage {
+	Run(oneshot::Sender<()>),
+	/// This is sent when a job is created, so it starts without waiting for the next heartbeat.
+	JobCreated,
+}
+
+/// Store the input file and queue a job to score it with the model.
+pub async fn create_batch_predict_job(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	storage: &Storage,
+	clock: &Clock,
+	model_id: Id,
+	format: BatchPredictFormat,
+	input: &[u8],
+) -> Result<Id> {
+	let id = insert_batch_predict_job(txn, clock, model_id, format).await?;
+	storage
+		.set(StorageEntity::BatchPredictInput, id, input)
+		.await?;
+	Ok(id)
+}
+
+/// Queue a job to score the file at `path`, such as a request body that was streamed to disk. The file is copied to storage without reading it into memory.
+pub async fn create_batch_predict_job_from_path(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	storage: &Storage,
+	clock: &Clock,
+	model_id: Id,
+	format: BatchPredictFormat,
+	path: &Path,
+) -> Result<Id> {
+	let id = insert_batch_predict_job(txn, clock, model_id, format).await?;
+	storage
+		.set_from_path(StorageEntity::BatchPredictInput, id, path)
+		.await?;
+	Ok(id)
+}
+
+async fn insert_batch_predict_job(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	clock: &Clock,
+	model_id: Id,
+	format: BatchPredictFormat,
+) -> Result<Id> {
+	let id = Id::generate();
+	sqlx::query(
+		"
+			insert into batch_predict_jobs (
+				id, model_id, status, format, created_at
+			) values (
+				$1, $2, $3, $4, $5
+			)
+		",
+	)
+	.bind(&id.to_string())
+	.bind(&model_id.to_string())
+	.bind(BatchPredictJobStatus::Queued.as_str())
+	.bind(format.as_str())
+	.bind(clock.now_utc().unix_timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(id)
+}
+
+/// Get the batch predict job with the given id, if it belongs to the model.
+pub async fn get_batch_predict_job(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	job_id: Id,
+) -> Result<Option<BatchPredictJob>> {
+	let row = sqlx::query(
+		"
+			select
+				status,
+				format,
+				created_at,
+				started_at,
+				finished_at,
+				row_count,
+				error
+			from batch_predict_jobs
+			where
+				id = $1
+			and
+				model_id = $2
+		",
+	)
+	.bind(&job_id.to_string())
+	.bind(&model_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	let row = match row {
+		Some(row) => row,
+		None => return Ok(None),
+	};
+	let status: String = row.get(0);
+	let format: String = row.get(1);
+	Ok(Some(BatchPredictJob {
+		id: job_id,
+		model_id,
+		status: BatchPredictJobStatus::parse(&status)
+			.ok_or_else(|| anyhow!("invalid batch predict job status"))?,
+		format: BatchPredictFormat::parse(&format)
+			.ok_or_else(|| anyhow!("invalid batch predict job format"))?,
+		created_at: row.get(2),
+		started_at: row.get(3),
+		finished_at: row.get(4),
+		row_count: row.get(5),
+		error: row.get(6),
+	}))
+}
+
+/// Get the output file of a job that succeeded.
+pub async fn get_batch_predict_output(storage: &Storage, job_id: Id) -> Result<Bytes> {
+	read_storage(storage, StorageEntity::BatchPredictOutput, job_id).await
+}
+
+/// Delete the files of a model's batch predict jobs. The jobs themselves are deleted with the model.
+pub async fn delete_batch_predict_files(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	storage: &Storage,
+	model_id: Id,
+) -> Result<()> {
+	let rows = sqlx::query(
+		"
+			select
+				id,
+				status
+			from batch_predict_jobs
+			where model_id = $1
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	for row in rows {
+		let job_id: Id = row.get::<String, _>(0).parse()?;
+		let status: String = row.get(1);
+		// Queued and running jobs still have their input, and jobs that succeeded have their output. Jobs that failed have neither.
+		let entity = match BatchPredictJobStatus::parse(&status) {
+			Some(BatchPredictJobStatus::Queued) | Some(BatchPredictJobStatus::Running) => {
+				StorageEntity::BatchPredictInput
+			}
+			Some(BatchPredictJobStatus::Succeeded) => StorageEntity::BatchPredictOutput,
+			Some(BatchPredictJobStatus::Failed) | None => continue,
+		};
+		storage.remove(entity, job_id).await?;
+		notify_storage_invalidation(txn, entity, job_id).await?;
+	}
+	Ok(())
+}
+
+impl App {
+	/// Start the jobs that were just created without waiting for the next heartbeat of the batch predictor.
+	pub fn notify_batch_predict_job_created(&self) -> Result<()> {
+		self.batch_predictor_sender
+			.send(BatchPredictorMessage::JobCreated)?;
+		Ok(())
+	}
+}
+
+/// Run queued batch predict jobs on every heartbeat and whenever a job is created.
+#[tracing::instrument(level = "info", skip_all)]
+pub async fn batch_predictor(
+	app_state: Arc<AppState>,
+	mut receiver: mpsc::UnboundedReceiver<BatchPredictorMessage>,
+) -> Result<()> {
+	let period = if cfg!(debug_assertions) {
+		BATCH_PREDICTOR_HEARTBEAT_DURATION_TESTING
+	} else {
+		BATCH_PREDICTOR_HEARTBEAT_DURATION_PRODUCTION
+	};
+	let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+	interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+	loop {
+		enum Event {
+			Tick,
+			Message(BatchPredictorMessage),
+		}
+		let event = select! {
+			_ = interval.tick().fuse() => Event::Tick,
+			message = receiver.recv().fuse() => match message {
+				None => break,
+				Some(message) => Event::Message(message),
+			}
+		};
+		if let Some(leadership) =
+			Leadership::acquire(&app_state, BackgroundTask::BatchPredictor).await?
+		{
+			tracing::info!("Begin batch_predictor heartbeat");
+			run_batch_predict_jobs(&app_state).await?;
+			leadership.release().await?;
+			tracing::info!("End batch_predictor heartbeat");
+		}
+		if let Event::Message(BatchPredictorMessage::Run(sender)) = event {
+			sender.send(()).unwrap();
+		}
+	}
+	Ok(())
+}
+
+/// Run the queued jobs in the order they were created until none are left.
+pub async fn run_batch_predict_jobs(app_state: &AppState) -> Result<()> {
+	// Only the leader runs jobs, so a job that is still running at the start of a heartbeat was interrupted by an instance that stopped in the middle of it. Run it again.
+	let mut txn = app_state.begin_transaction().await?;
+	sqlx::query(
+		"
+			update batch_predict_jobs
+			set status = $1, started_at = null
+			where status = $2
+		",
+	)
+	.bind(BatchPredictJobStatus::Queued.as_str())
+	.bind(BatchPredictJobStatus::Running.as_str())
+	.execute(txn.borrow_mut())
+	.await?;
+	app_state.commit_transaction(txn).await?;
+	while let Some((job_id, model_id, format)) = start_next_batch_predict_job(app_state).await? {
+		let result = run_batch_predict_job(app_state, job_id, model_id, format).await;
+		let (status, row_count, error) = match result {
+			Ok(row_count) => (BatchPredictJobStatus::Succeeded, Some(row_count), None),
+			Err(error) => (BatchPredictJobStatus::Failed, None, Some(error.to_string())),
+		};
+		let mut txn = app_state.begin_transaction().await?;
+		sqlx::query(
+			"
+				update batch_predict_jobs
+				set status = $1, finished_at = $2, row_count = $3, error = $4
+				where id = $5
+			",
+		)
+		.bind(status.as_str())
+		.bind(app_state.clock.now_utc().unix_timestamp())
+		.bind(row_count)
+		.bind(error)
+		.bind(&job_id.to_string())
+		.execute(txn.borrow_mut())
+		.await?;
+		app_state
+			.storage
+			.remove(StorageEntity::BatchPredictInput, job_id)
+			.await?;
+		notify_storage_invalidation(&mut txn, StorageEntity::BatchPredictInput, job_id).await?;
+		app_state.commit_transaction(txn).await?;
+		tracing::info!(%job_id, status = status.as_str(), "finished batch predict job");
+	}
+	Ok(())
+}
+
+/// Mark the oldest queued job as running and return it.
+async fn start_next_batch_predict_job(
+	app_state: &AppState,
+) -> Result<Option<(Id, Id, BatchPredictFormat)>> {
+	let mut txn = app_state.begin_transaction().await?;
+	let row = sqlx::query(
+		"
+			select
+				id,
+				model_id,
+				format
+			from batch_predict_jobs
+			where status = $1
+			order by created_at
+			limit 1
+		",
+	)
+	.bind(BatchPredictJobStatus::Queued.as_str())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	let row = match row {
+		Some(row) => row,
+		None => {
+			app_state.commit_transaction(txn).await?;
+			return Ok(None);
+		}
+	};
+	let job_id: Id = row.get::<String, _>(0).parse()?;
+	let model_id: Id = row.get::<String, _>(1).parse()?;
+	let format: String = row.get(2);
+	let format = BatchPredictFormat::parse(&format)
+		.ok_or_else(|| anyhow!("invalid batch predict job format"))?;
+	sqlx::query(
+		"
+			update batch_predict_jobs
+			set status = $1, started_at = $2
+			where id = $3
+		",
+	)
+	.bind(BatchPredictJobStatus::Running.as_str())
+	.bind(app_state.clock.now_utc().unix_timestamp())
+	.bind(&job_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	app_state.commit_transaction(txn).await?;
+	Ok(Some((job_id, model_id, format)))
+}
+
+/// Score the job's input and store the output, returning the number of rows. The predictions use the thresholds saved on the model's tuning page.
+async fn run_batch_predict_job(
+	app_state: &AppState,
+	job_id: Id,
+	model_id: Id,
+	format: BatchPredictFormat,
+) -> Result<i64> {
+	let mut txn = app_state.begin_transaction().await?;
+	let decision_threshold = get_model_decision_threshold(&mut txn, model_id).await?;
+	let class_thresholds = get_model_class_thresholds(&mut txn, model_id).await?;
+	app_state.commit_transaction(txn).await?;
+	let mut options = PredictOptions {
+		compute_feature_contributions: false,
+		class_thresholds,
+		..Default::default()
+	};
+	if let Some(decision_threshold) = decision_threshold {
+		options.threshold = decision_threshold;
+	}
+	let model_bytes = get_model_bytes(&app_state.storage, model_id).await?;
+	let input = read_storage(&app_state.storage, StorageEntity::BatchPredictInput, job_id).await?;
+	// Scoring a large file takes a while, so do it on a blocking thread instead of holding up the other tasks on this one.
+	let (output, row_count) = tokio::task::spawn_blocking(move || -> Result<(Vec<u8>, usize)> {
+		let model = modelfox_model::from_bytes(&model_bytes)?;
+		let model = Model::from(model);
+		batch_predict(&model, format, &input, &options)
+	})
+	.await??;
+	app_state
+		.storage
+		.set(StorageEntity::BatchPredictOutput, job_id, &output)
+		.await?;
+	Ok(row_count.try_into().unwrap())
+}
+
+/// Score each row of `input` and return the output file along with the number of rows. The output has one row for each input row, in the same order, with empty values for rows that were dropped because of an invalid value.
+pub fn batch_predict(
+	model: &Model,
+	format: BatchPredictFormat,
+	input: &[u8],
+	options: &PredictOptions,
+) -> Result<(Vec<u8>, usize)> {
+	match format {
+		BatchPredictFormat::Csv => batch_predict_csv(model, input, options),
+		BatchPredictFormat::Ndjson => batch_predict_ndjson(model, input, options),
+	}
+}
+
+fn batch_predict_csv(
+	model: &Model,
+	input: &[u8],
+	options: &PredictOptions,
+) -> Result<(Vec<u8>, usize)> {
+	let mut reader = csv::Reader::from_reader(input);
+	let mut writer = csv::Writer::from_writer(Vec::new());
+	writer.write_record(&["prediction", "probability"])?;
+	let header = reader.headers()?.to_owned();
+	let mut row_count = 0;
+	for records in &reader.records().chunks(BATCH_PREDICT_CHUNK_SIZE) {
+		let input = records
+			.into_iter()
+			.map(|record| -> Result<PredictInput> {
+				let record = record?;
+				let input = zip!(header.iter(), record.iter())
+					.map(|(column_name, value)| {
+						(
+							column_name.to_owned(),
+							PredictInputValue::String(value.to_owned()),
+						)
+					})
+					.collect();
+				Ok(PredictInput(input))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		row_count += input.len();
+		for output in try_predict(model, &input, options)? {
+			let (prediction, probability) = match output {
+				None => (String::new(), String::new()),
+				Some(PredictOutput::Regression(output)) => {
+					(output.value.to_string(), String::new())
+				}
+				Some(PredictOutput::BinaryClassification(output)) => {
+					(output.class_name, output.probability.to_string())
+				}
+				Some(PredictOutput::MulticlassClassification(output)) => {
+					(output.class_name, output.probability.to_string())
+				}
+			};
+			writer.write_record(&[prediction, probability])?;
+		}
+	}
+	Ok((writer.into_inner()?, row_count))
+}
+
+/// Each line of the input is a json object, which may have nested objects. Each line of the output is the json predict output for the line with the same number, or `null` if the row was dropped. Blank lines are skipped.
+fn batch_predict_ndjson(
+	model: &Model,
+	input: &[u8],
+	options: &PredictOptions,
+) -> Result<(Vec<u8>, usize)> {
+	let input = std::str::from_utf8(input).map_err(|_| anyhow!("The file is not valid UTF-8."))?;
+	let flatten_options = FlattenOptions::default();
+	let lines = input
+		.lines()
+		.enumerate()
+		.filter(|(_, line)| !line.trim().is_empty());
+	let mut output = Vec::new();
+	let mut row_count = 0;
+	for chunk in &lines.chunks(BATCH_PREDICT_CHUNK_SIZE) {
+		let input = chunk
+			.map(|(line_index, line)| -> Result<PredictInput> {
+				let input: BTreeMap<String, NestedPredictInputValue> =
+					serde_json::from_str(line)
+						.map_err(|error| anyhow!("line {}: {}", line_index + 1, error))?;
+				Ok(flatten_predict_input(input, &flatten_options))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		row_count += input.len();
+		for predict_output in try_predict(model, &input, options)? {
+			serde_json::to_writer(&mut output, &predict_output)?;
+			output.push(b'\n');
+		}
+	}
+	Ok((output, row_count))
+}
+
+async fn read_storage(storage: &Storage, entity: StorageEntity, id: Id) -> Result<Bytes> {
+	let data = match storage.get(entity, id).await? {
+		BytesOrFilePath::Bytes(bytes) => bytes,
+		BytesOrFilePath::Path(path) => tokio::fs::read(path).await?.into(),
+	};
+	Ok(data)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_common::*;
+
+	#[tokio::test]
+	async fn test_run_batch_predict_jobs() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		let input = "age,gender,chest_pain\n63,male,typical angina\n,,\n";
+		let mut txn = app.begin_transaction().await.unwrap();
+		let job_id = create_batch_predict_job(
+			&mut txn,
+			app.storage(),
+			app.clock(),
+			model_id,
+			BatchPredictFormat::Csv,
+			input.as_bytes(),
+		)
+		.await
+		.unwrap();
+		app.commit_transaction(txn).await.unwrap();
+		run_batch_predict_jobs(&app.state).await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let job = get_batch_predict_job(&mut txn, model_id, job_id)
+			.await
+			.unwrap()
+			.unwrap();
+		app.commit_transaction(txn).await.unwrap();
+		assert_eq!(job.status, BatchPredictJobStatus::Succeeded);
+		assert_eq!(job.row_count, Some(2));
+		let output = get_batch_predict_output(app.storage(), job_id)
+			.await
+			.unwrap();
+		let output = std::str::from_utf8(&output).unwrap();
+		assert_eq!(output.lines().count(), 3);
+		assert_eq!(output.lines().next(), Some("prediction,probability"));
+	}
+}
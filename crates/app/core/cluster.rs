@@ -0,0 +1,225 @@
+/*!
+This module contains what lets more than one instance of the app run against the same database. Every instance runs the background tasks, so each heartbeat first tries to take a PostgreSQL advisory lock for its task and skips the heartbeat if another instance holds it. Instances cache files from S3 storage on local disk, so deleting a model or custom metric notifies the other instances over PostgreSQL's LISTEN/NOTIFY to evict their cached copies.
+
+With SQLite, there can only be a single instance, so every heartbeat runs and no notifications are sent.
+*/
+
+use crate::{
+	heuristics::{
+		STORAGE_INVALIDATION_LISTENER_MAX_BACKOFF, STORAGE_INVALIDATION_LISTENER_MIN_BACKOFF,
+	},
+	storage::{Storage, StorageEntity},
+	AppState,
+};
+use anyhow::{anyhow, Result};
+use modelfox_id::Id;
+use sqlx::{any::AnyKind, prelude::*};
+use std::{
+	borrow::BorrowMut,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+/// This is the channel that storage invalidations are sent on.
+const STORAGE_INVALIDATION_CHANNEL: &str = "modelfox_storage_invalidation";
+
+#[derive(Clone, Copy, Debug)]
+pub enum BackgroundTask {
+	AlertSender,
+	BatchPredictor,
+	KinesisIngest,
+	MonitorChecker,
+	ProductionRollups,
+	TrashPurger,
+}
+
+impl BackgroundTask {
+	/// Retrieve the key of the advisory lock that the instance running this task holds. The keys are arbitrary, but must not change between versions so that instances running different versions during a deploy agree on them.
+	fn lock_key(&self) -> i64 {
+		match self {
+			BackgroundTask::AlertSender => 0x6d66_0001,
+			BackgroundTask::MonitorChecker => 0x6d66_0002,
+			BackgroundTask::ProductionRollups => 0x6d66_0003,
+			BackgroundTask::TrashPurger => 0x6d66_0004,
+			BackgroundTask::KinesisIngest => 0x6d66_0005,
+			BackgroundTask::BatchPredictor => 0x6d66_0006,
+		}
+	}
+}
+
+/// A `Leadership` is held by the one instance running a heartbeat of a background task. Call [`Leadership::release`] when the heartbeat finishes.
+pub struct Leadership {
+	/// This is the connection that holds the advisory lock. It is `None` for SQLite.
+	connection: Option<sqlx::pool::PoolConnection<sqlx::Any>>,
+	task: BackgroundTask,
+}
+
+impl Leadership {
+	/// Try to become the instance that runs the next heartbeat of `task`. This returns `None` if another instance is running it.
+	pub async fn acquire(app_state: &AppState, task: BackgroundTask) -> Result<Option<Leadership>> {
+		let mut connection = app_state.database_pool.acquire().await?;
+		if connection.kind() != AnyKind::Postgres {
+			return Ok(Some(Leadership {
+				connection: None,
+				task,
+			}));
+		}
+		let acquired: bool = sqlx::query(
+			"
+				select pg_try_advisory_lock($1)
+			",
+		)
+		.bind(task.lock_key())
+		.fetch_one(&mut *connection)
+		.await?
+		.get(0);
+		if !acquired {
+			tracing::info!(?task, "another instance is running this task");
+			return Ok(None);
+		}
+		Ok(Some(Leadership {
+			connection: Some(connection),
+			task,
+		}))
+	}
+
+	pub async fn release(mut self) -> Result<()> {
+		let mut connection = match self.connection.take() {
+			Some(connection) => connection,
+			None => return Ok(()),
+		};
+		let result = sqlx::query(
+			"
+				select pg_advisory_unlock($1)
+			",
+		)
+		.bind(self.task.lock_key())
+		.execute(&mut *connection)
+		.await;
+		if result.is_err() {
+			// Close the connection instead of returning it to the pool. Closing the session releases the lock.
+			connection.detach();
+		}
+		result?;
+		Ok(())
+	}
+}
+
+impl Drop for Leadership {
+	fn drop(&mut self) {
+		// If the heartbeat failed before releasing the lock, close the connection so the lock is not held by an idle connection in the pool.
+		if let Some(connection) = self.connection.take() {
+			connection.detach();
+		}
+	}
+}
+
+/// Tell every instance to evict its cached copy of the stored item. The notification is delivered when `txn` commits, so it is not sent if the deletion is rolled back.
+pub async fn notify_storage_invalidation(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	entity: StorageEntity,
+	id: Id,
+) -> Result<()> {
+	if txn.kind() != AnyKind::Postgres {
+		return Ok(());
+	}
+	sqlx::query(
+		"
+			select pg_notify($1, $2)
+		",
+	)
+	.bind(STORAGE_INVALIDATION_CHANNEL)
+	.bind(format!("{}/{}", entity.dir_name(), id))
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Listen for storage invalidations for as long as the app runs. If the connection to the database is lost, the error is logged and the listener reconnects, waiting twice as long after each consecutive failure. Invalidations sent while it is disconnected are missed, so this instance may serve stale cached files until the cache is cleared.
+#[tracing::instrument(level = "info", skip_all)]
+pub async fn storage_invalidation_listener(app_state: Arc<AppState>) {
+	if app_state.options.database.url.scheme() != "postgres" {
+		return;
+	}
+	let mut backoff = STORAGE_INVALIDATION_LISTENER_MIN_BACKOFF;
+	loop {
+		let started_at = Instant::now();
+		if let Err(error) = listen_for_storage_invalidations(&app_state).await {
+			tracing::error!(%error, ?backoff, "storage invalidation listener disconnected");
+		}
+		// A listener that stayed connected for a while failed on its own, not because the database is still unavailable, so reconnect quickly.
+		if started_at.elapsed() > STORAGE_INVALIDATION_LISTENER_MAX_BACKOFF {
+			backoff = STORAGE_INVALIDATION_LISTENER_MIN_BACKOFF;
+		}
+		tokio::time::sleep(backoff).await;
+		backoff = next_backoff(backoff);
+	}
+}
+
+async fn listen_for_storage_invalidations(app_state: &AppState) -> Result<()> {
+	let database_url = &app_state.options.database.url;
+	let mut listener = sqlx::postgres::PgListener::connect(database_url.as_str()).await?;
+	listener.listen(STORAGE_INVALIDATION_CHANNEL).await?;
+	tracing::info!("storage invalidation listener connected");
+	loop {
+		let notification = listener.recv().await?;
+		let (entity, id) = match parse_storage_invalidation(notification.payload()) {
+			Ok(item) => item,
+			Err(error) => {
+				tracing::error!(%error, "invalid storage invalidation");
+				continue;
+			}
+		};
+		if let Err(error) = evict_cached(&app_state.storage, entity, id).await {
+			tracing::error!(%error, %id, "failed to evict cached file");
+		}
+	}
+}
+
+fn next_backoff(backoff: Duration) -> Duration {
+	(backoff * 2).min(STORAGE_INVALIDATION_LISTENER_MAX_BACKOFF)
+}
+
+async fn evict_cached(storage: &Storage, entity: StorageEntity, id: Id) -> Result<()> {
+	match storage {
+		Storage::S3(storage) => storage.evict_cached(entity, id).await,
+		// Local and in memory storage have no cache. Local storage is not shared between instances, see `Options::stateless`.
+		Storage::InMemory(_) | Storage::Local(_) => Ok(()),
+	}
+}
+
+fn parse_storage_invalidation(payload: &str) -> Result<(StorageEntity, Id)> {
+	let (dir_name, id) = payload
+		.split_once('/')
+		.ok_or_else(|| anyhow!("expected a payload of the form <entity>/<id>"))?;
+	let entity = StorageEntity::from_dir_name(dir_name)
+		.ok_or_else(|| anyhow!("unknown storage entity \"{}\"", dir_name))?;
+	let id = id.parse()?;
+	Ok((entity, id))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_parse_storage_invalidation() {
+		let id = Id::generate();
+		let (entity, parsed_id) = parse_storage_invalidation(&format!("models/{}", id)).unwrap();
+		assert!(matches!(entity, StorageEntity::Model));
+		assert_eq!(parsed_id, id);
+		assert!(parse_storage_invalidation("models").is_err());
+		assert!(parse_storage_invalidation(&format!("datasets/{}", id)).is_err());
+	}
+
+	#[test]
+	fn test_next_backoff() {
+		let mut backoff = STORAGE_INVALIDATION_LISTENER_MIN_BACKOFF;
+		let mut backoffs = Vec::new();
+		for _ in 0..8 {
+			backoffs.push(backoff.as_secs());
+			backoff = next_backoff(backoff);
+		}
+		assert_eq!(backoffs, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+	}
+}
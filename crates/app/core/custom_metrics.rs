@@ -0,0 +1,316 @@
+//! Custom metrics let users upload a small WebAssembly module that computes a metric over the examples recorded for a model, so monitors can alert on metrics the app does not compute itself.
+//!
+//! A module must export its `memory`, an `alloc(len: i32) -> i32` function that returns a pointer to `len` writable bytes, and a `compute(ptr: i32, len: i32) -> f32` function. To run a metric, the host writes a JSON array of `{"prediction": ..., "true_value": ..., "input": ...}` objects to the bytes returned by `alloc` and calls `compute` with their location and length. Modules cannot import anything, so they have no access to the host, and each run is limited in fuel and memory.
+
+use crate::{
+	clock::Clock,
+	cluster::notify_storage_invalidation,
+	storage::{Storage, StorageEntity},
+};
+use anyhow::{anyhow, bail, Result};
+use bytes::Bytes;
+use modelfox_id::Id;
+use num::ToPrimitive;
+use sqlx::prelude::*;
+use std::borrow::BorrowMut;
+
+/// Uploaded modules larger than this are rejected.
+pub const CUSTOM_METRIC_MAX_MODULE_SIZE: usize = 10 * 1024 * 1024;
+/// This is the most memory a module can grow to while computing a metric.
+const CUSTOM_METRIC_MAX_MEMORY_SIZE: usize = 256 * 1024 * 1024;
+/// This bounds the number of instructions a module can execute while computing a metric, so a module that loops forever cannot stall the monitor checker.
+const CUSTOM_METRIC_FUEL: u64 = 10_000_000_000;
+
+#[derive(Clone, Debug)]
+pub struct CustomMetric {
+	pub id: Id,
+	pub repo_id: Id,
+	pub name: String,
+	pub created_at: i64,
+}
+
+/// One example a custom metric is computed over.
+#[derive(Debug, serde::Serialize)]
+pub struct CustomMetricExample {
+	pub prediction: serde_json::Value,
+	pub true_value: serde_json::Value,
+	pub input: serde_json::Value,
+}
+
+pub async fn get_custom_metrics(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+) -> Result<Vec<CustomMetric>> {
+	let rows = sqlx::query(
+		"
+			select
+				id,
+				name,
+				created_at
+			from custom_metrics
+			where repo_id = $1
+			order by created_at
+		",
+	)
+	.bind(&repo_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let id: String = row.get(0);
+			Ok(CustomMetric {
+				id: id.parse()?,
+				repo_id,
+				name: row.get(1),
+				created_at: row.get(2),
+			})
+		})
+		.collect()
+}
+
+pub async fn get_custom_metric(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	custom_metric_id: Id,
+) -> Result<Option<CustomMetric>> {
+	let row = sqlx::query(
+		"
+			select
+				repo_id,
+				name,
+				created_at
+			from custom_metrics
+			where id = $1
+		",
+	)
+	.bind(&custom_metric_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	row.map(|row| {
+		let repo_id: String = row.get(0);
+		Ok(CustomMetric {
+			id: custom_metric_id,
+			repo_id: repo_id.parse()?,
+			name: row.get(1),
+			created_at: row.get(2),
+		})
+	})
+	.transpose()
+}
+
+/// Validate the module and store it. The module is checked to export the functions described in the module documentation, but it is not run.
+pub async fn create_custom_metric(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	storage: &Storage,
+	clock: &Clock,
+	repo_id: Id,
+	name: &str,
+	module: &[u8],
+) -> Result<Id> {
+	if name.trim().is_empty() {
+		bail!("A name is required.");
+	}
+	if module.len() > CUSTOM_METRIC_MAX_MODULE_SIZE {
+		bail!(
+			"The module must be smaller than {} MB.",
+			CUSTOM_METRIC_MAX_MODULE_SIZE / 1024 / 1024
+		);
+	}
+	validate_custom_metric_module(module)?;
+	let id = Id::generate();
+	sqlx::query(
+		"
+			insert into custom_metrics (
+				id, repo_id, name, created_at
+			) values (
+				$1, $2, $3, $4
+			)
+		",
+	)
+	.bind(&id.to_string())
+	.bind(&repo_id.to_string())
+	.bind(name.trim())
+	.bind(clock.now_utc().unix_timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	storage.set(StorageEntity::CustomMetric, id, module).await?;
+	Ok(id)
+}
+
+/// Delete a custom metric. Custom metrics used by a monitor cannot be deleted.
+pub async fn delete_custom_metric(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	storage: &Storage,
+	repo_id: Id,
+	custom_metric_id: Id,
+) -> Result<()> {
+	// Ids are unique strings, so a monitor uses the custom metric exactly when its serialized data contains the id.
+	let in_use: bool = sqlx::query(
+		"
+			select
+				count(*) > 0
+			from monitors
+			join models
+				on models.id = monitors.model_id
+			where
+				models.repo_id = $1
+			and
+				monitors.data like $2
+		",
+	)
+	.bind(&repo_id.to_string())
+	.bind(format!("%{}%", custom_metric_id))
+	.fetch_one(txn.borrow_mut())
+	.await?
+	.get(0);
+	if in_use {
+		bail!("This custom metric is used by a monitor. Delete the monitor first.");
+	}
+	let result = sqlx::query(
+		"
+			delete from custom_metrics
+			where
+				id = $1
+			and
+				repo_id = $2
+		",
+	)
+	.bind(&custom_metric_id.to_string())
+	.bind(&repo_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	if result.rows_affected() > 0 {
+		storage
+			.remove(StorageEntity::CustomMetric, custom_metric_id)
+			.await?;
+		notify_storage_invalidation(txn, StorageEntity::CustomMetric, custom_metric_id).await?;
+	}
+	Ok(())
+}
+
+pub async fn get_custom_metric_module(storage: &Storage, custom_metric_id: Id) -> Result<Bytes> {
+	let module = storage
+		.get(StorageEntity::CustomMetric, custom_metric_id)
+		.await?;
+	let module = match module {
+		crate::storage::BytesOrFilePath::Bytes(bytes) => bytes,
+		crate::storage::BytesOrFilePath::Path(path) => tokio::fs::read(path).await?.into(),
+	};
+	Ok(module)
+}
+
+/// Get the examples for a model whose true values were logged after `since`, pairing each true value with the prediction that has the same identifier.
+pub async fn get_custom_metric_examples(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	since: Option<i64>,
+) -> Result<Vec<CustomMetricExample>> {
+	let rows = sqlx::query(
+		"
+			select
+				predictions.output,
+				true_values.value,
+				predictions.input
+			from true_values
+			join predictions
+				on predictions.model_id = true_values.model_id
+				and predictions.identifier = true_values.identifier
+			where
+				true_values.model_id = $1
+			and
+				true_values.date > $2
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(since.unwrap_or(i64::MIN))
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let prediction: String = row.get(0);
+			let true_value: String = row.get(1);
+			let input: String = row.get(2);
+			Ok(CustomMetricExample {
+				prediction: serde_json::from_str(&prediction)?,
+				true_value: serde_json::from_str(&true_value)
+					.unwrap_or(serde_json::Value::String(true_value)),
+				input: serde_json::from_str(&input)?,
+			})
+		})
+		.collect()
+}
+
+struct CustomMetricInstance {
+	store: wasmtime::Store<wasmtime::StoreLimits>,
+	memory: wasmtime::Memory,
+	alloc: wasmtime::TypedFunc<i32, i32>,
+	compute: wasmtime::TypedFunc<(i32, i32), f32>,
+}
+
+fn instantiate_custom_metric_module(module: &[u8]) -> Result<CustomMetricInstance> {
+	let mut config = wasmtime::Config::new();
+	config.consume_fuel(true);
+	let engine = wasmtime::Engine::new(&config)?;
+	let module = wasmtime::Module::new(&engine, module)
+		.map_err(|error| anyhow!("The file is not a valid WebAssembly module: {}", error))?;
+	if module.imports().next().is_some() {
+		bail!("Custom metric modules cannot import anything.");
+	}
+	let limits = wasmtime::StoreLimitsBuilder::new()
+		.memory_size(CUSTOM_METRIC_MAX_MEMORY_SIZE)
+		.instances(1)
+		.build();
+	let mut store = wasmtime::Store::new(&engine, limits);
+	store.limiter(|limits| limits);
+	store.add_fuel(CUSTOM_METRIC_FUEL)?;
+	let instance = wasmtime::Instance::new(&mut store, &module, &[])?;
+	let memory = instance
+		.get_memory(&mut store, "memory")
+		.ok_or_else(|| anyhow!("Custom metric modules must export their memory as \"memory\"."))?;
+	let alloc = instance
+		.get_typed_func::<i32, i32, _>(&mut store, "alloc")
+		.map_err(|_| {
+			anyhow!("Custom metric modules must export a function \"alloc(len: i32) -> i32\".")
+		})?;
+	let compute = instance
+		.get_typed_func::<(i32, i32), f32, _>(&mut store, "compute")
+		.map_err(|_| {
+			anyhow!(
+				"Custom metric modules must export a function \"compute(ptr: i32, len: i32) -> f32\"."
+			)
+		})?;
+	Ok(CustomMetricInstance {
+		store,
+		memory,
+		alloc,
+		compute,
+	})
+}
+
+pub fn validate_custom_metric_module(module: &[u8]) -> Result<()> {
+	instantiate_custom_metric_module(module)?;
+	Ok(())
+}
+
+/// Compute a custom metric over the examples. This is CPU bound, so call it from a blocking task.
+pub fn run_custom_metric(module: &[u8], examples: &[CustomMetricExample]) -> Result<f32> {
+	let CustomMetricInstance {
+		mut store,
+		memory,
+		alloc,
+		compute,
+	} = instantiate_custom_metric_module(module)?;
+	let input = serde_json::to_vec(examples)?;
+	let len = input
+		.len()
+		.to_i32()
+		.ok_or_else(|| anyhow!("Too many examples to compute the custom metric over."))?;
+	let ptr = alloc.call(&mut store, len)?;
+	let offset = usize::try_from(ptr)
+		.map_err(|_| anyhow!("The custom metric's alloc function returned a negative pointer."))?;
+	memory.write(&mut store, offset, &input)?;
+	let value = compute.call(&mut store, (ptr, len))?;
+	if !value.is_finite() {
+		bail!("The custom metric computed a value that is not finite.");
+	}
+	Ok(value)
+}
@@ -1,7 +1,9 @@
+use modelfox_app_base_path::app_path;
+
 pub fn redirect_to_login() -> http::Response<hyper::Body> {
 	http::Response::builder()
 		.status(http::StatusCode::SEE_OTHER)
-		.header(http::header::LOCATION, "/login")
+		.header(http::header::LOCATION, app_path("/login"))
 		.body(hyper::Body::empty())
 		.unwrap()
 }
@@ -14,6 +16,14 @@ pub fn bad_request() -> http::Response<hyper::Body> {
 		.unwrap()
 }
 
+/// 400, with a message that describes what is wrong with the request.
+pub fn bad_request_with_message(message: String) -> http::Response<hyper::Body> {
+	http::Response::builder()
+		.status(http::StatusCode::BAD_REQUEST)
+		.body(hyper::Body::from(message))
+		.unwrap()
+}
+
 /// 401
 pub fn unauthorized() -> http::Response<hyper::Body> {
 	http::Response::builder()
@@ -46,6 +56,14 @@ pub fn method_not_allowed() -> http::Response<hyper::Body> {
 		.unwrap()
 }
 
+/// 413
+pub fn payload_too_large() -> http::Response<hyper::Body> {
+	http::Response::builder()
+		.status(http::StatusCode::PAYLOAD_TOO_LARGE)
+		.body(hyper::Body::from("payload too large"))
+		.unwrap()
+}
+
 /// 503
 pub fn service_unavailable() -> http::Response<hyper::Body> {
 	http::Response::builder()
@@ -12,11 +12,49 @@ pub const ALERT_SENDER_MAXIMUM_RETRY_PERIODS: u32 = 10;
 pub const ALERT_SENDER_RETRY_INITIAL_PERIOD: std::time::Duration =
 	std::time::Duration::from_secs(60);
 pub const ALERT_SENDER_RETRY_DECAY_FACTOR: u64 = 2;
+pub const WEBHOOK_MAXIMUM_ATTEMPTS: i64 = 10;
+pub const PRODUCTION_ROLLUPS_HEARTBEAT_DURATION_TESTING: std::time::Duration =
+	std::time::Duration::from_secs(5);
+pub const PRODUCTION_ROLLUPS_HEARTBEAT_DURATION_PRODUCTION: std::time::Duration =
+	std::time::Duration::from_secs(10 * 60);
+pub const PRODUCTION_ROLLUPS_MAX_DAYS_PER_TRANSACTION: i64 = 100;
+pub const MODEL_TRASH_RETENTION_DURATION: std::time::Duration =
+	std::time::Duration::from_secs(30 * 24 * 60 * 60);
+pub const TRASH_PURGER_HEARTBEAT_DURATION_TESTING: std::time::Duration =
+	std::time::Duration::from_secs(5);
+pub const TRASH_PURGER_HEARTBEAT_DURATION_PRODUCTION: std::time::Duration =
+	std::time::Duration::from_secs(60 * 60);
+pub const BATCH_PREDICTOR_HEARTBEAT_DURATION_TESTING: std::time::Duration =
+	std::time::Duration::from_secs(5);
+pub const BATCH_PREDICTOR_HEARTBEAT_DURATION_PRODUCTION: std::time::Duration =
+	std::time::Duration::from_secs(60);
+pub const PREDICTION_KEY_REJECTIONS_FLUSH_DURATION_TESTING: std::time::Duration =
+	std::time::Duration::from_secs(5);
+pub const PREDICTION_KEY_REJECTIONS_FLUSH_DURATION_PRODUCTION: std::time::Duration =
+	std::time::Duration::from_secs(30);
+pub const STORAGE_INVALIDATION_LISTENER_MIN_BACKOFF: std::time::Duration =
+	std::time::Duration::from_secs(1);
+pub const STORAGE_INVALIDATION_LISTENER_MAX_BACKOFF: std::time::Duration =
+	std::time::Duration::from_secs(60);
+pub const PREDICT_MODEL_CACHE_MAX_MODELS: usize = 16;
+pub const BATCH_PREDICT_CHUNK_SIZE: usize = 1000;
 pub const PRODUCTION_PREDICTIONS_NUM_PREDICTIONS_PER_PAGE_TABLE: i64 = 10;
 pub const PRODUCTION_STATS_LARGE_ABSENT_RATIO_THRESHOLD_TO_TRIGGER_ALERT: f32 = 0.1;
 pub const PRODUCTION_STATS_LARGE_INVALID_RATIO_THRESHOLD_TO_TRIGGER_ALERT: f32 = 0.1;
 pub const PRODUCTION_STATS_TEXT_COLUMN_MAX_TOKENS_TO_SHOW_IN_TABLE: usize = 100;
+pub const PRODUCTION_STATS_LARGE_PSI_THRESHOLD_TO_TRIGGER_ALERT: f32 = 0.25;
+pub const PRODUCTION_STATS_LARGE_OUT_OF_RANGE_RATIO_THRESHOLD_TO_TRIGGER_ALERT: f32 = 0.05;
+pub const PRODUCTION_STATS_LARGE_UNSEEN_TOKEN_RATIO_THRESHOLD_TO_TRIGGER_ALERT: f32 = 0.2;
+pub const PRODUCTION_STATS_MAX_DRIFTED_COLUMNS_TO_SHOW_IN_CHART: usize = 10;
+pub const REPORT_MAX_FEATURE_IMPORTANCES_TO_SHOW_IN_CHART: usize = 20;
 pub const TRAINING_IMPORTANCES_MAX_FEATURE_IMPORTANCES_TO_SHOW_IN_CHART: usize = 100;
 pub const TRAINING_IMPORTANCES_MAX_FEATURE_IMPORTANCES_TO_SHOW_IN_TABLE: usize = 100;
 pub const TRAINING_STATS_TEXT_COLUMN_MAX_TOKENS_TO_SHOW_IN_CHART: usize = 100;
 pub const TRAINING_STATS_TEXT_COLUMN_MAX_TOKENS_TO_SHOW_IN_TABLE: usize = 100;
+pub const TRAINING_STATS_COLUMNS_PER_PAGE: usize = 100;
+pub const TRAINING_STATS_TEXT_COLUMN_MAX_DROPPED_TOKENS_TO_SHOW_IN_TABLE: usize = 20;
+pub const TRAINING_STATS_CORRELATION_HEATMAP_MAX_COLUMNS: usize = 20;
+pub const TRAINING_STATS_TARGET_CORRELATION_LEAKAGE_THRESHOLD: f32 = 0.95;
+pub const METRIC_SIGNIFICANT_DIGITS: u8 = 4;
+pub const METRIC_PERCENT_DECIMAL_PLACES: usize = 2;
+pub const UPLOAD_PROGRESS_LOG_INTERVAL_BYTES: u64 = 64 * 1024 * 1024;
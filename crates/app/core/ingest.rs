@@ -0,0 +1,328 @@
+/*!
+The ingest worker consumes monitor events from a Kafka topic or a Kinesis stream, for deployments that log too many predictions to post each batch of them to the `/track` route. Each message holds one monitor event or an array of them, in the same JSON format the `/track` route accepts.
+
+Events are handled in batches, one database transaction per batch. An event that fails to be handled is logged and skipped, so one malformed event does not stop the rest of the stream. The position in the stream is saved only after the batch's transaction commits, so events are handled at least once: if the app stops between the two, the batch is handled again when it restarts.
+*/
+
+use crate::{
+	options::IngestOptions,
+	track::{handle_prediction_monitor_event, handle_true_value_monitor_event},
+	AppState,
+};
+use anyhow::Result;
+use memmap::Mmap;
+use modelfox_app_monitor_event::MonitorEvent;
+use modelfox_id::Id;
+use sqlx::prelude::*;
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+/// A batch is handled once it has this many messages, or once [`BATCH_DURATION`] has passed since its first message arrived.
+const BATCH_SIZE: usize = 1000;
+const BATCH_DURATION: Duration = Duration::from_secs(1);
+
+/// After an error talking to Kafka or Kinesis, the worker waits this long before trying again.
+const RETRY_DURATION: Duration = Duration::from_secs(10);
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum MonitorEventSet {
+	Single(MonitorEvent),
+	Multiple(Vec<MonitorEvent>),
+}
+
+#[tracing::instrument(level = "info", skip_all)]
+pub async fn ingest(app_state: Arc<AppState>, options: IngestOptions) -> Result<()> {
+	loop {
+		let result = match &options {
+			IngestOptions::Kafka(options) => kafka::ingest(&app_state, options).await,
+			IngestOptions::Kinesis(options) => kinesis::ingest(&app_state, options).await,
+		};
+		if let Err(error) = result {
+			tracing::error!(%error, "ingest failed, retrying");
+		}
+		tokio::time::sleep(RETRY_DURATION).await;
+	}
+}
+
+/// Handle the monitor events in each message in one transaction. This returns the number of events that were handled.
+async fn handle_messages(app_state: &AppState, messages: Vec<Vec<u8>>) -> Result<usize> {
+	let mut txn = app_state.begin_transaction().await?;
+	let mut model_cache: BTreeMap<Id, Mmap> = BTreeMap::new();
+	let mut n_handled = 0;
+	for message in messages {
+		let monitor_events = match serde_json::from_slice(&message) {
+			Ok(MonitorEventSet::Single(monitor_event)) => vec![monitor_event],
+			Ok(MonitorEventSet::Multiple(monitor_events)) => monitor_events,
+			Err(error) => {
+				tracing::error!(%error, "skipping a message that is not a monitor event");
+				continue;
+			}
+		};
+		for monitor_event in monitor_events {
+			// Handle each event in a savepoint, so an event that fails partway through is rolled back without rolling back the rest of the batch.
+			let mut savepoint = txn.begin().await?;
+			let result = match monitor_event {
+				MonitorEvent::Prediction(monitor_event) => {
+					handle_prediction_monitor_event(
+						&mut savepoint,
+						&app_state.storage,
+						&mut model_cache,
+						monitor_event,
+					)
+					.await
+				}
+				MonitorEvent::TrueValue(monitor_event) => {
+					handle_true_value_monitor_event(
+						&mut savepoint,
+						&app_state.storage,
+						&mut model_cache,
+						monitor_event,
+					)
+					.await
+				}
+			};
+			match result {
+				Ok(()) => {
+					savepoint.commit().await?;
+					n_handled += 1;
+				}
+				Err(error) => {
+					savepoint.rollback().await?;
+					tracing::error!(%error, "skipping a monitor event that failed to be handled");
+				}
+			}
+		}
+	}
+	app_state.commit_transaction(txn).await?;
+	Ok(n_handled)
+}
+
+#[cfg(feature = "kafka")]
+mod kafka {
+	use super::{handle_messages, BATCH_DURATION, BATCH_SIZE};
+	use crate::{options::KafkaIngestOptions, AppState};
+	use anyhow::Result;
+	use rdkafka::{
+		consumer::{CommitMode, Consumer, StreamConsumer},
+		ClientConfig, Message,
+	};
+
+	/// Consume the topic as a member of the consumer group, so instances of the app that share the group split the topic's partitions between them. Offsets are committed after each batch is handled.
+	pub async fn ingest(app_state: &AppState, options: &KafkaIngestOptions) -> Result<()> {
+		let mut config = ClientConfig::new();
+		config
+			.set("bootstrap.servers", &options.brokers)
+			.set("group.id", &options.group_id)
+			.set("enable.auto.commit", "false")
+			.set("auto.offset.reset", "earliest");
+		for (key, value) in options.properties.iter() {
+			config.set(key, value);
+		}
+		let consumer: StreamConsumer = config.create()?;
+		consumer.subscribe(&[&options.topic])?;
+		tracing::info!(topic = %options.topic, "consuming monitor events from kafka");
+		loop {
+			let message = consumer.recv().await?;
+			let mut messages = vec![message.payload().unwrap_or_default().to_owned()];
+			let deadline = tokio::time::Instant::now() + BATCH_DURATION;
+			while messages.len() < BATCH_SIZE {
+				match tokio::time::timeout_at(deadline, consumer.recv()).await {
+					Ok(message) => messages.push(message?.payload().unwrap_or_default().to_owned()),
+					Err(_) => break,
+				}
+			}
+			let n_messages = messages.len();
+			let n_handled = handle_messages(app_state, messages).await?;
+			consumer.commit_consumer_state(CommitMode::Async)?;
+			tracing::info!(n_messages, n_handled, "handled monitor events from kafka");
+		}
+	}
+}
+
+#[cfg(not(feature = "kafka"))]
+mod kafka {
+	use crate::{options::KafkaIngestOptions, AppState};
+	use anyhow::{bail, Result};
+
+	pub async fn ingest(_app_state: &AppState, _options: &KafkaIngestOptions) -> Result<()> {
+		bail!("This build of the app does not support ingesting from Kafka. Build it with the kafka feature enabled.");
+	}
+}
+
+#[cfg(feature = "kinesis")]
+mod kinesis {
+	use super::{handle_messages, BATCH_DURATION, BATCH_SIZE};
+	use crate::{
+		cluster::{BackgroundTask, Leadership},
+		options::KinesisIngestOptions,
+		AppState,
+	};
+	use anyhow::Result;
+	use aws_sdk_kinesis::{model::ShardIteratorType, Client, Region};
+	use num::ToPrimitive;
+	use sqlx::prelude::*;
+	use std::borrow::BorrowMut;
+
+	/// Kinesis has no consumer groups, so only the instance holding the leadership reads the stream, and the sequence number of the last record read from each shard is saved in the database. This lets another instance pick up where the last one left off.
+	pub async fn ingest(app_state: &AppState, options: &KinesisIngestOptions) -> Result<()> {
+		let mut config = aws_config::from_env();
+		if let Some(region) = &options.region {
+			config = config.region(Region::new(region.clone()));
+		}
+		let client = Client::new(&config.load().await);
+		tracing::info!(stream_name = %options.stream_name, "consuming monitor events from kinesis");
+		let mut interval = tokio::time::interval(BATCH_DURATION);
+		interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+		loop {
+			interval.tick().await;
+			let leadership =
+				match Leadership::acquire(app_state, BackgroundTask::KinesisIngest).await? {
+					Some(leadership) => leadership,
+					None => continue,
+				};
+			let shard_ids = list_shard_ids(&client, &options.stream_name).await?;
+			for shard_id in shard_ids {
+				ingest_shard(app_state, &client, &options.stream_name, &shard_id).await?;
+			}
+			leadership.release().await?;
+		}
+	}
+
+	async fn list_shard_ids(client: &Client, stream_name: &str) -> Result<Vec<String>> {
+		let mut shard_ids = Vec::new();
+		let mut next_token: Option<String> = None;
+		loop {
+			let request = match next_token.take() {
+				Some(next_token) => client.list_shards().next_token(next_token),
+				None => client.list_shards().stream_name(stream_name),
+			};
+			let output = request.send().await?;
+			shard_ids.extend(
+				output
+					.shards()
+					.unwrap_or_default()
+					.iter()
+					.filter_map(|shard| shard.shard_id().map(ToOwned::to_owned)),
+			);
+			match output.next_token() {
+				Some(token) => next_token = Some(token.to_owned()),
+				None => break,
+			}
+		}
+		Ok(shard_ids)
+	}
+
+	/// Read the records added to the shard since its checkpoint, in batches of up to [`BATCH_SIZE`], until the shard is caught up.
+	async fn ingest_shard(
+		app_state: &AppState,
+		client: &Client,
+		stream_name: &str,
+		shard_id: &str,
+	) -> Result<()> {
+		let checkpoint = get_checkpoint(app_state, stream_name, shard_id).await?;
+		let request = client
+			.get_shard_iterator()
+			.stream_name(stream_name)
+			.shard_id(shard_id);
+		let request = match checkpoint {
+			Some(sequence_number) => request
+				.shard_iterator_type(ShardIteratorType::AfterSequenceNumber)
+				.starting_sequence_number(sequence_number),
+			None => request.shard_iterator_type(ShardIteratorType::TrimHorizon),
+		};
+		let mut shard_iterator = request
+			.send()
+			.await?
+			.shard_iterator()
+			.map(ToOwned::to_owned);
+		while let Some(iterator) = shard_iterator.take() {
+			let output = client
+				.get_records()
+				.shard_iterator(iterator)
+				.limit(BATCH_SIZE.to_i32().unwrap())
+				.send()
+				.await?;
+			let records = output.records().unwrap_or_default();
+			if let Some(sequence_number) =
+				records.last().and_then(|record| record.sequence_number())
+			{
+				let messages = records
+					.iter()
+					.filter_map(|record| record.data().map(|data| data.as_ref().to_owned()))
+					.collect::<Vec<_>>();
+				let n_messages = messages.len();
+				let n_handled = handle_messages(app_state, messages).await?;
+				set_checkpoint(app_state, stream_name, shard_id, sequence_number).await?;
+				tracing::info!(
+					shard_id,
+					n_messages,
+					n_handled,
+					"handled monitor events from kinesis"
+				);
+			}
+			// Stop once the shard is caught up. A closed shard has no next iterator.
+			if output.millis_behind_latest() == Some(0) || records.is_empty() {
+				break;
+			}
+			shard_iterator = output.next_shard_iterator().map(ToOwned::to_owned);
+		}
+		Ok(())
+	}
+
+	async fn get_checkpoint(
+		app_state: &AppState,
+		stream_name: &str,
+		shard_id: &str,
+	) -> Result<Option<String>> {
+		let mut txn = app_state.begin_transaction().await?;
+		let row = sqlx::query(
+			"
+				select
+					sequence_number
+				from ingest_checkpoints
+				where
+					stream_name = $1
+					and shard_id = $2
+			",
+		)
+		.bind(stream_name)
+		.bind(shard_id)
+		.fetch_optional(txn.borrow_mut())
+		.await?;
+		app_state.commit_transaction(txn).await?;
+		Ok(row.map(|row| row.get(0)))
+	}
+
+	async fn set_checkpoint(
+		app_state: &AppState,
+		stream_name: &str,
+		shard_id: &str,
+		sequence_number: &str,
+	) -> Result<()> {
+		let mut txn = app_state.begin_transaction().await?;
+		sqlx::query(
+			"
+				insert into ingest_checkpoints (stream_name, shard_id, sequence_number)
+				values ($1, $2, $3)
+				on conflict (stream_name, shard_id) do update set sequence_number = excluded.sequence_number
+			",
+		)
+		.bind(stream_name)
+		.bind(shard_id)
+		.bind(sequence_number)
+		.execute(txn.borrow_mut())
+		.await?;
+		app_state.commit_transaction(txn).await?;
+		Ok(())
+	}
+}
+
+#[cfg(not(feature = "kinesis"))]
+mod kinesis {
+	use crate::{options::KinesisIngestOptions, AppState};
+	use anyhow::{bail, Result};
+
+	pub async fn ingest(_app_state: &AppState, _options: &KinesisIngestOptions) -> Result<()> {
+		bail!("This build of the app does not support ingesting from Kinesis. Build it with the kinesis feature enabled.");
+	}
+}
@@ -0,0 +1,204 @@
+use crate::{
+	alert::AlertMetric,
+	monitor::{get_latest_monitor_evaluation, get_monitors_for_model},
+	monitor_checker::{get_out_of_range_fraction, get_production_metric},
+};
+use anyhow::Result;
+use modelfox_id::Id;
+use sqlx::prelude::*;
+use std::borrow::BorrowMut;
+
+/// One model in an organization's model inventory. See [`get_model_inventory`].
+pub struct ModelInventoryEntry {
+	pub repo_id: Id,
+	pub repo_title: String,
+	pub model_id: Id,
+	pub model_tag: Option<String>,
+	pub created_at: i64,
+	pub stage: ModelStage,
+	/// This is `None` if no predictions have been logged for the model or they have all been deleted.
+	pub last_prediction_date: Option<i64>,
+	/// This is the accuracy for classifiers and the root mean squared error for regressors, in the latest hour true values were logged.
+	pub production_metric: Option<(AlertMetric, f32)>,
+	/// This is the largest fraction of values outside the training range across the number columns, in the latest hour predictions were logged.
+	pub out_of_range_fraction: Option<f32>,
+	pub monitor_count: usize,
+	/// This is the number of the model's enabled monitors whose latest check exceeded their thresholds.
+	pub alerting_monitor_count: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModelStage {
+	/// The model was approved for production. See [`crate::model_reviews::approve_model`].
+	Approved,
+	/// The model is the newest in its repo but has not been approved.
+	Latest,
+	/// A newer model was uploaded to the repo and this one was not approved.
+	Superseded,
+}
+
+impl std::fmt::Display for ModelStage {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let s = match self {
+			ModelStage::Approved => "Approved",
+			ModelStage::Latest => "Latest",
+			ModelStage::Superseded => "Superseded",
+		};
+		write!(f, "{}", s)
+	}
+}
+
+/// Retrieve every model in the organization's repos that the user can access, grouped by repo with the newest model first. Repos granted to teams are only included for the members of those teams and the organization's admins.
+pub async fn get_model_inventory(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+	user_id: Id,
+	is_admin: bool,
+) -> Result<Vec<ModelInventoryEntry>> {
+	let rows = sqlx::query(
+		"
+			select
+				repos.id,
+				repos.title,
+				models.id,
+				models.tag,
+				models.created_at,
+				model_approvals.model_id,
+				(
+					select max(predictions.date)
+					from predictions
+					where predictions.model_id = models.id
+				)
+			from models
+			join repos
+				on repos.id = models.repo_id
+			left join model_approvals
+				on model_approvals.model_id = models.id
+			where
+				models.deleted_at is null and
+				repos.organization_id = $1 and (
+					$3 or
+					not exists (
+						select 1
+						from repos_teams
+						where repos_teams.repo_id = repos.id
+					) or
+					exists (
+						select 1
+						from repos_teams
+						join teams_users
+							on teams_users.team_id = repos_teams.team_id
+						where
+							repos_teams.repo_id = repos.id and
+							teams_users.user_id = $2
+					)
+				)
+			order by repos.title, repos.id, models.created_at desc
+		",
+	)
+	.bind(&organization_id.to_string())
+	.bind(&user_id.to_string())
+	.bind(is_admin)
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	let mut entries = Vec::with_capacity(rows.len());
+	let mut previous_repo_id = None;
+	for row in rows {
+		let repo_id: Id = row.get::<String, _>(0).parse()?;
+		let model_id: Id = row.get::<String, _>(2).parse()?;
+		let is_approved = row.get::<Option<String>, _>(5).is_some();
+		// The rows of each repo are ordered newest first, so the first is the repo's latest model.
+		let is_latest = previous_repo_id != Some(repo_id);
+		previous_repo_id = Some(repo_id);
+		let stage = if is_approved {
+			ModelStage::Approved
+		} else if is_latest {
+			ModelStage::Latest
+		} else {
+			ModelStage::Superseded
+		};
+		let production_metric = get_current_production_metric(txn, model_id).await?;
+		let out_of_range_fraction = get_out_of_range_fraction(model_id, txn)
+			.await?
+			.map(|(out_of_range_fraction, _)| out_of_range_fraction);
+		let monitors = get_monitors_for_model(txn, model_id).await?;
+		let mut alerting_monitor_count = 0;
+		for monitor in monitors.iter().filter(|monitor| monitor.enabled) {
+			let exceeded_thresholds = get_latest_monitor_evaluation(txn, monitor.id)
+				.await?
+				.map(|evaluation| evaluation.exceeded_thresholds())
+				.unwrap_or(false);
+			if exceeded_thresholds {
+				alerting_monitor_count += 1;
+			}
+		}
+		entries.push(ModelInventoryEntry {
+			repo_id,
+			repo_title: row.get(1),
+			model_id,
+			model_tag: row.get(3),
+			created_at: row.get(4),
+			stage,
+			last_prediction_date: row.get(6),
+			production_metric,
+			out_of_range_fraction,
+			monitor_count: monitors.len(),
+			alerting_monitor_count,
+		});
+	}
+	Ok(entries)
+}
+
+/// Retrieve the accuracy of a classifier or the root mean squared error of a regressor. Only the metric that applies to the model's task is found in its production metrics, so this does not need to read the model.
+async fn get_current_production_metric(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Option<(AlertMetric, f32)>> {
+	for metric in [AlertMetric::Accuracy, AlertMetric::RootMeanSquaredError] {
+		if let Some((value, _, _)) = get_production_metric(metric, model_id, txn).await? {
+			return Ok(Some((metric, value)));
+		}
+	}
+	Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_common::*;
+
+	#[tokio::test]
+	async fn test_model_inventory() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		seed_events(&app, 10, model_id).await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let organization_id = Id::generate();
+		sqlx::query("insert into organizations (id, name) values ($1, 'Test')")
+			.bind(&organization_id.to_string())
+			.execute(txn.borrow_mut())
+			.await
+			.unwrap();
+		sqlx::query(
+			"update repos set organization_id = $1 where id = (select repo_id from models where id = $2)",
+		)
+		.bind(&organization_id.to_string())
+		.bind(&model_id.to_string())
+		.execute(txn.borrow_mut())
+		.await
+		.unwrap();
+		let entries = get_model_inventory(&mut txn, organization_id, Id::generate(), true)
+			.await
+			.unwrap();
+		assert_eq!(entries.len(), 1);
+		let entry = &entries[0];
+		assert_eq!(entry.model_id, model_id);
+		assert_eq!(entry.stage, ModelStage::Latest);
+		assert!(entry.last_prediction_date.is_some());
+		assert!(matches!(
+			entry.production_metric,
+			Some((AlertMetric::Accuracy, _))
+		));
+		assert_eq!(entry.alerting_monitor_count, 0);
+	}
+}
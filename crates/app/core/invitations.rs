@@ -0,0 +1,187 @@
+use crate::{clock::Clock, options::AuthOptions};
+use anyhow::Result;
+use modelfox_id::Id;
+use sqlx::prelude::*;
+use std::borrow::BorrowMut;
+
+/// An invitation pre-authorizes an email address to sign in when the app is invite only or restricted to a set of email domains.
+pub struct Invitation {
+	pub id: Id,
+	pub email: String,
+	/// This is `None` if the inviter has been deleted.
+	pub inviter_email: Option<String>,
+	pub created_at: i64,
+}
+
+/// Determine whether the email address's domain is one of `allowed_email_domains`. If no domains are configured, every domain is allowed.
+pub fn email_domain_allowed(options: &AuthOptions, email: &str) -> bool {
+	let allowed_email_domains = match options.allowed_email_domains.as_ref() {
+		Some(allowed_email_domains) => allowed_email_domains,
+		None => return true,
+	};
+	let domain = match email.trim().rsplit_once('@') {
+		Some((_, domain)) => domain,
+		None => return false,
+	};
+	allowed_email_domains.iter().any(|allowed_email_domain| {
+		allowed_email_domain
+			.trim()
+			.trim_start_matches('@')
+			.eq_ignore_ascii_case(domain)
+	})
+}
+
+/// Determine whether the email address is one of the configured `admin_emails`.
+pub fn is_admin_email(options: &AuthOptions, email: &str) -> bool {
+	options
+		.admin_emails
+		.iter()
+		.any(|admin_email| admin_email.trim().eq_ignore_ascii_case(email.trim()))
+}
+
+/// Determine whether the email address may sign in. Admins and invited email addresses may always sign in. Otherwise, the email address's domain must be allowed and, if the app is invite only, the user must already exist.
+pub async fn is_email_authorized(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	options: &AuthOptions,
+	email: &str,
+) -> Result<bool> {
+	if is_admin_email(options, email) {
+		return Ok(true);
+	}
+	let email = normalize_email(email);
+	let invited: bool = sqlx::query(
+		"
+			select
+				count(*) > 0
+			from invitations
+			where
+				email = $1
+		",
+	)
+	.bind(&email)
+	.fetch_one(txn.borrow_mut())
+	.await?
+	.get(0);
+	if invited {
+		return Ok(true);
+	}
+	if !email_domain_allowed(options, &email) {
+		return Ok(false);
+	}
+	if options.invite_only {
+		let user_exists: bool = sqlx::query(
+			"
+				select
+					count(*) > 0
+				from users
+				where
+					lower(email) = $1
+			",
+		)
+		.bind(&email)
+		.fetch_one(txn.borrow_mut())
+		.await?
+		.get(0);
+		return Ok(user_exists);
+	}
+	Ok(true)
+}
+
+pub async fn create_invitation(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	email: &str,
+	inviter_user_id: Id,
+	clock: &Clock,
+) -> Result<()> {
+	sqlx::query(
+		"
+			insert into invitations (
+				id, email, inviter_user_id, created_at
+			) values (
+				$1, $2, $3, $4
+			)
+			on conflict (email) do nothing
+		",
+	)
+	.bind(&Id::generate().to_string())
+	.bind(&normalize_email(email))
+	.bind(&inviter_user_id.to_string())
+	.bind(clock.now_utc().unix_timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn delete_invitation(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	invitation_id: Id,
+) -> Result<()> {
+	sqlx::query(
+		"
+			delete from invitations
+			where
+				id = $1
+		",
+	)
+	.bind(&invitation_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn get_invitations(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Vec<Invitation>> {
+	let rows = sqlx::query(
+		"
+			select
+				invitations.id,
+				invitations.email,
+				users.email,
+				invitations.created_at
+			from invitations
+			left join users
+				on users.id = invitations.inviter_user_id
+			order by invitations.created_at
+		",
+	)
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let id: String = row.get(0);
+			Ok(Invitation {
+				id: id.parse()?,
+				email: row.get(1),
+				inviter_email: row.get(2),
+				created_at: row.get(3),
+			})
+		})
+		.collect()
+}
+
+fn normalize_email(email: &str) -> String {
+	email.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_email_domain_allowed() {
+		let options = AuthOptions {
+			allowed_email_domains: Some(vec!["modelfox.dev".to_owned(), "@example.com".to_owned()]),
+			..Default::default()
+		};
+		assert!(email_domain_allowed(&options, "user@modelfox.dev"));
+		assert!(email_domain_allowed(&options, "user@Example.com"));
+		assert!(!email_domain_allowed(&options, "user@evil.com"));
+		assert!(!email_domain_allowed(&options, "user@sub.modelfox.dev"));
+		assert!(!email_domain_allowed(&options, "modelfox.dev"));
+		assert!(email_domain_allowed(
+			&AuthOptions::default(),
+			"user@evil.com"
+		));
+	}
+}
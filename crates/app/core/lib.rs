@@ -1,37 +1,65 @@
 use crate::{
 	alert_sender::{alert_sender, AlertSenderMessage},
+	batch_predict::{batch_predictor, BatchPredictorMessage},
 	clock::Clock,
+	cluster::storage_invalidation_listener,
+	ingest::ingest,
+	model::PredictModelCache,
 	monitor_checker::{monitor_checker, MonitorCheckerMessage},
-	options::{Options, StorageOptions},
+	options::{IngestOptions, Options, StorageOptions},
+	prediction_keys::{prediction_key_rejections_flusher, PredictionKeyRateLimiter},
+	production_rollups::{production_rollups, ProductionRollupsMessage},
 	storage::{LocalStorage, S3Storage, Storage},
+	trash::{trash_purger, TrashPurgerMessage},
 };
 use anyhow::{anyhow, bail, Result};
 use lettre::AsyncTransport;
 use lettre::Transport;
 use serde::Serialize;
 use sqlx::postgres::PgPoolOptions;
+use std::future::Future;
 use std::sync::{Arc, RwLock};
 use storage::InMemoryStorage;
 use tokio::sync::{mpsc, oneshot};
 use url::Url;
 use urlencoding::decode;
 
+pub mod admin;
 pub mod alert;
 pub mod alert_sender;
+pub mod alert_template;
+pub mod batch_predict;
 pub mod clock;
+pub mod cluster;
 pub mod cookies;
+pub mod custom_metrics;
 pub mod error;
 pub mod heuristics;
+pub mod ingest;
+pub mod inventory;
+pub mod invitations;
 pub mod model;
+pub mod model_reviews;
 pub mod monitor;
 pub mod monitor_checker;
+pub mod monitor_templates;
+pub mod notification_channels;
 pub mod options;
 pub mod organizations;
+pub mod prediction_keys;
+pub mod privacy;
+pub mod production_rollups;
 pub mod repos;
+pub mod request_body;
+pub mod request_tracing;
+pub mod sessions;
 pub mod storage;
+pub mod teams;
 pub mod timezone;
 pub mod track;
+pub mod trash;
 pub mod user;
+pub mod webhooks;
 
 #[cfg(test)]
 pub mod test_common;
@@ -40,14 +68,21 @@ pub struct App {
 	state: Arc<AppState>,
 	monitor_checker_sender: mpsc::UnboundedSender<MonitorCheckerMessage>,
 	alert_sender_sender: mpsc::UnboundedSender<AlertSenderMessage>,
+	production_rollups_sender: mpsc::UnboundedSender<ProductionRollupsMessage>,
+	trash_purger_sender: mpsc::UnboundedSender<TrashPurgerMessage>,
+	batch_predictor_sender: mpsc::UnboundedSender<BatchPredictorMessage>,
 }
 
 #[derive(Debug)]
 pub struct AppState {
 	pub clock: Clock,
 	pub database_pool: sqlx::AnyPool,
+	/// This is the pool for the read replica, if one is configured.
+	pub database_replica_pool: Option<sqlx::AnyPool>,
 	pub http_sender: HttpSender,
 	pub options: Options,
+	pub predict_model_cache: PredictModelCache,
+	pub prediction_key_rate_limiter: PredictionKeyRateLimiter,
 	pub smtp_transport: Option<Mailer>,
 	pub storage: Storage,
 }
@@ -63,14 +98,28 @@ impl HttpSender {
 		&self,
 		payload: S,
 		url: Url,
+	) -> Result<http::Response<hyper::Body>> {
+		self.post_body(serde_json::to_string(&payload)?, url, &[])
+			.await
+	}
+
+	/// POST `body` to `url` with the given headers.
+	pub async fn post_body(
+		&self,
+		body: String,
+		url: Url,
+		headers: &[(&str, String)],
 	) -> Result<http::Response<hyper::Body>> {
 		match self {
 			HttpSender::Production => {
 				let client = hyper::Client::new();
-				let request = hyper::Request::builder()
+				let mut request = hyper::Request::builder()
 					.method(hyper::Method::POST)
-					.uri(url.as_str())
-					.body(hyper::Body::from(serde_json::to_string(&payload)?))?;
+					.uri(url.as_str());
+				for (name, value) in headers {
+					request = request.header(*name, value.as_str());
+				}
+				let request = request.body(hyper::Body::from(body))?;
 				Ok(client.request(request).await?)
 			}
 			HttpSender::Testing(should_succeed) => {
@@ -162,6 +211,42 @@ async fn create_database_pool(options: CreateDatabasePoolOptions) -> Result<sqlx
 	Ok(pool)
 }
 
+/// Check if a query failed because it exceeded PostgreSQL's `statement_timeout`, which cancels the query with the `query_canceled` error code.
+fn is_statement_timeout_error(error: &anyhow::Error) -> bool {
+	match error.downcast_ref::<sqlx::Error>() {
+		Some(sqlx::Error::Database(error)) => error.code().as_deref() == Some("57014"),
+		_ => false,
+	}
+}
+
+/// When more than one instance of the app runs behind a load balancer, any instance may serve any request, so instances must not keep data that the others cannot see. The database must be PostgreSQL, because a SQLite database is a local file, and storage must be S3, because local and in memory storage are only visible to one instance. Each instance may have its own S3 `cache_path`.
+fn validate_stateless_options(options: &Options) -> Result<()> {
+	if options.database.url.scheme() != "postgres" {
+		bail!("Running the app as a stateless instance requires a PostgreSQL database.");
+	}
+	match options.storage {
+		StorageOptions::S3(_) => {}
+		StorageOptions::Local(_) | StorageOptions::InMemory => {
+			bail!("Running the app as a stateless instance requires S3 storage. Local and in memory storage are not shared between instances.");
+		}
+	}
+	Ok(())
+}
+
+/// Kafka and Kinesis clients are only compiled into builds of the app with the `kafka` and `kinesis` features, so fail at startup instead of retrying an ingest that can never succeed.
+fn validate_ingest_options(ingest: &IngestOptions) -> Result<()> {
+	match ingest {
+		IngestOptions::Kafka(_) if !cfg!(feature = "kafka") => {
+			bail!("This build of the app does not support ingesting from Kafka. Build it with the kafka feature enabled.");
+		}
+		IngestOptions::Kinesis(_) if !cfg!(feature = "kinesis") => {
+			bail!("This build of the app does not support ingesting from Kinesis. Build it with the kinesis feature enabled.");
+		}
+		_ => {}
+	}
+	Ok(())
+}
+
 pub fn migrate(database_url: Url) -> Result<()> {
 	tokio::runtime::Builder::new_multi_thread()
 		.enable_all()
@@ -182,12 +267,34 @@ pub async fn migrate_inner(database_url: Url) -> Result<()> {
 
 impl App {
 	pub async fn new(options: Options) -> Result<Self> {
+		if options.stateless {
+			validate_stateless_options(&options)?;
+		}
+		if let Some(ingest) = options.ingest.as_ref() {
+			validate_ingest_options(ingest)?;
+		}
 		// Create the database pool.
 		let database_pool = create_database_pool(CreateDatabasePoolOptions {
 			database_max_connections: options.database.max_connections,
 			database_url: options.database.url.clone(),
 		})
 		.await?;
+		// Create the read replica database pool. Migrations are not run against the replica because it receives them from the primary.
+		let database_replica_pool = if let Some(replica_url) = options.database.replica_url.clone()
+		{
+			if replica_url.scheme() != "postgres" {
+				bail!("The database replica url must start with postgres:.");
+			}
+			Some(
+				create_database_pool(CreateDatabasePoolOptions {
+					database_max_connections: options.database.max_connections,
+					database_url: replica_url,
+				})
+				.await?,
+			)
+		} else {
+			None
+		};
 		// Create the database pool.
 		if modelfox_app_migrations::empty(&database_pool).await? {
 			// Run all migrations if the database is empty.
@@ -228,8 +335,11 @@ impl App {
 		let state = AppState {
 			clock: Clock::new(),
 			database_pool,
+			database_replica_pool,
 			http_sender,
 			options,
+			predict_model_cache: PredictModelCache::default(),
+			prediction_key_rate_limiter: PredictionKeyRateLimiter::default(),
 			smtp_transport,
 			storage,
 		};
@@ -237,6 +347,11 @@ impl App {
 		let (monitor_checker_sender, monitor_checker_receiver) =
 			tokio::sync::mpsc::unbounded_channel();
 		let (alert_sender_sender, alert_sender_receiver) = tokio::sync::mpsc::unbounded_channel();
+		let (production_rollups_sender, production_rollups_receiver) =
+			tokio::sync::mpsc::unbounded_channel();
+		let (trash_purger_sender, trash_purger_receiver) = tokio::sync::mpsc::unbounded_channel();
+		let (batch_predictor_sender, batch_predictor_receiver) =
+			tokio::sync::mpsc::unbounded_channel();
 		tokio::spawn({
 			let state = Arc::clone(&state);
 			async move {
@@ -251,10 +366,55 @@ impl App {
 				alert_sender(state, alert_sender_receiver).await.unwrap();
 			}
 		});
+		tokio::spawn({
+			let state = Arc::clone(&state);
+			async move {
+				production_rollups(state, production_rollups_receiver)
+					.await
+					.unwrap();
+			}
+		});
+		tokio::spawn({
+			let state = Arc::clone(&state);
+			async move {
+				trash_purger(state, trash_purger_receiver).await.unwrap();
+			}
+		});
+		tokio::spawn({
+			let state = Arc::clone(&state);
+			async move {
+				batch_predictor(state, batch_predictor_receiver)
+					.await
+					.unwrap();
+			}
+		});
+		tokio::spawn({
+			let state = Arc::clone(&state);
+			async move {
+				storage_invalidation_listener(state).await;
+			}
+		});
+		tokio::spawn({
+			let state = Arc::clone(&state);
+			async move {
+				prediction_key_rejections_flusher(state).await;
+			}
+		});
+		if let Some(ingest_options) = state.options.ingest.clone() {
+			tokio::spawn({
+				let state = Arc::clone(&state);
+				async move {
+					ingest(state, ingest_options).await.unwrap();
+				}
+			});
+		}
 		let app = App {
 			state,
 			monitor_checker_sender,
 			alert_sender_sender,
+			production_rollups_sender,
+			trash_purger_sender,
+			batch_predictor_sender,
 		};
 		Ok(app)
 	}
@@ -268,6 +428,24 @@ impl App {
 		Ok(())
 	}
 
+	/// Begin a transaction for read only queries. See [`AppState::begin_read_transaction`].
+	pub async fn begin_read_transaction(&self) -> Result<sqlx::Transaction<'_, sqlx::Any>> {
+		self.state.begin_read_transaction().await
+	}
+
+	/// Begin a transaction for an analytics query. See [`AppState::begin_analytics_transaction`].
+	pub async fn begin_analytics_transaction(&self) -> Result<sqlx::Transaction<'_, sqlx::Any>> {
+		self.state.begin_analytics_transaction().await
+	}
+
+	/// Run an analytics query with the statement timeout. See [`AppState::with_analytics_statement_timeout`].
+	pub async fn with_analytics_statement_timeout<T>(
+		&self,
+		query: impl Future<Output = Result<T>>,
+	) -> Result<Option<T>> {
+		self.state.with_analytics_statement_timeout(query).await
+	}
+
 	pub async fn db_acquire(&self) -> Result<sqlx::pool::PoolConnection<sqlx::Any>> {
 		Ok(self.state.database_pool.acquire().await?)
 	}
@@ -298,6 +476,21 @@ impl App {
 			.send(AlertSenderMessage::Run(sender))?;
 		receiver.await?;
 		tracing::info!("alert_sender response received");
+		let (sender, receiver) = oneshot::channel();
+		self.production_rollups_sender
+			.send(ProductionRollupsMessage::Run(sender))?;
+		receiver.await?;
+		tracing::info!("production_rollups response received");
+		let (sender, receiver) = oneshot::channel();
+		self.trash_purger_sender
+			.send(TrashPurgerMessage::Run(sender))?;
+		receiver.await?;
+		tracing::info!("trash_purger response received");
+		let (sender, receiver) = oneshot::channel();
+		self.batch_predictor_sender
+			.send(BatchPredictorMessage::Run(sender))?;
+		receiver.await?;
+		tracing::info!("batch_predictor response received");
 		Ok(())
 	}
 
@@ -309,11 +502,60 @@ impl App {
 
 impl AppState {
 	pub async fn begin_transaction(&self) -> Result<sqlx::Transaction<'_, sqlx::Any>> {
-		Ok(self.database_pool.begin().await?)
+		let txn = self.database_pool.begin().await?;
+		request_tracing::record_transaction_begin();
+		Ok(txn)
+	}
+
+	/// Begin a transaction for heavy read only queries, such as those for production stats and predictions. This uses the read replica if one is configured, and the primary database otherwise. The replica may lag behind the primary, so authorization and anything that must observe a recent write should use [`AppState::begin_transaction`] instead.
+	pub async fn begin_read_transaction(&self) -> Result<sqlx::Transaction<'_, sqlx::Any>> {
+		let database_pool = self
+			.database_replica_pool
+			.as_ref()
+			.unwrap_or(&self.database_pool);
+		let txn = database_pool.begin().await?;
+		request_tracing::record_transaction_begin();
+		Ok(txn)
+	}
+
+	/// Begin a read transaction for an analytics query, such as the one behind the production stats page. On PostgreSQL, the `analytics_statement_timeout` is set on the transaction, so the database stops working on a query that runs too long instead of just the app giving up on it.
+	pub async fn begin_analytics_transaction(&self) -> Result<sqlx::Transaction<'_, sqlx::Any>> {
+		let mut txn = self.begin_read_transaction().await?;
+		let is_postgres = self.database_replica_pool.is_some()
+			|| self.options.database.url.scheme() == "postgres";
+		let timeout = match self.options.database.analytics_statement_timeout {
+			Some(timeout) if is_postgres => timeout,
+			_ => return Ok(txn),
+		};
+		sqlx::query(&format!(
+			"set local statement_timeout = {}",
+			timeout.as_millis()
+		))
+		.execute(&mut txn)
+		.await?;
+		Ok(txn)
+	}
+
+	/// Run an analytics query, giving up if it takes longer than the `analytics_statement_timeout`. This returns `None` if the query timed out, so the caller can fall back to cheaper data instead of failing. The query's transaction should be rolled back afterward, because it is left in an unknown state.
+	pub async fn with_analytics_statement_timeout<T>(
+		&self,
+		query: impl Future<Output = Result<T>>,
+	) -> Result<Option<T>> {
+		let timeout = match self.options.database.analytics_statement_timeout {
+			Some(timeout) => timeout,
+			None => return query.await.map(Some),
+		};
+		match tokio::time::timeout(timeout, query).await {
+			Ok(Ok(output)) => Ok(Some(output)),
+			Ok(Err(error)) if is_statement_timeout_error(&error) => Ok(None),
+			Ok(Err(error)) => Err(error),
+			Err(_) => Ok(None),
+		}
 	}
 
 	pub async fn commit_transaction(&self, txn: sqlx::Transaction<'_, sqlx::Any>) -> Result<()> {
 		txn.commit().await?;
+		request_tracing::record_transaction_commit();
 		Ok(())
 	}
 
@@ -365,3 +607,39 @@ pub async fn reset_database(database_url: &Option<Url>) -> Result<()> {
 pub fn path_components(request: &http::Request<hyper::Body>) -> Vec<&str> {
 	request.uri().path().split('/').skip(1).collect::<Vec<_>>()
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_common::*;
+	use std::time::Duration;
+
+	#[tokio::test]
+	async fn test_analytics_statement_timeout() {
+		let mut options = init_test_options();
+		options.database.analytics_statement_timeout = Some(Duration::from_millis(10));
+		let app = App::new(options).await.unwrap();
+		let fast_query = async { Ok(1) };
+		assert_eq!(
+			app.with_analytics_statement_timeout(fast_query)
+				.await
+				.unwrap(),
+			Some(1)
+		);
+		let slow_query = async {
+			tokio::time::sleep(Duration::from_secs(1)).await;
+			Ok(1)
+		};
+		assert_eq!(
+			app.with_analytics_statement_timeout(slow_query)
+				.await
+				.unwrap(),
+			None
+		);
+		let failed_query = async { Err::<i32, _>(anyhow!("failed")) };
+		assert!(app
+			.with_analytics_statement_timeout(failed_query)
+			.await
+			.is_err());
+	}
+}
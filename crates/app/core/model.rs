@@ -1,15 +1,20 @@
 use crate::{
+	heuristics::PREDICT_MODEL_CACHE_MAX_MODELS,
 	repos::add_model_version,
 	storage::{BytesOrFilePath, Storage, StorageEntity},
-	App,
+	App, AppState,
 };
 use anyhow::Result;
+use indexmap::IndexMap;
 use memmap::{Mmap, MmapMut};
 use modelfox_id::Id;
-use sqlx::Acquire;
+use sqlx::{prelude::*, Acquire};
 use std::{
+	borrow::BorrowMut,
+	collections::BTreeMap,
 	io::{Read, Write},
 	path::Path,
+	sync::{Arc, Mutex},
 };
 
 /// Retrieves the model with the specified id.
@@ -29,6 +34,149 @@ pub async fn get_model_bytes(data_storage: &Storage, model_id: Id) -> Result<Mma
 	Ok(mmap)
 }
 
+/// This holds the models loaded by the predict endpoint, so each request does not read and deserialize its model again. A model's bytes never change after it is uploaded, so an entry only has to be removed when its model is deleted. When the cache is full, the model that was loaded longest ago is evicted.
+#[derive(Debug, Default)]
+pub struct PredictModelCache {
+	models: Mutex<IndexMap<Id, Arc<modelfox_core::predict::Model>>>,
+}
+
+impl PredictModelCache {
+	pub fn get(&self, model_id: Id) -> Option<Arc<modelfox_core::predict::Model>> {
+		self.models.lock().unwrap().get(&model_id).cloned()
+	}
+
+	pub fn insert(&self, model_id: Id, model: Arc<modelfox_core::predict::Model>) {
+		let mut models = self.models.lock().unwrap();
+		if !models.contains_key(&model_id) && models.len() >= PREDICT_MODEL_CACHE_MAX_MODELS {
+			models.shift_remove_index(0);
+		}
+		models.insert(model_id, model);
+	}
+
+	pub fn remove(&self, model_id: Id) {
+		self.models.lock().unwrap().shift_remove(&model_id);
+	}
+}
+
+impl App {
+	/// Get the model with the specified id, ready to make predictions with. See [`PredictModelCache`].
+	pub async fn get_predict_model(
+		&self,
+		model_id: Id,
+	) -> Result<Arc<modelfox_core::predict::Model>> {
+		self.state.get_predict_model(model_id).await
+	}
+}
+
+impl AppState {
+	pub async fn get_predict_model(
+		&self,
+		model_id: Id,
+	) -> Result<Arc<modelfox_core::predict::Model>> {
+		if let Some(model) = self.predict_model_cache.get(model_id) {
+			return Ok(model);
+		}
+		let model_bytes = get_model_bytes(&self.storage, model_id).await?;
+		// Deserializing the model is cpu bound, so do it on a blocking thread instead of holding up other requests.
+		let model = tokio::task::spawn_blocking(move || -> Result<_> {
+			let model = modelfox_model::from_bytes(&model_bytes)?;
+			Ok(Arc::new(modelfox_core::predict::Model::from(model)))
+		})
+		.await??;
+		self.predict_model_cache
+			.insert(model_id, Arc::clone(&model));
+		Ok(model)
+	}
+}
+
+/// Get the decision threshold saved for a binary classifier on the tuning page, if any.
+pub async fn get_model_decision_threshold(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Option<f32>> {
+	let row = sqlx::query(
+		"
+			select
+				decision_threshold
+			from models
+			where id = $1
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?;
+	Ok(row.get(0))
+}
+
+/// Save the decision threshold for a binary classifier. Pass `None` to go back to the model's default threshold.
+pub async fn set_model_decision_threshold(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	decision_threshold: Option<f32>,
+) -> Result<()> {
+	sqlx::query(
+		"
+			update models
+			set decision_threshold = $1
+			where id = $2
+		",
+	)
+	.bind(decision_threshold)
+	.bind(&model_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Get the per class thresholds saved for a multiclass classifier on the tuning page. This is empty if none have been saved.
+pub async fn get_model_class_thresholds(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<BTreeMap<String, f32>> {
+	let row = sqlx::query(
+		"
+			select
+				class_thresholds
+			from models
+			where id = $1
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?;
+	let class_thresholds: Option<String> = row.get(0);
+	let class_thresholds = match class_thresholds {
+		Some(class_thresholds) => serde_json::from_str(&class_thresholds)?,
+		None => BTreeMap::new(),
+	};
+	Ok(class_thresholds)
+}
+
+/// Save the per class thresholds for a multiclass classifier. Pass an empty map to go back to always predicting the most probable class.
+pub async fn set_model_class_thresholds(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	class_thresholds: &BTreeMap<String, f32>,
+) -> Result<()> {
+	let class_thresholds = if class_thresholds.is_empty() {
+		None
+	} else {
+		Some(serde_json::to_string(class_thresholds)?)
+	};
+	sqlx::query(
+		"
+			update models
+			set class_thresholds = $1
+			where id = $2
+		",
+	)
+	.bind(class_thresholds)
+	.bind(&model_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
 impl App {
 	pub async fn add_model_to_repo(
 		&self,
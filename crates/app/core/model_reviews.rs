@@ -0,0 +1,213 @@
+use crate::{clock::Clock, user::User};
+use anyhow::Result;
+use modelfox_id::Id;
+use sqlx::prelude::*;
+use std::borrow::BorrowMut;
+
+/// A markdown comment left on a model by a member of its repo.
+pub struct ModelNote {
+	pub id: Id,
+	/// This is `None` if the note was written by the root user or its author has been deleted.
+	pub user_id: Option<Id>,
+	pub author_email: Option<String>,
+	pub content: String,
+	pub created_at: i64,
+}
+
+/// A model is approved for production when a user with the approver role approves it. For repos owned by an organization the approver role is organization admin, and for repos owned by a user it is the owner.
+pub struct ModelApproval {
+	pub approver_email: Option<String>,
+	pub created_at: i64,
+}
+
+fn user_id(user: &User) -> Option<String> {
+	match user {
+		User::Root => None,
+		User::Normal(user) => Some(user.id.to_string()),
+	}
+}
+
+pub async fn create_model_note(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	user: &User,
+	content: &str,
+	clock: &Clock,
+) -> Result<Id> {
+	let id = Id::generate();
+	sqlx::query(
+		"
+			insert into model_notes (
+				id, model_id, user_id, content, created_at
+			) values (
+				$1, $2, $3, $4, $5
+			)
+		",
+	)
+	.bind(&id.to_string())
+	.bind(&model_id.to_string())
+	.bind(user_id(user))
+	.bind(content)
+	.bind(clock.now_utc().unix_timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(id)
+}
+
+pub async fn delete_model_note(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	model_note_id: Id,
+) -> Result<()> {
+	sqlx::query(
+		"
+			delete from model_notes
+			where
+				id = $1
+			and
+				model_id = $2
+		",
+	)
+	.bind(&model_note_id.to_string())
+	.bind(&model_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn get_model_notes(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Vec<ModelNote>> {
+	let rows = sqlx::query(
+		"
+			select
+				model_notes.id,
+				model_notes.user_id,
+				users.email,
+				model_notes.content,
+				model_notes.created_at
+			from model_notes
+			left join users
+				on users.id = model_notes.user_id
+			where model_notes.model_id = $1
+			order by model_notes.created_at
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let id: String = row.get(0);
+			let user_id: Option<String> = row.get(1);
+			let user_id = user_id.map(|user_id| user_id.parse()).transpose()?;
+			Ok(ModelNote {
+				id: id.parse()?,
+				user_id,
+				author_email: row.get(2),
+				content: row.get(3),
+				created_at: row.get(4),
+			})
+		})
+		.collect()
+}
+
+/// Determine whether the user has the approver role for the repo the model belongs to.
+pub async fn can_approve_model(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user: &User,
+	model_id: Id,
+) -> Result<bool> {
+	let user = match user {
+		User::Root => return Ok(true),
+		User::Normal(user) => user,
+	};
+	Ok(sqlx::query(
+		"
+			select
+				count(*) > 0
+			from models
+			join repos
+				on repos.id = models.repo_id
+			left join organizations_users
+				on organizations_users.organization_id = repos.organization_id
+				and organizations_users.user_id = $2
+			where models.id = $1
+			and (
+				repos.user_id = $2
+				or organizations_users.is_admin
+			)
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&user.id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?
+	.get(0))
+}
+
+pub async fn get_model_approval(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Option<ModelApproval>> {
+	let row = sqlx::query(
+		"
+			select
+				users.email,
+				model_approvals.created_at
+			from model_approvals
+			left join users
+				on users.id = model_approvals.user_id
+			where model_approvals.model_id = $1
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	Ok(row.map(|row| ModelApproval {
+		approver_email: row.get(0),
+		created_at: row.get(1),
+	}))
+}
+
+/// Approve the model for production. Approving a model that is already approved keeps the original approval.
+pub async fn approve_model(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	user: &User,
+	clock: &Clock,
+) -> Result<()> {
+	sqlx::query(
+		"
+			insert into model_approvals (
+				model_id, user_id, created_at
+			) values (
+				$1, $2, $3
+			)
+			on conflict (model_id) do nothing
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(user_id(user))
+	.bind(clock.now_utc().unix_timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn revoke_model_approval(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<()> {
+	sqlx::query(
+		"
+			delete from model_approvals
+			where model_id = $1
+		",
+	)
+	.bind(&model_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
@@ -3,7 +3,10 @@ use crate::{
 	clock::Clock,
 };
 use anyhow::Result;
+use chrono::{Datelike, NaiveDate, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
 use modelfox_id::Id;
+use num::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::*;
 use std::{borrow::BorrowMut, fmt, io, str::FromStr};
@@ -18,6 +21,28 @@ pub struct Monitor {
 	pub model_id: Id,
 	pub threshold: MonitorThreshold,
 	pub title: String,
+	/// If this is set, the monitor is not evaluated when fewer than this many true values were recorded in the production metrics it is checked against.
+	#[serde(default)]
+	pub minimum_sample_size: Option<u64>,
+	/// If this is true, a warning is logged when the monitor is not evaluated because of `minimum_sample_size`.
+	#[serde(default)]
+	pub warn_below_minimum_sample_size: bool,
+	/// After the monitor alerts, it does not alert again for this many periods of its cadence.
+	#[serde(default)]
+	pub cooldown_periods: u64,
+	/// If this is set, alert emails and webhook payloads use this template for their message. See [`crate::alert_template`] for the available variables.
+	#[serde(default)]
+	pub message_template: Option<String>,
+	/// If this is true, the monitor only alerts when the whole confidence interval of the production value is past its thresholds, rather than just the value itself.
+	#[serde(default)]
+	pub require_confidence_interval: bool,
+	/// Disabled monitors keep their configuration but are not evaluated, so they never alert.
+	#[serde(default = "default_enabled")]
+	pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+	true
 }
 
 impl Monitor {
@@ -28,11 +53,12 @@ impl Monitor {
 		)
 	}
 
-	/// Check if the given timestamp is more than one cadence interval behind the current time
+	/// Check if the given timestamp is more than one cadence interval behind the current time. If `timezone` is set, the monitor is instead overdue once a period boundary in that timezone has passed since it was last checked, so for example a daily monitor runs at local midnight.
 	pub async fn is_overdue(
 		&self,
 		txn: &mut sqlx::Transaction<'_, sqlx::Any>,
 		clock: &Clock,
+		timezone: Option<Tz>,
 	) -> Result<bool> {
 		// check when the last alert from this cadence was recorded
 		let last_run = {
@@ -68,11 +94,79 @@ impl Monitor {
 		}
 		// Otherwise, unwrap it and calculate the expected next run
 		let last_run = last_run.unwrap();
-		let next_due = self.cadence.add_to_time(last_run);
 		let now = clock.now_utc();
+		if let (Some(timezone), false) = (timezone, self.cadence == MonitorCadence::Testing) {
+			return Ok(self.cadence.period_start(now, timezone) > last_run);
+		}
+		let next_due = self.cadence.add_to_time(last_run);
 		Ok(now >= next_due)
 	}
 
+	/// Get the unix timestamp at which the monitor was last checked, if it has been checked.
+	pub async fn last_checked(
+		&self,
+		txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	) -> Result<Option<i64>> {
+		let row = sqlx::query(
+			"
+				select
+					last_checked
+				from
+					monitors
+				where
+					monitors.id = $1
+			",
+		)
+		.bind(&self.id.to_string())
+		.fetch_optional(txn.borrow_mut())
+		.await?;
+		Ok(row.and_then(|row| row.try_get(0).ok()))
+	}
+
+	/// Get the number of periods remaining in the monitor's cooldown. While it is greater than zero, the monitor does not alert.
+	pub async fn cooldown_remaining(
+		&self,
+		txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	) -> Result<u64> {
+		let row = sqlx::query(
+			"
+				select
+					cooldown_remaining
+				from
+					monitors
+				where
+					monitors.id = $1
+			",
+		)
+		.bind(&self.id.to_string())
+		.fetch_optional(txn.borrow_mut())
+		.await?;
+		let cooldown_remaining: i64 = row.map(|row| row.get(0)).unwrap_or(0);
+		Ok(cooldown_remaining.to_u64().unwrap_or(0))
+	}
+
+	pub async fn set_cooldown_remaining(
+		&self,
+		txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+		cooldown_remaining: u64,
+	) -> Result<()> {
+		sqlx::query(
+			"
+				update
+					monitors
+				set
+					cooldown_remaining = $1
+				where
+					id = $2
+			",
+		)
+		.bind(cooldown_remaining.to_i64().unwrap())
+		.bind(self.id.to_string())
+		.execute(txn.borrow_mut())
+		.await?;
+		Ok(())
+	}
+
 	pub async fn update_timestamp(
 		&self,
 		txn: &mut sqlx::Transaction<'_, sqlx::Any>,
@@ -189,6 +283,24 @@ pub fn extract_threshold_bounds(
 	Ok((lower, upper))
 }
 
+/// Parse the minimum sample size and cooldown periods fields of the monitor forms. An empty minimum sample size means there is no minimum, and an empty cooldown means zero periods.
+pub fn extract_data_requirements(
+	minimum_sample_size: &str,
+	cooldown_periods: &str,
+) -> Result<(Option<u64>, u64)> {
+	let minimum_sample_size = if !minimum_sample_size.is_empty() {
+		Some(minimum_sample_size.trim().parse()?)
+	} else {
+		None
+	};
+	let cooldown_periods = if !cooldown_periods.is_empty() {
+		cooldown_periods.trim().parse()?
+	} else {
+		0
+	};
+	Ok((minimum_sample_size, cooldown_periods))
+}
+
 pub fn validate_threshold_bounds(lower: String, upper: String) -> Option<(String, String)> {
 	let at_least_one = (!lower.is_empty() && !upper.is_empty())
 		|| (lower.is_empty() && !upper.is_empty())
@@ -249,6 +361,32 @@ impl MonitorCadence {
 			}
 		}
 	}
+
+	/// Get the start of the period of this cadence that contains `time`, where periods start at the top of the hour, at midnight, on Monday, or on the first of the month in `timezone`.
+	pub fn period_start(&self, time: OffsetDateTime, timezone: Tz) -> OffsetDateTime {
+		let local_time = Utc
+			.timestamp(time.unix_timestamp(), 0)
+			.with_timezone(&timezone);
+		let local_date = local_time.date().naive_local();
+		let start = match self {
+			MonitorCadence::Testing => return time,
+			MonitorCadence::Hourly => local_date.and_hms(local_time.hour(), 0, 0),
+			MonitorCadence::Daily => local_date.and_hms(0, 0, 0),
+			MonitorCadence::Weekly => {
+				let days_from_monday = local_time.weekday().num_days_from_monday();
+				(local_date - chrono::Duration::days(days_from_monday.into())).and_hms(0, 0, 0)
+			}
+			MonitorCadence::Monthly => {
+				NaiveDate::from_ymd(local_time.year(), local_time.month(), 1).and_hms(0, 0, 0)
+			}
+		};
+		// If the start of the period does not exist in the timezone because of a daylight saving time transition, fall back to the given time.
+		timezone
+			.from_local_datetime(&start)
+			.earliest()
+			.and_then(|start| OffsetDateTime::from_unix_timestamp(start.timestamp()).ok())
+			.unwrap_or(time)
+	}
 }
 
 impl Default for MonitorCadence {
@@ -362,6 +500,54 @@ pub async fn update_monitor(
 	Ok(())
 }
 
+/// Enable or disable the monitors of the model with the given ids. Ids of monitors that belong to other models are ignored.
+pub async fn set_monitors_enabled(
+	db: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	monitor_ids: &[Id],
+	enabled: bool,
+) -> Result<()> {
+	for monitor_id in monitor_ids {
+		let row = sqlx::query(
+			"
+				select
+					data
+				from
+					monitors
+				where
+					id = $1 and model_id = $2
+			",
+		)
+		.bind(monitor_id.to_string())
+		.bind(model_id.to_string())
+		.fetch_optional(db.borrow_mut())
+		.await?;
+		let row = if let Some(row) = row {
+			row
+		} else {
+			continue;
+		};
+		let monitor: String = row.get(0);
+		let mut monitor: Monitor = serde_json::from_str(&monitor)?;
+		monitor.enabled = enabled;
+		sqlx::query(
+			"
+				update
+					monitors
+				set
+					data = $1
+				where
+					id = $2
+			",
+		)
+		.bind(serde_json::to_string(&monitor)?)
+		.bind(monitor_id.to_string())
+		.execute(db.borrow_mut())
+		.await?;
+	}
+	Ok(())
+}
+
 pub async fn check_for_duplicate_monitor(
 	db: &mut sqlx::Transaction<'_, sqlx::Any>,
 	monitor: &Monitor,
@@ -417,6 +603,31 @@ pub async fn get_monitor(
 	Ok(monitor)
 }
 
+pub async fn get_monitors_for_model(
+	db: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Vec<Monitor>> {
+	let rows = sqlx::query(
+		"
+			select
+				data
+			from
+				monitors
+			where
+				model_id = $1
+		",
+	)
+	.bind(model_id.to_string())
+	.fetch_all(db)
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let monitor: String = row.get(0);
+			Ok(serde_json::from_str(&monitor)?)
+		})
+		.collect()
+}
+
 pub async fn delete_monitor(
 	db: &mut sqlx::Transaction<'_, sqlx::Any>,
 	monitor_id: &str,
@@ -432,3 +643,217 @@ pub async fn delete_monitor(
 	.await?;
 	Ok(())
 }
+
+/// The result of a single check of a monitor, recorded whether or not it fired an alert.
+#[derive(Debug, Clone)]
+pub struct MonitorEvaluation {
+	pub date: i64,
+	pub production_value: f32,
+	pub training_value: f32,
+	/// This is the value the thresholds are compared against. See [`Monitor::get_thresholds`].
+	pub difference: f32,
+	pub difference_upper: Option<f32>,
+	pub difference_lower: Option<f32>,
+	pub fired: bool,
+}
+
+impl MonitorEvaluation {
+	/// Whether the difference was outside the thresholds. This may be true even if the evaluation did not fire an alert because the monitor was cooling down.
+	pub fn exceeded_thresholds(&self) -> bool {
+		exceeds_thresholds(
+			self.difference,
+			self.difference_upper,
+			self.difference_lower,
+		)
+	}
+}
+
+/// Check whether a difference between the production and training values is outside a monitor's thresholds. See [`Monitor::get_thresholds`].
+pub fn exceeds_thresholds(difference: f32, upper: Option<f32>, lower: Option<f32>) -> bool {
+	let upper_exceeded = if let Some(upper) = upper {
+		difference > upper
+	} else {
+		false
+	};
+	let lower_exceeded = if let Some(lower) = lower {
+		difference.abs() > lower
+	} else {
+		false
+	};
+	upper_exceeded || lower_exceeded
+}
+
+/// Check whether every difference from `difference_lower` to `difference_upper` is outside a monitor's thresholds, as checked by [`exceeds_thresholds`].
+pub fn interval_exceeds_thresholds(
+	difference_lower: f32,
+	difference_upper: f32,
+	upper: Option<f32>,
+	lower: Option<f32>,
+) -> bool {
+	// These are the bounds of the differences that are within the thresholds.
+	let within_lower = lower.map(|lower| -lower).unwrap_or(f32::NEG_INFINITY);
+	let within_upper = upper
+		.unwrap_or(f32::INFINITY)
+		.min(lower.unwrap_or(f32::INFINITY));
+	difference_upper < within_lower || difference_lower > within_upper
+}
+
+pub async fn create_monitor_evaluation(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	monitor_id: Id,
+	evaluation: &MonitorEvaluation,
+) -> Result<()> {
+	sqlx::query(
+		"
+			insert into monitor_evaluations (
+				id, monitor_id, date, production_value, training_value, difference, difference_upper, difference_lower, fired
+			) values (
+				$1, $2, $3, $4, $5, $6, $7, $8, $9
+			)
+		",
+	)
+	.bind(Id::generate().to_string())
+	.bind(monitor_id.to_string())
+	.bind(evaluation.date)
+	.bind(evaluation.production_value)
+	.bind(evaluation.training_value)
+	.bind(evaluation.difference)
+	.bind(evaluation.difference_upper)
+	.bind(evaluation.difference_lower)
+	.bind(evaluation.fired)
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Get the evaluations of a monitor, oldest first.
+pub async fn get_monitor_evaluations(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	monitor_id: Id,
+) -> Result<Vec<MonitorEvaluation>> {
+	let rows = sqlx::query(
+		"
+			select
+				date,
+				production_value,
+				training_value,
+				difference,
+				difference_upper,
+				difference_lower,
+				fired
+			from monitor_evaluations
+			where monitor_id = $1
+			order by date
+		",
+	)
+	.bind(monitor_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	Ok(rows
+		.iter()
+		.map(|row| MonitorEvaluation {
+			date: row.get(0),
+			production_value: row.get(1),
+			training_value: row.get(2),
+			difference: row.get(3),
+			difference_upper: row.get(4),
+			difference_lower: row.get(5),
+			fired: row.get(6),
+		})
+		.collect())
+}
+
+/// Get the most recent evaluation of a monitor, if it has been evaluated.
+pub async fn get_latest_monitor_evaluation(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	monitor_id: Id,
+) -> Result<Option<MonitorEvaluation>> {
+	let row = sqlx::query(
+		"
+			select
+				date,
+				production_value,
+				training_value,
+				difference,
+				difference_upper,
+				difference_lower,
+				fired
+			from monitor_evaluations
+			where monitor_id = $1
+			order by date desc
+			limit 1
+		",
+	)
+	.bind(monitor_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	Ok(row.map(|row| MonitorEvaluation {
+		date: row.get(0),
+		production_value: row.get(1),
+		training_value: row.get(2),
+		difference: row.get(3),
+		difference_upper: row.get(4),
+		difference_lower: row.get(5),
+		fired: row.get(6),
+	}))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_interval_exceeds_thresholds() {
+		assert!(interval_exceeds_thresholds(0.2, 0.3, Some(0.1), Some(0.1)));
+		assert!(interval_exceeds_thresholds(
+			-0.3,
+			-0.2,
+			Some(0.1),
+			Some(0.1)
+		));
+		assert!(!interval_exceeds_thresholds(
+			0.05,
+			0.3,
+			Some(0.1),
+			Some(0.1)
+		));
+		assert!(!interval_exceeds_thresholds(
+			-0.3,
+			0.3,
+			Some(0.1),
+			Some(0.1)
+		));
+		assert!(interval_exceeds_thresholds(0.2, 0.3, Some(0.1), None));
+		assert!(!interval_exceeds_thresholds(-0.3, -0.2, Some(0.1), None));
+		assert!(!interval_exceeds_thresholds(0.2, 0.3, None, None));
+	}
+
+	#[test]
+	fn test_period_start() {
+		// 2022-06-15 02:30 UTC is 2022-06-14 22:30 in New York, a Tuesday.
+		let time = OffsetDateTime::from_unix_timestamp(1_655_260_200).unwrap();
+		let timezone = Tz::America__New_York;
+		let local = |timestamp: OffsetDateTime| {
+			Utc.timestamp(timestamp.unix_timestamp(), 0)
+				.with_timezone(&timezone)
+				.format("%Y-%m-%d %H:%M")
+				.to_string()
+		};
+		assert_eq!(
+			local(MonitorCadence::Hourly.period_start(time, timezone)),
+			"2022-06-14 22:00"
+		);
+		assert_eq!(
+			local(MonitorCadence::Daily.period_start(time, timezone)),
+			"2022-06-14 00:00"
+		);
+		assert_eq!(
+			local(MonitorCadence::Weekly.period_start(time, timezone)),
+			"2022-06-13 00:00"
+		);
+		assert_eq!(
+			local(MonitorCadence::Monthly.period_start(time, timezone)),
+			"2022-06-01 00:00"
+		);
+	}
+}
@@ -1,5 +1,11 @@
 use crate::{
 	alert::{write_alert, Alert, AlertMethod, AlertMetric, AlertResult},
+	alert_template::validate_alert_template,
+	clock::Clock,
+	cluster::{BackgroundTask, Leadership},
+	custom_metrics::{
+		get_custom_metric, get_custom_metric_examples, get_custom_metric_module, run_custom_metric,
+	},
 	heuristics::{
 		ALERT_METRICS_MINIMUM_PRODUCTION_METRICS_DEBUG_THRESHOLD,
 		ALERT_METRICS_MINIMUM_PRODUCTION_METRICS_THRESHOLD,
@@ -7,15 +13,26 @@ use crate::{
 	},
 	model::get_model_bytes,
 	monitor::{
-		check_for_duplicate_monitor, create_monitor, get_monitor, update_monitor, AlertModelType,
-		Monitor, MonitorCadence, MonitorThreshold, MonitorThresholdMode,
+		check_for_duplicate_monitor, create_monitor, create_monitor_evaluation, exceeds_thresholds,
+		get_latest_monitor_evaluation, get_monitor, interval_exceeds_thresholds, update_monitor,
+		AlertModelType, Monitor, MonitorCadence, MonitorEvaluation, MonitorThreshold,
+		MonitorThresholdMode,
 	},
+	repos::get_model_repo_id,
+	timezone::get_model_owner_timezone,
+	webhooks::{create_webhook_deliveries, MonitorState, WebhookEvent},
 	App, AppState,
 };
 use anyhow::{anyhow, bail, Result};
 use futures::FutureExt;
-use modelfox_app_production_metrics::{ProductionMetrics, ProductionPredictionMetricsOutput};
+use modelfox_app_production_metrics::{
+	ConfidenceInterval, ProductionMetrics, ProductionPredictionMetricsOutput,
+};
+use modelfox_app_production_stats::{
+	ProductionColumnStatsOutput, ProductionStats, ProductionStatsOutput,
+};
 use modelfox_id::Id;
+use num::ToPrimitive;
 use sqlx::prelude::*;
 use std::{borrow::BorrowMut, sync::Arc};
 use tokio::sync::{mpsc, oneshot};
@@ -75,9 +92,12 @@ pub async fn monitor_checker(
 				Some(message) => Event::Message(message),
 			},
 		};
-		let monitors = get_overdue_monitors(&app).await?;
-		for monitor in monitors {
-			app.check_monitor(&monitor).await?;
+		if let Some(leadership) = Leadership::acquire(&app, BackgroundTask::MonitorChecker).await? {
+			let monitors = get_overdue_monitors(&app).await?;
+			for monitor in monitors {
+				app.check_monitor(&monitor).await?;
+			}
+			leadership.release().await?;
 		}
 		tracing::info!("End monitor_checker heartbeat");
 		if let Event::Message(MonitorCheckerMessage::Run(sender)) = event {
@@ -92,9 +112,13 @@ pub async fn get_overdue_monitors(app_state: &AppState) -> Result<Vec<Monitor>>
 	let rows = sqlx::query(
 		"
 			select
-				data
+				monitors.data
 			from
 				monitors
+			join models
+				on models.id = monitors.model_id
+			where
+				models.deleted_at is null
 		",
 	)
 	.fetch_all(txn.borrow_mut())
@@ -108,9 +132,10 @@ pub async fn get_overdue_monitors(app_state: &AppState) -> Result<Vec<Monitor>>
 		.collect();
 	let mut result = Vec::new();
 	// TODO do this in the query, not in Rust.
-	for monitor in monitors {
+	for monitor in monitors.into_iter().filter(|monitor| monitor.enabled) {
+		let timezone = get_model_owner_timezone(txn.borrow_mut(), monitor.model_id).await?;
 		if monitor
-			.is_overdue(txn.borrow_mut(), &app_state.clock)
+			.is_overdue(txn.borrow_mut(), &app_state.clock, timezone)
 			.await?
 		{
 			result.push(monitor);
@@ -126,6 +151,14 @@ pub async fn find_current_training_metric(
 	model_id: Id,
 	app_state: &AppState,
 ) -> Result<f32> {
+	// Custom metrics, the out of range fraction, the unseen token fraction, and the model age have no training value. Their thresholds apply to the production value itself.
+	if let AlertMetric::Custom { .. }
+	| AlertMetric::OutOfRangeFraction
+	| AlertMetric::UnseenTokenFraction
+	| AlertMetric::ModelAge = metric
+	{
+		return Ok(0.0);
+	}
 	// Grab the model from the DB
 	let bytes = get_model_bytes(&app_state.storage, model_id).await?;
 	let model = modelfox_model::from_bytes(&bytes)?;
@@ -176,6 +209,26 @@ pub async fn find_current_training_metric(
 				_ => unreachable!(),
 			}
 		}
+		AlertMetric::MeanAbsoluteError => match model_inner {
+			modelfox_model::ModelInnerReader::Regressor(regressor) => {
+				regressor.read().test_metrics().mae()
+			}
+			_ => unreachable!(),
+		},
+		// An unbiased model's residuals average to zero, so a threshold on the mean residual bounds the model's bias.
+		AlertMetric::MeanResidual => 0.0,
+		AlertMetric::ToleranceAccuracy => match model_inner {
+			modelfox_model::ModelInnerReader::Regressor(regressor) => regressor
+				.read()
+				.test_metrics()
+				.tolerance_accuracy()
+				.ok_or_else(|| anyhow!("The model was not trained with a regression tolerance"))?,
+			_ => unreachable!(),
+		},
+		AlertMetric::OutOfRangeFraction
+		| AlertMetric::UnseenTokenFraction
+		| AlertMetric::ModelAge
+		| AlertMetric::Custom { .. } => unreachable!(),
 	};
 	Ok(result)
 }
@@ -187,7 +240,7 @@ impl App {
 		Ok(())
 	}
 
-	pub async fn create_monitor(&self, args: CreateMonitorArgs<'_, '_>) -> Result<()> {
+	pub async fn create_monitor(&self, args: CreateMonitorArgs<'_, '_>) -> Result<Id> {
 		let CreateMonitorArgs {
 			db,
 			cadence,
@@ -195,6 +248,11 @@ impl App {
 			model_id,
 			threshold,
 			title,
+			minimum_sample_size,
+			warn_below_minimum_sample_size,
+			cooldown_periods,
+			message_template,
+			require_confidence_interval,
 		} = args;
 		let mut monitor = Monitor {
 			cadence,
@@ -203,15 +261,27 @@ impl App {
 			model_id,
 			threshold,
 			title: title.to_owned(),
+			minimum_sample_size,
+			warn_below_minimum_sample_size,
+			cooldown_periods,
+			message_template,
+			require_confidence_interval,
+			enabled: true,
 		};
 		if monitor.title.is_empty() {
 			monitor.title = monitor.default_title();
 		}
+		validate_custom_metric_threshold(db, model_id, &monitor.threshold).await?;
+		validate_confidence_interval_requirement(&monitor)?;
+		if let Some(message_template) = &monitor.message_template {
+			validate_alert_template(message_template)?;
+		}
 		if check_for_duplicate_monitor(db, &monitor, model_id).await? {
 			bail!("Identical alert already exists");
 		}
+		let monitor_id = monitor.id;
 		create_monitor(db, monitor, model_id).await?;
-		Ok(())
+		Ok(monitor_id)
 	}
 
 	pub async fn create_monitor_from_config(
@@ -234,6 +304,11 @@ impl App {
 			model_id,
 			threshold: config.threshold,
 			title,
+			minimum_sample_size: config.minimum_sample_size,
+			warn_below_minimum_sample_size: config.warn_below_minimum_sample_size,
+			cooldown_periods: config.cooldown_periods,
+			message_template: config.message_template.clone(),
+			require_confidence_interval: config.require_confidence_interval,
 		})
 		.await?;
 
@@ -250,6 +325,11 @@ impl App {
 			model_id,
 			threshold,
 			title,
+			minimum_sample_size,
+			warn_below_minimum_sample_size,
+			cooldown_periods,
+			message_template,
+			require_confidence_interval,
 		} = args;
 		let mut monitor = get_monitor(db, monitor_id).await?;
 		let mut title = title.to_owned();
@@ -269,6 +349,16 @@ impl App {
 		if title != monitor.title {
 			monitor.title = title;
 		}
+		monitor.minimum_sample_size = minimum_sample_size;
+		monitor.warn_below_minimum_sample_size = warn_below_minimum_sample_size;
+		monitor.cooldown_periods = cooldown_periods;
+		monitor.message_template = message_template;
+		monitor.require_confidence_interval = require_confidence_interval;
+		validate_custom_metric_threshold(db, model_id, &monitor.threshold).await?;
+		validate_confidence_interval_requirement(&monitor)?;
+		if let Some(message_template) = &monitor.message_template {
+			validate_alert_template(message_template)?;
+		}
 		if check_for_duplicate_monitor(db, &monitor, model_id).await? {
 			return Err(anyhow!("Identical alert already exists"));
 		}
@@ -277,6 +367,66 @@ impl App {
 	}
 }
 
+/// Confidence intervals are only computed for the production metrics, so only monitors on those can require them.
+fn validate_confidence_interval_requirement(monitor: &Monitor) -> Result<()> {
+	if monitor.require_confidence_interval && !monitor.threshold.metric.has_confidence_interval() {
+		bail!(
+			"A confidence interval is not computed for {}, so the monitor cannot require one",
+			monitor.threshold.metric
+		);
+	}
+	Ok(())
+}
+
+/// Check that a threshold on a custom metric refers to a custom metric uploaded to the model's repo. Custom metrics, the out of range fraction, the unseen token fraction, and the model age have no training value to compare against, and the mean residual's is zero, so only absolute thresholds are allowed for them.
+async fn validate_custom_metric_threshold(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	threshold: &MonitorThreshold,
+) -> Result<()> {
+	let custom_metric_id = match threshold.metric {
+		AlertMetric::Custom { custom_metric_id } => custom_metric_id,
+		AlertMetric::OutOfRangeFraction => {
+			if threshold.mode != MonitorThresholdMode::Absolute {
+				bail!("The out of range fraction only supports absolute thresholds");
+			}
+			return Ok(());
+		}
+		AlertMetric::UnseenTokenFraction => {
+			if threshold.mode != MonitorThresholdMode::Absolute {
+				bail!("The unseen token fraction only supports absolute thresholds");
+			}
+			return Ok(());
+		}
+		AlertMetric::ModelAge => {
+			if threshold.mode != MonitorThresholdMode::Absolute {
+				bail!("The model age only supports absolute thresholds");
+			}
+			return Ok(());
+		}
+		// The mean residual's training value is zero, so a relative threshold would divide by zero.
+		AlertMetric::MeanResidual => {
+			if threshold.mode != MonitorThresholdMode::Absolute {
+				bail!("The mean residual only supports absolute thresholds");
+			}
+			return Ok(());
+		}
+		_ => return Ok(()),
+	};
+	let repo_id = get_model_repo_id(txn, model_id).await?;
+	let custom_metric = get_custom_metric(txn, custom_metric_id).await?;
+	if !custom_metric
+		.map(|custom_metric| custom_metric.repo_id == repo_id)
+		.unwrap_or(false)
+	{
+		bail!("Custom metric not found");
+	}
+	if threshold.mode != MonitorThresholdMode::Absolute {
+		bail!("Custom metrics only support absolute thresholds");
+	}
+	Ok(())
+}
+
 impl AppState {
 	pub async fn check_monitor(&self, monitor: &Monitor) -> Result<()> {
 		bring_monitor_up_to_date(self, monitor).await?;
@@ -291,6 +441,11 @@ pub struct CreateMonitorArgs<'a, 't> {
 	pub model_id: Id,
 	pub threshold: MonitorThreshold,
 	pub title: &'a str,
+	pub minimum_sample_size: Option<u64>,
+	pub warn_below_minimum_sample_size: bool,
+	pub cooldown_periods: u64,
+	pub message_template: Option<String>,
+	pub require_confidence_interval: bool,
 }
 
 pub struct UpdateMonitorArgs<'a, 't> {
@@ -301,6 +456,11 @@ pub struct UpdateMonitorArgs<'a, 't> {
 	pub model_id: Id,
 	pub threshold: MonitorThreshold,
 	pub title: &'a str,
+	pub minimum_sample_size: Option<u64>,
+	pub warn_below_minimum_sample_size: bool,
+	pub cooldown_periods: u64,
+	pub message_template: Option<String>,
+	pub require_confidence_interval: bool,
 }
 
 pub struct MonitorConfig {
@@ -308,6 +468,11 @@ pub struct MonitorConfig {
 	pub threshold: MonitorThreshold,
 	pub title: Option<String>,
 	pub methods: Vec<AlertMethod>,
+	pub minimum_sample_size: Option<u64>,
+	pub warn_below_minimum_sample_size: bool,
+	pub cooldown_periods: u64,
+	pub message_template: Option<String>,
+	pub require_confidence_interval: bool,
 }
 
 pub async fn bring_monitor_up_to_date(app_state: &AppState, monitor: &Monitor) -> Result<()> {
@@ -319,29 +484,104 @@ pub async fn bring_monitor_up_to_date(app_state: &AppState, monitor: &Monitor) -
 
 	let mut txn = app_state.begin_transaction().await?;
 
-	let not_enough_existing_metrics =
-		get_total_production_metrics(txn.borrow_mut()).await? < minimum_metrics_threshold;
+	let uses_production_data = monitor.threshold.metric.uses_production_data();
+	let not_enough_existing_metrics = uses_production_data
+		&& get_total_production_metrics(txn.borrow_mut()).await? < minimum_metrics_threshold;
 	if not_enough_existing_metrics {
 		return Ok(());
 	}
 
-	let result = check_metrics(monitor, app_state).await?;
-	let exceeded_thresholds: bool = {
-		let (upper, lower) = monitor.get_thresholds();
-		let upper_exceeded = if let Some(upper) = upper {
-			result.difference > upper
-		} else {
-			false
+	// A period of the monitor's cadence has passed since it was last checked, so the cooldown is one period shorter.
+	let cooldown_remaining = monitor.cooldown_remaining(txn.borrow_mut()).await?;
+	if cooldown_remaining > 0 {
+		monitor
+			.set_cooldown_remaining(txn.borrow_mut(), cooldown_remaining - 1)
+			.await?;
+	}
+
+	let (result, true_values_count, difference_confidence_interval) =
+		match check_metrics(monitor, app_state).await? {
+			Some(result) => result,
+			None => {
+				app_state.commit_transaction(txn).await?;
+				return Ok(());
+			}
 		};
-		let lower_exceeded = if let Some(lower) = lower {
-			result.difference.abs() > lower
+	let minimum_sample_size = monitor.minimum_sample_size.filter(|_| uses_production_data);
+	if let Some(minimum_sample_size) = minimum_sample_size {
+		if true_values_count < minimum_sample_size {
+			if monitor.warn_below_minimum_sample_size {
+				tracing::warn!(
+					monitor_id = %monitor.id,
+					true_values_count,
+					minimum_sample_size,
+					"skipped monitor because too few true values were recorded"
+				);
+			}
+			app_state.commit_transaction(txn).await?;
+			return Ok(());
+		}
+	}
+
+	let (upper, lower) = monitor.get_thresholds();
+	let exceeded_thresholds = exceeds_thresholds(result.difference, upper, lower);
+	// If the monitor requires it, the thresholds are only exceeded if the whole confidence interval of the difference is past them. Without a confidence interval, for example because too few true values were logged, the monitor does not alert.
+	let exceeded_thresholds = if monitor.require_confidence_interval {
+		exceeded_thresholds
+			&& difference_confidence_interval
+				.map(|interval| {
+					interval_exceeds_thresholds(interval.lower, interval.upper, upper, lower)
+				})
+				.unwrap_or(false)
+	} else {
+		exceeded_thresholds
+	};
+
+	let fired = exceeded_thresholds && cooldown_remaining == 0;
+
+	// Notify webhooks when the monitor starts or stops exceeding its thresholds. A monitor that has never been evaluated is considered ok.
+	let previously_exceeded_thresholds =
+		get_latest_monitor_evaluation(txn.borrow_mut(), monitor.id)
+			.await?
+			.map(|evaluation| evaluation.exceeded_thresholds())
+			.unwrap_or(false);
+	if exceeded_thresholds != previously_exceeded_thresholds {
+		let repo_id = get_model_repo_id(txn.borrow_mut(), monitor.model_id).await?;
+		let state = if exceeded_thresholds {
+			MonitorState::Alerting
 		} else {
-			false
+			MonitorState::Ok
 		};
-		upper_exceeded || lower_exceeded
-	};
+		create_webhook_deliveries(
+			txn.borrow_mut(),
+			&app_state.clock,
+			repo_id,
+			&WebhookEvent::MonitorStateChanged {
+				monitor_id: monitor.id,
+				model_id: monitor.model_id,
+				state,
+			},
+		)
+		.await?;
+	}
 
-	if exceeded_thresholds {
+	// Record every evaluation so the monitor's page can chart the metric against its thresholds, including near misses.
+	create_monitor_evaluation(
+		txn.borrow_mut(),
+		monitor.id,
+		&MonitorEvaluation {
+			date: app_state.clock.now_utc().unix_timestamp(),
+			production_value: result.production_value,
+			training_value: result.training_value,
+			difference: result.difference,
+			difference_upper: upper,
+			difference_lower: lower,
+			fired,
+		},
+	)
+	.await?;
+
+	if fired {
 		let alert_data = Alert {
 			id: Id::generate(),
 			monitor: monitor.to_owned(),
@@ -349,49 +589,228 @@ pub async fn bring_monitor_up_to_date(app_state: &AppState, monitor: &Monitor) -
 			timestamp: time::OffsetDateTime::now_utc().unix_timestamp(),
 		};
 		write_alert(app_state, alert_data, monitor.id, txn.borrow_mut()).await?;
+		monitor
+			.set_cooldown_remaining(txn.borrow_mut(), monitor.cooldown_periods)
+			.await?;
 	}
 	app_state.commit_transaction(txn).await?;
 
 	Ok(())
 }
 
-/// Return the current observed values for each heuristic
-async fn check_metrics(monitor: &Monitor, app_state: &AppState) -> Result<AlertResult> {
+/// Return the current observed values for each heuristic, along with the number of true values the production value was computed from and the confidence interval of the difference, if one was computed. This returns `None` if a custom metric could not be computed.
+async fn check_metrics(
+	monitor: &Monitor,
+	app_state: &AppState,
+) -> Result<Option<(AlertResult, u64, Option<ConfidenceInterval>)>> {
 	let current_training_value =
 		find_current_training_metric(monitor.threshold.metric, monitor.model_id, app_state).await?;
 	let mut txn = app_state.begin_transaction().await?;
-	let current_production_value =
-		get_production_metric(monitor.threshold.metric, monitor.model_id, txn.borrow_mut()).await?;
-	if current_production_value.is_none() {
-		return Err(anyhow!("Unable to find production metric value"));
-	}
-	let current_production_value = current_production_value.unwrap();
-	let observed_difference = match monitor.threshold.mode {
-		MonitorThresholdMode::Absolute => current_production_value - current_training_value,
+	let mut production_confidence_interval = None;
+	let current_production_value = match monitor.threshold.metric {
+		AlertMetric::Custom { custom_metric_id } => {
+			get_custom_production_metric(monitor, custom_metric_id, app_state, txn.borrow_mut())
+				.await?
+		}
+		AlertMetric::OutOfRangeFraction => {
+			get_out_of_range_fraction(monitor.model_id, txn.borrow_mut()).await?
+		}
+		AlertMetric::UnseenTokenFraction => {
+			get_unseen_token_fraction(monitor.model_id, txn.borrow_mut()).await?
+		}
+		AlertMetric::ModelAge => {
+			get_model_age(monitor.model_id, &app_state.clock, txn.borrow_mut()).await?
+		}
+		metric => {
+			let current_production_value =
+				get_production_metric(metric, monitor.model_id, txn.borrow_mut()).await?;
+			let (current_production_value, true_values_count, confidence_interval) =
+				match current_production_value {
+					Some(current_production_value) => current_production_value,
+					None => return Err(anyhow!("Unable to find production metric value")),
+				};
+			production_confidence_interval = confidence_interval;
+			Some((current_production_value, true_values_count))
+		}
+	};
+	// Update monitor last-checked time
+	monitor
+		.update_timestamp(txn.borrow_mut(), &app_state.clock)
+		.await?;
+	app_state.commit_transaction(txn).await?;
+	let (current_production_value, true_values_count) = match current_production_value {
+		Some(current_production_value) => current_production_value,
+		None => return Ok(None),
+	};
+	let difference = |production_value: f32| match monitor.threshold.mode {
+		MonitorThresholdMode::Absolute => production_value - current_training_value,
 		MonitorThresholdMode::Percentage => {
-			((current_production_value - current_training_value) / current_training_value) * 100.0
+			((production_value - current_training_value) / current_training_value) * 100.0
 		}
 	};
+	let observed_difference = difference(current_production_value);
+	let difference_confidence_interval = production_confidence_interval.map(|interval| {
+		let (a, b) = (difference(interval.lower), difference(interval.upper));
+		ConfidenceInterval {
+			lower: a.min(b),
+			upper: a.max(b),
+		}
+	});
 	let result = AlertResult {
 		metric: monitor.threshold.metric,
 		production_value: current_production_value,
 		training_value: current_training_value,
 		difference: observed_difference,
 	};
-	// Update monitor last-checked time
-	monitor
-		.update_timestamp(txn.borrow_mut(), &app_state.clock)
-		.await?;
-	app_state.commit_transaction(txn).await?;
-	Ok(result)
+	Ok(Some((
+		result,
+		true_values_count,
+		difference_confidence_interval,
+	)))
+}
+
+/// Compute a custom metric over the examples whose true values were logged since the monitor was last checked, along with the number of examples. Errors from the custom metric's module are logged rather than returned, so a broken module does not stop the other monitors from being checked.
+async fn get_custom_production_metric(
+	monitor: &Monitor,
+	custom_metric_id: Id,
+	app_state: &AppState,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Option<(f32, u64)>> {
+	let since = monitor.last_checked(txn).await?;
+	let examples = get_custom_metric_examples(txn, monitor.model_id, since).await?;
+	if examples.is_empty() {
+		return Ok(None);
+	}
+	let true_values_count = examples.len().to_u64().unwrap();
+	let module = get_custom_metric_module(&app_state.storage, custom_metric_id).await?;
+	let value = tokio::task::spawn_blocking(move || run_custom_metric(&module, &examples)).await?;
+	match value {
+		Ok(value) => Ok(Some((value, true_values_count))),
+		Err(error) => {
+			tracing::error!(
+				monitor_id = %monitor.id,
+				%custom_metric_id,
+				%error,
+				"failed to compute custom metric"
+			);
+			Ok(None)
+		}
+	}
 }
 
-/// Retrieve the latest value for the given metric from the production_metrics table
+/// Retrieve the largest fraction of values outside the training range across the number columns in the latest production_stats row, along with the number of valid values in that column.
+pub async fn get_out_of_range_fraction(
+	model_id: Id,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Option<(f32, u64)>> {
+	let production_stats = match get_latest_production_stats(model_id, txn).await? {
+		Some(production_stats) => production_stats,
+		None => return Ok(None),
+	};
+	let out_of_range_fraction = production_stats
+		.column_stats
+		.into_iter()
+		.filter_map(|column_stats| match column_stats {
+			ProductionColumnStatsOutput::Number(column_stats) => {
+				let out_of_range_fraction = column_stats.out_of_range_fraction()?;
+				let valid_count = column_stats.stats.as_ref()?.n;
+				Some((out_of_range_fraction, valid_count))
+			}
+			_ => None,
+		})
+		.max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+	Ok(out_of_range_fraction)
+}
+
+/// Retrieve the largest fraction of tokens not in the training vocabulary across the text columns in the latest production_stats row, along with the number of tokens in that column.
+async fn get_unseen_token_fraction(
+	model_id: Id,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Option<(f32, u64)>> {
+	let production_stats = match get_latest_production_stats(model_id, txn).await? {
+		Some(production_stats) => production_stats,
+		None => return Ok(None),
+	};
+	let unseen_token_fraction = production_stats
+		.column_stats
+		.into_iter()
+		.filter_map(|column_stats| match column_stats {
+			ProductionColumnStatsOutput::Text(column_stats) => {
+				let unseen_token_fraction = column_stats.unseen_token_fraction()?;
+				Some((unseen_token_fraction, column_stats.token_count))
+			}
+			_ => None,
+		})
+		.max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+	Ok(unseen_token_fraction)
+}
+
+/// Compute the number of days since the newest model in the model's repo was uploaded. Uploading a retrained model to the repo resets the age for every monitor in it. The count is always zero because the age does not depend on tracked data.
+async fn get_model_age(
+	model_id: Id,
+	clock: &Clock,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Option<(f32, u64)>> {
+	let row = sqlx::query(
+		"
+			select
+				max(created_at)
+			from
+				models
+			where
+				repo_id = (select repo_id from models where id = $1)
+		",
+	)
+	.bind(model_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?;
+	let created_at: Option<i64> = row.get(0);
+	let created_at = match created_at {
+		Some(created_at) => created_at,
+		None => return Ok(None),
+	};
+	let age = (clock.now_utc().unix_timestamp() - created_at).max(0);
+	let age_days = age.to_f32().unwrap() / (24.0 * 60.0 * 60.0);
+	Ok(Some((age_days, 0)))
+}
+
+/// Retrieve the latest production_stats row for the model.
+async fn get_latest_production_stats(
+	model_id: Id,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<Option<ProductionStatsOutput>> {
+	let row = sqlx::query(
+		"
+			select
+				data
+			from
+				production_stats
+			where
+				model_id = $1
+			order by
+				hour
+			desc
+			limit 1
+		",
+	)
+	.bind(model_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	let row = match row {
+		Some(row) => row,
+		None => return Ok(None),
+	};
+	let data: String = row.get(0);
+	let production_stats: ProductionStats = serde_json::from_str(&data)?;
+	Ok(Some(production_stats.finalize()))
+}
+
+/// Retrieve the latest value for the given metric from the production_metrics table, along with the number of true values it was computed from and its confidence interval.
 pub async fn get_production_metric(
 	metric: AlertMetric,
 	model_id: Id,
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
-) -> Result<Option<f32>> {
+) -> Result<Option<(f32, u64, Option<ConfidenceInterval>)>> {
 	let row = sqlx::query(
 		"
 			select
@@ -412,21 +831,52 @@ pub async fn get_production_metric(
 	if let Some(row) = row {
 		let data: String = row.get(0);
 		let production_metrics: ProductionMetrics = serde_json::from_str(&data)?;
+		let true_values_count = production_metrics.true_values_count;
 		let output = production_metrics.finalize();
+		let confidence_intervals = output.confidence_intervals;
 		let metrics = output.prediction_metrics;
 		if let Some(metrics) = metrics {
 			match metrics {
 				ProductionPredictionMetricsOutput::Regression(r) => match metric {
-					AlertMetric::MeanSquaredError => Ok(Some(r.mse)),
-					AlertMetric::RootMeanSquaredError => Ok(Some(r.rmse)),
+					AlertMetric::MeanSquaredError => {
+						Ok(Some((r.mse, true_values_count, confidence_intervals.mse)))
+					}
+					AlertMetric::RootMeanSquaredError => {
+						Ok(Some((r.rmse, true_values_count, confidence_intervals.rmse)))
+					}
+					AlertMetric::MeanAbsoluteError => {
+						Ok(Some((r.mae, true_values_count, confidence_intervals.mae)))
+					}
+					AlertMetric::MeanResidual => Ok(Some((
+						r.mean_residual,
+						true_values_count,
+						confidence_intervals.mean_residual,
+					))),
+					AlertMetric::ToleranceAccuracy => {
+						Ok(r.tolerance_accuracy.map(|tolerance_accuracy| {
+							(
+								tolerance_accuracy,
+								true_values_count,
+								confidence_intervals.tolerance_accuracy,
+							)
+						}))
+					}
 					_ => Ok(None),
 				},
 				ProductionPredictionMetricsOutput::BinaryClassification(bc) => match metric {
-					AlertMetric::Accuracy => Ok(Some(bc.accuracy)),
+					AlertMetric::Accuracy => Ok(Some((
+						bc.accuracy,
+						true_values_count,
+						confidence_intervals.accuracy,
+					))),
 					_ => Ok(None),
 				},
 				ProductionPredictionMetricsOutput::MulticlassClassification(mc) => match metric {
-					AlertMetric::Accuracy => Ok(Some(mc.accuracy)),
+					AlertMetric::Accuracy => Ok(Some((
+						mc.accuracy,
+						true_values_count,
+						confidence_intervals.accuracy,
+					))),
 					_ => Ok(None),
 				},
 			}
@@ -455,7 +905,10 @@ async fn get_total_production_metrics(txn: &mut sqlx::Transaction<'_, sqlx::Any>
 #[cfg(test)]
 mod test {
 	use super::*;
-	use crate::test_common::*;
+	use crate::{
+		monitor::{get_monitor_evaluations, get_monitors_for_model, set_monitors_enabled},
+		test_common::*,
+	};
 	use tracing_test::traced_test;
 
 	#[tokio::test]
@@ -572,6 +1025,54 @@ mod test {
 		//ctx.drop().await;
 	}
 
+	#[tokio::test]
+	async fn test_get_overdue_monitors_skips_disabled_monitors() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		seed_monitors(&app, model_id).await.unwrap();
+		assert_eq!(get_overdue_monitors(&app.state).await.unwrap().len(), 4);
+		let mut txn = app.begin_transaction().await.unwrap();
+		let monitor_ids = get_monitors_for_model(txn.borrow_mut(), model_id)
+			.await
+			.unwrap()
+			.iter()
+			.map(|monitor| monitor.id)
+			.take(2)
+			.collect::<Vec<_>>();
+		set_monitors_enabled(txn.borrow_mut(), model_id, &monitor_ids, false)
+			.await
+			.unwrap();
+		app.commit_transaction(txn).await.unwrap();
+		let overdue_monitors = get_overdue_monitors(&app.state).await.unwrap();
+		assert_eq!(overdue_monitors.len(), 2);
+		assert!(overdue_monitors
+			.iter()
+			.all(|monitor| !monitor_ids.contains(&monitor.id)));
+		let mut txn = app.begin_transaction().await.unwrap();
+		set_monitors_enabled(txn.borrow_mut(), model_id, &monitor_ids, true)
+			.await
+			.unwrap();
+		app.commit_transaction(txn).await.unwrap();
+		assert_eq!(get_overdue_monitors(&app.state).await.unwrap().len(), 4);
+	}
+
+	#[tokio::test]
+	async fn test_get_model_age() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		let uploaded_at = app.clock().now_utc();
+		app.clock()
+			.set_mock_time(uploaded_at + time::Duration::days(10) + time::Duration::hours(12));
+		let mut txn = app.begin_transaction().await.unwrap();
+		let (age_days, count) = get_model_age(model_id, app.clock(), txn.borrow_mut())
+			.await
+			.unwrap()
+			.unwrap();
+		app.commit_transaction(txn).await.unwrap();
+		assert!((age_days - 10.5).abs() < 1e-3);
+		assert_eq!(count, 0);
+	}
+
 	#[tokio::test]
 	#[traced_test]
 	async fn test_resolve_monitor_lower_absolute() {
@@ -593,6 +1094,11 @@ mod test {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		};
 		seed_single_monitor(&app, &test_monitor, model_id)
 			.await
@@ -638,6 +1144,23 @@ mod test {
 			.unwrap();
 		app.commit_transaction(txn).await.unwrap();
 		assert_eq!(all_alerts.len(), 1);
+
+		// Both checks are recorded, including the one that did not fire.
+		let mut txn = app.begin_transaction().await.unwrap();
+		let monitor = get_monitors_for_model(txn.borrow_mut(), model_id)
+			.await
+			.unwrap()
+			.remove(0);
+		let evaluations = get_monitor_evaluations(txn.borrow_mut(), monitor.id)
+			.await
+			.unwrap();
+		app.commit_transaction(txn).await.unwrap();
+		let fired = evaluations
+			.iter()
+			.map(|evaluation| evaluation.fired)
+			.collect::<Vec<_>>();
+		assert_eq!(fired, vec![true, false]);
+		assert_eq!(evaluations[0].difference_lower, Some(0.1));
 	}
 
 	#[tokio::test]
@@ -661,6 +1184,11 @@ mod test {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		};
 		seed_single_monitor(&app, &test_monitor, model_id)
 			.await
@@ -732,6 +1260,11 @@ mod test {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		};
 		seed_single_monitor(&app, &test_monitor, model_id)
 			.await
@@ -800,6 +1333,11 @@ mod test {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		};
 		seed_single_monitor(&app, &test_monitor, model_id)
 			.await
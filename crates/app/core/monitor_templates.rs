@@ -0,0 +1,226 @@
+use crate::{
+	alert::AlertMethod,
+	clock::Clock,
+	model::get_model_bytes,
+	monitor::{
+		check_for_duplicate_monitor, create_monitor, AlertModelType, Monitor, MonitorCadence,
+		MonitorThreshold,
+	},
+	storage::Storage,
+};
+use anyhow::Result;
+use modelfox_id::Id;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::*;
+use std::borrow::BorrowMut;
+
+/// A monitor template describes a monitor that is created automatically for every model version uploaded to a repo.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MonitorTemplate {
+	pub cadence: MonitorCadence,
+	pub id: Id,
+	pub methods: Vec<AlertMethod>,
+	pub threshold: MonitorThreshold,
+	pub title: String,
+	#[serde(default)]
+	pub minimum_sample_size: Option<u64>,
+	#[serde(default)]
+	pub warn_below_minimum_sample_size: bool,
+	#[serde(default)]
+	pub cooldown_periods: u64,
+	#[serde(default)]
+	pub message_template: Option<String>,
+	#[serde(default)]
+	pub require_confidence_interval: bool,
+}
+
+impl MonitorTemplate {
+	/// Create a template from an existing monitor so that every future model version in the repo gets the same monitor.
+	pub fn from_monitor(monitor: &Monitor) -> MonitorTemplate {
+		MonitorTemplate {
+			cadence: monitor.cadence,
+			id: Id::generate(),
+			methods: monitor.methods.clone(),
+			threshold: monitor.threshold,
+			title: monitor.title.clone(),
+			minimum_sample_size: monitor.minimum_sample_size,
+			warn_below_minimum_sample_size: monitor.warn_below_minimum_sample_size,
+			cooldown_periods: monitor.cooldown_periods,
+			message_template: monitor.message_template.clone(),
+			require_confidence_interval: monitor.require_confidence_interval,
+		}
+	}
+}
+
+pub async fn create_monitor_template(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+	template: &MonitorTemplate,
+	clock: &Clock,
+) -> Result<()> {
+	let template_json = serde_json::to_string(template)?;
+	sqlx::query(
+		"
+			insert into monitor_templates (
+				id, repo_id, data, created_at
+			) values (
+				$1, $2, $3, $4
+			)
+		",
+	)
+	.bind(&template.id.to_string())
+	.bind(&repo_id.to_string())
+	.bind(template_json)
+	.bind(clock.now_utc().unix_timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn delete_monitor_template(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+	monitor_template_id: Id,
+) -> Result<()> {
+	sqlx::query(
+		"
+			delete from monitor_templates
+			where
+				id = $1
+			and
+				repo_id = $2
+		",
+	)
+	.bind(&monitor_template_id.to_string())
+	.bind(&repo_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn get_monitor_templates(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+) -> Result<Vec<MonitorTemplate>> {
+	let rows = sqlx::query(
+		"
+			select
+				monitor_templates.data
+			from monitor_templates
+			where monitor_templates.repo_id = $1
+			order by monitor_templates.created_at
+		",
+	)
+	.bind(&repo_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let data: String = row.get(0);
+			Ok(serde_json::from_str(&data)?)
+		})
+		.collect()
+}
+
+/// Create a monitor on the given model for each of the repo's monitor templates. Templates whose metric does not apply to the model's task, such as the RMSE for a classifier, and templates that would duplicate a monitor the model already has are skipped.
+pub async fn apply_monitor_templates(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	storage: &Storage,
+	repo_id: Id,
+	model_id: Id,
+) -> Result<()> {
+	let templates = get_monitor_templates(txn, repo_id).await?;
+	if templates.is_empty() {
+		return Ok(());
+	}
+	let bytes = get_model_bytes(storage, model_id).await?;
+	let model = modelfox_model::from_bytes(&bytes)?;
+	let model_type = AlertModelType::from(model.inner());
+	for template in templates {
+		if !template.threshold.metric.validate(model_type) {
+			continue;
+		}
+		let monitor = Monitor {
+			cadence: template.cadence,
+			id: Id::generate(),
+			methods: template.methods,
+			model_id,
+			threshold: template.threshold,
+			title: template.title,
+			minimum_sample_size: template.minimum_sample_size,
+			warn_below_minimum_sample_size: template.warn_below_minimum_sample_size,
+			cooldown_periods: template.cooldown_periods,
+			message_template: template.message_template,
+			require_confidence_interval: template.require_confidence_interval,
+			enabled: true,
+		};
+		if check_for_duplicate_monitor(txn, &monitor, model_id).await? {
+			continue;
+		}
+		create_monitor(txn, monitor, model_id).await?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		alert::AlertMetric,
+		monitor::{get_monitors_for_model, MonitorThresholdMode},
+		test_common::*,
+	};
+
+	fn template(title: &str, metric: AlertMetric) -> MonitorTemplate {
+		MonitorTemplate {
+			cadence: MonitorCadence::Daily,
+			id: Id::generate(),
+			methods: vec![AlertMethod::Stdout],
+			threshold: MonitorThreshold {
+				metric,
+				mode: MonitorThresholdMode::Absolute,
+				difference_lower: Some(0.1),
+				difference_upper: None,
+			},
+			title: title.to_owned(),
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_apply_monitor_templates_skips_metrics_for_other_tasks() {
+		let app = init_test_app().await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let repo_id = app
+			.create_root_repo(&mut txn, "Heart Disease")
+			.await
+			.unwrap();
+		for template in [
+			template("Accuracy", AlertMetric::Accuracy),
+			template("RMSE", AlertMetric::RootMeanSquaredError),
+			template("Model Age", AlertMetric::ModelAge),
+		] {
+			create_monitor_template(&mut txn, repo_id, &template, app.clock())
+				.await
+				.unwrap();
+		}
+		// The heart disease model is a classifier, so the template on the RMSE is skipped.
+		let model_path = workspace_root().join("heart_disease.modelfox");
+		let model_id = app
+			.add_model_to_repo(&mut txn, repo_id, model_path)
+			.await
+			.unwrap();
+		let mut titles = get_monitors_for_model(&mut txn, model_id)
+			.await
+			.unwrap()
+			.into_iter()
+			.map(|monitor| monitor.title)
+			.collect::<Vec<_>>();
+		titles.sort();
+		assert_eq!(titles, vec!["Accuracy".to_owned(), "Model Age".to_owned()]);
+	}
+}
@@ -0,0 +1,284 @@
+use crate::{
+	alert::{AlertMethod, AlertMethodNotificationChannel},
+	clock::Clock,
+};
+use anyhow::{anyhow, bail, Result};
+use chacha20poly1305::{
+	aead::{Aead, NewAead},
+	ChaCha20Poly1305, Key, Nonce,
+};
+use modelfox_id::Id;
+use sha2::Digest;
+use sqlx::prelude::*;
+use std::borrow::BorrowMut;
+
+/// The length in bytes of the nonce prepended to each encrypted notification channel.
+const NONCE_LENGTH: usize = 12;
+
+/// A notification channel is a named alert destination shared by all the monitors in an organization. Its method, which contains credentials such as webhook urls, is stored encrypted with the app's secret key.
+pub struct NotificationChannel {
+	pub id: Id,
+	pub name: String,
+	pub method: AlertMethod,
+}
+
+/// The id and name of a notification channel, which can be read without the secret key.
+#[derive(Clone)]
+pub struct NotificationChannelSummary {
+	pub id: Id,
+	pub name: String,
+}
+
+pub async fn create_notification_channel(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	secret_key: &str,
+	organization_id: Id,
+	name: &str,
+	method: &AlertMethod,
+	clock: &Clock,
+) -> Result<Id> {
+	validate_notification_channel_method(method)?;
+	let id = Id::generate();
+	let data = encrypt(secret_key, &serde_json::to_vec(method)?)?;
+	sqlx::query(
+		"
+			insert into notification_channels (
+				id, organization_id, name, data, created_at
+			) values (
+				$1, $2, $3, $4, $5
+			)
+		",
+	)
+	.bind(&id.to_string())
+	.bind(&organization_id.to_string())
+	.bind(name)
+	.bind(&data)
+	.bind(clock.now_utc().unix_timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(id)
+}
+
+/// Replace the method of a notification channel, for example to rotate a webhook url. Every monitor that references the channel will use the new method for subsequent alerts.
+pub async fn update_notification_channel(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	secret_key: &str,
+	organization_id: Id,
+	notification_channel_id: Id,
+	method: &AlertMethod,
+) -> Result<()> {
+	validate_notification_channel_method(method)?;
+	let data = encrypt(secret_key, &serde_json::to_vec(method)?)?;
+	sqlx::query(
+		"
+			update
+				notification_channels
+			set
+				data = $1
+			where
+				id = $2
+			and
+				organization_id = $3
+		",
+	)
+	.bind(&data)
+	.bind(&notification_channel_id.to_string())
+	.bind(&organization_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn delete_notification_channel(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+	notification_channel_id: Id,
+) -> Result<()> {
+	sqlx::query(
+		"
+			delete from notification_channels
+			where
+				id = $1
+			and
+				organization_id = $2
+		",
+	)
+	.bind(&notification_channel_id.to_string())
+	.bind(&organization_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn get_notification_channel(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	secret_key: &str,
+	notification_channel_id: Id,
+) -> Result<Option<NotificationChannel>> {
+	let row = sqlx::query(
+		"
+			select
+				notification_channels.name,
+				notification_channels.data
+			from notification_channels
+			where notification_channels.id = $1
+		",
+	)
+	.bind(&notification_channel_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	let row = match row {
+		Some(row) => row,
+		None => return Ok(None),
+	};
+	let name: String = row.get(0);
+	let data: String = row.get(1);
+	let method = serde_json::from_slice(&decrypt(secret_key, &data)?)?;
+	Ok(Some(NotificationChannel {
+		id: notification_channel_id,
+		name,
+		method,
+	}))
+}
+
+pub async fn get_notification_channels(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	secret_key: &str,
+	organization_id: Id,
+) -> Result<Vec<NotificationChannel>> {
+	let rows = sqlx::query(
+		"
+			select
+				notification_channels.id,
+				notification_channels.name,
+				notification_channels.data
+			from notification_channels
+			where notification_channels.organization_id = $1
+			order by notification_channels.created_at
+		",
+	)
+	.bind(&organization_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let id: String = row.get(0);
+			let name: String = row.get(1);
+			let data: String = row.get(2);
+			let method = serde_json::from_slice(&decrypt(secret_key, &data)?)?;
+			Ok(NotificationChannel {
+				id: id.parse()?,
+				name,
+				method,
+			})
+		})
+		.collect()
+}
+
+/// Get the notification channels that monitors on models in the given repo can reference. Repos that do not belong to an organization have none.
+pub async fn get_notification_channel_summaries_for_repo(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+) -> Result<Vec<NotificationChannelSummary>> {
+	let rows = sqlx::query(
+		"
+			select
+				notification_channels.id,
+				notification_channels.name
+			from notification_channels
+			join repos
+				on repos.organization_id = notification_channels.organization_id
+			where repos.id = $1
+			order by notification_channels.created_at
+		",
+	)
+	.bind(&repo_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let id: String = row.get(0);
+			let name: String = row.get(1);
+			Ok(NotificationChannelSummary {
+				id: id.parse()?,
+				name,
+			})
+		})
+		.collect()
+}
+
+/// Resolve the notification channel selected in a monitor form to an alert method. The selection must be empty or the id of one of `notification_channels`.
+pub fn notification_channel_alert_method(
+	selection: &str,
+	notification_channels: &[NotificationChannelSummary],
+) -> Result<Option<AlertMethod>> {
+	if selection.is_empty() {
+		return Ok(None);
+	}
+	let notification_channel_id: Id = selection.parse()?;
+	if !notification_channels
+		.iter()
+		.any(|notification_channel| notification_channel.id == notification_channel_id)
+	{
+		bail!("Unknown notification channel.");
+	}
+	Ok(Some(AlertMethod::NotificationChannel(
+		AlertMethodNotificationChannel {
+			notification_channel_id,
+		},
+	)))
+}
+
+fn validate_notification_channel_method(method: &AlertMethod) -> Result<()> {
+	match method {
+		AlertMethod::Email(_) | AlertMethod::Webhook(_) => Ok(()),
+		AlertMethod::Stdout | AlertMethod::NotificationChannel(_) => {
+			bail!("A notification channel must send alerts by email or webhook.")
+		}
+	}
+}
+
+fn cipher(secret_key: &str) -> ChaCha20Poly1305 {
+	let key = sha2::Sha256::digest(secret_key.as_bytes());
+	ChaCha20Poly1305::new(Key::from_slice(&key))
+}
+
+/// Encrypt `plaintext` with a key derived from `secret_key`, returning the base64 encoded nonce followed by the ciphertext.
+fn encrypt(secret_key: &str, plaintext: &[u8]) -> Result<String> {
+	let nonce: [u8; NONCE_LENGTH] = rand::random();
+	let ciphertext = cipher(secret_key)
+		.encrypt(Nonce::from_slice(&nonce), plaintext)
+		.map_err(|_| anyhow!("failed to encrypt notification channel"))?;
+	let mut data = nonce.to_vec();
+	data.extend(ciphertext);
+	Ok(base64::encode(data))
+}
+
+fn decrypt(secret_key: &str, data: &str) -> Result<Vec<u8>> {
+	let data = base64::decode(data)?;
+	if data.len() < NONCE_LENGTH {
+		bail!("malformed notification channel");
+	}
+	let (nonce, ciphertext) = data.split_at(NONCE_LENGTH);
+	cipher(secret_key)
+		.decrypt(Nonce::from_slice(nonce), ciphertext)
+		.map_err(|_| {
+			anyhow!("failed to decrypt notification channel, the secret key may have changed")
+		})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_encrypt_decrypt() {
+		let data = encrypt("secret", b"https://hooks.slack.com/services/T0/B0/X").unwrap();
+		assert!(!data.contains("slack"));
+		assert_eq!(
+			decrypt("secret", &data).unwrap(),
+			b"https://hooks.slack.com/services/T0/B0/X"
+		);
+		assert!(decrypt("other secret", &data).is_err());
+	}
+}
@@ -1,28 +1,112 @@
+use std::collections::BTreeMap;
 use std::net::IpAddr;
 use std::path::PathBuf;
+use std::time::Duration;
 use url::Url;
 
 #[derive(Debug)]
 pub struct Options {
 	pub auth: Option<AuthOptions>,
+	/// Set this to serve the app from a path below the root of its domain, such as `/modelfox`, when it runs behind a reverse proxy. This is empty if the app is served from the root.
+	pub base_path: String,
+	pub body_size_limits: BodySizeLimits,
 	pub cookie_domain: Option<String>,
 	pub database: DatabaseOptions,
 	pub host: IpAddr,
+	/// If set, the app consumes monitor events from this Kafka topic or Kinesis stream, in addition to those posted to the `/track` route.
+	pub ingest: Option<IngestOptions>,
+	/// Set this to pause all alert notifications and webhook deliveries, such as during maintenance. They are held until notifications are resumed, and a banner is shown on every page in the meantime.
+	pub pause_notifications: bool,
 	pub port: u16,
+	/// This key is used to encrypt secrets stored in the database, such as notification channel credentials.
+	pub secret_key: Option<String>,
 	pub smtp: Option<SmtpOptions>,
+	/// Set this when running more than one instance of the app against the same database. The app will refuse to start unless the database and storage are shared between instances.
+	pub stateless: bool,
 	pub storage: StorageOptions,
+	/// This controls how feature contributions for tree models are computed on the production prediction page. Fast mode is approximate, but much faster for models with deep trees.
+	pub tree_shap_mode: modelfox_core::predict::TreeShapMode,
+	/// These are the addresses of the reverse proxies in front of the app. The client address of a request is only taken from its `x-forwarded-for` header when the request comes from one of them.
+	pub trusted_proxies: Vec<IpAddr>,
 	pub url: Option<Url>,
 }
 
-#[derive(Debug)]
-pub struct AuthOptions {}
+#[derive(Debug, Default)]
+pub struct AuthOptions {
+	/// If set, only email addresses with one of these domains may sign in, unless they have been invited.
+	pub allowed_email_domains: Option<Vec<String>>,
+	/// If true, only existing users and email addresses that have been invited may sign in.
+	pub invite_only: bool,
+	/// These email addresses may always sign in and may manage invitations.
+	pub admin_emails: Vec<String>,
+}
+
+/// These are the largest request bodies, in bytes, that the app will accept. Requests with larger bodies are rejected with `413 Payload Too Large`.
+#[derive(Debug, Clone)]
+pub struct BodySizeLimits {
+	pub default: u64,
+	/// This applies to uploads of model files, which are streamed to storage and may be several gigabytes.
+	pub model_upload: u64,
+	/// This applies to CSV files posted to the batch predict API.
+	pub batch_predict: u64,
+}
+
+impl Default for BodySizeLimits {
+	fn default() -> BodySizeLimits {
+		BodySizeLimits {
+			default: 32 * 1024 * 1024,
+			model_upload: 16 * 1024 * 1024 * 1024,
+			batch_predict: 1024 * 1024 * 1024,
+		}
+	}
+}
+
+impl BodySizeLimits {
+	/// Get the limit for a request to `path`, which must already have the base path removed.
+	pub fn for_path(&self, path: &str) -> u64 {
+		let path_components: Vec<&str> = path.trim_matches('/').split('/').collect();
+		match path_components.as_slice() {
+			["repos", _, "models", "new"] => self.model_upload,
+			["api", "models", _, "batch_predict"] => self.batch_predict,
+			_ => self.default,
+		}
+	}
+}
 
 #[derive(Debug)]
 pub struct DatabaseOptions {
+	/// If set, the queries behind the production stats pages give up after this long. The pages then show the stats from the daily rollups, which may be missing the most recent predictions, instead of failing.
+	pub analytics_statement_timeout: Option<Duration>,
 	pub max_connections: Option<u32>,
+	/// If set, read only queries for analytics pages such as production stats and predictions are sent to this PostgreSQL read replica instead of the primary database.
+	pub replica_url: Option<Url>,
 	pub url: Url,
 }
 
+#[derive(Debug, Clone)]
+pub enum IngestOptions {
+	Kafka(KafkaIngestOptions),
+	Kinesis(KinesisIngestOptions),
+}
+
+#[derive(Debug, Clone)]
+pub struct KafkaIngestOptions {
+	/// This is a comma separated list of the Kafka brokers to connect to.
+	pub brokers: String,
+	pub topic: String,
+	/// Instances of the app with the same consumer group split the topic's partitions between them.
+	pub group_id: String,
+	/// These are passed through to librdkafka, for settings such as `security.protocol` and `sasl.mechanisms`.
+	pub properties: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct KinesisIngestOptions {
+	pub stream_name: String,
+	/// If not set, the region is read from the environment, as are the AWS credentials.
+	pub region: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct SmtpOptions {
 	pub host: String,
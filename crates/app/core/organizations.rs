@@ -7,6 +7,10 @@ use sqlx::prelude::*;
 pub struct GetOrganizationOutput {
 	pub id: Id,
 	pub name: String,
+	/// The timezone alerts, monitor cadences, and production pages use for the organization's repos, unless a user sets their own.
+	pub timezone: Option<String>,
+	/// If set, at most this many alert notifications are sent for the organization's repos per hour. See [`crate::alert_sender`].
+	pub alert_notifications_per_hour: Option<i64>,
 	pub members: Vec<Member>,
 }
 
@@ -14,6 +18,8 @@ pub struct Member {
 	pub id: Id,
 	pub email: String,
 	pub is_admin: bool,
+	/// Members without data access can see a model's metrics but not the inputs of its production predictions. See [`crate::user::authorize_user_for_data_access`].
+	pub has_data_access: bool,
 }
 
 pub async fn get_organization(
@@ -23,7 +29,9 @@ pub async fn get_organization(
 	let row = sqlx::query(
 		"
 			select
-				organizations.name
+				organizations.name,
+				organizations.timezone,
+				organizations.alert_notifications_per_hour
 			from organizations
 				where organizations.id = $1
 		",
@@ -32,12 +40,15 @@ pub async fn get_organization(
 	.fetch_one(txn.borrow_mut())
 	.await?;
 	let organization_name: String = row.get(0);
+	let organization_timezone: Option<String> = row.get(1);
+	let alert_notifications_per_hour: Option<i64> = row.get(2);
 	let user_rows = sqlx::query(
 		"
 			select
 				users.id,
 				users.email,
-				organizations_users.is_admin
+				organizations_users.is_admin,
+				organizations_users.has_data_access
 			from users
 			join organizations_users
 				on organizations_users.organization_id = $1
@@ -55,16 +66,38 @@ pub async fn get_organization(
 				id: user_id.parse().unwrap(),
 				email: row.get(1),
 				is_admin: row.get(2),
+				has_data_access: row.get(3),
 			}
 		})
 		.collect();
 	Ok(Some(GetOrganizationOutput {
 		id: organization_id,
+		alert_notifications_per_hour,
 		members,
 		name: organization_name,
+		timezone: organization_timezone,
 	}))
 }
 
+pub async fn set_organization_alert_notifications_per_hour(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+	alert_notifications_per_hour: Option<i64>,
+) -> Result<()> {
+	sqlx::query(
+		"
+			update organizations
+				set alert_notifications_per_hour = $1
+			where organizations.id = $2
+		",
+	)
+	.bind(alert_notifications_per_hour)
+	.bind(&organization_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
 pub async fn get_organization_user(
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
 	organization_id: Id,
@@ -75,7 +108,8 @@ pub async fn get_organization_user(
 			select
 				users.id,
 				users.email,
-				organizations_users.is_admin
+				organizations_users.is_admin,
+				organizations_users.has_data_access
 			from users
 			join organizations_users
 				on organizations_users.organization_id = $1
@@ -91,6 +125,7 @@ pub async fn get_organization_user(
 		id: user_id,
 		email: member_row.get(1),
 		is_admin: member_row.get(2),
+		has_data_access: member_row.get(3),
 	}))
 }
 
@@ -0,0 +1,468 @@
+/*!
+Prediction keys authorize requests to a model's predict endpoint. Each key belongs to a single model and has its own limit on the number of requests it can make per minute, so a client that sends too many requests gets 429 responses instead of slowing down the app for everyone else. Each key counts the requests made with it and the ones that were rejected, which are shown on the model's serving page. Rejected requests are counted in memory and written to the database periodically by [`prediction_key_rejections_flusher`], so a client hammering the endpoint does not cause a database write per rejected request.
+*/
+
+use crate::{
+	heuristics::{
+		PREDICTION_KEY_REJECTIONS_FLUSH_DURATION_PRODUCTION,
+		PREDICTION_KEY_REJECTIONS_FLUSH_DURATION_TESTING,
+	},
+	App, AppState,
+};
+use anyhow::Result;
+use modelfox_id::Id;
+use sqlx::prelude::*;
+use std::{
+	borrow::BorrowMut,
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+pub struct PredictionKey {
+	pub id: Id,
+	pub model_id: Id,
+	pub name: String,
+	/// This is the secret that clients send in the `Authorization` header as `Bearer <token>`.
+	pub token: Id,
+	pub requests_per_minute: i64,
+	pub created_at: i64,
+	pub last_used_at: Option<i64>,
+	/// This is the number of requests made with the key, including the rejected ones.
+	pub request_count: i64,
+	/// This is the number of requests that were rejected because the key exceeded its limit.
+	pub rejected_request_count: i64,
+}
+
+/// Create a prediction key for the model and return its id.
+pub async fn create_prediction_key(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	name: &str,
+	requests_per_minute: i64,
+	now: i64,
+) -> Result<Id> {
+	let id = Id::generate();
+	let token = Id::generate();
+	sqlx::query(
+		"
+			insert into prediction_keys (
+				id, model_id, name, token, requests_per_minute, created_at
+			) values (
+				$1, $2, $3, $4, $5, $6
+			)
+		",
+	)
+	.bind(&id.to_string())
+	.bind(&model_id.to_string())
+	.bind(name)
+	.bind(&token.to_string())
+	.bind(requests_per_minute)
+	.bind(now)
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(id)
+}
+
+/// Get the model's prediction keys, in the order they were created.
+pub async fn get_prediction_keys(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Vec<PredictionKey>> {
+	let rows = sqlx::query(
+		"
+			select
+				id,
+				name,
+				token,
+				requests_per_minute,
+				created_at,
+				last_used_at,
+				request_count,
+				rejected_request_count
+			from prediction_keys
+			where model_id = $1
+			order by created_at
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.into_iter()
+		.map(|row| {
+			Ok(PredictionKey {
+				id: row.get::<String, _>(0).parse()?,
+				model_id,
+				name: row.get(1),
+				token: row.get::<String, _>(2).parse()?,
+				requests_per_minute: row.get(3),
+				created_at: row.get(4),
+				last_used_at: row.get(5),
+				request_count: row.get(6),
+				rejected_request_count: row.get(7),
+			})
+		})
+		.collect()
+}
+
+/// Delete the prediction key with the given id, if it belongs to the model. Requests made with it are rejected from then on. Return whether the key was found.
+pub async fn delete_prediction_key(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	key_id: Id,
+) -> Result<bool> {
+	let result = sqlx::query(
+		"
+			delete from prediction_keys
+			where
+				id = $1
+			and
+				model_id = $2
+		",
+	)
+	.bind(&key_id.to_string())
+	.bind(&model_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(result.rows_affected() > 0)
+}
+
+/// Get the prediction key with the given token, if it belongs to the model and the model is not in the trash.
+pub async fn authorize_prediction_key(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	token: &str,
+) -> Result<Option<PredictionKey>> {
+	let row = sqlx::query(
+		"
+			select
+				prediction_keys.id,
+				prediction_keys.name,
+				prediction_keys.requests_per_minute,
+				prediction_keys.created_at,
+				prediction_keys.last_used_at,
+				prediction_keys.request_count,
+				prediction_keys.rejected_request_count
+			from prediction_keys
+			join models
+				on models.id = prediction_keys.model_id
+			where
+				prediction_keys.token = $1
+			and
+				prediction_keys.model_id = $2
+			and
+				models.deleted_at is null
+		",
+	)
+	.bind(token)
+	.bind(&model_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	let row = match row {
+		Some(row) => row,
+		None => return Ok(None),
+	};
+	Ok(Some(PredictionKey {
+		id: row.get::<String, _>(0).parse()?,
+		model_id,
+		name: row.get(1),
+		token: token.parse()?,
+		requests_per_minute: row.get(2),
+		created_at: row.get(3),
+		last_used_at: row.get(4),
+		request_count: row.get(5),
+		rejected_request_count: row.get(6),
+	}))
+}
+
+/// Count a request made with the key that was not rejected.
+pub async fn record_prediction_key_request(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	key_id: Id,
+	now: i64,
+) -> Result<()> {
+	sqlx::query(
+		"
+			update prediction_keys
+			set
+				last_used_at = $1,
+				request_count = request_count + 1
+			where id = $2
+		",
+	)
+	.bind(now)
+	.bind(&key_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Count `count` rejected requests made with the key, the last of which was made at `last_rejected_at`.
+pub async fn record_rejected_prediction_key_requests(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	key_id: Id,
+	rejected_requests: RejectedRequests,
+) -> Result<()> {
+	sqlx::query(
+		"
+			update prediction_keys
+			set
+				last_used_at = case
+					when last_used_at is null or last_used_at < $1 then $1
+					else last_used_at
+				end,
+				request_count = request_count + $2,
+				rejected_request_count = rejected_request_count + $2
+			where id = $3
+		",
+	)
+	.bind(rejected_requests.last_rejected_at)
+	.bind(rejected_requests.count)
+	.bind(&key_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// This limits the number of requests each prediction key makes in each one minute window. The counts are kept in memory, so when the app runs on more than one server, each server enforces the limit separately.
+#[derive(Debug, Default)]
+pub struct PredictionKeyRateLimiter {
+	/// For each key, this is the start of its current window and the number of requests it made in the window.
+	windows: Mutex<HashMap<Id, (i64, i64)>>,
+	/// For each key, these are the requests that were rejected since they were last written to the database.
+	rejected_requests: Mutex<HashMap<Id, RejectedRequests>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RejectedRequests {
+	pub count: i64,
+	pub last_rejected_at: i64,
+}
+
+impl PredictionKeyRateLimiter {
+	/// Count a request made with the key at `now`. If the key already made `requests_per_minute` requests in its current window, the request is counted as rejected instead and this returns the number of seconds until the window ends.
+	pub fn check(&self, key_id: Id, requests_per_minute: i64, now: i64) -> Result<(), i64> {
+		let mut windows = self.windows.lock().unwrap();
+		let (window_start, request_count) = windows.entry(key_id).or_insert((now, 0));
+		if now - *window_start >= 60 {
+			*window_start = now;
+			*request_count = 0;
+		}
+		if *request_count >= requests_per_minute {
+			let retry_after = *window_start + 60 - now;
+			drop(windows);
+			let mut rejected_requests = self.rejected_requests.lock().unwrap();
+			let rejected_requests = rejected_requests.entry(key_id).or_insert(RejectedRequests {
+				count: 0,
+				last_rejected_at: now,
+			});
+			rejected_requests.count += 1;
+			rejected_requests.last_rejected_at = now;
+			return Err(retry_after);
+		}
+		*request_count += 1;
+		Ok(())
+	}
+
+	/// Take the rejected requests counted since the last call, leaving none behind.
+	pub fn take_rejected_requests(&self) -> HashMap<Id, RejectedRequests> {
+		std::mem::take(&mut *self.rejected_requests.lock().unwrap())
+	}
+
+	/// Put back rejected requests that could not be written to the database, so they are written on the next flush.
+	fn restore_rejected_requests(&self, rejected_requests: HashMap<Id, RejectedRequests>) {
+		let mut current = self.rejected_requests.lock().unwrap();
+		for (key_id, restored) in rejected_requests {
+			let entry = current.entry(key_id).or_insert(RejectedRequests {
+				count: 0,
+				last_rejected_at: restored.last_rejected_at,
+			});
+			entry.count += restored.count;
+			entry.last_rejected_at = entry.last_rejected_at.max(restored.last_rejected_at);
+		}
+	}
+}
+
+/// Periodically write the rejected requests counted by the [`PredictionKeyRateLimiter`] to the database. Every server counts its own rejections, so this runs on every server instead of only on the leader.
+pub async fn prediction_key_rejections_flusher(app_state: Arc<AppState>) {
+	let period = if cfg!(debug_assertions) {
+		PREDICTION_KEY_REJECTIONS_FLUSH_DURATION_TESTING
+	} else {
+		PREDICTION_KEY_REJECTIONS_FLUSH_DURATION_PRODUCTION
+	};
+	let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+	interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+	loop {
+		interval.tick().await;
+		if let Err(error) = flush_rejected_prediction_key_requests(&app_state).await {
+			tracing::error!(%error, "failed to flush rejected prediction key requests");
+		}
+	}
+}
+
+/// Write the rejected requests counted in memory to the database. If the write fails, the counts are kept for the next flush.
+pub async fn flush_rejected_prediction_key_requests(app_state: &AppState) -> Result<()> {
+	let rejected_requests = app_state
+		.prediction_key_rate_limiter
+		.take_rejected_requests();
+	if rejected_requests.is_empty() {
+		return Ok(());
+	}
+	let result = async {
+		let mut txn = app_state.begin_transaction().await?;
+		for (key_id, rejected_requests) in rejected_requests.iter() {
+			record_rejected_prediction_key_requests(&mut txn, *key_id, *rejected_requests).await?;
+		}
+		app_state.commit_transaction(txn).await?;
+		Ok::<_, anyhow::Error>(())
+	}
+	.await;
+	if result.is_err() {
+		app_state
+			.prediction_key_rate_limiter
+			.restore_rejected_requests(rejected_requests);
+	}
+	result
+}
+
+impl App {
+	/// Count a request made with the key against its limit. See [`PredictionKeyRateLimiter::check`].
+	pub fn check_prediction_key_rate_limit(&self, key: &PredictionKey) -> Result<(), i64> {
+		self.state.check_prediction_key_rate_limit(key)
+	}
+}
+
+impl App {
+	/// Write the rejected requests counted in memory to the database now instead of waiting for the next periodic flush.
+	pub async fn flush_rejected_prediction_key_requests(&self) -> Result<()> {
+		flush_rejected_prediction_key_requests(&self.state).await
+	}
+}
+
+impl AppState {
+	pub fn check_prediction_key_rate_limit(&self, key: &PredictionKey) -> Result<(), i64> {
+		let now = self.clock().now_utc().unix_timestamp();
+		self.prediction_key_rate_limiter
+			.check(key.id, key.requests_per_minute, now)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{repos::trash_model_version, test_common::*};
+
+	#[test]
+	fn test_prediction_key_rate_limiter() {
+		let rate_limiter = PredictionKeyRateLimiter::default();
+		let key_a = Id::generate();
+		let key_b = Id::generate();
+		assert_eq!(rate_limiter.check(key_a, 2, 0), Ok(()));
+		assert_eq!(rate_limiter.check(key_a, 2, 10), Ok(()));
+		assert_eq!(rate_limiter.check(key_a, 2, 20), Err(40));
+		assert_eq!(rate_limiter.check(key_a, 2, 30), Err(30));
+		// Each key has its own limit.
+		assert_eq!(rate_limiter.check(key_b, 2, 20), Ok(()));
+		assert_eq!(rate_limiter.check(key_a, 2, 60), Ok(()));
+		// Rejected requests are counted in memory until they are taken.
+		let rejected_requests = rate_limiter.take_rejected_requests();
+		assert_eq!(rejected_requests.len(), 1);
+		assert_eq!(
+			rejected_requests[&key_a],
+			RejectedRequests {
+				count: 2,
+				last_rejected_at: 30,
+			}
+		);
+		assert!(rate_limiter.take_rejected_requests().is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_authorize_prediction_key() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let key_id = create_prediction_key(&mut txn, model_id, "test", 10, 0)
+			.await
+			.unwrap();
+		let token = get_prediction_keys(&mut txn, model_id).await.unwrap()[0].token;
+		let key = authorize_prediction_key(&mut txn, model_id, &token.to_string())
+			.await
+			.unwrap()
+			.unwrap();
+		assert_eq!(key.id, key_id);
+		assert!(
+			authorize_prediction_key(&mut txn, Id::generate(), &token.to_string())
+				.await
+				.unwrap()
+				.is_none()
+		);
+		record_prediction_key_request(&mut txn, key_id, 1)
+			.await
+			.unwrap();
+		let key = &get_prediction_keys(&mut txn, model_id).await.unwrap()[0];
+		assert_eq!(key.request_count, 1);
+		assert_eq!(key.rejected_request_count, 0);
+		assert_eq!(key.last_used_at, Some(1));
+		assert!(delete_prediction_key(&mut txn, model_id, key_id)
+			.await
+			.unwrap());
+		assert!(
+			authorize_prediction_key(&mut txn, model_id, &token.to_string())
+				.await
+				.unwrap()
+				.is_none()
+		);
+		app.commit_transaction(txn).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_authorize_prediction_key_for_trashed_model() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		create_prediction_key(&mut txn, model_id, "test", 10, 0)
+			.await
+			.unwrap();
+		let token = get_prediction_keys(&mut txn, model_id).await.unwrap()[0].token;
+		trash_model_version(&mut txn, &app, model_id).await.unwrap();
+		assert!(
+			authorize_prediction_key(&mut txn, model_id, &token.to_string())
+				.await
+				.unwrap()
+				.is_none()
+		);
+		app.commit_transaction(txn).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_flush_rejected_prediction_key_requests() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		create_prediction_key(&mut txn, model_id, "test", 1, 0)
+			.await
+			.unwrap();
+		app.commit_transaction(txn).await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let key = get_prediction_keys(&mut txn, model_id)
+			.await
+			.unwrap()
+			.remove(0);
+		app.commit_transaction(txn).await.unwrap();
+		assert!(app.check_prediction_key_rate_limit(&key).is_ok());
+		assert!(app.check_prediction_key_rate_limit(&key).is_err());
+		assert!(app.check_prediction_key_rate_limit(&key).is_err());
+		// The rejections are not written until they are flushed.
+		let mut txn = app.begin_transaction().await.unwrap();
+		let stored_key = &get_prediction_keys(&mut txn, model_id).await.unwrap()[0];
+		assert_eq!(stored_key.rejected_request_count, 0);
+		app.commit_transaction(txn).await.unwrap();
+		app.flush_rejected_prediction_key_requests().await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let stored_key = &get_prediction_keys(&mut txn, model_id).await.unwrap()[0];
+		assert_eq!(stored_key.request_count, 2);
+		assert_eq!(stored_key.rejected_request_count, 2);
+		assert_eq!(stored_key.last_used_at, Some(0));
+		app.commit_transaction(txn).await.unwrap();
+	}
+}
@@ -0,0 +1,161 @@
+/*!
+This module implements the per-model settings that limit the personal data the app keeps from tracked predictions. A model can be configured to not store the inputs of its predictions at all, in which case only a hash of each prediction's identifier is stored along with its output, and the production stats computed from the inputs when they are tracked. A model can also mark input columns as containing personally identifiable information. The values in those columns are replaced with their hashes before the prediction is stored, and the columns are left out of the production stats.
+*/
+
+use anyhow::Result;
+use modelfox_app_monitor_event::NumberOrString;
+use modelfox_id::Id;
+use sha2::{Digest, Sha256};
+use sqlx::prelude::*;
+use std::{borrow::BorrowMut, collections::HashMap};
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PrivacySettings {
+	/// If this is false, the inputs of tracked predictions are discarded and their identifiers are hashed.
+	pub store_inputs: bool,
+	/// The values of these input columns are hashed when predictions are tracked.
+	#[serde(default)]
+	pub pii_columns: Vec<String>,
+}
+
+impl Default for PrivacySettings {
+	fn default() -> PrivacySettings {
+		PrivacySettings {
+			store_inputs: true,
+			pii_columns: Vec::new(),
+		}
+	}
+}
+
+impl PrivacySettings {
+	/// Replace the values of the PII columns in `input` with their hashes. The PII columns are skipped in the production stats, because their hashes would be counted as invalid values.
+	pub fn redact_input(&self, input: &mut HashMap<String, serde_json::Value>) {
+		for column_name in self.pii_columns.iter() {
+			if let Some(value) = input.get_mut(column_name) {
+				let hash = match value {
+					serde_json::Value::Null => continue,
+					serde_json::Value::String(value) => hash_value(value),
+					value => hash_value(&value.to_string()),
+				};
+				*value = serde_json::Value::String(hash);
+			}
+		}
+	}
+
+	/// Return the identifier to store for a new prediction. Identifiers are hashed if inputs are not stored, so they cannot be used to look up the person a prediction was made for. The hash is deterministic, so true values are still matched with their predictions. Whether a prediction's identifier was hashed is stored with it, see [`identifier_candidates`], so changing this setting does not stop true values from matching predictions tracked before the change.
+	pub fn stored_identifier(&self, identifier: &NumberOrString) -> String {
+		if self.hashes_identifiers() {
+			hash_value(&identifier.as_string())
+		} else {
+			identifier.as_string().into_owned()
+		}
+	}
+
+	pub fn hashes_identifiers(&self) -> bool {
+		!self.store_inputs
+	}
+}
+
+/// Return the identifier as it is stored for predictions whose identifiers were not hashed, and as it is stored for predictions whose identifiers were.
+pub fn identifier_candidates(identifier: &NumberOrString) -> (String, String) {
+	let identifier = identifier.as_string();
+	let hashed_identifier = hash_value(&identifier);
+	(identifier.into_owned(), hashed_identifier)
+}
+
+/// Hash a value with SHA-256 and hex encode the result.
+pub fn hash_value(value: &str) -> String {
+	Sha256::digest(value.as_bytes())
+		.iter()
+		.map(|byte| format!("{:02x}", byte))
+		.collect()
+}
+
+/// Get a model's privacy settings. Models that never had them saved use the default settings, which store everything.
+pub async fn get_model_privacy_settings(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<PrivacySettings> {
+	let row = sqlx::query(
+		"
+			select
+				privacy_settings
+			from models
+			where id = $1
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?;
+	let privacy_settings: Option<String> = row.get(0);
+	let privacy_settings = match privacy_settings {
+		Some(privacy_settings) => serde_json::from_str(&privacy_settings)?,
+		None => PrivacySettings::default(),
+	};
+	Ok(privacy_settings)
+}
+
+/// Save a model's privacy settings. They apply to predictions tracked from now on. Predictions that were already stored are not changed.
+pub async fn set_model_privacy_settings(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	privacy_settings: &PrivacySettings,
+) -> Result<()> {
+	sqlx::query(
+		"
+			update models
+			set privacy_settings = $1
+			where id = $2
+		",
+	)
+	.bind(serde_json::to_string(privacy_settings)?)
+	.bind(&model_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_redact_input() {
+		let privacy_settings = PrivacySettings {
+			store_inputs: true,
+			pii_columns: vec!["email".to_owned(), "age".to_owned()],
+		};
+		let mut input = HashMap::new();
+		input.insert("email".to_owned(), serde_json::json!("jane@example.com"));
+		input.insert("age".to_owned(), serde_json::json!(42));
+		input.insert("chest_pain".to_owned(), serde_json::json!("asymptomatic"));
+		privacy_settings.redact_input(&mut input);
+		assert_eq!(
+			input["email"],
+			serde_json::json!(hash_value("jane@example.com"))
+		);
+		assert_eq!(input["age"], serde_json::json!(hash_value("42")));
+		assert_eq!(input["chest_pain"], serde_json::json!("asymptomatic"));
+	}
+
+	#[test]
+	fn test_stored_identifier() {
+		let identifier = NumberOrString::String("abc".to_owned());
+		assert_eq!(
+			PrivacySettings::default().stored_identifier(&identifier),
+			"abc"
+		);
+		let privacy_settings = PrivacySettings {
+			store_inputs: false,
+			pii_columns: Vec::new(),
+		};
+		assert_eq!(
+			privacy_settings.stored_identifier(&identifier),
+			hash_value("abc")
+		);
+		assert_eq!(
+			identifier_candidates(&identifier),
+			("abc".to_owned(), hash_value("abc"))
+		);
+	}
+}
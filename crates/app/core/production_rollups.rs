@@ -0,0 +1,124 @@
+use crate::{
+	cluster::{BackgroundTask, Leadership},
+	heuristics::{
+		PRODUCTION_ROLLUPS_HEARTBEAT_DURATION_PRODUCTION,
+		PRODUCTION_ROLLUPS_HEARTBEAT_DURATION_TESTING, PRODUCTION_ROLLUPS_MAX_DAYS_PER_TRANSACTION,
+	},
+	AppState,
+};
+use anyhow::{bail, Result};
+use chrono::prelude::*;
+use futures::{select, FutureExt};
+use modelfox_app_production_metrics::ProductionMetrics;
+use modelfox_app_production_stats::{
+	rollup::{
+		get_days_to_roll_up, get_hourly_rows_for_day, write_daily_row, RollupTable, SECONDS_PER_DAY,
+	},
+	ProductionStats,
+};
+use std::{borrow::BorrowMut, sync::Arc};
+use tokio::sync::{mpsc, oneshot};
+
+#[derive(Debug)]
+pub enum ProductionRollupsMessage {
+	Run(oneshot::Sender<()>),
+}
+
+/// Periodically roll up the hourly production stats and metrics of each complete day into daily rows.
+#[tracing::instrument(level = "info", skip_all)]
+pub async fn production_rollups(
+	app_state: Arc<AppState>,
+	mut receiver: mpsc::UnboundedReceiver<ProductionRollupsMessage>,
+) -> Result<()> {
+	let period = if cfg!(debug_assertions) {
+		PRODUCTION_ROLLUPS_HEARTBEAT_DURATION_TESTING
+	} else {
+		PRODUCTION_ROLLUPS_HEARTBEAT_DURATION_PRODUCTION
+	};
+	let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+	interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+	loop {
+		enum Event {
+			Tick,
+			Message(ProductionRollupsMessage),
+		}
+		let event = select! {
+			_ = interval.tick().fuse() => Event::Tick,
+			message = receiver.recv().fuse() => match message {
+				None => break,
+				Some(message) => Event::Message(message),
+			}
+		};
+		if let Some(leadership) =
+			Leadership::acquire(&app_state, BackgroundTask::ProductionRollups).await?
+		{
+			tracing::info!("Begin production_rollups heartbeat");
+			roll_up_production_data(&app_state, RollupTable::ProductionStats).await?;
+			roll_up_production_data(&app_state, RollupTable::ProductionMetrics).await?;
+			leadership.release().await?;
+			tracing::info!("End production_rollups heartbeat");
+		}
+		if let Event::Message(ProductionRollupsMessage::Run(sender)) = event {
+			sender.send(()).unwrap();
+		}
+	}
+	Ok(())
+}
+
+/// Roll up every complete day that does not have a daily row, a batch of days per transaction. The first run after upgrading backfills all the days tracked before rollups were added.
+pub async fn roll_up_production_data(app_state: &AppState, table: RollupTable) -> Result<()> {
+	let now = app_state.clock.now_utc().unix_timestamp();
+	loop {
+		let mut txn = app_state.begin_transaction().await?;
+		let days = get_days_to_roll_up(
+			txn.borrow_mut(),
+			table,
+			now,
+			PRODUCTION_ROLLUPS_MAX_DAYS_PER_TRANSACTION,
+		)
+		.await?;
+		if days.is_empty() {
+			app_state.commit_transaction(txn).await?;
+			break;
+		}
+		for (model_id, day) in days.iter() {
+			let hourly_rows =
+				get_hourly_rows_for_day(txn.borrow_mut(), table, model_id, *day).await?;
+			let data = match table {
+				RollupTable::ProductionStats => roll_up_production_stats(&hourly_rows, *day)?,
+				RollupTable::ProductionMetrics => roll_up_production_metrics(&hourly_rows, *day)?,
+			};
+			write_daily_row(txn.borrow_mut(), table, model_id, *day, &data).await?;
+		}
+		app_state.commit_transaction(txn).await?;
+	}
+	Ok(())
+}
+
+fn roll_up_production_stats(hourly_rows: &[String], day: i64) -> Result<String> {
+	let mut hourly_rows = hourly_rows.iter();
+	let mut production_stats: ProductionStats = match hourly_rows.next() {
+		Some(data) => serde_json::from_str(data)?,
+		None => bail!("there are no production stats to roll up"),
+	};
+	for data in hourly_rows {
+		production_stats.merge(serde_json::from_str(data)?);
+	}
+	production_stats.start_date = Utc.timestamp(day, 0);
+	production_stats.end_date = Utc.timestamp(day + SECONDS_PER_DAY, 0);
+	Ok(serde_json::to_string(&production_stats)?)
+}
+
+fn roll_up_production_metrics(hourly_rows: &[String], day: i64) -> Result<String> {
+	let mut hourly_rows = hourly_rows.iter();
+	let mut production_metrics: ProductionMetrics = match hourly_rows.next() {
+		Some(data) => serde_json::from_str(data)?,
+		None => bail!("there are no production metrics to roll up"),
+	};
+	for data in hourly_rows {
+		production_metrics.merge(serde_json::from_str(data)?);
+	}
+	production_metrics.start_date = Utc.timestamp(day, 0);
+	production_metrics.end_date = Utc.timestamp(day + SECONDS_PER_DAY, 0);
+	Ok(serde_json::to_string(&production_metrics)?)
+}
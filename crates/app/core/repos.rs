@@ -1,13 +1,18 @@
 use crate::{
+	batch_predict::delete_batch_predict_files,
 	clock::Clock,
+	cluster::notify_storage_invalidation,
+	custom_metrics::get_custom_metrics,
+	monitor_templates::apply_monitor_templates,
 	storage::{Storage, StorageEntity},
 	user::NormalUser,
+	webhooks::{create_webhook_deliveries, WebhookEvent},
 	App,
 };
 use anyhow::Result;
 use modelfox_id::Id;
 use sqlx::prelude::*;
-use std::borrow::BorrowMut;
+use std::{borrow::BorrowMut, path::Path};
 
 pub struct Repo {
 	pub id: String,
@@ -39,12 +44,89 @@ pub async fn get_repo(txn: &mut sqlx::Transaction<'_, sqlx::Any>, id: Id) -> Res
 	Ok(repo)
 }
 
+/// Get the markdown readme of `repo_id`, or `None` if it has none.
+pub async fn get_repo_readme(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+) -> Result<Option<String>> {
+	let row = sqlx::query(
+		"
+			select
+				repos.readme
+			from repos
+			where repos.id = $1
+		",
+	)
+	.bind(&repo_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?;
+	let readme: Option<String> = row.get(0);
+	Ok(readme)
+}
+
+/// Replace the markdown readme of `repo_id`. A readme that is empty or only whitespace removes it.
+pub async fn update_repo_readme(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+	readme: &str,
+) -> Result<()> {
+	let readme = if readme.trim().is_empty() {
+		None
+	} else {
+		Some(readme)
+	};
+	sqlx::query(
+		"
+			update repos
+				set readme = $1
+			where id = $2
+		",
+	)
+	.bind(readme)
+	.bind(&repo_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn get_model_repo_id(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Id> {
+	let row = sqlx::query(
+		"
+			select
+				models.repo_id
+			from models
+			where models.id = $1
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?;
+	let repo_id: String = row.get(0);
+	Ok(repo_id.parse()?)
+}
+
 pub async fn delete_repo(
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
 	storage: &Storage,
 	repo_id: Id,
 ) -> Result<()> {
-	let model_version_ids = get_model_version_ids(txn, repo_id).await?;
+	let model_version_ids = get_model_version_ids(txn, repo_id)
+		.await?
+		.into_iter()
+		.chain(
+			get_trashed_model_versions(txn, repo_id)
+				.await?
+				.into_iter()
+				.map(|model_version| model_version.id),
+		)
+		.collect::<Vec<_>>();
+	let custom_metrics = get_custom_metrics(txn, repo_id).await?;
+	for model_id in model_version_ids.iter() {
+		delete_batch_predict_files(txn, storage, *model_id).await?;
+	}
 	sqlx::query(
 		"
 			delete from repos
@@ -56,6 +138,13 @@ pub async fn delete_repo(
 	.await?;
 	for model_id in model_version_ids.into_iter() {
 		storage.remove(StorageEntity::Model, model_id).await?;
+		notify_storage_invalidation(txn, StorageEntity::Model, model_id).await?;
+	}
+	for custom_metric in custom_metrics {
+		storage
+			.remove(StorageEntity::CustomMetric, custom_metric.id)
+			.await?;
+		notify_storage_invalidation(txn, StorageEntity::CustomMetric, custom_metric.id).await?;
 	}
 	Ok(())
 }
@@ -126,6 +215,22 @@ pub async fn repos_for_user(
 			inner join organizations_users
 				on organizations_users.organization_id = repos.organization_id
 				and organizations_users.user_id = $1
+			where
+				organizations_users.is_admin = true or
+				not exists (
+					select 1
+					from repos_teams
+					where repos_teams.repo_id = repos.id
+				) or
+				exists (
+					select 1
+					from repos_teams
+					join teams_users
+						on teams_users.team_id = repos_teams.team_id
+					where
+						repos_teams.repo_id = repos.id and
+						teams_users.user_id = $1
+				)
 		",
 	)
 	.bind(&user.id.to_string())
@@ -223,6 +328,36 @@ pub async fn add_model_version(
 	repo_id: Id,
 	model_id: Id,
 	bytes: &[u8],
+) -> Result<()> {
+	insert_model_version(txn, app, repo_id, model_id).await?;
+	app.storage()
+		.set(StorageEntity::Model, model_id, bytes)
+		.await?;
+	model_version_added(txn, app, repo_id, model_id).await?;
+	Ok(())
+}
+
+/// Add a model version whose model file is at `path`, such as one that was streamed to disk as it was uploaded. The file is copied to storage without reading it into memory.
+pub async fn add_model_version_from_path(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	app: &App,
+	repo_id: Id,
+	model_id: Id,
+	path: &Path,
+) -> Result<()> {
+	insert_model_version(txn, app, repo_id, model_id).await?;
+	app.storage()
+		.set_from_path(StorageEntity::Model, model_id, path)
+		.await?;
+	model_version_added(txn, app, repo_id, model_id).await?;
+	Ok(())
+}
+
+async fn insert_model_version(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	app: &App,
+	repo_id: Id,
+	model_id: Id,
 ) -> Result<()> {
 	sqlx::query(
 		"
@@ -238,17 +373,120 @@ pub async fn add_model_version(
 	.bind(&repo_id.to_string())
 	.execute(txn.borrow_mut())
 	.await?;
-	app.storage()
-		.set(StorageEntity::Model, model_id, bytes)
-		.await?;
 	Ok(())
 }
 
+async fn model_version_added(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	app: &App,
+	repo_id: Id,
+	model_id: Id,
+) -> Result<()> {
+	apply_monitor_templates(txn, app.storage(), repo_id, model_id).await?;
+	create_webhook_deliveries(
+		txn,
+		app.clock(),
+		repo_id,
+		&WebhookEvent::ModelUploaded { model_id },
+	)
+	.await?;
+	Ok(())
+}
+
+/// Move a model version to the trash. It disappears from the app, but its monitoring history is kept until it is deleted for good [`MODEL_TRASH_RETENTION_DURATION`](crate::heuristics::MODEL_TRASH_RETENTION_DURATION) later.
+pub async fn trash_model_version(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	app: &App,
+	model_id: Id,
+) -> Result<()> {
+	let repo_id = get_model_repo_id(txn, model_id).await?;
+	sqlx::query(
+		"
+			update models
+				set deleted_at = $1
+			where id = $2
+		",
+	)
+	.bind(app.clock().now_utc().unix_timestamp())
+	.bind(&model_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	app.state.predict_model_cache.remove(model_id);
+	create_webhook_deliveries(
+		txn,
+		app.clock(),
+		repo_id,
+		&WebhookEvent::ModelDeleted { model_id },
+	)
+	.await?;
+	Ok(())
+}
+
+/// Take a model version in `repo_id` back out of the trash. This returns `false` if there is no such model version in the trash.
+pub async fn restore_model_version(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+	model_id: Id,
+) -> Result<bool> {
+	let result = sqlx::query(
+		"
+			update models
+				set deleted_at = null
+			where
+				id = $1 and
+				repo_id = $2 and
+				deleted_at is not null
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&repo_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(result.rows_affected() > 0)
+}
+
+pub struct TrashedModelVersion {
+	pub id: Id,
+	pub tag: Option<String>,
+	pub deleted_at: i64,
+}
+
+pub async fn get_trashed_model_versions(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+) -> Result<Vec<TrashedModelVersion>> {
+	Ok(sqlx::query(
+		"
+			select
+				models.id,
+				models.tag,
+				models.deleted_at
+			from models
+			where
+				models.repo_id = $1 and
+				models.deleted_at is not null
+			order by models.deleted_at desc
+		",
+	)
+	.bind(&repo_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?
+	.iter()
+	.map(|row| TrashedModelVersion {
+		id: row.get::<String, _>(0).parse().unwrap(),
+		tag: row.get(1),
+		deleted_at: row.get(2),
+	})
+	.collect())
+}
+
+/// Delete a model version for good. Its predictions, true values, production stats and metrics, and monitors are deleted along with it by the `on delete cascade` rules in the schema.
 pub async fn delete_model_version(
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
-	data_storage: &Storage,
+	storage: &Storage,
 	model_id: Id,
 ) -> Result<()> {
+	delete_batch_predict_files(txn, storage, model_id).await?;
 	sqlx::query(
 		"
 			delete from models
@@ -258,7 +496,8 @@ pub async fn delete_model_version(
 	.bind(&model_id.to_string())
 	.execute(txn.borrow_mut())
 	.await?;
-	data_storage.remove(StorageEntity::Model, model_id).await?;
+	storage.remove(StorageEntity::Model, model_id).await?;
+	notify_storage_invalidation(txn, StorageEntity::Model, model_id).await?;
 	Ok(())
 }
 
@@ -274,7 +513,8 @@ pub async fn get_model_version_ids(
 			join repos
 				on models.repo_id = repos.id
 			where
-			repos.id = $1
+				repos.id = $1 and
+				models.deleted_at is null
 		",
 	)
 	.bind(&repo_id.to_string())
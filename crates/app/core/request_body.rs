@@ -0,0 +1,131 @@
+//! This module limits the size of request bodies and streams large uploads to disk, so a request can never make the app hold more than its route's [`BodySizeLimits`](crate::options::BodySizeLimits) in memory.
+
+use crate::heuristics::UPLOAD_PROGRESS_LOG_INTERVAL_BYTES;
+use anyhow::Result;
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use tokio::io::AsyncWriteExt;
+
+/// This is the error a body limited by [`limit_request_body`] fails with when it is read past its limit.
+#[derive(Debug)]
+pub struct BodySizeLimitExceeded {
+	pub limit: u64,
+}
+
+impl std::fmt::Display for BodySizeLimitExceeded {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"the request body is larger than the limit of {} bytes",
+			self.limit
+		)
+	}
+}
+
+impl std::error::Error for BodySizeLimitExceeded {}
+
+/// Return true if reading a request body failed because it was larger than its limit. The error is usually wrapped by the error of whatever was reading the body, such as a multipart parser, so the whole chain of sources is checked.
+pub fn is_body_size_limit_exceeded(error: &anyhow::Error) -> bool {
+	error
+		.chain()
+		.any(|error| error.is::<BodySizeLimitExceeded>())
+}
+
+/// Limit the body of `request` to `limit` bytes. This returns false if the request's `Content-Length` is already over the limit, so it can be rejected without reading the body. Otherwise, the body is wrapped so that reading past the limit fails, which catches chunked requests that do not declare their length.
+pub fn limit_request_body(request: &mut http::Request<hyper::Body>, limit: u64) -> bool {
+	let content_length = request
+		.headers()
+		.get(http::header::CONTENT_LENGTH)
+		.and_then(|content_length| content_length.to_str().ok())
+		.and_then(|content_length| content_length.parse::<u64>().ok());
+	if content_length.map_or(false, |content_length| content_length > limit) {
+		return false;
+	}
+	let body = std::mem::take(request.body_mut());
+	let mut bytes_read: u64 = 0;
+	let body = body.map(
+		move |chunk| -> Result<Bytes, Box<dyn std::error::Error + Send + Sync>> {
+			let chunk = chunk?;
+			bytes_read += chunk.len() as u64;
+			if bytes_read > limit {
+				return Err(Box::new(BodySizeLimitExceeded { limit }));
+			}
+			Ok(chunk)
+		},
+	);
+	*request.body_mut() = hyper::Body::wrap_stream(body);
+	true
+}
+
+/// Write a stream of bytes, such as a multipart field, to a temporary file one chunk at a time, logging progress as it goes. The file is deleted when the returned handle is dropped.
+pub async fn write_stream_to_temp_file<S, E>(mut stream: S) -> Result<tempfile::NamedTempFile>
+where
+	S: Stream<Item = Result<Bytes, E>> + Unpin,
+	E: Into<anyhow::Error>,
+{
+	let temp_file = tempfile::NamedTempFile::new()?;
+	let mut file = tokio::fs::File::create(temp_file.path()).await?;
+	let mut bytes_written: u64 = 0;
+	let mut next_progress_log = UPLOAD_PROGRESS_LOG_INTERVAL_BYTES;
+	while let Some(chunk) = stream.next().await {
+		let chunk = chunk.map_err(Into::into)?;
+		file.write_all(&chunk).await?;
+		bytes_written += chunk.len() as u64;
+		if bytes_written >= next_progress_log {
+			tracing::info!(bytes_written, "upload in progress");
+			next_progress_log += UPLOAD_PROGRESS_LOG_INTERVAL_BYTES;
+		}
+	}
+	file.flush().await?;
+	tracing::info!(bytes_written, "upload complete");
+	Ok(temp_file)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_limit_request_body() {
+		// A request that declares a length over the limit is rejected up front.
+		let mut request = http::Request::builder()
+			.header(http::header::CONTENT_LENGTH, "11")
+			.body(hyper::Body::from("hello world"))
+			.unwrap();
+		assert!(!limit_request_body(&mut request, 10));
+		// A request without a declared length fails once it is read past the limit.
+		let mut request = http::Request::builder()
+			.body(hyper::Body::from("hello world"))
+			.unwrap();
+		assert!(limit_request_body(&mut request, 10));
+		let error = hyper::body::to_bytes(request.body_mut()).await.unwrap_err();
+		assert!(is_body_size_limit_exceeded(&error.into()));
+		// A request within the limit is read in full.
+		let mut request = http::Request::builder()
+			.body(hyper::Body::from("hello"))
+			.unwrap();
+		assert!(limit_request_body(&mut request, 10));
+		let body = hyper::body::to_bytes(request.body_mut()).await.unwrap();
+		assert_eq!(body, Bytes::from("hello"));
+	}
+
+	#[tokio::test]
+	async fn test_write_stream_to_temp_file() {
+		let chunks: Vec<Result<Bytes, std::io::Error>> =
+			vec![Ok(Bytes::from("hello ")), Ok(Bytes::from("world"))];
+		let temp_file = write_stream_to_temp_file(futures::stream::iter(chunks))
+			.await
+			.unwrap();
+		let contents = std::fs::read(temp_file.path()).unwrap();
+		assert_eq!(contents, b"hello world");
+		// Writing a limited body fails once the body is read past its limit.
+		let mut request = http::Request::builder()
+			.body(hyper::Body::from("hello world"))
+			.unwrap();
+		assert!(limit_request_body(&mut request, 10));
+		let error = write_stream_to_temp_file(request.body_mut())
+			.await
+			.unwrap_err();
+		assert!(is_body_size_limit_exceeded(&error));
+	}
+}
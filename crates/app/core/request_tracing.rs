@@ -0,0 +1,121 @@
+//! This module records per-request details on the tracing span the app creates for each request, including the time spent in database transactions.
+
+use modelfox_id::Id;
+use std::{
+	cell::RefCell,
+	future::Future,
+	time::{Duration, Instant},
+};
+use tracing::Instrument;
+
+tokio::task_local! {
+	static DB_TIME: RefCell<DbTime>;
+}
+
+#[derive(Default)]
+struct DbTime {
+	total: Duration,
+	open_transactions: Vec<Instant>,
+}
+
+/// Create the span for a request. The fields left empty are recorded as the request is handled.
+pub fn request_span(request_id: Id, path: &str) -> tracing::Span {
+	tracing::info_span!(
+		"app_request",
+		%request_id,
+		route = %route_pattern(path),
+		user_id = tracing::field::Empty,
+		model_id = tracing::field::Empty,
+		db_time_us = tracing::field::Empty,
+	)
+}
+
+/// Run `future` within `span`, then record the time its database transactions were open on the span and log the request.
+pub async fn trace_request<F: Future>(span: tracing::Span, future: F) -> F::Output {
+	DB_TIME
+		.scope(RefCell::new(DbTime::default()), async move {
+			let output = future.instrument(span.clone()).await;
+			let db_time = DB_TIME.with(|db_time| {
+				let db_time = db_time.borrow();
+				// Transactions that were rolled back by being dropped are counted until the end of the request.
+				db_time.total
+					+ db_time
+						.open_transactions
+						.iter()
+						.map(|opened_at| opened_at.elapsed())
+						.sum::<Duration>()
+			});
+			span.record(
+				"db_time_us",
+				&u64::try_from(db_time.as_micros()).unwrap_or(u64::MAX),
+			);
+			tracing::info!(parent: &span, "handled");
+			output
+		})
+		.await
+}
+
+pub fn record_transaction_begin() {
+	DB_TIME
+		.try_with(|db_time| db_time.borrow_mut().open_transactions.push(Instant::now()))
+		.ok();
+}
+
+pub fn record_transaction_commit() {
+	DB_TIME
+		.try_with(|db_time| {
+			let mut db_time = db_time.borrow_mut();
+			if let Some(opened_at) = db_time.open_transactions.pop() {
+				db_time.total += opened_at.elapsed();
+			}
+		})
+		.ok();
+}
+
+pub fn record_user_id(user_id: &str) {
+	tracing::Span::current().record("user_id", &user_id);
+}
+
+pub fn record_model_id(model_id: Id) {
+	tracing::Span::current().record("model_id", &tracing::field::display(model_id));
+}
+
+/// Replace the ids in a request path with `_`, matching the layout of the routes directory, so requests to the same route share a route name.
+pub fn route_pattern(path: &str) -> String {
+	let route = path
+		.split('/')
+		.map(|component| {
+			if component.parse::<Id>().is_ok() {
+				"_"
+			} else {
+				component
+			}
+		})
+		.collect::<Vec<_>>()
+		.join("/");
+	if route.is_empty() {
+		"/".to_owned()
+	} else {
+		route
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_route_pattern() {
+		let model_id = Id::generate();
+		let repo_id = Id::generate();
+		assert_eq!(
+			route_pattern(&format!(
+				"/repos/{}/models/{}/training_metrics",
+				repo_id, model_id
+			)),
+			"/repos/_/models/_/training_metrics"
+		);
+		assert_eq!(route_pattern("/login"), "/login");
+		assert_eq!(route_pattern("/"), "/");
+	}
+}
@@ -0,0 +1,259 @@
+//! A session is a token issued when a user logs in. Sessions record when and where they were created and last used, so users can review their active sessions and revoke the ones they do not recognize.
+
+use anyhow::Result;
+use modelfox_id::Id;
+use modelfox_serve::RemoteAddr;
+use sqlx::prelude::*;
+use std::{borrow::BorrowMut, net::IpAddr};
+
+/// A session's last active time is only updated when it is older than this many seconds, so every request does not write to the database.
+const SESSION_LAST_ACTIVE_RESOLUTION_SECONDS: i64 = 60;
+
+pub struct Session {
+	pub id: Id,
+	pub created_at: Option<i64>,
+	pub last_active_at: Option<i64>,
+	pub ip: Option<String>,
+	pub user_agent: Option<String>,
+	/// This is true for the session the request listing the sessions was made with.
+	pub current: bool,
+}
+
+/// Create a session for the user and return its token.
+pub async fn create_session(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	request: &http::Request<hyper::Body>,
+	user_id: Id,
+	now: i64,
+	trusted_proxies: &[IpAddr],
+) -> Result<Id> {
+	let id = Id::generate();
+	let token = Id::generate();
+	let user_agent = request
+		.headers()
+		.get(http::header::USER_AGENT)
+		.and_then(|user_agent| user_agent.to_str().ok());
+	sqlx::query(
+		"
+			insert into tokens (
+				id, token, user_id, created_at, last_active_at, ip, user_agent
+			) values (
+				$1, $2, $3, $4, $4, $5, $6
+			)
+		",
+	)
+	.bind(&id.to_string())
+	.bind(&token.to_string())
+	.bind(&user_id.to_string())
+	.bind(now)
+	.bind(client_ip(request, trusted_proxies))
+	.bind(user_agent)
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(token)
+}
+
+/// Get the user's sessions that have not been revoked, most recently active first.
+pub async fn get_sessions(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user_id: Id,
+	current_token: &str,
+) -> Result<Vec<Session>> {
+	let rows = sqlx::query(
+		"
+			select
+				id,
+				token,
+				created_at,
+				last_active_at,
+				ip,
+				user_agent
+			from tokens
+			where
+				user_id = $1
+			and
+				revoked_at is null
+			order by last_active_at desc
+		",
+	)
+	.bind(&user_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let id: String = row.get(0);
+			let token: String = row.get(1);
+			Ok(Session {
+				id: id.parse()?,
+				created_at: row.get(2),
+				last_active_at: row.get(3),
+				ip: row.get(4),
+				user_agent: row.get(5),
+				current: token == current_token,
+			})
+		})
+		.collect()
+}
+
+/// Record that the session with the given token was used.
+pub async fn touch_session(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	token: &str,
+	now: i64,
+) -> Result<()> {
+	sqlx::query(
+		"
+			update tokens
+			set
+				last_active_at = $1
+			where
+				token = $2
+			and
+				(last_active_at is null or last_active_at < $3)
+		",
+	)
+	.bind(now)
+	.bind(token)
+	.bind(now - SESSION_LAST_ACTIVE_RESOLUTION_SECONDS)
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Revoke one of the user's sessions.
+pub async fn revoke_session(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user_id: Id,
+	session_id: Id,
+	now: i64,
+) -> Result<()> {
+	sqlx::query(
+		"
+			update tokens
+			set
+				revoked_at = $1
+			where
+				id = $2
+			and
+				user_id = $3
+			and
+				revoked_at is null
+		",
+	)
+	.bind(now)
+	.bind(&session_id.to_string())
+	.bind(&user_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Revoke all of the user's sessions, logging them out everywhere.
+pub async fn revoke_all_sessions(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user_id: Id,
+	now: i64,
+) -> Result<()> {
+	sqlx::query(
+		"
+			update tokens
+			set
+				revoked_at = $1
+			where
+				user_id = $2
+			and
+				revoked_at is null
+		",
+	)
+	.bind(now)
+	.bind(&user_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Get the address of the client that sent the request. If the request comes from one of the `trusted_proxies`, this is the last address in the `x-forwarded-for` header that is not a trusted proxy, because each proxy appends the address it received the request from. Otherwise, the header may have been set by the client, so this is the address of the connection.
+pub fn client_ip(
+	request: &http::Request<hyper::Body>,
+	trusted_proxies: &[IpAddr],
+) -> Option<String> {
+	let remote_ip = request
+		.extensions()
+		.get::<RemoteAddr>()
+		.map(|remote_addr| remote_addr.0.ip())?;
+	if !trusted_proxies.contains(&remote_ip) {
+		return Some(remote_ip.to_string());
+	}
+	let forwarded_for = request
+		.headers()
+		.get("x-forwarded-for")
+		.and_then(|forwarded_for| forwarded_for.to_str().ok());
+	let forwarded_for = match forwarded_for {
+		Some(forwarded_for) => forwarded_for,
+		None => return Some(remote_ip.to_string()),
+	};
+	let mut client_ip = remote_ip;
+	for ip in forwarded_for.split(',').rev() {
+		let ip: IpAddr = match ip.trim().parse() {
+			Ok(ip) => ip,
+			Err(_) => break,
+		};
+		client_ip = ip;
+		if !trusted_proxies.contains(&ip) {
+			break;
+		}
+	}
+	Some(client_ip.to_string())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_client_ip() {
+		let request = |remote_ip: &str, forwarded_for: Option<&str>| {
+			let mut request = http::Request::builder();
+			if let Some(forwarded_for) = forwarded_for {
+				request = request.header("x-forwarded-for", forwarded_for);
+			}
+			let mut request = request.body(hyper::Body::empty()).unwrap();
+			let remote_addr = std::net::SocketAddr::new(remote_ip.parse().unwrap(), 1234);
+			request.extensions_mut().insert(RemoteAddr(remote_addr));
+			request
+		};
+		let trusted_proxies: Vec<IpAddr> =
+			vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()];
+		// The header is ignored in requests that do not come from a trusted proxy.
+		assert_eq!(
+			client_ip(&request("1.1.1.1", Some("2.2.2.2")), &trusted_proxies).as_deref(),
+			Some("1.1.1.1"),
+		);
+		assert_eq!(
+			client_ip(&request("1.1.1.1", Some("2.2.2.2")), &[]).as_deref(),
+			Some("1.1.1.1"),
+		);
+		// Addresses the client put in the header ahead of those appended by the proxies are ignored.
+		assert_eq!(
+			client_ip(
+				&request("10.0.0.1", Some("2.2.2.2, 3.3.3.3, 10.0.0.2")),
+				&trusted_proxies,
+			)
+			.as_deref(),
+			Some("3.3.3.3"),
+		);
+		assert_eq!(
+			client_ip(&request("10.0.0.1", None), &trusted_proxies).as_deref(),
+			Some("10.0.0.1"),
+		);
+		// An address that cannot be parsed stops the search at the last valid address.
+		assert_eq!(
+			client_ip(
+				&request("10.0.0.1", Some("not an ip, 10.0.0.2")),
+				&trusted_proxies
+			)
+			.as_deref(),
+			Some("10.0.0.2"),
+		);
+	}
+}
@@ -4,7 +4,7 @@ use bytes::Bytes;
 use modelfox_id::Id;
 use std::{
 	collections::HashMap,
-	path::PathBuf,
+	path::{Path, PathBuf},
 	sync::{Arc, RwLock},
 };
 
@@ -18,7 +18,8 @@ pub enum Storage {
 
 #[derive(Debug)]
 pub struct InMemoryStorage {
-	storage: Arc<RwLock<HashMap<Id, Bytes>>>,
+	/// The items are keyed by [`key_for_item`], so items of different entities with the same id do not collide.
+	storage: Arc<RwLock<HashMap<String, Bytes>>>,
 }
 
 #[derive(Debug)]
@@ -90,6 +91,15 @@ impl Storage {
 		}
 	}
 
+	/// Store the contents of the file at `path`. Unlike [`Storage::set`], this does not read the whole file into memory, except for in memory storage.
+	pub async fn set_from_path(&self, entity: StorageEntity, id: Id, path: &Path) -> Result<()> {
+		match self {
+			Storage::InMemory(s) => s.set(entity, id, &tokio::fs::read(path).await?).await,
+			Storage::Local(s) => s.set_from_path(entity, id, path).await,
+			Storage::S3(s) => s.set_from_path(entity, id, path).await,
+		}
+	}
+
 	pub async fn remove(&self, entity: StorageEntity, id: Id) -> Result<()> {
 		match self {
 			Storage::InMemory(s) => s.remove(entity, id).await,
@@ -114,10 +124,10 @@ impl Default for InMemoryStorage {
 }
 
 impl InMemoryStorage {
-	async fn get(&self, _entity: StorageEntity, id: Id) -> Result<BytesOrFilePath> {
+	async fn get(&self, entity: StorageEntity, id: Id) -> Result<BytesOrFilePath> {
 		let storage = Arc::clone(&self.storage);
 		let ret = if let Ok(read_guard) = storage.read() {
-			if let Some(bytes) = (*read_guard).get(&id) {
+			if let Some(bytes) = (*read_guard).get(&key_for_item(entity, id)) {
 				Ok(BytesOrFilePath::from(bytes.clone()))
 			} else {
 				Err(anyhow!("No such ID in storage"))
@@ -128,18 +138,18 @@ impl InMemoryStorage {
 		ret
 	}
 
-	async fn set(&self, _entity: StorageEntity, id: Id, data: &[u8]) -> Result<()> {
+	async fn set(&self, entity: StorageEntity, id: Id, data: &[u8]) -> Result<()> {
 		let storage = Arc::clone(&self.storage);
 		if let Ok(mut write_guard) = storage.write() {
-			(*write_guard).insert(id, Bytes::from(data.to_owned()));
+			(*write_guard).insert(key_for_item(entity, id), Bytes::from(data.to_owned()));
 		}
 		Ok(())
 	}
 
-	async fn remove(&self, _entity: StorageEntity, id: Id) -> Result<()> {
+	async fn remove(&self, entity: StorageEntity, id: Id) -> Result<()> {
 		let storage = Arc::clone(&self.storage);
 		if let Ok(mut write_guard) = storage.write() {
-			(*write_guard).remove(&id);
+			(*write_guard).remove(&key_for_item(entity, id));
 		}
 		Ok(())
 	}
@@ -160,6 +170,14 @@ impl LocalStorage {
 		Ok(())
 	}
 
+	async fn set_from_path(&self, entity: StorageEntity, id: Id, path: &Path) -> Result<()> {
+		let entity_path = self.path.join(entity.dir_name());
+		tokio::fs::create_dir_all(&entity_path).await?;
+		let item_path = entity_path.join(id.to_string());
+		tokio::fs::copy(path, item_path).await?;
+		Ok(())
+	}
+
 	async fn remove(&self, entity: StorageEntity, id: Id) -> Result<()> {
 		let entity_path = self.path.join(entity.dir_name());
 		let item_path = entity_path.join(id.to_string());
@@ -217,6 +235,20 @@ impl S3Storage {
 		Ok(())
 	}
 
+	async fn set_from_path(&self, entity: StorageEntity, id: Id, path: &Path) -> Result<()> {
+		let entity_cache_path = self.cache_path.join(entity.dir_name());
+		tokio::fs::create_dir_all(&entity_cache_path).await?;
+		let item_cache_path = entity_cache_path.join(id.to_string());
+		// Upload the item to s3 in parts.
+		let mut file = tokio::fs::File::open(path).await?;
+		self.bucket
+			.put_object_stream(&mut file, key_for_item(entity, id))
+			.await?;
+		// Add the item to the cache.
+		tokio::fs::copy(path, item_cache_path).await?;
+		Ok(())
+	}
+
 	async fn remove(&self, entity: StorageEntity, id: Id) -> Result<()> {
 		// Remove the item from the cache if it exists.
 		let entity_cache_path = self.cache_path.join(entity.dir_name());
@@ -228,6 +260,16 @@ impl S3Storage {
 		self.bucket.delete_object(&key_for_item(entity, id)).await?;
 		Ok(())
 	}
+
+	/// Remove the item from the local cache without removing it from s3. This is used when another instance of the app deletes the item.
+	pub async fn evict_cached(&self, entity: StorageEntity, id: Id) -> Result<()> {
+		let entity_cache_path = self.cache_path.join(entity.dir_name());
+		let item_cache_path = entity_cache_path.join(id.to_string());
+		if tokio::fs::metadata(&item_cache_path).await.is_ok() {
+			tokio::fs::remove_file(item_cache_path).await?;
+		}
+		Ok(())
+	}
 }
 
 fn key_for_item(entity: StorageEntity, id: Id) -> String {
@@ -237,12 +279,28 @@ fn key_for_item(entity: StorageEntity, id: Id) -> String {
 #[derive(Clone, Copy)]
 pub enum StorageEntity {
 	Model,
+	CustomMetric,
+	BatchPredictInput,
+	BatchPredictOutput,
 }
 
 impl StorageEntity {
-	fn dir_name(&self) -> &'static str {
+	pub fn dir_name(&self) -> &'static str {
 		match self {
 			StorageEntity::Model => "models",
+			StorageEntity::CustomMetric => "custom_metrics",
+			StorageEntity::BatchPredictInput => "batch_predict_inputs",
+			StorageEntity::BatchPredictOutput => "batch_predict_outputs",
+		}
+	}
+
+	pub fn from_dir_name(dir_name: &str) -> Option<StorageEntity> {
+		match dir_name {
+			"models" => Some(StorageEntity::Model),
+			"custom_metrics" => Some(StorageEntity::CustomMetric),
+			"batch_predict_inputs" => Some(StorageEntity::BatchPredictInput),
+			"batch_predict_outputs" => Some(StorageEntity::BatchPredictOutput),
+			_ => None,
 		}
 	}
 }
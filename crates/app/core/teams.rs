@@ -0,0 +1,558 @@
+/*!
+Teams group the members of an organization so repos can be granted to a team instead of to each member. A repo that is granted to one or more teams is only accessible to the members of those teams and to the organization's admins. A repo that is granted to no teams is accessible to every member of the organization, which is how repos worked before teams existed. Each member of a team is either a member or a maintainer, and maintainers can manage the team's members. Only the organization's admins can grant repos to teams. See [`crate::user::authorize_normal_user_for_repo`].
+*/
+
+use crate::user::NormalUser;
+use anyhow::Result;
+use modelfox_id::Id;
+use sqlx::prelude::*;
+use std::borrow::BorrowMut;
+
+pub struct Team {
+	pub id: Id,
+	pub organization_id: Id,
+	pub name: String,
+	pub created_at: i64,
+	pub member_count: i64,
+	pub repo_count: i64,
+}
+
+pub struct TeamMember {
+	pub user_id: Id,
+	pub email: String,
+	pub is_maintainer: bool,
+}
+
+pub struct TeamRepo {
+	pub id: Id,
+	pub title: String,
+}
+
+/// Create a team in the organization and return its id.
+pub async fn create_team(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+	name: &str,
+	now: i64,
+) -> Result<Id> {
+	let id = Id::generate();
+	sqlx::query(
+		"
+			insert into teams (
+				id, organization_id, name, created_at
+			) values (
+				$1, $2, $3, $4
+			)
+		",
+	)
+	.bind(&id.to_string())
+	.bind(&organization_id.to_string())
+	.bind(name)
+	.bind(now)
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(id)
+}
+
+/// Get the organization's teams, in the order they were created.
+pub async fn get_teams(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+) -> Result<Vec<Team>> {
+	let rows = sqlx::query(
+		"
+			select
+				teams.id,
+				teams.name,
+				teams.created_at,
+				(select count(*) from teams_users where teams_users.team_id = teams.id),
+				(select count(*) from repos_teams where repos_teams.team_id = teams.id)
+			from teams
+			where teams.organization_id = $1
+			order by teams.created_at
+		",
+	)
+	.bind(&organization_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.into_iter()
+		.map(|row| {
+			Ok(Team {
+				id: row.get::<String, _>(0).parse()?,
+				organization_id,
+				name: row.get(1),
+				created_at: row.get(2),
+				member_count: row.get(3),
+				repo_count: row.get(4),
+			})
+		})
+		.collect()
+}
+
+/// Get the team with the given id, if it belongs to the organization.
+pub async fn get_team(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+	team_id: Id,
+) -> Result<Option<Team>> {
+	Ok(get_teams(txn, organization_id)
+		.await?
+		.into_iter()
+		.find(|team| team.id == team_id))
+}
+
+/// Delete the team. The repos granted to it are no longer accessible to its members, unless they are granted to another of their teams or to no teams at all.
+pub async fn delete_team(txn: &mut sqlx::Transaction<'_, sqlx::Any>, team_id: Id) -> Result<()> {
+	for query in [
+		"delete from repos_teams where team_id = $1",
+		"delete from teams_users where team_id = $1",
+		"delete from teams where id = $1",
+	] {
+		sqlx::query(query)
+			.bind(&team_id.to_string())
+			.execute(txn.borrow_mut())
+			.await?;
+	}
+	Ok(())
+}
+
+/// Get the members of the team, ordered by email.
+pub async fn get_team_members(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	team_id: Id,
+) -> Result<Vec<TeamMember>> {
+	let rows = sqlx::query(
+		"
+			select
+				users.id,
+				users.email,
+				teams_users.is_maintainer
+			from teams_users
+			join users
+				on users.id = teams_users.user_id
+			where teams_users.team_id = $1
+			order by users.email
+		",
+	)
+	.bind(&team_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.into_iter()
+		.map(|row| {
+			Ok(TeamMember {
+				user_id: row.get::<String, _>(0).parse()?,
+				email: row.get(1),
+				is_maintainer: row.get(2),
+			})
+		})
+		.collect()
+}
+
+/// Add a member of the team's organization to the team, or update their role if they are already on it. Return whether the user is a member of the organization.
+pub async fn add_team_member(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	team_id: Id,
+	user_id: Id,
+	is_maintainer: bool,
+) -> Result<bool> {
+	remove_team_member(txn, team_id, user_id).await?;
+	let result = sqlx::query(
+		"
+			insert into teams_users (
+				team_id, user_id, is_maintainer
+			)
+			select
+				teams.id,
+				organizations_users.user_id,
+				$3
+			from teams
+			join organizations_users
+				on organizations_users.organization_id = teams.organization_id
+			where
+				teams.id = $1 and
+				organizations_users.user_id = $2
+		",
+	)
+	.bind(&team_id.to_string())
+	.bind(&user_id.to_string())
+	.bind(is_maintainer)
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(result.rows_affected() > 0)
+}
+
+pub async fn remove_team_member(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	team_id: Id,
+	user_id: Id,
+) -> Result<()> {
+	sqlx::query(
+		"
+			delete from teams_users
+			where
+				team_id = $1 and
+				user_id = $2
+		",
+	)
+	.bind(&team_id.to_string())
+	.bind(&user_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Remove the user from every team in the organization. Call this when the user leaves the organization.
+pub async fn remove_user_from_organization_teams(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+	user_id: Id,
+) -> Result<()> {
+	sqlx::query(
+		"
+			delete from teams_users
+			where
+				user_id = $2 and
+				team_id in (
+					select teams.id
+					from teams
+					where teams.organization_id = $1
+				)
+		",
+	)
+	.bind(&organization_id.to_string())
+	.bind(&user_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Get the repos granted to the team, ordered by title.
+pub async fn get_team_repos(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	team_id: Id,
+) -> Result<Vec<TeamRepo>> {
+	let rows = sqlx::query(
+		"
+			select
+				repos.id,
+				repos.title
+			from repos_teams
+			join repos
+				on repos.id = repos_teams.repo_id
+			where repos_teams.team_id = $1
+			order by repos.title
+		",
+	)
+	.bind(&team_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.into_iter()
+		.map(|row| {
+			Ok(TeamRepo {
+				id: row.get::<String, _>(0).parse()?,
+				title: row.get(1),
+			})
+		})
+		.collect()
+}
+
+/// Grant the repo to the team. Return whether the repo belongs to the team's organization.
+pub async fn grant_repo_to_team(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+	team_id: Id,
+) -> Result<bool> {
+	revoke_repo_from_team(txn, repo_id, team_id).await?;
+	let result = sqlx::query(
+		"
+			insert into repos_teams (
+				repo_id, team_id
+			)
+			select
+				repos.id,
+				teams.id
+			from repos
+			join teams
+				on teams.organization_id = repos.organization_id
+			where
+				repos.id = $1 and
+				teams.id = $2
+		",
+	)
+	.bind(&repo_id.to_string())
+	.bind(&team_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(result.rows_affected() > 0)
+}
+
+pub async fn revoke_repo_from_team(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+	team_id: Id,
+) -> Result<()> {
+	sqlx::query(
+		"
+			delete from repos_teams
+			where
+				repo_id = $1 and
+				team_id = $2
+		",
+	)
+	.bind(&repo_id.to_string())
+	.bind(&team_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Determine if the user may manage the team's members. The organization's admins may manage every team, and the team's maintainers may manage their team.
+pub async fn authorize_normal_user_for_team_maintenance(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user: &NormalUser,
+	team_id: Id,
+) -> Result<bool> {
+	Ok(sqlx::query(
+		"
+			select
+				count(*) > 0
+			from teams
+			left join organizations_users
+				on organizations_users.organization_id = teams.organization_id
+				and organizations_users.user_id = $1
+			left join teams_users
+				on teams_users.team_id = teams.id
+				and teams_users.user_id = $1
+			where
+				teams.id = $2 and (
+					organizations_users.is_admin = true or
+					teams_users.is_maintainer = true
+				)
+		",
+	)
+	.bind(&user.id.to_string())
+	.bind(&team_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?
+	.get(0))
+}
+
+/// Determine if the user may grant repos to the team and revoke them. Granting a repo to a team hides it from the organization's other members who are not on a team it is granted to, and would let the team's members into a repo restricted to another team, so only the organization's admins may, not the team's maintainers.
+pub async fn authorize_normal_user_for_team_repos(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user: &NormalUser,
+	team_id: Id,
+) -> Result<bool> {
+	Ok(sqlx::query(
+		"
+			select
+				count(*) > 0
+			from teams
+			join organizations_users
+				on organizations_users.organization_id = teams.organization_id
+				and organizations_users.user_id = $1
+			where
+				teams.id = $2 and
+				organizations_users.is_admin = true
+		",
+	)
+	.bind(&user.id.to_string())
+	.bind(&team_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?
+	.get(0))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		test_common::*,
+		user::{authorize_normal_user_for_model, authorize_normal_user_for_repo},
+	};
+
+	async fn create_user(txn: &mut sqlx::Transaction<'_, sqlx::Any>, email: &str) -> NormalUser {
+		let id = Id::generate();
+		sqlx::query("insert into users (id, email) values ($1, $2)")
+			.bind(&id.to_string())
+			.bind(email)
+			.execute(txn.borrow_mut())
+			.await
+			.unwrap();
+		NormalUser {
+			id,
+			email: email.to_owned(),
+			token: Id::generate().to_string(),
+		}
+	}
+
+	/// Create an organization and move the repo of the model into it. This returns the ids of the organization and the repo.
+	async fn move_model_to_organization(
+		txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+		model_id: Id,
+	) -> (Id, Id) {
+		let organization_id = Id::generate();
+		sqlx::query("insert into organizations (id, name) values ($1, 'Test')")
+			.bind(&organization_id.to_string())
+			.execute(txn.borrow_mut())
+			.await
+			.unwrap();
+		let repo_id: String = sqlx::query("select repo_id from models where id = $1")
+			.bind(&model_id.to_string())
+			.fetch_one(txn.borrow_mut())
+			.await
+			.unwrap()
+			.get(0);
+		sqlx::query("update repos set organization_id = $1 where id = $2")
+			.bind(&organization_id.to_string())
+			.bind(&repo_id)
+			.execute(txn.borrow_mut())
+			.await
+			.unwrap();
+		(organization_id, repo_id.parse().unwrap())
+	}
+
+	async fn add_organization_user(
+		txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+		organization_id: Id,
+		user: &NormalUser,
+		is_admin: bool,
+	) {
+		sqlx::query(
+			"insert into organizations_users (organization_id, user_id, is_admin) values ($1, $2, $3)",
+		)
+		.bind(&organization_id.to_string())
+		.bind(&user.id.to_string())
+		.bind(is_admin)
+		.execute(txn.borrow_mut())
+		.await
+		.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_repo_access_through_teams() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let (organization_id, repo_id) = move_model_to_organization(&mut txn, model_id).await;
+		let admin = create_user(&mut txn, "admin@modelfox.dev").await;
+		let member = create_user(&mut txn, "member@modelfox.dev").await;
+		let outsider = create_user(&mut txn, "outsider@modelfox.dev").await;
+		add_organization_user(&mut txn, organization_id, &admin, true).await;
+		add_organization_user(&mut txn, organization_id, &member, false).await;
+		// A repo granted to no teams is accessible to every member of the organization.
+		assert!(authorize_normal_user_for_repo(&mut txn, &member, repo_id)
+			.await
+			.unwrap());
+		assert!(
+			!authorize_normal_user_for_repo(&mut txn, &outsider, repo_id)
+				.await
+				.unwrap()
+		);
+		// Once the repo is granted to a team, only its members and the admins can access it.
+		let team_id = create_team(&mut txn, organization_id, "Data Science", 0)
+			.await
+			.unwrap();
+		assert!(grant_repo_to_team(&mut txn, repo_id, team_id)
+			.await
+			.unwrap());
+		assert!(!authorize_normal_user_for_repo(&mut txn, &member, repo_id)
+			.await
+			.unwrap());
+		assert!(
+			!authorize_normal_user_for_model(&mut txn, &member, model_id)
+				.await
+				.unwrap()
+		);
+		assert!(authorize_normal_user_for_repo(&mut txn, &admin, repo_id)
+			.await
+			.unwrap());
+		assert!(add_team_member(&mut txn, team_id, member.id, false)
+			.await
+			.unwrap());
+		assert!(!add_team_member(&mut txn, team_id, outsider.id, false)
+			.await
+			.unwrap());
+		assert!(authorize_normal_user_for_repo(&mut txn, &member, repo_id)
+			.await
+			.unwrap());
+		assert!(authorize_normal_user_for_model(&mut txn, &member, model_id)
+			.await
+			.unwrap());
+		assert!(
+			!authorize_normal_user_for_team_maintenance(&mut txn, &member, team_id)
+				.await
+				.unwrap()
+		);
+		assert!(
+			authorize_normal_user_for_team_maintenance(&mut txn, &admin, team_id)
+				.await
+				.unwrap()
+		);
+		remove_user_from_organization_teams(&mut txn, organization_id, member.id)
+			.await
+			.unwrap();
+		assert!(!authorize_normal_user_for_repo(&mut txn, &member, repo_id)
+			.await
+			.unwrap());
+		app.commit_transaction(txn).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_maintainer_cannot_grant_repo_restricted_to_another_team() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let (organization_id, repo_id) = move_model_to_organization(&mut txn, model_id).await;
+		let admin = create_user(&mut txn, "admin@modelfox.dev").await;
+		let maintainer = create_user(&mut txn, "maintainer@modelfox.dev").await;
+		add_organization_user(&mut txn, organization_id, &admin, true).await;
+		add_organization_user(&mut txn, organization_id, &maintainer, false).await;
+		// The repo is restricted to a team the maintainer is not on.
+		let restricted_team_id = create_team(&mut txn, organization_id, "Finance", 0)
+			.await
+			.unwrap();
+		assert!(grant_repo_to_team(&mut txn, repo_id, restricted_team_id)
+			.await
+			.unwrap());
+		let team_id = create_team(&mut txn, organization_id, "Data Science", 0)
+			.await
+			.unwrap();
+		assert!(add_team_member(&mut txn, team_id, maintainer.id, true)
+			.await
+			.unwrap());
+		// The maintainer manages their team's members, but may not grant repos to it, so they cannot let themselves into the restricted repo.
+		assert!(
+			authorize_normal_user_for_team_maintenance(&mut txn, &maintainer, team_id)
+				.await
+				.unwrap()
+		);
+		assert!(
+			!authorize_normal_user_for_team_repos(&mut txn, &maintainer, team_id)
+				.await
+				.unwrap()
+		);
+		assert!(
+			!authorize_normal_user_for_repo(&mut txn, &maintainer, repo_id)
+				.await
+				.unwrap()
+		);
+		assert!(
+			!authorize_normal_user_for_model(&mut txn, &maintainer, model_id)
+				.await
+				.unwrap()
+		);
+		// The organization's admins may grant repos to every team.
+		assert!(
+			authorize_normal_user_for_team_repos(&mut txn, &admin, team_id)
+				.await
+				.unwrap()
+		);
+		assert!(
+			authorize_normal_user_for_team_repos(&mut txn, &admin, restricted_team_id)
+				.await
+				.unwrap()
+		);
+		app.commit_transaction(txn).await.unwrap();
+	}
+}
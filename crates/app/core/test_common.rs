@@ -24,18 +24,28 @@ pub fn init_test_options() -> Options {
 	let host: IpAddr = "0.0.0.0".parse().unwrap();
 	let port = 8080;
 	let database = DatabaseOptions {
+		analytics_statement_timeout: None,
 		max_connections: None,
+		replica_url: None,
 		url: "sqlite::memory:".parse().unwrap(),
 	};
 	let storage = StorageOptions::InMemory;
 	Options {
 		auth: None,
+		base_path: String::new(),
+		body_size_limits: Default::default(),
 		cookie_domain: None,
 		database,
 		host,
+		ingest: None,
+		pause_notifications: false,
 		port,
+		secret_key: Some("test secret key".to_owned()),
 		smtp: None,
+		stateless: false,
 		storage,
+		tree_shap_mode: Default::default(),
+		trusted_proxies: Vec::new(),
 		url: None,
 	}
 }
@@ -110,6 +120,7 @@ pub async fn seed_events(app: &App, examples_count: usize, model_id: Id) -> Resu
 				model_id: model_id.parse().unwrap(),
 				options: None,
 				output,
+				sample_rate: None,
 			})];
 			if seed_float > 0.4 {
 				events.push(MonitorEvent::TrueValue(TrueValueMonitorEvent {
@@ -117,6 +128,7 @@ pub async fn seed_events(app: &App, examples_count: usize, model_id: Id) -> Resu
 					identifier: NumberOrString::String(id.to_string()),
 					true_value: target,
 					date,
+					sample_rate: None,
 				}));
 			}
 			idx += 1;
@@ -283,6 +295,7 @@ pub async fn seed_single_prediction_event(app: &App, model_id: Id) -> Result<(Id
 		model_id,
 		options: None,
 		output,
+		sample_rate: None,
 	});
 
 	let mut txn = app.begin_transaction().await?;
@@ -324,6 +337,7 @@ pub async fn seed_single_true_value_event(
 		identifier: NumberOrString::String(id.to_string()),
 		true_value: target.into(),
 		date,
+		sample_rate: None,
 	});
 
 	let mut txn = app.begin_transaction().await?;
@@ -376,6 +390,11 @@ pub async fn seed_monitors(app: &App, model_id: Id) -> Result<()> {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		},
 		MonitorConfig {
 			cadence: MonitorCadence::Daily,
@@ -387,6 +406,11 @@ pub async fn seed_monitors(app: &App, model_id: Id) -> Result<()> {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		},
 		MonitorConfig {
 			cadence: MonitorCadence::Weekly,
@@ -398,6 +422,11 @@ pub async fn seed_monitors(app: &App, model_id: Id) -> Result<()> {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		},
 		MonitorConfig {
 			cadence: MonitorCadence::Monthly,
@@ -409,6 +438,11 @@ pub async fn seed_monitors(app: &App, model_id: Id) -> Result<()> {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		},
 	];
 	for monitor_config in monitor_configs {
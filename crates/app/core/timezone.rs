@@ -1,7 +1,17 @@
 use super::cookies::parse_cookies;
+use crate::user::User;
+use anyhow::Result;
 use chrono_tz::Tz;
+use modelfox_id::Id;
+use sqlx::prelude::*;
+use std::borrow::BorrowMut;
 
+/// Get the timezone the browser reported in the `modelfox_timezone` cookie, or UTC if it is absent.
 pub fn get_timezone(request: &http::Request<hyper::Body>) -> Tz {
+	get_cookie_timezone(request).unwrap_or(Tz::UTC)
+}
+
+fn get_cookie_timezone(request: &http::Request<hyper::Body>) -> Option<Tz> {
 	request
 		.headers()
 		.get(http::header::COOKIE)
@@ -9,5 +19,193 @@ pub fn get_timezone(request: &http::Request<hyper::Body>) -> Tz {
 		.and_then(|cookie_header_value| parse_cookies(cookie_header_value).ok())
 		.and_then(|cookies| cookies.get("modelfox_timezone").cloned())
 		.and_then(|timezone_str| timezone_str.parse().ok())
-		.unwrap_or(Tz::UTC)
+}
+
+/// Parse an IANA timezone name such as `America/New_York`. An empty string is parsed as `None`, which clears a timezone setting.
+pub fn parse_timezone(timezone: &str) -> Result<Option<Tz>, String> {
+	let timezone = timezone.trim();
+	if timezone.is_empty() {
+		return Ok(None);
+	}
+	timezone.parse().map(Some)
+}
+
+/// Get the timezone to display dates in for a page about a model. The user's own timezone takes precedence, then the timezone of the organization or user that owns the model's repo, then the timezone the browser reported.
+pub async fn get_timezone_for_model(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	request: &http::Request<hyper::Body>,
+	user: &User,
+	model_id: Id,
+) -> Result<Tz> {
+	let owner_timezone = get_model_owner_timezone(txn, model_id).await?;
+	get_timezone_with_owner_timezone(txn, request, user, owner_timezone).await
+}
+
+/// Get the timezone to display dates in for a page about a repo. See [`get_timezone_for_model`].
+pub async fn get_timezone_for_repo(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	request: &http::Request<hyper::Body>,
+	user: &User,
+	repo_id: Id,
+) -> Result<Tz> {
+	let owner_timezone = get_repo_owner_timezone(txn, repo_id).await?;
+	get_timezone_with_owner_timezone(txn, request, user, owner_timezone).await
+}
+
+async fn get_timezone_with_owner_timezone(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	request: &http::Request<hyper::Body>,
+	user: &User,
+	owner_timezone: Option<Tz>,
+) -> Result<Tz> {
+	let user_timezone = match user {
+		User::Root => None,
+		User::Normal(user) => get_user_timezone(txn, user.id).await?,
+	};
+	Ok(user_timezone
+		.or(owner_timezone)
+		.or_else(|| get_cookie_timezone(request))
+		.unwrap_or(Tz::UTC))
+}
+
+pub async fn get_user_timezone(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user_id: Id,
+) -> Result<Option<Tz>> {
+	let row = sqlx::query(
+		"
+			select
+				users.timezone
+			from users
+			where users.id = $1
+		",
+	)
+	.bind(&user_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	Ok(row.and_then(|row| timezone_from_column(row.get(0))))
+}
+
+pub async fn set_user_timezone(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user_id: Id,
+	timezone: Option<Tz>,
+) -> Result<()> {
+	sqlx::query(
+		"
+			update users
+				set timezone = $1
+			where users.id = $2
+		",
+	)
+	.bind(timezone.map(|timezone| timezone.name().to_owned()))
+	.bind(&user_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn get_organization_timezone(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+) -> Result<Option<Tz>> {
+	let row = sqlx::query(
+		"
+			select
+				organizations.timezone
+			from organizations
+			where organizations.id = $1
+		",
+	)
+	.bind(&organization_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	Ok(row.and_then(|row| timezone_from_column(row.get(0))))
+}
+
+pub async fn set_organization_timezone(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+	timezone: Option<Tz>,
+) -> Result<()> {
+	sqlx::query(
+		"
+			update organizations
+				set timezone = $1
+			where organizations.id = $2
+		",
+	)
+	.bind(timezone.map(|timezone| timezone.name().to_owned()))
+	.bind(&organization_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Get the timezone of the organization or user that owns the repo.
+pub async fn get_repo_owner_timezone(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+) -> Result<Option<Tz>> {
+	let row = sqlx::query(
+		"
+			select
+				coalesce(organizations.timezone, users.timezone)
+			from repos
+			left join organizations
+				on organizations.id = repos.organization_id
+			left join users
+				on users.id = repos.user_id
+			where repos.id = $1
+		",
+	)
+	.bind(&repo_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	Ok(row.and_then(|row| timezone_from_column(row.get(0))))
+}
+
+/// Get the timezone of the organization or user that owns the model's repo. Alerts and monitor cadences use this timezone because they are not sent on behalf of any one user.
+pub async fn get_model_owner_timezone(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<Option<Tz>> {
+	let row = sqlx::query(
+		"
+			select
+				coalesce(organizations.timezone, users.timezone)
+			from models
+			join repos
+				on repos.id = models.repo_id
+			left join organizations
+				on organizations.id = repos.organization_id
+			left join users
+				on users.id = repos.user_id
+			where models.id = $1
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	Ok(row.and_then(|row| timezone_from_column(row.get(0))))
+}
+
+fn timezone_from_column(timezone: Option<String>) -> Option<Tz> {
+	timezone.and_then(|timezone| timezone.parse().ok())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_parse_timezone() {
+		assert_eq!(parse_timezone(""), Ok(None));
+		assert_eq!(parse_timezone("  "), Ok(None));
+		assert_eq!(
+			parse_timezone("America/New_York"),
+			Ok(Some(Tz::America__New_York))
+		);
+		assert!(parse_timezone("Mars/Olympus_Mons").is_err());
+	}
 }
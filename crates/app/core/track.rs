@@ -1,4 +1,8 @@
-use crate::{model::get_model_bytes, storage::Storage};
+use crate::{
+	model::get_model_bytes,
+	privacy::{get_model_privacy_settings, identifier_candidates, PrivacySettings},
+	storage::Storage,
+};
 use anyhow::{anyhow, bail, Result};
 use chrono::prelude::*;
 use memmap::Mmap;
@@ -8,11 +12,17 @@ use modelfox_app_monitor_event::{
 	TrueValueMonitorEvent,
 };
 use modelfox_app_production_metrics::ProductionMetrics;
-use modelfox_app_production_stats::ProductionStats;
+use modelfox_app_production_stats::{
+	rollup::{invalidate_daily_row, RollupTable},
+	ProductionStats,
+};
 use modelfox_id::Id;
 use num::ToPrimitive;
 use sqlx::prelude::*;
-use std::{borrow::BorrowMut, collections::BTreeMap};
+use std::{
+	borrow::BorrowMut,
+	collections::{BTreeMap, BTreeSet, HashMap},
+};
 use tracing::error;
 
 use super::App;
@@ -66,21 +76,45 @@ pub async fn handle_prediction_monitor_event(
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
 	data_storage: &Storage,
 	model_cache: &mut BTreeMap<Id, Mmap>,
-	monitor_event: PredictionMonitorEvent,
+	mut monitor_event: PredictionMonitorEvent,
 ) -> Result<()> {
+	validate_sample_rate(monitor_event.sample_rate)?;
+	monitor_event.flatten_input();
 	let model_id = monitor_event.model_id;
-	let bytes = match model_cache.get(&model_id) {
-		Some(bytes) => bytes,
-		None => {
-			let model = get_model_bytes(data_storage, model_id).await?;
-			model_cache.insert(model_id, model);
-			model_cache.get(&model_id).unwrap()
-		}
-	};
+	let privacy_settings = get_model_privacy_settings(txn, model_id).await?;
+	privacy_settings.redact_input(&mut monitor_event.input);
+	let bytes = get_cached_model_bytes(data_storage, model_cache, model_id).await?;
 	let model = modelfox_model::from_bytes(bytes)?;
-	write_prediction_monitor_event(txn, model_id, &monitor_event).await?;
-	insert_or_update_production_stats_for_monitor_event(txn, model_id, model, monitor_event)
-		.await?;
+	write_prediction_monitor_event(txn, model_id, &monitor_event, &privacy_settings).await?;
+	insert_or_update_production_stats_for_monitor_event(
+		txn,
+		model_id,
+		model,
+		monitor_event,
+		&privacy_settings,
+	)
+	.await?;
+	Ok(())
+}
+
+async fn get_cached_model_bytes<'a>(
+	data_storage: &Storage,
+	model_cache: &'a mut BTreeMap<Id, Mmap>,
+	model_id: Id,
+) -> Result<&'a Mmap> {
+	if !model_cache.contains_key(&model_id) {
+		let model = get_model_bytes(data_storage, model_id).await?;
+		model_cache.insert(model_id, model);
+	}
+	Ok(model_cache.get(&model_id).unwrap())
+}
+
+fn validate_sample_rate(sample_rate: Option<f32>) -> Result<()> {
+	if let Some(sample_rate) = sample_rate {
+		if !modelfox_core::sampling::is_valid_sample_rate(sample_rate) {
+			bail!("The sample rate must be greater than 0 and at most 1.");
+		}
+	}
 	Ok(())
 }
 
@@ -88,68 +122,99 @@ pub async fn write_prediction_monitor_event(
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
 	model_id: Id,
 	monitor_event: &PredictionMonitorEvent,
+	privacy_settings: &PrivacySettings,
 ) -> Result<()> {
-	let identifier = monitor_event.identifier.as_string();
-	let row = sqlx::query(
-		"
-			select count(*) from predictions
-			where
-				model_id = $1
-				and identifier = $2
-		",
-	)
-	.bind(&model_id.to_string())
-	.bind(&identifier.to_string())
-	.fetch_one(txn.borrow_mut())
-	.await?;
-	let prediction_count: i64 = row.get(0);
-	if prediction_count > 0 {
+	if find_prediction(txn, model_id, &monitor_event.identifier)
+		.await?
+		.is_some()
+	{
 		bail!("A prediction has already been logged with this identifier.");
 	}
+	let identifier = privacy_settings.stored_identifier(&monitor_event.identifier);
 	let prediction_monitor_event_id = Id::generate();
 	let date = &monitor_event.date;
-	let input = serde_json::to_string(&monitor_event.input)?;
+	let input = stored_input(monitor_event, privacy_settings)?;
 	let output = serde_json::to_string(&monitor_event.output)?;
 	let options = serde_json::to_string(&monitor_event.options)?;
 	sqlx::query(
 		"
 			insert into predictions
-				(id, model_id, date, identifier, input, options, output)
+				(id, model_id, date, identifier, identifier_hashed, input, options, output, sample_rate)
 			values
-				($1, $2, $3, $4, $5, $6, $7)
+				($1, $2, $3, $4, $5, $6, $7, $8, $9)
 		",
 	)
 	.bind(&prediction_monitor_event_id.to_string())
 	.bind(&model_id.to_string())
 	.bind(&date.timestamp())
-	.bind(&identifier.to_string())
+	.bind(&identifier)
+	.bind(privacy_settings.hashes_identifiers())
 	.bind(&input)
 	.bind(&options)
 	.bind(&output)
+	.bind(&monitor_event.sample_rate)
 	.execute(txn.borrow_mut())
 	.await?;
 	Ok(())
 }
 
+/// Find the prediction with a monitor event's identifier, returning its stored identifier and its date. The identifier is matched in the form it was stored in, which is hashed if the model did not store inputs when the prediction was tracked.
+async fn find_prediction(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	identifier: &NumberOrString,
+) -> Result<Option<(String, i64)>> {
+	let (identifier, hashed_identifier) = identifier_candidates(identifier);
+	let row = sqlx::query(
+		"
+			select
+				identifier,
+				date
+			from predictions
+			where
+				model_id = $1
+				and (
+					(identifier = $2 and not identifier_hashed)
+					or (identifier = $3 and identifier_hashed)
+				)
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&identifier)
+	.bind(&hashed_identifier)
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	Ok(row.map(|row| (row.get(0), row.get(1))))
+}
+
 pub async fn handle_true_value_monitor_event(
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
 	data_storage: &Storage,
 	model_cache: &mut BTreeMap<Id, Mmap>,
 	monitor_event: TrueValueMonitorEvent,
 ) -> Result<()> {
+	validate_sample_rate(monitor_event.sample_rate)?;
 	let model_id = monitor_event.model_id;
-	let bytes = match model_cache.get(&model_id) {
-		Some(model) => model,
-		None => {
-			let model = get_model_bytes(data_storage, monitor_event.model_id).await?;
-			model_cache.insert(model_id, model);
-			model_cache.get(&model_id).unwrap()
-		}
-	};
+	// True values are stored with the identifier of their prediction, in the form it was stored in.
+	let (identifier, _) = find_prediction(txn, model_id, &monitor_event.identifier)
+		.await?
+		.ok_or_else(|| {
+			anyhow!(
+				"Failed to find prediction with identifier {}",
+				monitor_event.identifier.as_string()
+			)
+		})?;
+	let bytes = get_cached_model_bytes(data_storage, model_cache, model_id).await?;
 	let model = modelfox_model::from_bytes(bytes)?;
-	write_true_value_monitor_event(txn, model_id, &monitor_event).await?;
-	insert_or_update_production_metrics_for_monitor_event(txn, model_id, model, monitor_event)
-		.await?;
+	write_true_value_monitor_event(txn, model_id, &monitor_event, &identifier).await?;
+	insert_or_update_production_metrics_for_monitor_event(
+		txn,
+		model_id,
+		model,
+		monitor_event,
+		&identifier,
+	)
+	.await?;
 	Ok(())
 }
 
@@ -157,8 +222,8 @@ pub async fn write_true_value_monitor_event(
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
 	model_id: Id,
 	monitor_event: &TrueValueMonitorEvent,
+	identifier: &str,
 ) -> Result<()> {
-	let identifier = monitor_event.identifier.as_string();
 	let row = sqlx::query(
 		"
 			select count(*) from true_values
@@ -189,7 +254,7 @@ pub async fn write_true_value_monitor_event(
 	.bind(&true_value_monitor_event_id.to_string())
 	.bind(&model_id.to_string())
 	.bind(&date.timestamp())
-	.bind(&identifier.to_string())
+	.bind(identifier)
 	.bind(&true_value.to_string())
 	.execute(txn.borrow_mut())
 	.await?;
@@ -201,6 +266,7 @@ pub async fn insert_or_update_production_stats_for_monitor_event(
 	model_id: Id,
 	model: modelfox_model::ModelReader<'_>,
 	monitor_event: PredictionMonitorEvent,
+	privacy_settings: &PrivacySettings,
 ) -> Result<()> {
 	let date = monitor_event.date;
 	let hour = Utc
@@ -223,7 +289,7 @@ pub async fn insert_or_update_production_stats_for_monitor_event(
 	if let Some(row) = rows.get(0) {
 		let data: String = row.get(0);
 		let mut production_stats: ProductionStats = serde_json::from_str(&data)?;
-		production_stats.update(model, monitor_event);
+		production_stats.update(model, monitor_event, &privacy_settings.pii_columns);
 		let data = serde_json::to_string(&production_stats)?;
 		sqlx::query(
 			"
@@ -245,7 +311,7 @@ pub async fn insert_or_update_production_stats_for_monitor_event(
 		let start_date = hour;
 		let end_date = hour + chrono::Duration::hours(1);
 		let mut production_stats = ProductionStats::new(model, start_date, end_date);
-		production_stats.update(model, monitor_event);
+		production_stats.update(model, monitor_event, &privacy_settings.pii_columns);
 		let data = serde_json::to_string(&production_stats)?;
 		sqlx::query(
 			"
@@ -261,6 +327,14 @@ pub async fn insert_or_update_production_stats_for_monitor_event(
 		.execute(txn.borrow_mut())
 		.await?;
 	}
+	// Events tracked late for a day that was already rolled up invalidate its rollup, so it is rolled up again.
+	invalidate_daily_row(
+		txn,
+		RollupTable::ProductionStats,
+		&model_id.to_string(),
+		hour.timestamp(),
+	)
+	.await?;
 	Ok(())
 }
 
@@ -269,8 +343,8 @@ pub async fn insert_or_update_production_metrics_for_monitor_event(
 	model_id: Id,
 	model: modelfox_model::ModelReader<'_>,
 	monitor_event: TrueValueMonitorEvent,
+	identifier: &str,
 ) -> Result<()> {
-	let identifier = monitor_event.identifier.as_string().to_string();
 	let rows = sqlx::query(
 		"
 			select
@@ -290,13 +364,7 @@ pub async fn insert_or_update_production_metrics_for_monitor_event(
 	if rows.is_empty() {
 		bail!("Failed to find prediction with identifier {}", identifier);
 	}
-	let true_value = match &monitor_event.true_value {
-		serde_json::Value::Number(value) => {
-			NumberOrString::Number(value.as_f64().unwrap().to_f32().unwrap())
-		}
-		serde_json::Value::String(value) => NumberOrString::String(value.clone()),
-		_ => unimplemented!(),
-	};
+	let true_value = true_value_for_metrics(&monitor_event.true_value);
 	let row = rows
 		.get(0)
 		.ok_or_else(|| anyhow!("Failed to find prediction with identifier {}", identifier))?;
@@ -311,19 +379,7 @@ pub async fn insert_or_update_production_metrics_for_monitor_event(
 		.with_nanosecond(0)
 		.unwrap();
 	let output: PredictOutput = serde_json::from_str(&output)?;
-	let prediction = match output {
-		PredictOutput::Regression(RegressionPredictOutput { value }) => {
-			NumberOrString::Number(value)
-		}
-		PredictOutput::BinaryClassification(BinaryClassificationPredictOutput {
-			class_name,
-			..
-		}) => NumberOrString::String(class_name),
-		PredictOutput::MulticlassClassification(MulticlassClassificationPredictOutput {
-			class_name,
-			..
-		}) => NumberOrString::String(class_name),
-	};
+	let prediction = prediction_for_metrics(output);
 	let row = sqlx::query(
 		"
 			select
@@ -379,5 +435,663 @@ pub async fn insert_or_update_production_metrics_for_monitor_event(
 		.execute(txn.borrow_mut())
 		.await?;
 	}
+	invalidate_daily_row(
+		txn,
+		RollupTable::ProductionMetrics,
+		&model_id.to_string(),
+		hour.timestamp(),
+	)
+	.await?;
+	Ok(())
+}
+
+fn true_value_for_metrics(true_value: &serde_json::Value) -> NumberOrString {
+	match true_value {
+		serde_json::Value::Number(value) => {
+			NumberOrString::Number(value.as_f64().unwrap().to_f32().unwrap())
+		}
+		serde_json::Value::String(value) => NumberOrString::String(value.clone()),
+		_ => unimplemented!(),
+	}
+}
+
+fn prediction_for_metrics(output: PredictOutput) -> NumberOrString {
+	match output {
+		PredictOutput::Regression(RegressionPredictOutput { value }) => {
+			NumberOrString::Number(value)
+		}
+		PredictOutput::BinaryClassification(BinaryClassificationPredictOutput {
+			class_name,
+			..
+		}) => NumberOrString::String(class_name),
+		PredictOutput::MulticlassClassification(MulticlassClassificationPredictOutput {
+			class_name,
+			..
+		}) => NumberOrString::String(class_name),
+	}
+}
+
+/// This is the number of events a bulk delete of a model's predictions removes, or would remove in a dry run.
+#[derive(Debug, serde::Serialize)]
+pub struct DeletePredictionsOutput {
+	pub prediction_count: u64,
+	pub true_value_count: u64,
+}
+
+/// Delete a model's predictions with dates in `[after, before)`, along with the true values logged for them, and recompute the production stats and metrics for every hour they were in. If `dry_run` is true, nothing is deleted and only the counts are returned.
+pub async fn delete_predictions(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	model: modelfox_model::ModelReader<'_>,
+	after: DateTime<Utc>,
+	before: DateTime<Utc>,
+	dry_run: bool,
+) -> Result<DeletePredictionsOutput> {
+	let rows = sqlx::query(
+		"
+			select
+				date
+			from predictions
+			where
+				model_id = $1
+				and date >= $2
+				and date < $3
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&after.timestamp())
+	.bind(&before.timestamp())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	let prediction_count = rows.len().to_u64().unwrap();
+	let hours = rows
+		.iter()
+		.map(|row| hour_for_timestamp(row.get(0)))
+		.collect::<BTreeSet<_>>();
+	let row = sqlx::query(
+		"
+			select
+				count(*)
+			from true_values
+			where
+				model_id = $1
+				and identifier in (
+					select
+						identifier
+					from predictions
+					where
+						model_id = $2
+						and date >= $3
+						and date < $4
+				)
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&model_id.to_string())
+	.bind(&after.timestamp())
+	.bind(&before.timestamp())
+	.fetch_one(txn.borrow_mut())
+	.await?;
+	let true_value_count: i64 = row.get(0);
+	let true_value_count = true_value_count.to_u64().unwrap();
+	if dry_run {
+		return Ok(DeletePredictionsOutput {
+			prediction_count,
+			true_value_count,
+		});
+	}
+	sqlx::query(
+		"
+			delete from true_values
+			where
+				model_id = $1
+				and identifier in (
+					select
+						identifier
+					from predictions
+					where
+						model_id = $2
+						and date >= $3
+						and date < $4
+				)
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&model_id.to_string())
+	.bind(&after.timestamp())
+	.bind(&before.timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	sqlx::query(
+		"
+			delete from predictions
+			where
+				model_id = $1
+				and date >= $2
+				and date < $3
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&after.timestamp())
+	.bind(&before.timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	for hour in hours {
+		recompute_production_stats_for_hour(txn, model_id, model, hour).await?;
+		recompute_production_metrics_for_hour(txn, model_id, model, hour).await?;
+	}
+	Ok(DeletePredictionsOutput {
+		prediction_count,
+		true_value_count,
+	})
+}
+
+/// Replay a prediction event. Events are keyed on their identifier and date. If a prediction with the same identifier was already logged at the same date, it is replaced and the production stats and metrics for its hour are recomputed, so replaying a corrected event does not count it twice. A prediction with the same identifier logged at a different date is an error.
+pub async fn replay_prediction_monitor_event(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	data_storage: &Storage,
+	model_cache: &mut BTreeMap<Id, Mmap>,
+	mut monitor_event: PredictionMonitorEvent,
+) -> Result<()> {
+	validate_sample_rate(monitor_event.sample_rate)?;
+	let model_id = monitor_event.model_id;
+	let privacy_settings = get_model_privacy_settings(txn, model_id).await?;
+	let (identifier, date) = match find_prediction(txn, model_id, &monitor_event.identifier).await?
+	{
+		Some(prediction) => prediction,
+		None => {
+			return handle_prediction_monitor_event(txn, data_storage, model_cache, monitor_event)
+				.await
+		}
+	};
+	if date != monitor_event.date.timestamp() {
+		bail!("A prediction has already been logged with this identifier at a different date.");
+	}
+	monitor_event.flatten_input();
+	privacy_settings.redact_input(&mut monitor_event.input);
+	let input = stored_input(&monitor_event, &privacy_settings)?;
+	let output = serde_json::to_string(&monitor_event.output)?;
+	let options = serde_json::to_string(&monitor_event.options)?;
+	sqlx::query(
+		"
+			update
+				predictions
+			set
+				input = $1,
+				options = $2,
+				output = $3,
+				sample_rate = $4
+			where
+				model_id = $5
+				and identifier = $6
+		",
+	)
+	.bind(&input)
+	.bind(&options)
+	.bind(&output)
+	.bind(&monitor_event.sample_rate)
+	.bind(&model_id.to_string())
+	.bind(&identifier)
+	.execute(txn.borrow_mut())
+	.await?;
+	let bytes = get_cached_model_bytes(data_storage, model_cache, model_id).await?;
+	let model = modelfox_model::from_bytes(bytes)?;
+	let hour = hour_for_timestamp(date);
+	recompute_production_stats_for_hour(txn, model_id, model, hour).await?;
+	recompute_production_metrics_for_hour(txn, model_id, model, hour).await?;
+	Ok(())
+}
+
+/// Replay a true value event. Like predictions, true values are keyed on their identifier and date, and replaying one that was already logged replaces it.
+pub async fn replay_true_value_monitor_event(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	data_storage: &Storage,
+	model_cache: &mut BTreeMap<Id, Mmap>,
+	monitor_event: TrueValueMonitorEvent,
+) -> Result<()> {
+	validate_sample_rate(monitor_event.sample_rate)?;
+	let model_id = monitor_event.model_id;
+	let (identifier, prediction_date) =
+		match find_prediction(txn, model_id, &monitor_event.identifier).await? {
+			Some(prediction) => prediction,
+			None => bail!(
+				"Failed to find prediction with identifier {}",
+				monitor_event.identifier.as_string()
+			),
+		};
+	let row = sqlx::query(
+		"
+			select
+				date
+			from true_values
+			where
+				model_id = $1
+				and identifier = $2
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&identifier)
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	let date: i64 = match row {
+		Some(row) => row.get(0),
+		None => {
+			return handle_true_value_monitor_event(txn, data_storage, model_cache, monitor_event)
+				.await
+		}
+	};
+	if date != monitor_event.date.timestamp() {
+		bail!("A true value has already been logged with this identifier at a different date.");
+	}
+	sqlx::query(
+		"
+			update
+				true_values
+			set
+				value = $1
+			where
+				model_id = $2
+				and identifier = $3
+		",
+	)
+	.bind(&monitor_event.true_value.to_string())
+	.bind(&model_id.to_string())
+	.bind(&identifier)
+	.execute(txn.borrow_mut())
+	.await?;
+	let bytes = get_cached_model_bytes(data_storage, model_cache, model_id).await?;
+	let model = modelfox_model::from_bytes(bytes)?;
+	recompute_production_metrics_for_hour(
+		txn,
+		model_id,
+		model,
+		hour_for_timestamp(prediction_date),
+	)
+	.await?;
+	Ok(())
+}
+
+/// Serialize the input to store for a prediction, which is empty if the model is configured to not store inputs.
+fn stored_input(
+	monitor_event: &PredictionMonitorEvent,
+	privacy_settings: &PrivacySettings,
+) -> Result<String> {
+	if privacy_settings.store_inputs {
+		Ok(serde_json::to_string(&monitor_event.input)?)
+	} else {
+		Ok("{}".to_owned())
+	}
+}
+
+fn hour_for_timestamp(timestamp: i64) -> DateTime<Utc> {
+	Utc.timestamp(timestamp - timestamp.rem_euclid(3600), 0)
+}
+
+/// Rebuild the production stats for an hour from the predictions that remain in it. This is used when predictions are deleted or replaced, because production stats can only be updated incrementally by adding events. If any of the remaining predictions were tracked without storing their inputs, their column stats cannot be rebuilt, so the hour's column stats are kept as they were and only its counts and prediction stats are rebuilt. The column stats then still include the inputs of the deleted or replaced predictions.
+async fn recompute_production_stats_for_hour(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	model: modelfox_model::ModelReader<'_>,
+	hour: DateTime<Utc>,
+) -> Result<()> {
+	let end_date = hour + chrono::Duration::hours(1);
+	let rows = sqlx::query(
+		"
+			select
+				date,
+				identifier,
+				input,
+				options,
+				output,
+				sample_rate
+			from predictions
+			where
+				model_id = $1
+				and date >= $2
+				and date < $3
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&hour.timestamp())
+	.bind(&end_date.timestamp())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	let privacy_settings = get_model_privacy_settings(txn, model_id).await?;
+	let row = sqlx::query(
+		"
+			select
+				data
+			from production_stats
+			where
+				model_id = $1
+				and hour = $2
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&hour.timestamp())
+	.fetch_optional(txn.borrow_mut())
+	.await?;
+	let existing_production_stats: Option<ProductionStats> = match row {
+		Some(row) => Some(serde_json::from_str(&row.get::<String, _>(0))?),
+		None => None,
+	};
+	sqlx::query(
+		"
+			delete from production_stats
+			where
+				model_id = $1
+				and hour = $2
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&hour.timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	if !rows.is_empty() {
+		let mut production_stats = ProductionStats::new(model, hour, end_date);
+		let mut has_unstored_inputs = false;
+		for row in rows {
+			let date: i64 = row.get(0);
+			let identifier: String = row.get(1);
+			let input: String = row.get(2);
+			let options: String = row.get(3);
+			let output: String = row.get(4);
+			let sample_rate: Option<f32> = row.get(5);
+			// Inputs that were not stored are saved as an empty object.
+			let input: HashMap<String, serde_json::Value> = serde_json::from_str(&input)?;
+			if input.is_empty() {
+				has_unstored_inputs = true;
+			}
+			let monitor_event = PredictionMonitorEvent {
+				model_id,
+				date: Utc.timestamp(date, 0),
+				identifier: NumberOrString::String(identifier),
+				options: serde_json::from_str(&options)?,
+				input,
+				output: serde_json::from_str(&output)?,
+				sample_rate,
+			};
+			production_stats.update(model, monitor_event, &privacy_settings.pii_columns);
+		}
+		if has_unstored_inputs {
+			if let Some(existing_production_stats) = existing_production_stats {
+				production_stats.column_stats = existing_production_stats.column_stats;
+			}
+		}
+		let data = serde_json::to_string(&production_stats)?;
+		sqlx::query(
+			"
+				insert into production_stats
+					(model_id, data, hour)
+				values
+					($1, $2, $3)
+			",
+		)
+		.bind(&model_id.to_string())
+		.bind(&data)
+		.bind(&hour.timestamp())
+		.execute(txn.borrow_mut())
+		.await?;
+	}
+	invalidate_daily_row(
+		txn,
+		RollupTable::ProductionStats,
+		&model_id.to_string(),
+		hour.timestamp(),
+	)
+	.await?;
+	Ok(())
+}
+
+/// Rebuild the production metrics for an hour from the predictions in it that have true values.
+async fn recompute_production_metrics_for_hour(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+	model: modelfox_model::ModelReader<'_>,
+	hour: DateTime<Utc>,
+) -> Result<()> {
+	let end_date = hour + chrono::Duration::hours(1);
+	let rows = sqlx::query(
+		"
+			select
+				predictions.output,
+				true_values.value
+			from predictions
+			join true_values
+			on
+				true_values.model_id = predictions.model_id
+				and true_values.identifier = predictions.identifier
+			where
+				predictions.model_id = $1
+				and predictions.date >= $2
+				and predictions.date < $3
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&hour.timestamp())
+	.bind(&end_date.timestamp())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	sqlx::query(
+		"
+			delete from production_metrics
+			where
+				model_id = $1
+				and hour = $2
+		",
+	)
+	.bind(&model_id.to_string())
+	.bind(&hour.timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	if !rows.is_empty() {
+		let mut production_metrics = ProductionMetrics::new(model, hour, end_date);
+		for row in rows {
+			let output: String = row.get(0);
+			let true_value: String = row.get(1);
+			let output: PredictOutput = serde_json::from_str(&output)?;
+			let true_value: serde_json::Value = serde_json::from_str(&true_value)?;
+			production_metrics.update((
+				prediction_for_metrics(output),
+				true_value_for_metrics(&true_value),
+			));
+		}
+		let data = serde_json::to_string(&production_metrics)?;
+		sqlx::query(
+			"
+				insert into production_metrics
+					(model_id, data, hour)
+				values
+					($1, $2, $3)
+			",
+		)
+		.bind(&model_id.to_string())
+		.bind(&data)
+		.bind(&hour.timestamp())
+		.execute(txn.borrow_mut())
+		.await?;
+	}
+	invalidate_daily_row(
+		txn,
+		RollupTable::ProductionMetrics,
+		&model_id.to_string(),
+		hour.timestamp(),
+	)
+	.await?;
 	Ok(())
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		privacy::set_model_privacy_settings,
+		test_common::{
+			init_heart_disease_model, init_test_app, seed_single_prediction_event,
+			seed_single_true_value_event,
+		},
+	};
+	use std::time::Duration;
+
+	async fn get_production_stats_for_hour(
+		txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+		model_id: Id,
+		hour: DateTime<Utc>,
+	) -> ProductionStats {
+		let row = sqlx::query(
+			"
+				select
+					data
+				from production_stats
+				where
+					model_id = $1
+					and hour = $2
+			",
+		)
+		.bind(&model_id.to_string())
+		.bind(&hour.timestamp())
+		.fetch_one(txn.borrow_mut())
+		.await
+		.unwrap();
+		serde_json::from_str(&row.get::<String, _>(0)).unwrap()
+	}
+
+	fn column_row_count(production_stats: &ProductionStats, column_name: &str) -> u64 {
+		match production_stats
+			.column_stats
+			.iter()
+			.find(|column_stats| column_stats.column_name() == column_name)
+			.unwrap()
+		{
+			modelfox_app_production_stats::ProductionColumnStats::Number(stats) => stats.row_count,
+			modelfox_app_production_stats::ProductionColumnStats::Enum(stats) => stats.row_count,
+			_ => unreachable!(),
+		}
+	}
+
+	async fn set_privacy_settings(app: &App, model_id: Id, privacy_settings: PrivacySettings) {
+		let mut txn = app.begin_transaction().await.unwrap();
+		set_model_privacy_settings(&mut txn, model_id, &privacy_settings)
+			.await
+			.unwrap();
+		app.commit_transaction(txn).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_delete_prediction_keeps_column_stats_when_inputs_are_not_stored() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		set_privacy_settings(
+			&app,
+			model_id,
+			PrivacySettings {
+				store_inputs: false,
+				pii_columns: Vec::new(),
+			},
+		)
+		.await;
+		// Track two predictions ten minutes apart in the same hour.
+		seed_single_prediction_event(&app, model_id).await.unwrap();
+		app.clock().advance(Duration::from_secs(600)).await;
+		seed_single_prediction_event(&app, model_id).await.unwrap();
+		let hour = Utc.timestamp(0, 0);
+		let mut txn = app.begin_transaction().await.unwrap();
+		let production_stats = get_production_stats_for_hour(&mut txn, model_id, hour).await;
+		assert_eq!(production_stats.row_count, 2);
+		assert_eq!(column_row_count(&production_stats, "age"), 2);
+		// Delete the second prediction.
+		let bytes = get_model_bytes(app.storage(), model_id).await.unwrap();
+		let model = modelfox_model::from_bytes(&bytes).unwrap();
+		let output = delete_predictions(
+			&mut txn,
+			model_id,
+			model,
+			Utc.timestamp(600, 0),
+			Utc.timestamp(3600, 0),
+			false,
+		)
+		.await
+		.unwrap();
+		assert_eq!(output.prediction_count, 1);
+		let production_stats = get_production_stats_for_hour(&mut txn, model_id, hour).await;
+		assert_eq!(production_stats.row_count, 1);
+		// The column stats cannot be rebuilt without the inputs, so they are kept.
+		assert_eq!(column_row_count(&production_stats, "age"), 2);
+		assert_eq!(column_row_count(&production_stats, "chest_pain"), 2);
+		app.commit_transaction(txn).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_true_value_matches_prediction_after_privacy_settings_change() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		set_privacy_settings(
+			&app,
+			model_id,
+			PrivacySettings {
+				store_inputs: false,
+				pii_columns: Vec::new(),
+			},
+		)
+		.await;
+		let (id, output) = seed_single_prediction_event(&app, model_id).await.unwrap();
+		// The prediction's identifier was hashed. Storing inputs again does not change that.
+		set_privacy_settings(&app, model_id, PrivacySettings::default()).await;
+		seed_single_true_value_event(&app, model_id, id, output, true)
+			.await
+			.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let row = sqlx::query(
+			"
+				select
+					count(*)
+				from true_values
+				join predictions
+				on
+					true_values.model_id = predictions.model_id
+					and true_values.identifier = predictions.identifier
+				where
+					predictions.model_id = $1
+			",
+		)
+		.bind(&model_id.to_string())
+		.fetch_one(txn.borrow_mut())
+		.await
+		.unwrap();
+		let count: i64 = row.get(0);
+		assert_eq!(count, 1);
+		app.commit_transaction(txn).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_pii_columns_are_skipped_in_production_stats() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		set_privacy_settings(
+			&app,
+			model_id,
+			PrivacySettings {
+				store_inputs: true,
+				pii_columns: vec!["age".to_owned()],
+			},
+		)
+		.await;
+		seed_single_prediction_event(&app, model_id).await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let production_stats =
+			get_production_stats_for_hour(&mut txn, model_id, Utc.timestamp(0, 0)).await;
+		assert_eq!(production_stats.row_count, 1);
+		assert_eq!(column_row_count(&production_stats, "age"), 0);
+		assert_eq!(column_row_count(&production_stats, "chest_pain"), 1);
+		app.commit_transaction(txn).await.unwrap();
+	}
+
+	#[test]
+	fn test_hour_for_timestamp() {
+		// 2022-06-08T13:45:10Z
+		assert_eq!(
+			hour_for_timestamp(1654695910),
+			Utc.ymd(2022, 6, 8).and_hms(13, 0, 0)
+		);
+	}
+}
@@ -0,0 +1,127 @@
+use crate::{
+	cluster::{BackgroundTask, Leadership},
+	heuristics::{
+		MODEL_TRASH_RETENTION_DURATION, TRASH_PURGER_HEARTBEAT_DURATION_PRODUCTION,
+		TRASH_PURGER_HEARTBEAT_DURATION_TESTING,
+	},
+	repos::delete_model_version,
+	AppState,
+};
+use anyhow::Result;
+use futures::{select, FutureExt};
+use modelfox_id::Id;
+use sqlx::prelude::*;
+use std::{borrow::BorrowMut, sync::Arc};
+use tokio::sync::{mpsc, oneshot};
+
+#[derive(Debug)]
+pub enum TrashPurgerMessage {
+	Run(oneshot::Sender<()>),
+}
+
+/// Periodically delete the model versions that have been in the trash for longer than [`MODEL_TRASH_RETENTION_DURATION`], along with their bytes in storage and their monitoring history.
+#[tracing::instrument(level = "info", skip_all)]
+pub async fn trash_purger(
+	app_state: Arc<AppState>,
+	mut receiver: mpsc::UnboundedReceiver<TrashPurgerMessage>,
+) -> Result<()> {
+	let period = if cfg!(debug_assertions) {
+		TRASH_PURGER_HEARTBEAT_DURATION_TESTING
+	} else {
+		TRASH_PURGER_HEARTBEAT_DURATION_PRODUCTION
+	};
+	let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+	interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+	loop {
+		enum Event {
+			Tick,
+			Message(TrashPurgerMessage),
+		}
+		let event = select! {
+			_ = interval.tick().fuse() => Event::Tick,
+			message = receiver.recv().fuse() => match message {
+				None => break,
+				Some(message) => Event::Message(message),
+			}
+		};
+		if let Some(leadership) =
+			Leadership::acquire(&app_state, BackgroundTask::TrashPurger).await?
+		{
+			tracing::info!("Begin trash_purger heartbeat");
+			purge_expired_model_versions(&app_state).await?;
+			leadership.release().await?;
+			tracing::info!("End trash_purger heartbeat");
+		}
+		if let Event::Message(TrashPurgerMessage::Run(sender)) = event {
+			sender.send(()).unwrap();
+		}
+	}
+	Ok(())
+}
+
+/// Delete each expired model version in its own transaction, so emptying a large trash does not hold one long transaction open.
+pub async fn purge_expired_model_versions(app_state: &AppState) -> Result<()> {
+	let retention: i64 = MODEL_TRASH_RETENTION_DURATION.as_secs().try_into().unwrap();
+	let expired_before = app_state.clock.now_utc().unix_timestamp() - retention;
+	let mut txn = app_state.begin_transaction().await?;
+	let model_ids: Vec<Id> = sqlx::query(
+		"
+			select
+				id
+			from models
+			where deleted_at <= $1
+		",
+	)
+	.bind(expired_before)
+	.fetch_all(txn.borrow_mut())
+	.await?
+	.iter()
+	.map(|row| row.get::<String, _>(0).parse().unwrap())
+	.collect();
+	app_state.commit_transaction(txn).await?;
+	for model_id in model_ids {
+		let mut txn = app_state.begin_transaction().await?;
+		delete_model_version(&mut txn, &app_state.storage, model_id).await?;
+		app_state.commit_transaction(txn).await?;
+		app_state.predict_model_cache.remove(model_id);
+		tracing::info!(%model_id, "deleted model version from the trash");
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{repos::trash_model_version, test_common::*};
+
+	async fn count_rows(app: &crate::App, query: &str) -> i64 {
+		let mut txn = app.begin_transaction().await.unwrap();
+		let count = sqlx::query(query)
+			.fetch_one(txn.borrow_mut())
+			.await
+			.unwrap()
+			.get(0);
+		app.commit_transaction(txn).await.unwrap();
+		count
+	}
+
+	#[tokio::test]
+	async fn test_purge_expired_model_versions() {
+		let app = init_test_app().await.unwrap();
+		let model_id = init_heart_disease_model(&app).await.unwrap();
+		seed_monitors(&app, model_id).await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		trash_model_version(&mut txn, &app, model_id).await.unwrap();
+		app.commit_transaction(txn).await.unwrap();
+		let trashed_at = app.clock().now_utc();
+		app.clock()
+			.set_mock_time(trashed_at + MODEL_TRASH_RETENTION_DURATION - time::Duration::DAY);
+		purge_expired_model_versions(&app.state).await.unwrap();
+		assert_eq!(count_rows(&app, "select count(*) from models").await, 1);
+		app.clock()
+			.set_mock_time(trashed_at + MODEL_TRASH_RETENTION_DURATION);
+		purge_expired_model_versions(&app.state).await.unwrap();
+		assert_eq!(count_rows(&app, "select count(*) from models").await, 0);
+		assert_eq!(count_rows(&app, "select count(*) from monitors").await, 0);
+	}
+}
@@ -1,6 +1,6 @@
 use std::borrow::BorrowMut;
 
-use crate::cookies::parse_cookies;
+use crate::{clock::Clock, cookies::parse_cookies, request_tracing, sessions::touch_session};
 use anyhow::Result;
 use modelfox_id::Id;
 use sqlx::prelude::*;
@@ -24,26 +24,32 @@ pub enum AuthorizeUserError {
 	CookieParseFailed,
 	CookieAuthAbsent,
 	TokenUnknown,
+	TokenRevoked,
 }
 
 pub async fn authorize_user(
 	request: &http::Request<hyper::Body>,
 	db: &mut sqlx::Transaction<'_, sqlx::Any>,
 	auth_enabled: bool,
+	clock: &Clock,
 ) -> Result<Result<User, AuthorizeUserError>> {
 	// When auth is disabled, everyone is authorized as the root user.
 	if !auth_enabled {
+		request_tracing::record_user_id("root");
 		Ok(Ok(User::Root))
 	} else {
-		authorize_normal_user(request, db)
-			.await
-			.map(|r| r.map(User::Normal))
+		let user = authorize_normal_user(request, db, clock).await?;
+		if let Ok(user) = &user {
+			request_tracing::record_user_id(&user.id.to_string());
+		}
+		Ok(user.map(User::Normal))
 	}
 }
 
 pub async fn authorize_normal_user(
 	request: &http::Request<hyper::Body>,
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	clock: &Clock,
 ) -> Result<Result<NormalUser, AuthorizeUserError>> {
 	let token = if let Some(authorization) = request.headers().get(http::header::AUTHORIZATION) {
 		let authorization = match authorization.to_str() {
@@ -74,7 +80,7 @@ pub async fn authorize_normal_user(
 	let row = sqlx::query(
 		"
 			select
-				users.id, users.email
+				users.id, users.email, tokens.revoked_at
 			from tokens
 			join users
 				on users.id = tokens.user_id
@@ -90,9 +96,14 @@ pub async fn authorize_normal_user(
 	} else {
 		return Ok(Err(AuthorizeUserError::TokenUnknown));
 	};
+	let revoked_at: Option<i64> = row.get(2);
+	if revoked_at.is_some() {
+		return Ok(Err(AuthorizeUserError::TokenRevoked));
+	}
 	let id: String = row.get(0);
 	let id: Id = id.parse().unwrap();
 	let email = row.get(1);
+	touch_session(txn, &token, clock.now_utc().unix_timestamp()).await?;
 	let user = NormalUser { id, email, token };
 	Ok(Ok(user))
 }
@@ -142,6 +153,7 @@ pub async fn authorize_user_for_repo(
 	}
 }
 
+/// Determine if the user may access the repo. The owner of a personal repo may access it. The members of an organization may access its repos, except that a repo granted to one or more teams may only be accessed by the members of those teams and the organization's admins. See [`crate::teams`].
 pub async fn authorize_normal_user_for_repo(
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
 	user: &NormalUser,
@@ -152,14 +164,31 @@ pub async fn authorize_normal_user_for_repo(
 			select
 				count(*) > 0
 			from repos
-			left join users
-				on users.id = repos.user_id
 			left join organizations_users
 				on organizations_users.organization_id = repos.organization_id
-			and
-				organizations_users.user_id = $1
+				and organizations_users.user_id = $1
 			where
-				repos.id = $2
+				repos.id = $2 and (
+					repos.user_id = $1 or (
+						organizations_users.user_id is not null and (
+							organizations_users.is_admin = true or
+							not exists (
+								select 1
+								from repos_teams
+								where repos_teams.repo_id = repos.id
+							) or
+							exists (
+								select 1
+								from repos_teams
+								join teams_users
+									on teams_users.team_id = repos_teams.team_id
+								where
+									repos_teams.repo_id = repos.id and
+									teams_users.user_id = $1
+							)
+						)
+					)
+				)
 		",
 	)
 	.bind(&user.id.to_string())
@@ -174,12 +203,36 @@ pub async fn authorize_user_for_model(
 	user: &User,
 	model_id: Id,
 ) -> Result<bool> {
+	request_tracing::record_model_id(model_id);
 	match user {
-		User::Root => Ok(true),
+		User::Root => model_is_in_trash(txn, model_id)
+			.await
+			.map(|in_trash| !in_trash),
 		User::Normal(user) => authorize_normal_user_for_model(txn, user, model_id).await,
 	}
 }
 
+/// Model versions in the trash are hidden from every user until they are restored from the repo's settings.
+async fn model_is_in_trash(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model_id: Id,
+) -> Result<bool> {
+	Ok(sqlx::query(
+		"
+			select
+				count(*) > 0
+			from models
+			where
+				models.id = $1 and
+				models.deleted_at is not null
+		",
+	)
+	.bind(&model_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?
+	.get(0))
+}
+
 pub async fn authorize_normal_user_for_model(
 	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
 	user: &NormalUser,
@@ -192,14 +245,131 @@ pub async fn authorize_normal_user_for_model(
 			from models
 			join repos
 				on repos.id = models.repo_id
-			left join users
-				on users.id = repos.user_id
-			left join organizations_users on
-				organizations_users.organization_id = repos.organization_id
-			and
-				organizations_users.user_id = $1
+			left join organizations_users
+				on organizations_users.organization_id = repos.organization_id
+				and organizations_users.user_id = $1
+			where
+				models.id = $2 and
+				models.deleted_at is null and (
+					repos.user_id = $1 or (
+						organizations_users.user_id is not null and (
+							organizations_users.is_admin = true or
+							not exists (
+								select 1
+								from repos_teams
+								where repos_teams.repo_id = repos.id
+							) or
+							exists (
+								select 1
+								from repos_teams
+								join teams_users
+									on teams_users.team_id = repos_teams.team_id
+								where
+									repos_teams.repo_id = repos.id and
+									teams_users.user_id = $1
+							)
+						)
+					)
+				)
+		",
+	)
+	.bind(&user.id.to_string())
+	.bind(&model_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?
+	.get(0))
+}
+
+/// Determine if the user may make destructive changes to the model's data, such as deleting its production predictions. Only the owner of a personal repo and the admins of an organization may.
+pub async fn authorize_user_to_manage_model(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user: &User,
+	model_id: Id,
+) -> Result<bool> {
+	match user {
+		User::Root => Ok(true),
+		User::Normal(user) => authorize_normal_user_to_manage_model(txn, user, model_id).await,
+	}
+}
+
+pub async fn authorize_normal_user_to_manage_model(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user: &NormalUser,
+	model_id: Id,
+) -> Result<bool> {
+	Ok(sqlx::query(
+		"
+			select
+				count(*) > 0
+			from models
+			join repos
+				on repos.id = models.repo_id
+			left join organizations_users
+				on organizations_users.organization_id = repos.organization_id
+				and organizations_users.user_id = $1
+			where
+				models.id = $2 and (
+					repos.user_id = $1 or
+					organizations_users.is_admin = true
+				)
+		",
+	)
+	.bind(&user.id.to_string())
+	.bind(&model_id.to_string())
+	.fetch_one(txn.borrow_mut())
+	.await?
+	.get(0))
+}
+
+/// Determine if the user may see the inputs of the model's production predictions. Users without data access can still see the model's metrics. The owner of a personal repo and the admins of an organization always have data access, and other members of an organization need access to the repo, which may be limited to teams. See [`crate::teams`].
+pub async fn authorize_user_for_data_access(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user: &User,
+	model_id: Id,
+) -> Result<bool> {
+	match user {
+		User::Root => Ok(true),
+		User::Normal(user) => authorize_normal_user_for_data_access(txn, user, model_id).await,
+	}
+}
+
+pub async fn authorize_normal_user_for_data_access(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	user: &NormalUser,
+	model_id: Id,
+) -> Result<bool> {
+	Ok(sqlx::query(
+		"
+			select
+				count(*) > 0
+			from models
+			join repos
+				on repos.id = models.repo_id
+			left join organizations_users
+				on organizations_users.organization_id = repos.organization_id
+				and organizations_users.user_id = $1
 			where
-				models.id = $2
+				models.id = $2 and (
+					repos.user_id = $1 or
+					organizations_users.is_admin = true or (
+						organizations_users.has_data_access = true and (
+							not exists (
+								select 1
+								from repos_teams
+								where repos_teams.repo_id = repos.id
+							) or
+							exists (
+								select 1
+								from repos_teams
+								join teams_users
+									on teams_users.team_id = repos_teams.team_id
+								where
+									repos_teams.repo_id = repos.id and
+									teams_users.user_id = $1
+							)
+						)
+					)
+				)
 		",
 	)
 	.bind(&user.id.to_string())
@@ -0,0 +1,449 @@
+/*!
+This module contains repo webhooks, which notify external systems such as deploy pipelines and chat bots when something changes in a repo. Each webhook subscribes to a set of [`WebhookEventType`]s. When an event occurs, a delivery is written for each subscribed webhook in the same transaction as the change, so an event is delivered only if the change is committed. The alert sender delivers pending webhooks on each heartbeat, retrying failed deliveries on later heartbeats.
+
+Each delivery is a POST request whose body is a JSON [`WebhookPayload`]. The request's `X-ModelFox-Signature` header contains `sha256=` followed by the hex encoded HMAC-SHA256 of the body, keyed with the webhook's secret, so receivers can verify that the request came from the app.
+*/
+
+use crate::{clock::Clock, heuristics::WEBHOOK_MAXIMUM_ATTEMPTS, AppState};
+use anyhow::{bail, Result};
+use modelfox_id::Id;
+use sha2::{Digest, Sha256};
+use sqlx::prelude::*;
+use std::{borrow::BorrowMut, fmt};
+use url::Url;
+
+/// This is the catalog of events a webhook can subscribe to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum WebhookEventType {
+	#[serde(rename = "model_uploaded")]
+	ModelUploaded,
+	#[serde(rename = "model_deleted")]
+	ModelDeleted,
+	#[serde(rename = "monitor_state_changed")]
+	MonitorStateChanged,
+}
+
+impl WebhookEventType {
+	pub fn all() -> &'static [WebhookEventType] {
+		&[
+			WebhookEventType::ModelUploaded,
+			WebhookEventType::ModelDeleted,
+			WebhookEventType::MonitorStateChanged,
+		]
+	}
+
+	/// Retrieve the name of the event type, which is sent in the `X-ModelFox-Event` header and the `type` field of the payload.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			WebhookEventType::ModelUploaded => "model_uploaded",
+			WebhookEventType::ModelDeleted => "model_deleted",
+			WebhookEventType::MonitorStateChanged => "monitor_state_changed",
+		}
+	}
+
+	pub fn description(&self) -> &'static str {
+		match self {
+			WebhookEventType::ModelUploaded => "A model version was uploaded to the repo.",
+			WebhookEventType::ModelDeleted => "A model version was deleted from the repo.",
+			WebhookEventType::MonitorStateChanged => {
+				"A monitor's metric moved outside of its thresholds, or back within them."
+			}
+		}
+	}
+}
+
+impl fmt::Display for WebhookEventType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+/// A monitor is alerting while its most recent evaluation exceeds its thresholds, whether or not an alert was sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+pub enum MonitorState {
+	#[serde(rename = "ok")]
+	Ok,
+	#[serde(rename = "alerting")]
+	Alerting,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum WebhookEvent {
+	#[serde(rename = "model_uploaded")]
+	ModelUploaded { model_id: Id },
+	#[serde(rename = "model_deleted")]
+	ModelDeleted { model_id: Id },
+	#[serde(rename = "monitor_state_changed")]
+	MonitorStateChanged {
+		monitor_id: Id,
+		model_id: Id,
+		state: MonitorState,
+	},
+}
+
+impl WebhookEvent {
+	pub fn event_type(&self) -> WebhookEventType {
+		match self {
+			WebhookEvent::ModelUploaded { .. } => WebhookEventType::ModelUploaded,
+			WebhookEvent::ModelDeleted { .. } => WebhookEventType::ModelDeleted,
+			WebhookEvent::MonitorStateChanged { .. } => WebhookEventType::MonitorStateChanged,
+		}
+	}
+}
+
+/// This is the body of every webhook request. The `type` and `data` fields come from the [`WebhookEvent`].
+#[derive(Debug, serde::Serialize)]
+pub struct WebhookPayload<'a> {
+	/// This uniquely identifies the event. It is the same for every webhook the event is delivered to and for every retry, so receivers can ignore duplicates.
+	pub id: Id,
+	/// This is the unix timestamp at which the event occurred.
+	pub date: i64,
+	pub repo_id: Id,
+	#[serde(flatten)]
+	pub event: &'a WebhookEvent,
+}
+
+pub struct Webhook {
+	pub id: Id,
+	pub url: Url,
+	pub secret: String,
+	pub event_types: Vec<WebhookEventType>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebhookDeliveryStatus {
+	Pending,
+	Succeeded,
+	Failed,
+}
+
+impl From<WebhookDeliveryStatus> for i64 {
+	fn from(status: WebhookDeliveryStatus) -> i64 {
+		match status {
+			WebhookDeliveryStatus::Pending => 0,
+			WebhookDeliveryStatus::Succeeded => 1,
+			WebhookDeliveryStatus::Failed => 2,
+		}
+	}
+}
+
+pub async fn create_webhook(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+	url: &Url,
+	secret: &str,
+	event_types: &[WebhookEventType],
+	clock: &Clock,
+) -> Result<Id> {
+	if !matches!(url.scheme(), "http" | "https") {
+		bail!("A webhook url must start with http: or https:.");
+	}
+	if secret.is_empty() {
+		bail!("A webhook must have a secret.");
+	}
+	if event_types.is_empty() {
+		bail!("A webhook must subscribe to at least one event.");
+	}
+	let id = Id::generate();
+	sqlx::query(
+		"
+			insert into webhooks (
+				id, repo_id, url, secret, event_types, created_at
+			) values (
+				$1, $2, $3, $4, $5, $6
+			)
+		",
+	)
+	.bind(&id.to_string())
+	.bind(&repo_id.to_string())
+	.bind(url.as_str())
+	.bind(secret)
+	.bind(serde_json::to_string(event_types)?)
+	.bind(clock.now_utc().unix_timestamp())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(id)
+}
+
+pub async fn delete_webhook(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+	webhook_id: Id,
+) -> Result<()> {
+	sqlx::query(
+		"
+			delete from webhooks
+			where
+				id = $1
+			and
+				repo_id = $2
+		",
+	)
+	.bind(&webhook_id.to_string())
+	.bind(&repo_id.to_string())
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+pub async fn get_webhooks(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	repo_id: Id,
+) -> Result<Vec<Webhook>> {
+	let rows = sqlx::query(
+		"
+			select
+				id,
+				url,
+				secret,
+				event_types
+			from webhooks
+			where repo_id = $1
+			order by created_at
+		",
+	)
+	.bind(&repo_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.iter()
+		.map(|row| {
+			let id: String = row.get(0);
+			let url: String = row.get(1);
+			let secret: String = row.get(2);
+			let event_types: String = row.get(3);
+			Ok(Webhook {
+				id: id.parse()?,
+				url: url.parse()?,
+				secret,
+				event_types: serde_json::from_str(&event_types)?,
+			})
+		})
+		.collect()
+}
+
+/// Write a pending delivery of `event` for each webhook in the repo that subscribes to it.
+pub async fn create_webhook_deliveries(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	clock: &Clock,
+	repo_id: Id,
+	event: &WebhookEvent,
+) -> Result<()> {
+	let webhooks = get_webhooks(txn, repo_id).await?;
+	let event_type = event.event_type();
+	let now = clock.now_utc().unix_timestamp();
+	let payload = WebhookPayload {
+		id: Id::generate(),
+		date: now,
+		repo_id,
+		event,
+	};
+	let payload = serde_json::to_string(&payload)?;
+	for webhook in webhooks
+		.iter()
+		.filter(|webhook| webhook.event_types.contains(&event_type))
+	{
+		sqlx::query(
+			"
+				insert into webhook_deliveries (
+					id, webhook_id, event_type, payload, status, attempt_count, created_at
+				) values (
+					$1, $2, $3, $4, $5, 0, $6
+				)
+			",
+		)
+		.bind(&Id::generate().to_string())
+		.bind(&webhook.id.to_string())
+		.bind(event_type.as_str())
+		.bind(&payload)
+		.bind(i64::from(WebhookDeliveryStatus::Pending))
+		.bind(now)
+		.execute(txn.borrow_mut())
+		.await?;
+	}
+	Ok(())
+}
+
+/// Attempt each pending delivery once. A delivery succeeds if the receiver responds with a success status. Otherwise it is retried on the next call until it has been attempted [`WEBHOOK_MAXIMUM_ATTEMPTS`] times.
+pub async fn send_webhook_deliveries(
+	app_state: &AppState,
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<()> {
+	let rows = sqlx::query(
+		"
+			select
+				webhook_deliveries.id,
+				webhook_deliveries.event_type,
+				webhook_deliveries.payload,
+				webhook_deliveries.attempt_count,
+				webhooks.url,
+				webhooks.secret
+			from webhook_deliveries
+			join webhooks
+				on webhooks.id = webhook_deliveries.webhook_id
+			where webhook_deliveries.status = $1
+			order by webhook_deliveries.created_at
+		",
+	)
+	.bind(i64::from(WebhookDeliveryStatus::Pending))
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	for row in rows {
+		let id: String = row.get(0);
+		let event_type: String = row.get(1);
+		let payload: String = row.get(2);
+		let attempt_count: i64 = row.get(3);
+		let url: String = row.get(4);
+		let secret: String = row.get(5);
+		let signature = format!(
+			"sha256={}",
+			hex_encode(&hmac_sha256(secret.as_bytes(), payload.as_bytes()))
+		);
+		let headers = [
+			("content-type", "application/json".to_owned()),
+			("x-modelfox-delivery", id.clone()),
+			("x-modelfox-event", event_type),
+			("x-modelfox-signature", signature),
+		];
+		let succeeded = match url.parse() {
+			Ok(url) => match app_state
+				.http_sender
+				.post_body(payload, url, &headers)
+				.await
+			{
+				Ok(response) => response.status().is_success(),
+				Err(error) => {
+					tracing::warn!(%error, webhook_delivery_id = %id, "failed to send webhook");
+					false
+				}
+			},
+			Err(_) => false,
+		};
+		let attempt_count = attempt_count + 1;
+		let status = if succeeded {
+			WebhookDeliveryStatus::Succeeded
+		} else if attempt_count >= WEBHOOK_MAXIMUM_ATTEMPTS {
+			WebhookDeliveryStatus::Failed
+		} else {
+			WebhookDeliveryStatus::Pending
+		};
+		sqlx::query(
+			"
+				update
+					webhook_deliveries
+				set
+					status = $1,
+					attempt_count = $2,
+					last_attempted_at = $3
+				where
+					id = $4
+			",
+		)
+		.bind(i64::from(status))
+		.bind(attempt_count)
+		.bind(app_state.clock().now_utc().unix_timestamp())
+		.bind(&id)
+		.execute(txn.borrow_mut())
+		.await?;
+	}
+	Ok(())
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+	const BLOCK_SIZE: usize = 64;
+	let mut block = [0u8; BLOCK_SIZE];
+	if key.len() > BLOCK_SIZE {
+		block[..32].copy_from_slice(&Sha256::digest(key));
+	} else {
+		block[..key.len()].copy_from_slice(key);
+	}
+	let mut inner = Sha256::new();
+	inner.update(block.map(|byte| byte ^ 0x36));
+	inner.update(message);
+	let mut outer = Sha256::new();
+	outer.update(block.map(|byte| byte ^ 0x5c));
+	outer.update(inner.finalize());
+	let mut output = [0u8; 32];
+	output.copy_from_slice(&outer.finalize());
+	output
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+	bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_common::*;
+
+	async fn get_delivery_statuses(
+		txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	) -> Result<Vec<(String, i64)>> {
+		let rows = sqlx::query(
+			"
+				select
+					event_type,
+					status
+				from webhook_deliveries
+			",
+		)
+		.fetch_all(txn.borrow_mut())
+		.await?;
+		Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+	}
+
+	#[test]
+	fn test_hmac_sha256() {
+		// This is test case 2 from RFC 4231.
+		assert_eq!(
+			hex_encode(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+			"5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+		);
+	}
+
+	#[tokio::test]
+	async fn test_model_uploaded_webhook() {
+		let app = init_test_app().await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		let repo_id = app
+			.create_root_repo(&mut txn, "Heart Disease")
+			.await
+			.unwrap();
+		create_webhook(
+			&mut txn,
+			repo_id,
+			&"http://0.0.0.0:8085/webhook".parse().unwrap(),
+			"secret",
+			&[WebhookEventType::ModelUploaded],
+			app.clock(),
+		)
+		.await
+		.unwrap();
+		app.add_model_to_repo(
+			&mut txn,
+			repo_id,
+			workspace_root().join("heart_disease.modelfox"),
+		)
+		.await
+		.unwrap();
+		assert_eq!(
+			get_delivery_statuses(&mut txn).await.unwrap(),
+			vec![(
+				"model_uploaded".to_owned(),
+				i64::from(WebhookDeliveryStatus::Pending)
+			)]
+		);
+		app.commit_transaction(txn).await.unwrap();
+		app.send_alerts().await.unwrap();
+		let mut txn = app.begin_transaction().await.unwrap();
+		assert_eq!(
+			get_delivery_statuses(&mut txn).await.unwrap(),
+			vec![(
+				"model_uploaded".to_owned(),
+				i64::from(WebhookDeliveryStatus::Succeeded)
+			)]
+		);
+		app.commit_transaction(txn).await.unwrap();
+	}
+}
@@ -1,3 +1,5 @@
+use chrono::{prelude::*, Duration};
+use chrono_tz::Tz;
 use std::fmt::Display;
 
 #[derive(serde::Deserialize, Clone, Copy, Debug)]
@@ -20,21 +22,201 @@ impl Display for DateWindow {
 	}
 }
 
-#[derive(Clone, Copy, Debug)]
+/// The size of the intervals a date window is split into. Interval boundaries are aligned to the model's timezone, so days start at local midnight, weeks start on Monday, and months start on the first of the month, as in most BI tools.
+#[derive(serde::Deserialize, Clone, Copy, Debug, PartialEq)]
 pub enum DateWindowInterval {
+	#[serde(rename = "hourly")]
 	Hourly,
+	#[serde(rename = "daily")]
 	Daily,
+	#[serde(rename = "weekly")]
+	Weekly,
+	#[serde(rename = "monthly")]
 	Monthly,
 }
 
+impl Display for DateWindowInterval {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+		match *self {
+			DateWindowInterval::Hourly => f.write_str("hourly"),
+			DateWindowInterval::Daily => f.write_str("daily"),
+			DateWindowInterval::Weekly => f.write_str("weekly"),
+			DateWindowInterval::Monthly => f.write_str("monthly"),
+		}
+	}
+}
+
+impl DateWindow {
+	/// Retrieve the intervals the date window can be split into. The first is the default. Hourly intervals are only offered for today, because a longer window would have hundreds of them.
+	pub fn intervals(&self) -> &'static [DateWindowInterval] {
+		match self {
+			DateWindow::Today => &[DateWindowInterval::Hourly],
+			DateWindow::ThisMonth => &[DateWindowInterval::Daily, DateWindowInterval::Weekly],
+			DateWindow::ThisYear => &[
+				DateWindowInterval::Monthly,
+				DateWindowInterval::Weekly,
+				DateWindowInterval::Daily,
+			],
+		}
+	}
+}
+
+/// Choose the date window and interval from the search params. If the interval is missing or cannot be used with the date window, such as after the date window is changed, the date window's default interval is used.
 pub fn get_date_window_and_interval(
 	date_window: &Option<DateWindow>,
+	date_window_interval: &Option<DateWindowInterval>,
 ) -> Option<(DateWindow, DateWindowInterval)> {
 	let date_window = date_window.unwrap_or(DateWindow::ThisMonth);
-	let date_window_interval = match date_window {
-		DateWindow::Today => DateWindowInterval::Hourly,
-		DateWindow::ThisMonth => DateWindowInterval::Daily,
-		DateWindow::ThisYear => DateWindowInterval::Monthly,
-	};
+	let intervals = date_window.intervals();
+	let date_window_interval = date_window_interval
+		.filter(|date_window_interval| intervals.contains(date_window_interval))
+		.unwrap_or(intervals[0]);
 	Some((date_window, date_window_interval))
 }
+
+/// Compute the start and end of the date window that contains `now`, in `now`'s timezone.
+pub fn get_date_window_bounds(
+	date_window: DateWindow,
+	now: DateTime<Tz>,
+) -> (DateTime<Tz>, DateTime<Tz>) {
+	let timezone = now.timezone();
+	let today = now.date().naive_local();
+	let (start, end) = match date_window {
+		DateWindow::Today => (today, today.succ()),
+		DateWindow::ThisMonth => {
+			let start = NaiveDate::from_ymd(today.year(), today.month(), 1);
+			(start, next_month(start))
+		}
+		DateWindow::ThisYear => (
+			NaiveDate::from_ymd(today.year(), 1, 1),
+			NaiveDate::from_ymd(today.year() + 1, 1, 1),
+		),
+	};
+	(start_of_day(timezone, start), start_of_day(timezone, end))
+}
+
+/// Split the date window that contains `now` into intervals, and return the start and end of each. Weeks are clipped to the date window, so the first and last weeks of a month or year can be shorter than seven days. Hourly intervals are consecutive hours of elapsed time, so a day with a daylight saving time transition has 23 or 25 of them.
+pub fn get_date_window_intervals(
+	date_window: DateWindow,
+	date_window_interval: DateWindowInterval,
+	now: DateTime<Tz>,
+) -> Vec<(DateTime<Tz>, DateTime<Tz>)> {
+	let timezone = now.timezone();
+	let (window_start, window_end) = get_date_window_bounds(date_window, now);
+	if date_window_interval == DateWindowInterval::Hourly {
+		let mut intervals = Vec::new();
+		let mut start = window_start;
+		while start < window_end {
+			let end = (start + Duration::hours(1)).min(window_end);
+			intervals.push((start, end));
+			start = end;
+		}
+		return intervals;
+	}
+	let window_end_date = window_end.date().naive_local();
+	let mut intervals = Vec::new();
+	let mut start_date = window_start.date().naive_local();
+	while start_date < window_end_date {
+		let end_date = match date_window_interval {
+			DateWindowInterval::Hourly => unreachable!(),
+			DateWindowInterval::Daily => start_date.succ(),
+			DateWindowInterval::Weekly => {
+				let days_until_monday = 7 - start_date.weekday().num_days_from_monday();
+				start_date + Duration::days(i64::from(days_until_monday))
+			}
+			DateWindowInterval::Monthly => next_month(NaiveDate::from_ymd(
+				start_date.year(),
+				start_date.month(),
+				1,
+			)),
+		}
+		.min(window_end_date);
+		intervals.push((
+			start_of_day(timezone, start_date),
+			start_of_day(timezone, end_date),
+		));
+		start_date = end_date;
+	}
+	intervals
+}
+
+fn next_month(date: NaiveDate) -> NaiveDate {
+	if date.month() == 12 {
+		NaiveDate::from_ymd(date.year() + 1, 1, 1)
+	} else {
+		NaiveDate::from_ymd(date.year(), date.month() + 1, 1)
+	}
+}
+
+/// Get the first instant of the day in the timezone. This is usually midnight, but in timezones whose daylight saving time transitions skip midnight, it is the first hour after the transition.
+fn start_of_day(timezone: Tz, date: NaiveDate) -> DateTime<Tz> {
+	(0..24)
+		.find_map(|hour| {
+			timezone
+				.from_local_datetime(&date.and_hms(hour, 0, 0))
+				.earliest()
+		})
+		.unwrap()
+}
+
+/// A range of the intervals in a date window, which is selected by brushing a production chart. `start` and `end` are the indexes of the first and last intervals in the range.
+#[derive(Clone, Copy, Debug)]
+pub struct DateWindowRange {
+	pub start: usize,
+	pub end: usize,
+}
+
+impl DateWindowRange {
+	/// Create a range from the `range_start` and `range_end` search params, which are only used if both are present.
+	pub fn from_search_params(
+		range_start: Option<usize>,
+		range_end: Option<usize>,
+	) -> Option<DateWindowRange> {
+		match (range_start, range_end) {
+			(Some(start), Some(end)) if start <= end => Some(DateWindowRange { start, end }),
+			_ => None,
+		}
+	}
+
+	/// Retrieve the indexes of the intervals in the range, clamped to the `n_intervals` intervals in the date window.
+	pub fn indexes(&self, n_intervals: usize) -> std::ops::RangeInclusive<usize> {
+		let last = n_intervals.saturating_sub(1);
+		let start = self.start.min(last);
+		let end = self.end.clamp(start, last);
+		start..=end
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_weekly_intervals_are_clipped_to_the_month() {
+		let now = chrono_tz::UTC.ymd(2022, 6, 15).and_hms(12, 0, 0);
+		let intervals =
+			get_date_window_intervals(DateWindow::ThisMonth, DateWindowInterval::Weekly, now);
+		let days = intervals
+			.iter()
+			.map(|(start, end)| (start.day(), end.day()))
+			.collect::<Vec<_>>();
+		// June 1, 2022 is a Wednesday, and the last interval ends on July 1.
+		assert_eq!(days, vec![(1, 6), (6, 13), (13, 20), (20, 27), (27, 1)]);
+	}
+
+	#[test]
+	fn test_intervals_are_aligned_to_the_timezone() {
+		let timezone = chrono_tz::America::New_York;
+		let now = timezone.ymd(2022, 3, 13).and_hms(12, 0, 0);
+		// Daylight saving time starts at 2am on March 13, so the day has 23 hours.
+		let intervals =
+			get_date_window_intervals(DateWindow::Today, DateWindowInterval::Hourly, now);
+		assert_eq!(intervals.len(), 23);
+		let intervals =
+			get_date_window_intervals(DateWindow::ThisYear, DateWindowInterval::Monthly, now);
+		assert_eq!(intervals.len(), 12);
+		for (start, _) in intervals {
+			assert_eq!((start.day(), start.hour()), (1, 0));
+		}
+	}
+}
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use modelfox_app_core::App;
 use modelfox_app_ui::topbar::{Topbar, TopbarAvatar};
+use modelfox_ui as ui;
 use pinwheel::prelude::*;
 
 pub struct AppLayoutInfo {
+	pub notifications_paused: bool,
 	pub topbar_avatar: Option<TopbarAvatar>,
 }
 
@@ -22,7 +24,27 @@ impl Component for AppLayout {
 		div()
 			.class("app-layout-topbar-grid")
 			.child(Topbar::new().topbar_avatar(topbar_avatar))
-			.child(div().class("app-layout").child(self.children))
+			.child(
+				div()
+					.class("app-layout")
+					.child(
+						self.info
+							.notifications_paused
+							.then(|| NotificationsPausedBanner),
+					)
+					.child(self.children),
+			)
+			.into_node()
+	}
+}
+
+/// This banner is shown at the top of every page while the app's `pause_notifications` option is set.
+pub struct NotificationsPausedBanner;
+
+impl Component for NotificationsPausedBanner {
+	fn into_node(self) -> Node {
+		ui::Alert::new(ui::Level::Warning)
+			.child("Notifications are paused. Alerts are still recorded, and their notifications will be sent when notifications are resumed.")
 			.into_node()
 	}
 }
@@ -33,5 +55,8 @@ pub async fn app_layout_info(app: &App) -> Result<AppLayoutInfo> {
 	} else {
 		None
 	};
-	Ok(AppLayoutInfo { topbar_avatar })
+	Ok(AppLayoutInfo {
+		notifications_paused: app.options().pause_notifications,
+		topbar_avatar,
+	})
 }
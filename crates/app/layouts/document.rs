@@ -1,3 +1,4 @@
+use modelfox_app_base_path::app_path;
 use pinwheel::prelude::*;
 
 #[derive(builder, children, Default, new)]
@@ -19,14 +20,14 @@ impl Component for Document {
 			)
 			.child(
 				link()
-					.attribute("href", "/favicon.png")
+					.attribute("href", app_path("/favicon.png"))
 					.attribute("rel", "icon")
 					.attribute("type", "image/png"),
 			)
 			.child(title().child("ModelFox"))
 			.child(
 				link()
-					.attribute("href", "/styles.css")
+					.attribute("href", app_path("/styles.css"))
 					.attribute("rel", "stylesheet"),
 			)
 			.child(
@@ -34,14 +35,16 @@ impl Component for Document {
 					.attribute("content", "ModelFox makes it easy to train, deploy, and monitor machine learning models.")
 					.attribute("name", "description"),
 			);
-		let timezone_script = script().child(
-			"document.cookie = `modelfox_timezone=${Intl.DateTimeFormat().resolvedOptions().timeZone};max-age=31536000;path=/;samesite=lax`");
+		let timezone_script = script().child(format!(
+			"document.cookie = `modelfox_timezone=${{Intl.DateTimeFormat().resolvedOptions().timeZone}};max-age=31536000;path={};samesite=lax`",
+			app_path("/"),
+		));
 		let client_script = self.client.map(|client| {
 			let paths = sunfish::client_paths(client);
 			script().attribute("type", "module").inner_html(format!(
 				r#"import init from "{path_js}"; init("{path_wasm}")"#,
-				path_js = paths.path_js,
-				path_wasm = paths.path_wasm,
+				path_js = app_path(paths.path_js),
+				path_wasm = app_path(paths.path_wasm),
 			))
 		});
 		let body = body()
@@ -1,8 +1,10 @@
+use crate::app_layout::NotificationsPausedBanner;
 use anyhow::Result;
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
-	repos::get_model_version_ids,
+	repos::{get_model_version_ids, trash_model_version},
 	user::{authorize_user, authorize_user_for_model},
 	App,
 };
@@ -17,6 +19,7 @@ pub struct ModelLayoutInfo {
 	pub model_id: Id,
 	pub model_tag: Option<String>,
 	pub model_version_ids: Vec<Id>,
+	pub notifications_paused: bool,
 	pub owner: Option<Owner>,
 	pub repo_id: String,
 	pub repo_title: String,
@@ -105,6 +108,7 @@ pub async fn model_layout_info(
 		model_id,
 		model_tag,
 		model_version_ids,
+		notifications_paused: app.options().pause_notifications,
 		owner,
 		repo_id: repo_id.to_string(),
 		repo_title,
@@ -132,10 +136,11 @@ pub async fn post(
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -143,28 +148,11 @@ pub async fn post(
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
-	let response = delete_model(&mut db, model_id).await?;
+	trash_model_version(&mut db, app, model_id).await?;
 	app.commit_transaction(db).await?;
-	Ok(response)
-}
-
-async fn delete_model(
-	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
-	model_id: Id,
-) -> Result<http::Response<hyper::Body>> {
-	sqlx::query(
-		"
-		delete from models
-		where
-			models.id = $1
-	",
-	)
-	.bind(&model_id.to_string())
-	.execute(txn.borrow_mut())
-	.await?;
 	let response = http::Response::builder()
 		.status(http::StatusCode::SEE_OTHER)
-		.header(http::header::LOCATION, "/")
+		.header(http::header::LOCATION, app_path("/"))
 		.body(hyper::Body::empty())
 		.unwrap();
 	Ok(response)
@@ -176,13 +164,18 @@ pub enum ModelNavItem {
 	TrainingGrid,
 	TrainingStats,
 	TrainingMetrics,
+	TrainingTrees,
+	TrainingCoefficients,
 	Playground,
 	Tuning,
+	Serving,
 	ProductionPredictions,
 	ProductionStats,
 	ProductionMetrics,
 	Alerts,
 	Monitors,
+	Notes,
+	ModelCard,
 }
 
 impl Component for ModelLayout {
@@ -217,7 +210,14 @@ impl Component for ModelLayout {
 			model_id: self.info.model_id.to_string(),
 			selected_item: self.info.selected_item,
 		});
-		let center = div().class("model-layout-center").child(self.children);
+		let center = div()
+			.class("model-layout-center")
+			.child(
+				self.info
+					.notifications_paused
+					.then(|| NotificationsPausedBanner),
+			)
+			.child(self.children);
 		let right = div().class("model-layout-right");
 		div()
 			.class("model-layout")
@@ -252,11 +252,11 @@ impl Component for ModelLayoutTop {
 		let owner_info = self.owner.map(|owner| match owner {
 			Owner::User { email, .. } => OwnerInfo {
 				title: email,
-				href: "/user".to_owned(),
+				href: app_path("/user"),
 			},
 			Owner::Organization { name, id } => OwnerInfo {
 				title: name,
-				href: format!("/organizations/{}/", id),
+				href: app_path(format!("/organizations/{}/", id)),
 			},
 		});
 		let owner_segment = owner_info.map(|owner_info| {
@@ -271,13 +271,16 @@ impl Component for ModelLayoutTop {
 		let repo_segment = a()
 			.class("model-layout-top-title-segment")
 			.attribute("title", "repo")
-			.href(format!("/repos/{}/", repo_id))
+			.href(app_path(format!("/repos/{}/", repo_id)))
 			.child(self.repo_title.clone());
 		let repo_slash = span().class("model-layout-top-title-slash").child("/");
 		let model_segment = a()
 			.class("model-layout-top-title-segment")
 			.attribute("title", "repo")
-			.href(format!("/repos/{}/models/{}/", repo_id, self.model_id,))
+			.href(app_path(format!(
+				"/repos/{}/models/{}/",
+				repo_id, self.model_id,
+			)))
 			.child(model_heading);
 		let title = div()
 			.class("model-layout-top-title-wrapper")
@@ -291,15 +294,27 @@ impl Component for ModelLayoutTop {
 			.child(
 				ui::Button::new()
 					.color(ui::colors::GRAY.to_owned())
-					.href(format!("/repos/{}/models/{}/edit", repo_id, self.model_id))
+					.href(app_path(format!(
+						"/repos/{}/models/{}/edit",
+						repo_id, self.model_id
+					)))
 					.child("Edit"),
 			)
 			.child(
 				ui::Button::new()
-					.href(format!(
+					.color(ui::colors::GRAY.to_owned())
+					.href(app_path(format!(
+						"/repos/{}/models/{}/report",
+						repo_id, self.model_id
+					)))
+					.child("Download Report"),
+			)
+			.child(
+				ui::Button::new()
+					.href(app_path(format!(
 						"/repos/{}/models/{}/download",
 						repo_id, self.model_id
-					))
+					)))
 					.download(format!("{}.modelfox", self.repo_title))
 					.child("Download"),
 			);
@@ -319,102 +334,151 @@ pub struct ModelNav {
 
 impl Component for ModelNav {
 	fn into_node(self) -> Node {
-		let overview = ui::NavSection::new("Overview".to_owned()).child(
-			ui::NavItem::new()
-				.title("Overview".to_owned())
-				.href(format!("/repos/{}/models/{}/", self.repo_id, self.model_id))
-				.selected(self.selected_item == ModelNavItem::Overview),
-		);
+		let overview = ui::NavSection::new("Overview".to_owned())
+			.child(
+				ui::NavItem::new()
+					.title("Overview".to_owned())
+					.href(app_path(format!(
+						"/repos/{}/models/{}/",
+						self.repo_id, self.model_id
+					)))
+					.selected(self.selected_item == ModelNavItem::Overview),
+			)
+			.child(
+				ui::NavItem::new()
+					.title("Notes".to_owned())
+					.href(app_path(format!(
+						"/repos/{}/models/{}/notes",
+						self.repo_id, self.model_id
+					)))
+					.selected(self.selected_item == ModelNavItem::Notes),
+			)
+			.child(
+				ui::NavItem::new()
+					.title("Model Card".to_owned())
+					.href(app_path(format!(
+						"/repos/{}/models/{}/model_card",
+						self.repo_id, self.model_id
+					)))
+					.selected(self.selected_item == ModelNavItem::ModelCard),
+			);
 		let training = ui::NavSection::new("Training".to_owned())
 			.child(
 				ui::NavItem::new()
 					.title("Grid".to_owned())
-					.href(format!(
+					.href(app_path(format!(
 						"/repos/{}/models/{}/training_grid/",
 						self.repo_id, self.model_id
-					))
+					)))
 					.selected(self.selected_item == ModelNavItem::TrainingGrid),
 			)
 			.child(
 				ui::NavItem::new()
 					.title("Stats".to_owned())
-					.href(format!(
+					.href(app_path(format!(
 						"/repos/{}/models/{}/training_stats/",
 						self.repo_id, self.model_id
-					))
+					)))
 					.selected(self.selected_item == ModelNavItem::TrainingStats),
 			)
 			.child(
 				ui::NavItem::new()
 					.title("Metrics".to_owned())
-					.href(format!(
+					.href(app_path(format!(
 						"/repos/{}/models/{}/training_metrics/",
 						self.repo_id, self.model_id
-					))
+					)))
 					.selected(self.selected_item == ModelNavItem::TrainingMetrics),
+			)
+			.child(
+				ui::NavItem::new()
+					.title("Trees".to_owned())
+					.href(app_path(format!(
+						"/repos/{}/models/{}/training_trees",
+						self.repo_id, self.model_id
+					)))
+					.selected(self.selected_item == ModelNavItem::TrainingTrees),
+			)
+			.child(
+				ui::NavItem::new()
+					.title("Coefficients".to_owned())
+					.href(app_path(format!(
+						"/repos/{}/models/{}/training_coefficients",
+						self.repo_id, self.model_id
+					)))
+					.selected(self.selected_item == ModelNavItem::TrainingCoefficients),
 			);
 		let playground = ui::NavSection::new("Playground".to_owned()).child(
 			ui::NavItem::new()
 				.title("Playground".to_owned())
-				.href(format!(
+				.href(app_path(format!(
 					"/repos/{}/models/{}/playground",
 					self.repo_id, self.model_id
-				))
+				)))
 				.selected(self.selected_item == ModelNavItem::Playground),
 		);
 		let tuning = ui::NavSection::new("Tuning".to_owned()).child(
 			ui::NavItem::new()
 				.title("Tuning".to_owned())
-				.href(format!(
+				.href(app_path(format!(
 					"/repos/{}/models/{}/tuning",
 					self.repo_id, self.model_id
-				))
+				)))
 				.selected(self.selected_item == ModelNavItem::Tuning),
 		);
 		let production = ui::NavSection::new("Production".to_owned())
+			.child(
+				ui::NavItem::new()
+					.title("Serving".to_owned())
+					.href(app_path(format!(
+						"/repos/{}/models/{}/serving",
+						self.repo_id, self.model_id
+					)))
+					.selected(self.selected_item == ModelNavItem::Serving),
+			)
 			.child(
 				ui::NavItem::new()
 					.title("Predictions".to_owned())
-					.href(format!(
+					.href(app_path(format!(
 						"/repos/{}/models/{}/production_predictions/",
 						self.repo_id, self.model_id
-					))
+					)))
 					.selected(self.selected_item == ModelNavItem::ProductionPredictions),
 			)
 			.child(
 				ui::NavItem::new()
 					.title("Stats".to_owned())
-					.href(format!(
+					.href(app_path(format!(
 						"/repos/{}/models/{}/production_stats/",
 						self.repo_id, self.model_id
-					))
+					)))
 					.selected(self.selected_item == ModelNavItem::ProductionStats),
 			)
 			.child(
 				ui::NavItem::new()
 					.title("Metrics".to_owned())
-					.href(format!(
+					.href(app_path(format!(
 						"/repos/{}/models/{}/production_metrics/",
 						self.repo_id, self.model_id
-					))
+					)))
 					.selected(self.selected_item == ModelNavItem::ProductionMetrics),
 			)
 			.child(
 				ui::NavItem::new()
 					.title("Alerts".to_owned())
-					.href(format!(
+					.href(app_path(format!(
 						"/repos/{}/models/{}/alerts/",
 						self.repo_id, self.model_id
-					))
+					)))
 					.selected(self.selected_item == ModelNavItem::Alerts),
 			)
 			.child(
 				ui::NavItem::new()
 					.title("Monitors".to_owned())
-					.href(format!(
+					.href(app_path(format!(
 						"/repos/{}/models/{}/monitors/",
 						self.repo_id, self.model_id
-					))
+					)))
 					.selected(self.selected_item == ModelNavItem::Monitors),
 			);
 		ui::Nav::new()
@@ -1,13 +1,17 @@
 use anyhow::Result;
 use modelfox_app_context::Context;
 pub use modelfox_app_core::{clock::Clock, options};
-use modelfox_app_core::{options::Options, App};
+use modelfox_app_core::{
+	error::payload_too_large, options::Options, request_body, request_tracing, App,
+};
+use modelfox_id::Id;
 use std::sync::Arc;
 use tracing::error;
 
 pub async fn run(options: Options) -> Result<()> {
 	let host = options.host;
 	let port = options.port;
+	modelfox_app_base_path::set_base_path(&options.base_path)?;
 	let addr = std::net::SocketAddr::new(host, port);
 	let app = App::new(options).await?;
 	let sunfish = sunfish::init!();
@@ -19,23 +23,56 @@ pub async fn run(options: Options) -> Result<()> {
 
 async fn handle(mut request: http::Request<hyper::Body>) -> http::Response<hyper::Body> {
 	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
-	let response = context
-		.sunfish
-		.handle(&mut request)
+	let request_id = *request.extensions().get::<Id>().unwrap();
+	if !strip_base_path_from_request(&mut request) {
+		return not_found();
+	}
+	let body_size_limit = context
+		.app
+		.options()
+		.body_size_limits
+		.for_path(request.uri().path());
+	if !request_body::limit_request_body(&mut request, body_size_limit) {
+		return payload_too_large();
+	}
+	let span = request_tracing::request_span(request_id, request.uri().path());
+	let response = request_tracing::trace_request(span, context.sunfish.handle(&mut request))
 		.await
 		.unwrap_or_else(|error| {
 			error!(%error, backtrace = %error.backtrace());
 			Some(
 				http::Response::builder()
 					.status(http::StatusCode::INTERNAL_SERVER_ERROR)
-					.body(hyper::Body::from("internal server error"))
+					.body(hyper::Body::from(format!(
+						"internal server error\ntrace id: {}",
+						request_id
+					)))
 					.unwrap(),
 			)
 		});
-	response.unwrap_or_else(|| {
-		http::Response::builder()
-			.status(http::StatusCode::NOT_FOUND)
-			.body(hyper::Body::from("not found"))
-			.unwrap()
-	})
+	response.unwrap_or_else(not_found)
+}
+
+/// Routes are matched as if the app were served from the root, so remove the base path from the request's uri before routing it. This returns false if the request is not below the base path.
+fn strip_base_path_from_request(request: &mut http::Request<hyper::Body>) -> bool {
+	if modelfox_app_base_path::base_path().is_empty() {
+		return true;
+	}
+	let path = match modelfox_app_base_path::strip_base_path(request.uri().path()) {
+		Some(path) => path,
+		None => return false,
+	};
+	let path_and_query = match request.uri().query() {
+		Some(query) => format!("{}?{}", path, query),
+		None => path.to_owned(),
+	};
+	*request.uri_mut() = path_and_query.parse().unwrap();
+	true
+}
+
+fn not_found() -> http::Response<hyper::Body> {
+	http::Response::builder()
+		.status(http::StatusCode::NOT_FOUND)
+		.body(hyper::Body::from("not found"))
+		.unwrap()
 }
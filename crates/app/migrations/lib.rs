@@ -8,6 +8,30 @@ use std::collections::BTreeMap;
 mod migration_2020_01_01_000000;
 mod migration_2020_04_19_000000;
 mod migration_2021_11_23_000000;
+mod migration_2022_06_01_000000;
+mod migration_2022_06_02_000000;
+mod migration_2022_06_03_000000;
+mod migration_2022_06_04_000000;
+mod migration_2022_06_05_000000;
+mod migration_2022_06_06_000000;
+mod migration_2022_06_07_000000;
+mod migration_2022_06_08_000000;
+mod migration_2022_06_09_000000;
+mod migration_2022_06_10_000000;
+mod migration_2022_06_11_000000;
+mod migration_2022_06_12_000000;
+mod migration_2022_06_13_000000;
+mod migration_2022_06_14_000000;
+mod migration_2022_06_15_000000;
+mod migration_2022_06_16_000000;
+mod migration_2022_06_17_000000;
+mod migration_2022_06_18_000000;
+mod migration_2022_06_19_000000;
+mod migration_2022_06_20_000000;
+mod migration_2022_06_21_000000;
+mod migration_2022_06_22_000000;
+mod migration_2022_06_23_000000;
+mod migration_2022_06_24_000000;
 
 #[rustfmt::skip]
 type Migration = &'static (dyn Sync + for<'a> Fn(&'a mut sqlx::Transaction<sqlx::Any>) -> BoxFuture<'a, Result<()>>);
@@ -25,6 +49,78 @@ static MIGRATIONS: Lazy<MigrationMap> = Lazy::new(|| {
 	migrations.insert("2021_11_23_000000", &|db| {
 		migration_2021_11_23_000000::migrate(db).boxed()
 	});
+	migrations.insert("2022_06_01_000000", &|db| {
+		migration_2022_06_01_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_02_000000", &|db| {
+		migration_2022_06_02_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_03_000000", &|db| {
+		migration_2022_06_03_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_04_000000", &|db| {
+		migration_2022_06_04_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_05_000000", &|db| {
+		migration_2022_06_05_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_06_000000", &|db| {
+		migration_2022_06_06_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_07_000000", &|db| {
+		migration_2022_06_07_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_08_000000", &|db| {
+		migration_2022_06_08_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_09_000000", &|db| {
+		migration_2022_06_09_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_10_000000", &|db| {
+		migration_2022_06_10_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_11_000000", &|db| {
+		migration_2022_06_11_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_12_000000", &|db| {
+		migration_2022_06_12_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_13_000000", &|db| {
+		migration_2022_06_13_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_14_000000", &|db| {
+		migration_2022_06_14_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_15_000000", &|db| {
+		migration_2022_06_15_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_16_000000", &|db| {
+		migration_2022_06_16_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_17_000000", &|db| {
+		migration_2022_06_17_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_18_000000", &|db| {
+		migration_2022_06_18_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_19_000000", &|db| {
+		migration_2022_06_19_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_20_000000", &|db| {
+		migration_2022_06_20_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_21_000000", &|db| {
+		migration_2022_06_21_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_22_000000", &|db| {
+		migration_2022_06_22_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_23_000000", &|db| {
+		migration_2022_06_23_000000::migrate(db).boxed()
+	});
+	migrations.insert("2022_06_24_000000", &|db| {
+		migration_2022_06_24_000000::migrate(db).boxed()
+	});
 	migrations
 });
 
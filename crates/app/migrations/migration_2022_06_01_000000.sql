@@ -0,0 +1,7 @@
+create table notification_channels (
+	id char(32) primary key,
+	organization_id char(32) references organizations (id) on delete cascade not null,
+	name text not null,
+	data text not null,
+	created_at bigint not null
+);
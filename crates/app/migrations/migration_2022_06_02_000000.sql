@@ -0,0 +1,6 @@
+create table monitor_templates (
+	id char(32) primary key,
+	repo_id char(32) references repos (id) on delete cascade not null,
+	data text not null,
+	created_at bigint not null
+);
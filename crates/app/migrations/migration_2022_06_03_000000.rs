@@ -0,0 +1,8 @@
+use anyhow::Result;
+use sqlx::prelude::*;
+
+pub async fn migrate(db: &mut sqlx::Transaction<'_, sqlx::Any>) -> Result<()> {
+	db.execute(include_str!("./migration_2022_06_03_000000.sql"))
+		.await?;
+	Ok(())
+}
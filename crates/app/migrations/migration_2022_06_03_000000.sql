@@ -0,0 +1,13 @@
+create table model_notes (
+	id char(32) primary key,
+	model_id char(32) references models (id) on delete cascade not null,
+	user_id char(32) references users (id) on delete set null,
+	content text not null,
+	created_at bigint not null
+);
+
+create table model_approvals (
+	model_id char(32) primary key references models (id) on delete cascade,
+	user_id char(32) references users (id) on delete set null,
+	created_at bigint not null
+);
@@ -0,0 +1 @@
+alter table monitors add column cooldown_remaining integer not null default 0;
@@ -0,0 +1,5 @@
+alter table tokens add column created_at bigint;
+alter table tokens add column last_active_at bigint;
+alter table tokens add column ip text;
+alter table tokens add column user_agent text;
+alter table tokens add column revoked_at bigint;
@@ -0,0 +1 @@
+alter table models add column decision_threshold real;
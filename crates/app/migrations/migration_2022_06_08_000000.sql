@@ -0,0 +1,13 @@
+create table production_stats_daily (
+	model_id char(32) references models (id) on delete cascade not null,
+	day bigint not null,
+	data text not null,
+	primary key (model_id, day)
+);
+
+create table production_metrics_daily (
+	model_id char(32) references models (id) on delete cascade not null,
+	day bigint not null,
+	data text not null,
+	primary key (model_id, day)
+);
@@ -0,0 +1,6 @@
+create table invitations (
+	id char(32) primary key,
+	email varchar(320) unique not null,
+	inviter_user_id char(32) references users (id) on delete set null,
+	created_at bigint not null
+);
@@ -0,0 +1,13 @@
+create table monitor_evaluations (
+	id char(32) primary key,
+	monitor_id char(32) references monitors (id) on delete cascade not null,
+	date bigint not null,
+	production_value real not null,
+	training_value real not null,
+	difference real not null,
+	difference_upper real,
+	difference_lower real,
+	fired boolean not null
+);
+
+create index monitor_evaluations_monitor_id_date_index on monitor_evaluations (monitor_id, date);
@@ -0,0 +1,3 @@
+alter table organizations add column timezone text;
+
+alter table users add column timezone text;
@@ -0,0 +1,3 @@
+alter table predictions add column sample_rate real;
+
+create index predictions_model_id_date_index on predictions (model_id, date);
@@ -0,0 +1,21 @@
+create table webhooks (
+	id char(32) primary key,
+	repo_id char(32) references repos (id) on delete cascade not null,
+	url text not null,
+	secret text not null,
+	event_types text not null,
+	created_at bigint not null
+);
+
+create table webhook_deliveries (
+	id char(32) primary key,
+	webhook_id char(32) references webhooks (id) on delete cascade not null,
+	event_type text not null,
+	payload text not null,
+	status integer not null,
+	attempt_count integer not null,
+	created_at bigint not null,
+	last_attempted_at bigint
+);
+
+create index webhook_deliveries_status_index on webhook_deliveries (status);
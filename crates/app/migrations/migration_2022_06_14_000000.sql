@@ -0,0 +1 @@
+alter table models add column privacy_settings text;
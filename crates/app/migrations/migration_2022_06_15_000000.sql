@@ -0,0 +1,67 @@
+/* models in the trash have a deleted_at date and are deleted for good once they have been in the trash for 30 days */
+alter table models add column deleted_at bigint;
+
+create index models_deleted_at_index on models (deleted_at);
+
+/* recreate the monitor tables so that deleting a model deletes its monitors, their alerts, and their evaluations */
+create table monitors_new (
+	id char(32) primary key,
+	model_id char(32) references models (id) on delete cascade not null,
+	data text not null,
+	cadence integer not null,
+	last_checked bigint,
+	cooldown_remaining integer not null default 0
+);
+
+insert into monitors_new (id, model_id, data, cadence, last_checked, cooldown_remaining)
+	select id, model_id, data, cadence, last_checked, cooldown_remaining from monitors;
+
+create table alerts_new (
+	id char(32) primary key,
+	monitor_id char(32) references monitors_new (id) on delete cascade not null,
+	data text not null,
+	date bigint not null
+);
+
+insert into alerts_new (id, monitor_id, data, date)
+	select id, monitor_id, data, date from alerts;
+
+create table alert_sends_new (
+	id char(32) primary key,
+	alert_id char(32) references alerts_new (id) on delete cascade not null,
+	attempt_count integer not null,
+	method text not null,
+	status integer not null,
+	initiated_date bigint not null,
+	completed_date bigint
+);
+
+insert into alert_sends_new (id, alert_id, attempt_count, method, status, initiated_date, completed_date)
+	select id, alert_id, attempt_count, method, status, initiated_date, completed_date from alert_sends;
+
+create table monitor_evaluations_new (
+	id char(32) primary key,
+	monitor_id char(32) references monitors_new (id) on delete cascade not null,
+	date bigint not null,
+	production_value real not null,
+	training_value real not null,
+	difference real not null,
+	difference_upper real,
+	difference_lower real,
+	fired boolean not null
+);
+
+insert into monitor_evaluations_new (id, monitor_id, date, production_value, training_value, difference, difference_upper, difference_lower, fired)
+	select id, monitor_id, date, production_value, training_value, difference, difference_upper, difference_lower, fired from monitor_evaluations;
+
+drop table alert_sends;
+drop table alerts;
+drop table monitor_evaluations;
+drop table monitors;
+
+alter table monitors_new rename to monitors;
+alter table alerts_new rename to alerts;
+alter table alert_sends_new rename to alert_sends;
+alter table monitor_evaluations_new rename to monitor_evaluations;
+
+create index monitor_evaluations_monitor_id_date_index on monitor_evaluations (monitor_id, date);
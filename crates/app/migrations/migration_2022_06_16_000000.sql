@@ -0,0 +1,2 @@
+/* organizations may limit how many alert notifications are sent for their repos per hour, and alerts over the limit are combined into a summary */
+alter table organizations add column alert_notifications_per_hour integer;
@@ -0,0 +1,7 @@
+/* the sequence number of the last record ingested from each shard of a kinesis stream */
+create table ingest_checkpoints (
+	stream_name text not null,
+	shard_id text not null,
+	sequence_number text not null,
+	primary key (stream_name, shard_id)
+);
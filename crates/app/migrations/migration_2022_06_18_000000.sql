@@ -0,0 +1,2 @@
+/* organization members without data access can see a model's metrics but not the inputs of its production predictions */
+alter table organizations_users add column has_data_access boolean not null default true;
@@ -0,0 +1,2 @@
+/* a repo's readme is markdown describing the problem its models solve, rendered on the repo page */
+alter table repos add column readme text;
@@ -0,0 +1,2 @@
+/* the per class probability thresholds saved for a multiclass classifier on the tuning page, as a json object from class names to thresholds */
+alter table models add column class_thresholds text;
@@ -0,0 +1,14 @@
+/* batch predict jobs score an uploaded csv or ndjson file in the background. the status is one of queued, running, succeeded, and failed. */
+create table batch_predict_jobs (
+	id char(32) primary key,
+	model_id char(32) references models (id) on delete cascade not null,
+	status text not null,
+	format text not null,
+	created_at bigint not null,
+	started_at bigint,
+	finished_at bigint,
+	row_count bigint,
+	error text
+);
+
+create index batch_predict_jobs_status_created_at_index on batch_predict_jobs (status, created_at);
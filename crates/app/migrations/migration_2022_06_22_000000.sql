@@ -0,0 +1,14 @@
+/* prediction keys authorize requests to a model's predict endpoint. each key has its own limit on requests per minute, and counts the requests it made and the ones that were rejected for exceeding the limit. */
+create table prediction_keys (
+	id char(32) primary key,
+	model_id char(32) references models (id) on delete cascade not null,
+	name text not null,
+	token char(32) unique not null,
+	requests_per_minute bigint not null,
+	created_at bigint not null,
+	last_used_at bigint,
+	request_count bigint not null default 0,
+	rejected_request_count bigint not null default 0
+);
+
+create index prediction_keys_model_id_index on prediction_keys (model_id);
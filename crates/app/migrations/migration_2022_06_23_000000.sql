@@ -0,0 +1,23 @@
+/* teams group the members of an organization. a repo granted to one or more teams is only accessible to the members of those teams and the organization's admins, and a repo granted to no teams is accessible to every member of the organization. */
+create table teams (
+	id char(32) primary key,
+	organization_id char(32) references organizations (id) on delete cascade not null,
+	name text not null,
+	created_at bigint not null
+);
+
+create index teams_organization_id_index on teams (organization_id);
+
+/* team maintainers can add and remove the team's members and grant repos to the team. */
+create table teams_users (
+	team_id char(32) references teams (id) on delete cascade not null,
+	user_id char(32) references users (id) on delete cascade not null,
+	is_maintainer boolean not null,
+	primary key (team_id, user_id)
+);
+
+create table repos_teams (
+	repo_id char(32) references repos (id) on delete cascade not null,
+	team_id char(32) references teams (id) on delete cascade not null,
+	primary key (repo_id, team_id)
+);
@@ -0,0 +1,8 @@
+/* a prediction's identifier is hashed if its model did not store inputs when it was tracked. this is recorded on each prediction so true values still match predictions after the setting is changed. predictions tracked before this column was added are assumed to have been tracked with their model's current setting. */
+alter table predictions add column identifier_hashed boolean not null default false;
+
+update predictions
+	set identifier_hashed = true
+	where model_id in (
+		select id from models where privacy_settings like '%"store_inputs":false%'
+	);
@@ -1,3 +1,4 @@
+use modelfox_app_openapi::{Property, Schema, SchemaKind, Type};
 use modelfox_finite::NotFiniteError;
 use modelfox_id::Id;
 use std::{borrow::Cow, collections::HashMap};
@@ -20,6 +21,55 @@ pub struct PredictionMonitorEvent {
 	pub options: Option<PredictOptions>,
 	pub input: HashMap<String, serde_json::Value>,
 	pub output: PredictOutput,
+	/// This is the fraction of predictions the SDK was configured to log. It is absent for events logged by SDKs that do not sample, in which case every prediction was logged.
+	#[serde(default, alias = "sampleRate")]
+	pub sample_rate: Option<f32>,
+}
+
+impl PredictionMonitorEvent {
+	/// Flatten nested objects in the input into columns, using the separator and maximum depth from the event's options, so `{"user": {"age": 3}}` becomes the column `user.age` just as it did when the SDK made the prediction.
+	pub fn flatten_input(&mut self) {
+		if !self.input.values().any(|value| value.is_object()) {
+			return;
+		}
+		let separator = self
+			.options
+			.as_ref()
+			.and_then(|options| options.flatten_separator.as_deref())
+			.unwrap_or(".");
+		let max_depth = self
+			.options
+			.as_ref()
+			.and_then(|options| options.flatten_max_depth);
+		let mut input = HashMap::new();
+		for (key, value) in std::mem::take(&mut self.input) {
+			flatten_value_into(&mut input, key, value, 0, separator, max_depth);
+		}
+		self.input = input;
+	}
+}
+
+fn flatten_value_into(
+	output: &mut HashMap<String, serde_json::Value>,
+	column_name: String,
+	value: serde_json::Value,
+	depth: usize,
+	separator: &str,
+	max_depth: Option<usize>,
+) {
+	match value {
+		serde_json::Value::Object(values) => {
+			if max_depth.map(|max_depth| depth < max_depth).unwrap_or(true) {
+				for (key, value) in values {
+					let column_name = format!("{}{}{}", column_name, separator, key);
+					flatten_value_into(output, column_name, value, depth + 1, separator, max_depth);
+				}
+			}
+		}
+		value => {
+			output.insert(column_name, value);
+		}
+	}
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -27,6 +77,20 @@ pub struct PredictOptions {
 	pub threshold: f32,
 	#[serde(alias = "computeFeatureContributions")]
 	pub compute_feature_contributions: bool,
+	/// This is the separator the SDK used to flatten nested objects in the input. See [`PredictionMonitorEvent::flatten_input`].
+	#[serde(
+		default,
+		alias = "flattenSeparator",
+		skip_serializing_if = "Option::is_none"
+	)]
+	pub flatten_separator: Option<String>,
+	/// This is the number of levels of nested objects the SDK flattened in the input.
+	#[serde(
+		default,
+		alias = "flattenMaxDepth",
+		skip_serializing_if = "Option::is_none"
+	)]
+	pub flatten_max_depth: Option<usize>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -37,6 +101,8 @@ pub struct TrueValueMonitorEvent {
 	pub identifier: NumberOrString,
 	#[serde(alias = "trueValue")]
 	pub true_value: serde_json::Value,
+	#[serde(default, alias = "sampleRate")]
+	pub sample_rate: Option<f32>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -90,3 +156,204 @@ impl NumberOrString {
 		}
 	}
 }
+
+/// These are the schemas of the monitor events for the app's OpenAPI document.
+pub const SCHEMAS: &[Schema] = &[
+	Schema {
+		name: "MonitorEvent",
+		description: "A monitor event is a prediction or a true value, distinguished by its type.",
+		kind: SchemaKind::TaggedUnion {
+			tag: "type",
+			variants: &["PredictionMonitorEvent", "TrueValueMonitorEvent"],
+		},
+	},
+	Schema {
+		name: "PredictionMonitorEvent",
+		description: "",
+		kind: SchemaKind::Object(&[
+			Property {
+				name: "type",
+				ty: Type::Const("prediction"),
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "model_id",
+				ty: Type::String,
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "date",
+				ty: Type::DateTime,
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "identifier",
+				ty: Type::Ref("NumberOrString"),
+				required: true,
+				description:
+					"This identifies the prediction so true values can be matched with it.",
+			},
+			Property {
+				name: "options",
+				ty: Type::Ref("PredictOptions"),
+				required: false,
+				description: "",
+			},
+			Property {
+				name: "input",
+				ty: Type::Map(&Type::Any),
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "output",
+				ty: Type::Ref("PredictOutput"),
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "sample_rate",
+				ty: Type::Number,
+				required: false,
+				description: "This is the fraction of predictions the SDK was configured to log.",
+			},
+		]),
+	},
+	Schema {
+		name: "TrueValueMonitorEvent",
+		description: "",
+		kind: SchemaKind::Object(&[
+			Property {
+				name: "type",
+				ty: Type::Const("true_value"),
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "model_id",
+				ty: Type::String,
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "date",
+				ty: Type::DateTime,
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "identifier",
+				ty: Type::Ref("NumberOrString"),
+				required: true,
+				description: "This is the identifier of the prediction this is the true value for.",
+			},
+			Property {
+				name: "true_value",
+				ty: Type::Any,
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "sample_rate",
+				ty: Type::Number,
+				required: false,
+				description: "",
+			},
+		]),
+	},
+	Schema {
+		name: "PredictOptions",
+		description: "",
+		kind: SchemaKind::Object(&[
+			Property {
+				name: "threshold",
+				ty: Type::Number,
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "compute_feature_contributions",
+				ty: Type::Boolean,
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "flatten_separator",
+				ty: Type::String,
+				required: false,
+				description:
+					"This is the separator the SDK used to flatten nested objects in the input.",
+			},
+			Property {
+				name: "flatten_max_depth",
+				ty: Type::Integer,
+				required: false,
+				description:
+					"This is the number of levels of nested objects the SDK flattened in the input.",
+			},
+		]),
+	},
+	Schema {
+		name: "PredictOutput",
+		description: "",
+		kind: SchemaKind::Union(&[
+			Type::Ref("RegressionPredictOutput"),
+			Type::Ref("BinaryClassificationPredictOutput"),
+			Type::Ref("MulticlassClassificationPredictOutput"),
+		]),
+	},
+	Schema {
+		name: "RegressionPredictOutput",
+		description: "",
+		kind: SchemaKind::Object(&[Property {
+			name: "value",
+			ty: Type::Number,
+			required: true,
+			description: "",
+		}]),
+	},
+	Schema {
+		name: "BinaryClassificationPredictOutput",
+		description: "",
+		kind: SchemaKind::Object(&[
+			Property {
+				name: "class_name",
+				ty: Type::String,
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "probability",
+				ty: Type::Number,
+				required: true,
+				description: "",
+			},
+		]),
+	},
+	Schema {
+		name: "MulticlassClassificationPredictOutput",
+		description: "",
+		kind: SchemaKind::Object(&[
+			Property {
+				name: "class_name",
+				ty: Type::String,
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "probabilities",
+				ty: Type::Map(&Type::Number),
+				required: true,
+				description: "",
+			},
+		]),
+	},
+	Schema {
+		name: "NumberOrString",
+		description: "",
+		kind: SchemaKind::Union(&[Type::Number, Type::String]),
+	},
+];
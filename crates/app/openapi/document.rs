@@ -0,0 +1,184 @@
+use crate::{Operation, ParameterLocation, Schema, SchemaKind, Type};
+use serde_json::{json, Map, Value};
+
+/// Build the OpenAPI document describing `operations` and the `schemas` they refer to.
+pub fn document(version: &str, operations: &[Operation], schemas: &[Schema]) -> Value {
+	let mut paths = Map::new();
+	for operation in operations {
+		let path = paths
+			.entry(operation.path)
+			.or_insert_with(|| Value::Object(Map::new()));
+		path[operation.method.as_str().to_lowercase()] = operation_object(operation);
+	}
+	let schemas = schemas
+		.iter()
+		.map(|schema| (schema.name.to_owned(), schema_object(schema)))
+		.collect::<Map<_, _>>();
+	json!({
+		"openapi": "3.0.3",
+		"info": {
+			"title": "ModelFox",
+			"version": version,
+		},
+		"paths": paths,
+		"components": {
+			"schemas": schemas,
+			"securitySchemes": {
+				"bearer": {
+					"type": "http",
+					"scheme": "bearer",
+				},
+			},
+		},
+	})
+}
+
+fn operation_object(operation: &Operation) -> Value {
+	let mut response = json!({ "description": operation.response.description });
+	if let Some(body) = &operation.response.body {
+		response["content"] = json!({ "application/json": { "schema": type_schema(body) } });
+	}
+	let mut object = json!({
+		"operationId": operation.operation_id,
+		"summary": operation.summary,
+		"responses": {
+			operation.response.status.to_string(): response,
+		},
+	});
+	if !operation.parameters.is_empty() {
+		let parameters = operation
+			.parameters
+			.iter()
+			.map(|parameter| {
+				json!({
+					"name": parameter.name,
+					"in": match parameter.location {
+						ParameterLocation::Path => "path",
+						ParameterLocation::Query => "query",
+					},
+					"required": parameter.required,
+					"description": parameter.description,
+					"schema": type_schema(&parameter.ty),
+				})
+			})
+			.collect::<Vec<_>>();
+		object["parameters"] = Value::Array(parameters);
+	}
+	if let Some(request_body) = &operation.request_body {
+		object["requestBody"] = json!({
+			"required": true,
+			"content": { "application/json": { "schema": type_schema(request_body) } },
+		});
+	}
+	if operation.authenticated {
+		object["security"] = json!([{ "bearer": [] }]);
+	}
+	object
+}
+
+fn schema_object(schema: &Schema) -> Value {
+	let mut object = match &schema.kind {
+		SchemaKind::Object(properties) => {
+			let required = properties
+				.iter()
+				.filter(|property| property.required)
+				.map(|property| property.name)
+				.collect::<Vec<_>>();
+			let properties = properties
+				.iter()
+				.map(|property| {
+					let mut schema = type_schema(&property.ty);
+					if !property.description.is_empty() {
+						schema["description"] = Value::String(property.description.to_owned());
+					}
+					(property.name.to_owned(), schema)
+				})
+				.collect::<Map<_, _>>();
+			json!({
+				"type": "object",
+				"properties": properties,
+				"required": required,
+			})
+		}
+		SchemaKind::TaggedUnion { tag, variants } => json!({
+			"oneOf": variants.iter().map(|variant| type_schema(&Type::Ref(*variant))).collect::<Vec<_>>(),
+			"discriminator": { "propertyName": tag },
+		}),
+		SchemaKind::Union(types) => json!({
+			"oneOf": types.iter().map(type_schema).collect::<Vec<_>>(),
+		}),
+	};
+	if !schema.description.is_empty() {
+		object["description"] = Value::String(schema.description.to_owned());
+	}
+	object
+}
+
+fn type_schema(ty: &Type) -> Value {
+	match ty {
+		Type::Boolean => json!({ "type": "boolean" }),
+		Type::Integer => json!({ "type": "integer" }),
+		Type::Number => json!({ "type": "number" }),
+		Type::String => json!({ "type": "string" }),
+		Type::DateTime => json!({ "type": "string", "format": "date-time" }),
+		Type::Any => json!({}),
+		Type::Const(value) => json!({ "type": "string", "enum": [value] }),
+		Type::Ref(name) => json!({ "$ref": format!("#/components/schemas/{}", name) }),
+		Type::Array(ty) => json!({ "type": "array", "items": type_schema(ty) }),
+		Type::Map(ty) => json!({ "type": "object", "additionalProperties": type_schema(ty) }),
+		Type::Either(a, b) => json!({ "oneOf": [type_schema(a), type_schema(b)] }),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{Method, Parameter, Property, Response};
+
+	#[test]
+	fn test_document() {
+		let operations = [Operation {
+			method: Method::Delete,
+			path: "/api/models/{model_id}/predictions",
+			operation_id: "delete_predictions",
+			summary: "Delete predictions.",
+			parameters: &[Parameter {
+				name: "model_id",
+				location: ParameterLocation::Path,
+				required: true,
+				ty: Type::String,
+				description: "",
+			}],
+			request_body: None,
+			response: Response {
+				status: 200,
+				description: "The predictions were deleted.",
+				body: Some(Type::Ref("DeletePredictionsResponse")),
+			},
+			authenticated: true,
+		}];
+		let schemas = [Schema {
+			name: "DeletePredictionsResponse",
+			description: "",
+			kind: SchemaKind::Object(&[Property {
+				name: "prediction_count",
+				ty: Type::Integer,
+				required: true,
+				description: "",
+			}]),
+		}];
+		let document = document("0.0.0", &operations, &schemas);
+		let operation = &document["paths"]["/api/models/{model_id}/predictions"]["delete"];
+		assert_eq!(operation["operationId"], "delete_predictions");
+		assert_eq!(operation["parameters"][0]["in"], "path");
+		assert_eq!(
+			operation["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+			"#/components/schemas/DeletePredictionsResponse"
+		);
+		assert_eq!(operation["security"][0]["bearer"], json!([]));
+		assert_eq!(
+			document["components"]["schemas"]["DeletePredictionsResponse"]["required"],
+			json!(["prediction_count"])
+		);
+	}
+}
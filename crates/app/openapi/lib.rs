@@ -0,0 +1,155 @@
+/*!
+This crate describes the app's HTTP API so it can be published as an [OpenAPI](https://spec.openapis.org/oas/v3.0.3) document and used to generate clients. Each API route crate declares the operations it serves in an `OPERATIONS` constant, and crates that define request and response types declare their schemas in a `SCHEMAS` constant, so the document served at `/api/openapi.json` and the clients checked into `languages` are derived from the same definitions as the routes.
+*/
+
+pub use self::document::document;
+
+mod document;
+pub mod python;
+pub mod typescript;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+	Get,
+	Post,
+	Put,
+	Delete,
+}
+
+impl Method {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Method::Get => "GET",
+			Method::Post => "POST",
+			Method::Put => "PUT",
+			Method::Delete => "DELETE",
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Operation {
+	pub method: Method,
+	/// This is the path of the operation, with path parameters in braces, such as `/api/models/{model_id}/predictions`.
+	pub path: &'static str,
+	/// This is the snake case name of the operation, which generated clients use as the name of its method.
+	pub operation_id: &'static str,
+	pub summary: &'static str,
+	pub parameters: &'static [Parameter],
+	pub request_body: Option<Type>,
+	pub response: Response,
+	/// Operations that require authentication accept a token in the `Authorization` header as `Bearer <token>` when the app has auth enabled.
+	pub authenticated: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Parameter {
+	pub name: &'static str,
+	pub location: ParameterLocation,
+	pub required: bool,
+	pub ty: Type,
+	pub description: &'static str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParameterLocation {
+	Path,
+	Query,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Response {
+	pub status: u16,
+	pub description: &'static str,
+	/// This is the type of the json response body, or `None` if the response has no body.
+	pub body: Option<Type>,
+}
+
+/// A `Schema` is a named type that operations and other schemas refer to with [`Type::Ref`].
+#[derive(Clone, Copy, Debug)]
+pub struct Schema {
+	pub name: &'static str,
+	pub description: &'static str,
+	pub kind: SchemaKind,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum SchemaKind {
+	Object(&'static [Property]),
+	/// This is a union of the named object schemas, each of which has a [`Type::Const`] property with the name `tag` that distinguishes it from the others.
+	TaggedUnion {
+		tag: &'static str,
+		variants: &'static [&'static str],
+	},
+	/// This is a union of types that are distinguished by their shape.
+	Union(&'static [Type]),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Property {
+	pub name: &'static str,
+	pub ty: Type,
+	pub required: bool,
+	pub description: &'static str,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Type {
+	Boolean,
+	Integer,
+	Number,
+	String,
+	/// This is a string with an RFC 3339 date and time.
+	DateTime,
+	/// This is any json value.
+	Any,
+	/// This is a string that must have this value.
+	Const(&'static str),
+	Ref(&'static str),
+	Array(&'static Type),
+	/// This is an object with arbitrary keys whose values have this type.
+	Map(&'static Type),
+	/// This is a value of either of the types.
+	Either(&'static Type, &'static Type),
+}
+
+/// Convert a snake case name, like an operation id or path parameter, to camel case.
+pub(crate) fn camel_case(name: &str) -> String {
+	let mut output = String::new();
+	let mut uppercase_next = false;
+	for c in name.chars() {
+		if c == '_' {
+			uppercase_next = true;
+		} else if uppercase_next {
+			output.extend(c.to_uppercase());
+			uppercase_next = false;
+		} else {
+			output.push(c);
+		}
+	}
+	output
+}
+
+/// A `PathSegment` is a part of an operation's path, which generated clients either copy or fill in with the value of a path parameter.
+pub(crate) enum PathSegment<'a> {
+	Literal(&'a str),
+	Parameter(&'a str),
+}
+
+/// Split a path like `/api/models/{model_id}/predictions` into the literal parts and the names of the path parameters.
+pub(crate) fn path_segments(path: &str) -> Vec<PathSegment<'_>> {
+	let mut segments = Vec::new();
+	let mut rest = path;
+	while let Some(start) = rest.find('{') {
+		let end = start + rest[start..].find('}').unwrap();
+		if start > 0 {
+			segments.push(PathSegment::Literal(&rest[..start]));
+		}
+		segments.push(PathSegment::Parameter(&rest[start + 1..end]));
+		rest = &rest[end + 1..];
+	}
+	if !rest.is_empty() {
+		segments.push(PathSegment::Literal(rest));
+	}
+	segments
+}
@@ -0,0 +1,218 @@
+/*!
+This module generates a Python client with a `TypedDict` for each object schema and a method on `Client` for each operation. It only uses the standard library.
+*/
+
+use crate::{path_segments, Operation, ParameterLocation, PathSegment, Schema, SchemaKind, Type};
+use std::fmt::Write;
+
+pub fn generate(operations: &[Operation], schemas: &[Schema]) -> String {
+	let mut code = String::new();
+	writeln!(code, "# This file is generated by `scripts/app/openapi` from the app's OpenAPI document. Do not edit it by hand.").unwrap();
+	writeln!(code).unwrap();
+	writeln!(code, "import json").unwrap();
+	writeln!(code, "import urllib.parse").unwrap();
+	writeln!(code, "import urllib.request").unwrap();
+	writeln!(
+		code,
+		"from typing import Any, Dict, List, Literal, Optional, TypedDict, Union"
+	)
+	.unwrap();
+	for schema in schemas {
+		writeln!(code).unwrap();
+		writeln!(code).unwrap();
+		write_schema(&mut code, schema);
+	}
+	writeln!(code).unwrap();
+	writeln!(code).unwrap();
+	writeln!(code, "class Client:").unwrap();
+	writeln!(code, "    \"\"\"A `Client` calls the app's HTTP API at `url`, authenticating with `token` if the app has auth enabled.\"\"\"").unwrap();
+	writeln!(code).unwrap();
+	writeln!(
+		code,
+		"    def __init__(self, url: str, token: Optional[str] = None):"
+	)
+	.unwrap();
+	writeln!(code, "        self.url = url").unwrap();
+	writeln!(code, "        self.token = token").unwrap();
+	for operation in operations {
+		writeln!(code).unwrap();
+		write_operation(&mut code, operation);
+	}
+	writeln!(code).unwrap();
+	code.push_str(REQUEST);
+	code
+}
+
+fn write_schema(code: &mut String, schema: &Schema) {
+	match &schema.kind {
+		SchemaKind::Object(properties) => {
+			writeln!(code, "class {}(TypedDict):", schema.name).unwrap();
+			if !schema.description.is_empty() {
+				writeln!(code, "    \"\"\"{}\"\"\"", schema.description).unwrap();
+				writeln!(code).unwrap();
+			}
+			for property in properties.iter() {
+				if !property.description.is_empty() {
+					writeln!(code, "    # {}", property.description).unwrap();
+				}
+				let ty = python_type(&property.ty);
+				let ty = if property.required {
+					ty
+				} else {
+					format!("Optional[{}]", ty)
+				};
+				writeln!(code, "    {}: {}", property.name, ty).unwrap();
+			}
+			if properties.is_empty() {
+				writeln!(code, "    pass").unwrap();
+			}
+		}
+		SchemaKind::TaggedUnion { variants, .. } => {
+			let variants = variants
+				.iter()
+				.map(|variant| format!("\"{}\"", variant))
+				.collect::<Vec<_>>();
+			writeln!(code, "{} = Union[{}]", schema.name, variants.join(", ")).unwrap();
+		}
+		SchemaKind::Union(types) => {
+			let types = types.iter().map(python_type).collect::<Vec<_>>();
+			writeln!(code, "{} = Union[{}]", schema.name, types.join(", ")).unwrap();
+		}
+	}
+}
+
+fn write_operation(code: &mut String, operation: &Operation) {
+	// Python requires arguments with defaults to come after the others, so optional query parameters come last.
+	let mut arguments = vec!["self".to_owned()];
+	for parameter in operation.parameters.iter() {
+		if parameter.location == ParameterLocation::Path {
+			arguments.push(format!(
+				"{}: {}",
+				parameter.name,
+				python_type(&parameter.ty)
+			));
+		}
+	}
+	if let Some(request_body) = &operation.request_body {
+		arguments.push(format!("body: {}", python_type(request_body)));
+	}
+	let query_parameters = operation
+		.parameters
+		.iter()
+		.filter(|parameter| parameter.location == ParameterLocation::Query)
+		.collect::<Vec<_>>();
+	for parameter in query_parameters.iter() {
+		if parameter.required {
+			arguments.push(format!(
+				"{}: {}",
+				parameter.name,
+				python_type(&parameter.ty)
+			));
+		}
+	}
+	for parameter in query_parameters.iter() {
+		if !parameter.required {
+			arguments.push(format!(
+				"{}: Optional[{}] = None",
+				parameter.name,
+				python_type(&parameter.ty)
+			));
+		}
+	}
+	let path = path_segments(operation.path)
+		.into_iter()
+		.map(|segment| match segment {
+			PathSegment::Literal(literal) => format!("\"{}\"", literal),
+			PathSegment::Parameter(name) => {
+				format!("urllib.parse.quote({}, safe=\"\")", name)
+			}
+		})
+		.collect::<Vec<_>>()
+		.join(" + ");
+	let query = if query_parameters.is_empty() {
+		"None".to_owned()
+	} else {
+		let entries = query_parameters
+			.iter()
+			.map(|parameter| format!("\"{}\": {}", parameter.name, parameter.name))
+			.collect::<Vec<_>>();
+		format!("{{{}}}", entries.join(", "))
+	};
+	let body = if operation.request_body.is_some() {
+		"body"
+	} else {
+		"None"
+	};
+	let response_type = operation
+		.response
+		.body
+		.as_ref()
+		.map(python_type)
+		.unwrap_or_else(|| "None".to_owned());
+	writeln!(
+		code,
+		"    def {}({}) -> {}:",
+		operation.operation_id,
+		arguments.join(", "),
+		response_type
+	)
+	.unwrap();
+	writeln!(code, "        \"\"\"{}\"\"\"", operation.summary).unwrap();
+	let request = format!(
+		"self._request(\"{}\", {}, {}, {})",
+		operation.method.as_str(),
+		path,
+		query,
+		body
+	);
+	if operation.response.body.is_some() {
+		writeln!(code, "        return {}", request).unwrap();
+	} else {
+		writeln!(code, "        {}", request).unwrap();
+	}
+}
+
+/// Schemas are referred to by quoted names, so they can refer to schemas defined after them.
+fn python_type(ty: &Type) -> String {
+	match ty {
+		Type::Boolean => "bool".to_owned(),
+		Type::Integer => "int".to_owned(),
+		Type::Number => "float".to_owned(),
+		Type::String | Type::DateTime => "str".to_owned(),
+		Type::Any => "Any".to_owned(),
+		Type::Const(value) => format!("Literal[\"{}\"]", value),
+		Type::Ref(name) => format!("\"{}\"", name),
+		Type::Array(ty) => format!("List[{}]", python_type(ty)),
+		Type::Map(ty) => format!("Dict[str, {}]", python_type(ty)),
+		Type::Either(a, b) => format!("Union[{}, {}]", python_type(a), python_type(b)),
+	}
+}
+
+/// Every generated method calls this method to make its request.
+const REQUEST: &str = r#"    def _request(
+        self,
+        method: str,
+        path: str,
+        query: Optional[Dict[str, Any]],
+        body: Any,
+    ) -> Any:
+        url = self.url + path
+        if query is not None:
+            query = {
+                key: json.dumps(value) if isinstance(value, bool) else value
+                for key, value in query.items()
+                if value is not None
+            }
+            url += "?" + urllib.parse.urlencode(query)
+        headers = {}
+        data = None
+        if body is not None:
+            headers["Content-Type"] = "application/json"
+            data = json.dumps(body).encode()
+        if self.token is not None:
+            headers["Authorization"] = "Bearer " + self.token
+        request = urllib.request.Request(url, data=data, headers=headers, method=method)
+        with urllib.request.urlopen(request) as response:
+            text = response.read().decode()
+        return json.loads(text) if text else None
+"#;
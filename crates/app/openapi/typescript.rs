@@ -0,0 +1,203 @@
+/*!
+This module generates a TypeScript client with a type for each schema and a method on `Client` for each operation.
+*/
+
+use crate::{
+	camel_case, path_segments, Operation, ParameterLocation, PathSegment, Schema, SchemaKind, Type,
+};
+use std::fmt::Write;
+
+pub fn generate(operations: &[Operation], schemas: &[Schema]) -> String {
+	let mut code = String::new();
+	writeln!(code, "// This file is generated by `scripts/app/openapi` from the app's OpenAPI document. Do not edit it by hand.").unwrap();
+	for schema in schemas {
+		writeln!(code).unwrap();
+		write_schema(&mut code, schema);
+	}
+	writeln!(code).unwrap();
+	writeln!(code, "/** A `Client` calls the app's HTTP API at `url`, authenticating with `token` if the app has auth enabled. */").unwrap();
+	writeln!(code, "export class Client {{").unwrap();
+	writeln!(code, "\tprivate url: string").unwrap();
+	writeln!(code, "\tprivate token: string | undefined").unwrap();
+	writeln!(code).unwrap();
+	writeln!(code, "\tconstructor(url: string, token?: string) {{").unwrap();
+	writeln!(code, "\t\tthis.url = url").unwrap();
+	writeln!(code, "\t\tthis.token = token").unwrap();
+	writeln!(code, "\t}}").unwrap();
+	for operation in operations {
+		writeln!(code).unwrap();
+		write_operation(&mut code, operation);
+	}
+	writeln!(code).unwrap();
+	code.push_str(REQUEST);
+	writeln!(code, "}}").unwrap();
+	code
+}
+
+fn write_schema(code: &mut String, schema: &Schema) {
+	if !schema.description.is_empty() {
+		writeln!(code, "/** {} */", schema.description).unwrap();
+	}
+	match &schema.kind {
+		SchemaKind::Object(properties) => {
+			writeln!(code, "export type {} = {{", schema.name).unwrap();
+			for property in properties.iter() {
+				if !property.description.is_empty() {
+					writeln!(code, "\t/** {} */", property.description).unwrap();
+				}
+				let optional = if property.required { "" } else { "?" };
+				writeln!(
+					code,
+					"\t{}{}: {}",
+					property.name,
+					optional,
+					typescript_type(&property.ty)
+				)
+				.unwrap();
+			}
+			writeln!(code, "}}").unwrap();
+		}
+		SchemaKind::TaggedUnion { variants, .. } => {
+			writeln!(
+				code,
+				"export type {} = {}",
+				schema.name,
+				variants.join(" | ")
+			)
+			.unwrap();
+		}
+		SchemaKind::Union(types) => {
+			let types = types.iter().map(typescript_type).collect::<Vec<_>>();
+			writeln!(code, "export type {} = {}", schema.name, types.join(" | ")).unwrap();
+		}
+	}
+}
+
+fn write_operation(code: &mut String, operation: &Operation) {
+	let mut arguments = Vec::new();
+	for parameter in operation.parameters.iter() {
+		if parameter.location == ParameterLocation::Path {
+			arguments.push(format!(
+				"{}: {}",
+				camel_case(parameter.name),
+				typescript_type(&parameter.ty)
+			));
+		}
+	}
+	let query_parameters = operation
+		.parameters
+		.iter()
+		.filter(|parameter| parameter.location == ParameterLocation::Query)
+		.map(|parameter| {
+			let optional = if parameter.required { "" } else { "?" };
+			format!(
+				"{}{}: {}",
+				parameter.name,
+				optional,
+				typescript_type(&parameter.ty)
+			)
+		})
+		.collect::<Vec<_>>();
+	if !query_parameters.is_empty() {
+		arguments.push(format!("query: {{ {} }}", query_parameters.join("; ")));
+	}
+	if let Some(request_body) = &operation.request_body {
+		arguments.push(format!("body: {}", typescript_type(request_body)));
+	}
+	let path = path_segments(operation.path)
+		.into_iter()
+		.map(|segment| match segment {
+			PathSegment::Literal(literal) => literal.to_owned(),
+			PathSegment::Parameter(name) => {
+				format!("${{encodeURIComponent({})}}", camel_case(name))
+			}
+		})
+		.collect::<String>();
+	let request = format!(
+		"this.request(\"{}\", `{}`, {}, {})",
+		operation.method.as_str(),
+		path,
+		if query_parameters.is_empty() {
+			"undefined"
+		} else {
+			"query"
+		},
+		if operation.request_body.is_some() {
+			"body"
+		} else {
+			"undefined"
+		},
+	);
+	let response_type = operation
+		.response
+		.body
+		.as_ref()
+		.map(typescript_type)
+		.unwrap_or_else(|| "void".to_owned());
+	writeln!(code, "\t/** {} */", operation.summary).unwrap();
+	writeln!(
+		code,
+		"\tasync {}({}): Promise<{}> {{",
+		camel_case(operation.operation_id),
+		arguments.join(", "),
+		response_type
+	)
+	.unwrap();
+	if operation.response.body.is_some() {
+		writeln!(code, "\t\treturn (await {}) as {}", request, response_type).unwrap();
+	} else {
+		writeln!(code, "\t\tawait {}", request).unwrap();
+	}
+	writeln!(code, "\t}}").unwrap();
+}
+
+fn typescript_type(ty: &Type) -> String {
+	match ty {
+		Type::Boolean => "boolean".to_owned(),
+		Type::Integer | Type::Number => "number".to_owned(),
+		Type::String | Type::DateTime => "string".to_owned(),
+		Type::Any => "unknown".to_owned(),
+		Type::Const(value) => format!("\"{}\"", value),
+		Type::Ref(name) => (*name).to_owned(),
+		Type::Array(ty) => format!("Array<{}>", typescript_type(ty)),
+		Type::Map(ty) => format!("Record<string, {}>", typescript_type(ty)),
+		Type::Either(a, b) => format!("{} | {}", typescript_type(a), typescript_type(b)),
+	}
+}
+
+/// Every generated method calls this method to make its request.
+const REQUEST: &str = r#"	private async request(
+		method: string,
+		path: string,
+		query: Record<string, string | number | boolean | undefined> | undefined,
+		body: unknown,
+	): Promise<unknown> {
+		let url = this.url + path
+		if (query !== undefined) {
+			let searchParams = new URLSearchParams()
+			for (let [key, value] of Object.entries(query)) {
+				if (value !== undefined) {
+					searchParams.set(key, value.toString())
+				}
+			}
+			url += "?" + searchParams.toString()
+		}
+		let headers: Record<string, string> = {}
+		if (body !== undefined) {
+			headers["Content-Type"] = "application/json"
+		}
+		if (this.token !== undefined) {
+			headers["Authorization"] = `Bearer ${this.token}`
+		}
+		let response = await fetch(url, {
+			body: body !== undefined ? JSON.stringify(body) : undefined,
+			headers,
+			method,
+		})
+		if (!response.ok) {
+			throw Error(await response.text())
+		}
+		let text = await response.text()
+		return text.length > 0 ? JSON.parse(text) : undefined
+	}
+"#;
@@ -1,3 +1,4 @@
+use crate::{bootstrap::proportion_confidence_interval, ProductionMetricsConfidenceIntervals};
 use modelfox_app_monitor_event::NumberOrString;
 use num::ToPrimitive;
 
@@ -90,6 +91,14 @@ impl BinaryClassificationProductionPredictionMetrics {
 		self.confusion_matrix.true_positives += other.confusion_matrix.true_positives;
 	}
 
+	pub fn confidence_intervals(&self) -> ProductionMetricsConfidenceIntervals {
+		let n_correct = self.confusion_matrix.true_positives + self.confusion_matrix.true_negatives;
+		ProductionMetricsConfidenceIntervals {
+			accuracy: proportion_confidence_interval(n_correct, self.confusion_matrix.n_examples()),
+			..ProductionMetricsConfidenceIntervals::default()
+		}
+	}
+
 	pub fn finalize(self) -> Option<BinaryClassificationProductionPredictionMetricsOutput> {
 		let n_examples = self.confusion_matrix.n_examples();
 		let true_positives = self.confusion_matrix.true_positives;
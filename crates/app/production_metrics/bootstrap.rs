@@ -0,0 +1,188 @@
+/*!
+This module computes bootstrap confidence intervals for production metrics. Metrics computed from the few true values logged in a short interval are noisy, so the app shows how far each one could plausibly be from its true value, and monitors can wait until the whole interval is past their threshold before alerting.
+
+Metrics that are means of a value per true value, like mean squared error, keep a [`BootstrapSample`] of those values alongside their sums, because the production metrics are rolled up hourly and merged, so the true values themselves are not available when the metrics are finalized. Accuracy only needs the number of correct predictions, so its interval is computed from the counts the metrics already keep.
+*/
+
+use num::ToPrimitive;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256Plus;
+
+/// This is the largest number of values a [`BootstrapSample`] keeps.
+pub const BOOTSTRAP_SAMPLE_SIZE: usize = 500;
+
+/// This is the number of times the values are resampled to compute a confidence interval.
+const BOOTSTRAP_RESAMPLE_COUNT: usize = 500;
+
+/// This is the fraction of resampled metrics that fall within a confidence interval.
+pub const CONFIDENCE_LEVEL: f64 = 0.95;
+
+/// The resampling is seeded so the same metrics always have the same confidence interval, and charts do not change each time they are loaded.
+const BOOTSTRAP_SEED: u64 = 0;
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConfidenceInterval {
+	pub lower: f32,
+	pub upper: f32,
+}
+
+impl ConfidenceInterval {
+	pub fn contains(&self, value: f32) -> bool {
+		self.lower <= value && value <= self.upper
+	}
+
+	/// Apply `f` to both bounds. `f` must be increasing, like `f32::sqrt` for non-negative values.
+	pub fn map(self, f: impl Fn(f32) -> f32) -> ConfidenceInterval {
+		ConfidenceInterval {
+			lower: f(self.lower),
+			upper: f(self.upper),
+		}
+	}
+}
+
+/// A uniform random sample of at most [`BOOTSTRAP_SAMPLE_SIZE`] of the values it was updated with. Samples can be merged, and the result is a sample of the values of both.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct BootstrapSample {
+	/// This is the number of values the sample was updated with, including those it no longer holds.
+	count: u64,
+	values: Vec<f32>,
+}
+
+impl BootstrapSample {
+	pub fn count(&self) -> u64 {
+		self.count
+	}
+
+	pub fn values(&self) -> &[f32] {
+		&self.values
+	}
+
+	pub fn update(&mut self, value: f32) {
+		self.count += 1;
+		if self.values.len() < BOOTSTRAP_SAMPLE_SIZE {
+			self.values.push(value);
+		} else {
+			// Replace a random value with probability `BOOTSTRAP_SAMPLE_SIZE / count`, so every value seen so far is equally likely to be in the sample.
+			let index = Xoshiro256Plus::seed_from_u64(self.count)
+				.gen_range(0..self.count)
+				.to_usize()
+				.unwrap();
+			if let Some(sample_value) = self.values.get_mut(index) {
+				*sample_value = value;
+			}
+		}
+	}
+
+	pub fn merge(&mut self, other: BootstrapSample) {
+		let count = self.count + other.count;
+		if self.values.len() + other.values.len() <= BOOTSTRAP_SAMPLE_SIZE {
+			self.values.extend(other.values);
+		} else {
+			// Keep values from each sample in proportion to the number of values it represents.
+			let mut rng = Xoshiro256Plus::seed_from_u64(count);
+			let mut values = std::mem::take(&mut self.values);
+			let mut other_values = other.values;
+			values.shuffle(&mut rng);
+			other_values.shuffle(&mut rng);
+			let n_values = (BOOTSTRAP_SAMPLE_SIZE.to_f64().unwrap() * self.count.to_f64().unwrap()
+				/ count.to_f64().unwrap())
+			.round()
+			.to_usize()
+			.unwrap()
+			.min(values.len())
+			.max(BOOTSTRAP_SAMPLE_SIZE.saturating_sub(other_values.len()));
+			values.truncate(n_values);
+			values.extend(
+				other_values
+					.into_iter()
+					.take(BOOTSTRAP_SAMPLE_SIZE - n_values),
+			);
+			self.values = values;
+		}
+		self.count = count;
+	}
+}
+
+/// Compute the confidence interval of the mean of `count` values around `estimate`, from a random sample of those values. This returns `None` if there are fewer than two values to resample.
+pub fn mean_confidence_interval(
+	values: &[f32],
+	count: u64,
+	estimate: f32,
+) -> Option<ConfidenceInterval> {
+	let n_values = values.len();
+	if n_values < 2 {
+		return None;
+	}
+	let n_values_f64 = n_values.to_f64().unwrap();
+	let sample_mean = values.iter().map(|value| *value as f64).sum::<f64>() / n_values_f64;
+	// The resampled means vary as much as means of `n_values` values do. If the sample holds fewer values than it represents, scale their deviations down to those of means of `count` values.
+	let scale = (n_values_f64 / count.to_f64().unwrap().max(n_values_f64)).sqrt();
+	let mut rng = Xoshiro256Plus::seed_from_u64(BOOTSTRAP_SEED);
+	let mut deviations = (0..BOOTSTRAP_RESAMPLE_COUNT)
+		.map(|_| {
+			let sum = (0..n_values)
+				.map(|_| values[rng.gen_range(0..n_values)] as f64)
+				.sum::<f64>();
+			(sum / n_values_f64 - sample_mean) * scale
+		})
+		.collect::<Vec<_>>();
+	deviations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+	let quantile = |q: f64| {
+		let index = (q * (BOOTSTRAP_RESAMPLE_COUNT - 1).to_f64().unwrap())
+			.round()
+			.to_usize()
+			.unwrap();
+		deviations[index]
+	};
+	let alpha = (1.0 - CONFIDENCE_LEVEL) / 2.0;
+	let estimate = estimate as f64;
+	Some(ConfidenceInterval {
+		lower: (estimate + quantile(alpha)).to_f32().unwrap(),
+		upper: (estimate + quantile(1.0 - alpha)).to_f32().unwrap(),
+	})
+}
+
+/// Compute the confidence interval of the fraction of `count` examples that are `successes`, for example the accuracy of a classifier.
+pub fn proportion_confidence_interval(successes: u64, count: u64) -> Option<ConfidenceInterval> {
+	if count == 0 {
+		return None;
+	}
+	let proportion = successes.to_f64().unwrap() / count.to_f64().unwrap();
+	// Resampling examples only depends on whether each one is a success, so a sample with the same proportion of successes stands in for the examples.
+	let n_values = count.min(BOOTSTRAP_SAMPLE_SIZE.to_u64().unwrap());
+	let n_successes = (proportion * n_values.to_f64().unwrap())
+		.round()
+		.to_u64()
+		.unwrap();
+	let values = (0..n_values)
+		.map(|index| if index < n_successes { 1.0 } else { 0.0 })
+		.collect::<Vec<f32>>();
+	mean_confidence_interval(&values, count, proportion.to_f32().unwrap())
+		.map(|interval| interval.map(|bound| bound.clamp(0.0, 1.0)))
+}
+
+#[test]
+fn test_proportion_confidence_interval() {
+	let small = proportion_confidence_interval(8, 10).unwrap();
+	let large = proportion_confidence_interval(8000, 10000).unwrap();
+	assert!(small.contains(0.8));
+	assert!(large.contains(0.8));
+	assert!(large.upper - large.lower < small.upper - small.lower);
+	assert!(small.lower >= 0.0 && small.upper <= 1.0);
+	assert_eq!(proportion_confidence_interval(0, 0), None);
+}
+
+#[test]
+fn test_bootstrap_sample_merge() {
+	let mut a = BootstrapSample::default();
+	let mut b = BootstrapSample::default();
+	for value in 0..BOOTSTRAP_SAMPLE_SIZE {
+		a.update(value.to_f32().unwrap());
+		b.update(value.to_f32().unwrap());
+	}
+	a.update(0.0);
+	assert_eq!(a.values().len(), BOOTSTRAP_SAMPLE_SIZE);
+	a.merge(b);
+	assert_eq!(a.count(), 2 * BOOTSTRAP_SAMPLE_SIZE.to_u64().unwrap() + 1);
+	assert_eq!(a.values().len(), BOOTSTRAP_SAMPLE_SIZE);
+}
@@ -1,27 +1,30 @@
-use std::borrow::BorrowMut;
-
 pub use self::{
 	binary_classification_production_metrics::{
 		BinaryClassificationProductionPredictionMetrics,
 		BinaryClassificationProductionPredictionMetricsOutput,
 	},
+	bootstrap::{ConfidenceInterval, CONFIDENCE_LEVEL},
 	multiclass_classification_production_metrics::{
 		MulticlassClassificationProductionPredictionMetrics,
 		MulticlassClassificationProductionPredictionMetricsOutput,
 	},
 	regression_production_metrics::{
 		RegressionProductionPredictionMetrics, RegressionProductionPredictionMetricsOutput,
+		RegressionProductionTolerance,
 	},
 };
 use anyhow::Result;
 use chrono::prelude::*;
 use chrono_tz::Tz;
-use modelfox_app_date_window::{DateWindow, DateWindowInterval};
+use modelfox_app_date_window::{
+	get_date_window_bounds, get_date_window_intervals, DateWindow, DateWindowInterval,
+	DateWindowRange,
+};
 use modelfox_app_monitor_event::NumberOrString;
-use num::ToPrimitive;
-use sqlx::prelude::*;
+use modelfox_app_production_stats::rollup::{get_rollup_rows, RollupTable};
 
 mod binary_classification_production_metrics;
+mod bootstrap;
 mod multiclass_classification_production_metrics;
 mod regression_production_metrics;
 
@@ -50,6 +53,19 @@ pub struct ProductionMetricsOutput {
 	pub end_date: DateTime<Utc>,
 	pub true_values_count: u64,
 	pub prediction_metrics: Option<ProductionPredictionMetricsOutput>,
+	#[serde(default)]
+	pub confidence_intervals: ProductionMetricsConfidenceIntervals,
+}
+
+/// These are the bootstrap confidence intervals of the production metrics at [`CONFIDENCE_LEVEL`]. Each is `None` if the metric does not apply to the model or too few true values were logged to compute it.
+#[derive(Clone, Copy, Debug, Default, serde::Deserialize)]
+pub struct ProductionMetricsConfidenceIntervals {
+	pub accuracy: Option<ConfidenceInterval>,
+	pub mse: Option<ConfidenceInterval>,
+	pub rmse: Option<ConfidenceInterval>,
+	pub mae: Option<ConfidenceInterval>,
+	pub mean_residual: Option<ConfidenceInterval>,
+	pub tolerance_accuracy: Option<ConfidenceInterval>,
 }
 
 #[derive(serde::Deserialize)]
@@ -87,11 +103,13 @@ impl ProductionMetrics {
 	}
 
 	pub fn finalize(self) -> ProductionMetricsOutput {
+		let confidence_intervals = self.prediction_metrics.confidence_intervals();
 		ProductionMetricsOutput {
 			start_date: self.start_date,
 			end_date: self.end_date,
 			true_values_count: self.true_values_count,
 			prediction_metrics: self.prediction_metrics.finalize(),
+			confidence_intervals,
 		}
 	}
 }
@@ -99,8 +117,11 @@ impl ProductionMetrics {
 impl ProductionPredictionMetrics {
 	pub fn new(model: modelfox_model::ModelReader) -> ProductionPredictionMetrics {
 		match model.inner() {
-			modelfox_model::ModelInnerReader::Regressor(_) => {
-				ProductionPredictionMetrics::Regression(RegressionProductionPredictionMetrics::new())
+			modelfox_model::ModelInnerReader::Regressor(model) => {
+				let model = model.read();
+				ProductionPredictionMetrics::Regression(RegressionProductionPredictionMetrics::new(
+					model.tolerance().map(Into::into),
+				))
 			}
 			modelfox_model::ModelInnerReader::BinaryClassifier(model) => {
 				let model = model.read();
@@ -150,6 +171,14 @@ impl ProductionPredictionMetrics {
 		}
 	}
 
+	pub fn confidence_intervals(&self) -> ProductionMetricsConfidenceIntervals {
+		match self {
+			ProductionPredictionMetrics::Regression(s) => s.confidence_intervals(),
+			ProductionPredictionMetrics::BinaryClassification(s) => s.confidence_intervals(),
+			ProductionPredictionMetrics::MulticlassClassification(s) => s.confidence_intervals(),
+		}
+	}
+
 	pub fn finalize(self) -> Option<ProductionPredictionMetricsOutput> {
 		match self {
 			ProductionPredictionMetrics::Regression(s) => s
@@ -165,11 +194,18 @@ impl ProductionPredictionMetrics {
 	}
 }
 
+/// These are the numbers of intervals a rolling metric can be computed over.
+pub const ROLLING_WINDOWS: [usize; 4] = [1, 3, 7, 14];
+
+pub const DEFAULT_ROLLING_WINDOW: usize = 3;
+
 pub struct GetProductionMetricsOutput {
 	pub date_window: DateWindow,
 	pub date_window_interval: DateWindowInterval,
 	pub overall: ProductionMetricsOutput,
 	pub intervals: Vec<ProductionMetricsOutput>,
+	/// These are the metrics of each interval merged with those of the `rolling_window - 1` intervals before it.
+	pub rolling_intervals: Vec<ProductionMetricsOutput>,
 }
 
 pub async fn get_production_metrics(
@@ -177,107 +213,65 @@ pub async fn get_production_metrics(
 	model: modelfox_model::ModelReader<'_>,
 	date_window: DateWindow,
 	date_window_interval: DateWindowInterval,
+	range: Option<DateWindowRange>,
+	rolling_window: usize,
 	timezone: Tz,
 ) -> Result<GetProductionMetricsOutput> {
-	// Compute the start date given the date window.
-	// * For today, use the start of this day in this timezone.
-	// * For this month, use the start of this month in this timezone.
-	// * For this year, use the start of this year in this timezone.
 	let now: DateTime<Tz> = Utc::now().with_timezone(&timezone);
-	let start_date = match date_window {
-		DateWindow::Today => timezone
-			.ymd(now.year(), now.month(), now.day())
-			.and_hms(0, 0, 0),
-		DateWindow::ThisMonth => timezone.ymd(now.year(), now.month(), 1).and_hms(0, 0, 0),
-		DateWindow::ThisYear => timezone.ymd(now.year(), 1, 1).and_hms(0, 0, 0),
-	};
-	let end_date = match date_window {
-		DateWindow::Today => start_date + chrono::Duration::days(1),
-		DateWindow::ThisMonth => {
-			start_date
-				+ chrono::Duration::days(n_days_in_month(start_date.year(), start_date.month()))
-		}
-		DateWindow::ThisYear => timezone.ymd(now.year() + 1, 1, 1).and_hms(0, 0, 0),
-	};
-	let rows = sqlx::query(
-		"
-			select
-				data,
-				hour
-			from production_metrics
-			where
-				model_id = $1 and
-				hour >= $2 and
-				hour < $3
-			order by hour
-		",
+	let (start_date, end_date) = get_date_window_bounds(date_window, now);
+	let mut intervals: Vec<ProductionMetrics> =
+		get_date_window_intervals(date_window, date_window_interval, now)
+			.into_iter()
+			.map(|(start, end)| {
+				ProductionMetrics::new(model, start.with_timezone(&Utc), end.with_timezone(&Utc))
+			})
+			.collect();
+	// Retrieve the hourly and daily production metrics for the date window.
+	let interval_bounds = intervals
+		.iter()
+		.map(|interval| {
+			(
+				interval.start_date.timestamp(),
+				interval.end_date.timestamp(),
+			)
+		})
+		.collect::<Vec<_>>();
+	let rows = get_rollup_rows(
+		txn,
+		RollupTable::ProductionMetrics,
+		&model.id().to_string(),
+		&interval_bounds,
 	)
-	.bind(&model.id().to_string())
-	.bind(&start_date.timestamp())
-	.bind(&end_date.timestamp())
-	.fetch_all(txn.borrow_mut())
 	.await?;
-	/*
-	 Compute the number of intervals.
-	 * For today, use 24.
-	 * For this month, use the number of days in this month.
-	 * For this year, use 12.
-	*/
-	let n_intervals: usize = match date_window_interval {
-		DateWindowInterval::Hourly => 24,
-		DateWindowInterval::Daily => n_days_in_month(start_date.year(), start_date.month())
-			.to_usize()
-			.unwrap(),
-		DateWindowInterval::Monthly => 12,
-	};
-	let mut intervals: Vec<ProductionMetrics> = (0..n_intervals.to_u32().unwrap())
-		.map(|i| {
-			// Determine the start and end dates for the interval.
-			let start = match date_window_interval {
-				DateWindowInterval::Hourly => start_date.with_hour(i).unwrap(),
-				DateWindowInterval::Daily => start_date.with_day0(i).unwrap(),
-				DateWindowInterval::Monthly => start_date.with_month0(i).unwrap(),
-			};
-			let end = match date_window_interval {
-				DateWindowInterval::Hourly => start + chrono::Duration::hours(1),
-				DateWindowInterval::Daily => start + chrono::Duration::days(1),
-				DateWindowInterval::Monthly => {
-					start + chrono::Duration::days(n_days_in_month(start.year(), start.month()))
-				}
-			};
-			ProductionMetrics::new(model, start.with_timezone(&Utc), end.with_timezone(&Utc))
-		})
-		.collect();
-	// Merge each hourly production metrics entry into its corresponding interval.
+	// Merge each hourly or daily production metrics entry into the interval that contains it.
 	for row in rows {
-		let data: String = row.get(0);
-		let hour: i64 = row.get(1);
-		let hour = timezone.timestamp(hour, 0);
-		let interval = match date_window_interval {
-			DateWindowInterval::Hourly => {
-				let hour = hour.hour().to_usize().unwrap();
-				intervals.get_mut(hour).unwrap()
-			}
-			DateWindowInterval::Daily => {
-				let day = hour.day0().to_usize().unwrap();
-				intervals.get_mut(day).unwrap()
-			}
-			DateWindowInterval::Monthly => {
-				let month = hour.month0().to_usize().unwrap();
-				intervals.get_mut(month).unwrap()
-			}
+		let interval_index = interval_bounds.partition_point(|(_, end)| *end <= row.timestamp);
+		let interval = match intervals.get_mut(interval_index) {
+			Some(interval) => interval,
+			None => continue,
 		};
-		let hourly_production_metrics: ProductionMetrics = serde_json::from_str(&data).unwrap();
-		interval.merge(hourly_production_metrics);
+		let production_metrics: ProductionMetrics = serde_json::from_str(&row.data)?;
+		interval.merge(production_metrics);
 	}
+	// The rolling metrics are computed before a range is selected so the first intervals of the range include the intervals before it.
+	let mut rolling_intervals = rolling_production_metrics(&intervals, rolling_window);
+	// If a range of the date window was selected, only the intervals in the range are returned and included in the overall metrics.
+	let (start_date, end_date) = if let Some(range) = range {
+		rolling_intervals = rolling_intervals
+			.drain(range.indexes(rolling_intervals.len()))
+			.collect();
+		intervals = intervals.drain(range.indexes(intervals.len())).collect();
+		(
+			intervals.first().unwrap().start_date,
+			intervals.last().unwrap().end_date,
+		)
+	} else {
+		(start_date.with_timezone(&Utc), end_date.with_timezone(&Utc))
+	};
 	let overall = intervals
 		.iter()
 		.fold(
-			ProductionMetrics::new(
-				model,
-				start_date.with_timezone(&Utc),
-				end_date.with_timezone(&Utc),
-			),
+			ProductionMetrics::new(model, start_date, end_date),
 			|mut metrics, next| {
 				metrics.merge(next.clone());
 				metrics
@@ -288,21 +282,32 @@ pub async fn get_production_metrics(
 		.into_iter()
 		.map(|metrics| metrics.finalize())
 		.collect();
+	let rolling_intervals = rolling_intervals
+		.into_iter()
+		.map(|metrics| metrics.finalize())
+		.collect();
 	Ok(GetProductionMetricsOutput {
 		date_window,
 		date_window_interval,
 		overall,
 		intervals,
+		rolling_intervals,
 	})
 }
 
-fn n_days_in_month(year: i32, month: u32) -> i64 {
-	let (end_year, end_month) = if month == 12 {
-		(year + 1, 1)
-	} else {
-		(year, month + 1)
-	};
-	let start = NaiveDate::from_ymd(year, month, 1);
-	let end = NaiveDate::from_ymd(end_year, end_month, 1);
-	(end - start).num_days()
+/// Merge each interval's metrics with those of the `rolling_window - 1` intervals before it, so they start at the first interval in the window.
+fn rolling_production_metrics(
+	intervals: &[ProductionMetrics],
+	rolling_window: usize,
+) -> Vec<ProductionMetrics> {
+	(0..intervals.len())
+		.map(|index| {
+			let start = (index + 1).saturating_sub(rolling_window.max(1));
+			let mut metrics = intervals[index].clone();
+			for interval in &intervals[start..index] {
+				metrics.merge(interval.clone());
+			}
+			metrics
+		})
+		.collect()
 }
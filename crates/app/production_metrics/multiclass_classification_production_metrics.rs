@@ -1,3 +1,4 @@
+use crate::{bootstrap::proportion_confidence_interval, ProductionMetricsConfidenceIntervals};
 use modelfox_app_monitor_event::NumberOrString;
 use modelfox_zip::zip;
 use ndarray::prelude::*;
@@ -105,6 +106,8 @@ pub struct MulticlassClassificationProductionPredictionMetricsOutput {
 	pub precision_weighted: f32,
 	pub recall_unweighted: f32,
 	pub recall_weighted: f32,
+	/// The confusion matrix has one row for each predicted class and one column for each actual class.
+	pub confusion_matrix: Vec<Vec<u64>>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -152,6 +155,15 @@ impl MulticlassClassificationProductionPredictionMetrics {
 		self.confusion_matrix += &other.confusion_matrix;
 	}
 
+	pub fn confidence_intervals(&self) -> ProductionMetricsConfidenceIntervals {
+		let n_correct = self.confusion_matrix.diag().sum();
+		let n_examples = self.confusion_matrix.sum();
+		ProductionMetricsConfidenceIntervals {
+			accuracy: proportion_confidence_interval(n_correct, n_examples),
+			..ProductionMetricsConfidenceIntervals::default()
+		}
+	}
+
 	pub fn finalize(self) -> Option<MulticlassClassificationProductionPredictionMetricsOutput> {
 		let n_classes = self.classes.len();
 		let n_examples = self.confusion_matrix.sum();
@@ -227,6 +239,10 @@ impl MulticlassClassificationProductionPredictionMetrics {
 				precision_weighted,
 				recall_unweighted,
 				recall_weighted,
+				confusion_matrix: confusion_matrix
+					.axis_iter(Axis(0))
+					.map(|row| row.to_vec())
+					.collect(),
 			})
 		}
 	}
@@ -286,6 +302,16 @@ fn test_binary() {
          precision_weighted: 0.6318681,
          recall_unweighted: 0.6125,
          recall_weighted: 0.61538464,
+         confusion_matrix: [
+             [
+                 5,
+                 2,
+             ],
+             [
+                 3,
+                 3,
+             ],
+         ],
      },
  )
  "###);
@@ -361,6 +387,23 @@ fn test_multiclass() {
          precision_weighted: 0.7363316,
          recall_unweighted: 0.65705127,
          recall_weighted: 0.7037037,
+         confusion_matrix: [
+             [
+                 5,
+                 2,
+                 0,
+             ],
+             [
+                 3,
+                 3,
+                 2,
+             ],
+             [
+                 0,
+                 1,
+                 11,
+             ],
+         ],
      },
  )
  "###);
@@ -1,3 +1,7 @@
+use crate::{
+	bootstrap::{mean_confidence_interval, proportion_confidence_interval, BootstrapSample},
+	ProductionMetricsConfidenceIntervals,
+};
 use modelfox_app_monitor_event::NumberOrString;
 use modelfox_app_production_stats::NumberStats;
 use num::ToPrimitive;
@@ -7,6 +11,57 @@ pub struct RegressionProductionPredictionMetrics {
 	stats: Option<NumberStats>,
 	absolute_error: f64,
 	squared_error: f64,
+	/// This is the sum of the residuals, the predictions minus their true values. It is zero for metrics tracked before the mean residual was added.
+	#[serde(default)]
+	error: f64,
+	/// This is the tolerance the model was trained with. It is absent if the model was trained without a tolerance or for metrics tracked before tolerance accuracy was added.
+	#[serde(default)]
+	tolerance: Option<RegressionProductionTolerance>,
+	/// This is the number of true values compared against the tolerance.
+	#[serde(default)]
+	tolerance_count: u64,
+	/// This is the number of predictions within the tolerance of their true value.
+	#[serde(default)]
+	within_tolerance_count: u64,
+	/// This is a sample of the errors of the predictions, used to compute confidence intervals. It is empty for metrics tracked before confidence intervals were added.
+	#[serde(default)]
+	error_sample: BootstrapSample,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum RegressionProductionTolerance {
+	#[serde(rename = "absolute")]
+	Absolute { value: f32 },
+	#[serde(rename = "relative")]
+	Relative { value: f32 },
+}
+
+impl RegressionProductionTolerance {
+	pub fn contains(&self, prediction: f32, label: f32) -> bool {
+		let error = (prediction - label).abs();
+		match self {
+			RegressionProductionTolerance::Absolute { value } => error <= *value,
+			RegressionProductionTolerance::Relative { value } => error <= *value * label.abs(),
+		}
+	}
+}
+
+impl<'a> From<modelfox_model::RegressionToleranceReader<'a>> for RegressionProductionTolerance {
+	fn from(value: modelfox_model::RegressionToleranceReader<'a>) -> Self {
+		match value.kind() {
+			modelfox_model::RegressionToleranceKindReader::Absolute(_) => {
+				RegressionProductionTolerance::Absolute {
+					value: value.value(),
+				}
+			}
+			modelfox_model::RegressionToleranceKindReader::Relative(_) => {
+				RegressionProductionTolerance::Relative {
+					value: value.value(),
+				}
+			}
+		}
+	}
 }
 
 #[derive(serde::Deserialize)]
@@ -14,9 +69,15 @@ pub struct RegressionProductionPredictionMetricsOutput {
 	pub mse: f32,
 	pub rmse: f32,
 	pub mae: f32,
+	/// This is the mean of the predictions minus their true values. It is positive if the model overestimates on average and negative if it underestimates.
+	#[serde(default)]
+	pub mean_residual: f32,
 	pub r2: f32,
 	pub baseline_mse: f32,
 	pub baseline_rmse: f32,
+	/// This is the fraction of predictions within the model's tolerance of their true value. It is `None` if the model was trained without a tolerance.
+	#[serde(default)]
+	pub tolerance_accuracy: Option<f32>,
 }
 
 impl Default for RegressionProductionPredictionMetrics {
@@ -25,13 +86,23 @@ impl Default for RegressionProductionPredictionMetrics {
 			stats: None,
 			absolute_error: 0.0,
 			squared_error: 0.0,
+			error: 0.0,
+			tolerance: None,
+			tolerance_count: 0,
+			within_tolerance_count: 0,
+			error_sample: BootstrapSample::default(),
 		}
 	}
 }
 
 impl RegressionProductionPredictionMetrics {
-	pub fn new() -> RegressionProductionPredictionMetrics {
-		RegressionProductionPredictionMetrics::default()
+	pub fn new(
+		tolerance: Option<RegressionProductionTolerance>,
+	) -> RegressionProductionPredictionMetrics {
+		RegressionProductionPredictionMetrics {
+			tolerance,
+			..RegressionProductionPredictionMetrics::default()
+		}
 	}
 
 	pub fn update(&mut self, value: (NumberOrString, NumberOrString)) {
@@ -43,8 +114,9 @@ impl RegressionProductionPredictionMetrics {
 			Ok(value) => value,
 			Err(_) => return,
 		};
-		let absolute_error = prediction - label;
-		let squared_error = absolute_error * absolute_error;
+		let error = prediction - label;
+		let absolute_error = error.abs();
+		let squared_error = error * error;
 		match &mut self.stats {
 			Some(stats) => stats.update(prediction),
 			None => {
@@ -53,6 +125,14 @@ impl RegressionProductionPredictionMetrics {
 		};
 		self.absolute_error += absolute_error as f64;
 		self.squared_error += squared_error as f64;
+		self.error += error as f64;
+		self.error_sample.update(error);
+		if let Some(tolerance) = self.tolerance {
+			self.tolerance_count += 1;
+			if tolerance.contains(prediction, label) {
+				self.within_tolerance_count += 1;
+			}
+		}
 	}
 
 	pub fn merge(&mut self, other: RegressionProductionPredictionMetrics) {
@@ -66,14 +146,59 @@ impl RegressionProductionPredictionMetrics {
 		};
 		self.absolute_error += other.absolute_error;
 		self.squared_error += other.squared_error;
+		self.error += other.error;
+		self.tolerance = self.tolerance.or(other.tolerance);
+		self.tolerance_count += other.tolerance_count;
+		self.within_tolerance_count += other.within_tolerance_count;
+		self.error_sample.merge(other.error_sample);
+	}
+
+	pub fn confidence_intervals(&self) -> ProductionMetricsConfidenceIntervals {
+		let n = match &self.stats {
+			Some(stats) => stats.n,
+			None => return ProductionMetricsConfidenceIntervals::default(),
+		};
+		let n_f64 = n.to_f64().unwrap();
+		let errors = self.error_sample.values();
+		let squared_errors = errors.iter().map(|error| error * error).collect::<Vec<_>>();
+		let absolute_errors = errors.iter().map(|error| error.abs()).collect::<Vec<_>>();
+		let mse = (self.squared_error / n_f64).to_f32().unwrap();
+		let mae = (self.absolute_error / n_f64).to_f32().unwrap();
+		let mean_residual = (self.error / n_f64).to_f32().unwrap();
+		let mse = mean_confidence_interval(&squared_errors, self.error_sample.count(), mse)
+			.map(|interval| interval.map(|bound| bound.max(0.0)));
+		let mae = mean_confidence_interval(&absolute_errors, self.error_sample.count(), mae)
+			.map(|interval| interval.map(|bound| bound.max(0.0)));
+		let rmse = mse.map(|interval| interval.map(f32::sqrt));
+		let mean_residual =
+			mean_confidence_interval(errors, self.error_sample.count(), mean_residual);
+		let tolerance_accuracy =
+			proportion_confidence_interval(self.within_tolerance_count, self.tolerance_count);
+		ProductionMetricsConfidenceIntervals {
+			mse,
+			rmse,
+			mae,
+			mean_residual,
+			tolerance_accuracy,
+			..ProductionMetricsConfidenceIntervals::default()
+		}
 	}
 
 	pub fn finalize(self) -> Option<RegressionProductionPredictionMetricsOutput> {
+		let tolerance_accuracy = if self.tolerance_count > 0 {
+			Some(
+				self.within_tolerance_count.to_f32().unwrap()
+					/ self.tolerance_count.to_f32().unwrap(),
+			)
+		} else {
+			None
+		};
 		let stats = self.stats.map(|s| s.finalize());
 		match stats {
 			Some(stats) => {
 				let variance = stats.variance;
 				let mae = self.absolute_error.to_f32().unwrap() / stats.n.to_f32().unwrap();
+				let mean_residual = self.error.to_f32().unwrap() / stats.n.to_f32().unwrap();
 				let mse = self.squared_error.to_f32().unwrap() / stats.n.to_f32().unwrap();
 				let rmse = mse.sqrt();
 				let r2 = 1.0
@@ -84,12 +209,48 @@ impl RegressionProductionPredictionMetrics {
 					mse,
 					rmse,
 					mae,
+					mean_residual,
 					r2,
 					baseline_mse,
 					baseline_rmse,
+					tolerance_accuracy,
 				})
 			}
 			None => None,
 		}
 	}
 }
+
+#[test]
+fn test_tolerance_accuracy() {
+	let mut metrics =
+		RegressionProductionPredictionMetrics::new(Some(RegressionProductionTolerance::Absolute {
+			value: 0.5,
+		}));
+	let predictions = [1.0, 2.5, 10.0, 95.0];
+	let labels = [1.2, 2.0, 13.0, 100.0];
+	for (prediction, label) in predictions.iter().zip(labels.iter()) {
+		metrics.update((
+			NumberOrString::Number(*prediction),
+			NumberOrString::Number(*label),
+		));
+	}
+	let metrics = metrics.finalize().unwrap();
+	assert_eq!(metrics.tolerance_accuracy, Some(0.5));
+}
+
+#[test]
+fn test_mean_absolute_error_and_mean_residual() {
+	let mut metrics = RegressionProductionPredictionMetrics::new(None);
+	let predictions = [1.0, 3.0, 10.0, 95.0];
+	let labels = [2.0, 2.0, 13.0, 100.0];
+	for (prediction, label) in predictions.iter().zip(labels.iter()) {
+		metrics.update((
+			NumberOrString::Number(*prediction),
+			NumberOrString::Number(*label),
+		));
+	}
+	let metrics = metrics.finalize().unwrap();
+	assert_eq!(metrics.mae, 2.5);
+	assert_eq!(metrics.mean_residual, -2.0);
+}
@@ -5,12 +5,16 @@ use itertools::Itertools;
 use modelfox_text::{NGram, NGramRef, NGramType, Tokenizer};
 use num::ToPrimitive;
 
+/// Production values of a number column that are further than this many interquartile ranges below the training p25 or above the training p75 are counted as outliers.
+pub const OUTLIER_IQR_MULTIPLIER: f32 = 1.5;
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub enum ProductionColumnStats {
 	Unknown(UnknownProductionColumnStats),
 	Number(NumberProductionColumnStats),
 	Enum(EnumProductionColumnStats),
 	Text(TextProductionColumnStats),
+	DateTime(DateTimeProductionColumnStats),
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
@@ -28,15 +32,46 @@ pub struct NumberProductionColumnStats {
 	pub invalid_count: u64,
 	pub row_count: u64,
 	pub stats: Option<NumberStats>,
+	/// This is the number of valid values outside the training min and max. It is zero for stats tracked before outlier detection was added.
+	#[serde(default)]
+	pub out_of_range_count: u64,
+	/// This is the number of valid values more than [`OUTLIER_IQR_MULTIPLIER`] interquartile ranges outside the training p25 and p75.
+	#[serde(default)]
+	pub iqr_outlier_count: u64,
+}
+
+/// The training stats of a number column that production values are compared against to find outliers.
+#[derive(Debug, Clone, Copy)]
+pub struct NumberColumnTrainingRange {
+	pub min: f32,
+	pub max: f32,
+	pub p25: f32,
+	pub p75: f32,
+}
+
+impl NumberColumnTrainingRange {
+	pub fn is_out_of_range(&self, value: f32) -> bool {
+		value < self.min || value > self.max
+	}
+
+	pub fn is_iqr_outlier(&self, value: f32) -> bool {
+		let iqr = self.p75 - self.p25;
+		value < self.p25 - OUTLIER_IQR_MULTIPLIER * iqr
+			|| value > self.p75 + OUTLIER_IQR_MULTIPLIER * iqr
+	}
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub struct EnumProductionColumnStats {
 	pub absent_count: u64,
 	pub column_name: String,
-	pub histogram: FnvHashMap<String, u64>,
+	/// These are the estimated number of predictions with each variant. A row logged with a sample rate counts as `1 / sample_rate` predictions, so the counts are not whole numbers.
+	pub histogram: FnvHashMap<String, f64>,
 	pub invalid_count: u64,
-	pub invalid_histogram: Option<FnvHashMap<String, u64>>,
+	/// This is the estimated number of predictions with an invalid value, weighted like `histogram`. It is `None` for stats written before prediction logging supported sampling, in which case it is equal to `invalid_count`.
+	#[serde(default)]
+	pub estimated_invalid_count: Option<f64>,
+	pub invalid_histogram: Option<FnvHashMap<String, f64>>,
 	pub row_count: u64,
 }
 
@@ -49,6 +84,24 @@ pub struct TextProductionColumnStats {
 	#[serde(with = "indexmap::serde_seq")]
 	pub ngrams: IndexMap<NGram, TextProductionColumnStatsNGramEntry, FnvBuildHasher>,
 	pub untracked_ngram_occurence_count: u64,
+	/// This is the number of tokens in valid values. It is zero for stats tracked before unseen tokens were counted.
+	#[serde(default)]
+	pub token_count: u64,
+	/// This is the number of tokens in valid values that are not in the training vocabulary.
+	#[serde(default)]
+	pub unseen_token_count: u64,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct DateTimeProductionColumnStats {
+	pub absent_count: u64,
+	pub column_name: String,
+	pub invalid_count: u64,
+	pub row_count: u64,
+	/// This is the name of the timezone used to interpret values without an offset, taken from the training stats.
+	pub timezone: String,
+	pub min: Option<i64>,
+	pub max: Option<i64>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
@@ -63,6 +116,7 @@ pub enum ProductionColumnStatsOutput {
 	Number(NumberProductionColumnStatsOutput),
 	Enum(EnumProductionColumnStatsOutput),
 	Text(TextProductionColumnStatsOutput),
+	DateTime(DateTimeProductionColumnStatsOutput),
 }
 
 #[derive(Debug)]
@@ -78,14 +132,34 @@ pub struct NumberProductionColumnStatsOutput {
 	pub column_name: String,
 	pub invalid_count: u64,
 	pub stats: Option<NumberStatsOutput>,
+	pub out_of_range_count: u64,
+	pub iqr_outlier_count: u64,
+}
+
+impl NumberProductionColumnStatsOutput {
+	/// Get the fraction of valid values outside the training min and max, or `None` if there were no valid values.
+	pub fn out_of_range_fraction(&self) -> Option<f32> {
+		self.stats
+			.as_ref()
+			.map(|stats| self.out_of_range_count.to_f32().unwrap() / stats.n.to_f32().unwrap())
+	}
+
+	/// Get the fraction of valid values that are interquartile range outliers, or `None` if there were no valid values.
+	pub fn iqr_outlier_fraction(&self) -> Option<f32> {
+		self.stats
+			.as_ref()
+			.map(|stats| self.iqr_outlier_count.to_f32().unwrap() / stats.n.to_f32().unwrap())
+	}
 }
 
 #[derive(Debug)]
 pub struct EnumProductionColumnStatsOutput {
 	pub absent_count: u64,
 	pub column_name: String,
+	/// These are the estimated number of predictions with each variant, rounded to whole numbers.
 	pub histogram: Vec<(String, u64)>,
 	pub invalid_count: u64,
+	pub estimated_invalid_count: u64,
 	pub invalid_histogram: Option<Vec<(String, u64)>>,
 }
 
@@ -96,6 +170,28 @@ pub struct TextProductionColumnStatsOutput {
 	pub invalid_count: u64,
 	pub ngrams: Vec<(NGram, TextProductionColumnStatsOutputNGramEntry)>,
 	pub untracked_ngram_occurence_count: u64,
+	pub token_count: u64,
+	pub unseen_token_count: u64,
+}
+
+impl TextProductionColumnStatsOutput {
+	/// Get the fraction of tokens that are not in the training vocabulary, or `None` if there were no tokens.
+	pub fn unseen_token_fraction(&self) -> Option<f32> {
+		if self.token_count > 0 {
+			Some(self.unseen_token_count.to_f32().unwrap() / self.token_count.to_f32().unwrap())
+		} else {
+			None
+		}
+	}
+}
+
+#[derive(Debug)]
+pub struct DateTimeProductionColumnStatsOutput {
+	pub absent_count: u64,
+	pub column_name: String,
+	pub invalid_count: u64,
+	pub min: Option<i64>,
+	pub max: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -138,6 +234,14 @@ impl ProductionColumnStats {
 
 				ProductionColumnStats::Enum(EnumProductionColumnStats::new(name, &variants))
 			}
+
+			modelfox_model::ColumnStatsReader::DateTimeColumn(stats) => {
+				let stats = stats.read();
+				ProductionColumnStats::DateTime(DateTimeProductionColumnStats::new(
+					stats.column_name(),
+					stats.timezone(),
+				))
+			}
 		}
 	}
 
@@ -148,14 +252,16 @@ impl ProductionColumnStats {
 			ProductionColumnStats::Text(s) => s.column_name.as_str(),
 			ProductionColumnStats::Number(s) => s.column_name.as_str(),
 			ProductionColumnStats::Enum(s) => s.column_name.as_str(),
+			ProductionColumnStats::DateTime(s) => s.column_name.as_str(),
 		}
 	}
 
-	/// Incorporate a data value into the statistics being tracked.
+	/// Incorporate a data value into the statistics being tracked. `weight` is the number of predictions the value stands for, which is `1 / sample_rate` for a value logged with a sample rate. It weights the enum histograms and the number quantile sketch, which are used to show the distribution of values and compute drift.
 	pub fn update(
 		&mut self,
 		model: modelfox_model::ModelReader,
 		value: Option<&serde_json::Value>,
+		weight: f64,
 	) {
 		let column_name = self.column_name().to_string();
 		match self {
@@ -203,11 +309,36 @@ impl ProductionColumnStats {
 					})
 					.collect();
 
+				// Get the training vocabulary that tokens are compared against to find unseen tokens. This is the vocabulary of the column's bag of words feature group if the model has one, and the unigrams in the training stats otherwise.
+				let vocabulary =
+					bag_of_words_vocabulary(model, &column_name).unwrap_or_else(|| {
+						text_column_stats
+							.top_ngrams()
+							.iter()
+							.filter_map(|(ngram, _)| match ngram {
+								modelfox_model::NGramReader::Unigram(token) => {
+									Some(token.read().to_owned())
+								}
+								modelfox_model::NGramReader::Bigram(_) => None,
+							})
+							.collect()
+					});
+
 				// Interpret the value and update the statistics.
-				stats.update(value, &tokenizer, &tracked_ngrams, &ngram_types)
+				stats.update(
+					value,
+					&tokenizer,
+					&tracked_ngrams,
+					&ngram_types,
+					&vocabulary,
+				)
+			}
+			ProductionColumnStats::Number(stats) => {
+				let training_range = number_column_training_range(model, &column_name);
+				stats.update(value, training_range.as_ref(), weight)
 			}
-			ProductionColumnStats::Number(stats) => stats.update(value),
-			ProductionColumnStats::Enum(stats) => stats.update(value),
+			ProductionColumnStats::Enum(stats) => stats.update(value, weight),
+			ProductionColumnStats::DateTime(stats) => stats.update(value),
 		}
 	}
 
@@ -236,6 +367,11 @@ impl ProductionColumnStats {
 					stats.merge(other)
 				}
 			}
+			ProductionColumnStats::DateTime(stats) => {
+				if let ProductionColumnStats::DateTime(other) = other {
+					stats.merge(other)
+				}
+			}
 		}
 	}
 
@@ -253,6 +389,9 @@ impl ProductionColumnStats {
 			ProductionColumnStats::Enum(stats) => {
 				ProductionColumnStatsOutput::Enum(stats.finalize())
 			}
+			ProductionColumnStats::DateTime(stats) => {
+				ProductionColumnStatsOutput::DateTime(stats.finalize())
+			}
 		}
 	}
 }
@@ -295,6 +434,95 @@ impl UnknownProductionColumnStats {
 	}
 }
 
+/// Read the unigrams in the vocabulary of the bag of words feature group the model built for the column with `column_name`, if it has one.
+fn bag_of_words_vocabulary(
+	model: modelfox_model::ModelReader,
+	column_name: &str,
+) -> Option<FnvHashSet<String>> {
+	let feature_groups = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => match regressor.read().model() {
+			modelfox_model::RegressionModelReader::Linear(inner_model) => {
+				inner_model.read().feature_groups()
+			}
+			modelfox_model::RegressionModelReader::Tree(inner_model) => {
+				inner_model.read().feature_groups()
+			}
+		},
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			match binary_classifier.read().model() {
+				modelfox_model::BinaryClassificationModelReader::Linear(inner_model) => {
+					inner_model.read().feature_groups()
+				}
+				modelfox_model::BinaryClassificationModelReader::Tree(inner_model) => {
+					inner_model.read().feature_groups()
+				}
+			}
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			match multiclass_classifier.read().model() {
+				modelfox_model::MulticlassClassificationModelReader::Linear(inner_model) => {
+					inner_model.read().feature_groups()
+				}
+				modelfox_model::MulticlassClassificationModelReader::Tree(inner_model) => {
+					inner_model.read().feature_groups()
+				}
+			}
+		}
+	};
+	feature_groups
+		.iter()
+		.find_map(|feature_group| match feature_group {
+			modelfox_model::FeatureGroupReader::BagOfWords(feature_group) => {
+				let feature_group = feature_group.read();
+				if feature_group.source_column_name() == column_name {
+					Some(
+						feature_group
+							.ngrams()
+							.iter()
+							.filter_map(|(ngram, _)| match ngram {
+								modelfox_model::NGramReader::Unigram(token) => {
+									Some(token.read().to_owned())
+								}
+								modelfox_model::NGramReader::Bigram(_) => None,
+							})
+							.collect(),
+					)
+				} else {
+					None
+				}
+			}
+			_ => None,
+		})
+}
+
+/// Read the training stats for a number column out of the model.
+fn number_column_training_range(
+	model: modelfox_model::ModelReader,
+	column_name: &str,
+) -> Option<NumberColumnTrainingRange> {
+	let train_column_stats = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			regressor.read().train_column_stats()
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			binary_classifier.read().train_column_stats()
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			multiclass_classifier.read().train_column_stats()
+		}
+	};
+	let number_column_stats = train_column_stats
+		.iter()
+		.find(|column| column.column_name() == column_name)?
+		.as_number_column()?;
+	Some(NumberColumnTrainingRange {
+		min: number_column_stats.min(),
+		max: number_column_stats.max(),
+		p25: number_column_stats.p25(),
+		p75: number_column_stats.p75(),
+	})
+}
+
 impl NumberProductionColumnStats {
 	pub fn new(name: &str) -> NumberProductionColumnStats {
 		NumberProductionColumnStats {
@@ -303,10 +531,17 @@ impl NumberProductionColumnStats {
 			invalid_count: 0,
 			stats: None,
 			row_count: 0,
+			out_of_range_count: 0,
+			iqr_outlier_count: 0,
 		}
 	}
 
-	pub fn update(&mut self, value: Option<&serde_json::Value>) {
+	pub fn update(
+		&mut self,
+		value: Option<&serde_json::Value>,
+		training_range: Option<&NumberColumnTrainingRange>,
+		weight: f64,
+	) {
 		self.row_count += 1;
 		let value = match value {
 			None | Some(serde_json::Value::Null) => {
@@ -340,10 +575,18 @@ impl NumberProductionColumnStats {
 				return;
 			}
 		};
+		if let Some(training_range) = training_range {
+			if training_range.is_out_of_range(value) {
+				self.out_of_range_count += 1;
+			}
+			if training_range.is_iqr_outlier(value) {
+				self.iqr_outlier_count += 1;
+			}
+		}
 		match &mut self.stats {
-			Some(stats) => stats.update(value),
+			Some(stats) => stats.update_weighted(value, weight),
 			None => {
-				self.stats.replace(NumberStats::new(value));
+				self.stats.replace(NumberStats::new_weighted(value, weight));
 			}
 		};
 	}
@@ -360,6 +603,8 @@ impl NumberProductionColumnStats {
 		self.absent_count += other.absent_count;
 		self.invalid_count += other.invalid_count;
 		self.row_count += other.row_count;
+		self.out_of_range_count += other.out_of_range_count;
+		self.iqr_outlier_count += other.iqr_outlier_count;
 	}
 
 	pub fn finalize(self) -> NumberProductionColumnStatsOutput {
@@ -368,6 +613,8 @@ impl NumberProductionColumnStats {
 			column_name: self.column_name,
 			invalid_count: self.invalid_count,
 			stats: self.stats.map(|s| s.finalize()),
+			out_of_range_count: self.out_of_range_count,
+			iqr_outlier_count: self.iqr_outlier_count,
 		}
 	}
 }
@@ -377,12 +624,13 @@ impl EnumProductionColumnStats {
 		// Make an empty histogram, using the values we know about.
 		let histogram = variants
 			.iter()
-			.map(|value| (value.to_string(), 0))
+			.map(|value| (value.to_string(), 0.0))
 			.collect();
 
 		EnumProductionColumnStats {
 			column_name: name.to_string(),
 			invalid_count: 0,
+			estimated_invalid_count: Some(0.0),
 			absent_count: 0,
 			histogram,
 			invalid_histogram: None,
@@ -390,7 +638,7 @@ impl EnumProductionColumnStats {
 		}
 	}
 
-	pub fn update(&mut self, value: Option<&serde_json::Value>) {
+	pub fn update(&mut self, value: Option<&serde_json::Value>, weight: f64) {
 		self.row_count += 1;
 		let value = match value {
 			None | Some(serde_json::Value::Null) => {
@@ -399,7 +647,7 @@ impl EnumProductionColumnStats {
 			}
 
 			Some(serde_json::Value::Number(_)) => {
-				self.invalid_count += 1;
+				self.add_invalid(weight);
 				return;
 			}
 			Some(serde_json::Value::Bool(true)) => "true",
@@ -407,43 +655,47 @@ impl EnumProductionColumnStats {
 			Some(serde_json::Value::String(value)) => value,
 
 			_ => {
-				self.invalid_count += 1;
+				self.add_invalid(weight);
 				return;
 			}
 		};
 		match self.histogram.get_mut(value) {
-			Some(count) => *count += 1,
+			Some(count) => *count += weight,
 			None => {
-				self.invalid_count += 1;
-				match &mut self.invalid_histogram {
-					Some(histogram) => match histogram.get_mut(value) {
-						Some(count) => *count += 1,
-						None => {
-							histogram.insert(value.into(), 1);
-						}
-					},
-					None => {
-						let mut invalid_histogram = <FnvHashMap<String, u64>>::default();
-						invalid_histogram.insert(value.into(), 1);
-						self.invalid_histogram = Some(invalid_histogram)
-					}
-				}
+				self.add_invalid(weight);
+				*self
+					.invalid_histogram
+					.get_or_insert_with(FnvHashMap::default)
+					.entry(value.into())
+					.or_insert(0.0) += weight;
 			}
 		};
 	}
 
+	fn add_invalid(&mut self, weight: f64) {
+		self.estimated_invalid_count = Some(self.estimated_invalid_count() + weight);
+		self.invalid_count += 1;
+	}
+
+	fn estimated_invalid_count(&self) -> f64 {
+		self.estimated_invalid_count
+			.unwrap_or_else(|| self.invalid_count.to_f64().unwrap())
+	}
+
 	pub fn merge(&mut self, other: EnumProductionColumnStats) {
+		self.estimated_invalid_count =
+			Some(self.estimated_invalid_count() + other.estimated_invalid_count());
 		self.invalid_count += other.invalid_count;
 		self.absent_count += other.absent_count;
 		for (value, count) in other.histogram.into_iter() {
-			*self.histogram.entry(value).or_insert(0) += count;
+			*self.histogram.entry(value).or_insert(0.0) += count;
 		}
 		self.row_count += other.row_count;
 		match &mut self.invalid_histogram {
 			Some(histogram) => {
 				if let Some(other) = other.invalid_histogram {
 					for (value, count) in other.into_iter() {
-						*histogram.entry(value).or_insert(0) += count;
+						*histogram.entry(value).or_insert(0.0) += count;
 					}
 				};
 			}
@@ -456,12 +708,23 @@ impl EnumProductionColumnStats {
 	}
 
 	pub fn finalize(self) -> EnumProductionColumnStatsOutput {
+		let estimated_invalid_count = self.estimated_invalid_count();
 		EnumProductionColumnStatsOutput {
 			absent_count: self.absent_count,
 			column_name: self.column_name,
-			histogram: self.histogram.into_iter().collect(),
+			histogram: self
+				.histogram
+				.into_iter()
+				.map(|(value, count)| (value, count.round().to_u64().unwrap()))
+				.collect(),
 			invalid_count: self.invalid_count,
-			invalid_histogram: self.invalid_histogram.map(|h| h.into_iter().collect()),
+			estimated_invalid_count: estimated_invalid_count.round().to_u64().unwrap(),
+			invalid_histogram: self.invalid_histogram.map(|histogram| {
+				histogram
+					.into_iter()
+					.map(|(value, count)| (value, count.round().to_u64().unwrap()))
+					.collect()
+			}),
 		}
 	}
 }
@@ -475,6 +738,8 @@ impl TextProductionColumnStats {
 			row_count: 0,
 			ngrams: Default::default(),
 			untracked_ngram_occurence_count: 0,
+			token_count: 0,
+			unseen_token_count: 0,
 		}
 	}
 
@@ -484,6 +749,7 @@ impl TextProductionColumnStats {
 		tokenizer: &Tokenizer,
 		tracked_ngrams: &IndexSet<NGramRef>,
 		ngram_types: &IndexSet<NGramType>,
+		vocabulary: &FnvHashSet<String>,
 	) {
 		// Pull out a string from the value, if we have it. Otherwise, include an absent or invalid value.
 		self.row_count += 1;
@@ -499,6 +765,13 @@ impl TextProductionColumnStats {
 			}
 		};
 
+		for token in tokenizer.tokenize(value) {
+			self.token_count += 1;
+			if !vocabulary.contains(token.as_ref()) {
+				self.unseen_token_count += 1;
+			}
+		}
+
 		let mut ngrams_for_row = FnvHashSet::default();
 
 		let unigram_iter = ngram_types.contains(&NGramType::Unigram).then(|| {
@@ -555,6 +828,8 @@ impl TextProductionColumnStats {
 			}
 		}
 		self.untracked_ngram_occurence_count += other.untracked_ngram_occurence_count;
+		self.token_count += other.token_count;
+		self.unseen_token_count += other.unseen_token_count;
 	}
 
 	pub fn finalize(self) -> TextProductionColumnStatsOutput {
@@ -575,6 +850,72 @@ impl TextProductionColumnStats {
 			invalid_count: self.invalid_count,
 			ngrams,
 			untracked_ngram_occurence_count: self.untracked_ngram_occurence_count,
+			token_count: self.token_count,
+			unseen_token_count: self.unseen_token_count,
+		}
+	}
+}
+
+impl DateTimeProductionColumnStats {
+	pub fn new(name: &str, timezone: &str) -> DateTimeProductionColumnStats {
+		DateTimeProductionColumnStats {
+			absent_count: 0,
+			column_name: name.to_string(),
+			invalid_count: 0,
+			row_count: 0,
+			timezone: timezone.to_string(),
+			min: None,
+			max: None,
+		}
+	}
+
+	/// Strings are parsed the same way as during training, and numbers are interpreted as unix timestamps.
+	pub fn update(&mut self, value: Option<&serde_json::Value>) {
+		self.row_count += 1;
+		let value = match value {
+			None | Some(serde_json::Value::Null) => {
+				self.absent_count += 1;
+				return;
+			}
+			Some(serde_json::Value::String(value)) => {
+				let timezone = self.timezone.parse().unwrap_or(chrono_tz::Tz::UTC);
+				modelfox_table::parse_datetime(value, timezone)
+			}
+			Some(serde_json::Value::Number(value)) => value.as_i64(),
+			_ => None,
+		};
+		let value = match value {
+			Some(value) => value,
+			None => {
+				self.invalid_count += 1;
+				return;
+			}
+		};
+		self.min = Some(self.min.map_or(value, |min| min.min(value)));
+		self.max = Some(self.max.map_or(value, |max| max.max(value)));
+	}
+
+	pub fn merge(&mut self, other: DateTimeProductionColumnStats) {
+		self.absent_count += other.absent_count;
+		self.invalid_count += other.invalid_count;
+		self.row_count += other.row_count;
+		self.min = match (self.min, other.min) {
+			(Some(a), Some(b)) => Some(a.min(b)),
+			(a, b) => a.or(b),
+		};
+		self.max = match (self.max, other.max) {
+			(Some(a), Some(b)) => Some(a.max(b)),
+			(a, b) => a.or(b),
+		};
+	}
+
+	pub fn finalize(self) -> DateTimeProductionColumnStatsOutput {
+		DateTimeProductionColumnStatsOutput {
+			absent_count: self.absent_count,
+			column_name: self.column_name,
+			invalid_count: self.invalid_count,
+			min: self.min,
+			max: self.max,
 		}
 	}
 }
@@ -586,6 +927,7 @@ impl ProductionColumnStatsOutput {
 			ProductionColumnStatsOutput::Text(s) => s.column_name.as_str(),
 			ProductionColumnStatsOutput::Number(s) => s.column_name.as_str(),
 			ProductionColumnStatsOutput::Enum(s) => s.column_name.as_str(),
+			ProductionColumnStatsOutput::DateTime(s) => s.column_name.as_str(),
 		}
 	}
 }
@@ -594,6 +936,7 @@ impl ProductionColumnStatsOutput {
 mod tests {
 	use super::*;
 	use serde_json::Value;
+	use std::collections::BTreeMap;
 
 	/// Ensure that updating a number statistic with `null` reports an absent value
 	/// (Regression test for https://github.com/modelfoxdotdev/modelfox/issues/85)
@@ -602,7 +945,7 @@ mod tests {
 		let mut stats = NumberProductionColumnStats::new("number_stats");
 
 		// Update the stats with `null`
-		stats.update(Some(&Value::Null));
+		stats.update(Some(&Value::Null), None, 1.0);
 
 		// Check that the stats report an absent value correctly
 		assert_eq!(
@@ -643,7 +986,7 @@ mod tests {
 		let mut stats = EnumProductionColumnStats::new("enum_stat", enum_variants);
 
 		// Update the stats with `null`
-		stats.update(Some(&Value::Null));
+		stats.update(Some(&Value::Null), 1.0);
 
 		// Check that the stats report an absent value correctly
 		assert_eq!(
@@ -668,7 +1011,14 @@ mod tests {
 		let tracked_ngrams = &[].into_iter().collect();
 
 		// Update the stat
-		stats.update(Some(&Value::Null), &tokenizer, ngram_types, tracked_ngrams);
+		let vocabulary = FnvHashSet::default();
+		stats.update(
+			Some(&Value::Null),
+			&tokenizer,
+			ngram_types,
+			tracked_ngrams,
+			&vocabulary,
+		);
 
 		// Check that the stats report an absent value correctly
 		assert_eq!(
@@ -680,4 +1030,101 @@ mod tests {
 			"The stats wrongly reported an invalid value. Should be absent instead."
 		);
 	}
+
+	/// Ensure that datetime statistics parse strings with the training timezone and track the range of valid values.
+	#[test]
+	fn datetime_min_max() {
+		let mut stats = DateTimeProductionColumnStats::new("datetime_stat", "America/New_York");
+		stats.update(Some(&Value::Null));
+		stats.update(Some(&Value::String("not a date".to_owned())));
+		stats.update(Some(&Value::String("2022-01-01".to_owned())));
+		stats.update(Some(&Value::from(0)));
+		let stats = stats.finalize();
+		assert_eq!(stats.absent_count, 1);
+		assert_eq!(stats.invalid_count, 1);
+		assert_eq!(stats.min, Some(0));
+		assert_eq!(stats.max, Some(1641013200));
+	}
+
+	/// Ensure that number statistics count values outside the training range and beyond the interquartile range fences.
+	#[test]
+	fn number_outliers() {
+		let training_range = NumberColumnTrainingRange {
+			min: 0.0,
+			max: 100.0,
+			p25: 40.0,
+			p75: 60.0,
+		};
+		let mut stats = NumberProductionColumnStats::new("number_stat");
+		for value in [50.0, 75.0, 95.0, 105.0, -10.0] {
+			stats.update(Some(&Value::from(value)), Some(&training_range), 1.0);
+		}
+		stats.update(Some(&Value::Null), Some(&training_range), 1.0);
+		let stats = stats.finalize();
+		assert_eq!(stats.out_of_range_count, 2);
+		assert_eq!(stats.iqr_outlier_count, 3);
+		assert_eq!(stats.out_of_range_fraction(), Some(0.4));
+		assert_eq!(stats.iqr_outlier_fraction(), Some(0.6));
+	}
+
+	/// Ensure that text statistics count the tokens that are not in the training vocabulary.
+	#[test]
+	fn text_unseen_tokens() {
+		let mut stats = TextProductionColumnStats::new("text_stat");
+		let tokenizer = Tokenizer::default();
+		let ngram_types = [NGramType::Unigram].into_iter().collect();
+		let tracked_ngrams = IndexSet::default();
+		let vocabulary = ["good", "movie"]
+			.into_iter()
+			.map(ToOwned::to_owned)
+			.collect();
+		stats.update(
+			Some(&Value::String("good movie".to_owned())),
+			&tokenizer,
+			&tracked_ngrams,
+			&ngram_types,
+			&vocabulary,
+		);
+		stats.update(
+			Some(&Value::String("terrible movie".to_owned())),
+			&tokenizer,
+			&tracked_ngrams,
+			&ngram_types,
+			&vocabulary,
+		);
+		let stats = stats.finalize();
+		assert_eq!(stats.token_count, 4);
+		assert_eq!(stats.unseen_token_count, 1);
+		assert_eq!(stats.unseen_token_fraction(), Some(0.25));
+	}
+
+	/// Ensure that enum histograms count each value as the number of predictions it stands for.
+	#[test]
+	fn enum_histogram_is_weighted() {
+		let mut stats = EnumProductionColumnStats::new("enum_stat", &["a", "b"]);
+		stats.update(Some(&Value::String("a".to_owned())), 1.0);
+		stats.update(Some(&Value::String("b".to_owned())), 4.0);
+		stats.update(Some(&Value::String("c".to_owned())), 2.0);
+		let stats = stats.finalize();
+		let histogram = stats.histogram.into_iter().collect::<BTreeMap<_, _>>();
+		assert_eq!(histogram["a"], 1);
+		assert_eq!(histogram["b"], 4);
+		assert_eq!(stats.invalid_count, 1);
+		assert_eq!(stats.estimated_invalid_count, 2);
+		assert_eq!(stats.invalid_histogram.unwrap(), vec![("c".to_owned(), 2)]);
+	}
+
+	/// Ensure that enum stats written before histograms were weighted can be read and merged.
+	#[test]
+	fn enum_stats_without_weights() {
+		let legacy = r#"{"absent_count":0,"column_name":"enum_stat","histogram":{"a":3},"invalid_count":1,"invalid_histogram":{"c":1},"row_count":4}"#;
+		let mut stats: EnumProductionColumnStats = serde_json::from_str(legacy).unwrap();
+		let mut other = EnumProductionColumnStats::new("enum_stat", &["a"]);
+		other.update(Some(&Value::String("c".to_owned())), 2.0);
+		stats.merge(other);
+		let stats = stats.finalize();
+		assert_eq!(stats.histogram, vec![("a".to_owned(), 3)]);
+		assert_eq!(stats.invalid_count, 2);
+		assert_eq!(stats.estimated_invalid_count, 3);
+	}
 }
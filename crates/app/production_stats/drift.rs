@@ -0,0 +1,191 @@
+use super::column_stats::ProductionColumnStatsOutput;
+use modelfox_zip::zip;
+use num::ToPrimitive;
+
+/// This value is substituted for empty bins when computing the population stability index, so that the logarithm stays finite.
+const PSI_MIN_PROPORTION: f64 = 0.0001;
+
+#[derive(Debug)]
+pub struct ColumnDrift {
+	pub column_name: String,
+	/// The population stability index of the column's production distribution relative to its training distribution.
+	pub psi: f32,
+}
+
+/// Compute the drift of every column in the production stats relative to the model's training stats, sorted from most to least drifted. Columns whose drift cannot be measured, such as text columns or columns with no valid production values, are omitted.
+pub fn compute_column_drifts(
+	model: modelfox_model::ModelReader,
+	production_column_stats: &[ProductionColumnStatsOutput],
+) -> Vec<ColumnDrift> {
+	let train_column_stats = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			regressor.read().train_column_stats()
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			binary_classifier.read().train_column_stats()
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			multiclass_classifier.read().train_column_stats()
+		}
+	};
+	let mut column_drifts = zip!(train_column_stats.iter(), production_column_stats.iter())
+		.filter_map(|(train_column_stats, production_column_stats)| {
+			let psi = compute_column_psi(train_column_stats, production_column_stats)?;
+			Some(ColumnDrift {
+				column_name: production_column_stats.column_name().to_owned(),
+				psi,
+			})
+		})
+		.collect::<Vec<_>>();
+	column_drifts.sort_by(|a, b| a.psi.partial_cmp(&b.psi).unwrap().reverse());
+	column_drifts
+}
+
+fn compute_column_psi(
+	train_column_stats: modelfox_model::ColumnStatsReader,
+	production_column_stats: &ProductionColumnStatsOutput,
+) -> Option<f32> {
+	match (train_column_stats, production_column_stats) {
+		(
+			modelfox_model::ColumnStatsReader::NumberColumn(train_column_stats),
+			ProductionColumnStatsOutput::Number(production_column_stats),
+		) => {
+			let train_column_stats = train_column_stats.read();
+			let production_stats = production_column_stats.stats.as_ref()?;
+			// Bin the values using the training quartiles, so each training bin holds a quarter of the values. The proportion of production values in each bin is estimated by interpolating between the production quantiles.
+			let bin_boundaries = [
+				train_column_stats.p25(),
+				train_column_stats.p50(),
+				train_column_stats.p75(),
+			];
+			let production_quantiles = [
+				(production_stats.min, 0.0),
+				(production_stats.p25, 0.25),
+				(production_stats.p50, 0.5),
+				(production_stats.p75, 0.75),
+				(production_stats.max, 1.0),
+			];
+			let mut cumulative_proportions = vec![0.0];
+			cumulative_proportions.extend(
+				bin_boundaries
+					.iter()
+					.map(|boundary| interpolate_cdf(&production_quantiles, *boundary)),
+			);
+			cumulative_proportions.push(1.0);
+			let actual = cumulative_proportions
+				.windows(2)
+				.map(|window| window[1] - window[0])
+				.collect::<Vec<_>>();
+			let expected = vec![0.25; actual.len()];
+			Some(compute_psi(&expected, &actual))
+		}
+		(
+			modelfox_model::ColumnStatsReader::EnumColumn(train_column_stats),
+			ProductionColumnStatsOutput::Enum(production_column_stats),
+		) => {
+			let train_column_stats = train_column_stats.read();
+			let train_histogram = train_column_stats.histogram();
+			// Invalid production values are placed in their own bin, which is empty in training.
+			let mut expected = train_histogram
+				.iter()
+				.map(|(_, count)| count.to_f64().unwrap())
+				.collect::<Vec<_>>();
+			expected.push(0.0);
+			let mut actual = train_histogram
+				.iter()
+				.map(|(value, _)| {
+					production_column_stats
+						.histogram
+						.iter()
+						.find(|(production_value, _)| production_value == value)
+						.map(|(_, count)| count.to_f64().unwrap())
+						.unwrap_or(0.0)
+				})
+				.collect::<Vec<_>>();
+			actual.push(
+				production_column_stats
+					.estimated_invalid_count
+					.to_f64()
+					.unwrap(),
+			);
+			let expected_total = expected.iter().sum::<f64>();
+			let actual_total = actual.iter().sum::<f64>();
+			if expected_total == 0.0 || actual_total == 0.0 {
+				return None;
+			}
+			expected
+				.iter_mut()
+				.for_each(|count| *count /= expected_total);
+			actual.iter_mut().for_each(|count| *count /= actual_total);
+			Some(compute_psi(&expected, &actual))
+		}
+		_ => None,
+	}
+}
+
+/// Estimate the proportion of values less than or equal to `value` by linearly interpolating between a sorted list of `(quantile value, proportion)` pairs.
+fn interpolate_cdf(quantiles: &[(f32, f64)], value: f32) -> f64 {
+	let (first_value, _) = quantiles[0];
+	let (last_value, _) = quantiles[quantiles.len() - 1];
+	if value < first_value {
+		return 0.0;
+	}
+	if value >= last_value {
+		return 1.0;
+	}
+	quantiles
+		.windows(2)
+		.find(|window| value < window[1].0)
+		.map(|window| {
+			let (start_value, start_proportion) = window[0];
+			let (end_value, end_proportion) = window[1];
+			let fraction = ((value - start_value) / (end_value - start_value))
+				.to_f64()
+				.unwrap();
+			start_proportion + fraction * (end_proportion - start_proportion)
+		})
+		.unwrap_or(1.0)
+}
+
+/// Compute the population stability index between two binned distributions, each given as the proportion of values in each bin.
+pub fn compute_psi(expected: &[f64], actual: &[f64]) -> f32 {
+	zip!(expected.iter(), actual.iter())
+		.map(|(expected, actual)| {
+			let expected = expected.max(PSI_MIN_PROPORTION);
+			let actual = actual.max(PSI_MIN_PROPORTION);
+			(actual - expected) * (actual / expected).ln()
+		})
+		.sum::<f64>()
+		.to_f32()
+		.unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn identical_distributions_have_no_drift() {
+		let distribution = [0.25, 0.25, 0.25, 0.25];
+		assert_eq!(compute_psi(&distribution, &distribution), 0.0);
+	}
+
+	#[test]
+	fn shifted_distribution_has_drift() {
+		let expected = [0.25, 0.25, 0.25, 0.25];
+		let slightly_shifted = [0.2, 0.25, 0.25, 0.3];
+		let very_shifted = [0.0, 0.0, 0.2, 0.8];
+		let slight_psi = compute_psi(&expected, &slightly_shifted);
+		let large_psi = compute_psi(&expected, &very_shifted);
+		assert!(slight_psi > 0.0 && slight_psi < 0.1);
+		assert!(large_psi > slight_psi);
+	}
+
+	#[test]
+	fn interpolate_cdf_between_quantiles() {
+		let quantiles = [(0.0, 0.0), (1.0, 0.25), (2.0, 0.5), (3.0, 0.75), (4.0, 1.0)];
+		assert_eq!(interpolate_cdf(&quantiles, -1.0), 0.0);
+		assert_eq!(interpolate_cdf(&quantiles, 1.5), 0.375);
+		assert_eq!(interpolate_cdf(&quantiles, 5.0), 1.0);
+	}
+}
@@ -1,7 +1,10 @@
-use std::borrow::BorrowMut;
-
-pub use self::{column_stats::*, number_stats::*, prediction_stats::*};
-use modelfox_app_date_window::{DateWindow, DateWindowInterval};
+use self::rollup::{
+	get_daily_rollup_rows, get_rollup_rows, RollupRow, RollupTable, SECONDS_PER_DAY,
+};
+pub use self::{column_stats::*, drift::*, number_stats::*, prediction_stats::*};
+use modelfox_app_date_window::{
+	get_date_window_bounds, get_date_window_intervals, DateWindow, DateWindowInterval,
+};
 use modelfox_app_monitor_event::PredictionMonitorEvent;
 
 use anyhow::Result;
@@ -9,17 +12,21 @@ use chrono::prelude::*;
 use chrono_tz::Tz;
 use modelfox_zip::zip;
 use num::ToPrimitive;
-use sqlx::prelude::*;
 
 mod column_stats;
+mod drift;
 mod number_stats;
 mod prediction_stats;
+pub mod rollup;
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub struct ProductionStats {
 	pub start_date: DateTime<Utc>,
 	pub end_date: DateTime<Utc>,
 	pub row_count: u64,
+	/// This is the number of predictions the logged rows represent. A row logged with a sample rate counts as `1 / sample_rate` predictions. It is `None` for stats written before prediction logging supported sampling, in which case it is equal to `row_count`.
+	#[serde(default)]
+	pub estimated_row_count: Option<f64>,
 	pub column_stats: Vec<ProductionColumnStats>,
 	pub prediction_stats: ProductionPredictionStats,
 }
@@ -29,6 +36,7 @@ pub struct ProductionStatsOutput {
 	pub start_date: DateTime<Utc>,
 	pub end_date: DateTime<Utc>,
 	pub row_count: u64,
+	pub estimated_row_count: u64,
 	pub column_stats: Vec<ProductionColumnStatsOutput>,
 	pub prediction_stats: ProductionPredictionStatsOutput,
 }
@@ -59,16 +67,31 @@ impl ProductionStats {
 			start_date,
 			end_date,
 			row_count: 0,
+			estimated_row_count: Some(0.0),
 			column_stats,
 			prediction_stats,
 		}
 	}
 
-	pub fn update(&mut self, model: modelfox_model::ModelReader, value: PredictionMonitorEvent) {
+	/// Add a prediction to the stats. The columns in `skipped_column_names`, such as those whose values were redacted, are left out of the column stats. A prediction logged with a sample rate is weighted by `1 / sample_rate` in the estimated row count and the column distributions, so they estimate every prediction the model made rather than only the logged ones.
+	pub fn update(
+		&mut self,
+		model: modelfox_model::ModelReader,
+		value: PredictionMonitorEvent,
+		skipped_column_names: &[String],
+	) {
+		let sample_rate = value.sample_rate.unwrap_or(1.0).to_f64().unwrap();
+		self.estimated_row_count = Some(self.estimated_row_count() + 1.0 / sample_rate);
 		self.row_count += 1;
 		for column_stats in self.column_stats.iter_mut() {
+			if skipped_column_names
+				.iter()
+				.any(|column_name| column_name == column_stats.column_name())
+			{
+				continue;
+			}
 			let value = value.input.get(column_stats.column_name());
-			column_stats.update(model, value);
+			column_stats.update(model, value, 1.0 / sample_rate);
 		}
 		self.prediction_stats.update(value.output);
 	}
@@ -76,6 +99,7 @@ impl ProductionStats {
 	pub fn merge(&mut self, other: ProductionStats) {
 		self.start_date = self.start_date.min(other.start_date);
 		self.end_date = self.end_date.max(other.end_date);
+		self.estimated_row_count = Some(self.estimated_row_count() + other.estimated_row_count());
 		self.row_count += other.row_count;
 		for (this, other) in zip!(self.column_stats.iter_mut(), other.column_stats) {
 			this.merge(other)
@@ -83,11 +107,17 @@ impl ProductionStats {
 		self.prediction_stats.merge(other.prediction_stats);
 	}
 
+	fn estimated_row_count(&self) -> f64 {
+		self.estimated_row_count
+			.unwrap_or_else(|| self.row_count.to_f64().unwrap())
+	}
+
 	pub fn finalize(self) -> ProductionStatsOutput {
 		ProductionStatsOutput {
 			start_date: self.start_date,
 			end_date: self.end_date,
 			row_count: self.row_count,
+			estimated_row_count: self.estimated_row_count().round().to_u64().unwrap(),
 			column_stats: self
 				.column_stats
 				.into_iter()
@@ -104,6 +134,8 @@ pub struct GetProductionStatsOutput {
 	pub date_window_interval: DateWindowInterval,
 	pub overall: ProductionStatsOutput,
 	pub intervals: Vec<ProductionStatsOutput>,
+	/// This is set when the stats were read from the daily rollups alone, such as when reading all of them took too long. The stats are then missing the predictions made after this time.
+	pub rolled_up_through: Option<DateTime<Utc>>,
 }
 
 pub async fn get_production_stats(
@@ -113,96 +145,93 @@ pub async fn get_production_stats(
 	date_window_interval: DateWindowInterval,
 	timezone: Tz,
 ) -> Result<GetProductionStatsOutput> {
-	// Compute the start date given the date window.
-	// * For today, use the start of this day in this timezone.
-	// * For this month, use the start of this month in this timezone.
-	// * For this year, use the start of this year in this timezone.
-	let now: DateTime<Tz> = Utc::now().with_timezone(&timezone);
-	let start_date = match date_window {
-		DateWindow::Today => timezone
-			.ymd(now.year(), now.month(), now.day())
-			.and_hms(0, 0, 0),
-		DateWindow::ThisMonth => timezone.ymd(now.year(), now.month(), 1).and_hms(0, 0, 0),
-		DateWindow::ThisYear => timezone.ymd(now.year(), 1, 1).and_hms(0, 0, 0),
-	};
-	let end_date = match date_window {
-		DateWindow::Today => start_date + chrono::Duration::days(1),
-		DateWindow::ThisMonth => {
-			start_date
-				+ chrono::Duration::days(n_days_in_month(start_date.year(), start_date.month()))
-		}
-		DateWindow::ThisYear => timezone.ymd(now.year() + 1, 1, 1).and_hms(0, 0, 0),
-	};
-	// Retrieve the production stats for the date window.
-	let rows = sqlx::query(
-		"
-			select
-				data,
-				hour
-			from production_stats
-			where
-				model_id = $1 and
-				hour >= $2 and
-				hour < $3
-			order by hour
-		",
+	get_production_stats_inner(
+		txn,
+		model,
+		date_window,
+		date_window_interval,
+		timezone,
+		false,
 	)
-	.bind(&model.id().to_string())
-	.bind(&start_date.timestamp())
-	.bind(&end_date.timestamp())
-	.fetch_all(txn.borrow_mut())
-	.await?;
-	// Compute the number of intervals.
-	// * For today, use 24.
-	// * For this month, use the number of days in this month.
-	// * For this year, use 12.
-	let n_intervals: usize = match date_window_interval {
-		DateWindowInterval::Hourly => 24,
-		DateWindowInterval::Daily => n_days_in_month(start_date.year(), start_date.month())
-			.to_usize()
-			.unwrap(),
-		DateWindowInterval::Monthly => 12,
-	};
-	// Initialize the intervals with start and end dates.
-	let mut intervals: Vec<ProductionStats> = (0..n_intervals.to_u32().unwrap())
-		.map(|i| {
-			// Determine the start and end dates for the interval.
-			let start = match date_window_interval {
-				DateWindowInterval::Hourly => start_date.with_hour(i).unwrap(),
-				DateWindowInterval::Daily => start_date.with_day0(i).unwrap(),
-				DateWindowInterval::Monthly => start_date.with_month0(i).unwrap(),
-			};
-			let end = match date_window_interval {
-				DateWindowInterval::Hourly => start + chrono::Duration::hours(1),
-				DateWindowInterval::Daily => start + chrono::Duration::days(1),
-				DateWindowInterval::Monthly => {
-					start + chrono::Duration::days(n_days_in_month(start.year(), start.month()))
-				}
-			};
-			ProductionStats::new(model, start.with_timezone(&Utc), end.with_timezone(&Utc))
+	.await
+}
+
+/// Get the production stats from the daily rollups alone. This is the fallback for when [`get_production_stats`] takes too long, because it reads at most one row per day, but the stats are missing the predictions that have not been rolled up yet.
+pub async fn get_rolled_up_production_stats(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model: modelfox_model::ModelReader<'_>,
+	date_window: DateWindow,
+	date_window_interval: DateWindowInterval,
+	timezone: Tz,
+) -> Result<GetProductionStatsOutput> {
+	get_production_stats_inner(
+		txn,
+		model,
+		date_window,
+		date_window_interval,
+		timezone,
+		true,
+	)
+	.await
+}
+
+async fn get_production_stats_inner(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	model: modelfox_model::ModelReader<'_>,
+	date_window: DateWindow,
+	date_window_interval: DateWindowInterval,
+	timezone: Tz,
+	rolled_up_only: bool,
+) -> Result<GetProductionStatsOutput> {
+	let now: DateTime<Tz> = Utc::now().with_timezone(&timezone);
+	let (start_date, end_date) = get_date_window_bounds(date_window, now);
+	let mut intervals: Vec<ProductionStats> =
+		get_date_window_intervals(date_window, date_window_interval, now)
+			.into_iter()
+			.map(|(start, end)| {
+				ProductionStats::new(model, start.with_timezone(&Utc), end.with_timezone(&Utc))
+			})
+			.collect();
+	// Retrieve the hourly and daily production stats for the date window.
+	let interval_bounds = intervals
+		.iter()
+		.map(|interval| {
+			(
+				interval.start_date.timestamp(),
+				interval.end_date.timestamp(),
+			)
 		})
-		.collect();
-	// Merge each hourly production stats entry into its corresponding interval.
+		.collect::<Vec<_>>();
+	let model_id = model.id().to_string();
+	let (rows, rolled_up_through) = if rolled_up_only {
+		let rows = get_daily_rollup_rows(
+			txn,
+			RollupTable::ProductionStats,
+			&model_id,
+			&interval_bounds,
+		)
+		.await?;
+		let rolled_up_through = rolled_up_through(&rows, start_date.with_timezone(&Utc));
+		(rows, Some(rolled_up_through))
+	} else {
+		let rows = get_rollup_rows(
+			txn,
+			RollupTable::ProductionStats,
+			&model_id,
+			&interval_bounds,
+		)
+		.await?;
+		(rows, None)
+	};
+	// Merge each hourly or daily production stats entry into the interval that contains it.
 	for row in rows {
-		let data: String = row.get(0);
-		let hour: i64 = row.get(1);
-		let hour = timezone.timestamp(hour, 0);
-		let interval = match date_window_interval {
-			DateWindowInterval::Hourly => {
-				let hour = hour.hour().to_usize().unwrap();
-				intervals.get_mut(hour).unwrap()
-			}
-			DateWindowInterval::Daily => {
-				let day = hour.day0().to_usize().unwrap();
-				intervals.get_mut(day).unwrap()
-			}
-			DateWindowInterval::Monthly => {
-				let month = hour.month0().to_usize().unwrap();
-				intervals.get_mut(month).unwrap()
-			}
+		let interval_index = interval_bounds.partition_point(|(_, end)| *end <= row.timestamp);
+		let interval = match intervals.get_mut(interval_index) {
+			Some(interval) => interval,
+			None => continue,
 		};
-		let hourly_production_stats = serde_json::from_str(&data)?;
-		interval.merge(hourly_production_stats);
+		let production_stats = serde_json::from_str(&row.data)?;
+		interval.merge(production_stats);
 	}
 	// Compute the overall production stats by merging all the intervals together.
 	let overall = intervals
@@ -229,16 +258,16 @@ pub async fn get_production_stats(
 		date_window_interval,
 		overall,
 		intervals,
+		rolled_up_through,
 	})
 }
 
-fn n_days_in_month(year: i32, month: u32) -> i64 {
-	let (end_year, end_month) = if month == 12 {
-		(year + 1, 1)
-	} else {
-		(year, month + 1)
-	};
-	let start = NaiveDate::from_ymd(year, month, 1);
-	let end = NaiveDate::from_ymd(end_year, end_month, 1);
-	(end - start).num_days()
+/// Get the end of the last day in the daily rows, or the start of the date window if there are none.
+fn rolled_up_through(rows: &[RollupRow], start_date: DateTime<Utc>) -> DateTime<Utc> {
+	rows.iter()
+		.map(|row| row.timestamp + SECONDS_PER_DAY)
+		.max()
+		.map(|timestamp| Utc.timestamp(timestamp, 0))
+		.unwrap_or(start_date)
+		.max(start_date)
 }
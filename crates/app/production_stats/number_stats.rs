@@ -1,6 +1,5 @@
-use modelfox_zip::zip;
+use modelfox_metrics::QuantileSketch;
 use num::ToPrimitive;
-use rand::random;
 use std::num::NonZeroU64;
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
@@ -10,9 +9,12 @@ pub struct NumberStats {
 	pub max: f32,
 	pub mean: f64,
 	pub m2: f64,
-	/// Keep a reservoir of random samples to get an estimate for quantiles.
+	/// This is a sketch of the values, which is used to estimate quantiles. Unlike a sample of the values, it can be merged across hours and days without losing precision.
+	#[serde(default)]
+	pub sketch: QuantileSketch,
+	/// Rows written before the sketch was added kept a reservoir of random samples to estimate quantiles instead. The samples are moved into the sketch when the stats are merged or finalized.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
 	pub reservoir: Vec<f32>,
-	pub reservoir_max_size: usize,
 }
 
 #[derive(Debug)]
@@ -26,22 +28,44 @@ pub struct NumberStatsOutput {
 	pub p25: f32,
 	pub p50: f32,
 	pub p75: f32,
+	/// This is used to estimate quantiles other than the quartiles.
+	pub sketch: QuantileSketch,
+}
+
+impl NumberStatsOutput {
+	/// Estimate the value of the given quantile.
+	pub fn quantile(&self, quantile: f32) -> f32 {
+		self.sketch.quantile(quantile).unwrap()
+	}
 }
 
 impl NumberStats {
 	pub fn new(value: f32) -> NumberStats {
+		NumberStats::new_weighted(value, 1.0)
+	}
+
+	/// Create stats with a single value that stands for `weight` values in the quantile sketch. The count, mean, and variance are of the values that were added, regardless of their weights.
+	pub fn new_weighted(value: f32, weight: f64) -> NumberStats {
+		let mut sketch = QuantileSketch::default();
+		sketch.insert_weighted(value, weight);
 		NumberStats {
 			n: 1,
 			min: value,
 			max: value,
 			mean: value as f64,
 			m2: 0.0,
-			reservoir: vec![value],
-			reservoir_max_size: 100,
+			sketch,
+			reservoir: Vec::new(),
 		}
 	}
 
 	pub fn update(&mut self, value: f32) {
+		self.update_weighted(value, 1.0)
+	}
+
+	/// Add a value that stands for `weight` values in the quantile sketch. See [`NumberStats::new_weighted`].
+	pub fn update_weighted(&mut self, value: f32, weight: f64) {
+		self.move_reservoir_into_sketch();
 		let (new_mean, new_m2) =
 			modelfox_metrics::merge_mean_m2(self.n, self.mean, self.m2, 1, value as f64, 0.0);
 		self.n += 1;
@@ -49,20 +73,12 @@ impl NumberStats {
 		self.m2 = new_m2;
 		self.min = f32::min(self.min, value);
 		self.max = f32::max(self.max, value);
-		if self.reservoir.len() < self.reservoir_max_size {
-			self.reservoir.push(value)
-		} else {
-			let index = (random::<f32>() * self.n.to_f32().unwrap())
-				.floor()
-				.to_usize()
-				.unwrap();
-			if index < self.reservoir_max_size {
-				self.reservoir[index] = value;
-			}
-		}
+		self.sketch.insert_weighted(value, weight);
 	}
 
-	pub fn merge(&mut self, other: NumberStats) {
+	pub fn merge(&mut self, mut other: NumberStats) {
+		self.move_reservoir_into_sketch();
+		other.move_reservoir_into_sketch();
 		let (new_mean, new_m2) = modelfox_metrics::merge_mean_m2(
 			self.n, self.mean, self.m2, other.n, other.mean, other.m2,
 		);
@@ -71,49 +87,51 @@ impl NumberStats {
 		self.m2 = new_m2;
 		self.min = f32::min(self.min, other.min);
 		self.max = f32::max(self.max, other.max);
-		self.reservoir.extend(other.reservoir);
+		self.sketch.merge(other.sketch);
 	}
 
-	pub fn finalize(self) -> NumberStatsOutput {
-		let reservoir_len = self.reservoir.len().to_f32().unwrap();
-		let quantiles: Vec<f32> = vec![0.25, 0.50, 0.75];
-		// Find the index of each quantile given the total number of values in the dataset.
-		let quantile_indexes: Vec<usize> = quantiles
-			.iter()
-			.map(|q| ((reservoir_len - 1.0) * q).trunc().to_usize().unwrap())
-			.collect();
-		// This is the fractiononal part of the index used to interpolate values if the index is not an integer value.
-		let quantile_fracts: Vec<f32> = quantiles
-			.iter()
-			.map(|q| ((reservoir_len - 1.0) * q).fract())
-			.collect();
-		let mut quantiles: Vec<f32> = vec![0.0; quantiles.len()];
-		let mut samples = self.reservoir;
-		samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-		for (quantile, index, fract) in zip!(
-			quantiles.iter_mut(),
-			quantile_indexes.iter(),
-			quantile_fracts.iter(),
-		) {
-			let value = samples[*index];
-			if *fract > 0.0 {
-				let next_value = samples[index + 1];
-				// Interpolate between two values.
-				*quantile = value * (1.0 - fract) + next_value * fract;
-			} else {
-				*quantile = value;
-			}
-		}
+	pub fn finalize(mut self) -> NumberStatsOutput {
+		self.move_reservoir_into_sketch();
+		let quartiles = self.sketch.quantiles(&[0.25, 0.50, 0.75]).unwrap();
 		NumberStatsOutput {
 			n: self.n,
-			p25: quantiles[0],
-			p50: quantiles[1],
-			p75: quantiles[2],
+			p25: quartiles[0],
+			p50: quartiles[1],
+			p75: quartiles[2],
 			mean: self.mean.to_f32().unwrap(),
 			variance: modelfox_metrics::m2_to_variance(self.m2, NonZeroU64::new(self.n).unwrap()),
 			std: modelfox_metrics::m2_to_variance(self.m2, NonZeroU64::new(self.n).unwrap()).sqrt(),
 			min: self.min,
 			max: self.max,
+			sketch: self.sketch,
 		}
 	}
+
+	/// Each sample in the reservoir of a row written before the sketch was added stands for an equal share of the row's values.
+	fn move_reservoir_into_sketch(&mut self) {
+		if self.reservoir.is_empty() {
+			return;
+		}
+		let weight = self.n.to_f64().unwrap() / self.reservoir.len().to_f64().unwrap();
+		for value in std::mem::take(&mut self.reservoir) {
+			self.sketch.insert_weighted(value, weight);
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_rows_with_reservoirs() {
+		let legacy = r#"{"n":4,"min":1,"max":7,"mean":4,"m2":20,"reservoir":[1,3,5,7],"reservoir_max_size":100}"#;
+		let mut stats: NumberStats = serde_json::from_str(legacy).unwrap();
+		stats.merge(NumberStats::new(9.0));
+		assert!(stats.reservoir.is_empty());
+		let output = stats.finalize();
+		assert_eq!(output.n, 5);
+		assert_eq!(output.p50, 5.0);
+		assert_eq!(output.quantile(1.0), 9.0);
+	}
 }
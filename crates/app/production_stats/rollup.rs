@@ -0,0 +1,316 @@
+/*!
+Production stats and metrics are stored in hourly rows, which are updated as events are tracked, and in daily rows, which roll up the hourly rows of each complete UTC day. A background task writes the daily rows, so reading a long date window deserializes one row per day instead of one row per hour.
+*/
+
+use anyhow::Result;
+use sqlx::prelude::*;
+use std::borrow::BorrowMut;
+
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+#[derive(Clone, Copy, Debug)]
+pub enum RollupTable {
+	ProductionStats,
+	ProductionMetrics,
+}
+
+impl RollupTable {
+	pub fn hourly_table_name(&self) -> &'static str {
+		match self {
+			RollupTable::ProductionStats => "production_stats",
+			RollupTable::ProductionMetrics => "production_metrics",
+		}
+	}
+
+	pub fn daily_table_name(&self) -> &'static str {
+		match self {
+			RollupTable::ProductionStats => "production_stats_daily",
+			RollupTable::ProductionMetrics => "production_metrics_daily",
+		}
+	}
+}
+
+/// This is an hourly or daily row, along with the timestamp of the hour or day it starts at.
+pub struct RollupRow {
+	pub timestamp: i64,
+	pub data: String,
+}
+
+/// Get the start of the UTC day containing the timestamp.
+pub fn day_for_timestamp(timestamp: i64) -> i64 {
+	timestamp - timestamp.rem_euclid(SECONDS_PER_DAY)
+}
+
+/// Get the rows covering a date window, given the start and end timestamps of each of the window's intervals. A day's rollup is only used when the day falls entirely within one interval, so intervals that do not align with UTC days, such as days in other timezones, are assembled from hourly rows.
+pub async fn get_rollup_rows(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	table: RollupTable,
+	model_id: &str,
+	intervals: &[(i64, i64)],
+) -> Result<Vec<RollupRow>> {
+	let (start, end) = match (intervals.first(), intervals.last()) {
+		(Some((start, _)), Some((_, end))) => (*start, *end),
+		_ => return Ok(Vec::new()),
+	};
+	let mut rows = Vec::new();
+	// Retrieve the daily rows for the days that overlap the window.
+	let daily_rows = get_daily_rows(txn, table, model_id, start, end).await?;
+	let mut split_days = Vec::new();
+	for row in daily_rows {
+		if is_day_within_interval(intervals, row.timestamp) {
+			rows.push(row);
+		} else {
+			split_days.push(row.timestamp);
+		}
+	}
+	// Retrieve the hourly rows for the days that have not been rolled up yet.
+	let hourly_rows = sqlx::query(&format!(
+		"
+			select
+				hourly.hour,
+				hourly.data
+			from {} hourly
+			where
+				hourly.model_id = $1 and
+				hourly.hour >= $2 and
+				hourly.hour < $3 and
+				not exists (
+					select 1
+					from {} daily
+					where
+						daily.model_id = hourly.model_id and
+						daily.day = hourly.hour - hourly.hour % {}
+				)
+			order by hourly.hour
+		",
+		table.hourly_table_name(),
+		table.daily_table_name(),
+		SECONDS_PER_DAY,
+	))
+	.bind(model_id)
+	.bind(start)
+	.bind(end)
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.extend(hourly_rows.iter().map(|row| RollupRow {
+		timestamp: row.get(0),
+		data: row.get(1),
+	}));
+	// Retrieve the hourly rows for the days whose rollup spans more than one interval.
+	for day in split_days {
+		let hourly_rows = sqlx::query(&format!(
+			"
+				select
+					hour,
+					data
+				from {}
+				where
+					model_id = $1 and
+					hour >= $2 and
+					hour < $3
+				order by hour
+			",
+			table.hourly_table_name(),
+		))
+		.bind(model_id)
+		.bind(day.max(start))
+		.bind((day + SECONDS_PER_DAY).min(end))
+		.fetch_all(txn.borrow_mut())
+		.await?;
+		rows.extend(hourly_rows.iter().map(|row| RollupRow {
+			timestamp: row.get(0),
+			data: row.get(1),
+		}));
+	}
+	Ok(rows)
+}
+
+/// Get only the daily rows covering a date window. This is much cheaper than [`get_rollup_rows`], but it leaves out the hours that have not been rolled up yet and the days whose rollup spans more than one interval.
+pub async fn get_daily_rollup_rows(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	table: RollupTable,
+	model_id: &str,
+	intervals: &[(i64, i64)],
+) -> Result<Vec<RollupRow>> {
+	let (start, end) = match (intervals.first(), intervals.last()) {
+		(Some((start, _)), Some((_, end))) => (*start, *end),
+		_ => return Ok(Vec::new()),
+	};
+	let rows = get_daily_rows(txn, table, model_id, start, end)
+		.await?
+		.into_iter()
+		.filter(|row| is_day_within_interval(intervals, row.timestamp))
+		.collect();
+	Ok(rows)
+}
+
+async fn get_daily_rows(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	table: RollupTable,
+	model_id: &str,
+	start: i64,
+	end: i64,
+) -> Result<Vec<RollupRow>> {
+	let rows = sqlx::query(&format!(
+		"
+			select
+				day,
+				data
+			from {}
+			where
+				model_id = $1 and
+				day > $2 and
+				day < $3
+			order by day
+		",
+		table.daily_table_name(),
+	))
+	.bind(model_id)
+	.bind(start - SECONDS_PER_DAY)
+	.bind(end)
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	let rows = rows
+		.iter()
+		.map(|row| RollupRow {
+			timestamp: row.get(0),
+			data: row.get(1),
+		})
+		.collect();
+	Ok(rows)
+}
+
+fn is_day_within_interval(intervals: &[(i64, i64)], day: i64) -> bool {
+	intervals.iter().any(|(interval_start, interval_end)| {
+		*interval_start <= day && day + SECONDS_PER_DAY <= *interval_end
+	})
+}
+
+/// Get the hourly rows for a UTC day, so they can be rolled up into a daily row.
+pub async fn get_hourly_rows_for_day(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	table: RollupTable,
+	model_id: &str,
+	day: i64,
+) -> Result<Vec<String>> {
+	let rows = sqlx::query(&format!(
+		"
+			select
+				data
+			from {}
+			where
+				model_id = $1 and
+				hour >= $2 and
+				hour < $3
+			order by hour
+		",
+		table.hourly_table_name(),
+	))
+	.bind(model_id)
+	.bind(day)
+	.bind(day + SECONDS_PER_DAY)
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Find complete UTC days before `before` that have hourly rows but no rollup. This includes the days tracked before rollups were added, so running this repeatedly backfills them.
+pub async fn get_days_to_roll_up(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	table: RollupTable,
+	before: i64,
+	limit: i64,
+) -> Result<Vec<(String, i64)>> {
+	let rows = sqlx::query(&format!(
+		"
+			select distinct
+				hourly.model_id,
+				hourly.hour - hourly.hour % {} as day
+			from {} hourly
+			where
+				hourly.hour < $1 and
+				not exists (
+					select 1
+					from {} daily
+					where
+						daily.model_id = hourly.model_id and
+						daily.day = hourly.hour - hourly.hour % {}
+				)
+			limit $2
+		",
+		SECONDS_PER_DAY,
+		table.hourly_table_name(),
+		table.daily_table_name(),
+		SECONDS_PER_DAY,
+	))
+	.bind(day_for_timestamp(before))
+	.bind(limit)
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+}
+
+pub async fn write_daily_row(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	table: RollupTable,
+	model_id: &str,
+	day: i64,
+	data: &str,
+) -> Result<()> {
+	sqlx::query(&format!(
+		"
+			insert into {}
+				(model_id, day, data)
+			values
+				($1, $2, $3)
+		",
+		table.daily_table_name(),
+	))
+	.bind(model_id)
+	.bind(day)
+	.bind(data)
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+/// Delete the rollup for the day containing the timestamp. This is called when an event updates an hourly row, so that events tracked late for a day that was already rolled up are included when the day is rolled up again.
+pub async fn invalidate_daily_row(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	table: RollupTable,
+	model_id: &str,
+	timestamp: i64,
+) -> Result<()> {
+	sqlx::query(&format!(
+		"
+			delete from {}
+			where
+				model_id = $1 and
+				day = $2
+		",
+		table.daily_table_name(),
+	))
+	.bind(model_id)
+	.bind(day_for_timestamp(timestamp))
+	.execute(txn.borrow_mut())
+	.await?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_day_for_timestamp() {
+		// 2022-06-08T00:00:00Z
+		let day = 1_654_646_400;
+		assert_eq!(day_for_timestamp(day), day);
+		assert_eq!(day_for_timestamp(day + 13 * 60 * 60), day);
+		assert_eq!(day_for_timestamp(day + SECONDS_PER_DAY - 1), day);
+		assert_eq!(
+			day_for_timestamp(day + SECONDS_PER_DAY),
+			day + SECONDS_PER_DAY
+		);
+	}
+}
@@ -0,0 +1,56 @@
+use crate::BatchPredictJobResponse;
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	batch_predict::get_batch_predict_job,
+	error::{not_found, service_unavailable, unauthorized},
+	path_components,
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let (model_id, job_id) = if let ["api", "models", model_id, "batch_predict", job_id] =
+		*path_components(request).as_slice()
+	{
+		(model_id.to_owned(), job_id.to_owned())
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(unauthorized()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(not_found()),
+	};
+	let job_id: Id = match job_id.parse() {
+		Ok(job_id) => job_id,
+		Err(_) => return Ok(not_found()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let job = match get_batch_predict_job(&mut db, model_id, job_id).await? {
+		Some(job) => job,
+		None => return Ok(not_found()),
+	};
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.header(http::header::CONTENT_TYPE, "application/json")
+		.body(hyper::Body::from(serde_json::to_vec(
+			&BatchPredictJobResponse::from(job),
+		)?))
+		.unwrap();
+	Ok(response)
+}
@@ -0,0 +1,164 @@
+use chrono::prelude::*;
+use futures::FutureExt;
+use modelfox_app_base_path::app_path;
+use modelfox_app_core::{
+	batch_predict::{BatchPredictJob, BatchPredictJobStatus},
+	error::method_not_allowed,
+};
+use modelfox_app_openapi::{
+	Method, Operation, Parameter, ParameterLocation, Property, Response, Schema, SchemaKind, Type,
+};
+
+mod get;
+
+pub fn init() -> sunfish::Route {
+	sunfish::Route::new_dynamic(|request| match *request.method() {
+		http::Method::GET => self::get::get(request).boxed(),
+		_ => async { Ok(method_not_allowed()) }.boxed(),
+	})
+}
+
+/// This is the json representation of a batch predict job, which the create and status endpoints respond with.
+#[derive(serde::Serialize)]
+pub struct BatchPredictJobResponse {
+	pub id: String,
+	pub model_id: String,
+	pub status: &'static str,
+	pub format: &'static str,
+	pub created_at: DateTime<Utc>,
+	pub started_at: Option<DateTime<Utc>>,
+	pub finished_at: Option<DateTime<Utc>>,
+	pub row_count: Option<i64>,
+	pub error: Option<String>,
+	pub result_url: Option<String>,
+}
+
+impl From<BatchPredictJob> for BatchPredictJobResponse {
+	fn from(job: BatchPredictJob) -> BatchPredictJobResponse {
+		let result_url = if job.status == BatchPredictJobStatus::Succeeded {
+			Some(app_path(format!(
+				"/api/models/{}/batch_predict/{}/result",
+				job.model_id, job.id
+			)))
+		} else {
+			None
+		};
+		BatchPredictJobResponse {
+			id: job.id.to_string(),
+			model_id: job.model_id.to_string(),
+			status: job.status.as_str(),
+			format: job.format.as_str(),
+			created_at: Utc.timestamp(job.created_at, 0),
+			started_at: job
+				.started_at
+				.map(|started_at| Utc.timestamp(started_at, 0)),
+			finished_at: job
+				.finished_at
+				.map(|finished_at| Utc.timestamp(finished_at, 0)),
+			row_count: job.row_count,
+			error: job.error,
+			result_url,
+		}
+	}
+}
+
+/// These are the operations this route serves, for the app's OpenAPI document. Creating a job and downloading its result send and receive CSV or NDJSON files instead of json, so they are documented in the docs instead.
+pub const OPERATIONS: &[Operation] = &[Operation {
+	method: Method::Get,
+	path: "/api/models/{model_id}/batch_predict/{job_id}",
+	operation_id: "get_batch_predict_job",
+	summary: "Get the status of a batch predict job.",
+	parameters: &[
+		Parameter {
+			name: "model_id",
+			location: ParameterLocation::Path,
+			required: true,
+			ty: Type::String,
+			description: "",
+		},
+		Parameter {
+			name: "job_id",
+			location: ParameterLocation::Path,
+			required: true,
+			ty: Type::String,
+			description: "",
+		},
+	],
+	request_body: None,
+	response: Response {
+		status: 200,
+		description: "",
+		body: Some(Type::Ref("BatchPredictJob")),
+	},
+	authenticated: true,
+}];
+
+/// These are the schemas of this route's responses, for the app's OpenAPI document.
+pub const SCHEMAS: &[Schema] = &[Schema {
+	name: "BatchPredictJob",
+	description: "",
+	kind: SchemaKind::Object(&[
+		Property {
+			name: "id",
+			ty: Type::String,
+			required: true,
+			description: "",
+		},
+		Property {
+			name: "model_id",
+			ty: Type::String,
+			required: true,
+			description: "",
+		},
+		Property {
+			name: "status",
+			ty: Type::String,
+			required: true,
+			description: "This is one of queued, running, succeeded, and failed.",
+		},
+		Property {
+			name: "format",
+			ty: Type::String,
+			required: true,
+			description: "This is csv or ndjson. The result has the same format as the input.",
+		},
+		Property {
+			name: "created_at",
+			ty: Type::DateTime,
+			required: true,
+			description: "",
+		},
+		Property {
+			name: "started_at",
+			ty: Type::DateTime,
+			required: false,
+			description: "",
+		},
+		Property {
+			name: "finished_at",
+			ty: Type::DateTime,
+			required: false,
+			description: "",
+		},
+		Property {
+			name: "row_count",
+			ty: Type::Integer,
+			required: false,
+			description:
+				"This is the number of rows that were scored. It is set when the job succeeds.",
+		},
+		Property {
+			name: "error",
+			ty: Type::String,
+			required: false,
+			description: "This is the reason the job failed.",
+		},
+		Property {
+			name: "result_url",
+			ty: Type::String,
+			required: false,
+			description:
+				"This is the path to download the result from. It is set when the job succeeds.",
+		},
+	]),
+}];
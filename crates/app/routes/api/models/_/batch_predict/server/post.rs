@@ -0,0 +1,94 @@
+use anyhow::{bail, Result};
+use modelfox_app_api_model_batch_predict_job_server::BatchPredictJobResponse;
+use modelfox_app_base_path::app_path;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	batch_predict::{
+		create_batch_predict_job_from_path, get_batch_predict_job, BatchPredictFormat,
+	},
+	error::{bad_request, not_found, payload_too_large, service_unavailable, unauthorized},
+	path_components,
+	request_body::{is_body_size_limit_exceeded, write_stream_to_temp_file},
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+/// Queue a job to score the CSV or NDJSON file in the request body. The content type of the request says which format the file is in. The response has the job, and its `Location` header has the path to poll for the job's status.
+pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id = if let ["api", "models", model_id, "batch_predict"] =
+		*path_components(request).as_slice()
+	{
+		model_id.to_owned()
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(unauthorized()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(not_found()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let content_type = request
+		.headers()
+		.get(http::header::CONTENT_TYPE)
+		.and_then(|content_type| content_type.to_str().ok())
+		.and_then(|content_type| content_type.split(';').next())
+		.map(|content_type| content_type.trim().to_lowercase());
+	let format = match content_type.as_deref() {
+		Some("text/csv") => BatchPredictFormat::Csv,
+		Some("application/x-ndjson") | Some("application/jsonl") => BatchPredictFormat::Ndjson,
+		_ => {
+			return Ok(http::Response::builder()
+				.status(http::StatusCode::UNSUPPORTED_MEDIA_TYPE)
+				.body(hyper::Body::from(
+					"The content type must be text/csv or application/x-ndjson.",
+				))
+				.unwrap())
+		}
+	};
+	// The file is streamed to disk rather than buffered in memory. Its size is limited by the batch predict body size limit.
+	let file = match write_stream_to_temp_file(request.body_mut()).await {
+		Ok(file) => file,
+		Err(error) if is_body_size_limit_exceeded(&error) => return Ok(payload_too_large()),
+		Err(_) => return Ok(bad_request()),
+	};
+	let job_id = create_batch_predict_job_from_path(
+		&mut db,
+		app.storage(),
+		app.clock(),
+		model_id,
+		format,
+		file.path(),
+	)
+	.await?;
+	let job = get_batch_predict_job(&mut db, model_id, job_id)
+		.await?
+		.unwrap();
+	app.commit_transaction(db).await?;
+	app.notify_batch_predict_job_created()?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::ACCEPTED)
+		.header(http::header::CONTENT_TYPE, "application/json")
+		.header(
+			http::header::LOCATION,
+			app_path(format!("/api/models/{}/batch_predict/{}", model_id, job_id)),
+		)
+		.body(hyper::Body::from(serde_json::to_vec(
+			&BatchPredictJobResponse::from(job),
+		)?))
+		.unwrap();
+	Ok(response)
+}
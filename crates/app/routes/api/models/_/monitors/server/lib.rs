@@ -0,0 +1,211 @@
+use futures::FutureExt;
+use modelfox_app_core::error::method_not_allowed;
+use modelfox_app_openapi::{
+	Method, Operation, Parameter, ParameterLocation, Property, Response, Schema, SchemaKind, Type,
+};
+
+mod put;
+
+pub fn init() -> sunfish::Route {
+	sunfish::Route::new_dynamic(|request| match *request.method() {
+		http::Method::PUT => self::put::put(request).boxed(),
+		_ => async { Ok(method_not_allowed()) }.boxed(),
+	})
+}
+
+/// This is the operation this route serves, for the app's OpenAPI document.
+pub const OPERATIONS: &[Operation] = &[Operation {
+	method: Method::Put,
+	path: "/api/models/{model_id}/monitors",
+	operation_id: "put_monitors",
+	summary: "Create a model's monitors, or update them if they have the id of one of the model's existing monitors.",
+	parameters: &[Parameter {
+		name: "model_id",
+		location: ParameterLocation::Path,
+		required: true,
+		ty: Type::String,
+		description: "",
+	}],
+	request_body: Some(Type::Array(&Type::Ref("MonitorRequest"))),
+	response: Response {
+		status: 200,
+		description: "The monitors were created or updated. The response has their ids in the order of the request.",
+		body: Some(Type::Array(&Type::Ref("MonitorResponse"))),
+	},
+	authenticated: true,
+}];
+
+/// These are the schemas of this route's requests and responses, for the app's OpenAPI document.
+pub const SCHEMAS: &[Schema] = &[
+	Schema {
+		name: "MonitorRequest",
+		description: "",
+		kind: SchemaKind::Object(&[
+			Property {
+				name: "id",
+				ty: Type::String,
+				required: false,
+				description: "This is the id of the monitor to update. If it is absent, a monitor is created.",
+			},
+			Property {
+				name: "title",
+				ty: Type::String,
+				required: false,
+				description: "",
+			},
+			Property {
+				name: "cadence",
+				ty: Type::String,
+				required: true,
+				description: "This is one of hourly, daily, weekly, or monthly.",
+			},
+			Property {
+				name: "metric",
+				ty: Type::String,
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "mode",
+				ty: Type::String,
+				required: false,
+				description: "This is absolute or percentage. It defaults to absolute.",
+			},
+			Property {
+				name: "threshold_lower",
+				ty: Type::Number,
+				required: false,
+				description: "",
+			},
+			Property {
+				name: "threshold_upper",
+				ty: Type::Number,
+				required: false,
+				description: "",
+			},
+			Property {
+				name: "methods",
+				ty: Type::Array(&Type::Ref("AlertMethod")),
+				required: false,
+				description: "",
+			},
+			Property {
+				name: "minimum_sample_size",
+				ty: Type::Integer,
+				required: false,
+				description: "",
+			},
+			Property {
+				name: "warn_below_minimum_sample_size",
+				ty: Type::Boolean,
+				required: false,
+				description: "",
+			},
+			Property {
+				name: "cooldown_periods",
+				ty: Type::Integer,
+				required: false,
+				description: "",
+			},
+			Property {
+				name: "message_template",
+				ty: Type::String,
+				required: false,
+				description: "",
+			},
+			Property {
+				name: "require_confidence_interval",
+				ty: Type::Boolean,
+				required: false,
+				description: "",
+			},
+		]),
+	},
+	Schema {
+		name: "AlertMethod",
+		description: "",
+		kind: SchemaKind::TaggedUnion {
+			tag: "type",
+			variants: &[
+				"EmailAlertMethod",
+				"StdoutAlertMethod",
+				"WebhookAlertMethod",
+				"NotificationChannelAlertMethod",
+			],
+		},
+	},
+	Schema {
+		name: "EmailAlertMethod",
+		description: "",
+		kind: SchemaKind::Object(&[
+			Property {
+				name: "type",
+				ty: Type::Const("email"),
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "email",
+				ty: Type::String,
+				required: true,
+				description: "",
+			},
+		]),
+	},
+	Schema {
+		name: "StdoutAlertMethod",
+		description: "",
+		kind: SchemaKind::Object(&[Property {
+			name: "type",
+			ty: Type::Const("stdout"),
+			required: true,
+			description: "",
+		}]),
+	},
+	Schema {
+		name: "WebhookAlertMethod",
+		description: "",
+		kind: SchemaKind::Object(&[
+			Property {
+				name: "type",
+				ty: Type::Const("webhook"),
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "url",
+				ty: Type::String,
+				required: true,
+				description: "",
+			},
+		]),
+	},
+	Schema {
+		name: "NotificationChannelAlertMethod",
+		description: "",
+		kind: SchemaKind::Object(&[
+			Property {
+				name: "type",
+				ty: Type::Const("notification_channel"),
+				required: true,
+				description: "",
+			},
+			Property {
+				name: "notification_channel_id",
+				ty: Type::String,
+				required: true,
+				description: "",
+			},
+		]),
+	},
+	Schema {
+		name: "MonitorResponse",
+		description: "",
+		kind: SchemaKind::Object(&[Property {
+			name: "id",
+			ty: Type::String,
+			required: true,
+			description: "",
+		}]),
+	},
+];
@@ -0,0 +1,239 @@
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	alert::{AlertMethod, AlertMetric},
+	error::{bad_request, bad_request_with_message, not_found, service_unavailable, unauthorized},
+	model::get_model_bytes,
+	monitor::{
+		get_monitors_for_model, AlertModelType, MonitorCadence, MonitorThreshold,
+		MonitorThresholdMode,
+	},
+	monitor_checker::{CreateMonitorArgs, UpdateMonitorArgs},
+	notification_channels::{
+		get_notification_channel_summaries_for_repo, NotificationChannelSummary,
+	},
+	path_components,
+	repos::get_model_repo_id,
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_id::Id;
+use std::{str::FromStr, sync::Arc};
+use tracing::error;
+
+/// Each monitor in the request body is created, or updated if it has the id of one of the model's existing monitors.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MonitorRequest {
+	id: Option<Id>,
+	#[serde(default)]
+	title: String,
+	cadence: String,
+	metric: String,
+	#[serde(default = "default_mode")]
+	mode: String,
+	threshold_lower: Option<f32>,
+	threshold_upper: Option<f32>,
+	#[serde(default)]
+	methods: Vec<AlertMethod>,
+	minimum_sample_size: Option<u64>,
+	#[serde(default)]
+	warn_below_minimum_sample_size: bool,
+	#[serde(default)]
+	cooldown_periods: u64,
+	#[serde(default)]
+	message_template: Option<String>,
+	#[serde(default)]
+	require_confidence_interval: bool,
+}
+
+fn default_mode() -> String {
+	"absolute".to_owned()
+}
+
+#[derive(serde::Serialize)]
+struct MonitorResponse {
+	id: Id,
+}
+
+struct ValidatedMonitor {
+	cadence: MonitorCadence,
+	methods: Vec<AlertMethod>,
+	threshold: MonitorThreshold,
+	title: String,
+	minimum_sample_size: Option<u64>,
+	warn_below_minimum_sample_size: bool,
+	cooldown_periods: u64,
+	message_template: Option<String>,
+	require_confidence_interval: bool,
+}
+
+pub async fn put(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id =
+		if let ["api", "models", model_id, "monitors"] = *path_components(request).as_slice() {
+			model_id.to_owned()
+		} else {
+			bail!("unexpected path");
+		};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(unauthorized()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(not_found()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let bytes = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(bytes) => bytes,
+		Err(e) => {
+			error!(%e);
+			return Ok(bad_request());
+		}
+	};
+	let monitor_requests: Vec<MonitorRequest> = match serde_json::from_slice(&bytes) {
+		Ok(monitor_requests) => monitor_requests,
+		Err(e) => return Ok(bad_request_with_message(e.to_string())),
+	};
+	let model_bytes = get_model_bytes(app.storage(), model_id).await?;
+	let model = modelfox_model::from_bytes(&model_bytes)?;
+	let model_type = AlertModelType::from(model.inner());
+	let repo_id = get_model_repo_id(&mut db, model_id).await?;
+	let notification_channels =
+		get_notification_channel_summaries_for_repo(&mut db, repo_id).await?;
+	let existing_monitor_ids = get_monitors_for_model(&mut db, model_id)
+		.await?
+		.into_iter()
+		.map(|monitor| monitor.id)
+		.collect::<Vec<_>>();
+	// Apply every monitor in a single transaction, so a request that fails partway through does not leave some of its monitors behind.
+	let mut monitor_responses = Vec::with_capacity(monitor_requests.len());
+	for (index, monitor_request) in monitor_requests.into_iter().enumerate() {
+		let monitor_id = monitor_request.id;
+		let monitor =
+			match validate_monitor_request(monitor_request, model_type, &notification_channels) {
+				Ok(monitor) => monitor,
+				Err(e) => {
+					return Ok(bad_request_with_message(format!(
+						"monitor {}: {}",
+						index, e
+					)))
+				}
+			};
+		let result = match monitor_id {
+			Some(monitor_id) => {
+				if !existing_monitor_ids.contains(&monitor_id) {
+					return Ok(bad_request_with_message(format!(
+						"monitor {}: this model has no monitor with id {}",
+						index, monitor_id
+					)));
+				}
+				app.update_monitor(UpdateMonitorArgs {
+					db: &mut db,
+					monitor_id,
+					cadence: monitor.cadence,
+					methods: &monitor.methods,
+					model_id,
+					threshold: monitor.threshold,
+					title: &monitor.title,
+					minimum_sample_size: monitor.minimum_sample_size,
+					warn_below_minimum_sample_size: monitor.warn_below_minimum_sample_size,
+					cooldown_periods: monitor.cooldown_periods,
+					message_template: monitor.message_template,
+					require_confidence_interval: monitor.require_confidence_interval,
+				})
+				.await
+				.map(|_| monitor_id)
+			}
+			None => {
+				app.create_monitor(CreateMonitorArgs {
+					db: &mut db,
+					cadence: monitor.cadence,
+					methods: &monitor.methods,
+					model_id,
+					threshold: monitor.threshold,
+					title: &monitor.title,
+					minimum_sample_size: monitor.minimum_sample_size,
+					warn_below_minimum_sample_size: monitor.warn_below_minimum_sample_size,
+					cooldown_periods: monitor.cooldown_periods,
+					message_template: monitor.message_template,
+					require_confidence_interval: monitor.require_confidence_interval,
+				})
+				.await
+			}
+		};
+		match result {
+			Ok(id) => monitor_responses.push(MonitorResponse { id }),
+			Err(e) => {
+				return Ok(bad_request_with_message(format!(
+					"monitor {}: {}",
+					index, e
+				)))
+			}
+		}
+	}
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.header(http::header::CONTENT_TYPE, "application/json")
+		.body(hyper::Body::from(serde_json::to_vec(&monitor_responses)?))
+		.unwrap();
+	Ok(response)
+}
+
+fn validate_monitor_request(
+	monitor_request: MonitorRequest,
+	model_type: AlertModelType,
+	notification_channels: &[NotificationChannelSummary],
+) -> Result<ValidatedMonitor> {
+	let metric = AlertMetric::from_str(&monitor_request.metric)?;
+	if !metric.validate(model_type) {
+		bail!("the metric {} does not apply to this model", metric);
+	}
+	if monitor_request.threshold_lower.is_none() && monitor_request.threshold_upper.is_none() {
+		bail!("must provide at least one threshold bound");
+	}
+	let threshold = MonitorThreshold {
+		metric,
+		mode: MonitorThresholdMode::from_str(&monitor_request.mode)?,
+		difference_lower: monitor_request.threshold_lower,
+		difference_upper: monitor_request.threshold_upper,
+	};
+	let mut methods = vec![AlertMethod::Stdout];
+	for method in monitor_request.methods {
+		match &method {
+			AlertMethod::Stdout => continue,
+			AlertMethod::NotificationChannel(notification_channel) => {
+				if !notification_channels
+					.iter()
+					.any(|summary| summary.id == notification_channel.notification_channel_id)
+				{
+					bail!("unknown notification channel");
+				}
+			}
+			AlertMethod::Email(_) | AlertMethod::Webhook(_) => {}
+		}
+		methods.push(method);
+	}
+	Ok(ValidatedMonitor {
+		cadence: MonitorCadence::from_str(&monitor_request.cadence)?,
+		methods,
+		threshold,
+		title: monitor_request.title,
+		minimum_sample_size: monitor_request.minimum_sample_size,
+		warn_below_minimum_sample_size: monitor_request.warn_below_minimum_sample_size,
+		cooldown_periods: monitor_request.cooldown_periods,
+		message_template: monitor_request
+			.message_template
+			.filter(|message_template| !message_template.is_empty()),
+		require_confidence_interval: monitor_request.require_confidence_interval,
+	})
+}
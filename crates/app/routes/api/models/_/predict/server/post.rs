@@ -0,0 +1,103 @@
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, bad_request_with_message, not_found, service_unavailable, unauthorized},
+	model::{get_model_class_thresholds, get_model_decision_threshold},
+	path_components,
+	prediction_keys::{authorize_prediction_key, record_prediction_key_request},
+};
+use modelfox_core::predict::{try_predict, PredictInput, PredictOptions};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+struct PredictRequest {
+	inputs: Vec<PredictInput>,
+	/// If the request has no options, the predictions use the thresholds saved on the model's tuning page.
+	options: Option<PredictOptions>,
+}
+
+/// Make predictions with the model. Requests are authorized with one of the model's prediction keys instead of a user's token, and each key is limited to the number of requests per minute it was created with.
+pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id =
+		if let ["api", "models", model_id, "predict"] = *path_components(request).as_slice() {
+			model_id.to_owned()
+		} else {
+			bail!("unexpected path");
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(not_found()),
+	};
+	let token = request
+		.headers()
+		.get(http::header::AUTHORIZATION)
+		.and_then(|authorization| authorization.to_str().ok())
+		.and_then(|authorization| authorization.strip_prefix("Bearer "))
+		.map(ToOwned::to_owned);
+	let token = match token {
+		Some(token) => token,
+		None => return Ok(unauthorized()),
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let key = match authorize_prediction_key(&mut db, model_id, &token).await? {
+		Some(key) => key,
+		None => return Ok(unauthorized()),
+	};
+	// Rejected requests are counted in memory and written to the database periodically, so they do not cost a database write each.
+	if let Err(retry_after) = app.check_prediction_key_rate_limit(&key) {
+		app.commit_transaction(db).await?;
+		let response = http::Response::builder()
+			.status(http::StatusCode::TOO_MANY_REQUESTS)
+			.header(http::header::RETRY_AFTER, retry_after.to_string())
+			.body(hyper::Body::from(format!(
+				"This prediction key is limited to {} requests per minute.",
+				key.requests_per_minute
+			)))
+			.unwrap();
+		return Ok(response);
+	}
+	let now = app.clock().now_utc().unix_timestamp();
+	record_prediction_key_request(&mut db, key.id, now).await?;
+	let decision_threshold = get_model_decision_threshold(&mut db, model_id).await?;
+	let class_thresholds = get_model_class_thresholds(&mut db, model_id).await?;
+	app.commit_transaction(db).await?;
+	let bytes = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(bytes) => bytes,
+		Err(_) => return Ok(bad_request()),
+	};
+	let predict_request: PredictRequest = match serde_json::from_slice(&bytes) {
+		Ok(predict_request) => predict_request,
+		Err(e) => return Ok(bad_request_with_message(e.to_string())),
+	};
+	let options = predict_request.options.unwrap_or_else(|| {
+		let mut options = PredictOptions {
+			class_thresholds,
+			..Default::default()
+		};
+		if let Some(decision_threshold) = decision_threshold {
+			options.threshold = decision_threshold;
+		}
+		options
+	});
+	let model = app.get_predict_model(model_id).await?;
+	// Predicting is cpu bound, so do it on a blocking thread instead of holding up the other requests on this one.
+	let outputs =
+		tokio::task::spawn_blocking(move || try_predict(&model, &predict_request.inputs, &options))
+			.await?;
+	let outputs = match outputs {
+		Ok(outputs) => outputs,
+		Err(e) => return Ok(bad_request_with_message(e.to_string())),
+	};
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.header(http::header::CONTENT_TYPE, "application/json")
+		.body(hyper::Body::from(serde_json::to_vec(&outputs)?))
+		.unwrap();
+	Ok(response)
+}
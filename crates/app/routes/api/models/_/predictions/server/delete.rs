@@ -0,0 +1,113 @@
+use anyhow::{bail, Result};
+use chrono::prelude::*;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request_with_message, forbidden, not_found, service_unavailable, unauthorized},
+	model::get_model_bytes,
+	path_components,
+	track::delete_predictions,
+	user::{authorize_user, authorize_user_for_model, authorize_user_to_manage_model, User},
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+/// The predictions with dates in `[after, before)` are deleted. With `dry_run=true`, nothing is deleted and the response has the number of events that would be.
+#[derive(serde::Deserialize)]
+struct SearchParams {
+	after: DateTime<Utc>,
+	before: DateTime<Utc>,
+	#[serde(default)]
+	dry_run: bool,
+}
+
+#[derive(serde::Serialize)]
+struct DeleteResponse {
+	dry_run: bool,
+	prediction_count: u64,
+	true_value_count: u64,
+}
+
+pub async fn delete(
+	request: &mut http::Request<hyper::Body>,
+) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id =
+		if let ["api", "models", model_id, "predictions"] = *path_components(request).as_slice() {
+			model_id.to_owned()
+		} else {
+			bail!("unexpected path");
+		};
+	let search_params: SearchParams = match request.uri().query().map(serde_urlencoded::from_str) {
+		Some(Ok(search_params)) => search_params,
+		_ => {
+			return Ok(bad_request_with_message(
+				"must provide after and before dates".to_owned(),
+			))
+		}
+	};
+	if search_params.after >= search_params.before {
+		return Ok(bad_request_with_message(
+			"the after date must be earlier than the before date".to_owned(),
+		));
+	}
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(unauthorized()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(not_found()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	// Deleting predictions cannot be undone, so only the repo's owner or an admin of its organization may do it.
+	if !authorize_user_to_manage_model(&mut db, &user, model_id).await? {
+		return Ok(forbidden());
+	}
+	let model_bytes = get_model_bytes(app.storage(), model_id).await?;
+	let model = modelfox_model::from_bytes(&model_bytes)?;
+	let output = delete_predictions(
+		&mut db,
+		model_id,
+		model,
+		search_params.after,
+		search_params.before,
+		search_params.dry_run,
+	)
+	.await?;
+	app.commit_transaction(db).await?;
+	if !search_params.dry_run {
+		let (user_id, user_email) = match &user {
+			User::Root => ("root".to_owned(), None),
+			User::Normal(user) => (user.id.to_string(), Some(user.email.as_str())),
+		};
+		tracing::info!(
+			%user_id,
+			user_email,
+			%model_id,
+			after = %search_params.after,
+			before = %search_params.before,
+			prediction_count = output.prediction_count,
+			true_value_count = output.true_value_count,
+			"deleted predictions",
+		);
+	}
+	let response = DeleteResponse {
+		dry_run: search_params.dry_run,
+		prediction_count: output.prediction_count,
+		true_value_count: output.true_value_count,
+	};
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.header(http::header::CONTENT_TYPE, "application/json")
+		.body(hyper::Body::from(serde_json::to_vec(&response)?))
+		.unwrap();
+	Ok(response)
+}
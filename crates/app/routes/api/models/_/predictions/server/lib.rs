@@ -0,0 +1,108 @@
+use futures::FutureExt;
+use modelfox_app_core::error::method_not_allowed;
+use modelfox_app_openapi::{
+	Method, Operation, Parameter, ParameterLocation, Property, Response, Schema, SchemaKind, Type,
+};
+
+mod delete;
+mod put;
+
+pub fn init() -> sunfish::Route {
+	sunfish::Route::new_dynamic(|request| match *request.method() {
+		http::Method::DELETE => self::delete::delete(request).boxed(),
+		http::Method::PUT => self::put::put(request).boxed(),
+		_ => async { Ok(method_not_allowed()) }.boxed(),
+	})
+}
+
+const MODEL_ID: Parameter = Parameter {
+	name: "model_id",
+	location: ParameterLocation::Path,
+	required: true,
+	ty: Type::String,
+	description: "",
+};
+
+/// These are the operations this route serves, for the app's OpenAPI document.
+pub const OPERATIONS: &[Operation] = &[
+	Operation {
+		method: Method::Put,
+		path: "/api/models/{model_id}/predictions",
+		operation_id: "replay_predictions",
+		summary: "Re-ingest prediction and true value events for a model, replacing events that were already logged with the same identifier and date. Only the owner of the model's repo or an admin of its organization may re-ingest events.",
+		parameters: &[MODEL_ID],
+		request_body: Some(Type::Either(
+			&Type::Ref("MonitorEvent"),
+			&Type::Array(&Type::Ref("MonitorEvent")),
+		)),
+		response: Response {
+			status: 202,
+			description: "The events were logged.",
+			body: None,
+		},
+		authenticated: true,
+	},
+	Operation {
+		method: Method::Delete,
+		path: "/api/models/{model_id}/predictions",
+		operation_id: "delete_predictions",
+		summary: "Delete a model's predictions with dates in [after, before) and their true values. Only the owner of the model's repo or an admin of its organization may delete predictions.",
+		parameters: &[
+			MODEL_ID,
+			Parameter {
+				name: "after",
+				location: ParameterLocation::Query,
+				required: true,
+				ty: Type::DateTime,
+				description: "",
+			},
+			Parameter {
+				name: "before",
+				location: ParameterLocation::Query,
+				required: true,
+				ty: Type::DateTime,
+				description: "",
+			},
+			Parameter {
+				name: "dry_run",
+				location: ParameterLocation::Query,
+				required: false,
+				ty: Type::Boolean,
+				description: "If this is true, nothing is deleted and the response has the number of events that would be.",
+			},
+		],
+		request_body: None,
+		response: Response {
+			status: 200,
+			description: "The predictions were deleted.",
+			body: Some(Type::Ref("DeletePredictionsResponse")),
+		},
+		authenticated: true,
+	},
+];
+
+/// These are the schemas of this route's responses, for the app's OpenAPI document.
+pub const SCHEMAS: &[Schema] = &[Schema {
+	name: "DeletePredictionsResponse",
+	description: "",
+	kind: SchemaKind::Object(&[
+		Property {
+			name: "dry_run",
+			ty: Type::Boolean,
+			required: true,
+			description: "",
+		},
+		Property {
+			name: "prediction_count",
+			ty: Type::Integer,
+			required: true,
+			description: "",
+		},
+		Property {
+			name: "true_value_count",
+			ty: Type::Integer,
+			required: true,
+			description: "",
+		},
+	]),
+}];
@@ -0,0 +1,127 @@
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{
+		bad_request, bad_request_with_message, forbidden, not_found, service_unavailable,
+		unauthorized,
+	},
+	path_components,
+	track::{replay_prediction_monitor_event, replay_true_value_monitor_event},
+	user::{authorize_user, authorize_user_for_model, authorize_user_to_manage_model, User},
+};
+use modelfox_app_monitor_event::MonitorEvent;
+use modelfox_id::Id;
+use std::{collections::BTreeMap, sync::Arc};
+use tracing::error;
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum MonitorEventSet {
+	Single(MonitorEvent),
+	Multiple(Vec<MonitorEvent>),
+}
+
+/// Re-ingest prediction and true value events for a model. Unlike `/track`, an event that was already logged with the same identifier and date replaces the logged one instead of being rejected, so corrected events can be replayed without being counted twice.
+pub async fn put(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id =
+		if let ["api", "models", model_id, "predictions"] = *path_components(request).as_slice() {
+			model_id.to_owned()
+		} else {
+			bail!("unexpected path");
+		};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(unauthorized()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(not_found()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	// Replacing events that were already logged cannot be undone, so only the repo's owner or an admin of its organization may do it.
+	if !authorize_user_to_manage_model(&mut db, &user, model_id).await? {
+		return Ok(forbidden());
+	}
+	let bytes = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(bytes) => bytes,
+		Err(e) => {
+			error!(%e);
+			return Ok(bad_request());
+		}
+	};
+	let monitor_events: MonitorEventSet = match serde_json::from_slice(&bytes) {
+		Ok(monitor_events) => monitor_events,
+		Err(e) => return Ok(bad_request_with_message(e.to_string())),
+	};
+	let monitor_events = match monitor_events {
+		MonitorEventSet::Single(monitor_event) => vec![monitor_event],
+		MonitorEventSet::Multiple(monitor_events) => monitor_events,
+	};
+	let mut model_cache = BTreeMap::new();
+	let mut prediction_count = 0;
+	let mut true_value_count = 0;
+	for (index, monitor_event) in monitor_events.into_iter().enumerate() {
+		let event_model_id = match &monitor_event {
+			MonitorEvent::Prediction(monitor_event) => monitor_event.model_id,
+			MonitorEvent::TrueValue(monitor_event) => monitor_event.model_id,
+		};
+		if event_model_id != model_id {
+			return Ok(bad_request_with_message(format!(
+				"event {}: the event is for a different model",
+				index
+			)));
+		}
+		let result = match monitor_event {
+			MonitorEvent::Prediction(monitor_event) => {
+				prediction_count += 1;
+				replay_prediction_monitor_event(
+					&mut db,
+					app.storage(),
+					&mut model_cache,
+					monitor_event,
+				)
+				.await
+			}
+			MonitorEvent::TrueValue(monitor_event) => {
+				true_value_count += 1;
+				replay_true_value_monitor_event(
+					&mut db,
+					app.storage(),
+					&mut model_cache,
+					monitor_event,
+				)
+				.await
+			}
+		};
+		if let Err(e) = result {
+			return Ok(bad_request_with_message(format!("event {}: {}", index, e)));
+		}
+	}
+	app.commit_transaction(db).await?;
+	let (user_id, user_email) = match &user {
+		User::Root => ("root".to_owned(), None),
+		User::Normal(user) => (user.id.to_string(), Some(user.email.as_str())),
+	};
+	tracing::info!(
+		%user_id,
+		user_email,
+		%model_id,
+		prediction_count,
+		true_value_count,
+		"replayed predictions",
+	);
+	let response = http::Response::builder()
+		.status(http::StatusCode::ACCEPTED)
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
@@ -0,0 +1,45 @@
+/*!
+This program writes the API clients generated from the app's OpenAPI document to `languages`. With `--check`, it writes nothing and fails if the clients that are checked in differ from the generated ones, so the SDKs' HTTP clients can't drift from the server.
+*/
+
+use anyhow::{bail, Result};
+use modelfox_app_api_openapi_server::{operations, schemas};
+use modelfox_app_openapi::{python, typescript};
+use std::path::Path;
+
+fn main() -> Result<()> {
+	let check = std::env::args().skip(1).any(|arg| arg == "--check");
+	let workspace_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+		.ancestors()
+		.nth(6)
+		.unwrap();
+	let operations = operations();
+	let schemas = schemas();
+	let clients = [
+		(
+			workspace_path.join("languages/javascript/api.ts"),
+			typescript::generate(&operations, &schemas),
+		),
+		(
+			workspace_path.join("languages/python/modelfox/api.py"),
+			python::generate(&operations, &schemas),
+		),
+	];
+	let mut outdated_paths = Vec::new();
+	for (path, code) in clients.iter() {
+		if check {
+			if std::fs::read_to_string(path).ok().as_deref() != Some(code.as_str()) {
+				outdated_paths.push(path.display().to_string());
+			}
+		} else {
+			std::fs::write(path, code)?;
+		}
+	}
+	if !outdated_paths.is_empty() {
+		bail!(
+			"These clients differ from the ones generated from the app's OpenAPI document. Run scripts/app/openapi to regenerate them: {}",
+			outdated_paths.join(", ")
+		);
+	}
+	Ok(())
+}
@@ -0,0 +1,11 @@
+use anyhow::Result;
+
+pub async fn get(_request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let document = crate::document();
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.header(http::header::CONTENT_TYPE, "application/json")
+		.body(hyper::Body::from(serde_json::to_vec(&document)?))
+		.unwrap();
+	Ok(response)
+}
@@ -0,0 +1,39 @@
+use futures::FutureExt;
+use modelfox_app_core::error::method_not_allowed;
+use modelfox_app_openapi::{Operation, Schema};
+
+mod get;
+
+pub fn init() -> sunfish::Route {
+	sunfish::Route::new_dynamic(|request| match *request.method() {
+		http::Method::GET => self::get::get(request).boxed(),
+		_ => async { Ok(method_not_allowed()) }.boxed(),
+	})
+}
+
+/// Build the app's OpenAPI document from the operations and schemas the API route crates declare.
+pub fn document() -> serde_json::Value {
+	modelfox_app_openapi::document(env!("CARGO_PKG_VERSION"), &operations(), &schemas())
+}
+
+pub fn operations() -> Vec<Operation> {
+	[
+		modelfox_app_track_server::OPERATIONS,
+		modelfox_app_api_model_predictions_server::OPERATIONS,
+		modelfox_app_api_model_monitors_server::OPERATIONS,
+		modelfox_app_api_repo_readme_server::OPERATIONS,
+		modelfox_app_api_model_batch_predict_job_server::OPERATIONS,
+	]
+	.concat()
+}
+
+pub fn schemas() -> Vec<Schema> {
+	[
+		modelfox_app_monitor_event::SCHEMAS,
+		modelfox_app_api_model_predictions_server::SCHEMAS,
+		modelfox_app_api_model_monitors_server::SCHEMAS,
+		modelfox_app_api_repo_readme_server::SCHEMAS,
+		modelfox_app_api_model_batch_predict_job_server::SCHEMAS,
+	]
+	.concat()
+}
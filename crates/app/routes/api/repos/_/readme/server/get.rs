@@ -0,0 +1,48 @@
+use crate::put::RepoReadme;
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{not_found, service_unavailable, unauthorized},
+	path_components,
+	repos::get_repo_readme,
+	user::{authorize_user, authorize_user_for_repo},
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let repo_id = if let ["api", "repos", repo_id, "readme"] = *path_components(request).as_slice()
+	{
+		repo_id.to_owned()
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(unauthorized()),
+		};
+	let repo_id: Id = match repo_id.parse() {
+		Ok(repo_id) => repo_id,
+		Err(_) => return Ok(not_found()),
+	};
+	if !authorize_user_for_repo(&mut db, &user, repo_id).await? {
+		return Ok(not_found());
+	}
+	let readme = get_repo_readme(&mut db, repo_id).await?.unwrap_or_default();
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.header(http::header::CONTENT_TYPE, "application/json")
+		.body(hyper::Body::from(serde_json::to_vec(&RepoReadme {
+			readme,
+		})?))
+		.unwrap();
+	Ok(response)
+}
@@ -0,0 +1,68 @@
+use futures::FutureExt;
+use modelfox_app_core::error::method_not_allowed;
+use modelfox_app_openapi::{
+	Method, Operation, Parameter, ParameterLocation, Property, Response, Schema, SchemaKind, Type,
+};
+
+mod get;
+mod put;
+
+pub fn init() -> sunfish::Route {
+	sunfish::Route::new_dynamic(|request| match *request.method() {
+		http::Method::GET => self::get::get(request).boxed(),
+		http::Method::PUT => self::put::put(request).boxed(),
+		_ => async { Ok(method_not_allowed()) }.boxed(),
+	})
+}
+
+const REPO_ID: Parameter = Parameter {
+	name: "repo_id",
+	location: ParameterLocation::Path,
+	required: true,
+	ty: Type::String,
+	description: "",
+};
+
+/// These are the operations this route serves, for the app's OpenAPI document.
+pub const OPERATIONS: &[Operation] = &[
+	Operation {
+		method: Method::Get,
+		path: "/api/repos/{repo_id}/readme",
+		operation_id: "get_repo_readme",
+		summary: "Get a repo's markdown README.",
+		parameters: &[REPO_ID],
+		request_body: None,
+		response: Response {
+			status: 200,
+			description: "",
+			body: Some(Type::Ref("RepoReadme")),
+		},
+		authenticated: true,
+	},
+	Operation {
+		method: Method::Put,
+		path: "/api/repos/{repo_id}/readme",
+		operation_id: "put_repo_readme",
+		summary: "Replace a repo's markdown README, which is rendered at the top of the repo page. An empty README removes it.",
+		parameters: &[REPO_ID],
+		request_body: Some(Type::Ref("RepoReadme")),
+		response: Response {
+			status: 200,
+			description: "The README was replaced.",
+			body: None,
+		},
+		authenticated: true,
+	},
+];
+
+/// These are the schemas of this route's requests and responses, for the app's OpenAPI document.
+pub const SCHEMAS: &[Schema] = &[Schema {
+	name: "RepoReadme",
+	description: "",
+	kind: SchemaKind::Object(&[Property {
+		name: "readme",
+		ty: Type::String,
+		required: true,
+		description: "This is the README's markdown. It is empty if the repo has no README.",
+	}]),
+}];
@@ -0,0 +1,63 @@
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, service_unavailable, unauthorized},
+	path_components,
+	repos::update_repo_readme,
+	user::{authorize_user, authorize_user_for_repo},
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RepoReadme {
+	pub readme: String,
+}
+
+pub async fn put(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let repo_id = if let ["api", "repos", repo_id, "readme"] = *path_components(request).as_slice()
+	{
+		repo_id.to_owned()
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(unauthorized()),
+		};
+	let repo_id: Id = match repo_id.parse() {
+		Ok(repo_id) => repo_id,
+		Err(_) => return Ok(not_found()),
+	};
+	if !authorize_user_for_repo(&mut db, &user, repo_id).await? {
+		return Ok(not_found());
+	}
+	let bytes = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(bytes) => bytes,
+		Err(_) => return Ok(bad_request()),
+	};
+	let repo_readme: RepoReadme = match serde_json::from_slice(&bytes) {
+		Ok(repo_readme) => repo_readme,
+		Err(e) => {
+			return Ok(http::Response::builder()
+				.status(http::StatusCode::BAD_REQUEST)
+				.body(hyper::Body::from(e.to_string()))
+				.unwrap())
+		}
+	};
+	update_repo_readme(&mut db, repo_id, &repo_readme.readme).await?;
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
@@ -17,10 +17,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let app_layout_info = app_layout_info(app).await?;
 	let repos = match user {
 		User::Root => repos_for_root(&mut db).await?,
@@ -1,3 +1,4 @@
+use modelfox_app_base_path::app_path;
 use modelfox_app_layouts::{
 	app_layout::{AppLayout, AppLayoutInfo},
 	document::Document,
@@ -16,7 +17,7 @@ impl Component for Page {
 		let heading = PageHeading::new().child(ui::H1::new("Repositories")).child(
 			PageHeadingButtons::new().child(
 				ui::Button::new()
-					.href("/repos/new".to_owned())
+					.href(app_path("/repos/new"))
 					.child("Create Repo"),
 			),
 		);
@@ -58,7 +59,7 @@ impl Component for ReposTable {
 			)
 			.child(
 				ui::TableBody::new().children(self.rows.into_iter().map(|repo| {
-					let href = format!("/repos/{}/", repo.id);
+					let href = app_path(format!("/repos/{}/", repo.id));
 					let owner_slash = repo.owner_name.map(|owner_name| format!("{}/", owner_name));
 					let link_text =
 						format!("{}{}", owner_slash.as_deref().unwrap_or(""), repo.title,);
@@ -0,0 +1,55 @@
+use crate::page::{InvitationsTableRow, Page};
+use anyhow::Result;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{not_found, redirect_to_login, service_unavailable},
+	invitations::{get_invitations, is_admin_email},
+	user::authorize_normal_user,
+};
+use modelfox_app_layouts::app_layout::app_layout_info;
+use pinwheel::prelude::*;
+use std::sync::Arc;
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let auth_options = match app.options().auth.as_ref() {
+		Some(auth_options) => auth_options,
+		None => return Ok(not_found()),
+	};
+	let mut txn = match app.begin_transaction().await {
+		Ok(txn) => txn,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_normal_user(request, &mut txn, app.clock()).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(redirect_to_login()),
+	};
+	if !is_admin_email(auth_options, &user.email) {
+		return Ok(not_found());
+	}
+	let app_layout_info = app_layout_info(app).await?;
+	let rows = get_invitations(&mut txn)
+		.await?
+		.into_iter()
+		.map(|invitation| InvitationsTableRow {
+			id: invitation.id.to_string(),
+			email: invitation.email,
+			inviter_email: invitation.inviter_email,
+			created_at: invitation.created_at,
+		})
+		.collect();
+	app.commit_transaction(txn).await?;
+	let page = Page {
+		app_layout_info,
+		rows,
+		allowed_email_domains: auth_options.allowed_email_domains.clone(),
+		invite_only: auth_options.invite_only,
+	};
+	let html = html(page);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
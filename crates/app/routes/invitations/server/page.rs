@@ -0,0 +1,155 @@
+use modelfox_app_layouts::{
+	app_layout::{AppLayout, AppLayoutInfo},
+	document::Document,
+};
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+use time::macros::format_description;
+
+pub struct Page {
+	pub app_layout_info: AppLayoutInfo,
+	pub rows: Vec<InvitationsTableRow>,
+	pub allowed_email_domains: Option<Vec<String>>,
+	pub invite_only: bool,
+}
+
+pub struct InvitationsTableRow {
+	pub id: String,
+	pub email: String,
+	pub inviter_email: Option<String>,
+	pub created_at: i64,
+}
+
+impl Component for Page {
+	fn into_node(self) -> Node {
+		let description = match (self.invite_only, self.allowed_email_domains) {
+			(true, _) => "Only existing users and the email addresses invited below may sign in.".to_owned(),
+			(false, Some(allowed_email_domains)) => format!(
+				"Only email addresses with the domains {} and the email addresses invited below may sign in.",
+				allowed_email_domains.join(", ")
+			),
+			(false, None) => "Anyone may sign in. Invitations take effect if you restrict sign in with the auth.invite_only or auth.allowed_email_domains options.".to_owned(),
+		};
+		let invitations_table_or_empty_message = if !self.rows.is_empty() {
+			InvitationsTable { rows: self.rows }.into_node()
+		} else {
+			ui::Card::new()
+				.child(ui::P::new().child("No email addresses have been invited."))
+				.into_node()
+		};
+		Document::new()
+			.child(
+				AppLayout::new(self.app_layout_info).child(
+					ui::S1::new()
+						.child(ui::H1::new("Invitations"))
+						.child(ui::P::new().child(description))
+						.child(
+							ui::S2::new().child(ui::H2::new("Invite")).child(
+								ui::Form::new()
+									.post(true)
+									.child(
+										input()
+											.attribute("name", "action")
+											.attribute("type", "hidden")
+											.attribute("value", "create_invitation"),
+									)
+									.child(
+										ui::TextField::new()
+											.label("Email".to_owned())
+											.name("email".to_owned()),
+									)
+									.child(
+										ui::Button::new()
+											.button_type(ui::ButtonType::Submit)
+											.child("Invite"),
+									),
+							),
+						)
+						.child(
+							ui::S2::new()
+								.child(ui::H2::new("Invited Email Addresses"))
+								.child(invitations_table_or_empty_message),
+						),
+				),
+			)
+			.into_node()
+	}
+}
+
+struct InvitationsTable {
+	rows: Vec<InvitationsTableRow>,
+}
+
+impl Component for InvitationsTable {
+	fn into_node(self) -> Node {
+		ui::Table::new()
+			.width("100%".to_owned())
+			.child(
+				ui::TableHeader::new().child(
+					ui::TableRow::new()
+						.child(ui::TableHeaderCell::new().child("Email"))
+						.child(ui::TableHeaderCell::new().child("Invited By"))
+						.child(ui::TableHeaderCell::new().child("Created"))
+						.child(ui::TableHeaderCell::new()),
+				),
+			)
+			.child(
+				ui::TableBody::new().children(self.rows.into_iter().map(|row| {
+					ui::TableRow::new()
+						.child(ui::TableCell::new().child(row.email))
+						.child(
+							ui::TableCell::new()
+								.child(row.inviter_email.unwrap_or_else(|| "Unknown".to_owned())),
+						)
+						.child(ui::TableCell::new().child(format_timestamp(row.created_at)))
+						.child(ui::TableCell::new().child(DeleteInvitationForm {
+							invitation_id: row.id,
+						}))
+				})),
+			)
+			.into_node()
+	}
+}
+
+struct DeleteInvitationForm {
+	invitation_id: String,
+}
+
+impl Component for DeleteInvitationForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "delete_invitation"),
+			)
+			.child(
+				input()
+					.attribute("name", "invitation_id")
+					.attribute("type", "hidden")
+					.attribute("value", self.invitation_id),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.color(ui::colors::RED.to_owned())
+					.child("Delete"),
+			)
+			.into_node()
+	}
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+	time::OffsetDateTime::from_unix_timestamp(timestamp)
+		.ok()
+		.and_then(|date_time| {
+			date_time
+				.format(format_description!(
+					"[year]-[month]-[day] [hour]:[minute] UTC"
+				))
+				.ok()
+		})
+		.unwrap_or_else(|| "Unknown".to_owned())
+}
@@ -0,0 +1,80 @@
+use anyhow::Result;
+use modelfox_app_base_path::app_path;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, service_unavailable, unauthorized},
+	invitations::{create_invitation, delete_invitation, is_admin_email},
+	user::authorize_normal_user,
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "action")]
+enum Action {
+	#[serde(rename = "create_invitation")]
+	CreateInvitation(CreateInvitationAction),
+	#[serde(rename = "delete_invitation")]
+	DeleteInvitation(DeleteInvitationAction),
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct CreateInvitationAction {
+	email: String,
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct DeleteInvitationAction {
+	invitation_id: String,
+}
+
+pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let auth_options = match app.options().auth.as_ref() {
+		Some(auth_options) => auth_options,
+		None => return Ok(not_found()),
+	};
+	let mut txn = match app.begin_transaction().await {
+		Ok(txn) => txn,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_normal_user(request, &mut txn, app.clock()).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(unauthorized()),
+	};
+	if !is_admin_email(auth_options, &user.email) {
+		return Ok(not_found());
+	}
+	let data = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(data) => data,
+		Err(_) => return Ok(bad_request()),
+	};
+	let action: Action = match serde_urlencoded::from_bytes(&data) {
+		Ok(action) => action,
+		Err(_) => return Ok(bad_request()),
+	};
+	match action {
+		Action::CreateInvitation(action) => {
+			let email = action.email.trim();
+			if email.is_empty() || !email.contains('@') {
+				return Ok(bad_request());
+			}
+			create_invitation(&mut txn, email, user.id, app.clock()).await?;
+		}
+		Action::DeleteInvitation(action) => {
+			let invitation_id: Id = match action.invitation_id.parse() {
+				Ok(invitation_id) => invitation_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			delete_invitation(&mut txn, invitation_id).await?;
+		}
+	}
+	app.commit_transaction(txn).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::SEE_OTHER)
+		.header(http::header::LOCATION, app_path("/invitations"))
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
@@ -1,8 +1,11 @@
 use crate::page::{Page, Stage};
 use anyhow::Result;
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, service_unavailable},
+	invitations::is_email_authorized,
+	sessions::create_session,
 	App,
 };
 use modelfox_id::Id;
@@ -33,6 +36,21 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
+	// Refuse email addresses that are not allowed to sign in before creating a user for them.
+	if let Some(auth_options) = app.options().auth.as_ref() {
+		if !is_email_authorized(&mut txn, auth_options, &email).await? {
+			let page = Page {
+				stage: Some(Stage::Email),
+				error: Some("this email address is not allowed to sign in".to_owned()),
+				email: Some(email),
+			};
+			let html = html(page);
+			let response = http::Response::builder()
+				.status(http::StatusCode::BAD_REQUEST)
+				.body(hyper::Body::from(html))?;
+			return Ok(response);
+		}
+	}
 	// Upsert the user.
 	let user_id = Id::generate();
 	sqlx::query(
@@ -146,46 +164,34 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 				.status(http::StatusCode::SEE_OTHER)
 				.header(
 					http::header::LOCATION,
-					format!("/login?stage=code&email={}", email),
+					app_path(format!("/login?stage=code&email={}", email)),
 				)
 				.body(hyper::Body::empty())?;
 			return Ok(response);
 		}
 	}
-	let token = create_token(txn.borrow_mut(), user_id).await?;
+	let now = app.clock().now_utc().unix_timestamp();
+	let token = create_session(
+		txn.borrow_mut(),
+		request,
+		user_id,
+		now,
+		&app.options().trusted_proxies,
+	)
+	.await?;
 	app.commit_transaction(txn).await?;
 	let set_cookie = set_cookie_header_value(token, app.options().cookie_domain.as_deref());
 	let response = http::Response::builder()
 		.status(http::StatusCode::SEE_OTHER)
-		.header(http::header::LOCATION, "/")
+		.header(http::header::LOCATION, app_path("/"))
 		.header(http::header::SET_COOKIE, set_cookie)
 		.body(hyper::Body::empty())?;
 	Ok(response)
 }
 
-async fn create_token(db: &mut sqlx::Transaction<'_, sqlx::Any>, user_id: Id) -> Result<Id> {
-	let id = Id::generate();
-	let token = Id::generate();
-	sqlx::query(
-		"
-			insert into tokens (
-				id, token, user_id
-			) values (
-				$1, $2, $3
-			)
-		",
-	)
-	.bind(&id.to_string())
-	.bind(&token.to_string())
-	.bind(&user_id.to_string())
-	.execute(db)
-	.await?;
-	Ok(token)
-}
-
 fn set_cookie_header_value(token: Id, domain: Option<&str>) -> String {
 	let domain = domain.map(|domain| format!(";domain={}", domain));
-	let path = Some(";path=/");
+	let path = Some(format!(";path={}", app_path("/")));
 	let max_age = Some(";max-age=31536000");
 	let same_site = if domain.is_some() {
 		Some(";samesite=lax")
@@ -201,7 +207,7 @@ fn set_cookie_header_value(token: Id, domain: Option<&str>) -> String {
 		"modelfox_token={}{}{}{}{}{}",
 		token,
 		domain.as_deref().unwrap_or(""),
-		path.unwrap_or(""),
+		path.as_deref().unwrap_or(""),
 		max_age.unwrap_or(""),
 		same_site.unwrap_or(""),
 		secure.unwrap_or("")
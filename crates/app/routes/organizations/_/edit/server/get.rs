@@ -1,21 +1,59 @@
 use crate::page::Page;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use modelfox_app_context::Context;
-use modelfox_app_core::error::not_found;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	organizations::get_organization,
+	path_components,
+	user::{authorize_user, authorize_user_for_organization},
+};
 use modelfox_app_layouts::app_layout::app_layout_info;
+use modelfox_id::Id;
 use pinwheel::prelude::*;
 use std::sync::Arc;
 
 pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
 	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
 	let app = &context.app;
+	let organization_id =
+		if let ["organizations", organization_id, "edit"] = *path_components(request).as_slice() {
+			organization_id.to_owned()
+		} else {
+			bail!("unexpected path");
+		};
 	if !app.options().auth_enabled() {
 		return Ok(not_found());
 	}
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let organization_id: Id = match organization_id.parse() {
+		Ok(organization_id) => organization_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_organization(&mut db, &user, organization_id).await? {
+		return Ok(not_found());
+	};
+	let organization = match get_organization(organization_id, &mut db).await? {
+		Some(organization) => organization,
+		None => return Ok(not_found()),
+	};
+	app.commit_transaction(db).await?;
 	let app_layout_info = app_layout_info(app).await?;
 	let page = Page {
+		alert_notifications_per_hour: organization
+			.alert_notifications_per_hour
+			.map(|alert_notifications_per_hour| alert_notifications_per_hour.to_string()),
 		app_layout_info,
 		error: None,
+		name: Some(organization.name),
+		timezone: organization.timezone,
 	};
 	let html = html(page);
 	let response = http::Response::builder()
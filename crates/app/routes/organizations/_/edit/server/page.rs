@@ -6,8 +6,11 @@ use modelfox_ui as ui;
 use pinwheel::prelude::*;
 
 pub struct Page {
+	pub alert_notifications_per_hour: Option<String>,
 	pub app_layout_info: AppLayoutInfo,
 	pub error: Option<String>,
+	pub name: Option<String>,
+	pub timezone: Option<String>,
 }
 
 impl Component for Page {
@@ -18,11 +21,35 @@ impl Component for Page {
 					ui::S1::new().child(ui::H1::new("Edit Organization")).child(
 						ui::Form::new()
 							.post(true)
+							.child(self.error.map(|error| {
+								ui::Alert::new(ui::Level::Danger).child(error)
+							}))
 							.child(
 								ui::TextField::new()
 									.label("Organization Name".to_owned())
-									.name("name".to_owned()),
+									.name("name".to_owned())
+									.value(self.name),
 							)
+							.child(
+								ui::TextField::new()
+									.label("Timezone".to_owned())
+									.name("timezone".to_owned())
+									.placeholder("America/New_York".to_owned())
+									.value(self.timezone),
+							)
+							.child(ui::P::new().child(
+								"Alerts, monitor cadences, and production pages use this timezone for the organization's repos. Members can override it for the pages they view on their user page.",
+							))
+							.child(
+								ui::TextField::new()
+									.label("Alert Notifications Per Hour".to_owned())
+									.name("alert_notifications_per_hour".to_owned())
+									.placeholder("No limit".to_owned())
+									.value(self.alert_notifications_per_hour),
+							)
+							.child(ui::P::new().child(
+								"If set, at most this many alert notifications are sent for the organization's repos per hour. Once the limit is nearly reached, the rest of the hour's alerts are combined into one summary.",
+							))
 							.child(
 								ui::Button::new()
 									.button_type(ui::ButtonType::Submit)
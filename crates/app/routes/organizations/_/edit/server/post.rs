@@ -1,16 +1,26 @@
+use crate::page::Page;
 use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	organizations::set_organization_alert_notifications_per_hour,
 	path_components,
+	timezone::{parse_timezone, set_organization_timezone},
 	user::{authorize_user, authorize_user_for_organization},
 };
+use modelfox_app_layouts::app_layout::app_layout_info;
 use modelfox_id::Id;
+use pinwheel::prelude::*;
 use std::{borrow::BorrowMut, sync::Arc};
 
 #[derive(serde::Deserialize)]
 struct Action {
 	name: String,
+	#[serde(default)]
+	timezone: String,
+	#[serde(default)]
+	alert_notifications_per_hour: String,
 }
 
 pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
@@ -37,10 +47,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let organization_id: Id = match organization_id.parse() {
 		Ok(organization_id) => organization_id,
 		Err(_) => return Ok(bad_request()),
@@ -48,7 +59,36 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 	if !authorize_user_for_organization(&mut db, &user, organization_id).await? {
 		return Ok(not_found());
 	};
-	let Action { name } = action;
+	let Action {
+		name,
+		timezone,
+		alert_notifications_per_hour,
+	} = action;
+	let parsed_timezone = parse_timezone(&timezone)
+		.map_err(|_| format!("{} is not a valid timezone.", timezone.trim()));
+	let parsed_alert_notifications_per_hour =
+		parse_alert_notifications_per_hour(&alert_notifications_per_hour);
+	let (parsed_timezone, parsed_alert_notifications_per_hour) =
+		match (parsed_timezone, parsed_alert_notifications_per_hour) {
+			(Ok(timezone), Ok(alert_notifications_per_hour)) => {
+				(timezone, alert_notifications_per_hour)
+			}
+			(Err(error), _) | (_, Err(error)) => {
+				let app_layout_info = app_layout_info(app).await?;
+				let page = Page {
+					alert_notifications_per_hour: Some(alert_notifications_per_hour),
+					app_layout_info,
+					error: Some(error),
+					name: Some(name),
+					timezone: Some(timezone),
+				};
+				let response = http::Response::builder()
+					.status(http::StatusCode::BAD_REQUEST)
+					.body(hyper::Body::from(html(page)))
+					.unwrap();
+				return Ok(response);
+			}
+		};
 	sqlx::query(
 		"
 			update organizations
@@ -60,14 +100,36 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 	.bind(&organization_id.to_string())
 	.execute(db.borrow_mut())
 	.await?;
+	set_organization_timezone(&mut db, organization_id, parsed_timezone).await?;
+	set_organization_alert_notifications_per_hour(
+		&mut db,
+		organization_id,
+		parsed_alert_notifications_per_hour,
+	)
+	.await?;
 	app.commit_transaction(db).await?;
 	let response = http::Response::builder()
 		.status(http::StatusCode::SEE_OTHER)
 		.header(
 			http::header::LOCATION,
-			format!("/organizations/{}/", organization_id),
+			app_path(format!("/organizations/{}/", organization_id)),
 		)
 		.body(hyper::Body::empty())
 		.unwrap();
 	Ok(response)
 }
+
+/// Parse the limit of alert notifications per hour. An empty string is parsed as `None`, which removes the limit.
+fn parse_alert_notifications_per_hour(value: &str) -> Result<Option<i64>, String> {
+	let value = value.trim();
+	if value.is_empty() {
+		return Ok(None);
+	}
+	match value.parse::<i64>() {
+		Ok(limit) if limit > 0 => Ok(Some(limit)),
+		_ => Err(format!(
+			"{} is not a valid number of alert notifications per hour. It must be a positive integer.",
+			value
+		)),
+	}
+}
@@ -1,13 +1,17 @@
 use crate::page::{
-	DetailsSection, MembersSection, MembersTable, MembersTableRow, Page, ReposSection, ReposTable,
-	ReposTableRow,
+	DetailsSection, MembersSection, MembersTable, MembersTableRow, NotificationChannelsSection,
+	NotificationChannelsTable, NotificationChannelsTableRow, Page, ReposSection, ReposTable,
+	ReposTableRow, TeamsSection, TeamsTableRow,
 };
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
+	alert::AlertMethod,
 	error::{bad_request, not_found, service_unavailable, unauthorized},
+	notification_channels::get_notification_channels,
 	organizations::{get_organization, get_organization_user},
 	path_components,
+	teams::get_teams,
 	user::{authorize_normal_user, authorize_normal_user_for_organization},
 };
 use modelfox_app_layouts::app_layout::app_layout_info;
@@ -33,7 +37,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_normal_user(request, &mut db).await? {
+	let user = match authorize_normal_user(request, &mut db, app.clock()).await? {
 		Ok(user) => user,
 		Err(_) => return Ok(unauthorized()),
 	};
@@ -54,6 +58,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let details = DetailsSection {
 		organization_id: organization.id.to_string(),
 		organization_name: organization.name.clone(),
+		organization_timezone: organization.timezone.clone(),
 		can_edit: organization_user.is_admin,
 	};
 	let rows = organization
@@ -63,6 +68,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			id: member.id,
 			email: member.email,
 			is_admin: member.is_admin,
+			has_data_access: member.has_data_access,
 		})
 		.collect();
 	let members_table = MembersTable {
@@ -74,16 +80,75 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		organization_id: organization.id,
 		members_table,
 	};
+	let notification_channels_table = match app.options().secret_key.as_ref() {
+		Some(secret_key) => {
+			let rows = get_notification_channels(&mut db, secret_key, organization_id)
+				.await?
+				.into_iter()
+				.map(|notification_channel| NotificationChannelsTableRow {
+					id: notification_channel.id,
+					name: notification_channel.name,
+					kind: match notification_channel.method {
+						AlertMethod::Email(_) => "Email".to_owned(),
+						AlertMethod::Webhook(_) => "Webhook".to_owned(),
+						method => method.to_string(),
+					},
+				})
+				.collect();
+			Some(NotificationChannelsTable {
+				can_edit: organization_user.is_admin,
+				rows,
+			})
+		}
+		None => None,
+	};
+	let notification_channels_section = NotificationChannelsSection {
+		can_edit: organization_user.is_admin,
+		notification_channels_table,
+	};
+	let teams_section = TeamsSection {
+		can_edit: organization_user.is_admin,
+		rows: get_teams(&mut db, organization_id)
+			.await?
+			.into_iter()
+			.map(|team| TeamsTableRow {
+				id: team.id,
+				name: team.name,
+				member_count: team.member_count,
+				repo_count: team.repo_count,
+			})
+			.collect(),
+	};
+	// Repos granted to teams are only listed for the members of those teams and the organization's admins.
 	let rows = sqlx::query(
 		"
 			select
 				repos.id,
 				repos.title
 			from repos
-			where repos.organization_id = $1
+			where
+				repos.organization_id = $1 and (
+					$3 or
+					not exists (
+						select 1
+						from repos_teams
+						where repos_teams.repo_id = repos.id
+					) or
+					exists (
+						select 1
+						from repos_teams
+						join teams_users
+							on teams_users.team_id = repos_teams.team_id
+						where
+							repos_teams.repo_id = repos.id and
+							teams_users.user_id = $2
+					)
+				)
 		",
 	)
 	.bind(&organization_id.to_string())
+	.bind(&user.id.to_string())
+	.bind(organization_user.is_admin)
 	.fetch_all(db.borrow_mut())
 	.await?;
 	let rows: Vec<ReposTableRow> = rows
@@ -99,13 +164,18 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	} else {
 		None
 	};
-	let repos = ReposSection { repos_table };
+	let repos = ReposSection {
+		organization_id,
+		repos_table,
+	};
 	let page = Page {
 		app_layout_info,
 		details_section: details,
 		id: organization_id.to_string(),
 		members_section: members,
 		name: organization.name,
+		notification_channels_section,
+		teams_section,
 		repos_section: repos,
 		can_delete: organization_user.is_admin,
 	};
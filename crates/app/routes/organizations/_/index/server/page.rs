@@ -1,3 +1,4 @@
+use modelfox_app_base_path::app_path;
 use modelfox_app_layouts::{
 	app_layout::{AppLayout, AppLayoutInfo},
 	document::Document,
@@ -12,6 +13,8 @@ pub struct Page {
 	pub id: String,
 	pub members_section: MembersSection,
 	pub name: String,
+	pub notification_channels_section: NotificationChannelsSection,
+	pub teams_section: TeamsSection,
 	pub repos_section: ReposSection,
 	pub can_delete: bool,
 }
@@ -25,6 +28,8 @@ impl Component for Page {
 						.child(ui::H1::new(self.name))
 						.child(self.details_section)
 						.child(self.members_section)
+						.child(self.teams_section)
+						.child(self.notification_channels_section)
 						.child(self.repos_section)
 						.child(if self.can_delete {
 							Some(DangerZoneSection)
@@ -40,6 +45,7 @@ impl Component for Page {
 pub struct DetailsSection {
 	pub organization_id: String,
 	pub organization_name: String,
+	pub organization_timezone: Option<String>,
 	pub can_edit: bool,
 }
 
@@ -49,7 +55,10 @@ impl Component for DetailsSection {
 			.child(
 				ui::SpaceBetween::new().child(ui::H2::new("Details")).child(
 					ui::Button::new()
-						.href(format!("/organizations/{}/edit", self.organization_id))
+						.href(app_path(format!(
+							"/organizations/{}/edit",
+							self.organization_id
+						)))
 						.color(ui::colors::GRAY.to_owned())
 						.disabled(false)
 						.child("Edit"),
@@ -62,6 +71,16 @@ impl Component for DetailsSection {
 					.label("Organization Name".to_owned())
 					.readonly(true),
 			)
+			.child(
+				ui::TextField::new()
+					.disabled(true)
+					.value(
+						self.organization_timezone
+							.unwrap_or_else(|| "Not set".to_owned()),
+					)
+					.label("Timezone".to_owned())
+					.readonly(true),
+			)
 			.into_node()
 	}
 }
@@ -77,10 +96,10 @@ impl Component for MembersSection {
 			.child(
 				ui::SpaceBetween::new().child(ui::H2::new("Members")).child(
 					ui::Button::new()
-						.href(format!(
+						.href(app_path(format!(
 							"/organizations/{}/members/new",
 							self.organization_id,
-						))
+						)))
 						.child("Invite Team Member"),
 				),
 			)
@@ -99,6 +118,7 @@ pub struct MembersTableRow {
 	pub id: Id,
 	pub email: String,
 	pub is_admin: bool,
+	pub has_data_access: bool,
 }
 
 impl Component for MembersTable {
@@ -125,8 +145,10 @@ impl Component for MembersTable {
 					.child(ui::TableCell::new().child(member_cell))
 					.child(ui::TableCell::new().child(if row.is_admin {
 						"Admin"
-					} else {
+					} else if row.has_data_access {
 						"Member"
+					} else {
+						"Viewer"
 					}))
 			})))
 			.into_node()
@@ -163,7 +185,297 @@ impl Component for MemberDeleteForm {
 	}
 }
 
+pub struct TeamsSection {
+	pub can_edit: bool,
+	pub rows: Vec<TeamsTableRow>,
+}
+
+pub struct TeamsTableRow {
+	pub id: Id,
+	pub name: String,
+	pub member_count: i64,
+	pub repo_count: i64,
+}
+
+impl Component for TeamsSection {
+	fn into_node(self) -> Node {
+		let teams_table_or_empty_message = if self.rows.is_empty() {
+			ui::Card::new()
+				.child(ui::P::new().child(
+					"This organization does not have any teams. Grant a repo to one or more teams to limit access to it to the members of those teams and the organization's admins.",
+				))
+				.into_node()
+		} else {
+			ui::Table::new()
+				.width("100%".to_owned())
+				.child(
+					ui::TableHeader::new().child(
+						ui::TableRow::new()
+							.child(ui::TableHeaderCell::new().child("Name"))
+							.child(ui::TableHeaderCell::new().child("Members"))
+							.child(ui::TableHeaderCell::new().child("Repos")),
+					),
+				)
+				.child(
+					ui::TableBody::new().children(self.rows.into_iter().map(|row| {
+						ui::TableRow::new()
+							.child(
+								ui::TableCell::new().child(
+									ui::Link::new()
+										.href(format!("teams/{}", row.id))
+										.child(row.name),
+								),
+							)
+							.child(ui::TableCell::new().child(row.member_count.to_string()))
+							.child(ui::TableCell::new().child(row.repo_count.to_string()))
+					})),
+				)
+				.into_node()
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Teams"))
+			.child(teams_table_or_empty_message)
+			.child(if self.can_edit {
+				Some(TeamCreateForm)
+			} else {
+				None
+			})
+			.into_node()
+	}
+}
+
+struct TeamCreateForm;
+
+impl Component for TeamCreateForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "create_team"),
+			)
+			.child(
+				ui::TextField::new()
+					.label("Name".to_owned())
+					.name("name".to_owned())
+					.required(true),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.child("Create Team"),
+			)
+			.into_node()
+	}
+}
+
+pub struct NotificationChannelsSection {
+	pub can_edit: bool,
+	/// This is `None` if the app has no secret key to encrypt notification channels with.
+	pub notification_channels_table: Option<NotificationChannelsTable>,
+}
+
+impl Component for NotificationChannelsSection {
+	fn into_node(self) -> Node {
+		let can_edit = self.can_edit;
+		let notification_channels_table = match self.notification_channels_table {
+			Some(notification_channels_table) => notification_channels_table,
+			None => {
+				return ui::S2::new()
+					.child(ui::H2::new("Notification Channels"))
+					.child(ui::Card::new().child(ui::P::new().child(
+						"Set a secret key in your app config to store notification channels.",
+					)))
+					.into_node()
+			}
+		};
+		let notification_channels_table_or_empty_message = if notification_channels_table
+			.rows
+			.is_empty()
+		{
+			ui::Card::new()
+					.child(ui::P::new().child(
+						"This organization does not have any notification channels. Monitors can send alerts to a notification channel so that its credentials are managed in one place.",
+					))
+					.into_node()
+		} else {
+			notification_channels_table.into_node()
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Notification Channels"))
+			.child(notification_channels_table_or_empty_message)
+			.child(if can_edit {
+				Some(NotificationChannelCreateForm)
+			} else {
+				None
+			})
+			.into_node()
+	}
+}
+
+pub struct NotificationChannelsTable {
+	pub can_edit: bool,
+	pub rows: Vec<NotificationChannelsTableRow>,
+}
+
+pub struct NotificationChannelsTableRow {
+	pub id: Id,
+	pub name: String,
+	pub kind: String,
+}
+
+impl Component for NotificationChannelsTable {
+	fn into_node(self) -> Node {
+		let can_edit = self.can_edit;
+		ui::Table::new()
+			.width("100%".to_owned())
+			.child(
+				ui::TableHeader::new().child(
+					ui::TableRow::new()
+						.child(ui::TableHeaderCell::new().child("Name"))
+						.child(ui::TableHeaderCell::new().child("Type"))
+						.child(if can_edit {
+							Some(ui::TableHeaderCell::new().child("Rotate"))
+						} else {
+							None
+						})
+						.child(if can_edit {
+							Some(ui::TableHeaderCell::new())
+						} else {
+							None
+						}),
+				),
+			)
+			.child(
+				ui::TableBody::new().children(self.rows.into_iter().map(|row| {
+					let id = row.id.to_string();
+					ui::TableRow::new()
+						.child(ui::TableCell::new().child(row.name))
+						.child(ui::TableCell::new().child(row.kind))
+						.child(if can_edit {
+							Some(ui::TableCell::new().child(NotificationChannelUpdateForm {
+								notification_channel_id: id.clone(),
+							}))
+						} else {
+							None
+						})
+						.child(if can_edit {
+							Some(ui::TableCell::new().child(NotificationChannelDeleteForm {
+								notification_channel_id: id,
+							}))
+						} else {
+							None
+						})
+				})),
+			)
+			.into_node()
+	}
+}
+
+struct NotificationChannelCreateForm;
+
+impl Component for NotificationChannelCreateForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "create_notification_channel"),
+			)
+			.child(
+				ui::TextField::new()
+					.label("Name".to_owned())
+					.name("name".to_owned())
+					.required(true),
+			)
+			.child(
+				ui::TextField::new()
+					.label("Webhook URL or Email Address".to_owned())
+					.name("destination".to_owned())
+					.required(true),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.child("Create Notification Channel"),
+			)
+			.into_node()
+	}
+}
+
+struct NotificationChannelUpdateForm {
+	notification_channel_id: String,
+}
+
+impl Component for NotificationChannelUpdateForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "update_notification_channel"),
+			)
+			.child(
+				input()
+					.attribute("name", "notification_channel_id")
+					.attribute("type", "hidden")
+					.attribute("value", self.notification_channel_id),
+			)
+			.child(
+				ui::TextField::new()
+					.name("destination".to_owned())
+					.placeholder("New Webhook URL or Email Address".to_owned())
+					.required(true),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.color(ui::colors::GRAY.to_owned())
+					.child("Update"),
+			)
+			.into_node()
+	}
+}
+
+struct NotificationChannelDeleteForm {
+	notification_channel_id: String,
+}
+
+impl Component for NotificationChannelDeleteForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.onsubmit("return confirm(\"Monitors using this notification channel will stop sending alerts to it. Are you sure?\")".to_owned())
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "delete_notification_channel"),
+			)
+			.child(
+				input()
+					.attribute("name", "notification_channel_id")
+					.attribute("type", "hidden")
+					.attribute("value", self.notification_channel_id),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.color(ui::colors::RED.to_owned())
+					.child("Delete"),
+			)
+			.into_node()
+	}
+}
+
 pub struct ReposSection {
+	pub organization_id: Id,
 	pub repos_table: Option<ReposTable>,
 }
 
@@ -181,10 +493,23 @@ impl Component for ReposSection {
 			.child(
 				ui::SpaceBetween::new().child(ui::H2::new("Repos")).child(
 					ui::Button::new()
-						.href("/repos/new".to_owned())
+						.href(app_path("/repos/new"))
 						.child("Create New Repo"),
 				),
 			)
+			.child(
+				ui::P::new()
+					.child("See the stage, production metrics, and alerts of every model in one place in the ")
+					.child(
+						ui::Link::new()
+							.href(app_path(format!(
+								"/organizations/{}/models",
+								self.organization_id
+							)))
+							.child("model inventory"),
+					)
+					.child("."),
+			)
 			.child(repos_table_or_empty_message)
 			.into_node()
 	}
@@ -213,7 +538,7 @@ impl Component for ReposTable {
 					ui::TableRow::new().child(
 						ui::TableCell::new().child(
 							ui::Link::new()
-								.href(format!("/repos/{}/", row.id))
+								.href(app_path(format!("/repos/{}/", row.id)))
 								.child(row.title),
 						),
 					)
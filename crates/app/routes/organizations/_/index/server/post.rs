@@ -1,9 +1,15 @@
 use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
+	alert::AlertMethod,
 	error::{bad_request, not_found, service_unavailable, unauthorized},
-	organizations::delete_organization,
+	notification_channels::{
+		create_notification_channel, delete_notification_channel, update_notification_channel,
+	},
+	organizations::{delete_organization, get_organization_user},
 	path_components,
+	teams::create_team,
 	user::{authorize_normal_user, authorize_normal_user_for_organization},
 };
 use modelfox_id::Id;
@@ -14,6 +20,36 @@ use std::sync::Arc;
 enum Action {
 	#[serde(rename = "delete_organization")]
 	DeleteOrganization,
+	#[serde(rename = "create_notification_channel")]
+	CreateNotificationChannel(CreateNotificationChannelAction),
+	#[serde(rename = "update_notification_channel")]
+	UpdateNotificationChannel(UpdateNotificationChannelAction),
+	#[serde(rename = "delete_notification_channel")]
+	DeleteNotificationChannel(DeleteNotificationChannelAction),
+	#[serde(rename = "create_team")]
+	CreateTeam(CreateTeamAction),
+}
+
+#[derive(serde::Deserialize)]
+struct CreateNotificationChannelAction {
+	name: String,
+	destination: String,
+}
+
+#[derive(serde::Deserialize)]
+struct UpdateNotificationChannelAction {
+	notification_channel_id: String,
+	destination: String,
+}
+
+#[derive(serde::Deserialize)]
+struct DeleteNotificationChannelAction {
+	notification_channel_id: String,
+}
+
+#[derive(serde::Deserialize)]
+struct CreateTeamAction {
+	name: String,
 }
 
 pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
@@ -40,7 +76,7 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_normal_user(request, &mut db).await? {
+	let user = match authorize_normal_user(request, &mut db, app.clock()).await? {
 		Ok(user) => user,
 		Err(_) => return Ok(unauthorized()),
 	};
@@ -56,7 +92,98 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 			delete_organization(&mut db, organization_id).await?;
 			http::Response::builder()
 				.status(http::StatusCode::SEE_OTHER)
-				.header(http::header::LOCATION, "/user")
+				.header(http::header::LOCATION, app_path("/user"))
+				.body(hyper::Body::empty())
+				.unwrap()
+		}
+		Action::CreateTeam(action) => {
+			let is_admin = get_organization_user(&mut db, organization_id, user.id)
+				.await?
+				.map(|organization_user| organization_user.is_admin)
+				.unwrap_or(false);
+			if !is_admin {
+				return Ok(unauthorized());
+			}
+			let name = action.name.trim();
+			if name.is_empty() {
+				return Ok(bad_request());
+			}
+			let now = app.clock().now_utc().unix_timestamp();
+			let team_id = create_team(&mut db, organization_id, name, now).await?;
+			http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(
+					http::header::LOCATION,
+					app_path(format!(
+						"/organizations/{}/teams/{}",
+						organization_id, team_id
+					)),
+				)
+				.body(hyper::Body::empty())
+				.unwrap()
+		}
+		action => {
+			let is_admin = get_organization_user(&mut db, organization_id, user.id)
+				.await?
+				.map(|organization_user| organization_user.is_admin)
+				.unwrap_or(false);
+			if !is_admin {
+				return Ok(unauthorized());
+			}
+			let secret_key = match app.options().secret_key.as_ref() {
+				Some(secret_key) => secret_key,
+				None => return Ok(bad_request()),
+			};
+			match action {
+				Action::DeleteOrganization | Action::CreateTeam(_) => unreachable!(),
+				Action::CreateNotificationChannel(action) => {
+					let method = match parse_destination(action.destination) {
+						Some(method) => method,
+						None => return Ok(bad_request()),
+					};
+					create_notification_channel(
+						&mut db,
+						secret_key,
+						organization_id,
+						&action.name,
+						&method,
+						app.clock(),
+					)
+					.await?;
+				}
+				Action::UpdateNotificationChannel(action) => {
+					let notification_channel_id: Id = match action.notification_channel_id.parse() {
+						Ok(notification_channel_id) => notification_channel_id,
+						Err(_) => return Ok(bad_request()),
+					};
+					let method = match parse_destination(action.destination) {
+						Some(method) => method,
+						None => return Ok(bad_request()),
+					};
+					update_notification_channel(
+						&mut db,
+						secret_key,
+						organization_id,
+						notification_channel_id,
+						&method,
+					)
+					.await?;
+				}
+				Action::DeleteNotificationChannel(action) => {
+					let notification_channel_id: Id = match action.notification_channel_id.parse() {
+						Ok(notification_channel_id) => notification_channel_id,
+						Err(_) => return Ok(bad_request()),
+					};
+					delete_notification_channel(&mut db, organization_id, notification_channel_id)
+						.await?;
+				}
+			}
+			http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(
+					http::header::LOCATION,
+					app_path(format!("/organizations/{}/", organization_id)),
+				)
 				.body(hyper::Body::empty())
 				.unwrap()
 		}
@@ -64,3 +191,15 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 	app.commit_transaction(db).await?;
 	Ok(response)
 }
+
+/// Interpret a notification channel destination as a webhook url if it has a scheme, or otherwise as an email address.
+fn parse_destination(destination: String) -> Option<AlertMethod> {
+	let destination = destination.trim().to_owned();
+	if destination.contains("://") {
+		destination.try_into().ok().map(AlertMethod::Webhook)
+	} else if destination.contains('@') {
+		Some(AlertMethod::Email(destination.into()))
+	} else {
+		None
+	}
+}
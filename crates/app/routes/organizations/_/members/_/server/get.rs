@@ -24,7 +24,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_normal_user(request, &mut db).await? {
+	let user = match authorize_normal_user(request, &mut db, app.clock()).await? {
 		Ok(user) => user,
 		Err(_) => return Ok(unauthorized()),
 	};
@@ -48,7 +48,8 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			select
 				users.id,
 				users.email,
-				organizations_users.is_admin
+				organizations_users.is_admin,
+				organizations_users.has_data_access
 			from users
 			join organizations_users
 				on organizations_users.organization_id = $1
@@ -64,6 +65,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let admin_member_count = get_admin_member_count(&mut db, organization_id).await?;
 	let member_id = member_id.parse().unwrap();
 	let member_is_admin = row.get(2);
+	let member_has_data_access = row.get(3);
 	let user_is_admin = get_organization_user(&mut db, organization_id, user.id)
 		.await?
 		.unwrap()
@@ -79,6 +81,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	} else {
 		false
 	};
+	let can_force_logout = user_is_admin && user.id != member_id;
 	let remove_button_text = if user.id == member_id {
 		"Leave Organization".to_owned()
 	} else {
@@ -88,7 +91,9 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		app_layout_info,
 		member_email,
 		is_admin: member_is_admin,
+		has_data_access: member_has_data_access,
 		can_delete,
+		can_force_logout,
 		remove_button_text,
 	};
 	app.commit_transaction(db).await?;
@@ -8,6 +8,8 @@ use pinwheel::prelude::*;
 pub struct Page {
 	pub app_layout_info: AppLayoutInfo,
 	pub can_delete: bool,
+	pub can_force_logout: bool,
+	pub has_data_access: bool,
 	pub is_admin: bool,
 	pub member_email: String,
 	pub remove_button_text: String,
@@ -44,6 +46,16 @@ impl Component for Page {
 												.value("true".to_owned())
 												.checked(self.is_admin),
 										)
+										.child(
+											ui::CheckboxField::new()
+												.label("Data Access".to_owned())
+												.name("has_data_access".to_owned())
+												.value("true".to_owned())
+												.checked(self.has_data_access),
+										)
+										.child(ui::P::new().child(
+											"Members without data access can see the metrics of the organization's models but not the inputs of their production predictions. Admins always have data access.",
+										))
 										.child(
 											ui::Button::new()
 												.button_type(ui::ButtonType::Submit)
@@ -51,6 +63,11 @@ impl Component for Page {
 										),
 								),
 						)
+						.child(if self.can_force_logout {
+							Some(ForceLogoutSection)
+						} else {
+							None
+						})
 						.child(if self.can_delete {
 							Some(DangerZone {
 								remove_button_text: self.remove_button_text,
@@ -64,6 +81,36 @@ impl Component for Page {
 	}
 }
 
+struct ForceLogoutSection;
+
+impl Component for ForceLogoutSection {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("Sessions"))
+			.child(ui::P::new().child(
+				"Revoke all of this member's sessions. They will be logged out everywhere and must log in again.",
+			))
+			.child(
+				ui::Form::new()
+					.post(true)
+					.onsubmit("return confirm(\"Are you sure?\")".to_owned())
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "force_logout"),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.color(ui::colors::RED.to_owned())
+							.child("Log Out Everywhere"),
+					),
+			)
+			.into_node()
+	}
+}
+
 struct DangerZone {
 	remove_button_text: String,
 }
@@ -1,8 +1,12 @@
 use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, service_unavailable, unauthorized},
+	organizations::get_organization_user,
 	path_components,
+	sessions::revoke_all_sessions,
+	teams::remove_user_from_organization_teams,
 	user::{authorize_normal_user, authorize_normal_user_for_organization},
 };
 use modelfox_id::Id;
@@ -16,12 +20,16 @@ enum Action {
 	Delete,
 	#[serde(rename = "update_member")]
 	Update(MemberFields),
+	#[serde(rename = "force_logout")]
+	ForceLogout,
 }
 
 #[derive(Debug, serde::Deserialize)]
 struct MemberFields {
 	#[serde(default, deserialize_with = "bool_from_string")]
 	is_admin: bool,
+	#[serde(default, deserialize_with = "bool_from_string")]
+	has_data_access: bool,
 }
 
 fn bool_from_string<'de, D>(deserializer: D) -> Result<bool, D::Error>
@@ -55,7 +63,7 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_normal_user(request, &mut db).await? {
+	let user = match authorize_normal_user(request, &mut db, app.clock()).await? {
 		Ok(user) => user,
 		Err(_) => return Ok(unauthorized()),
 	};
@@ -82,9 +90,9 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Action::Delete => {
 			delete_member(&mut db, organization_id, member_id).await?;
 			let redirect_location = if member_id == user.id {
-				"/".to_owned()
+				app_path("/")
 			} else {
-				format!("/organizations/{}/", organization_id)
+				app_path(format!("/organizations/{}/", organization_id))
 			};
 			http::Response::builder()
 				.status(http::StatusCode::SEE_OTHER)
@@ -92,13 +100,38 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 				.body(hyper::Body::empty())
 				.unwrap()
 		}
+		Action::ForceLogout => {
+			let user_is_admin = get_organization_user(&mut db, organization_id, user.id)
+				.await?
+				.map(|organization_user| organization_user.is_admin)
+				.unwrap_or(false);
+			let member_in_organization = get_organization_user(&mut db, organization_id, member_id)
+				.await?
+				.is_some();
+			if !user_is_admin || !member_in_organization {
+				return Ok(not_found());
+			}
+			let now = app.clock().now_utc().unix_timestamp();
+			revoke_all_sessions(&mut db, member_id, now).await?;
+			http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(
+					http::header::LOCATION,
+					app_path(format!(
+						"/organizations/{}/members/{}",
+						organization_id, member_id
+					)),
+				)
+				.body(hyper::Body::empty())
+				.unwrap()
+		}
 		Action::Update(member_fields) => {
 			update_member(&mut db, organization_id, member_id, member_fields).await?;
 			http::Response::builder()
 				.status(http::StatusCode::SEE_OTHER)
 				.header(
 					http::header::LOCATION,
-					format!("/organizations/{}/", organization_id),
+					app_path(format!("/organizations/{}/", organization_id)),
 				)
 				.body(hyper::Body::empty())
 				.unwrap()
@@ -126,6 +159,7 @@ async fn delete_member(
 	.bind(&member_id.to_string())
 	.execute(txn.borrow_mut())
 	.await?;
+	remove_user_from_organization_teams(txn, organization_id, member_id).await?;
 	Ok(())
 }
 
@@ -135,12 +169,14 @@ async fn update_member(
 	member_id: Id,
 	member_fields: MemberFields,
 ) -> Result<()> {
-	dbg!(&member_fields);
+	tracing::debug!(%organization_id, %member_id, ?member_fields, "updating member");
 	sqlx::query(
 		"
 			update
 				organizations_users
-			set is_admin = $3
+			set
+				is_admin = $3,
+				has_data_access = $4
 			where
 				organization_id = $1
 				and user_id = $2
@@ -149,6 +185,7 @@ async fn update_member(
 	.bind(&organization_id.to_string())
 	.bind(&member_id.to_string())
 	.bind(&member_fields.is_admin)
+	.bind(&member_fields.has_data_access)
 	.execute(txn.borrow_mut())
 	.await?;
 	Ok(())
@@ -28,6 +28,12 @@ impl Component for Page {
 									.label("Admin".to_owned())
 									.name("is_admin".to_owned()),
 							)
+							.child(
+								ui::CheckboxField::new()
+									.label("Data Access".to_owned())
+									.name("has_data_access".to_owned())
+									.checked(true),
+							)
 							.child(
 								ui::Button::new()
 									.button_type(ui::ButtonType::Submit)
@@ -1,4 +1,5 @@
 use anyhow::{anyhow, bail, Result};
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, service_unavailable, unauthorized},
@@ -16,6 +17,7 @@ use url::Url;
 struct Action {
 	email: String,
 	is_admin: Option<String>,
+	has_data_access: Option<String>,
 }
 
 pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
@@ -35,7 +37,7 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_normal_user(request, &mut txn).await? {
+	let user = match authorize_normal_user(request, &mut txn, app.clock()).await? {
 		Ok(user) => user,
 		Err(_) => return Ok(unauthorized()),
 	};
@@ -107,18 +109,23 @@ async fn add_member(
 	} else {
 		false
 	};
+	let has_data_access = action
+		.has_data_access
+		.map(|has_data_access| has_data_access == "on")
+		.unwrap_or(false);
 	sqlx::query(
 		"
 			insert into organizations_users
-				(organization_id, user_id, is_admin)
+				(organization_id, user_id, is_admin, has_data_access)
 			values
-				($1, $2, $3)
+				($1, $2, $3, $4)
 			on conflict (organization_id, user_id) do nothing
 		",
 	)
 	.bind(&organization_id.to_string())
 	.bind(&user_id.to_string())
 	.bind(&is_admin)
+	.bind(&has_data_access)
 	.execute(txn.borrow_mut())
 	.await?;
 	// Send the new user an invitation email.
@@ -133,7 +140,7 @@ async fn add_member(
 		.status(http::StatusCode::SEE_OTHER)
 		.header(
 			http::header::LOCATION,
-			format!("/organizations/{}/", organization_id),
+			app_path(format!("/organizations/{}/", organization_id)),
 		)
 		.body(hyper::Body::empty())
 		.unwrap();
@@ -147,7 +154,7 @@ async fn send_invitation_email(
 	url: Url,
 ) -> Result<()> {
 	let mut href = url;
-	href.set_path("/login");
+	href.set_path(&app_path("/login"));
 	href.set_query(Some(&format!("email={}", invitee_email)));
 	let email = lettre::Message::builder()
 		.from("ModelFox <noreply@modelfox.dev>".parse()?)
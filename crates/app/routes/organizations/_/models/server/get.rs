@@ -0,0 +1,98 @@
+use crate::page::{ModelInventoryTable, ModelInventoryTableRow, Page};
+use anyhow::{bail, Result};
+use chrono::prelude::*;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, service_unavailable, unauthorized},
+	inventory::get_model_inventory,
+	organizations::{get_organization, get_organization_user},
+	path_components,
+	timezone::{get_organization_timezone, get_timezone},
+	user::{authorize_normal_user, authorize_normal_user_for_organization},
+};
+use modelfox_app_layouts::app_layout::app_layout_info;
+use modelfox_id::Id;
+use pinwheel::prelude::*;
+use std::sync::Arc;
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let organization_id = if let ["organizations", organization_id, "models"] =
+		*path_components(request).as_slice()
+	{
+		organization_id.to_owned()
+	} else {
+		bail!("unexpected path");
+	};
+	if !app.options().auth_enabled() {
+		return Ok(not_found());
+	}
+	let app_layout_info = app_layout_info(app).await?;
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_normal_user(request, &mut db, app.clock()).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(unauthorized()),
+	};
+	let organization_id: Id = match organization_id.parse() {
+		Ok(organization_id) => organization_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_normal_user_for_organization(&mut db, &user, organization_id).await? {
+		return Ok(not_found());
+	}
+	let organization = match get_organization(organization_id, &mut db).await? {
+		Some(organization) => organization,
+		None => return Ok(not_found()),
+	};
+	let organization_user = get_organization_user(&mut db, organization_id, user.id)
+		.await?
+		.unwrap();
+	let timezone = get_organization_timezone(&mut db, organization_id)
+		.await?
+		.unwrap_or_else(|| get_timezone(request));
+	let format_date = |date: i64| Utc.timestamp(date, 0).with_timezone(&timezone).to_string();
+	let rows = get_model_inventory(
+		&mut db,
+		organization_id,
+		user.id,
+		organization_user.is_admin,
+	)
+	.await?
+	.into_iter()
+	.map(|entry| ModelInventoryTableRow {
+		repo_id: entry.repo_id,
+		repo_title: entry.repo_title,
+		model_id: entry.model_id,
+		model_tag: entry.model_tag,
+		created_at: format_date(entry.created_at),
+		stage: entry.stage,
+		last_prediction_date: entry.last_prediction_date.map(format_date),
+		production_metric: entry.production_metric,
+		out_of_range_fraction: entry.out_of_range_fraction,
+		monitor_count: entry.monitor_count,
+		alerting_monitor_count: entry.alerting_monitor_count,
+	})
+	.collect::<Vec<_>>();
+	let model_inventory_table = if !rows.is_empty() {
+		Some(ModelInventoryTable { rows })
+	} else {
+		None
+	};
+	let page = Page {
+		app_layout_info,
+		organization_id,
+		organization_name: organization.name,
+		model_inventory_table,
+	};
+	app.commit_transaction(db).await?;
+	let html = html(page);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
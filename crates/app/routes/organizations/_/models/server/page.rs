@@ -0,0 +1,191 @@
+use modelfox_app_base_path::app_path;
+use modelfox_app_core::{alert::AlertMetric, inventory::ModelStage};
+use modelfox_app_layouts::{
+	app_layout::{AppLayout, AppLayoutInfo},
+	document::Document,
+};
+use modelfox_id::Id;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct Page {
+	pub app_layout_info: AppLayoutInfo,
+	pub organization_id: Id,
+	pub organization_name: String,
+	pub model_inventory_table: Option<ModelInventoryTable>,
+}
+
+impl Component for Page {
+	fn into_node(self) -> Node {
+		let model_inventory_table_or_empty_message = self
+			.model_inventory_table
+			.map(|model_inventory_table| model_inventory_table.into_node())
+			.unwrap_or_else(|| {
+				ui::Card::new()
+					.child(ui::P::new().child("This organization does not have any models."))
+					.into_node()
+			});
+		Document::new()
+			.child(
+				AppLayout::new(self.app_layout_info).child(
+					ui::S1::new()
+						.child(
+							ui::SpaceBetween::new()
+								.child(ui::H1::new(format!(
+									"{} Model Inventory",
+									self.organization_name
+								)))
+								.child(
+									ui::Button::new()
+										.href(app_path(format!(
+											"/organizations/{}/",
+											self.organization_id
+										)))
+										.color(ui::colors::GRAY.to_owned())
+										.child("Back to Organization"),
+								),
+						)
+						.child(ui::P::new().child(
+							"Every model in the organization's repos that you can access. The production metric and the out of range fraction are from the latest hour with logged data.",
+						))
+						.child(model_inventory_table_or_empty_message),
+				),
+			)
+			.into_node()
+	}
+}
+
+pub struct ModelInventoryTable {
+	pub rows: Vec<ModelInventoryTableRow>,
+}
+
+pub struct ModelInventoryTableRow {
+	pub repo_id: Id,
+	pub repo_title: String,
+	pub model_id: Id,
+	pub model_tag: Option<String>,
+	pub created_at: String,
+	pub stage: ModelStage,
+	pub last_prediction_date: Option<String>,
+	pub production_metric: Option<(AlertMetric, f32)>,
+	pub out_of_range_fraction: Option<f32>,
+	pub monitor_count: usize,
+	pub alerting_monitor_count: usize,
+}
+
+impl Component for ModelInventoryTable {
+	fn into_node(self) -> Node {
+		ui::Table::new()
+			.width("100%".to_owned())
+			.child(
+				ui::TableHeader::new().child(
+					ui::TableRow::new()
+						.child(ui::TableHeaderCell::new().child("Repo"))
+						.child(ui::TableHeaderCell::new().child("Model"))
+						.child(ui::TableHeaderCell::new().child("Stage"))
+						.child(ui::TableHeaderCell::new().child("Uploaded"))
+						.child(ui::TableHeaderCell::new().child("Last Prediction"))
+						.child(ui::TableHeaderCell::new().child("Production Metric"))
+						.child(ui::TableHeaderCell::new().child("Out of Range Fraction"))
+						.child(ui::TableHeaderCell::new().child("Alerts")),
+				),
+			)
+			.child(
+				ui::TableBody::new().children(self.rows.into_iter().map(|row| {
+					let model_title = row.model_tag.unwrap_or_else(|| row.model_id.to_string());
+					let production_metric = row
+						.production_metric
+						.map(|(metric, value)| {
+							format!("{} {}", metric, metric.number_formatter().format(value))
+						})
+						.unwrap_or_else(|| "N/A".to_owned());
+					let out_of_range_fraction = AlertMetric::OutOfRangeFraction
+						.number_formatter()
+						.format_option(row.out_of_range_fraction);
+					ui::TableRow::new()
+						.child(
+							ui::TableCell::new().child(
+								ui::Link::new()
+									.href(app_path(format!("/repos/{}/", row.repo_id)))
+									.child(row.repo_title),
+							),
+						)
+						.child(
+							ui::TableCell::new().child(
+								ui::Link::new()
+									.href(app_path(format!(
+										"/repos/{}/models/{}/",
+										row.repo_id, row.model_id
+									)))
+									.child(model_title),
+							),
+						)
+						.child(ui::TableCell::new().child(ModelStageToken { stage: row.stage }))
+						.child(ui::TableCell::new().child(row.created_at))
+						.child(
+							ui::TableCell::new().child(
+								row.last_prediction_date
+									.unwrap_or_else(|| "Never".to_owned()),
+							),
+						)
+						.child(ui::TableCell::new().child(production_metric))
+						.child(ui::TableCell::new().child(out_of_range_fraction))
+						.child(ui::TableCell::new().child(AlertsCell {
+							repo_id: row.repo_id,
+							model_id: row.model_id,
+							monitor_count: row.monitor_count,
+							alerting_monitor_count: row.alerting_monitor_count,
+						}))
+				})),
+			)
+			.into_node()
+	}
+}
+
+struct ModelStageToken {
+	stage: ModelStage,
+}
+
+impl Component for ModelStageToken {
+	fn into_node(self) -> Node {
+		let color = match self.stage {
+			ModelStage::Approved => ui::colors::GREEN,
+			ModelStage::Latest => ui::colors::BLUE,
+			ModelStage::Superseded => ui::colors::GRAY,
+		};
+		ui::Token::new()
+			.color(color.to_owned())
+			.child(self.stage.to_string().to_uppercase())
+			.into_node()
+	}
+}
+
+struct AlertsCell {
+	repo_id: Id,
+	model_id: Id,
+	monitor_count: usize,
+	alerting_monitor_count: usize,
+}
+
+impl Component for AlertsCell {
+	fn into_node(self) -> Node {
+		if self.monitor_count == 0 {
+			return span().child("No Monitors").into_node();
+		}
+		let (color, text) = if self.alerting_monitor_count > 0 {
+			(
+				ui::colors::RED,
+				format!("{} ALERTING", self.alerting_monitor_count),
+			)
+		} else {
+			(ui::colors::GREEN, "OK".to_owned())
+		};
+		ui::Link::new()
+			.href(app_path(format!(
+				"/repos/{}/models/{}/monitors/",
+				self.repo_id, self.model_id
+			)))
+			.child(ui::Token::new().color(color.to_owned()).child(text))
+			.into_node()
+	}
+}
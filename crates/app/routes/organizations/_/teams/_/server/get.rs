@@ -0,0 +1,153 @@
+use crate::page::{
+	MemberOption, MembersTableRow, Page, RepoOption, ReposTableRow, TeamMembersSection,
+	TeamReposSection,
+};
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, service_unavailable, unauthorized},
+	organizations::{get_organization, get_organization_user},
+	path_components,
+	teams::{
+		authorize_normal_user_for_team_maintenance, authorize_normal_user_for_team_repos, get_team,
+		get_team_members, get_team_repos,
+	},
+	user::{authorize_normal_user, authorize_normal_user_for_organization},
+};
+use modelfox_app_layouts::app_layout::app_layout_info;
+use modelfox_id::Id;
+use pinwheel::prelude::*;
+use sqlx::prelude::*;
+use std::{borrow::BorrowMut, sync::Arc};
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	if !app.options().auth_enabled() {
+		return Ok(not_found());
+	}
+	let (organization_id, team_id) = if let ["organizations", organization_id, "teams", team_id] =
+		*path_components(request).as_slice()
+	{
+		(organization_id.to_owned(), team_id.to_owned())
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_normal_user(request, &mut db, app.clock()).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(unauthorized()),
+	};
+	let organization_id: Id = match organization_id.parse() {
+		Ok(organization_id) => organization_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	let team_id: Id = match team_id.parse() {
+		Ok(team_id) => team_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_normal_user_for_organization(&mut db, &user, organization_id).await? {
+		return Ok(not_found());
+	}
+	let team = match get_team(&mut db, organization_id, team_id).await? {
+		Some(team) => team,
+		None => return Ok(not_found()),
+	};
+	let app_layout_info = app_layout_info(app).await?;
+	let can_manage = authorize_normal_user_for_team_maintenance(&mut db, &user, team_id).await?;
+	let can_manage_repos = authorize_normal_user_for_team_repos(&mut db, &user, team_id).await?;
+	let can_delete = get_organization_user(&mut db, organization_id, user.id)
+		.await?
+		.map(|organization_user| organization_user.is_admin)
+		.unwrap_or(false);
+	let team_members = get_team_members(&mut db, team_id).await?;
+	let team_repos = get_team_repos(&mut db, team_id).await?;
+	// Only the organization's members who are not on the team yet can be added to it.
+	let member_options = get_organization(organization_id, &mut db)
+		.await?
+		.map(|organization| organization.members)
+		.unwrap_or_default()
+		.into_iter()
+		.filter(|member| {
+			!team_members
+				.iter()
+				.any(|team_member| team_member.user_id == member.id)
+		})
+		.map(|member| MemberOption {
+			id: member.id,
+			email: member.email,
+		})
+		.collect();
+	let repo_options = get_organization_repos(&mut db, organization_id)
+		.await?
+		.into_iter()
+		.filter(|repo| !team_repos.iter().any(|team_repo| team_repo.id == repo.id))
+		.collect();
+	let members_section = TeamMembersSection {
+		can_manage,
+		rows: team_members
+			.into_iter()
+			.map(|team_member| MembersTableRow {
+				user_id: team_member.user_id,
+				email: team_member.email,
+				is_maintainer: team_member.is_maintainer,
+			})
+			.collect(),
+		member_options,
+	};
+	let repos_section = TeamReposSection {
+		can_manage: can_manage_repos,
+		rows: team_repos
+			.into_iter()
+			.map(|team_repo| ReposTableRow {
+				id: team_repo.id,
+				title: team_repo.title,
+			})
+			.collect(),
+		repo_options,
+	};
+	let page = Page {
+		app_layout_info,
+		name: team.name,
+		members_section,
+		repos_section,
+		can_delete,
+	};
+	app.commit_transaction(db).await?;
+	let html = html(page);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
+
+async fn get_organization_repos(
+	txn: &mut sqlx::Transaction<'_, sqlx::Any>,
+	organization_id: Id,
+) -> Result<Vec<RepoOption>> {
+	let rows = sqlx::query(
+		"
+			select
+				repos.id,
+				repos.title
+			from repos
+			where repos.organization_id = $1
+			order by repos.title
+		",
+	)
+	.bind(&organization_id.to_string())
+	.fetch_all(txn.borrow_mut())
+	.await?;
+	rows.into_iter()
+		.map(|row| {
+			Ok(RepoOption {
+				id: row.get::<String, _>(0).parse()?,
+				title: row.get(1),
+			})
+		})
+		.collect()
+}
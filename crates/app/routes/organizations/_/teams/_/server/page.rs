@@ -0,0 +1,343 @@
+use modelfox_app_base_path::app_path;
+use modelfox_app_layouts::{
+	app_layout::{AppLayout, AppLayoutInfo},
+	document::Document,
+};
+use modelfox_id::Id;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct Page {
+	pub app_layout_info: AppLayoutInfo,
+	pub name: String,
+	pub members_section: TeamMembersSection,
+	pub repos_section: TeamReposSection,
+	pub can_delete: bool,
+}
+
+impl Component for Page {
+	fn into_node(self) -> Node {
+		Document::new()
+			.child(
+				AppLayout::new(self.app_layout_info).child(
+					ui::S1::new()
+						.child(ui::H1::new(self.name))
+						.child(self.members_section)
+						.child(self.repos_section)
+						.child(if self.can_delete {
+							Some(DangerZoneSection)
+						} else {
+							None
+						}),
+				),
+			)
+			.into_node()
+	}
+}
+
+pub struct TeamMembersSection {
+	pub can_manage: bool,
+	pub rows: Vec<MembersTableRow>,
+	/// These are the members of the organization who are not on the team yet.
+	pub member_options: Vec<MemberOption>,
+}
+
+pub struct MembersTableRow {
+	pub user_id: Id,
+	pub email: String,
+	pub is_maintainer: bool,
+}
+
+pub struct MemberOption {
+	pub id: Id,
+	pub email: String,
+}
+
+impl Component for TeamMembersSection {
+	fn into_node(self) -> Node {
+		let can_manage = self.can_manage;
+		let members_table_or_empty_message = if self.rows.is_empty() {
+			ui::Card::new()
+				.child(ui::P::new().child("This team does not have any members."))
+				.into_node()
+		} else {
+			ui::Table::new()
+				.width("100%".to_owned())
+				.child(
+					ui::TableHeader::new().child(
+						ui::TableRow::new()
+							.child(ui::TableHeaderCell::new().child("Email"))
+							.child(ui::TableHeaderCell::new().child("Role"))
+							.child(if can_manage {
+								Some(ui::TableHeaderCell::new())
+							} else {
+								None
+							}),
+					),
+				)
+				.child(
+					ui::TableBody::new().children(self.rows.into_iter().map(|row| {
+						ui::TableRow::new()
+							.child(ui::TableCell::new().child(row.email))
+							.child(ui::TableCell::new().child(if row.is_maintainer {
+								"Maintainer"
+							} else {
+								"Member"
+							}))
+							.child(if can_manage {
+								Some(ui::TableCell::new().child(RemoveForm {
+									action: "remove_member",
+									field_name: "user_id",
+									id: row.user_id,
+								}))
+							} else {
+								None
+							})
+					})),
+				)
+				.into_node()
+		};
+		let add_member_form = if can_manage && !self.member_options.is_empty() {
+			Some(AddMemberForm {
+				member_options: self.member_options,
+			})
+		} else {
+			None
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Members"))
+			.child(ui::P::new().child(
+				"Maintainers can add and remove the team's members. Only the organization's admins can grant repos to the team.",
+			))
+			.child(members_table_or_empty_message)
+			.child(add_member_form)
+			.into_node()
+	}
+}
+
+struct AddMemberForm {
+	member_options: Vec<MemberOption>,
+}
+
+impl Component for AddMemberForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "add_member"),
+			)
+			.child(
+				ui::SelectField::new()
+					.label("Member".to_owned())
+					.name("user_id".to_owned())
+					.options(
+						self.member_options
+							.into_iter()
+							.map(|member_option| ui::SelectFieldOption {
+								text: member_option.email,
+								value: member_option.id.to_string(),
+							})
+							.collect::<Vec<_>>(),
+					),
+			)
+			.child(
+				ui::SelectField::new()
+					.label("Role".to_owned())
+					.name("role".to_owned())
+					.options(vec![
+						ui::SelectFieldOption {
+							text: "Member".to_owned(),
+							value: "member".to_owned(),
+						},
+						ui::SelectFieldOption {
+							text: "Maintainer".to_owned(),
+							value: "maintainer".to_owned(),
+						},
+					]),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.child("Add Member"),
+			)
+			.into_node()
+	}
+}
+
+pub struct TeamReposSection {
+	pub can_manage: bool,
+	pub rows: Vec<ReposTableRow>,
+	/// These are the organization's repos that are not granted to the team yet.
+	pub repo_options: Vec<RepoOption>,
+}
+
+pub struct ReposTableRow {
+	pub id: Id,
+	pub title: String,
+}
+
+pub struct RepoOption {
+	pub id: Id,
+	pub title: String,
+}
+
+impl Component for TeamReposSection {
+	fn into_node(self) -> Node {
+		let can_manage = self.can_manage;
+		let repos_table_or_empty_message = if self.rows.is_empty() {
+			ui::Card::new()
+				.child(ui::P::new().child("No repos are granted to this team."))
+				.into_node()
+		} else {
+			ui::Table::new()
+				.width("100%".to_owned())
+				.child(
+					ui::TableHeader::new().child(
+						ui::TableRow::new()
+							.child(ui::TableHeaderCell::new().child("Repo Title"))
+							.child(if can_manage {
+								Some(ui::TableHeaderCell::new())
+							} else {
+								None
+							}),
+					),
+				)
+				.child(
+					ui::TableBody::new().children(self.rows.into_iter().map(|row| {
+						ui::TableRow::new()
+							.child(
+								ui::TableCell::new().child(
+									ui::Link::new()
+										.href(app_path(format!("/repos/{}/", row.id)))
+										.child(row.title),
+								),
+							)
+							.child(if can_manage {
+								Some(ui::TableCell::new().child(RemoveForm {
+									action: "revoke_repo",
+									field_name: "repo_id",
+									id: row.id,
+								}))
+							} else {
+								None
+							})
+					})),
+				)
+				.into_node()
+		};
+		let grant_repo_form = if can_manage && !self.repo_options.is_empty() {
+			Some(GrantRepoForm {
+				repo_options: self.repo_options,
+			})
+		} else {
+			None
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Repos"))
+			.child(ui::P::new().child(
+				"A repo granted to one or more teams is only accessible to the members of those teams and the organization's admins. A repo granted to no teams is accessible to every member of the organization.",
+			))
+			.child(repos_table_or_empty_message)
+			.child(grant_repo_form)
+			.into_node()
+	}
+}
+
+struct GrantRepoForm {
+	repo_options: Vec<RepoOption>,
+}
+
+impl Component for GrantRepoForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "grant_repo"),
+			)
+			.child(
+				ui::SelectField::new()
+					.label("Repo".to_owned())
+					.name("repo_id".to_owned())
+					.options(
+						self.repo_options
+							.into_iter()
+							.map(|repo_option| ui::SelectFieldOption {
+								text: repo_option.title,
+								value: repo_option.id.to_string(),
+							})
+							.collect::<Vec<_>>(),
+					),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.child("Grant Repo"),
+			)
+			.into_node()
+	}
+}
+
+struct RemoveForm {
+	action: &'static str,
+	field_name: &'static str,
+	id: Id,
+}
+
+impl Component for RemoveForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", self.action),
+			)
+			.child(
+				input()
+					.attribute("name", self.field_name)
+					.attribute("type", "hidden")
+					.attribute("value", self.id.to_string()),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.color(ui::colors::RED.to_owned())
+					.child("Remove"),
+			)
+			.into_node()
+	}
+}
+
+struct DangerZoneSection;
+
+impl Component for DangerZoneSection {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("Danger Zone"))
+			.child(
+				ui::Form::new()
+					.post(true)
+					.onsubmit("return confirm(\"Are you sure?\")".to_owned())
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "delete_team"),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.color(ui::colors::RED.to_owned())
+							.child("Delete Team"),
+					),
+			)
+			.into_node()
+	}
+}
@@ -0,0 +1,173 @@
+use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, service_unavailable, unauthorized},
+	organizations::get_organization_user,
+	path_components,
+	teams::{
+		add_team_member, authorize_normal_user_for_team_maintenance,
+		authorize_normal_user_for_team_repos, delete_team, get_team, grant_repo_to_team,
+		remove_team_member, revoke_repo_from_team,
+	},
+	user::{authorize_normal_user, authorize_normal_user_for_organization},
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "action")]
+enum Action {
+	#[serde(rename = "add_member")]
+	AddMember(AddMemberAction),
+	#[serde(rename = "remove_member")]
+	RemoveMember(MemberAction),
+	#[serde(rename = "grant_repo")]
+	GrantRepo(RepoAction),
+	#[serde(rename = "revoke_repo")]
+	RevokeRepo(RepoAction),
+	#[serde(rename = "delete_team")]
+	DeleteTeam,
+}
+
+#[derive(serde::Deserialize)]
+struct AddMemberAction {
+	user_id: String,
+	role: Role,
+}
+
+#[derive(serde::Deserialize)]
+enum Role {
+	#[serde(rename = "member")]
+	Member,
+	#[serde(rename = "maintainer")]
+	Maintainer,
+}
+
+#[derive(serde::Deserialize)]
+struct MemberAction {
+	user_id: String,
+}
+
+#[derive(serde::Deserialize)]
+struct RepoAction {
+	repo_id: String,
+}
+
+pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let (organization_id, team_id) = if let ["organizations", organization_id, "teams", team_id] =
+		*path_components(request).as_slice()
+	{
+		(organization_id.to_owned(), team_id.to_owned())
+	} else {
+		bail!("unexpected path");
+	};
+	if !app.options().auth_enabled() {
+		return Ok(not_found());
+	}
+	let data = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(data) => data,
+		Err(_) => return Ok(bad_request()),
+	};
+	let action: Action = match serde_urlencoded::from_bytes(&data) {
+		Ok(action) => action,
+		Err(_) => return Ok(bad_request()),
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_normal_user(request, &mut db, app.clock()).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(unauthorized()),
+	};
+	let organization_id: Id = match organization_id.parse() {
+		Ok(organization_id) => organization_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	let team_id: Id = match team_id.parse() {
+		Ok(team_id) => team_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_normal_user_for_organization(&mut db, &user, organization_id).await? {
+		return Ok(not_found());
+	}
+	if get_team(&mut db, organization_id, team_id).await?.is_none() {
+		return Ok(not_found());
+	}
+	if !authorize_normal_user_for_team_maintenance(&mut db, &user, team_id).await? {
+		return Ok(unauthorized());
+	}
+	let team_location = app_path(format!(
+		"/organizations/{}/teams/{}",
+		organization_id, team_id
+	));
+	let location = match action {
+		Action::AddMember(action) => {
+			let user_id: Id = match action.user_id.parse() {
+				Ok(user_id) => user_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			let is_maintainer = matches!(action.role, Role::Maintainer);
+			if !add_team_member(&mut db, team_id, user_id, is_maintainer).await? {
+				return Ok(bad_request());
+			}
+			team_location
+		}
+		Action::RemoveMember(action) => {
+			let user_id: Id = match action.user_id.parse() {
+				Ok(user_id) => user_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			remove_team_member(&mut db, team_id, user_id).await?;
+			team_location
+		}
+		Action::GrantRepo(action) => {
+			// Only the organization's admins can grant and revoke repos, not the team's maintainers.
+			if !authorize_normal_user_for_team_repos(&mut db, &user, team_id).await? {
+				return Ok(unauthorized());
+			}
+			let repo_id: Id = match action.repo_id.parse() {
+				Ok(repo_id) => repo_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			if !grant_repo_to_team(&mut db, repo_id, team_id).await? {
+				return Ok(bad_request());
+			}
+			team_location
+		}
+		Action::RevokeRepo(action) => {
+			// Only the organization's admins can grant and revoke repos, not the team's maintainers.
+			if !authorize_normal_user_for_team_repos(&mut db, &user, team_id).await? {
+				return Ok(unauthorized());
+			}
+			let repo_id: Id = match action.repo_id.parse() {
+				Ok(repo_id) => repo_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			revoke_repo_from_team(&mut db, repo_id, team_id).await?;
+			team_location
+		}
+		Action::DeleteTeam => {
+			// Only the organization's admins can delete a team, not its maintainers.
+			let is_admin = get_organization_user(&mut db, organization_id, user.id)
+				.await?
+				.map(|organization_user| organization_user.is_admin)
+				.unwrap_or(false);
+			if !is_admin {
+				return Ok(unauthorized());
+			}
+			delete_team(&mut db, team_id).await?;
+			app_path(format!("/organizations/{}/", organization_id))
+		}
+	};
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::SEE_OTHER)
+		.header(http::header::LOCATION, location)
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
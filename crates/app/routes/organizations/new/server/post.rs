@@ -1,4 +1,5 @@
 use anyhow::Result;
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, service_unavailable, unauthorized},
@@ -30,7 +31,7 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_normal_user(request, &mut db).await? {
+	let user = match authorize_normal_user(request, &mut db, app.clock()).await? {
 		Ok(user) => user,
 		Err(_) => return Ok(unauthorized()),
 	};
@@ -74,7 +75,7 @@ async fn create_organization(
 		.status(http::StatusCode::SEE_OTHER)
 		.header(
 			http::header::LOCATION,
-			format!("/organizations/{}/", organization_id),
+			app_path(format!("/organizations/{}/", organization_id)),
 		)
 		.body(hyper::Body::empty())
 		.unwrap();
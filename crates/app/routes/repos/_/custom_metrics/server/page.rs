@@ -0,0 +1,141 @@
+use modelfox_app_core::custom_metrics::CustomMetric;
+use modelfox_app_layouts::{
+	app_layout::{AppLayout, AppLayoutInfo},
+	document::Document,
+};
+use modelfox_app_ui::page_heading::PageHeading;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct Page {
+	pub app_layout_info: AppLayoutInfo,
+	pub custom_metrics: Vec<CustomMetric>,
+	pub error: Option<String>,
+}
+
+impl Component for Page {
+	fn into_node(self) -> Node {
+		let table = if self.custom_metrics.is_empty() {
+			None
+		} else {
+			Some(CustomMetricsTable {
+				custom_metrics: self.custom_metrics,
+			})
+		};
+		Document::new()
+			.child(
+				AppLayout::new(self.app_layout_info).child(
+					ui::S1::new()
+						.child(PageHeading::new().child(ui::H1::new("Custom Metrics")))
+						.child(
+							self.error
+								.map(|error| ui::Alert::new(ui::Level::Danger).child(error)),
+						)
+						.child(ui::P::new().child(
+							"A custom metric is a WebAssembly module that computes a metric over the predictions logged for a model and their true values. Once uploaded, it can be selected as the metric of any monitor for a model in this repo.",
+						))
+						.child(table)
+						.child(UploadForm),
+				),
+			)
+			.into_node()
+	}
+}
+
+struct CustomMetricsTable {
+	custom_metrics: Vec<CustomMetric>,
+}
+
+impl Component for CustomMetricsTable {
+	fn into_node(self) -> Node {
+		ui::Table::new()
+			.width("100%".to_owned())
+			.child(
+				ui::TableHeader::new().child(
+					ui::TableRow::new()
+						.child(ui::TableHeaderCell::new().child("Name"))
+						.child(ui::TableHeaderCell::new().child("Id"))
+						.child(ui::TableHeaderCell::new()),
+				),
+			)
+			.child(
+				ui::TableBody::new().children(self.custom_metrics.into_iter().map(
+					|custom_metric| {
+						ui::TableRow::new()
+							.child(ui::TableCell::new().child(custom_metric.name))
+							.child(ui::TableCell::new().child(custom_metric.id.to_string()))
+							.child(ui::TableCell::new().child(CustomMetricDeleteForm {
+								custom_metric_id: custom_metric.id.to_string(),
+							}))
+					},
+				)),
+			)
+			.into_node()
+	}
+}
+
+struct CustomMetricDeleteForm {
+	custom_metric_id: String,
+}
+
+impl Component for CustomMetricDeleteForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.onsubmit("return confirm(\"Are you sure?\")".to_owned())
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "delete"),
+			)
+			.child(
+				input()
+					.attribute("name", "custom_metric_id")
+					.attribute("type", "hidden")
+					.attribute("value", self.custom_metric_id),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.color(ui::colors::RED.to_owned())
+					.child("Delete"),
+			)
+			.into_node()
+	}
+}
+
+struct UploadForm;
+
+impl Component for UploadForm {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("Upload Custom Metric"))
+			.child(ui::P::new().child(
+				"The module must export its memory as \"memory\", a function \"alloc(len: i32) -> i32\" that returns a pointer to len writable bytes, and a function \"compute(ptr: i32, len: i32) -> f32\". The examples are written as a JSON array of objects with \"prediction\", \"true_value\", and \"input\" keys to memory returned by alloc, and compute returns the metric's value. Modules cannot import anything, and each run is limited in time and memory.",
+			))
+			.child(
+				ui::Form::new()
+					.enc_type("multipart/form-data".to_owned())
+					.post(true)
+					.child(
+						ui::TextField::new()
+							.label("Name".to_owned())
+							.name("name".to_owned())
+							.required(true),
+					)
+					.child(
+						ui::FileField::new()
+							.label("Module".to_owned())
+							.name("file".to_owned())
+							.required(true),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Upload"),
+					),
+			)
+			.into_node()
+	}
+}
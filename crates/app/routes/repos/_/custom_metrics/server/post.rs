@@ -0,0 +1,127 @@
+use crate::page::Page;
+use anyhow::{anyhow, bail, Result};
+use modelfox_app_base_path::app_path;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	custom_metrics::{create_custom_metric, delete_custom_metric, get_custom_metrics},
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	path_components,
+	user::{authorize_user, authorize_user_for_repo},
+};
+use modelfox_app_layouts::app_layout::app_layout_info;
+use modelfox_id::Id;
+use multer::Multipart;
+use pinwheel::prelude::*;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "action")]
+enum Action {
+	#[serde(rename = "delete")]
+	Delete(DeleteAction),
+}
+
+#[derive(serde::Deserialize)]
+struct DeleteAction {
+	custom_metric_id: String,
+}
+
+pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let repo_id = if let ["repos", repo_id, "custom_metrics"] = *path_components(request).as_slice()
+	{
+		repo_id.to_owned()
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let repo_id: Id = match repo_id.parse() {
+		Ok(repo_id) => repo_id,
+		Err(_) => return Ok(not_found()),
+	};
+	if !authorize_user_for_repo(&mut db, &user, repo_id).await? {
+		return Ok(not_found());
+	}
+	// Uploads are sent as multipart form data and deletes are sent as urlencoded forms.
+	let boundary = request
+		.headers()
+		.get(http::header::CONTENT_TYPE)
+		.and_then(|ct| ct.to_str().ok())
+		.and_then(|ct| multer::parse_boundary(ct).ok());
+	let result = if let Some(boundary) = boundary {
+		let mut name: Option<String> = None;
+		let mut file: Option<Vec<u8>> = None;
+		let mut multipart = Multipart::new(request.body_mut(), boundary);
+		while let Some(field) = multipart.next_field().await? {
+			let field_name = match field.name() {
+				Some(field_name) => field_name.to_owned(),
+				None => return Ok(bad_request()),
+			};
+			match field_name.as_str() {
+				"name" => name = Some(field.text().await?),
+				"file" => file = Some(field.bytes().await?.to_vec()),
+				_ => return Ok(bad_request()),
+			}
+		}
+		match (name, file) {
+			(Some(name), Some(file)) => {
+				create_custom_metric(&mut db, app.storage(), app.clock(), repo_id, &name, &file)
+					.await
+					.map(|_| ())
+			}
+			_ => Err(anyhow!("A name and a module are required.")),
+		}
+	} else {
+		let data = match hyper::body::to_bytes(request.body_mut()).await {
+			Ok(data) => data,
+			Err(_) => return Ok(bad_request()),
+		};
+		let action: Action = match serde_urlencoded::from_bytes(&data) {
+			Ok(action) => action,
+			Err(_) => return Ok(bad_request()),
+		};
+		match action {
+			Action::Delete(action) => {
+				let custom_metric_id: Id = match action.custom_metric_id.parse() {
+					Ok(custom_metric_id) => custom_metric_id,
+					Err(_) => return Ok(bad_request()),
+				};
+				delete_custom_metric(&mut db, app.storage(), repo_id, custom_metric_id).await
+			}
+		}
+	};
+	if let Err(error) = result {
+		let app_layout_info = app_layout_info(app).await?;
+		let custom_metrics = get_custom_metrics(&mut db, repo_id).await?;
+		let page = Page {
+			app_layout_info,
+			custom_metrics,
+			error: Some(error.to_string()),
+		};
+		let html = html(page);
+		let response = http::Response::builder()
+			.status(http::StatusCode::BAD_REQUEST)
+			.body(hyper::Body::from(html))
+			.unwrap();
+		return Ok(response);
+	}
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::SEE_OTHER)
+		.header(
+			http::header::LOCATION,
+			app_path(format!("/repos/{}/custom_metrics", repo_id)),
+		)
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
@@ -1,11 +1,17 @@
-use crate::page::Page;
+use crate::page::{Page, TrashedModelRow};
 use anyhow::{bail, Result};
+use chrono::prelude::*;
+use chrono_tz::Tz;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{not_found, redirect_to_login, service_unavailable},
+	heuristics::MODEL_TRASH_RETENTION_DURATION,
+	monitor_templates::get_monitor_templates,
 	path_components,
-	repos::get_repo,
+	repos::{get_repo, get_repo_readme, get_trashed_model_versions},
+	timezone::get_timezone_for_repo,
 	user::{authorize_user, authorize_user_for_repo},
+	webhooks::get_webhooks,
 };
 use modelfox_app_layouts::app_layout::app_layout_info;
 use modelfox_id::Id;
@@ -24,10 +30,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let repo_id: Id = match repo_id.parse() {
 		Ok(repo_id) => repo_id,
 		Err(_) => return Ok(not_found()),
@@ -37,9 +44,34 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	};
 	let app_layout_info = app_layout_info(app).await?;
 	let repo = get_repo(&mut db, repo_id).await?;
+	let readme = get_repo_readme(&mut db, repo_id).await?;
+	let monitor_templates = get_monitor_templates(&mut db, repo_id).await?;
+	let webhooks = get_webhooks(&mut db, repo_id).await?;
+	let timezone = get_timezone_for_repo(&mut db, request, &user, repo_id).await?;
+	let retention = chrono::Duration::from_std(MODEL_TRASH_RETENTION_DURATION)?;
+	let trashed_models = get_trashed_model_versions(&mut db, repo_id)
+		.await?
+		.into_iter()
+		.map(|model_version| {
+			let deleted_at: DateTime<Tz> = Utc
+				.timestamp(model_version.deleted_at, 0)
+				.with_timezone(&timezone);
+			TrashedModelRow {
+				id: model_version.id.to_string(),
+				tag: model_version.tag,
+				deleted_at: deleted_at.to_string(),
+				expires_at: (deleted_at + retention).to_string(),
+			}
+		})
+		.collect();
 	let page = Page {
 		app_layout_info,
+		repo_id,
 		title: repo.title,
+		readme,
+		monitor_templates,
+		webhooks,
+		trashed_models,
 	};
 	let html = html(page);
 	let response = http::Response::builder()
@@ -1,14 +1,32 @@
+use modelfox_app_base_path::app_path;
+use modelfox_app_core::{
+	monitor_templates::MonitorTemplate,
+	webhooks::{Webhook, WebhookEventType},
+};
 use modelfox_app_layouts::{
 	app_layout::{AppLayout, AppLayoutInfo},
 	document::Document,
 };
 use modelfox_app_ui::page_heading::PageHeading;
+use modelfox_id::Id;
 use modelfox_ui as ui;
 use pinwheel::prelude::*;
 
 pub struct Page {
 	pub app_layout_info: AppLayoutInfo,
+	pub repo_id: Id,
 	pub title: String,
+	pub readme: Option<String>,
+	pub monitor_templates: Vec<MonitorTemplate>,
+	pub webhooks: Vec<Webhook>,
+	pub trashed_models: Vec<TrashedModelRow>,
+}
+
+pub struct TrashedModelRow {
+	pub id: String,
+	pub tag: Option<String>,
+	pub deleted_at: String,
+	pub expires_at: String,
 }
 
 impl Component for Page {
@@ -19,6 +37,21 @@ impl Component for Page {
 					ui::S1::new()
 						.child(PageHeading::new().child(ui::H1::new(self.title.clone())))
 						.child(UpdateTitleForm { title: self.title })
+						.child(UpdateReadmeForm {
+							readme: self.readme,
+						})
+						.child(MonitorTemplatesSection {
+							monitor_templates: self.monitor_templates,
+						})
+						.child(CustomMetricsSection {
+							repo_id: self.repo_id,
+						})
+						.child(WebhooksSection {
+							webhooks: self.webhooks,
+						})
+						.child(TrashSection {
+							trashed_models: self.trashed_models,
+						})
 						.child(DangerZone),
 				),
 			)
@@ -59,6 +92,374 @@ impl Component for UpdateTitleForm {
 	}
 }
 
+struct UpdateReadmeForm {
+	readme: Option<String>,
+}
+
+impl Component for UpdateReadmeForm {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("README"))
+			.child(ui::P::new().child(
+				"The README is shown at the top of the repo page. Use it to describe the problem this repo's models solve, where their data comes from, and who owns them. Save an empty README to remove it.",
+			))
+			.child(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "update_readme"),
+					)
+					.child(
+						ui::FieldLabel::new().child("README (Markdown)").child(
+							textarea()
+								.class("form-text-field")
+								.attribute("name", "readme")
+								.attribute("rows", 12)
+								.child(self.readme),
+						),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Update"),
+					),
+			)
+			.into_node()
+	}
+}
+
+struct MonitorTemplatesSection {
+	monitor_templates: Vec<MonitorTemplate>,
+}
+
+impl Component for MonitorTemplatesSection {
+	fn into_node(self) -> Node {
+		let table = if self.monitor_templates.is_empty() {
+			None
+		} else {
+			Some(MonitorTemplatesTable {
+				monitor_templates: self.monitor_templates,
+			})
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Monitor Templates"))
+			.child(ui::P::new().child(
+				"A monitor is created from each template for every new model version uploaded to this repo. To add a template, open a monitor and choose Save as Template.",
+			))
+			.child(table)
+			.into_node()
+	}
+}
+
+struct MonitorTemplatesTable {
+	monitor_templates: Vec<MonitorTemplate>,
+}
+
+impl Component for MonitorTemplatesTable {
+	fn into_node(self) -> Node {
+		ui::Table::new()
+			.width("100%".to_owned())
+			.child(
+				ui::TableHeader::new().child(
+					ui::TableRow::new()
+						.child(ui::TableHeaderCell::new().child("Title"))
+						.child(ui::TableHeaderCell::new().child("Cadence"))
+						.child(ui::TableHeaderCell::new().child("Metric"))
+						.child(ui::TableHeaderCell::new()),
+				),
+			)
+			.child(
+				ui::TableBody::new().children(self.monitor_templates.into_iter().map(
+					|monitor_template| {
+						ui::TableRow::new()
+							.child(ui::TableCell::new().child(monitor_template.title))
+							.child(ui::TableCell::new().child(monitor_template.cadence.to_string()))
+							.child(
+								ui::TableCell::new()
+									.child(monitor_template.threshold.metric.to_string()),
+							)
+							.child(ui::TableCell::new().child(MonitorTemplateDeleteForm {
+								monitor_template_id: monitor_template.id.to_string(),
+							}))
+					},
+				)),
+			)
+			.into_node()
+	}
+}
+
+struct MonitorTemplateDeleteForm {
+	monitor_template_id: String,
+}
+
+impl Component for MonitorTemplateDeleteForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "delete_monitor_template"),
+			)
+			.child(
+				input()
+					.attribute("name", "monitor_template_id")
+					.attribute("type", "hidden")
+					.attribute("value", self.monitor_template_id),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.color(ui::colors::RED.to_owned())
+					.child("Delete"),
+			)
+			.into_node()
+	}
+}
+
+struct CustomMetricsSection {
+	repo_id: Id,
+}
+
+impl Component for CustomMetricsSection {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("Custom Metrics"))
+			.child(ui::P::new().child(
+				"Custom metrics are WebAssembly modules that compute a metric over production predictions and true values. Monitors for models in this repo can alert on them.",
+			))
+			.child(
+				ui::Link::new()
+					.href(app_path(format!("/repos/{}/custom_metrics", self.repo_id)))
+					.child("Manage Custom Metrics"),
+			)
+			.into_node()
+	}
+}
+
+struct WebhooksSection {
+	webhooks: Vec<Webhook>,
+}
+
+impl Component for WebhooksSection {
+	fn into_node(self) -> Node {
+		let table = if self.webhooks.is_empty() {
+			None
+		} else {
+			Some(WebhooksTable {
+				webhooks: self.webhooks,
+			})
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Webhooks"))
+			.child(ui::P::new().child(
+				"Each webhook receives a POST request when one of the events it subscribes to occurs in this repo. The X-ModelFox-Signature header contains sha256= followed by the hex encoded HMAC-SHA256 of the request body, keyed with the webhook's secret.",
+			))
+			.child(table)
+			.child(CreateWebhookForm)
+			.into_node()
+	}
+}
+
+struct WebhooksTable {
+	webhooks: Vec<Webhook>,
+}
+
+impl Component for WebhooksTable {
+	fn into_node(self) -> Node {
+		ui::Table::new()
+			.width("100%".to_owned())
+			.child(
+				ui::TableHeader::new().child(
+					ui::TableRow::new()
+						.child(ui::TableHeaderCell::new().child("URL"))
+						.child(ui::TableHeaderCell::new().child("Events"))
+						.child(ui::TableHeaderCell::new()),
+				),
+			)
+			.child(
+				ui::TableBody::new().children(self.webhooks.into_iter().map(|webhook| {
+					let event_types = webhook
+						.event_types
+						.iter()
+						.map(|event_type| event_type.as_str())
+						.collect::<Vec<_>>()
+						.join(", ");
+					ui::TableRow::new()
+						.child(ui::TableCell::new().child(webhook.url.to_string()))
+						.child(ui::TableCell::new().child(event_types))
+						.child(ui::TableCell::new().child(WebhookDeleteForm {
+							webhook_id: webhook.id.to_string(),
+						}))
+				})),
+			)
+			.into_node()
+	}
+}
+
+struct WebhookDeleteForm {
+	webhook_id: String,
+}
+
+impl Component for WebhookDeleteForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "delete_webhook"),
+			)
+			.child(
+				input()
+					.attribute("name", "webhook_id")
+					.attribute("type", "hidden")
+					.attribute("value", self.webhook_id),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.color(ui::colors::RED.to_owned())
+					.child("Delete"),
+			)
+			.into_node()
+	}
+}
+
+struct CreateWebhookForm;
+
+impl Component for CreateWebhookForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "create_webhook"),
+			)
+			.child(
+				ui::TextField::new()
+					.label("URL".to_owned())
+					.name("url".to_owned())
+					.placeholder("https://example.com/webhook".to_owned()),
+			)
+			.child(
+				ui::TextField::new()
+					.label("Secret".to_owned())
+					.name("secret".to_owned()),
+			)
+			.children(WebhookEventType::all().iter().map(|event_type| {
+				ui::CheckboxField::new()
+					.label(format!(
+						"{} ({})",
+						event_type.as_str(),
+						event_type.description()
+					))
+					.name(event_type.as_str().to_owned())
+			}))
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.child("Add Webhook"),
+			)
+			.into_node()
+	}
+}
+
+struct TrashSection {
+	trashed_models: Vec<TrashedModelRow>,
+}
+
+impl Component for TrashSection {
+	fn into_node(self) -> Node {
+		let table = if self.trashed_models.is_empty() {
+			None
+		} else {
+			Some(TrashTable {
+				trashed_models: self.trashed_models,
+			})
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Trash"))
+			.child(ui::P::new().child(
+				"Deleted model versions stay in the trash for 30 days, along with their production data and monitors. After that, they are deleted for good.",
+			))
+			.child(table)
+			.into_node()
+	}
+}
+
+struct TrashTable {
+	trashed_models: Vec<TrashedModelRow>,
+}
+
+impl Component for TrashTable {
+	fn into_node(self) -> Node {
+		ui::Table::new()
+			.width("100%".to_owned())
+			.child(
+				ui::TableHeader::new().child(
+					ui::TableRow::new()
+						.child(ui::TableHeaderCell::new().child("Model Id"))
+						.child(ui::TableHeaderCell::new().child("Tag"))
+						.child(ui::TableHeaderCell::new().child("Deleted At"))
+						.child(ui::TableHeaderCell::new().child("Deleted For Good At"))
+						.child(ui::TableHeaderCell::new()),
+				),
+			)
+			.child(
+				ui::TableBody::new().children(self.trashed_models.into_iter().map(
+					|trashed_model| {
+						ui::TableRow::new()
+							.child(ui::TableCell::new().child(trashed_model.id.clone()))
+							.child(ui::TableCell::new().child(trashed_model.tag))
+							.child(ui::TableCell::new().child(trashed_model.deleted_at))
+							.child(ui::TableCell::new().child(trashed_model.expires_at))
+							.child(ui::TableCell::new().child(RestoreModelForm {
+								model_id: trashed_model.id,
+							}))
+					},
+				)),
+			)
+			.into_node()
+	}
+}
+
+struct RestoreModelForm {
+	model_id: String,
+}
+
+impl Component for RestoreModelForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "restore_model"),
+			)
+			.child(
+				input()
+					.attribute("name", "model_id")
+					.attribute("type", "hidden")
+					.attribute("value", self.model_id),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.child("Restore"),
+			)
+			.into_node()
+	}
+}
+
 struct DangerZone;
 
 impl Component for DangerZone {
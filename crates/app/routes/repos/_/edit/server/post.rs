@@ -1,10 +1,13 @@
 use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	monitor_templates::delete_monitor_template,
 	path_components,
-	repos::delete_repo,
+	repos::{delete_repo, restore_model_version, update_repo_readme},
 	user::{authorize_user, authorize_user_for_repo},
+	webhooks::{create_webhook, delete_webhook, WebhookEventType},
 };
 use modelfox_id::Id;
 use std::{borrow::BorrowMut, sync::Arc};
@@ -14,15 +17,54 @@ use std::{borrow::BorrowMut, sync::Arc};
 enum Action {
 	#[serde(rename = "update_title")]
 	UpdateTitle(UpdateTitleAction),
+	#[serde(rename = "update_readme")]
+	UpdateReadme(UpdateReadmeAction),
+	#[serde(rename = "delete_monitor_template")]
+	DeleteMonitorTemplate(DeleteMonitorTemplateAction),
+	#[serde(rename = "create_webhook")]
+	CreateWebhook(CreateWebhookAction),
+	#[serde(rename = "delete_webhook")]
+	DeleteWebhook(DeleteWebhookAction),
+	#[serde(rename = "restore_model")]
+	RestoreModel(RestoreModelAction),
 	#[serde(rename = "delete")]
 	Delete,
 }
 
+#[derive(serde::Deserialize)]
+struct RestoreModelAction {
+	model_id: String,
+}
+
+#[derive(serde::Deserialize)]
+struct DeleteMonitorTemplateAction {
+	monitor_template_id: String,
+}
+
+#[derive(serde::Deserialize)]
+struct CreateWebhookAction {
+	url: String,
+	secret: String,
+	model_uploaded: Option<String>,
+	model_deleted: Option<String>,
+	monitor_state_changed: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct DeleteWebhookAction {
+	webhook_id: String,
+}
+
 #[derive(serde::Deserialize)]
 struct UpdateTitleAction {
 	title: String,
 }
 
+#[derive(serde::Deserialize)]
+struct UpdateReadmeAction {
+	readme: String,
+}
+
 pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
 	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
 	let app = &context.app;
@@ -43,10 +85,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let repo_id: Id = match repo_id.parse() {
 		Ok(repo_id) => repo_id,
 		Err(_) => return Ok(not_found()),
@@ -60,7 +103,114 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 			app.commit_transaction(db).await?;
 			let response = http::Response::builder()
 				.status(http::StatusCode::SEE_OTHER)
-				.header(http::header::LOCATION, "/")
+				.header(http::header::LOCATION, app_path("/"))
+				.body(hyper::Body::empty())
+				.unwrap();
+			Ok(response)
+		}
+		Action::DeleteMonitorTemplate(action) => {
+			let monitor_template_id: Id = match action.monitor_template_id.parse() {
+				Ok(monitor_template_id) => monitor_template_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			delete_monitor_template(&mut db, repo_id, monitor_template_id).await?;
+			app.commit_transaction(db).await?;
+			let response = http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(
+					http::header::LOCATION,
+					app_path(format!("/repos/{}/edit", repo_id)),
+				)
+				.body(hyper::Body::empty())
+				.unwrap();
+			Ok(response)
+		}
+		Action::CreateWebhook(action) => {
+			let url = match action.url.parse() {
+				Ok(url) => url,
+				Err(_) => return Ok(bad_request()),
+			};
+			let event_types: Vec<WebhookEventType> = [
+				(WebhookEventType::ModelUploaded, action.model_uploaded),
+				(WebhookEventType::ModelDeleted, action.model_deleted),
+				(
+					WebhookEventType::MonitorStateChanged,
+					action.monitor_state_changed,
+				),
+			]
+			.into_iter()
+			.filter(|(_, checked)| checked.as_deref() == Some("on"))
+			.map(|(event_type, _)| event_type)
+			.collect();
+			if create_webhook(
+				&mut db,
+				repo_id,
+				&url,
+				&action.secret,
+				&event_types,
+				app.clock(),
+			)
+			.await
+			.is_err()
+			{
+				return Ok(bad_request());
+			}
+			app.commit_transaction(db).await?;
+			let response = http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(
+					http::header::LOCATION,
+					app_path(format!("/repos/{}/edit", repo_id)),
+				)
+				.body(hyper::Body::empty())
+				.unwrap();
+			Ok(response)
+		}
+		Action::DeleteWebhook(action) => {
+			let webhook_id: Id = match action.webhook_id.parse() {
+				Ok(webhook_id) => webhook_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			delete_webhook(&mut db, repo_id, webhook_id).await?;
+			app.commit_transaction(db).await?;
+			let response = http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(
+					http::header::LOCATION,
+					app_path(format!("/repos/{}/edit", repo_id)),
+				)
+				.body(hyper::Body::empty())
+				.unwrap();
+			Ok(response)
+		}
+		Action::RestoreModel(action) => {
+			let model_id: Id = match action.model_id.parse() {
+				Ok(model_id) => model_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			if !restore_model_version(&mut db, repo_id, model_id).await? {
+				return Ok(not_found());
+			}
+			app.commit_transaction(db).await?;
+			let response = http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(
+					http::header::LOCATION,
+					app_path(format!("/repos/{}/models/{}/", repo_id, model_id)),
+				)
+				.body(hyper::Body::empty())
+				.unwrap();
+			Ok(response)
+		}
+		Action::UpdateReadme(action) => {
+			update_repo_readme(&mut db, repo_id, &action.readme).await?;
+			app.commit_transaction(db).await?;
+			let response = http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(
+					http::header::LOCATION,
+					app_path(format!("/repos/{}/", repo_id)),
+				)
 				.body(hyper::Body::empty())
 				.unwrap();
 			Ok(response)
@@ -80,7 +230,10 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 			app.commit_transaction(db).await?;
 			let response = http::Response::builder()
 				.status(http::StatusCode::SEE_OTHER)
-				.header(http::header::LOCATION, format!("/repos/{}/edit", repo_id))
+				.header(
+					http::header::LOCATION,
+					app_path(format!("/repos/{}/edit", repo_id)),
+				)
 				.body(hyper::Body::empty())
 				.unwrap();
 			Ok(response)
@@ -6,8 +6,8 @@ use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{not_found, redirect_to_login, service_unavailable},
 	path_components,
-	repos::get_repo,
-	timezone::get_timezone,
+	repos::{get_repo, get_repo_readme},
+	timezone::get_timezone_for_repo,
 	user::{authorize_user, authorize_user_for_repo},
 };
 use modelfox_app_layouts::app_layout::app_layout_info;
@@ -24,15 +24,15 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	} else {
 		bail!("unexpected path");
 	};
-	let timezone = get_timezone(request);
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let repo_id: Id = match repo_id.parse() {
 		Ok(repo_id) => repo_id,
 		Err(_) => return Ok(not_found()),
@@ -40,16 +40,26 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	if !authorize_user_for_repo(&mut db, &user, repo_id).await? {
 		return Ok(not_found());
 	};
+	let timezone = get_timezone_for_repo(&mut db, request, &user, repo_id).await?;
 	let repo = get_repo(&mut db, repo_id).await?;
+	let readme = get_repo_readme(&mut db, repo_id).await?;
 	let app_layout_info = app_layout_info(app).await?;
 	let rows = sqlx::query(
 		"
 			select
 				models.id,
 				models.tag,
-				models.created_at
+				models.created_at,
+				model_approvals.created_at,
+				users.email
 			from models
-			where models.repo_id = $1
+			left join model_approvals
+				on model_approvals.model_id = models.id
+			left join users
+				on users.id = model_approvals.user_id
+			where
+				models.repo_id = $1 and
+				models.deleted_at is null
 			order by models.created_at desc
 		",
 	)
@@ -66,10 +76,15 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				let created_at: i64 = row.get(2);
 				let created_at: DateTime<Tz> =
 					Utc.timestamp(created_at, 0).with_timezone(&timezone);
+				let approved_at: Option<i64> = row.get(3);
+				let approver_email: Option<String> = row.get(4);
+				let approval = approved_at
+					.map(|_| approver_email.unwrap_or_else(|| "an administrator".to_owned()));
 				ModelsTableRow {
 					id: id.to_string(),
 					tag,
 					created_at: created_at.to_string(),
+					approval,
 				}
 			})
 			.collect();
@@ -81,6 +96,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let page = Page {
 		app_layout_info,
 		models_table,
+		readme,
 		title: repo.title,
 	};
 	let html = html(page);
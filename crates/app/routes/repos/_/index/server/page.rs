@@ -9,6 +9,7 @@ use pinwheel::prelude::*;
 pub struct Page {
 	pub app_layout_info: AppLayoutInfo,
 	pub models_table: Option<ModelsTable>,
+	pub readme: Option<String>,
 	pub title: String,
 }
 
@@ -21,6 +22,10 @@ impl Component for Page {
 				.child(ui::P::new().child("This repository has no models."))
 				.into_node()
 		};
+		// The readme is written by users, so render it without raw html.
+		let readme = self
+			.readme
+			.map(|readme| ui::Card::new().child(ui::Markdown::new(readme).untrusted(true)));
 		Document::new()
 			.child(
 				AppLayout::new(self.app_layout_info).child(
@@ -41,6 +46,7 @@ impl Component for Page {
 									),
 							),
 						)
+						.child(readme)
 						.child(models_table_or_empty_message),
 				),
 			)
@@ -56,6 +62,8 @@ pub struct ModelsTableRow {
 	pub id: String,
 	pub created_at: String,
 	pub tag: Option<String>,
+	/// This is the email of the user who approved the model for production, if it has been approved.
+	pub approval: Option<String>,
 }
 
 impl Component for ModelsTable {
@@ -67,11 +75,16 @@ impl Component for ModelsTable {
 					ui::TableRow::new()
 						.child(ui::TableHeaderCell::new().child("Id"))
 						.child(ui::TableHeaderCell::new().child("Tag"))
-						.child(ui::TableHeaderCell::new().child("Uploaded")),
+						.child(ui::TableHeaderCell::new().child("Uploaded"))
+						.child(ui::TableHeaderCell::new().child("Production Approval")),
 				),
 			)
 			.child(
 				ui::TableBody::new().children(self.rows.into_iter().map(|row| {
+					let approval = match row.approval {
+						Some(approver) => format!("Approved by {}", approver),
+						None => "Not Approved".to_owned(),
+					};
 					ui::TableRow::new()
 						.child(
 							ui::TableCell::new().child(
@@ -82,6 +95,7 @@ impl Component for ModelsTable {
 						)
 						.child(ui::TableCell::new().child(row.tag.clone()))
 						.child(ui::TableCell::new().child(row.created_at))
+						.child(ui::TableCell::new().child(approval))
 				})),
 			)
 			.into_node()
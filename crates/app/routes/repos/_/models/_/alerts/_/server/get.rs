@@ -4,6 +4,7 @@ use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	path_components,
+	timezone::get_timezone_for_model,
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
@@ -30,13 +31,15 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
 	let model_layout_info = model_layout_info(&mut db, app, model_id, ModelNavItem::Alerts).await?;
 	let alert = app.get_alert(&mut db, Id::from_str(alert_id)?).await?;
 	if alert.is_none() {
@@ -47,6 +50,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		alert: alert.unwrap(),
 		alert_id: alert_id.to_string(),
 		model_layout_info,
+		timezone,
 		error: None,
 	};
 	app.commit_transaction(db).await?;
@@ -1,4 +1,5 @@
-use modelfox_app_core::{alert::Alert, monitor::MonitorThresholdMode};
+use chrono_tz::Tz;
+use modelfox_app_core::alert::Alert;
 use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
@@ -14,11 +15,12 @@ pub struct Page {
 	pub alert: Alert,
 	pub alert_id: String,
 	pub model_layout_info: ModelLayoutInfo,
+	pub timezone: Tz,
 	pub error: Option<String>,
 }
 
-pub fn alert_description(alert: &Alert) -> String {
-	let time_range = alert.formated_time_range();
+pub fn alert_description(alert: &Alert, timezone: Tz) -> String {
+	let time_range = alert.formated_time_range(timezone);
 	let cadence = alert.monitor.cadence;
 	let metric = alert.monitor.threshold.metric;
 	let production_value = alert.result.production_value;
@@ -36,16 +38,15 @@ pub fn alert_description(alert: &Alert) -> String {
 
 impl Component for Page {
 	fn into_node(self) -> Node {
-		let formatter = match self.alert.monitor.threshold.mode {
-			MonitorThresholdMode::Absolute => ui::NumberFormatter::Float(Default::default()),
-			MonitorThresholdMode::Percentage => ui::NumberFormatter::Percent(Default::default()),
-		};
+		let formatter = self.alert.metric().number_formatter();
 		Document::new()
 			.child(
 				ModelLayout::new(self.model_layout_info).child(
 					ui::S1::new()
-						.child(PageHeading::new().child(ui::H1::new(self.alert.title())))
-						.child(ui::P::new().child(alert_description(&self.alert)))
+						.child(
+							PageHeading::new().child(ui::H1::new(self.alert.title(self.timezone))),
+						)
+						.child(ui::P::new().child(alert_description(&self.alert, self.timezone)))
 						.child(
 							ui::NumberComparisonCard::new(
 								Some(self.alert.training_value()),
@@ -6,7 +6,7 @@ use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	path_components,
-	timezone::get_timezone,
+	timezone::get_timezone_for_model,
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
@@ -17,7 +17,6 @@ use std::sync::Arc;
 pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
 	let context = request.extensions().get::<Arc<Context>>().unwrap().clone();
 	let app = &context.app;
-	let timezone = get_timezone(request);
 	let model_id = if let ["repos", _, "models", model_id, "alerts", ""] =
 		path_components(request).as_slice()
 	{
@@ -29,10 +28,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -40,6 +40,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
 	let model_layout_info = model_layout_info(&mut db, app, model_id, ModelNavItem::Alerts).await?;
 	let alerts = app.get_all_alerts_for_model(&mut db, model_id).await?;
 	let alerts_table = if !alerts.is_empty() {
@@ -51,7 +52,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				AlertsTableRow {
 					alert_type: row.cadence_and_metric(),
 					href: row.id.to_string(),
-					range: row.formated_time_range(),
+					range: row.formated_time_range(timezone),
 					last_updated: last_updated.to_string(),
 				}
 			})
@@ -2,7 +2,7 @@ use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
-	model::get_model_bytes,
+	model::{get_model_bytes, get_model_class_thresholds},
 	path_components,
 	user::{authorize_user, authorize_user_for_model},
 };
@@ -28,10 +28,11 @@ pub async fn download_inner(
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -40,7 +41,10 @@ pub async fn download_inner(
 		return Ok(not_found());
 	}
 	let bytes = get_model_bytes(app.storage(), model_id).await?;
-	let bytes = bytes.to_owned();
+	// Include the class thresholds saved on the tuning page so the SDKs use them by default.
+	let class_thresholds = get_model_class_thresholds(&mut db, model_id).await?;
+	let metadata = modelfox_model::Metadata { class_thresholds };
+	let bytes = modelfox_model::with_metadata(&bytes, &metadata)?;
 	app.commit_transaction(db).await?;
 	let response = http::Response::builder()
 		.status(http::StatusCode::OK)
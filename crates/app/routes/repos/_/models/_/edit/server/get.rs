@@ -6,7 +6,8 @@ use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	path_components,
-	timezone::get_timezone,
+	privacy::get_model_privacy_settings,
+	timezone::get_timezone_for_model,
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::app_layout::app_layout_info;
@@ -24,15 +25,15 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		} else {
 			bail!("unexpected path");
 		};
-	let timezone = get_timezone(request);
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -40,6 +41,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
 	let app_layout_info = app_layout_info(app).await?;
 	let row = sqlx::query(
 		"
@@ -58,12 +60,14 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let created_at = created_at.to_string();
 	let model_tag: Option<String> = row.get(0);
 	let model_heading = model_tag.clone().unwrap_or_else(|| model_id.to_string());
+	let privacy_settings = get_model_privacy_settings(&mut db, model_id).await?;
 	let page = Page {
 		app_layout_info,
 		model_id,
 		model_heading,
 		tag: model_tag,
 		created_at,
+		privacy_settings,
 	};
 	let html = html(page);
 	let response = http::Response::builder()
@@ -1,3 +1,4 @@
+use modelfox_app_core::privacy::PrivacySettings;
 use modelfox_app_layouts::{
 	app_layout::{AppLayout, AppLayoutInfo},
 	document::Document,
@@ -13,6 +14,7 @@ pub struct Page {
 	pub model_heading: String,
 	pub model_id: Id,
 	pub tag: Option<String>,
+	pub privacy_settings: PrivacySettings,
 }
 
 impl Component for Page {
@@ -27,6 +29,9 @@ impl Component for Page {
 							created_at: self.created_at,
 						})
 						.child(UpdateTagForm { tag: self.tag })
+						.child(PrivacySettingsForm {
+							privacy_settings: self.privacy_settings,
+						})
 						.child(DangerZone),
 				),
 			)
@@ -92,12 +97,57 @@ impl Component for UpdateTagForm {
 	}
 }
 
+struct PrivacySettingsForm {
+	privacy_settings: PrivacySettings,
+}
+
+impl Component for PrivacySettingsForm {
+	fn into_node(self) -> Node {
+		let description = "Choose what is stored when predictions are tracked for this model. If inputs are not stored, only a hash of each prediction's identifier, its output, and the production stats are kept, and the prediction pages cannot show inputs or explanations. The values of PII columns are replaced with their hashes before they are stored or counted in the production stats. These settings apply to predictions tracked after they are saved.";
+		ui::S2::new()
+			.child(ui::H2::new("Data Privacy"))
+			.child(ui::P::new().child(description))
+			.child(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "update_privacy_settings"),
+					)
+					.child(
+						ui::CheckboxField::new()
+							.label("Store Prediction Inputs".to_owned())
+							.name("store_inputs".to_owned())
+							.checked(self.privacy_settings.store_inputs),
+					)
+					.child(
+						ui::TextField::new()
+							.label("PII Columns (Comma Separated)".to_owned())
+							.name("pii_columns".to_owned())
+							.required(false)
+							.value(self.privacy_settings.pii_columns.join(", ")),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Save"),
+					),
+			)
+			.into_node()
+	}
+}
+
 struct DangerZone;
 
 impl Component for DangerZone {
 	fn into_node(self) -> Node {
 		ui::S2::new()
 			.child(ui::H2::new("Danger Zone"))
+			.child(ui::P::new().child(
+				"Deleting this model version moves it to the trash. It can be restored from the repo's settings for 30 days, after which it and its production data are deleted for good.",
+			))
 			.child(
 				ui::Form::new()
 					.post(true)
@@ -1,9 +1,11 @@
 use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	path_components,
-	repos::delete_model_version,
+	privacy::{set_model_privacy_settings, PrivacySettings},
+	repos::trash_model_version,
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_id::Id;
@@ -16,6 +18,8 @@ enum Action {
 	DeleteModel,
 	#[serde(rename = "update_tag")]
 	UpdateTag(UpdateTagAction),
+	#[serde(rename = "update_privacy_settings")]
+	UpdatePrivacySettings(UpdatePrivacySettingsAction),
 }
 
 #[derive(serde::Deserialize)]
@@ -23,6 +27,14 @@ struct UpdateTagAction {
 	tag: String,
 }
 
+#[derive(serde::Deserialize)]
+struct UpdatePrivacySettingsAction {
+	#[serde(default)]
+	store_inputs: Option<String>,
+	#[serde(default)]
+	pii_columns: String,
+}
+
 pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
 	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
 	let app = &context.app;
@@ -45,10 +57,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let response = match action {
 		Action::DeleteModel => {
 			let model_id: Id = match model_id.parse() {
@@ -58,11 +71,14 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 			if !authorize_user_for_model(&mut db, &user, model_id).await? {
 				return Ok(not_found());
 			};
-			delete_model_version(&mut db, app.storage(), model_id).await?;
+			trash_model_version(&mut db, app, model_id).await?;
 			app.commit_transaction(db).await?;
 			http::Response::builder()
 				.status(http::StatusCode::SEE_OTHER)
-				.header(http::header::LOCATION, format!("/repos/{}/", repo_id))
+				.header(
+					http::header::LOCATION,
+					app_path(format!("/repos/{}/", repo_id)),
+				)
 				.body(hyper::Body::empty())
 				.unwrap()
 		}
@@ -83,7 +99,36 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 				.status(http::StatusCode::SEE_OTHER)
 				.header(
 					http::header::LOCATION,
-					format!("/repos/{}/models/{}/edit", repo_id, model_id),
+					app_path(format!("/repos/{}/models/{}/edit", repo_id, model_id)),
+				)
+				.body(hyper::Body::empty())
+				.unwrap()
+		}
+		Action::UpdatePrivacySettings(action) => {
+			let model_id: Id = match model_id.parse() {
+				Ok(model_id) => model_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			if !authorize_user_for_model(&mut db, &user, model_id).await? {
+				return Ok(not_found());
+			};
+			let privacy_settings = PrivacySettings {
+				store_inputs: action.store_inputs.as_deref() == Some("on"),
+				pii_columns: action
+					.pii_columns
+					.split(',')
+					.map(|column_name| column_name.trim())
+					.filter(|column_name| !column_name.is_empty())
+					.map(|column_name| column_name.to_owned())
+					.collect(),
+			};
+			set_model_privacy_settings(&mut db, model_id, &privacy_settings).await?;
+			app.commit_transaction(db).await?;
+			http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(
+					http::header::LOCATION,
+					app_path(format!("/repos/{}/models/{}/edit", repo_id, model_id)),
 				)
 				.body(hyper::Body::empty())
 				.unwrap()
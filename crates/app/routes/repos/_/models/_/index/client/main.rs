@@ -1,7 +1,16 @@
+use modelfox_ui as ui;
 use pinwheel::prelude::*;
+use web_sys as dom;
 
 pub fn main() {
 	modelfox_ui::client_start();
+	let document = dom::window().unwrap().document().unwrap();
+	if document
+		.get_element_by_id("axis_scale_select_field")
+		.is_some()
+	{
+		ui::select_field_submit_on_change("axis_scale_select_field".to_owned());
+	}
 	hydrate::<modelfox_charts::components::LineChart>("loss");
 	hydrate::<modelfox_charts::components::BarChart>("feature_importances");
 }
@@ -1,6 +1,7 @@
-use crate::common::{FeatureImportancesSection, TrainingSummarySection};
-use modelfox_app_ui::metrics_row::MetricsRow;
+use crate::common::{DroppedFeaturesSection, FeatureImportancesSection, TrainingSummarySection};
+use modelfox_app_ui::{axis_scale_select_field::AxisScaleSelectField, metrics_row::MetricsRow};
 use modelfox_charts::{
+	common::AxisScale,
 	components::LineChart,
 	line_chart::{LineChartPoint, LineChartSeries, LineStyle, PointStyle},
 };
@@ -14,6 +15,7 @@ pub struct BinaryClassifier {
 	pub id: String,
 	pub training_metrics_section: BinaryClassifierMetricsSection,
 	pub training_summary_section: TrainingSummarySection,
+	pub dropped_features_section: Option<DroppedFeaturesSection>,
 	pub warning: Option<String>,
 }
 
@@ -27,6 +29,7 @@ impl Component for BinaryClassifier {
 			}))
 			.child(ui::H1::new("Overview"))
 			.child(self.training_summary_section)
+			.child(self.dropped_features_section)
 			.child(self.training_metrics_section)
 			.child(self.feature_importances_section)
 			.into_node()
@@ -40,6 +43,7 @@ pub struct BinaryClassifierMetricsSection {
 	pub precision: f32,
 	pub recall: f32,
 	pub losses_chart_series: Option<Vec<f32>>,
+	pub losses_chart_scale: AxisScale,
 }
 
 impl Component for BinaryClassifierMetricsSection {
@@ -82,15 +86,20 @@ impl Component for BinaryClassifierMetricsSection {
 				ui::format_percent(self.recall),
 			));
 		let losses_chart = losses_chart_series.map(|losses_chart_series| {
-			ui::Card::new().child(Dehydrate::new(
-				"loss",
-				LineChart::new()
-					.series(losses_chart_series)
-					.title("Training Loss By Round or Epoch".to_owned())
-					.x_axis_title("Round or Epoch".to_owned())
-					.y_axis_title("Loss".to_owned())
-					.y_min(Finite::new(0.0).unwrap()),
-			))
+			fragment()
+				.child(ui::Form::new().child(AxisScaleSelectField::new(self.losses_chart_scale)))
+				.child(
+					ui::Card::new().child(Dehydrate::new(
+						"loss",
+						LineChart::new()
+							.series(losses_chart_series)
+							.title("Training Loss By Round or Epoch".to_owned())
+							.x_axis_title("Round or Epoch".to_owned())
+							.y_axis_title("Loss".to_owned())
+							.y_axis_scale(self.losses_chart_scale)
+							.y_min(Finite::new(0.0).unwrap()),
+					)),
+				)
 		});
 		ui::S2::new()
 			.child(title)
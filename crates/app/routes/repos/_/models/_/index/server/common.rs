@@ -43,6 +43,39 @@ impl Component for TrainingSummarySection {
 	}
 }
 
+pub struct DroppedFeaturesSection {
+	pub dropped_features: Vec<DroppedFeature>,
+}
+
+pub struct DroppedFeature {
+	pub column_name: String,
+	pub reason: String,
+}
+
+impl Component for DroppedFeaturesSection {
+	fn into_node(self) -> Node {
+		let rows = self.dropped_features.into_iter().map(|dropped_feature| {
+			ui::TableRow::new()
+				.child(ui::TableCell::new().child(dropped_feature.column_name))
+				.child(ui::TableCell::new().child(dropped_feature.reason))
+		});
+		ui::S2::new()
+			.child(ui::H2::new("Dropped Features"))
+			.child(ui::P::new().child("Feature selection dropped the columns below before training, because they had near zero variance, mostly invalid values, or a high correlation with another column. You can change the thresholds in the features.selection section of the config."))
+			.child(
+				ui::Table::new()
+					.width("100%".to_owned())
+					.child(
+						ui::TableHeader::new()
+							.child(ui::TableHeaderCell::new().child("Column Name"))
+							.child(ui::TableHeaderCell::new().child("Reason")),
+					)
+					.child(ui::TableBody::new().children(rows)),
+			)
+			.into_node()
+	}
+}
+
 pub struct FeatureImportancesSection {
 	pub n_columns: usize,
 	pub n_features: usize,
@@ -53,13 +86,22 @@ pub struct FeatureImportancesSection {
 #[derive(Clone, Debug)]
 pub struct FeatureImportance {
 	pub feature_importance_value: FiniteF32,
+	/// This is the standard deviation of the feature importance across the cross validation folds, if the model was trained with cross validation.
+	pub feature_importance_std: Option<FiniteF32>,
 	pub feature_name: String,
 }
 
 impl Component for FeatureImportancesSection {
 	fn into_node(self) -> Node {
-		let description =
-			"The chart and table below show which features were most important to the model.";
+		let has_std = self
+			.feature_importances_table_rows
+			.iter()
+			.any(|feature_importance| feature_importance.feature_importance_std.is_some());
+		let description = if has_std {
+			"The chart and table below show which features were most important to the model. Each importance is the mean over the cross validation folds, and the error bars show one standard deviation. Features whose error bars are large relative to their importance may not be robust."
+		} else {
+			"The chart and table below show which features were most important to the model."
+		};
 		ui::S2::new()
 			.child(ui::H2::new("Feature Importances"))
 			.child(ui::P::new().child(description))
@@ -67,6 +109,7 @@ impl Component for FeatureImportancesSection {
 				values: self.feature_importances_chart_values,
 			})
 			.child(FeatureImportancesTable {
+				has_std,
 				rows: self.feature_importances_table_rows,
 			})
 			.into_node()
@@ -94,6 +137,9 @@ impl Component for FeatureImportancesChart {
 							.to_f64()
 							.unwrap(),
 					),
+					error: feature_importance
+						.feature_importance_std
+						.map(|feature_importance_std| feature_importance_std.to_f64().unwrap()),
 				})
 				.collect(),
 			title: Some("Feature Importance".to_owned()),
@@ -117,6 +163,7 @@ impl Component for FeatureImportancesChart {
 }
 
 struct FeatureImportancesTable {
+	has_std: bool,
 	rows: Vec<FeatureImportance>,
 }
 
@@ -127,7 +174,12 @@ impl Component for FeatureImportancesTable {
 			.child(
 				ui::TableHeader::new()
 					.child(ui::TableHeaderCell::new().child("Feature Name"))
-					.child(ui::TableHeaderCell::new().child("Feature Importance Value")),
+					.child(ui::TableHeaderCell::new().child("Feature Importance Value"))
+					.child(
+						self.has_std.then(|| {
+							ui::TableHeaderCell::new().child("Feature Importance Std Dev")
+						}),
+					),
 			)
 			.child(ui::TableBody::new().children(self.rows.iter().map(
 				|feature_importance_table_row| {
@@ -143,6 +195,16 @@ impl Component for FeatureImportancesTable {
 									.to_string(),
 							),
 						)
+						.child(self.has_std.then(|| {
+							ui::TableCell::new().child(
+								feature_importance_table_row
+									.feature_importance_std
+									.map(|feature_importance_std| {
+										feature_importance_std.to_string()
+									})
+									.unwrap_or_default(),
+							)
+						}))
 				},
 			)))
 			.into_node()
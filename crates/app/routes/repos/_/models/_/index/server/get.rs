@@ -1,5 +1,8 @@
 use crate::{
-	common::{FeatureImportance, FeatureImportancesSection, TrainingSummarySection},
+	common::{
+		DroppedFeature, DroppedFeaturesSection, FeatureImportance, FeatureImportancesSection,
+		TrainingSummarySection,
+	},
 	page::{
 		BinaryClassifier, BinaryClassifierMetricsSection, Inner, MulticlassClassifier,
 		MulticlassClassifierClassMetrics, MulticlassClassifierMetricsSection, Page, Regressor,
@@ -19,9 +22,11 @@ use modelfox_app_core::{
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
+use modelfox_charts::common::AxisScale;
 use modelfox_finite::{Finite, FiniteF32};
 use modelfox_id::Id;
-use modelfox_zip::zip;
+use modelfox_ui as ui;
+use modelfox_zip::zip_eq;
 use num::ToPrimitive;
 use pinwheel::prelude::*;
 use std::sync::Arc;
@@ -35,14 +40,27 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		} else {
 			bail!("unexpected path");
 		};
+	#[derive(serde::Deserialize, Default)]
+	struct SearchParams {
+		scale: Option<AxisScale>,
+	}
+	let search_params: SearchParams = match request.uri().query() {
+		Some(query) => match serde_urlencoded::from_str(query) {
+			Ok(search_params) => search_params,
+			Err(_) => return Ok(bad_request()),
+		},
+		None => SearchParams::default(),
+	};
+	let losses_chart_scale = search_params.scale.unwrap_or_default();
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -54,6 +72,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let model = modelfox_model::from_bytes(&bytes)?;
 	let summary_section = compute_summary_section(model);
 	let feature_importances_section = compute_feature_importances_section(model);
+	let dropped_features_section = compute_dropped_features_section(model);
 	let inner = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => {
 			let regressor = regressor.read();
@@ -80,8 +99,10 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 					mse: regressor.test_metrics().mse(),
 					baseline_mse: regressor.baseline_metrics().mse(),
 					losses_chart_series,
+					losses_chart_scale,
 				},
 				training_summary_section: summary_section,
+				dropped_features_section,
 				feature_importances_section,
 				warning,
 			})
@@ -119,8 +140,10 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 					precision: default_threshold_test_metrics.precision().unwrap(),
 					recall: default_threshold_test_metrics.recall().unwrap(),
 					losses_chart_series,
+					losses_chart_scale,
 				},
 				training_summary_section: summary_section,
+				dropped_features_section,
 				feature_importances_section,
 			})
 		}
@@ -160,8 +183,10 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 					class_metrics,
 					classes: model.classes().iter().map(ToOwned::to_owned).collect(),
 					losses_chart_series,
+					losses_chart_scale,
 				},
 				training_summary_section: summary_section,
+				dropped_features_section,
 				feature_importances_section,
 				warning,
 			})
@@ -247,6 +272,58 @@ fn compute_summary_section(model: modelfox_model::ModelReader) -> TrainingSummar
 	}
 }
 
+fn compute_dropped_features_section(
+	model: modelfox_model::ModelReader,
+) -> Option<DroppedFeaturesSection> {
+	let dropped_columns = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			regressor.read().dropped_columns()
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			binary_classifier.read().dropped_columns()
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			multiclass_classifier.read().dropped_columns()
+		}
+	}?;
+	let dropped_features = dropped_columns
+		.iter()
+		.map(|dropped_column| DroppedFeature {
+			column_name: dropped_column.column_name().to_owned(),
+			reason: dropped_column_reason(dropped_column.reason()),
+		})
+		.collect::<Vec<_>>();
+	if dropped_features.is_empty() {
+		return None;
+	}
+	Some(DroppedFeaturesSection { dropped_features })
+}
+
+fn dropped_column_reason(reason: modelfox_model::DroppedColumnReasonReader) -> String {
+	match reason {
+		modelfox_model::DroppedColumnReasonReader::LowVariance(reason) => {
+			format!(
+				"Near zero variance ({}).",
+				ui::format_float(reason.read().variance())
+			)
+		}
+		modelfox_model::DroppedColumnReasonReader::HighInvalidFraction(reason) => {
+			format!(
+				"{} of values are invalid.",
+				ui::format_percent(reason.read().invalid_fraction())
+			)
+		}
+		modelfox_model::DroppedColumnReasonReader::HighCorrelation(reason) => {
+			let reason = reason.read();
+			format!(
+				"Correlation of {} with the column \"{}\".",
+				ui::format_float(reason.correlation()),
+				reason.correlated_column_name()
+			)
+		}
+	}
+}
+
 fn regression_comparison_type_name(
 	comparison_metric: &modelfox_model::RegressionComparisonMetricReader,
 ) -> String {
@@ -271,6 +348,16 @@ fn binary_classification_comparison_type_name(
 		modelfox_model::BinaryClassificationComparisonMetricReader::Aucroc(_) => {
 			"Area Under the Receiver Operating Characteristic Curve".to_owned()
 		}
+		modelfox_model::BinaryClassificationComparisonMetricReader::F1(_) => "F1 Score".to_owned(),
+		modelfox_model::BinaryClassificationComparisonMetricReader::Precision(_) => {
+			"Precision".to_owned()
+		}
+		modelfox_model::BinaryClassificationComparisonMetricReader::Recall(_) => {
+			"Recall".to_owned()
+		}
+		modelfox_model::BinaryClassificationComparisonMetricReader::Accuracy(_) => {
+			"Accuracy".to_owned()
+		}
 	}
 }
 
@@ -327,110 +414,42 @@ fn compute_feature_importances_section(
 			multiclass_classifier.read().overall_column_stats().len()
 		}
 	};
-	let (mut feature_importances, n_features) = match model.inner() {
+	let (feature_groups, feature_importances, feature_importances_std) = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => match regressor.read().model() {
 			modelfox_model::RegressionModelReader::Linear(inner_model) => {
 				let inner_model = inner_model.read();
-				let feature_names = compute_feature_names(inner_model.feature_groups().iter());
-				let feature_importance_values = inner_model
-					.feature_importances()
-					.iter()
-					.map(|value| Finite::new(value).ok())
-					.collect::<Option<Vec<FiniteF32>>>()?;
-				let mut feature_importances = zip!(feature_names, feature_importance_values)
-					.map(
-						|(feature_name, feature_importance_value)| FeatureImportance {
-							feature_importance_value,
-							feature_name,
-						},
-					)
-					.collect::<Vec<_>>();
-				feature_importances.sort_by(|a, b| {
-					a.feature_importance_value
-						.partial_cmp(&b.feature_importance_value)
-						.unwrap()
-						.reverse()
-				});
-				let n_features = feature_importances.len();
-				(feature_importances, n_features)
+				(
+					inner_model.feature_groups(),
+					inner_model.feature_importances(),
+					inner_model.feature_importances_std(),
+				)
 			}
 			modelfox_model::RegressionModelReader::Tree(inner_model) => {
 				let inner_model = inner_model.read();
-				let feature_names = compute_feature_names(inner_model.feature_groups().iter());
-				let feature_importance_values = inner_model
-					.feature_importances()
-					.iter()
-					.map(|value| Finite::new(value).ok())
-					.collect::<Option<Vec<FiniteF32>>>()?;
-				let mut feature_importances = zip!(feature_names, feature_importance_values)
-					.map(
-						|(feature_name, feature_importance_value)| FeatureImportance {
-							feature_importance_value,
-							feature_name,
-						},
-					)
-					.collect::<Vec<_>>();
-				feature_importances.sort_by(|a, b| {
-					a.feature_importance_value
-						.partial_cmp(&b.feature_importance_value)
-						.unwrap()
-						.reverse()
-				});
-				let n_features = feature_importances.len();
-				(feature_importances, n_features)
+				(
+					inner_model.feature_groups(),
+					inner_model.feature_importances(),
+					inner_model.feature_importances_std(),
+				)
 			}
 		},
 		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
 			match binary_classifier.read().model() {
 				modelfox_model::BinaryClassificationModelReader::Linear(inner_model) => {
 					let inner_model = inner_model.read();
-					let feature_names = compute_feature_names(inner_model.feature_groups().iter());
-					let feature_importance_values = inner_model
-						.feature_importances()
-						.iter()
-						.map(|value| Finite::new(value).ok())
-						.collect::<Option<Vec<FiniteF32>>>()?;
-					let mut feature_importances = zip!(feature_names, feature_importance_values)
-						.map(
-							|(feature_name, feature_importance_value)| FeatureImportance {
-								feature_importance_value,
-								feature_name,
-							},
-						)
-						.collect::<Vec<_>>();
-					feature_importances.sort_by(|a, b| {
-						a.feature_importance_value
-							.partial_cmp(&b.feature_importance_value)
-							.unwrap()
-							.reverse()
-					});
-					let n_features = feature_importances.len();
-					(feature_importances, n_features)
+					(
+						inner_model.feature_groups(),
+						inner_model.feature_importances(),
+						inner_model.feature_importances_std(),
+					)
 				}
 				modelfox_model::BinaryClassificationModelReader::Tree(inner_model) => {
 					let inner_model = inner_model.read();
-					let feature_names = compute_feature_names(inner_model.feature_groups().iter());
-					let feature_importance_values = inner_model
-						.feature_importances()
-						.iter()
-						.map(|value| Finite::new(value).ok())
-						.collect::<Option<Vec<FiniteF32>>>()?;
-					let mut feature_importances = zip!(feature_names, feature_importance_values)
-						.map(
-							|(feature_name, feature_importance_value)| FeatureImportance {
-								feature_importance_value,
-								feature_name,
-							},
-						)
-						.collect::<Vec<_>>();
-					feature_importances.sort_by(|a, b| {
-						a.feature_importance_value
-							.partial_cmp(&b.feature_importance_value)
-							.unwrap()
-							.reverse()
-					});
-					let n_features = feature_importances.len();
-					(feature_importances, n_features)
+					(
+						inner_model.feature_groups(),
+						inner_model.feature_importances(),
+						inner_model.feature_importances_std(),
+					)
 				}
 			}
 		}
@@ -438,63 +457,67 @@ fn compute_feature_importances_section(
 			match multiclass_classifier.read().model() {
 				modelfox_model::MulticlassClassificationModelReader::Linear(inner_model) => {
 					let inner_model = inner_model.read();
-					let feature_names = compute_feature_names(inner_model.feature_groups().iter());
-					let feature_importance_values = inner_model
-						.feature_importances()
-						.iter()
-						.map(|value| Finite::new(value).ok())
-						.collect::<Option<Vec<FiniteF32>>>()?;
-					let mut feature_importances = zip!(feature_names, feature_importance_values)
-						.map(
-							|(feature_name, feature_importance_value)| FeatureImportance {
-								feature_importance_value,
-								feature_name,
-							},
-						)
-						.collect::<Vec<_>>();
-					feature_importances.sort_by(|a, b| {
-						a.feature_importance_value
-							.partial_cmp(&b.feature_importance_value)
-							.unwrap()
-							.reverse()
-					});
-					let n_features = feature_importances.len();
-					(feature_importances, n_features)
+					(
+						inner_model.feature_groups(),
+						inner_model.feature_importances(),
+						inner_model.feature_importances_std(),
+					)
 				}
 				modelfox_model::MulticlassClassificationModelReader::Tree(inner_model) => {
 					let inner_model = inner_model.read();
-					let feature_names = compute_feature_names(inner_model.feature_groups().iter());
-					let feature_importance_values = inner_model
-						.feature_importances()
-						.iter()
-						.map(|value| Finite::new(value).ok())
-						.collect::<Option<Vec<FiniteF32>>>()?;
-					let mut feature_importances = zip!(feature_names, feature_importance_values)
-						.map(
-							|(feature_name, feature_importance_value)| FeatureImportance {
-								feature_importance_value,
-								feature_name,
-							},
-						)
-						.collect::<Vec<_>>();
-					feature_importances.sort_by(|a, b| {
-						a.feature_importance_value
-							.partial_cmp(&b.feature_importance_value)
-							.unwrap()
-							.reverse()
-					});
-					let n_features = feature_importances.len();
-					(feature_importances, n_features)
+					(
+						inner_model.feature_groups(),
+						inner_model.feature_importances(),
+						inner_model.feature_importances_std(),
+					)
 				}
 			}
 		}
 	};
+	let feature_names = compute_feature_names(feature_groups.iter());
+	let feature_importance_values = feature_importances
+		.iter()
+		.map(|value| Finite::new(value).ok())
+		.collect::<Option<Vec<FiniteF32>>>()?;
+	// The standard deviations are only present for models evaluated with cross validation.
+	let feature_importance_stds = match feature_importances_std {
+		Some(feature_importances_std) => feature_importances_std
+			.iter()
+			.map(|value| Finite::new(value).ok().map(Some))
+			.collect::<Option<Vec<Option<FiniteF32>>>>()?,
+		None => vec![None; feature_importance_values.len()],
+	};
+	// If the number of feature names does not match the number of feature importances, the names would be paired with the wrong values, so do not show the section.
+	let mut feature_importances = zip_eq!(
+		feature_names,
+		feature_importance_values,
+		feature_importance_stds
+	)
+	.map(|item| {
+		item.map(
+			|(feature_name, feature_importance_value, feature_importance_std)| FeatureImportance {
+				feature_importance_value,
+				feature_importance_std,
+				feature_name,
+			},
+		)
+	})
+	.collect::<Result<Vec<_>, _>>()
+	.ok()?;
+	feature_importances.sort_by(|a, b| {
+		a.feature_importance_value
+			.partial_cmp(&b.feature_importance_value)
+			.unwrap()
+			.reverse()
+	});
+	let n_features = feature_importances.len();
 	let feature_importances_table_rows = feature_importances
 		.iter()
 		.take(TRAINING_IMPORTANCES_MAX_FEATURE_IMPORTANCES_TO_SHOW_IN_TABLE)
 		.map(|feature_importance| FeatureImportance {
 			feature_name: feature_importance.feature_name.to_owned(),
 			feature_importance_value: feature_importance.feature_importance_value,
+			feature_importance_std: feature_importance.feature_importance_std,
 		})
 		.collect();
 	feature_importances.truncate(TRAINING_IMPORTANCES_MAX_FEATURE_IMPORTANCES_TO_SHOW_IN_CHART);
@@ -511,53 +534,6 @@ fn compute_feature_names<'a>(
 	feature_groups: impl Iterator<Item = modelfox_model::FeatureGroupReader<'a>>,
 ) -> Vec<String> {
 	feature_groups
-		.flat_map(|feature_group| match feature_group {
-			modelfox_model::FeatureGroupReader::Identity(feature_group) => {
-				let feature_group = feature_group.read();
-				vec![feature_group.source_column_name().to_owned()]
-			}
-			modelfox_model::FeatureGroupReader::Normalized(feature_group) => {
-				let feature_group = feature_group.read();
-				vec![feature_group.source_column_name().to_owned()]
-			}
-			modelfox_model::FeatureGroupReader::OneHotEncoded(feature_group) => {
-				let feature_group = feature_group.read();
-				vec!["OOV"]
-					.into_iter()
-					.chain(feature_group.variants().iter())
-					.map(|variant| format!("{} = {}", feature_group.source_column_name(), variant,))
-					.collect()
-			}
-			modelfox_model::FeatureGroupReader::BagOfWords(feature_group) => {
-				let feature_group = feature_group.read();
-				feature_group
-					.ngrams()
-					.iter()
-					.map(|(ngram, _)| {
-						format!("{} contains {}", feature_group.source_column_name(), ngram)
-					})
-					.collect()
-			}
-			modelfox_model::FeatureGroupReader::BagOfWordsCosineSimilarity(feature_group) => {
-				let feature_group = feature_group.read();
-				vec![format!(
-					"similarity of {} and {}",
-					feature_group.source_column_name_a(),
-					feature_group.source_column_name_b(),
-				)]
-			}
-			modelfox_model::FeatureGroupReader::WordEmbedding(feature_group) => {
-				let feature_group = feature_group.read();
-				(0..feature_group.model().size())
-					.map(|i| {
-						format!(
-							"{} word embedding value {}",
-							feature_group.source_column_name(),
-							i
-						)
-					})
-					.collect()
-			}
-		})
+		.flat_map(|feature_group| feature_group.feature_names())
 		.collect()
 }
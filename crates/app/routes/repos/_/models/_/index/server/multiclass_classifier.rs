@@ -1,6 +1,10 @@
-use crate::common::{FeatureImportancesSection, TrainingSummarySection};
-use modelfox_app_ui::colors::{BASELINE_COLOR, TRAINING_COLOR};
+use crate::common::{DroppedFeaturesSection, FeatureImportancesSection, TrainingSummarySection};
+use modelfox_app_ui::{
+	axis_scale_select_field::AxisScaleSelectField,
+	colors::{BASELINE_COLOR, TRAINING_COLOR},
+};
 use modelfox_charts::{
+	common::AxisScale,
 	components::LineChart,
 	line_chart::{LineChartPoint, LineChartSeries, LineStyle, PointStyle},
 };
@@ -13,6 +17,7 @@ pub struct MulticlassClassifier {
 	pub id: String,
 	pub warning: Option<String>,
 	pub training_summary_section: TrainingSummarySection,
+	pub dropped_features_section: Option<DroppedFeaturesSection>,
 	pub training_metrics_section: MulticlassClassifierMetricsSection,
 	pub feature_importances_section: Option<FeatureImportancesSection>,
 }
@@ -27,6 +32,7 @@ impl Component for MulticlassClassifier {
 			}))
 			.child(ui::H1::new("Overview"))
 			.child(self.training_summary_section)
+			.child(self.dropped_features_section)
 			.child(self.training_metrics_section)
 			.child(self.feature_importances_section)
 			.into_node()
@@ -39,6 +45,7 @@ pub struct MulticlassClassifierMetricsSection {
 	pub class_metrics: Vec<MulticlassClassifierClassMetrics>,
 	pub classes: Vec<String>,
 	pub losses_chart_series: Option<Vec<f32>>,
+	pub losses_chart_scale: AxisScale,
 }
 
 pub struct MulticlassClassifierClassMetrics {
@@ -86,15 +93,22 @@ impl Component for MulticlassClassifierMetricsSection {
 					.number_formatter(ui::NumberFormatter::Percent(Default::default())),
 			)
 			.child(losses_chart_series.map(|losses_chart_series| {
-				ui::Card::new().child(Dehydrate::new(
-					"loss",
-					LineChart::new()
-						.series(losses_chart_series)
-						.title("Training Loss By Round or Epoch".to_owned())
-						.x_axis_title("Round or Epoch".to_owned())
-						.y_axis_title("Loss".to_owned())
-						.y_min(Finite::new(0.0).unwrap()),
-				))
+				fragment()
+					.child(
+						ui::Form::new().child(AxisScaleSelectField::new(self.losses_chart_scale)),
+					)
+					.child(
+						ui::Card::new().child(Dehydrate::new(
+							"loss",
+							LineChart::new()
+								.series(losses_chart_series)
+								.title("Training Loss By Round or Epoch".to_owned())
+								.x_axis_title("Round or Epoch".to_owned())
+								.y_axis_title("Loss".to_owned())
+								.y_axis_scale(self.losses_chart_scale)
+								.y_min(Finite::new(0.0).unwrap()),
+						)),
+					)
 			}))
 			.into_node()
 	}
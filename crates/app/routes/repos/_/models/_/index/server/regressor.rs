@@ -1,6 +1,11 @@
-use crate::common::{FeatureImportancesSection, TrainingSummarySection};
-use modelfox_app_ui::colors::{BASELINE_COLOR, TRAINING_COLOR};
+use crate::common::{DroppedFeaturesSection, FeatureImportancesSection, TrainingSummarySection};
+use modelfox_app_core::alert::AlertMetric;
+use modelfox_app_ui::{
+	axis_scale_select_field::AxisScaleSelectField,
+	colors::{BASELINE_COLOR, TRAINING_COLOR},
+};
 use modelfox_charts::{
+	common::AxisScale,
 	components::LineChart,
 	line_chart::{LineChartPoint, LineChartSeries, LineStyle, PointStyle},
 };
@@ -13,6 +18,7 @@ pub struct Regressor {
 	pub id: String,
 	pub warning: Option<String>,
 	pub training_summary_section: TrainingSummarySection,
+	pub dropped_features_section: Option<DroppedFeaturesSection>,
 	pub training_metrics_section: RegressorMetricsSection,
 	pub feature_importances_section: Option<FeatureImportancesSection>,
 }
@@ -27,6 +33,7 @@ impl Component for Regressor {
 			}))
 			.child(ui::H1::new("Overview"))
 			.child(self.training_summary_section)
+			.child(self.dropped_features_section)
 			.child(self.training_metrics_section)
 			.child(self.feature_importances_section)
 			.into_node()
@@ -39,6 +46,7 @@ pub struct RegressorMetricsSection {
 	pub mse: f32,
 	pub rmse: f32,
 	pub losses_chart_series: Option<Vec<f32>>,
+	pub losses_chart_scale: AxisScale,
 }
 
 impl Component for RegressorMetricsSection {
@@ -59,12 +67,13 @@ impl Component for RegressorMetricsSection {
 				title: Some("loss".to_owned()),
 			}]
 		});
+		let number_formatter = AlertMetric::RootMeanSquaredError.number_formatter();
 		let title = ui::H2::new("Metrics");
 		let p = ui::P::new()
 			.child("Your model was evaluated on the test dataset and achieved a root mean squared error of ")
-			.child(b().child(ui::format_float(self.rmse)))
+			.child(b().child(number_formatter.format(self.rmse)))
 			.child(". This is compared with the baseline root mean squared error of ")
-			.child(b().child(ui::format_float(self.baseline_rmse)))
+			.child(b().child(number_formatter.format(self.baseline_rmse)))
 			.child(", which is what the model would get if it always predicted the mean.");
 		ui::S2::new()
 			.child(title)
@@ -76,18 +85,25 @@ impl Component for RegressorMetricsSection {
 					.title("Root Mean Squared Error".to_owned())
 					.value_a_title("Baseline".to_owned())
 					.value_b_title("Training".to_owned())
-					.number_formatter(ui::NumberFormatter::Float(Default::default())),
+					.number_formatter(number_formatter),
 			)
 			.child(losses_chart_series.map(|losses_chart_series| {
-				ui::Card::new().child(Dehydrate::new(
-					"loss",
-					LineChart::new()
-						.series(losses_chart_series)
-						.title("Training Loss By Round or Epoch".to_owned())
-						.x_axis_title("Round or Epoch".to_owned())
-						.y_axis_title("Loss".to_owned())
-						.y_min(Finite::new(0.0).unwrap()),
-				))
+				fragment()
+					.child(
+						ui::Form::new().child(AxisScaleSelectField::new(self.losses_chart_scale)),
+					)
+					.child(
+						ui::Card::new().child(Dehydrate::new(
+							"loss",
+							LineChart::new()
+								.series(losses_chart_series)
+								.title("Training Loss By Round or Epoch".to_owned())
+								.x_axis_title("Round or Epoch".to_owned())
+								.y_axis_title("Loss".to_owned())
+								.y_axis_scale(self.losses_chart_scale)
+								.y_min(Finite::new(0.0).unwrap()),
+						)),
+					)
 			}))
 			.into_node()
 	}
@@ -0,0 +1,93 @@
+use crate::page::Page;
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::get_model_bytes,
+	path_components,
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
+use modelfox_id::Id;
+use pinwheel::prelude::*;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Format {
+	Html,
+	Markdown,
+}
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id = if let ["repos", _, "models", model_id, "model_card"] =
+		*path_components(request).as_slice()
+	{
+		model_id.to_owned()
+	} else {
+		bail!("unexpected path");
+	};
+	#[derive(serde::Deserialize, Default)]
+	struct SearchParams {
+		format: Option<Format>,
+	}
+	let search_params: SearchParams = match request.uri().query() {
+		Some(query) => match serde_urlencoded::from_str(query) {
+			Ok(search_params) => search_params,
+			Err(_) => return Ok(bad_request()),
+		},
+		None => SearchParams::default(),
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let model_layout_info =
+		model_layout_info(&mut db, app, model_id, ModelNavItem::ModelCard).await?;
+	app.commit_transaction(db).await?;
+	let bytes = get_model_bytes(app.storage(), model_id).await?;
+	let model = modelfox_model::from_bytes(&bytes)?;
+	let markdown =
+		modelfox_core::model_card::model_card(model, &model_layout_info.repo_title).to_markdown();
+	let filename = format!(
+		"{} Model Card.md",
+		model_layout_info.repo_title.replace('"', "")
+	);
+	let response = match search_params.format.unwrap_or(Format::Html) {
+		Format::Markdown => http::Response::builder()
+			.status(http::StatusCode::OK)
+			.header(http::header::CONTENT_TYPE, "text/markdown; charset=utf-8")
+			.header(
+				http::header::CONTENT_DISPOSITION,
+				format!("attachment; filename=\"{}\"", filename),
+			)
+			.body(hyper::Body::from(markdown))
+			.unwrap(),
+		Format::Html => {
+			let page = Page {
+				model_layout_info,
+				markdown,
+				filename,
+			};
+			http::Response::builder()
+				.status(http::StatusCode::OK)
+				.body(hyper::Body::from(html(page)))
+				.unwrap()
+		}
+	};
+	Ok(response)
+}
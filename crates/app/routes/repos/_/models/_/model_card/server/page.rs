@@ -0,0 +1,41 @@
+use modelfox_app_layouts::{
+	document::Document,
+	model_layout::{ModelLayout, ModelLayoutInfo},
+};
+use modelfox_app_ui::page_heading::{PageHeading, PageHeadingButtons};
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct Page {
+	pub model_layout_info: ModelLayoutInfo,
+	pub markdown: String,
+	pub filename: String,
+}
+
+impl Component for Page {
+	fn into_node(self) -> Node {
+		Document::new()
+			.child(
+				ModelLayout::new(self.model_layout_info).child(
+					ui::S1::new()
+						.child(
+							PageHeading::new()
+								.child(ui::H1::new("Model Card"))
+								.child(
+									PageHeadingButtons::new().child(
+										ui::Button::new()
+											.href("model_card?format=markdown".to_owned())
+											.download(self.filename)
+											.child("Download Markdown"),
+									),
+								),
+						)
+						.child(ui::P::new().child(
+							"This model card is generated from the model's training data, metrics, and configuration. Download it to publish it along with the model.",
+						))
+						.child(ui::Card::new().child(ui::Markdown::new(self.markdown).untrusted(true))),
+				),
+			)
+			.into_node()
+	}
+}
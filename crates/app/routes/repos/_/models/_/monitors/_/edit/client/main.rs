@@ -0,0 +1,11 @@
+use pinwheel::prelude::*;
+use web_sys as dom;
+
+pub fn main() {
+	modelfox_ui::client_start();
+	let window = dom::window().unwrap();
+	let document = window.document().unwrap();
+	if document.get_element_by_id("evaluation_history").is_some() {
+		hydrate::<modelfox_charts::components::LineChart>("evaluation_history");
+	}
+}
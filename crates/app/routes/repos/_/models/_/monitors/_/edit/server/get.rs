@@ -2,9 +2,11 @@ use crate::page::Page;
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
+	custom_metrics::get_custom_metrics,
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model_bytes,
-	monitor::{get_monitor, AlertModelType},
+	monitor::{get_monitor, get_monitor_evaluations, AlertModelType},
+	notification_channels::get_notification_channel_summaries_for_repo,
 	path_components,
 	user::{authorize_user, authorize_user_for_model},
 };
@@ -16,13 +18,21 @@ use std::{str::FromStr, sync::Arc};
 pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
 	let context = request.extensions().get::<Arc<Context>>().unwrap().clone();
 	let app = &context.app;
-	let (model_id, monitor_id) = if let ["repos", _, "models", model_id, "monitors", monitor_id, "edit"] =
+	let (repo_id, model_id, monitor_id) = if let ["repos", repo_id, "models", model_id, "monitors", monitor_id, "edit"] =
 		path_components(request).as_slice()
 	{
-		(model_id.to_owned(), monitor_id.to_owned())
+		(
+			repo_id.to_owned(),
+			model_id.to_owned(),
+			monitor_id.to_owned(),
+		)
 	} else {
 		bail!("unexpected path");
 	};
+	let repo_id: Id = match repo_id.parse() {
+		Ok(repo_id) => repo_id,
+		Err(_) => return Ok(bad_request()),
+	};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -31,10 +41,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
@@ -43,13 +54,22 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let model_type = AlertModelType::from(model.inner());
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::Monitors).await?;
-	let monitor = get_monitor(&mut db, Id::from_str(monitor_id)?).await?;
+	let monitor_id = Id::from_str(monitor_id)?;
+	let monitor = get_monitor(&mut db, monitor_id).await?;
+	let evaluations = get_monitor_evaluations(&mut db, monitor_id).await?;
+	let notification_channels =
+		get_notification_channel_summaries_for_repo(&mut db, repo_id).await?;
+	let custom_metrics = get_custom_metrics(&mut db, repo_id).await?;
 	let page = Page {
 		monitor,
 		monitor_id: monitor_id.to_string(),
 		model_layout_info,
 		model_type,
+		notification_channels,
+		custom_metrics,
+		evaluations,
 		error: None,
+		test_alert_sends: None,
 	};
 	let html = html(page);
 	let response = http::Response::builder()
@@ -1,25 +1,42 @@
 use modelfox_app_core::{
 	alert::AlertMethod,
-	monitor::{AlertModelType, Monitor},
+	alert_sender::TestAlertSend,
+	alert_template::{render_alert_template, AlertTemplateContext, ALERT_TEMPLATE_VARIABLES},
+	custom_metrics::CustomMetric,
+	monitor::{AlertModelType, Monitor, MonitorEvaluation},
+	notification_channels::NotificationChannelSummary,
 };
 use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
 };
+use modelfox_app_ui::colors::{PRODUCTION_COLOR, TRAINING_COLOR};
+use modelfox_charts::{
+	components::LineChart,
+	line_chart::{LineChartPoint, LineChartSeries, LineStyle, PointStyle},
+};
+use modelfox_finite::Finite;
 use modelfox_ui as ui;
+use num::ToPrimitive;
 use pinwheel::prelude::*;
+use time::macros::format_description;
 
 pub struct Page {
 	pub monitor: Monitor,
 	pub monitor_id: String,
 	pub model_layout_info: ModelLayoutInfo,
 	pub model_type: AlertModelType,
+	pub notification_channels: Vec<NotificationChannelSummary>,
+	pub custom_metrics: Vec<CustomMetric>,
+	pub evaluations: Vec<MonitorEvaluation>,
 	pub error: Option<String>,
+	/// These are the results of sending a test alert, shown after the user sends one.
+	pub test_alert_sends: Option<Vec<TestAlertSend>>,
 }
 
 impl Component for Page {
 	fn into_node(self) -> Node {
-		let metric_options = match self.model_type {
+		let mut metric_options = match self.model_type {
 			AlertModelType::Classifier => vec![ui::SelectFieldOption {
 				text: "Accuracy".to_owned(),
 				value: "accuracy".to_owned(),
@@ -33,8 +50,38 @@ impl Component for Page {
 					text: "Root Mean Squared Error".to_owned(),
 					value: "rmse".to_owned(),
 				},
+				ui::SelectFieldOption {
+					text: "Mean Absolute Error".to_owned(),
+					value: "mae".to_owned(),
+				},
+				ui::SelectFieldOption {
+					text: "Mean Residual".to_owned(),
+					value: "mean_residual".to_owned(),
+				},
+				ui::SelectFieldOption {
+					text: "Tolerance Accuracy".to_owned(),
+					value: "tolerance_accuracy".to_owned(),
+				},
 			],
 		};
+		metric_options.push(ui::SelectFieldOption {
+			text: "Out of Range Fraction".to_owned(),
+			value: "out_of_range".to_owned(),
+		});
+		metric_options.push(ui::SelectFieldOption {
+			text: "Unseen Token Fraction".to_owned(),
+			value: "unseen_tokens".to_owned(),
+		});
+		metric_options.push(ui::SelectFieldOption {
+			text: "Model Age (Days)".to_owned(),
+			value: "model_age".to_owned(),
+		});
+		metric_options.extend(self.custom_metrics.into_iter().map(|custom_metric| {
+			ui::SelectFieldOption {
+				text: custom_metric.name,
+				value: format!("custom:{}", custom_metric.id),
+			}
+		}));
 		let email = self
 			.monitor
 			.methods
@@ -43,6 +90,37 @@ impl Component for Page {
 				AlertMethod::Email(e) => e.to_string(),
 				_ => acc,
 			});
+		let notification_channel_id = self.monitor.methods.iter().find_map(|method| match method {
+			AlertMethod::NotificationChannel(notification_channel) => {
+				Some(notification_channel.notification_channel_id.to_string())
+			}
+			_ => None,
+		});
+		let notification_channel_field = if self.notification_channels.is_empty() {
+			None
+		} else {
+			let options = std::iter::once(ui::SelectFieldOption {
+				text: "None".to_owned(),
+				value: String::new(),
+			})
+			.chain(
+				self.notification_channels
+					.into_iter()
+					.map(|notification_channel| ui::SelectFieldOption {
+						text: notification_channel.name,
+						value: notification_channel.id.to_string(),
+					}),
+			)
+			.collect();
+			Some(
+				ui::SelectField::new()
+					.label("Notification Channel (Optional)".to_owned())
+					.name("notification_channel".to_owned())
+					.required(false)
+					.options(options)
+					.value(notification_channel_id.unwrap_or_default()),
+			)
+		};
 		let lower = if let Some(l) = self.monitor.threshold.difference_lower {
 			l.to_string()
 		} else {
@@ -53,7 +131,40 @@ impl Component for Page {
 		} else {
 			String::new()
 		};
+		let minimum_sample_size = self
+			.monitor
+			.minimum_sample_size
+			.map(|minimum_sample_size| minimum_sample_size.to_string())
+			.unwrap_or_default();
+		let cooldown_periods = if self.monitor.cooldown_periods > 0 {
+			self.monitor.cooldown_periods.to_string()
+		} else {
+			String::new()
+		};
+		let evaluation_history = EvaluationHistory {
+			evaluations: self.evaluations,
+		};
+		let message_template_preview =
+			self.monitor
+				.message_template
+				.as_ref()
+				.map(|message_template| MessageTemplatePreview {
+					message: render_alert_template(
+						message_template,
+						&AlertTemplateContext::example(
+							&self.monitor,
+							&self.model_layout_info.repo_title,
+						),
+					)
+					.map_err(|error| error.to_string()),
+				});
+		let variables = ALERT_TEMPLATE_VARIABLES
+			.iter()
+			.map(|variable| format!("{{{{{}}}}}", variable))
+			.collect::<Vec<_>>()
+			.join(", ");
 		Document::new()
+			.client("modelfox_app_monitors_edit_client")
 			.child(
 				ModelLayout::new(self.model_layout_info).child(
 					ui::S1::new()
@@ -136,6 +247,34 @@ impl Component for Page {
 										])
 										.value(self.monitor.threshold.mode.to_string()),
 								)
+								.child(
+									ui::TextField::new()
+										.label("Minimum Sample Size (Optional)".to_string())
+										.name("minimum_sample_size".to_string())
+										.required(false)
+										.value(minimum_sample_size),
+								)
+								.child(
+									ui::CheckboxField::new()
+										.label("Warn When Below Minimum Sample Size".to_owned())
+										.name("warn_below_minimum_sample_size".to_owned())
+										.checked(self.monitor.warn_below_minimum_sample_size),
+								)
+								.child(
+									ui::TextField::new()
+										.label("Cooldown Periods (Optional)".to_string())
+										.name("cooldown_periods".to_string())
+										.required(false)
+										.value(cooldown_periods),
+								)
+								.child(
+									ui::CheckboxField::new()
+										.label(
+											"Require Confidence Interval Past Threshold".to_owned(),
+										)
+										.name("require_confidence_interval".to_owned())
+										.checked(self.monitor.require_confidence_interval),
+								)
 								.child(
 									ui::TextField::new()
 										.label("Title (Optional)".to_string())
@@ -156,12 +295,34 @@ impl Component for Page {
 										.name("webhook".to_string())
 										.required(false),
 								)
+								.child(notification_channel_field)
+								.child(
+									ui::FieldLabel::new()
+										.child("Message Template (Optional)")
+										.child(
+											textarea()
+												.class("form-text-field")
+												.attribute("name", "message_template")
+												.attribute("rows", 4)
+												.child(self.monitor.message_template.clone()),
+										),
+								)
+								.child(ui::P::new().child(format!(
+									"Customize the message sent for this monitor's alerts. The available variables are {}.",
+									variables
+								)))
 								.child(
 									ui::Button::new()
 										.button_type(ui::ButtonType::Submit)
 										.child("Update"),
 								),
 						)
+						.child(message_template_preview)
+						.child(TestAlert {
+							test_alert_sends: self.test_alert_sends,
+						})
+						.child(evaluation_history)
+						.child(SaveAsTemplate)
 						.child(DangerZone),
 				),
 			)
@@ -169,6 +330,229 @@ impl Component for Page {
 	}
 }
 
+struct MessageTemplatePreview {
+	message: Result<String, String>,
+}
+
+impl Component for MessageTemplatePreview {
+	fn into_node(self) -> Node {
+		let description = "This is the message that will be sent for this monitor's alerts, rendered with example values.";
+		let preview = match self.message {
+			Ok(message) => ui::Card::new()
+				.child(ui::P::new().child(message))
+				.into_node(),
+			Err(error) => ui::Alert::new(ui::Level::Danger).child(error).into_node(),
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Message Preview"))
+			.child(ui::P::new().child(description))
+			.child(preview)
+			.into_node()
+	}
+}
+
+struct TestAlert {
+	test_alert_sends: Option<Vec<TestAlertSend>>,
+}
+
+impl Component for TestAlert {
+	fn into_node(self) -> Node {
+		let results = self.test_alert_sends.map(|test_alert_sends| {
+			if test_alert_sends.is_empty() {
+				return ui::Alert::new(ui::Level::Warning)
+					.child("This monitor has no alert methods to send a test alert with.")
+					.into_node();
+			}
+			div()
+				.children(test_alert_sends.into_iter().map(|test_alert_send| {
+					match test_alert_send.error {
+						None => ui::Alert::new(ui::Level::Success).child(format!(
+							"{}: The test alert was sent.",
+							test_alert_send.method
+						)),
+						Some(error) => ui::Alert::new(ui::Level::Danger)
+							.child(format!("{}: {}", test_alert_send.method, error)),
+					}
+				}))
+				.into_node()
+		});
+		ui::S2::new()
+			.child(ui::H2::new("Test Alert"))
+			.child(ui::P::new().child(
+				"Send a test alert with each of this monitor's saved alert methods to check that its alerts can be delivered. Test alerts are marked as tests, and webhooks receive a payload with \"test\": true.",
+			))
+			.child(results)
+			.child(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "send_test_alert"),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Send Test Alert"),
+					),
+			)
+			.into_node()
+	}
+}
+
+struct EvaluationHistory {
+	evaluations: Vec<MonitorEvaluation>,
+}
+
+impl Component for EvaluationHistory {
+	fn into_node(self) -> Node {
+		let description = "This chart shows the difference between the production and training values of the monitored metric each time the monitor was checked, along with the thresholds at the time. Points where the monitor fired an alert are marked in red.";
+		let chart_or_empty_message = if self.evaluations.is_empty() {
+			ui::Card::new()
+				.child(ui::P::new().child("This monitor has not been checked yet."))
+				.into_node()
+		} else {
+			let labels = self
+				.evaluations
+				.iter()
+				.map(|evaluation| format_date(evaluation.date))
+				.collect::<Vec<_>>();
+			let to_point = |index: usize, value: Option<f32>| LineChartPoint {
+				x: Finite::new(index.to_f64().unwrap()).unwrap(),
+				y: value.and_then(|value| Finite::new(value.to_f64().unwrap()).ok()),
+			};
+			let mut series = vec![LineChartSeries {
+				color: PRODUCTION_COLOR.to_owned(),
+				data: self
+					.evaluations
+					.iter()
+					.enumerate()
+					.map(|(index, evaluation)| to_point(index, Some(evaluation.difference)))
+					.collect(),
+				line_style: None,
+				point_style: None,
+				title: Some("Difference".to_owned()),
+			}];
+			// A value fires the lower threshold when its absolute value exceeds it, so the lower threshold is drawn below zero.
+			if self
+				.evaluations
+				.iter()
+				.any(|evaluation| evaluation.difference_lower.is_some())
+			{
+				series.push(LineChartSeries {
+					color: TRAINING_COLOR.to_owned(),
+					data: self
+						.evaluations
+						.iter()
+						.enumerate()
+						.map(|(index, evaluation)| {
+							to_point(index, evaluation.difference_lower.map(|lower| -lower))
+						})
+						.collect(),
+					line_style: Some(LineStyle::Dashed),
+					point_style: Some(PointStyle::Hidden),
+					title: Some("Lower Threshold".to_owned()),
+				});
+			}
+			if self
+				.evaluations
+				.iter()
+				.any(|evaluation| evaluation.difference_upper.is_some())
+			{
+				series.push(LineChartSeries {
+					color: TRAINING_COLOR.to_owned(),
+					data: self
+						.evaluations
+						.iter()
+						.enumerate()
+						.map(|(index, evaluation)| to_point(index, evaluation.difference_upper))
+						.collect(),
+					line_style: Some(LineStyle::Dashed),
+					point_style: Some(PointStyle::Hidden),
+					title: Some("Upper Threshold".to_owned()),
+				});
+			}
+			series.push(LineChartSeries {
+				color: ui::colors::RED.to_owned(),
+				data: self
+					.evaluations
+					.iter()
+					.enumerate()
+					.map(|(index, evaluation)| {
+						to_point(
+							index,
+							if evaluation.fired {
+								Some(evaluation.difference)
+							} else {
+								None
+							},
+						)
+					})
+					.collect(),
+				line_style: Some(LineStyle::Hidden),
+				point_style: Some(PointStyle::Circle),
+				title: Some("Fired".to_owned()),
+			});
+			ui::Card::new()
+				.child(Dehydrate::new(
+					"evaluation_history",
+					LineChart::new()
+						.labels(labels)
+						.series(series)
+						.title("Evaluation History".to_owned())
+						.x_axis_title("Date".to_owned())
+						.y_axis_title("Difference".to_owned()),
+				))
+				.into_node()
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Evaluation History"))
+			.child(ui::P::new().child(description))
+			.child(chart_or_empty_message)
+			.into_node()
+	}
+}
+
+fn format_date(timestamp: i64) -> String {
+	time::OffsetDateTime::from_unix_timestamp(timestamp)
+		.ok()
+		.and_then(|date_time| {
+			date_time
+				.format(format_description!("[year]-[month]-[day] [hour]:[minute]"))
+				.ok()
+		})
+		.unwrap_or_default()
+}
+
+struct SaveAsTemplate;
+
+impl Component for SaveAsTemplate {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("Monitor Template"))
+			.child(ui::P::new().child(
+				"Save this monitor as a template to create it automatically for every new model version uploaded to this repo.",
+			))
+			.child(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "save_as_template"),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Save as Template"),
+					),
+			)
+			.into_node()
+	}
+}
+
 struct DangerZone;
 
 impl Component for DangerZone {
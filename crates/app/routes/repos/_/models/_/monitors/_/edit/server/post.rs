@@ -1,13 +1,20 @@
 use crate::page::Page;
 use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	alert::{AlertMethod, AlertMetric},
+	custom_metrics::get_custom_metrics,
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model_bytes,
 	monitor::{
-		delete_monitor, extract_threshold_bounds, get_monitor, validate_threshold_bounds,
-		AlertModelType, Monitor, MonitorCadence, MonitorThreshold, MonitorThresholdMode,
+		delete_monitor, extract_data_requirements, extract_threshold_bounds, get_monitor,
+		get_monitor_evaluations, validate_threshold_bounds, AlertModelType, Monitor,
+		MonitorCadence, MonitorThreshold, MonitorThresholdMode,
+	},
+	monitor_templates::{create_monitor_template, MonitorTemplate},
+	notification_channels::{
+		get_notification_channel_summaries_for_repo, notification_channel_alert_method,
 	},
 	path_components,
 	user::{authorize_user, authorize_user_for_model, authorize_user_for_repo},
@@ -22,6 +29,10 @@ use std::{str, str::FromStr, sync::Arc};
 enum Action {
 	#[serde(rename = "update_alert")]
 	UpdateMonitor(UpdateMonitorAction),
+	#[serde(rename = "save_as_template")]
+	SaveAsTemplate,
+	#[serde(rename = "send_test_alert")]
+	SendTestAlert,
 	#[serde(rename = "delete")]
 	Delete,
 }
@@ -29,12 +40,24 @@ enum Action {
 #[derive(serde::Deserialize)]
 struct UpdateMonitorAction {
 	cadence: String,
+	#[serde(default)]
+	cooldown_periods: String,
 	email: String,
 	metric: String,
+	#[serde(default)]
+	message_template: String,
+	#[serde(default)]
+	minimum_sample_size: String,
 	mode: String,
+	#[serde(default)]
+	notification_channel: String,
+	#[serde(default)]
+	require_confidence_interval: Option<String>,
 	threshold_lower: String,
 	threshold_upper: String,
 	title: String,
+	#[serde(default)]
+	warn_below_minimum_sample_size: Option<String>,
 	webhook: String,
 }
 
@@ -56,10 +79,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let repo_id: Id = match repo_id.parse() {
 		Ok(repo_id) => repo_id,
 		Err(_) => return Ok(not_found()),
@@ -87,7 +111,49 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 	let model_type = AlertModelType::from(model.inner());
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::Monitors).await?;
+	let notification_channels =
+		get_notification_channel_summaries_for_repo(&mut db, repo_id).await?;
+	let custom_metrics = get_custom_metrics(&mut db, repo_id).await?;
 	match action {
+		Action::SaveAsTemplate => {
+			let monitor = get_monitor(&mut db, Id::from_str(&monitor_id)?).await?;
+			let template = MonitorTemplate::from_monitor(&monitor);
+			create_monitor_template(&mut db, repo_id, &template, app.clock()).await?;
+			app.commit_transaction(db).await?;
+			let response = http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(
+					http::header::LOCATION,
+					app_path(format!("/repos/{}/edit", repo_id)),
+				)
+				.body(hyper::Body::empty())
+				.unwrap();
+			Ok(response)
+		}
+		Action::SendTestAlert => {
+			let monitor_id = Id::from_str(&monitor_id)?;
+			let monitor = get_monitor(&mut db, monitor_id).await?;
+			let evaluations = get_monitor_evaluations(&mut db, monitor_id).await?;
+			let test_alert_sends = app.send_test_alerts(&monitor, &mut db).await?;
+			app.commit_transaction(db).await?;
+			let page = Page {
+				monitor,
+				monitor_id: monitor_id.to_string(),
+				model_layout_info,
+				model_type,
+				notification_channels,
+				custom_metrics,
+				evaluations,
+				error: None,
+				test_alert_sends: Some(test_alert_sends),
+			};
+			let html = html(page);
+			let response = http::Response::builder()
+				.status(http::StatusCode::OK)
+				.body(hyper::Body::from(html))
+				.unwrap();
+			Ok(response)
+		}
 		Action::Delete => {
 			delete_monitor(&mut db, &monitor_id).await?;
 			db.commit().await?;
@@ -95,21 +161,28 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 				.status(http::StatusCode::SEE_OTHER)
 				.header(
 					http::header::LOCATION,
-					format!("/repos/{}/models/{}/monitors/", repo_id, model_id),
+					app_path(format!("/repos/{}/models/{}/monitors/", repo_id, model_id)),
 				)
 				.body(hyper::Body::empty())
 				.unwrap();
 			Ok(response)
 		}
 		Action::UpdateMonitor(um) => {
+			let evaluations = get_monitor_evaluations(&mut db, Id::from_str(&monitor_id)?).await?;
 			let UpdateMonitorAction {
 				cadence,
+				cooldown_periods,
 				email,
+				message_template,
 				metric,
+				minimum_sample_size,
 				mode,
+				notification_channel,
+				require_confidence_interval,
 				threshold_lower,
 				threshold_upper,
 				title,
+				warn_below_minimum_sample_size,
 				webhook,
 			} = um;
 			let metric = AlertMetric::from_str(&metric)?;
@@ -127,7 +200,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 							monitor_id,
 							model_layout_info,
 							model_type,
+							notification_channels,
+							custom_metrics,
+							evaluations,
 							error: Some("Received malformed webhook url.".to_owned()),
+							test_alert_sends: None,
 						};
 						let html = html(page);
 						let response = http::Response::builder()
@@ -138,6 +215,29 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 					}
 				}
 			}
+			match notification_channel_alert_method(&notification_channel, &notification_channels) {
+				Ok(Some(method)) => methods.push(method),
+				Ok(None) => {}
+				Err(error) => {
+					let page = Page {
+						monitor: get_monitor(&mut db, Id::from_str(&monitor_id)?).await?,
+						monitor_id,
+						model_layout_info,
+						model_type,
+						notification_channels,
+						custom_metrics,
+						evaluations,
+						error: Some(error.to_string()),
+						test_alert_sends: None,
+					};
+					let html = html(page);
+					let response = http::Response::builder()
+						.status(http::StatusCode::BAD_REQUEST)
+						.body(hyper::Body::from(html))
+						.unwrap();
+					return Ok(response);
+				}
+			}
 			let threshold_bounds = validate_threshold_bounds(threshold_lower, threshold_upper);
 			if threshold_bounds.is_none() {
 				let page = Page {
@@ -145,7 +245,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 					monitor_id,
 					model_layout_info,
 					model_type,
+					notification_channels,
+					custom_metrics,
+					evaluations,
 					error: Some("Must provide at least one threshold bound.".to_owned()),
+					test_alert_sends: None,
 				};
 				let html = html(page);
 				let response = http::Response::builder()
@@ -163,6 +267,16 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 				difference_upper: variance_upper,
 			};
 			let cadence = MonitorCadence::from_str(&cadence)?;
+			let (minimum_sample_size, cooldown_periods) =
+				extract_data_requirements(&minimum_sample_size, &cooldown_periods)?;
+			let warn_below_minimum_sample_size =
+				warn_below_minimum_sample_size.as_deref() == Some("on");
+			let require_confidence_interval = require_confidence_interval.as_deref() == Some("on");
+			let message_template = if message_template.is_empty() {
+				None
+			} else {
+				Some(message_template)
+			};
 			let args = modelfox_app_core::monitor_checker::UpdateMonitorArgs {
 				db: &mut db,
 				monitor_id: Id::from_str(&monitor_id)?,
@@ -171,6 +285,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 				model_id,
 				threshold,
 				title: &title,
+				minimum_sample_size,
+				warn_below_minimum_sample_size,
+				cooldown_periods,
+				message_template: message_template.clone(),
+				require_confidence_interval,
 			};
 			let result = context.app.update_monitor(args).await;
 			if result.is_err() {
@@ -182,11 +301,21 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 						model_id,
 						threshold,
 						title,
+						minimum_sample_size,
+						warn_below_minimum_sample_size,
+						cooldown_periods,
+						message_template,
+						require_confidence_interval,
+						enabled: true,
 					},
 					monitor_id,
 					model_layout_info,
 					model_type,
+					notification_channels,
+					custom_metrics,
+					evaluations,
 					error: Some("There was an error editing your monitor.".to_owned()),
+					test_alert_sends: None,
 				};
 				let html = html(page);
 				let response = http::Response::builder()
@@ -200,7 +329,7 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 				.status(http::StatusCode::SEE_OTHER)
 				.header(
 					http::header::LOCATION,
-					format!("/repos/{}/models/{}/monitors/", repo_id, model_id),
+					app_path(format!("/repos/{}/models/{}/monitors/", repo_id, model_id)),
 				)
 				.body(hyper::Body::empty())
 				.unwrap();
@@ -1,13 +1,13 @@
-use crate::page::{MonitorsTable, MonitorsTableRow, Page};
+use crate::page::{MonitorStatus, MonitorsTable, MonitorsTableRow, Page};
 use anyhow::{bail, Result};
 use chrono::prelude::*;
 use chrono_tz::Tz;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
-	monitor::Monitor,
+	monitor::{get_latest_monitor_evaluation, Monitor},
 	path_components,
-	timezone::get_timezone,
+	timezone::get_timezone_for_model,
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
@@ -19,7 +19,6 @@ use std::sync::Arc;
 pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
 	let context = request.extensions().get::<Arc<Context>>().unwrap().clone();
 	let app = &context.app;
-	let timezone = get_timezone(request);
 	let model_id = if let ["repos", _, "models", model_id, "monitors", ""] =
 		path_components(request).as_slice()
 	{
@@ -31,10 +30,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -42,14 +42,14 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::Monitors).await?;
 	let rows = sqlx::query(
 		"
 			select
 				id,
-				data,
-				last_checked
+				data
 			from monitors
 			where model_id = $1
 		",
@@ -57,26 +57,36 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	.bind(model_id.to_string())
 	.fetch_all(&mut db)
 	.await?;
+	let format_date = |timestamp: i64| {
+		let date: DateTime<Tz> = Utc.timestamp(timestamp, 0).with_timezone(&timezone);
+		date.to_string()
+	};
 	let monitors_table = if !rows.is_empty() {
-		let rows = rows
-			.iter()
-			.map(|row| {
-				let id: String = row.get(0);
-				let id: Id = id.parse().unwrap();
-				let monitor: String = row.get(1);
-				let monitor: Monitor = serde_json::from_str(&monitor).unwrap();
-				let name = monitor.title;
-				let last_updated: i64 = row.get(2);
-				let last_updated: DateTime<Tz> =
-					Utc.timestamp(last_updated, 0).with_timezone(&timezone);
-				MonitorsTableRow {
-					id: id.to_string(),
-					name,
-					last_updated: last_updated.to_string(),
-				}
-			})
-			.collect();
-		let monitors_table = MonitorsTable { rows };
+		let mut table_rows = Vec::with_capacity(rows.len());
+		for row in rows.iter() {
+			let id: String = row.get(0);
+			let id: Id = id.parse().unwrap();
+			let monitor: String = row.get(1);
+			let monitor: Monitor = serde_json::from_str(&monitor).unwrap();
+			let evaluation = get_latest_monitor_evaluation(&mut db, id).await?;
+			let status = match &evaluation {
+				_ if !monitor.enabled => MonitorStatus::Disabled,
+				None => MonitorStatus::NotEvaluated,
+				Some(evaluation) if evaluation.exceeded_thresholds() => MonitorStatus::Alerting,
+				Some(_) => MonitorStatus::Ok,
+			};
+			table_rows.push(MonitorsTableRow {
+				id: id.to_string(),
+				name: monitor.title,
+				enabled: monitor.enabled,
+				status,
+				last_evaluated: evaluation
+					.as_ref()
+					.map(|evaluation| format_date(evaluation.date)),
+				last_value: evaluation.map(|evaluation| evaluation.production_value),
+			});
+		}
+		let monitors_table = MonitorsTable { rows: table_rows };
 		Some(monitors_table)
 	} else {
 		None
@@ -1,5 +1,6 @@
 mod get;
 mod page;
+mod post;
 
 use futures::FutureExt;
 use modelfox_app_core::error::method_not_allowed;
@@ -7,6 +8,7 @@ use modelfox_app_core::error::method_not_allowed;
 pub fn init() -> sunfish::Route {
 	sunfish::Route::new_dynamic(|request| match *request.method() {
 		http::Method::GET => self::get::get(request).boxed(),
+		http::Method::POST => self::post::post(request).boxed(),
 		_ => async { Ok(method_not_allowed()) }.boxed(),
 	})
 }
@@ -49,23 +49,72 @@ pub struct MonitorsTable {
 pub struct MonitorsTableRow {
 	pub id: String,
 	pub name: String,
-	pub last_updated: String,
+	pub enabled: bool,
+	pub status: MonitorStatus,
+	pub last_evaluated: Option<String>,
+	pub last_value: Option<f32>,
 }
 
+pub enum MonitorStatus {
+	Disabled,
+	NotEvaluated,
+	Ok,
+	Alerting,
+}
+
+/// The checkboxes in each row of the table belong to this form, so the selected monitors can be enabled or disabled at once.
+const BULK_TOGGLE_FORM_ID: &str = "monitors_bulk_toggle";
+
 impl Component for MonitorsTable {
 	fn into_node(self) -> Node {
-		ui::Table::new()
+		let bulk_toggle_form = ui::Form::new()
+			.id(BULK_TOGGLE_FORM_ID.to_owned())
+			.post(true)
+			.child(
+				ui::SelectField::new()
+					.label("With Selected Monitors".to_owned())
+					.name("action".to_owned())
+					.options(vec![
+						ui::SelectFieldOption {
+							text: "Enable".to_owned(),
+							value: "enable".to_owned(),
+						},
+						ui::SelectFieldOption {
+							text: "Disable".to_owned(),
+							value: "disable".to_owned(),
+						},
+					]),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.child("Apply"),
+			);
+		let table = ui::Table::new()
 			.width("100%".to_owned())
 			.child(
 				ui::TableHeader::new().child(
 					ui::TableRow::new()
+						.child(ui::TableHeaderCell::new())
 						.child(ui::TableHeaderCell::new().child("Name"))
-						.child(ui::TableHeaderCell::new().child("Uploaded")),
+						.child(ui::TableHeaderCell::new().child("Status"))
+						.child(ui::TableHeaderCell::new().child("Last Evaluated"))
+						.child(ui::TableHeaderCell::new().child("Last Value"))
+						.child(ui::TableHeaderCell::new()),
 				),
 			)
 			.child(
 				ui::TableBody::new().children(self.rows.into_iter().map(|row| {
 					ui::TableRow::new()
+						.child(
+							ui::TableCell::new().child(
+								input()
+									.attribute("form", BULK_TOGGLE_FORM_ID)
+									.attribute("name", "monitor_id")
+									.attribute("type", "checkbox")
+									.attribute("value", row.id.clone()),
+							),
+						)
 						.child(
 							ui::TableCell::new().child(
 								ui::Link::new()
@@ -73,8 +122,82 @@ impl Component for MonitorsTable {
 									.child(row.name),
 							),
 						)
-						.child(ui::TableCell::new().child(row.last_updated))
+						.child(
+							ui::TableCell::new().child(MonitorStatusToken { status: row.status }),
+						)
+						.child(
+							ui::TableCell::new()
+								.child(row.last_evaluated.unwrap_or_else(|| "-".to_owned())),
+						)
+						.child(
+							ui::TableCell::new().child(
+								row.last_value
+									.map(ui::format_float)
+									.unwrap_or_else(|| "-".to_owned()),
+							),
+						)
+						.child(ui::TableCell::new().child(ToggleMonitorForm {
+							monitor_id: row.id,
+							enabled: row.enabled,
+						}))
 				})),
+			);
+		ui::S2::new()
+			.child(bulk_toggle_form)
+			.child(table)
+			.into_node()
+	}
+}
+
+struct MonitorStatusToken {
+	status: MonitorStatus,
+}
+
+impl Component for MonitorStatusToken {
+	fn into_node(self) -> Node {
+		let (color, text) = match self.status {
+			MonitorStatus::Disabled => (ui::colors::GRAY, "DISABLED"),
+			MonitorStatus::NotEvaluated => (ui::colors::GRAY, "NOT EVALUATED"),
+			MonitorStatus::Ok => (ui::colors::GREEN, "OK"),
+			MonitorStatus::Alerting => (ui::colors::RED, "ALERTING"),
+		};
+		ui::Token::new()
+			.color(color.to_owned())
+			.child(text)
+			.into_node()
+	}
+}
+
+struct ToggleMonitorForm {
+	monitor_id: String,
+	enabled: bool,
+}
+
+impl Component for ToggleMonitorForm {
+	fn into_node(self) -> Node {
+		let (action, text) = if self.enabled {
+			("disable", "Disable")
+		} else {
+			("enable", "Enable")
+		};
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", action),
+			)
+			.child(
+				input()
+					.attribute("name", "monitor_id")
+					.attribute("type", "hidden")
+					.attribute("value", self.monitor_id),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.child(text),
 			)
 			.into_node()
 	}
@@ -0,0 +1,79 @@
+use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	monitor::set_monitors_enabled,
+	path_components,
+	user::{authorize_user, authorize_user_for_model, authorize_user_for_repo},
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = request.extensions().get::<Arc<Context>>().unwrap().clone();
+	let app = &context.app;
+	let (repo_id, model_id) = if let ["repos", repo_id, "models", model_id, "monitors", ""] =
+		*path_components(request).as_slice()
+	{
+		(repo_id.to_owned(), model_id.to_owned())
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let repo_id: Id = match repo_id.parse() {
+		Ok(repo_id) => repo_id,
+		Err(_) => return Ok(not_found()),
+	};
+	if !authorize_user_for_repo(&mut db, &user, repo_id).await? {
+		return Ok(not_found());
+	}
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let data = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(data) => data,
+		Err(_) => return Ok(bad_request()),
+	};
+	// The form has a `monitor_id` field for each selected monitor, which `serde_urlencoded` cannot deserialize, so the fields are read one at a time.
+	let mut enabled = None;
+	let mut monitor_ids = Vec::new();
+	for (key, value) in url::form_urlencoded::parse(&data) {
+		match (key.as_ref(), value.as_ref()) {
+			("action", "enable") => enabled = Some(true),
+			("action", "disable") => enabled = Some(false),
+			("monitor_id", monitor_id) => match monitor_id.parse() {
+				Ok(monitor_id) => monitor_ids.push(monitor_id),
+				Err(_) => return Ok(bad_request()),
+			},
+			_ => return Ok(bad_request()),
+		}
+	}
+	let enabled = match enabled {
+		Some(enabled) => enabled,
+		None => return Ok(bad_request()),
+	};
+	set_monitors_enabled(&mut db, model_id, &monitor_ids, enabled).await?;
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::SEE_OTHER)
+		.header(
+			http::header::LOCATION,
+			app_path(format!("/repos/{}/models/{}/monitors/", repo_id, model_id)),
+		)
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
@@ -2,9 +2,11 @@ use crate::page::Page;
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
+	custom_metrics::get_custom_metrics,
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model_bytes,
 	monitor::AlertModelType,
+	notification_channels::get_notification_channel_summaries_for_repo,
 	path_components,
 	user::{authorize_user, authorize_user_for_model, authorize_user_for_repo},
 };
@@ -27,10 +29,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let repo_id: Id = match repo_id.parse() {
 		Ok(repo_id) => repo_id,
 		Err(_) => return Ok(not_found()),
@@ -50,9 +53,14 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let model_type = AlertModelType::from(model.inner());
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::Monitors).await?;
+	let notification_channels =
+		get_notification_channel_summaries_for_repo(&mut db, repo_id).await?;
+	let custom_metrics = get_custom_metrics(&mut db, repo_id).await?;
 	let page = Page {
 		model_layout_info,
 		model_type,
+		notification_channels,
+		custom_metrics,
 		error: None,
 	};
 	let html = html(page);
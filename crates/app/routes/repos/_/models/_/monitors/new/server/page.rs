@@ -1,4 +1,7 @@
-use modelfox_app_core::monitor::AlertModelType;
+use modelfox_app_core::{
+	alert_template::ALERT_TEMPLATE_VARIABLES, custom_metrics::CustomMetric,
+	monitor::AlertModelType, notification_channels::NotificationChannelSummary,
+};
 use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
@@ -10,12 +13,14 @@ use pinwheel::prelude::*;
 pub struct Page {
 	pub model_layout_info: ModelLayoutInfo,
 	pub model_type: AlertModelType,
+	pub notification_channels: Vec<NotificationChannelSummary>,
+	pub custom_metrics: Vec<CustomMetric>,
 	pub error: Option<String>,
 }
 
 impl Component for Page {
 	fn into_node(self) -> Node {
-		let metric_options = match self.model_type {
+		let mut metric_options = match self.model_type {
 			AlertModelType::Classifier => vec![ui::SelectFieldOption {
 				text: "Accuracy".to_owned(),
 				value: "accuracy".to_owned(),
@@ -29,8 +34,38 @@ impl Component for Page {
 					text: "Root Mean Squared Error".to_owned(),
 					value: "rmse".to_owned(),
 				},
+				ui::SelectFieldOption {
+					text: "Mean Absolute Error".to_owned(),
+					value: "mae".to_owned(),
+				},
+				ui::SelectFieldOption {
+					text: "Mean Residual".to_owned(),
+					value: "mean_residual".to_owned(),
+				},
+				ui::SelectFieldOption {
+					text: "Tolerance Accuracy".to_owned(),
+					value: "tolerance_accuracy".to_owned(),
+				},
 			],
 		};
+		metric_options.push(ui::SelectFieldOption {
+			text: "Out of Range Fraction".to_owned(),
+			value: "out_of_range".to_owned(),
+		});
+		metric_options.push(ui::SelectFieldOption {
+			text: "Unseen Token Fraction".to_owned(),
+			value: "unseen_tokens".to_owned(),
+		});
+		metric_options.push(ui::SelectFieldOption {
+			text: "Model Age (Days)".to_owned(),
+			value: "model_age".to_owned(),
+		});
+		metric_options.extend(self.custom_metrics.into_iter().map(|custom_metric| {
+			ui::SelectFieldOption {
+				text: custom_metric.name,
+				value: format!("custom:{}", custom_metric.id),
+			}
+		}));
 		Document::new()
 			.client("modelfox_app_new_monitor_client")
 			.child(
@@ -107,6 +142,30 @@ impl Component for Page {
 											},
 										]),
 								)
+								.child(
+									ui::TextField::new()
+										.label("Minimum Sample Size (Optional)".to_string())
+										.name("minimum_sample_size".to_string())
+										.required(false),
+								)
+								.child(
+									ui::CheckboxField::new()
+										.label("Warn When Below Minimum Sample Size".to_owned())
+										.name("warn_below_minimum_sample_size".to_owned()),
+								)
+								.child(
+									ui::TextField::new()
+										.label("Cooldown Periods (Optional)".to_string())
+										.name("cooldown_periods".to_string())
+										.required(false),
+								)
+								.child(
+									ui::CheckboxField::new()
+										.label(
+											"Require Confidence Interval Past Threshold".to_owned(),
+										)
+										.name("require_confidence_interval".to_owned()),
+								)
 								.child(
 									ui::TextField::new()
 										.label("Title (Optional)".to_string())
@@ -125,6 +184,8 @@ impl Component for Page {
 										.name("webhook".to_string())
 										.required(false),
 								)
+								.child(notification_channel_field(self.notification_channels, None))
+								.child(message_template_field(None))
 								.child(
 									ui::Button::new()
 										.button_type(ui::ButtonType::Submit)
@@ -136,3 +197,60 @@ impl Component for Page {
 			.into_node()
 	}
 }
+
+/// Render a select field for the organization's notification channels, or nothing if it has none.
+fn notification_channel_field(
+	notification_channels: Vec<NotificationChannelSummary>,
+	value: Option<String>,
+) -> Option<ui::SelectField> {
+	if notification_channels.is_empty() {
+		return None;
+	}
+	let options = std::iter::once(ui::SelectFieldOption {
+		text: "None".to_owned(),
+		value: String::new(),
+	})
+	.chain(
+		notification_channels
+			.into_iter()
+			.map(|notification_channel| ui::SelectFieldOption {
+				text: notification_channel.name,
+				value: notification_channel.id.to_string(),
+			}),
+	)
+	.collect();
+	Some(
+		ui::SelectField::new()
+			.label("Notification Channel (Optional)".to_owned())
+			.name("notification_channel".to_owned())
+			.required(false)
+			.options(options)
+			.value(value.unwrap_or_default()),
+	)
+}
+
+/// Render a textarea for the monitor's message template along with the variables it may reference.
+fn message_template_field(value: Option<String>) -> Node {
+	let variables = ALERT_TEMPLATE_VARIABLES
+		.iter()
+		.map(|variable| format!("{{{{{}}}}}", variable))
+		.collect::<Vec<_>>()
+		.join(", ");
+	fragment()
+		.child(
+			ui::FieldLabel::new()
+				.child("Message Template (Optional)")
+				.child(
+					textarea()
+						.class("form-text-field")
+						.attribute("name", "message_template")
+						.attribute("rows", 4)
+						.child(value),
+				),
+		)
+		.child(ui::P::new().child(format!(
+			"Customize the message sent for this monitor's alerts. The available variables are {}.",
+			variables
+		)))
+		.into_node()
+}
@@ -1,15 +1,20 @@
 use crate::page::Page;
 use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	alert::{AlertMethod, AlertMetric},
+	custom_metrics::get_custom_metrics,
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model_bytes,
 	monitor::{
-		extract_threshold_bounds, validate_threshold_bounds, AlertModelType, MonitorCadence,
-		MonitorThreshold, MonitorThresholdMode,
+		extract_data_requirements, extract_threshold_bounds, validate_threshold_bounds,
+		AlertModelType, MonitorCadence, MonitorThreshold, MonitorThresholdMode,
 	},
 	monitor_checker::CreateMonitorArgs,
+	notification_channels::{
+		get_notification_channel_summaries_for_repo, notification_channel_alert_method,
+	},
 	path_components,
 	user::{authorize_user, authorize_user_for_model, authorize_user_for_repo},
 };
@@ -21,12 +26,24 @@ use std::{str::FromStr, sync::Arc};
 #[derive(serde::Deserialize)]
 struct Action {
 	cadence: String,
+	#[serde(default)]
+	cooldown_periods: String,
 	email: String,
 	metric: String,
+	#[serde(default)]
+	message_template: String,
+	#[serde(default)]
+	minimum_sample_size: String,
 	mode: String,
+	#[serde(default)]
+	notification_channel: String,
+	#[serde(default)]
+	require_confidence_interval: Option<String>,
 	threshold_lower: String,
 	threshold_upper: String,
 	title: String,
+	#[serde(default)]
+	warn_below_minimum_sample_size: Option<String>,
 	webhook: String,
 }
 
@@ -44,10 +61,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let repo_id: Id = match repo_id.parse() {
 		Ok(repo_id) => repo_id,
 		Err(_) => return Ok(not_found()),
@@ -75,14 +93,23 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 	let model_type = AlertModelType::from(model.inner());
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::Monitors).await?;
+	let notification_channels =
+		get_notification_channel_summaries_for_repo(&mut db, repo_id).await?;
+	let custom_metrics = get_custom_metrics(&mut db, repo_id).await?;
 	let Action {
 		cadence,
+		cooldown_periods,
 		email,
+		message_template,
 		metric,
+		minimum_sample_size,
 		mode,
+		notification_channel,
+		require_confidence_interval,
 		threshold_lower,
 		threshold_upper,
 		title,
+		warn_below_minimum_sample_size,
 		webhook,
 	} = action;
 	let metric = AlertMetric::from_str(&metric)?;
@@ -98,6 +125,8 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 				let page = Page {
 					model_layout_info,
 					model_type,
+					notification_channels,
+					custom_metrics,
 					error: Some("Received malformed webhook url.".to_owned()),
 				};
 				let html = html(page);
@@ -109,11 +138,32 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 			}
 		}
 	}
+	match notification_channel_alert_method(&notification_channel, &notification_channels) {
+		Ok(Some(method)) => methods.push(method),
+		Ok(None) => {}
+		Err(error) => {
+			let page = Page {
+				model_layout_info,
+				model_type,
+				notification_channels,
+				custom_metrics,
+				error: Some(error.to_string()),
+			};
+			let html = html(page);
+			let response = http::Response::builder()
+				.status(http::StatusCode::BAD_REQUEST)
+				.body(hyper::Body::from(html))
+				.unwrap();
+			return Ok(response);
+		}
+	}
 	let threshold_bounds = validate_threshold_bounds(threshold_lower, threshold_upper);
 	if threshold_bounds.is_none() {
 		let page = Page {
 			model_layout_info,
 			model_type,
+			notification_channels,
+			custom_metrics,
 			error: Some("Must provide at least one threshold bound.".to_owned()),
 		};
 		let html = html(page);
@@ -130,6 +180,8 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		difference_lower,
 		difference_upper,
 	};
+	let (minimum_sample_size, cooldown_periods) =
+		extract_data_requirements(&minimum_sample_size, &cooldown_periods)?;
 	let args = CreateMonitorArgs {
 		db: &mut db,
 		cadence: MonitorCadence::from_str(&cadence)?,
@@ -137,12 +189,23 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		model_id,
 		threshold,
 		title: &title,
+		minimum_sample_size,
+		warn_below_minimum_sample_size: warn_below_minimum_sample_size.as_deref() == Some("on"),
+		cooldown_periods,
+		message_template: if message_template.is_empty() {
+			None
+		} else {
+			Some(message_template)
+		},
+		require_confidence_interval: require_confidence_interval.as_deref() == Some("on"),
 	};
 	let result = app.create_monitor(args).await;
 	if result.is_err() {
 		let page = Page {
 			model_layout_info,
 			model_type,
+			notification_channels,
+			custom_metrics,
 			error: Some(result.err().unwrap().to_string()),
 		};
 		let html = html(page);
@@ -157,7 +220,7 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		.status(http::StatusCode::SEE_OTHER)
 		.header(
 			http::header::LOCATION,
-			format!("/repos/{}/models/{}/monitors/", repo_id, model_id),
+			app_path(format!("/repos/{}/models/{}/monitors/", repo_id, model_id)),
 		)
 		.body(hyper::Body::empty())
 		.unwrap();
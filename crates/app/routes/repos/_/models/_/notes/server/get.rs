@@ -0,0 +1,92 @@
+use crate::page::{Approval, Note, Page};
+use anyhow::{bail, Result};
+use chrono::prelude::*;
+use chrono_tz::Tz;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model_reviews::{can_approve_model, get_model_approval, get_model_notes},
+	path_components,
+	timezone::get_timezone_for_model,
+	user::{authorize_user, authorize_user_for_model, User},
+};
+use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
+use modelfox_id::Id;
+use pinwheel::prelude::*;
+use std::sync::Arc;
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id =
+		if let ["repos", _, "models", model_id, "notes"] = *path_components(request).as_slice() {
+			model_id.to_owned()
+		} else {
+			bail!("unexpected path");
+		};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
+	let model_layout_info = model_layout_info(&mut db, app, model_id, ModelNavItem::Notes).await?;
+	let can_approve = can_approve_model(&mut db, &user, model_id).await?;
+	let approval = get_model_approval(&mut db, model_id)
+		.await?
+		.map(|approval| Approval {
+			approver: approval
+				.approver_email
+				.unwrap_or_else(|| "an administrator".to_owned()),
+			approved_at: format_timestamp(approval.created_at, &timezone),
+		});
+	let notes = get_model_notes(&mut db, model_id)
+		.await?
+		.into_iter()
+		.map(|note| {
+			// Users can delete their own notes. The root user can delete any note.
+			let can_delete = match &user {
+				User::Root => true,
+				User::Normal(user) => note.user_id == Some(user.id),
+			};
+			Note {
+				id: note.id.to_string(),
+				author: note
+					.author_email
+					.unwrap_or_else(|| "an administrator".to_owned()),
+				can_delete,
+				content: note.content,
+				created_at: format_timestamp(note.created_at, &timezone),
+			}
+		})
+		.collect();
+	let page = Page {
+		approval,
+		can_approve,
+		model_layout_info,
+		notes,
+	};
+	app.commit_transaction(db).await?;
+	let html = html(page);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
+
+fn format_timestamp(timestamp: i64, timezone: &Tz) -> String {
+	let date: DateTime<Tz> = Utc.timestamp(timestamp, 0).with_timezone(timezone);
+	date.to_string()
+}
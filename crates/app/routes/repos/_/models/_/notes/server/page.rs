@@ -0,0 +1,205 @@
+use modelfox_app_layouts::{
+	document::Document,
+	model_layout::{ModelLayout, ModelLayoutInfo},
+};
+use modelfox_app_ui::page_heading::PageHeading;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct Page {
+	pub approval: Option<Approval>,
+	pub can_approve: bool,
+	pub model_layout_info: ModelLayoutInfo,
+	pub notes: Vec<Note>,
+}
+
+pub struct Approval {
+	pub approver: String,
+	pub approved_at: String,
+}
+
+pub struct Note {
+	pub id: String,
+	pub author: String,
+	pub can_delete: bool,
+	pub content: String,
+	pub created_at: String,
+}
+
+impl Component for Page {
+	fn into_node(self) -> Node {
+		Document::new()
+			.child(
+				ModelLayout::new(self.model_layout_info).child(
+					ui::S1::new()
+						.child(PageHeading::new().child(ui::H1::new("Notes")))
+						.child(ApprovalSection {
+							approval: self.approval,
+							can_approve: self.can_approve,
+						})
+						.child(NotesSection { notes: self.notes })
+						.child(AddNoteSection),
+				),
+			)
+			.into_node()
+	}
+}
+
+struct ApprovalSection {
+	approval: Option<Approval>,
+	can_approve: bool,
+}
+
+impl Component for ApprovalSection {
+	fn into_node(self) -> Node {
+		let (status, action, button_text) = match self.approval {
+			Some(approval) => (
+				format!(
+					"Approved for production by {} on {}.",
+					approval.approver, approval.approved_at
+				),
+				"revoke_approval",
+				"Revoke Approval",
+			),
+			None => (
+				"This model has not been approved for production.".to_owned(),
+				"approve",
+				"Approve for Production",
+			),
+		};
+		let form = if self.can_approve {
+			Some(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", action),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child(button_text),
+					),
+			)
+		} else {
+			None
+		};
+		let approver_message = if self.can_approve {
+			None
+		} else {
+			Some(ui::P::new().child(
+				"Only the owner of this repo or an admin of the organization that owns it can approve models.",
+			))
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Approval"))
+			.child(ui::P::new().child(status))
+			.child(approver_message)
+			.child(form)
+			.into_node()
+	}
+}
+
+struct NotesSection {
+	notes: Vec<Note>,
+}
+
+impl Component for NotesSection {
+	fn into_node(self) -> Node {
+		let notes_or_empty_message = if self.notes.is_empty() {
+			ui::Card::new()
+				.child(ui::P::new().child("There are no notes on this model yet."))
+				.into_node()
+		} else {
+			fragment()
+				.children(self.notes.into_iter().map(|note| NoteCard { note }))
+				.into_node()
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Notes"))
+			.child(notes_or_empty_message)
+			.into_node()
+	}
+}
+
+struct NoteCard {
+	note: Note,
+}
+
+impl Component for NoteCard {
+	fn into_node(self) -> Node {
+		let note = self.note;
+		let delete_form = if note.can_delete {
+			Some(
+				ui::Form::new()
+					.post(true)
+					.onsubmit("return confirm(\"Are you sure?\")".to_owned())
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "delete_note"),
+					)
+					.child(
+						input()
+							.attribute("name", "note_id")
+							.attribute("type", "hidden")
+							.attribute("value", note.id),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.color(ui::colors::RED.to_owned())
+							.child("Delete"),
+					),
+			)
+		} else {
+			None
+		};
+		ui::Card::new()
+			.child(
+				ui::SpaceBetween::new()
+					.child(ui::P::new().child(format!("{} on {}", note.author, note.created_at)))
+					.child(delete_form),
+			)
+			// Notes are written by users, so render them without raw html.
+			.child(ui::Markdown::new(note.content).untrusted(true))
+			.into_node()
+	}
+}
+
+struct AddNoteSection;
+
+impl Component for AddNoteSection {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("Add a Note"))
+			.child(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "add_note"),
+					)
+					.child(
+						ui::FieldLabel::new().child("Note (Markdown)").child(
+							textarea()
+								.class("form-text-field")
+								.attribute("name", "content")
+								.attribute("required", true)
+								.attribute("rows", 6),
+						),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Add Note"),
+					),
+			)
+			.into_node()
+	}
+}
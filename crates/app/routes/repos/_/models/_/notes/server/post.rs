@@ -0,0 +1,121 @@
+use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model_reviews::{
+		approve_model, can_approve_model, create_model_note, delete_model_note, get_model_notes,
+		revoke_model_approval,
+	},
+	path_components,
+	user::{authorize_user, authorize_user_for_model, User},
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "action")]
+enum Action {
+	#[serde(rename = "add_note")]
+	AddNote(AddNoteAction),
+	#[serde(rename = "delete_note")]
+	DeleteNote(DeleteNoteAction),
+	#[serde(rename = "approve")]
+	Approve,
+	#[serde(rename = "revoke_approval")]
+	RevokeApproval,
+}
+
+#[derive(serde::Deserialize)]
+struct AddNoteAction {
+	content: String,
+}
+
+#[derive(serde::Deserialize)]
+struct DeleteNoteAction {
+	note_id: Id,
+}
+
+pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let (repo_id, model_id) = if let ["repos", repo_id, "models", model_id, "notes"] =
+		*path_components(request).as_slice()
+	{
+		(repo_id.to_owned(), model_id.to_owned())
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let data = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(data) => data,
+		Err(_) => return Ok(bad_request()),
+	};
+	let action: Action = match serde_urlencoded::from_bytes(&data) {
+		Ok(action) => action,
+		Err(_) => return Ok(bad_request()),
+	};
+	match action {
+		Action::AddNote(AddNoteAction { content }) => {
+			if content.trim().is_empty() {
+				return Ok(bad_request());
+			}
+			create_model_note(&mut db, model_id, &user, &content, app.clock()).await?;
+		}
+		Action::DeleteNote(DeleteNoteAction { note_id }) => {
+			let note = get_model_notes(&mut db, model_id)
+				.await?
+				.into_iter()
+				.find(|note| note.id == note_id);
+			let note = match note {
+				Some(note) => note,
+				None => return Ok(not_found()),
+			};
+			let can_delete = match &user {
+				User::Root => true,
+				User::Normal(user) => note.user_id == Some(user.id),
+			};
+			if !can_delete {
+				return Ok(not_found());
+			}
+			delete_model_note(&mut db, model_id, note_id).await?;
+		}
+		Action::Approve => {
+			if !can_approve_model(&mut db, &user, model_id).await? {
+				return Ok(not_found());
+			}
+			approve_model(&mut db, model_id, &user, app.clock()).await?;
+		}
+		Action::RevokeApproval => {
+			if !can_approve_model(&mut db, &user, model_id).await? {
+				return Ok(not_found());
+			}
+			revoke_model_approval(&mut db, model_id).await?;
+		}
+	}
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::SEE_OTHER)
+		.header(
+			http::header::LOCATION,
+			app_path(format!("/repos/{}/models/{}/notes", repo_id, model_id)),
+		)
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
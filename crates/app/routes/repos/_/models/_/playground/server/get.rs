@@ -3,7 +3,7 @@ use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
-	model::get_model_bytes,
+	model::{get_model_bytes, get_model_class_thresholds, get_model_decision_threshold},
 	path_components,
 	user::{authorize_user, authorize_user_for_model},
 };
@@ -44,10 +44,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -57,9 +58,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	}
 	let bytes = get_model_bytes(app.storage(), model_id).await?;
 	let model = modelfox_model::from_bytes(&bytes)?;
+	let decision_threshold = get_model_decision_threshold(&mut db, model_id).await?;
+	let class_thresholds = get_model_class_thresholds(&mut db, model_id).await?;
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::Playground).await?;
-	let inner = compute_inner(model, search_params);
+	let inner = compute_inner(model, search_params, decision_threshold, class_thresholds);
 	let page = Page {
 		model_layout_info,
 		inner,
@@ -76,10 +79,17 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 fn compute_inner(
 	model: modelfox_model::ModelReader,
 	search_params: Option<BTreeMap<String, String>>,
+	decision_threshold: Option<f32>,
+	class_thresholds: BTreeMap<String, f32>,
 ) -> Inner {
 	let input = predict_input_from_search_params(search_params);
 	if let Some(input) = input {
-		Inner::Output(compute_predict_output(model, input))
+		Inner::Output(compute_predict_output(
+			model,
+			input,
+			decision_threshold,
+			class_thresholds,
+		))
 	} else {
 		Inner::Form(compute_form(model, &input))
 	}
@@ -88,12 +98,17 @@ fn compute_inner(
 fn compute_predict_output(
 	model: modelfox_model::ModelReader,
 	input: modelfox_core::predict::PredictInput,
+	decision_threshold: Option<f32>,
+	class_thresholds: BTreeMap<String, f32>,
 ) -> PredictOutput {
 	let input_table = compute_input_table(model, &input);
 	let predict_model = modelfox_core::predict::Model::from(model);
+	let default_options = PredictOptions::default();
 	let options = PredictOptions {
 		compute_feature_contributions: true,
-		..Default::default()
+		threshold: decision_threshold.unwrap_or(default_options.threshold),
+		class_thresholds,
+		..default_options
 	};
 	let mut output = modelfox_core::predict::predict(&predict_model, &[input], &options);
 	let output = output.remove(0);
@@ -248,6 +263,18 @@ fn compute_form(
 					.into();
 				Field::Text(TextField { name, value })
 			}
+			// Datetime values are entered as text and parsed the same way as the training data.
+			modelfox_model::ColumnStatsReader::DateTimeColumn(column_stats) => {
+				let column_stats = column_stats.read();
+				let name = column_stats.column_name().to_owned();
+				let value = input
+					.as_ref()
+					.and_then(|s| s.0.get(&name))
+					.cloned()
+					.unwrap_or_else(|| PredictInputValue::String("".to_owned()))
+					.into();
+				Field::Text(TextField { name, value })
+			}
 		})
 		.collect();
 	Form { fields }
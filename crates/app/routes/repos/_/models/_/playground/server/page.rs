@@ -193,6 +193,7 @@ impl Component for EnumField {
 					label: label.to_owned(),
 					x: index.to_f64().unwrap(),
 					y: Some(value.to_f64().unwrap()),
+					error: None,
 				})
 				.collect::<Vec<_>>(),
 			title: Some("histogram".to_owned()),
@@ -9,10 +9,10 @@ use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model_bytes,
 	path_components,
-	timezone::get_timezone,
+	timezone::get_timezone_for_model,
 	user::{authorize_user, authorize_user_for_model},
 };
-use modelfox_app_date_window::{get_date_window_and_interval, DateWindow};
+use modelfox_app_date_window::{get_date_window_and_interval, DateWindow, DateWindowInterval};
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
 use modelfox_app_production_metrics::{
 	get_production_metrics, GetProductionMetricsOutput, ProductionPredictionMetricsOutput,
@@ -37,6 +37,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	#[derive(serde::Deserialize, Default)]
 	struct SearchParams {
 		date_window: Option<DateWindow>,
+		date_window_interval: Option<DateWindowInterval>,
 		class: Option<String>,
 	}
 	let search_params: Option<SearchParams> = if let Some(query) = request.uri().query() {
@@ -47,19 +48,23 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let date_window = search_params
 		.as_ref()
 		.and_then(|search_params| search_params.date_window);
-	let (date_window, date_window_interval) = match get_date_window_and_interval(&date_window) {
-		Some((date_window, date_window_interval)) => (date_window, date_window_interval),
-		None => return Ok(bad_request()),
-	};
-	let timezone = get_timezone(request);
+	let date_window_interval = search_params
+		.as_ref()
+		.and_then(|search_params| search_params.date_window_interval);
+	let (date_window, date_window_interval) =
+		match get_date_window_and_interval(&date_window, &date_window_interval) {
+			Some((date_window, date_window_interval)) => (date_window, date_window_interval),
+			None => return Ok(bad_request()),
+		};
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -67,12 +72,26 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
 	let bytes = get_model_bytes(app.storage(), model_id).await?;
 	let model = modelfox_model::from_bytes(&bytes)?;
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::ProductionMetrics).await?;
-	let production_metrics =
-		get_production_metrics(&mut db, model, date_window, date_window_interval, timezone).await?;
+	let mut read_db = match app.begin_read_transaction().await {
+		Ok(read_db) => read_db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let production_metrics = get_production_metrics(
+		&mut read_db,
+		model,
+		date_window,
+		date_window_interval,
+		None,
+		1,
+		timezone,
+	)
+	.await?;
+	app.commit_transaction(read_db).await?;
 	let model = match model.inner() {
 		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
 			multiclass_classifier.read()
@@ -192,6 +211,9 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				}
 			})
 			.collect();
+	let confusion_matrix = overall_prediction_metrics
+		.as_ref()
+		.map(|prediction_metrics| prediction_metrics.confusion_matrix.clone());
 	let overall = OverallClassMetrics {
 		label: format_date_window_interval(overall.start_date, &date_window_interval, timezone),
 		class_metrics: overall_class_metrics,
@@ -262,6 +284,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			date_window,
 			date_window_interval,
 			classes,
+			confusion_matrix,
 			overall,
 			class,
 		},
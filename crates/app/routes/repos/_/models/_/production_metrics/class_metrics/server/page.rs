@@ -29,6 +29,7 @@ pub struct Inner {
 	pub class_metrics: Vec<ClassMetricsEntry>,
 	pub class: String,
 	pub classes: Vec<String>,
+	pub confusion_matrix: Option<Vec<Vec<u64>>>,
 	pub date_window_interval: DateWindowInterval,
 	pub date_window: DateWindow,
 	pub id: String,
@@ -133,7 +134,10 @@ impl Component for Inner {
 			)
 			.child(
 				ui::Form::new()
-					.child(DateWindowSelectField::new(self.date_window))
+					.child(DateWindowSelectField::new(
+						self.date_window,
+						self.date_window_interval,
+					))
 					.child(ClassSelectField {
 						class: self.class.clone(),
 						classes: self.classes.clone(),
@@ -156,6 +160,13 @@ impl Component for Inner {
 				class: self.class.to_owned(),
 				confusion_matrix: selected_class_overall_metrics.confusion_matrix,
 			})
+			.child(
+				self.confusion_matrix
+					.map(|confusion_matrix| ConfusionMatrixHeatmapSection {
+						classes: self.classes,
+						confusion_matrix,
+					}),
+			)
 			.child(ProductionTrainingSection {
 				class: self.class,
 				confusion_matrix_training_production_comparison: selected_class_overall_metrics
@@ -401,6 +412,26 @@ impl Component for ConfusionMatrixSection {
 	}
 }
 
+struct ConfusionMatrixHeatmapSection {
+	classes: Vec<String>,
+	confusion_matrix: Vec<Vec<u64>>,
+}
+
+impl Component for ConfusionMatrixHeatmapSection {
+	fn into_node(self) -> Node {
+		let confusion_matrix_heatmap_definition = "This confusion matrix shows the production predictions for every class at once. Each row is a predicted class and each column is an actual class. Hover over a cell to see its value.";
+		ui::S2::new()
+			.child(ui::H2::new("All Classes"))
+			.child(ui::P::new().child(confusion_matrix_heatmap_definition))
+			.child(ui::ConfusionMatrixHeatmap {
+				id: "production-confusion-matrix".to_owned(),
+				classes: self.classes,
+				confusion_matrix: self.confusion_matrix,
+			})
+			.into_node()
+	}
+}
+
 struct ProductionTrainingSection {
 	class: String,
 	confusion_matrix_training_production_comparison: ConfusionMatrixTrainingProductionComparison,
@@ -1,16 +1,50 @@
+use modelfox_charts::chart::{on_brush, BrushRange};
 use modelfox_ui as ui;
+use num::ToPrimitive;
 use pinwheel::prelude::*;
 use web_sys as dom;
 
 pub fn main() {
 	modelfox_ui::client_start();
 	ui::select_field_submit_on_change("date_window_select_field".to_owned());
+	ui::select_field_submit_on_change("date_window_interval_select_field".to_owned());
 	let window = dom::window().unwrap();
 	let document = window.document().unwrap();
+	if document
+		.get_element_by_id("rolling_window_select_field")
+		.is_some()
+	{
+		ui::select_field_submit_on_change("rolling_window_select_field".to_owned());
+	}
 	if document.get_element_by_id("mse").is_some() {
 		hydrate::<modelfox_charts::components::LineChart>("mse");
+		on_brush("mse", zoom_to_range);
+	}
+	if document.get_element_by_id("rolling_error").is_some() {
+		hydrate::<modelfox_charts::components::LineChart>("rolling_error");
+	}
+	if document.get_element_by_id("mean_residual").is_some() {
+		hydrate::<modelfox_charts::components::LineChart>("mean_residual");
 	}
 	if document.get_element_by_id("accuracy").is_some() {
 		hydrate::<modelfox_charts::components::LineChart>("accuracy");
+		on_brush("accuracy", zoom_to_range);
 	}
 }
+
+/// Reload the page with the intervals that were brushed as its range. The chart's x values are the indexes of the intervals it shows, so if the page is already zoomed in, they are offset by the start of the current range.
+fn zoom_to_range(range: BrushRange) {
+	let location = dom::window().unwrap().location();
+	let search_params = dom::UrlSearchParams::new_with_str(&location.search().unwrap()).unwrap();
+	let offset = search_params
+		.get("range_start")
+		.and_then(|range_start| range_start.parse::<usize>().ok())
+		.unwrap_or(0);
+	let start = offset + range.start.round().to_usize().unwrap();
+	let end = offset + range.end.round().to_usize().unwrap();
+	search_params.set("range_start", &start.to_string());
+	search_params.set("range_end", &end.to_string());
+	location
+		.set_search(&String::from(search_params.to_string()))
+		.unwrap();
+}
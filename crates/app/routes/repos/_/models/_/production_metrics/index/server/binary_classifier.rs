@@ -1,5 +1,8 @@
-use crate::page::{AccuracyChart, TrainingProductionMetrics, TrueValuesCountChartEntry};
-use modelfox_app_date_window::{DateWindow, DateWindowInterval};
+use crate::page::{
+	confidence_interval_band, confidence_interval_note, date_window_range_note, AccuracyChart,
+	TrainingProductionMetrics, TrueValuesCountChartEntry,
+};
+use modelfox_app_date_window::{DateWindow, DateWindowInterval, DateWindowRange};
 use modelfox_app_ui::{
 	colors::{PRODUCTION_COLOR, TRAINING_COLOR},
 	date_window_select_field::DateWindowSelectField,
@@ -19,6 +22,8 @@ use pinwheel::prelude::*;
 pub struct BinaryClassifierProductionMetrics {
 	pub date_window: DateWindow,
 	pub date_window_interval: DateWindowInterval,
+	/// This is the range of the date window selected by brushing a chart, if any.
+	pub date_window_range: Option<DateWindowRange>,
 	pub true_values_count_chart: Vec<TrueValuesCountChartEntry>,
 	pub overall: BinaryClassificationOverallProductionMetrics,
 	pub id: String,
@@ -40,6 +45,12 @@ impl Component for BinaryClassifierProductionMetrics {
 			.iter()
 			.map(|entry| entry.label.clone())
 			.collect::<Vec<_>>();
+		let accuracy_band = confidence_interval_band(
+			self.accuracy_chart
+				.data
+				.iter()
+				.map(|entry| entry.accuracy_confidence_interval),
+		);
 		let accuracy_series = vec![
 			LineChartSeries {
 				color: TRAINING_COLOR.to_owned(),
@@ -83,7 +94,10 @@ impl Component for BinaryClassifierProductionMetrics {
 				ui::S2::new()
 					.child(
 						ui::Form::new()
-							.child(DateWindowSelectField::new(self.date_window))
+							.child(DateWindowSelectField::new(
+								self.date_window,
+								self.date_window_interval,
+							))
 							.child(
 								noscript().child(
 									ui::Button::new()
@@ -98,6 +112,11 @@ impl Component for BinaryClassifierProductionMetrics {
 							.child(b().child(self.overall.true_values_count.to_string()))
 							.child(" true values for this date range."),
 					)
+					.child(date_window_range_note(
+						self.date_window,
+						self.date_window_interval,
+						self.date_window_range,
+					))
 					.child(MetricsRow::new().child(ui::NumberCard::new(
 						"True Value Count".to_owned(),
 						self.overall.true_values_count.to_string(),
@@ -126,14 +145,17 @@ impl Component for BinaryClassifierProductionMetrics {
 						ui::Card::new().child(Dehydrate::new(
 							"accuracy",
 							LineChart::new()
+								.brushable(true)
 								.labels(chart_labels)
+								.bands(vec![accuracy_band])
 								.series(accuracy_series)
 								.title(accuracy_chart_title)
 								.x_axis_grid_line_interval(GridLineInterval { k: 1.0, p: 0.0 })
 								.y_max(Finite::new(1.0).unwrap())
 								.y_min(Finite::new(0.0).unwrap()),
 						)),
-					),
+					)
+					.child(confidence_interval_note()),
 			)
 			.into_node()
 	}
@@ -1,9 +1,10 @@
 use crate::page::{
 	AccuracyChart, AccuracyChartEntry, BinaryClassificationOverallProductionMetrics,
-	BinaryClassifierProductionMetrics, ClassMetricsTableEntry, Inner, MeanSquaredErrorChart,
-	MeanSquaredErrorChartEntry, MulticlassClassificationOverallProductionMetrics,
-	MulticlassClassifierProductionMetrics, Page, RegressionProductionMetrics,
-	RegressorProductionMetrics, TrainingProductionMetrics, TrueValuesCountChartEntry,
+	BinaryClassifierProductionMetrics, ClassMetricsTableEntry, Inner, MeanResidualChart,
+	MeanResidualChartEntry, MeanSquaredErrorChart, MeanSquaredErrorChartEntry,
+	MulticlassClassificationOverallProductionMetrics, MulticlassClassifierProductionMetrics, Page,
+	RegressionProductionMetrics, RegressorProductionMetrics, RollingErrorChart,
+	RollingErrorChartEntry, TrainingProductionMetrics, TrueValuesCountChartEntry,
 };
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
@@ -11,12 +12,17 @@ use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model_bytes,
 	path_components,
-	timezone::get_timezone,
+	timezone::get_timezone_for_model,
 	user::{authorize_user, authorize_user_for_model},
 };
-use modelfox_app_date_window::{get_date_window_and_interval, DateWindow};
+use modelfox_app_date_window::{
+	get_date_window_and_interval, DateWindow, DateWindowInterval, DateWindowRange,
+};
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
-use modelfox_app_production_metrics::{get_production_metrics, ProductionPredictionMetricsOutput};
+use modelfox_app_production_metrics::{
+	get_production_metrics, ProductionPredictionMetricsOutput, DEFAULT_ROLLING_WINDOW,
+	ROLLING_WINDOWS,
+};
 use modelfox_app_ui::time::format_date_window_interval;
 use modelfox_id::Id;
 use modelfox_zip::zip;
@@ -36,6 +42,10 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	#[derive(serde::Deserialize, Default)]
 	struct SearchParams {
 		date_window: Option<DateWindow>,
+		date_window_interval: Option<DateWindowInterval>,
+		range_start: Option<usize>,
+		range_end: Option<usize>,
+		rolling_window: Option<usize>,
 	}
 	let search_params: Option<SearchParams> = if let Some(query) = request.uri().query() {
 		Some(serde_urlencoded::from_str(query)?)
@@ -45,19 +55,33 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let date_window = search_params
 		.as_ref()
 		.and_then(|search_params| search_params.date_window);
-	let (date_window, date_window_interval) = match get_date_window_and_interval(&date_window) {
-		Some((date_window, date_window_interval)) => (date_window, date_window_interval),
-		None => return Ok(bad_request()),
-	};
-	let timezone = get_timezone(request);
+	let date_window_interval = search_params
+		.as_ref()
+		.and_then(|search_params| search_params.date_window_interval);
+	let (date_window, date_window_interval) =
+		match get_date_window_and_interval(&date_window, &date_window_interval) {
+			Some((date_window, date_window_interval)) => (date_window, date_window_interval),
+			None => return Ok(bad_request()),
+		};
+	let date_window_range = search_params.as_ref().and_then(|search_params| {
+		DateWindowRange::from_search_params(search_params.range_start, search_params.range_end)
+	});
+	let rolling_window = search_params
+		.as_ref()
+		.and_then(|search_params| search_params.rolling_window)
+		.unwrap_or(DEFAULT_ROLLING_WINDOW);
+	if !ROLLING_WINDOWS.contains(&rolling_window) {
+		return Ok(bad_request());
+	}
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -65,10 +89,24 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
 	let bytes = get_model_bytes(app.storage(), model_id).await?;
 	let model = modelfox_model::from_bytes(&bytes)?;
-	let production_metrics =
-		get_production_metrics(&mut db, model, date_window, date_window_interval, timezone).await?;
+	let mut read_db = match app.begin_read_transaction().await {
+		Ok(read_db) => read_db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let production_metrics = get_production_metrics(
+		&mut read_db,
+		model,
+		date_window,
+		date_window_interval,
+		date_window_range,
+		rolling_window,
+		timezone,
+	)
+	.await?;
+	app.commit_transaction(read_db).await?;
 	let inner = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => {
 			let regressor = regressor.read();
@@ -91,6 +129,19 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 					production: overall_production_metrics.as_ref().map(|m| m.rmse),
 					training: training_metrics.rmse(),
 				},
+				mae: TrainingProductionMetrics {
+					production: overall_production_metrics.as_ref().map(|m| m.mae),
+					training: training_metrics.mae(),
+				},
+				mean_residual: overall_production_metrics.as_ref().map(|m| m.mean_residual),
+				tolerance_accuracy: training_metrics.tolerance_accuracy().map(|training| {
+					TrainingProductionMetrics {
+						production: overall_production_metrics
+							.as_ref()
+							.and_then(|m| m.tolerance_accuracy),
+						training,
+					}
+				}),
 				true_values_count,
 			};
 			let mse_chart = {
@@ -116,7 +167,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 									unreachable!()
 								}
 							});
-						MeanSquaredErrorChartEntry { label, mse }
+						MeanSquaredErrorChartEntry {
+							label,
+							mse,
+							mse_confidence_interval: interval.confidence_intervals.mse,
+						}
 					})
 					.collect();
 				MeanSquaredErrorChart {
@@ -124,6 +179,54 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 					training_mse: training_metrics.mse(),
 				}
 			};
+			// The rolling metrics are labeled with the interval they end at.
+			let rolling_metrics = zip!(
+				production_metrics.intervals.iter(),
+				production_metrics.rolling_intervals.iter()
+			)
+			.map(|(interval, rolling_interval)| {
+				let label = format_date_window_interval(
+					interval.start_date,
+					&date_window_interval,
+					timezone,
+				);
+				let metrics =
+					rolling_interval
+						.prediction_metrics
+						.as_ref()
+						.map(|prediction_metrics| match prediction_metrics {
+							ProductionPredictionMetricsOutput::Regression(prediction_metrics) => {
+								prediction_metrics
+							}
+							_ => unreachable!(),
+						});
+				(label, metrics, rolling_interval.confidence_intervals)
+			})
+			.collect::<Vec<_>>();
+			let rolling_error_chart = RollingErrorChart {
+				data: rolling_metrics
+					.iter()
+					.map(|(label, metrics, _)| RollingErrorChartEntry {
+						label: label.clone(),
+						rmse: metrics.map(|metrics| metrics.rmse),
+						mae: metrics.map(|metrics| metrics.mae),
+					})
+					.collect(),
+				training_rmse: training_metrics.rmse(),
+				training_mae: training_metrics.mae(),
+			};
+			let mean_residual_chart = MeanResidualChart {
+				data: rolling_metrics
+					.into_iter()
+					.map(
+						|(label, metrics, confidence_intervals)| MeanResidualChartEntry {
+							label,
+							mean_residual: metrics.map(|metrics| metrics.mean_residual),
+							mean_residual_confidence_interval: confidence_intervals.mean_residual,
+						},
+					)
+					.collect(),
+			};
 			let true_values_count_chart = production_metrics
 				.intervals
 				.iter()
@@ -139,7 +242,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			Inner::Regressor(RegressorProductionMetrics {
 				date_window,
 				date_window_interval,
+				date_window_range,
+				rolling_window,
 				mse_chart,
+				rolling_error_chart,
+				mean_residual_chart,
 				overall,
 				true_values_count_chart,
 			})
@@ -190,7 +297,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 										unreachable!()
 									}
 								});
-						AccuracyChartEntry { accuracy, label }
+						AccuracyChartEntry {
+							accuracy,
+							accuracy_confidence_interval: interval.confidence_intervals.accuracy,
+							label,
+						}
 					})
 					.collect();
 				let test_metrics = binary_classifier.test_metrics();
@@ -231,6 +342,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			Inner::BinaryClassifier(BinaryClassifierProductionMetrics {
 				date_window,
 				date_window_interval,
+				date_window_range,
 				true_values_count_chart,
 				id: model_id.to_string(),
 				accuracy_chart,
@@ -286,7 +398,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 										unreachable!()
 									}
 								});
-						AccuracyChartEntry { accuracy, label }
+						AccuracyChartEntry {
+							accuracy,
+							accuracy_confidence_interval: interval.confidence_intervals.accuracy,
+							label,
+						}
 					})
 					.collect();
 				AccuracyChart {
@@ -337,6 +453,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			Inner::MulticlassClassifier(MulticlassClassifierProductionMetrics {
 				date_window,
 				date_window_interval,
+				date_window_range,
 				true_values_count_chart,
 				id: model_id.to_string(),
 				accuracy_chart,
@@ -1,7 +1,8 @@
 use crate::page::{
-	AccuracyChart, ClassMetricsTableEntry, TrainingProductionMetrics, TrueValuesCountChartEntry,
+	confidence_interval_band, confidence_interval_note, date_window_range_note, AccuracyChart,
+	ClassMetricsTableEntry, TrainingProductionMetrics, TrueValuesCountChartEntry,
 };
-use modelfox_app_date_window::{DateWindow, DateWindowInterval};
+use modelfox_app_date_window::{DateWindow, DateWindowInterval, DateWindowRange};
 use modelfox_app_ui::{
 	colors::{PRODUCTION_COLOR, TRAINING_COLOR},
 	date_window_select_field::DateWindowSelectField,
@@ -21,6 +22,8 @@ use pinwheel::prelude::*;
 pub struct MulticlassClassifierProductionMetrics {
 	pub date_window: DateWindow,
 	pub date_window_interval: DateWindowInterval,
+	/// This is the range of the date window selected by brushing a chart, if any.
+	pub date_window_range: Option<DateWindowRange>,
 	pub true_values_count_chart: Vec<TrueValuesCountChartEntry>,
 	pub overall: MulticlassClassificationOverallProductionMetrics,
 	pub id: String,
@@ -41,6 +44,12 @@ impl Component for MulticlassClassifierProductionMetrics {
 			.iter()
 			.map(|entry| entry.label.clone())
 			.collect::<Vec<_>>();
+		let accuracy_band = confidence_interval_band(
+			self.accuracy_chart
+				.data
+				.iter()
+				.map(|entry| entry.accuracy_confidence_interval),
+		);
 		let accuracy_series = vec![
 			LineChartSeries {
 				color: TRAINING_COLOR.to_owned(),
@@ -85,7 +94,10 @@ impl Component for MulticlassClassifierProductionMetrics {
 					.child(ui::TabLink::new("".to_owned(), true).child("Overview"))
 					.child(
 						ui::TabLink::new(
-							format!("class_metrics?date_window={}", self.date_window),
+							format!(
+								"class_metrics?date_window={}&date_window_interval={}",
+								self.date_window, self.date_window_interval
+							),
 							false,
 						)
 						.child("Class Metrics"),
@@ -95,7 +107,10 @@ impl Component for MulticlassClassifierProductionMetrics {
 				ui::S2::new()
 					.child(
 						ui::Form::new()
-							.child(DateWindowSelectField::new(self.date_window))
+							.child(DateWindowSelectField::new(
+								self.date_window,
+								self.date_window_interval,
+							))
 							.child(
 								noscript().child(
 									ui::Button::new()
@@ -110,6 +125,11 @@ impl Component for MulticlassClassifierProductionMetrics {
 							.child(b().child(self.overall.true_values_count.to_string()))
 							.child(" true values for this date range."),
 					)
+					.child(date_window_range_note(
+						self.date_window,
+						self.date_window_interval,
+						self.date_window_range,
+					))
 					.child(MetricsRow::new().child(ui::NumberCard::new(
 						"True Value Count".to_owned(),
 						self.overall.true_values_count.to_string(),
@@ -138,14 +158,17 @@ impl Component for MulticlassClassifierProductionMetrics {
 						ui::Card::new().child(Dehydrate::new(
 							"accuracy",
 							LineChart::new()
+								.brushable(true)
 								.labels(chart_labels)
+								.bands(vec![accuracy_band])
 								.series(accuracy_series)
 								.title(accuracy_chart_title)
 								.x_axis_grid_line_interval(GridLineInterval { k: 1.0, p: 0.0 })
 								.y_max(Finite::new(1.0).unwrap())
 								.y_min(Finite::new(0.0).unwrap()),
 						)),
-					),
+					)
+					.child(confidence_interval_note()),
 			)
 			.child(ClassMetricsTable {
 				rows: self.overall.class_metrics_table_rows,
@@ -1,8 +1,15 @@
 pub use crate::{binary_classifier::*, multiclass_classifier::*, regressor::*};
+use modelfox_app_date_window::{DateWindow, DateWindowInterval, DateWindowRange};
 use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
 };
+use modelfox_app_production_metrics::{ConfidenceInterval, CONFIDENCE_LEVEL};
+use modelfox_app_ui::colors::PRODUCTION_CONFIDENCE_INTERVAL_COLOR;
+use modelfox_charts::line_chart::{LineChartBand, LineChartBandPoint};
+use modelfox_finite::Finite;
+use modelfox_ui as ui;
+use num::ToPrimitive;
 use pinwheel::prelude::*;
 
 pub struct Page {
@@ -34,9 +41,59 @@ pub struct AccuracyChart {
 
 pub struct AccuracyChartEntry {
 	pub accuracy: Option<f32>,
+	pub accuracy_confidence_interval: Option<ConfidenceInterval>,
 	pub label: String,
 }
 
+/// Create a band shading the confidence interval of a production metric at each point of a chart.
+pub fn confidence_interval_band(
+	confidence_intervals: impl Iterator<Item = Option<ConfidenceInterval>>,
+) -> LineChartBand {
+	let to_finite = |value: f32| Finite::new(value.to_f64().unwrap()).ok();
+	LineChartBand {
+		color: PRODUCTION_CONFIDENCE_INTERVAL_COLOR.to_owned(),
+		data: confidence_intervals
+			.enumerate()
+			.map(|(index, confidence_interval)| LineChartBandPoint {
+				x: Finite::new(index.to_f64().unwrap()).unwrap(),
+				lower: confidence_interval.and_then(|interval| to_finite(interval.lower)),
+				upper: confidence_interval.and_then(|interval| to_finite(interval.upper)),
+			})
+			.collect(),
+	}
+}
+
+/// Explain how to zoom in on the production metric charts, or how to zoom back out if a range of the date window is selected.
+pub fn date_window_range_note(
+	date_window: DateWindow,
+	date_window_interval: DateWindowInterval,
+	date_window_range: Option<DateWindowRange>,
+) -> ui::P {
+	if date_window_range.is_some() {
+		ui::P::new()
+			.child("The metrics below are for the range selected on the charts. ")
+			.child(
+				ui::Link::new()
+					.href(format!(
+						"?date_window={}&date_window_interval={}",
+						date_window, date_window_interval
+					))
+					.child("Reset Zoom"),
+			)
+	} else {
+		ui::P::new().child("Drag across a chart to zoom in on a range of dates.")
+	}
+}
+
+/// Explain the shaded band on the production metric charts.
+pub fn confidence_interval_note() -> ui::P {
+	let confidence_level = (CONFIDENCE_LEVEL * 100.0).round();
+	ui::P::new().child(format!(
+		"The shaded region around each production value is its {}% confidence interval. It is wider when fewer true values were logged.",
+		confidence_level
+	))
+}
+
 pub struct ClassMetricsTableEntry {
 	pub class_name: String,
 	pub precision: TrainingProductionMetrics,
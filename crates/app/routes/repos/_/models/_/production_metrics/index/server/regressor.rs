@@ -1,5 +1,10 @@
-use crate::page::{TrainingProductionMetrics, TrueValuesCountChartEntry};
-use modelfox_app_date_window::{DateWindow, DateWindowInterval};
+use crate::page::{
+	confidence_interval_band, confidence_interval_note, date_window_range_note,
+	TrainingProductionMetrics, TrueValuesCountChartEntry,
+};
+use modelfox_app_core::alert::AlertMetric;
+use modelfox_app_date_window::{DateWindow, DateWindowInterval, DateWindowRange};
+use modelfox_app_production_metrics::{ConfidenceInterval, ROLLING_WINDOWS};
 use modelfox_app_ui::{
 	colors::{PRODUCTION_COLOR, TRAINING_COLOR},
 	date_window_select_field::DateWindowSelectField,
@@ -19,7 +24,13 @@ use pinwheel::prelude::*;
 pub struct RegressorProductionMetrics {
 	pub date_window: DateWindow,
 	pub date_window_interval: DateWindowInterval,
+	/// This is the range of the date window selected by brushing a chart, if any.
+	pub date_window_range: Option<DateWindowRange>,
+	/// This is the number of intervals the rolling metrics are computed over.
+	pub rolling_window: usize,
 	pub mse_chart: MeanSquaredErrorChart,
+	pub rolling_error_chart: RollingErrorChart,
+	pub mean_residual_chart: MeanResidualChart,
 	pub overall: RegressionProductionMetrics,
 	pub true_values_count_chart: Vec<TrueValuesCountChartEntry>,
 }
@@ -32,11 +43,39 @@ pub struct MeanSquaredErrorChart {
 pub struct MeanSquaredErrorChartEntry {
 	pub label: String,
 	pub mse: Option<f32>,
+	pub mse_confidence_interval: Option<ConfidenceInterval>,
+}
+
+pub struct RollingErrorChart {
+	pub data: Vec<RollingErrorChartEntry>,
+	pub training_rmse: f32,
+	pub training_mae: f32,
+}
+
+pub struct RollingErrorChartEntry {
+	pub label: String,
+	pub rmse: Option<f32>,
+	pub mae: Option<f32>,
+}
+
+pub struct MeanResidualChart {
+	pub data: Vec<MeanResidualChartEntry>,
+}
+
+pub struct MeanResidualChartEntry {
+	pub label: String,
+	pub mean_residual: Option<f32>,
+	pub mean_residual_confidence_interval: Option<ConfidenceInterval>,
 }
 
 pub struct RegressionProductionMetrics {
 	pub mse: TrainingProductionMetrics,
 	pub rmse: TrainingProductionMetrics,
+	pub mae: TrainingProductionMetrics,
+	/// The model has no training mean residual to compare against, so only the production value is shown.
+	pub mean_residual: Option<f32>,
+	/// This is absent if the model was trained without a regression tolerance.
+	pub tolerance_accuracy: Option<TrainingProductionMetrics>,
 	pub true_values_count: u64,
 }
 
@@ -48,6 +87,12 @@ impl Component for RegressorProductionMetrics {
 			.iter()
 			.map(|entry| entry.label.clone())
 			.collect::<Vec<_>>();
+		let mse_band = confidence_interval_band(
+			self.mse_chart
+				.data
+				.iter()
+				.map(|entry| entry.mse_confidence_interval),
+		);
 		let mse_series = vec![
 			LineChartSeries {
 				color: TRAINING_COLOR.to_owned(),
@@ -84,13 +129,88 @@ impl Component for RegressorProductionMetrics {
 		];
 		let mse_chart_title =
 			interval_chart_title(&self.date_window_interval, "Mean Squared Error".to_owned());
+		let rolling_window_title = format_rolling_window(self.rolling_window);
+		let rolling_error_chart_labels = self
+			.rolling_error_chart
+			.data
+			.iter()
+			.map(|entry| entry.label.clone())
+			.collect::<Vec<_>>();
+		let rolling_error_series = vec![
+			constant_series(
+				TRAINING_COLOR,
+				self.rolling_error_chart.data.len(),
+				self.rolling_error_chart.training_rmse,
+				"Training Root Mean Squared Error",
+			),
+			rolling_series(
+				PRODUCTION_COLOR,
+				self.rolling_error_chart.data.iter().map(|entry| entry.rmse),
+				"Production Root Mean Squared Error",
+			),
+			constant_series(
+				MAE_TRAINING_COLOR,
+				self.rolling_error_chart.data.len(),
+				self.rolling_error_chart.training_mae,
+				"Training Mean Absolute Error",
+			),
+			rolling_series(
+				MAE_PRODUCTION_COLOR,
+				self.rolling_error_chart.data.iter().map(|entry| entry.mae),
+				"Production Mean Absolute Error",
+			),
+		];
+		let rolling_error_chart_title = format!(
+			"{} ({})",
+			interval_chart_title(&self.date_window_interval, "Error".to_owned()),
+			rolling_window_title,
+		);
+		let mean_residual_chart_labels = self
+			.mean_residual_chart
+			.data
+			.iter()
+			.map(|entry| entry.label.clone())
+			.collect::<Vec<_>>();
+		let mean_residual_band = confidence_interval_band(
+			self.mean_residual_chart
+				.data
+				.iter()
+				.map(|entry| entry.mean_residual_confidence_interval),
+		);
+		let mean_residual_series = vec![
+			constant_series(
+				TRAINING_COLOR,
+				self.mean_residual_chart.data.len(),
+				0.0,
+				"No Bias",
+			),
+			rolling_series(
+				PRODUCTION_COLOR,
+				self.mean_residual_chart
+					.data
+					.iter()
+					.map(|entry| entry.mean_residual),
+				"Production Mean Residual",
+			),
+		];
+		let mean_residual_chart_title = format!(
+			"{} ({})",
+			interval_chart_title(&self.date_window_interval, "Mean Residual".to_owned()),
+			rolling_window_title,
+		);
 		ui::S1::new()
 			.child(ui::H1::new("Production Metrics"))
 			.child(
 				ui::S2::new()
 					.child(
 						ui::Form::new()
-							.child(DateWindowSelectField::new(self.date_window))
+							.child(DateWindowSelectField::new(
+								self.date_window,
+								self.date_window_interval,
+							))
+							.child(RollingWindowSelectField {
+								rolling_window: self.rolling_window,
+							})
 							.child(
 								noscript().child(
 									ui::Button::new()
@@ -105,11 +225,18 @@ impl Component for RegressorProductionMetrics {
 							.child(b().child(self.overall.true_values_count.to_string()))
 							.child(" true values for this date range."),
 					)
+					.child(date_window_range_note(
+						self.date_window,
+						self.date_window_interval,
+						self.date_window_range,
+					))
 					.child(
 						ui::Card::new().child(Dehydrate::new(
 							"mse",
 							LineChart::new()
+								.brushable(true)
 								.labels(mse_chart_labels)
+								.bands(vec![mse_band])
 								.series(mse_series)
 								.title(mse_chart_title)
 								.x_axis_grid_line_interval(GridLineInterval { k: 1.0, p: 0.0 })
@@ -117,6 +244,33 @@ impl Component for RegressorProductionMetrics {
 								.y_min(Finite::new(0.0).unwrap()),
 						)),
 					)
+					.child(
+						ui::Card::new().child(Dehydrate::new(
+							"rolling_error",
+							LineChart::new()
+								.labels(rolling_error_chart_labels)
+								.series(rolling_error_series)
+								.title(rolling_error_chart_title)
+								.x_axis_grid_line_interval(GridLineInterval { k: 1.0, p: 0.0 })
+								.y_min(Finite::new(0.0).unwrap()),
+						)),
+					)
+					.child(
+						ui::Card::new().child(Dehydrate::new(
+							"mean_residual",
+							LineChart::new()
+								.labels(mean_residual_chart_labels)
+								.bands(vec![mean_residual_band])
+								.series(mean_residual_series)
+								.title(mean_residual_chart_title)
+								.x_axis_grid_line_interval(GridLineInterval { k: 1.0, p: 0.0 }),
+						)),
+					)
+					.child(
+						ui::P::new()
+							.child("The mean residual is the mean of the predictions minus their true values. It is above zero if the model overestimates and below zero if it underestimates."),
+					)
+					.child(confidence_interval_note())
 					.child(MetricsRow::new().child(ui::NumberCard::new(
 						"True Value Count".to_owned(),
 						self.overall.true_values_count.to_string(),
@@ -133,7 +287,9 @@ impl Component for RegressorProductionMetrics {
 								.title("Root Mean Squared Error".to_owned())
 								.value_a_title("Training".to_owned())
 								.value_b_title("Production".to_owned())
-								.number_formatter(ui::NumberFormatter::Float(Default::default())),
+								.number_formatter(
+									AlertMetric::RootMeanSquaredError.number_formatter(),
+								),
 							)
 							.child(
 								ui::NumberComparisonCard::new(
@@ -145,10 +301,120 @@ impl Component for RegressorProductionMetrics {
 								.title("Mean Squared Error".to_owned())
 								.value_a_title("Training".to_owned())
 								.value_b_title("Production".to_owned())
-								.number_formatter(ui::NumberFormatter::Float(Default::default())),
+								.number_formatter(AlertMetric::MeanSquaredError.number_formatter()),
 							),
-					),
+					)
+					.child(
+						MetricsRow::new()
+							.child(
+								ui::NumberComparisonCard::new(
+									Some(self.overall.mae.training),
+									self.overall.mae.production,
+								)
+								.color_a(TRAINING_COLOR.to_owned())
+								.color_b(PRODUCTION_COLOR.to_owned())
+								.title("Mean Absolute Error".to_owned())
+								.value_a_title("Training".to_owned())
+								.value_b_title("Production".to_owned())
+								.number_formatter(AlertMetric::MeanAbsoluteError.number_formatter()),
+							)
+							.child(ui::NumberCard::new(
+								"Mean Residual".to_owned(),
+								AlertMetric::MeanResidual
+									.number_formatter()
+									.format_option(self.overall.mean_residual),
+							)),
+					)
+					.child(self.overall.tolerance_accuracy.map(|tolerance_accuracy| {
+						MetricsRow::new().child(
+							ui::NumberComparisonCard::new(
+								Some(tolerance_accuracy.training),
+								tolerance_accuracy.production,
+							)
+							.color_a(TRAINING_COLOR.to_owned())
+							.color_b(PRODUCTION_COLOR.to_owned())
+							.title("Tolerance Accuracy".to_owned())
+							.value_a_title("Training".to_owned())
+							.value_b_title("Production".to_owned())
+							.number_formatter(AlertMetric::ToleranceAccuracy.number_formatter()),
+						)
+					})),
 			)
 			.into_node()
 	}
 }
+
+/// These colors distinguish the mean absolute error from the root mean squared error on the rolling error chart.
+const MAE_TRAINING_COLOR: &str = ui::colors::INDIGO;
+const MAE_PRODUCTION_COLOR: &str = ui::colors::TEAL;
+
+fn format_rolling_window(rolling_window: usize) -> String {
+	if rolling_window == 1 {
+		"No Rolling Window".to_owned()
+	} else {
+		format!("Rolling {} Intervals", rolling_window)
+	}
+}
+
+/// Create a dashed series with the same value at each of `len` points, like a training metric.
+fn constant_series(color: &str, len: usize, value: f32, title: &str) -> LineChartSeries {
+	LineChartSeries {
+		color: color.to_owned(),
+		data: (0..len)
+			.map(|index| LineChartPoint {
+				x: Finite::new(index.to_f64().unwrap()).unwrap(),
+				y: Some(Finite::new(value.to_f64().unwrap()).unwrap()),
+			})
+			.collect(),
+		line_style: Some(LineStyle::Dashed),
+		point_style: Some(PointStyle::Hidden),
+		title: Some(title.to_owned()),
+	}
+}
+
+fn rolling_series(
+	color: &str,
+	values: impl Iterator<Item = Option<f32>>,
+	title: &str,
+) -> LineChartSeries {
+	LineChartSeries {
+		color: color.to_owned(),
+		data: values
+			.enumerate()
+			.map(|(index, value)| LineChartPoint {
+				x: Finite::new(index.to_f64().unwrap()).unwrap(),
+				y: value.and_then(|value| Finite::new(value.to_f64().unwrap()).ok()),
+			})
+			.collect(),
+		line_style: None,
+		point_style: None,
+		title: Some(title.to_owned()),
+	}
+}
+
+struct RollingWindowSelectField {
+	rolling_window: usize,
+}
+
+impl Component for RollingWindowSelectField {
+	fn into_node(self) -> Node {
+		let options = ROLLING_WINDOWS
+			.iter()
+			.map(|rolling_window| ui::SelectFieldOption {
+				text: if *rolling_window == 1 {
+					"None".to_owned()
+				} else {
+					format!("{} Intervals", rolling_window)
+				},
+				value: rolling_window.to_string(),
+			})
+			.collect();
+		ui::SelectField::new()
+			.id("rolling_window_select_field".to_owned())
+			.label("Rolling Window".to_owned())
+			.name("rolling_window".to_owned())
+			.options(options)
+			.value(self.rolling_window.to_string())
+			.into_node()
+	}
+}
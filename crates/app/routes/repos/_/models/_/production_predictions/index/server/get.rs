@@ -7,7 +7,7 @@ use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	heuristics::PRODUCTION_PREDICTIONS_NUM_PREDICTIONS_PER_PAGE_TABLE,
 	path_components,
-	timezone::get_timezone,
+	timezone::get_timezone_for_model,
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
@@ -38,15 +38,15 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	} else {
 		None
 	};
-	let timezone = get_timezone(request);
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -54,8 +54,14 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::ProductionPredictions).await?;
+	// Listing predictions can be expensive, so read them from the read replica if one is configured.
+	let mut read_db = match app.begin_read_transaction().await {
+		Ok(read_db) => read_db,
+		Err(_) => return Ok(service_unavailable()),
+	};
 	let after = search_params.as_ref().and_then(|s| s.after);
 	let before = search_params.as_ref().and_then(|s| s.before);
 	let rows = match (after, before) {
@@ -88,7 +94,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			.bind(&model_id.to_string())
 			.bind(after)
 			.bind(PRODUCTION_PREDICTIONS_NUM_PREDICTIONS_PER_PAGE_TABLE)
-			.fetch_all(db.borrow_mut())
+			.fetch_all(read_db.borrow_mut())
 			.await?
 		}
 		(None, Some(before)) => {
@@ -111,7 +117,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			.bind(&model_id.to_string())
 			.bind(before)
 			.bind(PRODUCTION_PREDICTIONS_NUM_PREDICTIONS_PER_PAGE_TABLE)
-			.fetch_all(db.borrow_mut())
+			.fetch_all(read_db.borrow_mut())
 			.await?
 		}
 		(None, None) => {
@@ -132,7 +138,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			)
 			.bind(&model_id.to_string())
 			.bind(PRODUCTION_PREDICTIONS_NUM_PREDICTIONS_PER_PAGE_TABLE)
-			.fetch_all(db.borrow_mut())
+			.fetch_all(read_db.borrow_mut())
 			.await?
 		}
 		_ => unreachable!(),
@@ -151,7 +157,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				)
 				.bind(&model_id.to_string())
 				.bind(first_row_timestamp)
-				.fetch_one(db.borrow_mut())
+				.fetch_one(read_db.borrow_mut())
 				.await?
 				.get(0);
 				let older_predictions_exist: bool = sqlx::query(
@@ -163,7 +169,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				)
 				.bind(&model_id.to_string())
 				.bind(last_row_timestamp)
-				.fetch_one(db.borrow_mut())
+				.fetch_one(read_db.borrow_mut())
 				.await?
 				.get(0);
 				(newer_predictions_exist, older_predictions_exist)
@@ -221,6 +227,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		.status(http::StatusCode::OK)
 		.body(hyper::Body::from(html))
 		.unwrap();
+	app.commit_transaction(read_db).await?;
 	app.commit_transaction(db).await?;
 	Ok(response)
 }
@@ -7,8 +7,9 @@ use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	model::get_model_bytes,
 	path_components,
-	timezone::get_timezone,
-	user::{authorize_user, authorize_user_for_model},
+	privacy::get_model_privacy_settings,
+	timezone::get_timezone_for_model,
+	user::{authorize_user, authorize_user_for_data_access, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
 use modelfox_app_ui::predict::{
@@ -32,15 +33,15 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	} else {
 		bail!("unexpected path");
 	};
-	let timezone = get_timezone(request);
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -50,6 +51,8 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	let has_data_access = authorize_user_for_data_access(&mut db, &user, model_id).await?;
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::ProductionPredictions).await?;
 	let id: Id = match id.parse() {
@@ -83,61 +86,99 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let identifier: String = row.get(2);
 	let input: String = row.get(3);
 	let input: PredictInput = serde_json::from_str(&input)?;
-	let input_table = compute_input_table(model, &input);
-	let bytes = get_model_bytes(app.storage(), model_id).await?;
-	let model = modelfox_model::from_bytes(&bytes)?;
-	let predict_model = modelfox_core::predict::Model::from(model);
-	let options = PredictOptions {
-		compute_feature_contributions: true,
-		..Default::default()
-	};
-	let mut output = modelfox_core::predict::predict(&predict_model, &[input], &options);
-	let output = output.remove(0);
-	let inner = match output {
-		modelfox_core::predict::PredictOutput::Regression(output) => {
-			let feature_contributions = output.feature_contributions.unwrap();
-			let feature_contributions_chart_series = compute_feature_contributions_chart_series(
-				"output".to_owned(),
-				feature_contributions,
-			);
-			PredictOutputInner::Regression(RegressionPredictOutput {
-				feature_contributions_chart_series,
-				value: output.value,
-			})
-		}
-		modelfox_core::predict::PredictOutput::BinaryClassification(output) => {
-			let feature_contributions = output.feature_contributions.unwrap();
-			let feature_contributions_chart_series = compute_feature_contributions_chart_series(
-				"output".to_owned(),
-				feature_contributions,
-			);
-			PredictOutputInner::BinaryClassification(BinaryClassificationPredictOutput {
-				class_name: output.class_name,
-				feature_contributions_chart_series,
-				probability: output.probability,
-			})
+	let privacy_settings = get_model_privacy_settings(&mut db, model_id).await?;
+	let (predict_output, redaction_notice) = if !has_data_access {
+		// The explanation is hidden too, because its feature contributions reveal the values of enum and text columns.
+		let redaction_notice =
+			"You do not have data access for this model, so the inputs of this prediction and its explanation are hidden.".to_owned();
+		(None, Some(redaction_notice))
+	} else if !privacy_settings.store_inputs && input.0.is_empty() {
+		let redaction_notice =
+			"This model is configured to not store the inputs of its predictions, so this prediction cannot be explained.".to_owned();
+		(None, Some(redaction_notice))
+	} else {
+		// Redacted columns are treated as missing when explaining the prediction, because their hashes are not meaningful to the model.
+		let mut input = input;
+		let redacted_column_names = privacy_settings
+			.pii_columns
+			.iter()
+			.filter(|column_name| input.0.remove(column_name.as_str()).is_some())
+			.cloned()
+			.collect::<Vec<_>>();
+		let mut input_table = compute_input_table(model, &input);
+		for row in input_table.rows.iter_mut() {
+			if redacted_column_names.contains(&row.column_name) {
+				row.value = Some("Redacted".to_owned());
+			}
 		}
-		modelfox_core::predict::PredictOutput::MulticlassClassification(output) => {
-			let feature_contributions = output.feature_contributions.unwrap();
-			let feature_contributions_chart_series = feature_contributions
-				.into_iter()
-				.map(|(class, feature_contributions)| {
-					compute_feature_contributions_chart_series(class, feature_contributions)
+		let redaction_notice = if redacted_column_names.is_empty() {
+			None
+		} else {
+			Some(format!(
+				"The values of the PII columns {} were redacted when this prediction was tracked, so they are treated as missing in its explanation.",
+				redacted_column_names.join(", ")
+			))
+		};
+		let bytes = get_model_bytes(app.storage(), model_id).await?;
+		let model = modelfox_model::from_bytes(&bytes)?;
+		let predict_model = modelfox_core::predict::Model::from(model);
+		let options = PredictOptions {
+			compute_feature_contributions: true,
+			tree_shap_mode: app.options().tree_shap_mode,
+			..Default::default()
+		};
+		let mut output = modelfox_core::predict::predict(&predict_model, &[input], &options);
+		let output = output.remove(0);
+		let inner = match output {
+			modelfox_core::predict::PredictOutput::Regression(output) => {
+				let feature_contributions = output.feature_contributions.unwrap();
+				let feature_contributions_chart_series = compute_feature_contributions_chart_series(
+					"output".to_owned(),
+					feature_contributions,
+				);
+				PredictOutputInner::Regression(RegressionPredictOutput {
+					feature_contributions_chart_series,
+					value: output.value,
 				})
-				.collect();
-			PredictOutputInner::MulticlassClassification(MulticlassClassificationPredictOutput {
-				class_name: output.class_name,
-				feature_contributions_chart_series,
-				probabilities: output.probabilities.into_iter().collect(),
-				probability: output.probability,
-			})
-		}
+			}
+			modelfox_core::predict::PredictOutput::BinaryClassification(output) => {
+				let feature_contributions = output.feature_contributions.unwrap();
+				let feature_contributions_chart_series = compute_feature_contributions_chart_series(
+					"output".to_owned(),
+					feature_contributions,
+				);
+				PredictOutputInner::BinaryClassification(BinaryClassificationPredictOutput {
+					class_name: output.class_name,
+					feature_contributions_chart_series,
+					probability: output.probability,
+				})
+			}
+			modelfox_core::predict::PredictOutput::MulticlassClassification(output) => {
+				let feature_contributions = output.feature_contributions.unwrap();
+				let feature_contributions_chart_series = feature_contributions
+					.into_iter()
+					.map(|(class, feature_contributions)| {
+						compute_feature_contributions_chart_series(class, feature_contributions)
+					})
+					.collect();
+				PredictOutputInner::MulticlassClassification(
+					MulticlassClassificationPredictOutput {
+						class_name: output.class_name,
+						feature_contributions_chart_series,
+						probabilities: output.probabilities.into_iter().collect(),
+						probability: output.probability,
+					},
+				)
+			}
+		};
+		(Some(PredictOutput { inner, input_table }), redaction_notice)
 	};
 	let page = Page {
 		id,
 		date: date.to_string(),
 		identifier: identifier.to_owned(),
-		predict_output: PredictOutput { inner, input_table },
+		predict_output,
+		redaction_notice,
 		model_layout_info,
 	};
 	let html = html(page);
@@ -11,7 +11,8 @@ pub struct Page {
 	pub id: Id,
 	pub date: String,
 	pub identifier: String,
-	pub predict_output: PredictOutput,
+	pub predict_output: Option<PredictOutput>,
+	pub redaction_notice: Option<String>,
 	pub model_layout_info: ModelLayoutInfo,
 }
 
@@ -27,6 +28,10 @@ impl Component for Page {
 							identifier: self.identifier,
 							date: self.date,
 						})
+						.child(
+							self.redaction_notice
+								.map(|notice| ui::Alert::new(ui::Level::Info).child(notice)),
+						)
 						.child(self.predict_output),
 				),
 			)
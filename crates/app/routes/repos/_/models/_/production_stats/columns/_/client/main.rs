@@ -6,6 +6,7 @@ pub fn main() {
 	modelfox_ui::client_start();
 	let document = dom::window().unwrap().document().unwrap();
 	ui::select_field_submit_on_change("date_window_select_field".to_owned());
+	ui::select_field_submit_on_change("date_window_interval_select_field".to_owned());
 	if document.get_element_by_id("number_intervals").is_some() {
 		hydrate::<modelfox_charts::components::BoxChart>("number_intervals");
 	}
@@ -18,4 +19,7 @@ pub fn main() {
 	if document.get_element_by_id("text_overall").is_some() {
 		hydrate::<modelfox_charts::components::BarChart>("text_overall");
 	}
+	if document.get_element_by_id("text_unseen_tokens").is_some() {
+		hydrate::<modelfox_charts::components::LineChart>("text_unseen_tokens");
+	}
 }
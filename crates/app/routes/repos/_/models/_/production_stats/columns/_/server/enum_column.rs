@@ -61,6 +61,7 @@ impl Component for EnumColumnStatsSection {
 						label: label.to_owned(),
 						x: index.to_f64().unwrap(),
 						y: Some(value.training_fraction.to_f64().unwrap()),
+						error: None,
 					})
 					.collect(),
 				title: Some("Training".to_owned()),
@@ -77,6 +78,7 @@ impl Component for EnumColumnStatsSection {
 						y: value
 							.production_fraction
 							.map(|production_fraction| production_fraction.to_f64().unwrap()),
+						error: None,
 					})
 					.collect(),
 				title: Some("Production".to_owned()),
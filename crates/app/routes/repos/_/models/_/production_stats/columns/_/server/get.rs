@@ -1,13 +1,17 @@
 use crate::{
-	number_column::{NumberColumnCountsSection, NumberColumnStatsSection},
+	number_column::{
+		NumberColumnCountsSection, NumberColumnOutliersSection, NumberColumnQuantilesRow,
+		NumberColumnQuantilesSection, NumberColumnStatsSection,
+	},
 	page::{
 		EnumColumn, EnumColumnCountsSection, EnumColumnInvalidValuesSection,
 		EnumColumnOverallHistogramEntry, EnumColumnStatsSection, EnumColumnUniqueValuesSection,
 		EnumInvalidValuesTable, EnumInvalidValuesTableRow, EnumUniqueValuesTable,
 		EnumUniqueValuesTableRow, Inner, IntervalBoxChartDataPoint, IntervalBoxChartDataPointStats,
-		NumberColumn, NumberTrainingProductionComparison, OverallBoxChartData,
-		OverallBoxChartDataStats, Page, TextColumn, TextColumnCountsSection,
-		TextColumnStatsSection, TextColumnTokensSection, TextNGramsTable, TextNGramsTableRow,
+		IntervalUnseenTokenFraction, NumberColumn, NumberTrainingProductionComparison,
+		OverallBoxChartData, OverallBoxChartDataStats, Page, TextColumn, TextColumnCountsSection,
+		TextColumnStatsSection, TextColumnTokensSection, TextColumnUnseenTokensSection,
+		TextNGramsTable, TextNGramsTableRow,
 	},
 };
 use anyhow::{bail, Result};
@@ -15,18 +19,23 @@ use chrono_tz::Tz;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
-	heuristics::PRODUCTION_STATS_TEXT_COLUMN_MAX_TOKENS_TO_SHOW_IN_TABLE,
+	heuristics::{
+		PRODUCTION_STATS_LARGE_OUT_OF_RANGE_RATIO_THRESHOLD_TO_TRIGGER_ALERT,
+		PRODUCTION_STATS_LARGE_UNSEEN_TOKEN_RATIO_THRESHOLD_TO_TRIGGER_ALERT,
+		PRODUCTION_STATS_TEXT_COLUMN_MAX_TOKENS_TO_SHOW_IN_TABLE,
+	},
 	model::get_model_bytes,
 	path_components,
-	timezone::get_timezone,
+	timezone::get_timezone_for_model,
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_date_window::{get_date_window_and_interval, DateWindow, DateWindowInterval};
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
 use modelfox_app_production_stats::{
-	get_production_stats, GetProductionStatsOutput, ProductionColumnStatsOutput,
+	get_production_stats, get_rolled_up_production_stats, GetProductionStatsOutput,
+	ProductionColumnStatsOutput,
 };
-use modelfox_app_ui::time::format_date_window_interval;
+use modelfox_app_ui::{stale_stats_alert::StaleStatsAlert, time::format_date_window_interval};
 use modelfox_id::Id;
 use modelfox_ui as ui;
 use num::ToPrimitive;
@@ -52,6 +61,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	#[derive(serde::Deserialize, Default)]
 	struct SearchParams {
 		date_window: Option<DateWindow>,
+		date_window_interval: Option<DateWindowInterval>,
 	}
 	let search_params: Option<SearchParams> = if let Some(query) = request.uri().query() {
 		Some(serde_urlencoded::from_str(query)?)
@@ -61,28 +71,75 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let date_window = search_params
 		.as_ref()
 		.and_then(|search_params| search_params.date_window);
-	let (date_window, date_window_interval) = match get_date_window_and_interval(&date_window) {
-		Some((date_window, date_window_interval)) => (date_window, date_window_interval),
-		None => return Ok(bad_request()),
-	};
-	let timezone = get_timezone(request);
+	let date_window_interval = search_params
+		.as_ref()
+		.and_then(|search_params| search_params.date_window_interval);
+	let (date_window, date_window_interval) =
+		match get_date_window_and_interval(&date_window, &date_window_interval) {
+			Some((date_window, date_window_interval)) => (date_window, date_window_interval),
+			None => return Ok(bad_request()),
+		};
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
 	let bytes = get_model_bytes(app.storage(), model_id).await?;
 	let model = modelfox_model::from_bytes(&bytes)?;
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::ProductionStats).await?;
-	let get_production_stats_output =
-		get_production_stats(&mut db, model, date_window, date_window_interval, timezone).await?;
+	let mut read_db = match app.begin_analytics_transaction().await {
+		Ok(read_db) => read_db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let get_production_stats_output = app
+		.with_analytics_statement_timeout(get_production_stats(
+			&mut read_db,
+			model,
+			date_window,
+			date_window_interval,
+			timezone,
+		))
+		.await?;
+	let get_production_stats_output = match get_production_stats_output {
+		Some(get_production_stats_output) => {
+			app.commit_transaction(read_db).await?;
+			get_production_stats_output
+		}
+		None => {
+			// Reading the production stats took too long, so fall back to the daily rollups, which are much cheaper to read.
+			drop(read_db);
+			let mut read_db = match app.begin_read_transaction().await {
+				Ok(read_db) => read_db,
+				Err(_) => return Ok(service_unavailable()),
+			};
+			let get_production_stats_output = get_rolled_up_production_stats(
+				&mut read_db,
+				model,
+				date_window,
+				date_window_interval,
+				timezone,
+			)
+			.await?;
+			app.commit_transaction(read_db).await?;
+			get_production_stats_output
+		}
+	};
+	let stale_stats_alert =
+		get_production_stats_output
+			.rolled_up_through
+			.map(|rolled_up_through| StaleStatsAlert {
+				rolled_up_through,
+				timezone,
+			});
 	let overall_train_row_count = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => {
 			let regressor = regressor.read();
@@ -151,10 +208,12 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	};
 	let page = Page {
 		date_window,
+		date_window_interval,
 		column_name,
 		id: model_id.to_string(),
 		inner,
 		model_layout_info,
+		stale_stats_alert,
 	};
 	let html = html(page);
 	let response = http::Response::builder()
@@ -254,11 +313,40 @@ fn number_column(
 		production: overall.stats.as_ref().map(|s| s.std),
 		training: train_column_stats.std(),
 	};
+	// Models trained before the quantiles were configurable only store the quartiles.
+	let training_quantiles: Vec<(f32, f32)> = match train_column_stats.quantiles() {
+		Some(quantiles) => quantiles.iter().collect(),
+		None => vec![
+			(0.25, train_column_stats.p25()),
+			(0.50, train_column_stats.p50()),
+			(0.75, train_column_stats.p75()),
+		],
+	};
+	let number_column_quantiles_section = NumberColumnQuantilesSection {
+		rows: training_quantiles
+			.into_iter()
+			.map(|(quantile, training)| NumberColumnQuantilesRow {
+				quantile,
+				training,
+				production: overall.stats.as_ref().map(|stats| stats.quantile(quantile)),
+			})
+			.collect(),
+	};
+	let out_of_range_fraction = overall.out_of_range_fraction();
+	let alert = match out_of_range_fraction {
+		Some(out_of_range_fraction)
+			if out_of_range_fraction
+				> PRODUCTION_STATS_LARGE_OUT_OF_RANGE_RATIO_THRESHOLD_TO_TRIGGER_ALERT =>
+		{
+			Some("High Out of Range Fraction".to_owned())
+		}
+		_ => None,
+	};
 	NumberColumn {
 		column_name: train_column_stats.column_name().to_owned(),
 		date_window,
 		date_window_interval,
-		alert: None,
+		alert,
 		number_column_counts_section: NumberColumnCountsSection {
 			absent_count: overall.absent_count,
 			row_count: get_production_stats_output.overall.row_count,
@@ -272,6 +360,11 @@ fn number_column(
 			min_comparison,
 			std_comparison,
 		},
+		number_column_quantiles_section,
+		number_column_outliers_section: NumberColumnOutliersSection {
+			out_of_range_fraction,
+			iqr_outlier_fraction: overall.iqr_outlier_fraction(),
+		},
 	}
 }
 
@@ -371,8 +464,8 @@ fn text_column(
 	get_production_stats_output: GetProductionStatsOutput,
 	train_column_stats: modelfox_model::TextColumnStatsReader,
 	date_window: DateWindow,
-	_date_window_interval: DateWindowInterval,
-	_timezone: Tz,
+	date_window_interval: DateWindowInterval,
+	timezone: Tz,
 ) -> TextColumn {
 	let overall = get_production_stats_output
 		.overall
@@ -401,13 +494,56 @@ fn text_column(
 		.iter()
 		.map(|(ngram, entry)| (ngram.to_string(), entry.row_count))
 		.collect();
+	let interval_unseen_token_fractions = get_production_stats_output
+		.intervals
+		.iter()
+		.map(|interval| {
+			let production_column_stats = interval
+				.column_stats
+				.iter()
+				.find(|production_column_stats| {
+					production_column_stats.column_name() == train_column_stats.column_name()
+				})
+				.unwrap();
+			let production_column_stats = match production_column_stats {
+				ProductionColumnStatsOutput::Text(production_column_stats) => {
+					production_column_stats
+				}
+				_ => unreachable!(),
+			};
+			IntervalUnseenTokenFraction {
+				label: format_date_window_interval(
+					interval.start_date,
+					&date_window_interval,
+					timezone,
+				),
+				unseen_token_fraction: production_column_stats.unseen_token_fraction(),
+			}
+		})
+		.collect();
+	let unseen_token_fraction = overall.unseen_token_fraction();
+	let alert = match unseen_token_fraction {
+		Some(unseen_token_fraction)
+			if unseen_token_fraction
+				> PRODUCTION_STATS_LARGE_UNSEEN_TOKEN_RATIO_THRESHOLD_TO_TRIGGER_ALERT =>
+		{
+			Some("High Unseen Token Fraction".to_owned())
+		}
+		_ => None,
+	};
 	TextColumn {
-		alert: None,
+		alert,
 		text_column_counts_section: TextColumnCountsSection {
 			row_count: get_production_stats_output.overall.row_count,
 			absent_count: overall.absent_count,
 			invalid_count: overall.invalid_count,
 		},
+		text_column_unseen_tokens_section: TextColumnUnseenTokensSection {
+			column_name: overall.column_name.to_owned(),
+			date_window_interval,
+			unseen_token_fraction,
+			interval_unseen_token_fractions,
+		},
 		text_ngrams_section: TextColumnTokensSection {
 			text_ngrams_table: TextNGramsTable {
 				rows: text_ngrams_table_rows,
@@ -1,5 +1,7 @@
 use crate::page::{IntervalBoxChartDataPoint, OverallBoxChartData};
+use modelfox_app_core::alert::AlertMetric;
 use modelfox_app_date_window::{DateWindow, DateWindowInterval};
+use modelfox_app_production_stats::OUTLIER_IQR_MULTIPLIER;
 use modelfox_app_ui::{
 	colors::{PRODUCTION_COLOR, TRAINING_COLOR},
 	metrics_row::MetricsRow,
@@ -20,6 +22,8 @@ pub struct NumberColumn {
 	pub interval_box_chart_data: Vec<IntervalBoxChartDataPoint>,
 	pub overall_box_chart_data: OverallBoxChartData,
 	pub number_column_stats_section: NumberColumnStatsSection,
+	pub number_column_quantiles_section: NumberColumnQuantilesSection,
+	pub number_column_outliers_section: NumberColumnOutliersSection,
 }
 
 pub struct NumberTrainingProductionComparison {
@@ -110,6 +114,8 @@ impl Component for NumberColumn {
 				)),
 			)
 			.child(self.number_column_stats_section)
+			.child(self.number_column_quantiles_section)
+			.child(self.number_column_outliers_section)
 			.into_node()
 	}
 }
@@ -209,3 +215,73 @@ impl Component for NumberColumnStatsSection {
 			.into_node()
 	}
 }
+
+pub struct NumberColumnOutliersSection {
+	pub out_of_range_fraction: Option<f32>,
+	pub iqr_outlier_fraction: Option<f32>,
+}
+
+impl Component for NumberColumnOutliersSection {
+	fn into_node(self) -> Node {
+		let description = format!(
+			"Out of range values are production values below the training min or above the training max. Outliers are production values more than {} interquartile ranges below the training p25 or above the training p75.",
+			OUTLIER_IQR_MULTIPLIER,
+		);
+		ui::S2::new()
+			.child(ui::H2::new("Outliers"))
+			.child(ui::P::new().child(description))
+			.child(
+				MetricsRow::new()
+					.child(ui::NumberCard::formatted(
+						"Out of Range Fraction".to_owned(),
+						self.out_of_range_fraction,
+						&AlertMetric::OutOfRangeFraction.number_formatter(),
+					))
+					.child(ui::NumberCard::new(
+						"Outlier Fraction".to_owned(),
+						self.iqr_outlier_fraction
+							.map(ui::format_percent)
+							.unwrap_or_else(|| "N/A".to_owned()),
+					)),
+			)
+			.into_node()
+	}
+}
+
+pub struct NumberColumnQuantilesSection {
+	pub rows: Vec<NumberColumnQuantilesRow>,
+}
+
+pub struct NumberColumnQuantilesRow {
+	pub quantile: f32,
+	pub training: f32,
+	pub production: Option<f32>,
+}
+
+impl Component for NumberColumnQuantilesSection {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("Quantiles"))
+			.child(
+				ui::Table::new()
+					.width("100%".to_owned())
+					.child(
+						ui::TableHeader::new().child(
+							ui::TableRow::new()
+								.child(ui::TableHeaderCell::new().child("Quantile"))
+								.child(ui::TableHeaderCell::new().child("Training"))
+								.child(ui::TableHeaderCell::new().child("Production")),
+						),
+					)
+					.child(ui::TableBody::new().children(self.rows.iter().map(|row| {
+						ui::TableRow::new()
+							.child(ui::TableCell::new().child(ui::format_quantile(row.quantile)))
+							.child(ui::TableCell::new().child(ui::format_float(row.training)))
+							.child(
+								ui::TableCell::new().child(ui::format_option_float(row.production)),
+							)
+					}))),
+			)
+			.into_node()
+	}
+}
@@ -1,19 +1,23 @@
 pub use crate::{enum_column::*, number_column::*, text_column::*};
-use modelfox_app_date_window::DateWindow;
+use modelfox_app_date_window::{DateWindow, DateWindowInterval};
 use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
 };
-use modelfox_app_ui::date_window_select_field::DateWindowSelectField;
+use modelfox_app_ui::{
+	date_window_select_field::DateWindowSelectField, stale_stats_alert::StaleStatsAlert,
+};
 use modelfox_ui as ui;
 use pinwheel::prelude::*;
 
 pub struct Page {
 	pub column_name: String,
 	pub date_window: DateWindow,
+	pub date_window_interval: DateWindowInterval,
 	pub id: String,
 	pub inner: Inner,
 	pub model_layout_info: ModelLayoutInfo,
+	pub stale_stats_alert: Option<StaleStatsAlert>,
 }
 
 pub enum Inner {
@@ -63,7 +67,9 @@ impl Component for Page {
 						.child(ui::H1::new(self.column_name))
 						.child(DateWindowSelectForm {
 							date_window: self.date_window,
+							date_window_interval: self.date_window_interval,
 						})
+						.child(self.stale_stats_alert)
 						.child(inner),
 				),
 			)
@@ -73,12 +79,16 @@ impl Component for Page {
 
 pub struct DateWindowSelectForm {
 	date_window: DateWindow,
+	date_window_interval: DateWindowInterval,
 }
 
 impl Component for DateWindowSelectForm {
 	fn into_node(self) -> Node {
 		ui::Form::new()
-			.child(DateWindowSelectField::new(self.date_window))
+			.child(DateWindowSelectField::new(
+				self.date_window,
+				self.date_window_interval,
+			))
 			.child(
 				noscript().child(
 					ui::Button::new()
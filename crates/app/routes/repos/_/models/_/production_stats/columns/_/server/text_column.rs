@@ -1,11 +1,17 @@
-use modelfox_app_date_window::DateWindow;
+use modelfox_app_core::alert::AlertMetric;
+use modelfox_app_date_window::{DateWindow, DateWindowInterval};
 use modelfox_app_ui::{
-	colors::PRODUCTION_COLOR, metrics_row::MetricsRow, time::overall_chart_title,
+	colors::PRODUCTION_COLOR,
+	metrics_row::MetricsRow,
+	time::{interval_chart_title, overall_chart_title},
 };
 use modelfox_charts::{
 	bar_chart::{BarChartPoint, BarChartSeries},
-	components::BarChart,
+	common::GridLineInterval,
+	components::{BarChart, LineChart},
+	line_chart::{LineChartPoint, LineChartSeries},
 };
+use modelfox_finite::Finite;
 use modelfox_ui as ui;
 use num::ToPrimitive;
 use pinwheel::prelude::*;
@@ -14,6 +20,7 @@ pub struct TextColumn {
 	pub alert: Option<String>,
 	pub text_column_counts_section: TextColumnCountsSection,
 	pub text_column_stats_section: TextColumnStatsSection,
+	pub text_column_unseen_tokens_section: TextColumnUnseenTokensSection,
 	pub text_ngrams_section: TextColumnTokensSection,
 }
 
@@ -26,6 +33,7 @@ impl Component for TextColumn {
 			)
 			.child(self.text_column_stats_section)
 			.child(self.text_column_counts_section)
+			.child(self.text_column_unseen_tokens_section)
 			.child(self.text_ngrams_section)
 			.into_node()
 	}
@@ -49,6 +57,7 @@ impl Component for TextColumnStatsSection {
 					label: ngram.to_owned(),
 					x: index.to_f64().unwrap(),
 					y: Some(row_count.to_f64().unwrap()),
+					error: None,
 				})
 				.collect(),
 			title: Some("Production".to_owned()),
@@ -101,6 +110,72 @@ impl Component for TextColumnCountsSection {
 	}
 }
 
+pub struct TextColumnUnseenTokensSection {
+	pub column_name: String,
+	pub date_window_interval: DateWindowInterval,
+	pub unseen_token_fraction: Option<f32>,
+	pub interval_unseen_token_fractions: Vec<IntervalUnseenTokenFraction>,
+}
+
+pub struct IntervalUnseenTokenFraction {
+	pub label: String,
+	pub unseen_token_fraction: Option<f32>,
+}
+
+impl Component for TextColumnUnseenTokensSection {
+	fn into_node(self) -> Node {
+		let chart_labels = self
+			.interval_unseen_token_fractions
+			.iter()
+			.map(|entry| entry.label.clone())
+			.collect::<Vec<_>>();
+		let chart_series = vec![LineChartSeries {
+			color: PRODUCTION_COLOR.to_owned(),
+			data: self
+				.interval_unseen_token_fractions
+				.iter()
+				.enumerate()
+				.map(|(index, entry)| LineChartPoint {
+					x: Finite::new(index.to_f64().unwrap()).unwrap(),
+					y: entry.unseen_token_fraction.map(|unseen_token_fraction| {
+						Finite::new(unseen_token_fraction.to_f64().unwrap()).unwrap()
+					}),
+				})
+				.collect(),
+			line_style: None,
+			point_style: None,
+			title: Some("Unseen Token Fraction".to_owned()),
+		}];
+		let chart_title = interval_chart_title(
+			&self.date_window_interval,
+			format!("Unseen Token Fraction for {}", self.column_name),
+		);
+		ui::S2::new()
+			.child(ui::H2::new("Unseen Tokens"))
+			.child(ui::P::new().child(
+				"Unseen tokens are production tokens that are not in the vocabulary the model learned from the training data. A rising unseen token fraction is an early sign that the text in production is drifting away from the text the model was trained on.",
+			))
+			.child(MetricsRow::new().child(ui::NumberCard::formatted(
+				"Unseen Token Fraction".to_owned(),
+				self.unseen_token_fraction,
+				&AlertMetric::UnseenTokenFraction.number_formatter(),
+			)))
+			.child(
+				ui::Card::new().child(Dehydrate::new(
+					"text_unseen_tokens",
+					LineChart::new()
+						.labels(chart_labels)
+						.series(chart_series)
+						.title(chart_title)
+						.x_axis_grid_line_interval(GridLineInterval { k: 1.0, p: 0.0 })
+						.y_max(Finite::new(1.0).unwrap())
+						.y_min(Finite::new(0.0).unwrap()),
+				)),
+			)
+			.into_node()
+	}
+}
+
 pub struct TextColumnTokensSection {
 	pub text_ngrams_table: TextNGramsTable,
 }
@@ -6,6 +6,7 @@ pub fn main() {
 	modelfox_ui::client_start();
 	let document = dom::window().unwrap().document().unwrap();
 	ui::select_field_submit_on_change("date_window_select_field".to_owned());
+	ui::select_field_submit_on_change("date_window_interval_select_field".to_owned());
 	if document.get_element_by_id("class_select_field").is_some() {
 		ui::select_field_submit_on_change("class_select_field".to_owned());
 	}
@@ -24,4 +25,7 @@ pub fn main() {
 	if document.get_element_by_id("histogram_intervals").is_some() {
 		hydrate::<modelfox_charts::components::BarChart>("histogram_intervals");
 	}
+	if document.get_element_by_id("column_drift").is_some() {
+		hydrate::<modelfox_charts::components::BarChart>("column_drift");
+	}
 }
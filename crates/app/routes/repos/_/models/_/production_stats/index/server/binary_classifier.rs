@@ -1,7 +1,7 @@
 use crate::common::{
 	ClassificationProductionStatsChart, ClassificationProductionStatsIntervalChart,
-	ClassifierChartEntry, ColumnStatsTable, DateWindowSelectForm, PredictionCountChart,
-	PredictionCountChartEntry,
+	ClassifierChartEntry, ColumnDriftSection, ColumnStatsTable, DateWindowSelectForm,
+	PredictionCountChart, PredictionCountChartEntry,
 };
 use modelfox_app_date_window::{DateWindow, DateWindowInterval};
 use modelfox_ui as ui;
@@ -13,6 +13,7 @@ pub struct BinaryClassifier {
 	pub prediction_count_chart: Vec<PredictionCountChartEntry>,
 	pub prediction_stats_chart: ClassifierChartEntry,
 	pub prediction_stats_interval_chart: Vec<ClassifierChartEntry>,
+	pub column_drift_section: Option<ColumnDriftSection>,
 	pub overall_column_stats_table: ColumnStatsTable,
 }
 
@@ -22,6 +23,7 @@ impl Component for BinaryClassifier {
 			.child(ui::H1::new("Production Stats"))
 			.child(DateWindowSelectForm {
 				date_window: self.date_window,
+				date_window_interval: self.date_window_interval,
 			})
 			.child(
 				ui::Card::new().child(ClassificationProductionStatsIntervalChart {
@@ -37,6 +39,7 @@ impl Component for BinaryClassifier {
 				chart_data: self.prediction_stats_chart,
 				date_window: self.date_window,
 			}))
+			.child(self.column_drift_section)
 			.child(self.overall_column_stats_table)
 			.into_node()
 	}
@@ -69,6 +69,7 @@ impl Component for ClassificationProductionStatsIntervalChart {
 							label: entry.label.to_owned(),
 							x: entry_index.to_f64().unwrap(),
 							y: Some(entry.histogram.production[index].1.to_f64().unwrap()),
+							error: None,
 						})
 						.collect::<Vec<_>>(),
 					title: Some(class.to_owned()),
@@ -119,6 +120,7 @@ impl Component for ClassificationProductionStatsChart {
 						label: self.chart_data.label.to_owned(),
 						x: 0.0,
 						y: Some(entry.1.to_f64().unwrap()),
+						error: None,
 					}],
 					title: Some(class.to_owned()),
 				}
@@ -149,6 +151,7 @@ impl Component for PredictionCountChart {
 					label: entry.label,
 					x: index.to_f64().unwrap(),
 					y: Some(entry.count.to_f64().unwrap()),
+					error: None,
 				})
 				.collect::<Vec<_>>(),
 			title: Some("Prediction Count".to_owned()),
@@ -175,6 +178,7 @@ pub struct ColumnStatsTable {
 pub struct ColumnStatsTableRow {
 	pub absent_count: u64,
 	pub invalid_count: u64,
+	pub invalid_value_policy: String,
 	pub alert: Option<String>,
 	pub href: Option<String>,
 	pub name: String,
@@ -192,7 +196,8 @@ impl Component for ColumnStatsTable {
 						.child(ui::TableHeaderCell::new().child("Column"))
 						.child(ui::TableHeaderCell::new().child("Type"))
 						.child(ui::TableHeaderCell::new().child("Absent Count"))
-						.child(ui::TableHeaderCell::new().child("Invalid Count")),
+						.child(ui::TableHeaderCell::new().child("Invalid Count"))
+						.child(ui::TableHeaderCell::new().child("Invalid Value Policy")),
 				),
 			)
 			.child(
@@ -215,20 +220,111 @@ impl Component for ColumnStatsTable {
 						.child(ui::TableCell::new().child(ColumnTypeToken::new(row.column_type)))
 						.child(ui::TableCell::new().child(row.absent_count.to_string()))
 						.child(ui::TableCell::new().child(row.invalid_count.to_string()))
+						.child(ui::TableCell::new().child(row.invalid_value_policy))
 				})),
 			)
 			.into_node()
 	}
 }
 
+pub struct ColumnDriftSection {
+	pub date_window: DateWindow,
+	pub rows: Vec<ColumnDriftRow>,
+}
+
+pub struct ColumnDriftRow {
+	pub alert: Option<String>,
+	pub href: String,
+	pub name: String,
+	pub psi: f32,
+}
+
+impl Component for ColumnDriftSection {
+	fn into_node(self) -> Node {
+		let description = "The chart and table below rank the columns whose production distribution has drifted the most from their training distribution, as measured by the population stability index (PSI). A PSI above 0.25 usually indicates a significant shift.";
+		let bar_chart_series = vec![BarChartSeries {
+			color: ui::colors::ORANGE.to_owned(),
+			data: self
+				.rows
+				.iter()
+				.enumerate()
+				.map(|(index, row)| BarChartPoint {
+					label: row.name.clone(),
+					x: index.to_f64().unwrap(),
+					y: Some(row.psi.to_f64().unwrap()),
+					error: None,
+				})
+				.collect(),
+			title: Some("PSI".to_owned()),
+		}];
+		let title = overall_chart_title(&self.date_window, "Most Drifted Columns".to_owned());
+		ui::S2::new()
+			.child(ui::H2::new("Column Drift"))
+			.child(ui::P::new().child(description))
+			.child(
+				ui::Card::new().child(Dehydrate::new(
+					"column_drift",
+					BarChart::new()
+						.series(bar_chart_series)
+						.title(title)
+						.x_axis_title("Column Name".to_owned())
+						.y_axis_title("PSI".to_owned())
+						.y_min(0.0),
+				)),
+			)
+			.child(
+				ui::Table::new()
+					.width("100%".to_owned())
+					.child(
+						ui::TableHeader::new().child(
+							ui::TableRow::new()
+								.child(ui::TableHeaderCell::new().child("Status"))
+								.child(ui::TableHeaderCell::new().child("Rank"))
+								.child(ui::TableHeaderCell::new().child("Column"))
+								.child(ui::TableHeaderCell::new().child("PSI")),
+						),
+					)
+					.child(
+						ui::TableBody::new().children(self.rows.into_iter().enumerate().map(
+							|(index, row)| {
+								ui::TableRow::new()
+									.child(ui::TableCell::new().child(
+										if let Some(alert) = row.alert {
+											ui::AlertIcon::new(alert, ui::Level::Danger).child("!")
+										} else {
+											ui::AlertIcon::new(
+												"All good".to_owned(),
+												ui::Level::Success,
+											)
+											.child("✓")
+										},
+									))
+									.child(ui::TableCell::new().child((index + 1).to_string()))
+									.child(
+										ui::TableCell::new()
+											.child(ui::Link::new().href(row.href).child(row.name)),
+									)
+									.child(ui::TableCell::new().child(ui::format_float(row.psi)))
+							},
+						)),
+					),
+			)
+			.into_node()
+	}
+}
+
 pub struct DateWindowSelectForm {
 	pub date_window: DateWindow,
+	pub date_window_interval: DateWindowInterval,
 }
 
 impl Component for DateWindowSelectForm {
 	fn into_node(self) -> Node {
 		ui::Form::new()
-			.child(DateWindowSelectField::new(self.date_window))
+			.child(DateWindowSelectField::new(
+				self.date_window,
+				self.date_window_interval,
+			))
 			.child(
 				noscript().child(
 					ui::Button::new()
@@ -1,5 +1,5 @@
 use crate::{
-	common::{ColumnStatsTable, ColumnStatsTableRow},
+	common::{ColumnDriftRow, ColumnDriftSection, ColumnStatsTable, ColumnStatsTableRow},
 	page::{
 		BinaryClassifier, ClassifierChartEntry, Inner, MulticlassClassifier, Page,
 		PredictionCountChartEntry, ProductionTrainingHistogram, ProductionTrainingQuantiles,
@@ -14,28 +14,33 @@ use modelfox_app_core::{
 	heuristics::{
 		PRODUCTION_STATS_LARGE_ABSENT_RATIO_THRESHOLD_TO_TRIGGER_ALERT,
 		PRODUCTION_STATS_LARGE_INVALID_RATIO_THRESHOLD_TO_TRIGGER_ALERT,
+		PRODUCTION_STATS_LARGE_PSI_THRESHOLD_TO_TRIGGER_ALERT,
+		PRODUCTION_STATS_MAX_DRIFTED_COLUMNS_TO_SHOW_IN_CHART,
 	},
 	model::get_model_bytes,
 	path_components,
-	timezone::get_timezone,
+	timezone::get_timezone_for_model,
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_date_window::{get_date_window_and_interval, DateWindow, DateWindowInterval};
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
 use modelfox_app_production_stats::{
-	get_production_stats, GetProductionStatsOutput, ProductionColumnStatsOutput,
-	ProductionPredictionStatsOutput, RegressionProductionPredictionStatsOutput,
+	compute_column_drifts, get_production_stats, get_rolled_up_production_stats,
+	GetProductionStatsOutput, ProductionColumnStatsOutput, ProductionPredictionStatsOutput,
+	ProductionStatsOutput, RegressionProductionPredictionStatsOutput,
 };
 use modelfox_app_ui::column_type::ColumnType;
+use modelfox_app_ui::stale_stats_alert::StaleStatsAlert;
 use modelfox_app_ui::time::{format_date_window, format_date_window_interval};
 use modelfox_id::Id;
 use num::ToPrimitive;
 use pinwheel::prelude::*;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 #[derive(serde::Deserialize, Default)]
 struct SearchParams {
 	date_window: Option<DateWindow>,
+	date_window_interval: Option<DateWindowInterval>,
 	class: Option<String>,
 }
 
@@ -57,19 +62,23 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let date_window = search_params
 		.as_ref()
 		.and_then(|search_params| search_params.date_window);
-	let (date_window, date_window_interval) = match get_date_window_and_interval(&date_window) {
-		Some((date_window, date_window_interval)) => (date_window, date_window_interval),
-		None => return Ok(bad_request()),
-	};
-	let timezone = get_timezone(request);
+	let date_window_interval = search_params
+		.as_ref()
+		.and_then(|search_params| search_params.date_window_interval);
+	let (date_window, date_window_interval) =
+		match get_date_window_and_interval(&date_window, &date_window_interval) {
+			Some((date_window, date_window_interval)) => (date_window, date_window_interval),
+			None => return Ok(bad_request()),
+		};
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -77,10 +86,53 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
 	let bytes = get_model_bytes(app.storage(), model_id).await?;
 	let model = modelfox_model::from_bytes(&bytes)?;
-	let production_stats =
-		get_production_stats(&mut db, model, date_window, date_window_interval, timezone).await?;
+	let mut read_db = match app.begin_analytics_transaction().await {
+		Ok(read_db) => read_db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let production_stats = app
+		.with_analytics_statement_timeout(get_production_stats(
+			&mut read_db,
+			model,
+			date_window,
+			date_window_interval,
+			timezone,
+		))
+		.await?;
+	let production_stats = match production_stats {
+		Some(production_stats) => {
+			app.commit_transaction(read_db).await?;
+			production_stats
+		}
+		None => {
+			// Reading the production stats took too long, so fall back to the daily rollups, which are much cheaper to read.
+			drop(read_db);
+			let mut read_db = match app.begin_read_transaction().await {
+				Ok(read_db) => read_db,
+				Err(_) => return Ok(service_unavailable()),
+			};
+			let production_stats = get_rolled_up_production_stats(
+				&mut read_db,
+				model,
+				date_window,
+				date_window_interval,
+				timezone,
+			)
+			.await?;
+			app.commit_transaction(read_db).await?;
+			production_stats
+		}
+	};
+	let stale_stats_alert =
+		production_stats
+			.rolled_up_through
+			.map(|rolled_up_through| StaleStatsAlert {
+				rolled_up_through,
+				timezone,
+			});
 	let inner = match production_stats.overall.prediction_stats {
 		ProductionPredictionStatsOutput::Regression(_) => Inner::Regressor(compute_regressor(
 			model,
@@ -114,6 +166,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let page = Page {
 		model_id: model_id.to_string(),
 		model_layout_info,
+		stale_stats_alert,
 		inner,
 	};
 	let html = html(page);
@@ -156,17 +209,29 @@ fn compute_regressor(
 	date_window_interval: DateWindowInterval,
 	timezone: Tz,
 ) -> Regressor {
+	let column_drift_section =
+		compute_column_drift_section(model, &production_stats.overall, date_window);
 	let model = model.inner().as_regressor().unwrap();
+	let invalid_value_policies = model
+		.invalid_value_policies()
+		.map(|invalid_value_policies| {
+			invalid_value_policies
+				.iter()
+				.map(invalid_value_policy_label)
+				.collect()
+		})
+		.unwrap_or_default();
 	let target_column_stats = model.overall_target_column_stats();
 	let overall_column_stats_table = compute_overall_column_stats_table(
 		production_stats.overall.column_stats,
 		production_stats.overall.row_count,
+		&invalid_value_policies,
 	);
 	let prediction_count_chart = production_stats
 		.intervals
 		.iter()
 		.map(|interval| PredictionCountChartEntry {
-			count: interval.row_count,
+			count: interval.estimated_row_count,
 			label: format_date_window_interval(
 				interval.start_date,
 				&date_window_interval,
@@ -216,6 +281,7 @@ fn compute_regressor(
 		prediction_count_chart,
 		prediction_stats_chart,
 		prediction_stats_interval_chart,
+		column_drift_section,
 		overall_column_stats_table,
 	}
 }
@@ -227,13 +293,24 @@ fn compute_binary_classifier(
 	date_window_interval: DateWindowInterval,
 	timezone: Tz,
 ) -> BinaryClassifier {
+	let column_drift_section =
+		compute_column_drift_section(model, &production_stats.overall, date_window);
 	let model = model.inner().as_binary_classifier().unwrap();
+	let invalid_value_policies = model
+		.invalid_value_policies()
+		.map(|invalid_value_policies| {
+			invalid_value_policies
+				.iter()
+				.map(invalid_value_policy_label)
+				.collect()
+		})
+		.unwrap_or_default();
 	let target_column_stats = model.overall_target_column_stats();
 	let prediction_count_chart = production_stats
 		.intervals
 		.iter()
 		.map(|interval| PredictionCountChartEntry {
-			count: interval.row_count,
+			count: interval.estimated_row_count,
 			label: format_date_window_interval(
 				interval.start_date,
 				&date_window_interval,
@@ -297,6 +374,7 @@ fn compute_binary_classifier(
 	let overall_column_stats_table = compute_overall_column_stats_table(
 		production_stats.overall.column_stats,
 		production_stats.overall.row_count,
+		&invalid_value_policies,
 	);
 	BinaryClassifier {
 		date_window,
@@ -304,6 +382,7 @@ fn compute_binary_classifier(
 		prediction_count_chart,
 		prediction_stats_chart,
 		prediction_stats_interval_chart,
+		column_drift_section,
 		overall_column_stats_table,
 	}
 }
@@ -316,7 +395,18 @@ fn compute_multiclass_classifier(
 	timezone: Tz,
 	search_params: Option<SearchParams>,
 ) -> MulticlassClassifier {
+	let column_drift_section =
+		compute_column_drift_section(model, &production_stats.overall, date_window);
 	let model = model.inner().as_multiclass_classifier().unwrap();
+	let invalid_value_policies = model
+		.invalid_value_policies()
+		.map(|invalid_value_policies| {
+			invalid_value_policies
+				.iter()
+				.map(invalid_value_policy_label)
+				.collect()
+		})
+		.unwrap_or_default();
 	let class = search_params.and_then(|s| s.class);
 	let classes = model.classes().to_owned();
 	let class_index = if let Some(class) = &class {
@@ -330,7 +420,7 @@ fn compute_multiclass_classifier(
 		.intervals
 		.iter()
 		.map(|interval| PredictionCountChartEntry {
-			count: interval.row_count,
+			count: interval.estimated_row_count,
 			label: format_date_window_interval(
 				interval.start_date,
 				&date_window_interval,
@@ -401,6 +491,7 @@ fn compute_multiclass_classifier(
 	let overall_column_stats_table = compute_overall_column_stats_table(
 		production_stats.overall.column_stats,
 		production_stats.overall.row_count,
+		&invalid_value_policies,
 	);
 	let classes = model
 		.classes()
@@ -415,6 +506,7 @@ fn compute_multiclass_classifier(
 		prediction_count_chart,
 		prediction_stats_chart,
 		prediction_stats_interval_chart,
+		column_drift_section,
 		overall_column_stats_table,
 	}
 }
@@ -435,16 +527,37 @@ fn alert_message(count: u64, absent_count: u64, invalid_count: u64) -> Option<St
 	}
 }
 
+/// Label the policy the model applies to invalid values in a column, so the invalid counts can be read as the number of values that were treated as missing, dropped, or rejected.
+fn invalid_value_policy_label(
+	column_invalid_value_policy: modelfox_model::ColumnInvalidValuePolicyReader,
+) -> (String, &'static str) {
+	let label = match column_invalid_value_policy.policy() {
+		modelfox_model::InvalidValuePolicyReader::Missing(_) => "Treat as Missing",
+		modelfox_model::InvalidValuePolicyReader::DropRow(_) => "Drop Row",
+		modelfox_model::InvalidValuePolicyReader::Error(_) => "Error",
+	};
+	(column_invalid_value_policy.column_name().to_owned(), label)
+}
+
 fn compute_overall_column_stats_table(
 	overall_production_column_stats: Vec<ProductionColumnStatsOutput>,
 	overall_production_stats_row_count: u64,
+	invalid_value_policies: &BTreeMap<String, &'static str>,
 ) -> ColumnStatsTable {
+	let invalid_value_policy = |column_name: &str| {
+		invalid_value_policies
+			.get(column_name)
+			.copied()
+			.unwrap_or("Treat as Missing")
+			.to_owned()
+	};
 	let rows = overall_production_column_stats
 		.iter()
 		.map(|column_stats| match column_stats {
 			ProductionColumnStatsOutput::Unknown(column_stats) => ColumnStatsTableRow {
 				absent_count: column_stats.absent_count,
 				invalid_count: column_stats.invalid_count,
+				invalid_value_policy: invalid_value_policy(&column_stats.column_name),
 				href: Some(format!("./columns/{}", column_stats.column_name)),
 				alert: alert_message(
 					overall_production_stats_row_count,
@@ -457,6 +570,7 @@ fn compute_overall_column_stats_table(
 			ProductionColumnStatsOutput::Text(column_stats) => ColumnStatsTableRow {
 				absent_count: column_stats.absent_count,
 				invalid_count: column_stats.invalid_count,
+				invalid_value_policy: invalid_value_policy(&column_stats.column_name),
 				href: Some(format!("./columns/{}", column_stats.column_name)),
 				alert: alert_message(
 					overall_production_stats_row_count,
@@ -469,6 +583,7 @@ fn compute_overall_column_stats_table(
 			ProductionColumnStatsOutput::Number(column_stats) => ColumnStatsTableRow {
 				absent_count: column_stats.absent_count,
 				invalid_count: column_stats.invalid_count,
+				invalid_value_policy: invalid_value_policy(&column_stats.column_name),
 				href: Some(format!("./columns/{}", column_stats.column_name)),
 				alert: alert_message(
 					overall_production_stats_row_count,
@@ -481,6 +596,7 @@ fn compute_overall_column_stats_table(
 			ProductionColumnStatsOutput::Enum(column_stats) => ColumnStatsTableRow {
 				absent_count: column_stats.absent_count,
 				invalid_count: column_stats.invalid_count,
+				invalid_value_policy: invalid_value_policy(&column_stats.column_name),
 				href: Some(format!("./columns/{}", column_stats.column_name)),
 				alert: alert_message(
 					overall_production_stats_row_count,
@@ -490,7 +606,48 @@ fn compute_overall_column_stats_table(
 				name: column_stats.column_name.to_owned(),
 				column_type: ColumnType::Enum,
 			},
+			ProductionColumnStatsOutput::DateTime(column_stats) => ColumnStatsTableRow {
+				absent_count: column_stats.absent_count,
+				invalid_count: column_stats.invalid_count,
+				invalid_value_policy: invalid_value_policy(&column_stats.column_name),
+				href: None,
+				alert: alert_message(
+					overall_production_stats_row_count,
+					column_stats.absent_count,
+					column_stats.invalid_count,
+				),
+				name: column_stats.column_name.to_owned(),
+				column_type: ColumnType::DateTime,
+			},
 		})
 		.collect::<Vec<_>>();
 	ColumnStatsTable { rows }
 }
+
+fn compute_column_drift_section(
+	model: modelfox_model::ModelReader,
+	overall_production_stats: &ProductionStatsOutput,
+	date_window: DateWindow,
+) -> Option<ColumnDriftSection> {
+	if overall_production_stats.row_count == 0 {
+		return None;
+	}
+	let rows = compute_column_drifts(model, &overall_production_stats.column_stats)
+		.into_iter()
+		.take(PRODUCTION_STATS_MAX_DRIFTED_COLUMNS_TO_SHOW_IN_CHART)
+		.map(|column_drift| ColumnDriftRow {
+			alert: if column_drift.psi > PRODUCTION_STATS_LARGE_PSI_THRESHOLD_TO_TRIGGER_ALERT {
+				Some("Large Drift".to_owned())
+			} else {
+				None
+			},
+			href: format!("./columns/{}", column_drift.column_name),
+			name: column_drift.column_name,
+			psi: column_drift.psi,
+		})
+		.collect::<Vec<_>>();
+	if rows.is_empty() {
+		return None;
+	}
+	Some(ColumnDriftSection { date_window, rows })
+}
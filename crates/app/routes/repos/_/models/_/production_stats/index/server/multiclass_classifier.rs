@@ -1,6 +1,7 @@
 use crate::common::{
 	ClassificationProductionStatsChart, ClassificationProductionStatsIntervalChart,
-	ClassifierChartEntry, ColumnStatsTable, PredictionCountChart, PredictionCountChartEntry,
+	ClassifierChartEntry, ColumnDriftSection, ColumnStatsTable, PredictionCountChart,
+	PredictionCountChartEntry,
 };
 use modelfox_app_date_window::{DateWindow, DateWindowInterval};
 use modelfox_app_ui::{
@@ -17,6 +18,7 @@ pub struct MulticlassClassifier {
 	pub prediction_count_chart: Vec<PredictionCountChartEntry>,
 	pub prediction_stats_chart: ClassifierChartEntry,
 	pub prediction_stats_interval_chart: Vec<ClassifierChartEntry>,
+	pub column_drift_section: Option<ColumnDriftSection>,
 	pub overall_column_stats_table: ColumnStatsTable,
 }
 
@@ -44,6 +46,7 @@ impl Component for MulticlassClassifier {
 				chart_data: self.prediction_stats_chart,
 				date_window: self.date_window,
 			}))
+			.child(self.column_drift_section)
 			.child(self.overall_column_stats_table)
 			.into_node()
 	}
@@ -59,7 +62,10 @@ pub struct DateWindowAndClassSelectForm {
 impl Component for DateWindowAndClassSelectForm {
 	fn into_node(self) -> Node {
 		ui::Form::new()
-			.child(DateWindowSelectField::new(self.date_window))
+			.child(DateWindowSelectField::new(
+				self.date_window,
+				self.date_window_interval,
+			))
 			.child(ClassSelectField {
 				class: self.class.clone(),
 				classes: self.classes,
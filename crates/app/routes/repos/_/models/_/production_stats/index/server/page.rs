@@ -8,11 +8,13 @@ use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
 };
+use modelfox_app_ui::stale_stats_alert::StaleStatsAlert;
 use pinwheel::prelude::*;
 
 pub struct Page {
 	pub model_id: String,
 	pub model_layout_info: ModelLayoutInfo,
+	pub stale_stats_alert: Option<StaleStatsAlert>,
 	pub inner: Inner,
 }
 
@@ -31,7 +33,11 @@ impl Component for Page {
 		};
 		Document::new()
 			.client("modelfox_app_production_stats_index_client")
-			.child(ModelLayout::new(self.model_layout_info).child(inner))
+			.child(
+				ModelLayout::new(self.model_layout_info)
+					.child(self.stale_stats_alert)
+					.child(inner),
+			)
 			.into_node()
 	}
 }
@@ -1,5 +1,6 @@
 use crate::common::{
-	ColumnStatsTable, DateWindowSelectForm, PredictionCountChart, PredictionCountChartEntry,
+	ColumnDriftSection, ColumnStatsTable, DateWindowSelectForm, PredictionCountChart,
+	PredictionCountChartEntry,
 };
 use modelfox_app_date_window::{DateWindow, DateWindowInterval};
 use modelfox_app_ui::time::{interval_chart_title, overall_chart_title};
@@ -17,6 +18,7 @@ pub struct Regressor {
 	pub prediction_count_chart: Vec<PredictionCountChartEntry>,
 	pub prediction_stats_chart: RegressorChartEntry,
 	pub prediction_stats_interval_chart: Vec<RegressorChartEntry>,
+	pub column_drift_section: Option<ColumnDriftSection>,
 	pub overall_column_stats_table: ColumnStatsTable,
 }
 
@@ -26,6 +28,7 @@ impl Component for Regressor {
 			.child(ui::H1::new("Production Stats"))
 			.child(DateWindowSelectForm {
 				date_window: self.date_window,
+				date_window_interval: self.date_window_interval,
 			})
 			.child(
 				ui::Card::new().child(RegressionProductionStatsIntervalChart {
@@ -41,6 +44,7 @@ impl Component for Regressor {
 				chart_data: self.prediction_stats_chart,
 				date_window: self.date_window,
 			}))
+			.child(self.column_drift_section)
 			.child(self.overall_column_stats_table)
 			.into_node()
 	}
@@ -0,0 +1,410 @@
+use crate::page::{ClassMetricsRow, ColumnRow, FeatureImportances, MetricRow, Overview, Report};
+use anyhow::{bail, Result};
+use chrono::prelude::*;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	heuristics::REPORT_MAX_FEATURE_IMPORTANCES_TO_SHOW_IN_CHART,
+	model::get_model_bytes,
+	path_components,
+	repos::{get_model_repo_id, get_repo},
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_id::Id;
+use modelfox_zip::zip_eq;
+use pinwheel::prelude::*;
+use std::sync::Arc;
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id =
+		if let ["repos", _, "models", model_id, "report"] = *path_components(request).as_slice() {
+			model_id.to_owned()
+		} else {
+			bail!("unexpected path");
+		};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let repo_id = get_model_repo_id(&mut db, model_id).await?;
+	let repo = get_repo(&mut db, repo_id).await?;
+	let bytes = get_model_bytes(app.storage(), model_id).await?;
+	let model = modelfox_model::from_bytes(&bytes)?;
+	let generated_at = Utc
+		.timestamp(app.clock().now_utc().unix_timestamp(), 0)
+		.format("%Y-%m-%d %H:%M UTC")
+		.to_string();
+	let report = compute_report(model, repo.title.clone(), model_id, generated_at);
+	let html = html(report);
+	let filename = format!("{} Report.html", repo.title.replace('"', ""));
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.header(http::header::CONTENT_TYPE, "text/html; charset=utf-8")
+		.header(
+			http::header::CONTENT_DISPOSITION,
+			format!("attachment; filename=\"{}\"", filename),
+		)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	app.commit_transaction(db).await?;
+	Ok(response)
+}
+
+fn compute_report(
+	model: modelfox_model::ModelReader,
+	title: String,
+	model_id: Id,
+	generated_at: String,
+) -> Report {
+	let feature_importances = compute_feature_importances(model);
+	match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			let regressor = regressor.read();
+			let test_metrics = regressor.test_metrics();
+			let baseline_metrics = regressor.baseline_metrics();
+			let model_type_name = match regressor.model() {
+				modelfox_model::RegressionModelReader::Linear(_) => "Linear Regressor",
+				modelfox_model::RegressionModelReader::Tree(_) => "Gradient Boosted Tree Regressor",
+			};
+			let comparison_metric_name = match regressor.comparison_metric() {
+				modelfox_model::RegressionComparisonMetricReader::MeanAbsoluteError(_) => {
+					"Mean Absolute Error"
+				}
+				modelfox_model::RegressionComparisonMetricReader::MeanSquaredError(_) => {
+					"Mean Squared Error"
+				}
+				modelfox_model::RegressionComparisonMetricReader::RootMeanSquaredError(_) => {
+					"Root Mean Squared Error"
+				}
+				modelfox_model::RegressionComparisonMetricReader::R2(_) => "R2",
+			};
+			Report {
+				title,
+				model_id: model_id.to_string(),
+				generated_at,
+				overview: Overview {
+					task_name: "regression".to_owned(),
+					model_type_name: model_type_name.to_owned(),
+					comparison_metric_name: comparison_metric_name.to_owned(),
+					column_count: regressor.overall_column_stats().len() + 1,
+					overall_row_count: regressor.overall_row_count(),
+					train_row_count: regressor.train_row_count(),
+					test_row_count: regressor.test_row_count(),
+				},
+				target_column: compute_column_row(&regressor.overall_target_column_stats()),
+				columns: regressor
+					.overall_column_stats()
+					.iter()
+					.map(|column_stats| compute_column_row(&column_stats))
+					.collect(),
+				metrics: vec![
+					MetricRow {
+						name: "Root Mean Squared Error".to_owned(),
+						value: Some(test_metrics.rmse()),
+						baseline_value: Some(baseline_metrics.rmse()),
+					},
+					MetricRow {
+						name: "Mean Squared Error".to_owned(),
+						value: Some(test_metrics.mse()),
+						baseline_value: Some(baseline_metrics.mse()),
+					},
+					MetricRow {
+						name: "Mean Absolute Error".to_owned(),
+						value: Some(test_metrics.mae()),
+						baseline_value: Some(baseline_metrics.mae()),
+					},
+					MetricRow {
+						name: "R2".to_owned(),
+						value: Some(test_metrics.r2()),
+						baseline_value: Some(baseline_metrics.r2()),
+					},
+				],
+				class_metrics: None,
+				feature_importances,
+			}
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			let binary_classifier = binary_classifier.read();
+			let test_metrics = binary_classifier.test_metrics();
+			let default_threshold_test_metrics = test_metrics.default_threshold();
+			let default_threshold_baseline_metrics =
+				binary_classifier.baseline_metrics().default_threshold();
+			let model_type_name = match binary_classifier.model() {
+				modelfox_model::BinaryClassificationModelReader::Linear(_) => {
+					"Linear Binary Classifier"
+				}
+				modelfox_model::BinaryClassificationModelReader::Tree(_) => {
+					"Gradient Boosted Tree Binary Classifier"
+				}
+			};
+			let comparison_metric_name = match binary_classifier.comparison_metric() {
+				modelfox_model::BinaryClassificationComparisonMetricReader::Aucroc(_) => {
+					"Area Under the Receiver Operating Characteristic Curve"
+				}
+				modelfox_model::BinaryClassificationComparisonMetricReader::F1(_) => "F1 Score",
+				modelfox_model::BinaryClassificationComparisonMetricReader::Precision(_) => {
+					"Precision"
+				}
+				modelfox_model::BinaryClassificationComparisonMetricReader::Recall(_) => "Recall",
+				modelfox_model::BinaryClassificationComparisonMetricReader::Accuracy(_) => {
+					"Accuracy"
+				}
+			};
+			Report {
+				title,
+				model_id: model_id.to_string(),
+				generated_at,
+				overview: Overview {
+					task_name: "binary classification".to_owned(),
+					model_type_name: model_type_name.to_owned(),
+					comparison_metric_name: comparison_metric_name.to_owned(),
+					column_count: binary_classifier.overall_column_stats().len() + 1,
+					overall_row_count: binary_classifier.overall_row_count(),
+					train_row_count: binary_classifier.train_row_count(),
+					test_row_count: binary_classifier.test_row_count(),
+				},
+				target_column: compute_column_row(&binary_classifier.overall_target_column_stats()),
+				columns: binary_classifier
+					.overall_column_stats()
+					.iter()
+					.map(|column_stats| compute_column_row(&column_stats))
+					.collect(),
+				metrics: vec![
+					MetricRow {
+						name: "AUC ROC".to_owned(),
+						value: Some(test_metrics.auc_roc()),
+						baseline_value: None,
+					},
+					MetricRow {
+						name: "Accuracy".to_owned(),
+						value: Some(default_threshold_test_metrics.accuracy()),
+						baseline_value: Some(default_threshold_baseline_metrics.accuracy()),
+					},
+					MetricRow {
+						name: "Precision".to_owned(),
+						value: default_threshold_test_metrics.precision(),
+						baseline_value: None,
+					},
+					MetricRow {
+						name: "Recall".to_owned(),
+						value: default_threshold_test_metrics.recall(),
+						baseline_value: None,
+					},
+					MetricRow {
+						name: "F1 Score".to_owned(),
+						value: default_threshold_test_metrics.f1_score(),
+						baseline_value: None,
+					},
+				],
+				class_metrics: None,
+				feature_importances,
+			}
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier = multiclass_classifier.read();
+			let test_metrics = multiclass_classifier.test_metrics();
+			let baseline_metrics = multiclass_classifier.baseline_metrics();
+			let model_type_name = match multiclass_classifier.model() {
+				modelfox_model::MulticlassClassificationModelReader::Linear(_) => {
+					"Linear Multiclass Classifier"
+				}
+				modelfox_model::MulticlassClassificationModelReader::Tree(_) => {
+					"Gradient Boosted Tree Multiclass Classifier"
+				}
+			};
+			let comparison_metric_name = match multiclass_classifier.comparison_metric() {
+				modelfox_model::MulticlassClassificationComparisonMetricReader::Accuracy(_) => {
+					"Accuracy"
+				}
+			};
+			let class_metrics = multiclass_classifier
+				.classes()
+				.iter()
+				.zip(test_metrics.class_metrics().iter())
+				.map(|(class_name, class_metrics)| ClassMetricsRow {
+					class_name: class_name.to_owned(),
+					precision: class_metrics.precision(),
+					recall: class_metrics.recall(),
+				})
+				.collect();
+			Report {
+				title,
+				model_id: model_id.to_string(),
+				generated_at,
+				overview: Overview {
+					task_name: "multiclass classification".to_owned(),
+					model_type_name: model_type_name.to_owned(),
+					comparison_metric_name: comparison_metric_name.to_owned(),
+					column_count: multiclass_classifier.overall_column_stats().len() + 1,
+					overall_row_count: multiclass_classifier.overall_row_count(),
+					train_row_count: multiclass_classifier.train_row_count(),
+					test_row_count: multiclass_classifier.test_row_count(),
+				},
+				target_column: compute_column_row(
+					&multiclass_classifier.overall_target_column_stats(),
+				),
+				columns: multiclass_classifier
+					.overall_column_stats()
+					.iter()
+					.map(|column_stats| compute_column_row(&column_stats))
+					.collect(),
+				metrics: vec![MetricRow {
+					name: "Accuracy".to_owned(),
+					value: Some(test_metrics.accuracy()),
+					baseline_value: Some(baseline_metrics.accuracy()),
+				}],
+				class_metrics: Some(class_metrics),
+				feature_importances,
+			}
+		}
+	}
+}
+
+fn compute_column_row(column_stats: &modelfox_model::ColumnStatsReader) -> ColumnRow {
+	match column_stats {
+		modelfox_model::ColumnStatsReader::UnknownColumn(column_stats) => ColumnRow {
+			name: column_stats.read().column_name().to_owned(),
+			column_type: "Unknown".to_owned(),
+			unique_count: None,
+			invalid_count: None,
+			min: None,
+			max: None,
+			mean: None,
+			std: None,
+		},
+		modelfox_model::ColumnStatsReader::NumberColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			ColumnRow {
+				name: column_stats.column_name().to_owned(),
+				column_type: "Number".to_owned(),
+				unique_count: Some(column_stats.unique_count()),
+				invalid_count: Some(column_stats.invalid_count()),
+				min: Some(column_stats.min()),
+				max: Some(column_stats.max()),
+				mean: Some(column_stats.mean()),
+				std: Some(column_stats.std()),
+			}
+		}
+		modelfox_model::ColumnStatsReader::EnumColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			ColumnRow {
+				name: column_stats.column_name().to_owned(),
+				column_type: "Enum".to_owned(),
+				unique_count: Some(column_stats.unique_count()),
+				invalid_count: Some(column_stats.invalid_count()),
+				min: None,
+				max: None,
+				mean: None,
+				std: None,
+			}
+		}
+		modelfox_model::ColumnStatsReader::TextColumn(column_stats) => ColumnRow {
+			name: column_stats.read().column_name().to_owned(),
+			column_type: "Text".to_owned(),
+			unique_count: None,
+			invalid_count: None,
+			min: None,
+			max: None,
+			mean: None,
+			std: None,
+		},
+		modelfox_model::ColumnStatsReader::DateTimeColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			ColumnRow {
+				name: column_stats.column_name().to_owned(),
+				column_type: "DateTime".to_owned(),
+				unique_count: Some(column_stats.unique_count()),
+				invalid_count: Some(column_stats.invalid_count()),
+				min: None,
+				max: None,
+				mean: None,
+				std: None,
+			}
+		}
+	}
+}
+
+fn compute_feature_importances(model: modelfox_model::ModelReader) -> Option<FeatureImportances> {
+	let (feature_groups, feature_importances) = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => match regressor.read().model() {
+			modelfox_model::RegressionModelReader::Linear(inner_model) => {
+				let inner_model = inner_model.read();
+				(
+					inner_model.feature_groups(),
+					inner_model.feature_importances(),
+				)
+			}
+			modelfox_model::RegressionModelReader::Tree(inner_model) => {
+				let inner_model = inner_model.read();
+				(
+					inner_model.feature_groups(),
+					inner_model.feature_importances(),
+				)
+			}
+		},
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			match binary_classifier.read().model() {
+				modelfox_model::BinaryClassificationModelReader::Linear(inner_model) => {
+					let inner_model = inner_model.read();
+					(
+						inner_model.feature_groups(),
+						inner_model.feature_importances(),
+					)
+				}
+				modelfox_model::BinaryClassificationModelReader::Tree(inner_model) => {
+					let inner_model = inner_model.read();
+					(
+						inner_model.feature_groups(),
+						inner_model.feature_importances(),
+					)
+				}
+			}
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			match multiclass_classifier.read().model() {
+				modelfox_model::MulticlassClassificationModelReader::Linear(inner_model) => {
+					let inner_model = inner_model.read();
+					(
+						inner_model.feature_groups(),
+						inner_model.feature_importances(),
+					)
+				}
+				modelfox_model::MulticlassClassificationModelReader::Tree(inner_model) => {
+					let inner_model = inner_model.read();
+					(
+						inner_model.feature_groups(),
+						inner_model.feature_importances(),
+					)
+				}
+			}
+		}
+	};
+	let feature_names = feature_groups
+		.iter()
+		.flat_map(|feature_group| feature_group.feature_names());
+	let mut values = zip_eq!(feature_names, feature_importances.iter())
+		.collect::<Result<Vec<_>, _>>()
+		.ok()?;
+	values.retain(|(_, value)| value.is_finite());
+	if values.is_empty() {
+		return None;
+	}
+	let n_features = values.len();
+	values.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+	values.truncate(REPORT_MAX_FEATURE_IMPORTANCES_TO_SHOW_IN_CHART);
+	Some(FeatureImportances { n_features, values })
+}
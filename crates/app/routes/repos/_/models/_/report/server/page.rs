@@ -0,0 +1,368 @@
+use modelfox_ui as ui;
+use num::ToPrimitive;
+use pinwheel::prelude::*;
+
+/// The report is a single html file that is viewed outside the app, so it cannot link to the app's stylesheet or client scripts. These styles are inlined instead, and include print styles so the report can be saved as a PDF from the browser's print dialog.
+const REPORT_STYLES: &str = r#"
+body {
+	color: #222;
+	font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif;
+	line-height: 1.5;
+	margin: 0 auto;
+	max-width: 960px;
+	padding: 2rem;
+}
+h1, h2 {
+	line-height: 1.2;
+}
+h2 {
+	border-bottom: 1px solid #ddd;
+	margin-top: 2.5rem;
+	padding-bottom: 0.25rem;
+}
+table {
+	border-collapse: collapse;
+	width: 100%;
+}
+th, td {
+	border-bottom: 1px solid #eee;
+	padding: 0.25rem 0.5rem;
+	text-align: left;
+}
+th {
+	background-color: #f6f6f6;
+}
+.report-subtitle {
+	color: #666;
+}
+.report-chart {
+	display: block;
+	margin-bottom: 1rem;
+	width: 100%;
+}
+@media print {
+	body {
+		max-width: none;
+		padding: 0;
+	}
+	h2 {
+		break-after: avoid;
+	}
+	tr, svg {
+		break-inside: avoid;
+	}
+}
+"#;
+
+pub struct Report {
+	pub title: String,
+	pub model_id: String,
+	pub generated_at: String,
+	pub overview: Overview,
+	pub target_column: ColumnRow,
+	pub columns: Vec<ColumnRow>,
+	pub metrics: Vec<MetricRow>,
+	pub class_metrics: Option<Vec<ClassMetricsRow>>,
+	pub feature_importances: Option<FeatureImportances>,
+}
+
+pub struct Overview {
+	pub task_name: String,
+	pub model_type_name: String,
+	pub comparison_metric_name: String,
+	pub column_count: usize,
+	pub overall_row_count: u64,
+	pub train_row_count: u64,
+	pub test_row_count: u64,
+}
+
+pub struct ColumnRow {
+	pub name: String,
+	pub column_type: String,
+	pub unique_count: Option<u64>,
+	pub invalid_count: Option<u64>,
+	pub min: Option<f32>,
+	pub max: Option<f32>,
+	pub mean: Option<f32>,
+	pub std: Option<f32>,
+}
+
+pub struct MetricRow {
+	pub name: String,
+	pub value: Option<f32>,
+	pub baseline_value: Option<f32>,
+}
+
+pub struct ClassMetricsRow {
+	pub class_name: String,
+	pub precision: f32,
+	pub recall: f32,
+}
+
+pub struct FeatureImportances {
+	pub n_features: usize,
+	pub values: Vec<(String, f32)>,
+}
+
+impl Component for Report {
+	fn into_node(self) -> Node {
+		let head = head()
+			.child(meta().attribute("charset", "utf-8"))
+			.child(
+				meta()
+					.attribute("content", "width=device-width, initial-scale=1")
+					.attribute("name", "viewport"),
+			)
+			.child(title().child(format!("{} Report", self.title)))
+			.child(style().inner_html(REPORT_STYLES));
+		let class_metrics_section = self.class_metrics.map(ClassMetricsSection);
+		let feature_importances_section = self.feature_importances.map(FeatureImportancesSection);
+		let body = body()
+			.child(h1().child(format!("{} Report", self.title)))
+			.child(p().class("report-subtitle").child(format!(
+				"Model {}. Generated {}.",
+				self.model_id, self.generated_at
+			)))
+			.child(self.overview)
+			.child(MetricsSection(self.metrics))
+			.child(class_metrics_section)
+			.child(feature_importances_section)
+			.child(TrainingStatsSection {
+				target_column: self.target_column,
+				columns: self.columns,
+			});
+		html::html().child(head).child(body).into_node()
+	}
+}
+
+impl Component for Overview {
+	fn into_node(self) -> Node {
+		let comparison_row_count =
+			self.overall_row_count - self.train_row_count - self.test_row_count;
+		fragment()
+			.child(h2().child("Overview"))
+			.child(
+				p().child("This is a ")
+					.child(b().child(self.task_name))
+					.child(" model. The dataset had ")
+					.child(b().child(self.overall_row_count.to_string()))
+					.child(" rows and ")
+					.child(b().child(self.column_count.to_string()))
+					.child(" columns. ")
+					.child(b().child(self.train_row_count.to_string()))
+					.child(" rows were used in training, ")
+					.child(b().child(self.test_row_count.to_string()))
+					.child(" rows were used in testing, and ")
+					.child(b().child(comparison_row_count.to_string()))
+					.child(" rows were used in model comparison. The model with the best ")
+					.child(b().child(self.comparison_metric_name))
+					.child(" was chosen. The chosen model is a ")
+					.child(b().child(self.model_type_name))
+					.child("."),
+			)
+			.into_node()
+	}
+}
+
+struct MetricsSection(Vec<MetricRow>);
+
+impl Component for MetricsSection {
+	fn into_node(self) -> Node {
+		let rows = self.0.into_iter().map(|metric| {
+			tr().child(td().child(metric.name))
+				.child(td().child(format_optional_float(metric.value)))
+				.child(td().child(format_optional_float(metric.baseline_value)))
+		});
+		fragment()
+			.child(h2().child("Metrics"))
+			.child(p().child(
+				"These metrics were computed on the test dataset. The baseline is a model that always predicts the mean of the target column for regression, or the majority class for classification.",
+			))
+			.child(
+				table()
+					.child(thead().child(
+						tr().child(th().child("Metric"))
+							.child(th().child("Value"))
+							.child(th().child("Baseline")),
+					))
+					.child(tbody().children(rows)),
+			)
+			.into_node()
+	}
+}
+
+struct ClassMetricsSection(Vec<ClassMetricsRow>);
+
+impl Component for ClassMetricsSection {
+	fn into_node(self) -> Node {
+		let rows = self.0.into_iter().map(|class_metrics| {
+			tr().child(td().child(class_metrics.class_name))
+				.child(td().child(ui::format_percent(class_metrics.precision)))
+				.child(td().child(ui::format_percent(class_metrics.recall)))
+		});
+		fragment()
+			.child(h2().child("Class Metrics"))
+			.child(
+				table()
+					.child(
+						thead().child(
+							tr().child(th().child("Class"))
+								.child(th().child("Precision"))
+								.child(th().child("Recall")),
+						),
+					)
+					.child(tbody().children(rows)),
+			)
+			.into_node()
+	}
+}
+
+struct FeatureImportancesSection(FeatureImportances);
+
+impl Component for FeatureImportancesSection {
+	fn into_node(self) -> Node {
+		let FeatureImportances { n_features, values } = self.0;
+		let description = format!(
+			"The chart below shows the {} most important of the model's {} features.",
+			values.len(),
+			n_features
+		);
+		let rows = values.iter().map(|(feature_name, value)| {
+			tr().child(td().child(feature_name.to_owned()))
+				.child(td().child(ui::format_float(*value)))
+		});
+		let rows = rows.collect::<Vec<_>>();
+		fragment()
+			.child(h2().child("Feature Importances"))
+			.child(p().child(description))
+			.child(FeatureImportancesChart { values })
+			.child(
+				table()
+					.child(
+						thead().child(
+							tr().child(th().child("Feature"))
+								.child(th().child("Importance")),
+						),
+					)
+					.child(tbody().children(rows)),
+			)
+			.into_node()
+	}
+}
+
+/// This is a horizontal bar chart drawn as an inline svg, because the app's charts are drawn on a canvas by client side code that does not run in the report.
+struct FeatureImportancesChart {
+	values: Vec<(String, f32)>,
+}
+
+impl Component for FeatureImportancesChart {
+	fn into_node(self) -> Node {
+		let width = 800.0;
+		let label_width = 240.0;
+		let bar_height = 20.0;
+		let bar_gap = 6.0;
+		let height = self.values.len().to_f32().unwrap() * (bar_height + bar_gap);
+		let max_value = self
+			.values
+			.iter()
+			.map(|(_, value)| *value)
+			.fold(0.0f32, f32::max);
+		let bars = self
+			.values
+			.into_iter()
+			.enumerate()
+			.map(|(index, (feature_name, value))| {
+				let y = index.to_f32().unwrap() * (bar_height + bar_gap);
+				let bar_width = if max_value > 0.0 {
+					(width - label_width) * value / max_value
+				} else {
+					0.0
+				};
+				svg::g()
+					.child(
+						svg::text()
+							.attribute("dominant-baseline", "middle")
+							.attribute("font-size", "12")
+							.attribute("text-anchor", "end")
+							.attribute("x", (label_width - 8.0).to_string())
+							.attribute("y", (y + bar_height / 2.0).to_string())
+							.child(feature_name),
+					)
+					.child(
+						svg::rect()
+							.attribute("fill", ui::colors::BLUE)
+							.attribute("height", bar_height.to_string())
+							.attribute("width", bar_width.max(0.0).to_string())
+							.attribute("x", label_width.to_string())
+							.attribute("y", y.to_string()),
+					)
+			});
+		svg()
+			.class("report-chart")
+			.attribute("viewBox", format!("0 0 {} {}", width, height))
+			.attribute("xmlns", "http://www.w3.org/2000/svg")
+			.child(svg::desc().child("feature importances"))
+			.children(bars)
+			.into_node()
+	}
+}
+
+struct TrainingStatsSection {
+	target_column: ColumnRow,
+	columns: Vec<ColumnRow>,
+}
+
+impl Component for TrainingStatsSection {
+	fn into_node(self) -> Node {
+		fragment()
+			.child(h2().child("Target Column"))
+			.child(ColumnStatsTable(vec![self.target_column]))
+			.child(h2().child("Columns"))
+			.child(ColumnStatsTable(self.columns))
+			.into_node()
+	}
+}
+
+struct ColumnStatsTable(Vec<ColumnRow>);
+
+impl Component for ColumnStatsTable {
+	fn into_node(self) -> Node {
+		let rows = self.0.into_iter().map(|column| {
+			tr().child(td().child(column.name))
+				.child(td().child(column.column_type))
+				.child(td().child(format_optional_count(column.unique_count)))
+				.child(td().child(format_optional_count(column.invalid_count)))
+				.child(td().child(format_optional_float(column.min)))
+				.child(td().child(format_optional_float(column.max)))
+				.child(td().child(format_optional_float(column.mean)))
+				.child(td().child(format_optional_float(column.std)))
+		});
+		table()
+			.child(
+				thead().child(
+					tr().child(th().child("Column"))
+						.child(th().child("Type"))
+						.child(th().child("Unique Values"))
+						.child(th().child("Invalid Values"))
+						.child(th().child("Min"))
+						.child(th().child("Max"))
+						.child(th().child("Mean"))
+						.child(th().child("Std")),
+				),
+			)
+			.child(tbody().children(rows))
+			.into_node()
+	}
+}
+
+fn format_optional_float(value: Option<f32>) -> String {
+	value
+		.map(ui::format_float)
+		.unwrap_or_else(|| "N/A".to_owned())
+}
+
+fn format_optional_count(value: Option<u64>) -> String {
+	value
+		.map(|value| value.to_string())
+		.unwrap_or_else(|| "N/A".to_owned())
+}
@@ -0,0 +1,79 @@
+use crate::page::{Page, PredictionKey};
+use anyhow::{bail, Result};
+use chrono::prelude::*;
+use chrono_tz::Tz;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	path_components,
+	prediction_keys::get_prediction_keys,
+	timezone::get_timezone_for_model,
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
+use modelfox_id::Id;
+use pinwheel::prelude::*;
+use std::sync::Arc;
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id =
+		if let ["repos", _, "models", model_id, "serving"] = *path_components(request).as_slice() {
+			model_id.to_owned()
+		} else {
+			bail!("unexpected path");
+		};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let timezone = get_timezone_for_model(&mut db, request, &user, model_id).await?;
+	let model_layout_info =
+		model_layout_info(&mut db, app, model_id, ModelNavItem::Serving).await?;
+	let prediction_keys = get_prediction_keys(&mut db, model_id)
+		.await?
+		.into_iter()
+		.map(|key| PredictionKey {
+			id: key.id.to_string(),
+			name: key.name,
+			token: key.token.to_string(),
+			requests_per_minute: key.requests_per_minute,
+			created_at: format_timestamp(key.created_at, &timezone),
+			last_used_at: key
+				.last_used_at
+				.map(|last_used_at| format_timestamp(last_used_at, &timezone)),
+			request_count: key.request_count,
+			rejected_request_count: key.rejected_request_count,
+		})
+		.collect();
+	let page = Page {
+		model_id,
+		model_layout_info,
+		prediction_keys,
+	};
+	app.commit_transaction(db).await?;
+	let html = html(page);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	Ok(response)
+}
+
+fn format_timestamp(timestamp: i64, timezone: &Tz) -> String {
+	let date: DateTime<Tz> = Utc.timestamp(timestamp, 0).with_timezone(timezone);
+	date.to_string()
+}
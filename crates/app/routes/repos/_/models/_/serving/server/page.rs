@@ -0,0 +1,167 @@
+use modelfox_app_base_path::app_path;
+use modelfox_app_layouts::{
+	document::Document,
+	model_layout::{ModelLayout, ModelLayoutInfo},
+};
+use modelfox_app_ui::page_heading::PageHeading;
+use modelfox_id::Id;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+use std::borrow::Cow;
+
+pub struct Page {
+	pub model_id: Id,
+	pub model_layout_info: ModelLayoutInfo,
+	pub prediction_keys: Vec<PredictionKey>,
+}
+
+pub struct PredictionKey {
+	pub id: String,
+	pub name: String,
+	pub token: String,
+	pub requests_per_minute: i64,
+	pub created_at: String,
+	pub last_used_at: Option<String>,
+	pub request_count: i64,
+	pub rejected_request_count: i64,
+}
+
+impl Component for Page {
+	fn into_node(self) -> Node {
+		let predict_path = app_path(format!("/api/models/{}/predict", self.model_id));
+		// The code is rendered as html, so it must not have any characters that need to be escaped.
+		let example = format!(
+			"curl -X POST $MODELFOX_URL{} \\\n  -H \"Authorization: Bearer $PREDICTION_KEY\" \\\n  -H \"Content-Type: application/json\" \\\n  -d '{{ \"inputs\": [{{ \"age\": 63, \"gender\": \"male\" }}] }}'",
+			predict_path
+		);
+		Document::new()
+			.child(
+				ModelLayout::new(self.model_layout_info).child(
+					ui::S1::new()
+						.child(PageHeading::new().child(ui::H1::new("Serving")))
+						.child(
+							ui::S2::new()
+								.child(ui::H2::new("Predict Endpoint"))
+								.child(ui::P::new().child(
+									"Make predictions with this model by posting its inputs to the predict endpoint with one of the prediction keys below. The response has one output for each input. Unless the request has options, the predictions use the thresholds saved on the tuning page.",
+								))
+								.child(ui::Code::new().code(Cow::Owned(example))),
+						)
+						.child(PredictionKeysSection {
+							prediction_keys: self.prediction_keys,
+						})
+						.child(CreatePredictionKeySection),
+				),
+			)
+			.into_node()
+	}
+}
+
+struct PredictionKeysSection {
+	prediction_keys: Vec<PredictionKey>,
+}
+
+impl Component for PredictionKeysSection {
+	fn into_node(self) -> Node {
+		let table_or_empty_message = if self.prediction_keys.is_empty() {
+			ui::Card::new()
+				.child(ui::P::new().child("This model has no prediction keys yet."))
+				.into_node()
+		} else {
+			let table_header = ui::TableRow::new()
+				.child(ui::TableHeaderCell::new().child("Name"))
+				.child(ui::TableHeaderCell::new().child("Key"))
+				.child(ui::TableHeaderCell::new().child("Requests per Minute"))
+				.child(ui::TableHeaderCell::new().child("Requests"))
+				.child(ui::TableHeaderCell::new().child("Rejected Requests"))
+				.child(ui::TableHeaderCell::new().child("Created"))
+				.child(ui::TableHeaderCell::new().child("Last Used"))
+				.child(ui::TableHeaderCell::new());
+			let table_body = self.prediction_keys.into_iter().map(|key| {
+				let delete_form = ui::Form::new()
+					.post(true)
+					.onsubmit("return confirm(\"Requests made with this key will be rejected. Are you sure?\")".to_owned())
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "delete_key"),
+					)
+					.child(
+						input()
+							.attribute("name", "key_id")
+							.attribute("type", "hidden")
+							.attribute("value", key.id),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.color(ui::colors::RED.to_owned())
+							.child("Delete"),
+					);
+				ui::TableRow::new()
+					.child(ui::TableCell::new().child(key.name))
+					.child(ui::TableCell::new().child(ui::InlineCode::new(key.token)))
+					.child(ui::TableCell::new().child(key.requests_per_minute.to_string()))
+					.child(ui::TableCell::new().child(key.request_count.to_string()))
+					.child(ui::TableCell::new().child(key.rejected_request_count.to_string()))
+					.child(ui::TableCell::new().child(key.created_at))
+					.child(
+						ui::TableCell::new()
+							.child(key.last_used_at.unwrap_or_else(|| "Never".to_owned())),
+					)
+					.child(ui::TableCell::new().child(delete_form))
+			});
+			ui::Table::new()
+				.width("100%".to_owned())
+				.child(ui::TableHeader::new().child(table_header))
+				.child(ui::TableBody::new().children(table_body))
+				.into_node()
+		};
+		ui::S2::new()
+			.child(ui::H2::new("Prediction Keys"))
+			.child(ui::P::new().child(
+				"Each key is limited to its number of requests per minute. Requests over the limit get a 429 response with a Retry-After header, and are counted as rejected requests.",
+			))
+			.child(table_or_empty_message)
+			.into_node()
+	}
+}
+
+struct CreatePredictionKeySection;
+
+impl Component for CreatePredictionKeySection {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("Create a Prediction Key"))
+			.child(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "create_key"),
+					)
+					.child(
+						ui::TextField::new()
+							.label("Name".to_owned())
+							.name("name".to_owned())
+							.required(true),
+					)
+					.child(
+						ui::TextField::new()
+							.label("Requests per Minute".to_owned())
+							.name("requests_per_minute".to_owned())
+							.required(true)
+							.value("60".to_owned()),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Create Key"),
+					),
+			)
+			.into_node()
+	}
+}
@@ -0,0 +1,94 @@
+use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	path_components,
+	prediction_keys::{create_prediction_key, delete_prediction_key},
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "action")]
+enum Action {
+	#[serde(rename = "create_key")]
+	CreateKey(CreateKeyAction),
+	#[serde(rename = "delete_key")]
+	DeleteKey(DeleteKeyAction),
+}
+
+#[derive(serde::Deserialize)]
+struct CreateKeyAction {
+	name: String,
+	requests_per_minute: i64,
+}
+
+#[derive(serde::Deserialize)]
+struct DeleteKeyAction {
+	key_id: Id,
+}
+
+pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let (repo_id, model_id) = if let ["repos", repo_id, "models", model_id, "serving"] =
+		*path_components(request).as_slice()
+	{
+		(repo_id.to_owned(), model_id.to_owned())
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let data = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(data) => data,
+		Err(_) => return Ok(bad_request()),
+	};
+	let action: Action = match serde_urlencoded::from_bytes(&data) {
+		Ok(action) => action,
+		Err(_) => return Ok(bad_request()),
+	};
+	match action {
+		Action::CreateKey(CreateKeyAction {
+			name,
+			requests_per_minute,
+		}) => {
+			if name.trim().is_empty() || requests_per_minute <= 0 {
+				return Ok(bad_request());
+			}
+			let now = app.clock().now_utc().unix_timestamp();
+			create_prediction_key(&mut db, model_id, name.trim(), requests_per_minute, now).await?;
+		}
+		Action::DeleteKey(DeleteKeyAction { key_id }) => {
+			if !delete_prediction_key(&mut db, model_id, key_id).await? {
+				return Ok(not_found());
+			}
+		}
+	}
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::SEE_OTHER)
+		.header(
+			http::header::LOCATION,
+			app_path(format!("/repos/{}/models/{}/serving", repo_id, model_id)),
+		)
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
@@ -0,0 +1,10 @@
+use modelfox_ui as ui;
+use web_sys as dom;
+
+pub fn main() {
+	modelfox_ui::client_start();
+	let document = dom::window().unwrap().document().unwrap();
+	if document.get_element_by_id("class_select_field").is_some() {
+		ui::select_field_submit_on_change("class_select_field".to_owned());
+	}
+}
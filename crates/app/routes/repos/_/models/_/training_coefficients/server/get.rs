@@ -0,0 +1,288 @@
+use crate::page::{Coefficient, Coefficients, Inner, Page, Task};
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::get_model_bytes,
+	path_components,
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
+use modelfox_id::Id;
+use pinwheel::prelude::*;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Format {
+	Html,
+	Csv,
+}
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id = if let ["repos", _, "models", model_id, "training_coefficients"] =
+		*path_components(request).as_slice()
+	{
+		model_id.to_owned()
+	} else {
+		bail!("unexpected path");
+	};
+	#[derive(serde::Deserialize, Default)]
+	struct SearchParams {
+		class: Option<String>,
+		feature: Option<String>,
+		format: Option<Format>,
+	}
+	let search_params: SearchParams = match request.uri().query() {
+		Some(query) => match serde_urlencoded::from_str(query) {
+			Ok(search_params) => search_params,
+			Err(_) => return Ok(bad_request()),
+		},
+		None => SearchParams::default(),
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let model_layout_info =
+		model_layout_info(&mut db, app, model_id, ModelNavItem::TrainingCoefficients).await?;
+	app.commit_transaction(db).await?;
+	let bytes = get_model_bytes(app.storage(), model_id).await?;
+	let model = modelfox_model::from_bytes(&bytes)?;
+	let linear_model = match get_linear_model(model) {
+		Some(linear_model) => linear_model,
+		None => {
+			let page = Page {
+				model_layout_info,
+				inner: Inner::Tree,
+			};
+			let response = http::Response::builder()
+				.status(http::StatusCode::OK)
+				.body(hyper::Body::from(html(page)))
+				.unwrap();
+			return Ok(response);
+		}
+	};
+	let class_index = match (&search_params.class, &linear_model.classes) {
+		(Some(class), Some(classes)) => match classes.iter().position(|c| c == class) {
+			Some(class_index) => class_index,
+			None => return Ok(bad_request()),
+		},
+		_ => 0,
+	};
+	let mut coefficients = linear_model
+		.features
+		.iter()
+		.zip(linear_model.weights[class_index].iter())
+		.map(|(feature, weight)| Coefficient {
+			feature_name: feature.name.clone(),
+			value: *weight,
+			normalization: feature.normalization,
+		})
+		.collect::<Vec<_>>();
+	// Sort by magnitude, so the features that move the prediction the most for a given change in their value come first.
+	coefficients.sort_by(|a, b| b.value.abs().partial_cmp(&a.value.abs()).unwrap());
+	let intercept = linear_model.biases[class_index];
+	let task = match (linear_model.classes, linear_model.positive_class) {
+		(Some(classes), _) => Task::MulticlassClassification {
+			class: classes[class_index].clone(),
+			classes,
+		},
+		(None, Some(positive_class)) => Task::BinaryClassification { positive_class },
+		(None, None) => Task::Regression,
+	};
+	let response = match search_params.format.unwrap_or(Format::Html) {
+		// The CSV has every coefficient, regardless of the feature filter.
+		Format::Csv => {
+			let filename = match &task {
+				Task::MulticlassClassification { class, .. } => {
+					format!("coefficients_{}.csv", class.replace('"', ""))
+				}
+				_ => "coefficients.csv".to_owned(),
+			};
+			http::Response::builder()
+				.status(http::StatusCode::OK)
+				.header(http::header::CONTENT_TYPE, "text/csv; charset=utf-8")
+				.header(
+					http::header::CONTENT_DISPOSITION,
+					format!("attachment; filename=\"{}\"", filename),
+				)
+				.body(hyper::Body::from(coefficients_csv(
+					intercept,
+					&coefficients,
+				)?))
+				.unwrap()
+		}
+		Format::Html => {
+			let n_features_total = coefficients.len();
+			let feature_filter = search_params.feature.unwrap_or_default();
+			if !feature_filter.is_empty() {
+				let feature_filter = feature_filter.to_lowercase();
+				coefficients.retain(|coefficient| {
+					coefficient
+						.feature_name
+						.to_lowercase()
+						.contains(&feature_filter)
+				});
+			}
+			let page = Page {
+				model_layout_info,
+				inner: Inner::Coefficients(Coefficients {
+					coefficients,
+					feature_filter,
+					intercept,
+					n_features_total,
+					task,
+				}),
+			};
+			http::Response::builder()
+				.status(http::StatusCode::OK)
+				.body(hyper::Body::from(html(page)))
+				.unwrap()
+		}
+	};
+	Ok(response)
+}
+
+struct LinearModel {
+	/// This is the positive class of a binary classifier.
+	positive_class: Option<String>,
+	/// These are the classes of a multiclass classifier, which has separate weights for each class.
+	classes: Option<Vec<String>>,
+	/// This has one bias for each class, or a single bias for regressors and binary classifiers.
+	biases: Vec<f32>,
+	/// This has the weights for each class, or a single `Vec` for regressors and binary classifiers.
+	weights: Vec<Vec<f32>>,
+	features: Vec<Feature>,
+}
+
+struct Feature {
+	name: String,
+	/// If the feature is a normalized number column, these are the mean and standard deviation used to normalize it.
+	normalization: Option<(f32, f32)>,
+}
+
+fn get_linear_model(model: modelfox_model::ModelReader) -> Option<LinearModel> {
+	match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			let regressor = regressor.read();
+			match regressor.model() {
+				modelfox_model::RegressionModelReader::Tree(_) => None,
+				modelfox_model::RegressionModelReader::Linear(inner_model) => {
+					let inner_model = inner_model.read();
+					let features = compute_features(inner_model.feature_groups().iter());
+					let inner_model = modelfox_linear::Regressor::from_reader(inner_model.model());
+					Some(LinearModel {
+						positive_class: None,
+						classes: None,
+						biases: vec![inner_model.bias],
+						weights: vec![inner_model.weights.to_vec()],
+						features,
+					})
+				}
+			}
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			let binary_classifier = binary_classifier.read();
+			match binary_classifier.model() {
+				modelfox_model::BinaryClassificationModelReader::Tree(_) => None,
+				modelfox_model::BinaryClassificationModelReader::Linear(inner_model) => {
+					let inner_model = inner_model.read();
+					let features = compute_features(inner_model.feature_groups().iter());
+					let inner_model =
+						modelfox_linear::BinaryClassifier::from_reader(inner_model.model());
+					Some(LinearModel {
+						positive_class: Some(binary_classifier.positive_class().to_owned()),
+						classes: None,
+						biases: vec![inner_model.bias],
+						weights: vec![inner_model.weights.to_vec()],
+						features,
+					})
+				}
+			}
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier = multiclass_classifier.read();
+			match multiclass_classifier.model() {
+				modelfox_model::MulticlassClassificationModelReader::Tree(_) => None,
+				modelfox_model::MulticlassClassificationModelReader::Linear(inner_model) => {
+					let inner_model = inner_model.read();
+					let features = compute_features(inner_model.feature_groups().iter());
+					let inner_model =
+						modelfox_linear::MulticlassClassifier::from_reader(inner_model.model());
+					let classes = multiclass_classifier
+						.classes()
+						.iter()
+						.map(ToOwned::to_owned)
+						.collect::<Vec<_>>();
+					let weights = (0..classes.len())
+						.map(|class_index| inner_model.weights.column(class_index).to_vec())
+						.collect();
+					Some(LinearModel {
+						positive_class: None,
+						classes: Some(classes),
+						biases: inner_model.biases.to_vec(),
+						weights,
+						features,
+					})
+				}
+			}
+		}
+	}
+}
+
+fn compute_features<'a>(
+	feature_groups: impl Iterator<Item = modelfox_model::FeatureGroupReader<'a>>,
+) -> Vec<Feature> {
+	feature_groups
+		.flat_map(|feature_group| {
+			let normalization = match &feature_group {
+				modelfox_model::FeatureGroupReader::Normalized(feature_group) => {
+					let feature_group = feature_group.read();
+					Some((feature_group.mean(), feature_group.variance().sqrt()))
+				}
+				_ => None,
+			};
+			feature_group
+				.feature_names()
+				.into_iter()
+				.map(move |name| Feature {
+					name,
+					normalization,
+				})
+		})
+		.collect()
+}
+
+fn coefficients_csv(intercept: f32, coefficients: &[Coefficient]) -> Result<Vec<u8>> {
+	let mut writer = csv::Writer::from_writer(Vec::new());
+	writer.write_record(&["feature", "coefficient", "mean", "std"])?;
+	writer.write_record(&["(intercept)", &intercept.to_string(), "", ""])?;
+	for coefficient in coefficients {
+		let (mean, std) = match coefficient.normalization {
+			Some((mean, std)) => (mean.to_string(), std.to_string()),
+			None => (String::new(), String::new()),
+		};
+		writer.write_record(&[
+			coefficient.feature_name.as_str(),
+			&coefficient.value.to_string(),
+			&mean,
+			&std,
+		])?;
+	}
+	Ok(writer.into_inner()?)
+}
@@ -0,0 +1,178 @@
+use modelfox_app_layouts::{
+	document::Document,
+	model_layout::{ModelLayout, ModelLayoutInfo},
+};
+use modelfox_app_ui::{
+	class_select_field::ClassSelectField,
+	page_heading::{PageHeading, PageHeadingButtons},
+};
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct Page {
+	pub model_layout_info: ModelLayoutInfo,
+	pub inner: Inner,
+}
+
+pub enum Inner {
+	Tree,
+	Coefficients(Coefficients),
+}
+
+pub struct Coefficients {
+	/// These are sorted by magnitude, and only include the features that match the filter.
+	pub coefficients: Vec<Coefficient>,
+	pub feature_filter: String,
+	pub intercept: f32,
+	pub n_features_total: usize,
+	pub task: Task,
+}
+
+pub enum Task {
+	Regression,
+	BinaryClassification { positive_class: String },
+	MulticlassClassification { class: String, classes: Vec<String> },
+}
+
+pub struct Coefficient {
+	pub feature_name: String,
+	pub value: f32,
+	/// If the feature is a normalized number column, these are the mean and standard deviation used to normalize it.
+	pub normalization: Option<(f32, f32)>,
+}
+
+impl Component for Page {
+	fn into_node(self) -> Node {
+		let inner = match self.inner {
+			Inner::Tree => ui::S1::new()
+				.child(ui::H1::new("Coefficients"))
+				.child(ui::P::new().child(
+					"This model is a gradient boosted tree model, so it has no coefficients to show. This page shows the coefficients of linear models.",
+				))
+				.into_node(),
+			Inner::Coefficients(coefficients) => coefficients.into_node(),
+		};
+		Document::new()
+			.client("modelfox_app_training_coefficients_client")
+			.child(ModelLayout::new(self.model_layout_info).child(inner))
+			.into_node()
+	}
+}
+
+impl Component for Coefficients {
+	fn into_node(self) -> Node {
+		let (description, csv_href) = match &self.task {
+			Task::Regression => (
+				"The model predicts the sum of the intercept and each feature's value multiplied by its coefficient.".to_owned(),
+				"training_coefficients?format=csv".to_owned(),
+			),
+			Task::BinaryClassification { positive_class } => (
+				format!(
+					"The model computes the log odds that the example is {} as the sum of the intercept and each feature's value multiplied by its coefficient.",
+					positive_class
+				),
+				"training_coefficients?format=csv".to_owned(),
+			),
+			Task::MulticlassClassification { class, .. } => (
+				format!(
+					"The model has coefficients for each class. It computes the logit for {} as the sum of the class's intercept and each feature's value multiplied by the class's coefficient for it.",
+					class
+				),
+				format!(
+					"training_coefficients?format=csv&class={}",
+					ui::percent_encode(class)
+				),
+			),
+		};
+		let class_select_field = match self.task {
+			Task::MulticlassClassification { class, classes } => {
+				Some(ClassSelectField { class, classes })
+			}
+			_ => None,
+		};
+		ui::S1::new()
+			.child(
+				PageHeading::new()
+					.child(ui::H1::new("Coefficients"))
+					.child(
+						PageHeadingButtons::new().child(
+							ui::Button::new()
+								.href(csv_href)
+								.child("Download CSV"),
+						),
+					),
+			)
+			.child(ui::P::new().child(description))
+			.child(ui::P::new().child(
+				"A positive coefficient means the prediction increases as the feature increases, and a negative coefficient means it decreases. Number columns are normalized by subtracting their mean and dividing by their standard deviation before they are multiplied by their coefficient, so their coefficients are the change in the prediction for a one standard deviation change in the column.",
+			))
+			.child(ui::NumberCard::new(
+				"Intercept".to_owned(),
+				ui::format_float(self.intercept),
+			))
+			.child(
+				ui::Form::new()
+					.child(class_select_field)
+					.child(
+						ui::TextField::new()
+							.autocomplete("off".to_owned())
+							.label("Feature".to_owned())
+							.name("feature".to_owned())
+							.value(self.feature_filter),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Filter"),
+					),
+			)
+			.child(ui::P::new().child(format!(
+				"Showing {} of {} features.",
+				self.coefficients.len(),
+				self.n_features_total
+			)))
+			.child(CoefficientsTable {
+				coefficients: self.coefficients,
+			})
+			.into_node()
+	}
+}
+
+struct CoefficientsTable {
+	coefficients: Vec<Coefficient>,
+}
+
+impl Component for CoefficientsTable {
+	fn into_node(self) -> Node {
+		let table_header = ui::TableRow::new()
+			.child(ui::TableHeaderCell::new().child("Feature"))
+			.child(ui::TableHeaderCell::new().child("Coefficient"))
+			.child(ui::TableHeaderCell::new().child("Effect"))
+			.child(ui::TableHeaderCell::new().child("Normalization Mean"))
+			.child(ui::TableHeaderCell::new().child("Normalization Std"));
+		let table_body = self.coefficients.into_iter().map(|coefficient| {
+			let effect = if coefficient.value > 0.0 {
+				"Increases"
+			} else if coefficient.value < 0.0 {
+				"Decreases"
+			} else {
+				"None"
+			};
+			let (mean, std) = match coefficient.normalization {
+				Some((mean, std)) => (Some(ui::format_float(mean)), Some(ui::format_float(std))),
+				None => (None, None),
+			};
+			ui::TableRow::new()
+				.child(ui::TableCell::new().child(coefficient.feature_name))
+				.child(ui::TableCell::new().child(ui::format_float(coefficient.value)))
+				.child(ui::TableCell::new().child(effect))
+				.child(ui::TableCell::new().child(mean))
+				.child(ui::TableCell::new().child(std))
+		});
+		ui::Table::new()
+			.width("100%".to_owned())
+			.child(ui::TableHeader::new().child(table_header))
+			.child(ui::TableBody::new().children(table_body))
+			.into_node()
+	}
+}
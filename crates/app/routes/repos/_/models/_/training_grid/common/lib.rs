@@ -1,154 +1,98 @@
+use num::ToPrimitive;
+use std::collections::BTreeMap;
+
 pub fn hyperparameters_for_grid_item(
 	train_grid_item_output: &modelfox_model::TrainGridItemOutputReader,
 ) -> Vec<(String, String)> {
-	match &train_grid_item_output.hyperparameters() {
-		modelfox_model::ModelTrainOptionsReader::Linear(hyperparameters) => {
-			let hyperparameters = hyperparameters.read();
-			vec![
-				(
-					"l2_regularization".to_owned(),
-					hyperparameters.l2_regularization().to_string(),
-				),
-				(
-					"learning_rate".to_owned(),
-					hyperparameters.learning_rate().to_string(),
-				),
-				(
-					"max_epochs".to_owned(),
-					hyperparameters.max_epochs().to_string(),
-				),
-				(
-					"n_examples_per_batch".to_owned(),
-					hyperparameters.n_examples_per_batch().to_string(),
-				),
-				(
-					"early_stopping:early_stopping_fraction".to_owned(),
-					hyperparameters
-						.early_stopping_options()
-						.map(|options| options.early_stopping_fraction().to_string())
-						.unwrap_or_else(|| "None".to_string()),
-				),
-				(
-					"early_stopping:n_rounds_without_improvement_to_stop".to_owned(),
-					hyperparameters
-						.early_stopping_options()
-						.map(|options| options.n_rounds_without_improvement_to_stop().to_string())
-						.unwrap_or_else(|| "None".to_string()),
-				),
-				(
-					"early_stopping:min_decrease_in_loss_for_significant_change".to_owned(),
-					hyperparameters
-						.early_stopping_options()
-						.map(|options| {
-							options
-								.min_decrease_in_loss_for_significant_change()
-								.to_string()
-						})
-						.unwrap_or_else(|| "None".to_string()),
-				),
-			]
+	train_grid_item_output.hyperparameters().hyperparameters()
+}
+
+/// The name of a grid item's model type, as shown on the training grid pages.
+pub fn model_type_for_grid_item(
+	train_grid_item_output: &modelfox_model::TrainGridItemOutputReader,
+) -> String {
+	match train_grid_item_output.hyperparameters() {
+		modelfox_model::ModelTrainOptionsReader::Linear(_) => "Linear".to_owned(),
+		modelfox_model::ModelTrainOptionsReader::Tree(_) => "Gradient Boosted Tree".to_owned(),
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct HyperparameterImportance {
+	pub name: String,
+	pub n_values: usize,
+	/// This is the fraction of the variance in the comparison metric across the grid items that is explained by the value of this hyperparameter.
+	pub importance: f32,
+}
+
+/// Compute how much each hyperparameter mattered across a grid. For each hyperparameter, the grid items that have it are grouped by its value, and its importance is the fraction of the variance in their comparison metric values that is between the groups rather than within them. This is a one way analysis of variance, so it does not account for interactions between hyperparameters, but it shows which knobs moved the comparison metric. The model type is included as a hyperparameter, and other hyperparameters are named by their model type because linear and tree models are tuned separately. Hyperparameters that took a single value are omitted. The result is sorted by decreasing importance.
+pub fn compute_hyperparameter_importances(
+	grid_items: &[modelfox_model::TrainGridItemOutputReader],
+) -> Vec<HyperparameterImportance> {
+	let mut groups: BTreeMap<String, BTreeMap<String, Vec<f32>>> = BTreeMap::new();
+	for grid_item in grid_items {
+		let value = grid_item.comparison_metric_value();
+		if !value.is_finite() {
+			continue;
 		}
-		modelfox_model::ModelTrainOptionsReader::Tree(hyperparameters) => {
-			let hyperparameters = hyperparameters.read();
-			vec![
-				(
-					"binned_features_layout".to_owned(),
-					match hyperparameters.binned_features_layout() {
-						modelfox_model::BinnedFeaturesLayoutReader::RowMajor(_) => {
-							"row major".to_owned()
-						}
-						modelfox_model::BinnedFeaturesLayoutReader::ColumnMajor(_) => {
-							"column major".to_owned()
-						}
-					},
-				),
-				(
-					"early_stopping:early_stopping_fraction".to_owned(),
-					hyperparameters
-						.early_stopping_options()
-						.map(|options| options.early_stopping_fraction().to_string())
-						.unwrap_or_else(|| "None".to_string()),
-				),
-				(
-					"early_stopping:n_rounds_without_improvement_to_stop".to_owned(),
-					hyperparameters
-						.early_stopping_options()
-						.map(|options| options.n_rounds_without_improvement_to_stop().to_string())
-						.unwrap_or_else(|| "None".to_string()),
-				),
-				(
-					"early_stopping:min_decrease_in_loss_for_significant_change".to_owned(),
-					hyperparameters
-						.early_stopping_options()
-						.map(|options| {
-							options
-								.min_decrease_in_loss_for_significant_change()
-								.to_string()
-						})
-						.unwrap_or_else(|| "None".to_string()),
-				),
-				(
-					"l2_regularization_for_continuous_splits".to_owned(),
-					hyperparameters
-						.l2_regularization_for_continuous_splits()
-						.to_string(),
-				),
-				(
-					"l2_regularization_for_discrete_splits".to_owned(),
-					hyperparameters
-						.l2_regularization_for_discrete_splits()
-						.to_string(),
-				),
-				(
-					"learning_rate".to_owned(),
-					hyperparameters.learning_rate().to_string(),
-				),
-				(
-					"max_depth".to_owned(),
-					hyperparameters
-						.max_depth()
-						.map(|max_depth| max_depth.to_string())
-						.unwrap_or_else(|| "None".to_owned()),
-				),
-				(
-					"max_examples_for_computing_bin_thresholds".to_owned(),
-					hyperparameters
-						.max_examples_for_computing_bin_thresholds()
-						.to_string(),
-				),
-				(
-					"max_leaf_nodes".to_owned(),
-					hyperparameters.max_leaf_nodes().to_string(),
-				),
-				(
-					"max_rounds".to_owned(),
-					hyperparameters.max_rounds().to_string(),
-				),
-				(
-					"max_valid_bins_for_number_features".to_owned(),
-					hyperparameters
-						.max_valid_bins_for_number_features()
-						.to_string(),
-				),
-				(
-					"min_examples_per_node".to_owned(),
-					hyperparameters.min_examples_per_node().to_string(),
-				),
-				(
-					"min_gain_to_split".to_owned(),
-					hyperparameters.min_gain_to_split().to_string(),
-				),
-				(
-					"min_sum_hessians_per_node".to_owned(),
-					hyperparameters.min_sum_hessians_per_node().to_string(),
-				),
-				(
-					"smoothing_factor_for_discrete_bin_sorting".to_owned(),
-					hyperparameters
-						.smoothing_factor_for_discrete_bin_sorting()
-						.to_string(),
-				),
-			]
+		let model_type = model_type_for_grid_item(grid_item);
+		groups
+			.entry("model_type".to_owned())
+			.or_default()
+			.entry(model_type.clone())
+			.or_default()
+			.push(value);
+		for (name, hyperparameter_value) in hyperparameters_for_grid_item(grid_item) {
+			groups
+				.entry(format!("{}: {}", model_type, name))
+				.or_default()
+				.entry(hyperparameter_value)
+				.or_default()
+				.push(value);
 		}
 	}
+	let mut importances = groups
+		.into_iter()
+		.filter(|(_, groups)| groups.len() > 1)
+		.filter_map(|(name, groups)| {
+			let importance = correlation_ratio(groups.values())?;
+			Some(HyperparameterImportance {
+				name,
+				n_values: groups.len(),
+				importance,
+			})
+		})
+		.collect::<Vec<_>>();
+	importances.sort_by(|a, b| b.importance.partial_cmp(&a.importance).unwrap());
+	importances
+}
+
+/// Compute the ratio of the between group sum of squares to the total sum of squares. This returns `None` if the values do not vary.
+fn correlation_ratio<'a>(groups: impl Iterator<Item = &'a Vec<f32>> + Clone) -> Option<f32> {
+	let (sum, count) = groups
+		.clone()
+		.flatten()
+		.fold((0.0, 0usize), |(sum, count), value| {
+			(sum + value.to_f64().unwrap(), count + 1)
+		});
+	let mean = sum / count.to_f64().unwrap();
+	let total_sum_of_squares = groups
+		.clone()
+		.flatten()
+		.map(|value| (value.to_f64().unwrap() - mean).powi(2))
+		.sum::<f64>();
+	if total_sum_of_squares <= 0.0 {
+		return None;
+	}
+	let between_sum_of_squares = groups
+		.map(|group| {
+			let group_mean = group
+				.iter()
+				.map(|value| value.to_f64().unwrap())
+				.sum::<f64>()
+				/ group.len().to_f64().unwrap();
+			group.len().to_f64().unwrap() * (group_mean - mean).powi(2)
+		})
+		.sum::<f64>();
+	(between_sum_of_squares / total_sum_of_squares).to_f32()
 }
@@ -8,7 +8,9 @@ use modelfox_app_core::{
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
-use modelfox_app_training_grid_common::hyperparameters_for_grid_item;
+use modelfox_app_training_grid_common::{
+	compute_hyperparameter_importances, hyperparameters_for_grid_item, model_type_for_grid_item,
+};
 use modelfox_id::Id;
 use num::ToPrimitive;
 use pinwheel::prelude::*;
@@ -29,10 +31,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -64,6 +67,18 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				modelfox_model::BinaryClassificationComparisonMetricReader::Aucroc(_) => {
 					"AUC".to_owned()
 				}
+				modelfox_model::BinaryClassificationComparisonMetricReader::F1(_) => {
+					"F1".to_owned()
+				}
+				modelfox_model::BinaryClassificationComparisonMetricReader::Precision(_) => {
+					"Precision".to_owned()
+				}
+				modelfox_model::BinaryClassificationComparisonMetricReader::Recall(_) => {
+					"Recall".to_owned()
+				}
+				modelfox_model::BinaryClassificationComparisonMetricReader::Accuracy(_) => {
+					"Accuracy".to_owned()
+				}
 			}
 		}
 		modelfox_model::ModelInnerReader::MulticlassClassifier(model) => {
@@ -147,6 +162,35 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		}
 	};
 	let best_model_hyperparameters = hyperparameters_for_grid_item(&best_model);
+	let hyperparameter_importances = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			compute_hyperparameter_importances(
+				&regressor
+					.read()
+					.train_grid_item_outputs()
+					.iter()
+					.collect::<Vec<_>>(),
+			)
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			compute_hyperparameter_importances(
+				&binary_classifier
+					.read()
+					.train_grid_item_outputs()
+					.iter()
+					.collect::<Vec<_>>(),
+			)
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			compute_hyperparameter_importances(
+				&multiclass_classifier
+					.read()
+					.train_grid_item_outputs()
+					.iter()
+					.collect::<Vec<_>>(),
+			)
+		}
+	};
 	let page = Page {
 		id: model_id.to_string(),
 		comparison_metric_name,
@@ -154,6 +198,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		trained_models_metrics,
 		best_model_metrics,
 		best_model_hyperparameters,
+		hyperparameter_importances,
 		model_layout_info,
 	};
 	let html = html(page);
@@ -169,10 +214,7 @@ fn trained_model_metrics_for_grid_item(
 	identifier: String,
 	train_grid_item_output: &modelfox_model::TrainGridItemOutputReader,
 ) -> TrainedModel {
-	let model_type = match &train_grid_item_output.hyperparameters() {
-		modelfox_model::ModelTrainOptionsReader::Linear(_) => "Linear".into(),
-		modelfox_model::ModelTrainOptionsReader::Tree(_) => "Gradient Boosted Tree".into(),
-	};
+	let model_type = model_type_for_grid_item(train_grid_item_output);
 	let duration = Duration::from_secs_f32(train_grid_item_output.duration());
 	let time = format!("{:?}", duration);
 	TrainedModel {
@@ -2,6 +2,7 @@ use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
 };
+use modelfox_app_training_grid_common::HyperparameterImportance;
 use modelfox_ui as ui;
 use pinwheel::prelude::*;
 
@@ -13,6 +14,7 @@ pub struct Page {
 	pub best_model_metrics: TrainedModel,
 	pub comparison_metric_name: String,
 	pub best_model_hyperparameters: Vec<(String, String)>,
+	pub hyperparameter_importances: Vec<HyperparameterImportance>,
 }
 
 #[derive(Clone, Debug)]
@@ -47,6 +49,14 @@ impl Component for Page {
 									hyperparameters: self.best_model_hyperparameters,
 								}),
 						)
+						.child(if self.hyperparameter_importances.is_empty() {
+							None
+						} else {
+							Some(HyperparameterImportanceSection {
+								hyperparameter_importances: self.hyperparameter_importances,
+								comparison_metric_name: self.comparison_metric_name.clone(),
+							})
+						})
 						.child(ui::S2::new().child(ui::H2::new("All Models")).child(
 							AllTrainedModelsMetricsTable {
 								trained_models: self.trained_models_metrics,
@@ -151,3 +161,46 @@ impl Component for ModelHyperparametersTable {
 			.into_node()
 	}
 }
+
+pub struct HyperparameterImportanceSection {
+	hyperparameter_importances: Vec<HyperparameterImportance>,
+	comparison_metric_name: String,
+}
+
+impl Component for HyperparameterImportanceSection {
+	fn into_node(self) -> Node {
+		let description = format!(
+			"The importance of a hyperparameter is the fraction of the variation in {} across the models in the grid that is explained by its value. Hyperparameters with low importance did not affect the models much, so there is little to gain from searching over more of their values.",
+			self.comparison_metric_name
+		);
+		ui::S2::new()
+			.child(ui::H2::new("Hyperparameter Importance"))
+			.child(ui::P::new().child(description))
+			.child(
+				ui::Table::new()
+					.width("100%".to_owned())
+					.child(
+						ui::TableHeader::new().child(
+							ui::TableRow::new()
+								.child(ui::TableHeaderCell::new().child("Hyperparameter"))
+								.child(ui::TableHeaderCell::new().child("Distinct Values"))
+								.child(ui::TableHeaderCell::new().child("Importance")),
+						),
+					)
+					.children(self.hyperparameter_importances.into_iter().map(
+						|hyperparameter_importance| {
+							ui::TableRow::new()
+								.child(ui::TableCell::new().child(hyperparameter_importance.name))
+								.child(
+									ui::TableCell::new()
+										.child(hyperparameter_importance.n_values.to_string()),
+								)
+								.child(ui::TableCell::new().child(ui::format_percent(
+									hyperparameter_importance.importance,
+								)))
+						},
+					)),
+			)
+			.into_node()
+	}
+}
@@ -1,4 +1,6 @@
-use crate::page::{ConfusionMatrixSection, Page, PrecisionRecallSection};
+use crate::page::{
+	ConfusionMatrixHeatmapSection, ConfusionMatrixSection, Page, PrecisionRecallSection,
+};
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
@@ -35,10 +37,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -75,6 +78,20 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let true_positives = class_metrics.true_positives();
 	let false_negatives = class_metrics.false_negatives();
 	let false_positives = class_metrics.false_positives();
+	// Models trained before the full confusion matrix was saved only have per class metrics.
+	let confusion_matrix_heatmap_section =
+		test_metrics
+			.confusion_matrix()
+			.map(|confusion_matrix| ConfusionMatrixHeatmapSection {
+				classes: classes.clone(),
+				confusion_matrix: confusion_matrix
+					.iter()
+					.collect::<Vec<u64>>()
+					.chunks(classes.len())
+					.map(|row| row.to_vec())
+					.collect(),
+			});
+	let has_roc_curves = test_metrics.class_roc_curves().is_some();
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::TrainingMetrics).await?;
 	let precision_recall_section = PrecisionRecallSection {
@@ -96,7 +113,9 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		class,
 		classes,
 		confusion_matrix_section,
+		confusion_matrix_heatmap_section,
 		precision_recall_section,
+		has_roc_curves,
 	};
 	let html = html(page);
 	let response = http::Response::builder()
@@ -14,6 +14,8 @@ pub struct Page {
 	pub model_layout_info: ModelLayoutInfo,
 	pub precision_recall_section: PrecisionRecallSection,
 	pub confusion_matrix_section: ConfusionMatrixSection,
+	pub confusion_matrix_heatmap_section: Option<ConfusionMatrixHeatmapSection>,
+	pub has_roc_curves: bool,
 }
 
 impl Component for Page {
@@ -30,7 +32,10 @@ impl Component for Page {
 								.child(
 									ui::TabLink::new("class_metrics".to_owned(), true)
 										.child("Class Metrics"),
-								),
+								)
+								.child(self.has_roc_curves.then(|| {
+									ui::TabLink::new("roc".to_owned(), false).child("ROC Curve")
+								})),
 						)
 						.child(
 							ui::Form::new()
@@ -41,7 +46,8 @@ impl Component for Page {
 								.child(noscript().child(ui::Button::new().child("Submit"))),
 						)
 						.child(self.precision_recall_section)
-						.child(self.confusion_matrix_section),
+						.child(self.confusion_matrix_section)
+						.child(self.confusion_matrix_heatmap_section),
 				),
 			)
 			.into_node()
@@ -104,3 +110,23 @@ impl Component for ConfusionMatrixSection {
 			.into_node()
 	}
 }
+
+pub struct ConfusionMatrixHeatmapSection {
+	pub classes: Vec<String>,
+	pub confusion_matrix: Vec<Vec<u64>>,
+}
+
+impl Component for ConfusionMatrixHeatmapSection {
+	fn into_node(self) -> Node {
+		let confusion_matrix_heatmap_definition = "This confusion matrix shows every class at once. Each row is a predicted class and each column is an actual class. Normalize by predicted to see how often predictions of each class are correct, or by actual to see how often examples of each class are recognized. Hover over a cell to see its value.";
+		ui::S2::new()
+			.child(ui::H2::new("All Classes"))
+			.child(ui::P::new().child(confusion_matrix_heatmap_definition))
+			.child(ui::ConfusionMatrixHeatmap {
+				id: "training-confusion-matrix".to_owned(),
+				classes: self.classes,
+				confusion_matrix: self.confusion_matrix,
+			})
+			.into_node()
+	}
+}
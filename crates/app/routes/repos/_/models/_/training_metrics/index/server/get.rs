@@ -1,6 +1,6 @@
 use crate::page::{
 	BinaryClassifier, ClassMetrics, ConfusionMatrixSection, Inner, MulticlassClassifier, Page,
-	Regressor,
+	Regressor, ToleranceAccuracy,
 };
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
@@ -29,10 +29,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -83,9 +84,27 @@ fn build_inner_regressor(model: modelfox_model::RegressorReader) -> Regressor {
 		baseline_rmse: model.baseline_metrics().rmse(),
 		mse: model.test_metrics().mse(),
 		baseline_mse: model.baseline_metrics().mse(),
+		tolerance_accuracy: build_tolerance_accuracy(model),
 	}
 }
 
+fn build_tolerance_accuracy(model: modelfox_model::RegressorReader) -> Option<ToleranceAccuracy> {
+	let tolerance = model.tolerance()?;
+	let tolerance = match tolerance.kind() {
+		modelfox_model::RegressionToleranceKindReader::Absolute(_) => {
+			format!("within {}", tolerance.value())
+		}
+		modelfox_model::RegressionToleranceKindReader::Relative(_) => {
+			format!("within {}%", tolerance.value() * 100.0)
+		}
+	};
+	Some(ToleranceAccuracy {
+		tolerance,
+		baseline: model.baseline_metrics().tolerance_accuracy(),
+		training: model.test_metrics().tolerance_accuracy()?,
+	})
+}
+
 fn build_inner_binary_classifier(
 	model: modelfox_model::BinaryClassifierReader,
 ) -> BinaryClassifier {
@@ -149,10 +168,15 @@ fn build_inner_multiclass_classifier(
 	} else {
 		None
 	};
+	// Models trained before one-vs-rest ROC curves were computed for multiclass classifiers do not have an averaged AUC.
+	let auc_roc_macro = test_metrics.auc_roc_macro();
+	let baseline_auc_roc_macro = baseline_metrics.auc_roc_macro();
 	MulticlassClassifier {
 		warning,
 		accuracy: model.test_metrics().accuracy(),
 		baseline_accuracy: model.baseline_metrics().accuracy(),
+		auc_roc_macro,
+		baseline_auc_roc_macro,
 		class_metrics,
 		classes,
 	}
@@ -7,6 +7,8 @@ pub struct MulticlassClassifier {
 	pub warning: Option<String>,
 	pub accuracy: f32,
 	pub baseline_accuracy: f32,
+	pub auc_roc_macro: Option<f32>,
+	pub baseline_auc_roc_macro: Option<f32>,
 	pub class_metrics: Vec<ClassMetrics>,
 	pub classes: Vec<String>,
 }
@@ -19,6 +21,25 @@ pub struct ClassMetrics {
 impl Component for MulticlassClassifier {
 	fn into_node(self) -> Node {
 		let precision_definition = "Precision is the percentage of examples that were labeled as this class that are actually this class. Recall is the percentage of examples that are of this class that were labeled as this class.";
+		let roc_tab_link = self
+			.auc_roc_macro
+			.map(|_| ui::TabLink::new("roc".to_owned(), false).child("ROC Curve"));
+		let auc_roc_section = self.auc_roc_macro.map(|auc_roc_macro| {
+			ui::S2::new()
+				.child(ui::H2::new("Area Under the Receiver Operating Characteristic"))
+				.child(ui::P::new().child(
+					"The macro averaged AUC ROC is the mean of the one-vs-rest AUC ROC for each class.",
+				))
+				.child(
+					ui::NumberComparisonCard::new(self.baseline_auc_roc_macro, Some(auc_roc_macro))
+						.color_a(BASELINE_COLOR.to_owned())
+						.color_b(TRAINING_COLOR.to_owned())
+						.title("Macro AUC ROC".to_owned())
+						.value_a_title("Baseline".to_owned())
+						.value_b_title("Training".to_owned())
+						.number_formatter(ui::NumberFormatter::Percent(Default::default())),
+				)
+		});
 		ui::S1::new()
 			.child(ui::H1::new("Training Metrics"))
 			.child(
@@ -26,7 +47,8 @@ impl Component for MulticlassClassifier {
 					.child(ui::TabLink::new("".to_owned(), true).child("Overview"))
 					.child(
 						ui::TabLink::new("class_metrics".to_owned(), false).child("Class Metrics"),
-					),
+					)
+					.child(roc_tab_link),
 			)
 			.child(
 				ui::S2::new()
@@ -48,6 +70,7 @@ impl Component for MulticlassClassifier {
 						.number_formatter(ui::NumberFormatter::Percent(Default::default())),
 					),
 			)
+			.child(auc_roc_section)
 			.child(
 				ui::S2::new()
 					.child(ui::H2::new("Precision and Recall"))
@@ -1,3 +1,4 @@
+use modelfox_app_core::alert::AlertMetric;
 use modelfox_app_ui::colors::{BASELINE_COLOR, TRAINING_COLOR};
 use modelfox_ui as ui;
 use pinwheel::prelude::*;
@@ -8,6 +9,14 @@ pub struct Regressor {
 	pub baseline_rmse: f32,
 	pub mse: f32,
 	pub rmse: f32,
+	pub tolerance_accuracy: Option<ToleranceAccuracy>,
+}
+
+pub struct ToleranceAccuracy {
+	/// This describes the tolerance, for example "within 5" or "within 10%".
+	pub tolerance: String,
+	pub baseline: Option<f32>,
+	pub training: f32,
 }
 
 impl Component for Regressor {
@@ -26,7 +35,7 @@ impl Component for Regressor {
 							.title("Root Mean Squared Error".to_owned())
 							.value_a_title("Baseline".to_owned())
 							.value_b_title("Training".to_owned())
-							.number_formatter(ui::NumberFormatter::float_default()),
+							.number_formatter(AlertMetric::RootMeanSquaredError.number_formatter()),
 					)
 					.child(ui::P::new().child(mse_description))
 					.child(
@@ -36,8 +45,25 @@ impl Component for Regressor {
 							.title("Mean Squared Error".to_owned())
 							.value_a_title("Baseline".to_owned())
 							.value_b_title("Training".to_owned())
-							.number_formatter(ui::NumberFormatter::float_default()),
-					),
+							.number_formatter(AlertMetric::MeanSquaredError.number_formatter()),
+					)
+					.child(self.tolerance_accuracy.map(|tolerance_accuracy| {
+						let tolerance_accuracy_description = format!("The tolerance accuracy is the fraction of predictions {} of their corresponding actual value.", tolerance_accuracy.tolerance);
+						fragment()
+							.child(ui::P::new().child(tolerance_accuracy_description))
+							.child(
+								ui::NumberComparisonCard::new(
+									tolerance_accuracy.baseline,
+									Some(tolerance_accuracy.training),
+								)
+								.color_a(BASELINE_COLOR.to_owned())
+								.color_b(TRAINING_COLOR.to_owned())
+								.title("Tolerance Accuracy".to_owned())
+								.value_a_title("Baseline".to_owned())
+								.value_b_title("Training".to_owned())
+								.number_formatter(AlertMetric::ToleranceAccuracy.number_formatter()),
+							)
+					})),
 			)
 			.into_node()
 	}
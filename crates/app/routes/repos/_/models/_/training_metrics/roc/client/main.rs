@@ -1,6 +1,13 @@
+use modelfox_ui as ui;
 use pinwheel::prelude::*;
+use web_sys as dom;
 
 pub fn main() {
 	modelfox_ui::client_start();
+	let window = dom::window().unwrap();
+	let document = window.document().unwrap();
+	if document.get_element_by_id("class_select_field").is_some() {
+		ui::select_field_submit_on_change("class_select_field".to_owned());
+	}
 	hydrate::<modelfox_charts::components::LineChart>("roc");
 }
@@ -1,4 +1,4 @@
-use crate::page::{Page, RocCurveData};
+use crate::page::{AveragedAucRoc, ClassSelect, Page, RocCurveData};
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
@@ -22,14 +22,24 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	} else {
 		bail!("unexpected path");
 	};
+	#[derive(serde::Deserialize, Default)]
+	struct SearchParams {
+		class: Option<String>,
+	}
+	let search_params: Option<SearchParams> = if let Some(query) = request.uri().query() {
+		Some(serde_urlencoded::from_str(query)?)
+	} else {
+		None
+	};
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -60,6 +70,59 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				roc_curve_data,
 				auc_roc,
 				model_layout_info,
+				class_select: None,
+				averaged_auc_roc: None,
+			}
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier = multiclass_classifier.read();
+			let test_metrics = multiclass_classifier.test_metrics();
+			// Models trained before one-vs-rest ROC curves were computed for multiclass classifiers do not have them.
+			let (class_roc_curves, auc_roc_macro, auc_roc_micro) = match (
+				test_metrics.class_roc_curves(),
+				test_metrics.auc_roc_macro(),
+				test_metrics.auc_roc_micro(),
+			) {
+				(Some(class_roc_curves), Some(auc_roc_macro), Some(auc_roc_micro)) => {
+					(class_roc_curves, auc_roc_macro, auc_roc_micro)
+				}
+				_ => return Ok(bad_request()),
+			};
+			let classes: Vec<String> = multiclass_classifier
+				.classes()
+				.iter()
+				.map(ToOwned::to_owned)
+				.collect();
+			let class_index = match search_params.and_then(|s| s.class) {
+				Some(class) => match classes.iter().position(|c| *c == class) {
+					Some(class_index) => class_index,
+					None => return Ok(bad_request()),
+				},
+				None => 0,
+			};
+			let class_roc_curve = class_roc_curves.get(class_index).unwrap();
+			let roc_curve_data = class_roc_curve
+				.points()
+				.iter()
+				.map(|point| RocCurveData {
+					false_positive_rate: point.false_positive_rate(),
+					true_positive_rate: point.true_positive_rate(),
+				})
+				.collect();
+			let auc_roc = class_roc_curve.auc_roc_approx();
+			let model_layout_info =
+				model_layout_info(&mut db, app, model_id, ModelNavItem::TrainingMetrics).await?;
+			Page {
+				id: model_id.to_string(),
+				class: classes[class_index].clone(),
+				roc_curve_data,
+				auc_roc,
+				model_layout_info,
+				class_select: Some(ClassSelect { classes }),
+				averaged_auc_roc: Some(AveragedAucRoc {
+					auc_roc_macro,
+					auc_roc_micro,
+				}),
 			}
 		}
 		_ => {
@@ -2,6 +2,7 @@ use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
 };
+use modelfox_app_ui::{class_select_field::ClassSelectField, metrics_row::MetricsRow};
 use modelfox_charts::{
 	components::LineChart,
 	line_chart::{LineChartPoint, LineChartSeries, LineStyle, PointStyle},
@@ -17,6 +18,18 @@ pub struct Page {
 	pub model_layout_info: ModelLayoutInfo,
 	pub class: String,
 	pub auc_roc: f32,
+	/// This is present for multiclass classifiers, whose page shows the one-vs-rest curve for the selected class.
+	pub class_select: Option<ClassSelect>,
+	pub averaged_auc_roc: Option<AveragedAucRoc>,
+}
+
+pub struct ClassSelect {
+	pub classes: Vec<String>,
+}
+
+pub struct AveragedAucRoc {
+	pub auc_roc_macro: f32,
+	pub auc_roc_micro: f32,
 }
 
 pub struct RocCurveData {
@@ -61,14 +74,52 @@ impl Component for Page {
 				title: Some("Reference".to_owned()),
 			},
 		];
+		let auc_roc_title = if self.class_select.is_some() {
+			format!("AUC ROC for {}", self.class)
+		} else {
+			"AUC ROC".to_owned()
+		};
+		let tab_bar = if self.class_select.is_some() {
+			ui::TabBar::new()
+				.child(ui::TabLink::new("./".to_owned(), false).child("Overview"))
+				.child(ui::TabLink::new("class_metrics".to_owned(), false).child("Class Metrics"))
+				.child(ui::TabLink::new("roc".to_owned(), true).child("ROC Curve"))
+		} else {
+			ui::TabBar::new()
+				.child(ui::TabLink::new("./".to_owned(), false).child("Overview"))
+				.child(ui::TabLink::new("precision_recall".to_owned(), false).child("PR Curve"))
+				.child(ui::TabLink::new("roc".to_owned(), true).child("ROC Curve"))
+		};
+		let class_select_form = self.class_select.map(|class_select| {
+			ui::Form::new()
+				.child(ClassSelectField {
+					class: self.class.clone(),
+					classes: class_select.classes,
+				})
+				.child(noscript().child(ui::Button::new().child("Submit")))
+		});
+		let averaged_auc_roc_section = self.averaged_auc_roc.map(|averaged_auc_roc| {
+			let averaged_auc_roc_description = "The macro averaged AUC is the mean of each class's one-vs-rest AUC, so every class counts equally. The micro averaged AUC pools the one-vs-rest predictions for every class into a single curve, so classes with more examples count more.";
+			ui::S2::new()
+				.child(ui::H2::new("Averaged Area Under the Receiver Operating Characteristic"))
+				.child(ui::P::new().child(averaged_auc_roc_description))
+				.child(
+					MetricsRow::new()
+						.child(ui::NumberCard::new(
+							"Macro AUC ROC".to_owned(),
+							ui::format_percent(averaged_auc_roc.auc_roc_macro),
+						))
+						.child(ui::NumberCard::new(
+							"Micro AUC ROC".to_owned(),
+							ui::format_percent(averaged_auc_roc.auc_roc_micro),
+						)),
+				)
+		});
 		let content = ui::S1::new()
 			.child(ui::H1::new("Training Metrics"))
-			.child(
-				ui::TabBar::new()
-					.child(ui::TabLink::new("./".to_owned(), false).child("Overview"))
-					.child(ui::TabLink::new("precision_recall".to_owned(), false).child("PR Curve"))
-					.child(ui::TabLink::new("roc".to_owned(), true).child("ROC Curve")),
-			)
+			.child(tab_bar)
+			.child(averaged_auc_roc_section)
+			.child(class_select_form)
 			.child(
 				ui::S2::new()
 					.child(ui::H2::new(
@@ -76,7 +127,7 @@ impl Component for Page {
 					))
 					.child(ui::P::new().child(aucroc_description))
 					.child(ui::NumberCard::new(
-						"AUC ROC".to_owned(),
+						auc_roc_title,
 						ui::format_percent(self.auc_roc),
 					)),
 			)
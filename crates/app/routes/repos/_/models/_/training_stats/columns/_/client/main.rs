@@ -1,3 +1,4 @@
+use modelfox_ui as ui;
 use pinwheel::prelude::*;
 use web_sys as dom;
 
@@ -5,6 +6,12 @@ pub fn main() {
 	modelfox_ui::client_start();
 	let window = dom::window().unwrap();
 	let document = window.document().unwrap();
+	if document
+		.get_element_by_id("axis_scale_select_field")
+		.is_some()
+	{
+		ui::select_field_submit_on_change("axis_scale_select_field".to_owned());
+	}
 	if document.get_element_by_id("enum_histogram").is_some() {
 		hydrate::<modelfox_charts::components::BarChart>("enum_histogram");
 	}
@@ -14,6 +21,9 @@ pub fn main() {
 	if document.get_element_by_id("number_histogram").is_some() {
 		hydrate::<modelfox_charts::components::BarChart>("number_histogram");
 	}
+	if document.get_element_by_id("datetime_histogram").is_some() {
+		hydrate::<modelfox_charts::components::BarChart>("datetime_histogram");
+	}
 	if document.get_element_by_id("ngram_histogram").is_some() {
 		hydrate::<modelfox_charts::components::BarChart>("ngram_histogram");
 	}
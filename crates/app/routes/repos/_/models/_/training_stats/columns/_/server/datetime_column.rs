@@ -0,0 +1,108 @@
+use modelfox_app_ui::{axis_scale_select_field::AxisScaleSelectField, metrics_row::MetricsRow};
+use modelfox_charts::{
+	bar_chart::{BarChartPoint, BarChartSeries},
+	common::AxisScale,
+	components::BarChart,
+};
+use modelfox_ui as ui;
+use num::ToPrimitive;
+use pinwheel::prelude::*;
+
+pub struct DateTimeColumn {
+	pub histogram: Vec<(String, u64)>,
+	pub histogram_scale: AxisScale,
+	pub invalid_count: u64,
+	pub max: Option<String>,
+	pub min: Option<String>,
+	pub name: String,
+	pub timezone: String,
+	pub unique_count: u64,
+}
+
+impl Component for DateTimeColumn {
+	fn into_node(self) -> Node {
+		let histogram = if self.histogram.is_empty() {
+			None
+		} else {
+			Some(DateTimeColumnHistogramChart {
+				name: self.name.clone(),
+				histogram: self.histogram,
+				histogram_scale: self.histogram_scale,
+			})
+		};
+		ui::S1::new()
+			.child(ui::H1::new(self.name.clone()))
+			.child(
+				ui::S2::new()
+					.child(
+						MetricsRow::new()
+							.child(ui::NumberCard::new(
+								"Unique Count".to_owned(),
+								self.unique_count.to_string(),
+							))
+							.child(ui::NumberCard::new(
+								"Invalid Count".to_owned(),
+								self.invalid_count.to_string(),
+							))
+							.child(ui::NumberCard::new("Timezone".to_owned(), self.timezone)),
+					)
+					.child(
+						MetricsRow::new()
+							.child(ui::NumberCard::new(
+								"Min".to_owned(),
+								self.min.unwrap_or_else(|| "N/A".to_owned()),
+							))
+							.child(ui::NumberCard::new(
+								"Max".to_owned(),
+								self.max.unwrap_or_else(|| "N/A".to_owned()),
+							)),
+					)
+					.child(histogram),
+			)
+			.into_node()
+	}
+}
+
+pub struct DateTimeColumnHistogramChart {
+	name: String,
+	histogram: Vec<(String, u64)>,
+	histogram_scale: AxisScale,
+}
+
+impl Component for DateTimeColumnHistogramChart {
+	fn into_node(self) -> Node {
+		let data = self
+			.histogram
+			.iter()
+			.enumerate()
+			.map(|(i, (label, count))| BarChartPoint {
+				label: label.clone(),
+				x: i.to_f64().unwrap(),
+				y: Some(count.to_f64().unwrap()),
+				error: None,
+			})
+			.collect();
+		let chart_series = vec![BarChartSeries {
+			color: ui::colors::BLUE.to_owned(),
+			data,
+			title: Some("Count".to_owned()),
+		}];
+		let datetime_histogram_title = Some(format!("Histogram of Values for {}", self.name));
+		fragment()
+			.child(ui::Form::new().child(AxisScaleSelectField::new(self.histogram_scale)))
+			.child(
+				ui::Card::new().child(Dehydrate::new(
+					"datetime_histogram",
+					BarChart::new()
+						.hide_legend(true)
+						.series(chart_series)
+						.title(datetime_histogram_title)
+						.x_axis_title(self.name)
+						.y_axis_scale(self.histogram_scale)
+						.y_axis_title("Count".to_owned())
+						.y_min(0.0),
+				)),
+			)
+			.into_node()
+	}
+}
@@ -1,6 +1,7 @@
-use modelfox_app_ui::metrics_row::MetricsRow;
+use modelfox_app_ui::{axis_scale_select_field::AxisScaleSelectField, metrics_row::MetricsRow};
 use modelfox_charts::{
 	bar_chart::{BarChartPoint, BarChartSeries},
+	common::AxisScale,
 	components::BarChart,
 };
 use modelfox_ui as ui;
@@ -9,6 +10,7 @@ use pinwheel::prelude::*;
 
 pub struct EnumColumn {
 	pub unique_values_chart_data: Option<Vec<(String, u64)>>,
+	pub histogram_scale: AxisScale,
 	pub unique_values_table_rows: Option<Vec<(String, u64, f64)>>,
 	pub invalid_count: u64,
 	pub name: String,
@@ -36,6 +38,7 @@ impl Component for EnumColumn {
 					.child(
 						self.unique_values_chart_data
 							.map(|unique_values_chart_data| EnumColumnUniqueValuesChart {
+								histogram_scale: self.histogram_scale,
 								name,
 								unique_values_chart_data,
 							}),
@@ -52,6 +55,7 @@ impl Component for EnumColumn {
 }
 
 pub struct EnumColumnUniqueValuesChart {
+	histogram_scale: AxisScale,
 	name: String,
 	unique_values_chart_data: Vec<(String, u64)>,
 }
@@ -66,6 +70,7 @@ impl Component for EnumColumnUniqueValuesChart {
 				label: value.clone(),
 				x: i.to_f64().unwrap(),
 				y: Some(count.to_f64().unwrap()),
+				error: None,
 			})
 			.collect();
 		let chart_series = vec![BarChartSeries {
@@ -74,17 +79,21 @@ impl Component for EnumColumnUniqueValuesChart {
 			title: Some("Unique Values".to_owned()),
 		}];
 		let enum_histogram_title = Some(format!("Histogram of Unique Values for {}", self.name));
-		ui::Card::new()
-			.child(Dehydrate::new(
-				"enum_histogram",
-				BarChart::new()
-					.hide_legend(true)
-					.series(chart_series)
-					.title(enum_histogram_title)
-					.x_axis_title(self.name)
-					.y_axis_title("Count".to_owned())
-					.y_min(0.0),
-			))
+		fragment()
+			.child(ui::Form::new().child(AxisScaleSelectField::new(self.histogram_scale)))
+			.child(
+				ui::Card::new().child(Dehydrate::new(
+					"enum_histogram",
+					BarChart::new()
+						.hide_legend(true)
+						.series(chart_series)
+						.title(enum_histogram_title)
+						.x_axis_title(self.name)
+						.y_axis_scale(self.histogram_scale)
+						.y_axis_title("Count".to_owned())
+						.y_min(0.0),
+				)),
+			)
 			.into_node()
 	}
 }
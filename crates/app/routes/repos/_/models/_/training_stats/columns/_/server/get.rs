@@ -1,9 +1,15 @@
-use crate::page::{EnumColumn, Inner, NGramStats, NGramsTableRow, NumberColumn, Page, TextColumn};
+use crate::page::{
+	DateTimeColumn, EnumColumn, Inner, NGramStats, NGramsTableRow, NumberColumn, Page, TextColumn,
+	Vocabulary,
+};
 use anyhow::{bail, Result};
+use chrono::prelude::*;
+use chrono_tz::Tz;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
 	heuristics::{
+		TRAINING_STATS_TEXT_COLUMN_MAX_DROPPED_TOKENS_TO_SHOW_IN_TABLE,
 		TRAINING_STATS_TEXT_COLUMN_MAX_TOKENS_TO_SHOW_IN_CHART,
 		TRAINING_STATS_TEXT_COLUMN_MAX_TOKENS_TO_SHOW_IN_TABLE,
 	},
@@ -12,11 +18,12 @@ use modelfox_app_core::{
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
+use modelfox_charts::common::AxisScale;
 use modelfox_id::Id;
 use modelfox_ui as ui;
 use num::ToPrimitive;
 use pinwheel::prelude::*;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
 	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
@@ -25,10 +32,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let (model_id, column_name) = if let ["repos", _, "models", model_id, "training_stats", "columns", column_name] =
 		path_components(request).as_slice()
 	{
@@ -36,6 +44,18 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	} else {
 		bail!("unexpected path");
 	};
+	#[derive(serde::Deserialize, Default)]
+	struct SearchParams {
+		scale: Option<AxisScale>,
+	}
+	let search_params: SearchParams = match request.uri().query() {
+		Some(query) => match serde_urlencoded::from_str(query) {
+			Ok(search_params) => search_params,
+			Err(_) => return Ok(bad_request()),
+		},
+		None => SearchParams::default(),
+	};
+	let histogram_scale = search_params.scale.unwrap_or_default();
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -84,6 +104,15 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		modelfox_model::ColumnStatsReader::UnknownColumn(_) => unimplemented!(),
 		modelfox_model::ColumnStatsReader::NumberColumn(column_stats) => {
 			let column_stats = column_stats.read();
+			// Models trained before the quantiles were configurable only store the quartiles.
+			let quantiles = match column_stats.quantiles() {
+				Some(quantiles) => quantiles.iter().collect(),
+				None => vec![
+					(0.25, column_stats.p25()),
+					(0.50, column_stats.p50()),
+					(0.75, column_stats.p75()),
+				],
+			};
 			Inner::Number(NumberColumn {
 				invalid_count: column_stats.invalid_count(),
 				min: column_stats.min(),
@@ -93,6 +122,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				p25: column_stats.p25(),
 				p50: column_stats.p50(),
 				p75: column_stats.p75(),
+				quantiles,
 				std: column_stats.std(),
 				unique_count: column_stats.unique_count(),
 			})
@@ -105,6 +135,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				.map(|(_, count)| count)
 				.sum();
 			Inner::Enum(EnumColumn {
+				histogram_scale,
 				unique_values_chart_data: Some(
 					column_stats
 						.histogram()
@@ -159,11 +190,58 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				.collect();
 			top_ngrams_chart_values
 				.truncate(TRAINING_STATS_TEXT_COLUMN_MAX_TOKENS_TO_SHOW_IN_CHART);
+			let language = column_stats
+				.language()
+				.and_then(|language| language.parse::<modelfox_text::Language>().ok())
+				.map(|language| language.to_string());
+			let vocabulary = bag_of_words_feature_group_ngrams(model, column_stats.column_name())
+				.map(|vocabulary_ngrams| {
+					let dropped_ngrams_table_rows = column_stats
+						.top_ngrams()
+						.iter()
+						.map(|(ngram, entry)| (ngram.to_string(), entry.row_count()))
+						.filter(|(ngram, _)| !vocabulary_ngrams.contains(ngram))
+						.take(TRAINING_STATS_TEXT_COLUMN_MAX_DROPPED_TOKENS_TO_SHOW_IN_TABLE)
+						.map(|(ngram, row_count)| NGramsTableRow {
+							ngram,
+							count: row_count,
+						})
+						.collect();
+					Vocabulary {
+						size: vocabulary_ngrams.len(),
+						dropped_ngrams_table_rows,
+					}
+				});
 			Inner::Text(TextColumn {
+				histogram_scale,
 				name: column_stats.column_name().to_owned(),
+				language,
 				ngram_count,
 				top_ngrams_chart_values,
 				ngrams_table_rows,
+				vocabulary,
+			})
+		}
+		modelfox_model::ColumnStatsReader::DateTimeColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			let timezone: Tz = column_stats.timezone().parse().unwrap_or(Tz::UTC);
+			let format = |value: i64| timezone.timestamp(value, 0).to_rfc3339();
+			Inner::DateTime(DateTimeColumn {
+				histogram_scale,
+				histogram: column_stats
+					.histogram()
+					.iter()
+					.map(|(value, count)| {
+						let label = timezone.timestamp(value, 0).format("%Y-%m-%d").to_string();
+						(label, count)
+					})
+					.collect(),
+				invalid_count: column_stats.invalid_count(),
+				max: column_stats.max().map(format),
+				min: column_stats.min().map(format),
+				name: column_stats.column_name().to_owned(),
+				timezone: timezone.name().to_owned(),
+				unique_count: column_stats.unique_count(),
 			})
 		}
 	};
@@ -181,3 +259,59 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	app.commit_transaction(db).await?;
 	Ok(response)
 }
+
+/// Retrieve the ngrams in the vocabulary of the bag of words feature group the model built for the column with `column_name`, if it has one.
+fn bag_of_words_feature_group_ngrams(
+	model: modelfox_model::ModelReader,
+	column_name: &str,
+) -> Option<HashSet<String>> {
+	let feature_groups = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => match regressor.read().model() {
+			modelfox_model::RegressionModelReader::Linear(inner_model) => {
+				inner_model.read().feature_groups()
+			}
+			modelfox_model::RegressionModelReader::Tree(inner_model) => {
+				inner_model.read().feature_groups()
+			}
+		},
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			match binary_classifier.read().model() {
+				modelfox_model::BinaryClassificationModelReader::Linear(inner_model) => {
+					inner_model.read().feature_groups()
+				}
+				modelfox_model::BinaryClassificationModelReader::Tree(inner_model) => {
+					inner_model.read().feature_groups()
+				}
+			}
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			match multiclass_classifier.read().model() {
+				modelfox_model::MulticlassClassificationModelReader::Linear(inner_model) => {
+					inner_model.read().feature_groups()
+				}
+				modelfox_model::MulticlassClassificationModelReader::Tree(inner_model) => {
+					inner_model.read().feature_groups()
+				}
+			}
+		}
+	};
+	feature_groups
+		.iter()
+		.find_map(|feature_group| match feature_group {
+			modelfox_model::FeatureGroupReader::BagOfWords(feature_group) => {
+				let feature_group = feature_group.read();
+				if feature_group.source_column_name() == column_name {
+					Some(
+						feature_group
+							.ngrams()
+							.iter()
+							.map(|(ngram, _)| ngram.to_string())
+							.collect(),
+					)
+				} else {
+					None
+				}
+			}
+			_ => None,
+		})
+}
@@ -1,3 +1,4 @@
+mod datetime_column;
 mod enum_column;
 mod get;
 mod number_column;
@@ -17,6 +17,7 @@ pub struct NumberColumn {
 	pub p25: f32,
 	pub p50: f32,
 	pub p75: f32,
+	pub quantiles: Vec<(f32, f32)>,
 	pub std: f32,
 	pub unique_count: u64,
 }
@@ -76,21 +77,9 @@ impl Component for NumberColumn {
 								ui::format_float(self.max),
 							)),
 					)
-					.child(
-						MetricsRow::new()
-							.child(ui::NumberCard::new(
-								"p25".to_owned(),
-								ui::format_float(self.p25),
-							))
-							.child(ui::NumberCard::new(
-								"p50 (median)".to_owned(),
-								ui::format_float(self.p50),
-							))
-							.child(ui::NumberCard::new(
-								"p75".to_owned(),
-								ui::format_float(self.p75),
-							)),
-					)
+					.child(NumberColumnQuantilesTable {
+						quantiles: self.quantiles,
+					})
 					.child(
 						ui::Card::new().child(Dehydrate::new(
 							"number_quantiles",
@@ -103,3 +92,29 @@ impl Component for NumberColumn {
 			.into_node()
 	}
 }
+
+pub struct NumberColumnQuantilesTable {
+	quantiles: Vec<(f32, f32)>,
+}
+
+impl Component for NumberColumnQuantilesTable {
+	fn into_node(self) -> Node {
+		ui::Table::new()
+			.width("100%".to_owned())
+			.child(
+				ui::TableHeader::new().child(
+					ui::TableRow::new()
+						.child(ui::TableHeaderCell::new().child("Quantile"))
+						.child(ui::TableHeaderCell::new().child("Value")),
+				),
+			)
+			.child(
+				ui::TableBody::new().children(self.quantiles.iter().map(|(quantile, value)| {
+					ui::TableRow::new()
+						.child(ui::TableCell::new().child(ui::format_quantile(*quantile)))
+						.child(ui::TableCell::new().child(ui::format_float(*value)))
+				})),
+			)
+			.into_node()
+	}
+}
@@ -1,4 +1,4 @@
-pub use crate::{enum_column::*, number_column::*, text_column::*};
+pub use crate::{datetime_column::*, enum_column::*, number_column::*, text_column::*};
 use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
@@ -14,6 +14,7 @@ pub enum Inner {
 	Number(NumberColumn),
 	Enum(EnumColumn),
 	Text(TextColumn),
+	DateTime(DateTimeColumn),
 }
 
 impl Component for Page {
@@ -22,6 +23,7 @@ impl Component for Page {
 			Inner::Number(inner) => inner.into_node(),
 			Inner::Enum(inner) => inner.into_node(),
 			Inner::Text(inner) => inner.into_node(),
+			Inner::DateTime(inner) => inner.into_node(),
 		};
 		Document::new()
 			.client("modelfox_app_training_stats_column_client")
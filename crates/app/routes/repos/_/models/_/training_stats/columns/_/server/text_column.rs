@@ -1,5 +1,7 @@
+use modelfox_app_ui::{axis_scale_select_field::AxisScaleSelectField, metrics_row::MetricsRow};
 use modelfox_charts::{
 	bar_chart::{BarChartPoint, BarChartSeries},
+	common::AxisScale,
 	components::BarChart,
 };
 use modelfox_ui as ui;
@@ -7,10 +9,19 @@ use num::ToPrimitive;
 use pinwheel::prelude::*;
 
 pub struct TextColumn {
+	pub histogram_scale: AxisScale,
 	pub name: String,
+	pub language: Option<String>,
 	pub ngram_count: usize,
 	pub top_ngrams_chart_values: Vec<NGramStats>,
 	pub ngrams_table_rows: Vec<NGramsTableRow>,
+	pub vocabulary: Option<Vocabulary>,
+}
+
+/// This is the vocabulary of the bag of words feature group built for the column, if the model has one.
+pub struct Vocabulary {
+	pub size: usize,
+	pub dropped_ngrams_table_rows: Vec<NGramsTableRow>,
 }
 
 #[derive(Clone)]
@@ -38,11 +49,16 @@ impl Component for TextColumn {
 					label: stats.ngram.clone(),
 					x: i.to_f64().unwrap(),
 					y: Some(stats.occurrence_count.to_f64().unwrap()),
+					error: None,
 				})
 				.collect(),
 			title: Some("NGram Count".to_owned()),
 		}];
-		let description = "Each value in this column was broken up into individual tokens. View the most frequent ngrams in the chart below.";
+		let description = match self.language.as_ref() {
+			Some(_) => "Each value in this column was broken up into individual tokens. Stop words in the column's language, such as \"the\" or \"and\" in English, are excluded from the ngrams. View the most frequent ngrams in the chart below.",
+			None => "Each value in this column was broken up into individual tokens. View the most frequent ngrams in the chart below.",
+		};
+		let language = self.language.unwrap_or_else(|| "Not Detected".to_owned());
 		let chart_title = format!(
 			"{} Most Frequent NGrams",
 			self.top_ngrams_chart_values.len()
@@ -70,14 +86,70 @@ impl Component for TextColumn {
 					)),
 				),
 		);
+		let vocabulary_size_card = self.vocabulary.as_ref().map(|vocabulary| {
+			ui::NumberCard::new("Vocabulary Size".to_owned(), vocabulary.size.to_string())
+		});
+		let dropped_ngrams_section = self
+			.vocabulary
+			.filter(|vocabulary| !vocabulary.dropped_ngrams_table_rows.is_empty())
+			.map(|vocabulary| {
+				let title = format!(
+					"Top {} Dropped NGrams",
+					vocabulary.dropped_ngrams_table_rows.len()
+				);
+				ui::S2::new()
+					.child(ui::H2::new(title))
+					.child(ui::P::new().child(
+						"These ngrams appear in the column but were left out of the bag of words vocabulary by the feature group's vocabulary size, document frequency, or stop word settings.",
+					))
+					.child(
+						ui::Table::new()
+							.width("100%".to_owned())
+							.child(
+								ui::TableHeader::new()
+									.child(ui::TableHeaderCell::new().child("Token"))
+									.child(ui::TableHeaderCell::new().child("Row Count")),
+							)
+							.child(ui::TableBody::new().children(
+								vocabulary.dropped_ngrams_table_rows.into_iter().map(
+									|ngram_table_row| {
+										ui::TableRow::new()
+											.child(ui::TableCell::new().child(ngram_table_row.ngram))
+											.child(
+												ui::TableCell::new()
+													.child(ngram_table_row.count.to_string()),
+											)
+									},
+								),
+							)),
+					)
+			});
 		ui::S1::new()
 			.child(ui::H1::new(self.name))
 			.child(ui::S2::new().child(ui::P::new().child(description)))
-			.child(ui::S2::new().child(ui::Card::new().child(Dehydrate::new(
-				"ngram_histogram",
-				BarChart::new().series(series).title(chart_title).y_min(0.0),
-			))))
+			.child(
+				ui::S2::new().child(
+					MetricsRow::new()
+						.child(ui::NumberCard::new("Language".to_owned(), language))
+						.child(vocabulary_size_card),
+				),
+			)
+			.child(
+				ui::S2::new()
+					.child(ui::Form::new().child(AxisScaleSelectField::new(self.histogram_scale)))
+					.child(
+						ui::Card::new().child(Dehydrate::new(
+							"ngram_histogram",
+							BarChart::new()
+								.series(series)
+								.title(chart_title)
+								.y_axis_scale(self.histogram_scale)
+								.y_min(0.0),
+						)),
+					),
+			)
 			.child(ui::S2::new().child(table_section))
+			.child(dropped_ngrams_section)
 			.into_node()
 	}
 }
@@ -0,0 +1,163 @@
+use crate::page::{CorrelationTableRow, Correlations, Inner, Page};
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	heuristics::{
+		TRAINING_STATS_CORRELATION_HEATMAP_MAX_COLUMNS,
+		TRAINING_STATS_TARGET_CORRELATION_LEAKAGE_THRESHOLD,
+	},
+	model::get_model_bytes,
+	path_components,
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
+use modelfox_id::Id;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+use std::sync::Arc;
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id = if let ["repos", _, "models", model_id, "training_stats", "correlations"] =
+		path_components(request).as_slice()
+	{
+		model_id.to_owned()
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let bytes = get_model_bytes(app.storage(), model_id).await?;
+	let model = modelfox_model::from_bytes(&bytes)?;
+	let model_layout_info =
+		model_layout_info(&mut db, app, model_id, ModelNavItem::TrainingStats).await?;
+	let (target_column_name, column_correlations) = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			let regressor = regressor.read();
+			(
+				regressor.target_column_name(),
+				regressor.column_correlations(),
+			)
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			let binary_classifier = binary_classifier.read();
+			(
+				binary_classifier.target_column_name(),
+				binary_classifier.column_correlations(),
+			)
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier = multiclass_classifier.read();
+			(
+				multiclass_classifier.target_column_name(),
+				multiclass_classifier.column_correlations(),
+			)
+		}
+	};
+	// Models trained before column correlations were added do not have them.
+	let inner = match column_correlations {
+		Some(column_correlations) => {
+			let table_rows = column_correlations
+				.iter()
+				.map(|column_correlation| build_correlation_table_row(&column_correlation))
+				.collect();
+			Inner::Correlations(compute_correlations(target_column_name, table_rows))
+		}
+		None => Inner::NotComputed,
+	};
+	let page = Page {
+		model_layout_info,
+		inner,
+	};
+	let html = html(page);
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html))
+		.unwrap();
+	app.commit_transaction(db).await?;
+	Ok(response)
+}
+
+fn build_correlation_table_row(
+	column_correlation: &modelfox_model::ColumnCorrelationReader,
+) -> CorrelationTableRow {
+	CorrelationTableRow {
+		column_name_a: column_correlation.column_name_a().to_owned(),
+		column_name_b: column_correlation.column_name_b().to_owned(),
+		method: match column_correlation.method() {
+			modelfox_model::CorrelationMethodReader::Pearson(_) => "Pearson",
+			modelfox_model::CorrelationMethodReader::CramersV(_) => "Cramér's V",
+		},
+		value: column_correlation.value(),
+	}
+}
+
+fn compute_correlations(
+	target_column_name: &str,
+	table_rows: Vec<CorrelationTableRow>,
+) -> Correlations {
+	// The columns that are highly correlated with the target may be leaking it.
+	let leaking_column_names = table_rows
+		.iter()
+		.filter(|row| row.value.abs() >= TRAINING_STATS_TARGET_CORRELATION_LEAKAGE_THRESHOLD)
+		.filter_map(|row| {
+			if row.column_name_a == target_column_name {
+				Some(row.column_name_b.clone())
+			} else if row.column_name_b == target_column_name {
+				Some(row.column_name_a.clone())
+			} else {
+				None
+			}
+		})
+		.collect();
+	// The heatmap shows the columns in the most correlated pairs, in the order they first appear.
+	let mut heatmap_column_names: Vec<String> = Vec::new();
+	for row in table_rows.iter() {
+		for column_name in [&row.column_name_a, &row.column_name_b] {
+			if heatmap_column_names.len() < TRAINING_STATS_CORRELATION_HEATMAP_MAX_COLUMNS
+				&& !heatmap_column_names.contains(column_name)
+			{
+				heatmap_column_names.push(column_name.clone());
+			}
+		}
+	}
+	let mut heatmap_correlations =
+		vec![vec![None; heatmap_column_names.len()]; heatmap_column_names.len()];
+	for row in table_rows.iter() {
+		let index_a = heatmap_column_names
+			.iter()
+			.position(|column_name| *column_name == row.column_name_a);
+		let index_b = heatmap_column_names
+			.iter()
+			.position(|column_name| *column_name == row.column_name_b);
+		if let (Some(index_a), Some(index_b)) = (index_a, index_b) {
+			heatmap_correlations[index_a][index_b] = Some(row.value);
+			heatmap_correlations[index_b][index_a] = Some(row.value);
+		}
+	}
+	Correlations {
+		target_column_name: target_column_name.to_owned(),
+		leaking_column_names,
+		heatmap: ui::CorrelationHeatmap {
+			column_names: heatmap_column_names,
+			correlations: heatmap_correlations,
+		},
+		table_rows,
+	}
+}
@@ -0,0 +1,110 @@
+use modelfox_app_layouts::{
+	document::Document,
+	model_layout::{ModelLayout, ModelLayoutInfo},
+};
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct Page {
+	pub model_layout_info: ModelLayoutInfo,
+	pub inner: Inner,
+}
+
+pub enum Inner {
+	NotComputed,
+	Correlations(Correlations),
+}
+
+pub struct Correlations {
+	pub target_column_name: String,
+	/// These are the columns whose correlation with the target column is so high that they may be leaking it.
+	pub leaking_column_names: Vec<String>,
+	pub heatmap: ui::CorrelationHeatmap,
+	pub table_rows: Vec<CorrelationTableRow>,
+}
+
+pub struct CorrelationTableRow {
+	pub column_name_a: String,
+	pub column_name_b: String,
+	pub method: &'static str,
+	pub value: f32,
+}
+
+impl Component for Page {
+	fn into_node(self) -> Node {
+		let inner = match self.inner {
+			Inner::NotComputed => ui::P::new()
+				.child("This model was trained before column correlations were computed. Train it again to see them.")
+				.into_node(),
+			Inner::Correlations(correlations) => correlations.into_node(),
+		};
+		Document::new()
+			.child(
+				ModelLayout::new(self.model_layout_info).child(
+					ui::S1::new()
+						.child(ui::H1::new("Column Correlations"))
+						.child(ui::P::new().child(
+							"These are the pairs of columns in the training data that are most correlated with each other, including the target column. Pairs of number columns use the Pearson correlation, which is between -1 and 1, and pairs of enum columns use Cramér's V, which is between 0 and 1. Pairs of a number column and an enum column are not compared.",
+						))
+						.child(inner),
+				),
+			)
+			.into_node()
+	}
+}
+
+impl Component for Correlations {
+	fn into_node(self) -> Node {
+		if self.table_rows.is_empty() {
+			return ui::P::new()
+				.child("The training data does not have any pairs of number or enum columns to compare.")
+				.into_node();
+		}
+		let leakage_alert = if self.leaking_column_names.is_empty() {
+			None
+		} else {
+			Some(
+				ui::Alert::new(ui::Level::Warning)
+					.title("Possible Target Leakage".to_owned())
+					.child(format!(
+						"These columns are almost perfectly correlated with the target column \"{}\": {}. If their values are not known at the time of prediction, remove them from the training data.",
+						self.target_column_name,
+						self.leaking_column_names.join(", "),
+					)),
+			)
+		};
+		let table_header = ui::TableRow::new()
+			.child(ui::TableHeaderCell::new().child("Column"))
+			.child(ui::TableHeaderCell::new().child("Column"))
+			.child(ui::TableHeaderCell::new().child("Method"))
+			.child(ui::TableHeaderCell::new().child("Correlation"));
+		let table_body = self.table_rows.into_iter().map(|row| {
+			ui::TableRow::new()
+				.child(ui::TableCell::new().child(row.column_name_a))
+				.child(ui::TableCell::new().child(row.column_name_b))
+				.child(ui::TableCell::new().child(row.method))
+				.child(ui::TableCell::new().child(ui::format_float(row.value)))
+		});
+		fragment()
+			.child(leakage_alert)
+			.child(
+				ui::S2::new()
+					.child(ui::H2::new("Heatmap"))
+					.child(ui::P::new().child(
+						"Positive correlations are blue and negative correlations are red. Strongly correlated features carry the same information, which makes linear model coefficients hard to interpret.",
+					))
+					.child(self.heatmap),
+			)
+			.child(
+				ui::S2::new()
+					.child(ui::H2::new("Most Correlated Pairs"))
+					.child(
+						ui::Table::new()
+							.width("100%".to_owned())
+							.child(ui::TableHeader::new().child(table_header))
+							.child(ui::TableBody::new().children(table_body)),
+					),
+			)
+			.into_node()
+	}
+}
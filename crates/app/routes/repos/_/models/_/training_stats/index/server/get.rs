@@ -1,14 +1,18 @@
-use crate::page::{ColumnStatsTable, ColumnStatsTableRow, Page, TargetColumnStatsTable};
+use crate::page::{
+	ColumnStatsFilters, ColumnStatsSort, ColumnStatsTable, ColumnStatsTableRow, Page,
+	TargetColumnStatsTable,
+};
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	heuristics::TRAINING_STATS_COLUMNS_PER_PAGE,
 	model::get_model_bytes,
 	path_components,
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
-use modelfox_app_ui::column_type::ColumnType;
+use modelfox_app_ui::{column_type::ColumnType, pagination::compute_pagination};
 use modelfox_id::Id;
 use num::ToPrimitive;
 use pinwheel::prelude::*;
@@ -28,10 +32,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -39,75 +44,123 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	if !authorize_user_for_model(&mut db, &user, model_id).await? {
 		return Ok(not_found());
 	}
+	#[derive(serde::Deserialize, Default)]
+	struct SearchParams {
+		name: Option<String>,
+		column_type: Option<String>,
+		sort: Option<ColumnStatsSort>,
+		offset: Option<usize>,
+	}
+	let search_params: SearchParams = match request.uri().query() {
+		Some(query) => match serde_urlencoded::from_str(query) {
+			Ok(search_params) => search_params,
+			Err(_) => return Ok(bad_request()),
+		},
+		None => SearchParams::default(),
+	};
 	let bytes = get_model_bytes(app.storage(), model_id).await?;
 	let model = modelfox_model::from_bytes(&bytes)?;
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::TrainingStats).await?;
-	let page = match model.inner() {
+	let (column_stats, target_column_stats, row_count) = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => {
 			let regressor = regressor.read();
-			let column_stats = regressor.overall_column_stats();
-			Page {
-				column_stats_table: ColumnStatsTable {
-					column_stats_table_rows: column_stats
-						.iter()
-						.map(|column_stats| build_column_stats(&column_stats))
-						.collect(),
-				},
-				model_layout_info,
-				column_count: column_stats.len(),
-				row_count: regressor.test_row_count().to_usize().unwrap()
+			(
+				regressor.overall_column_stats(),
+				regressor.overall_target_column_stats(),
+				regressor.test_row_count().to_usize().unwrap()
 					+ regressor.train_row_count().to_usize().unwrap(),
-				target_column_stats_table: TargetColumnStatsTable {
-					target_column_stats_table_row: build_column_stats(
-						&regressor.overall_target_column_stats(),
-					),
-				},
-			}
+			)
 		}
 		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
 			let binary_classifier = binary_classifier.read();
-			let column_stats = binary_classifier.overall_column_stats();
-			Page {
-				column_stats_table: ColumnStatsTable {
-					column_stats_table_rows: column_stats
-						.iter()
-						.map(|column_stats| build_column_stats(&column_stats))
-						.collect(),
-				},
-				model_layout_info,
-				column_count: column_stats.len(),
-				row_count: binary_classifier.test_row_count().to_usize().unwrap()
+			(
+				binary_classifier.overall_column_stats(),
+				binary_classifier.overall_target_column_stats(),
+				binary_classifier.test_row_count().to_usize().unwrap()
 					+ binary_classifier.train_row_count().to_usize().unwrap(),
-				target_column_stats_table: TargetColumnStatsTable {
-					target_column_stats_table_row: build_column_stats(
-						&binary_classifier.overall_target_column_stats(),
-					),
-				},
-			}
+			)
 		}
 		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
 			let multiclass_classifier = multiclass_classifier.read();
-			let column_stats = multiclass_classifier.overall_column_stats();
-			Page {
-				column_stats_table: ColumnStatsTable {
-					column_stats_table_rows: column_stats
-						.iter()
-						.map(|column_stats| build_column_stats(&column_stats))
-						.collect(),
-				},
-				model_layout_info,
-				row_count: multiclass_classifier.test_row_count().to_usize().unwrap()
+			(
+				multiclass_classifier.overall_column_stats(),
+				multiclass_classifier.overall_target_column_stats(),
+				multiclass_classifier.test_row_count().to_usize().unwrap()
 					+ multiclass_classifier.train_row_count().to_usize().unwrap(),
-				column_count: column_stats.len(),
-				target_column_stats_table: TargetColumnStatsTable {
-					target_column_stats_table_row: build_column_stats(
-						&multiclass_classifier.overall_target_column_stats(),
-					),
-				},
-			}
+			)
 		}
 	};
+	// Filter the columns by name and type, then sort them.
+	let name_filter = search_params
+		.name
+		.as_ref()
+		.map(|name| name.trim().to_lowercase())
+		.filter(|name| !name.is_empty());
+	let column_type_filter = search_params
+		.column_type
+		.as_deref()
+		.and_then(column_type_from_str);
+	let mut column_stats_table_rows: Vec<ColumnStatsTableRow> = column_stats
+		.iter()
+		.map(|column_stats| build_column_stats(&column_stats))
+		.filter(|row| {
+			name_filter
+				.as_ref()
+				.map(|name_filter| row.name.to_lowercase().contains(name_filter))
+				.unwrap_or(true)
+		})
+		.filter(|row| {
+			column_type_filter
+				.map(|column_type_filter| row.column_type == column_type_filter)
+				.unwrap_or(true)
+		})
+		.collect();
+	let sort = search_params.sort.unwrap_or_default();
+	match sort {
+		ColumnStatsSort::Default => {}
+		ColumnStatsSort::Name => {
+			column_stats_table_rows.sort_by(|a, b| a.name.cmp(&b.name));
+		}
+		// Columns without an invalid or unique count sort after all the columns that have one.
+		ColumnStatsSort::InvalidCount => {
+			column_stats_table_rows.sort_by_key(|row| std::cmp::Reverse(row.invalid_count));
+		}
+		ColumnStatsSort::UniqueCount => {
+			column_stats_table_rows.sort_by_key(|row| std::cmp::Reverse(row.unique_count));
+		}
+	}
+	// Take the rows for the requested page.
+	let filtered_column_count = column_stats_table_rows.len();
+	let offset = search_params.offset.unwrap_or(0);
+	let pagination = compute_pagination(
+		offset,
+		filtered_column_count,
+		TRAINING_STATS_COLUMNS_PER_PAGE,
+	);
+	let column_stats_table_rows = column_stats_table_rows
+		.into_iter()
+		.skip(offset)
+		.take(TRAINING_STATS_COLUMNS_PER_PAGE)
+		.collect();
+	let page = Page {
+		column_stats_table: ColumnStatsTable {
+			column_stats_table_rows,
+		},
+		column_stats_filters: ColumnStatsFilters {
+			name: search_params.name.unwrap_or_default(),
+			column_type: search_params.column_type.unwrap_or_default(),
+			sort,
+		},
+		pagination,
+		model_layout_info,
+		column_count: column_stats.len(),
+		filtered_column_count,
+		row_count,
+		target_column_stats_table: TargetColumnStatsTable {
+			target_column_stats_table_row: build_column_stats(&target_column_stats),
+		},
+	};
 	let html = html(page);
 	let response = http::Response::builder()
 		.status(http::StatusCode::OK)
@@ -117,6 +170,18 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	Ok(response)
 }
 
+/// Parse the value of the column type filter. Any other value, including the empty string, shows columns of every type.
+fn column_type_from_str(column_type: &str) -> Option<ColumnType> {
+	match column_type {
+		"unknown" => Some(ColumnType::Unknown),
+		"number" => Some(ColumnType::Number),
+		"enum" => Some(ColumnType::Enum),
+		"text" => Some(ColumnType::Text),
+		"date_time" => Some(ColumnType::DateTime),
+		_ => None,
+	}
+}
+
 fn build_column_stats(column_stats: &modelfox_model::ColumnStatsReader) -> ColumnStatsTableRow {
 	match column_stats {
 		modelfox_model::ColumnStatsReader::UnknownColumn(column_stats) => {
@@ -175,5 +240,19 @@ fn build_column_stats(column_stats: &modelfox_model::ColumnStatsReader) -> Colum
 				variance: None,
 			}
 		}
+		modelfox_model::ColumnStatsReader::DateTimeColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			ColumnStatsTableRow {
+				column_type: ColumnType::DateTime,
+				unique_count: column_stats.unique_count().to_usize(),
+				invalid_count: column_stats.invalid_count().to_usize(),
+				name: column_stats.column_name().to_owned(),
+				max: None,
+				min: None,
+				std: None,
+				mean: None,
+				variance: None,
+			}
+		}
 	}
 }
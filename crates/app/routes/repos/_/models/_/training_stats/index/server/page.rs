@@ -5,7 +5,9 @@ use modelfox_app_layouts::{
 use modelfox_app_ui::{
 	column_type::ColumnType,
 	metrics_row::MetricsRow,
-	tokens::{EnumColumnToken, NumberColumnToken, TextColumnToken},
+	page_heading::{PageHeading, PageHeadingButtons},
+	pagination::{Pagination, PaginationButtons},
+	tokens::{DateTimeColumnToken, EnumColumnToken, NumberColumnToken, TextColumnToken},
 };
 use modelfox_ui as ui;
 use pinwheel::prelude::*;
@@ -13,11 +15,61 @@ use pinwheel::prelude::*;
 pub struct Page {
 	pub model_layout_info: ModelLayoutInfo,
 	pub target_column_stats_table: TargetColumnStatsTable,
+	pub column_stats_filters: ColumnStatsFilters,
 	pub column_stats_table: ColumnStatsTable,
+	pub pagination: Pagination,
 	pub column_count: usize,
+	pub filtered_column_count: usize,
 	pub row_count: usize,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum ColumnStatsSort {
+	#[serde(rename = "default")]
+	Default,
+	#[serde(rename = "name")]
+	Name,
+	#[serde(rename = "invalid_count")]
+	InvalidCount,
+	#[serde(rename = "unique_count")]
+	UniqueCount,
+}
+
+impl Default for ColumnStatsSort {
+	fn default() -> ColumnStatsSort {
+		ColumnStatsSort::Default
+	}
+}
+
+impl std::fmt::Display for ColumnStatsSort {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let s = match self {
+			ColumnStatsSort::Default => "default",
+			ColumnStatsSort::Name => "name",
+			ColumnStatsSort::InvalidCount => "invalid_count",
+			ColumnStatsSort::UniqueCount => "unique_count",
+		};
+		write!(f, "{}", s)
+	}
+}
+
+pub struct ColumnStatsFilters {
+	pub name: String,
+	pub column_type: String,
+	pub sort: ColumnStatsSort,
+}
+
+impl ColumnStatsFilters {
+	/// Get the query params that keep these filters applied when moving between pages.
+	pub fn query_params(&self) -> Vec<(String, String)> {
+		vec![
+			("name".to_owned(), self.name.clone()),
+			("column_type".to_owned(), self.column_type.clone()),
+			("sort".to_owned(), self.sort.to_string()),
+		]
+	}
+}
+
 pub struct ColumnStatsTableRow {
 	pub invalid_count: Option<usize>,
 	pub max: Option<f32>,
@@ -36,7 +88,17 @@ impl Component for Page {
 			.child(
 				ModelLayout::new(self.model_layout_info).child(
 					ui::S1::new()
-						.child(ui::H1::new("Training Stats"))
+						.child(
+							PageHeading::new()
+								.child(ui::H1::new("Training Stats"))
+								.child(
+									PageHeadingButtons::new().child(
+										ui::Button::new()
+											.href("./correlations".to_owned())
+											.child("Column Correlations"),
+									),
+								),
+						)
 						.child(
 							ui::S2::new()
 								.child(ui::H2::new("Target Column"))
@@ -56,7 +118,19 @@ impl Component for Page {
 											self.column_count.to_string(),
 										)),
 								)
-								.child(self.column_stats_table),
+								.child(ColumnStatsFiltersForm {
+									filters: &self.column_stats_filters,
+								})
+								.child(ui::P::new().child(format!(
+									"Showing {} of {} columns.",
+									self.filtered_column_count, self.column_count
+								)))
+								.child(self.column_stats_table)
+								.child(PaginationButtons {
+									pagination: self.pagination,
+									form_action: None,
+									hidden_fields: self.column_stats_filters.query_params(),
+								}),
 						),
 				),
 			)
@@ -64,6 +138,73 @@ impl Component for Page {
 	}
 }
 
+struct ColumnStatsFiltersForm<'a> {
+	filters: &'a ColumnStatsFilters,
+}
+
+impl<'a> Component for ColumnStatsFiltersForm<'a> {
+	fn into_node(self) -> Node {
+		let column_type_options = [
+			("All", ""),
+			("Number", "number"),
+			("Enum", "enum"),
+			("Text", "text"),
+			("Date Time", "date_time"),
+			("Unknown", "unknown"),
+		]
+		.into_iter()
+		.map(|(text, value)| ui::SelectFieldOption {
+			text: text.to_owned(),
+			value: value.to_owned(),
+		})
+		.collect();
+		let sort_options = [
+			("Column Order", ColumnStatsSort::Default),
+			("Name", ColumnStatsSort::Name),
+			("Invalid Count", ColumnStatsSort::InvalidCount),
+			("Unique Count", ColumnStatsSort::UniqueCount),
+		]
+		.into_iter()
+		.map(|(text, value)| ui::SelectFieldOption {
+			text: text.to_owned(),
+			value: value.to_string(),
+		})
+		.collect();
+		ui::Form::new()
+			.child(
+				div()
+					.class("column-stats-filters")
+					.child(
+						ui::TextField::new()
+							.autocomplete("off".to_owned())
+							.label("Name".to_owned())
+							.name("name".to_owned())
+							.value(self.filters.name.clone()),
+					)
+					.child(
+						ui::SelectField::new()
+							.label("Type".to_owned())
+							.name("column_type".to_owned())
+							.options(column_type_options)
+							.value(self.filters.column_type.clone()),
+					)
+					.child(
+						ui::SelectField::new()
+							.label("Sort By".to_owned())
+							.name("sort".to_owned())
+							.options(sort_options)
+							.value(self.filters.sort.to_string()),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Filter"),
+					),
+			)
+			.into_node()
+	}
+}
+
 pub struct TargetColumnStatsTable {
 	pub target_column_stats_table_row: ColumnStatsTableRow,
 }
@@ -210,6 +351,7 @@ impl Component for ColumnTypeToken {
 			ColumnType::Number => Some(NumberColumnToken.into_node()),
 			ColumnType::Enum => Some(EnumColumnToken.into_node()),
 			ColumnType::Text => Some(TextColumnToken.into_node()),
+			ColumnType::DateTime => Some(DateTimeColumnToken.into_node()),
 			ColumnType::Unknown => None,
 		}
 		.into_node()
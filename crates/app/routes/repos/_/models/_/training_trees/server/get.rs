@@ -0,0 +1,323 @@
+use crate::page::{BranchNode, Inner, LeafNode, Page, Task, TreeNode, Trees};
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::get_model_bytes,
+	path_components,
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
+use modelfox_id::Id;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+use std::{collections::HashMap, sync::Arc};
+
+/// Only the first trees can be selected, because models can have thousands of trees and the later ones contribute less to each prediction.
+const MAX_TREES: usize = 100;
+
+/// Discrete splits list the enum variants sent each way, up to this many.
+const MAX_VARIANTS_IN_CONDITION: usize = 5;
+
+pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let model_id = if let ["repos", _, "models", model_id, "training_trees"] =
+		*path_components(request).as_slice()
+	{
+		model_id.to_owned()
+	} else {
+		bail!("unexpected path");
+	};
+	#[derive(serde::Deserialize, Default)]
+	struct SearchParams {
+		tree: Option<usize>,
+		class: Option<String>,
+	}
+	let search_params: SearchParams = match request.uri().query() {
+		Some(query) => match serde_urlencoded::from_str(query) {
+			Ok(search_params) => search_params,
+			Err(_) => return Ok(bad_request()),
+		},
+		None => SearchParams::default(),
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let model_layout_info =
+		model_layout_info(&mut db, app, model_id, ModelNavItem::TrainingTrees).await?;
+	app.commit_transaction(db).await?;
+	let bytes = get_model_bytes(app.storage(), model_id).await?;
+	let model = modelfox_model::from_bytes(&bytes)?;
+	let inner = match get_tree_model(model) {
+		None => Inner::Linear,
+		Some(tree_model) => {
+			let class_index = match (&search_params.class, &tree_model.classes) {
+				(Some(class), Some(classes)) => match classes.iter().position(|c| c == class) {
+					Some(class_index) => class_index,
+					None => return Ok(bad_request()),
+				},
+				_ => 0,
+			};
+			let trees = &tree_model.trees[class_index];
+			let n_trees = trees.len().min(MAX_TREES);
+			let tree_index = search_params.tree.unwrap_or(0);
+			if n_trees > 0 && tree_index >= n_trees {
+				return Ok(bad_request());
+			}
+			let root = trees
+				.get(tree_index)
+				.map(|tree| compute_tree_node(tree, 0, &tree_model.features));
+			let task = match (tree_model.classes, tree_model.positive_class) {
+				(Some(classes), _) => Task::MulticlassClassification {
+					class: classes[class_index].clone(),
+					classes,
+				},
+				(None, Some(positive_class)) => Task::BinaryClassification { positive_class },
+				(None, None) => Task::Regression,
+			};
+			Inner::Trees(Trees {
+				bias: tree_model.biases[class_index],
+				n_trees,
+				n_trees_total: trees.len(),
+				root,
+				task,
+				tree_index,
+			})
+		}
+	};
+	let page = Page {
+		model_layout_info,
+		inner,
+	};
+	let response = http::Response::builder()
+		.status(http::StatusCode::OK)
+		.body(hyper::Body::from(html(page)))
+		.unwrap();
+	Ok(response)
+}
+
+struct TreeModel {
+	/// This is the positive class of a binary classifier.
+	positive_class: Option<String>,
+	/// These are the classes of a multiclass classifier, which has separate trees for each class.
+	classes: Option<Vec<String>>,
+	/// This has one bias for each class, or a single bias for regressors and binary classifiers.
+	biases: Vec<f32>,
+	/// This has the trees for each class, or a single `Vec` for regressors and binary classifiers.
+	trees: Vec<Vec<modelfox_tree::Tree>>,
+	features: Vec<Feature>,
+}
+
+struct Feature {
+	name: String,
+	/// If the feature is an enum column, these are its variants, in the order of the bins of the discrete splits on it.
+	variants: Option<Vec<String>>,
+}
+
+fn get_tree_model(model: modelfox_model::ModelReader) -> Option<TreeModel> {
+	match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			let regressor = regressor.read();
+			match regressor.model() {
+				modelfox_model::RegressionModelReader::Linear(_) => None,
+				modelfox_model::RegressionModelReader::Tree(inner_model) => {
+					let inner_model = inner_model.read();
+					let features = compute_features(
+						inner_model.feature_groups().iter(),
+						regressor.train_column_stats().iter(),
+					);
+					let inner_model = modelfox_tree::Regressor::from_reader(inner_model.model());
+					Some(TreeModel {
+						positive_class: None,
+						classes: None,
+						biases: vec![inner_model.bias],
+						trees: vec![inner_model.trees],
+						features,
+					})
+				}
+			}
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			let binary_classifier = binary_classifier.read();
+			match binary_classifier.model() {
+				modelfox_model::BinaryClassificationModelReader::Linear(_) => None,
+				modelfox_model::BinaryClassificationModelReader::Tree(inner_model) => {
+					let inner_model = inner_model.read();
+					let features = compute_features(
+						inner_model.feature_groups().iter(),
+						binary_classifier.train_column_stats().iter(),
+					);
+					let inner_model =
+						modelfox_tree::BinaryClassifier::from_reader(inner_model.model());
+					Some(TreeModel {
+						positive_class: Some(binary_classifier.positive_class().to_owned()),
+						classes: None,
+						biases: vec![inner_model.bias],
+						trees: vec![inner_model.trees],
+						features,
+					})
+				}
+			}
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier = multiclass_classifier.read();
+			match multiclass_classifier.model() {
+				modelfox_model::MulticlassClassificationModelReader::Linear(_) => None,
+				modelfox_model::MulticlassClassificationModelReader::Tree(inner_model) => {
+					let inner_model = inner_model.read();
+					let features = compute_features(
+						inner_model.feature_groups().iter(),
+						multiclass_classifier.train_column_stats().iter(),
+					);
+					let inner_model =
+						modelfox_tree::MulticlassClassifier::from_reader(inner_model.model());
+					let classes = multiclass_classifier
+						.classes()
+						.iter()
+						.map(ToOwned::to_owned)
+						.collect::<Vec<_>>();
+					let trees = (0..classes.len())
+						.map(|class_index| inner_model.trees.column(class_index).to_vec())
+						.collect();
+					Some(TreeModel {
+						positive_class: None,
+						classes: Some(classes),
+						biases: inner_model.biases.to_vec(),
+						trees,
+						features,
+					})
+				}
+			}
+		}
+	}
+}
+
+fn compute_features<'a>(
+	feature_groups: impl Iterator<Item = modelfox_model::FeatureGroupReader<'a>>,
+	column_stats: impl Iterator<Item = modelfox_model::ColumnStatsReader<'a>>,
+) -> Vec<Feature> {
+	let enum_variants = column_stats
+		.filter_map(|column_stats| match column_stats {
+			modelfox_model::ColumnStatsReader::EnumColumn(column_stats) => {
+				let column_stats = column_stats.read();
+				let variants = column_stats
+					.histogram()
+					.iter()
+					.map(|(variant, _)| variant.to_owned())
+					.collect::<Vec<_>>();
+				Some((column_stats.column_name().to_owned(), variants))
+			}
+			_ => None,
+		})
+		.collect::<HashMap<_, _>>();
+	feature_groups
+		.flat_map(|feature_group| {
+			let variants = match &feature_group {
+				modelfox_model::FeatureGroupReader::Identity(feature_group) => enum_variants
+					.get(feature_group.read().source_column_name())
+					.cloned(),
+				_ => None,
+			};
+			feature_group
+				.feature_names()
+				.into_iter()
+				.map(move |name| Feature {
+					name,
+					variants: variants.clone(),
+				})
+		})
+		.collect()
+}
+
+fn compute_tree_node(
+	tree: &modelfox_tree::Tree,
+	node_index: usize,
+	features: &[Feature],
+) -> TreeNode {
+	match &tree.nodes[node_index] {
+		modelfox_tree::Node::Leaf(node) => TreeNode::Leaf(LeafNode {
+			examples_fraction: node.examples_fraction,
+			value: node.value,
+		}),
+		modelfox_tree::Node::Branch(node) => {
+			let feature = &features[node.split.feature_index()];
+			let (left_condition, right_condition) = match &node.split {
+				modelfox_tree::BranchSplit::Continuous(split) => {
+					let split_value = ui::format_float(split.split_value);
+					let mut left_condition = format!("<= {}", split_value);
+					let mut right_condition = format!("> {}", split_value);
+					match split.invalid_values_direction {
+						modelfox_tree::SplitDirection::Left => {
+							left_condition.push_str(" or invalid")
+						}
+						modelfox_tree::SplitDirection::Right => {
+							right_condition.push_str(" or invalid")
+						}
+					}
+					(left_condition, right_condition)
+				}
+				modelfox_tree::BranchSplit::Discrete(split) => {
+					compute_discrete_split_conditions(split, feature.variants.as_deref())
+				}
+			};
+			TreeNode::Branch(BranchNode {
+				examples_fraction: node.examples_fraction,
+				feature_name: feature.name.clone(),
+				left_condition,
+				left: Box::new(compute_tree_node(tree, node.left_child_index, features)),
+				right_condition,
+				right: Box::new(compute_tree_node(tree, node.right_child_index, features)),
+			})
+		}
+	}
+}
+
+/// Describe the enum values sent in each direction. The first bin holds invalid values, and the rest hold the variants in order.
+fn compute_discrete_split_conditions(
+	split: &modelfox_tree::BranchSplitDiscrete,
+	variants: Option<&[String]>,
+) -> (String, String) {
+	let mut left_values = Vec::new();
+	let mut right_values = Vec::new();
+	for (bin_index, direction) in split.directions.iter().enumerate() {
+		let value = if bin_index == 0 {
+			"invalid".to_owned()
+		} else {
+			variants
+				.and_then(|variants| variants.get(bin_index - 1))
+				.cloned()
+				.unwrap_or_else(|| format!("bin {}", bin_index))
+		};
+		match modelfox_tree::SplitDirection::from(*direction) {
+			modelfox_tree::SplitDirection::Left => left_values.push(value),
+			modelfox_tree::SplitDirection::Right => right_values.push(value),
+		}
+	}
+	(
+		format_discrete_split_condition(left_values),
+		format_discrete_split_condition(right_values),
+	)
+}
+
+fn format_discrete_split_condition(mut values: Vec<String>) -> String {
+	let n_more = values.len().saturating_sub(MAX_VARIANTS_IN_CONDITION);
+	values.truncate(MAX_VARIANTS_IN_CONDITION);
+	if n_more > 0 {
+		values.push(format!("and {} more", n_more));
+	}
+	format!("in {{{}}}", values.join(", "))
+}
@@ -0,0 +1,220 @@
+use modelfox_app_layouts::{
+	document::Document,
+	model_layout::{ModelLayout, ModelLayoutInfo},
+};
+use modelfox_app_ui::class_select_field::ClassSelectField;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+/// Nodes deeper than this start collapsed, so large trees fit on the page until they are expanded.
+const OPEN_DEPTH: usize = 3;
+
+pub struct Page {
+	pub model_layout_info: ModelLayoutInfo,
+	pub inner: Inner,
+}
+
+pub enum Inner {
+	Linear,
+	Trees(Trees),
+}
+
+pub struct Trees {
+	pub bias: f32,
+	pub n_trees: usize,
+	pub n_trees_total: usize,
+	pub root: Option<TreeNode>,
+	pub task: Task,
+	pub tree_index: usize,
+}
+
+pub enum Task {
+	Regression,
+	BinaryClassification { positive_class: String },
+	MulticlassClassification { class: String, classes: Vec<String> },
+}
+
+pub enum TreeNode {
+	Branch(BranchNode),
+	Leaf(LeafNode),
+}
+
+pub struct BranchNode {
+	pub examples_fraction: f32,
+	pub feature_name: String,
+	pub left_condition: String,
+	pub left: Box<TreeNode>,
+	pub right_condition: String,
+	pub right: Box<TreeNode>,
+}
+
+pub struct LeafNode {
+	pub examples_fraction: f32,
+	pub value: f64,
+}
+
+impl Component for Page {
+	fn into_node(self) -> Node {
+		let inner = match self.inner {
+			Inner::Linear => ui::S1::new()
+				.child(ui::H1::new("Trees"))
+				.child(ui::P::new().child(
+					"This model is a linear model, so it has no trees to show. This page shows the trees of gradient boosted tree models.",
+				))
+				.into_node(),
+			Inner::Trees(trees) => trees.into_node(),
+		};
+		Document::new()
+			.client("modelfox_app_training_trees_client")
+			.child(ModelLayout::new(self.model_layout_info).child(inner))
+			.into_node()
+	}
+}
+
+impl Component for Trees {
+	fn into_node(self) -> Node {
+		let bias = ui::format_float(self.bias);
+		let description = match &self.task {
+			Task::Regression => format!(
+				"The model predicts the sum of the bias, {}, and the value of the leaf that the example reaches in each tree.",
+				bias
+			),
+			Task::BinaryClassification { positive_class } => format!(
+				"The model computes the log odds that the example is {} as the sum of the bias, {}, and the value of the leaf that the example reaches in each tree.",
+				positive_class, bias
+			),
+			Task::MulticlassClassification { class, .. } => format!(
+				"The model has trees for each class. It computes the logit for {} as the sum of the bias, {}, and the value of the leaf that the example reaches in each of the class's trees.",
+				class, bias
+			),
+		};
+		let truncated_description = if self.n_trees < self.n_trees_total {
+			Some(format!(
+				"The first {} of the {} trees are shown.",
+				self.n_trees, self.n_trees_total
+			))
+		} else {
+			None
+		};
+		let tree_options = (0..self.n_trees)
+			.map(|tree_index| ui::SelectFieldOption {
+				text: format!("Tree {}", tree_index + 1),
+				value: tree_index.to_string(),
+			})
+			.collect::<Vec<_>>();
+		let class_select_field = match self.task {
+			Task::MulticlassClassification { class, classes } => {
+				Some(ClassSelectField { class, classes })
+			}
+			_ => None,
+		};
+		let diagram = match self.root {
+			Some(root) => div()
+				.class("training-trees-diagram")
+				.child(TreeNodeDiagram {
+					node: root,
+					depth: 0,
+				})
+				.into_node(),
+			None => ui::P::new()
+				.child("Training stopped before any trees were trained.")
+				.into_node(),
+		};
+		ui::S1::new()
+			.child(ui::H1::new("Trees"))
+			.child(
+				ui::P::new()
+					.child(description)
+					.child(" ")
+					.child(truncated_description),
+			)
+			.child(ui::P::new().child(
+				"Each branch sends examples left or right depending on the value of a feature, and each leaf shows its value. Every node shows the percent of training examples that reached it. Click a branch to collapse or expand it.",
+			))
+			.child(
+				ui::Form::new()
+					.child(
+						ui::SelectField::new()
+							.id("tree_select_field".to_owned())
+							.label("Select Tree".to_owned())
+							.name("tree".to_owned())
+							.options(tree_options)
+							.value(self.tree_index.to_string()),
+					)
+					.child(class_select_field)
+					.child(
+						noscript().child(
+							ui::Button::new()
+								.button_type(ui::ButtonType::Submit)
+								.child("Submit"),
+						),
+					),
+			)
+			.child(ui::Card::new().child(diagram))
+			.into_node()
+	}
+}
+
+struct TreeNodeDiagram {
+	node: TreeNode,
+	depth: usize,
+}
+
+impl Component for TreeNodeDiagram {
+	fn into_node(self) -> Node {
+		match self.node {
+			TreeNode::Leaf(node) => div()
+				.class("training-trees-node training-trees-leaf")
+				.child(
+					div()
+						.class("training-trees-node-box")
+						.child(
+							div()
+								.class("training-trees-node-title")
+								.child(ui::format_float(node.value)),
+						)
+						.child(div().class("training-trees-node-examples").child(format!(
+							"{} of examples",
+							ui::format_percent(node.examples_fraction)
+						))),
+				)
+				.into_node(),
+			TreeNode::Branch(node) => {
+				let child = |condition: String, child: TreeNode| {
+					div()
+						.class("training-trees-edge")
+						.child(div().class("training-trees-edge-label").child(condition))
+						.child(TreeNodeDiagram {
+							node: child,
+							depth: self.depth + 1,
+						})
+				};
+				details()
+					.class("training-trees-node")
+					.attribute("open", self.depth < OPEN_DEPTH)
+					.child(
+						summary().class("training-trees-node-summary").child(
+							div()
+								.class("training-trees-node-box")
+								.child(
+									div()
+										.class("training-trees-node-title")
+										.child(node.feature_name),
+								)
+								.child(div().class("training-trees-node-examples").child(format!(
+									"{} of examples",
+									ui::format_percent(node.examples_fraction)
+								))),
+						),
+					)
+					.child(
+						div()
+							.class("training-trees-children")
+							.child(child(node.left_condition, *node.left))
+							.child(child(node.right_condition, *node.right)),
+					)
+					.into_node()
+			}
+		}
+	}
+}
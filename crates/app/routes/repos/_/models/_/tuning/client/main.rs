@@ -1,6 +1,10 @@
 use pinwheel::prelude::*;
+use web_sys as dom;
 
 pub fn main() {
 	modelfox_ui::client_start();
-	hydrate::<modelfox_app_tuning_common::Tuning>("tuning");
+	let document = dom::window().unwrap().document().unwrap();
+	if document.get_element_by_id("tuning").is_some() {
+		hydrate::<modelfox_app_tuning_common::Tuning>("tuning");
+	}
 }
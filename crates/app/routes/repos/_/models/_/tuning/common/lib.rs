@@ -10,6 +10,18 @@ pub struct Tuning {
 	pub metrics: Vec<Metrics>,
 	pub default_threshold_metrics: Metrics,
 	pub class: String,
+	/// These are the costs the user entered to get a threshold recommendation.
+	pub costs: Option<Costs>,
+	/// This is the threshold that minimizes the expected cost for the entered costs.
+	pub recommended_threshold: Option<f32>,
+	/// This is the decision threshold saved for the model, if any.
+	pub decision_threshold: Option<f32>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Costs {
+	pub false_positive_cost: f32,
+	pub false_negative_cost: f32,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -25,6 +37,52 @@ pub struct Metrics {
 	pub true_positives_fraction: f32,
 }
 
+impl Metrics {
+	/// Compute the expected cost per prediction at this threshold.
+	pub fn expected_cost(&self, costs: &Costs) -> f32 {
+		costs.false_positive_cost * self.false_positives_fraction
+			+ costs.false_negative_cost * self.false_negatives_fraction
+	}
+}
+
+/// Find the threshold with the lowest expected cost. Ties go to the threshold closest to the default.
+pub fn recommend_threshold(
+	metrics: &[Metrics],
+	default_threshold: f32,
+	costs: &Costs,
+) -> Option<f32> {
+	metrics
+		.iter()
+		.filter(|metrics| metrics.expected_cost(costs).is_finite())
+		.min_by(|a, b| {
+			a.expected_cost(costs)
+				.partial_cmp(&b.expected_cost(costs))
+				.unwrap()
+				.then_with(|| {
+					(a.threshold - default_threshold)
+						.abs()
+						.partial_cmp(&(b.threshold - default_threshold).abs())
+						.unwrap()
+				})
+		})
+		.map(|metrics| metrics.threshold)
+}
+
+/// Find the index of the metrics whose threshold is closest to `threshold`.
+fn closest_threshold_index(metrics: &[Metrics], threshold: f32) -> usize {
+	metrics
+		.iter()
+		.enumerate()
+		.min_by(|(_, a), (_, b)| {
+			(a.threshold - threshold)
+				.abs()
+				.partial_cmp(&(b.threshold - threshold).abs())
+				.unwrap()
+		})
+		.map(|(index, _)| index)
+		.unwrap()
+}
+
 impl Component for Tuning {
 	fn into_node(self) -> Node {
 		let baseline_metrics = Rc::new(self.default_threshold_metrics);
@@ -34,7 +92,12 @@ impl Component for Tuning {
 			.iter()
 			.position(|metrics| (metrics.threshold - default_threshold).abs() < std::f32::EPSILON)
 			.unwrap();
-		let selected_index: Mutable<usize> = Mutable::new(baseline_index);
+		let initial_index = self
+			.recommended_threshold
+			.or(self.decision_threshold)
+			.map(|threshold| closest_threshold_index(&metrics, threshold))
+			.unwrap_or(baseline_index);
+		let selected_index: Mutable<usize> = Mutable::new(initial_index);
 		let on_change = {
 			clone!(selected_index);
 			Box::new(move |value: f32| selected_index.set(value.to_usize().unwrap()))
@@ -134,8 +197,24 @@ impl Component for Tuning {
 					value_b_title: "Selected Threshold".to_owned(),
 				})
 		};
+		let recommendation = self.recommended_threshold.map(|recommended_threshold| {
+			let expected_cost = self.costs.as_ref().map(|costs| {
+				metrics[closest_threshold_index(&metrics, recommended_threshold)]
+					.expected_cost(costs)
+			});
+			ThresholdRecommendation {
+				recommended_threshold,
+				expected_cost,
+			}
+		});
+		let decision_threshold = self
+			.decision_threshold
+			.map(|decision_threshold| SavedDecisionThreshold { decision_threshold });
 		ui::S1::new()
 			.child(ui::H1::new("Tuning"))
+			.child(decision_threshold)
+			.child(CostsForm { costs: self.costs })
+			.child(recommendation)
 			.child(ui::P::new().child("Drag the silder to choose a threshold."))
 			.child(
 				ui::Slider::new(ui::SliderInit {
@@ -161,3 +240,130 @@ impl Component for Tuning {
 			.into_node()
 	}
 }
+
+struct CostsForm {
+	costs: Option<Costs>,
+}
+
+impl Component for CostsForm {
+	fn into_node(self) -> Node {
+		let false_positive_cost = self
+			.costs
+			.as_ref()
+			.map(|costs| costs.false_positive_cost.to_string());
+		let false_negative_cost = self
+			.costs
+			.as_ref()
+			.map(|costs| costs.false_negative_cost.to_string());
+		ui::S2::new()
+			.child(ui::H2::new("Cost Sensitive Threshold"))
+			.child(ui::P::new().child(
+				"Enter what a false positive and a false negative cost you, and the threshold with the lowest expected cost on the test dataset will be recommended.",
+			))
+			.child(
+				ui::Form::new()
+					.child(
+						ui::TextField::new()
+							.label("False Positive Cost".to_owned())
+							.name("false_positive_cost".to_owned())
+							.value(false_positive_cost)
+							.required(true),
+					)
+					.child(
+						ui::TextField::new()
+							.label("False Negative Cost".to_owned())
+							.name("false_negative_cost".to_owned())
+							.value(false_negative_cost)
+							.required(true),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Recommend Threshold"),
+					),
+			)
+			.into_node()
+	}
+}
+
+struct ThresholdRecommendation {
+	recommended_threshold: f32,
+	expected_cost: Option<f32>,
+}
+
+impl Component for ThresholdRecommendation {
+	fn into_node(self) -> Node {
+		let expected_cost = self.expected_cost.map(|expected_cost| {
+			ui::NumberCard::new(
+				"Expected Cost per Prediction".to_owned(),
+				ui::format_float(expected_cost),
+			)
+		});
+		ui::S2::new()
+			.child(
+				div()
+					.class("tuning-metrics-grid")
+					.child(ui::NumberCard::new(
+						"Recommended Threshold".to_owned(),
+						ui::format_float(self.recommended_threshold),
+					))
+					.child(expected_cost),
+			)
+			.child(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "set_decision_threshold"),
+					)
+					.child(
+						input()
+							.attribute("name", "decision_threshold")
+							.attribute("type", "hidden")
+							.attribute("value", self.recommended_threshold.to_string()),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Save as Decision Threshold"),
+					),
+			)
+			.into_node()
+	}
+}
+
+struct SavedDecisionThreshold {
+	decision_threshold: f32,
+}
+
+impl Component for SavedDecisionThreshold {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::NumberCard::new(
+				"Saved Decision Threshold".to_owned(),
+				ui::format_float(self.decision_threshold),
+			))
+			.child(ui::P::new().child(
+				"The playground uses this threshold when making predictions with this model.",
+			))
+			.child(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "reset_decision_threshold"),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.color(ui::colors::RED.to_owned())
+							.child("Reset to Default Threshold"),
+					),
+			)
+			.into_node()
+	}
+}
@@ -1,17 +1,17 @@
-use crate::page::Page;
+use crate::page::{ClassTuning, Inner, MulticlassTuning, Page};
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
-	model::get_model_bytes,
+	model::{get_model_bytes, get_model_class_thresholds, get_model_decision_threshold},
 	path_components,
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
-use modelfox_app_tuning_common::{Metrics, Tuning};
+use modelfox_app_tuning_common::{recommend_threshold, Costs, Metrics, Tuning};
 use modelfox_id::Id;
 use pinwheel::prelude::*;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
 	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
@@ -22,14 +22,25 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		} else {
 			bail!("unexpected path");
 		};
+	let search_params: Option<BTreeMap<String, String>> = request
+		.uri()
+		.path_and_query()
+		.unwrap()
+		.query()
+		.map(|query| {
+			url::form_urlencoded::parse(query.as_bytes())
+				.into_owned()
+				.collect()
+		});
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let model_id: Id = match model_id.parse() {
 		Ok(model_id) => model_id,
 		Err(_) => return Ok(bad_request()),
@@ -39,7 +50,9 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	}
 	let bytes = get_model_bytes(app.storage(), model_id).await?;
 	let model = modelfox_model::from_bytes(&bytes)?;
-	let tuning = match model.inner() {
+	let decision_threshold = get_model_decision_threshold(&mut db, model_id).await?;
+	let costs = search_params.as_ref().and_then(costs_from_search_params);
+	let inner = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(_) => None,
 		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
 			let model = binary_classifier.read();
@@ -84,19 +97,44 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				true_negatives_fraction: default_threshold_metrics.true_negatives() as f32 / total,
 				true_positives_fraction: default_threshold_metrics.true_positives() as f32 / total,
 			};
-			Some(Tuning {
-				default_threshold: 0.5,
+			let default_threshold = 0.5;
+			let recommended_threshold = costs
+				.as_ref()
+				.and_then(|costs| recommend_threshold(&metrics, default_threshold, costs));
+			Some(Inner::BinaryClassifier(Tuning {
+				default_threshold,
 				metrics,
 				default_threshold_metrics,
 				class: model.positive_class().to_owned(),
-			})
+				costs,
+				recommended_threshold,
+				decision_threshold,
+			}))
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let model = multiclass_classifier.read();
+			let class_thresholds = get_model_class_thresholds(&mut db, model_id).await?;
+			let classes = model
+				.classes()
+				.iter()
+				.zip(model.test_metrics().class_metrics().iter())
+				.map(|(class, class_metrics)| ClassTuning {
+					class: class.to_owned(),
+					precision: class_metrics.precision(),
+					recall: class_metrics.recall(),
+					threshold: class_thresholds.get(class).cloned(),
+				})
+				.collect();
+			Some(Inner::MulticlassClassifier(MulticlassTuning {
+				classes,
+				has_saved_thresholds: !class_thresholds.is_empty(),
+			}))
 		}
-		modelfox_model::ModelInnerReader::MulticlassClassifier(_) => None,
 	};
 	let model_layout_info = model_layout_info(&mut db, app, model_id, ModelNavItem::Tuning).await?;
 	let page = Page {
 		model_layout_info,
-		tuning,
+		inner,
 	};
 	let html = html(page);
 	let response = http::Response::builder()
@@ -106,3 +144,16 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	app.commit_transaction(db).await?;
 	Ok(response)
 }
+
+fn costs_from_search_params(search_params: &BTreeMap<String, String>) -> Option<Costs> {
+	let parse_cost = |key: &str| {
+		search_params
+			.get(key)
+			.and_then(|value| value.trim().parse::<f32>().ok())
+			.filter(|value| value.is_finite() && *value >= 0.0)
+	};
+	Some(Costs {
+		false_positive_cost: parse_cost("false_positive_cost")?,
+		false_negative_cost: parse_cost("false_negative_cost")?,
+	})
+}
@@ -8,13 +8,32 @@ use pinwheel::prelude::*;
 
 pub struct Page {
 	pub model_layout_info: ModelLayoutInfo,
-	pub tuning: Option<Tuning>,
+	pub inner: Option<Inner>,
+}
+
+pub enum Inner {
+	BinaryClassifier(Tuning),
+	MulticlassClassifier(MulticlassTuning),
+}
+
+pub struct MulticlassTuning {
+	pub classes: Vec<ClassTuning>,
+	pub has_saved_thresholds: bool,
+}
+
+pub struct ClassTuning {
+	pub class: String,
+	/// These are the class's one-vs-rest precision and recall on the test dataset when always predicting the most probable class.
+	pub precision: f32,
+	pub recall: f32,
+	pub threshold: Option<f32>,
 }
 
 impl Component for Page {
 	fn into_node(self) -> Node {
-		let inner = match self.tuning {
-			Some(tuning) => Dehydrate::new("tuning", tuning).into_node(),
+		let inner = match self.inner {
+			Some(Inner::BinaryClassifier(tuning)) => Dehydrate::new("tuning", tuning).into_node(),
+			Some(Inner::MulticlassClassifier(tuning)) => tuning.into_node(),
 			None => ui::S1::new()
 				.child(ui::P::new().child("Tuning is not supported for this model."))
 				.into_node(),
@@ -25,3 +44,83 @@ impl Component for Page {
 			.into_node()
 	}
 }
+
+impl Component for MulticlassTuning {
+	fn into_node(self) -> Node {
+		let reset_form = if self.has_saved_thresholds {
+			Some(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "reset_class_thresholds"),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.color(ui::colors::RED.to_owned())
+							.child("Reset All Thresholds"),
+					),
+			)
+		} else {
+			None
+		};
+		let table_header = ui::TableRow::new()
+			.child(ui::TableHeaderCell::new().child("Class"))
+			.child(ui::TableHeaderCell::new().child("Test Precision"))
+			.child(ui::TableHeaderCell::new().child("Test Recall"))
+			.child(ui::TableHeaderCell::new().child("Threshold"));
+		let table_body = self
+			.classes
+			.into_iter()
+			.enumerate()
+			.map(|(class_index, class)| {
+				ui::TableRow::new()
+					.child(ui::TableCell::new().child(class.class))
+					.child(ui::TableCell::new().child(ui::format_percent(class.precision)))
+					.child(ui::TableCell::new().child(ui::format_percent(class.recall)))
+					.child(
+						ui::TableCell::new().child(
+							ui::TextField::new()
+								.autocomplete("off".to_owned())
+								.name(format!("threshold_{}", class_index))
+								.placeholder("None".to_owned())
+								.value(class.threshold.map(|threshold| threshold.to_string())),
+						),
+					)
+			});
+		ui::S1::new()
+			.child(ui::H1::new("Tuning"))
+			.child(ui::P::new().child(
+				"By default, the model predicts the class with the highest probability. Give a class a threshold between 0 and 1 to only predict it when its probability is at least the threshold. Classes without a threshold can always be predicted, so if no class with a threshold qualifies, the most probable class without one is predicted. If every class has a threshold and none qualifies, the most probable class is predicted. Leave a threshold blank to remove it.",
+			))
+			.child(ui::P::new().child(
+				"The playground uses the saved thresholds, and so do the SDKs when they load a model downloaded after the thresholds were saved, unless you pass the class_thresholds predict option.",
+			))
+			.child(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "set_class_thresholds"),
+					)
+					.child(
+						ui::Table::new()
+							.width("100%".to_owned())
+							.child(ui::TableHeader::new().child(table_header))
+							.child(ui::TableBody::new().children(table_body)),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Save Thresholds"),
+					),
+			)
+			.child(reset_form)
+			.into_node()
+	}
+}
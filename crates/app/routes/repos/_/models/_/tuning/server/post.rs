@@ -0,0 +1,121 @@
+use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::{get_model_bytes, set_model_class_thresholds, set_model_decision_threshold},
+	path_components,
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_id::Id;
+use std::{collections::BTreeMap, sync::Arc};
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "action")]
+enum Action {
+	#[serde(rename = "set_decision_threshold")]
+	SetDecisionThreshold(SetDecisionThresholdAction),
+	#[serde(rename = "reset_decision_threshold")]
+	ResetDecisionThreshold,
+	/// The fields are named `threshold_<class_index>`, because the classes are only known after reading the model.
+	#[serde(rename = "set_class_thresholds")]
+	SetClassThresholds(BTreeMap<String, String>),
+	#[serde(rename = "reset_class_thresholds")]
+	ResetClassThresholds,
+}
+
+#[derive(serde::Deserialize)]
+struct SetDecisionThresholdAction {
+	decision_threshold: String,
+}
+
+pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let (repo_id, model_id) = if let ["repos", repo_id, "models", model_id, "tuning"] =
+		*path_components(request).as_slice()
+	{
+		(repo_id.to_owned(), model_id.to_owned())
+	} else {
+		bail!("unexpected path");
+	};
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let data = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(data) => data,
+		Err(_) => return Ok(bad_request()),
+	};
+	let action: Action = match serde_urlencoded::from_bytes(&data) {
+		Ok(action) => action,
+		Err(_) => return Ok(bad_request()),
+	};
+	match action {
+		Action::SetDecisionThreshold(SetDecisionThresholdAction { decision_threshold }) => {
+			let decision_threshold = match decision_threshold.parse::<f32>() {
+				Ok(decision_threshold) if (0.0..=1.0).contains(&decision_threshold) => {
+					decision_threshold
+				}
+				_ => return Ok(bad_request()),
+			};
+			set_model_decision_threshold(&mut db, model_id, Some(decision_threshold)).await?;
+		}
+		Action::ResetDecisionThreshold => {
+			set_model_decision_threshold(&mut db, model_id, None).await?;
+		}
+		Action::SetClassThresholds(fields) => {
+			let bytes = get_model_bytes(app.storage(), model_id).await?;
+			let model = modelfox_model::from_bytes(&bytes)?;
+			let classes = match model.inner() {
+				modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+					multiclass_classifier
+						.read()
+						.classes()
+						.iter()
+						.map(ToOwned::to_owned)
+						.collect::<Vec<_>>()
+				}
+				_ => return Ok(bad_request()),
+			};
+			let mut class_thresholds = BTreeMap::new();
+			for (class_index, class) in classes.into_iter().enumerate() {
+				let threshold = match fields.get(&format!("threshold_{}", class_index)) {
+					Some(threshold) if !threshold.trim().is_empty() => threshold,
+					_ => continue,
+				};
+				let threshold = match threshold.trim().parse::<f32>() {
+					Ok(threshold) if (0.0..=1.0).contains(&threshold) => threshold,
+					_ => return Ok(bad_request()),
+				};
+				class_thresholds.insert(class, threshold);
+			}
+			set_model_class_thresholds(&mut db, model_id, &class_thresholds).await?;
+		}
+		Action::ResetClassThresholds => {
+			set_model_class_thresholds(&mut db, model_id, &BTreeMap::new()).await?;
+		}
+	}
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::SEE_OTHER)
+		.header(
+			http::header::LOCATION,
+			app_path(format!("/repos/{}/models/{}/tuning", repo_id, model_id)),
+		)
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
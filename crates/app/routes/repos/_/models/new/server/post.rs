@@ -1,10 +1,12 @@
 use crate::page::Page;
 use anyhow::{bail, Result};
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
-	error::{not_found, redirect_to_login, service_unavailable},
+	error::{not_found, payload_too_large, redirect_to_login, service_unavailable},
 	path_components,
-	repos::add_model_version,
+	repos::add_model_version_from_path,
+	request_body::{is_body_size_limit_exceeded, write_stream_to_temp_file},
 	user::{authorize_user, authorize_user_for_repo},
 };
 use modelfox_app_layouts::app_layout::app_layout_info;
@@ -26,10 +28,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let repo_id: Id = match repo_id.parse() {
 		Ok(repo_id) => repo_id,
 		Err(_) => return Ok(not_found()),
@@ -62,9 +65,15 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 			return Ok(response);
 		}
 	};
-	let mut file: Option<Vec<u8>> = None;
+	// The model file is streamed to a temporary file as it is uploaded, rather than buffered in memory, because models may be several gigabytes.
+	let mut file: Option<tempfile::NamedTempFile> = None;
 	let mut multipart = Multipart::new(request.body_mut(), boundary);
-	while let Some(field) = multipart.next_field().await? {
+	loop {
+		let field = match multipart.next_field().await {
+			Ok(Some(field)) => field,
+			Ok(None) => break,
+			Err(error) => return upload_error(error.into()),
+		};
 		let name = match field.name() {
 			Some(name) => name.to_owned(),
 			None => {
@@ -84,9 +93,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 				return Ok(response);
 			}
 		};
-		let field_data = field.bytes().await?.to_vec();
 		match name.as_str() {
-			"file" => file = Some(field_data),
+			"file" => match write_stream_to_temp_file(field).await {
+				Ok(temp_file) => file = Some(temp_file),
+				Err(error) => return upload_error(error),
+			},
 			_ => {
 				let page = Page {
 					app_layout_info,
@@ -105,9 +116,10 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 			}
 		}
 	}
-	let bytes = match file {
-		Some(file) => file,
-		None => {
+	// Browsers submit an empty file if none was chosen.
+	let file = match file {
+		Some(file) if file.as_file().metadata()?.len() > 0 => file,
+		_ => {
 			let page = Page {
 				app_layout_info,
 				error: Some("A file is required.".to_owned()),
@@ -120,8 +132,9 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 			return Ok(response);
 		}
 	};
-	let model = match modelfox_model::from_bytes(&bytes) {
-		Ok(model) => model,
+	let bytes = unsafe { memmap::Mmap::map(file.as_file())? };
+	let model_id = match modelfox_model::from_bytes(&bytes) {
+		Ok(model) => model.id().to_owned(),
 		Err(_) => {
 			let page = Page {
 				app_layout_info,
@@ -135,8 +148,14 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 			return Ok(response);
 		}
 	};
-	let result =
-		add_model_version(&mut db, app, repo_id, model.id().parse().unwrap(), &bytes).await;
+	let result = add_model_version_from_path(
+		&mut db,
+		app,
+		repo_id,
+		model_id.parse().unwrap(),
+		file.path(),
+	)
+	.await;
 	if result.is_err() {
 		let page = Page {
 			app_layout_info,
@@ -154,9 +173,18 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		.status(http::StatusCode::SEE_OTHER)
 		.header(
 			http::header::LOCATION,
-			format!("/repos/{}/models/{}/", repo_id, model.id()),
+			app_path(format!("/repos/{}/models/{}/", repo_id, model_id)),
 		)
 		.body(hyper::Body::empty())
 		.unwrap();
 	Ok(response)
 }
+
+/// A chunked upload that is larger than the model upload body size limit fails while it is being read, so respond to it the same way as an upload whose declared length is over the limit.
+fn upload_error(error: anyhow::Error) -> Result<http::Response<hyper::Body>> {
+	if is_body_size_limit_exceeded(&error) {
+		Ok(payload_too_large())
+	} else {
+		Err(error)
+	}
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, redirect_to_login, service_unavailable},
@@ -21,10 +22,11 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(txn) => txn,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut txn, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut txn, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let data = match hyper::body::to_bytes(request.body_mut()).await {
 		Ok(data) => data,
 		Err(_) => return Ok(bad_request()),
@@ -69,7 +71,10 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 	app.commit_transaction(txn).await?;
 	let response = http::Response::builder()
 		.status(http::StatusCode::SEE_OTHER)
-		.header(http::header::LOCATION, format!("/repos/{}/", repo_id))
+		.header(
+			http::header::LOCATION,
+			app_path(format!("/repos/{}/", repo_id)),
+		)
 		.body(hyper::Body::empty())
 		.unwrap();
 	Ok(response)
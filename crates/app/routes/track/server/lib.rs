@@ -1,5 +1,6 @@
 use futures::FutureExt;
 use modelfox_app_core::error::method_not_allowed;
+use modelfox_app_openapi::{Method, Operation, Response, Type};
 
 mod post;
 
@@ -9,3 +10,22 @@ pub fn init() -> sunfish::Route {
 		_ => async { Ok(method_not_allowed()) }.boxed(),
 	})
 }
+
+/// This is the operation this route serves, for the app's OpenAPI document.
+pub const OPERATIONS: &[Operation] = &[Operation {
+	method: Method::Post,
+	path: "/track",
+	operation_id: "track",
+	summary: "Log a prediction or true value event, or a list of them.",
+	parameters: &[],
+	request_body: Some(Type::Either(
+		&Type::Ref("MonitorEvent"),
+		&Type::Array(&Type::Ref("MonitorEvent")),
+	)),
+	response: Response {
+		status: 202,
+		description: "The events were logged.",
+		body: None,
+	},
+	authenticated: false,
+}];
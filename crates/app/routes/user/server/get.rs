@@ -1,12 +1,14 @@
 use crate::page::{
 	Auth, DetailsSection, Inner, NoAuth, OrganizationsSection, OrganizationsTable,
-	OrganizationsTableRow, Page, ReposSection, ReposTable, ReposTableRow,
+	OrganizationsTableRow, Page, ReposSection, ReposTable, ReposTableRow, SessionsSection,
 };
 use anyhow::Result;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{not_found, redirect_to_login, service_unavailable},
 	organizations::get_organizations,
+	sessions::get_sessions,
+	timezone::get_user_timezone,
 	user::{authorize_user, User},
 };
 use modelfox_app_layouts::app_layout::app_layout_info;
@@ -25,10 +27,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
-		Ok(user) => user,
-		Err(_) => return Ok(redirect_to_login()),
-	};
+	let user =
+		match authorize_user(request, &mut db, app.options().auth_enabled(), app.clock()).await? {
+			Ok(user) => user,
+			Err(_) => return Ok(redirect_to_login()),
+		};
 	let app_layout_info = app_layout_info(app).await?;
 	let page = match user {
 		User::Root => {
@@ -53,7 +56,11 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			}
 		}
 		User::Normal(user) => {
-			let details_section = DetailsSection { email: user.email };
+			let timezone = get_user_timezone(&mut db, user.id).await?;
+			let details_section = DetailsSection {
+				email: user.email,
+				timezone: timezone.map(|timezone| timezone.name().to_owned()),
+			};
 			let organizations = get_organizations(&mut db, user.id).await?;
 			let rows: Vec<OrganizationsTableRow> = organizations
 				.into_iter()
@@ -84,10 +91,13 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				None
 			};
 			let repos_section = ReposSection { repos_table };
+			let sessions = get_sessions(&mut db, user.id, &user.token).await?;
+			let sessions_section = SessionsSection { sessions };
 			let inner = Inner::Auth(Auth {
 				details_section,
 				organizations_section,
 				repos_section,
+				sessions_section,
 			});
 			Page {
 				app_layout_info,
@@ -1,9 +1,12 @@
+use modelfox_app_base_path::app_path;
+use modelfox_app_core::sessions::Session;
 use modelfox_app_layouts::{
 	app_layout::{AppLayout, AppLayoutInfo},
 	document::Document,
 };
 use modelfox_ui as ui;
 use pinwheel::prelude::*;
+use time::macros::format_description;
 
 pub struct Page {
 	pub app_layout_info: AppLayoutInfo,
@@ -19,6 +22,7 @@ pub struct Auth {
 	pub details_section: DetailsSection,
 	pub organizations_section: OrganizationsSection,
 	pub repos_section: ReposSection,
+	pub sessions_section: SessionsSection,
 }
 
 pub struct NoAuth {
@@ -50,12 +54,14 @@ impl Component for Auth {
 			organizations_section,
 			repos_section,
 			details_section,
+			sessions_section,
 		} = self;
 		ui::S1::new()
 			.child(Header)
 			.child(details_section)
 			.child(organizations_section)
 			.child(repos_section)
+			.child(sessions_section)
 			.into_node()
 	}
 }
@@ -88,6 +94,7 @@ impl Component for Header {
 
 pub struct DetailsSection {
 	pub email: String,
+	pub timezone: Option<String>,
 }
 
 impl Component for DetailsSection {
@@ -101,6 +108,28 @@ impl Component for DetailsSection {
 						.value(self.email),
 				),
 			)
+			.child(
+				ui::Form::new()
+					.post(true)
+					.child(
+						input()
+							.attribute("name", "action")
+							.attribute("type", "hidden")
+							.attribute("value", "update_timezone"),
+					)
+					.child(
+						ui::TextField::new()
+							.label("Timezone".to_owned())
+							.name("timezone".to_owned())
+							.placeholder("Use your organization's or browser's timezone".to_owned())
+							.value(self.timezone),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Save Timezone"),
+					),
+			)
 			.into_node()
 	}
 }
@@ -124,7 +153,7 @@ impl Component for ReposSection {
 					.child(ui::H2::new("User Repos"))
 					.child(
 						ui::Button::new()
-							.href("/repos/new".to_owned())
+							.href(app_path("/repos/new"))
 							.id(None)
 							.child("Create New Repo"),
 					),
@@ -154,7 +183,7 @@ impl Component for ReposTable {
 			)
 			.child(
 				ui::TableBody::new().children(self.rows.into_iter().map(|row| {
-					let href = format!("/repos/{}/", row.id);
+					let href = app_path(format!("/repos/{}/", row.id));
 					{
 						ui::TableRow::new().child(
 							ui::TableCell::new().child(ui::Link::new().href(href).child(row.title)),
@@ -186,7 +215,7 @@ impl Component for OrganizationsSection {
 					.child(ui::H2::new("Organizations"))
 					.child(
 						ui::Button::new()
-							.href("/organizations/new".to_owned())
+							.href(app_path("/organizations/new"))
 							.child("Create New Organization"),
 					),
 			)
@@ -212,7 +241,7 @@ impl Component for OrganizationsTable {
 				ui::TableRow::new().child(ui::TableHeaderCell::new().child("Organization Name")),
 			))
 			.child(ui::TableBody::new().children(self.rows.iter().map(|row| {
-				let href = format!("/organizations/{}/", row.id);
+				let href = app_path(format!("/organizations/{}/", row.id));
 				{
 					ui::TableRow::new().child(
 						ui::TableCell::new()
@@ -223,3 +252,101 @@ impl Component for OrganizationsTable {
 			.into_node()
 	}
 }
+
+pub struct SessionsSection {
+	pub sessions: Vec<Session>,
+}
+
+impl Component for SessionsSection {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("Sessions"))
+			.child(ui::P::new().child(
+				"These are the devices you are logged in on. If you do not recognize a session, revoke it to log it out.",
+			))
+			.child(
+				ui::Table::new()
+					.width("100%".to_owned())
+					.child(
+						ui::TableHeader::new().child(
+							ui::TableRow::new()
+								.child(ui::TableHeaderCell::new().child("Created"))
+								.child(ui::TableHeaderCell::new().child("Last Active"))
+								.child(ui::TableHeaderCell::new().child("IP Address"))
+								.child(ui::TableHeaderCell::new().child("User Agent"))
+								.child(ui::TableHeaderCell::new()),
+						),
+					)
+					.child(
+						ui::TableBody::new().children(self.sessions.into_iter().map(|session| {
+							let revoke_cell = if session.current {
+								ui::TableCell::new().child("This session")
+							} else {
+								ui::TableCell::new().child(RevokeSessionForm {
+									session_id: session.id.to_string(),
+								})
+							};
+							ui::TableRow::new()
+								.child(ui::TableCell::new().child(format_timestamp(session.created_at)))
+								.child(
+									ui::TableCell::new()
+										.child(format_timestamp(session.last_active_at)),
+								)
+								.child(
+									ui::TableCell::new()
+										.child(session.ip.unwrap_or_else(|| "Unknown".to_owned())),
+								)
+								.child(ui::TableCell::new().child(
+									session.user_agent.unwrap_or_else(|| "Unknown".to_owned()),
+								))
+								.child(revoke_cell)
+						})),
+					),
+			)
+			.into_node()
+	}
+}
+
+struct RevokeSessionForm {
+	session_id: String,
+}
+
+impl Component for RevokeSessionForm {
+	fn into_node(self) -> Node {
+		ui::Form::new()
+			.post(true)
+			.child(
+				input()
+					.attribute("name", "action")
+					.attribute("type", "hidden")
+					.attribute("value", "revoke_session"),
+			)
+			.child(
+				input()
+					.attribute("name", "session_id")
+					.attribute("type", "hidden")
+					.attribute("value", self.session_id),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.color(ui::colors::RED.to_owned())
+					.child("Revoke"),
+			)
+			.into_node()
+	}
+}
+
+/// Sessions created before sessions recorded their details have no timestamps.
+fn format_timestamp(timestamp: Option<i64>) -> String {
+	timestamp
+		.and_then(|timestamp| time::OffsetDateTime::from_unix_timestamp(timestamp).ok())
+		.and_then(|date_time| {
+			date_time
+				.format(format_description!(
+					"[year]-[month]-[day] [hour]:[minute] UTC"
+				))
+				.ok()
+		})
+		.unwrap_or_else(|| "Unknown".to_owned())
+}
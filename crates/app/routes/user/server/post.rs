@@ -1,9 +1,13 @@
 use anyhow::Result;
+use modelfox_app_base_path::app_path;
 use modelfox_app_context::Context;
 use modelfox_app_core::{
 	error::{bad_request, not_found, service_unavailable, unauthorized},
+	sessions::revoke_session,
+	timezone::{parse_timezone, set_user_timezone},
 	user::{authorize_normal_user, NormalUser},
 };
+use modelfox_id::Id;
 use std::{borrow::BorrowMut, sync::Arc};
 
 #[derive(serde::Deserialize, Debug)]
@@ -11,6 +15,20 @@ use std::{borrow::BorrowMut, sync::Arc};
 enum Action {
 	#[serde(rename = "logout")]
 	Logout,
+	#[serde(rename = "revoke_session")]
+	RevokeSession(RevokeSessionAction),
+	#[serde(rename = "update_timezone")]
+	UpdateTimezone(UpdateTimezoneAction),
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct RevokeSessionAction {
+	session_id: String,
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct UpdateTimezoneAction {
+	timezone: String,
 }
 
 pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
@@ -23,7 +41,7 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
 	};
-	let user = match authorize_normal_user(request, &mut db).await? {
+	let user = match authorize_normal_user(request, &mut db, app.clock()).await? {
 		Ok(user) => user,
 		Err(_) => return Ok(unauthorized()),
 	};
@@ -37,6 +55,31 @@ pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Resp
 	};
 	let response = match action {
 		Action::Logout => logout(&user, &mut db).await?,
+		Action::RevokeSession(action) => {
+			let session_id: Id = match action.session_id.parse() {
+				Ok(session_id) => session_id,
+				Err(_) => return Ok(bad_request()),
+			};
+			let now = app.clock().now_utc().unix_timestamp();
+			revoke_session(&mut db, user.id, session_id, now).await?;
+			http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(http::header::LOCATION, app_path("/user"))
+				.body(hyper::Body::empty())
+				.unwrap()
+		}
+		Action::UpdateTimezone(action) => {
+			let timezone = match parse_timezone(&action.timezone) {
+				Ok(timezone) => timezone,
+				Err(_) => return Ok(bad_request()),
+			};
+			set_user_timezone(&mut db, user.id, timezone).await?;
+			http::Response::builder()
+				.status(http::StatusCode::SEE_OTHER)
+				.header(http::header::LOCATION, app_path("/user"))
+				.body(hyper::Body::empty())
+				.unwrap()
+		}
 	};
 	app.commit_transaction(db).await?;
 	Ok(response)
@@ -56,8 +99,11 @@ async fn logout(
 	.await?;
 	let response = http::Response::builder()
 		.status(http::StatusCode::SEE_OTHER)
-		.header(http::header::LOCATION, "/login")
-		.header(http::header::SET_COOKIE, "auth=; Path=/; Max-Age=0")
+		.header(http::header::LOCATION, app_path("/login"))
+		.header(
+			http::header::SET_COOKIE,
+			format!("auth=; Path={}; Max-Age=0", app_path("/")),
+		)
 		.body(hyper::Body::empty())
 		.unwrap();
 	Ok(response)
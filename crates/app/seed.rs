@@ -57,7 +57,9 @@ pub async fn main() -> Result<()> {
 		modelfox_table::Table::from_path(Path::new(dataset.path), Default::default(), &mut |_| {})?;
 	let mut rng = rand::thread_rng();
 	let database = modelfox_app_core::options::DatabaseOptions {
+		analytics_statement_timeout: None,
 		max_connections: None,
+		replica_url: None,
 		url: args.database_url,
 	};
 	let host: IpAddr = "0.0.0.0".parse().unwrap();
@@ -65,12 +67,20 @@ pub async fn main() -> Result<()> {
 	let storage = modelfox_app_core::options::StorageOptions::InMemory;
 	let options = modelfox_app_core::options::Options {
 		auth: None,
+		base_path: String::new(),
+		body_size_limits: Default::default(),
 		cookie_domain: None,
 		database,
 		host,
+		ingest: None,
+		pause_notifications: false,
 		port,
+		secret_key: None,
 		smtp: None,
+		stateless: false,
 		storage,
+		tree_shap_mode: Default::default(),
+		trusted_proxies: Vec::new(),
 		url: None,
 	};
 	let app = App::new(options).await?;
@@ -103,6 +113,7 @@ pub async fn main() -> Result<()> {
 				model_id: model_id.parse().unwrap(),
 				options: None,
 				output,
+				sample_rate: None,
 			})];
 			if rng.gen::<f32>() > 0.4 {
 				events.push(MonitorEvent::TrueValue(TrueValueMonitorEvent {
@@ -110,6 +121,7 @@ pub async fn main() -> Result<()> {
 					identifier: NumberOrString::String(id.to_string()),
 					true_value: target,
 					date,
+					sample_rate: None,
 				}));
 			}
 			events
@@ -137,6 +149,11 @@ fn generate_fake_monitors() -> Vec<MonitorConfig> {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		},
 		MonitorConfig {
 			cadence: MonitorCadence::Daily,
@@ -148,6 +165,11 @@ fn generate_fake_monitors() -> Vec<MonitorConfig> {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		},
 		MonitorConfig {
 			cadence: MonitorCadence::Weekly,
@@ -159,6 +181,11 @@ fn generate_fake_monitors() -> Vec<MonitorConfig> {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		},
 		MonitorConfig {
 			cadence: MonitorCadence::Monthly,
@@ -170,6 +197,11 @@ fn generate_fake_monitors() -> Vec<MonitorConfig> {
 			},
 			title: None,
 			methods: vec![AlertMethod::Stdout],
+			minimum_sample_size: None,
+			warn_below_minimum_sample_size: false,
+			cooldown_periods: 0,
+			message_template: None,
+			require_confidence_interval: false,
 		},
 	]
 }
@@ -0,0 +1,41 @@
+use modelfox_charts::common::AxisScale;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+/// This select field switches a chart between a linear and a log scale. The page reads the selected scale from the `scale` search param.
+pub struct AxisScaleSelectField {
+	pub axis_scale: AxisScale,
+}
+
+impl AxisScaleSelectField {
+	pub fn new(axis_scale: AxisScale) -> AxisScaleSelectField {
+		AxisScaleSelectField { axis_scale }
+	}
+}
+
+impl Component for AxisScaleSelectField {
+	fn into_node(self) -> Node {
+		let options = vec![
+			ui::SelectFieldOption {
+				text: "Linear".to_owned(),
+				value: "linear".to_owned(),
+			},
+			ui::SelectFieldOption {
+				text: "Log".to_owned(),
+				value: "log".to_owned(),
+			},
+		];
+		let value = match self.axis_scale {
+			AxisScale::Linear => "linear",
+			AxisScale::Log => "log",
+			AxisScale::Symlog => "symlog",
+		};
+		ui::SelectField::new()
+			.id("axis_scale_select_field".to_owned())
+			.label("Scale".to_owned())
+			.name("scale".to_owned())
+			.options(options)
+			.value(value.to_owned())
+			.into_node()
+	}
+}
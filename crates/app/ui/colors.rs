@@ -4,3 +4,5 @@ pub const TRAINING_COLOR: &str = ui::colors::BLUE;
 pub const PRODUCTION_COLOR: &str = ui::colors::GREEN;
 pub const BASELINE_COLOR: &str = ui::colors::GRAY;
 pub const SELECTED_THRESHOLD_COLOR: &str = ui::colors::BLUE;
+/// This is [`PRODUCTION_COLOR`] at a fifth of its opacity, for shading confidence intervals behind production metrics.
+pub const PRODUCTION_CONFIDENCE_INTERVAL_COLOR: &str = "#30d15833";
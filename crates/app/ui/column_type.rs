@@ -4,4 +4,5 @@ pub enum ColumnType {
 	Number,
 	Enum,
 	Text,
+	DateTime,
 }
@@ -1,14 +1,21 @@
-use modelfox_app_date_window::DateWindow;
+use modelfox_app_date_window::{DateWindow, DateWindowInterval};
 use modelfox_ui as ui;
 use pinwheel::prelude::*;
 
 pub struct DateWindowSelectField {
 	pub date_window: DateWindow,
+	pub date_window_interval: DateWindowInterval,
 }
 
 impl DateWindowSelectField {
-	pub fn new(date_window: DateWindow) -> DateWindowSelectField {
-		DateWindowSelectField { date_window }
+	pub fn new(
+		date_window: DateWindow,
+		date_window_interval: DateWindowInterval,
+	) -> DateWindowSelectField {
+		DateWindowSelectField {
+			date_window,
+			date_window_interval,
+		}
 	}
 }
 
@@ -28,12 +35,37 @@ impl Component for DateWindowSelectField {
 				value: "this_year".to_owned(),
 			},
 		];
-		ui::SelectField::new()
-			.id("date_window_select_field".to_owned())
-			.label("Date Window".to_owned())
-			.name("date_window".to_owned())
-			.options(options)
-			.value(self.date_window.to_string())
+		let interval_options = self
+			.date_window
+			.intervals()
+			.iter()
+			.map(|date_window_interval| ui::SelectFieldOption {
+				text: match date_window_interval {
+					DateWindowInterval::Hourly => "Hourly".to_owned(),
+					DateWindowInterval::Daily => "Daily".to_owned(),
+					DateWindowInterval::Weekly => "Weekly".to_owned(),
+					DateWindowInterval::Monthly => "Monthly".to_owned(),
+				},
+				value: date_window_interval.to_string(),
+			})
+			.collect();
+		fragment()
+			.child(
+				ui::SelectField::new()
+					.id("date_window_select_field".to_owned())
+					.label("Date Window".to_owned())
+					.name("date_window".to_owned())
+					.options(options)
+					.value(self.date_window.to_string()),
+			)
+			.child(
+				ui::SelectField::new()
+					.id("date_window_interval_select_field".to_owned())
+					.label("Interval".to_owned())
+					.name("date_window_interval".to_owned())
+					.options(interval_options)
+					.value(self.date_window_interval.to_string()),
+			)
 			.into_node()
 	}
 }
@@ -1,3 +1,4 @@
+pub mod axis_scale_select_field;
 pub mod class_select_field;
 pub mod colors;
 pub mod column_type;
@@ -7,6 +8,7 @@ pub mod metrics_row;
 pub mod page_heading;
 pub mod pagination;
 pub mod predict;
+pub mod stale_stats_alert;
 pub mod time;
 pub mod tokens;
 pub mod topbar;
@@ -11,15 +11,29 @@ pub struct Pagination {
 pub struct PaginationButtons {
 	pub pagination: Pagination,
 	pub form_action: Option<String>,
+	/// These are the names and values of other query params, such as filters, to submit with the offset.
+	pub hidden_fields: Vec<(String, String)>,
 }
 
 impl Component for PaginationButtons {
 	fn into_node(self) -> Node {
+		let hidden_fields = || {
+			self.hidden_fields
+				.iter()
+				.map(|(name, value)| {
+					input()
+						.attribute("name", name.clone())
+						.attribute("type", "hidden")
+						.attribute("value", value.clone())
+				})
+				.collect::<Vec<_>>()
+		};
 		div()
 			.class("offset-pagination-buttons")
 			.child(
 				ui::Form::new()
 					.action(self.form_action.clone())
+					.children(hidden_fields())
 					.child(self.pagination.first_offset.map(|first_offset| {
 						input()
 							.name("offset")
@@ -36,6 +50,7 @@ impl Component for PaginationButtons {
 			.child(
 				ui::Form::new()
 					.action(self.form_action.clone())
+					.children(hidden_fields())
 					.child(self.pagination.previous_offset.map(|previous_offset| {
 						input()
 							.attribute("name", "offset")
@@ -52,6 +67,7 @@ impl Component for PaginationButtons {
 			.child(
 				ui::Form::new()
 					.action(self.form_action.clone())
+					.children(hidden_fields())
 					.child(self.pagination.next_offset.map(|next_offset| {
 						input()
 							.attribute("name", "offset")
@@ -68,6 +84,7 @@ impl Component for PaginationButtons {
 			.child(
 				ui::Form::new()
 					.action(self.form_action.clone())
+					.children(hidden_fields())
 					.child(self.pagination.last_offset.map(|last_offset| {
 						input()
 							.attribute("name", "offset")
@@ -98,7 +115,7 @@ pub fn compute_pagination(offset: usize, n_items: usize, limit: usize) -> Pagina
 	} else {
 		Some(offset - limit)
 	};
-	let last_offset = n_items - limit;
+	let last_offset = n_items.saturating_sub(limit);
 	let last_offset = if offset >= last_offset {
 		None
 	} else {
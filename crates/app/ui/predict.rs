@@ -1,6 +1,9 @@
 use crate::{
 	metrics_row::MetricsRow,
-	tokens::{EnumColumnToken, NumberColumnToken, TextColumnToken, UnknownColumnToken},
+	tokens::{
+		DateTimeColumnToken, EnumColumnToken, NumberColumnToken, TextColumnToken,
+		UnknownColumnToken,
+	},
 };
 use modelfox_charts::{
 	bar_chart::{BarChartPoint, BarChartSeries},
@@ -58,6 +61,7 @@ pub enum InputTableColumnType {
 	Number,
 	Enum,
 	Text,
+	DateTime,
 }
 
 impl Component for InputTable {
@@ -97,6 +101,7 @@ impl Component for ColumnTypeToken {
 			InputTableColumnType::Number => NumberColumnToken.into(),
 			InputTableColumnType::Enum => EnumColumnToken.into(),
 			InputTableColumnType::Text => TextColumnToken.into(),
+			InputTableColumnType::DateTime => DateTimeColumnToken.into(),
 		}
 	}
 }
@@ -171,6 +176,19 @@ pub fn compute_input_table(
 					column_type: InputTableColumnType::Text,
 				}
 			}
+			modelfox_model::ColumnStatsReader::DateTimeColumn(column_stats) => {
+				let column_stats = column_stats.read();
+				let column_name = column_stats.column_name().to_owned();
+				let value = input.0.get(&column_name).map(|value| match value {
+					modelfox_core::predict::PredictInputValue::Number(n) => n.to_string(),
+					modelfox_core::predict::PredictInputValue::String(s) => s.clone(),
+				});
+				InputTableRow {
+					column_name,
+					value,
+					column_type: InputTableColumnType::DateTime,
+				}
+			}
 		})
 		.collect::<Vec<_>>();
 	InputTable { rows }
@@ -271,6 +289,7 @@ impl Component for MulticlassClassificationPredictOutput {
 					label: class_name.to_owned(),
 					x: index.to_f64().unwrap(),
 					y: Some(probability.to_f64().unwrap()),
+					error: None,
 				})
 				.collect::<Vec<_>>(),
 		}];
@@ -422,5 +441,18 @@ fn compute_feature_contributions_chart_value(
 					.unwrap(),
 			}
 		}
+		modelfox_core::predict::FeatureContributionEntry::Cyclical(feature_contribution) => {
+			let feature = format!(
+				"{} ({})",
+				feature_contribution.column_name, feature_contribution.component
+			);
+			FeatureContributionsChartValue {
+				feature,
+				value: feature_contribution
+					.feature_contribution_value
+					.to_f64()
+					.unwrap(),
+			}
+		}
 	}
 }
@@ -0,0 +1,25 @@
+use crate::time::{format_day, format_hour};
+use chrono::prelude::*;
+use chrono_tz::Tz;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+/// This alert is shown above production stats that were read from the daily rollups alone because reading all of them took too long.
+pub struct StaleStatsAlert {
+	pub rolled_up_through: DateTime<Utc>,
+	pub timezone: Tz,
+}
+
+impl Component for StaleStatsAlert {
+	fn into_node(self) -> Node {
+		let rolled_up_through = self.rolled_up_through.with_timezone(&self.timezone);
+		let message = format!(
+			"Loading the most recent production data took too long, so these stats only include predictions made before {} on {}. Reload the page to try again.",
+			format_hour(rolled_up_through),
+			format_day(rolled_up_through),
+		);
+		ui::Alert::new(ui::Level::Warning)
+			.child(message)
+			.into_node()
+	}
+}
@@ -20,6 +20,7 @@ pub fn format_date_window_interval(
 	match date_window_interval {
 		DateWindowInterval::Hourly => format_hour(date),
 		DateWindowInterval::Daily => format_day_of_month(date),
+		DateWindowInterval::Weekly => format_day_of_month(date),
 		DateWindowInterval::Monthly => format_month(date),
 	}
 }
@@ -28,6 +29,7 @@ pub fn interval_chart_title(date_window_interval: &DateWindowInterval, title: St
 	match date_window_interval {
 		DateWindowInterval::Hourly => format!("Hourly {}", title),
 		DateWindowInterval::Daily => format!("Daily {}", title),
+		DateWindowInterval::Weekly => format!("Weekly {}", title),
 		DateWindowInterval::Monthly => format!("Monthly {}", title),
 	}
 }
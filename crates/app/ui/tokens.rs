@@ -46,6 +46,17 @@ impl Component for TextColumnToken {
 	}
 }
 
+pub struct DateTimeColumnToken;
+
+impl Component for DateTimeColumnToken {
+	fn into_node(self) -> Node {
+		ui::Token::new()
+			.color(ui::colors::INDIGO.to_owned())
+			.child("DateTime")
+			.into_node()
+	}
+}
+
 pub struct ColumnTypeToken {
 	column_type: ColumnType,
 }
@@ -63,6 +74,7 @@ impl Component for ColumnTypeToken {
 			ColumnType::Number => NumberColumnToken.into_node(),
 			ColumnType::Enum => EnumColumnToken.into_node(),
 			ColumnType::Text => TextColumnToken.into_node(),
+			ColumnType::DateTime => DateTimeColumnToken.into_node(),
 		}
 	}
 }
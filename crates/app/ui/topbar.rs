@@ -1,4 +1,5 @@
 use crate::logo::Logo;
+use modelfox_app_base_path::app_path;
 use modelfox_ui as ui;
 use pinwheel::prelude::*;
 
@@ -19,11 +20,11 @@ impl Component for Topbar {
 			let avatar_item = ui::TopbarItem {
 				element: Some(
 					ui::Link::new()
-						.href("/user".to_owned())
+						.href(app_path("/user"))
 						.child(ui::Avatar::new().src(topbar_avatar.avatar_url))
 						.into_node(),
 				),
-				href: "/user".to_owned(),
+				href: app_path("/user"),
 				title: "Settings".to_owned(),
 			};
 			Some(vec![avatar_item])
@@ -35,7 +36,7 @@ impl Component for Topbar {
 			.background_color(ui::colors::HEADER.to_owned())
 			.dropdown_background_color(ui::colors::SURFACE.to_owned())
 			.items(items)
-			.logo_href("/".to_owned())
+			.logo_href(app_path("/"))
 			.logo(logo)
 			.title("ModelFox".to_owned())
 			.into_node()
@@ -32,6 +32,7 @@ fn main() {
 	build_rpms(&version);
 	build_pkgs(&version, &pkgs_url);
 	build_release(&version);
+	build_native_extensions(&version);
 }
 
 fn compile() {
@@ -802,6 +803,85 @@ fn build_release(version: &str) {
 	}
 }
 
+fn build_native_extensions(version: &str) {
+	let root_path = std::env::current_dir().unwrap();
+	let compile_path = root_path.join("dist").join("compile");
+	let release_path = root_path.join("dist").join("release");
+	// modelfox ruby gems, one per platform so installing one does not require a rust toolchain.
+	let ruby_path = root_path.join("languages").join("ruby");
+	for target in TARGETS {
+		let gem_platform = match target.ruby_gem_platform() {
+			Some(gem_platform) => gem_platform,
+			None => continue,
+		};
+		let libmodelfox_dynamic_file_name =
+			TargetFileNames::for_target(target).libmodelfox_dynamic_file_name;
+		let gem_tempdir = tempdir().unwrap();
+		let gem_path = gem_tempdir.path();
+		for file_path in [
+			"LICENSE",
+			"README.md",
+			"modelfox.gemspec",
+			"lib/modelfox.rb",
+			"lib/modelfox/modelfox.rb",
+		] {
+			std::fs::create_dir_all(gem_path.join(file_path).parent().unwrap()).unwrap();
+			std::fs::copy(ruby_path.join(file_path), gem_path.join(file_path)).unwrap();
+		}
+		// Only this target's libmodelfox goes into its gem.
+		let libmodelfox_path = gem_path
+			.join("lib")
+			.join("modelfox")
+			.join("libmodelfox")
+			.join(target.target_name());
+		std::fs::create_dir_all(&libmodelfox_path).unwrap();
+		std::fs::copy(
+			compile_path
+				.join(target.target_name())
+				.join(libmodelfox_dynamic_file_name),
+			libmodelfox_path.join(libmodelfox_dynamic_file_name),
+		)
+		.unwrap();
+		let output_path = release_path.join(format!(
+			"modelfox-{version}-{gem_platform}.gem",
+			version = version,
+			gem_platform = gem_platform,
+		));
+		cmd!("gem", "build", "modelfox.gemspec", "-o", &output_path)
+			.dir(gem_path)
+			.env("MODELFOX_GEM_PLATFORM", gem_platform)
+			.run()
+			.unwrap();
+	}
+	// modelfox_elixir
+	for target in TARGETS {
+		if target == Target::X8664WindowsGnu {
+			continue;
+		}
+		let modelfox_elixir_file_name =
+			TargetFileNames::for_target(target).modelfox_elixir_file_name;
+		let modelfox_elixir_path = compile_path
+			.join(target.target_name())
+			.join(modelfox_elixir_file_name);
+		// The BEAM loads NIFs with the .so extension on macOS, so the .dylib is renamed.
+		let nif_file_name = match target {
+			Target::X8664WindowsMsvc => "modelfox_elixir.dll",
+			_ => "libmodelfox_elixir.so",
+		};
+		let target_name = target.target_name();
+		let output_path = release_path.join(format!(
+			"modelfox_elixir_{version}_{target_name}.tar.gz",
+			version = version,
+			target_name = target_name,
+		));
+		let inputs = vec![(
+			modelfox_elixir_path,
+			PathBuf::from(target_name).join(nif_file_name),
+		)];
+		tar(&inputs, &output_path);
+	}
+}
+
 const TARGETS: [Target; 8] = [
 	Target::AArch64LinuxGnu,
 	Target::AArch64LinuxMusl,
@@ -873,6 +953,20 @@ impl Target {
 		}
 	}
 
+	#[must_use]
+	pub fn ruby_gem_platform(&self) -> Option<&'static str> {
+		match self {
+			Target::AArch64LinuxGnu => Some("aarch64-linux"),
+			Target::AArch64LinuxMusl => Some("aarch64-linux-musl"),
+			Target::AArch64MacOs => Some("arm64-darwin"),
+			Target::X8664LinuxGnu => Some("x86_64-linux"),
+			Target::X8664LinuxMusl => Some("x86_64-linux-musl"),
+			Target::X8664MacOs => Some("x86_64-darwin"),
+			Target::X8664WindowsGnu => None,
+			Target::X8664WindowsMsvc => Some("x64-mingw32"),
+		}
+	}
+
 	#[must_use]
 	pub fn rust_target(&self) -> &'static str {
 		match self {
@@ -2,17 +2,18 @@ use crate::{
 	chart::{ChartImpl, DrawChartOptions, DrawChartOutput, DrawOverlayOptions, HoverRegion},
 	common::{
 		compute_rects, draw_rounded_rect, draw_x_axis, draw_x_axis_title, draw_y_axis_grid_lines,
-		draw_y_axis_labels, draw_y_axis_title, ComputeRectsOptions, ComputeRectsOutput,
+		draw_y_axis_labels, draw_y_axis_title, AxisScale, ComputeRectsOptions, ComputeRectsOutput,
 		DrawRoundedRectOptions, DrawXAxisOptions, DrawXAxisTitleOptions, DrawYAxisGridLinesOptions,
 		DrawYAxisLabelsOptions, DrawYAxisTitleOptions, GridLineInterval, Point, Rect,
 	},
 	config::{ChartColors, ChartConfig},
+	context::DrawingContext,
+	data_table::ChartDataTable,
 	tooltip::{draw_tooltip, DrawTooltipOptions, TooltipLabel},
 };
 use itertools::Itertools;
 use modelfox_number_formatter::NumberFormatter;
 use num::ToPrimitive;
-use web_sys as dom;
 
 pub struct BarChart;
 
@@ -32,6 +33,57 @@ impl ChartImpl for BarChart {
 	) {
 		draw_bar_chart_overlay(options);
 	}
+
+	fn data_table(options: &Self::Options) -> ChartDataTable {
+		// Each row is a category, with a column for the value of each series.
+		let headers = std::iter::once(
+			options
+				.x_axis_title
+				.clone()
+				.unwrap_or_else(|| "Label".to_owned()),
+		)
+		.chain(
+			options
+				.series
+				.iter()
+				.enumerate()
+				.map(|(series_index, series)| {
+					series
+						.title
+						.clone()
+						.unwrap_or_else(|| format!("Series {}", series_index + 1))
+				}),
+		)
+		.collect();
+		let n_categories = options
+			.series
+			.iter()
+			.map(|series| series.data.len())
+			.max()
+			.unwrap_or(0);
+		let rows = (0..n_categories)
+			.map(|point_index| {
+				let label = options
+					.series
+					.iter()
+					.find_map(|series| series.data.get(point_index))
+					.map(|point| point.label.clone())
+					.unwrap_or_default();
+				std::iter::once(label)
+					.chain(options.series.iter().map(|series| {
+						options
+							.number_formatter
+							.format_option(series.data.get(point_index).and_then(|point| point.y))
+					}))
+					.collect()
+			})
+			.collect();
+		ChartDataTable {
+			caption: options.y_axis_title.clone(),
+			headers,
+			rows,
+		}
+	}
 }
 
 #[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -44,6 +96,8 @@ pub struct BarChartOptions {
 	pub should_draw_y_axis_labels: Option<bool>,
 	pub x_axis_title: Option<String>,
 	pub y_axis_grid_line_interval: Option<GridLineInterval>,
+	/// On a log scale, bars start at the bottom of the chart, and values that are not positive have no bar.
+	pub y_axis_scale: Option<AxisScale>,
 	pub y_axis_title: Option<String>,
 	pub y_max: Option<f64>,
 	pub y_min: Option<f64>,
@@ -61,6 +115,9 @@ pub struct BarChartPoint {
 	pub label: String,
 	pub x: f64,
 	pub y: Option<f64>,
+	/// If this is set, an error bar is drawn from `y - error` to `y + error`.
+	#[serde(default)]
+	pub error: Option<f64>,
 }
 
 pub struct BarChartOverlayInfo {
@@ -96,27 +153,35 @@ fn draw_bar_chart(
 		y_axis_title,
 		..
 	} = options;
-	let width = ctx.canvas().unwrap().client_width().to_f64().unwrap();
-	let height = ctx.canvas().unwrap().client_height().to_f64().unwrap();
+	let width = ctx.width();
+	let height = ctx.height();
 	let mut hover_regions: Vec<HoverRegion<BarChartHoverRegionInfo>> = Vec::new();
 
-	// Compute the bounds.
-	let y_min: Option<f64> = options.y_min.or_else(|| {
+	// Compute the bounds in the space transformed by the y axis scale. Values the scale cannot transform are left out.
+	let y_axis_scale = options.y_axis_scale.unwrap_or_default();
+	let transform_y = |y: f64| Some(y_axis_scale.transform(y)).filter(|y| y.is_finite());
+	let y_min: Option<f64> = options.y_min.and_then(transform_y).or_else(|| {
 		series
 			.iter()
-			.flat_map(|series| series.data.iter().map(|p| p.y))
+			.flat_map(|series| series.data.iter().map(|p| p.y.and_then(transform_y)))
 			.flatten()
 			.min_by(|a, b| a.partial_cmp(b).unwrap())
 	});
-	let y_max = options.y_max.or_else(|| {
+	let y_max = options.y_max.and_then(transform_y).or_else(|| {
 		series
 			.iter()
-			.flat_map(|series| series.data.iter().map(|p| p.y))
+			.flat_map(|series| {
+				series
+					.data
+					.iter()
+					.map(|p| p.y.and_then(|y| transform_y(y + p.error.unwrap_or(0.0))))
+			})
 			.flatten()
 			.max_by(|a, b| a.partial_cmp(b).unwrap())
 	});
 	let (y_min, y_max) = match (y_min, y_max) {
 		(Some(y_min), Some(y_max)) => {
+			let (y_min, y_max) = y_axis_scale.round_bounds(y_min, y_max);
 			if (y_max - y_min).abs() < f64::EPSILON {
 				(y_min, y_min + 1.0)
 			} else {
@@ -146,6 +211,7 @@ fn draw_bar_chart(
 		width,
 		x_axis_grid_line_interval: None,
 		y_axis_grid_line_interval: y_axis_grid_line_interval.as_ref(),
+		y_axis_scale,
 		y_max,
 		y_min,
 	});
@@ -225,19 +291,18 @@ fn draw_bar_chart(
 		});
 	}
 
-	// Draw the bars.
+	// Draw the bars. Bars extend from zero, or from the bottom of the chart on a log scale, which has no zero.
+	let baseline = match y_axis_scale {
+		AxisScale::Log => y_min,
+		AxisScale::Linear | AxisScale::Symlog => 0.0f64.max(y_min).min(y_max),
+	};
 	let series_len = series.len();
 	let has_multiple_series = series_len > 1;
 	for (series_index, series) in series.iter().enumerate() {
 		for (point_index, point) in series.data.iter().enumerate() {
-			if let Some(y) = point.y {
-				let h = if y < 0.0 {
-					(y - y_max.min(0.0)) / (y_max - y_min) * chart_rect.h
-				} else {
-					(y - y_min.max(0.0)) / (y_max - y_min) * chart_rect.h
-				};
+			if let Some((value, y)) = point.y.and_then(|value| Some((value, transform_y(value)?))) {
 				let rect = Rect {
-					h,
+					h: (y - baseline) / (y_max - y_min) * chart_rect.h,
 					w: (bar_group_width
 						- chart_config.bar_gap * (series_len - 1).to_f64().unwrap())
 						/ series_len.to_f64().unwrap(),
@@ -253,19 +318,34 @@ fn draw_bar_chart(
 					color: &format!("{}af", series.color),
 					ctx,
 				});
+				if let Some(error) = point.error {
+					// Clamp the ends of the error bar to the chart, including ends that the scale cannot transform.
+					let to_pixels = |value: f64| {
+						let value = y_axis_scale.transform(value).max(y_min).min(y_max);
+						chart_rect.y + ((y_max - value) / (y_max - y_min)) * chart_rect.h
+					};
+					draw_error_bar(DrawErrorBarOptions {
+						chart_colors,
+						ctx,
+						x: rect.x + rect.w / 2.0,
+						whisker_width: rect.w / 4.0,
+						y_top: to_pixels(value + error),
+						y_bottom: to_pixels(value - error),
+					});
+				}
 				let hover_region = HoverRegion {
 					distance: Box::new(move |x, _| (rect.x + rect.w / 2.0 - x).abs()),
 					hit_test: Box::new(move |x, y| {
 						x >= rect.x
-							&& x < rect.x + rect.w && y >= chart_rect.y
-							&& y < chart_rect.y + chart_rect.h
+							&& x < rect.x + rect.w
+							&& y >= chart_rect.y && y < chart_rect.y + chart_rect.h
 					}),
 					info: BarChartHoverRegionInfo {
 						rect,
 						color: series.color.clone(),
 						point: point.clone(),
 						point_label: point.label.clone(),
-						point_value: y,
+						point_value: value,
 						series_title: if has_multiple_series {
 							series.title.clone()
 						} else {
@@ -294,7 +374,7 @@ fn draw_bar_chart(
 struct DrawBarOptions<'a> {
 	chart_config: &'a ChartConfig,
 	color: &'a str,
-	ctx: &'a dom::CanvasRenderingContext2d,
+	ctx: &'a dyn DrawingContext,
 	rect: Rect,
 }
 
@@ -328,20 +408,49 @@ fn draw_bar(options: DrawBarOptions) {
 	});
 }
 
+struct DrawErrorBarOptions<'a> {
+	chart_colors: &'a ChartColors,
+	ctx: &'a dyn DrawingContext,
+	x: f64,
+	whisker_width: f64,
+	y_top: f64,
+	y_bottom: f64,
+}
+
+fn draw_error_bar(options: DrawErrorBarOptions) {
+	let DrawErrorBarOptions {
+		chart_colors,
+		ctx,
+		x,
+		whisker_width,
+		y_top,
+		y_bottom,
+	} = options;
+	ctx.save();
+	ctx.set_stroke_style(chart_colors.crosshairs_color);
+	ctx.set_line_width(1.0);
+	ctx.begin_path();
+	ctx.move_to(x, y_top);
+	ctx.line_to(x, y_bottom);
+	ctx.move_to(x - whisker_width / 2.0, y_top);
+	ctx.line_to(x + whisker_width / 2.0, y_top);
+	ctx.move_to(x - whisker_width / 2.0, y_bottom);
+	ctx.line_to(x + whisker_width / 2.0, y_bottom);
+	ctx.stroke();
+	ctx.restore();
+}
+
 #[derive(Clone, Copy)]
 pub struct DrawBarChartXAxisLabelsOptions<'a> {
 	pub bar_group_gap: f64,
 	pub chart_colors: &'a ChartColors,
 	pub rect: Rect,
 	pub categories: &'a [&'a String],
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub group_width: f64,
 	pub width: f64,
 }
 
-/// # Panics
-///
-/// This function will fail is the draw call to the `CanvasRenderingContext2d` handle fails.
 pub fn draw_bar_chart_x_axis_labels(options: DrawBarChartXAxisLabelsOptions) {
 	let DrawBarChartXAxisLabelsOptions {
 		bar_group_gap,
@@ -353,13 +462,13 @@ pub fn draw_bar_chart_x_axis_labels(options: DrawBarChartXAxisLabelsOptions) {
 		width,
 	} = options;
 	ctx.save();
-	ctx.set_fill_style(&chart_colors.label_color.into());
+	ctx.set_fill_style(chart_colors.label_color);
 	ctx.set_text_baseline("bottom");
 	ctx.set_text_align("center");
 	// Find the smallest label step size at which labels do not overlap.
 	let label_widths: Vec<f64> = categories
 		.iter()
-		.map(|label| ctx.measure_text(label).unwrap().width())
+		.map(|label| ctx.measure_text(label))
 		.collect();
 	let mut label_step_size = 1;
 	loop {
@@ -384,15 +493,12 @@ pub fn draw_bar_chart_x_axis_labels(options: DrawBarChartXAxisLabelsOptions) {
 			+ group_width / 2.0
 			+ (bar_group_gap + group_width) * label_index.to_f64().unwrap();
 		// Do not draw the label if it will overflow the chart.
-		let overflow_left =
-			rect.x + label_offset - ctx.measure_text(label).unwrap().width() / 2.0 < 0.0;
-		let overflow_right =
-			rect.x + label_offset + ctx.measure_text(label).unwrap().width() / 2.0 > width;
+		let overflow_left = rect.x + label_offset - ctx.measure_text(label) / 2.0 < 0.0;
+		let overflow_right = rect.x + label_offset + ctx.measure_text(label) / 2.0 > width;
 		if overflow_left || overflow_right {
 			continue;
 		}
-		ctx.fill_text(label, rect.x + label_offset, rect.y + rect.h)
-			.unwrap();
+		ctx.fill_text(label, rect.x + label_offset, rect.y + rect.h);
 	}
 	ctx.restore();
 }
@@ -407,6 +513,15 @@ fn draw_bar_chart_overlay(
 			.options
 			.number_formatter
 			.format(active_hover_region.info.point_value);
+		let point_value = if let Some(error) = active_hover_region.info.point.error {
+			format!(
+				"{} ± {}",
+				point_value,
+				options.options.number_formatter.format(error)
+			)
+		} else {
+			point_value
+		};
 		let text = if let Some(series_title) = series_title {
 			format!("{} ({}, {})", series_title, point_label, point_value)
 		} else {
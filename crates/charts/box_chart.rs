@@ -3,17 +3,17 @@ use crate::{
 	chart::{ChartImpl, DrawChartOptions, DrawChartOutput, DrawOverlayOptions, HoverRegion},
 	common::{
 		compute_rects, draw_rounded_rect, draw_x_axis, draw_x_axis_title, draw_y_axis_grid_lines,
-		draw_y_axis_labels, draw_y_axis_title, ComputeRectsOptions, ComputeRectsOutput,
+		draw_y_axis_labels, draw_y_axis_title, AxisScale, ComputeRectsOptions, ComputeRectsOutput,
 		DrawRoundedRectOptions, DrawXAxisOptions, DrawXAxisTitleOptions, DrawYAxisGridLinesOptions,
 		DrawYAxisLabelsOptions, DrawYAxisTitleOptions, Point, Rect,
 	},
 	config::ChartConfig,
+	context::DrawingContext,
+	data_table::ChartDataTable,
 	tooltip::{draw_tooltip, DrawTooltipOptions, TooltipLabel},
 };
 use modelfox_number_formatter::NumberFormatter;
 use num::ToPrimitive;
-use wasm_bindgen::JsValue;
-use web_sys as dom;
 
 pub struct BoxChart;
 
@@ -83,6 +83,59 @@ impl ChartImpl for BoxChart {
 	) {
 		draw_box_chart_overlay(options);
 	}
+
+	fn data_table(options: &Self::Options) -> ChartDataTable {
+		// Each row is a box, labeled with its series if there is more than one.
+		let include_series = options.series.len() > 1;
+		let mut headers = vec![options
+			.x_axis_title
+			.clone()
+			.unwrap_or_else(|| "Label".to_owned())];
+		if include_series {
+			headers.push("Series".to_owned());
+		}
+		headers.extend(
+			["Min", "25th Percentile", "Median", "75th Percentile", "Max"]
+				.iter()
+				.map(|header| (*header).to_owned()),
+		);
+		let number_formatter = &options.number_formatter;
+		let rows = options
+			.series
+			.iter()
+			.enumerate()
+			.flat_map(|(series_index, series)| {
+				series.data.iter().map(move |point| {
+					let mut row = vec![point.label.clone()];
+					if include_series {
+						row.push(
+							series
+								.title
+								.clone()
+								.unwrap_or_else(|| format!("Series {}", series_index + 1)),
+						);
+					}
+					match &point.y {
+						Some(value) => row.extend(
+							[value.min, value.p25, value.p50, value.p75, value.max]
+								.iter()
+								.map(|value| number_formatter.format(*value)),
+						),
+						None => row.extend(std::iter::repeat("N/A".to_owned()).take(5)),
+					}
+					row
+				})
+			})
+			.collect();
+		ChartDataTable {
+			caption: options
+				.title
+				.clone()
+				.or_else(|| options.y_axis_title.clone()),
+			headers,
+			rows,
+		}
+	}
 }
 
 #[allow(clippy::too_many_lines)]
@@ -101,9 +154,8 @@ fn draw_box_chart(
 		y_axis_title,
 		..
 	} = &options;
-	let canvas = ctx.canvas().unwrap();
-	let width = canvas.client_width().to_f64().unwrap();
-	let height = canvas.client_height().to_f64().unwrap();
+	let width = ctx.width();
+	let height = ctx.height();
 	let mut hover_regions: Vec<HoverRegion<BoxChartHoverRegionInfo>> = Vec::new();
 
 	// Compute the bounds.
@@ -152,6 +204,7 @@ fn draw_box_chart(
 		width,
 		x_axis_grid_line_interval: None,
 		y_axis_grid_line_interval: None,
+		y_axis_scale: AxisScale::Linear,
 		y_max,
 		y_min,
 	});
@@ -330,7 +383,7 @@ struct DrawBoxOptions<'a> {
 	box_group_width: f64,
 	chart_rect: Rect,
 	chart_config: &'a ChartConfig,
-	ctx: &'a dom::CanvasRenderingContext2d,
+	ctx: &'a dyn DrawingContext,
 	data: &'a [BoxChartSeries],
 	point: &'a BoxChartPoint,
 	point_index: usize,
@@ -645,7 +698,7 @@ fn box_chart_hover_region(
 #[derive(Clone, Copy)]
 struct DrawLineOptions<'a> {
 	color: Option<&'a str>,
-	ctx: &'a dom::CanvasRenderingContext2d,
+	ctx: &'a dyn DrawingContext,
 	dashed: Option<bool>,
 	end: Point,
 	line_cap: Option<&'a str>,
@@ -668,11 +721,10 @@ fn draw_line(options: DrawLineOptions) {
 	let line_cap = line_cap.unwrap_or("butt");
 	ctx.save();
 	if dashed {
-		ctx.set_line_dash(&JsValue::from_serde(&[4.0, 4.0]).unwrap())
-			.unwrap();
+		ctx.set_line_dash(&[4.0, 4.0]);
 	}
 	if let Some(color) = &color {
-		ctx.set_stroke_style(&color.to_owned().into());
+		ctx.set_stroke_style(color);
 	}
 	ctx.set_line_width(line_width);
 	ctx.set_line_cap(line_cap);
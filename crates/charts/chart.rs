@@ -1,4 +1,10 @@
-use crate::config::{ChartColors, ChartConfig, DARK_CHART_COLORS, LIGHT_CHART_COLORS};
+use crate::{
+	common::Rect,
+	config::{ChartColors, ChartConfig, DARK_CHART_COLORS, LIGHT_CHART_COLORS},
+	context::DrawingContext,
+	data_table::ChartDataTable,
+	svg::chart_to_svg,
+};
 use num::ToPrimitive;
 use std::{cell::RefCell, rc::Rc};
 use wasm_bindgen::{prelude::*, JsCast};
@@ -9,15 +15,22 @@ where
 	T: ChartImpl,
 {
 	active_hover_regions: Vec<ActiveHoverRegion<T::HoverRegionInfo>>,
+	brush: Option<Brush>,
 	chart_canvas: dom::HtmlCanvasElement,
 	chart_colors: Option<ChartColors>,
 	chart_config: Option<ChartConfig>,
 	color_scheme_media_query: Option<dom::MediaQueryList>,
 	container: dom::HtmlDivElement,
+	export_div: dom::HtmlElement,
+	focused_hover_region_index: Option<usize>,
 	hover_regions: Option<Vec<HoverRegion<T::HoverRegionInfo>>>,
 	intersection_callback: Option<Closure<dyn Fn(JsValue, dom::IntersectionObserver)>>,
 	intersection_observer: Option<dom::IntersectionObserver>,
+	on_blur: Option<Closure<dyn Fn()>>,
 	on_color_scheme_media_query_change: Option<Closure<dyn Fn()>>,
+	on_export_png: Option<Closure<dyn Fn()>>,
+	on_export_svg: Option<Closure<dyn Fn()>>,
+	on_keydown: Option<Closure<dyn Fn(dom::KeyboardEvent)>>,
 	on_mouse_event: Option<Closure<dyn Fn(dom::MouseEvent)>>,
 	on_resize: Option<Closure<dyn Fn()>>,
 	on_touch_event: Option<Closure<dyn Fn(dom::TouchEvent)>>,
@@ -37,13 +50,46 @@ pub trait ChartImpl: 'static {
 	fn draw_overlay(
 		options: &DrawOverlayOptions<Self::Options, Self::OverlayInfo, Self::HoverRegionInfo>,
 	);
+	/// Return the chart's data as a table for screen readers.
+	fn data_table(options: &Self::Options) -> ChartDataTable;
+	/// Charts that can be brushed to select a range of x values return the region that can be brushed.
+	fn brush_info(_overlay_info: &Self::OverlayInfo) -> Option<BrushInfo> {
+		None
+	}
+}
+
+/// This is the name of the event dispatched on a chart's container when a range of it is brushed. Its detail is a [`BrushRange`].
+pub const BRUSH_EVENT: &str = "chart-brush";
+
+/// Brushes narrower than this many pixels are treated as clicks.
+const MIN_BRUSH_WIDTH: f64 = 4.0;
+
+#[derive(Clone, Copy)]
+pub struct BrushInfo {
+	pub chart_rect: Rect,
+	pub x_max: f64,
+	pub x_min: f64,
+}
+
+/// The range of x values that was brushed.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct BrushRange {
+	pub start: f64,
+	pub end: f64,
+}
+
+/// A brush in progress. `start` is where the mouse was pressed and `end` is where it is now, in pixels.
+#[derive(Clone, Copy)]
+struct Brush {
+	start: f64,
+	end: f64,
 }
 
 #[derive(Clone, Copy)]
 pub struct DrawChartOptions<'a, Options> {
 	pub chart_colors: &'a ChartColors,
 	pub chart_config: &'a ChartConfig,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub options: &'a Options,
 }
 
@@ -62,7 +108,7 @@ where
 	pub active_hover_regions: &'a [ActiveHoverRegion<HoverRegionInfo>],
 	pub chart_colors: &'a ChartColors,
 	pub chart_config: &'a ChartConfig,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub options: &'a Options,
 	pub overlay_info: &'a OverlayInfo,
 	pub overlay_div: &'a dom::HtmlElement,
@@ -104,6 +150,19 @@ where
 			.style()
 			.set_property("position", "relative")
 			.unwrap();
+		// Make the container focusable so the chart's data points can be navigated with the keyboard.
+		container.class_list().add_1("chart-focusable").unwrap();
+		container.set_attribute("tabindex", "0").unwrap();
+		container.set_attribute("role", "group").unwrap();
+		container
+			.set_attribute("aria-roledescription", "chart")
+			.unwrap();
+		container
+			.set_attribute(
+				"aria-label",
+				"Chart. Use the arrow keys to move between data points.",
+			)
+			.unwrap();
 		let chart_canvas = document
 			.create_element("canvas")
 			.unwrap()
@@ -133,6 +192,8 @@ where
 				right: 0;
 			"#,
 		);
+		// Tooltips are announced by screen readers as they appear.
+		overlay_div.set_attribute("aria-live", "polite").unwrap();
 		container.append_child(&overlay_div).unwrap();
 		let overlay_canvas = document
 			.create_element("canvas")
@@ -149,18 +210,41 @@ where
 			"#,
 		);
 		container.append_child(&overlay_canvas).unwrap();
+		// Create the export buttons, which are drawn above the overlay canvas.
+		let export_div = document
+			.create_element("div")
+			.unwrap()
+			.dyn_into::<dom::HtmlElement>()
+			.unwrap();
+		export_div.set_class_name("chart-export");
+		let export_svg_button = document.create_element("button").unwrap();
+		export_svg_button.set_class_name("chart-export-button");
+		export_svg_button.set_text_content(Some("SVG"));
+		export_div.append_child(&export_svg_button).unwrap();
+		let export_png_button = document.create_element("button").unwrap();
+		export_png_button.set_class_name("chart-export-button");
+		export_png_button.set_text_content(Some("PNG"));
+		export_div.append_child(&export_png_button).unwrap();
+		container.append_child(&export_div).unwrap();
 		// Create the Chart.
 		let chart = Rc::new(RefCell::new(Chart {
 			active_hover_regions: Vec::new(),
+			brush: None,
 			chart_canvas,
 			chart_colors: None,
 			chart_config: Some(ChartConfig::default()),
 			color_scheme_media_query: None,
 			container,
+			export_div,
+			focused_hover_region_index: None,
 			hover_regions: None,
 			intersection_callback: None,
 			intersection_observer: None,
+			on_blur: None,
 			on_color_scheme_media_query_change: None,
+			on_export_png: None,
+			on_export_svg: None,
+			on_keydown: None,
 			on_mouse_event: None,
 			on_resize: None,
 			on_touch_event: None,
@@ -192,9 +276,15 @@ where
 			let canvas_client_rect = chart.chart_canvas.get_bounding_client_rect();
 			let x = event.client_x().to_f64().unwrap() - canvas_client_rect.left();
 			let y = event.client_y().to_f64().unwrap() - canvas_client_rect.top();
+			chart.update_brush(&event.type_(), x);
 			chart.update_active_hover_regions(x, y);
 			chart.draw_overlay();
 		}));
+		chart
+			.borrow_mut()
+			.overlay_canvas
+			.add_event_listener_with_callback("mousedown", on_mouse_event.as_ref().unchecked_ref())
+			.unwrap();
 		chart
 			.borrow_mut()
 			.overlay_canvas
@@ -210,6 +300,11 @@ where
 			.overlay_canvas
 			.add_event_listener_with_callback("mousemove", on_mouse_event.as_ref().unchecked_ref())
 			.unwrap();
+		chart
+			.borrow_mut()
+			.overlay_canvas
+			.add_event_listener_with_callback("mouseup", on_mouse_event.as_ref().unchecked_ref())
+			.unwrap();
 		chart.borrow_mut().on_mouse_event = Some(on_mouse_event);
 		// Add the touch event handler.
 		let chart_ref = Rc::downgrade(&chart);
@@ -229,6 +324,35 @@ where
 			.add_event_listener_with_callback("touchstart", on_touch_event.as_ref().unchecked_ref())
 			.unwrap();
 		chart.borrow_mut().on_touch_event = Some(on_touch_event);
+		// Add the keyboard handlers.
+		let chart_ref = Rc::downgrade(&chart);
+		let on_keydown = Closure::<dyn Fn(_)>::wrap(Box::new(move |event: dom::KeyboardEvent| {
+			let chart = chart_ref.upgrade().unwrap();
+			let mut chart = chart.borrow_mut();
+			if chart.focus_hover_region(&event.key()) {
+				event.prevent_default();
+				chart.draw_overlay();
+			}
+		}));
+		chart
+			.borrow_mut()
+			.container
+			.add_event_listener_with_callback("keydown", on_keydown.as_ref().unchecked_ref())
+			.unwrap();
+		chart.borrow_mut().on_keydown = Some(on_keydown);
+		let chart_ref = Rc::downgrade(&chart);
+		let on_blur = Closure::<dyn Fn()>::wrap(Box::new(move || {
+			let chart = chart_ref.upgrade().unwrap();
+			let mut chart = chart.borrow_mut();
+			chart.focus_hover_region("Escape");
+			chart.draw_overlay();
+		}));
+		chart
+			.borrow_mut()
+			.container
+			.add_event_listener_with_callback("blur", on_blur.as_ref().unchecked_ref())
+			.unwrap();
+		chart.borrow_mut().on_blur = Some(on_blur);
 		// Add the resize handler.
 		let chart_ref = Rc::downgrade(&chart);
 		let on_resize = Closure::<dyn Fn()>::wrap(Box::new(move || {
@@ -262,9 +386,75 @@ where
 		chart.borrow_mut().color_scheme_media_query = Some(color_scheme_media_query);
 		chart.borrow_mut().on_color_scheme_media_query_change =
 			Some(on_color_scheme_media_query_change);
+		// Add the export handlers.
+		let chart_ref = Rc::downgrade(&chart);
+		let on_export_svg = Closure::<dyn Fn()>::wrap(Box::new(move || {
+			let chart = chart_ref.upgrade().unwrap();
+			let chart = chart.borrow();
+			download(&svg_data_url(&chart.to_svg()), "chart.svg");
+		}));
+		export_svg_button
+			.add_event_listener_with_callback("click", on_export_svg.as_ref().unchecked_ref())
+			.unwrap();
+		chart.borrow_mut().on_export_svg = Some(on_export_svg);
+		let chart_ref = Rc::downgrade(&chart);
+		let on_export_png = Closure::<dyn Fn()>::wrap(Box::new(move || {
+			let chart = chart_ref.upgrade().unwrap();
+			let chart = chart.borrow();
+			chart.export_png();
+		}));
+		export_png_button
+			.add_event_listener_with_callback("click", on_export_png.as_ref().unchecked_ref())
+			.unwrap();
+		chart.borrow_mut().on_export_png = Some(on_export_png);
 		chart
 	}
 
+	/// Serialize the chart at its current size to a standalone svg document.
+	#[must_use]
+	pub fn to_svg(&self) -> String {
+		let width = self.container.client_width().to_f64().unwrap();
+		let height = self.container.client_height().to_f64().unwrap();
+		chart_to_svg::<T>(&self.options, width, height)
+	}
+
+	/// Rasterize the chart's svg to a png at twice its size and download it. The svg is drawn to a canvas that is not attached to the document, so the png does not include the overlay.
+	fn export_png(&self) {
+		let width = self.container.client_width().to_f64().unwrap();
+		let height = self.container.client_height().to_f64().unwrap();
+		let scale = 2.0;
+		let image = dom::HtmlImageElement::new().unwrap();
+		let image_ref = image.clone();
+		let on_load = Closure::once_into_js(move || {
+			let document = dom::window().unwrap().document().unwrap();
+			let canvas = document
+				.create_element("canvas")
+				.unwrap()
+				.dyn_into::<dom::HtmlCanvasElement>()
+				.unwrap();
+			canvas.set_width((width * scale).to_u32().unwrap());
+			canvas.set_height((height * scale).to_u32().unwrap());
+			let ctx = canvas
+				.get_context("2d")
+				.unwrap()
+				.unwrap()
+				.dyn_into::<dom::CanvasRenderingContext2d>()
+				.unwrap();
+			ctx.draw_image_with_html_image_element_and_dw_and_dh(
+				&image_ref,
+				0.0,
+				0.0,
+				width * scale,
+				height * scale,
+			)
+			.unwrap();
+			let data_url = canvas.to_data_url_with_type("image/png").unwrap();
+			download(&data_url, "chart.png");
+		});
+		image.set_onload(Some(on_load.unchecked_ref()));
+		image.set_src(&svg_data_url(&self.to_svg()));
+	}
+
 	pub fn update(&mut self, options: T::Options) {
 		self.options = options;
 		self.draw_chart();
@@ -370,6 +560,77 @@ where
 			overlay_info: self.overlay_info.as_ref().unwrap(),
 			overlay_div: &self.overlay_div,
 		});
+		let brush_info = self.overlay_info.as_ref().and_then(T::brush_info);
+		if let (Some(brush), Some(brush_info)) = (self.brush, brush_info) {
+			draw_brush(&ctx, self.chart_colors.as_ref().unwrap(), brush_info, brush);
+		}
+	}
+
+	/// Start, extend, or finish brushing as the mouse is pressed, moved, and released. When a brush is finished, a [`BRUSH_EVENT`] is dispatched on the container with the range of x values that was brushed.
+	fn update_brush(&mut self, event_type: &str, x: f64) {
+		let brush_info = match self.overlay_info.as_ref().and_then(T::brush_info) {
+			Some(brush_info) => brush_info,
+			None => return,
+		};
+		let chart_rect = brush_info.chart_rect;
+		match event_type {
+			"mousedown" => {
+				if x >= chart_rect.x && x <= chart_rect.x + chart_rect.w {
+					self.brush = Some(Brush { start: x, end: x });
+				}
+			}
+			"mousemove" => {
+				if let Some(brush) = self.brush.as_mut() {
+					brush.end = x;
+				}
+			}
+			"mouseup" => {
+				if let Some(brush) = self.brush.take() {
+					if (brush.end - brush.start).abs() >= MIN_BRUSH_WIDTH {
+						self.dispatch_brush_event(brush_range(brush_info, brush));
+					}
+				}
+			}
+			"mouseleave" => {
+				self.brush = None;
+			}
+			_ => {}
+		}
+	}
+
+	fn dispatch_brush_event(&self, range: BrushRange) {
+		let detail = JsValue::from_serde(&range).unwrap();
+		let mut event_init = dom::CustomEventInit::new();
+		event_init.bubbles(true).detail(&detail);
+		let event = dom::CustomEvent::new_with_event_init_dict(BRUSH_EVENT, &event_init).unwrap();
+		self.container.dispatch_event(&event).unwrap();
+	}
+
+	/// Move the keyboard focus between hover regions in response to a key, showing the focused region's tooltip. The arrow keys move to the next or previous region, Home and End move to the first or last region, and Escape clears the focus. This returns whether the key was handled.
+	fn focus_hover_region(&mut self, key: &str) -> bool {
+		let hover_regions = match self.hover_regions.as_ref() {
+			Some(hover_regions) if !hover_regions.is_empty() => hover_regions,
+			_ => return false,
+		};
+		let last_index = hover_regions.len() - 1;
+		let index = match (key, self.focused_hover_region_index) {
+			("ArrowRight" | "ArrowDown", Some(index)) => Some((index + 1).min(last_index)),
+			("ArrowLeft" | "ArrowUp", Some(index)) => Some(index.min(last_index).saturating_sub(1)),
+			("ArrowRight" | "ArrowDown" | "ArrowLeft" | "ArrowUp" | "Home", _) => Some(0),
+			("End", _) => Some(last_index),
+			("Escape", _) => None,
+			_ => return false,
+		};
+		self.focused_hover_region_index = index;
+		self.active_hover_regions = index
+			.map(|index| {
+				vec![ActiveHoverRegion {
+					distance: 0.0,
+					info: hover_regions[index].info.clone(),
+				}]
+			})
+			.unwrap_or_default();
+		true
 	}
 
 	fn update_active_hover_regions(&mut self, x: f64, y: f64) {
@@ -394,6 +655,15 @@ where
 	fn drop(&mut self) {
 		// Remove event listeners.
 		let on_mouse_event = self.on_mouse_event.as_ref().unwrap();
+		self.overlay_canvas
+			.remove_event_listener_with_callback(
+				"mousedown",
+				on_mouse_event.as_ref().unchecked_ref(),
+			)
+			.unwrap();
+		self.overlay_canvas
+			.remove_event_listener_with_callback("mouseup", on_mouse_event.as_ref().unchecked_ref())
+			.unwrap();
 		self.chart_canvas
 			.remove_event_listener_with_callback(
 				"mouseenter",
@@ -419,6 +689,14 @@ where
 				on_touch_event.as_ref().unchecked_ref(),
 			)
 			.unwrap();
+		let on_keydown = self.on_keydown.as_ref().unwrap();
+		self.container
+			.remove_event_listener_with_callback("keydown", on_keydown.as_ref().unchecked_ref())
+			.unwrap();
+		let on_blur = self.on_blur.as_ref().unwrap();
+		self.container
+			.remove_event_listener_with_callback("blur", on_blur.as_ref().unchecked_ref())
+			.unwrap();
 		let on_resize = self.on_resize.as_ref().unwrap();
 		dom::window()
 			.unwrap()
@@ -434,9 +712,94 @@ where
 				on_color_scheme_media_query_change.as_ref().unchecked_ref(),
 			)
 			.unwrap();
-		// Remove html elements.
+		// Remove html elements. The export buttons' listeners are removed with the export div.
+		self.container.remove_child(&self.export_div).unwrap();
 		self.container.remove_child(&self.chart_canvas).unwrap();
 		self.container.remove_child(&self.overlay_canvas).unwrap();
 		self.container.remove_child(&self.overlay_div).unwrap();
 	}
 }
+
+/// Call `f` with the range of x values each time a chart in the element with the given id is brushed.
+pub fn on_brush(id: &str, f: impl Fn(BrushRange) + 'static) {
+	let document = dom::window().unwrap().document().unwrap();
+	let element = document.get_element_by_id(id).unwrap();
+	let callback = Closure::<dyn Fn(_)>::wrap(Box::new(move |event: dom::CustomEvent| {
+		if let Ok(range) = event.detail().into_serde() {
+			f(range);
+		}
+	}));
+	element
+		.add_event_listener_with_callback(BRUSH_EVENT, callback.as_ref().unchecked_ref())
+		.unwrap();
+	callback.forget();
+}
+
+/// Convert a brush from pixels to the range of x values it spans, clamped to the chart's bounds.
+fn brush_range(brush_info: BrushInfo, brush: Brush) -> BrushRange {
+	let BrushInfo {
+		chart_rect,
+		x_max,
+		x_min,
+	} = brush_info;
+	let to_value = |x: f64| {
+		let fraction = ((x - chart_rect.x) / chart_rect.w).clamp(0.0, 1.0);
+		x_min + fraction * (x_max - x_min)
+	};
+	BrushRange {
+		start: to_value(brush.start.min(brush.end)),
+		end: to_value(brush.start.max(brush.end)),
+	}
+}
+
+/// Shade the region of the chart that is being brushed.
+fn draw_brush(
+	ctx: &dyn DrawingContext,
+	chart_colors: &ChartColors,
+	brush_info: BrushInfo,
+	brush: Brush,
+) {
+	let chart_rect = brush_info.chart_rect;
+	let start = brush
+		.start
+		.min(brush.end)
+		.clamp(chart_rect.x, chart_rect.x + chart_rect.w);
+	let end = brush
+		.start
+		.max(brush.end)
+		.clamp(chart_rect.x, chart_rect.x + chart_rect.w);
+	ctx.save();
+	ctx.begin_path();
+	ctx.set_fill_style(chart_colors.brush_color);
+	ctx.move_to(start, chart_rect.y);
+	ctx.line_to(end, chart_rect.y);
+	ctx.line_to(end, chart_rect.y + chart_rect.h);
+	ctx.line_to(start, chart_rect.y + chart_rect.h);
+	ctx.fill();
+	ctx.restore();
+}
+
+/// Download the resource at `url` with the given file name.
+fn download(url: &str, file_name: &str) {
+	let document = dom::window().unwrap().document().unwrap();
+	let anchor = document
+		.create_element("a")
+		.unwrap()
+		.dyn_into::<dom::HtmlAnchorElement>()
+		.unwrap();
+	anchor.set_href(url);
+	anchor.set_download(file_name);
+	anchor.click();
+}
+
+fn svg_data_url(svg: &str) -> String {
+	let mut url = "data:image/svg+xml;charset=utf-8,".to_owned();
+	for byte in svg.bytes() {
+		if byte.is_ascii_alphanumeric() || b"-_.!~*'() ".contains(&byte) {
+			url.push(char::from(byte));
+		} else {
+			url.push_str(&format!("%{:02X}", byte));
+		}
+	}
+	url
+}
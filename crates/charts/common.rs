@@ -1,8 +1,10 @@
-use super::config::{ChartColors, ChartConfig};
+use super::{
+	config::{ChartColors, ChartConfig},
+	context::DrawingContext,
+};
 use modelfox_number_formatter::NumberFormatter;
 use num::ToPrimitive;
 use std::borrow::Cow;
-use web_sys as dom;
 
 // |--------------------------------------------------|
 // |  | |                                             |
@@ -22,7 +24,7 @@ use web_sys as dom;
 #[derive(Clone, Copy)]
 pub struct ComputeRectsOptions<'a> {
 	pub chart_config: &'a ChartConfig,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub height: f64,
 	pub include_x_axis_labels: bool,
 	pub include_x_axis_title: bool,
@@ -32,6 +34,7 @@ pub struct ComputeRectsOptions<'a> {
 	pub width: f64,
 	pub x_axis_grid_line_interval: Option<&'a GridLineInterval>,
 	pub y_axis_grid_line_interval: Option<&'a GridLineInterval>,
+	pub y_axis_scale: AxisScale,
 	pub y_max: f64,
 	pub y_min: f64,
 }
@@ -59,6 +62,65 @@ pub struct Rect {
 	pub h: f64,
 }
 
+/// This is how values are positioned along an axis. Charts convert values with [`AxisScale::transform`] before laying them out, so bounds and grid lines are in the transformed space, and convert grid line values back with [`AxisScale::invert`] to label them.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AxisScale {
+	Linear,
+	/// Values are positioned by their base 10 logarithm, so each power of ten is the same distance apart. Values that are not positive cannot be drawn on this scale.
+	Log,
+	/// Values between -1 and 1 are positioned linearly and the rest by the logarithm of their magnitude, so values with long tails on both sides of zero can be drawn together.
+	Symlog,
+}
+
+impl Default for AxisScale {
+	fn default() -> AxisScale {
+		AxisScale::Linear
+	}
+}
+
+impl AxisScale {
+	#[must_use]
+	pub fn transform(self, value: f64) -> f64 {
+		match self {
+			AxisScale::Linear => value,
+			AxisScale::Log => value.log10(),
+			AxisScale::Symlog => {
+				if value.abs() <= 1.0 {
+					value
+				} else {
+					value.signum() * (1.0 + value.abs().log10())
+				}
+			}
+		}
+	}
+
+	#[must_use]
+	pub fn invert(self, value: f64) -> f64 {
+		match self {
+			AxisScale::Linear => value,
+			AxisScale::Log => 10.0f64.powf(value),
+			AxisScale::Symlog => {
+				if value.abs() <= 1.0 {
+					value
+				} else {
+					value.signum() * 10.0f64.powf(value.abs() - 1.0)
+				}
+			}
+		}
+	}
+
+	/// Widen transformed bounds so they fall on grid lines. Log bounds are widened to the next power of ten, so the smallest bar in a bar chart still has a height.
+	#[must_use]
+	pub fn round_bounds(self, min: f64, max: f64) -> (f64, f64) {
+		match self {
+			AxisScale::Linear => (min, max),
+			AxisScale::Log => (min.ceil() - 1.0, max.floor() + 1.0),
+			AxisScale::Symlog => (min.floor(), max.ceil()),
+		}
+	}
+}
+
 /// The interval is k * 10 ** p. k will always be 1, 2, or 5.
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct GridLineInterval {
@@ -73,6 +135,8 @@ pub struct GridLineInfo {
 	pub k: f64,
 	pub num_grid_lines: usize,
 	pub p: f64,
+	/// The grid line values are in the space transformed by this scale.
+	pub scale: AxisScale,
 	pub start: f64,
 	pub start_pixels: f64,
 }
@@ -100,6 +164,7 @@ pub fn compute_rects(options: ComputeRectsOptions) -> ComputeRectsOutput {
 		number_formatter,
 		width,
 		y_axis_grid_line_interval,
+		y_axis_scale,
 		y_max,
 		y_min,
 		..
@@ -121,6 +186,7 @@ pub fn compute_rects(options: ComputeRectsOptions) -> ComputeRectsOutput {
 		chart_height,
 		font_size,
 		y_axis_grid_line_interval,
+		y_axis_scale,
 		y_max,
 		y_min,
 	});
@@ -250,13 +316,14 @@ fn compute_grid_line_interval(
 	max: f64,
 	distance_pixels: f64,
 	min_grid_line_distance_pixels: f64,
+	scale: AxisScale,
 ) -> GridLineInterval {
 	let range = max - min;
 	let ideal_n = (distance_pixels / min_grid_line_distance_pixels).floor();
 	let ideal_interval = range / ideal_n;
 	let ideal_p = ideal_interval.log10().floor();
 	let ideal_k = ideal_interval / 10.0f64.powf(ideal_p);
-	if ideal_k <= 2.0 {
+	let grid_line_interval = if ideal_k <= 2.0 {
 		GridLineInterval { k: 2.0, p: ideal_p }
 	} else if ideal_k <= 5.0 {
 		GridLineInterval { k: 5.0, p: ideal_p }
@@ -265,6 +332,12 @@ fn compute_grid_line_interval(
 			k: 1.0,
 			p: ideal_p + 1.0,
 		}
+	};
+	// On log and symlog scales, grid lines are only drawn at powers of ten.
+	if scale != AxisScale::Linear && grid_line_interval.p < 0.0 {
+		GridLineInterval { k: 1.0, p: 0.0 }
+	} else {
+		grid_line_interval
 	}
 }
 
@@ -277,6 +350,7 @@ pub fn compute_grid_line_info(
 	max: f64,
 	distance_pixels: f64,
 	grid_line_interval: &GridLineInterval,
+	scale: AxisScale,
 ) -> GridLineInfo {
 	let range = max - min;
 	let GridLineInterval { k, p } = *grid_line_interval;
@@ -292,6 +366,7 @@ pub fn compute_grid_line_info(
 		k,
 		num_grid_lines,
 		p,
+		scale,
 		start,
 		start_pixels,
 	}
@@ -299,18 +374,17 @@ pub fn compute_grid_line_info(
 
 pub struct ComputeXAxisGridLineInfoOptions<'a> {
 	pub chart_width: f64,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub number_formatter: &'a NumberFormatter,
 	pub x_axis_grid_line_interval: Option<GridLineInterval>,
+	pub x_axis_scale: AxisScale,
 	pub x_max: f64,
 	pub x_min: f64,
 }
 
 /// # Panics
 ///
-/// This function has two possible panics:
-/// 1. The `grid_line_index` fails to cast from `usize` to `f64.`
-/// 1. The `ctx` is unable to preform the `measure_text()` FFI method.
+/// This function panics if the `grid_line_index` fails to cast from `usize` to `f64.`
 #[must_use]
 pub fn compute_x_axis_grid_line_info(options: ComputeXAxisGridLineInfoOptions) -> GridLineInfo {
 	let ComputeXAxisGridLineInfoOptions {
@@ -318,24 +392,41 @@ pub fn compute_x_axis_grid_line_info(options: ComputeXAxisGridLineInfoOptions) -
 		ctx,
 		number_formatter,
 		x_axis_grid_line_interval,
+		x_axis_scale,
 		x_max,
 		x_min,
 	} = options;
 	if let Some(x_axis_grid_line_interval) = x_axis_grid_line_interval {
-		return compute_grid_line_info(x_min, x_max, chart_width, &x_axis_grid_line_interval);
+		return compute_grid_line_info(
+			x_min,
+			x_max,
+			chart_width,
+			&x_axis_grid_line_interval,
+			x_axis_scale,
+		);
 	}
 	let mut x_axis_min_grid_line_distance = 1.0;
 	loop {
-		let x_axis_grid_line_interval =
-			compute_grid_line_interval(x_min, x_max, chart_width, x_axis_min_grid_line_distance);
-		let x_axis_grid_line_info =
-			compute_grid_line_info(x_min, x_max, chart_width, &x_axis_grid_line_interval);
+		let x_axis_grid_line_interval = compute_grid_line_interval(
+			x_min,
+			x_max,
+			chart_width,
+			x_axis_min_grid_line_distance,
+			x_axis_scale,
+		);
+		let x_axis_grid_line_info = compute_grid_line_info(
+			x_min,
+			x_max,
+			chart_width,
+			&x_axis_grid_line_interval,
+			x_axis_scale,
+		);
 		let mut found_overlap = false;
 		for grid_line_index in 0..x_axis_grid_line_info.num_grid_lines {
 			let grid_line_value = x_axis_grid_line_info.start
 				+ grid_line_index.to_f64().unwrap() * x_axis_grid_line_info.interval;
-			let label = number_formatter.format(grid_line_value);
-			let label_width = ctx.measure_text(&label).unwrap().width();
+			let label = number_formatter.format(x_axis_scale.invert(grid_line_value));
+			let label_width = ctx.measure_text(&label);
 			if label_width > x_axis_grid_line_info.interval_pixels {
 				x_axis_min_grid_line_distance = label_width;
 				found_overlap = true;
@@ -353,6 +444,7 @@ pub struct ComputeYAxisGridLineInfoOptions<'a> {
 	chart_height: f64,
 	font_size: f64,
 	y_axis_grid_line_interval: Option<&'a GridLineInterval>,
+	y_axis_scale: AxisScale,
 	y_max: f64,
 	y_min: f64,
 }
@@ -363,17 +455,24 @@ pub fn compute_y_axis_grid_line_info(options: ComputeYAxisGridLineInfoOptions) -
 		y_axis_grid_line_interval,
 		chart_height,
 		font_size,
+		y_axis_scale,
 		y_max,
 		y_min,
 	} = options;
-	let y_axis_grid_line_interval = y_axis_grid_line_interval
-		.cloned()
-		.unwrap_or_else(|| compute_grid_line_interval(y_min, y_max, chart_height, font_size));
-	compute_grid_line_info(y_min, y_max, chart_height, &y_axis_grid_line_interval)
+	let y_axis_grid_line_interval = y_axis_grid_line_interval.cloned().unwrap_or_else(|| {
+		compute_grid_line_interval(y_min, y_max, chart_height, font_size, y_axis_scale)
+	});
+	compute_grid_line_info(
+		y_min,
+		y_max,
+		chart_height,
+		&y_axis_grid_line_interval,
+		y_axis_scale,
+	)
 }
 
 fn compute_axis_labels_max_width(
-	ctx: &dom::CanvasRenderingContext2d,
+	ctx: &dyn DrawingContext,
 	grid_line_info: &GridLineInfo,
 	number_formatter: &NumberFormatter,
 ) -> f64 {
@@ -381,8 +480,8 @@ fn compute_axis_labels_max_width(
 		.map(|grid_line_index| {
 			let grid_line_value =
 				grid_line_info.start + grid_line_index.to_f64().unwrap() * grid_line_info.interval;
-			let label = number_formatter.format(grid_line_value);
-			ctx.measure_text(&label).unwrap().width()
+			let label = number_formatter.format(grid_line_info.scale.invert(grid_line_value));
+			ctx.measure_text(&label)
 		})
 		.max_by(|a, b| a.partial_cmp(b).unwrap())
 		.unwrap()
@@ -391,7 +490,7 @@ fn compute_axis_labels_max_width(
 pub struct DrawXAxisGridLinesOptions<'a> {
 	pub chart_colors: &'a ChartColors,
 	pub chart_config: &'a ChartConfig,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub rect: Rect,
 	pub x_axis_grid_line_info: GridLineInfo,
 }
@@ -412,7 +511,7 @@ pub fn draw_x_axis_grid_lines(options: DrawXAxisGridLinesOptions) {
 			+ grid_line_index.to_f64().unwrap() * x_axis_grid_line_info.interval_pixels;
 		let x = rect.x + grid_line_offset_pixels;
 		ctx.begin_path();
-		ctx.set_stroke_style(&chart_colors.grid_line_color.into());
+		ctx.set_stroke_style(chart_colors.grid_line_color);
 		ctx.set_line_width(chart_config.axis_width);
 		ctx.set_line_cap("square");
 		ctx.move_to(x, rect.y);
@@ -424,7 +523,7 @@ pub fn draw_x_axis_grid_lines(options: DrawXAxisGridLinesOptions) {
 pub struct DrawXAxisOptions<'a> {
 	pub chart_colors: &'a ChartColors,
 	pub chart_config: &'a ChartConfig,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub rect: Rect,
 	pub y_axis_grid_line_info: &'a GridLineInfo,
 }
@@ -443,12 +542,13 @@ pub fn draw_x_axis(options: &DrawXAxisOptions) {
 	for grid_line_index in 0..y_axis_grid_line_info.num_grid_lines {
 		let grid_line_value = y_axis_grid_line_info.start
 			+ grid_line_index.to_f64().unwrap() * y_axis_grid_line_info.interval;
-		if grid_line_value.abs() < f64::EPSILON {
+		// A log scale has no zero, so it has no axis line.
+		if y_axis_grid_line_info.scale.invert(grid_line_value).abs() < f64::EPSILON {
 			let grid_line_offset_pixels = y_axis_grid_line_info.start_pixels
 				+ grid_line_index.to_f64().unwrap() * y_axis_grid_line_info.interval_pixels;
 			let y = rect.y + rect.h - grid_line_offset_pixels;
 			ctx.begin_path();
-			ctx.set_stroke_style(&chart_colors.axis_color.into());
+			ctx.set_stroke_style(chart_colors.axis_color);
 			ctx.set_line_width(chart_config.axis_width);
 			ctx.set_line_cap("square");
 			ctx.move_to(rect.x, y);
@@ -462,7 +562,7 @@ pub fn draw_x_axis(options: &DrawXAxisOptions) {
 pub struct DrawYAxisGridLinesOptions<'a> {
 	pub chart_colors: &'a ChartColors,
 	pub chart_config: &'a ChartConfig,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub rect: Rect,
 	pub y_axis_grid_line_info: &'a GridLineInfo,
 }
@@ -483,7 +583,7 @@ pub fn draw_y_axis_grid_lines(options: DrawYAxisGridLinesOptions) {
 			+ grid_line_index.to_f64().unwrap() * y_axis_grid_line_info.interval_pixels;
 		let y = rect.y + rect.h - grid_line_offset_pixels;
 		ctx.begin_path();
-		ctx.set_stroke_style(&chart_colors.grid_line_color.into());
+		ctx.set_stroke_style(chart_colors.grid_line_color);
 		ctx.set_line_width(chart_config.axis_width);
 		ctx.set_line_cap("square");
 		ctx.move_to(rect.x, y);
@@ -495,7 +595,7 @@ pub fn draw_y_axis_grid_lines(options: DrawYAxisGridLinesOptions) {
 pub struct DrawYAxisOptions<'a> {
 	pub chart_colors: &'a ChartColors,
 	pub chart_config: &'a ChartConfig,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub rect: Rect,
 	pub x_axis_grid_line_info: &'a GridLineInfo,
 }
@@ -514,12 +614,12 @@ pub fn draw_y_axis(options: &DrawYAxisOptions) {
 	for grid_line_index in 0..x_axis_grid_line_info.num_grid_lines {
 		let grid_line_value = x_axis_grid_line_info.start
 			+ grid_line_index.to_f64().unwrap() * x_axis_grid_line_info.interval;
-		if grid_line_value.abs() < f64::EPSILON {
+		if x_axis_grid_line_info.scale.invert(grid_line_value).abs() < f64::EPSILON {
 			let grid_line_offset_pixels = x_axis_grid_line_info.start_pixels
 				+ grid_line_index.to_f64().unwrap() * x_axis_grid_line_info.interval_pixels;
 			let x = rect.x + grid_line_offset_pixels;
 			ctx.begin_path();
-			ctx.set_stroke_style(&chart_colors.axis_color.into());
+			ctx.set_stroke_style(chart_colors.axis_color);
 			ctx.set_line_width(chart_config.axis_width);
 			ctx.set_line_cap("square");
 			ctx.move_to(x, rect.y);
@@ -532,7 +632,7 @@ pub fn draw_y_axis(options: &DrawYAxisOptions) {
 pub struct DrawXAxisLabelsOptions<'a> {
 	pub chart_colors: &'a ChartColors,
 	pub rect: Rect,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub grid_line_info: GridLineInfo,
 	pub labels: &'a Option<Vec<String>>,
 	pub number_formatter: &'a NumberFormatter,
@@ -553,7 +653,7 @@ pub fn draw_x_axis_labels(options: DrawXAxisLabelsOptions) {
 		number_formatter,
 		width,
 	} = options;
-	ctx.set_fill_style(&chart_colors.label_color.into());
+	ctx.set_fill_style(chart_colors.label_color);
 	ctx.set_text_baseline("bottom");
 	ctx.set_text_align("center");
 	let mut previous_label_endpoint: Option<f64> = None;
@@ -565,28 +665,25 @@ pub fn draw_x_axis_labels(options: DrawXAxisLabelsOptions) {
 		let label: Cow<str> = if let Some(labels) = &labels {
 			labels.get(grid_line_index).unwrap().into()
 		} else {
-			number_formatter.format(grid_line_value).into()
+			number_formatter
+				.format(grid_line_info.scale.invert(grid_line_value))
+				.into()
 		};
 		// Do not draw the label if it will overlap the previous label.
 		if let Some(previous_label_endpoint) = previous_label_endpoint {
-			if grid_line_offset_pixels - ctx.measure_text(&label).unwrap().width() / 2.0
-				< previous_label_endpoint
-			{
+			if grid_line_offset_pixels - ctx.measure_text(&label) / 2.0 < previous_label_endpoint {
 				continue;
 			}
 		}
 		// Do not draw the label if it will overflow the chart.
-		if rect.x + grid_line_offset_pixels - ctx.measure_text(&label).unwrap().width() / 2.0 < 0.0
-			|| rect.x + grid_line_offset_pixels + ctx.measure_text(&label).unwrap().width() / 2.0
-				> width
+		if rect.x + grid_line_offset_pixels - ctx.measure_text(&label) / 2.0 < 0.0
+			|| rect.x + grid_line_offset_pixels + ctx.measure_text(&label) / 2.0 > width
 		{
 			break;
 		}
-		ctx.fill_text(&label, rect.x + grid_line_offset_pixels, rect.y + rect.h)
-			.unwrap();
+		ctx.fill_text(&label, rect.x + grid_line_offset_pixels, rect.y + rect.h);
 		// Set the endpoint value of the previous label. This is used to determine if the next label overlaps.
-		previous_label_endpoint =
-			Some(grid_line_offset_pixels + ctx.measure_text(&label).unwrap().width() / 2.0);
+		previous_label_endpoint = Some(grid_line_offset_pixels + ctx.measure_text(&label) / 2.0);
 	}
 }
 
@@ -594,7 +691,7 @@ pub fn draw_x_axis_labels(options: DrawXAxisLabelsOptions) {
 pub struct DrawYAxisLabelsOptions<'a> {
 	pub chart_colors: &'a ChartColors,
 	pub rect: Rect,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub font_size: f64,
 	pub grid_line_info: &'a GridLineInfo,
 	pub height: f64,
@@ -614,7 +711,7 @@ pub fn draw_y_axis_labels(options: DrawYAxisLabelsOptions) {
 		height,
 		number_formatter,
 	} = options;
-	ctx.set_fill_style(&chart_colors.label_color.into());
+	ctx.set_fill_style(chart_colors.label_color);
 	ctx.set_text_baseline("middle");
 	ctx.set_text_align("right");
 	for grid_line_index in 0..grid_line_info.num_grid_lines {
@@ -622,7 +719,7 @@ pub fn draw_y_axis_labels(options: DrawYAxisLabelsOptions) {
 			+ grid_line_index.to_f64().unwrap() * grid_line_info.interval_pixels;
 		let grid_line_value =
 			grid_line_info.start + grid_line_index.to_f64().unwrap() * grid_line_info.interval;
-		let label = number_formatter.format(grid_line_value);
+		let label = number_formatter.format(grid_line_info.scale.invert(grid_line_value));
 		if rect.y + rect.h - grid_line_offset_pixels - font_size / 2.0 < 0.0
 			|| rect.y + rect.h - grid_line_offset_pixels + font_size / 2.0 > height
 		{
@@ -632,15 +729,14 @@ pub fn draw_y_axis_labels(options: DrawYAxisLabelsOptions) {
 			&label,
 			rect.x + rect.w,
 			rect.y + rect.h - grid_line_offset_pixels,
-		)
-		.unwrap();
+		);
 	}
 }
 
 pub struct DrawXAxisTitleOptions<'a> {
 	pub chart_colors: &'a ChartColors,
 	pub rect: Rect,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub title: &'a str,
 }
 
@@ -658,16 +754,15 @@ pub fn draw_x_axis_title(options: &DrawXAxisTitleOptions) {
 	ctx.save();
 	ctx.set_text_align("center");
 	ctx.set_text_baseline("bottom");
-	ctx.set_fill_style(&chart_colors.title_color.into());
-	ctx.fill_text(&truncated_title, rect.x + rect.w / 2.0, rect.y + rect.h)
-		.unwrap();
+	ctx.set_fill_style(chart_colors.title_color);
+	ctx.fill_text(&truncated_title, rect.x + rect.w / 2.0, rect.y + rect.h);
 	ctx.restore();
 }
 
 pub struct DrawYAxisTitleOptions<'a> {
 	pub chart_colors: &'a ChartColors,
 	pub rect: Rect,
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub title: &'a str,
 }
 
@@ -683,20 +778,19 @@ pub fn draw_y_axis_title(options: &DrawYAxisTitleOptions) {
 	} = options;
 	let truncated_title = truncate_text(ctx, title, rect.h);
 	ctx.save();
-	ctx.translate(rect.x + rect.w / 2.0, rect.y + rect.h / 2.0)
-		.unwrap();
-	ctx.rotate(-std::f64::consts::PI / 2.0).unwrap();
+	ctx.translate(rect.x + rect.w / 2.0, rect.y + rect.h / 2.0);
+	ctx.rotate(-std::f64::consts::PI / 2.0);
 	ctx.set_text_align("center");
 	ctx.set_text_baseline("middle");
-	ctx.set_fill_style(&chart_colors.title_color.into());
-	ctx.fill_text(&truncated_title, 0.0, 0.0).unwrap();
+	ctx.set_fill_style(chart_colors.title_color);
+	ctx.fill_text(&truncated_title, 0.0, 0.0);
 	ctx.restore();
 }
 
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Copy)]
 pub struct DrawRoundedRectOptions<'a> {
-	pub ctx: &'a dom::CanvasRenderingContext2d,
+	pub ctx: &'a dyn DrawingContext,
 	pub fill_color: Option<&'a str>,
 	pub radius: f64,
 	pub rect: Rect,
@@ -743,10 +837,10 @@ pub fn draw_rounded_rect(options: DrawRoundedRectOptions) {
 		ctx.set_line_width(stroke_width);
 	}
 	if let Some(fill_color) = &fill_color {
-		ctx.set_fill_style(&fill_color.to_owned().into());
+		ctx.set_fill_style(fill_color);
 	}
 	if let Some(stroke_color) = &stroke_color {
-		ctx.set_stroke_style(&stroke_color.to_owned().into());
+		ctx.set_stroke_style(stroke_color);
 	}
 	ctx.begin_path();
 	if round_top_left {
@@ -756,26 +850,25 @@ pub fn draw_rounded_rect(options: DrawRoundedRectOptions) {
 	}
 	if round_top_right {
 		ctx.line_to(x + w - radius, y);
-		ctx.arc_to(x + w, y, x + w, y + radius, radius).unwrap();
+		ctx.arc_to(x + w, y, x + w, y + radius, radius);
 	} else {
 		ctx.line_to(x + w, y);
 	}
 	if round_bottom_right {
 		ctx.line_to(x + w, y + h - radius);
-		ctx.arc_to(x + w, y + h, x + w - radius, y + h, radius)
-			.unwrap();
+		ctx.arc_to(x + w, y + h, x + w - radius, y + h, radius);
 	} else {
 		ctx.line_to(x + w, y + h);
 	}
 	if round_bottom_left {
 		ctx.line_to(x + radius, y + h);
-		ctx.arc_to(x, y + h, x, y + h - radius, radius).unwrap();
+		ctx.arc_to(x, y + h, x, y + h - radius, radius);
 	} else {
 		ctx.line_to(x, y + h);
 	}
 	if round_top_left {
 		ctx.line_to(x, y + radius);
-		ctx.arc_to(x, y, x + radius, y, radius).unwrap();
+		ctx.arc_to(x, y, x + radius, y, radius);
 	} else {
 		ctx.line_to(x, y);
 	}
@@ -788,18 +881,14 @@ pub fn draw_rounded_rect(options: DrawRoundedRectOptions) {
 	ctx.restore();
 }
 
-fn truncate_text<'a>(
-	ctx: &'a dom::CanvasRenderingContext2d,
-	label: &'a str,
-	width: f64,
-) -> Cow<'a, str> {
-	if ctx.measure_text(label).unwrap().width() < width {
+fn truncate_text<'a>(ctx: &'a dyn DrawingContext, label: &'a str, width: f64) -> Cow<'a, str> {
+	if ctx.measure_text(label) < width {
 		return label.into();
 	}
 	let mut longest_truncated_label = "...".to_owned();
 	for i in 0..label.len() {
 		let truncated_label = format!("{}...", &label[0..i]);
-		let truncated_label_width = ctx.measure_text(&truncated_label).unwrap().width();
+		let truncated_label_width = ctx.measure_text(&truncated_label);
 		if truncated_label_width < width {
 			longest_truncated_label = truncated_label;
 		} else {
@@ -808,3 +897,16 @@ fn truncate_text<'a>(
 	}
 	longest_truncated_label.into()
 }
+
+#[test]
+fn test_axis_scale() {
+	for scale in [AxisScale::Linear, AxisScale::Log, AxisScale::Symlog] {
+		for value in [0.5, 1.0, 20.0, 1000.0] {
+			assert!((scale.invert(scale.transform(value)) - value).abs() < 1e-9);
+		}
+	}
+	assert!((AxisScale::Symlog.transform(-100.0) + 3.0).abs() < f64::EPSILON);
+	assert!(AxisScale::Log.transform(0.0).is_infinite());
+	assert_eq!(AxisScale::Log.round_bounds(0.0, 2.5), (-1.0, 3.0));
+	assert_eq!(AxisScale::Symlog.round_bounds(0.0, 2.5), (0.0, 3.0));
+}
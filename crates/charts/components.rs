@@ -1,15 +1,15 @@
 use crate::{
 	bar_chart::{BarChartOptions, BarChartSeries},
 	box_chart::{BoxChartOptions, BoxChartSeries},
-	chart::Chart,
-	common::GridLineInterval,
+	chart::{Chart, ChartImpl},
+	common::{AxisScale, GridLineInterval},
 	config::ChartConfig,
 	feature_contributions_chart::CompressFeatureContributionsChartSeriesOptions,
 	feature_contributions_chart::{
 		compress_feature_contributions_chart_series, FeatureContributionsChartOptions,
 		FeatureContributionsChartSeries,
 	},
-	line_chart::{LineChartOptions, LineChartSeries},
+	line_chart::{LineChartBand, LineChartOptions, LineChartSeries},
 };
 use futures::future::pending;
 use modelfox_finite::Finite;
@@ -39,6 +39,8 @@ pub struct BarChart {
 	#[builder]
 	pub y_axis_grid_line_interval: Option<GridLineInterval>,
 	#[builder]
+	pub y_axis_scale: Option<AxisScale>,
+	#[builder]
 	pub y_axis_title: Option<String>,
 	#[builder]
 	pub y_max: Option<f64>,
@@ -57,6 +59,7 @@ impl Component for BarChart {
 			should_draw_y_axis_labels: self.should_draw_y_axis_labels,
 			x_axis_title: self.x_axis_title,
 			y_axis_grid_line_interval: self.y_axis_grid_line_interval,
+			y_axis_scale: self.y_axis_scale,
 			y_axis_title: self.y_axis_title,
 			y_max: self.y_max,
 			y_min: self.y_min,
@@ -77,6 +80,9 @@ impl Component for BarChart {
 				})
 			})
 			.collect();
+		// The data table makes the chart's data available to screen readers.
+		let mut data_table = crate::bar_chart::BarChart::data_table(&options);
+		data_table.caption = self.title.clone().or(data_table.caption);
 		let title = self.title.map(ChartTitle::new);
 		let legend = if hide_legend {
 			None
@@ -110,6 +116,7 @@ impl Component for BarChart {
 			.child(title)
 			.child(legend)
 			.child(chart)
+			.child(data_table)
 			.into_node()
 	}
 }
@@ -167,6 +174,9 @@ impl Component for BoxChart {
 				})
 			})
 			.collect();
+		// The data table makes the chart's data available to screen readers.
+		let mut data_table = crate::box_chart::BoxChart::data_table(&options);
+		data_table.caption = self.title.clone().or(data_table.caption);
 		let title = self.title.map(ChartTitle::new);
 		let legend = if hide_legend {
 			None
@@ -200,6 +210,7 @@ impl Component for BoxChart {
 			.child(title)
 			.child(legend)
 			.child(chart)
+			.child(data_table)
 			.into_node()
 	}
 }
@@ -221,6 +232,8 @@ pub struct FeatureContributionsChart {
 	pub include_y_axis_title: Option<bool>,
 	#[builder]
 	pub title: Option<String>,
+	#[builder]
+	pub x_axis_scale: Option<AxisScale>,
 }
 
 impl Component for FeatureContributionsChart {
@@ -244,6 +257,7 @@ impl Component for FeatureContributionsChart {
 			number_formatter: NumberFormatter::default(),
 			positive_color: self.positive_color,
 			series,
+			x_axis_scale: self.x_axis_scale,
 		};
 		let inner_chart_height = n_series.to_f64().unwrap()
 			* chart_config.feature_contributions_series_height
@@ -264,6 +278,10 @@ impl Component for FeatureContributionsChart {
 			0.0
 		} + label_padding
 			+ font_size + bottom_padding;
+		// The data table makes the chart's data available to screen readers.
+		let mut data_table =
+			crate::feature_contributions_chart::FeatureContributionsChart::data_table(&options);
+		data_table.caption = self.title.clone().or(data_table.caption);
 		let title = self.title.map(ChartTitle::new);
 		let chart =
 			div()
@@ -289,6 +307,7 @@ impl Component for FeatureContributionsChart {
 			.class("chart-wrapper")
 			.child(title)
 			.child(chart)
+			.child(data_table)
 			.into_node()
 	}
 }
@@ -296,6 +315,10 @@ impl Component for FeatureContributionsChart {
 #[derive(builder, Default, new, serde::Serialize, serde::Deserialize)]
 #[new(default)]
 pub struct LineChart {
+	#[builder]
+	pub bands: Option<Vec<LineChartBand>>,
+	#[builder]
+	pub brushable: Option<bool>,
 	#[builder]
 	pub hide_legend: Option<bool>,
 	#[builder]
@@ -319,6 +342,8 @@ pub struct LineChart {
 	#[builder]
 	pub y_axis_grid_line_interval: Option<GridLineInterval>,
 	#[builder]
+	pub y_axis_scale: Option<AxisScale>,
+	#[builder]
 	pub y_axis_title: Option<String>,
 	#[builder]
 	pub y_max: Option<Finite<f64>>,
@@ -329,6 +354,8 @@ pub struct LineChart {
 impl Component for LineChart {
 	fn into_node(self) -> Node {
 		let options = LineChartOptions {
+			bands: self.bands.unwrap_or_default(),
+			brushable: self.brushable,
 			hide_legend: self.hide_legend,
 			labels: self.labels,
 			number_formatter: NumberFormatter::default(),
@@ -341,6 +368,7 @@ impl Component for LineChart {
 			x_max: self.x_max,
 			x_min: self.x_min,
 			y_axis_grid_line_interval: self.y_axis_grid_line_interval,
+			y_axis_scale: self.y_axis_scale,
 			y_axis_title: self.y_axis_title,
 			y_max: self.y_max,
 			y_min: self.y_min,
@@ -361,6 +389,9 @@ impl Component for LineChart {
 				})
 			})
 			.collect();
+		// The data table makes the chart's data available to screen readers.
+		let mut data_table = crate::line_chart::LineChart::data_table(&options);
+		data_table.caption = self.title.clone().or(data_table.caption);
 		let title = self.title.map(ChartTitle::new);
 		let legend = if hide_legend {
 			None
@@ -394,6 +425,7 @@ impl Component for LineChart {
 			.child(title)
 			.child(legend)
 			.child(chart)
+			.child(data_table)
 			.into_node()
 	}
 }
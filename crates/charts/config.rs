@@ -61,6 +61,7 @@ impl Default for ChartConfig {
 pub struct ChartColors {
 	pub axis_color: &'static str,
 	pub border_color: &'static str,
+	pub brush_color: &'static str,
 	pub crosshairs_color: &'static str,
 	pub grid_line_color: &'static str,
 	pub label_color: &'static str,
@@ -73,6 +74,7 @@ pub struct ChartColors {
 pub const LIGHT_CHART_COLORS: ChartColors = ChartColors {
 	axis_color: "#BBBBBB",
 	border_color: "#EEEEEE",
+	brush_color: "rgba(0, 0, 0, .1)",
 	crosshairs_color: "#666666",
 	grid_line_color: "#EEEEEE",
 	label_color: "#666666",
@@ -85,6 +87,7 @@ pub const LIGHT_CHART_COLORS: ChartColors = ChartColors {
 pub const DARK_CHART_COLORS: ChartColors = ChartColors {
 	axis_color: "#AAAAAA",
 	border_color: "#333333",
+	brush_color: "rgba(255, 255, 255, .1)",
 	crosshairs_color: "#AAAAAA",
 	grid_line_color: "#222222",
 	label_color: "#888888",
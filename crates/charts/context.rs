@@ -0,0 +1,135 @@
+use num::ToPrimitive;
+use wasm_bindgen::JsValue;
+use web_sys as dom;
+
+/// A `DrawingContext` is the subset of the canvas 2d drawing api that charts are drawn with. It is implemented for the browser's `CanvasRenderingContext2d`, which draws the live chart, and for [`SvgContext`](crate::svg::SvgContext), which serializes a chart to svg without the dom.
+pub trait DrawingContext {
+	/// Retrieve the width of the drawing surface in css pixels.
+	fn width(&self) -> f64;
+	/// Retrieve the height of the drawing surface in css pixels.
+	fn height(&self) -> f64;
+	fn arc(&self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64);
+	fn arc_to(&self, x1: f64, y1: f64, x2: f64, y2: f64, radius: f64);
+	fn begin_path(&self);
+	fn bezier_curve_to(&self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64);
+	fn clip(&self);
+	fn fill(&self);
+	fn fill_text(&self, text: &str, x: f64, y: f64);
+	fn line_to(&self, x: f64, y: f64);
+	/// Retrieve the width of `text` when drawn in the current font.
+	fn measure_text(&self, text: &str) -> f64;
+	fn move_to(&self, x: f64, y: f64);
+	fn restore(&self);
+	fn rotate(&self, angle: f64);
+	fn save(&self);
+	fn set_fill_style(&self, color: &str);
+	fn set_line_cap(&self, line_cap: &str);
+	fn set_line_dash(&self, segments: &[f64]);
+	fn set_line_width(&self, line_width: f64);
+	fn set_stroke_style(&self, color: &str);
+	fn set_text_align(&self, text_align: &str);
+	fn set_text_baseline(&self, text_baseline: &str);
+	fn stroke(&self);
+	fn translate(&self, x: f64, y: f64);
+}
+
+impl DrawingContext for dom::CanvasRenderingContext2d {
+	fn width(&self) -> f64 {
+		self.canvas().unwrap().client_width().to_f64().unwrap()
+	}
+
+	fn height(&self) -> f64 {
+		self.canvas().unwrap().client_height().to_f64().unwrap()
+	}
+
+	fn arc(&self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) {
+		dom::CanvasRenderingContext2d::arc(self, x, y, radius, start_angle, end_angle).unwrap();
+	}
+
+	fn arc_to(&self, x1: f64, y1: f64, x2: f64, y2: f64, radius: f64) {
+		dom::CanvasRenderingContext2d::arc_to(self, x1, y1, x2, y2, radius).unwrap();
+	}
+
+	fn begin_path(&self) {
+		dom::CanvasRenderingContext2d::begin_path(self);
+	}
+
+	fn bezier_curve_to(&self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) {
+		dom::CanvasRenderingContext2d::bezier_curve_to(self, cp1x, cp1y, cp2x, cp2y, x, y);
+	}
+
+	fn clip(&self) {
+		dom::CanvasRenderingContext2d::clip(self);
+	}
+
+	fn fill(&self) {
+		dom::CanvasRenderingContext2d::fill(self);
+	}
+
+	fn fill_text(&self, text: &str, x: f64, y: f64) {
+		dom::CanvasRenderingContext2d::fill_text(self, text, x, y).unwrap();
+	}
+
+	fn line_to(&self, x: f64, y: f64) {
+		dom::CanvasRenderingContext2d::line_to(self, x, y);
+	}
+
+	fn measure_text(&self, text: &str) -> f64 {
+		dom::CanvasRenderingContext2d::measure_text(self, text)
+			.unwrap()
+			.width()
+	}
+
+	fn move_to(&self, x: f64, y: f64) {
+		dom::CanvasRenderingContext2d::move_to(self, x, y);
+	}
+
+	fn restore(&self) {
+		dom::CanvasRenderingContext2d::restore(self);
+	}
+
+	fn rotate(&self, angle: f64) {
+		dom::CanvasRenderingContext2d::rotate(self, angle).unwrap();
+	}
+
+	fn save(&self) {
+		dom::CanvasRenderingContext2d::save(self);
+	}
+
+	fn set_fill_style(&self, color: &str) {
+		dom::CanvasRenderingContext2d::set_fill_style(self, &color.into());
+	}
+
+	fn set_line_cap(&self, line_cap: &str) {
+		dom::CanvasRenderingContext2d::set_line_cap(self, line_cap);
+	}
+
+	fn set_line_dash(&self, segments: &[f64]) {
+		dom::CanvasRenderingContext2d::set_line_dash(self, &JsValue::from_serde(segments).unwrap())
+			.unwrap();
+	}
+
+	fn set_line_width(&self, line_width: f64) {
+		dom::CanvasRenderingContext2d::set_line_width(self, line_width);
+	}
+
+	fn set_stroke_style(&self, color: &str) {
+		dom::CanvasRenderingContext2d::set_stroke_style(self, &color.into());
+	}
+
+	fn set_text_align(&self, text_align: &str) {
+		dom::CanvasRenderingContext2d::set_text_align(self, text_align);
+	}
+
+	fn set_text_baseline(&self, text_baseline: &str) {
+		dom::CanvasRenderingContext2d::set_text_baseline(self, text_baseline);
+	}
+
+	fn stroke(&self) {
+		dom::CanvasRenderingContext2d::stroke(self);
+	}
+
+	fn translate(&self, x: f64, y: f64) {
+		dom::CanvasRenderingContext2d::translate(self, x, y).unwrap();
+	}
+}
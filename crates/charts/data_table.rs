@@ -0,0 +1,37 @@
+use pinwheel::prelude::*;
+
+/// A chart's data as a table. Every chart renders its data table next to its canvas, hidden visually but available to screen readers, because the canvas itself is just pixels to them. See [`crate::chart::ChartImpl::data_table`].
+#[derive(Clone, Debug, Default)]
+pub struct ChartDataTable {
+	pub caption: Option<String>,
+	pub headers: Vec<String>,
+	pub rows: Vec<Vec<String>>,
+}
+
+impl Component for ChartDataTable {
+	fn into_node(self) -> Node {
+		let caption = self.caption.map(|text| caption().child(text));
+		let header = thead().child(
+			tr().children(
+				self.headers
+					.into_iter()
+					.map(|header| th().attribute("scope", "col").child(header)),
+			),
+		);
+		let body = tbody().children(self.rows.into_iter().map(|row| {
+			let mut cells = row.into_iter();
+			// The first cell of each row labels the row.
+			let row_header = cells
+				.next()
+				.map(|cell| th().attribute("scope", "row").child(cell));
+			tr().child(row_header)
+				.children(cells.map(|cell| td().child(cell)))
+		}));
+		table()
+			.class("chart-data-table")
+			.child(caption)
+			.child(header)
+			.child(body)
+			.into_node()
+	}
+}
@@ -5,11 +5,13 @@ use crate::{
 	},
 	common::{
 		compute_x_axis_grid_line_info, draw_x_axis_grid_lines, draw_x_axis_labels,
-		draw_x_axis_title, draw_y_axis_title, ComputeXAxisGridLineInfoOptions,
+		draw_x_axis_title, draw_y_axis_title, AxisScale, ComputeXAxisGridLineInfoOptions,
 		DrawXAxisGridLinesOptions, DrawXAxisLabelsOptions, DrawXAxisTitleOptions,
 		DrawYAxisTitleOptions, Point, Rect,
 	},
 	config::{ChartColors, ChartConfig},
+	context::DrawingContext,
+	data_table::ChartDataTable,
 	tooltip::{draw_tooltip, DrawTooltipOptions, TooltipLabel},
 };
 use modelfox_number_formatter::NumberFormatter;
@@ -34,6 +36,53 @@ impl ChartImpl for FeatureContributionsChart {
 	) {
 		draw_feature_contributions_chart_overlay(options);
 	}
+
+	fn data_table(options: &Self::Options) -> ChartDataTable {
+		// Each series starts at its baseline, adds the contribution of each feature, and ends at its output.
+		let number_formatter = &options.number_formatter;
+		let mut rows = Vec::new();
+		for series in options.series.iter() {
+			rows.push(vec![
+				series.title.clone(),
+				"Baseline".to_owned(),
+				series.baseline_label.clone(),
+			]);
+			for value in series.values.iter() {
+				rows.push(vec![
+					series.title.clone(),
+					value.feature.clone(),
+					number_formatter.format(value.value),
+				]);
+			}
+			// The smallest contributions are combined when the chart is compressed.
+			for compressed_values in series
+				.compressed_positive_values
+				.iter()
+				.chain(series.compressed_negative_values.iter())
+				.filter(|compressed_values| compressed_values.count > 0)
+			{
+				rows.push(vec![
+					series.title.clone(),
+					format!("{} other features", compressed_values.count),
+					number_formatter.format(compressed_values.sum),
+				]);
+			}
+			rows.push(vec![
+				series.title.clone(),
+				"Output".to_owned(),
+				series.output_label.clone(),
+			]);
+		}
+		ChartDataTable {
+			caption: None,
+			headers: vec![
+				"Series".to_owned(),
+				"Feature".to_owned(),
+				"Contribution".to_owned(),
+			],
+			rows,
+		}
+	}
 }
 
 /// These are the options for displaying a feature contributions chart.
@@ -46,6 +95,8 @@ pub struct FeatureContributionsChartOptions {
 	pub number_formatter: NumberFormatter,
 	pub positive_color: Option<String>,
 	pub series: Vec<FeatureContributionsChartSeries>,
+	/// Contributions can be negative, which a log scale cannot show, so a log scale is drawn as a symlog scale.
+	pub x_axis_scale: Option<AxisScale>,
 }
 
 /// This is the configuration used across all feature contributions charts.
@@ -122,9 +173,8 @@ fn draw_feature_contributions_chart(
 		.as_deref()
 		.unwrap_or(chart_config.feature_contributions_default_positive_color);
 
-	let canvas = ctx.canvas().unwrap();
-	let height = canvas.client_height().to_f64().unwrap();
-	let width = canvas.client_width().to_f64().unwrap();
+	let height = ctx.height();
+	let width = ctx.width();
 	let ChartConfig {
 		bottom_padding,
 		font_size,
@@ -141,7 +191,7 @@ fn draw_feature_contributions_chart(
 	let y_axis_labels_width = options
 		.series
 		.iter()
-		.map(|series| ctx.measure_text(&series.title).unwrap().width())
+		.map(|series| ctx.measure_text(&series.title))
 		.max_by(|a, b| a.partial_cmp(b).unwrap())
 		.unwrap();
 
@@ -158,15 +208,16 @@ fn draw_feature_contributions_chart(
 				0.0
 			} + annotations_padding);
 
-	let chart_height = height
-		- (top_padding
-			+ font_size + label_padding
-			+ font_size + label_padding
-			+ if include_x_axis_title {
-				label_padding + font_size
-			} else {
-				0.0
-			} + bottom_padding);
+	let chart_height =
+		height
+			- (top_padding
+				+ font_size + label_padding
+				+ font_size + label_padding
+				+ if include_x_axis_title {
+					label_padding + font_size
+				} else {
+					0.0
+				} + bottom_padding);
 
 	// Compress the series given the client chart width.
 	let mut series = options.series.clone();
@@ -178,8 +229,14 @@ fn draw_feature_contributions_chart(
 		},
 	);
 
-	// Compute the bounds.
+	// Compute the bounds in the space transformed by the x axis scale.
+	let x_axis_scale = match options.x_axis_scale.unwrap_or_default() {
+		AxisScale::Log => AxisScale::Symlog,
+		x_axis_scale => x_axis_scale,
+	};
 	let (x_min, x_max) = compute_x_min_x_max(series.as_slice());
+	let (x_min, x_max) =
+		x_axis_scale.round_bounds(x_axis_scale.transform(x_min), x_axis_scale.transform(x_max));
 
 	let chart_rect = Rect {
 		h: chart_height,
@@ -262,7 +319,8 @@ fn draw_feature_contributions_chart(
 			0.0
 		} + annotations_padding,
 		y: top_padding
-			+ font_size + label_padding
+			+ font_size
+			+ label_padding
 			+ if include_x_axis_title {
 				label_padding + font_size
 			} else {
@@ -325,6 +383,7 @@ fn draw_feature_contributions_chart(
 		ctx,
 		number_formatter: &options.number_formatter,
 		x_axis_grid_line_interval: None,
+		x_axis_scale,
 		x_max,
 		x_min,
 	});
@@ -380,14 +439,16 @@ fn draw_feature_contributions_chart(
 			+ (i - 1).to_f64().unwrap() * chart_config.feature_contributions_series_gap
 			+ chart_config.feature_contributions_series_gap / 2.0;
 		ctx.save();
-		ctx.set_stroke_style(&chart_colors.grid_line_color.into());
+		ctx.set_stroke_style(chart_colors.grid_line_color);
 		ctx.move_to(chart_rect.x, y);
 		ctx.line_to(chart_rect.x + chart_rect.w, y);
 		ctx.stroke();
 		ctx.restore();
 	}
 
-	let value_width_multiplier = chart_rect.w / (x_max - x_min);
+	let value_to_pixels = |value: f64| {
+		chart_rect.x + (x_axis_scale.transform(value) - x_min) / (x_max - x_min) * chart_rect.w
+	};
 	for (series_index, series) in series.iter().enumerate() {
 		let mut sum_positives = series
 			.values
@@ -400,14 +461,13 @@ fn draw_feature_contributions_chart(
 		};
 		let min = series.baseline.min(series.output);
 		let max = series.baseline + sum_positives;
-		let width = max - min;
 		let box_height = (chart_config.feature_contributions_series_height
 			- chart_config.feature_contributions_bar_gap)
 			/ 2.0;
 		let rect = Rect {
 			h: chart_config.feature_contributions_series_height,
-			w: width * value_width_multiplier,
-			x: chart_rect.x + (min - x_min) * value_width_multiplier,
+			w: value_to_pixels(max) - value_to_pixels(min),
+			x: value_to_pixels(min),
 			y: chart_rect.y
 				+ (chart_config.feature_contributions_series_gap
 					+ chart_config.feature_contributions_series_height)
@@ -421,7 +481,7 @@ fn draw_feature_contributions_chart(
 			negative_color,
 			positive_color,
 			series,
-			value_width_multiplier,
+			value_to_pixels: &value_to_pixels,
 		});
 		hover_regions.extend(output.hover_regions);
 	}
@@ -437,11 +497,11 @@ struct DrawFeatureContributionSeriesOptions<'a> {
 	chart_config: &'a ChartConfig,
 	rect: Rect,
 	box_height: f64,
-	ctx: &'a dom::CanvasRenderingContext2d,
+	ctx: &'a dyn DrawingContext,
 	negative_color: &'a str,
 	positive_color: &'a str,
 	series: &'a FeatureContributionsChartSeries,
-	value_width_multiplier: f64,
+	value_to_pixels: &'a dyn Fn(f64) -> f64,
 }
 
 struct DrawFeatureContributionsSeriesOutput {
@@ -460,10 +520,9 @@ fn draw_feature_contribution_series(
 		negative_color,
 		positive_color,
 		series,
-		value_width_multiplier,
+		value_to_pixels,
 		chart_config,
 	} = options;
-	let min = series.baseline.min(series.output);
 
 	// Draw the positive boxes which start at the baseline and go to the max, ending with the remaining features rect.
 	let mut positive_values: Vec<FeatureContributionsChartValue> = series
@@ -473,7 +532,9 @@ fn draw_feature_contribution_series(
 		.filter(|value| value.value > 0.0)
 		.collect();
 	positive_values.sort_unstable_by(|a, b| a.value.partial_cmp(&b.value).unwrap().reverse());
-	let mut x = rect.x + (series.baseline - min) * value_width_multiplier;
+	// Each box ends at the running sum of the contributions drawn so far.
+	let mut value = series.baseline;
+	let mut x = value_to_pixels(value);
 	// Draw the baseline value and label.
 	ctx.set_text_baseline("bottom");
 	ctx.set_text_align("right");
@@ -481,19 +542,18 @@ fn draw_feature_contribution_series(
 		"baseline",
 		x - chart_config.label_padding,
 		rect.y + box_height / 2.0,
-	)
-	.unwrap();
+	);
 	ctx.set_text_baseline("top");
 	ctx.set_text_align("right");
 	ctx.fill_text(
 		&series.baseline_label,
 		x - chart_config.label_padding,
 		rect.y + box_height / 2.0,
-	)
-	.unwrap();
+	);
 	for positive_value in positive_values {
 		let feature_contribution_value = positive_value;
-		let width = feature_contribution_value.value * value_width_multiplier;
+		value += feature_contribution_value.value;
+		let width = value_to_pixels(value) - x;
 		let value_rect = Rect {
 			h: box_height,
 			w: width,
@@ -525,7 +585,8 @@ fn draw_feature_contribution_series(
 	if let Some(too_small_positive_values) = series.compressed_positive_values.as_ref() {
 		let n_remaining_features = too_small_positive_values.count;
 		let feature_contribution_value = too_small_positive_values;
-		let width = feature_contribution_value.sum * value_width_multiplier;
+		value += feature_contribution_value.sum;
+		let width = value_to_pixels(value) - x;
 		let remaining_features_rect = Rect {
 			h: box_height,
 			w: width,
@@ -567,7 +628,8 @@ fn draw_feature_contribution_series(
 	if let Some(too_small_negative_values) = series.compressed_negative_values.as_ref() {
 		let n_remaining_features = too_small_negative_values.count;
 		let feature_contribution_value = too_small_negative_values;
-		let width = feature_contribution_value.sum * value_width_multiplier;
+		value += feature_contribution_value.sum;
+		let width = value_to_pixels(value) - x;
 		let remaining_features_rect = Rect {
 			h: box_height,
 			w: width,
@@ -598,7 +660,8 @@ fn draw_feature_contribution_series(
 	}
 	for negative_value in negative_values {
 		let feature_contribution_value = negative_value;
-		let width = feature_contribution_value.value * value_width_multiplier;
+		value += feature_contribution_value.value;
+		let width = value_to_pixels(value) - x;
 		let value_rect = Rect {
 			h: box_height,
 			w: width,
@@ -633,15 +696,13 @@ fn draw_feature_contribution_series(
 		"output",
 		x - chart_config.label_padding,
 		rect.y + box_height + chart_config.feature_contributions_bar_gap + box_height / 2.0,
-	)
-	.unwrap();
+	);
 	ctx.set_text_baseline("top");
 	ctx.fill_text(
 		&series.output_label,
 		x - chart_config.label_padding,
 		rect.y + box_height + chart_config.feature_contributions_bar_gap + box_height / 2.0,
-	)
-	.unwrap();
+	);
 
 	DrawFeatureContributionsSeriesOutput { hover_regions }
 }
@@ -650,7 +711,7 @@ struct DrawFeatureContributionsChartYAxisLabelsOptions<'a> {
 	chart_config: &'a ChartConfig,
 	rect: Rect,
 	categories: &'a [&'a String],
-	ctx: &'a dom::CanvasRenderingContext2d,
+	ctx: &'a dyn DrawingContext,
 }
 
 fn draw_feature_contributions_chart_y_axis_labels(
@@ -669,8 +730,7 @@ fn draw_feature_contributions_chart_y_axis_labels(
 				+ chart_config.feature_contributions_series_height)
 				* i.to_f64().unwrap();
 		ctx.set_text_baseline("middle");
-		ctx.fill_text(label, rect.x + rect.w, rect.y + label_offset)
-			.unwrap();
+		ctx.fill_text(label, rect.x + rect.w, rect.y + label_offset);
 	}
 }
 
@@ -761,7 +821,8 @@ fn feature_contributions_chart_hover_region(
 		hit_test: Box::new(move |x, y| {
 			x > rect.x.min(rect.x + rect.w)
 				&& x < rect.x.max(rect.x + rect.w)
-				&& y > rect.y && y < rect.y + rect.h
+				&& y > rect.y
+				&& y < rect.y + rect.h
 		}),
 		info: FeatureContributionsChartHoverRegionInfo {
 			rect,
@@ -777,7 +838,7 @@ struct DrawFeatureContributionBoxOptions<'a> {
 	chart_config: &'a ChartConfig,
 	rect: Rect,
 	color: String,
-	ctx: &'a dom::CanvasRenderingContext2d,
+	ctx: &'a dyn DrawingContext,
 	direction: FeatureContributionsBoxDirection,
 	label: String,
 }
@@ -807,8 +868,8 @@ fn draw_feature_contribution_box(options: DrawFeatureContributionBoxOptions) {
 	let width = rect.w;
 
 	ctx.save();
-	ctx.set_stroke_style(&color.clone().into());
-	ctx.set_fill_style(&color.into());
+	ctx.set_stroke_style(&color);
+	ctx.set_fill_style(&color);
 	ctx.set_line_width(1.0);
 	ctx.set_line_cap("butt");
 
@@ -836,10 +897,10 @@ fn draw_feature_contribution_box(options: DrawFeatureContributionBoxOptions) {
 
 	ctx.fill();
 
-	let label_width = ctx.measure_text(&label).unwrap().width();
+	let label_width = ctx.measure_text(&label);
 	ctx.set_text_baseline("middle");
 	ctx.set_text_align("center");
-	ctx.set_fill_style(&"#fff".into());
+	ctx.set_fill_style("#fff");
 
 	let max_label_width =
 		rect.w.abs() - text_padding - chart_config.feature_contributions_arrow_depth * 2.0;
@@ -848,8 +909,7 @@ fn draw_feature_contribution_box(options: DrawFeatureContributionBoxOptions) {
 			&label,
 			rect.x + (rect.w + arrow_depth) / 2.0,
 			rect.y + rect.h / 2.0,
-		)
-		.unwrap();
+		);
 	}
 
 	ctx.restore();
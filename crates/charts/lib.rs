@@ -7,6 +7,9 @@ pub mod chart;
 pub mod common;
 pub mod components;
 pub mod config;
+pub mod context;
+pub mod data_table;
 pub mod feature_contributions_chart;
 pub mod line_chart;
+pub mod svg;
 mod tooltip;
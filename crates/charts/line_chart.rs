@@ -1,30 +1,35 @@
 use crate::{
 	chart::{
-		ActiveHoverRegion, ChartImpl, DrawChartOptions, DrawChartOutput, DrawOverlayOptions,
-		HoverRegion,
+		ActiveHoverRegion, BrushInfo, ChartImpl, DrawChartOptions, DrawChartOutput,
+		DrawOverlayOptions, HoverRegion,
 	},
 	common::{
 		compute_rects, compute_x_axis_grid_line_info, draw_x_axis, draw_x_axis_grid_lines,
 		draw_x_axis_labels, draw_x_axis_title, draw_y_axis, draw_y_axis_grid_lines,
-		draw_y_axis_labels, draw_y_axis_title, ComputeRectsOptions, ComputeRectsOutput,
+		draw_y_axis_labels, draw_y_axis_title, AxisScale, ComputeRectsOptions, ComputeRectsOutput,
 		ComputeXAxisGridLineInfoOptions, DrawXAxisGridLinesOptions, DrawXAxisLabelsOptions,
 		DrawXAxisOptions, DrawXAxisTitleOptions, DrawYAxisGridLinesOptions, DrawYAxisLabelsOptions,
 		DrawYAxisOptions, DrawYAxisTitleOptions, GridLineInterval, Point, Rect,
 	},
 	config::ChartConfig,
+	context::DrawingContext,
+	data_table::ChartDataTable,
 	tooltip::{draw_tooltip, DrawTooltipOptions, TooltipLabel},
 };
 use itertools::Itertools;
 use modelfox_finite::Finite;
 use modelfox_number_formatter::NumberFormatter;
 use num::ToPrimitive;
-use wasm_bindgen::JsValue;
-use web_sys as dom;
 
 pub struct LineChart;
 
 #[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct LineChartOptions {
+	/// These are drawn beneath the series.
+	#[serde(default)]
+	pub bands: Vec<LineChartBand>,
+	/// If this is true, dragging across the chart selects a range of x values and dispatches a [`BRUSH_EVENT`](crate::chart::BRUSH_EVENT).
+	pub brushable: Option<bool>,
 	pub hide_legend: Option<bool>,
 	pub labels: Option<Vec<String>>,
 	pub number_formatter: NumberFormatter,
@@ -37,6 +42,8 @@ pub struct LineChartOptions {
 	pub x_max: Option<Finite<f64>>,
 	pub x_min: Option<Finite<f64>>,
 	pub y_axis_grid_line_interval: Option<GridLineInterval>,
+	/// On a log scale, points and bounds that are not positive are left out.
+	pub y_axis_scale: Option<AxisScale>,
 	pub y_axis_title: Option<String>,
 	pub y_max: Option<Finite<f64>>,
 	pub y_min: Option<Finite<f64>>,
@@ -57,6 +64,21 @@ pub struct LineChartPoint {
 	pub y: Option<Finite<f64>>,
 }
 
+/// A band is a region shaded between a lower and an upper value at each point, for example the confidence interval of a series.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct LineChartBand {
+	pub color: String,
+	pub data: Vec<LineChartBandPoint>,
+}
+
+/// The band is not drawn at points where `lower` or `upper` is absent.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct LineChartBandPoint {
+	pub x: Finite<f64>,
+	pub lower: Option<Finite<f64>>,
+	pub upper: Option<Finite<f64>>,
+}
+
 #[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
 pub enum LineStyle {
 	#[serde(rename = "hidden")]
@@ -76,6 +98,7 @@ pub enum PointStyle {
 }
 
 pub struct LineChartOverlayInfo {
+	brushable: bool,
 	chart_rect: Rect,
 	n_series: usize,
 	x_max: f64,
@@ -111,6 +134,78 @@ impl ChartImpl for LineChart {
 	) {
 		draw_line_chart_overlay(options);
 	}
+
+	fn brush_info(overlay_info: &Self::OverlayInfo) -> Option<BrushInfo> {
+		if overlay_info.brushable {
+			Some(BrushInfo {
+				chart_rect: overlay_info.chart_rect,
+				x_max: overlay_info.x_max,
+				x_min: overlay_info.x_min,
+			})
+		} else {
+			None
+		}
+	}
+
+	fn data_table(options: &Self::Options) -> ChartDataTable {
+		// Each row is an x value, with a column for the value of each series at that x value.
+		let headers = std::iter::once(
+			options
+				.x_axis_title
+				.clone()
+				.unwrap_or_else(|| "X".to_owned()),
+		)
+		.chain(
+			options
+				.series
+				.iter()
+				.enumerate()
+				.map(|(series_index, series)| {
+					series
+						.title
+						.clone()
+						.unwrap_or_else(|| format!("Series {}", series_index + 1))
+				}),
+		)
+		.collect();
+		let xs: Vec<Finite<f64>> = options
+			.series
+			.iter()
+			.flat_map(|series| series.data.iter().map(|point| point.x))
+			.sorted()
+			.dedup()
+			.collect();
+		let rows = xs
+			.iter()
+			.enumerate()
+			.map(|(x_index, x)| {
+				// If the chart has labels, they label the points by index, as on the x axis.
+				let label = options
+					.labels
+					.as_ref()
+					.and_then(|labels| labels.get(x_index).cloned())
+					.unwrap_or_else(|| options.number_formatter.format(x.get()));
+				std::iter::once(label)
+					.chain(options.series.iter().map(|series| {
+						let y = series
+							.data
+							.iter()
+							.find(|point| point.x == *x)
+							.and_then(|point| point.y);
+						options.number_formatter.format_option(y.map(Finite::get))
+					}))
+					.collect()
+			})
+			.collect();
+		ChartDataTable {
+			caption: options
+				.title
+				.clone()
+				.or_else(|| options.y_axis_title.clone()),
+			headers,
+			rows,
+		}
+	}
 }
 
 #[allow(clippy::too_many_lines)]
@@ -126,26 +221,64 @@ fn draw_line_chart(
 	} = options;
 	let LineChartOptions {
 		labels,
-		series,
 		x_axis_grid_line_interval,
 		x_axis_title,
 		y_axis_grid_line_interval,
 		y_axis_title,
 		..
 	} = &options;
-	let canvas = ctx.canvas().unwrap();
-	let width = canvas.client_width().to_f64().unwrap();
-	let height = canvas.client_height().to_f64().unwrap();
+	let width = ctx.width();
+	let height = ctx.height();
 	let mut hover_regions: Vec<HoverRegion<LineChartHoverRegionInfo>> = Vec::new();
 
+	// Transform the y values by the y axis scale, so the rest of the chart is laid out in the transformed space.
+	let y_axis_scale = options.y_axis_scale.unwrap_or_default();
+	let transform_y =
+		|y: Option<Finite<f64>>| y.and_then(|y| Finite::new(y_axis_scale.transform(y.get())).ok());
+	let series = &options
+		.series
+		.iter()
+		.map(|series| LineChartSeries {
+			data: series
+				.data
+				.iter()
+				.map(|point| LineChartPoint {
+					x: point.x,
+					y: transform_y(point.y),
+				})
+				.collect(),
+			..series.clone()
+		})
+		.collect::<Vec<_>>();
+	let bands = &options
+		.bands
+		.iter()
+		.map(|band| LineChartBand {
+			data: band
+				.data
+				.iter()
+				.map(|point| LineChartBandPoint {
+					x: point.x,
+					lower: transform_y(point.lower),
+					upper: transform_y(point.upper),
+				})
+				.collect(),
+			..band.clone()
+		})
+		.collect::<Vec<_>>();
+
 	// Compute the bounds.
 	let x_min: f64 = options
 		.x_min
 		.unwrap_or_else(|| {
-			options
-				.series
+			series
 				.iter()
 				.flat_map(|series| series.data.iter().map(|point| point.x))
+				.chain(
+					bands
+						.iter()
+						.flat_map(|band| band.data.iter().map(|point| point.x)),
+				)
 				.min_by(|a, b| a.partial_cmp(b).unwrap())
 				.unwrap()
 		})
@@ -153,38 +286,49 @@ fn draw_line_chart(
 	let x_max: f64 = options
 		.x_max
 		.unwrap_or_else(|| {
-			options
-				.series
+			series
 				.iter()
 				.flat_map(|series| series.data.iter().map(|point| point.x))
+				.chain(
+					bands
+						.iter()
+						.flat_map(|band| band.data.iter().map(|point| point.x)),
+				)
 				.max_by(|a, b| a.partial_cmp(b).unwrap())
 				.unwrap()
 		})
 		.get();
-	let y_min: f64 = options
-		.y_min
+	let y_min: f64 = transform_y(options.y_min)
 		.unwrap_or_else(|| {
-			options
-				.series
+			series
 				.iter()
 				.flat_map(|series| series.data.iter().map(|point| point.y))
+				.chain(bands.iter().flat_map(|band| {
+					band.data
+						.iter()
+						.flat_map(|point| [point.lower, point.upper])
+				}))
 				.flatten()
 				.min_by(|a, b| a.partial_cmp(b).unwrap())
 				.unwrap()
 		})
 		.get();
-	let y_max: f64 = options
-		.y_max
+	let y_max: f64 = transform_y(options.y_max)
 		.unwrap_or_else(|| {
-			options
-				.series
+			series
 				.iter()
 				.flat_map(|series| series.data.iter().map(|point| point.y))
+				.chain(bands.iter().flat_map(|band| {
+					band.data
+						.iter()
+						.flat_map(|point| [point.lower, point.upper])
+				}))
 				.flatten()
 				.max_by(|a, b| a.partial_cmp(b).unwrap())
 				.unwrap()
 		})
 		.get();
+	let (y_min, y_max) = y_axis_scale.round_bounds(y_min, y_max);
 	let (y_min, y_max) = if (y_max - y_min).abs() < f64::EPSILON {
 		(y_min, y_min + 1.0)
 	} else {
@@ -216,6 +360,7 @@ fn draw_line_chart(
 		width,
 		x_axis_grid_line_interval: x_axis_grid_line_interval.as_ref(),
 		y_axis_grid_line_interval: y_axis_grid_line_interval.as_ref(),
+		y_axis_scale,
 		y_max,
 		y_min,
 	});
@@ -226,6 +371,7 @@ fn draw_line_chart(
 		ctx,
 		number_formatter: &options.number_formatter,
 		x_axis_grid_line_interval: x_axis_grid_line_interval.clone(),
+		x_axis_scale: AxisScale::Linear,
 		x_max,
 		x_min,
 	});
@@ -308,6 +454,19 @@ fn draw_line_chart(
 		});
 	}
 
+	// Draw the bands.
+	for band in bands.iter() {
+		draw_band(DrawBandOptions {
+			band,
+			chart_rect,
+			ctx,
+			x_max,
+			x_min,
+			y_max,
+			y_min,
+		});
+	}
+
 	// Draw the lines.
 	for series in series.iter() {
 		draw_line(DrawLineOptions {
@@ -393,7 +552,11 @@ fn draw_line_chart(
 					color: series.color.clone(),
 					point,
 					point_label,
-					point_value: point.y,
+					// The tooltip shows the value before it was transformed.
+					point_value: options.series[series_index].data[point_index]
+						.y
+						.unwrap()
+						.get(),
 					series_index,
 					series_title: if has_multiple_series {
 						series.title.clone()
@@ -408,6 +571,7 @@ fn draw_line_chart(
 	}
 
 	let overlay_info = LineChartOverlayInfo {
+		brushable: options.brushable.unwrap_or(false),
 		chart_rect,
 		n_series: options.series.len(),
 		x_max,
@@ -426,7 +590,7 @@ fn draw_line_chart(
 struct DrawPointOptions<'a> {
 	chart_rect: Rect,
 	color: &'a str,
-	ctx: &'a dom::CanvasRenderingContext2d,
+	ctx: &'a dyn DrawingContext,
 	point: Point,
 	point_style: PointStyle,
 	radius: f64,
@@ -461,23 +625,86 @@ fn draw_point(options: DrawPointOptions) {
 		y_min,
 	});
 	ctx.begin_path();
-	ctx.set_fill_style(&color.into());
+	ctx.set_fill_style(&color);
 	ctx.arc(
 		point_pixels.x,
 		point_pixels.y,
 		radius,
 		0.0,
 		2.0 * std::f64::consts::PI,
-	)
-	.unwrap();
+	);
 	ctx.fill();
 }
 
+#[derive(Clone, Copy)]
+struct DrawBandOptions<'a> {
+	band: &'a LineChartBand,
+	chart_rect: Rect,
+	ctx: &'a dyn DrawingContext,
+	x_max: f64,
+	x_min: f64,
+	y_max: f64,
+	y_min: f64,
+}
+
+fn draw_band(options: DrawBandOptions) {
+	let DrawBandOptions {
+		band,
+		chart_rect,
+		ctx,
+		x_max,
+		x_min,
+		y_max,
+		y_min,
+	} = options;
+	let to_pixels = |x: f64, y: f64| {
+		point_to_pixels(PointToPixelsOptions {
+			chart_rect,
+			point: Point { x, y },
+			x_max,
+			x_min,
+			y_max,
+			y_min,
+		})
+	};
+	// Shade each run of consecutive points that have both bounds by tracing along the upper bounds and back along the lower bounds.
+	let points = band
+		.data
+		.iter()
+		.map(|point| match (point.lower, point.upper) {
+			(Some(lower), Some(upper)) => Some((point.x.get(), lower.get(), upper.get())),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	ctx.save();
+	ctx.set_fill_style(&band.color);
+	for run in points.split(Option::is_none) {
+		let run = run.iter().flatten().collect::<Vec<_>>();
+		if run.len() < 2 {
+			continue;
+		}
+		ctx.begin_path();
+		let (x, _, upper) = run[0];
+		let first_point_pixels = to_pixels(*x, *upper);
+		ctx.move_to(first_point_pixels.x, first_point_pixels.y);
+		for (x, _, upper) in run.iter().skip(1) {
+			let point_pixels = to_pixels(*x, *upper);
+			ctx.line_to(point_pixels.x, point_pixels.y);
+		}
+		for (x, lower, _) in run.iter().rev() {
+			let point_pixels = to_pixels(*x, *lower);
+			ctx.line_to(point_pixels.x, point_pixels.y);
+		}
+		ctx.fill();
+	}
+	ctx.restore();
+}
+
 #[derive(Clone, Copy)]
 struct DrawLineOptions<'a> {
 	chart_rect: Rect,
 	chart_config: &'a ChartConfig,
-	ctx: &'a dom::CanvasRenderingContext2d,
+	ctx: &'a dyn DrawingContext,
 	series: &'a LineChartSeries,
 	x_max: f64,
 	x_min: f64,
@@ -502,10 +729,9 @@ fn draw_line(options: DrawLineOptions) {
 	}
 	ctx.save();
 	ctx.begin_path();
-	ctx.set_stroke_style(&series.color.as_str().into());
+	ctx.set_stroke_style(&series.color);
 	if let Some(LineStyle::Dashed) = &series.line_style {
-		ctx.set_line_dash(&JsValue::from_serde(&[4, 4]).unwrap())
-			.unwrap();
+		ctx.set_line_dash(&[4.0, 4.0]);
 	}
 	if series.data.len() < 2 {
 		return;
@@ -666,6 +892,7 @@ fn draw_line_chart_overlay(
 		x_min,
 		y_max,
 		y_min,
+		..
 	} = &overlay_info;
 	let mut closest_active_hover_region_for_series: Vec<
 		Option<ActiveHoverRegion<LineChartHoverRegionInfo>>,
@@ -775,7 +1002,7 @@ fn draw_line_chart_overlay(
 struct DrawCrosshairsOptions<'a> {
 	chart_rect: Rect,
 	crosshairs_color: String,
-	ctx: &'a dom::CanvasRenderingContext2d,
+	ctx: &'a dyn DrawingContext,
 	origin: Point,
 }
 
@@ -788,9 +1015,8 @@ fn draw_crosshairs(options: DrawCrosshairsOptions) {
 	} = options;
 	ctx.save();
 	ctx.begin_path();
-	ctx.set_line_dash(&JsValue::from_serde(&[4, 4]).unwrap())
-		.unwrap();
-	ctx.set_stroke_style(&crosshairs_color.into());
+	ctx.set_line_dash(&[4.0, 4.0]);
+	ctx.set_stroke_style(&crosshairs_color);
 	ctx.move_to(origin.x, chart_rect.y);
 	ctx.line_to(origin.x, chart_rect.y + chart_rect.h);
 	ctx.move_to(chart_rect.x, origin.y);
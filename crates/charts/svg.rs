@@ -0,0 +1,409 @@
+use crate::{
+	chart::{ChartImpl, DrawChartOptions},
+	config::{ChartConfig, LIGHT_CHART_COLORS},
+	context::DrawingContext,
+};
+use num::ToPrimitive;
+use std::{cell::RefCell, f64::consts::PI, fmt::Write};
+
+/// Draw a chart to a standalone svg document. This does not use the dom, so a chart can be exported independently of the `Chart` that displays it. Exported charts always use the light color scheme, so they read well when pasted into documents and slides.
+pub fn chart_to_svg<T>(options: &T::Options, width: f64, height: f64) -> String
+where
+	T: ChartImpl,
+{
+	let chart_config = ChartConfig::default();
+	let ctx = SvgContext::new(width, height, chart_config.font);
+	T::draw_chart(&DrawChartOptions {
+		chart_colors: &LIGHT_CHART_COLORS,
+		chart_config: &chart_config,
+		ctx: &ctx,
+		options,
+	});
+	ctx.into_svg()
+}
+
+/// An `SvgContext` is a [`DrawingContext`] that records what is drawn to it as svg elements.
+pub struct SvgContext {
+	font: String,
+	font_size: f64,
+	height: f64,
+	state: RefCell<SvgContextState>,
+	width: f64,
+}
+
+struct SvgContextState {
+	body: String,
+	current_point: Option<(f64, f64)>,
+	defs: String,
+	n_clip_paths: usize,
+	path: String,
+	saved_styles: Vec<SvgStyle>,
+	style: SvgStyle,
+}
+
+#[derive(Clone)]
+struct SvgStyle {
+	clip_path: Option<usize>,
+	fill_style: String,
+	line_cap: String,
+	line_dash: Vec<f64>,
+	line_width: f64,
+	stroke_style: String,
+	text_align: String,
+	text_baseline: String,
+	transform: String,
+}
+
+impl Default for SvgStyle {
+	fn default() -> SvgStyle {
+		SvgStyle {
+			clip_path: None,
+			fill_style: "#000".to_owned(),
+			line_cap: "butt".to_owned(),
+			line_dash: Vec::new(),
+			line_width: 1.0,
+			stroke_style: "#000".to_owned(),
+			text_align: "start".to_owned(),
+			text_baseline: "alphabetic".to_owned(),
+			transform: String::new(),
+		}
+	}
+}
+
+impl SvgContext {
+	/// Create an `SvgContext` for a chart of the given size in css pixels. `font` is a css font shorthand with the size in pixels, such as `"14px JetBrains Mono"`.
+	#[must_use]
+	pub fn new(width: f64, height: f64, font: &str) -> SvgContext {
+		let font_size = font
+			.split_whitespace()
+			.find_map(|part| part.strip_suffix("px"))
+			.and_then(|font_size| font_size.parse().ok())
+			.unwrap_or(14.0);
+		SvgContext {
+			font: font.to_owned(),
+			font_size,
+			height,
+			state: RefCell::new(SvgContextState {
+				body: String::new(),
+				current_point: None,
+				defs: String::new(),
+				n_clip_paths: 0,
+				path: String::new(),
+				saved_styles: Vec::new(),
+				style: SvgStyle::default(),
+			}),
+			width,
+		}
+	}
+
+	/// Finish drawing and retrieve the svg document.
+	#[must_use]
+	pub fn into_svg(self) -> String {
+		let state = self.state.into_inner();
+		format!(
+			r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" style="font: {font}"><defs>{defs}</defs>{body}</svg>"#,
+			width = format_number(self.width),
+			height = format_number(self.height),
+			font = escape(&self.font),
+			defs = state.defs,
+			body = state.body,
+		)
+	}
+
+	fn push_path_command(&self, command: &str, point: (f64, f64)) {
+		let mut state = self.state.borrow_mut();
+		write!(
+			state.path,
+			"{}{} {} ",
+			command,
+			format_number(point.0),
+			format_number(point.1)
+		)
+		.unwrap();
+		state.current_point = Some(point);
+	}
+}
+
+impl DrawingContext for SvgContext {
+	fn width(&self) -> f64 {
+		self.width
+	}
+
+	fn height(&self) -> f64 {
+		self.height
+	}
+
+	fn arc(&self, x: f64, y: f64, radius: f64, start_angle: f64, end_angle: f64) {
+		let point_at = |angle: f64| (x + radius * angle.cos(), y + radius * angle.sin());
+		let start = point_at(start_angle);
+		let command = if self.state.borrow().current_point.is_some() {
+			"L"
+		} else {
+			"M"
+		};
+		self.push_path_command(command, start);
+		let r = format_number(radius);
+		let sweep_angle = end_angle - start_angle;
+		if sweep_angle >= 2.0 * PI {
+			// An svg arc cannot start and end at the same point, so a full circle is drawn as two halves.
+			let middle = point_at(start_angle + PI);
+			self.push_path_command(&format!("A{r} {r} 0 0 1 ", r = r), middle);
+			self.push_path_command(&format!("A{r} {r} 0 0 1 ", r = r), start);
+		} else {
+			let sweep_angle = sweep_angle.rem_euclid(2.0 * PI);
+			let large_arc = u8::from(sweep_angle > PI);
+			self.push_path_command(
+				&format!("A{r} {r} 0 {large_arc} 1 ", r = r, large_arc = large_arc),
+				point_at(end_angle),
+			);
+		}
+	}
+
+	fn arc_to(&self, x1: f64, y1: f64, x2: f64, y2: f64, radius: f64) {
+		let (x0, y0) = match self.state.borrow().current_point {
+			Some(point) => point,
+			None => (x1, y1),
+		};
+		let (d1x, d1y) = (x0 - x1, y0 - y1);
+		let (d2x, d2y) = (x2 - x1, y2 - y1);
+		let d1_length = d1x.hypot(d1y);
+		let d2_length = d2x.hypot(d2y);
+		let cross = d1x * d2y - d1y * d2x;
+		// If the points are collinear or the radius is zero, the arc degenerates to a line to the corner.
+		if radius == 0.0 || d1_length == 0.0 || d2_length == 0.0 || cross.abs() < f64::EPSILON {
+			self.line_to(x1, y1);
+			return;
+		}
+		let angle = ((d1x * d2x + d1y * d2y) / (d1_length * d2_length)).acos();
+		let tangent_distance = radius / (angle / 2.0).tan();
+		let start = (
+			x1 + d1x / d1_length * tangent_distance,
+			y1 + d1y / d1_length * tangent_distance,
+		);
+		let end = (
+			x1 + d2x / d2_length * tangent_distance,
+			y1 + d2y / d2_length * tangent_distance,
+		);
+		self.line_to(start.0, start.1);
+		// The path turns clockwise at the corner when the cross product of the incoming and outgoing directions is positive.
+		let sweep = u8::from(cross < 0.0);
+		let r = format_number(radius);
+		self.push_path_command(&format!("A{r} {r} 0 0 {sweep} ", r = r, sweep = sweep), end);
+	}
+
+	fn begin_path(&self) {
+		let mut state = self.state.borrow_mut();
+		state.path.clear();
+		state.current_point = None;
+	}
+
+	fn bezier_curve_to(&self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) {
+		self.push_path_command(
+			&format!(
+				"C{} {} {} {} ",
+				format_number(cp1x),
+				format_number(cp1y),
+				format_number(cp2x),
+				format_number(cp2y)
+			),
+			(x, y),
+		);
+	}
+
+	fn clip(&self) {
+		let mut state = self.state.borrow_mut();
+		let id = state.n_clip_paths;
+		state.n_clip_paths += 1;
+		let element = format!(
+			r#"<clipPath id="clip-{}"{}><path d="{}"{}/></clipPath>"#,
+			id,
+			clip_path_attribute(&state.style),
+			state.path.trim_end(),
+			transform_attribute(&state.style),
+		);
+		state.defs.push_str(&element);
+		state.style.clip_path = Some(id);
+	}
+
+	fn fill(&self) {
+		let mut state = self.state.borrow_mut();
+		let element = format!(
+			r#"<path d="{}" fill="{}"{}{}/>"#,
+			state.path.trim_end(),
+			escape(&state.style.fill_style),
+			transform_attribute(&state.style),
+			clip_path_attribute(&state.style),
+		);
+		state.body.push_str(&element);
+	}
+
+	fn fill_text(&self, text: &str, x: f64, y: f64) {
+		let mut state = self.state.borrow_mut();
+		let text_anchor = match state.style.text_align.as_str() {
+			"center" => "middle",
+			"right" | "end" => "end",
+			_ => "start",
+		};
+		let dominant_baseline = match state.style.text_baseline.as_str() {
+			"top" | "hanging" => "hanging",
+			"middle" => "middle",
+			"bottom" | "ideographic" => "text-after-edge",
+			_ => "alphabetic",
+		};
+		let element = format!(
+			r#"<text x="{}" y="{}" fill="{}" text-anchor="{}" dominant-baseline="{}"{}{}>{}</text>"#,
+			format_number(x),
+			format_number(y),
+			escape(&state.style.fill_style),
+			text_anchor,
+			dominant_baseline,
+			transform_attribute(&state.style),
+			clip_path_attribute(&state.style),
+			escape(text),
+		);
+		state.body.push_str(&element);
+	}
+
+	fn line_to(&self, x: f64, y: f64) {
+		let command = if self.state.borrow().current_point.is_some() {
+			"L"
+		} else {
+			"M"
+		};
+		self.push_path_command(command, (x, y));
+	}
+
+	/// The chart font is monospaced, so the width of text is its number of characters times the advance width of one character, which is 0.6em for JetBrains Mono.
+	fn measure_text(&self, text: &str) -> f64 {
+		text.chars().count().to_f64().unwrap() * self.font_size * 0.6
+	}
+
+	fn move_to(&self, x: f64, y: f64) {
+		self.push_path_command("M", (x, y));
+	}
+
+	fn restore(&self) {
+		let mut state = self.state.borrow_mut();
+		if let Some(style) = state.saved_styles.pop() {
+			state.style = style;
+		}
+	}
+
+	fn rotate(&self, angle: f64) {
+		let mut state = self.state.borrow_mut();
+		let degrees = angle.to_degrees();
+		write!(state.style.transform, "rotate({}) ", format_number(degrees)).unwrap();
+	}
+
+	fn save(&self) {
+		let mut state = self.state.borrow_mut();
+		let style = state.style.clone();
+		state.saved_styles.push(style);
+	}
+
+	fn set_fill_style(&self, color: &str) {
+		self.state.borrow_mut().style.fill_style = color.to_owned();
+	}
+
+	fn set_line_cap(&self, line_cap: &str) {
+		self.state.borrow_mut().style.line_cap = line_cap.to_owned();
+	}
+
+	fn set_line_dash(&self, segments: &[f64]) {
+		self.state.borrow_mut().style.line_dash = segments.to_owned();
+	}
+
+	fn set_line_width(&self, line_width: f64) {
+		self.state.borrow_mut().style.line_width = line_width;
+	}
+
+	fn set_stroke_style(&self, color: &str) {
+		self.state.borrow_mut().style.stroke_style = color.to_owned();
+	}
+
+	fn set_text_align(&self, text_align: &str) {
+		self.state.borrow_mut().style.text_align = text_align.to_owned();
+	}
+
+	fn set_text_baseline(&self, text_baseline: &str) {
+		self.state.borrow_mut().style.text_baseline = text_baseline.to_owned();
+	}
+
+	fn stroke(&self) {
+		let mut state = self.state.borrow_mut();
+		let line_dash = if state.style.line_dash.is_empty() {
+			String::new()
+		} else {
+			let line_dash = state
+				.style
+				.line_dash
+				.iter()
+				.map(|segment| format_number(*segment))
+				.collect::<Vec<_>>()
+				.join(" ");
+			format!(r#" stroke-dasharray="{}""#, line_dash)
+		};
+		let element = format!(
+			r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="{}"{}{}{}/>"#,
+			state.path.trim_end(),
+			escape(&state.style.stroke_style),
+			format_number(state.style.line_width),
+			escape(&state.style.line_cap),
+			line_dash,
+			transform_attribute(&state.style),
+			clip_path_attribute(&state.style),
+		);
+		state.body.push_str(&element);
+	}
+
+	fn translate(&self, x: f64, y: f64) {
+		let mut state = self.state.borrow_mut();
+		write!(
+			state.style.transform,
+			"translate({} {}) ",
+			format_number(x),
+			format_number(y)
+		)
+		.unwrap();
+	}
+}
+
+fn transform_attribute(style: &SvgStyle) -> String {
+	if style.transform.is_empty() {
+		String::new()
+	} else {
+		format!(r#" transform="{}""#, style.transform.trim_end())
+	}
+}
+
+fn clip_path_attribute(style: &SvgStyle) -> String {
+	match style.clip_path {
+		Some(id) => format!(r#" clip-path="url(#clip-{})""#, id),
+		None => String::new(),
+	}
+}
+
+/// Format a coordinate with at most two decimal places, which is more precise than a pixel and keeps the svg small.
+fn format_number(value: f64) -> String {
+	let value = (value * 100.0).round() / 100.0;
+	if value == 0.0 {
+		"0".to_owned()
+	} else {
+		value.to_string()
+	}
+}
+
+fn escape(value: &str) -> String {
+	let mut escaped = String::with_capacity(value.len());
+	for c in value.chars() {
+		match c {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&#39;"),
+			c => escaped.push(c),
+		}
+	}
+	escaped
+}
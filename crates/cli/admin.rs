@@ -0,0 +1,149 @@
+use crate::{
+	app::default_database_url, AdminArgs, AdminCommand, AdminOrganizationsCommand,
+	AdminPredictionsCommand, AdminReposCommand, AdminUsersCommand,
+};
+use anyhow::{anyhow, bail, Result};
+use modelfox_app_core::{
+	admin::{
+		connect_admin_database, create_organization, create_user, delete_user,
+		get_user_id_by_email, list_organizations, list_repos, list_users, purge_predictions,
+		reset_user_auth, transfer_repo, RepoOwner,
+	},
+	organizations::delete_organization,
+};
+use modelfox_id::Id;
+
+#[tokio::main]
+pub async fn admin(args: AdminArgs) -> Result<()> {
+	let database_url = match args.database_url {
+		Some(database_url) => database_url.parse()?,
+		None => default_database_url(),
+	};
+	let database_pool = connect_admin_database(database_url).await?;
+	let mut txn = database_pool.begin().await?;
+	let json = args.json;
+	match args.command {
+		AdminCommand::Users(AdminUsersCommand::List) => {
+			let users = list_users(&mut txn).await?;
+			if json {
+				println!("{}", serde_json::to_string_pretty(&users)?);
+			} else {
+				for user in users {
+					println!("{}\t{}", user.id, user.email);
+				}
+			}
+		}
+		AdminCommand::Users(AdminUsersCommand::Create { email }) => {
+			let user_id = create_user(&mut txn, &email).await?;
+			print_id(user_id, json);
+		}
+		AdminCommand::Users(AdminUsersCommand::Delete { email }) => {
+			let user_id = get_user_id_by_email(&mut txn, &email).await?;
+			delete_user(&mut txn, user_id).await?;
+		}
+		AdminCommand::Users(AdminUsersCommand::ResetAuth { email }) => {
+			let user_id = get_user_id_by_email(&mut txn, &email).await?;
+			reset_user_auth(&mut txn, user_id, chrono::Utc::now().timestamp()).await?;
+		}
+		AdminCommand::Organizations(AdminOrganizationsCommand::List) => {
+			let organizations = list_organizations(&mut txn).await?;
+			if json {
+				println!("{}", serde_json::to_string_pretty(&organizations)?);
+			} else {
+				for organization in organizations {
+					println!(
+						"{}\t{}\t{} members",
+						organization.id, organization.name, organization.member_count
+					);
+				}
+			}
+		}
+		AdminCommand::Organizations(AdminOrganizationsCommand::Create { name, admin }) => {
+			let admin_user_id = get_user_id_by_email(&mut txn, &admin).await?;
+			let organization_id = create_organization(&mut txn, &name, admin_user_id).await?;
+			print_id(organization_id, json);
+		}
+		AdminCommand::Organizations(AdminOrganizationsCommand::Delete { id }) => {
+			let organization_id = parse_id(&id)?;
+			// Deleting an organization deletes its repos, which would leave their models behind in storage.
+			let repo_count = list_repos(&mut txn)
+				.await?
+				.into_iter()
+				.filter(|repo| {
+					matches!(repo.owner, Some(RepoOwner::Organization(owner_id)) if owner_id == organization_id)
+				})
+				.count();
+			if repo_count > 0 {
+				bail!(
+					"The organization owns {} repos. Transfer or delete them before deleting the organization.",
+					repo_count
+				);
+			}
+			delete_organization(&mut txn, organization_id).await?;
+		}
+		AdminCommand::Repos(AdminReposCommand::List) => {
+			let repos = list_repos(&mut txn).await?;
+			if json {
+				println!("{}", serde_json::to_string_pretty(&repos)?);
+			} else {
+				for repo in repos {
+					let owner = match repo.owner {
+						Some(RepoOwner::User(user_id)) => format!("user {}", user_id),
+						Some(RepoOwner::Organization(organization_id)) => {
+							format!("organization {}", organization_id)
+						}
+						None => "root".to_owned(),
+					};
+					println!("{}\t{}\t{}", repo.id, repo.title, owner);
+				}
+			}
+		}
+		AdminCommand::Repos(AdminReposCommand::Transfer {
+			id,
+			user,
+			organization,
+		}) => {
+			let owner = match (user, organization) {
+				(Some(email), _) => RepoOwner::User(get_user_id_by_email(&mut txn, &email).await?),
+				(None, Some(organization_id)) => {
+					RepoOwner::Organization(parse_id(&organization_id)?)
+				}
+				(None, None) => unreachable!(),
+			};
+			transfer_repo(&mut txn, parse_id(&id)?, owner).await?;
+		}
+		AdminCommand::Predictions(AdminPredictionsCommand::Purge {
+			model,
+			before,
+			dry_run,
+		}) => {
+			let model_id = model.as_deref().map(parse_id).transpose()?;
+			let before = chrono::DateTime::parse_from_rfc3339(&before)
+				.map_err(|_| anyhow!("failed to parse the date {}", before))?;
+			let output = purge_predictions(&mut txn, model_id, before.timestamp(), dry_run).await?;
+			if json {
+				println!("{}", serde_json::to_string_pretty(&output)?);
+			} else {
+				let verb = if dry_run { "Would delete" } else { "Deleted" };
+				println!(
+					"{} {} predictions and {} true values.",
+					verb, output.prediction_count, output.true_value_count
+				);
+			}
+		}
+	}
+	txn.commit().await?;
+	Ok(())
+}
+
+fn parse_id(id: &str) -> Result<Id> {
+	id.parse().map_err(|_| anyhow!("invalid id {}", id))
+}
+
+fn print_id(id: Id, json: bool) {
+	if json {
+		println!("{}", serde_json::json!({ "id": id }));
+	} else {
+		println!("{}", id);
+	}
+}
@@ -1,32 +1,103 @@
 use crate::AppArgs;
 use anyhow::{anyhow, bail, Result};
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 use url::Url;
 
 #[derive(Clone, serde::Deserialize)]
 struct AppConfig {
 	auth: Option<AuthConfig>,
+	body_size_limits: Option<BodySizeLimitsConfig>,
 	cookie_domain: Option<String>,
 	database: Option<DatabaseConfig>,
 	host: Option<std::net::IpAddr>,
+	id_format: Option<IdFormatConfig>,
+	ingest: Option<IngestConfig>,
 	license: Option<PathBuf>,
+	#[serde(default)]
+	pause_notifications: bool,
 	port: Option<u16>,
+	secret_key: Option<String>,
 	smtp: Option<SmtpConfig>,
+	#[serde(default)]
+	stateless: bool,
 	storage: Option<StorageConfig>,
-	url: Option<String>,
+	tree_shap_mode: Option<modelfox_core::predict::TreeShapMode>,
+	#[serde(default)]
+	trusted_proxies: Vec<std::net::IpAddr>,
+	url: Option<UrlConfig>,
 }
 
 #[derive(Clone, serde::Deserialize)]
 struct AuthConfig {
+	#[serde(default)]
+	admin_emails: Vec<String>,
+	allowed_email_domains: Option<Vec<String>>,
 	enable: bool,
+	#[serde(default)]
+	invite_only: bool,
+}
+
+#[derive(Clone, serde::Deserialize)]
+struct BodySizeLimitsConfig {
+	default_bytes: Option<u64>,
+	model_upload_bytes: Option<u64>,
+	batch_predict_bytes: Option<u64>,
 }
 
 #[derive(Clone, serde::Deserialize)]
 struct DatabaseConfig {
+	analytics_statement_timeout_ms: Option<u64>,
 	max_connections: Option<u32>,
+	replica_url: Option<Url>,
 	url: Url,
 }
 
+#[derive(Clone, Copy, serde::Deserialize)]
+enum IdFormatConfig {
+	#[serde(rename = "random")]
+	Random,
+	#[serde(rename = "ulid")]
+	Ulid,
+}
+
+#[derive(Clone, serde::Deserialize)]
+#[serde(tag = "type")]
+enum IngestConfig {
+	#[serde(rename = "kafka")]
+	Kafka(KafkaIngestConfig),
+	#[serde(rename = "kinesis")]
+	Kinesis(KinesisIngestConfig),
+}
+
+#[derive(Clone, serde::Deserialize)]
+struct KafkaIngestConfig {
+	brokers: String,
+	topic: String,
+	group_id: Option<String>,
+	#[serde(default)]
+	properties: BTreeMap<String, String>,
+}
+
+#[derive(Clone, serde::Deserialize)]
+struct KinesisIngestConfig {
+	stream_name: String,
+	region: Option<String>,
+}
+
+/// The `url` is either the URL at which the app is accessible, or an object with the origin and the base path to serve the app from.
+#[derive(Clone, serde::Deserialize)]
+#[serde(untagged)]
+enum UrlConfig {
+	Url(String),
+	Options(UrlOptionsConfig),
+}
+
+#[derive(Clone, serde::Deserialize)]
+struct UrlOptionsConfig {
+	origin: Option<String>,
+	base_path: Option<String>,
+}
+
 #[derive(Clone, serde::Deserialize)]
 struct SmtpConfig {
 	host: String,
@@ -84,11 +155,30 @@ pub async fn app(args: AppArgs) -> Result<()> {
 		.and_then(|c| c.auth.as_ref())
 		.and_then(|auth| {
 			if auth.enable {
-				Some(modelfox_app::options::AuthOptions {})
+				Some(modelfox_app::options::AuthOptions {
+					admin_emails: auth.admin_emails.clone(),
+					allowed_email_domains: auth.allowed_email_domains.clone(),
+					invite_only: auth.invite_only,
+				})
 			} else {
 				None
 			}
 		});
+	let body_size_limits = {
+		let default = modelfox_app::options::BodySizeLimits::default();
+		let config = config.as_ref().and_then(|c| c.body_size_limits.as_ref());
+		modelfox_app::options::BodySizeLimits {
+			default: config
+				.and_then(|c| c.default_bytes)
+				.unwrap_or(default.default),
+			model_upload: config
+				.and_then(|c| c.model_upload_bytes)
+				.unwrap_or(default.model_upload),
+			batch_predict: config
+				.and_then(|c| c.batch_predict_bytes)
+				.unwrap_or(default.batch_predict),
+		}
+	};
 	let cookie_domain = config.as_ref().and_then(|c| c.cookie_domain.clone());
 	let storage = if let Some(storage) = config.as_ref().and_then(|c| c.storage.as_ref()) {
 		match storage {
@@ -121,11 +211,17 @@ pub async fn app(args: AppArgs) -> Result<()> {
 		.as_ref()
 		.and_then(|c| c.database.as_ref())
 		.map(|database| modelfox_app::options::DatabaseOptions {
+			analytics_statement_timeout: database
+				.analytics_statement_timeout_ms
+				.map(std::time::Duration::from_millis),
 			max_connections: database.max_connections,
+			replica_url: database.replica_url.clone(),
 			url: database.url.clone(),
 		})
 		.unwrap_or_else(|| modelfox_app::options::DatabaseOptions {
+			analytics_statement_timeout: None,
 			max_connections: None,
+			replica_url: None,
 			url: default_database_url(),
 		});
 	let host_from_env = if let Ok(host) = std::env::var("HOST") {
@@ -173,19 +269,77 @@ pub async fn app(args: AppArgs) -> Result<()> {
 	} else {
 		None
 	};
-	let url = if let Some(url) = config.as_ref().and_then(|c| c.url.clone()) {
-		Some(url.parse()?)
-	} else {
-		None
+	let ingest = config
+		.as_ref()
+		.and_then(|c| c.ingest.clone())
+		.map(|ingest| match ingest {
+			IngestConfig::Kafka(ingest) => modelfox_app::options::IngestOptions::Kafka(
+				modelfox_app::options::KafkaIngestOptions {
+					brokers: ingest.brokers,
+					topic: ingest.topic,
+					group_id: ingest.group_id.unwrap_or_else(|| "modelfox".to_owned()),
+					properties: ingest.properties,
+				},
+			),
+			IngestConfig::Kinesis(ingest) => modelfox_app::options::IngestOptions::Kinesis(
+				modelfox_app::options::KinesisIngestOptions {
+					stream_name: ingest.stream_name,
+					region: ingest.region,
+				},
+			),
+		});
+	let pause_notifications = config
+		.as_ref()
+		.map(|c| c.pause_notifications)
+		.unwrap_or(false);
+	let stateless = config.as_ref().map(|c| c.stateless).unwrap_or(false);
+	let tree_shap_mode = config
+		.as_ref()
+		.and_then(|c| c.tree_shap_mode)
+		.unwrap_or_default();
+	let trusted_proxies = config
+		.as_ref()
+		.map(|c| c.trusted_proxies.clone())
+		.unwrap_or_default();
+	let secret_key = std::env::var("MODELFOX_SECRET_KEY")
+		.ok()
+		.or_else(|| config.as_ref().and_then(|c| c.secret_key.clone()));
+	let (url, base_path) = match config.as_ref().and_then(|c| c.url.clone()) {
+		Some(UrlConfig::Url(url)) => (Some(url.parse()?), None),
+		Some(UrlConfig::Options(url)) => {
+			let origin = if let Some(origin) = url.origin {
+				Some(origin.parse()?)
+			} else {
+				None
+			};
+			(origin, url.base_path)
+		}
+		None => (None, None),
 	};
+	let base_path = base_path.unwrap_or_default();
+	// Set how new ids are generated.
+	if let Some(id_format) = config.as_ref().and_then(|c| c.id_format) {
+		modelfox_id::set_generate_mode(match id_format {
+			IdFormatConfig::Random => modelfox_id::GenerateMode::Random,
+			IdFormatConfig::Ulid => modelfox_id::GenerateMode::Ulid,
+		});
+	}
 	let options = modelfox_app::options::Options {
 		auth,
+		base_path,
+		body_size_limits,
 		cookie_domain,
 		database,
 		host,
+		ingest,
+		pause_notifications,
 		port,
+		secret_key,
 		smtp,
+		stateless,
 		storage,
+		tree_shap_mode,
+		trusted_proxies,
 		url,
 	};
 	modelfox_app::run(options).await
@@ -0,0 +1,83 @@
+use crate::DiffArgs;
+use anyhow::Result;
+use colored::Colorize;
+use modelfox_model::diff::{FeatureGroupDiff, ModelDiff};
+
+pub fn diff(args: DiffArgs) -> Result<()> {
+	let bytes_a = std::fs::read(&args.model_a)?;
+	let model_a = modelfox_model::from_bytes(&bytes_a)?;
+	let bytes_b = std::fs::read(&args.model_b)?;
+	let model_b = modelfox_model::from_bytes(&bytes_b)?;
+	let model_diff = modelfox_model::diff::diff(model_a, model_b);
+	if args.json {
+		println!("{}", serde_json::to_string_pretty(&model_diff)?);
+	} else {
+		print_model_diff(&model_diff);
+	}
+	Ok(())
+}
+
+fn print_model_diff(model_diff: &ModelDiff) {
+	println!("{}", "Hyperparameters".bold());
+	if model_diff.hyperparameters.is_empty() {
+		println!("  no changes");
+	}
+	for hyperparameter in model_diff.hyperparameters.iter() {
+		println!(
+			"  {}: {} -> {}",
+			hyperparameter.name,
+			hyperparameter.a.as_deref().unwrap_or("-"),
+			hyperparameter.b.as_deref().unwrap_or("-"),
+		);
+	}
+	println!("{}", "Feature Groups".bold());
+	if model_diff.feature_groups.is_empty() {
+		println!("  no changes");
+	}
+	for feature_group in model_diff.feature_groups.iter() {
+		match feature_group {
+			FeatureGroupDiff::Added { feature_group } => {
+				println!("  {}", format!("+ {}", feature_group).green());
+			}
+			FeatureGroupDiff::Removed { feature_group } => {
+				println!("  {}", format!("- {}", feature_group).red());
+			}
+			FeatureGroupDiff::Changed {
+				feature_group,
+				added_variants,
+				removed_variants,
+			} => {
+				println!("  ~ {}", feature_group);
+				for variant in added_variants.iter() {
+					println!("    {}", format!("+ {}", variant).green());
+				}
+				for variant in removed_variants.iter() {
+					println!("    {}", format!("- {}", variant).red());
+				}
+			}
+		}
+	}
+	println!("{}", "Metrics".bold());
+	for metric in model_diff.metrics.iter() {
+		println!(
+			"  {}: {} -> {} ({:+})",
+			metric.name, metric.a, metric.b, metric.delta
+		);
+	}
+	println!("{}", "Feature Importance Ranks".bold());
+	if model_diff.feature_importances.is_empty() {
+		println!("  no changes");
+	}
+	for feature_importance in model_diff.feature_importances.iter() {
+		let format_rank = |rank: Option<usize>| {
+			rank.map(|rank| rank.to_string())
+				.unwrap_or_else(|| "-".to_owned())
+		};
+		println!(
+			"  {}: {} -> {}",
+			feature_importance.feature_name,
+			format_rank(feature_importance.a_rank),
+			format_rank(feature_importance.b_rank),
+		);
+	}
+}
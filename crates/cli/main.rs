@@ -5,10 +5,14 @@ use colored::Colorize;
 use std::path::PathBuf;
 use tracing_subscriber::prelude::*;
 
+#[cfg(feature = "modelfox_app")]
+mod admin;
 #[cfg(feature = "modelfox_app")]
 mod app;
+mod diff;
 #[cfg(feature = "modelfox_app")]
 mod migrate;
+mod model_card;
 #[cfg(feature = "train")]
 mod predict;
 #[cfg(feature = "serve")]
@@ -41,9 +45,16 @@ enum Subcommand {
 	#[cfg(feature = "modelfox_app")]
 	#[clap(name = "migrate")]
 	Migrate(Box<MigrateArgs>),
+	#[cfg(feature = "modelfox_app")]
+	#[clap(name = "admin")]
+	Admin(Box<AdminArgs>),
 	#[cfg(feature = "serve")]
 	#[clap(name = "serve")]
 	Serve(Box<ServeArgs>),
+	#[clap(name = "diff")]
+	Diff(Box<DiffArgs>),
+	#[clap(name = "modelcard")]
+	ModelCard(Box<ModelCardArgs>),
 }
 
 #[cfg(feature = "train")]
@@ -56,24 +67,37 @@ pub struct TrainArgs {
 	#[clap(
 		short,
 		long,
-		help = "the path to your .csv file",
+		help = "the path to your .csv, .csv.gz, or .csv.zst file",
 		conflicts_with_all=&["file-train", "file-test"],
 	)]
 	file: Option<PathBuf>,
 	#[clap(
 		long,
-		help = "the path to your .csv file used for training",
+		help = "the path to your .csv, .csv.gz, or .csv.zst file used for training",
 		requires = "file-test"
 	)]
 	file_train: Option<PathBuf>,
 	#[clap(
 		long,
-		help = "the path to your .csv file used for testing",
+		help = "the path to your .csv, .csv.gz, or .csv.zst file used for testing",
 		requires = "file-train"
 	)]
 	file_test: Option<PathBuf>,
 	#[clap(long, help = "Pass the training data via stdin.")]
 	stdin: bool,
+	#[clap(
+		long,
+		help = "the url of a postgres or mysql database to load the training data from",
+		requires = "query",
+		conflicts_with_all=&["file", "file-train", "file-test", "stdin"],
+	)]
+	database: Option<String>,
+	#[clap(
+		long,
+		help = "the sql query whose result is the training data, used with --database",
+		requires = "database"
+	)]
+	query: Option<String>,
 	#[clap(short, long, help = "the name of the column to predict")]
 	target: String,
 	#[clap(short, long, help = "the path to a config file")]
@@ -86,6 +110,46 @@ pub struct TrainArgs {
 		parse(from_flag = std::ops::Not::not),
 	)]
 	progress: bool,
+	#[clap(
+		long = "progress",
+		arg_enum,
+		default_value = "terminal",
+		help = "the format to write progress in, either progress bars in the terminal or newline delimited json events on stdout"
+	)]
+	progress_format: ProgressFormat,
+	#[clap(
+		long,
+		help = "infer column types and compute column stats without training, then print a report and write a starter config file to the output path"
+	)]
+	inspect_only: bool,
+	#[clap(
+		long,
+		requires = "config",
+		help = "check the config file for errors and unknown keys, and print them with their line and column, without training"
+	)]
+	check_config: bool,
+	#[clap(
+		long,
+		help = "the path to a checkpoint written by a previous training that was interrupted, to continue training where it left off"
+	)]
+	resume_from_checkpoint: Option<PathBuf>,
+	#[clap(
+		long,
+		help = "a directory to cache the tables parsed from csv files in, so training again on the same files skips parsing them"
+	)]
+	cache_dir: Option<PathBuf>,
+	#[clap(
+		long,
+		help = "train even if the leakage guard finds columns that predict the target on their own, and print a warning for each of them"
+	)]
+	allow_leakage: bool,
+}
+
+#[cfg(feature = "train")]
+#[derive(Clone, Copy, clap::ArgEnum)]
+pub enum ProgressFormat {
+	Terminal,
+	Json,
 }
 
 #[cfg(feature = "train")]
@@ -125,6 +189,23 @@ pub struct PredictArgs {
 pub struct AppArgs {
 	#[clap(short, long = "config")]
 	config: Option<PathBuf>,
+	#[clap(
+		long,
+		help = "the log level or filter directives to log with, such as info or modelfox_app=debug, overriding the MODELFOX_TRACING environment variable"
+	)]
+	log_level: Option<String>,
+	#[clap(
+		long,
+		arg_enum,
+		help = "the format to write logs in, defaults to pretty in debug builds and json in release builds"
+	)]
+	log_format: Option<LogFormat>,
+}
+
+#[derive(Clone, Copy, clap::ArgEnum)]
+pub enum LogFormat {
+	Pretty,
+	Json,
 }
 
 #[cfg(feature = "modelfox_app")]
@@ -138,11 +219,143 @@ pub struct MigrateArgs {
 	database_url: Option<String>,
 }
 
+#[cfg(feature = "modelfox_app")]
+#[derive(Parser)]
+#[clap(
+	about = "Manage your app.",
+	long_about = "Manage the users, organizations, repos, and predictions in your app database."
+)]
+pub struct AdminArgs {
+	#[clap(
+		long,
+		help = "the url of the app database, defaults to the sqlite database in the user data directory"
+	)]
+	database_url: Option<String>,
+	#[clap(long, help = "print the output as json")]
+	json: bool,
+	#[clap(subcommand)]
+	command: AdminCommand,
+}
+
+#[cfg(feature = "modelfox_app")]
+#[derive(Parser)]
+pub enum AdminCommand {
+	#[clap(subcommand, name = "users", about = "List, create, and delete users.")]
+	Users(AdminUsersCommand),
+	#[clap(
+		subcommand,
+		name = "organizations",
+		about = "List, create, and delete organizations."
+	)]
+	Organizations(AdminOrganizationsCommand),
+	#[clap(subcommand, name = "repos", about = "List and transfer repos.")]
+	Repos(AdminReposCommand),
+	#[clap(subcommand, name = "predictions", about = "Purge logged predictions.")]
+	Predictions(AdminPredictionsCommand),
+}
+
+#[cfg(feature = "modelfox_app")]
+#[derive(Parser)]
+pub enum AdminUsersCommand {
+	#[clap(name = "list", about = "List all users.")]
+	List,
+	#[clap(name = "create", about = "Create a user.")]
+	Create {
+		#[clap(help = "the email of the user to create")]
+		email: String,
+	},
+	#[clap(
+		name = "delete",
+		about = "Delete a user. A user who owns repos cannot be deleted until the repos are transferred."
+	)]
+	Delete {
+		#[clap(help = "the email of the user to delete")]
+		email: String,
+	},
+	#[clap(
+		name = "reset-auth",
+		about = "Log a user out everywhere by revoking their sessions and login codes."
+	)]
+	ResetAuth {
+		#[clap(help = "the email of the user")]
+		email: String,
+	},
+}
+
+#[cfg(feature = "modelfox_app")]
+#[derive(Parser)]
+pub enum AdminOrganizationsCommand {
+	#[clap(name = "list", about = "List all organizations.")]
+	List,
+	#[clap(name = "create", about = "Create an organization.")]
+	Create {
+		#[clap(help = "the name of the organization")]
+		name: String,
+		#[clap(long, help = "the email of the user to make the organization's admin")]
+		admin: String,
+	},
+	#[clap(
+		name = "delete",
+		about = "Delete an organization. An organization that owns repos cannot be deleted until the repos are transferred."
+	)]
+	Delete {
+		#[clap(help = "the id of the organization to delete")]
+		id: String,
+	},
+}
+
+#[cfg(feature = "modelfox_app")]
+#[derive(Parser)]
+pub enum AdminReposCommand {
+	#[clap(name = "list", about = "List all repos.")]
+	List,
+	#[clap(
+		name = "transfer",
+		about = "Transfer a repo to a user or an organization."
+	)]
+	Transfer {
+		#[clap(help = "the id of the repo to transfer")]
+		id: String,
+		#[clap(
+			long,
+			conflicts_with = "organization",
+			required_unless_present = "organization",
+			help = "the email of the user to transfer the repo to"
+		)]
+		user: Option<String>,
+		#[clap(long, help = "the id of the organization to transfer the repo to")]
+		organization: Option<String>,
+	},
+}
+
+#[cfg(feature = "modelfox_app")]
+#[derive(Parser)]
+pub enum AdminPredictionsCommand {
+	#[clap(
+		name = "purge",
+		about = "Delete predictions and true values logged before a date. Production stats and metrics are kept."
+	)]
+	Purge {
+		#[clap(
+			long,
+			help = "the id of the model to purge predictions for, defaults to all models"
+		)]
+		model: Option<String>,
+		#[clap(
+			long,
+			help = "delete predictions logged before this date, formatted as rfc 3339, such as 2022-01-01T00:00:00Z"
+		)]
+		before: String,
+		#[clap(long, help = "print the counts without deleting anything")]
+		dry_run: bool,
+	},
+}
+
 #[cfg(feature = "serve")]
 #[derive(Parser)]
 #[clap(
 	about = "Serve predictions via HTTP",
-	long_about = "Create HTTP server exposing an endpoint for running predictions against a ModelFox model"
+	long_about = "Create a standalone HTTP server exposing /predict, /health, and /metadata endpoints for a ModelFox model, without requiring a database"
 )]
 pub struct ServeArgs {
 	#[clap(
@@ -160,11 +373,60 @@ pub struct ServeArgs {
 	model: PathBuf,
 	#[clap(short, long, default_value = "8080", help = "Port to listen on")]
 	port: u16,
+	#[clap(
+		long,
+		help = "The url of a ModelFox app to log prediction and true value events to"
+	)]
+	app_url: Option<url::Url>,
+}
+
+#[derive(Parser)]
+#[clap(
+	about = "Compare two models.",
+	long_about = "Report the changes in hyperparameters, feature groups, metrics, and feature importances between two .modelfox files."
+)]
+pub struct DiffArgs {
+	#[clap(help = "the path to the original .modelfox file")]
+	model_a: PathBuf,
+	#[clap(help = "the path to the new .modelfox file")]
+	model_b: PathBuf,
+	#[clap(long, help = "print the diff as json")]
+	json: bool,
+}
+
+#[derive(Parser)]
+#[clap(
+	about = "Generate a model card.",
+	long_about = "Generate a markdown model card describing a model's intended use, training data, metrics, feature importances, training configuration, and caveats."
+)]
+pub struct ModelCardArgs {
+	#[clap(help = "the path to the .modelfox file")]
+	model: PathBuf,
+	#[clap(
+		short,
+		long,
+		help = "the path to write the model card to, defaults to stdout"
+	)]
+	output: Option<PathBuf>,
+	#[clap(
+		long,
+		help = "the title of the model card, defaults to the model's file name"
+	)]
+	title: Option<String>,
 }
 
 fn main() {
-	setup_tracing();
 	let args = Args::parse();
+	let (log_level, log_format) = match &args.subcommand {
+		#[cfg(feature = "modelfox_app")]
+		Subcommand::App(args) => (args.log_level.as_deref(), args.log_format),
+		#[allow(unreachable_patterns)]
+		_ => (None, None),
+	};
+	if let Err(error) = setup_tracing(log_level, log_format) {
+		eprintln!("{}: {}", "error".red().bold(), error);
+		std::process::exit(1);
+	}
 	let result = match args.subcommand {
 		#[cfg(feature = "train")]
 		Subcommand::Train(args) => self::train::train(*args),
@@ -174,8 +436,12 @@ fn main() {
 		Subcommand::App(args) => self::app::app(*args),
 		#[cfg(feature = "modelfox_app")]
 		Subcommand::Migrate(args) => self::migrate::migrate(*args),
+		#[cfg(feature = "modelfox_app")]
+		Subcommand::Admin(args) => self::admin::admin(*args),
 		#[cfg(feature = "serve")]
 		Subcommand::Serve(args) => self::serve::serve(*args),
+		Subcommand::Diff(args) => self::diff::diff(*args),
+		Subcommand::ModelCard(args) => self::model_card::model_card(*args),
 	};
 	if let Err(error) = result {
 		eprintln!("{}: {}", "error".red().bold(), error);
@@ -183,26 +449,42 @@ fn main() {
 	}
 }
 
-fn setup_tracing() {
-	let env_layer = tracing_subscriber::EnvFilter::try_from_env("MODELFOX_TRACING");
-	let env_layer = if cfg!(debug_assertions) {
-		Some(env_layer.unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("[]=info")))
+fn setup_tracing(
+	log_level: Option<&str>,
+	log_format: Option<LogFormat>,
+) -> Result<(), tracing_subscriber::filter::ParseError> {
+	let env_layer = if let Some(log_level) = log_level {
+		Some(tracing_subscriber::EnvFilter::try_new(log_level)?)
 	} else {
-		env_layer.ok()
-	};
-	if let Some(env_layer) = env_layer {
+		let env_layer = tracing_subscriber::EnvFilter::try_from_env("MODELFOX_TRACING");
 		if cfg!(debug_assertions) {
-			let format_layer = tracing_subscriber::fmt::layer().pretty();
-			let subscriber = tracing_subscriber::registry()
-				.with(env_layer)
-				.with(format_layer);
-			subscriber.init();
+			Some(env_layer.unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("[]=info")))
 		} else {
-			let json_layer = tracing_subscriber::fmt::layer().json();
-			let subscriber = tracing_subscriber::registry()
-				.with(env_layer)
-				.with(json_layer);
-			subscriber.init();
+			env_layer.ok()
+		}
+	};
+	let log_format = log_format.unwrap_or(if cfg!(debug_assertions) {
+		LogFormat::Pretty
+	} else {
+		LogFormat::Json
+	});
+	if let Some(env_layer) = env_layer {
+		match log_format {
+			LogFormat::Pretty => {
+				let format_layer = tracing_subscriber::fmt::layer().pretty();
+				let subscriber = tracing_subscriber::registry()
+					.with(env_layer)
+					.with(format_layer);
+				subscriber.init();
+			}
+			LogFormat::Json => {
+				let json_layer = tracing_subscriber::fmt::layer().json();
+				let subscriber = tracing_subscriber::registry()
+					.with(env_layer)
+					.with(json_layer);
+				subscriber.init();
+			}
 		}
 	}
+	Ok(())
 }
@@ -0,0 +1,21 @@
+use crate::ModelCardArgs;
+use anyhow::Result;
+
+pub fn model_card(args: ModelCardArgs) -> Result<()> {
+	let bytes = std::fs::read(&args.model)?;
+	let model = modelfox_model::from_bytes(&bytes)?;
+	let title = match args.title {
+		Some(title) => title,
+		None => args
+			.model
+			.file_stem()
+			.map(|file_stem| file_stem.to_string_lossy().into_owned())
+			.unwrap_or_default(),
+	};
+	let markdown = modelfox_core::model_card::model_card(model, &title).to_markdown();
+	match args.output {
+		Some(output) => std::fs::write(output, markdown)?,
+		None => print!("{}", markdown),
+	}
+	Ok(())
+}
@@ -21,7 +21,7 @@ pub fn predict(args: PredictArgs) -> Result<()> {
 			multiclass_classifier.read().target_column_name()
 		}
 	};
-	let model = modelfox_core::predict::Model::from(model);
+	let model = modelfox_core::predict::Model::from_bytes(&bytes)?;
 	let mut options = PredictOptions {
 		compute_feature_contributions: false,
 		..Default::default()
@@ -68,6 +68,16 @@ pub fn predict(args: PredictArgs) -> Result<()> {
 			}
 		}
 	};
+	// Rows dropped because of an invalid value are written with an empty value for each output column, so the output still has one row for each input row.
+	let n_output_columns = match &model.inner {
+		modelfox_core::predict::ModelInner::MulticlassClassifier(model)
+			if should_output_probabilies =>
+		{
+			model.classes.len()
+		}
+		modelfox_core::predict::ModelInner::BinaryClassifier(_) if should_output_probabilies => 2,
+		_ => 1,
+	};
 	let header = reader.headers()?.to_owned();
 	for records in &reader.records().chunks(PREDICT_CHUNK_SIZE) {
 		let input: Vec<PredictInput> = records
@@ -85,8 +95,15 @@ pub fn predict(args: PredictArgs) -> Result<()> {
 				Ok(PredictInput(input))
 			})
 			.collect::<Result<_, _>>()?;
-		let output = modelfox_core::predict::predict(&model, &input, &options);
+		let output = modelfox_core::predict::try_predict(&model, &input, &options)?;
 		for output in output {
+			let output = match output {
+				Some(output) => output,
+				None => {
+					writer.write_record(&vec![""; n_output_columns])?;
+					continue;
+				}
+			};
 			let output = match output {
 				modelfox_core::predict::PredictOutput::Regression(output) => {
 					vec![output.value.to_string()]
@@ -1,4 +1,4 @@
-//! This module runs an HTTP server for making predictions with a modelfox model.
+//! This module runs an HTTP server for making predictions with a modelfox model. It does not require a database, so it is a lightweight alternative to running the app when you only need to serve a single model.
 //!
 //! Start the server with a `.modelfox` file:
 //! ```not-rust
@@ -10,6 +10,10 @@
 //! $ curl -X POST http://localhost:8080/predict -H 'Content-Type: application/json' -d '{ "inputs": [{"age": 63.0,"gender": "male","chest_pain": "typical angina","resting_blood_pressure": 145.0,"cholesterol": 233.0,"fasting_blood_sugar_greater_than_120": "true","resting_ecg_result": "probable or definite left ventricular hypertrophy","exercise_max_heart_rate": 150.0,"exercise_induced_angina": "no","exercise_st_depression": 2.3,"exercise_st_slope": "downsloping","fluoroscopy_vessels_colored": "0","thallium_stress_test": "fixed defect"}]}'
 //![{"type":"binary_classification","class_name":"Positive","probability":0.560434,"feature_contributions":null}]
 //! ```
+//!
+//! The server also responds to `GET /health` for load balancer health checks and `GET /metadata` with a description of the model and the columns it expects.
+//!
+//! If the server is started with `--app-url`, predictions made with `identifiers` are logged to the app, and events posted to `/track`, such as true values, are forwarded to it.
 
 use crate::ServeArgs;
 use anyhow::Result;
@@ -18,14 +22,31 @@ use hyper::http;
 use modelfox_core::predict::{PredictInput, PredictOptions, PredictOutput};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use url::Url;
+
+struct Context {
+	model: modelfox_core::predict::Model,
+	metadata: Metadata,
+	app_url: Option<Url>,
+}
+
+#[derive(Serialize)]
+struct Metadata {
+	id: String,
+	version: String,
+	date: String,
+	#[serde(rename = "type")]
+	model_type: &'static str,
+	target_column_name: String,
+	column_names: Vec<String>,
+	classes: Option<Vec<String>>,
+}
 
 #[tokio::main]
 pub async fn serve(args: ServeArgs) -> Result<()> {
 	// Read model and create context
 	let bytes = std::fs::read(&args.model)?;
-	let model = modelfox_model::from_bytes(&bytes)?;
-	let model = modelfox_core::predict::Model::from(model);
-	let context = Arc::new(model);
+	let context = Arc::new(create_context(&bytes, args.app_url)?);
 
 	// Parse address
 	let addr = std::net::SocketAddr::new(args.address.parse()?, args.port);
@@ -35,6 +56,79 @@ pub async fn serve(args: ServeArgs) -> Result<()> {
 	Ok(())
 }
 
+fn create_context(bytes: &[u8], app_url: Option<Url>) -> Result<Context> {
+	let model = modelfox_model::from_bytes(bytes)?;
+	let metadata = compute_metadata(model);
+	let model = modelfox_core::predict::Model::from_bytes(bytes)?;
+	Ok(Context {
+		model,
+		metadata,
+		app_url,
+	})
+}
+
+fn compute_metadata(model: modelfox_model::ModelReader) -> Metadata {
+	let (model_type, target_column_name, column_names, classes) = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			let regressor = regressor.read();
+			(
+				"regressor",
+				regressor.target_column_name().to_owned(),
+				regressor
+					.train_column_stats()
+					.iter()
+					.map(|column_stats| column_stats.column_name().to_owned())
+					.collect(),
+				None,
+			)
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			let binary_classifier = binary_classifier.read();
+			(
+				"binary_classifier",
+				binary_classifier.target_column_name().to_owned(),
+				binary_classifier
+					.train_column_stats()
+					.iter()
+					.map(|column_stats| column_stats.column_name().to_owned())
+					.collect(),
+				Some(vec![
+					binary_classifier.negative_class().to_owned(),
+					binary_classifier.positive_class().to_owned(),
+				]),
+			)
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier = multiclass_classifier.read();
+			(
+				"multiclass_classifier",
+				multiclass_classifier.target_column_name().to_owned(),
+				multiclass_classifier
+					.train_column_stats()
+					.iter()
+					.map(|column_stats| column_stats.column_name().to_owned())
+					.collect(),
+				Some(
+					multiclass_classifier
+						.classes()
+						.iter()
+						.map(ToOwned::to_owned)
+						.collect(),
+				),
+			)
+		}
+	};
+	Metadata {
+		id: model.id().to_owned(),
+		version: model.version().to_owned(),
+		date: model.date().to_owned(),
+		model_type,
+		target_column_name,
+		column_names,
+		classes,
+	}
+}
+
 fn bad_request(msg: &str) -> http::Response<hyper::Body> {
 	http::Response::builder()
 		.status(http::StatusCode::BAD_REQUEST)
@@ -49,9 +143,17 @@ fn not_found() -> http::Response<hyper::Body> {
 		.unwrap()
 }
 
+fn json_response<T: Serialize>(value: &T) -> http::Response<hyper::Body> {
+	let json = serde_json::to_string(value).unwrap();
+	tracing::debug!("sending {} bytes", json.len());
+	http::Response::builder()
+		.header(http::header::CONTENT_TYPE, "application/json")
+		.body(hyper::Body::from(json))
+		.unwrap()
+}
+
 async fn predict(request: http::Request<hyper::Body>) -> http::Response<hyper::Body> {
-	let context: Arc<modelfox_core::predict::Model> =
-		Arc::clone(request.extensions().get().unwrap());
+	let context: Arc<Context> = Arc::clone(request.extensions().get().unwrap());
 	let body = request.into_body();
 	let body_bytes = hyper::body::aggregate(body).await.unwrap();
 	let inputs: PredictInputs = match serde_json::from_reader(body_bytes.reader()) {
@@ -62,30 +164,105 @@ async fn predict(request: http::Request<hyper::Body>) -> http::Response<hyper::B
 			return bad_request(&msg);
 		}
 	};
-	let outputs = PredictOutputs(modelfox_core::predict::predict(
-		&context,
-		&inputs.inputs,
-		&inputs.options.unwrap_or_default(),
-	));
-	let json = serde_json::to_string(&outputs).unwrap();
-	tracing::debug!("sending {} bytes", json.len());
+	if let Some(identifiers) = &inputs.identifiers {
+		if identifiers.len() != inputs.inputs.len() {
+			return bad_request("the number of identifiers must equal the number of inputs");
+		}
+	}
+	let options = inputs.options.unwrap_or_default();
+	let outputs =
+		match modelfox_core::predict::try_predict(&context.model, &inputs.inputs, &options) {
+			Ok(outputs) => outputs,
+			Err(e) => return bad_request(&e.to_string()),
+		};
+	if let (Some(app_url), Some(identifiers)) = (&context.app_url, inputs.identifiers) {
+		let events = identifiers
+			.into_iter()
+			.zip(inputs.inputs.into_iter())
+			.zip(outputs.iter())
+			.filter_map(|((identifier, input), output)| {
+				// Inputs that were dropped because of an invalid value have no prediction to log.
+				let output = output.as_ref()?;
+				Some(serde_json::json!({
+					"type": "prediction",
+					"model_id": context.model.id,
+					"date": chrono::Utc::now(),
+					"identifier": identifier,
+					"input": input,
+					"options": options,
+					"output": output,
+				}))
+			})
+			.collect::<Vec<_>>();
+		forward_events(app_url.clone(), serde_json::to_vec(&events).unwrap());
+	}
+	json_response(&PredictOutputs(outputs))
+}
+
+async fn track(request: http::Request<hyper::Body>) -> http::Response<hyper::Body> {
+	let context: Arc<Context> = Arc::clone(request.extensions().get().unwrap());
+	let app_url = match &context.app_url {
+		Some(app_url) => app_url.clone(),
+		None => {
+			return bad_request(
+				"events can only be tracked when the server is started with --app-url",
+			)
+		}
+	};
+	let body = match hyper::body::to_bytes(request.into_body()).await {
+		Ok(body) => body,
+		Err(e) => return bad_request(&e.to_string()),
+	};
+	forward_events(app_url, body.to_vec());
 	http::Response::builder()
-		.body(hyper::Body::from(json))
+		.status(http::StatusCode::ACCEPTED)
+		.body(hyper::Body::empty())
 		.unwrap()
 }
 
+/// Send events to the app's `/track` endpoint, below the path of `app_url` if the app is served from a base path, in the background, so that predictions are not slowed down or failed by the app being unavailable.
+fn forward_events(mut app_url: Url, body: Vec<u8>) {
+	app_url.set_path(&format!("{}/track", app_url.path().trim_end_matches('/')));
+	tokio::spawn(async move {
+		let result = reqwest::Client::new()
+			.post(app_url)
+			.header(reqwest::header::CONTENT_TYPE, "application/json")
+			.body(body)
+			.send()
+			.await
+			.and_then(|response| response.error_for_status());
+		if let Err(error) = result {
+			tracing::error!(%error, "failed to forward events to the app");
+		}
+	});
+}
+
+fn health() -> http::Response<hyper::Body> {
+	json_response(&serde_json::json!({ "status": "ok" }))
+}
+
+fn metadata(request: &http::Request<hyper::Body>) -> http::Response<hyper::Body> {
+	let context: &Arc<Context> = request.extensions().get().unwrap();
+	json_response(&context.metadata)
+}
+
 #[derive(Deserialize)]
 struct PredictInputs {
 	inputs: Vec<PredictInput>,
 	options: Option<PredictOptions>,
+	/// If the server was started with `--app-url`, a prediction event is logged to the app for each input with its identifier.
+	identifiers: Option<Vec<serde_json::Value>>,
 }
 
 #[derive(Serialize)]
-struct PredictOutputs(Vec<PredictOutput>);
+struct PredictOutputs(Vec<Option<PredictOutput>>);
 
 async fn handle(request: http::Request<hyper::Body>) -> http::Response<hyper::Body> {
 	match (request.method(), request.uri().path()) {
 		(&hyper::Method::POST, "/predict") => predict(request).await,
+		(&hyper::Method::POST, "/track") => track(request).await,
+		(&hyper::Method::GET, "/health") => health(),
+		(&hyper::Method::GET, "/metadata") => metadata(&request),
 		_ => not_found(),
 	}
 }
@@ -96,10 +273,9 @@ mod test {
 	use pretty_assertions::assert_eq;
 	use serde_json::{json, Value};
 
-	fn test_model() -> modelfox_core::predict::Model {
+	fn test_context() -> Context {
 		let bytes = std::fs::read("../../heart_disease.modelfox").unwrap();
-		let model = modelfox_model::from_bytes(&bytes).unwrap();
-		modelfox_core::predict::Model::from(model)
+		create_context(&bytes, None).unwrap()
 	}
 
 	#[tokio::test]
@@ -109,7 +285,7 @@ mod test {
 			.uri("/nonsense")
 			.body(hyper::Body::empty())
 			.unwrap();
-		let context = Arc::new(test_model());
+		let context = Arc::new(test_context());
 		request.extensions_mut().insert(Arc::clone(&context));
 		let response = handle(request).await;
 
@@ -144,7 +320,7 @@ mod test {
 			.body(hyper::Body::from(payload.to_string()))
 			.unwrap();
 
-		let context = Arc::new(test_model());
+		let context = Arc::new(test_context());
 		request.extensions_mut().insert(Arc::clone(&context));
 		let response = handle(request).await;
 
@@ -195,7 +371,7 @@ mod test {
 			.body(hyper::Body::from(payload.to_string()))
 			.unwrap();
 
-		let context = Arc::new(test_model());
+		let context = Arc::new(test_context());
 		request.extensions_mut().insert(Arc::clone(&context));
 		let response = handle(request).await;
 
@@ -448,7 +624,7 @@ mod test {
 			.header(http::header::CONTENT_TYPE, "application/json")
 			.body(hyper::Body::from(bad_payload.to_string()))
 			.unwrap();
-		let context = Arc::new(test_model());
+		let context = Arc::new(test_context());
 		request.extensions_mut().insert(Arc::clone(&context));
 		let response = handle(request).await;
 
@@ -459,4 +635,65 @@ mod test {
 			hyper::body::Bytes::from("bad request: missing field `inputs` at line 1 column 22")
 		);
 	}
+
+	#[tokio::test]
+	async fn test_health() {
+		let mut request = hyper::Request::builder()
+			.method(http::Method::GET)
+			.uri("/health")
+			.body(hyper::Body::empty())
+			.unwrap();
+		let context = Arc::new(test_context());
+		request.extensions_mut().insert(Arc::clone(&context));
+		let response = handle(request).await;
+		assert_eq!(response.status(), http::StatusCode::OK);
+		let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+		assert_eq!(body, hyper::body::Bytes::from(r#"{"status":"ok"}"#));
+	}
+
+	#[tokio::test]
+	async fn test_metadata() {
+		let mut request = hyper::Request::builder()
+			.method(http::Method::GET)
+			.uri("/metadata")
+			.body(hyper::Body::empty())
+			.unwrap();
+		let context = Arc::new(test_context());
+		request.extensions_mut().insert(Arc::clone(&context));
+		let response = handle(request).await;
+		assert_eq!(response.status(), http::StatusCode::OK);
+		let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+		let body: Value = serde_json::from_slice(&body).unwrap();
+		assert_eq!(body["type"], "binary_classifier");
+		assert_eq!(body["target_column_name"], "diagnosis");
+		assert_eq!(body["classes"], json!(["Negative", "Positive"]));
+	}
+
+	#[tokio::test]
+	async fn test_predict_mismatched_identifiers() {
+		let payload = json!({ "inputs": [{ "age": 63.0 }], "identifiers": ["a", "b"] });
+		let mut request = hyper::Request::builder()
+			.method(http::Method::POST)
+			.uri("/predict")
+			.header(http::header::CONTENT_TYPE, "application/json")
+			.body(hyper::Body::from(payload.to_string()))
+			.unwrap();
+		let context = Arc::new(test_context());
+		request.extensions_mut().insert(Arc::clone(&context));
+		let response = handle(request).await;
+		assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+	}
+
+	#[tokio::test]
+	async fn test_track_without_app_url() {
+		let mut request = hyper::Request::builder()
+			.method(http::Method::POST)
+			.uri("/track")
+			.body(hyper::Body::from("[]"))
+			.unwrap();
+		let context = Arc::new(test_context());
+		request.extensions_mut().insert(Arc::clone(&context));
+		let response = handle(request).await;
+		assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+	}
 }
@@ -1,4 +1,4 @@
-use crate::TrainArgs;
+use crate::{ProgressFormat, TrainArgs};
 use anyhow::{anyhow, bail, Result};
 use backtrace::Backtrace;
 use modelfox_core::progress::{
@@ -26,6 +26,12 @@ use tortoise::{
 
 #[cfg(feature = "train")]
 pub fn train(args: TrainArgs) -> Result<()> {
+	if args.check_config {
+		return check_config(args);
+	}
+	if args.inspect_only {
+		return inspect(args);
+	}
 	// Start the progress view if enabled and train the model. However, we need to do some extra work to make panic messages display properly. The problem is that progress is written to the terminal from another thread, which may conflict with the default panic hook. To work around this, we create a custom panic hook to store the panic message, wrap the progress view and training with `catch_unwind`, and then print the panic message if `catch_unwind` returns an `Err`. This ensures that the progress manager will be dropped before the panic message is displayed.
 	static PANIC_MESSAGE_AND_BACKTRACE: Lazy<Mutex<Option<(String, Backtrace)>>> =
 		Lazy::new(|| Mutex::new(None));
@@ -34,35 +40,30 @@ pub fn train(args: TrainArgs) -> Result<()> {
 		let value = (panic_info.to_string(), Backtrace::new());
 		PANIC_MESSAGE_AND_BACKTRACE.lock().unwrap().replace(value);
 	}));
+	let progress_format = if args.progress {
+		Some(args.progress_format)
+	} else {
+		None
+	};
 	let result = std::panic::catch_unwind(|| {
-		let mut progress_thread = if args.progress {
-			if let Ok(terminal) = Terminal::new() {
-				let progress_thread = ProgressThread::start(terminal);
-				Some(progress_thread)
-			} else {
-				None
+		let mut progress_thread = match progress_format {
+			Some(ProgressFormat::Terminal) => {
+				if let Ok(terminal) = Terminal::new() {
+					let progress_thread = ProgressThread::start(terminal);
+					Some(progress_thread)
+				} else {
+					None
+				}
 			}
-		} else {
-			None
+			Some(ProgressFormat::Json) => Some(ProgressThread::start_json()),
+			None => None,
 		};
 		let mut handle_progress_event = |progress_event| {
 			if let Some(progress_thread) = progress_thread.as_mut() {
 				progress_thread.send_progress_event(progress_event)
 			}
 		};
-		let input = match (&args.file, &args.file_train, &args.file_test, args.stdin) {
-			(None, None, None, true) => modelfox_core::train::TrainingDataSource::Stdin,
-			(Some(file_path), None, None, false) => {
-				modelfox_core::train::TrainingDataSource::File(file_path.to_owned())
-			}
-			(None, Some(file_path_train), Some(file_path_test), false) => {
-				modelfox_core::train::TrainingDataSource::TrainAndTest {
-					train: file_path_train.to_owned(),
-					test: file_path_test.to_owned(),
-				}
-			}
-			_ => bail!("Must use the stdin flag or provide training data files."),
-		};
+		let input = training_data_source(&args)?;
 		// Retrieve the output path from the command line arguments or generate a default.
 		let output_path = match (args.file, args.file_train, args.output, args.stdin) {
 			(_, _, Some(output), _) => output,
@@ -76,16 +77,18 @@ pub fn train(args: TrainArgs) -> Result<()> {
 				let csv_file_name = file_train_path.file_stem().unwrap().to_str().unwrap();
 				available_path(&dir, csv_file_name, "modelfox")?
 			}
-			_ => bail!("Must provide an output path when using stdin for input!"),
+			_ => bail!("Must provide an output path when using stdin or a database for input!"),
 		};
 		// Load the dataset, compute stats, and prepare for training.
-		let mut trainer = modelfox_core::train::Trainer::prepare(
-			modelfox_id::Id::generate(),
-			input,
-			&args.target,
-			args.config.as_deref(),
-			&mut handle_progress_event,
-		)?;
+		let mut trainer_builder = modelfox_core::train::TrainerBuilder::new(input, &args.target);
+		if let Some(config_path) = &args.config {
+			trainer_builder = trainer_builder.config_path(config_path);
+		}
+		if let Some(cache_dir) = &args.cache_dir {
+			trainer_builder = trainer_builder.cache_dir(cache_dir);
+		}
+		trainer_builder = trainer_builder.allow_leakage(args.allow_leakage);
+		let mut trainer = trainer_builder.prepare(&mut handle_progress_event)?;
 		if let Some(progress_thread) = progress_thread.as_mut() {
 			progress_thread.send_progress_event(ProgressEvent::Info(
 				"Press ctrl-c to stop early and save the best model trained so far.".to_owned(),
@@ -96,15 +99,33 @@ pub fn train(args: TrainArgs) -> Result<()> {
 				progress_thread.send_progress_event(progress_event)
 			}
 		};
+		// Write a checkpoint after each grid item so training can be resumed if it is interrupted.
+		let checkpoint_path = args.resume_from_checkpoint.clone().unwrap_or_else(|| {
+			std::env::temp_dir()
+				.join("modelfox")
+				.join(format!("{}.checkpoint.json", modelfox_id::Id::generate()))
+		});
 		let kill_chip = unsafe { ctrl_c::register_ctrl_c_handler()? };
-		let train_grid_item_outputs = trainer.train_grid(kill_chip, &mut handle_progress_event)?;
+		let train_grid_item_outputs = trainer.train_grid(
+			kill_chip,
+			Some(&checkpoint_path),
+			&mut handle_progress_event,
+		)?;
 		unsafe { ctrl_c::unregister_ctrl_c_handler()? };
 		if kill_chip.is_activated() {
 			if let Some(progress_thread) = progress_thread.as_mut() {
+				progress_thread.send_progress_event(ProgressEvent::Info(format!(
+					"A checkpoint was saved to {}. To continue training, run the same command with --resume-from-checkpoint {}.",
+					checkpoint_path.display(),
+					checkpoint_path.display(),
+				)));
 				progress_thread.send_progress_event(ProgressEvent::Info(
 					"Testing and writing the best model. Press ctrl-c again to kill the process immediately.".to_owned(),
 				))
 			}
+		} else if checkpoint_path.exists() {
+			// Training finished, so the checkpoint is no longer needed.
+			std::fs::remove_file(&checkpoint_path)?;
 		}
 		let mut handle_progress_event = |progress_event| {
 			if let Some(progress_thread) = progress_thread.as_mut() {
@@ -127,6 +148,11 @@ pub fn train(args: TrainArgs) -> Result<()> {
 
 	// Write the model to the output path.
 	model.to_path(&output_path)?;
+	if let Some(ProgressFormat::Json) = progress_format {
+		write_json_progress_event(&JsonProgressEvent::Done {
+			output_path: &output_path,
+		})?;
+	}
 
 	// Announce that everything worked!
 	eprintln!("Your model was written to {}.", output_path.display());
@@ -140,6 +166,128 @@ pub fn train(args: TrainArgs) -> Result<()> {
 	Ok(())
 }
 
+#[cfg(feature = "train")]
+fn training_data_source(args: &TrainArgs) -> Result<modelfox_core::train::TrainingDataSource> {
+	if let (Some(url), Some(query)) = (&args.database, &args.query) {
+		return Ok(modelfox_core::train::TrainingDataSource::Database {
+			url: url.to_owned(),
+			query: query.to_owned(),
+		});
+	}
+	let input = match (&args.file, &args.file_train, &args.file_test, args.stdin) {
+		(None, None, None, true) => modelfox_core::train::TrainingDataSource::Stdin,
+		(Some(file_path), None, None, false) => {
+			modelfox_core::train::TrainingDataSource::File(file_path.to_owned())
+		}
+		(None, Some(file_path_train), Some(file_path_test), false) => {
+			modelfox_core::train::TrainingDataSource::TrainAndTest {
+				train: file_path_train.to_owned(),
+				test: file_path_test.to_owned(),
+			}
+		}
+		_ => bail!("Must use the stdin flag, provide training data files, or provide a database and query."),
+	};
+	Ok(input)
+}
+
+/// Check the config file without loading the dataset or training, and print every error and warning found in it.
+#[cfg(feature = "train")]
+fn check_config(args: TrainArgs) -> Result<()> {
+	let config_path = args.config.unwrap();
+	let output = modelfox_core::config_check::check_config(&config_path)?;
+	for issue in output.issues.iter() {
+		let level = match issue.level {
+			modelfox_core::config_check::ConfigIssueLevel::Error => "error",
+			modelfox_core::config_check::ConfigIssueLevel::Warning => "warning",
+		};
+		eprintln!("{}: {}: {}", level, config_path.display(), issue);
+	}
+	let n_errors = output.errors().count();
+	let n_warnings = output.warnings().count();
+	if n_errors > 0 {
+		bail!(
+			"The config {} has {} errors and {} warnings.",
+			config_path.display(),
+			n_errors,
+			n_warnings
+		);
+	}
+	eprintln!(
+		"The config {} is valid, with {} warnings.",
+		config_path.display(),
+		n_warnings
+	);
+	Ok(())
+}
+
+/// Load the dataset and compute column stats without training, print a report of the column types, and write a starter config with every column's type set explicitly.
+#[cfg(feature = "train")]
+fn inspect(args: TrainArgs) -> Result<()> {
+	let input = training_data_source(&args)?;
+	// Retrieve the output path from the command line arguments or generate a default.
+	let output_path = match (&args.file, &args.file_train, args.output, args.stdin) {
+		(_, _, Some(output), _) => output,
+		(Some(file), _, None, false) | (None, Some(file), None, false) => {
+			let dir = std::env::current_dir()?;
+			let csv_file_name = file.file_stem().unwrap().to_str().unwrap();
+			available_path(&dir, csv_file_name, "config.json")?
+		}
+		_ => bail!("Must provide an output path when using stdin or a database for input!"),
+	};
+	let mut progress_thread = if args.progress {
+		Terminal::new().ok().map(ProgressThread::start)
+	} else {
+		None
+	};
+	let mut handle_progress_event = |progress_event| {
+		if let Some(progress_thread) = progress_thread.as_mut() {
+			progress_thread.send_progress_event(progress_event)
+		}
+	};
+	let output =
+		modelfox_core::inspect::inspect(input, args.config.as_deref(), &mut handle_progress_event)?;
+	drop(progress_thread);
+	output.target_column(&args.target)?;
+
+	// Print the report.
+	println!("{} rows", output.row_count);
+	for column in output.columns.iter() {
+		print!("{}: {}", column.name, column.column_type);
+		if let Some(unique_count) = column.unique_count {
+			print!(", {} unique", unique_count);
+		}
+		if column.invalid_count > 0 {
+			print!(", {} invalid", column.invalid_count);
+		}
+		println!();
+		if let Some(variants) = &column.suggested_variants {
+			println!("  variants: {}", variants.join(", "));
+		}
+		if column.suggested_column_type != column.column_type {
+			let number_fraction = column.number_fraction.unwrap_or(0.0);
+			println!(
+				"  {:.1}% of values are numbers, so the starter config uses the type {}. These values are not numbers and will be treated as invalid: {}",
+				number_fraction * 100.0,
+				column.suggested_column_type,
+				column.non_numeric_examples.join(", "),
+			);
+		}
+	}
+
+	// Write the starter config to the output path.
+	std::fs::write(
+		&output_path,
+		serde_json::to_string_pretty(&output.starter_config())?,
+	)?;
+	eprintln!("A config file was written to {}.", output_path.display());
+	eprintln!(
+		"Edit the column types if necessary, then train with `modelfox train --config {}`.",
+		output_path.display()
+	);
+
+	Ok(())
+}
+
 pub struct ProgressThread {
 	thread: Option<JoinHandle<Result<()>>>,
 	sender: Option<Sender<Option<ProgressEvent>>>,
@@ -155,6 +303,16 @@ impl ProgressThread {
 		}
 	}
 
+	/// Start a thread that writes progress events to stdout as newline delimited json for orchestration systems to parse, instead of drawing progress bars.
+	pub fn start_json() -> ProgressThread {
+		let (sender, receiver) = channel::<Option<ProgressEvent>>();
+		let thread = Some(spawn(move || json_progress_thread_main(receiver)));
+		ProgressThread {
+			thread,
+			sender: Some(sender),
+		}
+	}
+
 	pub fn send_progress_event(&mut self, progress_event: ProgressEvent) {
 		self.sender
 			.as_ref()
@@ -263,6 +421,17 @@ fn progress_thread_handle_progress_event(
 				modelfox_table::ProgressEvent::LoadDone => {
 					finish_progress_bar(terminal, state)?;
 				}
+				modelfox_table::ProgressEvent::InvalidValuesDropped {
+					column_name,
+					n_rows,
+				} => {
+					let message = invalid_values_dropped_message(&column_name, n_rows);
+					progress_thread_handle_progress_event(
+						terminal,
+						state,
+						ProgressEvent::Warning(message),
+					)?;
+				}
 			},
 			LoadProgressEvent::Test(progress_event) => match progress_event {
 				modelfox_table::ProgressEvent::InferStarted(progress_counter) => {
@@ -289,6 +458,17 @@ fn progress_thread_handle_progress_event(
 				modelfox_table::ProgressEvent::LoadDone => {
 					finish_progress_bar(terminal, state)?;
 				}
+				modelfox_table::ProgressEvent::InvalidValuesDropped {
+					column_name,
+					n_rows,
+				} => {
+					let message = invalid_values_dropped_message(&column_name, n_rows);
+					progress_thread_handle_progress_event(
+						terminal,
+						state,
+						ProgressEvent::Warning(message),
+					)?;
+				}
 			},
 			LoadProgressEvent::Shuffle => {
 				start(terminal, state, "🎰 Shuffling.".into())?;
@@ -462,6 +642,8 @@ fn progress_thread_handle_progress_event(
 						}
 					}
 				}
+				// The comparison metric value is written as an info message.
+				TrainGridItemProgressEvent::Done { .. } => {}
 			}
 		}
 		ProgressEvent::Test(progress_event) => match progress_event {
@@ -666,6 +848,334 @@ const EMPTY_CHAR: char = ' ';
 const FILL_CHAR: char = '=';
 const ARROW_CHAR: char = '>';
 
+/// This is how often the json progress view writes the progress of the current stage.
+const JSON_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A progress event written by `modelfox train --progress json`. Each event is written to stdout as one line of json.
+#[derive(serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JsonProgressEvent<'a> {
+	Info {
+		message: &'a str,
+	},
+	Warning {
+		message: &'a str,
+	},
+	StageStarted {
+		stage: &'static str,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		grid_item: Option<JsonGridItem>,
+	},
+	StageProgress {
+		stage: &'static str,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		grid_item: Option<JsonGridItem>,
+		value: u64,
+		total: u64,
+		percent: f64,
+		elapsed_seconds: f64,
+		eta_seconds: Option<f64>,
+	},
+	StageDone {
+		stage: &'static str,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		grid_item: Option<JsonGridItem>,
+		duration_seconds: f64,
+	},
+	GridItemDone {
+		grid_item: JsonGridItem,
+		comparison_metric: &'a str,
+		comparison_metric_value: f32,
+	},
+	Done {
+		output_path: &'a Path,
+	},
+}
+
+#[derive(Clone, Copy, serde::Serialize)]
+struct JsonGridItem {
+	index: usize,
+	count: usize,
+}
+
+/// The json progress view only distinguishes stages starting and finishing, so each progress event is first mapped to one of these.
+enum JsonStageEvent {
+	Info(String),
+	Warning(String),
+	Started {
+		stage: &'static str,
+		grid_item: Option<JsonGridItem>,
+		progress_counter: Option<ProgressCounter>,
+	},
+	Done,
+	GridItemDone {
+		grid_item: JsonGridItem,
+		comparison_metric: String,
+		comparison_metric_value: f32,
+	},
+}
+
+struct JsonStage {
+	stage: &'static str,
+	grid_item: Option<JsonGridItem>,
+	progress_counter: Option<ProgressCounter>,
+	timer: Timer,
+}
+
+impl JsonStage {
+	fn progress_event(&self) -> Option<JsonProgressEvent<'static>> {
+		let progress_counter = self.progress_counter.as_ref()?;
+		let value = progress_counter.get();
+		let total = progress_counter.total();
+		let fraction = value.to_f64().unwrap() / total.to_f64().unwrap();
+		let fraction = if fraction.is_nan() { 0.0 } else { fraction };
+		let elapsed_seconds = self.timer.0.elapsed().as_secs_f64();
+		let eta_seconds = if fraction > std::f64::EPSILON && fraction <= 1.0 {
+			Some((elapsed_seconds / fraction) - elapsed_seconds)
+		} else {
+			None
+		};
+		Some(JsonProgressEvent::StageProgress {
+			stage: self.stage,
+			grid_item: self.grid_item,
+			value,
+			total,
+			percent: fraction * 100.0,
+			elapsed_seconds,
+			eta_seconds,
+		})
+	}
+}
+
+fn json_progress_thread_main(receiver: Receiver<Option<ProgressEvent>>) -> Result<()> {
+	let mut current_stage: Option<JsonStage> = None;
+	loop {
+		let has_progress_counter = current_stage
+			.as_ref()
+			.map(|stage| stage.progress_counter.is_some())
+			.unwrap_or(false);
+		let progress_event = if has_progress_counter {
+			match receiver.recv_timeout(JSON_PROGRESS_INTERVAL) {
+				Ok(Some(progress_event)) => Some(progress_event),
+				Err(RecvTimeoutError::Timeout) => None,
+				Ok(None) | Err(RecvTimeoutError::Disconnected) => break,
+			}
+		} else {
+			match receiver.recv() {
+				Ok(Some(progress_event)) => Some(progress_event),
+				Ok(None) | Err(_) => break,
+			}
+		};
+		let progress_event = match progress_event {
+			Some(progress_event) => progress_event,
+			None => {
+				if let Some(event) = current_stage
+					.as_ref()
+					.and_then(|stage| stage.progress_event())
+				{
+					write_json_progress_event(&event)?;
+				}
+				continue;
+			}
+		};
+		match json_stage_event(progress_event) {
+			JsonStageEvent::Info(message) => {
+				write_json_progress_event(&JsonProgressEvent::Info { message: &message })?;
+			}
+			JsonStageEvent::Warning(message) => {
+				write_json_progress_event(&JsonProgressEvent::Warning { message: &message })?;
+			}
+			JsonStageEvent::Started {
+				stage,
+				grid_item,
+				progress_counter,
+			} => {
+				write_json_progress_event(&JsonProgressEvent::StageStarted { stage, grid_item })?;
+				current_stage = Some(JsonStage {
+					stage,
+					grid_item,
+					progress_counter,
+					timer: Timer::start(),
+				});
+			}
+			JsonStageEvent::Done => {
+				if let Some(stage) = current_stage.take() {
+					write_json_progress_event(&JsonProgressEvent::StageDone {
+						stage: stage.stage,
+						grid_item: stage.grid_item,
+						duration_seconds: stage.timer.stop().as_secs_f64(),
+					})?;
+				}
+			}
+			JsonStageEvent::GridItemDone {
+				grid_item,
+				comparison_metric,
+				comparison_metric_value,
+			} => {
+				write_json_progress_event(&JsonProgressEvent::GridItemDone {
+					grid_item,
+					comparison_metric: &comparison_metric,
+					comparison_metric_value,
+				})?;
+			}
+		}
+	}
+	Ok(())
+}
+
+fn invalid_values_dropped_message(column_name: &str, n_rows: usize) -> String {
+	format!(
+		"Dropped {} {} with invalid values in the column \"{}\".",
+		n_rows,
+		if n_rows == 1 { "row" } else { "rows" },
+		column_name,
+	)
+}
+
+fn json_stage_event(progress_event: ProgressEvent) -> JsonStageEvent {
+	let started = |stage, progress_counter| JsonStageEvent::Started {
+		stage,
+		grid_item: None,
+		progress_counter,
+	};
+	match progress_event {
+		ProgressEvent::Info(message) => JsonStageEvent::Info(message),
+		ProgressEvent::Warning(message) => JsonStageEvent::Warning(message),
+		ProgressEvent::Load(progress_event) => match progress_event {
+			LoadProgressEvent::Train(progress_event) => match progress_event {
+				modelfox_table::ProgressEvent::InferStarted(progress_counter) => {
+					started("infer_train_columns", Some(progress_counter))
+				}
+				modelfox_table::ProgressEvent::LoadStarted(progress_counter) => {
+					started("load_train_table", Some(progress_counter))
+				}
+				modelfox_table::ProgressEvent::InferDone
+				| modelfox_table::ProgressEvent::LoadDone => JsonStageEvent::Done,
+				modelfox_table::ProgressEvent::InvalidValuesDropped {
+					column_name,
+					n_rows,
+				} => JsonStageEvent::Warning(invalid_values_dropped_message(&column_name, n_rows)),
+			},
+			LoadProgressEvent::Test(progress_event) => match progress_event {
+				modelfox_table::ProgressEvent::InferStarted(progress_counter) => {
+					started("infer_test_columns", Some(progress_counter))
+				}
+				modelfox_table::ProgressEvent::LoadStarted(progress_counter) => {
+					started("load_test_table", Some(progress_counter))
+				}
+				modelfox_table::ProgressEvent::InferDone
+				| modelfox_table::ProgressEvent::LoadDone => JsonStageEvent::Done,
+				modelfox_table::ProgressEvent::InvalidValuesDropped {
+					column_name,
+					n_rows,
+				} => JsonStageEvent::Warning(invalid_values_dropped_message(&column_name, n_rows)),
+			},
+			LoadProgressEvent::Shuffle => started("shuffle", None),
+			LoadProgressEvent::ShuffleDone => JsonStageEvent::Done,
+		},
+		ProgressEvent::Stats(progress_event) => match progress_event {
+			StatsProgressEvent::ComputeTrainStats(progress_counter) => {
+				started("compute_train_stats", Some(progress_counter))
+			}
+			StatsProgressEvent::ComputeTestStats(progress_counter) => {
+				started("compute_test_stats", Some(progress_counter))
+			}
+			StatsProgressEvent::Finalize => started("finalize_stats", None),
+			StatsProgressEvent::ComputeTrainStatsDone
+			| StatsProgressEvent::ComputeTestStatsDone
+			| StatsProgressEvent::FinalizeDone => JsonStageEvent::Done,
+		},
+		ProgressEvent::ComputeBaselineMetrics(progress_counter) => {
+			started("compute_baseline_metrics", Some(progress_counter))
+		}
+		ProgressEvent::ComputeBaselineMetricsDone => JsonStageEvent::Done,
+		ProgressEvent::Train(TrainProgressEvent {
+			grid_item_index,
+			grid_item_count,
+			grid_item_progress_event,
+		}) => {
+			let grid_item = JsonGridItem {
+				index: grid_item_index,
+				count: grid_item_count,
+			};
+			let started = |stage, progress_counter| JsonStageEvent::Started {
+				stage,
+				grid_item: Some(grid_item),
+				progress_counter: Some(progress_counter),
+			};
+			match grid_item_progress_event {
+				TrainGridItemProgressEvent::ComputeFeatures(progress_counter) => {
+					started("compute_features", progress_counter)
+				}
+				TrainGridItemProgressEvent::TrainModel(
+					modelfox_core::progress::ModelTrainProgressEvent::Linear(progress_event),
+				) => match progress_event {
+					modelfox_core::progress::LinearTrainProgressEvent::Train(progress_counter) => {
+						started("train_model", progress_counter)
+					}
+					modelfox_core::progress::LinearTrainProgressEvent::TrainDone => {
+						JsonStageEvent::Done
+					}
+				},
+				TrainGridItemProgressEvent::TrainModel(
+					modelfox_core::progress::ModelTrainProgressEvent::Tree(progress_event),
+				) => match progress_event {
+					modelfox_core::progress::TreeTrainProgressEvent::Initialize(
+						progress_counter,
+					) => started("initialize_model", progress_counter),
+					modelfox_core::progress::TreeTrainProgressEvent::Train(progress_counter) => {
+						started("train_model", progress_counter)
+					}
+					modelfox_core::progress::TreeTrainProgressEvent::InitializeDone
+					| modelfox_core::progress::TreeTrainProgressEvent::TrainDone => JsonStageEvent::Done,
+				},
+				TrainGridItemProgressEvent::ComputeModelComparisonMetrics(progress_event) => {
+					match progress_event {
+						modelfox_core::progress::ModelTestProgressEvent::ComputeFeatures(
+							progress_counter,
+						) => started("compute_comparison_features", progress_counter),
+						modelfox_core::progress::ModelTestProgressEvent::Test(progress_counter) => {
+							started("compute_comparison_metrics", progress_counter)
+						}
+						modelfox_core::progress::ModelTestProgressEvent::ComputeFeaturesDone
+						| modelfox_core::progress::ModelTestProgressEvent::TestDone => JsonStageEvent::Done,
+					}
+				}
+				TrainGridItemProgressEvent::ComputeFeaturesDone => JsonStageEvent::Done,
+				TrainGridItemProgressEvent::Done {
+					comparison_metric,
+					comparison_metric_value,
+				} => JsonStageEvent::GridItemDone {
+					grid_item,
+					comparison_metric,
+					comparison_metric_value,
+				},
+			}
+		}
+		ProgressEvent::Test(progress_event) => match progress_event {
+			modelfox_core::progress::ModelTestProgressEvent::ComputeFeatures(progress_counter) => {
+				started("compute_test_features", Some(progress_counter))
+			}
+			modelfox_core::progress::ModelTestProgressEvent::Test(progress_counter) => {
+				started("test", Some(progress_counter))
+			}
+			modelfox_core::progress::ModelTestProgressEvent::ComputeFeaturesDone
+			| modelfox_core::progress::ModelTestProgressEvent::TestDone => JsonStageEvent::Done,
+		},
+		ProgressEvent::Finalize => started("finalize", None),
+		ProgressEvent::FinalizeDone => JsonStageEvent::Done,
+	}
+}
+
+fn write_json_progress_event(event: &JsonProgressEvent) -> Result<()> {
+	let stdout = std::io::stdout();
+	let mut stdout = stdout.lock();
+	serde_json::to_writer(&mut stdout, event)?;
+	writeln!(stdout)?;
+	stdout.flush()?;
+	Ok(())
+}
+
 pub struct DisplayBytes(pub u64);
 
 impl std::fmt::Display for DisplayBytes {
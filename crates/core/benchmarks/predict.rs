@@ -0,0 +1,48 @@
+use modelfox_core::predict::{predict, Model, PredictInput, PredictInputValue, PredictOptions};
+use serde_json::json;
+use std::{path::Path, time::Instant};
+
+/// Compare the latency of making a prediction for a single example with and without computing its feature contributions, which is what the production prediction page does. Run this from the root of the repo.
+fn main() {
+	// Load the model and the examples to predict.
+	let bytes = std::fs::read(Path::new("heart_disease.modelfox")).unwrap();
+	let model = Model::from(modelfox_model::from_bytes(&bytes).unwrap());
+	let mut reader = csv::Reader::from_path(Path::new("heart_disease.csv")).unwrap();
+	let headers = reader.headers().unwrap().clone();
+	let inputs: Vec<PredictInput> = reader
+		.records()
+		.map(|record| {
+			let record = record.unwrap();
+			let mut input = PredictInput::new();
+			for (header, value) in headers.iter().zip(record.iter()) {
+				input.0.insert(
+					header.to_owned(),
+					PredictInputValue::String(value.to_owned()),
+				);
+			}
+			input
+		})
+		.collect();
+
+	// Make a prediction for each example one at a time.
+	let mut output = serde_json::Map::new();
+	for (name, compute_feature_contributions) in [
+		("predict", false),
+		("predict_with_feature_contributions", true),
+	] {
+		let options = PredictOptions {
+			compute_feature_contributions,
+			..Default::default()
+		};
+		let start = Instant::now();
+		for input in inputs.iter() {
+			predict(&model, std::slice::from_ref(input), &options);
+		}
+		let duration = start.elapsed() / inputs.len() as u32;
+		output.insert(
+			format!("{}_duration_per_example_us", name),
+			json!(duration.as_secs_f64() * 1e6),
+		);
+	}
+	println!("{}", serde_json::Value::Object(output));
+}
@@ -0,0 +1,126 @@
+/*!
+Training writes a checkpoint after each grid item finishes so an interrupted training can be resumed. The checkpoint records the comparison metric value and duration of each finished grid item. Trained models are not stored, so when training is resumed, the finished grid items are skipped except for the best one, which is trained again to recover its model.
+*/
+
+use anyhow::{bail, Result};
+use std::{
+	collections::hash_map::DefaultHasher,
+	hash::{Hash, Hasher},
+	path::Path,
+	time::Duration,
+};
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct TrainCheckpoint {
+	/// This identifies the dataset and grid the checkpoint was written for, so a checkpoint is not resumed with a different dataset or config.
+	pub fingerprint: u64,
+	pub grid_items: Vec<GridItemCheckpoint>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct GridItemCheckpoint {
+	pub grid_item_index: usize,
+	pub comparison_metric_value: f32,
+	pub duration: f64,
+}
+
+impl TrainCheckpoint {
+	pub fn new(fingerprint: u64) -> TrainCheckpoint {
+		TrainCheckpoint {
+			fingerprint,
+			grid_items: Vec::new(),
+		}
+	}
+
+	/// Read the checkpoint at `path`, or create a new one if the file does not exist.
+	pub fn read_or_new(path: &Path, fingerprint: u64) -> Result<TrainCheckpoint> {
+		if !path.exists() {
+			return Ok(TrainCheckpoint::new(fingerprint));
+		}
+		let checkpoint: TrainCheckpoint = serde_json::from_slice(&std::fs::read(path)?)?;
+		if checkpoint.fingerprint != fingerprint {
+			bail!(
+				"The checkpoint at {} was written for a different dataset or config.",
+				path.display()
+			);
+		}
+		Ok(checkpoint)
+	}
+
+	/// Write the checkpoint to a temporary file and then rename it, so an interruption while writing does not corrupt an existing checkpoint.
+	pub fn write(&self, path: &Path) -> Result<()> {
+		if let Some(parent) = path.parent() {
+			std::fs::create_dir_all(parent)?;
+		}
+		let temp_path = path.with_extension("tmp");
+		std::fs::write(&temp_path, serde_json::to_vec(self)?)?;
+		std::fs::rename(&temp_path, path)?;
+		Ok(())
+	}
+
+	pub fn get(&self, grid_item_index: usize) -> Option<&GridItemCheckpoint> {
+		self.grid_items
+			.iter()
+			.find(|grid_item| grid_item.grid_item_index == grid_item_index)
+	}
+
+	pub fn record(
+		&mut self,
+		grid_item_index: usize,
+		comparison_metric_value: f32,
+		duration: Duration,
+	) {
+		self.grid_items
+			.retain(|grid_item| grid_item.grid_item_index != grid_item_index);
+		self.grid_items.push(GridItemCheckpoint {
+			grid_item_index,
+			comparison_metric_value,
+			duration: duration.as_secs_f64(),
+		});
+	}
+
+	/// Find the finished grid item with the best finite comparison metric value.
+	pub fn best_grid_item_index(&self, higher_is_better: bool) -> Option<usize> {
+		self.grid_items
+			.iter()
+			.filter(|grid_item| grid_item.comparison_metric_value.is_finite())
+			.max_by(|a, b| {
+				let ordering = a
+					.comparison_metric_value
+					.partial_cmp(&b.comparison_metric_value)
+					.unwrap();
+				if higher_is_better {
+					ordering
+				} else {
+					ordering.reverse()
+				}
+			})
+			.map(|grid_item| grid_item.grid_item_index)
+	}
+}
+
+/// Compute a fingerprint from anything that determines the grid items' results. This uses the `Debug` representation, so it is only stable across runs of the same version of modelfox.
+pub fn fingerprint(value: &impl std::fmt::Debug) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	format!("{:?}", value).hash(&mut hasher);
+	hasher.finish()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_best_grid_item_index() {
+		let mut checkpoint = TrainCheckpoint::new(0);
+		checkpoint.record(0, 0.5, Duration::from_secs(1));
+		checkpoint.record(1, 0.7, Duration::from_secs(1));
+		checkpoint.record(2, f32::NAN, Duration::from_secs(1));
+		checkpoint.record(3, 0.2, Duration::from_secs(1));
+		assert_eq!(checkpoint.best_grid_item_index(true), Some(1));
+		assert_eq!(checkpoint.best_grid_item_index(false), Some(3));
+		checkpoint.record(1, 0.1, Duration::from_secs(1));
+		assert_eq!(checkpoint.grid_items.len(), 4);
+		assert_eq!(checkpoint.best_grid_item_index(true), Some(0));
+	}
+}
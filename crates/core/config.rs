@@ -4,37 +4,76 @@ This module defines the `Config` struct, which is used to configure training a m
 
 /// This is a configuration used for training.
 #[derive(Debug, Default, serde::Deserialize)]
-#[serde(default, deny_unknown_fields)]
+#[serde(default)]
 pub struct Config {
 	/// Use this field to configure the loading, shuffling, and interpretation of your dataset.
 	pub dataset: Dataset,
 	/// Use this field to configure feature engineering.
 	pub features: Features,
+	/// Use this field to configure the column stats computed on the dataset and stored in the model.
+	pub stats: Stats,
 	// Use this field to configure training.
 	pub train: Train,
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(default, deny_unknown_fields)]
+#[serde(default)]
 pub struct Dataset {
 	/// This option controls shuffling of the dataset before splitting and training.
 	pub shuffle: Shuffle,
-	/// This is the fraction of the train dataset that will be set aside for choosing the best model. The default value is `0.1`.
+	/// This is the fraction of the train dataset that will be set aside for choosing the best model. If you do not provide a separate test dataset, it is a fraction of the whole dataset, and together with `test_fraction` it must leave some rows for training. The default value is `0.1`.
 	pub comparison_fraction: f32,
 	/// If you do not provide a separate test dataset, this is the fraction of the train dataset that will be set aside after shuffling to evalute your model. The default value is `0.2`.
 	pub test_fraction: f32,
 	/// Use this field to specify the column types for a subset of the columns. If you do not configure a column here, its configuration will be inferred.
 	pub columns: Vec<Column>,
+	/// Use this field to evaluate each grid item with cross validation instead of a single comparison dataset.
+	pub cross_validation: Option<CrossValidation>,
+	/// This is the name of a column that identifies groups of related rows, such as the rows for a single patient, customer, or session. All the rows in a group are placed in the same one of the train, comparison, and test datasets, and in the same cross validation window, so models are never evaluated on a group they were trained on.
+	pub split_group_column: Option<String>,
+	/// These are the names of columns to remove from the dataset after it is loaded. Ignored columns are not used as features and their stats are not stored in the model. They can still be used as the `split_group_column` or the rolling cross validation `date_column_name`.
+	pub ignore_columns: Vec<String>,
+	/// Use this field to configure the check for columns that leak the target.
+	pub leakage_guard: LeakageGuard,
+}
+
+/// The leakage guard screens each column on its own before training. A column that predicts the target almost perfectly by itself is usually derived from the target or only known after it, so a model trained with it would do much worse in production than its test metrics suggest. If any column trips the guard, training stops with an error, unless leakage is allowed with the `--allow-leakage` flag, in which case a warning is shown for each column instead.
+#[derive(Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct LeakageGuard {
+	/// Set this to `false` to skip the screening. The default value is `true`.
+	pub enable: bool,
+	/// For binary classification, a number or enum column trips the guard if its AUC on its own is at least this. The default value is `0.999`.
+	pub max_auc: f32,
+	/// For regression, a number column trips the guard if the absolute value of its correlation with the target is at least this. For multiclass classification, an enum column trips the guard if its Cramér's V with the target is at least this. The default value is `0.999`.
+	pub max_correlation: f32,
 }
 
 /// This option controls whether the dataset should be shuffled before splitting and training.
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct Shuffle {
 	pub enable: bool,
 	pub seed: u64,
 }
 
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "strategy")]
+pub enum CrossValidation {
+	#[serde(rename = "rolling")]
+	Rolling(RollingCrossValidation),
+}
+
+/// Rolling origin cross validation is suited to time-dependent datasets. The dataset is ordered by a date column instead of being shuffled, and each grid item is evaluated on `folds` consecutive validation windows, each trained on only the rows that precede it. The last `folds * horizon` rows before the test dataset are used for validation.
+#[derive(Debug, serde::Deserialize)]
+pub struct RollingCrossValidation {
+	/// This is the name of the column to order the dataset by. Number and datetime columns are ordered numerically, and enum and text columns are ordered lexicographically, which is correct for ISO 8601 dates. Rows with a missing value are ordered first, and rows with the same value keep the order they were loaded in.
+	pub date_column_name: String,
+	/// This is the number of validation windows.
+	pub folds: usize,
+	/// This is the number of rows in each validation window. If you do not specify this option, each window will have as many rows as the comparison dataset would have.
+	pub horizon: Option<usize>,
+}
+
 #[derive(Debug, serde::Deserialize)]
 #[serde(tag = "type")]
 pub enum Column {
@@ -46,44 +85,104 @@ pub enum Column {
 	Enum(EnumColumn),
 	#[serde(rename = "text")]
 	Text(TextColumn),
+	#[serde(rename = "datetime")]
+	DateTime(DateTimeColumn),
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct UnknownColumn {
 	pub name: String,
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct NumberColumn {
 	pub name: String,
+	/// This controls what happens to values that are not numbers.
+	#[serde(default)]
+	pub invalid_value_policy: InvalidValuePolicy,
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct EnumColumn {
 	pub name: String,
 	pub variants: Vec<String>,
+	/// This controls what happens to values that are not one of the variants.
+	#[serde(default)]
+	pub invalid_value_policy: InvalidValuePolicy,
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct TextColumn {
 	pub name: String,
 }
 
+#[derive(Debug, serde::Deserialize)]
+pub struct DateTimeColumn {
+	pub name: String,
+	/// This is the IANA name of the timezone, such as "America/New_York", used to interpret values that do not specify an offset. If you do not specify this option, such values are interpreted as UTC.
+	pub timezone: Option<String>,
+	/// This controls what happens to values that do not parse as a date or datetime.
+	#[serde(default)]
+	pub invalid_value_policy: InvalidValuePolicy,
+}
+
+/// This option controls what happens when a value in a column fails to parse for the column's type, both when training and when making predictions. Values that are empty or one of the common representations of a missing value, such as "NA" or "null", are always treated as missing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum InvalidValuePolicy {
+	/// Treat the value as missing. This is the default.
+	#[serde(rename = "missing")]
+	Missing,
+	/// Drop the row, and report how many rows were dropped with a warning. When making predictions, no prediction is made for the row.
+	#[serde(rename = "drop_row")]
+	DropRow,
+	/// Stop training, or fail the prediction, with an error.
+	#[serde(rename = "error")]
+	Error,
+}
+
+impl Default for InvalidValuePolicy {
+	fn default() -> InvalidValuePolicy {
+		InvalidValuePolicy::Missing
+	}
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct Stats {
+	/// Number columns with at most this many unique values store a histogram of every unique value. The default value is `100`.
+	pub number_histogram_max_size: usize,
+	/// Number columns with more than this many unique values estimate their quantiles with a sketch, which takes a fixed amount of memory, instead of counting every unique value. The default value is `100000`.
+	pub number_exact_quantiles_max_unique_count: usize,
+	/// This is the number of equal width bins in the histogram for datetime columns. The default value is `20`.
+	pub datetime_histogram_bin_count: usize,
+	/// These are the quantiles, between 0 and 1, computed for number columns and shown on the training and production stats pages. The default value is `[0.25, 0.5, 0.75]`.
+	pub number_quantiles: Vec<f32>,
+}
+
 #[derive(Debug, Default, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct Features {
 	/// Use this field to control automatic feature engineering.
 	pub auto: AutoFeatures,
 	/// Use this field to include custom feature groups.
 	pub include: Option<Vec<FeatureGroup>>,
+	/// Use this field to drop uninformative columns from automatic feature engineering before training. Columns with near zero variance, mostly invalid values, or a high correlation with another number column are dropped, and the dropped columns are recorded in the model.
+	pub selection: Option<FeatureSelection>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct FeatureSelection {
+	/// Enable or disable feature selection.
+	pub enable: bool,
+	/// Number columns whose variance is at most this value are dropped. The default value is `0.0`, which drops constant columns.
+	pub min_variance: f32,
+	/// Columns with a larger fraction of invalid values than this are dropped. The default value is `0.9`.
+	pub max_invalid_fraction: f32,
+	/// Of two number columns whose absolute correlation is larger than this, the later one is dropped. The default value is `0.98`.
+	pub max_correlation: f32,
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct AutoFeatures {
 	/// Enable or disable automatic feature engineering.
 	pub enable: bool,
@@ -104,36 +203,51 @@ pub enum FeatureGroup {
 	BagOfWords(BagOfWordsFeatureGroup),
 	#[serde(rename = "bag_of_words_cosine_similarity")]
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureGroup),
+	#[serde(rename = "cyclical")]
+	Cyclical(CyclicalFeatureGroup),
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct IdentityFeatureGroup {
 	pub source_column_name: String,
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct NormalizedFeatureGroup {
 	pub source_column_name: String,
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct OneHotEncodedFeatureGroup {
 	pub source_column_name: String,
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct BagOfWordsFeatureGroup {
 	pub source_column_name: String,
+	/// This is how each ngram's feature value is computed: `"present"` (or `"binary"`) is 1 if the ngram appears, `"count"` is the number of times it appears, and `"tfidf"` weights the count by the ngram's inverse document frequency, which is stored in the model, and normalizes each row to unit length.
 	pub strategy: Option<BagOfWordsFeatureGroupStrategy>,
+	/// This is the maximum number of ngrams to keep, choosing those that appear in the most rows. The default is to keep every ngram in the column's stats.
+	pub max_vocabulary_size: Option<usize>,
+	/// Ngrams appearing in fewer rows than this are dropped.
+	pub min_document_frequency: Option<DocumentFrequency>,
+	/// Ngrams appearing in more rows than this are dropped.
+	pub max_document_frequency: Option<DocumentFrequency>,
+	/// Ngrams made up entirely of these tokens are dropped, in addition to the stop words of the column's detected language. Tokens are compared case insensitively.
+	pub stop_words: Option<Vec<String>>,
+}
+
+/// A document frequency is either an absolute number of rows, such as `5`, or a fraction of the rows, such as `0.5`.
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+pub enum DocumentFrequency {
+	Count(u64),
+	Fraction(f32),
 }
 
 #[derive(Debug, serde::Deserialize)]
 pub enum BagOfWordsFeatureGroupStrategy {
-	#[serde(rename = "present")]
+	#[serde(rename = "present", alias = "binary")]
 	Present,
 	#[serde(rename = "count")]
 	Count,
@@ -142,14 +256,21 @@ pub enum BagOfWordsFeatureGroupStrategy {
 }
 
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct BagOfWordsCosineSimilarityFeatureGroup {
 	pub source_column_name_a: String,
 	pub source_column_name_b: String,
 }
 
+#[derive(Debug, serde::Deserialize)]
+pub struct CyclicalFeatureGroup {
+	pub source_column_name: String,
+	/// This is the length of one cycle in the units of the source column, for example 24 for an hour of the day or 360 for a direction in degrees. For a datetime column, it is in seconds.
+	pub period: f32,
+	/// This is the value at which each cycle starts. The default is 0.
+	pub offset: Option<f32>,
+}
+
 #[derive(Debug, Default, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct Train {
 	/// Use `autogrid` to control how the default hyperparameter grid is computed.
 	pub autogrid: Option<AutoGridOptions>,
@@ -157,6 +278,24 @@ pub struct Train {
 	pub grid: Option<Vec<GridItem>>,
 	/// This is the metric that will be computed on the comparison dataset to choose the best model.
 	pub comparison_metric: Option<ComparisonMetric>,
+	/// Use this field to restrict which columns tree models may combine. Each item is a group of column names, and every column split on along a path from the root of a tree to a leaf must belong to a single group. Columns that are not in any group may only be split on along with themselves. Linear models are not affected.
+	pub interaction_constraints: Option<Vec<Vec<String>>>,
+	/// If this is `true`, once the best model in the grid is chosen, it is trained again with the same hyperparameters on the train and comparison datasets together, before it is evaluated on the test dataset. The comparison metrics shown for each model are still the ones computed before retraining. The default value is `false`.
+	#[serde(default)]
+	pub retrain_best_model: bool,
+	/// If your model is a regressor, use this field to compute the tolerance accuracy, which is the fraction of predictions within this tolerance of the true value, on the test dataset and in production.
+	pub regression_tolerance: Option<RegressionTolerance>,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum RegressionTolerance {
+	/// A prediction is within the tolerance if it differs from the true value by at most `value`.
+	#[serde(rename = "absolute")]
+	Absolute { value: f32 },
+	/// A prediction is within the tolerance if it differs from the true value by at most `value` times the magnitude of the true value.
+	#[serde(rename = "relative")]
+	Relative { value: f32 },
 }
 
 #[derive(Debug, Default, serde::Deserialize)]
@@ -184,7 +323,6 @@ pub enum GridItem {
 
 /// These are the options used for training linear models.
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct LinearGridItem {
 	/// Specify options for early stopping. If the value is `Some`, early stopping will be enabled. If it is `None`, early stopping will be disabled.
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
@@ -192,18 +330,63 @@ pub struct LinearGridItem {
 	pub l2_regularization: Option<f32>,
 	/// This is the learning rate to use when updating the model parameters.
 	pub learning_rate: Option<f32>,
+	/// This is the schedule used to decay the learning rate over the course of training. The default is a constant learning rate.
+	pub learning_rate_schedule: Option<LearningRateSchedule>,
 	/// This is the maximum number of epochs to train.
 	pub max_epochs: Option<u64>,
 	/// This is the number of examples to use for each batch of training.
 	pub n_examples_per_batch: Option<u64>,
+	/// This is the optimizer used to update the model parameters. The default is `sgd`.
+	pub optimizer: Option<LinearOptimizer>,
+}
+
+/// This enum defines how a linear model's parameters are updated from the gradients of each batch.
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum LinearOptimizer {
+	/// Plain stochastic gradient descent.
+	#[serde(rename = "sgd")]
+	Sgd,
+	/// Stochastic gradient descent with momentum. The default `momentum` is 0.9.
+	#[serde(rename = "momentum")]
+	Momentum { momentum: Option<f32> },
+	/// The Adam optimizer. The defaults are a `beta_1` of 0.9, a `beta_2` of 0.999, and an `epsilon` of 1e-8.
+	#[serde(rename = "adam")]
+	Adam {
+		beta_1: Option<f32>,
+		beta_2: Option<f32>,
+		epsilon: Option<f32>,
+	},
+}
+
+/// This enum defines how a linear model's learning rate changes from one epoch to the next.
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum LearningRateSchedule {
+	/// Use the same learning rate for every epoch.
+	#[serde(rename = "constant")]
+	Constant,
+	/// Multiply the learning rate by `decay_rate` every `n_epochs_per_step` epochs.
+	#[serde(rename = "step")]
+	Step {
+		decay_rate: f32,
+		n_epochs_per_step: u64,
+	},
+	/// Multiply the learning rate by `decay_rate` every epoch.
+	#[serde(rename = "exponential")]
+	Exponential { decay_rate: f32 },
+	/// Divide the learning rate by `1 + decay_rate * epoch`.
+	#[serde(rename = "inverse_time")]
+	InverseTime { decay_rate: f32 },
 }
 
 /// These are the options used for training tree models.
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct TreeGridItem {
 	/// This option controls whether binned features will be laid out in row major or column major order. Each will produce the same result, but row major will be faster for datasets with more rows and fewer columns, while column major will be faster for datasets with fewer rows and more columns.
 	pub binned_features_layout: Option<BinnedFeaturesLayout>,
+	/// This option controls how the bin thresholds for number features are chosen when a feature has more unique values than `max_valid_bins_for_number_features`. The default is `quantile`.
+	pub binning_strategy: Option<BinningStrategy>,
 	/// This option controls early stopping. If it is `Some`, then early stopping will be enabled. If it is `None`, then early stopping will be disabled.
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
 	/// This option sets the L2 regularization value for continuous splits, which helps avoid overfitting.
@@ -221,6 +404,7 @@ pub struct TreeGridItem {
 	/// This is the maximum number of rounds of training that will occur. Fewer rounds may be trained if early stopping is enabled.
 	pub max_rounds: Option<u64>,
 	/// When computing the bin thresholds for number features, this is the maximum number of bins for valid values to create. If the number of unique values in the number feature is less than this value, the thresholds will be equal to the unique values, which can improve accuracy when number features have a small set of possible values.
+	#[serde(alias = "max_bins")]
 	pub max_valid_bins_for_number_features: Option<u8>,
 	/// A split will only be considered valid if the number of training examples sent to each of the resulting children is at least this value.
 	pub min_examples_per_node: Option<u64>,
@@ -232,6 +416,17 @@ pub struct TreeGridItem {
 	pub smoothing_factor_for_discrete_bin_sorting: Option<f32>,
 }
 
+/// This enum defines how the bin thresholds for number features are chosen.
+#[derive(Debug, serde::Deserialize)]
+pub enum BinningStrategy {
+	/// Place the thresholds at evenly spaced quantiles of the feature's values.
+	#[serde(rename = "quantile")]
+	Quantile,
+	/// Place the thresholds at evenly spaced values between the feature's minimum and maximum.
+	#[serde(rename = "uniform")]
+	Uniform,
+}
+
 /// This enum defines whether binned features will be layed out in row major or column major order.
 #[derive(Debug, serde::Deserialize)]
 pub enum BinnedFeaturesLayout {
@@ -243,7 +438,6 @@ pub enum BinnedFeaturesLayout {
 
 /// The parameters in this struct control how to determine whether training should stop early after each round or epoch.
 #[derive(Debug, serde::Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct EarlyStoppingOptions {
 	/// This is the fraction of the dataset that is set aside to compute the early stopping metric.
 	pub early_stopping_fraction: f32,
@@ -269,6 +463,10 @@ pub enum ComparisonMetric {
 	Auc,
 	#[serde(rename = "f1")]
 	F1,
+	#[serde(rename = "precision")]
+	Precision,
+	#[serde(rename = "recall")]
+	Recall,
 }
 
 impl Default for Dataset {
@@ -278,6 +476,31 @@ impl Default for Dataset {
 			test_fraction: 0.2,
 			shuffle: Default::default(),
 			columns: Default::default(),
+			cross_validation: None,
+			split_group_column: None,
+			ignore_columns: Vec::new(),
+			leakage_guard: Default::default(),
+		}
+	}
+}
+
+impl Default for LeakageGuard {
+	fn default() -> Self {
+		LeakageGuard {
+			enable: true,
+			max_auc: 0.999,
+			max_correlation: 0.999,
+		}
+	}
+}
+
+impl Default for Stats {
+	fn default() -> Self {
+		Stats {
+			number_histogram_max_size: 100,
+			number_exact_quantiles_max_unique_count: 100_000,
+			datetime_histogram_bin_count: 20,
+			number_quantiles: vec![0.25, 0.5, 0.75],
 		}
 	}
 }
@@ -291,6 +514,17 @@ impl Default for Shuffle {
 	}
 }
 
+impl Default for FeatureSelection {
+	fn default() -> Self {
+		FeatureSelection {
+			enable: true,
+			min_variance: 0.0,
+			max_invalid_fraction: 0.9,
+			max_correlation: 0.98,
+		}
+	}
+}
+
 impl Default for AutoFeatures {
 	fn default() -> Self {
 		AutoFeatures {
@@ -300,6 +534,15 @@ impl Default for AutoFeatures {
 	}
 }
 
+impl From<RegressionTolerance> for modelfox_metrics::Tolerance {
+	fn from(value: RegressionTolerance) -> modelfox_metrics::Tolerance {
+		match value {
+			RegressionTolerance::Absolute { value } => modelfox_metrics::Tolerance::Absolute(value),
+			RegressionTolerance::Relative { value } => modelfox_metrics::Tolerance::Relative(value),
+		}
+	}
+}
+
 impl std::fmt::Display for ComparisonMetric {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		let s = match self {
@@ -310,6 +553,8 @@ impl std::fmt::Display for ComparisonMetric {
 			ComparisonMetric::Accuracy => "Accuracy",
 			ComparisonMetric::Auc => "Area Under the Receiver Operating Characteristic Curve",
 			ComparisonMetric::F1 => "F1",
+			ComparisonMetric::Precision => "Precision",
+			ComparisonMetric::Recall => "Recall",
 		};
 		write!(f, "{}", s)
 	}
@@ -0,0 +1,516 @@
+/*!
+This module checks a `.json` or `.yaml` config file before training. It reports syntax errors, values of the wrong type, and invalid settings as errors, and keys that are not part of the config, which are usually typos, as warnings, each with the line and column in the file where it occurs.
+*/
+
+use crate::{
+	config::{self, Config},
+	train::validate_config,
+};
+use anyhow::{bail, Result};
+use std::path::Path;
+use yaml_rust::{
+	parser::{Event, MarkedEventReceiver, Parser},
+	scanner::Marker,
+};
+
+/// This is the result of checking a config file.
+#[derive(Debug)]
+pub struct ConfigCheckOutput {
+	/// This is the config parsed from the file, or `None` if it failed to parse.
+	pub config: Option<Config>,
+	/// These are the errors and warnings found in the file, in the order they were found.
+	pub issues: Vec<ConfigIssue>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ConfigIssue {
+	pub level: ConfigIssueLevel,
+	/// This is the line and column of the issue in the config file, both starting at 1. Settings that are invalid in combination, such as fractions that add up to more than 1, have no location.
+	pub location: Option<(usize, usize)>,
+	pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigIssueLevel {
+	Error,
+	Warning,
+}
+
+impl ConfigCheckOutput {
+	pub fn errors(&self) -> impl Iterator<Item = &ConfigIssue> {
+		self.issues
+			.iter()
+			.filter(|issue| issue.level == ConfigIssueLevel::Error)
+	}
+
+	pub fn warnings(&self) -> impl Iterator<Item = &ConfigIssue> {
+		self.issues
+			.iter()
+			.filter(|issue| issue.level == ConfigIssueLevel::Warning)
+	}
+}
+
+impl std::fmt::Display for ConfigIssue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.location {
+			Some((line, column)) => {
+				write!(f, "{} at line {} column {}", self.message, line, column)
+			}
+			None => write!(f, "{}", self.message),
+		}
+	}
+}
+
+/// Check the config file at `config_path`. This only returns an `Err` if the file cannot be read or does not have a `.json` or `.yaml` extension. Problems with the config itself are returned in [`ConfigCheckOutput::issues`].
+pub fn check_config(config_path: &Path) -> Result<ConfigCheckOutput> {
+	let source = std::fs::read_to_string(config_path)?;
+	let extension = config_path.extension().and_then(|s| s.to_str());
+	let format = match extension {
+		Some("json") => ConfigFormat::Json,
+		Some("yaml") => ConfigFormat::Yaml,
+		_ => bail!("the config path must have either .json or .yaml as its extension."),
+	};
+	Ok(check_config_source(&source, format))
+}
+
+#[derive(Clone, Copy)]
+enum ConfigFormat {
+	Json,
+	Yaml,
+}
+
+fn check_config_source(source: &str, format: ConfigFormat) -> ConfigCheckOutput {
+	let mut issues = Vec::new();
+	// JSON is a subset of YAML, so the YAML parser locates the keys in both formats. If it fails, the syntax error is reported below when deserializing.
+	if let Some(node) = parse_node(source) {
+		check_config_keys(&node, &mut issues);
+	}
+	let config: Result<Config, (String, Option<(usize, usize)>)> = match format {
+		ConfigFormat::Json => serde_json::from_str(source).map_err(|error| {
+			let location = if error.line() > 0 {
+				Some((error.line(), error.column()))
+			} else {
+				None
+			};
+			(error.to_string(), location)
+		}),
+		ConfigFormat::Yaml => serde_yaml::from_str(source).map_err(|error| {
+			let location = error
+				.location()
+				.map(|location| (location.line(), location.column()));
+			(error.to_string(), location)
+		}),
+	};
+	let config = match config {
+		Ok(config) => config,
+		Err((message, location)) => {
+			// The error messages end with the location, which the issue already has.
+			let message = match message.rfind(" at line ") {
+				Some(index) if location.is_some() => message[..index].to_owned(),
+				_ => message,
+			};
+			issues.push(ConfigIssue {
+				level: ConfigIssueLevel::Error,
+				location,
+				message,
+			});
+			return ConfigCheckOutput {
+				config: None,
+				issues,
+			};
+		}
+	};
+	if let Err(error) = validate_config(&config) {
+		issues.push(ConfigIssue {
+			level: ConfigIssueLevel::Error,
+			location: None,
+			message: error.to_string(),
+		});
+	}
+	ConfigCheckOutput {
+		config: Some(config),
+		issues,
+	}
+}
+
+/// This is a value in the config file along with the location of each mapping key in it.
+#[derive(Debug)]
+enum Node {
+	Mapping(Vec<(Key, Node)>),
+	Sequence(Vec<Node>),
+	Scalar(String),
+	Other,
+}
+
+#[derive(Debug)]
+struct Key {
+	name: String,
+	location: (usize, usize),
+}
+
+impl Node {
+	fn get(&self, name: &str) -> Option<&Node> {
+		match self {
+			Node::Mapping(entries) => entries
+				.iter()
+				.find(|(key, _)| key.name == name)
+				.map(|(_, node)| node),
+			_ => None,
+		}
+	}
+
+	fn items(&self) -> &[Node] {
+		match self {
+			Node::Sequence(items) => items,
+			_ => &[],
+		}
+	}
+}
+
+fn parse_node(source: &str) -> Option<Node> {
+	let mut builder = NodeBuilder::default();
+	let mut parser = Parser::new(source.chars());
+	parser.load(&mut builder, false).ok()?;
+	builder.root
+}
+
+#[derive(Default)]
+struct NodeBuilder {
+	stack: Vec<PartialNode>,
+	root: Option<Node>,
+}
+
+enum PartialNode {
+	Mapping(Vec<(Key, Node)>, Option<Key>),
+	Sequence(Vec<Node>),
+}
+
+impl NodeBuilder {
+	fn push(&mut self, node: Node, marker: Marker) {
+		match self.stack.last_mut() {
+			Some(PartialNode::Mapping(entries, key)) => match key.take() {
+				Some(key) => entries.push((key, node)),
+				None => {
+					let name = match node {
+						Node::Scalar(name) => name,
+						_ => String::new(),
+					};
+					*key = Some(Key {
+						name,
+						location: (marker.line(), marker.col() + 1),
+					});
+				}
+			},
+			Some(PartialNode::Sequence(items)) => items.push(node),
+			None => {
+				if self.root.is_none() {
+					self.root = Some(node);
+				}
+			}
+		}
+	}
+}
+
+impl MarkedEventReceiver for NodeBuilder {
+	fn on_event(&mut self, event: Event, marker: Marker) {
+		match event {
+			Event::Scalar(value, ..) => self.push(Node::Scalar(value), marker),
+			Event::Alias(_) => self.push(Node::Other, marker),
+			Event::SequenceStart(_) => self.stack.push(PartialNode::Sequence(Vec::new())),
+			Event::MappingStart(_) => self.stack.push(PartialNode::Mapping(Vec::new(), None)),
+			Event::SequenceEnd | Event::MappingEnd => {
+				let node = match self.stack.pop() {
+					Some(PartialNode::Sequence(items)) => Node::Sequence(items),
+					Some(PartialNode::Mapping(entries, _)) => Node::Mapping(entries),
+					None => return,
+				};
+				self.push(node, marker);
+			}
+			_ => {}
+		}
+	}
+}
+
+/// Warn about every key in the config that does not match a field of the struct it is in. The field names come from the `Deserialize` impls in [`config`], so they can not get out of date.
+fn check_config_keys(node: &Node, issues: &mut Vec<ConfigIssue>) {
+	check_keys(node, struct_fields::<Config>(), issues);
+	if let Some(dataset) = node.get("dataset") {
+		check_keys(dataset, struct_fields::<config::Dataset>(), issues);
+		if let Some(shuffle) = dataset.get("shuffle") {
+			check_keys(shuffle, struct_fields::<config::Shuffle>(), issues);
+		}
+		if let Some(leakage_guard) = dataset.get("leakage_guard") {
+			check_keys(
+				leakage_guard,
+				struct_fields::<config::LeakageGuard>(),
+				issues,
+			);
+		}
+		if let Some(columns) = dataset.get("columns") {
+			for column in columns.items() {
+				check_tagged_keys(
+					column,
+					"type",
+					&[
+						("unknown", struct_fields::<config::UnknownColumn>()),
+						("number", struct_fields::<config::NumberColumn>()),
+						("enum", struct_fields::<config::EnumColumn>()),
+						("text", struct_fields::<config::TextColumn>()),
+						("datetime", struct_fields::<config::DateTimeColumn>()),
+					],
+					issues,
+				);
+			}
+		}
+		if let Some(cross_validation) = dataset.get("cross_validation") {
+			check_tagged_keys(
+				cross_validation,
+				"strategy",
+				&[("rolling", struct_fields::<config::RollingCrossValidation>())],
+				issues,
+			);
+		}
+	}
+	if let Some(features) = node.get("features") {
+		check_keys(features, struct_fields::<config::Features>(), issues);
+		if let Some(auto) = features.get("auto") {
+			check_keys(auto, struct_fields::<config::AutoFeatures>(), issues);
+		}
+		if let Some(include) = features.get("include") {
+			for feature_group in include.items() {
+				check_tagged_keys(
+					feature_group,
+					"type",
+					&[
+						("identity", struct_fields::<config::IdentityFeatureGroup>()),
+						(
+							"normalized",
+							struct_fields::<config::NormalizedFeatureGroup>(),
+						),
+						(
+							"one_hot_encoded",
+							struct_fields::<config::OneHotEncodedFeatureGroup>(),
+						),
+						(
+							"bag_of_words",
+							struct_fields::<config::BagOfWordsFeatureGroup>(),
+						),
+						(
+							"bag_of_words_cosine_similarity",
+							struct_fields::<config::BagOfWordsCosineSimilarityFeatureGroup>(),
+						),
+						("cyclical", struct_fields::<config::CyclicalFeatureGroup>()),
+					],
+					issues,
+				);
+			}
+		}
+		if let Some(selection) = features.get("selection") {
+			check_keys(
+				selection,
+				struct_fields::<config::FeatureSelection>(),
+				issues,
+			);
+		}
+	}
+	if let Some(stats) = node.get("stats") {
+		check_keys(stats, struct_fields::<config::Stats>(), issues);
+	}
+	if let Some(train) = node.get("train") {
+		check_keys(train, struct_fields::<config::Train>(), issues);
+		if let Some(autogrid) = train.get("autogrid") {
+			check_keys(autogrid, struct_fields::<config::AutoGridOptions>(), issues);
+		}
+		if let Some(grid) = train.get("grid") {
+			for grid_item in grid.items() {
+				let model = check_tagged_keys(
+					grid_item,
+					"model",
+					&[
+						("linear", struct_fields::<config::LinearGridItem>()),
+						("tree", struct_fields::<config::TreeGridItem>()),
+					],
+					issues,
+				);
+				if let Some(early_stopping_options) = grid_item.get("early_stopping_options") {
+					check_keys(
+						early_stopping_options,
+						struct_fields::<config::EarlyStoppingOptions>(),
+						issues,
+					);
+				}
+				if model != Some("linear") {
+					continue;
+				}
+				if let Some(optimizer) = grid_item.get("optimizer") {
+					check_tagged_keys(
+						optimizer,
+						"type",
+						&[
+							("sgd", &[]),
+							("momentum", &["momentum"]),
+							("adam", &["beta_1", "beta_2", "epsilon"]),
+						],
+						issues,
+					);
+				}
+				if let Some(learning_rate_schedule) = grid_item.get("learning_rate_schedule") {
+					check_tagged_keys(
+						learning_rate_schedule,
+						"type",
+						&[
+							("constant", &[]),
+							("step", &["decay_rate", "n_epochs_per_step"]),
+							("exponential", &["decay_rate"]),
+							("inverse_time", &["decay_rate"]),
+						],
+						issues,
+					);
+				}
+			}
+		}
+		if let Some(regression_tolerance) = train.get("regression_tolerance") {
+			check_tagged_keys(
+				regression_tolerance,
+				"type",
+				&[("absolute", &["value"]), ("relative", &["value"])],
+				issues,
+			);
+		}
+	}
+}
+
+/// Check the keys of a mapping whose fields depend on the value of its `tag` key, and return the value of the tag. If the tag is missing or not one of the variants, deserializing the config reports the error, so no keys are checked.
+fn check_tagged_keys<'a>(
+	node: &'a Node,
+	tag: &str,
+	variants: &[(&str, &[&str])],
+	issues: &mut Vec<ConfigIssue>,
+) -> Option<&'a str> {
+	let variant = match node.get(tag) {
+		Some(Node::Scalar(variant)) => variant.as_str(),
+		_ => return None,
+	};
+	let (_, fields) = variants.iter().find(|(name, _)| *name == variant)?;
+	let fields = std::iter::once(tag)
+		.chain(fields.iter().copied())
+		.collect::<Vec<_>>();
+	check_keys(node, &fields, issues);
+	Some(variant)
+}
+
+fn check_keys(node: &Node, fields: &[&str], issues: &mut Vec<ConfigIssue>) {
+	let entries = match node {
+		Node::Mapping(entries) => entries,
+		_ => return,
+	};
+	for (key, _) in entries {
+		if fields.contains(&key.name.as_str()) {
+			continue;
+		}
+		let suggestion = fields
+			.iter()
+			.map(|field| (field, edit_distance(&key.name, field)))
+			.filter(|(field, distance)| *distance <= 2.max(field.len() / 3))
+			.min_by_key(|(_, distance)| *distance);
+		let message = match suggestion {
+			Some((field, _)) => format!(
+				"unknown key \"{}\", did you mean \"{}\"? It will be ignored.",
+				key.name, field
+			),
+			None => format!("unknown key \"{}\". It will be ignored.", key.name),
+		};
+		issues.push(ConfigIssue {
+			level: ConfigIssueLevel::Warning,
+			location: Some(key.location),
+			message,
+		});
+	}
+}
+
+/// Get the names of the fields of a struct that derives `Deserialize`, by deserializing it with a deserializer that records the field names it is asked for.
+fn struct_fields<T>() -> &'static [&'static str]
+where
+	T: for<'de> serde::Deserialize<'de>,
+{
+	let mut deserializer = StructFieldsDeserializer(None);
+	T::deserialize(&mut deserializer).ok();
+	deserializer.0.unwrap_or_default()
+}
+
+struct StructFieldsDeserializer(Option<&'static [&'static str]>);
+
+impl<'de, 'a> serde::Deserializer<'de> for &'a mut StructFieldsDeserializer {
+	type Error = serde::de::value::Error;
+
+	fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+	where
+		V: serde::de::Visitor<'de>,
+	{
+		Err(serde::de::Error::custom("expected a struct"))
+	}
+
+	fn deserialize_struct<V>(
+		self,
+		_name: &'static str,
+		fields: &'static [&'static str],
+		_visitor: V,
+	) -> Result<V::Value, Self::Error>
+	where
+		V: serde::de::Visitor<'de>,
+	{
+		self.0 = Some(fields);
+		Err(serde::de::Error::custom("recorded the fields"))
+	}
+
+	serde::forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf option unit unit_struct newtype_struct seq tuple
+		tuple_struct map enum identifier ignored_any
+	}
+}
+
+/// Compute the number of single character insertions, deletions, and substitutions needed to change `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+	let b = b.chars().collect::<Vec<_>>();
+	let mut previous = (0..=b.len()).collect::<Vec<_>>();
+	for (i, a) in a.chars().enumerate() {
+		let mut current = vec![i + 1; b.len() + 1];
+		for (j, b) in b.iter().enumerate() {
+			let substitution = previous[j] + if a == *b { 0 } else { 1 };
+			current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+		}
+		previous = current;
+	}
+	previous[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_check_config_unknown_keys() {
+		let source = "dataset:\n  test_fraction: 0.1\ntrain:\n  grid:\n    - model: tree\n      max_round: 10\n";
+		let output = check_config_source(source, ConfigFormat::Yaml);
+		assert!(output.config.is_some());
+		assert_eq!(output.errors().count(), 0);
+		let warnings = output.warnings().collect::<Vec<_>>();
+		assert_eq!(warnings.len(), 1);
+		assert_eq!(warnings[0].location, Some((6, 7)));
+		assert!(warnings[0].message.contains("did you mean \"max_rounds\""));
+	}
+
+	#[test]
+	fn test_check_config_errors() {
+		let source = "{\n  \"dataset\": {\n    \"test_fraction\": \"high\"\n  }\n}\n";
+		let output = check_config_source(source, ConfigFormat::Json);
+		assert!(output.config.is_none());
+		let errors = output.errors().collect::<Vec<_>>();
+		assert_eq!(errors.len(), 1);
+		assert_eq!(errors[0].location.map(|(line, _)| line), Some(3));
+		let source = "{ \"dataset\": { \"test_fraction\": 0.95 } }";
+		let output = check_config_source(source, ConfigFormat::Json);
+		assert_eq!(output.errors().count(), 1);
+	}
+}
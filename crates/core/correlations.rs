@@ -0,0 +1,193 @@
+/*!
+This module computes the correlations between pairs of columns in the train table, including the target column. Pairs of number columns use the Pearson correlation and pairs of enum columns use Cramér's V. The most correlated pairs are stored in the model and shown on the training stats correlations page, where they help find columns that leak the target and features that are nearly duplicates of each other.
+*/
+
+use modelfox_table::prelude::*;
+use num::ToPrimitive;
+use std::num::NonZeroUsize;
+
+/// Correlations are estimated from at most this many rows, which is plenty to find strongly correlated columns.
+const CORRELATION_MAX_ROWS: usize = 10_000;
+
+/// Correlations are computed between at most this many columns, so the number of pairs stays manageable for wide tables.
+const COLUMN_CORRELATIONS_MAX_COLUMNS: usize = 100;
+
+/// This is the number of pairs with the largest absolute correlation that are kept.
+const COLUMN_CORRELATIONS_MAX_PAIRS: usize = 100;
+
+#[derive(Clone, Debug)]
+pub struct ColumnCorrelation {
+	pub column_name_a: String,
+	pub column_name_b: String,
+	pub method: CorrelationMethod,
+	/// This is between -1 and 1 for the Pearson correlation, and between 0 and 1 for Cramér's V.
+	pub value: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CorrelationMethod {
+	Pearson,
+	CramersV,
+}
+
+/// Compute the correlation between each pair of number columns and each pair of enum columns in the table, and return the pairs with the largest absolute correlation, starting with the largest. Pairs of a number column and an enum column are not compared.
+pub fn compute_column_correlations(table: &TableView) -> Vec<ColumnCorrelation> {
+	let columns = table
+		.columns()
+		.iter()
+		.filter(|column| {
+			matches!(
+				column,
+				TableColumnView::Number(_) | TableColumnView::Enum(_)
+			)
+		})
+		.take(COLUMN_CORRELATIONS_MAX_COLUMNS)
+		.collect::<Vec<_>>();
+	let mut column_correlations = Vec::new();
+	for (index, column_a) in columns.iter().enumerate() {
+		for column_b in columns[index + 1..].iter() {
+			let correlation = match (column_a, column_b) {
+				(TableColumnView::Number(a), TableColumnView::Number(b)) => {
+					correlation(a.as_slice(), b.as_slice())
+						.map(|value| (CorrelationMethod::Pearson, value))
+				}
+				(TableColumnView::Enum(a), TableColumnView::Enum(b)) => cramers_v(
+					a.as_slice(),
+					a.variants().len(),
+					b.as_slice(),
+					b.variants().len(),
+				)
+				.map(|value| (CorrelationMethod::CramersV, value)),
+				_ => None,
+			};
+			let (method, value) = match correlation {
+				Some(correlation) if correlation.1.is_finite() => correlation,
+				_ => continue,
+			};
+			column_correlations.push(ColumnCorrelation {
+				column_name_a: column_a.name().unwrap().to_owned(),
+				column_name_b: column_b.name().unwrap().to_owned(),
+				method,
+				value,
+			});
+		}
+	}
+	column_correlations.sort_by(|a, b| b.value.abs().partial_cmp(&a.value.abs()).unwrap());
+	column_correlations.truncate(COLUMN_CORRELATIONS_MAX_PAIRS);
+	column_correlations
+}
+
+/// Compute the Pearson correlation between two number columns over the rows where both values are valid, or `None` if either column is constant over those rows.
+pub(crate) fn correlation(a: &[f32], b: &[f32]) -> Option<f32> {
+	let mut n = 0.0f64;
+	let mut sum_a = 0.0f64;
+	let mut sum_b = 0.0f64;
+	let mut sum_aa = 0.0f64;
+	let mut sum_bb = 0.0f64;
+	let mut sum_ab = 0.0f64;
+	for (a, b) in a.iter().zip(b.iter()).take(CORRELATION_MAX_ROWS) {
+		if !a.is_finite() || !b.is_finite() {
+			continue;
+		}
+		let (a, b) = (a.to_f64().unwrap(), b.to_f64().unwrap());
+		n += 1.0;
+		sum_a += a;
+		sum_b += b;
+		sum_aa += a * a;
+		sum_bb += b * b;
+		sum_ab += a * b;
+	}
+	let covariance = n * sum_ab - sum_a * sum_b;
+	let variance_a = n * sum_aa - sum_a * sum_a;
+	let variance_b = n * sum_bb - sum_b * sum_b;
+	if variance_a <= 0.0 || variance_b <= 0.0 {
+		return None;
+	}
+	(covariance / (variance_a.sqrt() * variance_b.sqrt())).to_f32()
+}
+
+/// Compute Cramér's V between two enum columns over the rows where both values are valid, or `None` if either column has fewer than two distinct values over those rows.
+pub(crate) fn cramers_v(
+	a: &[Option<NonZeroUsize>],
+	a_variant_count: usize,
+	b: &[Option<NonZeroUsize>],
+	b_variant_count: usize,
+) -> Option<f32> {
+	// The entry at `contingency_table[i * b_variant_count + j]` is the number of rows whose value in `a` is variant `i` and whose value in `b` is variant `j`.
+	let mut contingency_table = vec![0u64; a_variant_count * b_variant_count];
+	for (a, b) in a.iter().zip(b.iter()).take(CORRELATION_MAX_ROWS) {
+		if let (Some(a), Some(b)) = (a, b) {
+			contingency_table[(a.get() - 1) * b_variant_count + (b.get() - 1)] += 1;
+		}
+	}
+	let a_totals = (0..a_variant_count)
+		.map(|i| {
+			contingency_table[i * b_variant_count..(i + 1) * b_variant_count]
+				.iter()
+				.sum::<u64>()
+		})
+		.collect::<Vec<_>>();
+	let b_totals = (0..b_variant_count)
+		.map(|j| {
+			(0..a_variant_count)
+				.map(|i| contingency_table[i * b_variant_count + j])
+				.sum::<u64>()
+		})
+		.collect::<Vec<_>>();
+	let n = a_totals.iter().sum::<u64>().to_f64().unwrap();
+	let a_distinct_count = a_totals.iter().filter(|total| **total > 0).count();
+	let b_distinct_count = b_totals.iter().filter(|total| **total > 0).count();
+	let min_distinct_count = a_distinct_count.min(b_distinct_count);
+	if min_distinct_count < 2 {
+		return None;
+	}
+	let mut chi_squared = 0.0f64;
+	for (i, a_total) in a_totals.iter().enumerate() {
+		for (j, b_total) in b_totals.iter().enumerate() {
+			if *a_total == 0 || *b_total == 0 {
+				continue;
+			}
+			let observed = contingency_table[i * b_variant_count + j].to_f64().unwrap();
+			let expected = a_total.to_f64().unwrap() * b_total.to_f64().unwrap() / n;
+			chi_squared += (observed - expected).powi(2) / expected;
+		}
+	}
+	(chi_squared / (n * (min_distinct_count - 1).to_f64().unwrap()))
+		.sqrt()
+		.to_f32()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_correlation() {
+		let a = [1.0, 2.0, 3.0, 4.0];
+		let b = [2.0, 4.0, 6.0, 8.0];
+		let c = [4.0, 3.0, 2.0, 1.0];
+		assert!((correlation(&a, &b).unwrap() - 1.0).abs() < 1e-6);
+		assert!((correlation(&a, &c).unwrap() + 1.0).abs() < 1e-6);
+		// Rows with an invalid value in either column are skipped.
+		let d = [2.0, f32::NAN, 6.0, 8.0];
+		assert!((correlation(&a, &d).unwrap() - 1.0).abs() < 1e-6);
+		// A constant column has no correlation.
+		let e = [1.0, 1.0, 1.0, 1.0];
+		assert!(correlation(&a, &e).is_none());
+	}
+
+	#[test]
+	fn test_cramers_v() {
+		let variant = |index: usize| NonZeroUsize::new(index);
+		let a = [variant(1), variant(2), variant(1), variant(2)];
+		let b = [variant(2), variant(1), variant(2), variant(1)];
+		let c = [variant(1), variant(1), variant(2), variant(2)];
+		// Columns that determine each other are perfectly associated.
+		assert!((cramers_v(&a, 2, &b, 2).unwrap() - 1.0).abs() < 1e-6);
+		// Columns that are independent are not associated at all.
+		assert!(cramers_v(&a, 2, &c, 2).unwrap().abs() < 1e-6);
+		// A column with a single distinct value has no association.
+		let d = [variant(1), variant(1), None, variant(1)];
+		assert!(cramers_v(&a, 2, &d, 2).is_none());
+	}
+}
@@ -0,0 +1,227 @@
+/*!
+This module loads a training dataset from the result of a SQL query, so that a model can be trained directly from a PostgreSQL or MySQL database without first exporting the data to a csv file.
+
+The type of each column is chosen from the SQL type of the query result. Floating point columns become number columns, boolean columns become enum columns with the variants `false` and `true`, and date and timestamp columns become datetime columns. Integer and text columns are inferred from their values the same way they would be if the data was loaded from a csv, so that an integer column of zeros and ones becomes an enum column and a text column with few unique values becomes an enum column. Column types set in the config always take precedence.
+*/
+
+use anyhow::{bail, Result};
+use modelfox_table::prelude::*;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "database")]
+pub fn load_table_from_database(
+	database_url: &str,
+	query: &str,
+	column_types: Option<BTreeMap<String, TableColumnType>>,
+	invalid_value_policies: BTreeMap<String, modelfox_table::InvalidValuePolicy>,
+	handle_progress_event: &mut impl FnMut(modelfox_table::ProgressEvent),
+) -> Result<Table> {
+	if !(database_url.starts_with("postgres:")
+		|| database_url.starts_with("postgresql:")
+		|| database_url.starts_with("mysql:"))
+	{
+		bail!("The database url must start with postgres: or mysql:.");
+	}
+	let (column_names, database_column_types, rows) = tokio::runtime::Builder::new_current_thread()
+		.enable_all()
+		.build()?
+		.block_on(fetch_rows(database_url, query))?;
+	// Use the column types from the config if set, otherwise use the column types that follow from the SQL types.
+	let mut column_types = column_types.unwrap_or_default();
+	for (column_name, database_column_type) in column_names.iter().zip(database_column_types.iter())
+	{
+		if column_types.contains_key(column_name) {
+			continue;
+		}
+		if let Some(column_type) = database_column_type.table_column_type() {
+			column_types.insert(column_name.clone(), column_type);
+		}
+	}
+	Table::from_rows(
+		column_names,
+		&rows,
+		modelfox_table::FromCsvOptions {
+			column_types: Some(column_types),
+			invalid_value_policies,
+			..Default::default()
+		},
+		handle_progress_event,
+	)
+}
+
+#[cfg(not(feature = "database"))]
+pub fn load_table_from_database(
+	_database_url: &str,
+	_query: &str,
+	_column_types: Option<BTreeMap<String, TableColumnType>>,
+	_invalid_value_policies: BTreeMap<String, modelfox_table::InvalidValuePolicy>,
+	_handle_progress_event: &mut impl FnMut(modelfox_table::ProgressEvent),
+) -> Result<Table> {
+	bail!("This build of modelfox does not support loading data from a database.");
+}
+
+#[cfg(feature = "database")]
+#[derive(Clone, Copy, Debug)]
+enum DatabaseColumnType {
+	Int16,
+	Int32,
+	Int64,
+	Float32,
+	Float64,
+	Bool,
+	Text,
+	Date,
+	DateTime,
+	DateTimeTz,
+}
+
+#[cfg(feature = "database")]
+impl DatabaseColumnType {
+	/// Map the name of a PostgreSQL or MySQL type to the type used to decode its values.
+	fn from_type_name(type_name: &str) -> Option<DatabaseColumnType> {
+		let database_column_type = match type_name.to_uppercase().as_str() {
+			"INT2" | "SMALLINT" => DatabaseColumnType::Int16,
+			"INT4" | "INT" | "MEDIUMINT" => DatabaseColumnType::Int32,
+			"INT8" | "BIGINT" => DatabaseColumnType::Int64,
+			"FLOAT4" | "FLOAT" => DatabaseColumnType::Float32,
+			"FLOAT8" | "DOUBLE" => DatabaseColumnType::Float64,
+			"BOOL" | "BOOLEAN" => DatabaseColumnType::Bool,
+			"TEXT" | "VARCHAR" | "BPCHAR" | "CHAR" | "NAME" | "TINYTEXT" | "MEDIUMTEXT"
+			| "LONGTEXT" | "ENUM" => DatabaseColumnType::Text,
+			"DATE" => DatabaseColumnType::Date,
+			"TIMESTAMP" | "DATETIME" => DatabaseColumnType::DateTime,
+			"TIMESTAMPTZ" => DatabaseColumnType::DateTimeTz,
+			_ => return None,
+		};
+		Some(database_column_type)
+	}
+
+	/// Return the table column type for this SQL type, or `None` if the column type should be inferred from the values.
+	fn table_column_type(&self) -> Option<TableColumnType> {
+		match self {
+			DatabaseColumnType::Float32 | DatabaseColumnType::Float64 => {
+				Some(TableColumnType::Number)
+			}
+			DatabaseColumnType::Bool => Some(TableColumnType::Enum {
+				variants: vec!["false".to_owned(), "true".to_owned()],
+			}),
+			DatabaseColumnType::Date
+			| DatabaseColumnType::DateTime
+			| DatabaseColumnType::DateTimeTz => Some(TableColumnType::DateTime {
+				timezone: chrono_tz::Tz::UTC,
+			}),
+			DatabaseColumnType::Int16
+			| DatabaseColumnType::Int32
+			| DatabaseColumnType::Int64
+			| DatabaseColumnType::Text => None,
+		}
+	}
+}
+
+/// Run the query and read its result one row at a time, converting each value to the string representation that the table parses for its column type. `NULL` values become empty strings, which are invalid for every column type.
+#[cfg(feature = "database")]
+async fn fetch_rows(
+	database_url: &str,
+	query: &str,
+) -> Result<(Vec<String>, Vec<DatabaseColumnType>, Vec<Vec<String>>)> {
+	use futures::TryStreamExt;
+	use sqlx::{Column, Connection, Row, TypeInfo};
+	let mut connection = sqlx::AnyConnection::connect(database_url).await?;
+	let mut stream = sqlx::query(query).fetch(&mut connection);
+	let mut columns: Option<(Vec<String>, Vec<DatabaseColumnType>)> = None;
+	let mut rows = Vec::new();
+	while let Some(row) = stream.try_next().await? {
+		// Read the column names and types from the first row.
+		if columns.is_none() {
+			let mut column_names = Vec::new();
+			let mut database_column_types = Vec::new();
+			for column in row.columns() {
+				let type_name = column.type_info().name();
+				let database_column_type = match DatabaseColumnType::from_type_name(type_name) {
+					Some(database_column_type) => database_column_type,
+					None => bail!(
+						"The column \"{}\" has the unsupported type {}. Cast it to a float, integer, boolean, text, date, or timestamp type in the query.",
+						column.name(),
+						type_name,
+					),
+				};
+				column_names.push(column.name().to_owned());
+				database_column_types.push(database_column_type);
+			}
+			columns = Some((column_names, database_column_types));
+		}
+		let (_, database_column_types) = columns.as_ref().unwrap();
+		let mut values = Vec::with_capacity(database_column_types.len());
+		for (index, database_column_type) in database_column_types.iter().enumerate() {
+			let value = match database_column_type {
+				DatabaseColumnType::Int16 => row
+					.try_get::<Option<i16>, _>(index)?
+					.map(|value| value.to_string()),
+				DatabaseColumnType::Int32 => row
+					.try_get::<Option<i32>, _>(index)?
+					.map(|value| value.to_string()),
+				DatabaseColumnType::Int64 => row
+					.try_get::<Option<i64>, _>(index)?
+					.map(|value| value.to_string()),
+				DatabaseColumnType::Float32 => row
+					.try_get::<Option<f32>, _>(index)?
+					.map(|value| value.to_string()),
+				DatabaseColumnType::Float64 => row
+					.try_get::<Option<f64>, _>(index)?
+					.map(|value| value.to_string()),
+				DatabaseColumnType::Bool => row
+					.try_get::<Option<bool>, _>(index)?
+					.map(|value| value.to_string()),
+				DatabaseColumnType::Text => row.try_get::<Option<String>, _>(index)?,
+				DatabaseColumnType::Date => row
+					.try_get::<Option<chrono::NaiveDate>, _>(index)?
+					.map(|value| value.format("%Y-%m-%d").to_string()),
+				DatabaseColumnType::DateTime => row
+					.try_get::<Option<chrono::NaiveDateTime>, _>(index)?
+					.map(|value| value.format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+				DatabaseColumnType::DateTimeTz => row
+					.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>(index)?
+					.map(|value| value.to_rfc3339()),
+			};
+			values.push(value.unwrap_or_default());
+		}
+		rows.push(values);
+	}
+	let (column_names, database_column_types) = match columns {
+		Some(columns) => columns,
+		None => bail!("The query did not return any rows."),
+	};
+	Ok((column_names, database_column_types, rows))
+}
+
+#[cfg(all(test, feature = "database"))]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_database_column_types() {
+		assert!(matches!(
+			DatabaseColumnType::from_type_name("FLOAT8")
+				.unwrap()
+				.table_column_type(),
+			Some(TableColumnType::Number)
+		));
+		assert!(matches!(
+			DatabaseColumnType::from_type_name("BOOLEAN")
+				.unwrap()
+				.table_column_type(),
+			Some(TableColumnType::Enum { .. })
+		));
+		assert!(matches!(
+			DatabaseColumnType::from_type_name("datetime")
+				.unwrap()
+				.table_column_type(),
+			Some(TableColumnType::DateTime { .. })
+		));
+		assert!(DatabaseColumnType::from_type_name("BIGINT")
+			.unwrap()
+			.table_column_type()
+			.is_none());
+		assert!(DatabaseColumnType::from_type_name("NUMERIC").is_none());
+	}
+}
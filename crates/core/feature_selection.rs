@@ -0,0 +1,142 @@
+/*!
+This module implements the optional feature selection pass that drops uninformative columns before training. It is enabled with the `features.selection` section of the config.
+*/
+
+use crate::{config, correlations::correlation, stats::ColumnStatsOutput};
+use modelfox_table::prelude::*;
+use num::ToPrimitive;
+
+/// This is a column that was dropped by feature selection.
+#[derive(Clone, Debug)]
+pub struct DroppedColumn {
+	pub column_name: String,
+	pub reason: DroppedColumnReason,
+}
+
+#[derive(Clone, Debug)]
+pub enum DroppedColumnReason {
+	/// The column's variance is at most `features.selection.min_variance`. Enum columns with a single variant are also dropped for this reason, with a variance of zero.
+	LowVariance { variance: f32 },
+	/// The fraction of the column's values that are invalid or missing is above `features.selection.max_invalid_fraction`.
+	HighInvalidFraction { invalid_fraction: f32 },
+	/// The absolute correlation between the column and an earlier number column that was kept is above `features.selection.max_correlation`.
+	HighCorrelation {
+		correlated_column_name: String,
+		correlation: f32,
+	},
+}
+
+impl std::fmt::Display for DroppedColumnReason {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			DroppedColumnReason::LowVariance { variance } => {
+				write!(f, "near zero variance ({})", variance)
+			}
+			DroppedColumnReason::HighInvalidFraction { invalid_fraction } => {
+				write!(f, "{:.1}% of values are invalid", invalid_fraction * 100.0)
+			}
+			DroppedColumnReason::HighCorrelation {
+				correlated_column_name,
+				correlation,
+			} => write!(
+				f,
+				"correlation of {:.3} with column \"{}\"",
+				correlation, correlated_column_name
+			),
+		}
+	}
+}
+
+/// Choose the columns to drop, given the train table and the train column stats with the target column removed. Columns are checked for invalid values first, then for low variance, and the remaining number columns are checked for correlation with each other.
+pub fn select_columns(
+	table_train: &TableView,
+	train_column_stats: &[ColumnStatsOutput],
+	config: &config::FeatureSelection,
+) -> Vec<DroppedColumn> {
+	let mut dropped_columns = Vec::new();
+	let mut kept_number_column_names = Vec::new();
+	for column_stats in train_column_stats.iter() {
+		let reason = invalid_fraction(column_stats)
+			.filter(|invalid_fraction| *invalid_fraction > config.max_invalid_fraction)
+			.map(|invalid_fraction| DroppedColumnReason::HighInvalidFraction { invalid_fraction })
+			.or_else(|| {
+				variance(column_stats)
+					.filter(|variance| *variance <= config.min_variance)
+					.map(|variance| DroppedColumnReason::LowVariance { variance })
+			});
+		if let Some(reason) = reason {
+			dropped_columns.push(DroppedColumn {
+				column_name: column_stats.column_name().to_owned(),
+				reason,
+			});
+		} else if let ColumnStatsOutput::Number(_) = column_stats {
+			kept_number_column_names.push(column_stats.column_name());
+		}
+	}
+	// Check each number column against the earlier number columns that were kept, so only the later of a pair of correlated columns is dropped.
+	let number_columns = kept_number_column_names
+		.iter()
+		.filter_map(|column_name| {
+			table_train
+				.columns()
+				.iter()
+				.find(|column| column.name() == Some(*column_name))
+				.and_then(|column| match column {
+					TableColumnView::Number(column) => Some((*column_name, column.clone())),
+					_ => None,
+				})
+		})
+		.collect::<Vec<_>>();
+	let mut kept_number_columns: Vec<(&str, NumberTableColumnView)> = Vec::new();
+	for (column_name, column) in number_columns {
+		let correlated_column =
+			kept_number_columns
+				.iter()
+				.find_map(|(kept_column_name, kept_column)| {
+					let correlation = correlation(kept_column.as_slice(), column.as_slice())?;
+					if correlation.abs() > config.max_correlation {
+						Some((kept_column_name.to_string(), correlation))
+					} else {
+						None
+					}
+				});
+		if let Some((correlated_column_name, correlation)) = correlated_column {
+			dropped_columns.push(DroppedColumn {
+				column_name: column_name.to_owned(),
+				reason: DroppedColumnReason::HighCorrelation {
+					correlated_column_name,
+					correlation,
+				},
+			});
+		} else {
+			kept_number_columns.push((column_name, column));
+		}
+	}
+	dropped_columns
+}
+
+fn invalid_fraction(column_stats: &ColumnStatsOutput) -> Option<f32> {
+	let (invalid_count, count) = match column_stats {
+		ColumnStatsOutput::Number(column_stats) => (column_stats.invalid_count, column_stats.count),
+		ColumnStatsOutput::Enum(column_stats) => (
+			column_stats.invalid_count,
+			column_stats.count.to_usize().unwrap(),
+		),
+		ColumnStatsOutput::DateTime(column_stats) => {
+			(column_stats.invalid_count, column_stats.count)
+		}
+		ColumnStatsOutput::Unknown(_) | ColumnStatsOutput::Text(_) => return None,
+	};
+	if count == 0 {
+		return None;
+	}
+	Some(invalid_count.to_f32().unwrap() / count.to_f32().unwrap())
+}
+
+fn variance(column_stats: &ColumnStatsOutput) -> Option<f32> {
+	match column_stats {
+		ColumnStatsOutput::Number(column_stats) => Some(column_stats.variance),
+		ColumnStatsOutput::Enum(column_stats) if column_stats.unique_count <= 1 => Some(0.0),
+		_ => None,
+	}
+}
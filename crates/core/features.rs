@@ -5,14 +5,15 @@ This module implements ModelFox's feature engineering that prepares datasets for
 use crate::{
 	config,
 	stats::{
-		ColumnStatsOutput, EnumColumnStatsOutput, NumberColumnStatsOutput, TextColumnStatsOutput,
-		TextColumnStatsOutputTopNGramsEntry,
+		ColumnStatsOutput, DateTimeColumnStatsOutput, EnumColumnStatsOutput,
+		NumberColumnStatsOutput, TextColumnStatsOutput, TextColumnStatsOutputTopNGramsEntry,
 	},
 };
-use fnv::FnvBuildHasher;
+use fnv::{FnvBuildHasher, FnvHashSet};
 use indexmap::IndexMap;
 use modelfox_text::NGram;
 use num::ToPrimitive;
+use std::sync::Arc;
 
 pub fn choose_feature_groups_linear(
 	column_stats: &[ColumnStatsOutput],
@@ -103,11 +104,16 @@ pub fn compute_feature_group_types_from_config(
 						column_stats.column_name() == feature_group.source_column_name
 					})
 					.unwrap();
-				let column_stats = match column_stats {
-					ColumnStatsOutput::Number(column_stats) => column_stats,
+				let feature_group = match column_stats {
+					ColumnStatsOutput::Number(column_stats) => {
+						normalized_feature_group_for_column(column_stats)
+					}
+					ColumnStatsOutput::DateTime(column_stats) => {
+						normalized_feature_group_for_datetime_column(column_stats)
+					}
 					_ => panic!(),
 				};
-				result.push(normalized_feature_group_for_column(column_stats))
+				result.push(feature_group)
 			}
 			config::FeatureGroup::OneHotEncoded(feature_group) => {
 				let column_stats = column_stats
@@ -165,6 +171,22 @@ pub fn compute_feature_group_types_from_config(
 					Default::default(),
 				))
 			}
+			config::FeatureGroup::Cyclical(feature_group) => {
+				let column_stats = column_stats
+					.iter()
+					.find(|column_stats| {
+						column_stats.column_name() == feature_group.source_column_name
+					})
+					.unwrap();
+				match column_stats {
+					ColumnStatsOutput::Number(_) | ColumnStatsOutput::DateTime(_) => {}
+					_ => panic!(),
+				};
+				result.push(cyclical_feature_group_for_column(
+					column_stats,
+					feature_group,
+				))
+			}
 		}
 	}
 	result
@@ -194,6 +216,9 @@ pub fn choose_feature_group_linear(
 				feature_group_config,
 			))
 		}
+		ColumnStatsOutput::DateTime(column_stats) => {
+			choose_feature_group_linear_datetime_column(column_stats)
+		}
 	}
 }
 
@@ -209,6 +234,14 @@ fn choose_feature_group_linear_enum_column(
 	one_hot_encoded_feature_group_for_column(column_stats)
 }
 
+/// A datetime column is normalized as a unix timestamp. If the column has no valid values, it is not used.
+fn choose_feature_group_linear_datetime_column(
+	column_stats: &DateTimeColumnStatsOutput,
+) -> Option<modelfox_features::FeatureGroup> {
+	column_stats.min?;
+	Some(normalized_feature_group_for_datetime_column(column_stats))
+}
+
 fn choose_feature_group_linear_text_column(
 	column_stats: &TextColumnStatsOutput,
 	feature_group_config: Option<&config::BagOfWordsFeatureGroup>,
@@ -236,6 +269,10 @@ fn choose_feature_group_tree(
 				feature_group_config,
 			))
 		}
+		ColumnStatsOutput::DateTime(datetime_column_stats) => {
+			datetime_column_stats.min?;
+			Some(identity_feature_group_for_column(column_stats))
+		}
 	}
 }
 
@@ -276,6 +313,27 @@ fn normalized_feature_group_for_column(
 	})
 }
 
+fn normalized_feature_group_for_datetime_column(
+	column_stats: &DateTimeColumnStatsOutput,
+) -> modelfox_features::FeatureGroup {
+	modelfox_features::FeatureGroup::Normalized(modelfox_features::NormalizedFeatureGroup {
+		source_column_name: column_stats.column_name.to_owned(),
+		mean: column_stats.mean,
+		variance: column_stats.variance,
+	})
+}
+
+fn cyclical_feature_group_for_column(
+	column_stats: &ColumnStatsOutput,
+	feature_group: &config::CyclicalFeatureGroup,
+) -> modelfox_features::FeatureGroup {
+	modelfox_features::FeatureGroup::Cyclical(modelfox_features::CyclicalFeatureGroup {
+		source_column_name: column_stats.column_name().to_owned(),
+		period: feature_group.period,
+		offset: feature_group.offset.unwrap_or(0.0),
+	})
+}
+
 fn one_hot_encoded_feature_group_for_column(
 	column_stats: &EnumColumnStatsOutput,
 ) -> modelfox_features::FeatureGroup {
@@ -315,9 +373,49 @@ fn bag_of_words_feature_group_for_column(
 		})
 		.unwrap_or(modelfox_features::bag_of_words::BagOfWordsFeatureGroupStrategy::Present);
 	let tokenizer = column_stats.tokenizer.clone();
+	let row_count = column_stats.row_count.to_f64().unwrap();
+	let document_frequency_to_row_count =
+		|document_frequency: &config::DocumentFrequency| match document_frequency {
+			config::DocumentFrequency::Count(count) => count.to_f64().unwrap(),
+			config::DocumentFrequency::Fraction(fraction) => fraction.to_f64().unwrap() * row_count,
+		};
+	let min_row_count = feature_group
+		.and_then(|feature_group| feature_group.min_document_frequency.as_ref())
+		.map(document_frequency_to_row_count);
+	let max_row_count = feature_group
+		.and_then(|feature_group| feature_group.max_document_frequency.as_ref())
+		.map(document_frequency_to_row_count);
+	let stop_words: Option<FnvHashSet<String>> = feature_group
+		.and_then(|feature_group| feature_group.stop_words.as_ref())
+		.map(|stop_words| stop_words.iter().map(|word| word.to_lowercase()).collect());
+	let is_stop_word = |token: &str| {
+		stop_words
+			.as_ref()
+			.map(|stop_words| stop_words.contains(&token.to_lowercase()))
+			.unwrap_or(false)
+	};
+	let max_vocabulary_size = feature_group
+		.and_then(|feature_group| feature_group.max_vocabulary_size)
+		.unwrap_or(usize::MAX);
+	// The ngrams in the column stats are sorted by row count, so taking the first `max_vocabulary_size` that pass the filters keeps the most frequent ones.
 	let ngrams = column_stats
 		.top_ngrams
 		.iter()
+		.filter(|(ngram, entry)| {
+			let entry_row_count = entry.row_count.to_f64().unwrap();
+			if min_row_count.map_or(false, |min_row_count| entry_row_count < min_row_count) {
+				return false;
+			}
+			if max_row_count.map_or(false, |max_row_count| entry_row_count > max_row_count) {
+				return false;
+			}
+			let is_stop_word_ngram = match ngram {
+				NGram::Unigram(token) => is_stop_word(token),
+				NGram::Bigram(token_a, token_b) => is_stop_word(token_a) && is_stop_word(token_b),
+			};
+			!is_stop_word_ngram
+		})
+		.take(max_vocabulary_size)
 		.map(|(ngram, entry)| {
 			(
 				ngram.clone(),
@@ -332,7 +430,7 @@ fn bag_of_words_feature_group_for_column(
 		source_column_name: column_stats.column_name.to_owned(),
 		strategy,
 		tokenizer,
-		ngrams,
+		ngrams: Arc::new(ngrams),
 		ngram_types,
 	})
 }
@@ -395,7 +493,7 @@ fn bag_of_words_cosine_similarity_feature_group_for_column(
 			source_column_name_b: column_stats_b.column_name.to_owned(),
 			strategy,
 			tokenizer,
-			ngrams,
+			ngrams: Arc::new(ngrams),
 			ngram_types,
 		},
 	)
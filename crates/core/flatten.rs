@@ -0,0 +1,125 @@
+/*!
+This module flattens the nested objects that the language libraries accept as predict input into the flat map of columns that [`crate::predict::predict`] expects. For example, with the default options, `{"user": {"age": 3}}` becomes the column `user.age` with the value `3`. Every language library flattens its input with this module, so the column names are the same no matter which one made the prediction.
+*/
+
+use crate::predict::{PredictInput, PredictInputValue};
+use std::collections::BTreeMap;
+
+/// A predict input value that may be an object of nested values.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum NestedPredictInputValue {
+	Number(f64),
+	String(String),
+	Object(BTreeMap<String, NestedPredictInputValue>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlattenOptions {
+	/// This is placed between the key of an object and the keys of its values to form the column name, for example `"."` or `"_"`.
+	pub separator: String,
+	/// This is the number of levels of nested objects to flatten. Objects nested more deeply than this are dropped. If it is `None`, every level is flattened.
+	pub max_depth: Option<usize>,
+}
+
+impl Default for FlattenOptions {
+	fn default() -> FlattenOptions {
+		FlattenOptions {
+			separator: ".".to_owned(),
+			max_depth: None,
+		}
+	}
+}
+
+/// Flatten `input` into a predict input with one column per value.
+pub fn flatten_predict_input(
+	input: BTreeMap<String, NestedPredictInputValue>,
+	options: &FlattenOptions,
+) -> PredictInput {
+	let mut output = PredictInput::new();
+	flatten_into(&mut output, None, input, 0, options);
+	output
+}
+
+fn flatten_into(
+	output: &mut PredictInput,
+	prefix: Option<&str>,
+	values: BTreeMap<String, NestedPredictInputValue>,
+	depth: usize,
+	options: &FlattenOptions,
+) {
+	for (key, value) in values {
+		let column_name = match prefix {
+			Some(prefix) => format!("{}{}{}", prefix, options.separator, key),
+			None => key,
+		};
+		match value {
+			NestedPredictInputValue::Number(value) => {
+				output
+					.0
+					.insert(column_name, PredictInputValue::Number(value));
+			}
+			NestedPredictInputValue::String(value) => {
+				output
+					.0
+					.insert(column_name, PredictInputValue::String(value));
+			}
+			NestedPredictInputValue::Object(values) => {
+				if options
+					.max_depth
+					.map(|max_depth| depth < max_depth)
+					.unwrap_or(true)
+				{
+					flatten_into(output, Some(&column_name), values, depth + 1, options);
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn input() -> BTreeMap<String, NestedPredictInputValue> {
+		serde_json::from_str(
+			r#"{
+				"age": 63,
+				"user": {
+					"name": "Ben",
+					"address": { "city": "Boston" }
+				}
+			}"#,
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn test_flatten_predict_input() {
+		let output = flatten_predict_input(input(), &FlattenOptions::default());
+		let mut expected = PredictInput::new();
+		expected
+			.0
+			.insert("age".to_owned(), PredictInputValue::Number(63.0));
+		expected.0.insert(
+			"user.address.city".to_owned(),
+			PredictInputValue::String("Boston".to_owned()),
+		);
+		expected.0.insert(
+			"user.name".to_owned(),
+			PredictInputValue::String("Ben".to_owned()),
+		);
+		assert_eq!(output, expected);
+	}
+
+	#[test]
+	fn test_flatten_predict_input_max_depth() {
+		let options = FlattenOptions {
+			separator: "_".to_owned(),
+			max_depth: Some(1),
+		};
+		let output = flatten_predict_input(input(), &options);
+		let column_names = output.0.keys().cloned().collect::<Vec<_>>();
+		assert_eq!(column_names, vec!["age".to_owned(), "user_name".to_owned()]);
+	}
+}
@@ -44,15 +44,48 @@ pub enum GridItem {
 pub struct LinearModelTrainOptions {
 	pub l2_regularization: Option<f32>,
 	pub learning_rate: Option<f32>,
+	pub learning_rate_schedule: Option<LearningRateSchedule>,
 	pub max_epochs: Option<u64>,
 	pub n_examples_per_batch: Option<u64>,
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
+	pub optimizer: Option<LinearOptimizer>,
+}
+
+#[derive(Clone, Debug)]
+pub enum LinearOptimizer {
+	Sgd,
+	Momentum {
+		momentum: Option<f32>,
+	},
+	Adam {
+		beta_1: Option<f32>,
+		beta_2: Option<f32>,
+		epsilon: Option<f32>,
+	},
+}
+
+#[derive(Clone, Debug)]
+pub enum LearningRateSchedule {
+	Constant,
+	Step {
+		decay_rate: f32,
+		n_epochs_per_step: u64,
+	},
+	Exponential {
+		decay_rate: f32,
+	},
+	InverseTime {
+		decay_rate: f32,
+	},
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct TreeModelTrainOptions {
 	pub binned_features_layout: Option<BinnedFeaturesLayout>,
+	pub binning_strategy: Option<BinningStrategy>,
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
+	/// These are groups of column names. See [`config::Train::interaction_constraints`].
+	pub interaction_constraints: Option<Vec<Vec<String>>>,
 	pub l2_regularization_for_continuous_splits: Option<f32>,
 	pub l2_regularization_for_discrete_splits: Option<f32>,
 	pub learning_rate: Option<f32>,
@@ -73,6 +106,12 @@ pub enum BinnedFeaturesLayout {
 	ColumnMajor,
 }
 
+#[derive(Clone, Debug)]
+pub enum BinningStrategy {
+	Quantile,
+	Uniform,
+}
+
 #[derive(Clone, Debug)]
 pub struct EarlyStoppingOptions {
 	pub early_stopping_fraction: f32,
@@ -90,6 +129,43 @@ impl Default for EarlyStoppingOptions {
 	}
 }
 
+fn compute_linear_optimizer(optimizer: &config::LinearOptimizer) -> LinearOptimizer {
+	match *optimizer {
+		config::LinearOptimizer::Sgd => LinearOptimizer::Sgd,
+		config::LinearOptimizer::Momentum { momentum } => LinearOptimizer::Momentum { momentum },
+		config::LinearOptimizer::Adam {
+			beta_1,
+			beta_2,
+			epsilon,
+		} => LinearOptimizer::Adam {
+			beta_1,
+			beta_2,
+			epsilon,
+		},
+	}
+}
+
+fn compute_learning_rate_schedule(
+	learning_rate_schedule: &config::LearningRateSchedule,
+) -> LearningRateSchedule {
+	match *learning_rate_schedule {
+		config::LearningRateSchedule::Constant => LearningRateSchedule::Constant,
+		config::LearningRateSchedule::Step {
+			decay_rate,
+			n_epochs_per_step,
+		} => LearningRateSchedule::Step {
+			decay_rate,
+			n_epochs_per_step,
+		},
+		config::LearningRateSchedule::Exponential { decay_rate } => {
+			LearningRateSchedule::Exponential { decay_rate }
+		}
+		config::LearningRateSchedule::InverseTime { decay_rate } => {
+			LearningRateSchedule::InverseTime { decay_rate }
+		}
+	}
+}
+
 pub fn compute_regression_hyperparameter_grid(
 	grid: &[config::GridItem],
 	target_column_index: usize,
@@ -104,8 +180,13 @@ pub fn compute_regression_hyperparameter_grid(
 				options: LinearModelTrainOptions {
 					l2_regularization: item.l2_regularization,
 					learning_rate: item.learning_rate,
+					learning_rate_schedule: item
+						.learning_rate_schedule
+						.as_ref()
+						.map(compute_learning_rate_schedule),
 					max_epochs: item.max_epochs,
 					n_examples_per_batch: item.n_examples_per_batch,
+					optimizer: item.optimizer.as_ref().map(compute_linear_optimizer),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -131,6 +212,12 @@ pub fn compute_regression_hyperparameter_grid(
 							}
 						},
 					),
+					binning_strategy: item.binning_strategy.as_ref().map(|binning_strategy| {
+						match binning_strategy {
+							config::BinningStrategy::Quantile => BinningStrategy::Quantile,
+							config::BinningStrategy::Uniform => BinningStrategy::Uniform,
+						}
+					}),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -140,6 +227,7 @@ pub fn compute_regression_hyperparameter_grid(
 								.min_decrease_in_loss_for_significant_change,
 						},
 					),
+					interaction_constraints: config.train.interaction_constraints.clone(),
 					l2_regularization_for_continuous_splits: item
 						.l2_regularization_for_continuous_splits,
 					l2_regularization_for_discrete_splits: item
@@ -176,8 +264,13 @@ pub fn compute_binary_classification_hyperparameter_grid(
 				options: LinearModelTrainOptions {
 					l2_regularization: item.l2_regularization,
 					learning_rate: item.learning_rate,
+					learning_rate_schedule: item
+						.learning_rate_schedule
+						.as_ref()
+						.map(compute_learning_rate_schedule),
 					max_epochs: item.max_epochs,
 					n_examples_per_batch: item.n_examples_per_batch,
+					optimizer: item.optimizer.as_ref().map(compute_linear_optimizer),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -203,6 +296,12 @@ pub fn compute_binary_classification_hyperparameter_grid(
 							}
 						},
 					),
+					binning_strategy: item.binning_strategy.as_ref().map(|binning_strategy| {
+						match binning_strategy {
+							config::BinningStrategy::Quantile => BinningStrategy::Quantile,
+							config::BinningStrategy::Uniform => BinningStrategy::Uniform,
+						}
+					}),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -212,6 +311,7 @@ pub fn compute_binary_classification_hyperparameter_grid(
 								.min_decrease_in_loss_for_significant_change,
 						},
 					),
+					interaction_constraints: config.train.interaction_constraints.clone(),
 					l2_regularization_for_continuous_splits: item
 						.l2_regularization_for_continuous_splits,
 					l2_regularization_for_discrete_splits: item
@@ -248,8 +348,13 @@ pub fn compute_multiclass_classification_hyperparameter_grid(
 				options: LinearModelTrainOptions {
 					l2_regularization: item.l2_regularization,
 					learning_rate: item.learning_rate,
+					learning_rate_schedule: item
+						.learning_rate_schedule
+						.as_ref()
+						.map(compute_learning_rate_schedule),
 					max_epochs: item.max_epochs,
 					n_examples_per_batch: item.n_examples_per_batch,
+					optimizer: item.optimizer.as_ref().map(compute_linear_optimizer),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -275,6 +380,12 @@ pub fn compute_multiclass_classification_hyperparameter_grid(
 							}
 						},
 					),
+					binning_strategy: item.binning_strategy.as_ref().map(|binning_strategy| {
+						match binning_strategy {
+							config::BinningStrategy::Quantile => BinningStrategy::Quantile,
+							config::BinningStrategy::Uniform => BinningStrategy::Uniform,
+						}
+					}),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -284,6 +395,7 @@ pub fn compute_multiclass_classification_hyperparameter_grid(
 								.min_decrease_in_loss_for_significant_change,
 						},
 					),
+					interaction_constraints: config.train.interaction_constraints.clone(),
 					l2_regularization_for_continuous_splits: item
 						.l2_regularization_for_continuous_splits,
 					l2_regularization_for_discrete_splits: item
@@ -351,6 +463,7 @@ pub fn auto_regression_hyperparameter_grid(
 					max_epochs: Some(max_epochs),
 					n_examples_per_batch: Some(n_examples_per_batch),
 					early_stopping_options: Some(Default::default()),
+					..Default::default()
 				},
 			});
 		}
@@ -373,6 +486,7 @@ pub fn auto_regression_hyperparameter_grid(
 				target_column_index,
 				feature_groups: choose_feature_groups_tree(column_stats, config),
 				options: TreeModelTrainOptions {
+					interaction_constraints: config.train.interaction_constraints.clone(),
 					max_leaf_nodes: Some(max_leaf_nodes),
 					learning_rate: Some(learning_rate),
 					max_rounds: Some(max_rounds),
@@ -423,6 +537,7 @@ pub fn auto_binary_classification_hyperparameter_grid(
 					max_epochs: Some(max_epochs),
 					n_examples_per_batch: Some(n_examples_per_batch),
 					early_stopping_options: Some(Default::default()),
+					..Default::default()
 				},
 			});
 		}
@@ -445,6 +560,7 @@ pub fn auto_binary_classification_hyperparameter_grid(
 				target_column_index,
 				feature_groups: choose_feature_groups_tree(column_stats, config),
 				options: TreeModelTrainOptions {
+					interaction_constraints: config.train.interaction_constraints.clone(),
 					max_leaf_nodes: Some(max_leaf_nodes),
 					learning_rate: Some(learning_rate),
 					max_rounds: Some(max_rounds),
@@ -495,6 +611,7 @@ pub fn auto_multiclass_classification_hyperparameter_grid(
 					max_epochs: Some(max_epochs),
 					n_examples_per_batch: Some(n_examples_per_batch),
 					early_stopping_options: Some(Default::default()),
+					..Default::default()
 				},
 			});
 		}
@@ -517,6 +634,7 @@ pub fn auto_multiclass_classification_hyperparameter_grid(
 				target_column_index,
 				feature_groups: choose_feature_groups_tree(column_stats, config),
 				options: TreeModelTrainOptions {
+					interaction_constraints: config.train.interaction_constraints.clone(),
 					max_leaf_nodes: Some(max_leaf_nodes),
 					learning_rate: Some(learning_rate),
 					max_rounds: Some(max_rounds),
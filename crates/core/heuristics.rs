@@ -1,3 +1,7 @@
 pub const MIN_TRAIN_ROWS: usize = 35;
 pub const MIN_TEST_ROWS: usize = 10;
 pub const MIN_COMPARISON_ROWS: usize = 5;
+/// A warning is emitted if the largest group in the split group column has more than this many times as many rows as the median group, because the datasets can then be much larger or smaller than configured.
+pub const MAX_SPLIT_GROUP_SIZE_RATIO: usize = 10;
+/// Linear models are trained on sparse features when fewer than this fraction of feature values are nonzero.
+pub const LINEAR_SPARSE_FEATURES_MAX_DENSITY: f32 = 0.25;
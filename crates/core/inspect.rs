@@ -0,0 +1,361 @@
+/*!
+This module inspects a training dataset without training a model. It runs the same column type inference and stats computation as [`Trainer::prepare`](crate::train::Trainer::prepare) and reports what it found, so mistakes like a numeric column that was inferred as text because of a stray value can be fixed in a config file before starting a long training run.
+*/
+
+use crate::{
+	progress::{LoadProgressEvent, ProgressEvent, StatsProgressEvent},
+	stats::{ColumnStatsOutput, Stats},
+	train::{
+		column_types_from_config, load_config, stats_settings_from_config, TrainingDataSource,
+	},
+};
+use anyhow::{anyhow, Result};
+use modelfox_table::prelude::*;
+use num::ToPrimitive;
+use std::{io::Read, path::Path};
+
+/// If at least this fraction of the valid values in an enum or text column parse as numbers, the column is suggested to be a number column.
+const SUGGEST_NUMBER_MIN_FRACTION: f32 = 0.9;
+
+/// This is the maximum number of example non-numeric values reported for a column that is suggested to be a number column.
+const NON_NUMERIC_EXAMPLES_MAX_COUNT: usize = 5;
+
+#[derive(Debug)]
+pub struct InspectOutput {
+	/// This is the number of rows in the dataset.
+	pub row_count: usize,
+	pub columns: Vec<InspectColumn>,
+}
+
+#[derive(Debug)]
+pub struct InspectColumn {
+	/// This is the name of the column as it appears in the csv.
+	pub name: String,
+	/// This is the type the column was loaded with, either inferred or from the config.
+	pub column_type: InspectColumnType,
+	/// This is the type the starter config uses for the column. It differs from `column_type` when most of the values in an enum or text column are numbers.
+	pub suggested_column_type: InspectColumnType,
+	/// This is the number of values that are invalid for `column_type`.
+	pub invalid_count: usize,
+	/// This is the number of unique valid values. It is `None` for text and unknown columns.
+	pub unique_count: Option<usize>,
+	/// These are the enum variants ordered by count, descending. It is `None` if the column is not an enum column.
+	pub suggested_variants: Option<Vec<String>>,
+	/// This is the fraction of valid values that parse as numbers. It is `None` for number and unknown columns.
+	pub number_fraction: Option<f32>,
+	/// These are up to a few values that do not parse as numbers. They are only set if the suggested type is number and the inferred type is not.
+	pub non_numeric_examples: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InspectColumnType {
+	Unknown,
+	Number,
+	Enum,
+	Text,
+	DateTime,
+}
+
+impl std::fmt::Display for InspectColumnType {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let s = match self {
+			InspectColumnType::Unknown => "unknown",
+			InspectColumnType::Number => "number",
+			InspectColumnType::Enum => "enum",
+			InspectColumnType::Text => "text",
+			InspectColumnType::DateTime => "datetime",
+		};
+		write!(f, "{}", s)
+	}
+}
+
+/// Load the dataset with the column types from the config, if any, and report the type, invalid value count, and suggested variants of every column. When training with separate train and test files, only the train file is inspected, because the test file is always loaded with the train file's column types.
+pub fn inspect(
+	input: TrainingDataSource,
+	config_path: Option<&Path>,
+	handle_progress_event: &mut dyn FnMut(ProgressEvent),
+) -> Result<InspectOutput> {
+	let config = load_config(config_path, handle_progress_event)?;
+	// The invalid value policies from the config are not applied, so that every invalid value is counted instead of dropping rows or stopping at the first one.
+	let options = modelfox_table::FromCsvOptions {
+		column_types: column_types_from_config(&config),
+		infer_options: Default::default(),
+		..Default::default()
+	};
+	let mut handle_load_progress_event = |progress_event| {
+		handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Train(
+			progress_event,
+		)))
+	};
+	let table = match input {
+		TrainingDataSource::Stdin => {
+			let mut buf = Vec::new();
+			std::io::stdin().read_to_end(&mut buf)?;
+			Table::from_bytes(&buf, options, &mut handle_load_progress_event)?
+		}
+		TrainingDataSource::Csv(bytes) => {
+			Table::from_bytes(&bytes, options, &mut handle_load_progress_event)?
+		}
+		TrainingDataSource::File(file_path) => {
+			Table::from_path(&file_path, options, &mut handle_load_progress_event)?
+		}
+		TrainingDataSource::TrainAndTest { train, .. } => {
+			Table::from_path(&train, options, &mut handle_load_progress_event)?
+		}
+		TrainingDataSource::Database { url, query } => crate::database::load_table_from_database(
+			&url,
+			&query,
+			options.column_types,
+			options.invalid_value_policies,
+			&mut handle_load_progress_event,
+		)?,
+		TrainingDataSource::Table(table) => table,
+	};
+	let stats_settings = stats_settings_from_config(&config);
+	let stats = Stats::compute(&table.view(), &stats_settings, &mut |progress| {
+		handle_progress_event(ProgressEvent::Stats(StatsProgressEvent::ComputeTrainStats(
+			progress,
+		)));
+	});
+	handle_progress_event(ProgressEvent::Stats(
+		StatsProgressEvent::ComputeTrainStatsDone,
+	));
+	let column_stats = stats.finalize(&stats_settings).0;
+	let columns = table
+		.columns()
+		.iter()
+		.zip(column_stats.iter())
+		.map(|(column, column_stats)| inspect_column(column, column_stats))
+		.collect();
+	Ok(InspectOutput {
+		row_count: table.nrows(),
+		columns,
+	})
+}
+
+fn inspect_column(column: &TableColumn, column_stats: &ColumnStatsOutput) -> InspectColumn {
+	let name = column_stats.column_name().to_owned();
+	match (column, column_stats) {
+		(TableColumn::Unknown(_), ColumnStatsOutput::Unknown(column_stats)) => InspectColumn {
+			name,
+			column_type: InspectColumnType::Unknown,
+			suggested_column_type: InspectColumnType::Unknown,
+			invalid_count: column_stats.count,
+			unique_count: None,
+			suggested_variants: None,
+			number_fraction: None,
+			non_numeric_examples: Vec::new(),
+		},
+		(TableColumn::Number(_), ColumnStatsOutput::Number(column_stats)) => InspectColumn {
+			name,
+			column_type: InspectColumnType::Number,
+			suggested_column_type: InspectColumnType::Number,
+			invalid_count: column_stats.invalid_count,
+			unique_count: Some(column_stats.unique_count),
+			suggested_variants: None,
+			number_fraction: None,
+			non_numeric_examples: Vec::new(),
+		},
+		(TableColumn::Enum(_), ColumnStatsOutput::Enum(column_stats)) => {
+			let mut histogram = column_stats.histogram.clone();
+			histogram.sort_by(|(_, a), (_, b)| b.cmp(a));
+			let number_fraction = number_fraction(
+				histogram
+					.iter()
+					.map(|(variant, count)| (variant.as_str(), *count)),
+			);
+			let suggested_column_type =
+				suggested_column_type(InspectColumnType::Enum, number_fraction);
+			let non_numeric_examples = if suggested_column_type == InspectColumnType::Number {
+				non_numeric_examples(histogram.iter().map(|(variant, _)| variant.as_str()))
+			} else {
+				Vec::new()
+			};
+			InspectColumn {
+				name,
+				column_type: InspectColumnType::Enum,
+				suggested_column_type,
+				invalid_count: column_stats.invalid_count,
+				unique_count: Some(column_stats.unique_count),
+				suggested_variants: Some(
+					histogram.into_iter().map(|(variant, _)| variant).collect(),
+				),
+				number_fraction,
+				non_numeric_examples,
+			}
+		}
+		(TableColumn::Text(column), ColumnStatsOutput::Text(_)) => {
+			let number_fraction = number_fraction(
+				column
+					.iter()
+					.filter(|value| !value.is_empty())
+					.map(|value| (value.as_str(), 1)),
+			);
+			let suggested_column_type =
+				suggested_column_type(InspectColumnType::Text, number_fraction);
+			let non_numeric_examples = if suggested_column_type == InspectColumnType::Number {
+				non_numeric_examples(column.iter().map(|value| value.as_str()))
+			} else {
+				Vec::new()
+			};
+			InspectColumn {
+				name,
+				column_type: InspectColumnType::Text,
+				suggested_column_type,
+				invalid_count: 0,
+				unique_count: None,
+				suggested_variants: None,
+				number_fraction,
+				non_numeric_examples,
+			}
+		}
+		(TableColumn::DateTime(_), ColumnStatsOutput::DateTime(column_stats)) => InspectColumn {
+			name,
+			column_type: InspectColumnType::DateTime,
+			suggested_column_type: InspectColumnType::DateTime,
+			invalid_count: column_stats.invalid_count,
+			unique_count: Some(column_stats.unique_count),
+			suggested_variants: None,
+			number_fraction: None,
+			non_numeric_examples: Vec::new(),
+		},
+		_ => unreachable!(),
+	}
+}
+
+/// Compute the fraction of values, weighted by their counts, that parse as finite numbers.
+fn number_fraction<'a>(values: impl Iterator<Item = (&'a str, usize)>) -> Option<f32> {
+	let mut total_count = 0;
+	let mut number_count = 0;
+	for (value, count) in values {
+		total_count += count;
+		if parses_as_number(value) {
+			number_count += count;
+		}
+	}
+	if total_count == 0 {
+		None
+	} else {
+		Some(number_count.to_f32().unwrap() / total_count.to_f32().unwrap())
+	}
+}
+
+fn suggested_column_type(
+	column_type: InspectColumnType,
+	number_fraction: Option<f32>,
+) -> InspectColumnType {
+	// An enum column whose variants are all numbers, such as a rating from 1 to 5, was inferred as an enum on purpose because it has few unique values.
+	match number_fraction {
+		Some(number_fraction)
+			if number_fraction >= SUGGEST_NUMBER_MIN_FRACTION && number_fraction < 1.0 =>
+		{
+			InspectColumnType::Number
+		}
+		_ => column_type,
+	}
+}
+
+fn non_numeric_examples<'a>(values: impl Iterator<Item = &'a str>) -> Vec<String> {
+	let mut examples: Vec<String> = Vec::new();
+	for value in values {
+		if examples.len() == NON_NUMERIC_EXAMPLES_MAX_COUNT {
+			break;
+		}
+		if !value.is_empty()
+			&& !parses_as_number(value)
+			&& !examples.iter().any(|example| example == value)
+		{
+			examples.push(value.to_owned());
+		}
+	}
+	examples
+}
+
+fn parses_as_number(value: &str) -> bool {
+	value
+		.trim()
+		.parse::<f32>()
+		.map(f32::is_finite)
+		.unwrap_or(false)
+}
+
+impl InspectOutput {
+	/// Produce a config that sets the type of every column explicitly, using each column's suggested type. Pass it to `modelfox train --config` so that column types no longer depend on inference.
+	pub fn starter_config(&self) -> serde_json::Value {
+		let columns = self
+			.columns
+			.iter()
+			.map(|column| {
+				let mut value = serde_json::Map::new();
+				value.insert("name".to_owned(), column.name.clone().into());
+				value.insert(
+					"type".to_owned(),
+					column.suggested_column_type.to_string().into(),
+				);
+				if column.suggested_column_type == InspectColumnType::Enum {
+					let variants = column.suggested_variants.clone().unwrap_or_default();
+					value.insert("variants".to_owned(), variants.into());
+				}
+				serde_json::Value::Object(value)
+			})
+			.collect::<Vec<_>>();
+		serde_json::json!({
+			"dataset": {
+				"columns": columns,
+			},
+		})
+	}
+
+	/// Find the target column, returning an error listing the available columns if there is none.
+	pub fn target_column(&self, name: &str) -> Result<&InspectColumn> {
+		self.columns
+			.iter()
+			.find(|column| column.name == name)
+			.ok_or_else(|| {
+				let column_names = self
+					.columns
+					.iter()
+					.map(|column| column.name.as_str())
+					.collect::<Vec<_>>();
+				anyhow!(
+					"did not find target column \"{}\" among column names \"{}\"",
+					name,
+					column_names.join(", ")
+				)
+			})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_suggested_column_type() {
+		let values = vec![("1", 50), ("2", 45), ("n/a", 5)];
+		let number_fraction = number_fraction(values.into_iter());
+		assert_eq!(number_fraction, Some(0.95));
+		assert_eq!(
+			suggested_column_type(InspectColumnType::Enum, number_fraction),
+			InspectColumnType::Number
+		);
+		// Low cardinality numeric enums are left alone.
+		assert_eq!(
+			suggested_column_type(InspectColumnType::Enum, Some(1.0)),
+			InspectColumnType::Enum
+		);
+		assert_eq!(
+			suggested_column_type(InspectColumnType::Text, Some(0.5)),
+			InspectColumnType::Text
+		);
+	}
+
+	#[test]
+	fn test_non_numeric_examples() {
+		let values = vec!["1.5", "1,234", "", "1,234", "unknown"];
+		assert_eq!(
+			non_numeric_examples(values.into_iter()),
+			vec!["1,234".to_owned(), "unknown".to_owned()]
+		);
+	}
+}
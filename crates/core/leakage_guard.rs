@@ -0,0 +1,219 @@
+/*!
+This module implements the leakage guard, which screens each column on its own for how well it predicts the target before training. It is configured with the `dataset.leakage_guard` section of the config.
+*/
+
+use crate::{
+	config,
+	correlations::{correlation, cramers_v},
+	model::Task,
+};
+use modelfox_table::prelude::*;
+use num::ToPrimitive;
+use std::num::NonZeroUsize;
+
+/// The AUC of a column is computed from at most this many rows.
+const LEAKAGE_GUARD_MAX_ROWS: usize = 10_000;
+
+/// This is a column that tripped the leakage guard.
+#[derive(Clone, Debug)]
+pub struct LeakageGuardTrip {
+	pub column_name: String,
+	pub reason: LeakageGuardTripReason,
+}
+
+#[derive(Clone, Debug)]
+pub enum LeakageGuardTripReason {
+	/// The column's AUC for a binary classification target, on its own, is at least `dataset.leakage_guard.max_auc`.
+	Auc(f32),
+	/// The absolute value of the column's correlation with a regression target is at least `dataset.leakage_guard.max_correlation`.
+	Correlation(f32),
+	/// The column's Cramér's V with a multiclass classification target is at least `dataset.leakage_guard.max_correlation`.
+	CramersV(f32),
+}
+
+impl std::fmt::Display for LeakageGuardTrip {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.reason {
+			LeakageGuardTripReason::Auc(auc) => write!(
+				f,
+				"the column \"{}\" alone predicts the target with an AUC of {:.4}",
+				self.column_name, auc
+			),
+			LeakageGuardTripReason::Correlation(correlation) => write!(
+				f,
+				"the column \"{}\" has a correlation of {:.4} with the target",
+				self.column_name, correlation
+			),
+			LeakageGuardTripReason::CramersV(cramers_v) => write!(
+				f,
+				"the column \"{}\" has a Cramér's V of {:.4} with the target",
+				self.column_name, cramers_v
+			),
+		}
+	}
+}
+
+/// Screen each column of the train table other than the target column, and return the columns that predict the target so well on their own that they are likely leaking it. For regression, only number columns are screened, and for multiclass classification, only enum columns are screened.
+pub fn screen_columns(
+	table_train: &TableView,
+	target_column_index: usize,
+	task: Task,
+	config: &config::LeakageGuard,
+) -> Vec<LeakageGuardTrip> {
+	let target_column = &table_train.columns()[target_column_index];
+	table_train
+		.columns()
+		.iter()
+		.enumerate()
+		.filter(|(column_index, _)| *column_index != target_column_index)
+		.filter_map(|(_, column)| {
+			let reason = match (task, target_column, column) {
+				(
+					Task::Regression,
+					TableColumnView::Number(target_column),
+					TableColumnView::Number(column),
+				) => correlation(target_column.as_slice(), column.as_slice())
+					.map(f32::abs)
+					.filter(|correlation| *correlation >= config.max_correlation)
+					.map(LeakageGuardTripReason::Correlation),
+				(Task::BinaryClassification, TableColumnView::Enum(target_column), column) => {
+					let auc = match column {
+						TableColumnView::Number(column) => {
+							number_column_auc(target_column.as_slice(), column.as_slice())
+						}
+						TableColumnView::Enum(column) => enum_column_auc(
+							target_column.as_slice(),
+							column.as_slice(),
+							column.variants().len(),
+						),
+						_ => None,
+					};
+					auc.filter(|auc| *auc >= config.max_auc)
+						.map(LeakageGuardTripReason::Auc)
+				}
+				(
+					Task::MulticlassClassification,
+					TableColumnView::Enum(target_column),
+					TableColumnView::Enum(column),
+				) => cramers_v(
+					target_column.as_slice(),
+					target_column.variants().len(),
+					column.as_slice(),
+					column.variants().len(),
+				)
+				.filter(|cramers_v| *cramers_v >= config.max_correlation)
+				.map(LeakageGuardTripReason::CramersV),
+				_ => None,
+			}?;
+			Some(LeakageGuardTrip {
+				column_name: column.name().unwrap().to_owned(),
+				reason,
+			})
+		})
+		.collect()
+}
+
+/// Compute the AUC of a number column used on its own as the score for a binary target, over the rows where both values are valid.
+fn number_column_auc(target: &[Option<NonZeroUsize>], column: &[f32]) -> Option<f32> {
+	let scores_and_labels = target
+		.iter()
+		.zip(column.iter())
+		.take(LEAKAGE_GUARD_MAX_ROWS)
+		.filter_map(|(label, score)| match (label, score.is_finite()) {
+			(Some(label), true) => Some((*score, label.get() == 2)),
+			_ => None,
+		})
+		.collect();
+	auc(scores_and_labels)
+}
+
+/// Compute the AUC of an enum column for a binary target, scoring each row with the fraction of the rows with the same variant that are positive. See [`number_column_auc`].
+fn enum_column_auc(
+	target: &[Option<NonZeroUsize>],
+	column: &[Option<NonZeroUsize>],
+	variant_count: usize,
+) -> Option<f32> {
+	let rows = target
+		.iter()
+		.zip(column.iter())
+		.take(LEAKAGE_GUARD_MAX_ROWS)
+		.filter_map(|(label, variant)| match (label, variant) {
+			(Some(label), Some(variant)) => Some((variant.get() - 1, label.get() == 2)),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	let mut positive_counts = vec![0usize; variant_count];
+	let mut counts = vec![0usize; variant_count];
+	for (variant, label) in rows.iter() {
+		counts[*variant] += 1;
+		if *label {
+			positive_counts[*variant] += 1;
+		}
+	}
+	let scores_and_labels = rows
+		.iter()
+		.map(|(variant, label)| {
+			let score =
+				positive_counts[*variant].to_f32().unwrap() / counts[*variant].to_f32().unwrap();
+			(score, *label)
+		})
+		.collect();
+	auc(scores_and_labels)
+}
+
+/// Compute the AUC from the Mann-Whitney U statistic, giving tied scores their average rank. A column that predicts the target perfectly in reverse is just as leaky, so this returns the larger of the AUC and one minus the AUC, or `None` if there are no positive or no negative rows.
+fn auc(mut scores_and_labels: Vec<(f32, bool)>) -> Option<f32> {
+	scores_and_labels.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+	let n_positives = scores_and_labels.iter().filter(|(_, label)| *label).count();
+	let n_negatives = scores_and_labels.len() - n_positives;
+	if n_positives == 0 || n_negatives == 0 {
+		return None;
+	}
+	let mut positive_rank_sum = 0.0f64;
+	let mut start = 0;
+	while start < scores_and_labels.len() {
+		let mut end = start;
+		while end < scores_and_labels.len()
+			&& scores_and_labels[end].0 == scores_and_labels[start].0
+		{
+			end += 1;
+		}
+		// The rows from `start` to `end` have tied scores, so they all get the average of ranks `start + 1` through `end`.
+		let average_rank = (start + 1 + end).to_f64().unwrap() / 2.0;
+		let n_tied_positives = scores_and_labels[start..end]
+			.iter()
+			.filter(|(_, label)| *label)
+			.count();
+		positive_rank_sum += average_rank * n_tied_positives.to_f64().unwrap();
+		start = end;
+	}
+	let n_positives = n_positives.to_f64().unwrap();
+	let n_negatives = n_negatives.to_f64().unwrap();
+	let u = positive_rank_sum - n_positives * (n_positives + 1.0) / 2.0;
+	let auc = u / (n_positives * n_negatives);
+	auc.max(1.0 - auc).to_f32()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_auc() {
+		// A score that separates the labels perfectly has an AUC of 1, in either direction.
+		assert_eq!(
+			auc(vec![(0.1, false), (0.2, false), (0.8, true), (0.9, true)]),
+			Some(1.0)
+		);
+		assert_eq!(
+			auc(vec![(0.1, true), (0.2, true), (0.8, false), (0.9, false)]),
+			Some(1.0)
+		);
+		// A constant score has an AUC of 0.5.
+		assert_eq!(
+			auc(vec![(0.5, false), (0.5, true), (0.5, false), (0.5, true)]),
+			Some(0.5)
+		);
+		assert_eq!(auc(vec![(0.1, true), (0.2, true)]), None);
+	}
+}
@@ -1,10 +1,25 @@
-mod config;
+pub mod checkpoint;
+pub mod config;
+pub mod config_check;
+mod correlations;
+pub mod database;
+mod feature_selection;
 mod features;
+pub mod flatten;
 mod grid;
 pub mod heuristics;
+pub mod inspect;
+mod leakage_guard;
 pub mod model;
+pub mod model_card;
+pub mod model_store;
 pub mod predict;
+pub mod predict_cache;
 pub mod progress;
+pub mod sampling;
 mod stats;
+mod table_cache;
 mod test;
+#[doc(hidden)]
+pub mod test_common;
 pub mod train;
@@ -1,15 +1,18 @@
 use crate::{
+	correlations::{ColumnCorrelation, CorrelationMethod},
+	feature_selection::{DroppedColumn, DroppedColumnReason},
 	stats::{
-		ColumnStatsOutput, EnumColumnStatsOutput, NumberColumnStatsOutput, StatsSettings,
-		TextColumnStatsOutput, TextColumnStatsOutputTopNGramsEntry, UnknownColumnStatsOutput,
+		ColumnStatsOutput, DateTimeColumnStatsOutput, EnumColumnStatsOutput,
+		NumberColumnStatsOutput, StatsSettings, TextColumnStatsOutput,
+		TextColumnStatsOutputTopNGramsEntry, UnknownColumnStatsOutput,
 	},
-	train::{TrainGridItemOutput, TrainModelOutput},
+	train::{GridItemTrainOptions, TrainGridItemOutput},
 };
 use anyhow::Result;
 use modelfox_id::Id;
 use modelfox_zip::zip;
 use num::ToPrimitive;
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
 pub struct Model {
 	pub id: Id,
@@ -34,6 +37,9 @@ pub struct Regressor {
 	pub overall_target_column_stats: ColumnStatsOutput,
 	pub train_column_stats: Vec<ColumnStatsOutput>,
 	pub train_target_column_stats: ColumnStatsOutput,
+	pub dropped_columns: Vec<DroppedColumn>,
+	pub column_correlations: Vec<ColumnCorrelation>,
+	pub invalid_value_policies: BTreeMap<String, modelfox_table::InvalidValuePolicy>,
 	pub test_column_stats: Vec<ColumnStatsOutput>,
 	pub test_target_column_stats: ColumnStatsOutput,
 	pub baseline_metrics: modelfox_metrics::RegressionMetricsOutput,
@@ -42,6 +48,7 @@ pub struct Regressor {
 	pub best_grid_item_index: usize,
 	pub model: RegressionModel,
 	pub test_metrics: modelfox_metrics::RegressionMetricsOutput,
+	pub tolerance: Option<modelfox_metrics::Tolerance>,
 }
 
 pub struct BinaryClassifier {
@@ -56,6 +63,9 @@ pub struct BinaryClassifier {
 	pub overall_target_column_stats: ColumnStatsOutput,
 	pub train_column_stats: Vec<ColumnStatsOutput>,
 	pub train_target_column_stats: ColumnStatsOutput,
+	pub dropped_columns: Vec<DroppedColumn>,
+	pub column_correlations: Vec<ColumnCorrelation>,
+	pub invalid_value_policies: BTreeMap<String, modelfox_table::InvalidValuePolicy>,
 	pub test_column_stats: Vec<ColumnStatsOutput>,
 	pub test_target_column_stats: ColumnStatsOutput,
 	pub baseline_metrics: modelfox_metrics::BinaryClassificationMetricsOutput,
@@ -77,6 +87,9 @@ pub struct MulticlassClassifier {
 	pub overall_target_column_stats: ColumnStatsOutput,
 	pub train_column_stats: Vec<ColumnStatsOutput>,
 	pub train_target_column_stats: ColumnStatsOutput,
+	pub dropped_columns: Vec<DroppedColumn>,
+	pub column_correlations: Vec<ColumnCorrelation>,
+	pub invalid_value_policies: BTreeMap<String, modelfox_table::InvalidValuePolicy>,
 	pub test_column_stats: Vec<ColumnStatsOutput>,
 	pub test_target_column_stats: ColumnStatsOutput,
 	pub baseline_metrics: modelfox_metrics::MulticlassClassificationMetricsOutput,
@@ -94,9 +107,14 @@ pub enum Task {
 	Regression,
 }
 
+/// The F1 score, precision, recall, and accuracy are computed at the default classification threshold of 0.5.
 #[derive(Clone, Copy)]
 pub enum BinaryClassificationComparisonMetric {
 	AucRoc,
+	F1,
+	Precision,
+	Recall,
+	Accuracy,
 }
 
 #[derive(Clone, Copy)]
@@ -115,6 +133,8 @@ pub struct LinearRegressionModel {
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub losses: Option<Vec<f32>>,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, if there was more than one.
+	pub feature_importances_std: Option<Vec<f32>>,
 }
 
 pub struct TreeRegressionModel {
@@ -123,6 +143,9 @@ pub struct TreeRegressionModel {
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub losses: Option<Vec<f32>>,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, if there was more than one.
+	pub feature_importances_std: Option<Vec<f32>>,
+	pub bin_thresholds: Vec<Option<Vec<f32>>>,
 }
 
 #[derive(Clone, Copy)]
@@ -144,6 +167,8 @@ pub struct LinearBinaryClassificationModel {
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub losses: Option<Vec<f32>>,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, if there was more than one.
+	pub feature_importances_std: Option<Vec<f32>>,
 }
 
 pub struct TreeBinaryClassificationModel {
@@ -152,6 +177,9 @@ pub struct TreeBinaryClassificationModel {
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub losses: Option<Vec<f32>>,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, if there was more than one.
+	pub feature_importances_std: Option<Vec<f32>>,
+	pub bin_thresholds: Vec<Option<Vec<f32>>>,
 }
 
 pub enum MulticlassClassificationModel {
@@ -165,6 +193,8 @@ pub struct LinearMulticlassClassificationModel {
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub losses: Option<Vec<f32>>,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, if there was more than one.
+	pub feature_importances_std: Option<Vec<f32>>,
 }
 
 pub struct TreeMulticlassClassificationModel {
@@ -173,6 +203,9 @@ pub struct TreeMulticlassClassificationModel {
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub losses: Option<Vec<f32>>,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, if there was more than one.
+	pub feature_importances_std: Option<Vec<f32>>,
+	pub bin_thresholds: Vec<Option<Vec<f32>>>,
 }
 
 #[derive(Clone, Copy)]
@@ -190,12 +223,21 @@ pub enum Metrics {
 
 impl Model {
 	pub fn to_path(&self, path: &Path) -> Result<()> {
+		let bytes = self.serialize();
+		modelfox_model::to_path(path, &bytes)?;
+		Ok(())
+	}
+
+	/// Serialize the model to the contents of a `.modelfox` file.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		modelfox_model::to_bytes(&self.serialize())
+	}
+
+	fn serialize(&self) -> Vec<u8> {
 		let mut writer = buffalo::Writer::new();
 		let model = serialize_model(self, &mut writer);
 		writer.write(&model);
-		let bytes = writer.into_bytes();
-		modelfox_model::to_path(path, &bytes)?;
-		Ok(())
+		writer.into_bytes()
 	}
 }
 
@@ -258,6 +300,26 @@ fn serialize_regressor(
 	let train_column_stats = writer.write(&train_column_stats);
 	let train_target_column_stats =
 		serialize_column_stats_output(&regressor.train_target_column_stats, writer);
+	let dropped_columns = regressor
+		.dropped_columns
+		.iter()
+		.map(|dropped_column| serialize_dropped_column(dropped_column, writer))
+		.collect::<Vec<_>>();
+	let dropped_columns = Some(writer.write(&dropped_columns));
+	let column_correlations = regressor
+		.column_correlations
+		.iter()
+		.map(|column_correlation| serialize_column_correlation(column_correlation, writer))
+		.collect::<Vec<_>>();
+	let column_correlations = Some(writer.write(&column_correlations));
+	let invalid_value_policies = regressor
+		.invalid_value_policies
+		.iter()
+		.map(|(column_name, policy)| {
+			serialize_column_invalid_value_policy(column_name, *policy, writer)
+		})
+		.collect::<Vec<_>>();
+	let invalid_value_policies = Some(writer.write(&invalid_value_policies));
 	let test_column_stats = regressor
 		.test_column_stats
 		.iter()
@@ -279,6 +341,9 @@ fn serialize_regressor(
 	let train_grid_item_outputs = writer.write(&train_grid_item_outputs);
 	let model = serialize_regression_model(&regressor.model, writer);
 	let test_metrics = serialize_regression_metrics_output(&regressor.test_metrics, writer);
+	let tolerance = regressor
+		.tolerance
+		.map(|tolerance| serialize_regression_tolerance(&tolerance, writer));
 	let regressor_writer = modelfox_model::RegressorWriter {
 		target_column_name,
 		train_row_count: regressor.train_row_count.to_u64().unwrap(),
@@ -297,6 +362,10 @@ fn serialize_regressor(
 		best_grid_item_index: regressor.best_grid_item_index.to_u64().unwrap(),
 		model,
 		test_metrics,
+		dropped_columns,
+		invalid_value_policies,
+		column_correlations,
+		tolerance,
 	};
 	writer.write(&regressor_writer)
 }
@@ -325,6 +394,26 @@ fn serialize_binary_classifier(
 	let train_column_stats = writer.write(&train_column_stats);
 	let train_target_column_stats =
 		serialize_column_stats_output(&binary_classifier.train_target_column_stats, writer);
+	let dropped_columns = binary_classifier
+		.dropped_columns
+		.iter()
+		.map(|dropped_column| serialize_dropped_column(dropped_column, writer))
+		.collect::<Vec<_>>();
+	let dropped_columns = Some(writer.write(&dropped_columns));
+	let column_correlations = binary_classifier
+		.column_correlations
+		.iter()
+		.map(|column_correlation| serialize_column_correlation(column_correlation, writer))
+		.collect::<Vec<_>>();
+	let column_correlations = Some(writer.write(&column_correlations));
+	let invalid_value_policies = binary_classifier
+		.invalid_value_policies
+		.iter()
+		.map(|(column_name, policy)| {
+			serialize_column_invalid_value_policy(column_name, *policy, writer)
+		})
+		.collect::<Vec<_>>();
+	let invalid_value_policies = Some(writer.write(&invalid_value_policies));
 	let test_column_stats = binary_classifier
 		.test_column_stats
 		.iter()
@@ -370,6 +459,9 @@ fn serialize_binary_classifier(
 		test_metrics,
 		negative_class,
 		positive_class,
+		dropped_columns,
+		invalid_value_policies,
+		column_correlations,
 	};
 	writer.write(&binary_classifier_writer)
 }
@@ -396,6 +488,26 @@ fn serialize_multiclass_classifier(
 	let train_column_stats = writer.write(&train_column_stats);
 	let train_target_column_stats =
 		serialize_column_stats_output(&multiclass_classifier.train_target_column_stats, writer);
+	let dropped_columns = multiclass_classifier
+		.dropped_columns
+		.iter()
+		.map(|dropped_column| serialize_dropped_column(dropped_column, writer))
+		.collect::<Vec<_>>();
+	let dropped_columns = Some(writer.write(&dropped_columns));
+	let column_correlations = multiclass_classifier
+		.column_correlations
+		.iter()
+		.map(|column_correlation| serialize_column_correlation(column_correlation, writer))
+		.collect::<Vec<_>>();
+	let column_correlations = Some(writer.write(&column_correlations));
+	let invalid_value_policies = multiclass_classifier
+		.invalid_value_policies
+		.iter()
+		.map(|(column_name, policy)| {
+			serialize_column_invalid_value_policy(column_name, *policy, writer)
+		})
+		.collect::<Vec<_>>();
+	let invalid_value_policies = Some(writer.write(&invalid_value_policies));
 	let test_column_stats = multiclass_classifier
 		.test_column_stats
 		.iter()
@@ -450,6 +562,9 @@ fn serialize_multiclass_classifier(
 		model,
 		test_metrics,
 		classes,
+		dropped_columns,
+		invalid_value_policies,
+		column_correlations,
 	};
 	writer.write(&multiclass_classifier_writer)
 }
@@ -458,12 +573,102 @@ fn serialize_stats_settings(
 	stats_settings: &StatsSettings,
 	writer: &mut buffalo::Writer,
 ) -> buffalo::Position<modelfox_model::StatsSettingsWriter> {
+	let number_quantiles = writer.write(stats_settings.number_quantiles.as_slice());
 	let stats_settings_writer = modelfox_model::StatsSettingsWriter {
 		number_histogram_max_size: stats_settings.number_histogram_max_size.to_u64().unwrap(),
+		datetime_histogram_bin_count: Some(
+			stats_settings
+				.datetime_histogram_bin_count
+				.to_u64()
+				.unwrap(),
+		),
+		number_quantiles: Some(number_quantiles),
 	};
 	writer.write(&stats_settings_writer)
 }
 
+fn serialize_column_invalid_value_policy(
+	column_name: &str,
+	policy: modelfox_table::InvalidValuePolicy,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::ColumnInvalidValuePolicyWriter> {
+	let column_name = writer.write(column_name);
+	let policy = match policy {
+		modelfox_table::InvalidValuePolicy::Missing => {
+			modelfox_model::InvalidValuePolicyWriter::Missing
+		}
+		modelfox_table::InvalidValuePolicy::DropRow => {
+			modelfox_model::InvalidValuePolicyWriter::DropRow
+		}
+		modelfox_table::InvalidValuePolicy::Error => {
+			modelfox_model::InvalidValuePolicyWriter::Error
+		}
+	};
+	let column_invalid_value_policy_writer = modelfox_model::ColumnInvalidValuePolicyWriter {
+		column_name,
+		policy,
+	};
+	writer.write(&column_invalid_value_policy_writer)
+}
+
+fn serialize_column_correlation(
+	column_correlation: &ColumnCorrelation,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::ColumnCorrelationWriter> {
+	let column_name_a = writer.write(column_correlation.column_name_a.as_str());
+	let column_name_b = writer.write(column_correlation.column_name_b.as_str());
+	let method = match column_correlation.method {
+		CorrelationMethod::Pearson => modelfox_model::CorrelationMethodWriter::Pearson,
+		CorrelationMethod::CramersV => modelfox_model::CorrelationMethodWriter::CramersV,
+	};
+	let column_correlation_writer = modelfox_model::ColumnCorrelationWriter {
+		column_name_a,
+		column_name_b,
+		method,
+		value: column_correlation.value,
+	};
+	writer.write(&column_correlation_writer)
+}
+
+fn serialize_dropped_column(
+	dropped_column: &DroppedColumn,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::DroppedColumnWriter> {
+	let column_name = writer.write(dropped_column.column_name.as_str());
+	let reason = match &dropped_column.reason {
+		DroppedColumnReason::LowVariance { variance } => {
+			let reason = writer.write(&modelfox_model::LowVarianceDroppedColumnReasonWriter {
+				variance: *variance,
+			});
+			modelfox_model::DroppedColumnReasonWriter::LowVariance(reason)
+		}
+		DroppedColumnReason::HighInvalidFraction { invalid_fraction } => {
+			let reason = writer.write(
+				&modelfox_model::HighInvalidFractionDroppedColumnReasonWriter {
+					invalid_fraction: *invalid_fraction,
+				},
+			);
+			modelfox_model::DroppedColumnReasonWriter::HighInvalidFraction(reason)
+		}
+		DroppedColumnReason::HighCorrelation {
+			correlated_column_name,
+			correlation,
+		} => {
+			let correlated_column_name = writer.write(correlated_column_name.as_str());
+			let reason = writer.write(&modelfox_model::HighCorrelationDroppedColumnReasonWriter {
+				correlated_column_name,
+				correlation: *correlation,
+			});
+			modelfox_model::DroppedColumnReasonWriter::HighCorrelation(reason)
+		}
+	};
+	let dropped_column_writer = modelfox_model::DroppedColumnWriter {
+		column_name,
+		reason,
+	};
+	writer.write(&dropped_column_writer)
+}
+
 fn serialize_column_stats_output(
 	column_stats_output: &ColumnStatsOutput,
 	writer: &mut buffalo::Writer,
@@ -487,6 +692,11 @@ fn serialize_column_stats_output(
 			let text_column_stats = serialize_text_column_stats_output(text_column_stats, writer);
 			modelfox_model::ColumnStatsWriter::TextColumn(text_column_stats)
 		}
+		ColumnStatsOutput::DateTime(datetime_column_stats) => {
+			let datetime_column_stats =
+				serialize_datetime_column_stats_output(datetime_column_stats, writer);
+			modelfox_model::ColumnStatsWriter::DateTimeColumn(datetime_column_stats)
+		}
 	}
 }
 
@@ -514,6 +724,7 @@ fn serialize_number_column_stats_output(
 				.collect::<Vec<_>>()
 		});
 	let histogram = histogram.map(|histogram| writer.write(histogram.as_slice()));
+	let quantiles = writer.write(number_column_stats_output.quantiles.as_slice());
 	let number_column_stats = modelfox_model::NumberColumnStatsWriter {
 		column_name,
 		invalid_count: number_column_stats_output.invalid_count.to_u64().unwrap(),
@@ -527,6 +738,7 @@ fn serialize_number_column_stats_output(
 		p25: number_column_stats_output.p25,
 		p50: number_column_stats_output.p50,
 		p75: number_column_stats_output.p75,
+		quantiles: Some(quantiles),
 	};
 	writer.write(&number_column_stats)
 }
@@ -578,16 +790,44 @@ fn serialize_text_column_stats_output(
 		})
 		.collect::<Vec<_>>();
 	let top_ngrams = writer.write(&top_ngrams);
+	let language = text_column_stats_output
+		.language
+		.map(|language| writer.write(language.as_str()));
 	let text_column_stats = modelfox_model::TextColumnStatsWriter {
 		column_name,
 		tokenizer,
 		ngram_types,
 		ngrams_count,
 		top_ngrams,
+		language,
 	};
 	writer.write(&text_column_stats)
 }
 
+fn serialize_datetime_column_stats_output(
+	datetime_column_stats_output: &DateTimeColumnStatsOutput,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::DateTimeColumnStatsWriter> {
+	let column_name = writer.write(datetime_column_stats_output.column_name.as_str());
+	let timezone = writer.write(datetime_column_stats_output.timezone.name());
+	let histogram = datetime_column_stats_output
+		.histogram
+		.iter()
+		.map(|(key, value)| (*key, value.to_u64().unwrap()))
+		.collect::<Vec<_>>();
+	let histogram = writer.write(histogram.as_slice());
+	let datetime_column_stats = modelfox_model::DateTimeColumnStatsWriter {
+		column_name,
+		timezone,
+		invalid_count: datetime_column_stats_output.invalid_count.to_u64().unwrap(),
+		unique_count: datetime_column_stats_output.unique_count.to_u64().unwrap(),
+		min: datetime_column_stats_output.min,
+		max: datetime_column_stats_output.max,
+		histogram,
+	};
+	writer.write(&datetime_column_stats)
+}
+
 fn serialize_tokenizer(
 	tokenizer: &modelfox_text::Tokenizer,
 	writer: &mut buffalo::Writer,
@@ -651,10 +891,29 @@ fn serialize_regression_metrics_output(
 		rmse: regression_metrics_output.rmse,
 		mae: regression_metrics_output.mae,
 		r2: regression_metrics_output.r2,
+		tolerance_accuracy: regression_metrics_output.tolerance_accuracy,
 	};
 	writer.write(&regression_metrics_writer)
 }
 
+fn serialize_regression_tolerance(
+	tolerance: &modelfox_metrics::Tolerance,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::RegressionToleranceWriter> {
+	let (kind, value) = match tolerance {
+		modelfox_metrics::Tolerance::Absolute(value) => (
+			modelfox_model::RegressionToleranceKindWriter::Absolute,
+			*value,
+		),
+		modelfox_metrics::Tolerance::Relative(value) => (
+			modelfox_model::RegressionToleranceKindWriter::Relative,
+			*value,
+		),
+	};
+	let regression_tolerance_writer = modelfox_model::RegressionToleranceWriter { kind, value };
+	writer.write(&regression_tolerance_writer)
+}
+
 fn serialize_regression_comparison_metric(
 	regression_comparison_metric_writer: &RegressionComparisonMetric,
 	_writer: &mut buffalo::Writer,
@@ -710,6 +969,10 @@ fn serialize_linear_regression_model(
 	writer: &mut buffalo::Writer,
 ) -> buffalo::Position<modelfox_model::LinearRegressorWriter> {
 	let feature_importances = writer.write(linear_regression_model.feature_importances.as_slice());
+	let feature_importances_std = linear_regression_model
+		.feature_importances_std
+		.as_ref()
+		.map(|feature_importances_std| writer.write(feature_importances_std.as_slice()));
 	let train_options =
 		serialize_linear_train_options(&linear_regression_model.train_options, writer);
 	let feature_groups = linear_regression_model
@@ -729,6 +992,7 @@ fn serialize_linear_regression_model(
 		feature_groups,
 		losses,
 		feature_importances,
+		feature_importances_std,
 	};
 	writer.write(&linear_regressor_writer)
 }
@@ -744,6 +1008,60 @@ fn serialize_linear_train_options(
 			.map(|early_stopping_options| {
 				serialize_early_stopping_options(early_stopping_options, writer)
 			});
+	let (optimizer, momentum, adam_beta_1, adam_beta_2, adam_epsilon) =
+		match train_options.optimizer {
+			modelfox_linear::Optimizer::Sgd => (
+				modelfox_model::LinearOptimizerWriter::Sgd,
+				None,
+				None,
+				None,
+				None,
+			),
+			modelfox_linear::Optimizer::Momentum { momentum } => (
+				modelfox_model::LinearOptimizerWriter::Momentum,
+				Some(momentum),
+				None,
+				None,
+				None,
+			),
+			modelfox_linear::Optimizer::Adam {
+				beta_1,
+				beta_2,
+				epsilon,
+			} => (
+				modelfox_model::LinearOptimizerWriter::Adam,
+				None,
+				Some(beta_1),
+				Some(beta_2),
+				Some(epsilon),
+			),
+		};
+	let (learning_rate_schedule, learning_rate_decay_rate, learning_rate_n_epochs_per_step) =
+		match train_options.learning_rate_schedule {
+			modelfox_linear::LearningRateSchedule::Constant => (
+				modelfox_model::LearningRateScheduleWriter::Constant,
+				None,
+				None,
+			),
+			modelfox_linear::LearningRateSchedule::Step {
+				decay_rate,
+				n_epochs_per_step,
+			} => (
+				modelfox_model::LearningRateScheduleWriter::Step,
+				Some(decay_rate),
+				Some(n_epochs_per_step.to_u64().unwrap()),
+			),
+			modelfox_linear::LearningRateSchedule::Exponential { decay_rate } => (
+				modelfox_model::LearningRateScheduleWriter::Exponential,
+				Some(decay_rate),
+				None,
+			),
+			modelfox_linear::LearningRateSchedule::InverseTime { decay_rate } => (
+				modelfox_model::LearningRateScheduleWriter::InverseTime,
+				Some(decay_rate),
+				None,
+			),
+		};
 	let train_options = modelfox_model::LinearModelTrainOptionsWriter {
 		compute_loss: train_options.compute_losses,
 		l2_regularization: train_options.l2_regularization,
@@ -751,6 +1069,14 @@ fn serialize_linear_train_options(
 		max_epochs: train_options.max_epochs.to_u64().unwrap(),
 		n_examples_per_batch: train_options.n_examples_per_batch.to_u64().unwrap(),
 		early_stopping_options,
+		optimizer: Some(optimizer),
+		momentum,
+		adam_beta_1,
+		adam_beta_2,
+		adam_epsilon,
+		learning_rate_schedule: Some(learning_rate_schedule),
+		learning_rate_decay_rate,
+		learning_rate_n_epochs_per_step,
 	};
 	writer.write(&train_options)
 }
@@ -771,6 +1097,18 @@ fn serialize_tree_train_options(
 		.map(|max_depth| max_depth.to_u64().unwrap());
 	let binned_features_layout =
 		serialize_binned_features_layout(&train_options.binned_features_layout, writer);
+	let binning_strategy = serialize_binning_strategy(&train_options.binning_strategy, writer);
+	let interaction_constraints =
+		train_options
+			.interaction_constraints
+			.as_ref()
+			.map(|interaction_constraints| {
+				let groups = interaction_constraints
+					.iter()
+					.map(|group| serialize_tree_interaction_constraint_group(group, writer))
+					.collect::<Vec<_>>();
+				writer.write(&groups)
+			});
 	let train_options = modelfox_model::TreeModelTrainOptionsWriter {
 		compute_loss: train_options.compute_losses,
 		l2_regularization_for_continuous_splits: train_options
@@ -795,10 +1133,25 @@ fn serialize_tree_train_options(
 		min_sum_hessians_per_node: train_options.min_sum_hessians_per_node,
 		smoothing_factor_for_discrete_bin_sorting: train_options
 			.smoothing_factor_for_discrete_bin_sorting,
+		binning_strategy: Some(binning_strategy),
+		interaction_constraints,
 	};
 	writer.write(&train_options)
 }
 
+fn serialize_tree_interaction_constraint_group(
+	group: &[usize],
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::TreeInteractionConstraintGroupWriter> {
+	let feature_indexes = group
+		.iter()
+		.map(|feature_index| feature_index.to_u64().unwrap())
+		.collect::<Vec<_>>();
+	let feature_indexes = writer.write(&feature_indexes);
+	let group = modelfox_model::TreeInteractionConstraintGroupWriter { feature_indexes };
+	writer.write(&group)
+}
+
 fn serialize_tree_early_stopping_options(
 	early_stopping_options: &modelfox_tree::EarlyStoppingOptions,
 	writer: &mut buffalo::Writer,
@@ -820,6 +1173,10 @@ fn serialize_tree_regression_model(
 	writer: &mut buffalo::Writer,
 ) -> buffalo::Position<modelfox_model::TreeRegressorWriter> {
 	let feature_importances = writer.write(tree_regression_model.feature_importances.as_slice());
+	let feature_importances_std = tree_regression_model
+		.feature_importances_std
+		.as_ref()
+		.map(|feature_importances_std| writer.write(feature_importances_std.as_slice()));
 	let train_options = serialize_tree_train_options(&tree_regression_model.train_options, writer);
 	let feature_groups = tree_regression_model
 		.feature_groups
@@ -831,6 +1188,12 @@ fn serialize_tree_regression_model(
 		.losses
 		.as_ref()
 		.map(|losses| writer.write(losses.as_slice()));
+	let bin_thresholds = tree_regression_model
+		.bin_thresholds
+		.iter()
+		.map(|bin_thresholds| serialize_tree_bin_thresholds(bin_thresholds.as_deref(), writer))
+		.collect::<Vec<_>>();
+	let bin_thresholds = Some(writer.write(&bin_thresholds));
 	let model = tree_regression_model.model.to_writer(writer);
 	let model = modelfox_model::TreeRegressorWriter {
 		model,
@@ -838,10 +1201,31 @@ fn serialize_tree_regression_model(
 		feature_groups,
 		losses,
 		feature_importances,
+		feature_importances_std,
+		bin_thresholds,
 	};
 	writer.write(&model)
 }
 
+fn serialize_tree_bin_thresholds(
+	thresholds: Option<&[f32]>,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::TreeBinThresholdsWriter> {
+	let thresholds = thresholds.map(|thresholds| writer.write(thresholds));
+	let bin_thresholds = modelfox_model::TreeBinThresholdsWriter { thresholds };
+	writer.write(&bin_thresholds)
+}
+
+fn serialize_binning_strategy(
+	binning_strategy: &modelfox_tree::BinningStrategy,
+	_writer: &mut buffalo::Writer,
+) -> modelfox_model::BinningStrategyWriter {
+	match binning_strategy {
+		modelfox_tree::BinningStrategy::Quantile => modelfox_model::BinningStrategyWriter::Quantile,
+		modelfox_tree::BinningStrategy::Uniform => modelfox_model::BinningStrategyWriter::Uniform,
+	}
+}
+
 fn serialize_binned_features_layout(
 	binned_features_layout: &modelfox_tree::BinnedFeaturesLayout,
 	_writer: &mut buffalo::Writer,
@@ -860,29 +1244,13 @@ fn serialize_train_grid_item_output(
 	train_grid_item_output: &TrainGridItemOutput,
 	writer: &mut buffalo::Writer,
 ) -> buffalo::Position<modelfox_model::TrainGridItemOutputWriter> {
-	let hyperparameters = match &train_grid_item_output.train_model_output {
-		TrainModelOutput::LinearRegressor(model) => {
-			let options = serialize_linear_train_options(&model.train_options, writer);
-			modelfox_model::ModelTrainOptionsWriter::Linear(options)
-		}
-		TrainModelOutput::TreeRegressor(model) => {
-			let options = serialize_tree_train_options(&model.train_options, writer);
-			modelfox_model::ModelTrainOptionsWriter::Tree(options)
-		}
-		TrainModelOutput::LinearBinaryClassifier(model) => {
-			let options = serialize_linear_train_options(&model.train_options, writer);
+	let hyperparameters = match &train_grid_item_output.train_options {
+		GridItemTrainOptions::Linear(train_options) => {
+			let options = serialize_linear_train_options(train_options, writer);
 			modelfox_model::ModelTrainOptionsWriter::Linear(options)
 		}
-		TrainModelOutput::TreeBinaryClassifier(model) => {
-			let options = serialize_tree_train_options(&model.train_options, writer);
-			modelfox_model::ModelTrainOptionsWriter::Tree(options)
-		}
-		TrainModelOutput::LinearMulticlassClassifier(model) => {
-			let options = serialize_linear_train_options(&model.train_options, writer);
-			modelfox_model::ModelTrainOptionsWriter::Linear(options)
-		}
-		TrainModelOutput::TreeMulticlassClassifier(model) => {
-			let options = serialize_tree_train_options(&model.train_options, writer);
+		GridItemTrainOptions::Tree(train_options) => {
+			let options = serialize_tree_train_options(train_options, writer);
 			modelfox_model::ModelTrainOptionsWriter::Tree(options)
 		}
 	};
@@ -924,6 +1292,10 @@ fn serialize_feature_group(
 			let feature_group = serialize_word_embedding_feature_group(feature_group, writer);
 			modelfox_model::FeatureGroupWriter::WordEmbedding(feature_group)
 		}
+		modelfox_features::FeatureGroup::Cyclical(feature_group) => {
+			let feature_group = serialize_cyclical_feature_group(feature_group, writer);
+			modelfox_model::FeatureGroupWriter::Cyclical(feature_group)
+		}
 	}
 }
 
@@ -949,6 +1321,19 @@ fn serialize_normalized_feature_group(
 	writer.write(&feature_group)
 }
 
+fn serialize_cyclical_feature_group(
+	cyclical_feature_group: &modelfox_features::CyclicalFeatureGroup,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::CyclicalFeatureGroupWriter> {
+	let source_column_name = writer.write(cyclical_feature_group.source_column_name.as_str());
+	let feature_group = modelfox_model::CyclicalFeatureGroupWriter {
+		source_column_name,
+		period: cyclical_feature_group.period,
+		offset: cyclical_feature_group.offset,
+	};
+	writer.write(&feature_group)
+}
+
 fn serialize_one_hot_encoded_feature_group(
 	one_hot_encoded_feature_group: &modelfox_features::OneHotEncodedFeatureGroup,
 	writer: &mut buffalo::Writer,
@@ -1155,12 +1540,17 @@ fn serialize_linear_binary_classification_model(
 			.feature_importances
 			.as_slice(),
 	);
+	let feature_importances_std = linear_binary_classification_model
+		.feature_importances_std
+		.as_ref()
+		.map(|feature_importances_std| writer.write(feature_importances_std.as_slice()));
 	let model = modelfox_model::LinearBinaryClassifierWriter {
 		model,
 		train_options,
 		feature_groups,
 		losses,
 		feature_importances,
+		feature_importances_std,
 	};
 	writer.write(&model)
 }
@@ -1174,6 +1564,10 @@ fn serialize_tree_binary_classification_model(
 			.feature_importances
 			.as_slice(),
 	);
+	let feature_importances_std = tree_binary_classification_model
+		.feature_importances_std
+		.as_ref()
+		.map(|feature_importances_std| writer.write(feature_importances_std.as_slice()));
 	let train_options =
 		serialize_tree_train_options(&tree_binary_classification_model.train_options, writer);
 	let feature_groups = tree_binary_classification_model
@@ -1186,6 +1580,12 @@ fn serialize_tree_binary_classification_model(
 		.losses
 		.as_ref()
 		.map(|losses| writer.write(losses.as_slice()));
+	let bin_thresholds = tree_binary_classification_model
+		.bin_thresholds
+		.iter()
+		.map(|bin_thresholds| serialize_tree_bin_thresholds(bin_thresholds.as_deref(), writer))
+		.collect::<Vec<_>>();
+	let bin_thresholds = Some(writer.write(&bin_thresholds));
 	let model = tree_binary_classification_model.model.to_writer(writer);
 	let model = modelfox_model::TreeBinaryClassifierWriter {
 		model,
@@ -1193,6 +1593,8 @@ fn serialize_tree_binary_classification_model(
 		feature_groups,
 		losses,
 		feature_importances,
+		feature_importances_std,
+		bin_thresholds,
 	};
 	writer.write(&model)
 }
@@ -1262,6 +1664,18 @@ fn serialize_binary_classification_comparison_metric(
 		BinaryClassificationComparisonMetric::AucRoc => {
 			modelfox_model::BinaryClassificationComparisonMetricWriter::Aucroc
 		}
+		BinaryClassificationComparisonMetric::F1 => {
+			modelfox_model::BinaryClassificationComparisonMetricWriter::F1
+		}
+		BinaryClassificationComparisonMetric::Precision => {
+			modelfox_model::BinaryClassificationComparisonMetricWriter::Precision
+		}
+		BinaryClassificationComparisonMetric::Recall => {
+			modelfox_model::BinaryClassificationComparisonMetricWriter::Recall
+		}
+		BinaryClassificationComparisonMetric::Accuracy => {
+			modelfox_model::BinaryClassificationComparisonMetricWriter::Accuracy
+		}
 	}
 }
 
@@ -1294,6 +1708,10 @@ fn serialize_linear_multiclass_classification_model(
 			.feature_importances
 			.as_slice(),
 	);
+	let feature_importances_std = linear_multiclass_classification_model
+		.feature_importances_std
+		.as_ref()
+		.map(|feature_importances_std| writer.write(feature_importances_std.as_slice()));
 	let train_options = serialize_linear_train_options(
 		&linear_multiclass_classification_model.train_options,
 		writer,
@@ -1317,6 +1735,7 @@ fn serialize_linear_multiclass_classification_model(
 		feature_groups,
 		losses,
 		feature_importances,
+		feature_importances_std,
 	};
 	writer.write(&model)
 }
@@ -1330,6 +1749,10 @@ fn serialize_tree_multiclass_classification_model(
 			.feature_importances
 			.as_slice(),
 	);
+	let feature_importances_std = tree_multiclass_classification_model
+		.feature_importances_std
+		.as_ref()
+		.map(|feature_importances_std| writer.write(feature_importances_std.as_slice()));
 	let train_options =
 		serialize_tree_train_options(&tree_multiclass_classification_model.train_options, writer);
 	let feature_groups = tree_multiclass_classification_model
@@ -1342,6 +1765,12 @@ fn serialize_tree_multiclass_classification_model(
 		.losses
 		.as_ref()
 		.map(|losses| writer.write(losses.as_slice()));
+	let bin_thresholds = tree_multiclass_classification_model
+		.bin_thresholds
+		.iter()
+		.map(|bin_thresholds| serialize_tree_bin_thresholds(bin_thresholds.as_deref(), writer))
+		.collect::<Vec<_>>();
+	let bin_thresholds = Some(writer.write(&bin_thresholds));
 	let model = tree_multiclass_classification_model.model.to_writer(writer);
 	let model = modelfox_model::TreeMulticlassClassifierWriter {
 		model,
@@ -1349,6 +1778,8 @@ fn serialize_tree_multiclass_classification_model(
 		feature_groups,
 		losses,
 		feature_importances,
+		feature_importances_std,
+		bin_thresholds,
 	};
 	writer.write(&model)
 }
@@ -1363,6 +1794,19 @@ fn serialize_multiclass_classification_metrics_output(
 		.map(|class_metric| serialize_class_metrics(class_metric, writer))
 		.collect::<Vec<_>>();
 	let class_metrics = writer.write(&class_metrics);
+	let confusion_matrix = multiclass_classification_metrics_output
+		.confusion_matrix
+		.iter()
+		.flatten()
+		.cloned()
+		.collect::<Vec<_>>();
+	let confusion_matrix = Some(writer.write(&confusion_matrix));
+	let class_roc_curves = multiclass_classification_metrics_output
+		.class_roc_curves
+		.iter()
+		.map(|roc_curve| serialize_roc_curve(roc_curve, writer))
+		.collect::<Vec<_>>();
+	let class_roc_curves = Some(writer.write(&class_roc_curves));
 	let metrics = modelfox_model::MulticlassClassificationMetricsWriter {
 		class_metrics,
 		accuracy: multiclass_classification_metrics_output.accuracy,
@@ -1370,10 +1814,38 @@ fn serialize_multiclass_classification_metrics_output(
 		precision_weighted: multiclass_classification_metrics_output.precision_weighted,
 		recall_unweighted: multiclass_classification_metrics_output.recall_weighted,
 		recall_weighted: multiclass_classification_metrics_output.recall_weighted,
+		confusion_matrix,
+		class_roc_curves,
+		auc_roc_macro: Some(multiclass_classification_metrics_output.auc_roc_macro),
+		auc_roc_micro: Some(multiclass_classification_metrics_output.auc_roc_micro),
 	};
 	writer.write(&metrics)
 }
 
+fn serialize_roc_curve(
+	roc_curve: &modelfox_metrics::RocCurve,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::RocCurveWriter> {
+	let points = roc_curve
+		.points
+		.iter()
+		.map(|point| {
+			let point = modelfox_model::RocCurvePointWriter {
+				threshold: point.threshold,
+				true_positive_rate: point.true_positive_rate,
+				false_positive_rate: point.false_positive_rate,
+			};
+			writer.write(&point)
+		})
+		.collect::<Vec<_>>();
+	let points = writer.write(&points);
+	let roc_curve = modelfox_model::RocCurveWriter {
+		auc_roc_approx: roc_curve.auc_roc_approx,
+		points,
+	};
+	writer.write(&roc_curve)
+}
+
 fn serialize_class_metrics(
 	class_metrics: &modelfox_metrics::ClassMetrics,
 	writer: &mut buffalo::Writer,
@@ -0,0 +1,768 @@
+/*!
+This module generates a model card for a trained model. A model card is a short markdown document that describes what a model predicts, the data it was trained on, how well it performs, and the caveats to keep in mind when using it. Everything in it is derived from the `.modelfox` file, so it can be generated for any model, both by `modelfox modelcard` and by the model card page in the app.
+*/
+
+use crate::heuristics::{MIN_TEST_ROWS, MIN_TRAIN_ROWS};
+use modelfox_model::{
+	BinaryClassificationModelReader, ColumnStatsReader, DroppedColumnReader,
+	DroppedColumnReasonReader, FeatureGroupReader, ModelInnerReader, ModelReader,
+	MulticlassClassificationModelReader, RegressionModelReader,
+};
+use modelfox_number_formatter::NumberFormatter;
+use modelfox_zip::zip_eq;
+use num::ToPrimitive;
+use std::fmt::Write;
+
+/// This is the maximum number of features listed in the feature importances section, starting with the most important.
+const MAX_FEATURE_IMPORTANCES: usize = 20;
+
+pub struct ModelCard {
+	pub title: String,
+	pub model_id: String,
+	pub date: String,
+	/// This is the name of the task, such as "binary classification".
+	pub task_name: &'static str,
+	/// This is the name of the type of model, such as "Gradient Boosted Tree Binary Classifier".
+	pub model_type_name: &'static str,
+	pub target_column_name: String,
+	/// These are the classes the model predicts. It is `None` for regressors.
+	pub classes: Option<Vec<String>>,
+	pub overall_row_count: u64,
+	pub train_row_count: u64,
+	pub test_row_count: u64,
+	pub columns: Vec<ModelCardColumn>,
+	pub comparison_metric_name: &'static str,
+	pub metrics: Vec<ModelCardMetric>,
+	/// These are the metrics for each class. They are only computed for multiclass classifiers.
+	pub class_metrics: Option<Vec<ModelCardClassMetrics>>,
+	/// These are the most important features, ordered by importance, descending.
+	pub feature_importances: Vec<(String, f32)>,
+	pub hyperparameters: Vec<(String, String)>,
+	/// These are the caveats derived from the model's metrics and training data, such as the baseline outperforming the model.
+	pub caveats: Vec<String>,
+}
+
+pub struct ModelCardColumn {
+	pub name: String,
+	pub column_type: &'static str,
+	pub unique_count: Option<u64>,
+	pub invalid_count: Option<u64>,
+	pub min: Option<f32>,
+	pub max: Option<f32>,
+	pub mean: Option<f32>,
+}
+
+/// A test metric, along with the value the baseline model achieved, if it is available.
+pub struct ModelCardMetric {
+	pub name: &'static str,
+	pub value: Option<f32>,
+	pub baseline_value: Option<f32>,
+	/// If this is true, the values are fractions formatted as percentages.
+	pub percent: bool,
+}
+
+pub struct ModelCardClassMetrics {
+	pub class_name: String,
+	pub precision: f32,
+	pub recall: f32,
+	pub f1_score: f32,
+}
+
+/// Generate a model card for `model`. The `title` is used as the card's heading, for example the title of the model's repo.
+pub fn model_card(model: ModelReader, title: &str) -> ModelCard {
+	let title = title.to_owned();
+	let model_id = model.id().to_owned();
+	let date = model.date().to_owned();
+	match model.inner() {
+		ModelInnerReader::Regressor(regressor) => {
+			let regressor = regressor.read();
+			let best_grid_item = regressor
+				.train_grid_item_outputs()
+				.get(regressor.best_grid_item_index().to_usize().unwrap())
+				.unwrap();
+			let (model_type_name, feature_groups, feature_importances) = match regressor.model() {
+				RegressionModelReader::Linear(model) => {
+					let model = model.read();
+					(
+						"Linear Regressor",
+						model.feature_groups(),
+						model.feature_importances(),
+					)
+				}
+				RegressionModelReader::Tree(model) => {
+					let model = model.read();
+					(
+						"Gradient Boosted Tree Regressor",
+						model.feature_groups(),
+						model.feature_importances(),
+					)
+				}
+			};
+			let comparison_metric_name = match regressor.comparison_metric() {
+				modelfox_model::RegressionComparisonMetricReader::MeanAbsoluteError(_) => {
+					"Mean Absolute Error"
+				}
+				modelfox_model::RegressionComparisonMetricReader::MeanSquaredError(_) => {
+					"Mean Squared Error"
+				}
+				modelfox_model::RegressionComparisonMetricReader::RootMeanSquaredError(_) => {
+					"Root Mean Squared Error"
+				}
+				modelfox_model::RegressionComparisonMetricReader::R2(_) => "R2",
+			};
+			let test_metrics = regressor.test_metrics();
+			let baseline_metrics = regressor.baseline_metrics();
+			let mut metrics = vec![
+				ModelCardMetric {
+					name: "Root Mean Squared Error",
+					value: Some(test_metrics.rmse()),
+					baseline_value: Some(baseline_metrics.rmse()),
+					percent: false,
+				},
+				ModelCardMetric {
+					name: "Mean Squared Error",
+					value: Some(test_metrics.mse()),
+					baseline_value: Some(baseline_metrics.mse()),
+					percent: false,
+				},
+				ModelCardMetric {
+					name: "Mean Absolute Error",
+					value: Some(test_metrics.mae()),
+					baseline_value: Some(baseline_metrics.mae()),
+					percent: false,
+				},
+				ModelCardMetric {
+					name: "R2",
+					value: Some(test_metrics.r2()),
+					baseline_value: Some(baseline_metrics.r2()),
+					percent: false,
+				},
+			];
+			if let Some(tolerance_accuracy) = test_metrics.tolerance_accuracy() {
+				metrics.push(ModelCardMetric {
+					name: "Tolerance Accuracy",
+					value: Some(tolerance_accuracy),
+					baseline_value: baseline_metrics.tolerance_accuracy(),
+					percent: true,
+				});
+			}
+			let mut caveats = Vec::new();
+			if baseline_metrics.rmse() < test_metrics.rmse() {
+				caveats.push("The baseline root mean squared error is lower than the model's. The model performs worse than always predicting the mean of the target column.".to_owned());
+			}
+			caveats.extend(row_count_caveats(
+				regressor.train_row_count(),
+				regressor.test_row_count(),
+			));
+			if let Some(dropped_columns) = regressor.dropped_columns() {
+				caveats.extend(dropped_columns.iter().map(dropped_column_caveat));
+			}
+			ModelCard {
+				title,
+				model_id,
+				date,
+				task_name: "regression",
+				model_type_name,
+				target_column_name: regressor.target_column_name().to_owned(),
+				classes: None,
+				overall_row_count: regressor.overall_row_count(),
+				train_row_count: regressor.train_row_count(),
+				test_row_count: regressor.test_row_count(),
+				columns: regressor
+					.overall_column_stats()
+					.iter()
+					.map(|column_stats| compute_column(&column_stats))
+					.collect(),
+				comparison_metric_name,
+				metrics,
+				class_metrics: None,
+				feature_importances: compute_feature_importances(
+					feature_groups.iter(),
+					feature_importances.iter(),
+				),
+				hyperparameters: best_grid_item.hyperparameters().hyperparameters(),
+				caveats,
+			}
+		}
+		ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			let binary_classifier = binary_classifier.read();
+			let best_grid_item = binary_classifier
+				.train_grid_item_outputs()
+				.get(binary_classifier.best_grid_item_index().to_usize().unwrap())
+				.unwrap();
+			let (model_type_name, feature_groups, feature_importances) =
+				match binary_classifier.model() {
+					BinaryClassificationModelReader::Linear(model) => {
+						let model = model.read();
+						(
+							"Linear Binary Classifier",
+							model.feature_groups(),
+							model.feature_importances(),
+						)
+					}
+					BinaryClassificationModelReader::Tree(model) => {
+						let model = model.read();
+						(
+							"Gradient Boosted Tree Binary Classifier",
+							model.feature_groups(),
+							model.feature_importances(),
+						)
+					}
+				};
+			let comparison_metric_name = match binary_classifier.comparison_metric() {
+				modelfox_model::BinaryClassificationComparisonMetricReader::Aucroc(_) => {
+					"Area Under the Receiver Operating Characteristic Curve"
+				}
+				modelfox_model::BinaryClassificationComparisonMetricReader::F1(_) => "F1 Score",
+				modelfox_model::BinaryClassificationComparisonMetricReader::Precision(_) => {
+					"Precision"
+				}
+				modelfox_model::BinaryClassificationComparisonMetricReader::Recall(_) => "Recall",
+				modelfox_model::BinaryClassificationComparisonMetricReader::Accuracy(_) => {
+					"Accuracy"
+				}
+			};
+			let test_metrics = binary_classifier.test_metrics();
+			let default_threshold_test_metrics = test_metrics.default_threshold();
+			let default_threshold_baseline_metrics =
+				binary_classifier.baseline_metrics().default_threshold();
+			let metrics = vec![
+				ModelCardMetric {
+					name: "AUC ROC",
+					value: Some(test_metrics.auc_roc()),
+					baseline_value: None,
+					percent: false,
+				},
+				ModelCardMetric {
+					name: "Accuracy",
+					value: Some(default_threshold_test_metrics.accuracy()),
+					baseline_value: Some(default_threshold_baseline_metrics.accuracy()),
+					percent: true,
+				},
+				ModelCardMetric {
+					name: "Precision",
+					value: default_threshold_test_metrics.precision(),
+					baseline_value: None,
+					percent: true,
+				},
+				ModelCardMetric {
+					name: "Recall",
+					value: default_threshold_test_metrics.recall(),
+					baseline_value: None,
+					percent: true,
+				},
+				ModelCardMetric {
+					name: "F1 Score",
+					value: default_threshold_test_metrics.f1_score(),
+					baseline_value: None,
+					percent: true,
+				},
+			];
+			let mut caveats = Vec::new();
+			if default_threshold_baseline_metrics.accuracy()
+				> default_threshold_test_metrics.accuracy()
+			{
+				caveats.push("The baseline accuracy is higher than the model's. The model performs worse than always predicting the most common class.".to_owned());
+			}
+			if default_threshold_test_metrics.precision().is_none() {
+				caveats.push(format!(
+					"The model did not predict the positive class \"{}\" for any example in the test dataset at the default threshold of 0.5.",
+					binary_classifier.positive_class(),
+				));
+			}
+			caveats.extend(row_count_caveats(
+				binary_classifier.train_row_count(),
+				binary_classifier.test_row_count(),
+			));
+			if let Some(dropped_columns) = binary_classifier.dropped_columns() {
+				caveats.extend(dropped_columns.iter().map(dropped_column_caveat));
+			}
+			ModelCard {
+				title,
+				model_id,
+				date,
+				task_name: "binary classification",
+				model_type_name,
+				target_column_name: binary_classifier.target_column_name().to_owned(),
+				classes: Some(vec![
+					binary_classifier.negative_class().to_owned(),
+					binary_classifier.positive_class().to_owned(),
+				]),
+				overall_row_count: binary_classifier.overall_row_count(),
+				train_row_count: binary_classifier.train_row_count(),
+				test_row_count: binary_classifier.test_row_count(),
+				columns: binary_classifier
+					.overall_column_stats()
+					.iter()
+					.map(|column_stats| compute_column(&column_stats))
+					.collect(),
+				comparison_metric_name,
+				metrics,
+				class_metrics: None,
+				feature_importances: compute_feature_importances(
+					feature_groups.iter(),
+					feature_importances.iter(),
+				),
+				hyperparameters: best_grid_item.hyperparameters().hyperparameters(),
+				caveats,
+			}
+		}
+		ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier = multiclass_classifier.read();
+			let best_grid_item = multiclass_classifier
+				.train_grid_item_outputs()
+				.get(
+					multiclass_classifier
+						.best_grid_item_index()
+						.to_usize()
+						.unwrap(),
+				)
+				.unwrap();
+			let (model_type_name, feature_groups, feature_importances) =
+				match multiclass_classifier.model() {
+					MulticlassClassificationModelReader::Linear(model) => {
+						let model = model.read();
+						(
+							"Linear Multiclass Classifier",
+							model.feature_groups(),
+							model.feature_importances(),
+						)
+					}
+					MulticlassClassificationModelReader::Tree(model) => {
+						let model = model.read();
+						(
+							"Gradient Boosted Tree Multiclass Classifier",
+							model.feature_groups(),
+							model.feature_importances(),
+						)
+					}
+				};
+			let comparison_metric_name = match multiclass_classifier.comparison_metric() {
+				modelfox_model::MulticlassClassificationComparisonMetricReader::Accuracy(_) => {
+					"Accuracy"
+				}
+			};
+			let test_metrics = multiclass_classifier.test_metrics();
+			let baseline_metrics = multiclass_classifier.baseline_metrics();
+			let metrics = vec![
+				ModelCardMetric {
+					name: "Accuracy",
+					value: Some(test_metrics.accuracy()),
+					baseline_value: Some(baseline_metrics.accuracy()),
+					percent: true,
+				},
+				ModelCardMetric {
+					name: "Precision (Unweighted)",
+					value: Some(test_metrics.precision_unweighted()),
+					baseline_value: Some(baseline_metrics.precision_unweighted()),
+					percent: true,
+				},
+				ModelCardMetric {
+					name: "Precision (Weighted)",
+					value: Some(test_metrics.precision_weighted()),
+					baseline_value: Some(baseline_metrics.precision_weighted()),
+					percent: true,
+				},
+				ModelCardMetric {
+					name: "Recall (Unweighted)",
+					value: Some(test_metrics.recall_unweighted()),
+					baseline_value: Some(baseline_metrics.recall_unweighted()),
+					percent: true,
+				},
+				ModelCardMetric {
+					name: "Recall (Weighted)",
+					value: Some(test_metrics.recall_weighted()),
+					baseline_value: Some(baseline_metrics.recall_weighted()),
+					percent: true,
+				},
+			];
+			let class_metrics = multiclass_classifier
+				.classes()
+				.iter()
+				.zip(test_metrics.class_metrics().iter())
+				.map(|(class_name, class_metrics)| ModelCardClassMetrics {
+					class_name: class_name.to_owned(),
+					precision: class_metrics.precision(),
+					recall: class_metrics.recall(),
+					f1_score: class_metrics.f1_score(),
+				})
+				.collect::<Vec<_>>();
+			let mut caveats = Vec::new();
+			if baseline_metrics.accuracy() > test_metrics.accuracy() {
+				caveats.push("The baseline accuracy is higher than the model's. The model performs worse than always predicting the most common class.".to_owned());
+			}
+			for class_metrics in class_metrics.iter() {
+				if class_metrics.recall == 0.0 {
+					caveats.push(format!(
+						"The model did not correctly predict the class \"{}\" for any example in the test dataset.",
+						class_metrics.class_name,
+					));
+				}
+			}
+			caveats.extend(row_count_caveats(
+				multiclass_classifier.train_row_count(),
+				multiclass_classifier.test_row_count(),
+			));
+			if let Some(dropped_columns) = multiclass_classifier.dropped_columns() {
+				caveats.extend(dropped_columns.iter().map(dropped_column_caveat));
+			}
+			ModelCard {
+				title,
+				model_id,
+				date,
+				task_name: "multiclass classification",
+				model_type_name,
+				target_column_name: multiclass_classifier.target_column_name().to_owned(),
+				classes: Some(
+					multiclass_classifier
+						.classes()
+						.iter()
+						.map(ToOwned::to_owned)
+						.collect(),
+				),
+				overall_row_count: multiclass_classifier.overall_row_count(),
+				train_row_count: multiclass_classifier.train_row_count(),
+				test_row_count: multiclass_classifier.test_row_count(),
+				columns: multiclass_classifier
+					.overall_column_stats()
+					.iter()
+					.map(|column_stats| compute_column(&column_stats))
+					.collect(),
+				comparison_metric_name,
+				metrics,
+				class_metrics: Some(class_metrics),
+				feature_importances: compute_feature_importances(
+					feature_groups.iter(),
+					feature_importances.iter(),
+				),
+				hyperparameters: best_grid_item.hyperparameters().hyperparameters(),
+				caveats,
+			}
+		}
+	}
+}
+
+fn compute_column(column_stats: &ColumnStatsReader) -> ModelCardColumn {
+	match column_stats {
+		ColumnStatsReader::UnknownColumn(column_stats) => ModelCardColumn {
+			name: column_stats.read().column_name().to_owned(),
+			column_type: "Unknown",
+			unique_count: None,
+			invalid_count: None,
+			min: None,
+			max: None,
+			mean: None,
+		},
+		ColumnStatsReader::NumberColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			ModelCardColumn {
+				name: column_stats.column_name().to_owned(),
+				column_type: "Number",
+				unique_count: Some(column_stats.unique_count()),
+				invalid_count: Some(column_stats.invalid_count()),
+				min: Some(column_stats.min()),
+				max: Some(column_stats.max()),
+				mean: Some(column_stats.mean()),
+			}
+		}
+		ColumnStatsReader::EnumColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			ModelCardColumn {
+				name: column_stats.column_name().to_owned(),
+				column_type: "Enum",
+				unique_count: Some(column_stats.unique_count()),
+				invalid_count: Some(column_stats.invalid_count()),
+				min: None,
+				max: None,
+				mean: None,
+			}
+		}
+		ColumnStatsReader::TextColumn(column_stats) => ModelCardColumn {
+			name: column_stats.read().column_name().to_owned(),
+			column_type: "Text",
+			unique_count: None,
+			invalid_count: None,
+			min: None,
+			max: None,
+			mean: None,
+		},
+		ColumnStatsReader::DateTimeColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			ModelCardColumn {
+				name: column_stats.column_name().to_owned(),
+				column_type: "DateTime",
+				unique_count: Some(column_stats.unique_count()),
+				invalid_count: Some(column_stats.invalid_count()),
+				min: None,
+				max: None,
+				mean: None,
+			}
+		}
+	}
+}
+
+fn compute_feature_importances<'a>(
+	feature_groups: impl Iterator<Item = FeatureGroupReader<'a>>,
+	feature_importances: impl Iterator<Item = f32>,
+) -> Vec<(String, f32)> {
+	let feature_names = feature_groups.flat_map(|feature_group| feature_group.feature_names());
+	// If the number of feature names does not match the number of feature importances, the names would be paired with the wrong values, so leave the section empty.
+	let mut feature_importances = zip_eq!(feature_names, feature_importances)
+		.collect::<Result<Vec<_>, _>>()
+		.unwrap_or_default();
+	feature_importances.retain(|(_, value)| value.is_finite());
+	feature_importances.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+	feature_importances.truncate(MAX_FEATURE_IMPORTANCES);
+	feature_importances
+}
+
+fn row_count_caveats(train_row_count: u64, test_row_count: u64) -> Vec<String> {
+	let mut caveats = Vec::new();
+	if train_row_count.to_usize().unwrap() < MIN_TRAIN_ROWS {
+		caveats.push(format!(
+			"The train dataset is very small. It has only {} row(s).",
+			train_row_count
+		));
+	}
+	if test_row_count.to_usize().unwrap() < MIN_TEST_ROWS {
+		caveats.push(format!(
+			"The test dataset is very small, so the metrics may not reflect how the model performs in production. It has only {} row(s).",
+			test_row_count
+		));
+	}
+	caveats
+}
+
+fn dropped_column_caveat(dropped_column: DroppedColumnReader) -> String {
+	let reason = match dropped_column.reason() {
+		DroppedColumnReasonReader::LowVariance(reason) => format!(
+			"it has near zero variance ({})",
+			NumberFormatter::float(4).format(reason.read().variance())
+		),
+		DroppedColumnReasonReader::HighInvalidFraction(reason) => format!(
+			"{} of its values are invalid",
+			NumberFormatter::percent(2).format(reason.read().invalid_fraction())
+		),
+		DroppedColumnReasonReader::HighCorrelation(reason) => {
+			let reason = reason.read();
+			format!(
+				"it has a correlation of {} with the column \"{}\"",
+				NumberFormatter::float(4).format(reason.correlation()),
+				reason.correlated_column_name()
+			)
+		}
+	};
+	format!(
+		"Feature selection dropped the column \"{}\" because {}.",
+		dropped_column.column_name(),
+		reason
+	)
+}
+
+impl ModelCard {
+	/// Render the model card as markdown.
+	pub fn to_markdown(&self) -> String {
+		let number_formatter = NumberFormatter::float(4);
+		let percent_formatter = NumberFormatter::percent(2);
+		let format_metric = |value: Option<f32>, percent: bool| match value {
+			Some(value) if percent => percent_formatter.format(value),
+			Some(value) => number_formatter.format(value),
+			None => "-".to_owned(),
+		};
+		let format_count = |value: Option<u64>| {
+			value
+				.map(|value| value.to_string())
+				.unwrap_or_else(|| "-".to_owned())
+		};
+		let mut markdown = String::new();
+
+		writeln!(markdown, "# Model Card: {}", escape(&self.title)).unwrap();
+		writeln!(markdown).unwrap();
+		writeln!(
+			markdown,
+			"Model `{}`, trained on {}.",
+			self.model_id, self.date
+		)
+		.unwrap();
+		writeln!(markdown).unwrap();
+
+		writeln!(markdown, "## Intended Use").unwrap();
+		writeln!(markdown).unwrap();
+		let classes = match &self.classes {
+			Some(classes) => format!(
+				", one of {}",
+				classes
+					.iter()
+					.map(|class| format!("\"{}\"", escape(class)))
+					.collect::<Vec<_>>()
+					.join(", ")
+			),
+			None => String::new(),
+		};
+		writeln!(
+			markdown,
+			"This model is a {} that predicts the value of the column \"{}\"{}. It is intended to make predictions for examples drawn from the same population as its training data, with the same columns. Its predictions are less reliable for examples with values outside the ranges listed in the training data section below.",
+			self.model_type_name.to_lowercase(),
+			escape(&self.target_column_name),
+			classes,
+		)
+		.unwrap();
+		writeln!(markdown).unwrap();
+
+		writeln!(markdown, "## Training Data").unwrap();
+		writeln!(markdown).unwrap();
+		writeln!(
+			markdown,
+			"The dataset has {} rows, of which {} were used for training and {} were held out for testing. It has {} columns in addition to the target column.",
+			self.overall_row_count,
+			self.train_row_count,
+			self.test_row_count,
+			self.columns.len(),
+		)
+		.unwrap();
+		writeln!(markdown).unwrap();
+		writeln!(
+			markdown,
+			"| Column | Type | Unique Values | Invalid Values | Min | Max | Mean |"
+		)
+		.unwrap();
+		writeln!(markdown, "| --- | --- | --- | --- | --- | --- | --- |").unwrap();
+		for column in self.columns.iter() {
+			writeln!(
+				markdown,
+				"| {} | {} | {} | {} | {} | {} | {} |",
+				escape(&column.name),
+				column.column_type,
+				format_count(column.unique_count),
+				format_count(column.invalid_count),
+				format_metric(column.min, false),
+				format_metric(column.max, false),
+				format_metric(column.mean, false),
+			)
+			.unwrap();
+		}
+		writeln!(markdown).unwrap();
+
+		writeln!(markdown, "## Metrics").unwrap();
+		writeln!(markdown).unwrap();
+		writeln!(
+			markdown,
+			"These metrics were computed on the test dataset. The baseline is a model that always predicts the mean of the target column for regression, or the most common class for classification. The best model was chosen by its {}.",
+			self.comparison_metric_name.to_lowercase(),
+		)
+		.unwrap();
+		writeln!(markdown).unwrap();
+		writeln!(markdown, "| Metric | Value | Baseline |").unwrap();
+		writeln!(markdown, "| --- | --- | --- |").unwrap();
+		for metric in self.metrics.iter() {
+			writeln!(
+				markdown,
+				"| {} | {} | {} |",
+				metric.name,
+				format_metric(metric.value, metric.percent),
+				format_metric(metric.baseline_value, metric.percent),
+			)
+			.unwrap();
+		}
+		writeln!(markdown).unwrap();
+		if let Some(class_metrics) = &self.class_metrics {
+			writeln!(markdown, "### Metrics by Class").unwrap();
+			writeln!(markdown).unwrap();
+			writeln!(markdown, "| Class | Precision | Recall | F1 Score |").unwrap();
+			writeln!(markdown, "| --- | --- | --- | --- |").unwrap();
+			for class_metrics in class_metrics.iter() {
+				writeln!(
+					markdown,
+					"| {} | {} | {} | {} |",
+					escape(&class_metrics.class_name),
+					percent_formatter.format(class_metrics.precision),
+					percent_formatter.format(class_metrics.recall),
+					percent_formatter.format(class_metrics.f1_score),
+				)
+				.unwrap();
+			}
+			writeln!(markdown).unwrap();
+		}
+
+		writeln!(markdown, "## Feature Importances").unwrap();
+		writeln!(markdown).unwrap();
+		if self.feature_importances.is_empty() {
+			writeln!(
+				markdown,
+				"Feature importances are not available for this model."
+			)
+			.unwrap();
+		} else {
+			writeln!(markdown, "| Feature | Importance |").unwrap();
+			writeln!(markdown, "| --- | --- |").unwrap();
+			for (feature_name, feature_importance) in self.feature_importances.iter() {
+				writeln!(
+					markdown,
+					"| {} | {} |",
+					escape(feature_name),
+					number_formatter.format(*feature_importance),
+				)
+				.unwrap();
+			}
+		}
+		writeln!(markdown).unwrap();
+
+		writeln!(markdown, "## Training Configuration").unwrap();
+		writeln!(markdown).unwrap();
+		writeln!(markdown, "| Hyperparameter | Value |").unwrap();
+		writeln!(markdown, "| --- | --- |").unwrap();
+		for (name, value) in self.hyperparameters.iter() {
+			writeln!(markdown, "| {} | {} |", name, escape(value)).unwrap();
+		}
+		writeln!(markdown).unwrap();
+
+		writeln!(markdown, "## Caveats").unwrap();
+		writeln!(markdown).unwrap();
+		if self.caveats.is_empty() {
+			writeln!(
+				markdown,
+				"No problems were detected with this model's metrics or training data."
+			)
+			.unwrap();
+		} else {
+			for caveat in self.caveats.iter() {
+				writeln!(markdown, "- {}", escape(caveat)).unwrap();
+			}
+		}
+
+		markdown
+	}
+}
+
+/// Escape the characters that would otherwise be interpreted as markdown, so values from the dataset, such as column names, are rendered as text.
+fn escape(value: &str) -> String {
+	let mut output = String::with_capacity(value.len());
+	for c in value.chars() {
+		if matches!(
+			c,
+			'\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#'
+		) {
+			output.push('\\');
+		}
+		output.push(c);
+	}
+	output
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_escape() {
+		assert_eq!(escape("age"), "age");
+		assert_eq!(escape("a|b"), "a\\|b");
+		assert_eq!(escape("<script>"), "\\<script\\>");
+	}
+
+	#[test]
+	fn test_row_count_caveats() {
+		assert!(row_count_caveats(1000, 100).is_empty());
+		assert_eq!(row_count_caveats(10, 5).len(), 2);
+	}
+}
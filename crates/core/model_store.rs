@@ -0,0 +1,280 @@
+/*!
+This module implements a store for services that load many models in one process and predict with them by id. Models trained on the same text columns often have identical ngram vocabularies and word embeddings, which can be most of a model's memory. The store interns them, so every model with the same vocabulary or word embedding model shares one copy of it.
+*/
+
+use crate::predict::{predict, Model, ModelInner, PredictInput, PredictOptions, PredictOutput};
+use fnv::{FnvBuildHasher, FnvHashMap, FnvHasher};
+use indexmap::IndexMap;
+use modelfox_features::{bag_of_words::BagOfWordsFeatureGroupNGramEntry, FeatureGroup};
+use modelfox_text::{NGram, WordEmbeddingModel};
+use std::{
+	hash::{Hash, Hasher},
+	mem::size_of,
+	sync::{Arc, RwLock},
+};
+
+type Vocabulary = IndexMap<NGram, BagOfWordsFeatureGroupNGramEntry, FnvBuildHasher>;
+
+/// A thread safe map from model ids to models that shares identical vocabularies and word embedding models between them.
+#[derive(Debug, Default)]
+pub struct ModelStore {
+	state: RwLock<ModelStoreState>,
+}
+
+#[derive(Debug, Default)]
+struct ModelStoreState {
+	models: FnvHashMap<String, Arc<Model>>,
+	/// The interned vocabularies, grouped by the hash of their contents.
+	vocabularies: FnvHashMap<u64, Vec<Arc<Vocabulary>>>,
+	/// The interned word embedding models, grouped by the hash of their contents.
+	word_embedding_models: FnvHashMap<u64, Vec<Arc<WordEmbeddingModel>>>,
+}
+
+/// The sizes in this struct are estimates of the heap memory used by the interned values, not exact counts.
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+pub struct ModelStoreStats {
+	/// This is the number of models in the store.
+	pub n_models: usize,
+	/// This is the number of distinct vocabularies and word embedding models the models hold.
+	pub n_interned: usize,
+	/// This is the memory used by the one copy of each interned value.
+	pub interned_bytes: usize,
+	/// This is the memory the models would use for the copies of the interned values they share if they were not interned.
+	pub saved_bytes: usize,
+}
+
+impl ModelStore {
+	pub fn new() -> ModelStore {
+		ModelStore::default()
+	}
+
+	/// Add the model to the store, replacing any model with the same id, and return its id.
+	pub fn insert(&self, mut model: Model) -> String {
+		let mut state = self.state.write().unwrap();
+		for feature_group in feature_groups_mut(&mut model) {
+			match feature_group {
+				FeatureGroup::BagOfWords(feature_group) => {
+					feature_group.ngrams = state.intern_vocabulary(feature_group.ngrams.clone());
+				}
+				FeatureGroup::BagOfWordsCosineSimilarity(feature_group) => {
+					feature_group.ngrams = state.intern_vocabulary(feature_group.ngrams.clone());
+				}
+				FeatureGroup::WordEmbedding(feature_group) => {
+					feature_group.model =
+						state.intern_word_embedding_model(feature_group.model.clone());
+				}
+				_ => {}
+			}
+		}
+		let id = model.id.clone();
+		state.models.insert(id.clone(), Arc::new(model));
+		state.prune();
+		id
+	}
+
+	/// Remove the model with the given id from the store, and return it if it was present. Interned values that no model in the store uses anymore are dropped.
+	pub fn remove(&self, id: &str) -> Option<Arc<Model>> {
+		let mut state = self.state.write().unwrap();
+		let model = state.models.remove(id)?;
+		state.prune();
+		Some(model)
+	}
+
+	pub fn get(&self, id: &str) -> Option<Arc<Model>> {
+		self.state.read().unwrap().models.get(id).cloned()
+	}
+
+	pub fn ids(&self) -> Vec<String> {
+		self.state.read().unwrap().models.keys().cloned().collect()
+	}
+
+	/// Make predictions with the model with the given id, or return `None` if the store has no model with that id.
+	pub fn predict(
+		&self,
+		id: &str,
+		input: &[PredictInput],
+		options: &PredictOptions,
+	) -> Option<Vec<PredictOutput>> {
+		// Release the lock before predicting, so a slow prediction does not block inserting models.
+		let model = self.get(id)?;
+		Some(predict(&model, input, options))
+	}
+
+	pub fn stats(&self) -> ModelStoreStats {
+		let state = self.state.read().unwrap();
+		let vocabularies = state
+			.vocabularies
+			.values()
+			.flatten()
+			.map(|vocabulary| (Arc::strong_count(vocabulary), vocabulary_bytes(vocabulary)));
+		let word_embedding_models =
+			state
+				.word_embedding_models
+				.values()
+				.flatten()
+				.map(|word_embedding_model| {
+					(
+						Arc::strong_count(word_embedding_model),
+						word_embedding_model_bytes(word_embedding_model),
+					)
+				});
+		let mut stats = ModelStoreStats {
+			n_models: state.models.len(),
+			n_interned: 0,
+			interned_bytes: 0,
+			saved_bytes: 0,
+		};
+		for (strong_count, bytes) in vocabularies.chain(word_embedding_models) {
+			// One reference is the store's own, and the rest are from models.
+			let n_references = strong_count - 1;
+			stats.n_interned += 1;
+			stats.interned_bytes += bytes;
+			stats.saved_bytes += bytes * n_references.saturating_sub(1);
+		}
+		stats
+	}
+}
+
+impl ModelStoreState {
+	/// Return the interned copy of `vocabulary`, interning it if no identical vocabulary has been interned.
+	fn intern_vocabulary(&mut self, vocabulary: Arc<Vocabulary>) -> Arc<Vocabulary> {
+		let mut hasher = FnvHasher::default();
+		for (ngram, entry) in vocabulary.iter() {
+			ngram.hash(&mut hasher);
+			entry.idf.to_bits().hash(&mut hasher);
+		}
+		let interned = self.vocabularies.entry(hasher.finish()).or_default();
+		// The order of the ngrams is the order of the features, so vocabularies with the same ngrams in a different order are not identical.
+		let existing = interned.iter().find(|interned| {
+			Arc::ptr_eq(interned, &vocabulary)
+				|| (interned.len() == vocabulary.len() && interned.iter().eq(vocabulary.iter()))
+		});
+		match existing {
+			Some(existing) => existing.clone(),
+			None => {
+				interned.push(vocabulary.clone());
+				vocabulary
+			}
+		}
+	}
+
+	/// Return the interned copy of `word_embedding_model`, interning it if no identical word embedding model has been interned.
+	fn intern_word_embedding_model(
+		&mut self,
+		word_embedding_model: Arc<WordEmbeddingModel>,
+	) -> Arc<WordEmbeddingModel> {
+		// The words are in a hash map whose order depends on how it was built, so only the values are hashed.
+		let mut hasher = FnvHasher::default();
+		word_embedding_model.size.hash(&mut hasher);
+		word_embedding_model.words.len().hash(&mut hasher);
+		for value in word_embedding_model.values.iter() {
+			value.to_bits().hash(&mut hasher);
+		}
+		let interned = self
+			.word_embedding_models
+			.entry(hasher.finish())
+			.or_default();
+		let existing =
+			interned.iter().find(|interned| {
+				Arc::ptr_eq(interned, &word_embedding_model)
+					|| (interned.size == word_embedding_model.size
+						&& interned.words == word_embedding_model.words
+						&& interned.values.iter().map(|value| value.to_bits()).eq(
+							word_embedding_model
+								.values
+								.iter()
+								.map(|value| value.to_bits()),
+						))
+			});
+		match existing {
+			Some(existing) => existing.clone(),
+			None => {
+				interned.push(word_embedding_model.clone());
+				word_embedding_model
+			}
+		}
+	}
+
+	/// Drop the interned values that only the store holds.
+	fn prune(&mut self) {
+		for interned in self.vocabularies.values_mut() {
+			interned.retain(|vocabulary| Arc::strong_count(vocabulary) > 1);
+		}
+		self.vocabularies.retain(|_, interned| !interned.is_empty());
+		for interned in self.word_embedding_models.values_mut() {
+			interned.retain(|word_embedding_model| Arc::strong_count(word_embedding_model) > 1);
+		}
+		self.word_embedding_models
+			.retain(|_, interned| !interned.is_empty());
+	}
+}
+
+fn feature_groups_mut(model: &mut Model) -> &mut Vec<FeatureGroup> {
+	match &mut model.inner {
+		ModelInner::Regressor(model) => &mut model.feature_groups,
+		ModelInner::BinaryClassifier(model) => &mut model.feature_groups,
+		ModelInner::MulticlassClassifier(model) => &mut model.feature_groups,
+	}
+}
+
+fn vocabulary_bytes(vocabulary: &Vocabulary) -> usize {
+	vocabulary
+		.keys()
+		.map(|ngram| {
+			let token_bytes = match ngram {
+				NGram::Unigram(token) => token.len(),
+				NGram::Bigram(token_a, token_b) => token_a.len() + token_b.len(),
+			};
+			// Each entry also has a hash and an index in the map's table.
+			size_of::<NGram>()
+				+ size_of::<BagOfWordsFeatureGroupNGramEntry>()
+				+ 2 * size_of::<usize>()
+				+ token_bytes
+		})
+		.sum()
+}
+
+fn word_embedding_model_bytes(word_embedding_model: &WordEmbeddingModel) -> usize {
+	let words_bytes: usize = word_embedding_model
+		.words
+		.keys()
+		.map(|word| size_of::<String>() + size_of::<usize>() + word.len())
+		.sum();
+	words_bytes + word_embedding_model.values.len() * size_of::<f32>()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn vocabulary(tokens: &[&str]) -> Arc<Vocabulary> {
+		Arc::new(
+			tokens
+				.iter()
+				.map(|token| {
+					(
+						NGram::Unigram((*token).to_owned()),
+						BagOfWordsFeatureGroupNGramEntry { idf: 1.0 },
+					)
+				})
+				.collect(),
+		)
+	}
+
+	#[test]
+	fn test_intern_vocabulary() {
+		let mut state = ModelStoreState::default();
+		let a = state.intern_vocabulary(vocabulary(&["hello", "world"]));
+		let b = state.intern_vocabulary(vocabulary(&["hello", "world"]));
+		let c = state.intern_vocabulary(vocabulary(&["world", "hello"]));
+		assert!(Arc::ptr_eq(&a, &b));
+		assert!(!Arc::ptr_eq(&a, &c));
+		drop(c);
+		state.prune();
+		assert_eq!(state.vocabularies.values().flatten().count(), 1);
+		drop(a);
+		drop(b);
+		state.prune();
+		assert!(state.vocabularies.is_empty());
+	}
+}
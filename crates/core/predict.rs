@@ -1,16 +1,17 @@
+use chrono_tz::Tz;
 use modelfox_features::{
 	bag_of_words::BagOfWordsFeatureGroupNGramEntry, BagOfWordsCosineSimilarityFeatureGroup,
-	BagOfWordsFeatureGroup, FeatureGroup, IdentityFeatureGroup, NormalizedFeatureGroup,
-	OneHotEncodedFeatureGroup, WordEmbeddingFeatureGroup,
+	BagOfWordsFeatureGroup, CyclicalFeatureGroup, FeatureGroup, IdentityFeatureGroup,
+	NormalizedFeatureGroup, OneHotEncodedFeatureGroup, WordEmbeddingFeatureGroup,
 };
 use modelfox_table::prelude::*;
 use modelfox_text::NGramType;
 use modelfox_zip::zip;
 use ndarray::prelude::*;
 use num::ToPrimitive;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, sync::Arc};
 
-#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct PredictInput(pub BTreeMap<String, PredictInputValue>);
 
 impl Default for PredictInput {
@@ -25,7 +26,7 @@ impl PredictInput {
 	}
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum PredictInputValue {
 	Number(f64),
@@ -106,10 +107,16 @@ impl From<&str> for PredictInputValue {
 	}
 }
 
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct PredictOptions {
 	pub threshold: f32,
 	pub compute_feature_contributions: bool,
+	/// This controls how feature contributions are computed for tree models. It has no effect on linear models, whose feature contributions are always exact.
+	#[serde(default)]
+	pub tree_shap_mode: TreeShapMode,
+	/// If your model is a multiclass classifier, use this field to set a minimum probability for each class to be predicted, such as thresholds chosen on the tuning page of the app. A class with a threshold is only predicted if its probability is at least its threshold, and classes without one can always be predicted, so if no class with a threshold meets it, the most probable class without a threshold is predicted. If every class has a threshold and none meets it, the most probable class is predicted. If this is empty, the thresholds saved in the model when it was downloaded from the app are used.
+	#[serde(default)]
+	pub class_thresholds: BTreeMap<String, f32>,
 }
 
 impl Default for PredictOptions {
@@ -117,11 +124,38 @@ impl Default for PredictOptions {
 		PredictOptions {
 			threshold: 0.5,
 			compute_feature_contributions: false,
+			tree_shap_mode: TreeShapMode::Exact,
+			class_thresholds: BTreeMap::new(),
 		}
 	}
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum TreeShapMode {
+	/// Compute exact SHAP values with the TreeSHAP algorithm.
+	#[serde(rename = "exact")]
+	Exact,
+	/// Compute approximate feature contributions from the path the example takes through each tree, which is faster for deep trees.
+	#[serde(rename = "fast")]
+	Fast,
+}
+
+impl Default for TreeShapMode {
+	fn default() -> TreeShapMode {
+		TreeShapMode::Exact
+	}
+}
+
+impl From<TreeShapMode> for modelfox_tree::ShapMode {
+	fn from(value: TreeShapMode) -> modelfox_tree::ShapMode {
+		match value {
+			TreeShapMode::Exact => modelfox_tree::ShapMode::Exact,
+			TreeShapMode::Fast => modelfox_tree::ShapMode::Fast,
+		}
+	}
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
 #[serde(tag = "type")]
 pub enum PredictOutput {
 	#[serde(rename = "regression")]
@@ -132,20 +166,20 @@ pub enum PredictOutput {
 	MulticlassClassification(MulticlassClassificationPredictOutput),
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct RegressionPredictOutput {
 	pub value: f32,
 	pub feature_contributions: Option<FeatureContributions>,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct BinaryClassificationPredictOutput {
 	pub class_name: String,
 	pub probability: f32,
 	pub feature_contributions: Option<FeatureContributions>,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct MulticlassClassificationPredictOutput {
 	pub class_name: String,
 	pub probability: f32,
@@ -153,7 +187,7 @@ pub struct MulticlassClassificationPredictOutput {
 	pub feature_contributions: Option<BTreeMap<String, FeatureContributions>>,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct FeatureContributions {
 	/// The baseline value is the value output by the model for this class before taking into account the feature values.
 	pub baseline_value: f32,
@@ -163,7 +197,7 @@ pub struct FeatureContributions {
 	pub entries: Vec<FeatureContributionEntry>,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 #[serde(tag = "type")]
 pub enum FeatureContributionEntry {
 	#[serde(rename = "identity")]
@@ -178,23 +212,25 @@ pub enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "cyclical")]
+	Cyclical(CyclicalFeatureContribution),
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct IdentityFeatureContribution {
 	pub column_name: String,
 	pub feature_value: f32,
 	pub feature_contribution_value: f32,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct NormalizedFeatureContribution {
 	pub column_name: String,
 	pub feature_value: f32,
 	pub feature_contribution_value: f32,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct OneHotEncodedFeatureContribution {
 	pub column_name: String,
 	pub variant: Option<String>,
@@ -202,7 +238,7 @@ pub struct OneHotEncodedFeatureContribution {
 	pub feature_contribution_value: f32,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct BagOfWordsFeatureContribution {
 	pub column_name: String,
 	pub ngram: NGram,
@@ -210,7 +246,7 @@ pub struct BagOfWordsFeatureContribution {
 	pub feature_contribution_value: f32,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct BagOfWordsCosineSimilarityFeatureContribution {
 	pub column_name_a: String,
 	pub column_name_b: String,
@@ -218,14 +254,31 @@ pub struct BagOfWordsCosineSimilarityFeatureContribution {
 	pub feature_contribution_value: f32,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct WordEmbeddingFeatureContribution {
 	pub column_name: String,
 	pub value_index: usize,
 	pub feature_contribution_value: f32,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct CyclicalFeatureContribution {
+	pub column_name: String,
+	pub component: CyclicalComponent,
+	pub feature_value: f32,
+	pub feature_contribution_value: f32,
+}
+
+/// A cyclical feature group produces a sine and a cosine feature for its source column.
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+pub enum CyclicalComponent {
+	#[serde(rename = "sin")]
+	Sin,
+	#[serde(rename = "cos")]
+	Cos,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
 pub enum NGram {
 	#[serde(rename = "unigram")]
 	Unigram(String),
@@ -251,6 +304,15 @@ impl std::fmt::Display for NGram {
 	}
 }
 
+impl std::fmt::Display for CyclicalComponent {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			CyclicalComponent::Sin => write!(f, "sin"),
+			CyclicalComponent::Cos => write!(f, "cos"),
+		}
+	}
+}
+
 #[derive(Debug)]
 pub struct Model {
 	pub id: String,
@@ -267,6 +329,7 @@ pub enum ModelInner {
 #[derive(Debug)]
 pub struct Regressor {
 	pub columns: Vec<Column>,
+	pub invalid_value_policies: BTreeMap<String, modelfox_table::InvalidValuePolicy>,
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub model: RegressionModel,
 }
@@ -274,6 +337,7 @@ pub struct Regressor {
 #[derive(Debug)]
 pub struct BinaryClassifier {
 	pub columns: Vec<Column>,
+	pub invalid_value_policies: BTreeMap<String, modelfox_table::InvalidValuePolicy>,
 	pub negative_class: String,
 	pub positive_class: String,
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
@@ -283,9 +347,12 @@ pub struct BinaryClassifier {
 #[derive(Debug)]
 pub struct MulticlassClassifier {
 	pub columns: Vec<Column>,
+	pub invalid_value_policies: BTreeMap<String, modelfox_table::InvalidValuePolicy>,
 	pub classes: Vec<String>,
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub model: MulticlassClassificationModel,
+	/// These are the class thresholds from the model's metadata, which are used when the predict options have none.
+	pub class_thresholds: BTreeMap<String, f32>,
 }
 
 #[derive(Debug)]
@@ -312,6 +379,7 @@ pub enum Column {
 	Number(NumberColumn),
 	Enum(EnumColumn),
 	Text(TextColumn),
+	DateTime(DateTimeColumn),
 }
 
 #[derive(Debug)]
@@ -335,12 +403,30 @@ pub struct TextColumn {
 	name: String,
 }
 
+#[derive(Debug)]
+pub struct DateTimeColumn {
+	name: String,
+	timezone: Tz,
+}
+
 impl<'a> From<modelfox_model::ModelReader<'a>> for Model {
 	fn from(model: modelfox_model::ModelReader<'a>) -> Self {
 		deserialize_model(model)
 	}
 }
 
+impl Model {
+	/// Load a model from the contents of a .modelfox file, including the class thresholds in its metadata.
+	pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Model> {
+		let mut model = Model::from(modelfox_model::from_bytes(bytes)?);
+		let metadata = modelfox_model::metadata_from_bytes(bytes)?;
+		if let ModelInner::MulticlassClassifier(model) = &mut model.inner {
+			model.class_thresholds = metadata.class_thresholds;
+		}
+		Ok(model)
+	}
+}
+
 fn deserialize_model(model: modelfox_model::ModelReader) -> Model {
 	let id = model.id().parse().unwrap();
 	let inner = deserialize_model_inner(model.inner());
@@ -356,6 +442,15 @@ fn deserialize_model_inner(model_inner: modelfox_model::ModelInnerReader) -> Mod
 				.iter()
 				.map(deserialize_column_stats)
 				.collect::<Vec<_>>();
+			let invalid_value_policies = regressor
+				.invalid_value_policies()
+				.map(|invalid_value_policies| {
+					invalid_value_policies
+						.iter()
+						.map(deserialize_column_invalid_value_policy)
+						.collect()
+				})
+				.unwrap_or_default();
 			let feature_groups = match regressor.model() {
 				modelfox_model::RegressionModelReader::Linear(model) => model
 					.read()
@@ -380,6 +475,7 @@ fn deserialize_model_inner(model_inner: modelfox_model::ModelInnerReader) -> Mod
 			};
 			ModelInner::Regressor(Regressor {
 				columns,
+				invalid_value_policies,
 				feature_groups,
 				model,
 			})
@@ -393,6 +489,15 @@ fn deserialize_model_inner(model_inner: modelfox_model::ModelInnerReader) -> Mod
 				.iter()
 				.map(deserialize_column_stats)
 				.collect::<Vec<_>>();
+			let invalid_value_policies = binary_classifier
+				.invalid_value_policies()
+				.map(|invalid_value_policies| {
+					invalid_value_policies
+						.iter()
+						.map(deserialize_column_invalid_value_policy)
+						.collect()
+				})
+				.unwrap_or_default();
 			let feature_groups = match binary_classifier.model() {
 				modelfox_model::BinaryClassificationModelReader::Linear(model) => model
 					.read()
@@ -421,6 +526,7 @@ fn deserialize_model_inner(model_inner: modelfox_model::ModelInnerReader) -> Mod
 			};
 			ModelInner::BinaryClassifier(BinaryClassifier {
 				columns,
+				invalid_value_policies,
 				negative_class,
 				positive_class,
 				feature_groups,
@@ -439,6 +545,15 @@ fn deserialize_model_inner(model_inner: modelfox_model::ModelInnerReader) -> Mod
 				.iter()
 				.map(deserialize_column_stats)
 				.collect::<Vec<_>>();
+			let invalid_value_policies = multiclass_classifier
+				.invalid_value_policies()
+				.map(|invalid_value_policies| {
+					invalid_value_policies
+						.iter()
+						.map(deserialize_column_invalid_value_policy)
+						.collect()
+				})
+				.unwrap_or_default();
 			let feature_groups = match multiclass_classifier.model() {
 				modelfox_model::MulticlassClassificationModelReader::Linear(model) => model
 					.read()
@@ -467,9 +582,11 @@ fn deserialize_model_inner(model_inner: modelfox_model::ModelInnerReader) -> Mod
 			};
 			ModelInner::MulticlassClassifier(MulticlassClassifier {
 				columns,
+				invalid_value_policies,
 				classes,
 				feature_groups,
 				model,
+				class_thresholds: BTreeMap::new(),
 			})
 		}
 	}
@@ -502,9 +619,33 @@ fn deserialize_column_stats(column_stats: modelfox_model::ColumnStatsReader) ->
 			let name = column_stats.column_name().to_owned();
 			Column::Text(TextColumn { name })
 		}
+		modelfox_model::ColumnStatsReader::DateTimeColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			let name = column_stats.column_name().to_owned();
+			let timezone = column_stats.timezone().parse().unwrap();
+			Column::DateTime(DateTimeColumn { name, timezone })
+		}
 	}
 }
 
+fn deserialize_column_invalid_value_policy(
+	column_invalid_value_policy: modelfox_model::ColumnInvalidValuePolicyReader,
+) -> (String, modelfox_table::InvalidValuePolicy) {
+	let column_name = column_invalid_value_policy.column_name().to_owned();
+	let policy = match column_invalid_value_policy.policy() {
+		modelfox_model::InvalidValuePolicyReader::Missing(_) => {
+			modelfox_table::InvalidValuePolicy::Missing
+		}
+		modelfox_model::InvalidValuePolicyReader::DropRow(_) => {
+			modelfox_table::InvalidValuePolicy::DropRow
+		}
+		modelfox_model::InvalidValuePolicyReader::Error(_) => {
+			modelfox_table::InvalidValuePolicy::Error
+		}
+	};
+	(column_name, policy)
+}
+
 fn deserialize_feature_group(feature_group: modelfox_model::FeatureGroupReader) -> FeatureGroup {
 	match feature_group {
 		modelfox_model::FeatureGroupReader::Identity(feature_group) => {
@@ -565,7 +706,7 @@ fn deserialize_feature_group(feature_group: modelfox_model::FeatureGroupReader)
 				strategy,
 				tokenizer,
 				ngram_types,
-				ngrams,
+				ngrams: Arc::new(ngrams),
 			})
 		}
 		modelfox_model::FeatureGroupReader::BagOfWordsCosineSimilarity(feature_group) => {
@@ -599,7 +740,7 @@ fn deserialize_feature_group(feature_group: modelfox_model::FeatureGroupReader)
 				strategy,
 				tokenizer,
 				ngram_types,
-				ngrams,
+				ngrams: Arc::new(ngrams),
 			})
 		}
 		modelfox_model::FeatureGroupReader::WordEmbedding(feature_group) => {
@@ -610,7 +751,18 @@ fn deserialize_feature_group(feature_group: modelfox_model::FeatureGroupReader)
 			FeatureGroup::WordEmbedding(WordEmbeddingFeatureGroup {
 				source_column_name,
 				tokenizer,
-				model,
+				model: Arc::new(model),
+			})
+		}
+		modelfox_model::FeatureGroupReader::Cyclical(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = feature_group.source_column_name().to_owned();
+			let period = feature_group.period();
+			let offset = feature_group.offset();
+			FeatureGroup::Cyclical(CyclicalFeatureGroup {
+				source_column_name,
+				period,
+				offset,
 			})
 		}
 	}
@@ -655,6 +807,282 @@ fn deserialize_ngram(ngram: modelfox_model::NGramReader) -> modelfox_text::NGram
 	}
 }
 
+/// An `InvalidValueError` is returned by [`try_predict`] when an input has an invalid value in a column whose invalid value policy is [`modelfox_table::InvalidValuePolicy::Error`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidValueError {
+	pub column_name: String,
+	pub value: PredictInputValue,
+}
+
+impl std::fmt::Display for InvalidValueError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let value = match &self.value {
+			PredictInputValue::Number(value) => value.to_string(),
+			PredictInputValue::String(value) => value.clone(),
+		};
+		write!(
+			f,
+			"The value \"{}\" for the column \"{}\" is invalid.",
+			value, self.column_name
+		)
+	}
+}
+
+impl std::error::Error for InvalidValueError {}
+
+/// Make predictions like [`predict`], but enforce the invalid value policy each column was trained with. An input with an invalid value in a column whose policy is to drop the row does not get a prediction, so its output is `None`. An invalid value in a column whose policy is to error fails the whole call.
+pub fn try_predict(
+	model: &Model,
+	input: &[PredictInput],
+	options: &PredictOptions,
+) -> Result<Vec<Option<PredictOutput>>, InvalidValueError> {
+	try_predict_with(model, input, |input| predict(model, input, options))
+}
+
+/// Enforce the invalid value policy each column was trained with, as described in [`try_predict`], and make predictions for the inputs that are kept with `predict`.
+pub(crate) fn try_predict_with(
+	model: &Model,
+	input: &[PredictInput],
+	predict: impl FnOnce(&[PredictInput]) -> Vec<PredictOutput>,
+) -> Result<Vec<Option<PredictOutput>>, InvalidValueError> {
+	let keep = input
+		.iter()
+		.map(|input| check_invalid_values(model, input))
+		.collect::<Result<Vec<_>, _>>()?;
+	let kept_input = zip!(input, keep.iter())
+		.filter(|(_, keep)| **keep)
+		.map(|(input, _)| input.clone())
+		.collect::<Vec<_>>();
+	let mut output = predict(&kept_input).into_iter();
+	let output = keep
+		.into_iter()
+		.map(|keep| if keep { output.next() } else { None })
+		.collect();
+	Ok(output)
+}
+
+/// Check an input's values against the invalid value policies of the model's columns, returning `false` if the input should be dropped. Values are invalid under the same conditions as when the training dataset was loaded.
+fn check_invalid_values(model: &Model, input: &PredictInput) -> Result<bool, InvalidValueError> {
+	let (columns, invalid_value_policies) = match &model.inner {
+		ModelInner::Regressor(model) => (&model.columns, &model.invalid_value_policies),
+		ModelInner::BinaryClassifier(model) => (&model.columns, &model.invalid_value_policies),
+		ModelInner::MulticlassClassifier(model) => (&model.columns, &model.invalid_value_policies),
+	};
+	if invalid_value_policies.is_empty() {
+		return Ok(true);
+	}
+	for column in columns {
+		let column_name = match column {
+			Column::Number(column) => column.name.as_str(),
+			Column::Enum(column) => column.name.as_str(),
+			Column::DateTime(column) => column.name.as_str(),
+			Column::Unknown(_) | Column::Text(_) => continue,
+		};
+		let policy = match invalid_value_policies.get(column_name) {
+			Some(policy) => *policy,
+			None => continue,
+		};
+		let value = match input.0.get(column_name) {
+			Some(value) => value,
+			None => continue,
+		};
+		let is_missing = value
+			.as_str()
+			.map(|value| modelfox_table::DEFAULT_INVALID_VALUES.contains(&value))
+			.unwrap_or(false);
+		if is_missing || !is_invalid_value(column, value) {
+			continue;
+		}
+		match policy {
+			modelfox_table::InvalidValuePolicy::Missing => {}
+			modelfox_table::InvalidValuePolicy::DropRow => return Ok(false),
+			modelfox_table::InvalidValuePolicy::Error => {
+				return Err(InvalidValueError {
+					column_name: column_name.to_owned(),
+					value: value.clone(),
+				})
+			}
+		}
+	}
+	Ok(true)
+}
+
+fn is_invalid_value(column: &Column, value: &PredictInputValue) -> bool {
+	match (column, value) {
+		(Column::Number(_), PredictInputValue::String(value)) => {
+			!fast_float::parse::<f32, &str>(value)
+				.map(f32::is_finite)
+				.unwrap_or(false)
+		}
+		// Number columns are stored as `f32`, so a number that is infinite or NaN after conversion is as invalid as a string that parses to one.
+		(Column::Number(_), PredictInputValue::Number(value)) => !(*value as f32).is_finite(),
+		(Column::Enum(column), PredictInputValue::String(value)) => {
+			!column.variants.contains(value)
+		}
+		// Numbers are never one of the variants of an enum column.
+		(Column::Enum(_), PredictInputValue::Number(_)) => true,
+		(Column::DateTime(column), PredictInputValue::String(value)) => {
+			modelfox_table::parse_datetime(value, column.timezone).is_none()
+		}
+		_ => false,
+	}
+}
+
+/// A `ValidationIssue` describes a problem with one value of an input, found by [`validate_input`].
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct ValidationIssue {
+	#[serde(rename = "type")]
+	pub kind: ValidationIssueKind,
+	pub column_name: String,
+	pub value: Option<PredictInputValue>,
+	/// This is a message that describes the issue and what predicting with the input will do about it.
+	pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationIssueKind {
+	/// The input has no value for one of the model's columns, so it will be treated as missing.
+	MissingColumn,
+	/// The input has a value for a column the model was not trained with, so it will be ignored.
+	UnknownColumn,
+	/// The value for an enum column is not one of the column's variants.
+	UnknownEnumVariant,
+	/// The value for a number column is not a finite number, either because it is a string that does not parse as one or because it is infinite or NaN.
+	InvalidNumber,
+	/// The value for a date time column does not parse as a date time.
+	InvalidDateTime,
+}
+
+impl std::fmt::Display for ValidationIssue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.message)
+	}
+}
+
+/// The maximum number of an enum column's variants to list in a validation message.
+const MAX_VARIANTS_IN_MESSAGE: usize = 10;
+
+/// Validate an input against the model's columns before predicting. Predicting never fails because of a problem this function finds unless the column's invalid value policy is to error, so each issue's message says what predicting will do with the value. Values that are among the default invalid values, such as an empty string, are intentionally missing and are not reported.
+pub fn validate_input(model: &Model, input: &PredictInput) -> Vec<ValidationIssue> {
+	let (columns, invalid_value_policies) = match &model.inner {
+		ModelInner::Regressor(model) => (&model.columns, &model.invalid_value_policies),
+		ModelInner::BinaryClassifier(model) => (&model.columns, &model.invalid_value_policies),
+		ModelInner::MulticlassClassifier(model) => (&model.columns, &model.invalid_value_policies),
+	};
+	let mut issues = Vec::new();
+	for column in columns {
+		let column_name = column_name(column);
+		// Unknown columns are not used by any feature, so their values do not matter.
+		if let Column::Unknown(_) = column {
+			continue;
+		}
+		let value = match input.0.get(column_name) {
+			Some(value) => value,
+			None => {
+				issues.push(ValidationIssue {
+					kind: ValidationIssueKind::MissingColumn,
+					column_name: column_name.to_owned(),
+					value: None,
+					message: format!(
+						"The input has no value for the column \"{}\", so it will be treated as missing.",
+						column_name
+					),
+				});
+				continue;
+			}
+		};
+		let is_missing = value
+			.as_str()
+			.map(|value| modelfox_table::DEFAULT_INVALID_VALUES.contains(&value))
+			.unwrap_or(false);
+		if is_missing || !is_invalid_value(column, value) {
+			continue;
+		}
+		let kind = match column {
+			Column::Number(_) => ValidationIssueKind::InvalidNumber,
+			Column::Enum(_) => ValidationIssueKind::UnknownEnumVariant,
+			Column::DateTime(_) => ValidationIssueKind::InvalidDateTime,
+			Column::Unknown(_) | Column::Text(_) => unreachable!(),
+		};
+		let value_string = match value {
+			PredictInputValue::Number(value) => value.to_string(),
+			PredictInputValue::String(value) => value.clone(),
+		};
+		let problem = match column {
+			Column::Number(_) => format!(
+				"The value \"{}\" for the column \"{}\" is not a finite number.",
+				value_string, column_name
+			),
+			Column::Enum(column) => {
+				let mut variants = column
+					.variants
+					.iter()
+					.take(MAX_VARIANTS_IN_MESSAGE)
+					.map(|variant| format!("\"{}\"", variant))
+					.collect::<Vec<_>>()
+					.join(", ");
+				if column.variants.len() > MAX_VARIANTS_IN_MESSAGE {
+					variants.push_str(&format!(
+						", and {} more",
+						column.variants.len() - MAX_VARIANTS_IN_MESSAGE
+					));
+				}
+				format!(
+					"The value \"{}\" for the column \"{}\" is not one of its variants: {}.",
+					value_string, column_name, variants
+				)
+			}
+			Column::DateTime(_) => format!(
+				"The value \"{}\" for the column \"{}\" is not a date time.",
+				value_string, column_name
+			),
+			Column::Unknown(_) | Column::Text(_) => unreachable!(),
+		};
+		let consequence = match invalid_value_policies.get(column_name) {
+			Some(modelfox_table::InvalidValuePolicy::DropRow) => {
+				"The input will not get a prediction."
+			}
+			Some(modelfox_table::InvalidValuePolicy::Error) => "Predicting will fail.",
+			Some(modelfox_table::InvalidValuePolicy::Missing) | None => match column {
+				Column::Enum(_) => "It will be treated as out of vocabulary.",
+				_ => "It will be treated as missing.",
+			},
+		};
+		issues.push(ValidationIssue {
+			kind,
+			column_name: column_name.to_owned(),
+			value: Some(value.clone()),
+			message: format!("{} {}", problem, consequence),
+		});
+	}
+	for (name, value) in input.0.iter() {
+		if !columns.iter().any(|column| column_name(column) == name) {
+			issues.push(ValidationIssue {
+				kind: ValidationIssueKind::UnknownColumn,
+				column_name: name.clone(),
+				value: Some(value.clone()),
+				message: format!(
+					"The model was not trained with a column named \"{}\", so its value will be ignored.",
+					name
+				),
+			});
+		}
+	}
+	issues
+}
+
+fn column_name(column: &Column) -> &str {
+	match column {
+		Column::Unknown(column) => column.name.as_str(),
+		Column::Number(column) => column.name.as_str(),
+		Column::Enum(column) => column.name.as_str(),
+		Column::Text(column) => column.name.as_str(),
+		Column::DateTime(column) => column.name.as_str(),
+	}
+}
+
+/// Make predictions. Values that fail to parse for their column's type are treated as missing, regardless of the invalid value policies the model was trained with. Use [`try_predict`] to enforce them.
 pub fn predict(
 	model: &Model,
 	input: &[PredictInput],
@@ -675,6 +1103,7 @@ pub fn predict(
 			Column::Number(column) => Some(column.name.clone()),
 			Column::Enum(column) => Some(column.name.clone()),
 			Column::Text(column) => Some(column.name.clone()),
+			Column::DateTime(column) => Some(column.name.clone()),
 		})
 		.collect();
 	let column_types = columns
@@ -686,6 +1115,9 @@ pub fn predict(
 				variants: column.variants.clone(),
 			},
 			Column::Text(_) => modelfox_table::TableColumnType::Text,
+			Column::DateTime(column) => modelfox_table::TableColumnType::DateTime {
+				timezone: column.timezone,
+			},
 		})
 		.collect();
 	let mut table = modelfox_table::Table::new(column_names, column_types);
@@ -723,6 +1155,18 @@ pub fn predict(
 						.to_owned();
 					column.data_mut().push(value);
 				}
+				modelfox_table::TableColumn::DateTime(column) => {
+					// Numbers are interpreted as unix timestamps in seconds.
+					let timezone = column.timezone();
+					let value = match input.0.get(column.name().as_ref().unwrap()) {
+						Some(PredictInputValue::Number(value)) => value.to_i64(),
+						Some(PredictInputValue::String(value)) => {
+							modelfox_table::parse_datetime(value, timezone)
+						}
+						_ => None,
+					};
+					column.data_mut().push(value);
+				}
 			}
 		}
 	}
@@ -751,47 +1195,31 @@ fn predict_regressor(
 	options: &PredictOptions,
 ) -> Vec<RegressionPredictOutput> {
 	let n_rows = table.nrows();
-	match &model.model {
+	let mut predictions = Array::zeros(n_rows);
+	// Compute the features once. They are used both to make the predictions and to compute the feature contributions.
+	let (feature_values, feature_contributions) = match &model.model {
 		RegressionModel::Linear(inner_model) => {
-			let mut predictions = Array::zeros(n_rows);
 			let features = modelfox_features::compute_features_array_f32(
 				&table.view(),
 				&model.feature_groups,
 				&|| {},
 			);
 			inner_model.predict(features.view(), predictions.view_mut());
-			let mut outputs: Vec<RegressionPredictOutput> = predictions
-				.iter()
-				.map(|prediction| RegressionPredictOutput {
-					value: *prediction,
-					feature_contributions: None,
-				})
-				.collect();
-			if options.compute_feature_contributions {
-				let feature_contributions =
-					inner_model.compute_feature_contributions(features.view());
-				for (mut output, features, feature_contributions) in zip!(
-					outputs.iter_mut(),
-					features.axis_iter(Axis(0)),
-					feature_contributions,
-				) {
-					let baseline_value = feature_contributions.baseline_value;
-					let output_value = feature_contributions.output_value;
-					let feature_contributions = compute_feature_contributions(
-						model.feature_groups.iter(),
-						features.iter().cloned(),
-						feature_contributions
-							.feature_contribution_values
-							.into_iter(),
-					);
-					output.feature_contributions = Some(FeatureContributions {
-						baseline_value,
-						output_value,
-						entries: feature_contributions,
-					});
-				}
+			if !options.compute_feature_contributions {
+				return regression_outputs(predictions.view());
 			}
-			outputs
+			let feature_contributions = inner_model
+				.compute_feature_contributions(features.view())
+				.into_iter()
+				.map(|output| {
+					(
+						output.baseline_value,
+						output.output_value,
+						output.feature_contribution_values,
+					)
+				})
+				.collect::<Vec<_>>();
+			(features, feature_contributions)
 		}
 		RegressionModel::Tree(inner_model) => {
 			let features = modelfox_features::compute_features_array_value(
@@ -799,48 +1227,49 @@ fn predict_regressor(
 				&model.feature_groups,
 				&|| {},
 			);
-			let mut predictions = Array::zeros(n_rows);
 			inner_model.predict(features.view(), predictions.view_mut());
-			let mut outputs: Vec<RegressionPredictOutput> = predictions
-				.iter()
-				.map(|prediction| RegressionPredictOutput {
-					value: *prediction,
-					feature_contributions: None,
-				})
-				.collect();
-			if options.compute_feature_contributions {
-				let feature_contributions =
-					inner_model.compute_feature_contributions(features.view());
-				for (mut output, features, feature_contributions) in zip!(
-					outputs.iter_mut(),
-					features.axis_iter(Axis(0)),
-					feature_contributions,
-				) {
-					let baseline_value = feature_contributions.baseline_value;
-					let output_value = feature_contributions.output_value;
-					let feature_contributions = compute_feature_contributions(
-						model.feature_groups.iter(),
-						features.iter().map(|v| match v {
-							modelfox_table::TableValue::Number(value) => *value,
-							modelfox_table::TableValue::Enum(value) => {
-								value.map(|v| v.get()).unwrap_or(0).to_f32().unwrap()
-							}
-							_ => unreachable!(),
-						}),
-						feature_contributions
-							.feature_contribution_values
-							.into_iter(),
-					);
-					output.feature_contributions = Some(FeatureContributions {
-						baseline_value,
-						output_value,
-						entries: feature_contributions,
-					});
-				}
+			if !options.compute_feature_contributions {
+				return regression_outputs(predictions.view());
 			}
-			outputs
+			let feature_contributions = inner_model
+				.compute_feature_contributions(features.view(), options.tree_shap_mode.into())
+				.into_iter()
+				.map(|output| {
+					(
+						output.baseline_value,
+						output.output_value,
+						output.feature_contribution_values,
+					)
+				})
+				.collect::<Vec<_>>();
+			(tree_feature_values(features.view()), feature_contributions)
 		}
+	};
+	let mut outputs = regression_outputs(predictions.view());
+	for (output, feature_values, (baseline_value, output_value, feature_contribution_values)) in zip!(
+		outputs.iter_mut(),
+		feature_values.axis_iter(Axis(0)),
+		feature_contributions,
+	) {
+		output.feature_contributions = Some(example_feature_contributions(
+			&model.feature_groups,
+			feature_values,
+			baseline_value,
+			output_value,
+			feature_contribution_values,
+		));
 	}
+	outputs
+}
+
+fn regression_outputs(predictions: ArrayView1<f32>) -> Vec<RegressionPredictOutput> {
+	predictions
+		.iter()
+		.map(|prediction| RegressionPredictOutput {
+			value: *prediction,
+			feature_contributions: None,
+		})
+		.collect()
 }
 
 fn predict_binary_classifier(
@@ -849,53 +1278,31 @@ fn predict_binary_classifier(
 	options: &PredictOptions,
 ) -> Vec<BinaryClassificationPredictOutput> {
 	let n_rows = table.nrows();
-	match &model.model {
+	let mut probabilities = Array::zeros(n_rows);
+	// Compute the features once. They are used both to make the predictions and to compute the feature contributions.
+	let (feature_values, feature_contributions) = match &model.model {
 		BinaryClassificationModel::Linear(inner_model) => {
-			let mut probabilities = Array::zeros(n_rows);
 			let features = modelfox_features::compute_features_array_f32(
 				&table.view(),
 				&model.feature_groups,
 				&|| {},
 			);
 			inner_model.predict(features.view(), probabilities.view_mut());
-			let mut outputs: Vec<BinaryClassificationPredictOutput> = probabilities
-				.iter()
-				.map(|probability| {
-					let (probability, class_name) = if *probability >= options.threshold {
-						(*probability, model.positive_class.clone())
-					} else {
-						(1.0 - probability, model.negative_class.clone())
-					};
-					BinaryClassificationPredictOutput {
-						class_name,
-						probability,
-						feature_contributions: None,
-					}
-				})
-				.collect();
-			if options.compute_feature_contributions {
-				let feature_contributions =
-					inner_model.compute_feature_contributions(features.view());
-				for (mut output, feature_contributions) in
-					zip!(outputs.iter_mut(), feature_contributions)
-				{
-					let baseline_value = feature_contributions.baseline_value;
-					let output_value = feature_contributions.output_value;
-					let feature_contributions = compute_feature_contributions(
-						model.feature_groups.iter(),
-						features.iter().cloned(),
-						feature_contributions
-							.feature_contribution_values
-							.into_iter(),
-					);
-					output.feature_contributions = Some(FeatureContributions {
-						baseline_value,
-						output_value,
-						entries: feature_contributions,
-					});
-				}
+			if !options.compute_feature_contributions {
+				return binary_classification_outputs(model, probabilities.view(), options);
 			}
-			outputs
+			let feature_contributions = inner_model
+				.compute_feature_contributions(features.view())
+				.into_iter()
+				.map(|output| {
+					(
+						output.baseline_value,
+						output.output_value,
+						output.feature_contribution_values,
+					)
+				})
+				.collect::<Vec<_>>();
+			(features, feature_contributions)
 		}
 		BinaryClassificationModel::Tree(inner_model) => {
 			let features = modelfox_features::compute_features_array_value(
@@ -903,54 +1310,61 @@ fn predict_binary_classifier(
 				&model.feature_groups,
 				&|| {},
 			);
-			let mut probabilities = Array::zeros(n_rows);
 			inner_model.predict(features.view(), probabilities.view_mut());
-			let mut outputs: Vec<BinaryClassificationPredictOutput> = probabilities
-				.iter()
-				.map(|probability| {
-					let (probability, class_name) = if *probability >= options.threshold {
-						(*probability, model.positive_class.clone())
-					} else {
-						(1.0 - probability, model.negative_class.clone())
-					};
-					BinaryClassificationPredictOutput {
-						class_name,
-						probability,
-						feature_contributions: None,
-					}
-				})
-				.collect();
-			if options.compute_feature_contributions {
-				let feature_contributions =
-					inner_model.compute_feature_contributions(features.view());
-				for (mut output, feature_contributions) in
-					zip!(outputs.iter_mut(), feature_contributions)
-				{
-					let baseline_value = feature_contributions.baseline_value;
-					let output_value = feature_contributions.output_value;
-					let feature_contributions = compute_feature_contributions(
-						model.feature_groups.iter(),
-						features.iter().map(|v| match v {
-							modelfox_table::TableValue::Number(value) => *value,
-							modelfox_table::TableValue::Enum(value) => {
-								value.map(|v| v.get()).unwrap_or(0).to_f32().unwrap()
-							}
-							_ => unreachable!(),
-						}),
-						feature_contributions
-							.feature_contribution_values
-							.into_iter(),
-					);
-					output.feature_contributions = Some(FeatureContributions {
-						baseline_value,
-						output_value,
-						entries: feature_contributions,
-					});
-				}
+			if !options.compute_feature_contributions {
+				return binary_classification_outputs(model, probabilities.view(), options);
 			}
-			outputs
+			let feature_contributions = inner_model
+				.compute_feature_contributions(features.view(), options.tree_shap_mode.into())
+				.into_iter()
+				.map(|output| {
+					(
+						output.baseline_value,
+						output.output_value,
+						output.feature_contribution_values,
+					)
+				})
+				.collect::<Vec<_>>();
+			(tree_feature_values(features.view()), feature_contributions)
 		}
+	};
+	let mut outputs = binary_classification_outputs(model, probabilities.view(), options);
+	for (output, feature_values, (baseline_value, output_value, feature_contribution_values)) in zip!(
+		outputs.iter_mut(),
+		feature_values.axis_iter(Axis(0)),
+		feature_contributions,
+	) {
+		output.feature_contributions = Some(example_feature_contributions(
+			&model.feature_groups,
+			feature_values,
+			baseline_value,
+			output_value,
+			feature_contribution_values,
+		));
 	}
+	outputs
+}
+
+fn binary_classification_outputs(
+	model: &BinaryClassifier,
+	probabilities: ArrayView1<f32>,
+	options: &PredictOptions,
+) -> Vec<BinaryClassificationPredictOutput> {
+	probabilities
+		.iter()
+		.map(|probability| {
+			let (probability, class_name) = if *probability >= options.threshold {
+				(*probability, model.positive_class.clone())
+			} else {
+				(1.0 - probability, model.negative_class.clone())
+			};
+			BinaryClassificationPredictOutput {
+				class_name,
+				probability,
+				feature_contributions: None,
+			}
+		})
+		.collect()
 }
 
 fn predict_multiclass_classifier(
@@ -960,62 +1374,36 @@ fn predict_multiclass_classifier(
 ) -> Vec<MulticlassClassificationPredictOutput> {
 	let n_rows = table.nrows();
 	let n_classes = model.classes.len();
-	match &model.model {
+	let mut probabilities = Array::zeros((n_rows, n_classes));
+	// Compute the features once. They are used both to make the predictions and to compute the feature contributions for every class.
+	let (feature_values, feature_contributions) = match &model.model {
 		MulticlassClassificationModel::Linear(inner_model) => {
-			let mut probabilities = Array::zeros((n_rows, n_classes));
 			let features = modelfox_features::compute_features_array_f32(
 				&table.view(),
 				&model.feature_groups,
 				&|| {},
 			);
 			inner_model.predict(features.view(), probabilities.view_mut());
-			let mut outputs: Vec<MulticlassClassificationPredictOutput> = probabilities
-				.axis_iter(Axis(0))
-				.map(|probabilities| {
-					let (probability, class_name) =
-						zip!(probabilities.iter(), model.classes.iter())
-							.max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap())
-							.unwrap();
-					let probabilities = zip!(probabilities, model.classes.iter())
-						.map(|(p, c)| (c.clone(), *p))
-						.collect();
-					MulticlassClassificationPredictOutput {
-						class_name: class_name.clone(),
-						probability: *probability,
-						probabilities,
-						feature_contributions: None,
-					}
-				})
-				.collect();
-			if options.compute_feature_contributions {
-				let feature_contributions =
-					inner_model.compute_feature_contributions(features.view());
-				for (mut output, feature_contributions) in
-					zip!(outputs.iter_mut(), feature_contributions)
-				{
-					let feature_contributions = zip!(model.classes.iter(), feature_contributions)
-						.map(|(class, feature_contributions)| {
-							let baseline_value = feature_contributions.baseline_value;
-							let output_value = feature_contributions.output_value;
-							let feature_contributions = compute_feature_contributions(
-								model.feature_groups.iter(),
-								features.iter().cloned(),
-								feature_contributions
-									.feature_contribution_values
-									.into_iter(),
-							);
-							let feature_contributions = FeatureContributions {
-								baseline_value,
-								output_value,
-								entries: feature_contributions,
-							};
-							(class.clone(), feature_contributions)
-						})
-						.collect();
-					output.feature_contributions = Some(feature_contributions)
-				}
+			if !options.compute_feature_contributions {
+				return multiclass_classification_outputs(model, probabilities.view(), options);
 			}
-			outputs
+			let feature_contributions = inner_model
+				.compute_feature_contributions(features.view())
+				.into_iter()
+				.map(|outputs| {
+					outputs
+						.into_iter()
+						.map(|output| {
+							(
+								output.baseline_value,
+								output.output_value,
+								output.feature_contribution_values,
+							)
+						})
+						.collect::<Vec<_>>()
+				})
+				.collect::<Vec<_>>();
+			(features, feature_contributions)
 		}
 		MulticlassClassificationModel::Tree(inner_model) => {
 			let features = modelfox_features::compute_features_array_value(
@@ -1023,65 +1411,136 @@ fn predict_multiclass_classifier(
 				&model.feature_groups,
 				&|| {},
 			);
-			let mut probabilities = Array::zeros((n_rows, n_classes));
 			inner_model.predict(features.view(), probabilities.view_mut());
-			let mut outputs: Vec<MulticlassClassificationPredictOutput> = probabilities
-				.axis_iter(Axis(0))
-				.map(|probabilities| {
-					let (probability, class_name) =
-						zip!(probabilities.iter(), model.classes.iter())
-							.max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap())
-							.unwrap();
-					let probabilities = zip!(probabilities, model.classes.iter())
-						.map(|(p, c)| (c.clone(), *p))
-						.collect();
-					MulticlassClassificationPredictOutput {
-						class_name: class_name.clone(),
-						probability: *probability,
-						probabilities,
-						feature_contributions: None,
-					}
+			if !options.compute_feature_contributions {
+				return multiclass_classification_outputs(model, probabilities.view(), options);
+			}
+			let feature_contributions = inner_model
+				.compute_feature_contributions(features.view(), options.tree_shap_mode.into())
+				.into_iter()
+				.map(|outputs| {
+					outputs
+						.into_iter()
+						.map(|output| {
+							(
+								output.baseline_value,
+								output.output_value,
+								output.feature_contribution_values,
+							)
+						})
+						.collect::<Vec<_>>()
 				})
+				.collect::<Vec<_>>();
+			(tree_feature_values(features.view()), feature_contributions)
+		}
+	};
+	let mut outputs = multiclass_classification_outputs(model, probabilities.view(), options);
+	for (output, feature_values, feature_contributions) in zip!(
+		outputs.iter_mut(),
+		feature_values.axis_iter(Axis(0)),
+		feature_contributions,
+	) {
+		let feature_contributions = zip!(model.classes.iter(), feature_contributions)
+			.map(
+				|(class, (baseline_value, output_value, feature_contribution_values))| {
+					let feature_contributions = example_feature_contributions(
+						&model.feature_groups,
+						feature_values,
+						baseline_value,
+						output_value,
+						feature_contribution_values,
+					);
+					(class.clone(), feature_contributions)
+				},
+			)
+			.collect();
+		output.feature_contributions = Some(feature_contributions);
+	}
+	outputs
+}
+
+fn multiclass_classification_outputs(
+	model: &MulticlassClassifier,
+	probabilities: ArrayView2<f32>,
+	options: &PredictOptions,
+) -> Vec<MulticlassClassificationPredictOutput> {
+	let class_thresholds = if options.class_thresholds.is_empty() {
+		&model.class_thresholds
+	} else {
+		&options.class_thresholds
+	};
+	probabilities
+		.axis_iter(Axis(0))
+		.map(|probabilities| {
+			let (probability, class_name) = choose_class(
+				probabilities.as_slice().unwrap(),
+				&model.classes,
+				class_thresholds,
+			);
+			let probabilities = zip!(probabilities, model.classes.iter())
+				.map(|(p, c)| (c.clone(), *p))
 				.collect();
-			if options.compute_feature_contributions {
-				let feature_contributions =
-					inner_model.compute_feature_contributions(features.view());
-				for (mut output, feature_contributions) in
-					zip!(outputs.iter_mut(), feature_contributions)
-				{
-					let feature_contributions = zip!(model.classes.iter(), feature_contributions)
-						.map(|(class, feature_contributions)| {
-							let baseline_value = feature_contributions.baseline_value;
-							let output_value = feature_contributions.output_value;
-							let feature_contributions = compute_feature_contributions(
-								model.feature_groups.iter(),
-								features.iter().map(|v| match v {
-									modelfox_table::TableValue::Number(value) => *value,
-									modelfox_table::TableValue::Enum(value) => {
-										value.map(|v| v.get()).unwrap_or(0).to_f32().unwrap()
-									}
-									_ => unreachable!(),
-								}),
-								feature_contributions
-									.feature_contribution_values
-									.into_iter(),
-							);
-							let feature_contributions = FeatureContributions {
-								baseline_value,
-								output_value,
-								entries: feature_contributions,
-							};
-							(class.clone(), feature_contributions)
-						})
-						.collect();
-					output.feature_contributions = Some(feature_contributions)
-				}
+			MulticlassClassificationPredictOutput {
+				class_name: class_name.clone(),
+				probability: *probability,
+				probabilities,
+				feature_contributions: None,
 			}
-			outputs
+		})
+		.collect()
+}
+
+/// Tree models take enum features as variant indexes rather than numbers, so convert them to numbers once, to report them alongside the feature contributions.
+fn tree_feature_values(features: ArrayView2<modelfox_table::TableValue>) -> Array2<f32> {
+	features.map(|value| match value {
+		modelfox_table::TableValue::Number(value) => *value,
+		modelfox_table::TableValue::Enum(value) => {
+			value.map(|v| v.get()).unwrap_or(0).to_f32().unwrap()
 		}
+		_ => unreachable!(),
+	})
+}
+
+/// Compute the feature contributions for one example from its feature values and the contribution value of each of its features.
+fn example_feature_contributions(
+	feature_groups: &[modelfox_features::FeatureGroup],
+	feature_values: ArrayView1<f32>,
+	baseline_value: f32,
+	output_value: f32,
+	feature_contribution_values: Vec<f32>,
+) -> FeatureContributions {
+	let entries = compute_feature_contributions(
+		feature_groups.iter(),
+		feature_values.iter().cloned(),
+		feature_contribution_values.into_iter(),
+	);
+	FeatureContributions {
+		baseline_value,
+		output_value,
+		entries,
 	}
 }
 
+/// Choose the class to predict, which is the most probable class among those whose probability is at least their threshold in `class_thresholds`. Classes without a threshold always qualify, so if no class with a threshold meets it, the most probable class without a threshold is chosen. If every class has a threshold and none meets it, there is no class to fall back to, so the most probable class is chosen regardless of the thresholds.
+fn choose_class<'a>(
+	probabilities: &'a [f32],
+	classes: &'a [String],
+	class_thresholds: &BTreeMap<String, f32>,
+) -> (&'a f32, &'a String) {
+	let most_probable = |qualifies: &dyn Fn(f32, &String) -> bool| {
+		zip!(probabilities.iter(), classes.iter())
+			.filter(|(probability, class)| qualifies(**probability, class))
+			.max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap())
+	};
+	let meets_threshold = |probability: f32, class: &String| match class_thresholds.get(class) {
+		Some(threshold) => probability >= *threshold,
+		None => true,
+	};
+	most_probable(&meets_threshold)
+		.or_else(|| most_probable(&|_, _| true))
+		.unwrap()
+}
+
 fn compute_feature_contributions<'a>(
 	feature_groups: impl Iterator<Item = &'a modelfox_features::FeatureGroup>,
 	mut features: impl Iterator<Item = f32>,
@@ -1174,7 +1633,382 @@ fn compute_feature_contributions<'a>(
 					));
 				}
 			}
+			modelfox_features::FeatureGroup::Cyclical(feature_group) => {
+				for component in [CyclicalComponent::Sin, CyclicalComponent::Cos] {
+					let feature_value = features.next().unwrap();
+					let feature_contribution_value = feature_contribution_values.next().unwrap();
+					entries.push(FeatureContributionEntry::Cyclical(
+						CyclicalFeatureContribution {
+							column_name: feature_group.source_column_name.clone(),
+							component,
+							feature_value,
+							feature_contribution_value,
+						},
+					));
+				}
+			}
 		}
 	}
 	entries
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_choose_class() {
+		let classes = vec!["a".to_owned(), "b".to_owned(), "c".to_owned()];
+		let probabilities = [0.5, 0.3, 0.2];
+		let mut class_thresholds = BTreeMap::new();
+		let (_, class) = choose_class(&probabilities, &classes, &class_thresholds);
+		assert_eq!(class, "a");
+		class_thresholds.insert("a".to_owned(), 0.8);
+		let (probability, class) = choose_class(&probabilities, &classes, &class_thresholds);
+		assert_eq!(class, "b");
+		assert_eq!(*probability, 0.3);
+		class_thresholds.insert("b".to_owned(), 0.8);
+		let (probability, class) = choose_class(&probabilities, &classes, &class_thresholds);
+		assert_eq!(class, "c");
+		assert_eq!(*probability, 0.2);
+		class_thresholds.insert("c".to_owned(), 0.8);
+		let (_, class) = choose_class(&probabilities, &classes, &class_thresholds);
+		assert_eq!(class, "a");
+	}
+
+	fn heart_disease_model() -> Model {
+		let path =
+			std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.modelfox");
+		let bytes = std::fs::read(path).unwrap();
+		let mut model = Model::from_bytes(&bytes).unwrap();
+		// The heart disease dataset has no date time column, so add one.
+		match &mut model.inner {
+			ModelInner::BinaryClassifier(model) => {
+				model.columns.push(Column::DateTime(DateTimeColumn {
+					name: "visit_date".to_owned(),
+					timezone: Tz::UTC,
+				}));
+			}
+			_ => unreachable!(),
+		}
+		model
+	}
+
+	fn set_invalid_value_policy(
+		model: &mut Model,
+		column_name: &str,
+		policy: modelfox_table::InvalidValuePolicy,
+	) {
+		match &mut model.inner {
+			ModelInner::BinaryClassifier(model) => {
+				model
+					.invalid_value_policies
+					.insert(column_name.to_owned(), policy);
+			}
+			_ => unreachable!(),
+		}
+	}
+
+	fn heart_disease_input() -> PredictInput {
+		let mut input = PredictInput::new();
+		for (column_name, value) in [
+			("age", "63"),
+			("gender", "male"),
+			("chest_pain", "typical angina"),
+			("resting_blood_pressure", "145"),
+			("cholesterol", "233"),
+			("fasting_blood_sugar_greater_than_120", "true"),
+			(
+				"resting_ecg_result",
+				"probable or definite left ventricular hypertrophy",
+			),
+			("exercise_max_heart_rate", "150"),
+			("exercise_induced_angina", "no"),
+			("exercise_st_depression", "2.3"),
+			("exercise_st_slope", "downsloping"),
+			("fluoroscopy_vessels_colored", "0"),
+			("thallium_stress_test", "fixed defect"),
+			("visit_date", "2022-06-24"),
+		] {
+			input.0.insert(column_name.to_owned(), value.into());
+		}
+		input
+	}
+
+	#[test]
+	fn test_validate_valid_input() {
+		let model = heart_disease_model();
+		assert_eq!(validate_input(&model, &heart_disease_input()), vec![]);
+		// Values that are among the default invalid values are intentionally missing.
+		let mut input = heart_disease_input();
+		input.0.insert("age".to_owned(), "".into());
+		assert_eq!(validate_input(&model, &input), vec![]);
+	}
+
+	#[test]
+	fn test_validate_missing_column() {
+		let model = heart_disease_model();
+		let mut input = heart_disease_input();
+		input.0.remove("age");
+		assert_eq!(
+			validate_input(&model, &input),
+			vec![ValidationIssue {
+				kind: ValidationIssueKind::MissingColumn,
+				column_name: "age".to_owned(),
+				value: None,
+				message: "The input has no value for the column \"age\", so it will be treated as missing.".to_owned(),
+			}],
+		);
+	}
+
+	#[test]
+	fn test_validate_unknown_column() {
+		let model = heart_disease_model();
+		let mut input = heart_disease_input();
+		input.0.insert("favorite_color".to_owned(), "green".into());
+		assert_eq!(
+			validate_input(&model, &input),
+			vec![ValidationIssue {
+				kind: ValidationIssueKind::UnknownColumn,
+				column_name: "favorite_color".to_owned(),
+				value: Some("green".into()),
+				message: "The model was not trained with a column named \"favorite_color\", so its value will be ignored.".to_owned(),
+			}],
+		);
+	}
+
+	#[test]
+	fn test_validate_unknown_enum_variant() {
+		let mut model = heart_disease_model();
+		let mut input = heart_disease_input();
+		input.0.insert("gender".to_owned(), "other".into());
+		let issues = validate_input(&model, &input);
+		assert_eq!(issues.len(), 1);
+		assert_eq!(issues[0].kind, ValidationIssueKind::UnknownEnumVariant);
+		assert_eq!(issues[0].column_name, "gender");
+		assert_eq!(issues[0].value, Some("other".into()));
+		assert!(issues[0].message.starts_with(
+			"The value \"other\" for the column \"gender\" is not one of its variants: "
+		));
+		assert!(issues[0].message.contains("\"male\""));
+		assert!(issues[0]
+			.message
+			.ends_with("It will be treated as out of vocabulary."));
+		// Numbers are never one of an enum column's variants.
+		input.0.insert("gender".to_owned(), 1.0.into());
+		let issues = validate_input(&model, &input);
+		assert_eq!(issues[0].kind, ValidationIssueKind::UnknownEnumVariant);
+		set_invalid_value_policy(
+			&mut model,
+			"gender",
+			modelfox_table::InvalidValuePolicy::DropRow,
+		);
+		let issues = validate_input(&model, &input);
+		assert!(issues[0]
+			.message
+			.ends_with("The input will not get a prediction."));
+		set_invalid_value_policy(
+			&mut model,
+			"gender",
+			modelfox_table::InvalidValuePolicy::Error,
+		);
+		let issues = validate_input(&model, &input);
+		assert!(issues[0].message.ends_with("Predicting will fail."));
+	}
+
+	#[test]
+	fn test_validate_invalid_number() {
+		let mut model = heart_disease_model();
+		let mut input = heart_disease_input();
+		input.0.insert("age".to_owned(), "old".into());
+		assert_eq!(
+			validate_input(&model, &input),
+			vec![ValidationIssue {
+				kind: ValidationIssueKind::InvalidNumber,
+				column_name: "age".to_owned(),
+				value: Some("old".into()),
+				message: "The value \"old\" for the column \"age\" is not a finite number. It will be treated as missing.".to_owned(),
+			}],
+		);
+		for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, f64::MAX] {
+			input.0.insert("age".to_owned(), value.into());
+			let issues = validate_input(&model, &input);
+			assert_eq!(issues.len(), 1);
+			assert_eq!(issues[0].kind, ValidationIssueKind::InvalidNumber);
+		}
+		input.0.insert("age".to_owned(), 63.0.into());
+		assert_eq!(validate_input(&model, &input), vec![]);
+		input.0.insert("age".to_owned(), f64::NAN.into());
+		set_invalid_value_policy(
+			&mut model,
+			"age",
+			modelfox_table::InvalidValuePolicy::DropRow,
+		);
+		let issues = validate_input(&model, &input);
+		assert_eq!(
+			issues[0].message,
+			"The value \"NaN\" for the column \"age\" is not a finite number. The input will not get a prediction.",
+		);
+		set_invalid_value_policy(&mut model, "age", modelfox_table::InvalidValuePolicy::Error);
+		let issues = validate_input(&model, &input);
+		assert_eq!(
+			issues[0].message,
+			"The value \"NaN\" for the column \"age\" is not a finite number. Predicting will fail.",
+		);
+	}
+
+	#[test]
+	fn test_validate_invalid_date_time() {
+		let mut model = heart_disease_model();
+		let mut input = heart_disease_input();
+		input.0.insert("visit_date".to_owned(), "yesterday".into());
+		assert_eq!(
+			validate_input(&model, &input),
+			vec![ValidationIssue {
+				kind: ValidationIssueKind::InvalidDateTime,
+				column_name: "visit_date".to_owned(),
+				value: Some("yesterday".into()),
+				message: "The value \"yesterday\" for the column \"visit_date\" is not a date time. It will be treated as missing.".to_owned(),
+			}],
+		);
+		set_invalid_value_policy(
+			&mut model,
+			"visit_date",
+			modelfox_table::InvalidValuePolicy::DropRow,
+		);
+		let issues = validate_input(&model, &input);
+		assert_eq!(
+			issues[0].message,
+			"The value \"yesterday\" for the column \"visit_date\" is not a date time. The input will not get a prediction.",
+		);
+		set_invalid_value_policy(
+			&mut model,
+			"visit_date",
+			modelfox_table::InvalidValuePolicy::Error,
+		);
+		let issues = validate_input(&model, &input);
+		assert_eq!(
+			issues[0].message,
+			"The value \"yesterday\" for the column \"visit_date\" is not a date time. Predicting will fail.",
+		);
+	}
+
+	#[test]
+	fn test_class_thresholds_from_metadata() {
+		let bytes = train_iris_model();
+		assert_eq!(
+			modelfox_model::metadata_from_bytes(&bytes).unwrap(),
+			modelfox_model::Metadata::default(),
+		);
+		let model = Model::from_bytes(&bytes).unwrap();
+		let mut input = PredictInput::new();
+		input
+			.0
+			.insert("petal_length".to_owned(), PredictInputValue::Number(4.75));
+		let predict_class = |model: &Model, options: &PredictOptions| match predict(
+			model,
+			std::slice::from_ref(&input),
+			options,
+		)
+		.remove(0)
+		{
+			PredictOutput::MulticlassClassification(output) => output,
+			_ => unreachable!(),
+		};
+		let output = predict_class(&model, &PredictOptions::default());
+		assert!(output.probability < 1.0);
+		// Save a threshold the predicted class does not meet in the metadata.
+		let mut metadata = modelfox_model::Metadata::default();
+		metadata
+			.class_thresholds
+			.insert(output.class_name.clone(), (output.probability + 1.0) / 2.0);
+		let bytes_with_metadata = modelfox_model::with_metadata(&bytes, &metadata).unwrap();
+		assert_eq!(
+			modelfox_model::metadata_from_bytes(&bytes_with_metadata).unwrap(),
+			metadata,
+		);
+		let model_with_metadata = Model::from_bytes(&bytes_with_metadata).unwrap();
+		assert_eq!(model_with_metadata.id, model.id);
+		match &model_with_metadata.inner {
+			ModelInner::MulticlassClassifier(model) => {
+				assert_eq!(model.class_thresholds, metadata.class_thresholds);
+			}
+			_ => unreachable!(),
+		}
+		// Without thresholds in the options, the thresholds from the metadata are used.
+		let output_with_metadata = predict_class(&model_with_metadata, &PredictOptions::default());
+		assert_ne!(output_with_metadata.class_name, output.class_name);
+		// Thresholds in the options take the place of those from the metadata.
+		let mut options = PredictOptions::default();
+		options
+			.class_thresholds
+			.insert(output.class_name.clone(), 0.0);
+		let output_with_options = predict_class(&model_with_metadata, &options);
+		assert_eq!(output_with_options.class_name, output.class_name);
+		let bytes_without_metadata =
+			modelfox_model::with_metadata(&bytes_with_metadata, &Default::default()).unwrap();
+		assert_eq!(bytes_without_metadata, bytes);
+	}
+
+	/// Train a linear multiclass classifier that predicts the species of an iris from the length of its petals, where the lengths of versicolor and virginica petals overlap.
+	fn train_iris_model() -> Vec<u8> {
+		let species = [("setosa", 1.4), ("versicolor", 4.0), ("virginica", 5.5)];
+		let mut csv = "petal_length,species\n".to_owned();
+		for index in 0..150 {
+			let (species, mean) = species[index % species.len()];
+			let petal_length = mean + ((index / 3) % 10) as f32 * 0.2 - 0.9;
+			csv.push_str(&format!("{},{}\n", petal_length, species));
+		}
+		let config = serde_json::json!({
+			"dataset": {
+				"columns": [
+					{ "type": "number", "name": "petal_length" },
+					{ "type": "enum", "name": "species", "variants": ["setosa", "versicolor", "virginica"] },
+				],
+			},
+			"train": { "grid": [{ "model": "linear" }] },
+		});
+		let config = serde_json::from_value(config).unwrap();
+		crate::train::TrainerBuilder::new(
+			crate::train::TrainingDataSource::Csv(csv.into_bytes()),
+			"species",
+		)
+		.config(config)
+		.train(&mut |_| {})
+		.unwrap()
+		.to_bytes()
+	}
+
+	#[test]
+	fn test_feature_contributions_for_each_row() {
+		let path =
+			std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.modelfox");
+		let bytes = std::fs::read(path).unwrap();
+		let model = Model::from(modelfox_model::from_bytes(&bytes).unwrap());
+		let input = |age: f64, chest_pain: &str| {
+			let mut input = PredictInput::new();
+			input
+				.0
+				.insert("age".to_owned(), PredictInputValue::Number(age));
+			input.0.insert(
+				"chest_pain".to_owned(),
+				PredictInputValue::String(chest_pain.to_owned()),
+			);
+			input
+		};
+		let inputs = vec![input(63.0, "typical angina"), input(67.0, "asymptomatic")];
+		let options = PredictOptions {
+			compute_feature_contributions: true,
+			..Default::default()
+		};
+		// The feature contributions for each row of a batch must match those for the row alone.
+		let outputs = predict(&model, &inputs, &options);
+		for (input, output) in zip!(inputs.iter(), outputs.iter()) {
+			let expected = predict(&model, std::slice::from_ref(input), &options);
+			assert_eq!(
+				serde_json::to_string(output).unwrap(),
+				serde_json::to_string(&expected[0]).unwrap(),
+			);
+		}
+	}
+}
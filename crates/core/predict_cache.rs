@@ -0,0 +1,350 @@
+/*!
+This module implements an optional least recently used cache of predictions for the SDKs. Entries are keyed on a hash of the input and the predict options, so repeated identical predictions are served without running the model again.
+*/
+
+use crate::predict::{
+	predict, try_predict_with, InvalidValueError, Model, PredictInput, PredictInputValue,
+	PredictOptions, PredictOutput,
+};
+use fnv::FnvHasher;
+use std::{
+	collections::HashMap,
+	hash::{BuildHasherDefault, Hash, Hasher},
+	sync::Mutex,
+};
+
+/// A thread safe LRU cache of predictions. A cache with a capacity of zero never stores anything.
+#[derive(Debug)]
+pub struct PredictCache {
+	capacity: usize,
+	state: Mutex<PredictCacheState>,
+}
+
+/// The entries are stored in a doubly linked list threaded through `nodes` and ordered from most to least recently used, so looking up, promoting, and evicting an entry are all constant time. Once the cache is full, the node of the evicted entry is reused for the new one.
+#[derive(Debug, Default)]
+struct PredictCacheState {
+	index: HashMap<u64, usize, BuildHasherDefault<FnvHasher>>,
+	nodes: Vec<PredictCacheNode>,
+	/// The index of the most recently used node.
+	head: Option<usize>,
+	/// The index of the least recently used node.
+	tail: Option<usize>,
+	hits: u64,
+	misses: u64,
+}
+
+#[derive(Debug)]
+struct PredictCacheNode {
+	key: u64,
+	entry: PredictCacheEntry,
+	prev: Option<usize>,
+	next: Option<usize>,
+}
+
+#[derive(Debug)]
+struct PredictCacheEntry {
+	input: PredictInput,
+	options: PredictOptions,
+	output: PredictOutput,
+}
+
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+pub struct PredictCacheStats {
+	pub capacity: usize,
+	pub len: usize,
+	pub hits: u64,
+	pub misses: u64,
+}
+
+impl PredictCache {
+	pub fn new(capacity: usize) -> PredictCache {
+		PredictCache {
+			capacity,
+			state: Mutex::new(PredictCacheState::default()),
+		}
+	}
+
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	pub fn stats(&self) -> PredictCacheStats {
+		let state = self.state.lock().unwrap();
+		PredictCacheStats {
+			capacity: self.capacity,
+			len: state.index.len(),
+			hits: state.hits,
+			misses: state.misses,
+		}
+	}
+
+	/// Remove all entries and reset the hit and miss counts.
+	pub fn clear(&self) {
+		*self.state.lock().unwrap() = PredictCacheState::default();
+	}
+}
+
+impl PredictCacheState {
+	fn get(
+		&mut self,
+		key: u64,
+		input: &PredictInput,
+		options: &PredictOptions,
+	) -> Option<PredictOutput> {
+		// Check the full input as well as the key so a hash collision is treated as a miss.
+		let index = match self.index.get(&key) {
+			Some(&index)
+				if self.nodes[index].entry.input == *input
+					&& self.nodes[index].entry.options == *options =>
+			{
+				index
+			}
+			_ => {
+				self.misses += 1;
+				return None;
+			}
+		};
+		self.hits += 1;
+		self.unlink(index);
+		self.push_front(index);
+		Some(self.nodes[index].entry.output.clone())
+	}
+
+	fn insert(&mut self, capacity: usize, key: u64, entry: PredictCacheEntry) {
+		if capacity == 0 {
+			return;
+		}
+		// Replace the entry in place if the key is already cached, which happens when two inputs collide.
+		if let Some(&index) = self.index.get(&key) {
+			self.nodes[index].entry = entry;
+			self.unlink(index);
+			self.push_front(index);
+			return;
+		}
+		let index = if self.nodes.len() < capacity {
+			self.nodes.push(PredictCacheNode {
+				key,
+				entry,
+				prev: None,
+				next: None,
+			});
+			self.nodes.len() - 1
+		} else {
+			let index = self.tail.unwrap();
+			self.unlink(index);
+			let node = &mut self.nodes[index];
+			self.index.remove(&node.key);
+			node.key = key;
+			node.entry = entry;
+			index
+		};
+		self.push_front(index);
+		self.index.insert(key, index);
+	}
+
+	fn unlink(&mut self, index: usize) {
+		let (prev, next) = (self.nodes[index].prev, self.nodes[index].next);
+		match prev {
+			Some(prev) => self.nodes[prev].next = next,
+			None => self.head = next,
+		}
+		match next {
+			Some(next) => self.nodes[next].prev = prev,
+			None => self.tail = prev,
+		}
+		self.nodes[index].prev = None;
+		self.nodes[index].next = None;
+	}
+
+	fn push_front(&mut self, index: usize) {
+		self.nodes[index].next = self.head;
+		if let Some(head) = self.head {
+			self.nodes[head].prev = Some(index);
+		}
+		self.head = Some(index);
+		if self.tail.is_none() {
+			self.tail = Some(index);
+		}
+	}
+}
+
+/// Make predictions, serving any inputs that have been seen before with the same options from `cache` and storing the rest.
+pub fn predict_with_cache(
+	model: &Model,
+	cache: &PredictCache,
+	input: &[PredictInput],
+	options: &PredictOptions,
+) -> Vec<PredictOutput> {
+	if cache.capacity == 0 {
+		return predict(model, input, options);
+	}
+	let keys = input
+		.iter()
+		.map(|input| cache_key(input, options))
+		.collect::<Vec<_>>();
+	let mut outputs = {
+		let mut state = cache.state.lock().unwrap();
+		keys.iter()
+			.zip(input.iter())
+			.map(|(key, input)| state.get(*key, input, options))
+			.collect::<Vec<_>>()
+	};
+	// Run the model once on all the inputs that missed.
+	let miss_indexes = outputs
+		.iter()
+		.enumerate()
+		.filter(|(_, output)| output.is_none())
+		.map(|(index, _)| index)
+		.collect::<Vec<_>>();
+	if !miss_indexes.is_empty() {
+		let miss_input = miss_indexes
+			.iter()
+			.map(|index| input[*index].clone())
+			.collect::<Vec<_>>();
+		let miss_output = predict(model, &miss_input, options);
+		let mut state = cache.state.lock().unwrap();
+		for ((index, input), output) in miss_indexes.iter().zip(miss_input).zip(miss_output) {
+			let entry = PredictCacheEntry {
+				input,
+				options: options.clone(),
+				output: output.clone(),
+			};
+			state.insert(cache.capacity, keys[*index], entry);
+			outputs[*index] = Some(output);
+		}
+	}
+	outputs.into_iter().map(Option::unwrap).collect()
+}
+
+/// Make predictions like [`try_predict`](crate::predict::try_predict), enforcing the invalid value policy each column was trained with, and serve the inputs that are kept from `cache` like [`predict_with_cache`].
+pub fn try_predict_with_cache(
+	model: &Model,
+	cache: &PredictCache,
+	input: &[PredictInput],
+	options: &PredictOptions,
+) -> Result<Vec<Option<PredictOutput>>, InvalidValueError> {
+	try_predict_with(model, input, |input| {
+		predict_with_cache(model, cache, input, options)
+	})
+}
+
+fn cache_key(input: &PredictInput, options: &PredictOptions) -> u64 {
+	let mut hasher = FnvHasher::default();
+	for (column_name, value) in input.0.iter() {
+		column_name.hash(&mut hasher);
+		match value {
+			PredictInputValue::Number(value) => {
+				0u8.hash(&mut hasher);
+				value.to_bits().hash(&mut hasher);
+			}
+			PredictInputValue::String(value) => {
+				1u8.hash(&mut hasher);
+				value.hash(&mut hasher);
+			}
+		}
+	}
+	options.threshold.to_bits().hash(&mut hasher);
+	options.compute_feature_contributions.hash(&mut hasher);
+	(options.tree_shap_mode as u8).hash(&mut hasher);
+	for (class, threshold) in options.class_thresholds.iter() {
+		class.hash(&mut hasher);
+		threshold.to_bits().hash(&mut hasher);
+	}
+	hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::predict::RegressionPredictOutput;
+
+	fn entry(input: &PredictInput, value: f32) -> PredictCacheEntry {
+		PredictCacheEntry {
+			input: input.clone(),
+			options: PredictOptions::default(),
+			output: PredictOutput::Regression(RegressionPredictOutput {
+				value,
+				feature_contributions: None,
+			}),
+		}
+	}
+
+	fn input(value: PredictInputValue) -> PredictInput {
+		let mut input = PredictInput::new();
+		input.0.insert("x".to_owned(), value);
+		input
+	}
+
+	#[test]
+	fn test_cache_key_distinguishes_value_types() {
+		let options = PredictOptions::default();
+		let number = cache_key(&input(1.0.into()), &options);
+		let string = cache_key(&input("1".into()), &options);
+		assert_ne!(number, string);
+	}
+
+	#[test]
+	fn test_least_recently_used_entry_is_evicted() {
+		let options = PredictOptions::default();
+		let mut state = PredictCacheState::default();
+		let inputs = (0..3)
+			.map(|i| input(f64::from(i).into()))
+			.collect::<Vec<_>>();
+		let keys = inputs
+			.iter()
+			.map(|input| cache_key(input, &options))
+			.collect::<Vec<_>>();
+		state.insert(2, keys[0], entry(&inputs[0], 0.0));
+		state.insert(2, keys[1], entry(&inputs[1], 1.0));
+		assert!(state.get(keys[0], &inputs[0], &options).is_some());
+		state.insert(2, keys[2], entry(&inputs[2], 2.0));
+		assert!(state.get(keys[1], &inputs[1], &options).is_none());
+		assert!(state.get(keys[0], &inputs[0], &options).is_some());
+		assert!(state.get(keys[2], &inputs[2], &options).is_some());
+		assert_eq!(state.hits, 3);
+		assert_eq!(state.misses, 1);
+	}
+
+	#[test]
+	fn test_evicted_node_is_reused() {
+		let options = PredictOptions::default();
+		let mut state = PredictCacheState::default();
+		let inputs = (0..4)
+			.map(|i| input(f64::from(i).into()))
+			.collect::<Vec<_>>();
+		let keys = inputs
+			.iter()
+			.map(|input| cache_key(input, &options))
+			.collect::<Vec<_>>();
+		for (i, (key, input)) in keys.iter().zip(inputs.iter()).enumerate() {
+			state.insert(1, *key, entry(input, i as f32));
+		}
+		assert_eq!(state.nodes.len(), 1);
+		assert_eq!(state.index.len(), 1);
+		assert!(state.get(keys[2], &inputs[2], &options).is_none());
+		let output = state.get(keys[3], &inputs[3], &options).unwrap();
+		match output {
+			PredictOutput::Regression(output) => assert_eq!(output.value, 3.0),
+			_ => unreachable!(),
+		}
+	}
+
+	#[test]
+	fn test_reinserting_a_key_replaces_its_entry() {
+		let options = PredictOptions::default();
+		let mut state = PredictCacheState::default();
+		let inputs = (0..2)
+			.map(|i| input(f64::from(i).into()))
+			.collect::<Vec<_>>();
+		let keys = inputs
+			.iter()
+			.map(|input| cache_key(input, &options))
+			.collect::<Vec<_>>();
+		state.insert(2, keys[0], entry(&inputs[0], 0.0));
+		state.insert(2, keys[1], entry(&inputs[1], 1.0));
+		state.insert(2, keys[0], entry(&inputs[0], 0.0));
+		assert_eq!(state.index.len(), 2);
+		assert_eq!(state.head, Some(0));
+		assert_eq!(state.tail, Some(1));
+	}
+}
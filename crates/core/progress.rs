@@ -2,6 +2,20 @@ pub use modelfox_linear::TrainProgressEvent as LinearTrainProgressEvent;
 use modelfox_progress_counter::ProgressCounter;
 pub use modelfox_tree::TrainProgressEvent as TreeTrainProgressEvent;
 
+/// Implement this trait to receive progress events while training with [`TrainerBuilder`](crate::train::TrainerBuilder). It is implemented for every `FnMut(ProgressEvent)`, so you can pass a closure.
+pub trait ProgressHandler {
+	fn handle_progress_event(&mut self, progress_event: ProgressEvent);
+}
+
+impl<F> ProgressHandler for F
+where
+	F: FnMut(ProgressEvent),
+{
+	fn handle_progress_event(&mut self, progress_event: ProgressEvent) {
+		self(progress_event)
+	}
+}
+
 #[derive(Clone, Debug)]
 pub enum ProgressEvent {
 	Info(String),
@@ -47,6 +61,11 @@ pub enum TrainGridItemProgressEvent {
 	ComputeFeaturesDone,
 	TrainModel(ModelTrainProgressEvent),
 	ComputeModelComparisonMetrics(ModelTestProgressEvent),
+	/// The grid item finished training, and its comparison metric value is the mean over the comparison folds.
+	Done {
+		comparison_metric: String,
+		comparison_metric_value: f32,
+	},
 }
 
 #[derive(Clone, Debug)]
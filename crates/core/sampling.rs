@@ -0,0 +1,45 @@
+/*!
+This module decides which prediction events to log when an SDK is configured with a sample rate below 1. The decision is a deterministic function of the event's identifier, so the prediction and true value events for the same identifier are either both logged or both dropped.
+
+Every SDK makes the same decision for the same identifier. The Python, Rust, Elixir, and C libraries call [`is_sampled`], the Ruby library calls it through the C library, and the JavaScript and Go libraries reimplement the hash. Numeric identifiers are hashed as their decimal string, with no fractional part if they are whole, like `42`, and otherwise in their shortest round trip form, like `0.5`.
+*/
+
+const FNV_OFFSET_BASIS: u32 = 0x811c9dc5;
+const FNV_PRIME: u32 = 0x01000193;
+
+/// Determine whether the event with this identifier should be logged at the given sample rate. The identifier is hashed with 32 bit FNV-1a, which is simple to reproduce in the SDKs that are not built on this crate. The event is logged if the hash, as a fraction of 2^32, is less than the sample rate.
+pub fn is_sampled(identifier: &str, sample_rate: f32) -> bool {
+	if sample_rate >= 1.0 {
+		return true;
+	}
+	let hash = identifier.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+		(hash ^ u32::from(byte)).wrapping_mul(FNV_PRIME)
+	});
+	let fraction = f64::from(hash) / (f64::from(u32::MAX) + 1.0);
+	fraction < f64::from(sample_rate)
+}
+
+/// Check that a sample rate is in the range (0, 1].
+pub fn is_valid_sample_rate(sample_rate: f32) -> bool {
+	sample_rate > 0.0 && sample_rate <= 1.0
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_is_sampled_is_deterministic() {
+		for identifier in ["a", "b", "prediction_1", "12345"] {
+			assert_eq!(is_sampled(identifier, 0.5), is_sampled(identifier, 0.5));
+		}
+	}
+
+	#[test]
+	fn test_is_sampled_rate() {
+		let n = 10_000;
+		let n_sampled = (0..n).filter(|i| is_sampled(&i.to_string(), 0.1)).count();
+		assert!(n_sampled > 800 && n_sampled < 1200);
+		assert!((0..n).all(|i| is_sampled(&i.to_string(), 1.0)));
+	}
+}
@@ -1,10 +1,12 @@
+use chrono_tz::Tz;
 use fnv::{FnvBuildHasher, FnvHashSet};
 use indexmap::IndexMap;
 use itertools::Itertools;
 use modelfox_finite::Finite;
+use modelfox_metrics::QuantileSketch;
 use modelfox_progress_counter::ProgressCounter;
 use modelfox_table::prelude::*;
-use modelfox_text::{NGram, NGramType, Tokenizer};
+use modelfox_text::{Language, LanguageDetector, NGram, NGramType, Tokenizer};
 use modelfox_zip::zip;
 use num::ToPrimitive;
 use std::{cmp::Ordering, collections::BTreeMap, num::NonZeroU64};
@@ -14,6 +16,12 @@ use std::{cmp::Ordering, collections::BTreeMap, num::NonZeroU64};
 pub struct StatsSettings {
 	/// This is the maximum number of unique numeric values to store in the histogram.
 	pub number_histogram_max_size: usize,
+	/// Number columns with more unique values than this estimate their quantiles with a [`QuantileSketch`] instead of counting every unique value.
+	pub number_exact_quantiles_max_unique_count: usize,
+	/// This is the number of equal width bins in the histogram for datetime columns.
+	pub datetime_histogram_bin_count: usize,
+	/// These are the quantiles, between 0 and 1, to compute for number columns in addition to the p25, p50, and p75.
+	pub number_quantiles: Vec<f32>,
 	/// This is the maximum number of ngrams to track for text columns.
 	pub ngrams_max_count: usize,
 	/// This setting specifies which ngram types should be computed.
@@ -24,6 +32,9 @@ impl Default for StatsSettings {
 	fn default() -> StatsSettings {
 		StatsSettings {
 			number_histogram_max_size: 100,
+			number_exact_quantiles_max_unique_count: 100_000,
+			datetime_histogram_bin_count: 20,
+			number_quantiles: vec![0.25, 0.50, 0.75],
 			ngrams_max_count: 20_000,
 			ngram_types: vec![NGramType::Unigram, NGramType::Bigram]
 				.into_iter()
@@ -48,6 +59,7 @@ pub enum ColumnStats {
 	Number(NumberColumnStats),
 	Enum(EnumColumnStats),
 	Text(TextColumnStats),
+	DateTime(DateTimeColumnStats),
 }
 
 /// This struct contains stats for unknown columns.
@@ -70,8 +82,31 @@ pub struct NumberColumnStats {
 	pub valid_count: usize,
 	/// This is the total number of invalid values. Invalid values are values that fail to parse as finite f32.
 	pub invalid_count: usize,
-	/// This stores counts for each unique value.
-	pub histogram: BTreeMap<Finite<f32>, usize>,
+	/// These are the valid values.
+	pub values: NumberColumnValues,
+	/// This is the [`number_exact_quantiles_max_unique_count`](StatsSettings#number_exact_quantiles_max_unique_count) setting, which is needed to merge stats.
+	pub exact_quantiles_max_unique_count: usize,
+}
+
+/// This enum summarizes the valid values in a number column.
+#[derive(Clone, Debug)]
+pub enum NumberColumnValues {
+	/// This stores counts for each unique value, from which the quantiles are computed exactly.
+	Histogram(BTreeMap<Finite<f32>, usize>),
+	/// Once a column has more than [`number_exact_quantiles_max_unique_count`](StatsSettings#number_exact_quantiles_max_unique_count) unique values, the histogram is replaced with this.
+	Sketch(NumberColumnSketch),
+}
+
+/// This struct summarizes the values of a number column with too many unique values to count each.
+#[derive(Clone, Debug)]
+pub struct NumberColumnSketch {
+	/// This is used to estimate the quantiles.
+	pub sketch: QuantileSketch,
+	/// These are the bits of each unique value, which are kept to count them exactly. This takes a fraction of the memory of the histogram.
+	pub unique_values: FnvHashSet<u32>,
+	pub count: u64,
+	pub mean: f64,
+	pub m2: f64,
 }
 
 /// This struct contains stats for enum columns.
@@ -104,6 +139,25 @@ pub struct TextColumnStats {
 	pub ngram_types: FnvHashSet<NGramType>,
 	/// These are stats collected on ngrams.
 	pub ngrams: IndexMap<NGram, TextColumnStatsNGramEntry, FnvBuildHasher>,
+	/// This counts the stop words of each supported language to detect the column's language.
+	pub language_detector: LanguageDetector,
+}
+
+/// This struct contains stats for datetime columns.
+#[derive(Clone, Debug)]
+pub struct DateTimeColumnStats {
+	/// This is the name of the column.
+	pub column_name: String,
+	/// This is the timezone used to interpret values without a utc offset.
+	pub timezone: Tz,
+	/// The total number of values.
+	pub count: usize,
+	/// The total number of valid values.
+	pub valid_count: usize,
+	/// This is the total number of invalid values. Invalid values are values that fail to parse as a date or datetime.
+	pub invalid_count: usize,
+	/// This stores counts for each unique unix timestamp.
+	pub histogram: BTreeMap<i64, usize>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -121,6 +175,7 @@ pub enum ColumnStatsOutput {
 	Number(NumberColumnStatsOutput),
 	Enum(EnumColumnStatsOutput),
 	Text(TextColumnStatsOutput),
+	DateTime(DateTimeColumnStatsOutput),
 }
 
 impl ColumnStatsOutput {
@@ -131,6 +186,7 @@ impl ColumnStatsOutput {
 			ColumnStatsOutput::Number(value) => &value.column_name,
 			ColumnStatsOutput::Enum(value) => &value.column_name,
 			ColumnStatsOutput::Text(value) => &value.column_name,
+			ColumnStatsOutput::DateTime(value) => &value.column_name,
 		}
 	}
 }
@@ -173,6 +229,8 @@ pub struct NumberColumnStatsOutput {
 	pub p50: f32,
 	/// This is the p75, or 75th-percentile value in the column.
 	pub p75: f32,
+	/// These are the values of each of the [`number_quantiles`](StatsSettings#number_quantiles), as pairs of the quantile and its value.
+	pub quantiles: Vec<(f32, f32)>,
 }
 
 /// This struct contains stats for enum columns.
@@ -199,6 +257,8 @@ pub struct TextColumnStatsOutput {
 	pub row_count: u64,
 	/// This is the tokenizer that was used to separate values into tokens.
 	pub tokenizer: Tokenizer,
+	/// This is the dominant language of the column's values, if one was detected. When a language is detected, ngrams made up entirely of its stop words are excluded from `top_ngrams`.
+	pub language: Option<Language>,
 	/// This is the list of ngram types.
 	pub ngram_types: FnvHashSet<NGramType>,
 	/// This is the number of unique ngrams encountered.
@@ -207,6 +267,31 @@ pub struct TextColumnStatsOutput {
 	pub top_ngrams: IndexMap<NGram, TextColumnStatsOutputTopNGramsEntry, FnvBuildHasher>,
 }
 
+/// This struct contains stats for datetime columns. Datetimes are unix timestamps in seconds.
+#[derive(Debug)]
+pub struct DateTimeColumnStatsOutput {
+	/// This is the name of the column as it appears in the csv.
+	pub column_name: String,
+	/// This is the timezone used to interpret values without a utc offset.
+	pub timezone: Tz,
+	/// This is the total number of examples that these stats were computed on.
+	pub count: usize,
+	/// This is the total number of invalid values. Invalid values are values that fail to parse as a date or datetime.
+	pub invalid_count: usize,
+	/// This is the total number of unique values.
+	pub unique_count: usize,
+	/// This is the earliest value in the column. It is `None` if the column has no valid values.
+	pub min: Option<i64>,
+	/// This is the latest value in the column. It is `None` if the column has no valid values.
+	pub max: Option<i64>,
+	/// This is the mean of the values in the column.
+	pub mean: f32,
+	/// This is the variance of the values in the column.
+	pub variance: f32,
+	/// This is a histogram of [`datetime_histogram_bin_count`](StatsSettings#datetime_histogram_bin_count) equal width bins from `min` to `max`, mapping the start of each bin to the number of values in it.
+	pub histogram: Vec<(i64, usize)>,
+}
+
 /// This struct contains stats for individual ngrams.
 #[derive(Clone, Debug)]
 pub struct TextColumnStatsOutputTopNGramsEntry {
@@ -278,6 +363,9 @@ impl ColumnStats {
 			TableColumnView::Text(column) => {
 				ColumnStats::Text(TextColumnStats::compute(column, settings, progress))
 			}
+			TableColumnView::DateTime(column) => {
+				ColumnStats::DateTime(DateTimeColumnStats::compute(column, settings, progress))
+			}
 		}
 	}
 
@@ -293,6 +381,9 @@ impl ColumnStats {
 			(ColumnStats::Number(a), ColumnStats::Number(b)) => ColumnStats::Number(a.merge(b)),
 			(ColumnStats::Enum(a), ColumnStats::Enum(b)) => ColumnStats::Enum(a.merge(b)),
 			(ColumnStats::Text(a), ColumnStats::Text(b)) => ColumnStats::Text(a.merge(b)),
+			(ColumnStats::DateTime(a), ColumnStats::DateTime(b)) => {
+				ColumnStats::DateTime(a.merge(b))
+			}
 			_ => unreachable!(),
 		}
 	}
@@ -314,6 +405,9 @@ impl ColumnStats {
 			ColumnStats::Text(column_stats_output) => {
 				ColumnStatsOutput::Text(column_stats_output.finalize(settings))
 			}
+			ColumnStats::DateTime(column_stats_output) => {
+				ColumnStatsOutput::DateTime(column_stats_output.finalize(settings))
+			}
 		}
 	}
 }
@@ -321,20 +415,23 @@ impl ColumnStats {
 impl NumberColumnStats {
 	fn compute(
 		column: NumberTableColumnView,
-		_settings: &StatsSettings,
+		settings: &StatsSettings,
 		progress: impl Fn(u64),
 	) -> NumberColumnStats {
 		let mut stats = NumberColumnStats {
 			column_name: column.name().unwrap().to_owned(),
 			count: column.len(),
-			histogram: BTreeMap::new(),
+			values: NumberColumnValues::Histogram(BTreeMap::new()),
 			invalid_count: 0,
 			valid_count: 0,
+			exact_quantiles_max_unique_count: settings.number_exact_quantiles_max_unique_count,
 		};
 		for value in column.iter() {
-			// If the value parses as a finite f32, add it to the histogram. Otherwise, increment the invalid count.
+			// If the value parses as a finite f32, add it to the values. Otherwise, increment the invalid count.
 			if let Ok(value) = <Finite<f32>>::new(*value) {
-				*stats.histogram.entry(value).or_insert(0) += 1;
+				stats
+					.values
+					.insert(value, 1, stats.exact_quantiles_max_unique_count);
 				stats.valid_count += 1;
 			} else {
 				stats.invalid_count += 1;
@@ -345,9 +442,26 @@ impl NumberColumnStats {
 	}
 
 	fn merge(mut self, other: NumberColumnStats) -> NumberColumnStats {
-		for (value, count) in other.histogram.iter() {
-			*self.histogram.entry(*value).or_insert(0) += count;
-		}
+		self.values = match (self.values, other.values) {
+			(NumberColumnValues::Histogram(a), NumberColumnValues::Histogram(b)) => {
+				let mut values = NumberColumnValues::Histogram(a);
+				for (value, count) in b {
+					values.insert(value, count, self.exact_quantiles_max_unique_count);
+				}
+				values
+			}
+			(NumberColumnValues::Histogram(histogram), NumberColumnValues::Sketch(mut sketch))
+			| (NumberColumnValues::Sketch(mut sketch), NumberColumnValues::Histogram(histogram)) => {
+				for (value, count) in histogram {
+					sketch.insert(value, count);
+				}
+				NumberColumnValues::Sketch(sketch)
+			}
+			(NumberColumnValues::Sketch(mut a), NumberColumnValues::Sketch(b)) => {
+				a.merge(b);
+				NumberColumnValues::Sketch(a)
+			}
+		};
 		self.count += other.count;
 		self.invalid_count += other.invalid_count;
 		self.valid_count += other.valid_count;
@@ -355,94 +469,228 @@ impl NumberColumnStats {
 	}
 
 	fn finalize(self, settings: &StatsSettings) -> NumberColumnStatsOutput {
-		let unique_values_count = self.histogram.len();
-		let invalid_count = self.invalid_count;
-		let histogram = if self.histogram.len() <= settings.number_histogram_max_size {
-			Some(self.histogram.iter().map(|(k, v)| (*k, *v)).collect())
-		} else {
-			None
-		};
-		let min = self.histogram.iter().next().unwrap().0.get();
-		let max = self.histogram.iter().next_back().unwrap().0.get();
-		let total_values_count = self.valid_count.to_f32().unwrap();
-		let quantiles: Vec<f32> = vec![0.25, 0.50, 0.75];
-		// Find the index of each quantile given the total number of values in the dataset.
-		let quantile_indexes: Vec<usize> = quantiles
+		let quantiles: Vec<f32> = [0.25, 0.50, 0.75]
 			.iter()
-			.map(|q| ((total_values_count - 1.0) * q).trunc().to_usize().unwrap())
+			.chain(settings.number_quantiles.iter())
+			.cloned()
 			.collect();
-		// This is the fractiononal part of the index used to interpolate values if the index is not an integer value.
-		let quantile_fracts: Vec<f32> = quantiles
-			.iter()
-			.map(|q| ((total_values_count - 1.0) * q).fract())
-			.collect();
-		let mut quantiles: Vec<Option<f32>> = vec![None; quantiles.len()];
-		let mut current_count: usize = 0;
-		let mut mean = 0.0;
-		let mut m2 = 0.0;
-		let mut iter = self.histogram.iter().peekable();
-		while let Some((value, count)) = iter.next() {
-			let value = value.get();
-			let (new_mean, new_m2) = modelfox_metrics::merge_mean_m2(
-				current_count.to_u64().unwrap(),
-				mean,
-				m2,
-				count.to_u64().unwrap(),
-				value as f64,
-				0.0,
-			);
-			mean = new_mean;
-			m2 = new_m2;
-			current_count += count;
-			let quantiles_iter = zip!(
-				quantiles.iter_mut(),
-				quantile_indexes.iter(),
-				quantile_fracts.iter(),
-			)
-			.filter(|(quantile, _, _)| quantile.is_none());
-			for (quantile, index, fract) in quantiles_iter {
-				match (current_count - 1).cmp(index) {
-					Ordering::Equal => {
-						if *fract > 0.0 {
-							// Interpolate between two values.
-							let next_value = iter.peek().unwrap().0.get();
-							*quantile = Some(value * (1.0 - fract) + next_value * fract);
-						} else {
-							*quantile = Some(value);
-						}
-					}
-					Ordering::Greater => *quantile = Some(value),
-					Ordering::Less => {}
-				}
+		let values = match self.values {
+			NumberColumnValues::Histogram(histogram) => {
+				finalize_number_column_histogram(histogram, self.valid_count, &quantiles, settings)
 			}
-		}
-		let quantiles: Vec<f32> = quantiles.into_iter().map(|q| q.unwrap()).collect();
-		let p25 = quantiles[0];
-		let p50 = quantiles[1];
-		let p75 = quantiles[2];
-		let mean = mean.to_f32().unwrap();
-		let variance = modelfox_metrics::m2_to_variance(
-			m2,
-			NonZeroU64::new(current_count.to_u64().unwrap()).unwrap(),
-		);
+			NumberColumnValues::Sketch(sketch) => finalize_number_column_sketch(sketch, &quantiles),
+		};
+		let p25 = values.quantiles[0];
+		let p50 = values.quantiles[1];
+		let p75 = values.quantiles[2];
+		let quantiles = zip!(
+			settings.number_quantiles.iter().cloned(),
+			values.quantiles.into_iter().skip(3)
+		)
+		.collect();
 		NumberColumnStatsOutput {
 			column_name: self.column_name,
 			count: self.count,
-			histogram,
-			unique_count: unique_values_count,
-			max,
-			mean,
-			min,
-			invalid_count,
-			variance,
-			std: variance.sqrt(),
+			histogram: values.histogram,
+			unique_count: values.unique_count,
+			max: values.max,
+			mean: values.mean,
+			min: values.min,
+			invalid_count: self.invalid_count,
+			variance: values.variance,
+			std: values.variance.sqrt(),
 			p25,
 			p50,
 			p75,
+			quantiles,
+		}
+	}
+}
+
+impl NumberColumnValues {
+	fn insert(
+		&mut self,
+		value: Finite<f32>,
+		count: usize,
+		exact_quantiles_max_unique_count: usize,
+	) {
+		match self {
+			NumberColumnValues::Histogram(histogram) => {
+				*histogram.entry(value).or_insert(0) += count;
+				if histogram.len() > exact_quantiles_max_unique_count {
+					let histogram = std::mem::take(histogram);
+					let mut sketch = NumberColumnSketch::default();
+					for (value, count) in histogram {
+						sketch.insert(value, count);
+					}
+					*self = NumberColumnValues::Sketch(sketch);
+				}
+			}
+			NumberColumnValues::Sketch(sketch) => sketch.insert(value, count),
 		}
 	}
 }
 
+impl Default for NumberColumnSketch {
+	fn default() -> NumberColumnSketch {
+		NumberColumnSketch {
+			sketch: QuantileSketch::default(),
+			unique_values: FnvHashSet::default(),
+			count: 0,
+			mean: 0.0,
+			m2: 0.0,
+		}
+	}
+}
+
+impl NumberColumnSketch {
+	fn insert(&mut self, value: Finite<f32>, count: usize) {
+		let value = value.get();
+		let count = count.to_u64().unwrap();
+		self.sketch.insert_weighted(value, count.to_f64().unwrap());
+		// Adding zero turns -0.0 into 0.0, so the two are counted as one unique value, like they are in the histogram.
+		self.unique_values.insert((value + 0.0).to_bits());
+		let (mean, m2) = modelfox_metrics::merge_mean_m2(
+			self.count,
+			self.mean,
+			self.m2,
+			count,
+			value as f64,
+			0.0,
+		);
+		self.count += count;
+		self.mean = mean;
+		self.m2 = m2;
+	}
+
+	fn merge(&mut self, other: NumberColumnSketch) {
+		self.sketch.merge(other.sketch);
+		self.unique_values.extend(other.unique_values);
+		let (mean, m2) = modelfox_metrics::merge_mean_m2(
+			self.count,
+			self.mean,
+			self.m2,
+			other.count,
+			other.mean,
+			other.m2,
+		);
+		self.count += other.count;
+		self.mean = mean;
+		self.m2 = m2;
+	}
+}
+
+/// These are the stats computed from a number column's values, with the p25, p50, and p75 first in `quantiles`, followed by the [`number_quantiles`](StatsSettings#number_quantiles).
+struct NumberColumnValuesOutput {
+	histogram: Option<Vec<(Finite<f32>, usize)>>,
+	unique_count: usize,
+	min: f32,
+	max: f32,
+	mean: f32,
+	variance: f32,
+	quantiles: Vec<f32>,
+}
+
+fn finalize_number_column_histogram(
+	histogram: BTreeMap<Finite<f32>, usize>,
+	valid_count: usize,
+	quantiles: &[f32],
+	settings: &StatsSettings,
+) -> NumberColumnValuesOutput {
+	let unique_count = histogram.len();
+	let histogram_output = if histogram.len() <= settings.number_histogram_max_size {
+		Some(histogram.iter().map(|(k, v)| (*k, *v)).collect())
+	} else {
+		None
+	};
+	let min = histogram.iter().next().unwrap().0.get();
+	let max = histogram.iter().next_back().unwrap().0.get();
+	let total_values_count = valid_count.to_f32().unwrap();
+	// Find the index of each quantile given the total number of values in the dataset.
+	let quantile_indexes: Vec<usize> = quantiles
+		.iter()
+		.map(|q| ((total_values_count - 1.0) * q).trunc().to_usize().unwrap())
+		.collect();
+	// This is the fractiononal part of the index used to interpolate values if the index is not an integer value.
+	let quantile_fracts: Vec<f32> = quantiles
+		.iter()
+		.map(|q| ((total_values_count - 1.0) * q).fract())
+		.collect();
+	let mut quantiles: Vec<Option<f32>> = vec![None; quantiles.len()];
+	let mut current_count: usize = 0;
+	let mut mean = 0.0;
+	let mut m2 = 0.0;
+	let mut iter = histogram.iter().peekable();
+	while let Some((value, count)) = iter.next() {
+		let value = value.get();
+		let (new_mean, new_m2) = modelfox_metrics::merge_mean_m2(
+			current_count.to_u64().unwrap(),
+			mean,
+			m2,
+			count.to_u64().unwrap(),
+			value as f64,
+			0.0,
+		);
+		mean = new_mean;
+		m2 = new_m2;
+		current_count += count;
+		let quantiles_iter = zip!(
+			quantiles.iter_mut(),
+			quantile_indexes.iter(),
+			quantile_fracts.iter(),
+		)
+		.filter(|(quantile, _, _)| quantile.is_none());
+		for (quantile, index, fract) in quantiles_iter {
+			match (current_count - 1).cmp(index) {
+				Ordering::Equal => {
+					if *fract > 0.0 {
+						// Interpolate between two values.
+						let next_value = iter.peek().unwrap().0.get();
+						*quantile = Some(value * (1.0 - fract) + next_value * fract);
+					} else {
+						*quantile = Some(value);
+					}
+				}
+				Ordering::Greater => *quantile = Some(value),
+				Ordering::Less => {}
+			}
+		}
+	}
+	let quantiles = quantiles.into_iter().map(|q| q.unwrap()).collect();
+	let variance = modelfox_metrics::m2_to_variance(
+		m2,
+		NonZeroU64::new(current_count.to_u64().unwrap()).unwrap(),
+	);
+	NumberColumnValuesOutput {
+		histogram: histogram_output,
+		unique_count,
+		min,
+		max,
+		mean: mean.to_f32().unwrap(),
+		variance,
+		quantiles,
+	}
+}
+
+fn finalize_number_column_sketch(
+	sketch: NumberColumnSketch,
+	quantiles: &[f32],
+) -> NumberColumnValuesOutput {
+	NumberColumnValuesOutput {
+		// The sketch is only used for columns with too many unique values for the histogram.
+		histogram: None,
+		unique_count: sketch.unique_values.len(),
+		min: sketch.sketch.min().unwrap(),
+		max: sketch.sketch.max().unwrap(),
+		mean: sketch.mean.to_f32().unwrap(),
+		variance: modelfox_metrics::m2_to_variance(
+			sketch.m2,
+			NonZeroU64::new(sketch.count).unwrap(),
+		),
+		quantiles: sketch.sketch.quantiles(quantiles).unwrap(),
+	}
+}
+
 impl EnumColumnStats {
 	fn compute(
 		column: EnumTableColumnView,
@@ -489,6 +737,108 @@ impl EnumColumnStats {
 	}
 }
 
+impl DateTimeColumnStats {
+	fn compute(
+		column: DateTimeTableColumnView,
+		_settings: &StatsSettings,
+		progress: impl Fn(u64),
+	) -> DateTimeColumnStats {
+		let mut stats = DateTimeColumnStats {
+			column_name: column.name().unwrap().to_owned(),
+			timezone: column.timezone(),
+			count: column.len(),
+			histogram: BTreeMap::new(),
+			invalid_count: 0,
+			valid_count: 0,
+		};
+		for value in column.iter() {
+			if let Some(value) = value {
+				*stats.histogram.entry(*value).or_insert(0) += 1;
+				stats.valid_count += 1;
+			} else {
+				stats.invalid_count += 1;
+			}
+			progress(1);
+		}
+		stats
+	}
+
+	fn merge(mut self, other: DateTimeColumnStats) -> DateTimeColumnStats {
+		for (value, count) in other.histogram.iter() {
+			*self.histogram.entry(*value).or_insert(0) += count;
+		}
+		self.count += other.count;
+		self.invalid_count += other.invalid_count;
+		self.valid_count += other.valid_count;
+		self
+	}
+
+	fn finalize(self, settings: &StatsSettings) -> DateTimeColumnStatsOutput {
+		let min = self.histogram.keys().next().cloned();
+		let max = self.histogram.keys().next_back().cloned();
+		let mut current_count = 0;
+		let mut mean = 0.0;
+		let mut m2 = 0.0;
+		for (value, count) in self.histogram.iter() {
+			let (new_mean, new_m2) = modelfox_metrics::merge_mean_m2(
+				current_count,
+				mean,
+				m2,
+				count.to_u64().unwrap(),
+				value.to_f64().unwrap(),
+				0.0,
+			);
+			mean = new_mean;
+			m2 = new_m2;
+			current_count += count.to_u64().unwrap();
+		}
+		let variance = NonZeroU64::new(current_count)
+			.map(|current_count| modelfox_metrics::m2_to_variance(m2, current_count))
+			.unwrap_or(0.0);
+		let histogram = match (min, max) {
+			(Some(min), Some(max)) => {
+				let bin_count = settings.datetime_histogram_bin_count.to_i64().unwrap();
+				// Round the bin width up so the last bin includes `max`.
+				let bin_width = (max - min) / bin_count + 1;
+				let mut histogram = (0..bin_count)
+					.map(|bin_index| (min + bin_index * bin_width, 0))
+					.collect::<Vec<_>>();
+				for (value, count) in self.histogram.iter() {
+					let bin_index = ((value - min) / bin_width).to_usize().unwrap();
+					histogram[bin_index].1 += count;
+				}
+				histogram
+			}
+			_ => Vec::new(),
+		};
+		DateTimeColumnStatsOutput {
+			column_name: self.column_name,
+			timezone: self.timezone,
+			count: self.count,
+			invalid_count: self.invalid_count,
+			unique_count: self.histogram.len(),
+			min,
+			max,
+			mean: mean.to_f32().unwrap(),
+			variance,
+			histogram,
+		}
+	}
+}
+
+/// Language detection only looks at this many tokens in each chunk of a text column, which is plenty to find the dominant language.
+const TEXT_COLUMN_STATS_LANGUAGE_DETECTION_MAX_TOKENS: u64 = 100_000;
+
+/// An ngram is a stop word ngram if all of its tokens are stop words in the language. These carry little information, so they are left out of the top ngrams that bag of words features are built from.
+fn is_stop_word_ngram(ngram: &NGram, language: Language) -> bool {
+	match ngram {
+		NGram::Unigram(token) => language.is_stop_word(token),
+		NGram::Bigram(token_a, token_b) => {
+			language.is_stop_word(token_a) && language.is_stop_word(token_b)
+		}
+	}
+}
+
 #[derive(Clone, Debug, Eq)]
 struct TokenEntry(pub NGram, pub usize);
 
@@ -523,10 +873,18 @@ impl TextColumnStats {
 			tokenizer,
 			ngrams: IndexMap::default(),
 			ngram_types: settings.ngram_types.to_owned(),
+			language_detector: LanguageDetector::default(),
 		};
 		let mut ngrams_for_row = FnvHashSet::default();
 		for value in column.iter() {
 			ngrams_for_row.clear();
+			if stats.language_detector.token_count()
+				< TEXT_COLUMN_STATS_LANGUAGE_DETECTION_MAX_TOKENS
+			{
+				for token in stats.tokenizer.tokenize(value) {
+					stats.language_detector.update(&token);
+				}
+			}
 			let unigram_iter = if stats.ngram_types.contains(&NGramType::Unigram) {
 				Some(
 					stats
@@ -572,6 +930,7 @@ impl TextColumnStats {
 
 	fn merge(mut self, other: TextColumnStats) -> TextColumnStats {
 		self.row_count += other.row_count;
+		self.language_detector.merge(other.language_detector);
 		for (other_ngram, other_entry) in other.ngrams.into_iter() {
 			if let Some(entry) = self.ngrams.get_mut(&other_ngram) {
 				entry.row_count += other_entry.row_count;
@@ -588,10 +947,16 @@ impl TextColumnStats {
 			.sort_by(|_, entry_a, _, entry_b| entry_a.row_count.cmp(&entry_b.row_count));
 		let row_count = self.row_count.to_f32().unwrap();
 		let ngrams_count = self.ngrams.len();
+		let language = self.language_detector.detect();
 		let ngrams: IndexMap<NGram, TextColumnStatsOutputTopNGramsEntry, FnvBuildHasher> = self
 			.ngrams
 			.into_iter()
 			.rev()
+			.filter(|(ngram, _)| {
+				!language
+					.map(|language| is_stop_word_ngram(ngram, language))
+					.unwrap_or(false)
+			})
 			.take(settings.ngrams_max_count)
 			.map(|(ngram, entry)| {
 				let entry_row_count = entry.row_count.to_f32().unwrap();
@@ -607,6 +972,7 @@ impl TextColumnStats {
 		TextColumnStatsOutput {
 			column_name: self.column_name,
 			tokenizer: self.tokenizer,
+			language,
 			row_count: self.row_count.to_u64().unwrap(),
 			ngram_types: settings.ngram_types.clone(),
 			ngrams_count,
@@ -614,3 +980,31 @@ impl TextColumnStats {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_number_column_sketch() {
+		let settings = StatsSettings {
+			number_exact_quantiles_max_unique_count: 100,
+			..Default::default()
+		};
+		let compute = |values: Vec<f32>| {
+			let column = NumberTableColumn::new(Some("x".to_owned()), values);
+			NumberColumnStats::compute(column.view(), &settings, |_| {})
+		};
+		let a = compute((0..50).map(|value| value as f32).collect());
+		let b = compute((50..10_000).map(|value| value as f32).collect());
+		assert!(matches!(a.values, NumberColumnValues::Histogram(_)));
+		assert!(matches!(b.values, NumberColumnValues::Sketch(_)));
+		let output = a.merge(b).finalize(&settings);
+		assert!(output.histogram.is_none());
+		assert_eq!(output.unique_count, 10_000);
+		assert_eq!(output.min, 0.0);
+		assert_eq!(output.max, 9_999.0);
+		assert!((output.mean - 4_999.5).abs() < 1e-3);
+		assert!((output.p50 - 4_999.5).abs() < 20.0);
+	}
+}
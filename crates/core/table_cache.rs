@@ -0,0 +1,65 @@
+/*!
+The table cache saves the tables parsed from csv files in a directory, in the binary format written by [`Table::to_binary`]. Parsing a large csv file can take longer than the rest of loading a dataset, so when training again on the same file, such as after changing the hyperparameter grid, the parsed table is read from the cache instead.
+
+Each table is cached under a key computed from the contents of the file and the options it is parsed with, so editing the file or changing the column types in the config parses it again. Rows are dropped from the target column and the table is shuffled after it is loaded, so those options do not affect the key.
+*/
+
+use crate::checkpoint::fingerprint;
+use anyhow::Result;
+use modelfox_table::prelude::*;
+use std::{
+	collections::hash_map::DefaultHasher,
+	hash::Hasher,
+	io::{BufReader, BufWriter, Read},
+	path::Path,
+};
+
+/// Increment this when the parsing of csv files changes, so tables cached by earlier versions are not used.
+const CACHE_VERSION: u32 = 1;
+
+/// Parse the csv file at `file_path`, or read the table parsed from it from `cache_dir` if it was cached. If the table was not cached, it is written to the cache after it is parsed. Errors reading or writing the cache are ignored, because the table can always be parsed from the file.
+pub fn load_table(
+	file_path: &Path,
+	options: modelfox_table::FromCsvOptions,
+	cache_dir: Option<&Path>,
+	handle_progress_event: &mut impl FnMut(modelfox_table::ProgressEvent),
+) -> Result<Table> {
+	let cache_dir = match cache_dir {
+		Some(cache_dir) => cache_dir,
+		None => return Table::from_path(file_path, options, handle_progress_event),
+	};
+	let key = fingerprint(&(CACHE_VERSION, hash_file(file_path)?, &options));
+	let cache_path = cache_dir.join(format!("{:016x}.table", key));
+	if let Ok(file) = std::fs::File::open(&cache_path) {
+		if let Ok(table) = Table::from_binary(&mut BufReader::new(file)) {
+			return Ok(table);
+		}
+	}
+	let table = Table::from_path(file_path, options, handle_progress_event)?;
+	// Write to a temporary file and rename it, so an interrupted write does not leave a partial table in the cache.
+	let _ = (|| -> Result<()> {
+		std::fs::create_dir_all(cache_dir)?;
+		let temp_path = cache_path.with_extension("table.tmp");
+		let mut writer = BufWriter::new(std::fs::File::create(&temp_path)?);
+		table.to_binary(&mut writer)?;
+		writer.into_inner()?.sync_all()?;
+		std::fs::rename(&temp_path, &cache_path)?;
+		Ok(())
+	})();
+	Ok(table)
+}
+
+/// Hash the contents of the file. This reads the whole file, which is much faster than parsing it.
+fn hash_file(file_path: &Path) -> Result<u64> {
+	let mut reader = BufReader::new(std::fs::File::open(file_path)?);
+	let mut hasher = DefaultHasher::new();
+	let mut buffer = vec![0; 1 << 16];
+	loop {
+		let n = reader.read(&mut buffer)?;
+		if n == 0 {
+			break;
+		}
+		hasher.write(&buffer[..n]);
+	}
+	Ok(hasher.finish())
+}
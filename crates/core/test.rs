@@ -10,6 +10,7 @@ pub fn test_linear_regressor(
 	target_column_index: usize,
 	feature_groups: &[modelfox_features::FeatureGroup],
 	model: &modelfox_linear::Regressor,
+	tolerance: Option<modelfox_metrics::Tolerance>,
 	handle_progress_event: &mut dyn FnMut(ModelTestProgressEvent),
 ) -> modelfox_metrics::RegressionMetricsOutput {
 	let n_features = feature_groups.iter().map(|g| g.n_features()).sum::<usize>();
@@ -34,7 +35,7 @@ pub fn test_linear_regressor(
 		ArrayView1::from(labels.as_slice()).axis_chunks_iter(Axis(0), n_examples_per_batch),
 	)
 	.fold(
-		modelfox_metrics::RegressionMetrics::default,
+		|| modelfox_metrics::RegressionMetrics::with_tolerance(tolerance),
 		|mut test_metrics, (features, labels)| {
 			let mut predictions = Array::zeros(features.nrows());
 			model.predict(features, predictions.view_mut());
@@ -47,7 +48,7 @@ pub fn test_linear_regressor(
 		},
 	)
 	.reduce(
-		modelfox_metrics::RegressionMetrics::default,
+		|| modelfox_metrics::RegressionMetrics::with_tolerance(tolerance),
 		|mut metrics_a, metrics_b| {
 			metrics_a.merge(metrics_b);
 			metrics_a
@@ -63,6 +64,7 @@ pub fn test_tree_regressor(
 	target_column_index: usize,
 	feature_groups: &[modelfox_features::FeatureGroup],
 	model: &modelfox_tree::Regressor,
+	tolerance: Option<modelfox_metrics::Tolerance>,
 	handle_progress_event: &mut dyn FnMut(ModelTestProgressEvent),
 ) -> modelfox_metrics::RegressionMetricsOutput {
 	let n_features = feature_groups.iter().map(|g| g.n_features()).sum::<usize>();
@@ -86,7 +88,7 @@ pub fn test_tree_regressor(
 		ArrayView1::from(labels.as_slice()).axis_chunks_iter(Axis(0), n_examples_per_batch),
 	)
 	.fold(
-		modelfox_metrics::RegressionMetrics::default,
+		|| modelfox_metrics::RegressionMetrics::with_tolerance(tolerance),
 		|mut test_metrics, (features, labels)| {
 			let mut predictions = Array::zeros(features.nrows());
 			model.predict(features, predictions.view_mut());
@@ -99,7 +101,7 @@ pub fn test_tree_regressor(
 		},
 	)
 	.reduce(
-		modelfox_metrics::RegressionMetrics::default,
+		|| modelfox_metrics::RegressionMetrics::with_tolerance(tolerance),
 		|mut metrics_a, metrics_b| {
 			metrics_a.merge(metrics_b);
 			metrics_a
@@ -256,7 +258,7 @@ pub fn test_linear_multiclass_classifier(
 		ArrayView1::from(labels.as_slice()).axis_chunks_iter(Axis(0), n_examples_per_batch),
 	)
 	.fold(
-		|| modelfox_metrics::MulticlassClassificationMetrics::new(n_classes),
+		|| modelfox_metrics::MulticlassClassificationMetrics::new(n_classes, 99),
 		|mut test_metrics, (features, labels)| {
 			let mut predictions = Array::zeros((features.nrows(), n_classes));
 			model.predict(features, predictions.view_mut());
@@ -270,7 +272,7 @@ pub fn test_linear_multiclass_classifier(
 		},
 	)
 	.reduce(
-		|| modelfox_metrics::MulticlassClassificationMetrics::new(n_classes),
+		|| modelfox_metrics::MulticlassClassificationMetrics::new(n_classes, 99),
 		|mut metrics_a, metrics_b| {
 			metrics_a.merge(metrics_b);
 			metrics_a
@@ -315,7 +317,7 @@ pub fn test_tree_multiclass_classifier(
 		ArrayView1::from(labels.as_slice()).axis_chunks_iter(Axis(0), n_examples_per_batch),
 	)
 	.fold(
-		|| modelfox_metrics::MulticlassClassificationMetrics::new(n_classes),
+		|| modelfox_metrics::MulticlassClassificationMetrics::new(n_classes, 99),
 		|mut test_metrics, (features, labels)| {
 			let mut predictions = Array::zeros((features.nrows(), n_classes));
 			model.predict(features, predictions.view_mut());
@@ -329,7 +331,7 @@ pub fn test_tree_multiclass_classifier(
 		},
 	)
 	.reduce(
-		|| modelfox_metrics::MulticlassClassificationMetrics::new(n_classes),
+		|| modelfox_metrics::MulticlassClassificationMetrics::new(n_classes, 99),
 		|mut metrics_a, metrics_b| {
 			metrics_a.merge(metrics_b);
 			metrics_a
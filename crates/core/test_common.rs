@@ -0,0 +1,97 @@
+//! This module contains functionality used to test that the language libraries compute the same features as training. It is public so the tests of the C and Rust libraries can share it, but it is not part of the crate's API.
+
+use crate::{
+	model::{BinaryClassificationModel, Model, ModelInner},
+	train::{TrainerBuilder, TrainingDataSource},
+};
+use modelfox_features::{bag_of_words::BagOfWordsFeatureGroupStrategy, FeatureGroup};
+use modelfox_table::prelude::*;
+
+/// Generate the rows of a movie review dataset, each a review and its sentiment.
+pub fn tfidf_training_rows() -> Vec<(String, &'static str)> {
+	let subjects = ["movie", "plot", "acting", "ending", "music"];
+	let positive_words = ["great", "excellent", "wonderful", "lovely"];
+	let negative_words = ["terrible", "awful", "boring", "dull"];
+	(0..100)
+		.map(|index| {
+			let subject = subjects[index % subjects.len()];
+			let (words, sentiment) = if index % 2 == 0 {
+				(positive_words, "positive")
+			} else {
+				(negative_words, "negative")
+			};
+			let word = words[(index / 2) % words.len()];
+			// Repeat some words so the counts, and not just the presence, of ngrams matter.
+			let repeated_words = vec![word; index % 3].join(" ");
+			let review = format!("the {} was {} {}", subject, word, repeated_words);
+			(review, sentiment)
+		})
+		.collect()
+}
+
+/// Retrieve the reviews to make predictions with, which are the training reviews and a review with words the model has never seen.
+pub fn tfidf_prediction_reviews() -> Vec<String> {
+	let mut reviews = tfidf_training_rows()
+		.into_iter()
+		.map(|(review, _)| review)
+		.collect::<Vec<_>>();
+	reviews.push("the popcorn was great but the seats were dull".to_owned());
+	reviews
+}
+
+/// Train a linear binary classifier on the rows from [`tfidf_training_rows`] whose only feature group is a bag of words feature group for the `review` column with the tf-idf strategy.
+pub fn train_tfidf_model() -> Model {
+	let mut csv = "review,sentiment\n".to_owned();
+	for (review, sentiment) in tfidf_training_rows() {
+		csv.push_str(&format!("{},{}\n", review, sentiment));
+	}
+	let config = serde_json::json!({
+		"dataset": {
+			"columns": [
+				{ "type": "text", "name": "review" },
+				{ "type": "enum", "name": "sentiment", "variants": ["negative", "positive"] },
+			],
+		},
+		"features": {
+			"auto": { "enable": false },
+			"include": [
+				{ "type": "bag_of_words", "source_column_name": "review", "strategy": "tfidf" },
+			],
+		},
+		"train": { "grid": [{ "model": "linear" }] },
+	});
+	let config = serde_json::from_value(config).unwrap();
+	TrainerBuilder::new(TrainingDataSource::Csv(csv.into_bytes()), "sentiment")
+		.config(config)
+		.train(&mut |_| {})
+		.unwrap()
+}
+
+/// Compute the bag of words features for each review the way training does, with `compute_table`.
+pub fn tfidf_training_features(model: &Model, reviews: &[String]) -> Vec<Vec<f32>> {
+	let feature_groups = match &model.inner {
+		ModelInner::BinaryClassifier(model) => match &model.model {
+			BinaryClassificationModel::Linear(model) => &model.feature_groups,
+			_ => unreachable!(),
+		},
+		_ => unreachable!(),
+	};
+	let feature_group = match feature_groups.as_slice() {
+		[FeatureGroup::BagOfWords(feature_group)] => feature_group,
+		_ => unreachable!(),
+	};
+	assert!(matches!(
+		feature_group.strategy,
+		BagOfWordsFeatureGroupStrategy::TfIdf
+	));
+	let column = TextTableColumn::new(Some("review".to_owned()), reviews.to_vec());
+	let table_features = feature_group.compute_table(TableColumnView::Text(column.view()), &|_| {});
+	let mut features = vec![Vec::new(); reviews.len()];
+	for table_feature in table_features.iter() {
+		let table_feature = table_feature.as_number().unwrap();
+		for (row, value) in features.iter_mut().zip(table_feature.iter()) {
+			row.push(*value);
+		}
+	}
+	features
+}
@@ -1,7 +1,15 @@
 use crate::{
+	checkpoint::{self, TrainCheckpoint},
 	config::{self, Config},
+	config_check::check_config,
+	correlations::{self, ColumnCorrelation},
+	feature_selection::{self, DroppedColumn},
 	grid,
-	heuristics::{MIN_COMPARISON_ROWS, MIN_TEST_ROWS, MIN_TRAIN_ROWS},
+	heuristics::{
+		LINEAR_SPARSE_FEATURES_MAX_DENSITY, MAX_SPLIT_GROUP_SIZE_RATIO, MIN_COMPARISON_ROWS,
+		MIN_TEST_ROWS, MIN_TRAIN_ROWS,
+	},
+	leakage_guard,
 	model::{
 		BinaryClassificationComparisonMetric, BinaryClassificationModel, BinaryClassifier,
 		ComparisonMetric, LinearBinaryClassificationModel, LinearMulticlassClassificationModel,
@@ -12,36 +20,150 @@ use crate::{
 	},
 	progress::{
 		LoadProgressEvent, ModelTestProgressEvent, ModelTrainProgressEvent, ProgressEvent,
-		StatsProgressEvent, TrainGridItemProgressEvent, TrainProgressEvent,
+		ProgressHandler, StatsProgressEvent, TrainGridItemProgressEvent, TrainProgressEvent,
 	},
 	stats::{ColumnStatsOutput, Stats, StatsSettings},
-	test,
+	table_cache, test,
 };
 use anyhow::{anyhow, bail, Result};
+use chrono_tz::Tz;
 use modelfox_id::Id;
 use modelfox_kill_chip::KillChip;
 use modelfox_progress_counter::ProgressCounter;
 use modelfox_table::prelude::*;
+use modelfox_zip::{zip, zip_eq};
 use ndarray::prelude::*;
 use num::ToPrimitive;
 use rand::{seq::SliceRandom, SeedableRng};
 use rand_xoshiro::Xoshiro256Plus;
 use std::{
-	collections::BTreeMap,
+	collections::{BTreeMap, HashMap},
 	io::Read,
-	path::Path,
+	path::{Path, PathBuf},
 	sync::Arc,
 	time::{Duration, Instant},
 	unreachable,
 };
 
+/// This is the data a model is trained on.
 pub enum TrainingDataSource {
+	/// Read a CSV from stdin.
 	Stdin,
-	File(std::path::PathBuf),
-	TrainAndTest {
-		train: std::path::PathBuf,
-		test: std::path::PathBuf,
-	},
+	/// Read a CSV that has already been loaded into memory, which is split into train, comparison, and test datasets.
+	Csv(Vec<u8>),
+	/// Read a CSV file, which is split into train, comparison, and test datasets.
+	File(PathBuf),
+	/// Read separate CSV files for the train and test datasets. The comparison dataset is split from the train dataset.
+	TrainAndTest { train: PathBuf, test: PathBuf },
+	/// Run a query against a Postgres or MySQL database. This requires the `database` feature.
+	Database { url: String, query: String },
+	/// Use a table that has already been loaded, which is split into train, comparison, and test datasets. The column types in `dataset.columns` in the config are ignored because the table's columns already have types.
+	Table(Table),
+}
+
+/**
+Use this builder to train a model from Rust. It is the same entry point the CLI uses.
+
+```no_run
+use modelfox_core::train::{TrainerBuilder, TrainingDataSource};
+
+let model = TrainerBuilder::new(TrainingDataSource::File("heart_disease.csv".into()), "diagnosis")
+	.train(&mut |_progress_event| {})?;
+model.to_path(std::path::Path::new("heart_disease.modelfox"))?;
+# Ok::<(), anyhow::Error>(())
+```
+*/
+pub struct TrainerBuilder {
+	id: Option<Id>,
+	input: TrainingDataSource,
+	target_column_name: String,
+	config: TrainerConfig,
+	cache_dir: Option<PathBuf>,
+	allow_leakage: bool,
+}
+
+enum TrainerConfig {
+	Default,
+	Config(Box<Config>),
+	Path(PathBuf),
+}
+
+impl TrainerBuilder {
+	pub fn new(input: TrainingDataSource, target_column_name: impl Into<String>) -> TrainerBuilder {
+		TrainerBuilder {
+			id: None,
+			input,
+			target_column_name: target_column_name.into(),
+			config: TrainerConfig::Default,
+			cache_dir: None,
+			allow_leakage: false,
+		}
+	}
+
+	/// Set the id of the model. If you do not call this, a random id is generated.
+	pub fn id(mut self, id: Id) -> TrainerBuilder {
+		self.id = Some(id);
+		self
+	}
+
+	/// Configure training with a [`Config`]. This replaces any config set with [`TrainerBuilder::config_path`].
+	pub fn config(mut self, config: Config) -> TrainerBuilder {
+		self.config = TrainerConfig::Config(Box::new(config));
+		self
+	}
+
+	/// Configure training with a `.json` or `.yaml` config file. This replaces any config set with [`TrainerBuilder::config`].
+	pub fn config_path(mut self, config_path: impl Into<PathBuf>) -> TrainerBuilder {
+		self.config = TrainerConfig::Path(config_path.into());
+		self
+	}
+
+	/// Cache the tables parsed from csv files in this directory, so training again on the same files skips parsing them. A cached table is used only if the file and the column types and invalid value policies in the config are unchanged.
+	pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> TrainerBuilder {
+		self.cache_dir = Some(cache_dir.into());
+		self
+	}
+
+	/// Train even if the leakage guard finds columns that predict the target on their own. Each of these columns is reported with a warning instead of an error.
+	pub fn allow_leakage(mut self, allow_leakage: bool) -> TrainerBuilder {
+		self.allow_leakage = allow_leakage;
+		self
+	}
+
+	/// Load the dataset, compute stats, and create the hyperparameter grid. Use this instead of [`TrainerBuilder::train`] to control each step of training with the returned [`Trainer`].
+	pub fn prepare(self, progress_handler: &mut impl ProgressHandler) -> Result<Trainer> {
+		let config = match self.config {
+			TrainerConfig::Default => Config::default(),
+			TrainerConfig::Config(config) => {
+				validate_config(&config)?;
+				*config
+			}
+			TrainerConfig::Path(config_path) => {
+				load_config(Some(&config_path), &mut |progress_event| {
+					progress_handler.handle_progress_event(progress_event)
+				})?
+			}
+		};
+		Trainer::prepare_with_config(
+			self.id.unwrap_or_else(Id::generate),
+			self.input,
+			&self.target_column_name,
+			config,
+			self.cache_dir.as_deref(),
+			self.allow_leakage,
+			&mut |progress_event| progress_handler.handle_progress_event(progress_event),
+		)
+	}
+
+	/// Train every model in the grid, then test and return the best one.
+	pub fn train(self, progress_handler: &mut impl ProgressHandler) -> Result<Model> {
+		let mut trainer = self.prepare(progress_handler)?;
+		let mut handle_progress_event =
+			|progress_event| progress_handler.handle_progress_event(progress_event);
+		let train_grid_item_outputs =
+			trainer.train_grid(&KillChip::default(), None, &mut handle_progress_event)?;
+		trainer.test_and_assemble_model(train_grid_item_outputs, &mut handle_progress_event)
+	}
 }
 
 pub struct Trainer {
@@ -55,10 +177,16 @@ pub struct Trainer {
 	overall_target_column_stats: ColumnStatsOutput,
 	train_column_stats: Vec<ColumnStatsOutput>,
 	train_target_column_stats: ColumnStatsOutput,
+	dropped_columns: Vec<DroppedColumn>,
+	column_correlations: Vec<ColumnCorrelation>,
+	invalid_value_policies: BTreeMap<String, modelfox_table::InvalidValuePolicy>,
 	test_column_stats: Vec<ColumnStatsOutput>,
 	test_target_column_stats: ColumnStatsOutput,
 	baseline_metrics: Metrics,
 	comparison_metric: ComparisonMetric,
+	tolerance: Option<modelfox_metrics::Tolerance>,
+	n_comparison_folds: usize,
+	retrain_best_model: bool,
 	dataset: Arc<Dataset>,
 	grid: Vec<grid::GridItem>,
 	task: Task,
@@ -73,19 +201,59 @@ impl Trainer {
 		handle_progress_event: &mut dyn FnMut(ProgressEvent),
 	) -> Result<Trainer> {
 		// Load the config from the config file, if provided.
-		let config = load_config(config_path)?;
+		let config = load_config(config_path, handle_progress_event)?;
+		Trainer::prepare_with_config(
+			id,
+			input,
+			target_column_name,
+			config,
+			None,
+			false,
+			handle_progress_event,
+		)
+	}
+
+	fn prepare_with_config(
+		id: Id,
+		input: TrainingDataSource,
+		target_column_name: &str,
+		mut config: Config,
+		cache_dir: Option<&Path>,
+		allow_leakage: bool,
+		handle_progress_event: &mut dyn FnMut(ProgressEvent),
+	) -> Result<Trainer> {
+		// Without cross validation, each grid item is evaluated on a single comparison dataset.
+		let n_comparison_folds = match &config.dataset.cross_validation {
+			Some(config::CrossValidation::Rolling(cross_validation)) => {
+				if cross_validation.folds == 0 {
+					bail!("Rolling cross validation must have at least one fold.");
+				}
+				if cross_validation.horizon == Some(0) {
+					bail!("The rolling cross validation horizon must be at least one row.");
+				}
+				cross_validation.folds
+			}
+			None => 1,
+		};
 
 		// Load the train and test tables from the csv file(s).
-		let dataset = match input {
+		let mut dataset = match input {
 			TrainingDataSource::Stdin => Dataset::Train(load_and_shuffle_dataset_stdin(
 				&config,
 				target_column_name,
 				handle_progress_event,
 			)?),
+			TrainingDataSource::Csv(bytes) => Dataset::Train(load_and_shuffle_dataset_bytes(
+				&bytes,
+				&config,
+				target_column_name,
+				handle_progress_event,
+			)?),
 			TrainingDataSource::File(file_path) => Dataset::Train(load_and_shuffle_dataset_train(
 				&file_path,
 				&config,
 				target_column_name,
+				cache_dir,
 				handle_progress_event,
 			)?),
 			TrainingDataSource::TrainAndTest { train, test } => {
@@ -94,10 +262,53 @@ impl Trainer {
 					&test,
 					&config,
 					target_column_name,
+					cache_dir,
 					handle_progress_event,
 				)?)
 			}
+			TrainingDataSource::Database { url, query } => {
+				Dataset::Train(load_and_shuffle_dataset_database(
+					&url,
+					&query,
+					&config,
+					target_column_name,
+					handle_progress_event,
+				)?)
+			}
+			TrainingDataSource::Table(table) => Dataset::Train(shuffle_dataset_table(
+				table,
+				&config,
+				target_column_name,
+				handle_progress_event,
+			)?),
 		};
+
+		// Remove the ignored columns. They are removed after loading so they can still be used as the split group column or the date column.
+		if !config.dataset.ignore_columns.is_empty() {
+			let (table_train, _, _) = dataset.split();
+			for column_name in config.dataset.ignore_columns.iter() {
+				if column_name == target_column_name {
+					bail!("the target column \"{}\" cannot be ignored", column_name);
+				}
+				if !table_train
+					.columns()
+					.iter()
+					.any(|column| column.name() == Some(column_name.as_str()))
+				{
+					bail!(
+						"did not find ignored column \"{}\" among column names \"{}\"",
+						column_name,
+						table_train
+							.columns()
+							.iter()
+							.map(|column| column.name().unwrap())
+							.collect::<Vec<_>>()
+							.join(", ")
+					);
+				}
+			}
+			dataset.remove_columns(&config.dataset.ignore_columns);
+		}
 		let (table_train, table_comparison, table_test) = dataset.split();
 
 		// Do not allow training if any dataset has no rows, or emit warnings if any dataset is too small.
@@ -109,6 +320,25 @@ impl Trainer {
 				table_train.nrows(),
 			)));
 		}
+		if n_comparison_folds * table_comparison.nrows()
+			>= table_train.nrows() + table_comparison.nrows()
+		{
+			bail!(
+				"Rolling cross validation with {} folds of {} row(s) requires more than the {} row(s) preceding the test dataset.",
+				n_comparison_folds,
+				table_comparison.nrows(),
+				table_train.nrows() + table_comparison.nrows(),
+			);
+		}
+		// Splitting between groups of rows can leave a rolling cross validation window, or the rows preceding it, empty.
+		if n_comparison_folds > 1
+			&& dataset.split_folds(n_comparison_folds).iter().any(
+				|(table_train, table_comparison)| {
+					table_train.nrows() == 0 || table_comparison.nrows() == 0
+				},
+			) {
+			bail!("Every rolling cross validation window, and the rows preceding it, must contain at least one row. Use fewer folds or smaller groups.");
+		}
 		if table_comparison.nrows() == 0 {
 			bail!("The comparison dataset must contain at least one row.");
 		} else if table_comparison.nrows() < MIN_COMPARISON_ROWS {
@@ -139,7 +369,7 @@ impl Trainer {
 		let overall_row_count = train_row_count + test_row_count + table_comparison.nrows();
 
 		// Compute stats.
-		let stats_settings = StatsSettings::default();
+		let stats_settings = stats_settings_from_config(&config);
 		let train_column_stats = Stats::compute(&table_train, &stats_settings, &mut |progress| {
 			handle_progress_event(ProgressEvent::Stats(StatsProgressEvent::ComputeTrainStats(
 				progress,
@@ -163,6 +393,9 @@ impl Trainer {
 		let mut overall_column_stats = overall_column_stats.finalize(&stats_settings).0;
 		handle_progress_event(ProgressEvent::Stats(StatsProgressEvent::FinalizeDone));
 
+		// Compute the correlations between the columns, including the target column, so columns that leak the target can be found.
+		let column_correlations = correlations::compute_column_correlations(&table_train);
+
 		// Find the target column.
 		let target_column_index = column_names
 			.iter()
@@ -175,6 +408,25 @@ impl Trainer {
 				)
 			})?;
 
+		// Check that the interaction constraints refer to feature columns.
+		if let Some(interaction_constraints) = config.train.interaction_constraints.as_ref() {
+			for column_name in interaction_constraints.iter().flatten() {
+				if *column_name == target_column_name {
+					bail!(
+						"the target column \"{}\" cannot be used in interaction constraints",
+						column_name
+					);
+				}
+				if !column_names.iter().any(|c| c == column_name) {
+					bail!(
+						"did not find interaction constraint column \"{}\" among column names \"{}\"",
+						column_name,
+						column_names.join(", ")
+					);
+				}
+			}
+		}
+
 		// Pull out the target column from the column stats.
 		let train_target_column_stats = train_column_stats.remove(target_column_index);
 		let test_target_column_stats = test_column_stats.remove(target_column_index);
@@ -190,6 +442,36 @@ impl Trainer {
 			_ => bail!("invalid target column type"),
 		};
 
+		// Screen each column on its own for leaking the target.
+		if config.dataset.leakage_guard.enable {
+			let leakage_guard_trips = leakage_guard::screen_columns(
+				&table_train,
+				target_column_index,
+				task,
+				&config.dataset.leakage_guard,
+			);
+			if allow_leakage {
+				for leakage_guard_trip in leakage_guard_trips.iter() {
+					handle_progress_event(ProgressEvent::Warning(format!(
+						"Possible target leakage: {}.",
+						leakage_guard_trip
+					)));
+				}
+			} else if !leakage_guard_trips.is_empty() {
+				bail!(
+					"possible target leakage: {}. If these columns are not known at prediction time, add them to dataset.ignore_columns in the config. Otherwise, pass --allow-leakage to train anyway.",
+					leakage_guard_trips
+						.iter()
+						.map(|leakage_guard_trip| leakage_guard_trip.to_string())
+						.collect::<Vec<_>>()
+						.join("; ")
+				);
+			}
+		}
+
+		// Determine the tolerance for the tolerance accuracy metric.
+		let tolerance = choose_tolerance(&config, &task)?;
+
 		// Compute the baseline metrics.
 		let progress_counter = ProgressCounter::new(test_row_count as u64);
 		handle_progress_event(ProgressEvent::ComputeBaselineMetrics(
@@ -201,6 +483,7 @@ impl Trainer {
 			target_column_index,
 			&train_target_column_stats,
 			&test_target_column_stats,
+			tolerance,
 			&|| progress_counter.inc(1),
 		);
 		handle_progress_event(ProgressEvent::ComputeBaselineMetricsDone);
@@ -208,9 +491,36 @@ impl Trainer {
 		// Choose the comparison metric.
 		let comparison_metric = choose_comparison_metric(&config, &task)?;
 
+		// Drop uninformative columns by excluding them from automatic feature engineering.
+		let dropped_columns = match config.features.selection.as_ref() {
+			Some(selection) if selection.enable => {
+				feature_selection::select_columns(&table_train, &train_column_stats, selection)
+			}
+			_ => Vec::new(),
+		};
+		for dropped_column in dropped_columns.iter() {
+			handle_progress_event(ProgressEvent::Warning(format!(
+				"Feature selection dropped the column \"{}\": {}.",
+				dropped_column.column_name, dropped_column.reason,
+			)));
+		}
+		if !dropped_columns.is_empty() {
+			config
+				.features
+				.auto
+				.exclude_columns
+				.get_or_insert_with(Vec::new)
+				.extend(
+					dropped_columns
+						.iter()
+						.map(|dropped_column| dropped_column.column_name.clone()),
+				);
+		}
+
 		// Create the hyperparameter grid.
 		let grid =
 			compute_hyperparameter_grid(&config, &task, target_column_index, &train_column_stats);
+		let invalid_value_policies = invalid_value_policies_from_config(&config);
 
 		let trainer = Trainer {
 			id,
@@ -223,10 +533,16 @@ impl Trainer {
 			overall_target_column_stats,
 			train_column_stats,
 			train_target_column_stats,
+			dropped_columns,
+			column_correlations,
+			invalid_value_policies,
 			test_column_stats,
 			test_target_column_stats,
 			baseline_metrics,
 			comparison_metric,
+			tolerance,
+			n_comparison_folds,
+			retrain_best_model: config.train.retrain_best_model,
 			dataset: Arc::new(dataset),
 			grid,
 			task,
@@ -234,36 +550,139 @@ impl Trainer {
 		Ok(trainer)
 	}
 
-	/// Train each model in the grid and compute comparison metrics.
+	/// Train each model in the grid and compute comparison metrics. If `checkpoint_path` is provided, a checkpoint is written to it after each grid item finishes, and if a checkpoint already exists there, training resumes from it. See [`crate::checkpoint`].
 	pub fn train_grid(
 		&mut self,
 		kill_chip: &KillChip,
+		checkpoint_path: Option<&Path>,
 		handle_progress_event: &mut dyn FnMut(ProgressEvent),
 	) -> Result<Vec<TrainGridItemOutput>> {
-		let (table_train, table_comparison, _) = self.dataset.split();
+		let folds = self.dataset.split_folds(self.n_comparison_folds);
 		let grid = &self.grid;
 		let comparison_metric = self.comparison_metric;
-		let train_grid_item_outputs = grid
-			.iter()
-			.cloned()
-			.enumerate()
-			.take_while(|_| !kill_chip.is_activated())
-			.map(|(grid_item_index, grid_item)| {
-				train_grid_item(
-					grid.len(),
-					grid_item_index,
-					grid_item,
-					&table_train,
-					&table_comparison,
-					comparison_metric,
-					kill_chip,
-					handle_progress_event,
-				)
-			})
-			.collect();
+		let mut checkpoint = match checkpoint_path {
+			Some(checkpoint_path) => {
+				let fingerprint = checkpoint::fingerprint(&(
+					&self.target_column_name,
+					self.train_row_count,
+					self.n_comparison_folds,
+					grid,
+				));
+				Some(TrainCheckpoint::read_or_new(checkpoint_path, fingerprint)?)
+			}
+			None => None,
+		};
+		// The best grid item finished in a previous run is trained again because the checkpoint does not store models.
+		let best_checkpointed_grid_item_index = checkpoint.as_ref().and_then(|checkpoint| {
+			checkpoint.best_grid_item_index(comparison_metric_higher_is_better(comparison_metric))
+		});
+		let mut train_grid_item_outputs = Vec::with_capacity(grid.len());
+		for (grid_item_index, grid_item) in grid.iter().cloned().enumerate() {
+			if kill_chip.is_activated() {
+				break;
+			}
+			let checkpointed_grid_item = checkpoint
+				.as_ref()
+				.and_then(|checkpoint| checkpoint.get(grid_item_index));
+			if let Some(checkpointed_grid_item) = checkpointed_grid_item {
+				if Some(grid_item_index) != best_checkpointed_grid_item_index {
+					handle_progress_event(ProgressEvent::Info(format!(
+						"Restored model {} from the checkpoint.",
+						grid_item_index + 1,
+					)));
+					train_grid_item_outputs.push(TrainGridItemOutput {
+						train_model_output: None,
+						train_options: grid_item_train_options(&grid_item),
+						comparison_metrics: None,
+						comparison_metric_value: checkpointed_grid_item.comparison_metric_value,
+						duration: Duration::from_secs_f64(checkpointed_grid_item.duration),
+					});
+					continue;
+				}
+			}
+			let train_grid_item_output = train_grid_item(
+				grid.len(),
+				grid_item_index,
+				grid_item,
+				&folds,
+				comparison_metric,
+				kill_chip,
+				handle_progress_event,
+			)?;
+			// A grid item that was interrupted did not finish training, so it is not checkpointed and will be trained again when resuming.
+			if let (Some(checkpoint), Some(checkpoint_path)) =
+				(checkpoint.as_mut(), checkpoint_path)
+			{
+				if !kill_chip.is_activated() {
+					checkpoint.record(
+						grid_item_index,
+						train_grid_item_output.comparison_metric_value,
+						train_grid_item_output.duration,
+					);
+					checkpoint.write(checkpoint_path)?;
+				}
+			}
+			train_grid_item_outputs.push(train_grid_item_output);
+		}
+		if self.retrain_best_model && !kill_chip.is_activated() {
+			self.retrain_best_grid_item(
+				&mut train_grid_item_outputs,
+				kill_chip,
+				handle_progress_event,
+			)?;
+		}
 		Ok(train_grid_item_outputs)
 	}
 
+	/// Train the best grid item again on the train and comparison datasets together, and replace its model with the new one. Its comparison metrics are kept, because the new model has no held out rows to compute them on.
+	fn retrain_best_grid_item(
+		&self,
+		train_grid_item_outputs: &mut [TrainGridItemOutput],
+		kill_chip: &KillChip,
+		handle_progress_event: &mut dyn FnMut(ProgressEvent),
+	) -> Result<()> {
+		let (best_train_model_output, best_grid_item_index) =
+			choose_best_model(train_grid_item_outputs, &self.comparison_metric)?;
+		let table_train = self.dataset.table_train_and_comparison();
+		handle_progress_event(ProgressEvent::Info(format!(
+			"Retraining model {} on the train and comparison datasets, {} rows.",
+			best_grid_item_index + 1,
+			table_train.nrows(),
+		)));
+		let grid_item_count = self.grid.len();
+		let start = Instant::now();
+		let mut train_model_output = train_model(
+			self.grid[best_grid_item_index].clone(),
+			&table_train,
+			kill_chip,
+			&mut |progress| {
+				handle_progress_event(ProgressEvent::Train(TrainProgressEvent {
+					grid_item_index: best_grid_item_index,
+					grid_item_count,
+					grid_item_progress_event: progress,
+				}))
+			},
+		);
+		// A model whose retraining was interrupted stopped early, so keep the original.
+		if kill_chip.is_activated() {
+			return Ok(());
+		}
+		// Keep the mean and standard deviation of the feature importances over the cross validation folds.
+		if let Some(feature_importances_std) = best_train_model_output.feature_importances_std() {
+			let feature_importances = best_train_model_output
+				.named_feature_importances()?
+				.into_iter()
+				.map(|(_, feature_importance)| feature_importance)
+				.collect();
+			train_model_output
+				.set_feature_importances(feature_importances, feature_importances_std.to_owned());
+		}
+		let train_grid_item_output = &mut train_grid_item_outputs[best_grid_item_index];
+		train_grid_item_output.train_model_output = Some(train_model_output);
+		train_grid_item_output.duration += start.elapsed();
+		Ok(())
+	}
+
 	pub fn test_and_assemble_model(
 		self,
 		train_grid_item_outputs: Vec<TrainGridItemOutput>,
@@ -280,10 +699,14 @@ impl Trainer {
 			overall_target_column_stats,
 			train_column_stats,
 			train_target_column_stats,
+			dropped_columns,
+			column_correlations,
+			invalid_value_policies,
 			test_column_stats,
 			test_target_column_stats,
 			baseline_metrics,
 			comparison_metric,
+			tolerance,
 			task,
 			dataset,
 			..
@@ -308,9 +731,12 @@ impl Trainer {
 			train_grid_item_outputs[best_grid_item_index].comparison_metric_value;
 
 		// Test the best model.
-		let test_metrics = test_model(&train_model_output, &table_test, &mut |progress_event| {
-			handle_progress_event(ProgressEvent::Test(progress_event))
-		});
+		let test_metrics = test_model(
+			&train_model_output,
+			&table_test,
+			tolerance,
+			&mut |progress_event| handle_progress_event(ProgressEvent::Test(progress_event)),
+		);
 
 		handle_progress_event(ProgressEvent::Finalize);
 		// Assemble the model.
@@ -335,6 +761,7 @@ impl Trainer {
 						train_options,
 						losses,
 						feature_importances,
+						feature_importances_std,
 						..
 					}) => RegressionModel::Linear(LinearRegressionModel {
 						model,
@@ -342,6 +769,7 @@ impl Trainer {
 						feature_groups,
 						losses,
 						feature_importances,
+						feature_importances_std,
 					}),
 					TrainModelOutput::TreeRegressor(TreeRegressorTrainModelOutput {
 						model,
@@ -349,6 +777,8 @@ impl Trainer {
 						train_options,
 						losses,
 						feature_importances,
+						feature_importances_std,
+						bin_thresholds,
 						..
 					}) => RegressionModel::Tree(TreeRegressionModel {
 						model,
@@ -356,6 +786,8 @@ impl Trainer {
 						feature_groups,
 						losses,
 						feature_importances,
+						feature_importances_std,
+						bin_thresholds,
 					}),
 					_ => unreachable!(),
 				};
@@ -369,6 +801,9 @@ impl Trainer {
 					overall_target_column_stats,
 					train_column_stats,
 					train_target_column_stats,
+					dropped_columns,
+					column_correlations,
+					invalid_value_policies,
 					test_column_stats,
 					test_target_column_stats,
 					baseline_metrics,
@@ -377,6 +812,7 @@ impl Trainer {
 					best_grid_item_index,
 					model,
 					test_metrics,
+					tolerance,
 				})
 			}
 			Task::BinaryClassification => {
@@ -400,6 +836,7 @@ impl Trainer {
 							losses,
 							train_options,
 							feature_importances,
+							feature_importances_std,
 							..
 						},
 					) => BinaryClassificationModel::Linear(LinearBinaryClassificationModel {
@@ -408,6 +845,7 @@ impl Trainer {
 						feature_groups,
 						losses,
 						feature_importances,
+						feature_importances_std,
 					}),
 					TrainModelOutput::TreeBinaryClassifier(
 						TreeBinaryClassifierTrainModelOutput {
@@ -416,6 +854,8 @@ impl Trainer {
 							losses,
 							train_options,
 							feature_importances,
+							feature_importances_std,
+							bin_thresholds,
 							..
 						},
 					) => BinaryClassificationModel::Tree(TreeBinaryClassificationModel {
@@ -424,6 +864,8 @@ impl Trainer {
 						feature_groups,
 						losses,
 						feature_importances,
+						feature_importances_std,
+						bin_thresholds,
 					}),
 					_ => unreachable!(),
 				};
@@ -446,6 +888,9 @@ impl Trainer {
 					overall_target_column_stats,
 					train_column_stats,
 					train_target_column_stats,
+					dropped_columns,
+					column_correlations,
+					invalid_value_policies,
 					test_column_stats,
 					test_target_column_stats,
 					baseline_metrics,
@@ -479,6 +924,7 @@ impl Trainer {
 							train_options,
 							losses,
 							feature_importances,
+							feature_importances_std,
 							..
 						},
 					) => {
@@ -488,6 +934,7 @@ impl Trainer {
 							feature_groups,
 							losses,
 							feature_importances,
+							feature_importances_std,
 						})
 					}
 					TrainModelOutput::TreeMulticlassClassifier(
@@ -497,6 +944,8 @@ impl Trainer {
 							train_options,
 							losses,
 							feature_importances,
+							feature_importances_std,
+							bin_thresholds,
 							..
 						},
 					) => MulticlassClassificationModel::Tree(TreeMulticlassClassificationModel {
@@ -505,6 +954,8 @@ impl Trainer {
 						feature_groups,
 						losses,
 						feature_importances,
+						feature_importances_std,
+						bin_thresholds,
 					}),
 					_ => unreachable!(),
 				};
@@ -527,6 +978,9 @@ impl Trainer {
 					overall_target_column_stats,
 					train_column_stats,
 					train_target_column_stats,
+					dropped_columns,
+					column_correlations,
+					invalid_value_policies,
 					test_column_stats,
 					test_target_column_stats,
 					baseline_metrics,
@@ -553,6 +1007,10 @@ impl Trainer {
 		let comparison_metric_str = match comparison_metric {
 			ComparisonMetric::BinaryClassification(bcm) => match bcm {
 				BinaryClassificationComparisonMetric::AucRoc => "AUC ROC",
+				BinaryClassificationComparisonMetric::F1 => "F1",
+				BinaryClassificationComparisonMetric::Precision => "Precision",
+				BinaryClassificationComparisonMetric::Recall => "Recall",
+				BinaryClassificationComparisonMetric::Accuracy => "Accuracy",
 			},
 			ComparisonMetric::MulticlassClassification(mccm) => match mccm {
 				MulticlassClassificationComparisonMetric::Accuracy => "Accuracy",
@@ -577,19 +1035,145 @@ impl Trainer {
 	}
 }
 
-fn load_config(config_path: Option<&Path>) -> Result<Config> {
-	if let Some(config_path) = config_path {
-		let config = std::fs::read_to_string(config_path)?;
-		let extension = config_path.extension().and_then(|s| s.to_str());
-		let config = match extension {
-			Some("json") => serde_json::from_str(&config)?,
-			Some("yaml") => serde_yaml::from_str(&config)?,
-			_ => bail!("the config path must have either .json or .yaml as its extension."),
-		};
-		Ok(config)
-	} else {
-		Ok(Config::default())
+/// Load the config from the config file, if provided. Any error in the config stops training with its location in the file, and unknown keys are reported as warnings.
+pub(crate) fn load_config(
+	config_path: Option<&Path>,
+	handle_progress_event: &mut dyn FnMut(ProgressEvent),
+) -> Result<Config> {
+	let config_path = match config_path {
+		Some(config_path) => config_path,
+		None => return Ok(Config::default()),
+	};
+	let output = check_config(config_path)?;
+	if let Some(error) = output.errors().next() {
+		bail!("{}: {}", config_path.display(), error);
+	}
+	for warning in output.warnings() {
+		handle_progress_event(ProgressEvent::Warning(format!(
+			"{}: {}",
+			config_path.display(),
+			warning
+		)));
+	}
+	Ok(output.config.unwrap())
+}
+
+pub(crate) fn validate_config(config: &Config) -> Result<()> {
+	validate_config_fractions(config)?;
+	validate_config_timezones(config)?;
+	validate_config_stats(config)?;
+	validate_config_feature_groups(config)?;
+	validate_config_leakage_guard(config)?;
+	Ok(())
+}
+
+/// Check that the comparison and test fractions leave rows for training. Whether the comparison and test datasets are empty depends on the number of rows, so that is checked once the dataset is loaded.
+fn validate_config_fractions(config: &Config) -> Result<()> {
+	let comparison_fraction = config.dataset.comparison_fraction;
+	let test_fraction = config.dataset.test_fraction;
+	if !(0.0..1.0).contains(&comparison_fraction) {
+		bail!(
+			"the comparison fraction {} must be at least 0 and less than 1",
+			comparison_fraction
+		);
+	}
+	if !(0.0..1.0).contains(&test_fraction) {
+		bail!(
+			"the test fraction {} must be at least 0 and less than 1",
+			test_fraction
+		);
+	}
+	if comparison_fraction + test_fraction >= 1.0 {
+		bail!(
+			"the comparison fraction {} and the test fraction {} must add up to less than 1",
+			comparison_fraction,
+			test_fraction
+		);
+	}
+	Ok(())
+}
+
+/// Check that the timezone of every datetime column in the config is a valid timezone name, so that `column_types_from_config` can parse them.
+fn validate_config_timezones(config: &Config) -> Result<()> {
+	for column in config.dataset.columns.iter() {
+		if let config::Column::DateTime(column) = column {
+			if let Some(timezone) = &column.timezone {
+				if timezone.parse::<Tz>().is_err() {
+					bail!(
+						"the datetime column \"{}\" has an invalid timezone \"{}\"",
+						column.name,
+						timezone
+					);
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Check that the stats settings in the config are usable.
+fn validate_config_stats(config: &Config) -> Result<()> {
+	if config.stats.datetime_histogram_bin_count == 0 {
+		bail!("the datetime histogram bin count must be greater than zero");
+	}
+	for quantile in config.stats.number_quantiles.iter() {
+		if !(0.0..=1.0).contains(quantile) {
+			bail!("the number quantile {} must be between 0 and 1", quantile);
+		}
+	}
+	Ok(())
+}
+
+/// Check that the leakage guard thresholds are in the range of the values they are compared with.
+fn validate_config_leakage_guard(config: &Config) -> Result<()> {
+	let leakage_guard = &config.dataset.leakage_guard;
+	if !(0.5..=1.0).contains(&leakage_guard.max_auc) {
+		bail!(
+			"the leakage guard max auc {} must be between 0.5 and 1",
+			leakage_guard.max_auc
+		);
+	}
+	if !(0.0..=1.0).contains(&leakage_guard.max_correlation) {
+		bail!(
+			"the leakage guard max correlation {} must be between 0 and 1",
+			leakage_guard.max_correlation
+		);
 	}
+	Ok(())
+}
+
+/// Check that the settings of the feature groups in the config are usable.
+fn validate_config_feature_groups(config: &Config) -> Result<()> {
+	for feature_group in config.features.include.as_deref().unwrap_or_default() {
+		match feature_group {
+			config::FeatureGroup::Cyclical(feature_group) => {
+				if !(feature_group.period.is_finite() && feature_group.period > 0.0) {
+					bail!(
+						"the cyclical feature group for column \"{}\" must have a positive period",
+						feature_group.source_column_name
+					);
+				}
+			}
+			config::FeatureGroup::BagOfWords(feature_group) => {
+				let document_frequencies = [
+					&feature_group.min_document_frequency,
+					&feature_group.max_document_frequency,
+				];
+				for document_frequency in document_frequencies.into_iter().flatten() {
+					if let config::DocumentFrequency::Fraction(fraction) = document_frequency {
+						if !(0.0..=1.0).contains(fraction) {
+							bail!(
+								"the document frequency fractions for the bag of words feature group for column \"{}\" must be between 0 and 1",
+								feature_group.source_column_name
+							);
+						}
+					}
+				}
+			}
+			_ => {}
+		}
+	}
+	Ok(())
 }
 
 enum Dataset {
@@ -600,6 +1184,9 @@ enum Dataset {
 struct DatasetTrain {
 	table: Table,
 	comparison_fraction: f32,
+	comparison_horizon: Option<usize>,
+	/// If `split_group_column` is set, these are the indexes of the rows that start each group, followed by the number of rows. The datasets are only split at these rows.
+	group_boundaries: Option<Vec<usize>>,
 	test_fraction: f32,
 }
 
@@ -607,6 +1194,8 @@ struct DatasetTrainAndTest {
 	table_train: Table,
 	table_test: Table,
 	comparison_fraction: f32,
+	comparison_horizon: Option<usize>,
+	group_boundaries: Option<Vec<usize>>,
 }
 
 impl Dataset {
@@ -615,38 +1204,117 @@ impl Dataset {
 			Dataset::Train(DatasetTrain {
 				table,
 				comparison_fraction,
+				comparison_horizon,
+				group_boundaries,
 				test_fraction,
 			}) => {
 				let n_rows_test = (test_fraction * table.nrows().to_f32().unwrap())
 					.floor()
 					.to_usize()
 					.unwrap();
-				let n_rows_comparison = (comparison_fraction * table.nrows().to_f32().unwrap())
-					.floor()
-					.to_usize()
-					.unwrap();
-				let n_rows_train = table.nrows() - n_rows_test - n_rows_comparison;
-				let (table_train, table_rest) = table.view().split_at_row(n_rows_train);
-				let (table_comparison, table_test) = table_rest.split_at_row(n_rows_comparison);
+				let n_rows_comparison = comparison_horizon.unwrap_or_else(|| {
+					(comparison_fraction * table.nrows().to_f32().unwrap())
+						.floor()
+						.to_usize()
+						.unwrap()
+				});
+				let group_boundaries = group_boundaries.as_deref();
+				let test_start = snap_to_group_boundary(
+					table.nrows().saturating_sub(n_rows_test),
+					group_boundaries,
+				);
+				let comparison_start = snap_to_group_boundary(
+					test_start.saturating_sub(n_rows_comparison),
+					group_boundaries,
+				);
+				let (table_train, table_rest) = table.view().split_at_row(comparison_start);
+				let (table_comparison, table_test) =
+					table_rest.split_at_row(test_start - comparison_start);
 				(table_train, table_comparison, table_test)
 			}
 			Dataset::TrainAndTest(DatasetTrainAndTest {
 				table_train,
 				table_test,
 				comparison_fraction,
+				comparison_horizon,
+				group_boundaries,
 			}) => {
-				let n_rows_comparison = (comparison_fraction
-					* table_train.nrows().to_f32().unwrap())
-				.floor()
-				.to_usize()
-				.unwrap();
-				let n_rows_train = table_train.nrows() - n_rows_comparison;
+				let n_rows_comparison = comparison_horizon.unwrap_or_else(|| {
+					(comparison_fraction * table_train.nrows().to_f32().unwrap())
+						.floor()
+						.to_usize()
+						.unwrap()
+				});
+				let n_rows_train = snap_to_group_boundary(
+					table_train.nrows().saturating_sub(n_rows_comparison),
+					group_boundaries.as_deref(),
+				);
 				let (table_train, table_comparison) = table_train.view().split_at_row(n_rows_train);
 				let table_test = table_test.view();
 				(table_train, table_comparison, table_test)
 			}
 		}
 	}
+
+	/// Get the rows of the train and comparison datasets together, which are all the rows preceding the test dataset.
+	fn table_train_and_comparison(&self) -> TableView {
+		let (table_train, table_comparison, _) = self.split();
+		let n_rows = table_train.nrows() + table_comparison.nrows();
+		match self {
+			Dataset::Train(DatasetTrain { table, .. }) => table.view().split_at_row(n_rows).0,
+			Dataset::TrainAndTest(DatasetTrainAndTest { table_train, .. }) => table_train.view(),
+		}
+	}
+
+	/// Split the rows preceding the test dataset into `n_folds` pairs of train and comparison tables. Each comparison table immediately follows the rows of its train table, and the last pair is the train and comparison tables returned by `split`. If rows are grouped, the folds are split between groups, so their comparison tables may differ in size.
+	fn split_folds(&self, n_folds: usize) -> Vec<(TableView, TableView)> {
+		let (_, table_comparison, _) = self.split();
+		let n_rows_comparison = table_comparison.nrows();
+		let table = self.table_train_and_comparison();
+		let n_rows = table.nrows();
+		let group_boundaries = match self {
+			Dataset::Train(DatasetTrain {
+				group_boundaries, ..
+			}) => group_boundaries,
+			Dataset::TrainAndTest(DatasetTrainAndTest {
+				group_boundaries, ..
+			}) => group_boundaries,
+		};
+		let fold_start = |fold_index: usize| {
+			snap_to_group_boundary(
+				n_rows - (n_folds - fold_index) * n_rows_comparison,
+				group_boundaries.as_deref(),
+			)
+		};
+		(0..n_folds)
+			.map(|fold_index| {
+				let n_rows_train = fold_start(fold_index);
+				let n_rows_fold_comparison = fold_start(fold_index + 1) - n_rows_train;
+				let (table_train, table_rest) = table.split_at_row(n_rows_train);
+				let (table_comparison, _) = table_rest.split_at_row(n_rows_fold_comparison);
+				(table_train, table_comparison)
+			})
+			.collect()
+	}
+
+	/// Remove the columns with these names from every table in the dataset.
+	fn remove_columns(&mut self, column_names: &[String]) {
+		let tables = match self {
+			Dataset::Train(DatasetTrain { table, .. }) => vec![table],
+			Dataset::TrainAndTest(DatasetTrainAndTest {
+				table_train,
+				table_test,
+				..
+			}) => vec![table_train, table_test],
+		};
+		for table in tables {
+			table.columns_mut().retain(|column| {
+				!column_names
+					.iter()
+					.any(|column_name| Some(column_name.as_str()) == column.name())
+			});
+		}
+	}
 }
 
 fn drop_invalid_target_rows(
@@ -702,12 +1370,22 @@ fn load_and_shuffle_dataset_stdin(
 	let mut stdin = std::io::stdin();
 	let mut buf = Vec::new();
 	stdin.read_to_end(&mut buf)?;
+	load_and_shuffle_dataset_bytes(&buf, config, target_column_name, handle_progress_event)
+}
+
+fn load_and_shuffle_dataset_bytes(
+	bytes: &[u8],
+	config: &Config,
+	target_column_name: &str,
+	handle_progress_event: &mut dyn FnMut(ProgressEvent),
+) -> Result<DatasetTrain> {
 	// Get the column types from the config, if set.
 	let mut table = Table::from_bytes(
-		&buf,
+		bytes,
 		modelfox_table::FromCsvOptions {
 			column_types: column_types_from_config(config),
 			infer_options: Default::default(),
+			invalid_value_policies: invalid_value_policies_from_config(config),
 			..Default::default()
 		},
 		&mut |progress_event| {
@@ -718,12 +1396,14 @@ fn load_and_shuffle_dataset_stdin(
 	)?;
 	// Drop any rows with invalid data in the target column
 	drop_invalid_target_rows(&mut table, target_column_name, handle_progress_event);
-	// Shuffle the table if enabled.
-	shuffle_table(&mut table, config, handle_progress_event);
+	// Order the table by date for rolling cross validation, or shuffle it if enabled.
+	let group_boundaries = arrange_table(&mut table, config, handle_progress_event)?;
 	// Split the table into train and test tables.
 	Ok(DatasetTrain {
 		table,
 		comparison_fraction: config.dataset.comparison_fraction,
+		comparison_horizon: comparison_horizon_from_config(config),
+		group_boundaries,
 		test_fraction: config.dataset.test_fraction,
 	})
 }
@@ -732,16 +1412,52 @@ fn load_and_shuffle_dataset_train(
 	file_path: &Path,
 	config: &Config,
 	target_column_name: &str,
+	cache_dir: Option<&Path>,
 	handle_progress_event: &mut dyn FnMut(ProgressEvent),
 ) -> Result<DatasetTrain> {
 	// Get the column types from the config, if set.
-	let mut table = Table::from_path(
+	let mut table = table_cache::load_table(
 		file_path,
 		modelfox_table::FromCsvOptions {
 			column_types: column_types_from_config(config),
 			infer_options: Default::default(),
+			invalid_value_policies: invalid_value_policies_from_config(config),
 			..Default::default()
 		},
+		cache_dir,
+		&mut |progress_event| {
+			handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Train(
+				progress_event,
+			)))
+		},
+	)?;
+	// Drop any rows with invalid data in the target column
+	drop_invalid_target_rows(&mut table, target_column_name, handle_progress_event);
+	// Order the table by date for rolling cross validation, or shuffle it if enabled.
+	let group_boundaries = arrange_table(&mut table, config, handle_progress_event)?;
+	// Split the table into train and test tables.
+	Ok(DatasetTrain {
+		table,
+		comparison_fraction: config.dataset.comparison_fraction,
+		comparison_horizon: comparison_horizon_from_config(config),
+		group_boundaries,
+		test_fraction: config.dataset.test_fraction,
+	})
+}
+
+fn load_and_shuffle_dataset_database(
+	database_url: &str,
+	query: &str,
+	config: &Config,
+	target_column_name: &str,
+	handle_progress_event: &mut dyn FnMut(ProgressEvent),
+) -> Result<DatasetTrain> {
+	// Get the column types from the config, if set. The remaining column types follow from the SQL types of the query result.
+	let mut table = crate::database::load_table_from_database(
+		database_url,
+		query,
+		column_types_from_config(config),
+		invalid_value_policies_from_config(config),
 		&mut |progress_event| {
 			handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Train(
 				progress_event,
@@ -750,12 +1466,41 @@ fn load_and_shuffle_dataset_train(
 	)?;
 	// Drop any rows with invalid data in the target column
 	drop_invalid_target_rows(&mut table, target_column_name, handle_progress_event);
-	// Shuffle the table if enabled.
-	shuffle_table(&mut table, config, handle_progress_event);
+	// Order the table by date for rolling cross validation, or shuffle it if enabled.
+	let group_boundaries = arrange_table(&mut table, config, handle_progress_event)?;
 	// Split the table into train and test tables.
 	Ok(DatasetTrain {
 		table,
 		comparison_fraction: config.dataset.comparison_fraction,
+		comparison_horizon: comparison_horizon_from_config(config),
+		group_boundaries,
+		test_fraction: config.dataset.test_fraction,
+	})
+}
+
+fn shuffle_dataset_table(
+	mut table: Table,
+	config: &Config,
+	target_column_name: &str,
+	handle_progress_event: &mut dyn FnMut(ProgressEvent),
+) -> Result<DatasetTrain> {
+	if !table
+		.columns()
+		.iter()
+		.any(|column| column.name() == Some(target_column_name))
+	{
+		bail!("did not find target column \"{}\"", target_column_name);
+	}
+	// Drop any rows with invalid data in the target column
+	drop_invalid_target_rows(&mut table, target_column_name, handle_progress_event);
+	// Order the table by date for rolling cross validation, or shuffle it if enabled.
+	let group_boundaries = arrange_table(&mut table, config, handle_progress_event)?;
+	// Split the table into train and test tables.
+	Ok(DatasetTrain {
+		table,
+		comparison_fraction: config.dataset.comparison_fraction,
+		comparison_horizon: comparison_horizon_from_config(config),
+		group_boundaries,
 		test_fraction: config.dataset.test_fraction,
 	})
 }
@@ -765,17 +1510,20 @@ fn load_and_shuffle_dataset_train_and_test(
 	file_path_test: &Path,
 	config: &Config,
 	target_column_name: &str,
+	cache_dir: Option<&Path>,
 	handle_progress_event: &mut dyn FnMut(ProgressEvent),
 ) -> Result<DatasetTrainAndTest> {
 	// Get the column types from the config, if set.
 	let column_types = column_types_from_config(config);
-	let mut table_train = Table::from_path(
+	let mut table_train = table_cache::load_table(
 		file_path_train,
 		modelfox_table::FromCsvOptions {
 			column_types,
 			infer_options: Default::default(),
+			invalid_value_policies: invalid_value_policies_from_config(config),
 			..Default::default()
 		},
+		cache_dir,
 		&mut |progress_event| {
 			handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Train(
 				progress_event,
@@ -800,15 +1548,23 @@ fn load_and_shuffle_dataset_train_and_test(
 				(column.name().to_owned().unwrap(), TableColumnType::Number)
 			}
 			TableColumn::Text(column) => (column.name().to_owned().unwrap(), TableColumnType::Text),
+			TableColumn::DateTime(column) => (
+				column.name().to_owned().unwrap(),
+				TableColumnType::DateTime {
+					timezone: column.timezone(),
+				},
+			),
 		})
 		.collect();
-	let mut table_test = Table::from_path(
+	let mut table_test = table_cache::load_table(
 		file_path_test,
 		modelfox_table::FromCsvOptions {
 			column_types: Some(column_types),
 			infer_options: Default::default(),
+			invalid_value_policies: invalid_value_policies_from_config(config),
 			..Default::default()
 		},
+		cache_dir,
 		&mut |progress_event| {
 			handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Test(progress_event)))
 		},
@@ -819,15 +1575,19 @@ fn load_and_shuffle_dataset_train_and_test(
 	// Drop any rows with invalid data in the target column
 	drop_invalid_target_rows(&mut table_train, target_column_name, handle_progress_event);
 	drop_invalid_target_rows(&mut table_test, target_column_name, handle_progress_event);
-	shuffle_table(&mut table_train, config, handle_progress_event);
+	let group_boundaries = arrange_table(&mut table_train, config, handle_progress_event)?;
 	Ok(DatasetTrainAndTest {
 		table_train,
 		table_test,
 		comparison_fraction: config.dataset.comparison_fraction,
+		comparison_horizon: comparison_horizon_from_config(config),
+		group_boundaries,
 	})
 }
 
-fn column_types_from_config(config: &Config) -> Option<BTreeMap<String, TableColumnType>> {
+pub(crate) fn column_types_from_config(
+	config: &Config,
+) -> Option<BTreeMap<String, TableColumnType>> {
 	Some(
 		config
 			.dataset
@@ -843,11 +1603,228 @@ fn column_types_from_config(config: &Config) -> Option<BTreeMap<String, TableCol
 					},
 				),
 				config::Column::Text(column) => (column.name.clone(), TableColumnType::Text),
+				config::Column::DateTime(column) => (
+					column.name.clone(),
+					TableColumnType::DateTime {
+						timezone: column
+							.timezone
+							.as_ref()
+							.map(|timezone| timezone.parse().unwrap())
+							.unwrap_or(Tz::UTC),
+					},
+				),
 			})
 			.collect(),
 	)
 }
 
+/// Collect the invalid value policies of the columns in the config whose policy is not to treat invalid values as missing.
+pub(crate) fn invalid_value_policies_from_config(
+	config: &Config,
+) -> BTreeMap<String, modelfox_table::InvalidValuePolicy> {
+	config
+		.dataset
+		.columns
+		.iter()
+		.filter_map(|column| {
+			let (name, policy) = match column {
+				config::Column::Number(column) => (&column.name, column.invalid_value_policy),
+				config::Column::Enum(column) => (&column.name, column.invalid_value_policy),
+				config::Column::DateTime(column) => (&column.name, column.invalid_value_policy),
+				config::Column::Unknown(_) | config::Column::Text(_) => return None,
+			};
+			let policy = match policy {
+				config::InvalidValuePolicy::Missing => return None,
+				config::InvalidValuePolicy::DropRow => modelfox_table::InvalidValuePolicy::DropRow,
+				config::InvalidValuePolicy::Error => modelfox_table::InvalidValuePolicy::Error,
+			};
+			Some((name.clone(), policy))
+		})
+		.collect()
+}
+
+pub(crate) fn stats_settings_from_config(config: &Config) -> StatsSettings {
+	StatsSettings {
+		number_histogram_max_size: config.stats.number_histogram_max_size,
+		number_exact_quantiles_max_unique_count: config
+			.stats
+			.number_exact_quantiles_max_unique_count,
+		datetime_histogram_bin_count: config.stats.datetime_histogram_bin_count,
+		number_quantiles: config.stats.number_quantiles.clone(),
+		..Default::default()
+	}
+}
+
+fn comparison_horizon_from_config(config: &Config) -> Option<usize> {
+	match &config.dataset.cross_validation {
+		Some(config::CrossValidation::Rolling(cross_validation)) => cross_validation.horizon,
+		None => None,
+	}
+}
+
+/// Order the table by its date column if rolling cross validation is enabled, because shuffling would leak future rows into the train dataset. Otherwise, shuffle the table. If `split_group_column` is set, the rows of each group are then moved together, and the group boundaries are returned.
+fn arrange_table(
+	table: &mut Table,
+	config: &Config,
+	handle_progress_event: &mut dyn FnMut(ProgressEvent),
+) -> Result<Option<Vec<usize>>> {
+	match &config.dataset.cross_validation {
+		Some(config::CrossValidation::Rolling(cross_validation)) => {
+			sort_table_by_column(table, &cross_validation.date_column_name)?;
+		}
+		None => {
+			shuffle_table(table, config, handle_progress_event);
+		}
+	}
+	config
+		.dataset
+		.split_group_column
+		.as_ref()
+		.map(|column_name| group_table_rows(table, column_name, handle_progress_event))
+		.transpose()
+}
+
+/// Reorder the rows of the table so the rows in each group, identified by their value in the column named `column_name`, are contiguous. Groups are ordered by their first row, so a shuffled table's groups are in random order and a table sorted by date has its groups ordered by their earliest date. This returns the index of the first row of each group, followed by the number of rows.
+fn group_table_rows(
+	table: &mut Table,
+	column_name: &str,
+	handle_progress_event: &mut dyn FnMut(ProgressEvent),
+) -> Result<Vec<usize>> {
+	let column = table
+		.columns()
+		.iter()
+		.find(|column| column.name() == Some(column_name))
+		.ok_or_else(|| anyhow!("did not find split group column \"{}\"", column_name))?;
+	let group_ids = match column {
+		TableColumn::Number(column) => {
+			group_ids(column.view().as_slice().iter().map(|value| value.to_bits()))
+		}
+		TableColumn::Enum(column) => group_ids(column.view().as_slice().iter()),
+		TableColumn::Text(column) => group_ids(column.view().as_slice().iter()),
+		TableColumn::DateTime(column) => group_ids(column.view().as_slice().iter()),
+		TableColumn::Unknown(_) => {
+			bail!("the split group column \"{}\" has no values", column_name)
+		}
+	};
+	let mut row_indexes = (0..table.nrows()).collect::<Vec<_>>();
+	row_indexes.sort_by_key(|index| group_ids[*index]);
+	for column in table.columns_mut().iter_mut() {
+		match column {
+			TableColumn::Unknown(_) => {}
+			TableColumn::Number(column) => reorder(column.data_mut(), &row_indexes),
+			TableColumn::Enum(column) => reorder(column.data_mut(), &row_indexes),
+			TableColumn::Text(column) => reorder(column.data_mut(), &row_indexes),
+			TableColumn::DateTime(column) => reorder(column.data_mut(), &row_indexes),
+		}
+	}
+	let mut group_boundaries = (0..row_indexes.len())
+		.filter(|position| {
+			*position == 0
+				|| group_ids[row_indexes[*position]] != group_ids[row_indexes[*position - 1]]
+		})
+		.collect::<Vec<_>>();
+	group_boundaries.push(row_indexes.len());
+	// Warn if one group is so much larger than the others that the datasets cannot be split near their configured sizes.
+	let mut group_sizes = group_boundaries
+		.windows(2)
+		.map(|window| window[1] - window[0])
+		.collect::<Vec<_>>();
+	group_sizes.sort_unstable();
+	if let (Some(median_group_size), Some(max_group_size)) =
+		(group_sizes.get(group_sizes.len() / 2), group_sizes.last())
+	{
+		if *max_group_size > MAX_SPLIT_GROUP_SIZE_RATIO * median_group_size {
+			handle_progress_event(ProgressEvent::Warning(format!(
+				"The group sizes in the split group column \"{}\" are highly imbalanced. The largest group has {} row(s), but the median group has only {}, so the train, comparison, and test datasets may be much larger or smaller than configured.",
+				column_name, max_group_size, median_group_size,
+			)));
+		}
+	}
+	Ok(group_boundaries)
+}
+
+/// Assign each key an id in the order the keys first appear.
+fn group_ids<K: std::hash::Hash + Eq>(keys: impl Iterator<Item = K>) -> Vec<usize> {
+	let mut ids = HashMap::new();
+	keys.map(|key| {
+		let n_ids = ids.len();
+		*ids.entry(key).or_insert(n_ids)
+	})
+	.collect()
+}
+
+/// Move a split point to the nearest boundary between groups, so no group is split across datasets. If the rows are not grouped, the split point is returned unchanged.
+fn snap_to_group_boundary(row: usize, group_boundaries: Option<&[usize]>) -> usize {
+	match group_boundaries {
+		Some(group_boundaries) => group_boundaries
+			.iter()
+			.copied()
+			.min_by_key(|boundary| boundary.abs_diff(row))
+			.unwrap_or(row),
+		None => row,
+	}
+}
+
+/// Sort the rows of the table in ascending order of the values in the column named `column_name`. Rows with a missing value come first, and rows with equal values keep their order.
+fn sort_table_by_column(table: &mut Table, column_name: &str) -> Result<()> {
+	let column = table
+		.columns()
+		.iter()
+		.find(|column| column.name() == Some(column_name))
+		.ok_or_else(|| anyhow!("did not find date column \"{}\"", column_name))?;
+	let mut row_indexes = (0..table.nrows()).collect::<Vec<_>>();
+	match column {
+		TableColumn::Number(column) => {
+			let column = column.view();
+			let data = column.as_slice();
+			// Missing values are NaN, which do not compare with any other value, so order them first as missing values are in the other column types.
+			row_indexes.sort_by(|a, b| match (data[*a].is_nan(), data[*b].is_nan()) {
+				(true, true) => std::cmp::Ordering::Equal,
+				(true, false) => std::cmp::Ordering::Less,
+				(false, true) => std::cmp::Ordering::Greater,
+				(false, false) => data[*a].partial_cmp(&data[*b]).unwrap(),
+			});
+		}
+		TableColumn::Enum(column) => {
+			let variants = column.variants();
+			let column = column.view();
+			let data = column.as_slice();
+			let value = |index: usize| data[index].map(|value| variants[value.get() - 1].as_str());
+			row_indexes.sort_by_key(|index| value(*index));
+		}
+		TableColumn::Text(column) => {
+			let column = column.view();
+			let data = column.as_slice();
+			row_indexes.sort_by_key(|index| data[*index].as_str());
+		}
+		TableColumn::DateTime(column) => {
+			let column = column.view();
+			let data = column.as_slice();
+			row_indexes.sort_by_key(|index| data[*index]);
+		}
+		TableColumn::Unknown(_) => {
+			bail!("the date column \"{}\" has no values", column_name)
+		}
+	}
+	for column in table.columns_mut().iter_mut() {
+		match column {
+			TableColumn::Unknown(_) => {}
+			TableColumn::Number(column) => reorder(column.data_mut(), &row_indexes),
+			TableColumn::Enum(column) => reorder(column.data_mut(), &row_indexes),
+			TableColumn::Text(column) => reorder(column.data_mut(), &row_indexes),
+			TableColumn::DateTime(column) => reorder(column.data_mut(), &row_indexes),
+		}
+	}
+	Ok(())
+}
+
+fn reorder<T: Clone>(data: &mut Vec<T>, row_indexes: &[usize]) {
+	*data = row_indexes
+		.iter()
+		.map(|row_index| data[*row_index].clone())
+		.collect();
+}
+
 /// Shuffle the table.
 fn shuffle_table(
 	table: &mut Table,
@@ -863,6 +1840,7 @@ fn shuffle_table(
 				TableColumn::Number(column) => column.data_mut().shuffle(&mut rng),
 				TableColumn::Enum(column) => column.data_mut().shuffle(&mut rng),
 				TableColumn::Text(column) => column.data_mut().shuffle(&mut rng),
+				TableColumn::DateTime(column) => column.data_mut().shuffle(&mut rng),
 			}
 		}
 		handle_progress_event(ProgressEvent::Load(LoadProgressEvent::ShuffleDone));
@@ -928,6 +1906,7 @@ fn compute_baseline_metrics(
 	target_column_index: usize,
 	train_target_column_stats: &ColumnStatsOutput,
 	test_target_column_stats: &ColumnStatsOutput,
+	tolerance: Option<modelfox_metrics::Tolerance>,
 	progress: &impl Fn(),
 ) -> Metrics {
 	match task {
@@ -939,7 +1918,7 @@ fn compute_baseline_metrics(
 				_ => unreachable!(),
 			};
 			let baseline_prediction = train_target_column_stats.mean;
-			let mut metrics = modelfox_metrics::RegressionMetrics::new();
+			let mut metrics = modelfox_metrics::RegressionMetrics::with_tolerance(tolerance);
 			for label in labels.iter() {
 				metrics.update(modelfox_metrics::RegressionMetricsInput {
 					predictions: &[baseline_prediction],
@@ -994,6 +1973,7 @@ fn compute_baseline_metrics(
 				.collect::<Vec<_>>();
 			let mut metrics = modelfox_metrics::MulticlassClassificationMetrics::new(
 				test_target_column_stats.histogram.len(),
+				3,
 			);
 			for label in labels.iter() {
 				metrics.update(modelfox_metrics::MulticlassClassificationMetricsInput {
@@ -1009,47 +1989,107 @@ fn compute_baseline_metrics(
 }
 
 pub struct TrainGridItemOutput {
-	pub train_model_output: TrainModelOutput,
-	pub comparison_metrics: Metrics,
+	/// This is `None` if the grid item was restored from a checkpoint.
+	pub train_model_output: Option<TrainModelOutput>,
+	pub train_options: GridItemTrainOptions,
+	/// This is `None` if the grid item was restored from a checkpoint.
+	pub comparison_metrics: Option<Metrics>,
 	pub comparison_metric_value: f32,
 	pub duration: Duration,
 }
 
+#[derive(Clone, Debug)]
+pub enum GridItemTrainOptions {
+	Linear(modelfox_linear::TrainOptions),
+	Tree(modelfox_tree::TrainOptions),
+}
+
+fn grid_item_train_options(grid_item: &grid::GridItem) -> GridItemTrainOptions {
+	match grid_item {
+		grid::GridItem::LinearRegressor { options, .. }
+		| grid::GridItem::LinearBinaryClassifier { options, .. }
+		| grid::GridItem::LinearMulticlassClassifier { options, .. } => {
+			GridItemTrainOptions::Linear(compute_linear_options(options))
+		}
+		grid::GridItem::TreeRegressor {
+			options,
+			feature_groups,
+			..
+		}
+		| grid::GridItem::TreeBinaryClassifier {
+			options,
+			feature_groups,
+			..
+		}
+		| grid::GridItem::TreeMulticlassClassifier {
+			options,
+			feature_groups,
+			..
+		} => GridItemTrainOptions::Tree(compute_tree_options(options, feature_groups)),
+	}
+}
+
 #[allow(clippy::too_many_arguments)]
 fn train_grid_item(
 	grid_item_count: usize,
 	grid_item_index: usize,
 	grid_item: grid::GridItem,
-	table_train: &TableView,
-	table_comparison: &TableView,
+	folds: &[(TableView, TableView)],
 	comparison_metric: ComparisonMetric,
 	kill_chip: &KillChip,
 	handle_progress_event: &mut dyn FnMut(ProgressEvent),
-) -> TrainGridItemOutput {
-	let start = Instant::now();
-	let train_model_output = train_model(grid_item, table_train, kill_chip, &mut |progress| {
-		handle_progress_event(ProgressEvent::Train(TrainProgressEvent {
-			grid_item_index,
-			grid_item_count,
-			grid_item_progress_event: progress,
-		}))
-	});
-	let duration = start.elapsed();
-	let comparison_metrics =
-		compute_comparison_metrics(&train_model_output, table_comparison, &mut |progress| {
-			handle_progress_event(ProgressEvent::Train(TrainProgressEvent {
-				grid_item_index,
-				grid_item_count,
-				grid_item_progress_event: TrainGridItemProgressEvent::ComputeModelComparisonMetrics(
-					progress,
-				),
-			}))
-		});
-	let comparison_metric_value =
-		get_comparison_metric_value(&comparison_metrics, comparison_metric);
+) -> Result<TrainGridItemOutput> {
+	// Train and evaluate the grid item on each fold. The comparison metric value is the mean over the folds, and the model and comparison metrics from the last fold, which has the most training rows, are kept. When there is more than one fold, the feature importances are replaced with their mean over the folds, and their standard deviations are kept as well.
+	let train_options = grid_item_train_options(&grid_item);
+	let mut duration = Duration::ZERO;
+	let mut comparison_metric_values = Vec::with_capacity(folds.len());
+	let mut fold_feature_importances = Vec::with_capacity(folds.len());
+	let mut fold_outputs = None;
+	for (table_train, table_comparison) in folds {
+		let start = Instant::now();
+		let train_model_output =
+			train_model(grid_item.clone(), table_train, kill_chip, &mut |progress| {
+				handle_progress_event(ProgressEvent::Train(TrainProgressEvent {
+					grid_item_index,
+					grid_item_count,
+					grid_item_progress_event: progress,
+				}))
+			});
+		duration += start.elapsed();
+		let comparison_metrics =
+			compute_comparison_metrics(&train_model_output, table_comparison, &mut |progress| {
+				handle_progress_event(ProgressEvent::Train(TrainProgressEvent {
+					grid_item_index,
+					grid_item_count,
+					grid_item_progress_event:
+						TrainGridItemProgressEvent::ComputeModelComparisonMetrics(progress),
+				}))
+			});
+		comparison_metric_values.push(get_comparison_metric_value(
+			&comparison_metrics,
+			comparison_metric,
+		));
+		fold_feature_importances.push(train_model_output.named_feature_importances()?);
+		fold_outputs = Some((train_model_output, comparison_metrics));
+		if kill_chip.is_activated() {
+			break;
+		}
+	}
+	let (mut train_model_output, comparison_metrics) = fold_outputs.unwrap();
+	if fold_feature_importances.len() > 1 {
+		let (feature_importances, feature_importances_std) =
+			aggregate_fold_feature_importances(&fold_feature_importances);
+		train_model_output.set_feature_importances(feature_importances, feature_importances_std);
+	}
+	let comparison_metric_value = comparison_metric_values.iter().sum::<f32>()
+		/ comparison_metric_values.len().to_f32().unwrap();
 	let comparison_metric_str = match comparison_metric {
 		ComparisonMetric::BinaryClassification(bcm) => match bcm {
 			BinaryClassificationComparisonMetric::AucRoc => "AUC ROC",
+			BinaryClassificationComparisonMetric::F1 => "F1",
+			BinaryClassificationComparisonMetric::Precision => "Precision",
+			BinaryClassificationComparisonMetric::Recall => "Recall",
+			BinaryClassificationComparisonMetric::Accuracy => "Accuracy",
 		},
 		ComparisonMetric::MulticlassClassification(mccm) => match mccm {
 			MulticlassClassificationComparisonMetric::Accuracy => "Accuracy",
@@ -1067,11 +2107,33 @@ fn train_grid_item(
 		comparison_metric_str,
 		comparison_metric_value
 	)));
-	TrainGridItemOutput {
-		train_model_output,
-		comparison_metrics,
+	handle_progress_event(ProgressEvent::Train(TrainProgressEvent {
+		grid_item_index,
+		grid_item_count,
+		grid_item_progress_event: TrainGridItemProgressEvent::Done {
+			comparison_metric: comparison_metric_str.to_owned(),
+			comparison_metric_value,
+		},
+	}));
+	Ok(TrainGridItemOutput {
+		train_model_output: Some(train_model_output),
+		train_options,
+		comparison_metrics: Some(comparison_metrics),
 		comparison_metric_value,
 		duration,
+	})
+}
+
+fn comparison_metric_higher_is_better(comparison_metric: ComparisonMetric) -> bool {
+	match comparison_metric {
+		ComparisonMetric::Regression(comparison_metric) => match comparison_metric {
+			RegressionComparisonMetric::MeanAbsoluteError
+			| RegressionComparisonMetric::MeanSquaredError
+			| RegressionComparisonMetric::RootMeanSquaredError => false,
+			RegressionComparisonMetric::R2 => true,
+		},
+		ComparisonMetric::BinaryClassification(_)
+		| ComparisonMetric::MulticlassClassification(_) => true,
 	}
 }
 
@@ -1088,9 +2150,25 @@ fn get_comparison_metric_value(metrics: &Metrics, comparison_metric: ComparisonM
 		(
 			ComparisonMetric::BinaryClassification(comparison_metric),
 			Metrics::BinaryClassification(metrics),
-		) => match comparison_metric {
-			BinaryClassificationComparisonMetric::AucRoc => metrics.auc_roc_approx,
-		},
+		) => {
+			let default_threshold_metrics = &metrics.thresholds[metrics.thresholds.len() / 2];
+			// The precision, recall, and f1 score are undefined when the model never predicts the positive class or there are no positive examples. Treat that as the worst possible value.
+			match comparison_metric {
+				BinaryClassificationComparisonMetric::AucRoc => metrics.auc_roc_approx,
+				BinaryClassificationComparisonMetric::F1 => {
+					default_threshold_metrics.f1_score.unwrap_or(0.0)
+				}
+				BinaryClassificationComparisonMetric::Precision => {
+					default_threshold_metrics.precision.unwrap_or(0.0)
+				}
+				BinaryClassificationComparisonMetric::Recall => {
+					default_threshold_metrics.recall.unwrap_or(0.0)
+				}
+				BinaryClassificationComparisonMetric::Accuracy => {
+					default_threshold_metrics.accuracy
+				}
+			}
+		}
 		(
 			ComparisonMetric::MulticlassClassification(comparison_metric),
 			Metrics::MulticlassClassification(metrics),
@@ -1111,6 +2189,134 @@ pub enum TrainModelOutput {
 	TreeMulticlassClassifier(TreeMulticlassClassifierTrainModelOutput),
 }
 
+impl TrainModelOutput {
+	/// Pair each feature importance with the name of its feature.
+	fn named_feature_importances(&self) -> Result<Vec<(String, f32)>> {
+		let (feature_groups, feature_importances) = match self {
+			TrainModelOutput::LinearRegressor(output) => {
+				(&output.feature_groups, &output.feature_importances)
+			}
+			TrainModelOutput::TreeRegressor(output) => {
+				(&output.feature_groups, &output.feature_importances)
+			}
+			TrainModelOutput::LinearBinaryClassifier(output) => {
+				(&output.feature_groups, &output.feature_importances)
+			}
+			TrainModelOutput::TreeBinaryClassifier(output) => {
+				(&output.feature_groups, &output.feature_importances)
+			}
+			TrainModelOutput::LinearMulticlassClassifier(output) => {
+				(&output.feature_groups, &output.feature_importances)
+			}
+			TrainModelOutput::TreeMulticlassClassifier(output) => {
+				(&output.feature_groups, &output.feature_importances)
+			}
+		};
+		let feature_names = feature_groups
+			.iter()
+			.flat_map(|feature_group| feature_group.feature_names());
+		let named_feature_importances = zip_eq!(feature_names, feature_importances.iter().cloned())
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(named_feature_importances)
+	}
+
+	fn feature_importances_std(&self) -> Option<&[f32]> {
+		match self {
+			TrainModelOutput::LinearRegressor(output) => output.feature_importances_std.as_deref(),
+			TrainModelOutput::TreeRegressor(output) => output.feature_importances_std.as_deref(),
+			TrainModelOutput::LinearBinaryClassifier(output) => {
+				output.feature_importances_std.as_deref()
+			}
+			TrainModelOutput::TreeBinaryClassifier(output) => {
+				output.feature_importances_std.as_deref()
+			}
+			TrainModelOutput::LinearMulticlassClassifier(output) => {
+				output.feature_importances_std.as_deref()
+			}
+			TrainModelOutput::TreeMulticlassClassifier(output) => {
+				output.feature_importances_std.as_deref()
+			}
+		}
+	}
+
+	fn set_feature_importances(
+		&mut self,
+		feature_importances: Vec<f32>,
+		feature_importances_std: Vec<f32>,
+	) {
+		let (importances, importances_std) = match self {
+			TrainModelOutput::LinearRegressor(output) => (
+				&mut output.feature_importances,
+				&mut output.feature_importances_std,
+			),
+			TrainModelOutput::TreeRegressor(output) => (
+				&mut output.feature_importances,
+				&mut output.feature_importances_std,
+			),
+			TrainModelOutput::LinearBinaryClassifier(output) => (
+				&mut output.feature_importances,
+				&mut output.feature_importances_std,
+			),
+			TrainModelOutput::TreeBinaryClassifier(output) => (
+				&mut output.feature_importances,
+				&mut output.feature_importances_std,
+			),
+			TrainModelOutput::LinearMulticlassClassifier(output) => (
+				&mut output.feature_importances,
+				&mut output.feature_importances_std,
+			),
+			TrainModelOutput::TreeMulticlassClassifier(output) => (
+				&mut output.feature_importances,
+				&mut output.feature_importances_std,
+			),
+		};
+		*importances = feature_importances;
+		*importances_std = Some(feature_importances_std);
+	}
+}
+
+/// Compute the mean and standard deviation over the folds of the importance of each feature in the last fold's model. The feature groups are fit on each fold's train table, so a feature that is absent in a fold, such as an ngram that did not occur in its train table, has an importance of zero in that fold.
+fn aggregate_fold_feature_importances(
+	fold_feature_importances: &[Vec<(String, f32)>],
+) -> (Vec<f32>, Vec<f32>) {
+	let (last_fold_feature_importances, earlier_folds_feature_importances) =
+		fold_feature_importances.split_last().unwrap();
+	let earlier_folds_feature_importances = earlier_folds_feature_importances
+		.iter()
+		.map(|feature_importances| {
+			feature_importances
+				.iter()
+				.map(|(feature_name, feature_importance)| {
+					(feature_name.as_str(), *feature_importance)
+				})
+				.collect::<HashMap<&str, f32>>()
+		})
+		.collect::<Vec<_>>();
+	let n_folds = fold_feature_importances.len().to_f32().unwrap();
+	last_fold_feature_importances
+		.iter()
+		.map(|(feature_name, last_fold_feature_importance)| {
+			let values = earlier_folds_feature_importances
+				.iter()
+				.map(|feature_importances| {
+					feature_importances
+						.get(feature_name.as_str())
+						.cloned()
+						.unwrap_or(0.0)
+				})
+				.chain(std::iter::once(*last_fold_feature_importance))
+				.collect::<Vec<_>>();
+			let mean = values.iter().sum::<f32>() / n_folds;
+			let variance = values
+				.iter()
+				.map(|value| (value - mean).powi(2))
+				.sum::<f32>()
+				/ n_folds;
+			(mean, variance.sqrt())
+		})
+		.unzip()
+}
+
 #[derive(Clone, Debug)]
 pub struct LinearRegressorTrainModelOutput {
 	pub model: modelfox_linear::Regressor,
@@ -1119,6 +2325,8 @@ pub struct LinearRegressorTrainModelOutput {
 	pub losses: Option<Vec<f32>>,
 	pub train_options: modelfox_linear::TrainOptions,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, present when the grid item was evaluated on more than one fold.
+	pub feature_importances_std: Option<Vec<f32>>,
 }
 
 #[derive(Clone, Debug)]
@@ -1129,6 +2337,9 @@ pub struct TreeRegressorTrainModelOutput {
 	pub losses: Option<Vec<f32>>,
 	pub train_options: modelfox_tree::TrainOptions,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, present when the grid item was evaluated on more than one fold.
+	pub feature_importances_std: Option<Vec<f32>>,
+	pub bin_thresholds: Vec<Option<Vec<f32>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -1139,6 +2350,8 @@ pub struct LinearBinaryClassifierTrainModelOutput {
 	pub losses: Option<Vec<f32>>,
 	pub train_options: modelfox_linear::TrainOptions,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, present when the grid item was evaluated on more than one fold.
+	pub feature_importances_std: Option<Vec<f32>>,
 }
 
 #[derive(Clone, Debug)]
@@ -1149,6 +2362,9 @@ pub struct TreeBinaryClassifierTrainModelOutput {
 	pub losses: Option<Vec<f32>>,
 	pub train_options: modelfox_tree::TrainOptions,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, present when the grid item was evaluated on more than one fold.
+	pub feature_importances_std: Option<Vec<f32>>,
+	pub bin_thresholds: Vec<Option<Vec<f32>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -1159,6 +2375,8 @@ pub struct LinearMulticlassClassifierTrainModelOutput {
 	pub losses: Option<Vec<f32>>,
 	pub train_options: modelfox_linear::TrainOptions,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, present when the grid item was evaluated on more than one fold.
+	pub feature_importances_std: Option<Vec<f32>>,
 }
 
 #[derive(Clone, Debug)]
@@ -1169,6 +2387,9 @@ pub struct TreeMulticlassClassifierTrainModelOutput {
 	pub losses: Option<Vec<f32>>,
 	pub train_options: modelfox_tree::TrainOptions,
 	pub feature_importances: Vec<f32>,
+	/// The standard deviation of each feature importance across the cross validation folds, present when the grid item was evaluated on more than one fold.
+	pub feature_importances_std: Option<Vec<f32>>,
+	pub bin_thresholds: Vec<Option<Vec<f32>>>,
 }
 
 fn train_model(
@@ -1269,9 +2490,7 @@ fn train_linear_regressor(
 		progress_counter.clone(),
 	));
 	let features =
-		modelfox_features::compute_features_array_f32(table_train, &feature_groups, &|| {
-			progress_counter.inc(1)
-		});
+		compute_linear_features(table_train, &feature_groups, &|| progress_counter.inc(1));
 	handle_progress_event(TrainGridItemProgressEvent::ComputeFeaturesDone);
 	let labels = table_train
 		.columns()
@@ -1289,8 +2508,17 @@ fn train_linear_regressor(
 		kill_chip,
 		handle_progress_event: progress,
 	};
-	let train_output =
-		modelfox_linear::Regressor::train(features.view(), labels, &linear_options, progress);
+	let train_output = match &features {
+		LinearFeatures::Dense(features) => {
+			modelfox_linear::Regressor::train(features.view(), labels, &linear_options, progress)
+		}
+		LinearFeatures::Sparse(features) => modelfox_linear::Regressor::train_sparse(
+			features.view(),
+			labels,
+			&linear_options,
+			progress,
+		),
+	};
 	TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
 		model: train_output.model,
 		feature_groups,
@@ -1298,6 +2526,7 @@ fn train_linear_regressor(
 		train_options: linear_options,
 		losses: train_output.losses,
 		feature_importances: train_output.feature_importances.unwrap(),
+		feature_importances_std: None,
 	})
 }
 
@@ -1327,7 +2556,7 @@ fn train_tree_regressor(
 		.as_number()
 		.unwrap()
 		.clone();
-	let tree_options = compute_tree_options(&options);
+	let tree_options = compute_tree_options(&options, &feature_groups);
 	let progress = &mut |progress| {
 		handle_progress_event(TrainGridItemProgressEvent::TrainModel(
 			ModelTrainProgressEvent::Tree(progress),
@@ -1346,6 +2575,8 @@ fn train_tree_regressor(
 		train_options: tree_options,
 		losses: train_output.losses,
 		feature_importances: train_output.feature_importances.unwrap(),
+		feature_importances_std: None,
+		bin_thresholds: train_output.bin_thresholds,
 	})
 }
 
@@ -1365,9 +2596,7 @@ fn train_linear_binary_classifier(
 		progress_counter.clone(),
 	));
 	let features =
-		modelfox_features::compute_features_array_f32(table_train, &feature_groups, &|| {
-			progress_counter.inc(1)
-		});
+		compute_linear_features(table_train, &feature_groups, &|| progress_counter.inc(1));
 	handle_progress_event(TrainGridItemProgressEvent::ComputeFeaturesDone);
 	let labels = table_train
 		.columns()
@@ -1385,12 +2614,20 @@ fn train_linear_binary_classifier(
 		kill_chip,
 		handle_progress_event: progress,
 	};
-	let train_output = modelfox_linear::BinaryClassifier::train(
-		features.view(),
-		labels,
-		&linear_options,
-		progress,
-	);
+	let train_output = match &features {
+		LinearFeatures::Dense(features) => modelfox_linear::BinaryClassifier::train(
+			features.view(),
+			labels,
+			&linear_options,
+			progress,
+		),
+		LinearFeatures::Sparse(features) => modelfox_linear::BinaryClassifier::train_sparse(
+			features.view(),
+			labels,
+			&linear_options,
+			progress,
+		),
+	};
 	TrainModelOutput::LinearBinaryClassifier(LinearBinaryClassifierTrainModelOutput {
 		model: train_output.model,
 		feature_groups,
@@ -1398,6 +2635,7 @@ fn train_linear_binary_classifier(
 		train_options: linear_options,
 		losses: train_output.losses,
 		feature_importances: train_output.feature_importances.unwrap(),
+		feature_importances_std: None,
 	})
 }
 
@@ -1427,7 +2665,7 @@ fn train_tree_binary_classifier(
 		.as_enum()
 		.unwrap()
 		.clone();
-	let tree_options = compute_tree_options(&options);
+	let tree_options = compute_tree_options(&options, &feature_groups);
 	let progress = &mut |progress| {
 		handle_progress_event(TrainGridItemProgressEvent::TrainModel(
 			ModelTrainProgressEvent::Tree(progress),
@@ -1446,6 +2684,8 @@ fn train_tree_binary_classifier(
 		train_options: tree_options,
 		losses: train_output.losses,
 		feature_importances: train_output.feature_importances.unwrap(),
+		feature_importances_std: None,
+		bin_thresholds: train_output.bin_thresholds,
 	})
 }
 
@@ -1465,9 +2705,7 @@ fn train_linear_multiclass_classifier(
 		progress_counter.clone(),
 	));
 	let features =
-		modelfox_features::compute_features_array_f32(table_train, &feature_groups, &|| {
-			progress_counter.inc(1)
-		});
+		compute_linear_features(table_train, &feature_groups, &|| progress_counter.inc(1));
 	handle_progress_event(TrainGridItemProgressEvent::ComputeFeaturesDone);
 	let labels = table_train
 		.columns()
@@ -1485,12 +2723,20 @@ fn train_linear_multiclass_classifier(
 		kill_chip,
 		handle_progress_event: progress,
 	};
-	let train_output = modelfox_linear::MulticlassClassifier::train(
-		features.view(),
-		labels,
-		&linear_options,
-		progress,
-	);
+	let train_output = match &features {
+		LinearFeatures::Dense(features) => modelfox_linear::MulticlassClassifier::train(
+			features.view(),
+			labels,
+			&linear_options,
+			progress,
+		),
+		LinearFeatures::Sparse(features) => modelfox_linear::MulticlassClassifier::train_sparse(
+			features.view(),
+			labels,
+			&linear_options,
+			progress,
+		),
+	};
 	TrainModelOutput::LinearMulticlassClassifier(LinearMulticlassClassifierTrainModelOutput {
 		model: train_output.model,
 		feature_groups,
@@ -1498,6 +2744,7 @@ fn train_linear_multiclass_classifier(
 		train_options: linear_options,
 		losses: train_output.losses,
 		feature_importances: train_output.feature_importances.unwrap(),
+		feature_importances_std: None,
 	})
 }
 
@@ -1527,7 +2774,7 @@ fn train_tree_multiclass_classifier(
 		.as_enum()
 		.unwrap()
 		.clone();
-	let tree_options = compute_tree_options(&options);
+	let tree_options = compute_tree_options(&options, &feature_groups);
 	let progress = &mut |progress| {
 		handle_progress_event(TrainGridItemProgressEvent::TrainModel(
 			ModelTrainProgressEvent::Tree(progress),
@@ -1550,9 +2797,38 @@ fn train_tree_multiclass_classifier(
 		train_options: tree_options,
 		losses: train_output.losses,
 		feature_importances: train_output.feature_importances.unwrap(),
+		feature_importances_std: None,
+		bin_thresholds: train_output.bin_thresholds,
 	})
 }
 
+/// The features for training a linear model, which are stored sparsely when most of their values are zero, as with wide one hot encoded and bag of words feature groups.
+enum LinearFeatures {
+	Dense(Array2<f32>),
+	Sparse(modelfox_features::CsrMatrix),
+}
+
+fn compute_linear_features(
+	table: &TableView,
+	feature_groups: &[modelfox_features::FeatureGroup],
+	progress: &impl Fn(),
+) -> LinearFeatures {
+	let density = modelfox_features::estimate_features_density(table, feature_groups);
+	if density < LINEAR_SPARSE_FEATURES_MAX_DENSITY {
+		LinearFeatures::Sparse(modelfox_features::compute_features_sparse_f32(
+			table,
+			feature_groups,
+			progress,
+		))
+	} else {
+		LinearFeatures::Dense(modelfox_features::compute_features_array_f32(
+			table,
+			feature_groups,
+			progress,
+		))
+	}
+}
+
 fn compute_linear_options(
 	options: &grid::LinearModelTrainOptions,
 ) -> modelfox_linear::TrainOptions {
@@ -1566,12 +2842,47 @@ fn compute_linear_options(
 	if let Some(learning_rate) = options.learning_rate {
 		linear_options.learning_rate = learning_rate;
 	}
+	if let Some(learning_rate_schedule) = options.learning_rate_schedule.as_ref() {
+		linear_options.learning_rate_schedule = match *learning_rate_schedule {
+			grid::LearningRateSchedule::Constant => modelfox_linear::LearningRateSchedule::Constant,
+			grid::LearningRateSchedule::Step {
+				decay_rate,
+				n_epochs_per_step,
+			} => modelfox_linear::LearningRateSchedule::Step {
+				decay_rate,
+				n_epochs_per_step: n_epochs_per_step.to_usize().unwrap(),
+			},
+			grid::LearningRateSchedule::Exponential { decay_rate } => {
+				modelfox_linear::LearningRateSchedule::Exponential { decay_rate }
+			}
+			grid::LearningRateSchedule::InverseTime { decay_rate } => {
+				modelfox_linear::LearningRateSchedule::InverseTime { decay_rate }
+			}
+		};
+	}
 	if let Some(max_epochs) = options.max_epochs {
 		linear_options.max_epochs = max_epochs.to_usize().unwrap();
 	}
 	if let Some(n_examples_per_batch) = options.n_examples_per_batch {
 		linear_options.n_examples_per_batch = n_examples_per_batch.to_usize().unwrap();
 	}
+	if let Some(optimizer) = options.optimizer.as_ref() {
+		linear_options.optimizer = match *optimizer {
+			grid::LinearOptimizer::Sgd => modelfox_linear::Optimizer::Sgd,
+			grid::LinearOptimizer::Momentum { momentum } => modelfox_linear::Optimizer::Momentum {
+				momentum: momentum.unwrap_or(modelfox_linear::DEFAULT_MOMENTUM),
+			},
+			grid::LinearOptimizer::Adam {
+				beta_1,
+				beta_2,
+				epsilon,
+			} => modelfox_linear::Optimizer::Adam {
+				beta_1: beta_1.unwrap_or(modelfox_linear::DEFAULT_ADAM_BETA_1),
+				beta_2: beta_2.unwrap_or(modelfox_linear::DEFAULT_ADAM_BETA_2),
+				epsilon: epsilon.unwrap_or(modelfox_linear::DEFAULT_ADAM_EPSILON),
+			},
+		};
+	}
 	if let Some(early_stopping_options) = options.early_stopping_options.as_ref() {
 		linear_options.early_stopping_options = Some(modelfox_linear::EarlyStoppingOptions {
 			early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -1583,11 +2894,26 @@ fn compute_linear_options(
 	linear_options
 }
 
-fn compute_tree_options(options: &grid::TreeModelTrainOptions) -> modelfox_tree::TrainOptions {
+fn compute_tree_options(
+	options: &grid::TreeModelTrainOptions,
+	feature_groups: &[modelfox_features::FeatureGroup],
+) -> modelfox_tree::TrainOptions {
 	let mut tree_options = modelfox_tree::TrainOptions {
 		compute_losses: true,
 		..Default::default()
 	};
+	if let Some(interaction_constraints) = options.interaction_constraints.as_ref() {
+		tree_options.interaction_constraints = Some(compute_interaction_constraints(
+			interaction_constraints,
+			feature_groups,
+		));
+	}
+	if let Some(binning_strategy) = options.binning_strategy.as_ref() {
+		tree_options.binning_strategy = match binning_strategy {
+			grid::BinningStrategy::Quantile => modelfox_tree::BinningStrategy::Quantile,
+			grid::BinningStrategy::Uniform => modelfox_tree::BinningStrategy::Uniform,
+		};
+	}
 	if let Some(early_stopping_options) = options.early_stopping_options.as_ref() {
 		tree_options.early_stopping_options = Some(modelfox_tree::EarlyStoppingOptions {
 			early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -1648,6 +2974,68 @@ fn compute_tree_options(options: &grid::TreeModelTrainOptions) -> modelfox_tree:
 	tree_options
 }
 
+/// Convert interaction constraints on column names to interaction constraints on feature indexes. A feature belongs to a group if every column its feature group is computed from is in the group. The features of a feature group that does not belong to any group are placed in a group of their own, so that, for example, the ngrams of a text column may be split on together.
+fn compute_interaction_constraints(
+	interaction_constraints: &[Vec<String>],
+	feature_groups: &[modelfox_features::FeatureGroup],
+) -> Vec<Vec<usize>> {
+	let mut feature_index_ranges = Vec::with_capacity(feature_groups.len());
+	let mut n_features = 0;
+	for feature_group in feature_groups.iter() {
+		feature_index_ranges.push(n_features..n_features + feature_group.n_features());
+		n_features += feature_group.n_features();
+	}
+	let mut feature_group_has_group = vec![false; feature_groups.len()];
+	let mut groups: Vec<Vec<usize>> = interaction_constraints
+		.iter()
+		.map(|column_names| {
+			let mut group = Vec::new();
+			for (feature_group_index, (feature_group, feature_index_range)) in
+				zip!(feature_groups.iter(), feature_index_ranges.iter()).enumerate()
+			{
+				let in_group =
+					feature_group
+						.source_column_names()
+						.iter()
+						.all(|source_column_name| {
+							column_names
+								.iter()
+								.any(|column_name| column_name == source_column_name)
+						});
+				if in_group {
+					feature_group_has_group[feature_group_index] = true;
+					group.extend(feature_index_range.clone());
+				}
+			}
+			group
+		})
+		.collect();
+	for (feature_index_range, has_group) in zip!(feature_index_ranges, feature_group_has_group) {
+		if !has_group {
+			groups.push(feature_index_range.collect());
+		}
+	}
+	groups
+}
+
+fn choose_tolerance(config: &Config, task: &Task) -> Result<Option<modelfox_metrics::Tolerance>> {
+	let tolerance = match config.train.regression_tolerance {
+		Some(tolerance) => tolerance,
+		None => return Ok(None),
+	};
+	if !matches!(task, Task::Regression) {
+		bail!("regression_tolerance can only be used when training a regressor");
+	}
+	let value = match tolerance {
+		config::RegressionTolerance::Absolute { value }
+		| config::RegressionTolerance::Relative { value } => value,
+	};
+	if !(value.is_finite() && value >= 0.0) {
+		bail!("the regression tolerance must be a finite, nonnegative number");
+	}
+	Ok(Some(tolerance.into()))
+}
+
 fn choose_comparison_metric(config: &Config, task: &Task) -> Result<ComparisonMetric> {
 	match task {
 		Task::Regression => {
@@ -1678,17 +3066,24 @@ fn choose_comparison_metric(config: &Config, task: &Task) -> Result<ComparisonMe
 		}
 		Task::BinaryClassification => {
 			if let Some(comparison_metric) = &config.train.comparison_metric {
-				match comparison_metric {
+				let comparison_metric = match comparison_metric {
+					config::ComparisonMetric::Auc => BinaryClassificationComparisonMetric::AucRoc,
+					config::ComparisonMetric::F1 => BinaryClassificationComparisonMetric::F1,
+					config::ComparisonMetric::Precision => {
+						BinaryClassificationComparisonMetric::Precision
+					}
+					config::ComparisonMetric::Recall => {
+						BinaryClassificationComparisonMetric::Recall
+					}
 					config::ComparisonMetric::Accuracy => {
-						Ok(ComparisonMetric::BinaryClassification(
-							BinaryClassificationComparisonMetric::AucRoc,
-						))
+						BinaryClassificationComparisonMetric::Accuracy
 					}
-					metric => Err(anyhow!(
+					metric => bail!(
 						"{} is an invalid comparison metric for binary classification",
 						metric,
-					)),
-				}
+					),
+				};
+				Ok(ComparisonMetric::BinaryClassification(comparison_metric))
 			} else {
 				Ok(ComparisonMetric::BinaryClassification(
 					BinaryClassificationComparisonMetric::AucRoc,
@@ -1735,6 +3130,7 @@ fn compute_comparison_metrics(
 				*target_column_index,
 				feature_groups,
 				model,
+				None,
 				handle_progress_event,
 			);
 			Metrics::Regression(metrics)
@@ -1751,6 +3147,7 @@ fn compute_comparison_metrics(
 				*target_column_index,
 				feature_groups,
 				model,
+				None,
 				handle_progress_event,
 			);
 			Metrics::Regression(metrics)
@@ -1839,15 +3236,27 @@ fn choose_best_model(
 	}
 }
 
+/// Iterate over the grid item outputs that have a trained model, skipping the ones restored from a checkpoint. The best grid item restored from a checkpoint is always trained again, so the best model is among these.
+fn trained_grid_item_outputs(
+	outputs: &[TrainGridItemOutput],
+) -> impl Iterator<Item = (usize, &TrainModelOutput, &Metrics)> {
+	outputs.iter().enumerate().filter_map(|(index, output)| {
+		match (&output.train_model_output, &output.comparison_metrics) {
+			(Some(train_model_output), Some(comparison_metrics)) => {
+				Some((index, train_model_output, comparison_metrics))
+			}
+			_ => None,
+		}
+	})
+}
+
 fn choose_best_model_regression(
 	outputs: &[TrainGridItemOutput],
 	comparison_metric: &RegressionComparisonMetric,
 ) -> Result<(TrainModelOutput, usize)> {
-	outputs
-		.iter()
-		.enumerate()
-		.filter_map(|(index, output)| {
-			let metrics = match &output.comparison_metrics {
+	trained_grid_item_outputs(outputs)
+		.filter_map(|(index, train_model_output, metrics)| {
+			let metrics = match metrics {
 				Metrics::Regression(metrics) => metrics,
 				_ => unreachable!(),
 			};
@@ -1858,56 +3267,42 @@ fn choose_best_model_regression(
 				RegressionComparisonMetric::R2 => metrics.r2,
 			};
 			if metric.is_finite() {
-				Some((index, output, metric))
+				Some((index, train_model_output, metric))
 			} else {
 				None
 			}
 		})
 		.max_by(|(_, _, metric_a), (_, _, metric_b)| metric_a.partial_cmp(metric_b).unwrap())
 		.ok_or_else(|| anyhow!("None of the models trained had a finite comparison metric value."))
-		.map(|(index, output, _)| (output.train_model_output.clone(), index))
+		.map(|(index, train_model_output, _)| (train_model_output.clone(), index))
 }
 
 fn choose_best_model_binary_classification(
 	outputs: &[TrainGridItemOutput],
 	comparison_metric: &BinaryClassificationComparisonMetric,
 ) -> Result<(TrainModelOutput, usize)> {
-	Ok(outputs
-		.iter()
-		.enumerate()
-		.max_by(|(_, output_a), (_, output_b)| {
-			let metrics_a = match &output_a.comparison_metrics {
-				Metrics::BinaryClassification(metrics) => metrics,
-				_ => unreachable!(),
-			};
-			let metrics_b = match &output_b.comparison_metrics {
-				Metrics::BinaryClassification(metrics) => metrics,
-				_ => unreachable!(),
-			};
-			match comparison_metric {
-				BinaryClassificationComparisonMetric::AucRoc => metrics_a
-					.auc_roc_approx
-					.partial_cmp(&metrics_b.auc_roc_approx)
-					.unwrap(),
-			}
+	trained_grid_item_outputs(outputs)
+		.max_by(|(_, _, metrics_a), (_, _, metrics_b)| {
+			let comparison_metric = ComparisonMetric::BinaryClassification(*comparison_metric);
+			let value_a = get_comparison_metric_value(metrics_a, comparison_metric);
+			let value_b = get_comparison_metric_value(metrics_b, comparison_metric);
+			value_a.partial_cmp(&value_b).unwrap()
 		})
-		.map(|(index, output)| (output.train_model_output.clone(), index))
-		.unwrap())
+		.map(|(index, train_model_output, _)| (train_model_output.clone(), index))
+		.ok_or_else(|| anyhow!("No models were trained."))
 }
 
 fn choose_best_model_multiclass_classification(
 	outputs: &[TrainGridItemOutput],
 	comparison_metric: &MulticlassClassificationComparisonMetric,
 ) -> Result<(TrainModelOutput, usize)> {
-	Ok(outputs
-		.iter()
-		.enumerate()
-		.max_by(|(_, output_a), (_, output_b)| {
-			let metrics_a = match &output_a.comparison_metrics {
+	trained_grid_item_outputs(outputs)
+		.max_by(|(_, _, metrics_a), (_, _, metrics_b)| {
+			let metrics_a = match metrics_a {
 				Metrics::MulticlassClassification(metrics) => metrics,
 				_ => unreachable!(),
 			};
-			let metrics_b = match &output_b.comparison_metrics {
+			let metrics_b = match metrics_b {
 				Metrics::MulticlassClassification(metrics) => metrics,
 				_ => unreachable!(),
 			};
@@ -1917,13 +3312,14 @@ fn choose_best_model_multiclass_classification(
 				}
 			}
 		})
-		.map(|(index, output)| (output.train_model_output.clone(), index))
-		.unwrap())
+		.map(|(index, train_model_output, _)| (train_model_output.clone(), index))
+		.ok_or_else(|| anyhow!("No models were trained."))
 }
 
 fn test_model(
 	train_model_output: &TrainModelOutput,
 	table_test: &TableView,
+	tolerance: Option<modelfox_metrics::Tolerance>,
 	handle_progress_event: &mut dyn FnMut(ModelTestProgressEvent),
 ) -> Metrics {
 	match train_model_output {
@@ -1939,6 +3335,7 @@ fn test_model(
 				*target_column_index,
 				feature_groups,
 				model,
+				tolerance,
 				handle_progress_event,
 			);
 			Metrics::Regression(test_metrics)
@@ -1955,6 +3352,7 @@ fn test_model(
 				*target_column_index,
 				feature_groups,
 				model,
+				tolerance,
 				handle_progress_event,
 			);
 			Metrics::Regression(test_metrics)
@@ -2025,3 +3423,116 @@ fn test_model(
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_group_table_rows() {
+		let mut table = Table::from_bytes(
+			b"group,value\na,1\nb,2\na,3\nc,4\nb,5\n",
+			Default::default(),
+			&mut |_| {},
+		)
+		.unwrap();
+		let group_boundaries = group_table_rows(&mut table, "group", &mut |_| {}).unwrap();
+		assert_eq!(group_boundaries, vec![0, 2, 4, 5]);
+		match &table.columns()[1] {
+			TableColumn::Number(column) => {
+				assert_eq!(column.view().as_slice(), &[1.0, 3.0, 2.0, 5.0, 4.0]);
+			}
+			_ => unreachable!(),
+		}
+		assert_eq!(snap_to_group_boundary(3, Some(&group_boundaries)), 2);
+		assert_eq!(snap_to_group_boundary(3, None), 3);
+	}
+
+	#[test]
+	fn test_validate_config_fractions() {
+		let mut config = Config::default();
+		assert!(validate_config_fractions(&config).is_ok());
+		config.dataset.comparison_fraction = 0.5;
+		config.dataset.test_fraction = 0.5;
+		assert!(validate_config_fractions(&config).is_err());
+		config.dataset.test_fraction = 1.5;
+		assert!(validate_config_fractions(&config).is_err());
+	}
+
+	#[test]
+	fn test_sort_table_by_column() {
+		let mut table = Table::from_bytes(
+			b"day,date,row\n3,2022-01-03,0\n,,1\n1,2022-01-01,2\n2,2022-01-02,3\n,,4\n2,2022-01-02,5\n",
+			Default::default(),
+			&mut |_| {},
+		)
+		.unwrap();
+		let rows = |table: &Table| match &table.columns()[2] {
+			TableColumn::Number(column) => column.view().as_slice().to_owned(),
+			_ => unreachable!(),
+		};
+		// Rows with a missing value come first, and rows with the same value keep their order.
+		sort_table_by_column(&mut table, "day").unwrap();
+		assert_eq!(rows(&table), vec![1.0, 4.0, 2.0, 3.0, 5.0, 0.0]);
+		assert!(matches!(table.columns()[1], TableColumn::DateTime(_)));
+		sort_table_by_column(&mut table, "row").unwrap();
+		sort_table_by_column(&mut table, "date").unwrap();
+		assert_eq!(rows(&table), vec![1.0, 4.0, 2.0, 3.0, 5.0, 0.0]);
+		assert!(sort_table_by_column(&mut table, "missing").is_err());
+	}
+
+	#[test]
+	fn test_split_folds() {
+		let dates = |table: &TableView| match &table.columns()[0] {
+			TableColumnView::DateTime(column) => column
+				.as_slice()
+				.iter()
+				.map(|date| date.unwrap())
+				.collect::<Vec<_>>(),
+			_ => unreachable!(),
+		};
+		// The rows are loaded out of order, so the folds are only time ordered if the table is sorted.
+		let mut csv = "date\n".to_owned();
+		for index in 0..20 {
+			csv.push_str(&format!("2022-01-{:02}\n", (index * 7) % 20 + 1));
+		}
+		for (comparison_horizon, fold_starts) in
+			[(None, [10, 12, 14, 16]), (Some(3), [7, 10, 13, 16])]
+		{
+			let mut table =
+				Table::from_bytes(csv.as_bytes(), Default::default(), &mut |_| {}).unwrap();
+			sort_table_by_column(&mut table, "date").unwrap();
+			let dataset = Dataset::Train(DatasetTrain {
+				table,
+				comparison_fraction: 0.1,
+				comparison_horizon,
+				group_boundaries: None,
+				test_fraction: 0.2,
+			});
+			let (table_train, table_comparison, table_test) = dataset.split();
+			let test_start = dates(&table_test)[0];
+			let folds = dataset.split_folds(3);
+			assert_eq!(folds.len(), 3);
+			for (fold_index, (fold_train, fold_comparison)) in folds.iter().enumerate() {
+				// Each fold is trained on the rows before its comparison rows, which come immediately after.
+				assert_eq!(fold_train.nrows(), fold_starts[fold_index]);
+				assert_eq!(
+					fold_comparison.nrows(),
+					fold_starts[fold_index + 1] - fold_starts[fold_index]
+				);
+				let train_dates = dates(fold_train);
+				let comparison_dates = dates(fold_comparison);
+				assert!(train_dates.windows(2).all(|window| window[0] < window[1]));
+				assert!(train_dates.last().unwrap() < comparison_dates.first().unwrap());
+				assert!(comparison_dates
+					.windows(2)
+					.all(|window| window[0] < window[1]));
+				// No fold compares on the test rows.
+				assert!(*comparison_dates.last().unwrap() < test_start);
+			}
+			let (last_fold_train, last_fold_comparison) = folds.last().unwrap();
+			assert_eq!(last_fold_train.nrows(), table_train.nrows());
+			assert_eq!(dates(last_fold_comparison), dates(&table_comparison));
+		}
+	}
+}
@@ -0,0 +1,291 @@
+/*!
+This module converts `.modelfox` files to the format [`crate::Model::from_bytes`] reads. It requires the standard library, so run it ahead of time, for example in a build script, and embed the bytes it produces.
+*/
+
+use crate::write::Writer;
+use anyhow::{anyhow, bail, Result};
+use modelfox_model::{
+	BinaryClassificationModelReader, ColumnStatsReader, FeatureGroupReader, ModelInnerReader,
+	MulticlassClassificationModelReader, RegressionModelReader,
+};
+use std::path::Path;
+
+/// Read the `.modelfox` file at `path` and convert it.
+pub fn convert_path(path: &Path) -> Result<Vec<u8>> {
+	let bytes = std::fs::read(path)?;
+	convert(&bytes)
+}
+
+/// Convert the bytes of a `.modelfox` file. This fails if the model uses features this crate does not support, like text or date time features.
+pub fn convert(bytes: &[u8]) -> Result<Vec<u8>> {
+	let model = modelfox_model::from_bytes(bytes)?;
+	let mut writer = Writer::new();
+	writer.write_header();
+	match model.inner() {
+		ModelInnerReader::Regressor(regressor) => {
+			let regressor = regressor.read();
+			writer.write_u8(0);
+			let column_names =
+				write_columns(&mut writer, regressor.train_column_stats().iter().collect());
+			match regressor.model() {
+				RegressionModelReader::Linear(model) => {
+					let model = model.read();
+					write_feature_groups(
+						&mut writer,
+						&column_names,
+						model.feature_groups().iter().collect(),
+					)?;
+					let model = modelfox_linear::Regressor::from_reader(model.model());
+					write_linear_model(&mut writer, &[model.bias], model.weights.iter());
+				}
+				RegressionModelReader::Tree(model) => {
+					let model = model.read();
+					write_feature_groups(
+						&mut writer,
+						&column_names,
+						model.feature_groups().iter().collect(),
+					)?;
+					let model = modelfox_tree::Regressor::from_reader(model.model());
+					write_tree_model(
+						&mut writer,
+						&[model.bias],
+						model.trees.len(),
+						model.trees.iter(),
+					);
+				}
+			}
+		}
+		ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			let binary_classifier = binary_classifier.read();
+			writer.write_u8(1);
+			writer.write_str(binary_classifier.negative_class());
+			writer.write_str(binary_classifier.positive_class());
+			let column_names = write_columns(
+				&mut writer,
+				binary_classifier.train_column_stats().iter().collect(),
+			);
+			match binary_classifier.model() {
+				BinaryClassificationModelReader::Linear(model) => {
+					let model = model.read();
+					write_feature_groups(
+						&mut writer,
+						&column_names,
+						model.feature_groups().iter().collect(),
+					)?;
+					let model = modelfox_linear::BinaryClassifier::from_reader(model.model());
+					write_linear_model(&mut writer, &[model.bias], model.weights.iter());
+				}
+				BinaryClassificationModelReader::Tree(model) => {
+					let model = model.read();
+					write_feature_groups(
+						&mut writer,
+						&column_names,
+						model.feature_groups().iter().collect(),
+					)?;
+					let model = modelfox_tree::BinaryClassifier::from_reader(model.model());
+					write_tree_model(
+						&mut writer,
+						&[model.bias],
+						model.trees.len(),
+						model.trees.iter(),
+					);
+				}
+			}
+		}
+		ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier = multiclass_classifier.read();
+			writer.write_u8(2);
+			let classes = multiclass_classifier.classes();
+			writer.write_len(classes.len());
+			for class in classes.iter() {
+				writer.write_str(class);
+			}
+			let column_names = write_columns(
+				&mut writer,
+				multiclass_classifier.train_column_stats().iter().collect(),
+			);
+			match multiclass_classifier.model() {
+				MulticlassClassificationModelReader::Linear(model) => {
+					let model = model.read();
+					write_feature_groups(
+						&mut writer,
+						&column_names,
+						model.feature_groups().iter().collect(),
+					)?;
+					let model = modelfox_linear::MulticlassClassifier::from_reader(model.model());
+					let biases = model.biases.iter().copied().collect::<Vec<_>>();
+					// The weights have shape (n_features, n_classes), and iterating them yields them in row major order.
+					write_linear_model(&mut writer, &biases, model.weights.iter());
+				}
+				MulticlassClassificationModelReader::Tree(model) => {
+					let model = model.read();
+					write_feature_groups(
+						&mut writer,
+						&column_names,
+						model.feature_groups().iter().collect(),
+					)?;
+					let model = modelfox_tree::MulticlassClassifier::from_reader(model.model());
+					let biases = model.biases.iter().copied().collect::<Vec<_>>();
+					// The trees have shape (n_rounds, n_classes), and iterating them yields them ordered by round.
+					write_tree_model(
+						&mut writer,
+						&biases,
+						model.trees.nrows(),
+						model.trees.iter(),
+					);
+				}
+			}
+		}
+	}
+	Ok(writer.into_bytes())
+}
+
+/// Write the columns and return the names of the ones feature groups may use, so feature groups can refer to their columns by index.
+fn write_columns(writer: &mut Writer, column_stats: Vec<ColumnStatsReader>) -> Vec<Option<String>> {
+	writer.write_len(column_stats.len());
+	column_stats
+		.into_iter()
+		.map(|column_stats| {
+			let name = column_stats.column_name().to_owned();
+			match column_stats {
+				ColumnStatsReader::NumberColumn(_) => {
+					writer.write_u8(1);
+					writer.write_str(&name);
+					Some(name)
+				}
+				ColumnStatsReader::EnumColumn(column_stats) => {
+					let column_stats = column_stats.read();
+					let histogram = column_stats.histogram();
+					writer.write_u8(2);
+					writer.write_str(&name);
+					writer.write_len(histogram.len());
+					for (variant, _) in histogram.iter() {
+						writer.write_str(variant);
+					}
+					Some(name)
+				}
+				// Feature groups may not refer to these columns, but they are written so input values line up with the columns.
+				ColumnStatsReader::UnknownColumn(_)
+				| ColumnStatsReader::TextColumn(_)
+				| ColumnStatsReader::DateTimeColumn(_) => {
+					writer.write_u8(0);
+					writer.write_str(&name);
+					None
+				}
+			}
+		})
+		.collect()
+}
+
+fn write_feature_groups(
+	writer: &mut Writer,
+	column_names: &[Option<String>],
+	feature_groups: Vec<FeatureGroupReader>,
+) -> Result<()> {
+	let column_index = |source_column_name: &str| {
+		column_names
+			.iter()
+			.position(|name| name.as_deref() == Some(source_column_name))
+			.ok_or_else(|| {
+				anyhow!(
+					"The column \"{}\" has a type that is not supported.",
+					source_column_name
+				)
+			})
+	};
+	writer.write_len(feature_groups.len());
+	for feature_group in feature_groups {
+		match feature_group {
+			FeatureGroupReader::Identity(feature_group) => {
+				let feature_group = feature_group.read();
+				let column_index = column_index(feature_group.source_column_name())?;
+				writer.write_u8(0);
+				writer.write_len(column_index);
+			}
+			FeatureGroupReader::Normalized(feature_group) => {
+				let feature_group = feature_group.read();
+				let column_index = column_index(feature_group.source_column_name())?;
+				writer.write_u8(1);
+				writer.write_len(column_index);
+				writer.write_f32(feature_group.mean());
+				writer.write_f32(feature_group.variance());
+			}
+			FeatureGroupReader::OneHotEncoded(feature_group) => {
+				let feature_group = feature_group.read();
+				let column_index = column_index(feature_group.source_column_name())?;
+				writer.write_u8(2);
+				writer.write_len(column_index);
+			}
+			FeatureGroupReader::Cyclical(feature_group) => {
+				let feature_group = feature_group.read();
+				let column_index = column_index(feature_group.source_column_name())?;
+				writer.write_u8(3);
+				writer.write_len(column_index);
+				writer.write_f32(feature_group.period());
+				writer.write_f32(feature_group.offset());
+			}
+			FeatureGroupReader::BagOfWords(_)
+			| FeatureGroupReader::BagOfWordsCosineSimilarity(_)
+			| FeatureGroupReader::WordEmbedding(_) => {
+				bail!("Models with text features are not supported.")
+			}
+		}
+	}
+	Ok(())
+}
+
+fn write_linear_model<'a>(
+	writer: &mut Writer,
+	biases: &[f32],
+	weights: impl Iterator<Item = &'a f32>,
+) {
+	writer.write_u8(0);
+	writer.write_f32s(biases);
+	for weight in weights {
+		writer.write_f32(*weight);
+	}
+}
+
+fn write_tree_model<'a>(
+	writer: &mut Writer,
+	biases: &[f32],
+	n_rounds: usize,
+	trees: impl Iterator<Item = &'a modelfox_tree::Tree>,
+) {
+	writer.write_u8(1);
+	writer.write_f32s(biases);
+	writer.write_len(n_rounds);
+	for tree in trees {
+		writer.write_len(tree.nodes.len());
+		for node in tree.nodes.iter() {
+			write_node(writer, node);
+		}
+	}
+}
+
+fn write_node(writer: &mut Writer, node: &modelfox_tree::Node) {
+	match node {
+		modelfox_tree::Node::Leaf(leaf) => {
+			writer.write_u8(0);
+			writer.write_f64(leaf.value);
+		}
+		modelfox_tree::Node::Branch(branch) => match &branch.split {
+			modelfox_tree::BranchSplit::Continuous(split) => {
+				writer.write_u8(1);
+				writer.write_len(branch.left_child_index);
+				writer.write_len(branch.right_child_index);
+				writer.write_len(split.feature_index);
+				writer.write_f32(split.split_value);
+			}
+			modelfox_tree::BranchSplit::Discrete(split) => {
+				let directions = split.directions.as_raw_slice();
+				writer.write_u8(2);
+				writer.write_len(branch.left_child_index);
+				writer.write_len(branch.right_child_index);
+				writer.write_len(split.feature_index);
+				writer.write_len(directions.len());
+				writer.write_bytes(directions);
+			}
+		},
+	}
+}
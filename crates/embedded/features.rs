@@ -0,0 +1,176 @@
+/*!
+This module computes the features for a single example. It mirrors the `modelfox_features` crate for the feature groups this crate supports, and must produce the same values, because the models were trained on features computed by that crate.
+*/
+
+use crate::{tree::TreeFeature, Column, Value};
+use alloc::vec::Vec;
+
+pub(crate) enum FeatureGroup {
+	Identity {
+		column_index: usize,
+	},
+	Normalized {
+		column_index: usize,
+		mean: f32,
+		variance: f32,
+	},
+	OneHotEncoded {
+		column_index: usize,
+		n_variants: usize,
+	},
+	Cyclical {
+		column_index: usize,
+		period: f32,
+		offset: f32,
+	},
+}
+
+impl FeatureGroup {
+	pub(crate) fn n_features(&self) -> usize {
+		match self {
+			FeatureGroup::Identity { .. } => 1,
+			FeatureGroup::Normalized { .. } => 1,
+			// The first feature is for values that are not one of the variants.
+			FeatureGroup::OneHotEncoded { n_variants, .. } => n_variants + 1,
+			FeatureGroup::Cyclical { .. } => 2,
+		}
+	}
+
+	fn column_index(&self) -> usize {
+		match self {
+			FeatureGroup::Identity { column_index } => *column_index,
+			FeatureGroup::Normalized { column_index, .. } => *column_index,
+			FeatureGroup::OneHotEncoded { column_index, .. } => *column_index,
+			FeatureGroup::Cyclical { column_index, .. } => *column_index,
+		}
+	}
+}
+
+/// A `ColumnValue` is an input value converted to the type of its column, the way a value in a table is.
+#[derive(Clone, Copy)]
+pub(crate) enum ColumnValue {
+	Unsupported,
+	/// Values that are missing or could not be parsed are NaN.
+	Number(f32),
+	/// This is the index of the variant plus one, or zero if the value is not one of the variants.
+	Enum(usize),
+}
+
+impl ColumnValue {
+	pub(crate) fn new(column: &Column, value: &Value) -> ColumnValue {
+		match column {
+			Column::Unsupported { .. } => ColumnValue::Unsupported,
+			Column::Number { .. } => ColumnValue::Number(match value {
+				Value::Number(value) => *value,
+				Value::String(value) => value
+					.parse::<f32>()
+					.ok()
+					.filter(|value| value.is_finite())
+					.unwrap_or(f32::NAN),
+				Value::Missing => f32::NAN,
+			}),
+			Column::Enum { variants, .. } => ColumnValue::Enum(match value {
+				Value::String(value) => variants
+					.iter()
+					.position(|variant| variant == value)
+					.map(|index| index + 1)
+					.unwrap_or(0),
+				_ => 0,
+			}),
+		}
+	}
+
+	fn as_f32(self) -> f32 {
+		match self {
+			ColumnValue::Number(value) => value,
+			ColumnValue::Enum(value) => value as f32,
+			ColumnValue::Unsupported => f32::NAN,
+		}
+	}
+}
+
+/// Compute the features linear models take.
+pub(crate) fn compute_features_f32(
+	feature_groups: &[FeatureGroup],
+	column_values: &[ColumnValue],
+) -> Vec<f32> {
+	let mut features = Vec::new();
+	for feature_group in feature_groups {
+		let value = column_values[feature_group.column_index()];
+		match feature_group {
+			FeatureGroup::Identity { .. } => features.push(value.as_f32()),
+			FeatureGroup::Normalized { mean, variance, .. } => {
+				features.push(normalize(value.as_f32(), *mean, *variance))
+			}
+			FeatureGroup::OneHotEncoded { n_variants, .. } => {
+				let start = features.len();
+				features.resize(start + n_variants + 1, 0.0);
+				if let ColumnValue::Enum(value) = value {
+					features[start + value] = 1.0;
+				}
+			}
+			FeatureGroup::Cyclical { period, offset, .. } => {
+				let (sin, cos) = cyclical(value.as_f32(), *period, *offset);
+				features.push(sin);
+				features.push(cos);
+			}
+		}
+	}
+	features
+}
+
+/// Compute the features tree models take. Unlike linear models, tree models split on the variants of enum features directly.
+pub(crate) fn compute_features_tree(
+	feature_groups: &[FeatureGroup],
+	column_values: &[ColumnValue],
+) -> Vec<TreeFeature> {
+	let mut features = Vec::new();
+	for feature_group in feature_groups {
+		let value = column_values[feature_group.column_index()];
+		match feature_group {
+			FeatureGroup::Identity { .. } => features.push(match value {
+				ColumnValue::Enum(value) => TreeFeature::Enum(value),
+				value => TreeFeature::Number(value.as_f32()),
+			}),
+			FeatureGroup::Normalized { mean, variance, .. } => {
+				features.push(TreeFeature::Number(match value {
+					ColumnValue::Enum(0) => 0.0,
+					value => normalize(value.as_f32(), *mean, *variance),
+				}))
+			}
+			FeatureGroup::OneHotEncoded { .. } => unreachable!(),
+			FeatureGroup::Cyclical { period, offset, .. } => {
+				let (sin, cos) = cyclical(value.as_f32(), *period, *offset);
+				features.push(TreeFeature::Number(sin));
+				features.push(TreeFeature::Number(cos));
+			}
+		}
+	}
+	features
+}
+
+fn normalize(value: f32, mean: f32, variance: f32) -> f32 {
+	if value.is_nan() || variance == 0.0 {
+		0.0
+	} else {
+		(value - mean) / libm::sqrtf(variance)
+	}
+}
+
+/// This is computed in f64 like `modelfox_features::CyclicalFeatureGroup`, so the features match the ones the model was trained on.
+fn cyclical(value: f32, period: f32, offset: f32) -> (f32, f32) {
+	let period = f64::from(period);
+	if !value.is_finite() || period == 0.0 {
+		return (0.0, 0.0);
+	}
+	let position = (f64::from(value) - f64::from(offset)) / period;
+	// This is `position.rem_euclid(1.0)`, which requires std.
+	let position = position % 1.0;
+	let position = if position < 0.0 {
+		position + 1.0
+	} else {
+		position
+	};
+	let angle = position * core::f64::consts::TAU;
+	(libm::sin(angle) as f32, libm::cos(angle) as f32)
+}
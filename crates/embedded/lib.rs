@@ -0,0 +1,390 @@
+/*!
+This crate makes predictions with ModelFox models on targets without the standard library, like microcontrollers, operating system kernels, and SGX enclaves. It only needs an allocator.
+
+`.modelfox` files hold everything the app shows about how a model was trained, and reading them requires the standard library, so this crate reads models from a smaller format that holds only what is needed to make predictions. Enable the `convert` feature to produce it from a `.modelfox` file with [`convert::convert`], for example in a build script, and disable the default `std` feature when building for the target.
+
+```ignore
+static MODEL: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/heart_disease.modelfox_embedded"));
+
+let model = modelfox_embedded::Model::from_bytes(MODEL)?;
+let input = [Value::Number(63.0), Value::String("male"), Value::String("typical angina")];
+let output = model.predict(&input, &PredictOptions::default())?;
+```
+
+Input values are passed in the order of [`Model::column_names`]. Models with text or date time features are not supported yet.
+*/
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use self::{
+	features::{ColumnValue, FeatureGroup},
+	linear::LinearModel,
+	read::Reader,
+	tree::TreeModel,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt;
+
+#[cfg(feature = "convert")]
+pub mod convert;
+mod features;
+mod linear;
+mod read;
+mod tree;
+#[cfg(any(test, feature = "convert"))]
+mod write;
+
+/// A model is prefixed with this magic number followed by a 4-byte little endian revision number.
+const MAGIC_NUMBER: &[u8] = b"mfxembed";
+/// This is the revision number that this version of modelfox_embedded reads and writes.
+const CURRENT_REVISION: u32 = 0;
+
+pub struct Model {
+	columns: Vec<Column>,
+	task: Task,
+	feature_groups: Vec<FeatureGroup>,
+	inner: ModelInner,
+}
+
+enum Column {
+	/// Columns the model does not use, or uses only in features this crate does not support, are kept so the input values line up with the columns.
+	Unsupported {
+		name: String,
+	},
+	Number {
+		name: String,
+	},
+	Enum {
+		name: String,
+		variants: Vec<String>,
+	},
+}
+
+enum Task {
+	Regression,
+	BinaryClassification {
+		negative_class: String,
+		positive_class: String,
+	},
+	MulticlassClassification {
+		classes: Vec<String>,
+	},
+}
+
+enum ModelInner {
+	Linear(LinearModel),
+	Tree(TreeModel),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value<'a> {
+	Missing,
+	Number(f32),
+	String(&'a str),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PredictOptions {
+	pub threshold: f32,
+}
+
+impl Default for PredictOptions {
+	fn default() -> PredictOptions {
+		PredictOptions { threshold: 0.5 }
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Output<'a> {
+	Regression {
+		value: f32,
+	},
+	BinaryClassification {
+		class_name: &'a str,
+		probability: f32,
+	},
+	MulticlassClassification {
+		class_name: &'a str,
+		probability: f32,
+		/// This is the probability of each class, in the order of [`Model::class_names`].
+		probabilities: Vec<f32>,
+	},
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+	InvalidMagicNumber,
+	UnsupportedRevision(u32),
+	/// The model's bytes were truncated or do not describe a valid model.
+	InvalidModel,
+	WrongNumberOfValues {
+		expected: usize,
+		actual: usize,
+	},
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::InvalidMagicNumber => write!(
+				f,
+				"This model did not start with the modelfox_embedded magic number. Convert .modelfox files with modelfox_embedded::convert before reading them."
+			),
+			Error::UnsupportedRevision(revision) => write!(
+				f,
+				"This model has a revision number of {}, but this version of modelfox_embedded reads revision {}.",
+				revision, CURRENT_REVISION
+			),
+			Error::InvalidModel => write!(f, "The model is truncated or invalid."),
+			Error::WrongNumberOfValues { expected, actual } => write!(
+				f,
+				"The model has {} columns, but {} values were passed.",
+				expected, actual
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl Model {
+	pub fn from_bytes(bytes: &[u8]) -> Result<Model, Error> {
+		let mut reader = Reader::new(bytes);
+		if reader.take(MAGIC_NUMBER.len())? != MAGIC_NUMBER {
+			return Err(Error::InvalidMagicNumber);
+		}
+		let revision = reader.read_u32()?;
+		if revision != CURRENT_REVISION {
+			return Err(Error::UnsupportedRevision(revision));
+		}
+		let model = read::read_model(&mut reader)?;
+		if !reader.is_empty() {
+			return Err(Error::InvalidModel);
+		}
+		Ok(model)
+	}
+
+	/// Retrieve the names of the columns, in the order the input values are passed to [`Model::predict`].
+	pub fn column_names(&self) -> impl Iterator<Item = &str> {
+		self.columns.iter().map(|column| match column {
+			Column::Unsupported { name } => name.as_str(),
+			Column::Number { name } => name.as_str(),
+			Column::Enum { name, .. } => name.as_str(),
+		})
+	}
+
+	/// Retrieve the names of the classes for classifiers. This is empty for regressors.
+	pub fn class_names(&self) -> Vec<&str> {
+		match &self.task {
+			Task::Regression => Vec::new(),
+			Task::BinaryClassification {
+				negative_class,
+				positive_class,
+			} => vec![negative_class.as_str(), positive_class.as_str()],
+			Task::MulticlassClassification { classes } => {
+				classes.iter().map(|class| class.as_str()).collect()
+			}
+		}
+	}
+
+	/// Make a prediction. `input` has a value for each column, in the order of [`Model::column_names`].
+	pub fn predict(&self, input: &[Value], options: &PredictOptions) -> Result<Output, Error> {
+		if input.len() != self.columns.len() {
+			return Err(Error::WrongNumberOfValues {
+				expected: self.columns.len(),
+				actual: input.len(),
+			});
+		}
+		let column_values = self
+			.columns
+			.iter()
+			.zip(input.iter())
+			.map(|(column, value)| ColumnValue::new(column, value))
+			.collect::<Vec<_>>();
+		let mut logits = match &self.inner {
+			ModelInner::Linear(model) => {
+				let features = features::compute_features_f32(&self.feature_groups, &column_values);
+				model.predict(&features)
+			}
+			ModelInner::Tree(model) => {
+				let features =
+					features::compute_features_tree(&self.feature_groups, &column_values);
+				model.predict(&features)
+			}
+		};
+		let output = match &self.task {
+			Task::Regression => Output::Regression { value: logits[0] },
+			Task::BinaryClassification {
+				negative_class,
+				positive_class,
+			} => {
+				let probability = 1.0 / (libm::expf(-logits[0]) + 1.0);
+				let (probability, class_name) = if probability >= options.threshold {
+					(probability, positive_class)
+				} else {
+					(1.0 - probability, negative_class)
+				};
+				Output::BinaryClassification {
+					class_name,
+					probability,
+				}
+			}
+			Task::MulticlassClassification { classes } => {
+				softmax(&mut logits);
+				let (class_index, probability) = logits.iter().enumerate().fold(
+					(0, f32::MIN),
+					|(max_index, max), (index, probability)| {
+						if *probability > max {
+							(index, *probability)
+						} else {
+							(max_index, max)
+						}
+					},
+				);
+				Output::MulticlassClassification {
+					class_name: &classes[class_index],
+					probability,
+					probabilities: logits,
+				}
+			}
+		};
+		Ok(output)
+	}
+}
+
+fn softmax(logits: &mut [f32]) {
+	let max = logits.iter().fold(f32::MIN, |a, &b| f32::max(a, b));
+	for logit in logits.iter_mut() {
+		*logit = libm::expf(*logit - max);
+	}
+	let sum = logits.iter().sum::<f32>();
+	for logit in logits.iter_mut() {
+		*logit /= sum;
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::write::Writer;
+
+	/// Write a binary classifier with a single tree that splits on a normalized number column and then an enum column.
+	fn write_binary_classifier() -> Vec<u8> {
+		let mut writer = Writer::new();
+		writer.write_header();
+		writer.write_u8(1);
+		writer.write_str("no");
+		writer.write_str("yes");
+		writer.write_len(3);
+		writer.write_u8(1);
+		writer.write_str("age");
+		writer.write_u8(2);
+		writer.write_str("sex");
+		writer.write_len(2);
+		writer.write_str("female");
+		writer.write_str("male");
+		writer.write_u8(0);
+		writer.write_str("notes");
+		writer.write_len(2);
+		writer.write_u8(1);
+		writer.write_len(0);
+		writer.write_f32(50.0);
+		writer.write_f32(100.0);
+		writer.write_u8(0);
+		writer.write_len(1);
+		writer.write_u8(1);
+		writer.write_f32s(&[-0.5]);
+		writer.write_len(1);
+		writer.write_len(5);
+		writer.write_u8(1);
+		writer.write_len(1);
+		writer.write_len(2);
+		writer.write_len(0);
+		writer.write_f32(0.0);
+		writer.write_u8(0);
+		writer.write_f64(-1.0);
+		writer.write_u8(2);
+		writer.write_len(3);
+		writer.write_len(4);
+		writer.write_len(1);
+		writer.write_len(1);
+		writer.write_bytes(&[0b010]);
+		writer.write_u8(0);
+		writer.write_f64(1.0);
+		writer.write_u8(0);
+		writer.write_f64(2.0);
+		writer.into_bytes()
+	}
+
+	#[test]
+	fn test_predict() {
+		let bytes = write_binary_classifier();
+		let model = Model::from_bytes(&bytes).unwrap();
+		assert_eq!(
+			model.column_names().collect::<Vec<_>>(),
+			vec!["age", "sex", "notes"]
+		);
+		assert_eq!(model.class_names(), vec!["no", "yes"]);
+		let options = PredictOptions::default();
+		let output = model
+			.predict(
+				&[
+					Value::Number(60.0),
+					Value::String("female"),
+					Value::String("anything"),
+				],
+				&options,
+			)
+			.unwrap();
+		match output {
+			Output::BinaryClassification {
+				class_name,
+				probability,
+			} => {
+				assert_eq!(class_name, "yes");
+				assert!((probability - 0.817_574_5).abs() < 1e-6);
+			}
+			_ => panic!(),
+		}
+		// Values that are missing or not one of the variants are sent the same way they were in training.
+		let output = model
+			.predict(
+				&[Value::String("forty"), Value::Missing, Value::Missing],
+				&options,
+			)
+			.unwrap();
+		match output {
+			Output::BinaryClassification {
+				class_name,
+				probability,
+			} => {
+				assert_eq!(class_name, "no");
+				assert!((probability - 0.817_574_5).abs() < 1e-6);
+			}
+			_ => panic!(),
+		}
+		assert_eq!(
+			model.predict(&[Value::Number(60.0)], &options),
+			Err(Error::WrongNumberOfValues {
+				expected: 3,
+				actual: 1
+			})
+		);
+	}
+
+	#[test]
+	fn test_invalid_model() {
+		let bytes = write_binary_classifier();
+		assert!(matches!(
+			Model::from_bytes(&bytes[..bytes.len() - 1]),
+			Err(Error::InvalidModel)
+		));
+		assert!(matches!(
+			Model::from_bytes(b"tangram\0\0\0\0\0"),
+			Err(Error::InvalidMagicNumber)
+		));
+	}
+}
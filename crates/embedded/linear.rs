@@ -0,0 +1,20 @@
+use alloc::vec::Vec;
+
+pub(crate) struct LinearModel {
+	pub biases: Vec<f32>,
+	/// These are the weights for each feature and output, stored as a 2d array of shape (n_features, n_outputs) in row major order.
+	pub weights: Vec<f32>,
+}
+
+impl LinearModel {
+	pub(crate) fn predict(&self, features: &[f32]) -> Vec<f32> {
+		let mut logits = self.biases.clone();
+		let n_outputs = logits.len();
+		for (feature, weights) in features.iter().zip(self.weights.chunks_exact(n_outputs)) {
+			for (logit, weight) in logits.iter_mut().zip(weights.iter()) {
+				*logit += feature * weight;
+			}
+		}
+		logits
+	}
+}
@@ -0,0 +1,258 @@
+use crate::{
+	features::FeatureGroup,
+	linear::LinearModel,
+	tree::{Node, Tree, TreeModel},
+	Column, Error, Model, ModelInner, Task,
+};
+use alloc::{string::String, vec::Vec};
+
+/// A `Reader` reads the little endian values a model is made of from the front of a byte slice. Every read fails with [`Error::InvalidModel`] if the bytes run out, so a truncated model is never read past its end.
+pub(crate) struct Reader<'a> {
+	bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+	pub(crate) fn new(bytes: &'a [u8]) -> Reader<'a> {
+		Reader { bytes }
+	}
+
+	pub(crate) fn is_empty(&self) -> bool {
+		self.bytes.is_empty()
+	}
+
+	pub(crate) fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+		if len > self.bytes.len() {
+			return Err(Error::InvalidModel);
+		}
+		let (bytes, rest) = self.bytes.split_at(len);
+		self.bytes = rest;
+		Ok(bytes)
+	}
+
+	fn read_u8(&mut self) -> Result<u8, Error> {
+		Ok(self.take(1)?[0])
+	}
+
+	pub(crate) fn read_u32(&mut self) -> Result<u32, Error> {
+		Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+	}
+
+	fn read_len(&mut self) -> Result<usize, Error> {
+		usize::try_from(self.read_u32()?).map_err(|_| Error::InvalidModel)
+	}
+
+	fn read_f32(&mut self) -> Result<f32, Error> {
+		Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+	}
+
+	fn read_f64(&mut self) -> Result<f64, Error> {
+		Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+	}
+
+	fn read_f32s(&mut self, len: usize) -> Result<Vec<f32>, Error> {
+		let n_bytes = len.checked_mul(4).ok_or(Error::InvalidModel)?;
+		Ok(self
+			.take(n_bytes)?
+			.chunks_exact(4)
+			.map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+			.collect())
+	}
+
+	fn read_string(&mut self) -> Result<String, Error> {
+		let len = self.read_len()?;
+		let bytes = self.take(len)?;
+		core::str::from_utf8(bytes)
+			.map(String::from)
+			.map_err(|_| Error::InvalidModel)
+	}
+}
+
+pub(crate) fn read_model(reader: &mut Reader) -> Result<Model, Error> {
+	let task = read_task(reader)?;
+	let n_columns = reader.read_len()?;
+	let columns = (0..n_columns)
+		.map(|_| read_column(reader))
+		.collect::<Result<Vec<_>, _>>()?;
+	let n_feature_groups = reader.read_len()?;
+	let feature_groups = (0..n_feature_groups)
+		.map(|_| read_feature_group(reader, &columns))
+		.collect::<Result<Vec<_>, _>>()?;
+	let n_features = feature_groups
+		.iter()
+		.map(|feature_group| feature_group.n_features())
+		.sum::<usize>();
+	let n_outputs = match &task {
+		Task::Regression | Task::BinaryClassification { .. } => 1,
+		Task::MulticlassClassification { classes } => classes.len(),
+	};
+	let inner = match reader.read_u8()? {
+		0 => ModelInner::Linear(read_linear_model(reader, n_features, n_outputs)?),
+		1 => {
+			// Tree models take the values of enum features as they are rather than one hot encoded.
+			if feature_groups
+				.iter()
+				.any(|feature_group| matches!(feature_group, FeatureGroup::OneHotEncoded { .. }))
+			{
+				return Err(Error::InvalidModel);
+			}
+			ModelInner::Tree(read_tree_model(reader, n_features, n_outputs)?)
+		}
+		_ => return Err(Error::InvalidModel),
+	};
+	Ok(Model {
+		columns,
+		task,
+		feature_groups,
+		inner,
+	})
+}
+
+fn read_task(reader: &mut Reader) -> Result<Task, Error> {
+	let task = match reader.read_u8()? {
+		0 => Task::Regression,
+		1 => Task::BinaryClassification {
+			negative_class: reader.read_string()?,
+			positive_class: reader.read_string()?,
+		},
+		2 => {
+			let n_classes = reader.read_len()?;
+			if n_classes == 0 {
+				return Err(Error::InvalidModel);
+			}
+			let classes = (0..n_classes)
+				.map(|_| reader.read_string())
+				.collect::<Result<Vec<_>, _>>()?;
+			Task::MulticlassClassification { classes }
+		}
+		_ => return Err(Error::InvalidModel),
+	};
+	Ok(task)
+}
+
+fn read_column(reader: &mut Reader) -> Result<Column, Error> {
+	let column = match reader.read_u8()? {
+		0 => Column::Unsupported {
+			name: reader.read_string()?,
+		},
+		1 => Column::Number {
+			name: reader.read_string()?,
+		},
+		2 => {
+			let name = reader.read_string()?;
+			let n_variants = reader.read_len()?;
+			let variants = (0..n_variants)
+				.map(|_| reader.read_string())
+				.collect::<Result<Vec<_>, _>>()?;
+			Column::Enum { name, variants }
+		}
+		_ => return Err(Error::InvalidModel),
+	};
+	Ok(column)
+}
+
+fn read_feature_group(reader: &mut Reader, columns: &[Column]) -> Result<FeatureGroup, Error> {
+	let tag = reader.read_u8()?;
+	let column_index = reader.read_len()?;
+	let column = columns.get(column_index).ok_or(Error::InvalidModel)?;
+	let feature_group = match (tag, column) {
+		(0, Column::Number { .. } | Column::Enum { .. }) => FeatureGroup::Identity { column_index },
+		(1, Column::Number { .. } | Column::Enum { .. }) => FeatureGroup::Normalized {
+			column_index,
+			mean: reader.read_f32()?,
+			variance: reader.read_f32()?,
+		},
+		(2, Column::Enum { variants, .. }) => FeatureGroup::OneHotEncoded {
+			column_index,
+			n_variants: variants.len(),
+		},
+		(3, Column::Number { .. }) => FeatureGroup::Cyclical {
+			column_index,
+			period: reader.read_f32()?,
+			offset: reader.read_f32()?,
+		},
+		_ => return Err(Error::InvalidModel),
+	};
+	Ok(feature_group)
+}
+
+fn read_linear_model(
+	reader: &mut Reader,
+	n_features: usize,
+	n_outputs: usize,
+) -> Result<LinearModel, Error> {
+	let biases = reader.read_f32s(n_outputs)?;
+	let n_weights = n_features
+		.checked_mul(n_outputs)
+		.ok_or(Error::InvalidModel)?;
+	let weights = reader.read_f32s(n_weights)?;
+	Ok(LinearModel { biases, weights })
+}
+
+fn read_tree_model(
+	reader: &mut Reader,
+	n_features: usize,
+	n_outputs: usize,
+) -> Result<TreeModel, Error> {
+	let biases = reader.read_f32s(n_outputs)?;
+	let n_rounds = reader.read_len()?;
+	let n_trees = n_rounds.checked_mul(n_outputs).ok_or(Error::InvalidModel)?;
+	let trees = (0..n_trees)
+		.map(|_| read_tree(reader, n_features))
+		.collect::<Result<Vec<_>, _>>()?;
+	Ok(TreeModel { biases, trees })
+}
+
+fn read_tree(reader: &mut Reader, n_features: usize) -> Result<Tree, Error> {
+	let n_nodes = reader.read_len()?;
+	if n_nodes == 0 {
+		return Err(Error::InvalidModel);
+	}
+	let nodes = (0..n_nodes)
+		.map(|node_index| read_node(reader, node_index, n_nodes, n_features))
+		.collect::<Result<Vec<_>, _>>()?;
+	Ok(Tree { nodes })
+}
+
+/// Children are always stored after their parents, so checking that each child index is greater than its parent's guarantees that every prediction reaches a leaf.
+fn read_node(
+	reader: &mut Reader,
+	node_index: usize,
+	n_nodes: usize,
+	n_features: usize,
+) -> Result<Node, Error> {
+	let tag = reader.read_u8()?;
+	if tag == 0 {
+		return Ok(Node::Leaf {
+			value: reader.read_f64()?,
+		});
+	}
+	let left_child_index = reader.read_len()?;
+	let right_child_index = reader.read_len()?;
+	let feature_index = reader.read_len()?;
+	let valid_child_index = |child_index: usize| node_index < child_index && child_index < n_nodes;
+	if !valid_child_index(left_child_index)
+		|| !valid_child_index(right_child_index)
+		|| feature_index >= n_features
+	{
+		return Err(Error::InvalidModel);
+	}
+	let node = match tag {
+		1 => Node::Continuous {
+			left_child_index,
+			right_child_index,
+			feature_index,
+			split_value: reader.read_f32()?,
+		},
+		2 => {
+			let n_bytes = reader.read_len()?;
+			Node::Discrete {
+				left_child_index,
+				right_child_index,
+				feature_index,
+				directions: reader.take(n_bytes)?.to_vec(),
+			}
+		}
+		_ => return Err(Error::InvalidModel),
+	};
+	Ok(node)
+}
@@ -0,0 +1,95 @@
+use alloc::vec::Vec;
+
+/// A tree model holds a tree for each output in each round of training, ordered by round.
+pub(crate) struct TreeModel {
+	pub biases: Vec<f32>,
+	pub trees: Vec<Tree>,
+}
+
+pub(crate) struct Tree {
+	pub nodes: Vec<Node>,
+}
+
+pub(crate) enum Node {
+	Leaf {
+		value: f64,
+	},
+	/// Examples whose feature value is less than or equal to `split_value` are sent left.
+	Continuous {
+		left_child_index: usize,
+		right_child_index: usize,
+		feature_index: usize,
+		split_value: f32,
+	},
+	/// Examples are sent right if the bit for their enum feature value is set in `directions`, which is stored least significant bit first.
+	Discrete {
+		left_child_index: usize,
+		right_child_index: usize,
+		feature_index: usize,
+		directions: Vec<u8>,
+	},
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum TreeFeature {
+	Number(f32),
+	Enum(usize),
+}
+
+impl TreeModel {
+	pub(crate) fn predict(&self, features: &[TreeFeature]) -> Vec<f32> {
+		let mut logits = self.biases.clone();
+		let n_outputs = logits.len();
+		for (tree_index, tree) in self.trees.iter().enumerate() {
+			logits[tree_index % n_outputs] += tree.predict(features);
+		}
+		logits
+	}
+}
+
+impl Tree {
+	fn predict(&self, features: &[TreeFeature]) -> f32 {
+		let mut node_index = 0;
+		loop {
+			match &self.nodes[node_index] {
+				Node::Leaf { value } => return *value as f32,
+				Node::Continuous {
+					left_child_index,
+					right_child_index,
+					feature_index,
+					split_value,
+				} => {
+					let value = match features[*feature_index] {
+						TreeFeature::Number(value) => value,
+						TreeFeature::Enum(value) => value as f32,
+					};
+					node_index = if value <= *split_value {
+						*left_child_index
+					} else {
+						*right_child_index
+					};
+				}
+				Node::Discrete {
+					left_child_index,
+					right_child_index,
+					feature_index,
+					directions,
+				} => {
+					let bin_index = match features[*feature_index] {
+						TreeFeature::Enum(value) => value,
+						TreeFeature::Number(_) => 0,
+					};
+					let right = directions
+						.get(bin_index / 8)
+						.map(|byte| (byte >> (bin_index % 8)) & 1 == 1)
+						.unwrap_or(false);
+					node_index = if right {
+						*right_child_index
+					} else {
+						*left_child_index
+					};
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,57 @@
+use alloc::vec::Vec;
+
+/// A `Writer` writes the values a model is made of in the format [`crate::read::Reader`] reads.
+pub(crate) struct Writer {
+	bytes: Vec<u8>,
+}
+
+impl Writer {
+	pub(crate) fn new() -> Writer {
+		Writer { bytes: Vec::new() }
+	}
+
+	pub(crate) fn into_bytes(self) -> Vec<u8> {
+		self.bytes
+	}
+
+	pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+		self.bytes.extend_from_slice(bytes);
+	}
+
+	pub(crate) fn write_u8(&mut self, value: u8) {
+		self.bytes.push(value);
+	}
+
+	pub(crate) fn write_u32(&mut self, value: u32) {
+		self.write_bytes(&value.to_le_bytes());
+	}
+
+	pub(crate) fn write_len(&mut self, len: usize) {
+		self.write_u32(u32::try_from(len).unwrap());
+	}
+
+	pub(crate) fn write_f32(&mut self, value: f32) {
+		self.write_bytes(&value.to_le_bytes());
+	}
+
+	pub(crate) fn write_f64(&mut self, value: f64) {
+		self.write_bytes(&value.to_le_bytes());
+	}
+
+	pub(crate) fn write_f32s(&mut self, values: &[f32]) {
+		for value in values {
+			self.write_f32(*value);
+		}
+	}
+
+	pub(crate) fn write_str(&mut self, value: &str) {
+		self.write_len(value.len());
+		self.write_bytes(value.as_bytes());
+	}
+
+	/// Write the magic number and revision that every model starts with.
+	pub(crate) fn write_header(&mut self) {
+		self.write_bytes(crate::MAGIC_NUMBER);
+		self.write_u32(crate::CURRENT_REVISION);
+	}
+}
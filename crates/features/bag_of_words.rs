@@ -5,8 +5,10 @@ use modelfox_table::{
 	NumberTableColumn, TableColumn, TableColumnView, TableValue, TextTableColumnView,
 };
 use modelfox_text::{NGram, NGramType, Tokenizer};
+use modelfox_zip::zip;
 use ndarray::prelude::*;
 use num::ToPrimitive;
+use std::sync::Arc;
 
 /**
 A BagOfWordsFeatureGroup creates features for a text column using the [Bag of Words](https://en.wikipedia.org/wiki/Bag-of-words_model) method.
@@ -21,8 +23,8 @@ pub struct BagOfWordsFeatureGroup {
 	pub tokenizer: Tokenizer,
 	/// These are the ngram types used to create features.
 	pub ngram_types: FnvHashSet<NGramType>,
-	/// These are the ngrams, one for each feature in this feature group.
-	pub ngrams: IndexMap<NGram, BagOfWordsFeatureGroupNGramEntry, FnvBuildHasher>,
+	/// These are the ngrams, one for each feature in this feature group. They are in an `Arc` so models with the same vocabulary can share one copy of it.
+	pub ngrams: Arc<IndexMap<NGram, BagOfWordsFeatureGroupNGramEntry, FnvBuildHasher>>,
 }
 
 #[derive(Clone, Debug)]
@@ -32,7 +34,7 @@ pub enum BagOfWordsFeatureGroupStrategy {
 	TfIdf,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct BagOfWordsFeatureGroupNGramEntry {
 	pub idf: f32,
 }
@@ -47,6 +49,7 @@ impl BagOfWordsFeatureGroup {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(_) => unimplemented!(),
 			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::DateTime(_) => unimplemented!(),
 			TableColumnView::Text(column) => {
 				self.compute_table_for_text_column(column, &|| progress(1))
 			}
@@ -63,6 +66,7 @@ impl BagOfWordsFeatureGroup {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(_) => unimplemented!(),
 			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::DateTime(_) => unimplemented!(),
 			TableColumnView::Text(column) => {
 				self.compute_array_f32_for_text_column(features, column, progress)
 			}
@@ -79,6 +83,7 @@ impl BagOfWordsFeatureGroup {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(_) => unimplemented!(),
 			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::DateTime(_) => unimplemented!(),
 			TableColumnView::Text(column) => {
 				self.compute_array_value_for_text_column(features, column, progress)
 			}
@@ -87,69 +92,80 @@ impl BagOfWordsFeatureGroup {
 }
 
 impl BagOfWordsFeatureGroup {
+	/// Compute the feature values for a single example. `feature_values` must have one entry per ngram and be filled with zeros. Training and prediction both compute their features with this function, so the values a model sees at prediction time always match the ones it was trained on.
+	pub fn compute_example(&self, value: &str, feature_values: &mut [f32]) {
+		// Set the feature value for each token for this example.
+		let unigram_iter = if self.ngram_types.contains(&NGramType::Unigram) {
+			Some(
+				self.tokenizer
+					.tokenize(value)
+					.map(modelfox_text::NGramRef::Unigram),
+			)
+		} else {
+			None
+		};
+		let bigram_iter = if self.ngram_types.contains(&NGramType::Bigram) {
+			Some(
+				self.tokenizer
+					.tokenize(value)
+					.tuple_windows()
+					.map(|(token_a, token_b)| modelfox_text::NGramRef::Bigram(token_a, token_b)),
+			)
+		} else {
+			None
+		};
+		let ngram_iter = unigram_iter
+			.into_iter()
+			.flatten()
+			.chain(bigram_iter.into_iter().flatten());
+		for ngram in ngram_iter {
+			if let Some((ngram_index, _, ngram_entry)) = self.ngrams.get_full(&ngram) {
+				match self.strategy {
+					BagOfWordsFeatureGroupStrategy::Present => {
+						let feature_value = 1.0;
+						feature_values[ngram_index] = feature_value;
+					}
+					BagOfWordsFeatureGroupStrategy::Count => {
+						let feature_value = 1.0;
+						feature_values[ngram_index] += feature_value;
+					}
+					BagOfWordsFeatureGroupStrategy::TfIdf => {
+						let feature_value = 1.0 * ngram_entry.idf;
+						feature_values[ngram_index] += feature_value;
+					}
+				}
+			}
+		}
+		if matches!(self.strategy, BagOfWordsFeatureGroupStrategy::TfIdf) {
+			// Normalize the feature values for this example.
+			let feature_values_sum_of_squares = feature_values
+				.iter()
+				.map(|value| value.to_f64().unwrap() * value.to_f64().unwrap())
+				.sum::<f64>();
+			if feature_values_sum_of_squares > 0.0 {
+				let norm = feature_values_sum_of_squares.sqrt();
+				for feature_value in feature_values.iter_mut() {
+					*feature_value /= norm.to_f32().unwrap();
+				}
+			}
+		}
+	}
+
 	fn compute_table_for_text_column(
 		&self,
 		column: TextTableColumnView,
 		progress: &impl Fn(),
 	) -> Vec<TableColumn> {
 		let mut feature_columns = vec![vec![0.0; column.len()]; self.ngrams.len()];
+		let mut feature_values = vec![0.0; self.ngrams.len()];
 		// Compute the feature values for each example.
 		for (example_index, value) in column.iter().enumerate() {
-			// Set the feature value for each token for this example.
-			let unigram_iter = if self.ngram_types.contains(&NGramType::Unigram) {
-				Some(
-					self.tokenizer
-						.tokenize(value)
-						.map(modelfox_text::NGramRef::Unigram),
-				)
-			} else {
-				None
-			};
-			let bigram_iter =
-				if self.ngram_types.contains(&NGramType::Bigram) {
-					Some(self.tokenizer.tokenize(value).tuple_windows().map(
-						|(token_a, token_b)| modelfox_text::NGramRef::Bigram(token_a, token_b),
-					))
-				} else {
-					None
-				};
-			let ngram_iter = unigram_iter
-				.into_iter()
-				.flatten()
-				.chain(bigram_iter.into_iter().flatten());
-			for ngram in ngram_iter {
-				if let Some((ngram_index, _, ngram_entry)) = self.ngrams.get_full(&ngram) {
-					match self.strategy {
-						BagOfWordsFeatureGroupStrategy::Present => {
-							let feature_value = 1.0;
-							feature_columns[ngram_index][example_index] = feature_value;
-						}
-						BagOfWordsFeatureGroupStrategy::Count => {
-							let feature_value = 1.0;
-							feature_columns[ngram_index][example_index] += feature_value;
-						}
-						BagOfWordsFeatureGroupStrategy::TfIdf => {
-							let feature_value = 1.0 * ngram_entry.idf;
-							feature_columns[ngram_index][example_index] += feature_value;
-						}
-					}
-				}
-			}
-			if matches!(self.strategy, BagOfWordsFeatureGroupStrategy::TfIdf) {
-				let mut feature_values_sum_of_squares = 0.0;
-				#[allow(clippy::needless_range_loop)]
-				for ngram_index in 0..self.ngrams.len() {
-					let value = feature_columns[ngram_index][example_index];
-					feature_values_sum_of_squares +=
-						value.to_f64().unwrap() * value.to_f64().unwrap();
-				}
-				// Normalize the feature values for this example.
-				if feature_values_sum_of_squares > 0.0 {
-					let norm = feature_values_sum_of_squares.sqrt();
-					for feature_column in feature_columns.iter_mut() {
-						feature_column[example_index] /= norm.to_f32().unwrap();
-					}
-				}
+			feature_values.fill(0.0);
+			self.compute_example(value, &mut feature_values);
+			for (feature_column, feature_value) in
+				zip!(feature_columns.iter_mut(), feature_values.iter())
+			{
+				feature_column[example_index] = *feature_value;
 			}
 			progress();
 		}
@@ -165,66 +181,16 @@ impl BagOfWordsFeatureGroup {
 		column: TextTableColumnView,
 		progress: &impl Fn(),
 	) {
-		// Fill the features with zeros.
-		features.fill(0.0);
+		let mut feature_values = vec![0.0; self.ngrams.len()];
 		// Compute the feature values for each example.
 		for (example_index, value) in column.iter().enumerate() {
-			// Set the feature value for each token for this example.
-			let unigram_iter = if self.ngram_types.contains(&NGramType::Unigram) {
-				Some(
-					self.tokenizer
-						.tokenize(value)
-						.map(modelfox_text::NGramRef::Unigram),
-				)
-			} else {
-				None
-			};
-			let bigram_iter =
-				if self.ngram_types.contains(&NGramType::Bigram) {
-					Some(self.tokenizer.tokenize(value).tuple_windows().map(
-						|(token_a, token_b)| modelfox_text::NGramRef::Bigram(token_a, token_b),
-					))
-				} else {
-					None
-				};
-			let ngram_iter = unigram_iter
-				.into_iter()
-				.flatten()
-				.chain(bigram_iter.into_iter().flatten());
-			for ngram in ngram_iter {
-				if let Some((ngram_index, _, ngram_entry)) = self.ngrams.get_full(&ngram) {
-					match self.strategy {
-						BagOfWordsFeatureGroupStrategy::Present => {
-							let feature_value = 1.0;
-							*features.get_mut([example_index, ngram_index]).unwrap() =
-								feature_value;
-						}
-						BagOfWordsFeatureGroupStrategy::Count => {
-							let feature_value = 1.0;
-							*features.get_mut([example_index, ngram_index]).unwrap() +=
-								feature_value;
-						}
-						BagOfWordsFeatureGroupStrategy::TfIdf => {
-							let feature_value = 1.0 * ngram_entry.idf;
-							*features.get_mut([example_index, ngram_index]).unwrap() +=
-								feature_value;
-						}
-					}
-				}
-			}
-			if matches!(self.strategy, BagOfWordsFeatureGroupStrategy::TfIdf) {
-				// Normalize the feature values for this example.
-				let feature_values_sum_of_squares = features
-					.row(example_index)
-					.iter()
-					.map(|value| value.to_f64().unwrap() * value.to_f64().unwrap())
-					.sum::<f64>();
-				if feature_values_sum_of_squares > 0.0 {
-					let norm = feature_values_sum_of_squares.sqrt();
-					for feature in features.row_mut(example_index).iter_mut() {
-						*feature /= norm.to_f32().unwrap();
-					}
-				}
+			feature_values.fill(0.0);
+			self.compute_example(value, &mut feature_values);
+			for (feature, feature_value) in zip!(
+				features.row_mut(example_index).iter_mut(),
+				feature_values.iter()
+			) {
+				*feature = *feature_value;
 			}
 			progress();
 		}
@@ -236,82 +202,120 @@ impl BagOfWordsFeatureGroup {
 		column: TextTableColumnView,
 		progress: &impl Fn(),
 	) {
-		// Fill the features with zeros.
-		for feature in features.iter_mut() {
-			*feature = TableValue::Number(0.0);
-		}
+		let mut feature_values = vec![0.0; self.ngrams.len()];
 		// Compute the feature values for each example.
 		for (example_index, value) in column.iter().enumerate() {
-			// Set the feature value for each token for this example.
-			let unigram_iter = if self.ngram_types.contains(&NGramType::Unigram) {
-				Some(
-					self.tokenizer
-						.tokenize(value)
-						.map(modelfox_text::NGramRef::Unigram),
-				)
-			} else {
-				None
-			};
-			let bigram_iter =
-				if self.ngram_types.contains(&NGramType::Bigram) {
-					Some(self.tokenizer.tokenize(value).tuple_windows().map(
-						|(token_a, token_b)| modelfox_text::NGramRef::Bigram(token_a, token_b),
-					))
-				} else {
-					None
-				};
-			let ngram_iter = unigram_iter
-				.into_iter()
-				.flatten()
-				.chain(bigram_iter.into_iter().flatten());
-			for ngram in ngram_iter {
-				if let Some((ngram_index, _, ngram_entry)) = self.ngrams.get_full(&ngram) {
-					match self.strategy {
-						BagOfWordsFeatureGroupStrategy::Present => {
-							let feature_value = 1.0;
-							*features
-								.get_mut([example_index, ngram_index])
-								.unwrap()
-								.as_number_mut()
-								.unwrap() = feature_value;
-						}
-						BagOfWordsFeatureGroupStrategy::Count => {
-							let feature_value = 1.0;
-							*features
-								.get_mut([example_index, ngram_index])
-								.unwrap()
-								.as_number_mut()
-								.unwrap() += feature_value;
-						}
-						BagOfWordsFeatureGroupStrategy::TfIdf => {
-							let feature_value = 1.0 * ngram_entry.idf;
-							*features
-								.get_mut([example_index, ngram_index])
-								.unwrap()
-								.as_number_mut()
-								.unwrap() += feature_value;
-						}
-					}
-				}
+			feature_values.fill(0.0);
+			self.compute_example(value, &mut feature_values);
+			for (feature, feature_value) in zip!(
+				features.row_mut(example_index).iter_mut(),
+				feature_values.iter()
+			) {
+				*feature = TableValue::Number(*feature_value);
 			}
-			if matches!(self.strategy, BagOfWordsFeatureGroupStrategy::TfIdf) {
-				// Normalize the feature values for this example.
-				let feature_values_sum_of_squares = features
-					.row(example_index)
-					.iter()
-					.map(|value| {
-						value.as_number().unwrap().to_f64().unwrap()
-							* value.as_number().unwrap().to_f64().unwrap()
-					})
-					.sum::<f64>();
-				if feature_values_sum_of_squares > 0.0 {
-					let norm = feature_values_sum_of_squares.sqrt();
-					for feature in features.row_mut(example_index).iter_mut() {
-						*feature.as_number_mut().unwrap() /= norm.to_f32().unwrap();
-					}
+			progress();
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use modelfox_table::TextTableColumn;
+
+	fn feature_group(strategy: BagOfWordsFeatureGroupStrategy) -> BagOfWordsFeatureGroup {
+		BagOfWordsFeatureGroup {
+			source_column_name: "text".to_owned(),
+			strategy,
+			tokenizer: Tokenizer::default(),
+			ngram_types: vec![NGramType::Unigram, NGramType::Bigram]
+				.into_iter()
+				.collect(),
+			ngrams: Arc::new(
+				vec![
+					(
+						NGram::Unigram("hello".to_owned()),
+						BagOfWordsFeatureGroupNGramEntry { idf: 1.5 },
+					),
+					(
+						NGram::Unigram("world".to_owned()),
+						BagOfWordsFeatureGroupNGramEntry { idf: 2.0 },
+					),
+					(
+						NGram::Bigram("hello".to_owned(), "world".to_owned()),
+						BagOfWordsFeatureGroupNGramEntry { idf: 3.0 },
+					),
+				]
+				.into_iter()
+				.collect(),
+			),
+		}
+	}
+
+	#[test]
+	fn test_compute_example() {
+		let value = "Hello hello world";
+		let mut feature_values = vec![0.0; 3];
+		feature_group(BagOfWordsFeatureGroupStrategy::Present)
+			.compute_example(value, &mut feature_values);
+		assert_eq!(feature_values, vec![1.0, 1.0, 1.0]);
+		let mut feature_values = vec![0.0; 3];
+		feature_group(BagOfWordsFeatureGroupStrategy::Count)
+			.compute_example(value, &mut feature_values);
+		assert_eq!(feature_values, vec![2.0, 1.0, 1.0]);
+		let mut feature_values = vec![0.0; 3];
+		feature_group(BagOfWordsFeatureGroupStrategy::TfIdf)
+			.compute_example(value, &mut feature_values);
+		let norm = (3.0f32 * 3.0 + 2.0 * 2.0 + 3.0 * 3.0).sqrt();
+		let expected = [3.0 / norm, 2.0 / norm, 3.0 / norm];
+		for (value, expected_value) in zip!(feature_values.iter(), expected.iter()) {
+			assert!((value - expected_value).abs() < 1e-6);
+		}
+	}
+
+	#[test]
+	fn test_compute_parity() {
+		let column = TextTableColumn::new(
+			Some("text".to_owned()),
+			vec![
+				"hello world".to_owned(),
+				"world world hello".to_owned(),
+				"".to_owned(),
+				"goodbye".to_owned(),
+			],
+		);
+		for strategy in [
+			BagOfWordsFeatureGroupStrategy::Present,
+			BagOfWordsFeatureGroupStrategy::Count,
+			BagOfWordsFeatureGroupStrategy::TfIdf,
+		] {
+			let feature_group = feature_group(strategy);
+			let table_features =
+				feature_group.compute_table(TableColumnView::Text(column.view()), &|_| {});
+			let mut array_f32_features = Array::from_elem((4, 3), f32::NAN);
+			feature_group.compute_array_f32(
+				array_f32_features.view_mut(),
+				TableColumnView::Text(column.view()),
+				&|| {},
+			);
+			let mut array_value_features = Array::from_elem((4, 3), TableValue::Unknown);
+			feature_group.compute_array_value(
+				array_value_features.view_mut(),
+				TableColumnView::Text(column.view()),
+				&|| {},
+			);
+			for (feature_index, table_feature) in table_features.iter().enumerate() {
+				let table_feature = table_feature.as_number().unwrap();
+				for (example_index, table_value) in table_feature.iter().enumerate() {
+					let array_f32_value = array_f32_features[[example_index, feature_index]];
+					let array_value_value = array_value_features[[example_index, feature_index]]
+						.as_number()
+						.copied()
+						.unwrap();
+					assert!((table_value - array_f32_value).abs() < f32::EPSILON);
+					assert!((table_value - array_value_value).abs() < f32::EPSILON);
 				}
 			}
-			progress();
 		}
 	}
 }
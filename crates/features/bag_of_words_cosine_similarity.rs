@@ -8,6 +8,7 @@ use modelfox_table::{
 use modelfox_text::{NGram, NGramType, Tokenizer};
 use ndarray::prelude::*;
 use num::ToPrimitive;
+use std::sync::Arc;
 
 /**
 A BagOfWordsCosineSimilarityFeatureGroup creates features for comparing two text columns using the cosine similarity of the [Bag of Words](https://en.wikipedia.org/wiki/Bag-of-words_model) representation of each text column.
@@ -24,8 +25,8 @@ pub struct BagOfWordsCosineSimilarityFeatureGroup {
 	pub tokenizer: Tokenizer,
 	/// These are the ngram types used to create features.
 	pub ngram_types: FnvHashSet<NGramType>,
-	/// These are the ngrams, one for each feature in this feature group.
-	pub ngrams: IndexMap<NGram, BagOfWordsFeatureGroupNGramEntry, FnvBuildHasher>,
+	/// These are the ngrams, one for each feature in this feature group. They are in an `Arc` so models with the same vocabulary can share one copy of it.
+	pub ngrams: Arc<IndexMap<NGram, BagOfWordsFeatureGroupNGramEntry, FnvBuildHasher>>,
 }
 
 impl BagOfWordsCosineSimilarityFeatureGroup {
@@ -269,18 +270,20 @@ mod test {
 			strategy: BagOfWordsFeatureGroupStrategy::Present,
 			tokenizer: Tokenizer::default(),
 			ngram_types: vec![NGramType::Unigram].into_iter().collect(),
-			ngrams: vec![
-				(
-					NGram::Unigram("test".to_owned()),
-					BagOfWordsFeatureGroupNGramEntry { idf: 1.0 },
-				),
-				(
-					NGram::Unigram("hello".to_owned()),
-					BagOfWordsFeatureGroupNGramEntry { idf: 0.3 },
-				),
-			]
-			.into_iter()
-			.collect(),
+			ngrams: Arc::new(
+				vec![
+					(
+						NGram::Unigram("test".to_owned()),
+						BagOfWordsFeatureGroupNGramEntry { idf: 1.0 },
+					),
+					(
+						NGram::Unigram("hello".to_owned()),
+						BagOfWordsFeatureGroupNGramEntry { idf: 0.3 },
+					),
+				]
+				.into_iter()
+				.collect(),
+			),
 		};
 		let mut bag_of_words_features = vec![0.0; feature_group.ngrams.len()];
 		feature_group.compute_bag_of_words_feature("hello", bag_of_words_features.as_mut_slice());
@@ -302,22 +305,24 @@ mod test {
 			strategy: BagOfWordsFeatureGroupStrategy::Present,
 			tokenizer: Tokenizer::default(),
 			ngram_types: vec![NGramType::Unigram].into_iter().collect(),
-			ngrams: vec![
-				(
-					NGram::Unigram("test".to_owned()),
-					BagOfWordsFeatureGroupNGramEntry { idf: 1.0 },
-				),
-				(
-					NGram::Unigram("ben".to_owned()),
-					BagOfWordsFeatureGroupNGramEntry { idf: 0.3 },
-				),
-				(
-					NGram::Unigram("bitdiddle".to_owned()),
-					BagOfWordsFeatureGroupNGramEntry { idf: 0.3 },
-				),
-			]
-			.into_iter()
-			.collect(),
+			ngrams: Arc::new(
+				vec![
+					(
+						NGram::Unigram("test".to_owned()),
+						BagOfWordsFeatureGroupNGramEntry { idf: 1.0 },
+					),
+					(
+						NGram::Unigram("ben".to_owned()),
+						BagOfWordsFeatureGroupNGramEntry { idf: 0.3 },
+					),
+					(
+						NGram::Unigram("bitdiddle".to_owned()),
+						BagOfWordsFeatureGroupNGramEntry { idf: 0.3 },
+					),
+				]
+				.into_iter()
+				.collect(),
+			),
 		};
 		let mut bag_of_words_features_a = vec![0.0; feature_group.ngrams.len()];
 		let mut bag_of_words_features_b = vec![0.0; feature_group.ngrams.len()];
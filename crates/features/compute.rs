@@ -1,9 +1,9 @@
 use crate::{
 	bag_of_words::BagOfWordsFeatureGroup,
 	bag_of_words_cosine_similarity::BagOfWordsCosineSimilarityFeatureGroup,
-	identity::IdentityFeatureGroup, normalized::NormalizedFeatureGroup,
-	one_hot_encoded::OneHotEncodedFeatureGroup, word_embedding::WordEmbeddingFeatureGroup,
-	FeatureGroup,
+	cyclical::CyclicalFeatureGroup, identity::IdentityFeatureGroup,
+	normalized::NormalizedFeatureGroup, one_hot_encoded::OneHotEncodedFeatureGroup,
+	word_embedding::WordEmbeddingFeatureGroup, FeatureGroup,
 };
 use modelfox_table::prelude::*;
 use ndarray::prelude::*;
@@ -85,6 +85,14 @@ fn compute_features_array_f32_for_feature_group(
 				progress,
 			)
 		}
+		FeatureGroup::Cyclical(feature_group) => {
+			compute_features_array_f32_for_cyclical_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
 	}
 }
 
@@ -189,6 +197,21 @@ fn compute_features_array_f32_for_word_embedding_feature_group(
 	feature_group.compute_array_f32(features, source_column.view(), progress);
 }
 
+fn compute_features_array_f32_for_cyclical_feature_group(
+	table: &TableView,
+	feature_group: &CyclicalFeatureGroup,
+	features: ArrayViewMut2<f32>,
+	progress: &impl Fn(),
+) {
+	// Get the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name() == Some(&feature_group.source_column_name))
+		.unwrap();
+	feature_group.compute_array_f32(features, source_column.view(), progress);
+}
+
 /// Compute features as a `Table`.
 pub fn compute_features_table(
 	table: &TableView,
@@ -248,6 +271,12 @@ fn compute_features_table_for_feature_group(
 				progress,
 			)
 		}
+		FeatureGroup::Cyclical(feature_group) => compute_features_table_for_cyclical_feature_group(
+			table,
+			feature_group,
+			features,
+			progress,
+		),
 	};
 }
 
@@ -339,6 +368,24 @@ fn compute_features_table_for_word_embedding_feature_group(
 	}
 }
 
+fn compute_features_table_for_cyclical_feature_group(
+	table: &TableView,
+	feature_group: &CyclicalFeatureGroup,
+	features: &mut Table,
+	progress: &impl Fn(u64),
+) {
+	// Get the data for the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_column_name)
+		.unwrap();
+	let columns = feature_group.compute_table(source_column.view(), progress);
+	for column in columns {
+		features.columns_mut().push(column);
+	}
+}
+
 pub fn compute_features_array_value<'a>(
 	table: &TableView<'a>,
 	feature_groups: &[FeatureGroup],
@@ -405,6 +452,14 @@ fn compute_features_array_value_for_feature_group(
 				progress,
 			)
 		}
+		FeatureGroup::Cyclical(feature_group) => {
+			compute_features_array_value_for_cyclical_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
 	}
 }
 
@@ -490,3 +545,18 @@ fn compute_features_array_value_for_word_embedding_feature_group(
 		.unwrap();
 	feature_group.compute_array_value(features, source_column.view(), progress);
 }
+
+fn compute_features_array_value_for_cyclical_feature_group(
+	table: &TableView,
+	feature_group: &CyclicalFeatureGroup,
+	features: ArrayViewMut2<modelfox_table::TableValue>,
+	progress: &impl Fn(),
+) {
+	// Get the data for the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_column_name)
+		.unwrap();
+	feature_group.compute_array_value(features, source_column.view(), progress);
+}
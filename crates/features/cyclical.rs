@@ -0,0 +1,176 @@
+use modelfox_table::{
+	DateTimeTableColumnView, NumberTableColumn, NumberTableColumnView, TableColumn,
+	TableColumnView, TableValue,
+};
+use modelfox_zip::zip;
+use ndarray::prelude::*;
+use num::ToPrimitive;
+
+/**
+A `CyclicalFeatureGroup` maps a periodic value, such as the hour of the day, the day of the week, or a wind direction, onto a point on the unit circle, producing two features: the sine and cosine of the value's angle. Unlike the raw value, these features place the ends of the period next to each other, so hour 23 is as close to hour 0 as it is to hour 22.
+
+# Example
+
+```
+use modelfox_table::prelude::*;
+
+NumberTableColumn::new(
+  Some("hour".to_owned()),
+  vec![0.0, 6.0, 12.0, 23.0],
+);
+```
+
+Period: 24
+
+`angle = 2 * pi * (value - offset) / period`
+
+| input value | hour (sin) | hour (cos) |
+|-------------|------------|------------|
+| 0.0         | 0.0        | 1.0        |
+| 6.0         | 1.0        | 0.0        |
+| 12.0        | 0.0        | -1.0       |
+| 23.0        | -0.25882   | 0.96593    |
+
+A datetime column is treated as a unix timestamp in seconds, so a period of 86400 encodes the time of day and a period of 604800 encodes the time of the week. Invalid values have feature values of 0.
+*/
+#[derive(Clone, Debug)]
+pub struct CyclicalFeatureGroup {
+	pub source_column_name: String,
+	/// This is the length of one cycle in the units of the source column.
+	pub period: f32,
+	/// This is the value at which each cycle starts.
+	pub offset: f32,
+}
+
+impl CyclicalFeatureGroup {
+	pub fn compute_table(
+		&self,
+		column: TableColumnView,
+		progress: &impl Fn(u64),
+	) -> Vec<TableColumn> {
+		let values: Vec<Option<f64>> = match column {
+			TableColumnView::Number(column) => column
+				.iter()
+				.map(|value| value.to_f64().filter(|value| value.is_finite()))
+				.collect(),
+			TableColumnView::DateTime(column) => column
+				.iter()
+				.map(|value| value.and_then(|value| value.to_f64()))
+				.collect(),
+			_ => unimplemented!(),
+		};
+		let mut sin_values = Vec::with_capacity(values.len());
+		let mut cos_values = Vec::with_capacity(values.len());
+		for value in values {
+			let (sin, cos) = self.compute(value);
+			sin_values.push(sin);
+			cos_values.push(cos);
+			progress(1);
+		}
+		vec![
+			TableColumn::Number(NumberTableColumn::new(None, sin_values)),
+			TableColumn::Number(NumberTableColumn::new(None, cos_values)),
+		]
+	}
+
+	pub fn compute_array_f32(
+		&self,
+		features: ArrayViewMut2<f32>,
+		column: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		match column {
+			TableColumnView::Number(column) => {
+				self.compute_array_f32_for_number_column(features, column, progress)
+			}
+			TableColumnView::DateTime(column) => {
+				self.compute_array_f32_for_datetime_column(features, column, progress)
+			}
+			_ => unimplemented!(),
+		}
+	}
+
+	pub fn compute_array_value(
+		&self,
+		mut features: ArrayViewMut2<TableValue>,
+		column: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		let mut f32_features = Array::zeros((features.nrows(), 2));
+		self.compute_array_f32(f32_features.view_mut(), column, progress);
+		for (feature, value) in zip!(features.iter_mut(), f32_features.iter()) {
+			*feature = TableValue::Number(*value);
+		}
+	}
+
+	fn compute_array_f32_for_number_column(
+		&self,
+		mut features: ArrayViewMut2<f32>,
+		column: NumberTableColumnView,
+		progress: &impl Fn(),
+	) {
+		for (mut features, value) in zip!(features.axis_iter_mut(Axis(0)), column.iter()) {
+			let value = value.to_f64().filter(|value| value.is_finite());
+			let (sin, cos) = self.compute(value);
+			features[0] = sin;
+			features[1] = cos;
+			progress()
+		}
+	}
+
+	fn compute_array_f32_for_datetime_column(
+		&self,
+		mut features: ArrayViewMut2<f32>,
+		column: DateTimeTableColumnView,
+		progress: &impl Fn(),
+	) {
+		for (mut features, value) in zip!(features.axis_iter_mut(Axis(0)), column.iter()) {
+			let value = value.and_then(|value| value.to_f64());
+			let (sin, cos) = self.compute(value);
+			features[0] = sin;
+			features[1] = cos;
+			progress()
+		}
+	}
+
+	/// Compute the sine and cosine features for a value. This is done in f64 because timestamps are too large to compute their position in a short period precisely in f32.
+	fn compute(&self, value: Option<f64>) -> (f32, f32) {
+		let period = self.period.to_f64().unwrap();
+		match value {
+			Some(value) if period != 0.0 => {
+				let offset = self.offset.to_f64().unwrap();
+				let angle = ((value - offset) / period).rem_euclid(1.0) * std::f64::consts::TAU;
+				(angle.sin().to_f32().unwrap(), angle.cos().to_f32().unwrap())
+			}
+			_ => (0.0, 0.0),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_compute() {
+		let feature_group = CyclicalFeatureGroup {
+			source_column_name: "hour".to_owned(),
+			period: 24.0,
+			offset: 0.0,
+		};
+		let column =
+			NumberTableColumn::new(Some("hour".to_owned()), vec![0.0, 6.0, 24.0, f32::NAN]);
+		let mut features = Array::zeros((4, 2));
+		feature_group.compute_array_f32(
+			features.view_mut(),
+			TableColumnView::Number(column.view()),
+			&|| {},
+		);
+		let expected = [[0.0, 1.0], [1.0, 0.0], [0.0, 1.0], [0.0, 0.0]];
+		for (row, expected_row) in zip!(features.axis_iter(Axis(0)), expected.iter()) {
+			for (value, expected_value) in zip!(row.iter(), expected_row.iter()) {
+				assert!((value - expected_value).abs() < 1e-6);
+			}
+		}
+	}
+}
@@ -1,6 +1,6 @@
 use modelfox_table::{
-	EnumTableColumn, EnumTableColumnView, NumberTableColumn, NumberTableColumnView, TableColumn,
-	TableColumnView, TableValue,
+	DateTimeTableColumnView, EnumTableColumn, EnumTableColumnView, NumberTableColumn,
+	NumberTableColumnView, TableColumn, TableColumnView, TableValue,
 };
 use modelfox_zip::zip;
 use ndarray::prelude::*;
@@ -45,6 +45,8 @@ EnumTableColumn::new(
 | "red"           | Some(1)       |
 | "green"         | Some(2)       |
 | "blue"          | Some(3)       |
+
+For a datetime column, the feature value is the unix timestamp of the value, or NaN if the value is invalid.
 */
 #[derive(Clone, Debug)]
 pub struct IdentityFeatureGroup {
@@ -62,6 +64,9 @@ impl IdentityFeatureGroup {
 				TableColumn::Enum(self.compute_table_for_enum_column(column))
 			}
 			TableColumnView::Text(_) => unimplemented!(),
+			TableColumnView::DateTime(column) => {
+				TableColumn::Number(self.compute_table_for_datetime_column(column))
+			}
 		};
 		progress(column.len().to_u64().unwrap());
 		column
@@ -83,6 +88,9 @@ impl IdentityFeatureGroup {
 				self.compute_array_f32_for_enum_column(features, column, progress)
 			}
 			TableColumnView::Text(_) => unimplemented!(),
+			TableColumnView::DateTime(column) => {
+				self.compute_array_f32_for_datetime_column(features, column, progress)
+			}
 		}
 	}
 
@@ -101,6 +109,9 @@ impl IdentityFeatureGroup {
 				self.compute_array_value_for_enum_column(features, column, progress)
 			}
 			TableColumnView::Text(_) => unimplemented!(),
+			TableColumnView::DateTime(column) => {
+				self.compute_array_value_for_datetime_column(features, column, progress)
+			}
 		}
 	}
 
@@ -119,6 +130,19 @@ impl IdentityFeatureGroup {
 		)
 	}
 
+	fn compute_table_for_datetime_column(
+		&self,
+		column: DateTimeTableColumnView,
+	) -> NumberTableColumn {
+		NumberTableColumn::new(
+			column.name().map(|name| name.to_owned()),
+			column
+				.iter()
+				.map(|value| datetime_feature_value(*value))
+				.collect(),
+		)
+	}
+
 	fn compute_array_f32_for_number_column(
 		&self,
 		mut features: ArrayViewMut2<f32>,
@@ -143,6 +167,18 @@ impl IdentityFeatureGroup {
 		}
 	}
 
+	fn compute_array_f32_for_datetime_column(
+		&self,
+		mut features: ArrayViewMut2<f32>,
+		column: DateTimeTableColumnView,
+		progress: &impl Fn(),
+	) {
+		for (feature, value) in zip!(features.iter_mut(), column.iter()) {
+			*feature = datetime_feature_value(*value);
+			progress()
+		}
+	}
+
 	fn compute_array_value_for_number_column(
 		&self,
 		mut features: ArrayViewMut2<TableValue>,
@@ -166,4 +202,22 @@ impl IdentityFeatureGroup {
 			progress()
 		}
 	}
+
+	fn compute_array_value_for_datetime_column(
+		&self,
+		mut features: ArrayViewMut2<TableValue>,
+		column: DateTimeTableColumnView,
+		progress: &impl Fn(),
+	) {
+		for (feature_column, column_value) in zip!(features.column_mut(0), column.iter()) {
+			*feature_column = TableValue::Number(datetime_feature_value(*column_value));
+			progress()
+		}
+	}
+}
+
+fn datetime_feature_value(value: Option<i64>) -> f32 {
+	value
+		.map(|value| value.to_f32().unwrap())
+		.unwrap_or(f32::NAN)
 }
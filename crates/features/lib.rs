@@ -2,18 +2,22 @@ pub use self::{
 	bag_of_words::BagOfWordsFeatureGroup,
 	bag_of_words_cosine_similarity::BagOfWordsCosineSimilarityFeatureGroup,
 	compute::{compute_features_array_f32, compute_features_array_value, compute_features_table},
+	cyclical::CyclicalFeatureGroup,
 	identity::IdentityFeatureGroup,
 	normalized::NormalizedFeatureGroup,
 	one_hot_encoded::OneHotEncodedFeatureGroup,
+	sparse::{compute_features_sparse_f32, estimate_features_density, CsrMatrix, CsrMatrixView},
 	word_embedding::WordEmbeddingFeatureGroup,
 };
 
 pub mod bag_of_words;
 pub mod bag_of_words_cosine_similarity;
 pub mod compute;
+pub mod cyclical;
 pub mod identity;
 pub mod normalized;
 pub mod one_hot_encoded;
+pub mod sparse;
 pub mod word_embedding;
 
 /// The `FeatureGroup` struct describes how to transform one or more columns from the input table to one or more columns in the output features.
@@ -25,6 +29,7 @@ pub enum FeatureGroup {
 	BagOfWords(BagOfWordsFeatureGroup),
 	WordEmbedding(WordEmbeddingFeatureGroup),
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureGroup),
+	Cyclical(CyclicalFeatureGroup),
 }
 
 impl FeatureGroup {
@@ -37,6 +42,51 @@ impl FeatureGroup {
 			FeatureGroup::BagOfWords(s) => s.ngrams.len(),
 			FeatureGroup::BagOfWordsCosineSimilarity(_) => 1,
 			FeatureGroup::WordEmbedding(s) => s.model.size,
+			FeatureGroup::Cyclical(_) => 2,
+		}
+	}
+
+	/// Return the names of the columns this feature group computes its features from.
+	pub fn source_column_names(&self) -> Vec<&str> {
+		match self {
+			FeatureGroup::Identity(s) => vec![s.source_column_name.as_str()],
+			FeatureGroup::Normalized(s) => vec![s.source_column_name.as_str()],
+			FeatureGroup::OneHotEncoded(s) => vec![s.source_column_name.as_str()],
+			FeatureGroup::BagOfWords(s) => vec![s.source_column_name.as_str()],
+			FeatureGroup::BagOfWordsCosineSimilarity(s) => vec![
+				s.source_column_name_a.as_str(),
+				s.source_column_name_b.as_str(),
+			],
+			FeatureGroup::WordEmbedding(s) => vec![s.source_column_name.as_str()],
+			FeatureGroup::Cyclical(s) => vec![s.source_column_name.as_str()],
+		}
+	}
+
+	/// Return a human readable name for each feature this feature group will produce, in the order of the features.
+	pub fn feature_names(&self) -> Vec<String> {
+		match self {
+			FeatureGroup::Identity(s) => vec![s.source_column_name.clone()],
+			FeatureGroup::Normalized(s) => vec![s.source_column_name.clone()],
+			FeatureGroup::OneHotEncoded(s) => std::iter::once("OOV")
+				.chain(s.variants.iter().map(|variant| variant.as_str()))
+				.map(|variant| format!("{} = {}", s.source_column_name, variant))
+				.collect(),
+			FeatureGroup::BagOfWords(s) => s
+				.ngrams
+				.keys()
+				.map(|ngram| format!("{} contains {}", s.source_column_name, ngram))
+				.collect(),
+			FeatureGroup::BagOfWordsCosineSimilarity(s) => vec![format!(
+				"similarity of {} and {}",
+				s.source_column_name_a, s.source_column_name_b
+			)],
+			FeatureGroup::WordEmbedding(s) => (0..s.model.size)
+				.map(|i| format!("{} word embedding value {}", s.source_column_name, i))
+				.collect(),
+			FeatureGroup::Cyclical(s) => vec![
+				format!("{} (sin)", s.source_column_name),
+				format!("{} (cos)", s.source_column_name),
+			],
 		}
 	}
 }
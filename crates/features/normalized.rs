@@ -1,6 +1,6 @@
 use modelfox_table::{
-	EnumTableColumnView, NumberTableColumn, NumberTableColumnView, TableColumn, TableColumnView,
-	TableValue,
+	DateTimeTableColumnView, EnumTableColumnView, NumberTableColumn, NumberTableColumnView,
+	TableColumn, TableColumnView, TableValue,
 };
 use modelfox_zip::zip;
 use ndarray::prelude::*;
@@ -31,6 +31,8 @@ Standard Deviation: 2.70617
 | 0.0             | (0.0 - 2.16667) / 2.70617  = -0.80064 |
 | 5.2             | (5.2 - 2.16667) / 2.70617  = 1.12089  |
 | 1.3             | (1.3 - 2.16667) / 2.70617  = -0.32026 |
+
+A datetime column is normalized as unix timestamps, and invalid values have a feature value of 0.
 */
 #[derive(Clone, Debug)]
 pub struct NormalizedFeatureGroup {
@@ -84,6 +86,9 @@ impl NormalizedFeatureGroup {
 				TableColumn::Number(self.compute_table_for_enum_column(column, &|| progress(1)))
 			}
 			TableColumnView::Text(_) => unimplemented!(),
+			TableColumnView::DateTime(column) => {
+				TableColumn::Number(self.compute_table_for_datetime_column(column, &|| progress(1)))
+			}
 		}
 	}
 
@@ -103,6 +108,9 @@ impl NormalizedFeatureGroup {
 				self.compute_array_f32_for_enum_column(features, column, progress)
 			}
 			TableColumnView::Text(_) => unimplemented!(),
+			TableColumnView::DateTime(column) => {
+				self.compute_array_f32_for_datetime_column(features, column, progress)
+			}
 		}
 	}
 
@@ -121,6 +129,9 @@ impl NormalizedFeatureGroup {
 				self.compute_array_value_for_enum_column(features, column, progress)
 			}
 			TableColumnView::Text(_) => unimplemented!(),
+			TableColumnView::DateTime(column) => {
+				self.compute_array_value_for_datetime_column(features, column, progress)
+			}
 		}
 	}
 
@@ -163,6 +174,19 @@ impl NormalizedFeatureGroup {
 		NumberTableColumn::new(None, feature_values)
 	}
 
+	fn compute_table_for_datetime_column(
+		&self,
+		column: DateTimeTableColumnView,
+		progress: &impl Fn(),
+	) -> NumberTableColumn {
+		let mut feature_values = Vec::with_capacity(column.len());
+		for value in column.iter() {
+			feature_values.push(self.normalize_datetime(*value));
+			progress()
+		}
+		NumberTableColumn::new(None, feature_values)
+	}
+
 	fn compute_array_f32_for_number_column(
 		&self,
 		mut features: ArrayViewMut2<f32>,
@@ -198,6 +222,18 @@ impl NormalizedFeatureGroup {
 		}
 	}
 
+	fn compute_array_f32_for_datetime_column(
+		&self,
+		mut features: ArrayViewMut2<f32>,
+		column: DateTimeTableColumnView,
+		progress: &impl Fn(),
+	) {
+		for (feature, value) in zip!(features.iter_mut(), column.iter()) {
+			*feature = self.normalize_datetime(*value);
+			progress()
+		}
+	}
+
 	fn compute_array_value_for_number_column(
 		&self,
 		mut features: ArrayViewMut2<TableValue>,
@@ -231,4 +267,29 @@ impl NormalizedFeatureGroup {
 			progress()
 		}
 	}
+
+	fn compute_array_value_for_datetime_column(
+		&self,
+		mut features: ArrayViewMut2<TableValue>,
+		column: DateTimeTableColumnView,
+		progress: &impl Fn(),
+	) {
+		for (feature, value) in zip!(features.column_mut(0), column.iter()) {
+			*feature = TableValue::Number(self.normalize_datetime(*value));
+			progress()
+		}
+	}
+
+	/// Timestamps are large, so subtract the mean in f64 to avoid losing precision.
+	fn normalize_datetime(&self, value: Option<i64>) -> f32 {
+		match value {
+			Some(value) if self.variance != 0.0 => {
+				let value = value.to_f64().unwrap() - self.mean.to_f64().unwrap();
+				(value / self.variance.to_f64().unwrap().sqrt())
+					.to_f32()
+					.unwrap()
+			}
+			_ => 0.0,
+		}
+	}
 }
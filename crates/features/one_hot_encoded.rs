@@ -61,6 +61,7 @@ impl OneHotEncodedFeatureGroup {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(_) => unimplemented!(),
 			TableColumnView::Text(_) => unimplemented!(),
+			TableColumnView::DateTime(_) => unimplemented!(),
 		}
 	}
 
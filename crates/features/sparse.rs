@@ -0,0 +1,196 @@
+/*!
+This module defines a compressed sparse row matrix for features. One hot encoded and bag of words feature groups produce mostly zeros, so storing only the nonzero values of each row takes a fraction of the memory of a dense `Array2<f32>` for wide datasets.
+*/
+
+use crate::{compute::compute_features_array_f32, FeatureGroup};
+use modelfox_table::prelude::*;
+use modelfox_zip::zip;
+use ndarray::prelude::*;
+use num::ToPrimitive;
+
+/// The number of rows whose features are computed densely at a time before being compressed.
+const N_ROWS_PER_CHUNK: usize = 1024;
+
+/// A `CsrMatrix` stores the nonzero values of each row along with their column indexes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsrMatrix {
+	n_cols: usize,
+	/// The values for row `i` are at `row_offsets[i]..row_offsets[i + 1]`.
+	row_offsets: Vec<usize>,
+	column_indexes: Vec<u32>,
+	values: Vec<f32>,
+}
+
+/// A `CsrMatrixView` is a view of a contiguous range of rows of a `CsrMatrix`.
+#[derive(Clone, Copy, Debug)]
+pub struct CsrMatrixView<'a> {
+	n_cols: usize,
+	row_offsets: &'a [usize],
+	column_indexes: &'a [u32],
+	values: &'a [f32],
+}
+
+impl CsrMatrix {
+	/// Create an empty matrix with `n_cols` columns and no rows.
+	pub fn new(n_cols: usize) -> CsrMatrix {
+		CsrMatrix {
+			n_cols,
+			row_offsets: vec![0],
+			column_indexes: Vec::new(),
+			values: Vec::new(),
+		}
+	}
+
+	/// Create a matrix from the nonzero values of a dense array.
+	pub fn from_dense(array: ArrayView2<f32>) -> CsrMatrix {
+		let mut matrix = CsrMatrix::new(array.ncols());
+		for row in array.axis_iter(Axis(0)) {
+			matrix.push_row(row);
+		}
+		matrix
+	}
+
+	/// Append a row, storing only its nonzero values.
+	pub fn push_row(&mut self, row: ArrayView1<f32>) {
+		assert_eq!(row.len(), self.n_cols);
+		for (column_index, value) in row.iter().enumerate() {
+			if *value != 0.0 {
+				self.column_indexes.push(column_index.to_u32().unwrap());
+				self.values.push(*value);
+			}
+		}
+		self.row_offsets.push(self.values.len());
+	}
+
+	pub fn nrows(&self) -> usize {
+		self.row_offsets.len() - 1
+	}
+
+	pub fn ncols(&self) -> usize {
+		self.n_cols
+	}
+
+	pub fn view(&self) -> CsrMatrixView {
+		CsrMatrixView {
+			n_cols: self.n_cols,
+			row_offsets: &self.row_offsets,
+			column_indexes: &self.column_indexes,
+			values: &self.values,
+		}
+	}
+}
+
+impl<'a> CsrMatrixView<'a> {
+	pub fn nrows(&self) -> usize {
+		self.row_offsets.len() - 1
+	}
+
+	pub fn ncols(&self) -> usize {
+		self.n_cols
+	}
+
+	/// Return the number of nonzero values in this view.
+	pub fn nnz(&self) -> usize {
+		self.row_offsets[self.row_offsets.len() - 1] - self.row_offsets[0]
+	}
+
+	/// Return the column indexes and values of the nonzero entries in the row at `index`.
+	pub fn row(&self, index: usize) -> (&'a [u32], &'a [f32]) {
+		let range = self.row_offsets[index]..self.row_offsets[index + 1];
+		(&self.column_indexes[range.clone()], &self.values[range])
+	}
+
+	/// Split the view into the rows before `index` and the rows starting at `index`.
+	pub fn split_at_row(&self, index: usize) -> (CsrMatrixView<'a>, CsrMatrixView<'a>) {
+		let a = CsrMatrixView {
+			row_offsets: &self.row_offsets[..=index],
+			..*self
+		};
+		let b = CsrMatrixView {
+			row_offsets: &self.row_offsets[index..],
+			..*self
+		};
+		(a, b)
+	}
+
+	/// Split the view into consecutive views of `chunk_size` rows. The last view may have fewer rows.
+	pub fn row_chunks(&self, chunk_size: usize) -> Vec<CsrMatrixView<'a>> {
+		let mut chunks = Vec::new();
+		let mut rest = *self;
+		while rest.nrows() > 0 {
+			let (chunk, next) = rest.split_at_row(chunk_size.min(rest.nrows()));
+			chunks.push(chunk);
+			rest = next;
+		}
+		chunks
+	}
+
+	pub fn to_dense(&self) -> Array2<f32> {
+		let mut array = Array::zeros((self.nrows(), self.n_cols));
+		for (row_index, mut array_row) in array.axis_iter_mut(Axis(0)).enumerate() {
+			let (column_indexes, values) = self.row(row_index);
+			for (column_index, value) in zip!(column_indexes.iter(), values.iter()) {
+				array_row[column_index.to_usize().unwrap()] = *value;
+			}
+		}
+		array
+	}
+}
+
+/// Compute features as a `CsrMatrix`. The features are computed densely for a chunk of rows at a time, so the peak memory usage stays close to the size of the sparse result.
+pub fn compute_features_sparse_f32(
+	table: &TableView,
+	feature_groups: &[FeatureGroup],
+	progress: &impl Fn(),
+) -> CsrMatrix {
+	let n_features = feature_groups
+		.iter()
+		.map(|feature_group| feature_group.n_features())
+		.sum::<usize>();
+	let mut features = CsrMatrix::new(n_features);
+	let mut rest = table.view();
+	while rest.nrows() > 0 {
+		let (chunk, next) = rest.split_at_row(N_ROWS_PER_CHUNK.min(rest.nrows()));
+		let chunk_features = compute_features_array_f32(&chunk, feature_groups, progress);
+		for row in chunk_features.axis_iter(Axis(0)) {
+			features.push_row(row);
+		}
+		rest = next;
+	}
+	features
+}
+
+/// Estimate the fraction of feature values that are nonzero by computing the features for the first rows of `table`.
+pub fn estimate_features_density(table: &TableView, feature_groups: &[FeatureGroup]) -> f32 {
+	let (sample, _) = table.split_at_row(N_ROWS_PER_CHUNK.min(table.nrows()));
+	let features = compute_features_array_f32(&sample, feature_groups, &|| {});
+	if features.is_empty() {
+		return 1.0;
+	}
+	let n_nonzero = features.iter().filter(|value| **value != 0.0).count();
+	n_nonzero.to_f32().unwrap() / features.len().to_f32().unwrap()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_row_chunks() {
+		let array = arr2(&[
+			[0.0, 1.0, 0.0],
+			[2.0, 0.0, 0.0],
+			[0.0, 0.0, 0.0],
+			[0.0, 3.0, 4.0],
+			[5.0, 0.0, 0.0],
+		]);
+		let matrix = CsrMatrix::from_dense(array.view());
+		assert_eq!(matrix.view().nnz(), 5);
+		assert_eq!(matrix.view().to_dense(), array);
+		let chunks = matrix.view().row_chunks(2);
+		assert_eq!(chunks.len(), 3);
+		assert_eq!(chunks[1].nnz(), 2);
+		assert_eq!(chunks[1].row(1), (&[1, 2][..], &[3.0, 4.0][..]));
+		assert_eq!(chunks[2].to_dense(), array.slice(s![4.., ..]));
+	}
+}
@@ -3,6 +3,7 @@ use modelfox_table::{
 };
 use modelfox_text::{Tokenizer, WordEmbeddingModel};
 use ndarray::prelude::*;
+use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub struct WordEmbeddingFeatureGroup {
@@ -10,8 +11,8 @@ pub struct WordEmbeddingFeatureGroup {
 	pub source_column_name: String,
 	/// This is the tokenizer used to split the text into tokens.
 	pub tokenizer: Tokenizer,
-	/// This is the word embedding model.
-	pub model: WordEmbeddingModel,
+	/// This is the word embedding model. It is in an `Arc` so models with the same embeddings can share one copy of it.
+	pub model: Arc<WordEmbeddingModel>,
 }
 
 impl WordEmbeddingFeatureGroup {
@@ -24,6 +25,7 @@ impl WordEmbeddingFeatureGroup {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(_) => unimplemented!(),
 			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::DateTime(_) => unimplemented!(),
 			TableColumnView::Text(column) => {
 				self.compute_table_for_text_column(column, &|| progress(1))
 			}
@@ -40,6 +42,7 @@ impl WordEmbeddingFeatureGroup {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(_) => unimplemented!(),
 			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::DateTime(_) => unimplemented!(),
 			TableColumnView::Text(column) => {
 				self.compute_array_f32_for_text_column(features, column, progress)
 			}
@@ -56,6 +59,7 @@ impl WordEmbeddingFeatureGroup {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(_) => unimplemented!(),
 			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::DateTime(_) => unimplemented!(),
 			TableColumnView::Text(column) => {
 				self.compute_array_value_for_text_column(features, column, progress)
 			}
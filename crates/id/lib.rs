@@ -1,17 +1,140 @@
 /*!
 `ModelFox` uses the `Id` type to uniquely identify models, users, and anything that needs a primary key. This type is almost identical to UUID v4, except there are no bits reserved to specify the version, and the string representation has no dashes.
+
+For interop with external systems, ids can also be parsed from hyphenated UUIDs and ULIDs, and formatted as either with [`Id::to_uuid_string`] and [`Id::to_ulid_string`]. The `Display` implementation always produces the 32 character hex representation, which is the one stored in the database. New ids can be generated as ULIDs, which sort by the time they were created, by calling [`set_generate_mode`] with [`GenerateMode::Ulid`].
 */
 
 #![warn(clippy::pedantic)]
 
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU8, Ordering};
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Id(u128);
 
+/// This enum specifies how [`Id::generate`] produces new ids.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GenerateMode {
+	/// Every bit of the id is random.
+	Random,
+	/// The first 48 bits of the id are the number of milliseconds since the unix epoch and the remaining 80 bits are random, so ids sort by the time they were generated. Ids generated on the same thread in the same millisecond are monotonically increasing.
+	Ulid,
+}
+
+static GENERATE_MODE: AtomicU8 = AtomicU8::new(0);
+
+/// Set how [`Id::generate`] produces new ids for the rest of the process.
+pub fn set_generate_mode(mode: GenerateMode) {
+	let mode = match mode {
+		GenerateMode::Random => 0,
+		GenerateMode::Ulid => 1,
+	};
+	GENERATE_MODE.store(mode, Ordering::Relaxed);
+}
+
+thread_local! {
+	static LAST_ULID: Cell<u128> = Cell::new(0);
+}
+
 impl Id {
 	#[must_use]
 	pub fn generate() -> Id {
-		Id(rand::random())
+		match GENERATE_MODE.load(Ordering::Relaxed) {
+			1 => Id::generate_ulid(),
+			_ => Id(rand::random()),
+		}
+	}
+
+	/// Generate a ULID. See [`GenerateMode::Ulid`].
+	#[must_use]
+	pub fn generate_ulid() -> Id {
+		let timestamp = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.map_or(0, |duration| duration.as_millis())
+			& ((1 << 48) - 1);
+		let random = rand::random::<u128>() & ((1 << 80) - 1);
+		let id = LAST_ULID.with(|last| {
+			let previous = last.get();
+			let id = if previous >> 80 == timestamp {
+				// Increment the random bits of the previous id so ids in the same millisecond stay ordered.
+				previous.wrapping_add(1)
+			} else {
+				(timestamp << 80) | random
+			};
+			last.set(id);
+			id
+		});
+		Id(id)
+	}
+
+	/// Format the id as a hyphenated UUID, such as `f51a3a61-ee9d-4731-b1b0-6c816a8ab856`.
+	#[must_use]
+	pub fn to_uuid_string(&self) -> String {
+		let s = self.to_string();
+		format!(
+			"{}-{}-{}-{}-{}",
+			&s[0..8],
+			&s[8..12],
+			&s[12..16],
+			&s[16..20],
+			&s[20..32]
+		)
+	}
+
+	/// Format the id as a 26 character ULID in Crockford's base32.
+	#[must_use]
+	pub fn to_ulid_string(&self) -> String {
+		(0..26)
+			.rev()
+			.map(|index| {
+				let digit = usize::try_from((self.0 >> (index * 5)) & 0x1f).unwrap();
+				CROCKFORD_BASE32_ALPHABET[digit] as char
+			})
+			.collect()
+	}
+}
+
+const CROCKFORD_BASE32_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+fn parse_hex(s: &str) -> Result<Id, ParseIdError> {
+	if s.len() != 32 || !s.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+		return Err(ParseIdError);
 	}
+	let id = u128::from_str_radix(s, 16).map_err(|_| ParseIdError)?;
+	Ok(Id(id))
+}
+
+fn parse_uuid(s: &str) -> Result<Id, ParseIdError> {
+	let groups = s.split('-').collect::<Vec<_>>();
+	let group_lengths = groups.iter().map(|group| group.len()).collect::<Vec<_>>();
+	if group_lengths != [8, 4, 4, 4, 12] {
+		return Err(ParseIdError);
+	}
+	parse_hex(&groups.concat())
+}
+
+fn parse_ulid(s: &str) -> Result<Id, ParseIdError> {
+	if s.len() != 26 {
+		return Err(ParseIdError);
+	}
+	let mut id: u128 = 0;
+	for (index, byte) in s.bytes().enumerate() {
+		let byte = match byte.to_ascii_uppercase() {
+			b'O' => b'0',
+			b'I' | b'L' => b'1',
+			byte => byte,
+		};
+		let digit = CROCKFORD_BASE32_ALPHABET
+			.iter()
+			.position(|c| *c == byte)
+			.ok_or(ParseIdError)?;
+		// A ULID encodes 128 bits in 130, so the first character must not exceed 7.
+		if index == 0 && digit > 7 {
+			return Err(ParseIdError);
+		}
+		id = (id << 5) | digit as u128;
+	}
+	Ok(Id(id))
 }
 
 #[derive(Debug)]
@@ -27,13 +150,16 @@ impl std::error::Error for ParseIdError {}
 
 impl std::str::FromStr for Id {
 	type Err = ParseIdError;
+	/// Parse an id from its 32 character hex representation, a hyphenated UUID optionally wrapped in braces or prefixed with `urn:uuid:`, or a ULID.
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		if s.len() != 32 {
-			return Err(ParseIdError);
+		match s.len() {
+			32 => parse_hex(s),
+			26 => parse_ulid(s),
+			36 => parse_uuid(s),
+			38 if s.starts_with('{') && s.ends_with('}') => parse_uuid(&s[1..37]),
+			45 if s.starts_with("urn:uuid:") => parse_uuid(&s[9..]),
+			_ => Err(ParseIdError),
 		}
-		let id = u128::from_str_radix(s, 16).map_err(|_| ParseIdError)?;
-		let id = Id(id);
-		Ok(id)
 	}
 }
 
@@ -86,4 +212,40 @@ fn test_parse() {
 	assert_eq!(s.parse::<Id>().unwrap().to_string(), s);
 	let s = "abc123";
 	assert!(s.parse::<Id>().is_err());
+	let s = "+0000000000000000000000000000000";
+	assert!(s.parse::<Id>().is_err());
+}
+
+#[test]
+fn test_parse_uuid() {
+	let id = "f51a3a61ee9d4731b1b06c816a8ab856".parse::<Id>().unwrap();
+	let s = "f51a3a61-ee9d-4731-b1b0-6c816a8ab856";
+	assert_eq!(s.parse::<Id>().unwrap(), id);
+	assert_eq!(id.to_uuid_string(), s);
+	let s = "F51A3A61-EE9D-4731-B1B0-6C816A8AB856";
+	assert_eq!(s.parse::<Id>().unwrap(), id);
+	let s = "{f51a3a61-ee9d-4731-b1b0-6c816a8ab856}";
+	assert_eq!(s.parse::<Id>().unwrap(), id);
+	let s = "urn:uuid:f51a3a61-ee9d-4731-b1b0-6c816a8ab856";
+	assert_eq!(s.parse::<Id>().unwrap(), id);
+	let s = "f51a3a61ee9d-4731-b1b0-6c81-6a8ab856";
+	assert!(s.parse::<Id>().is_err());
+}
+
+#[test]
+fn test_parse_ulid() {
+	let s = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+	let id = s.parse::<Id>().unwrap();
+	assert_eq!(id.to_ulid_string(), s);
+	assert_eq!(s.to_lowercase().parse::<Id>().unwrap(), id);
+	let s = "7ZZZZZZZZZZZZZZZZZZZZZZZZZ";
+	assert_eq!(s.parse::<Id>().unwrap(), Id(u128::MAX));
+	let s = "8ZZZZZZZZZZZZZZZZZZZZZZZZZ";
+	assert!(s.parse::<Id>().is_err());
+}
+
+#[test]
+fn test_generate_ulid() {
+	let ids = (0..100).map(|_| Id::generate_ulid()).collect::<Vec<_>>();
+	assert!(ids.windows(2).all(|ids| ids[0] < ids[1]));
 }
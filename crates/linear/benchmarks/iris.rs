@@ -60,7 +60,7 @@ fn main() {
 		.predict(features_test.view(), probabilities.view_mut());
 
 	// Compute metrics.
-	let mut metrics = modelfox_metrics::MulticlassClassificationMetrics::new(3);
+	let mut metrics = modelfox_metrics::MulticlassClassificationMetrics::new(3, 99);
 	metrics.update(modelfox_metrics::MulticlassClassificationMetricsInput {
 		probabilities: probabilities.view(),
 		labels: labels_test.view().data().into(),
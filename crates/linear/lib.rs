@@ -1,5 +1,6 @@
 #![doc = include_str!("./README.md")]
 
+use self::matrix::FeatureMatrix;
 pub use self::{
 	binary_classifier::BinaryClassifier, multiclass_classifier::MulticlassClassifier,
 	regressor::Regressor,
@@ -9,12 +10,14 @@ use ndarray::prelude::*;
 use num::ToPrimitive;
 
 mod binary_classifier;
+mod matrix;
 mod multiclass_classifier;
+mod optimizer;
 mod regressor;
 pub mod serialize;
 mod shap;
 
-/// These are the options passed to `Regressor::train`, `BinaryClassifier::train`, and `MulticlassClassifier::train`.
+/// These are the options passed to `Regressor::train`, `BinaryClassifier::train`, and `MulticlassClassifier::train`, as well as their `train_sparse` counterparts.
 #[derive(Clone, Debug)]
 pub struct TrainOptions {
 	/// If true, the model will include the loss on the training data after each epoch.
@@ -25,10 +28,14 @@ pub struct TrainOptions {
 	pub l2_regularization: f32,
 	/// This is the learning rate to use when updating the model parameters.
 	pub learning_rate: f32,
+	/// This is the schedule used to decay the learning rate over the course of training.
+	pub learning_rate_schedule: LearningRateSchedule,
 	/// This is the maximum number of epochs to train.
 	pub max_epochs: usize,
 	/// This is the number of examples to use for each batch of training.
 	pub n_examples_per_batch: usize,
+	/// This is the optimizer used to update the model parameters from the gradients of each batch.
+	pub optimizer: Optimizer,
 }
 
 impl Default for TrainOptions {
@@ -38,8 +45,68 @@ impl Default for TrainOptions {
 			early_stopping_options: None,
 			l2_regularization: 0.0,
 			learning_rate: 0.1,
+			learning_rate_schedule: LearningRateSchedule::Constant,
 			max_epochs: 100,
 			n_examples_per_batch: 32,
+			optimizer: Optimizer::Sgd,
+		}
+	}
+}
+
+pub const DEFAULT_MOMENTUM: f32 = 0.9;
+pub const DEFAULT_ADAM_BETA_1: f32 = 0.9;
+pub const DEFAULT_ADAM_BETA_2: f32 = 0.999;
+pub const DEFAULT_ADAM_EPSILON: f32 = 1e-8;
+
+/// This enum specifies how the model parameters are updated from the gradients of each batch.
+#[derive(Clone, Debug)]
+pub enum Optimizer {
+	/// Move each parameter against its gradient, scaled by the learning rate.
+	Sgd,
+	/// Move each parameter against an exponentially decaying sum of its past gradients, which speeds up training along directions where the gradient is consistent.
+	Momentum { momentum: f32 },
+	/// Scale each parameter's step by running estimates of the mean and variance of its gradient.
+	Adam {
+		beta_1: f32,
+		beta_2: f32,
+		epsilon: f32,
+	},
+}
+
+/// This enum specifies how the learning rate changes from one epoch to the next.
+#[derive(Clone, Debug)]
+pub enum LearningRateSchedule {
+	/// Use the same learning rate for every epoch.
+	Constant,
+	/// Multiply the learning rate by `decay_rate` every `n_epochs_per_step` epochs.
+	Step {
+		decay_rate: f32,
+		n_epochs_per_step: usize,
+	},
+	/// Multiply the learning rate by `decay_rate` every epoch.
+	Exponential { decay_rate: f32 },
+	/// Divide the learning rate by `1 + decay_rate * epoch`.
+	InverseTime { decay_rate: f32 },
+}
+
+impl LearningRateSchedule {
+	/// Compute the learning rate to use for the zero based `epoch`, given the initial learning rate.
+	pub fn learning_rate(&self, learning_rate: f32, epoch: usize) -> f32 {
+		match *self {
+			LearningRateSchedule::Constant => learning_rate,
+			LearningRateSchedule::Step {
+				decay_rate,
+				n_epochs_per_step,
+			} => {
+				let n_steps = epoch / n_epochs_per_step.max(1);
+				learning_rate * decay_rate.powi(n_steps.to_i32().unwrap_or(i32::MAX))
+			}
+			LearningRateSchedule::Exponential { decay_rate } => {
+				learning_rate * decay_rate.powi(epoch.to_i32().unwrap_or(i32::MAX))
+			}
+			LearningRateSchedule::InverseTime { decay_rate } => {
+				learning_rate / (1.0 + decay_rate * epoch.to_f32().unwrap())
+			}
 		}
 	}
 }
@@ -68,20 +135,23 @@ pub enum TrainProgressEvent {
 }
 
 /// This function splits the `features` and `labels` arrays into training and early stopping arrays, where the size of the early stopping stopping array will be `features.len() * early_stopping_fraction`.
-fn train_early_stopping_split<'features, 'labels, Label>(
-	features: ArrayView2<'features, f32>,
+fn train_early_stopping_split<'labels, Features, Label>(
+	features: Features,
 	labels: ArrayView1<'labels, Label>,
 	early_stopping_fraction: f32,
 ) -> (
-	ArrayView2<'features, f32>,
+	Features,
 	ArrayView1<'labels, Label>,
-	ArrayView2<'features, f32>,
+	Features,
 	ArrayView1<'labels, Label>,
-) {
+)
+where
+	Features: FeatureMatrix,
+{
 	let split_index = ((1.0 - early_stopping_fraction) * features.nrows().to_f32().unwrap())
 		.to_usize()
 		.unwrap();
-	let (features_train, features_early_stopping) = features.split_at(Axis(0), split_index);
+	let (features_train, features_early_stopping) = features.split_at_row(split_index);
 	let (labels_train, labels_early_stopping) = labels.split_at(Axis(0), split_index);
 	(
 		features_train,
@@ -0,0 +1,110 @@
+use modelfox_features::CsrMatrixView;
+use modelfox_zip::zip;
+use ndarray::prelude::*;
+use num::ToPrimitive;
+
+/// This trait abstracts over the dense and sparse feature representations the linear models can be trained on, so the training loop is shared between them.
+pub trait FeatureMatrix: Copy + Send + Sync {
+	fn nrows(&self) -> usize;
+	fn ncols(&self) -> usize;
+	fn split_at_row(self, index: usize) -> (Self, Self);
+	fn row_chunks(self, chunk_size: usize) -> Vec<Self>;
+	fn column_means(self) -> Vec<f32>;
+	/// Add the product of this matrix and `vector` to `output`.
+	fn mul_vector_add(self, vector: ArrayView1<f32>, output: ArrayViewMut1<f32>);
+	/// Add the product of this matrix and `matrix` to `output`.
+	fn mul_matrix_add(self, matrix: ArrayView2<f32>, output: ArrayViewMut2<f32>);
+	/// Compute the mean of the rows after scaling each row by the corresponding entry in `scales`.
+	fn scaled_row_mean(self, scales: ArrayView1<f32>) -> Array1<f32>;
+}
+
+impl<'a> FeatureMatrix for ArrayView2<'a, f32> {
+	fn nrows(&self) -> usize {
+		ArrayBase::nrows(self)
+	}
+
+	fn ncols(&self) -> usize {
+		ArrayBase::ncols(self)
+	}
+
+	fn split_at_row(self, index: usize) -> (Self, Self) {
+		self.split_at(Axis(0), index)
+	}
+
+	fn row_chunks(self, chunk_size: usize) -> Vec<Self> {
+		self.axis_chunks_iter(Axis(0), chunk_size).collect()
+	}
+
+	fn column_means(self) -> Vec<f32> {
+		self.axis_iter(Axis(1))
+			.map(|column| column.mean().unwrap())
+			.collect()
+	}
+
+	fn mul_vector_add(self, vector: ArrayView1<f32>, mut output: ArrayViewMut1<f32>) {
+		ndarray::linalg::general_mat_vec_mul(1.0, &self, &vector, 1.0, &mut output);
+	}
+
+	fn mul_matrix_add(self, matrix: ArrayView2<f32>, mut output: ArrayViewMut2<f32>) {
+		ndarray::linalg::general_mat_mul(1.0, &self, &matrix, 1.0, &mut output);
+	}
+
+	fn scaled_row_mean(self, scales: ArrayView1<f32>) -> Array1<f32> {
+		(&self * &scales.insert_axis(Axis(1)))
+			.mean_axis(Axis(0))
+			.unwrap()
+	}
+}
+
+impl<'a> FeatureMatrix for CsrMatrixView<'a> {
+	fn nrows(&self) -> usize {
+		CsrMatrixView::nrows(self)
+	}
+
+	fn ncols(&self) -> usize {
+		CsrMatrixView::ncols(self)
+	}
+
+	fn split_at_row(self, index: usize) -> (Self, Self) {
+		CsrMatrixView::split_at_row(&self, index)
+	}
+
+	fn row_chunks(self, chunk_size: usize) -> Vec<Self> {
+		CsrMatrixView::row_chunks(&self, chunk_size)
+	}
+
+	fn column_means(self) -> Vec<f32> {
+		let scales = Array1::ones(self.nrows());
+		self.scaled_row_mean(scales.view()).to_vec()
+	}
+
+	fn mul_vector_add(self, vector: ArrayView1<f32>, mut output: ArrayViewMut1<f32>) {
+		for (row_index, output) in output.iter_mut().enumerate() {
+			let (column_indexes, values) = self.row(row_index);
+			for (column_index, value) in zip!(column_indexes.iter(), values.iter()) {
+				*output += value * vector[column_index.to_usize().unwrap()];
+			}
+		}
+	}
+
+	fn mul_matrix_add(self, matrix: ArrayView2<f32>, mut output: ArrayViewMut2<f32>) {
+		for (row_index, mut output) in output.axis_iter_mut(Axis(0)).enumerate() {
+			let (column_indexes, values) = self.row(row_index);
+			for (column_index, value) in zip!(column_indexes.iter(), values.iter()) {
+				output.scaled_add(*value, &matrix.row(column_index.to_usize().unwrap()));
+			}
+		}
+	}
+
+	fn scaled_row_mean(self, scales: ArrayView1<f32>) -> Array1<f32> {
+		let mut mean = Array1::zeros(self.ncols());
+		for (row_index, scale) in scales.iter().enumerate() {
+			let (column_indexes, values) = self.row(row_index);
+			for (column_index, value) in zip!(column_indexes.iter(), values.iter()) {
+				mean[column_index.to_usize().unwrap()] += value * scale;
+			}
+		}
+		mean /= self.nrows().to_f32().unwrap();
+		mean
+	}
+}
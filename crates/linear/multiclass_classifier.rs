@@ -1,14 +1,17 @@
 use crate::Progress;
 
 use super::{
+	matrix::FeatureMatrix,
+	optimizer::{OptimizerState, OptimizerStatePtr, OptimizerStep},
 	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
 	train_early_stopping_split, EarlyStoppingMonitor, TrainOptions, TrainProgressEvent,
 };
+use modelfox_features::CsrMatrixView;
 use modelfox_metrics::{CrossEntropy, CrossEntropyInput};
 use modelfox_progress_counter::ProgressCounter;
 use modelfox_table::prelude::*;
 use modelfox_zip::{pzip, zip};
-use ndarray::{self, prelude::*};
+use ndarray::prelude::*;
 use num::{clamp, ToPrimitive};
 use rayon::{self, prelude::*};
 use std::num::NonZeroUsize;
@@ -41,6 +44,25 @@ impl MulticlassClassifier {
 		labels: EnumTableColumnView,
 		train_options: &TrainOptions,
 		progress: Progress,
+	) -> MulticlassClassifierTrainOutput {
+		MulticlassClassifier::train_features(features, labels, train_options, progress)
+	}
+
+	/// Train a linear multiclass classifier on sparse features.
+	pub fn train_sparse(
+		features: CsrMatrixView,
+		labels: EnumTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> MulticlassClassifierTrainOutput {
+		MulticlassClassifier::train_features(features, labels, train_options, progress)
+	}
+
+	fn train_features(
+		features: impl FeatureMatrix,
+		labels: EnumTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
 	) -> MulticlassClassifierTrainOutput {
 		let n_classes = labels.variants().len();
 		let n_features = features.ncols();
@@ -54,10 +76,7 @@ impl MulticlassClassifier {
 					.map(|o| o.early_stopping_fraction)
 					.unwrap_or(0.0),
 			);
-		let means = features_train
-			.axis_iter(Axis(1))
-			.map(|column| column.mean().unwrap())
-			.collect();
+		let means = features_train.column_means();
 		let mut model = MulticlassClassifier {
 			biases: <Array1<f32>>::zeros(n_classes),
 			weights: <Array2<f32>>::zeros((n_features, n_classes)),
@@ -81,28 +100,45 @@ impl MulticlassClassifier {
 		} else {
 			None
 		};
+		let mut optimizer_state =
+			OptimizerState::new(&train_options.optimizer, (n_features + 1) * n_classes);
+		let n_batches_per_epoch = (features_train.nrows() + train_options.n_examples_per_batch - 1)
+			/ train_options.n_examples_per_batch;
 		let kill_chip = progress.kill_chip;
-		for _ in 0..train_options.max_epochs {
+		let features_train_chunks = features_train.row_chunks(train_options.n_examples_per_batch);
+		for epoch in 0..train_options.max_epochs {
 			progress_counter.inc(1);
 			let n_examples_per_batch = train_options.n_examples_per_batch;
 			struct MulticlassClassifierPtr(*mut MulticlassClassifier);
 			unsafe impl Send for MulticlassClassifierPtr {}
 			unsafe impl Sync for MulticlassClassifierPtr {}
 			let model_ptr = MulticlassClassifierPtr(&mut model);
+			let optimizer_state_ptr = OptimizerStatePtr(&mut optimizer_state);
+			let learning_rate = train_options
+				.learning_rate_schedule
+				.learning_rate(train_options.learning_rate, epoch);
 			pzip!(
-				features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+				features_train_chunks.par_iter().copied(),
 				labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
 				probabilities_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch),
 			)
-			.for_each(|(features, labels, probabilities)| {
+			.enumerate()
+			.for_each(|(batch_index, (features, labels, probabilities))| {
 				let model_ptr = &model_ptr;
 				let model = unsafe { &mut *model_ptr.0 };
+				let optimizer_state_ptr = &optimizer_state_ptr;
+				let optimizer_step = OptimizerStep {
+					optimizer: &train_options.optimizer,
+					state: unsafe { &mut *optimizer_state_ptr.0 },
+					learning_rate,
+					step: epoch * n_batches_per_epoch + batch_index + 1,
+				};
 				MulticlassClassifier::train_batch(
 					model,
 					features,
 					labels,
 					probabilities,
-					train_options,
+					optimizer_step,
 					kill_chip,
 				);
 			});
@@ -160,18 +196,22 @@ impl MulticlassClassifier {
 
 	fn train_batch(
 		&mut self,
-		features: ArrayView2<f32>,
+		features: impl FeatureMatrix,
 		labels: ArrayView1<Option<NonZeroUsize>>,
 		mut probabilities: ArrayViewMut2<f32>,
-		train_options: &TrainOptions,
+		mut optimizer_step: OptimizerStep,
 		kill_chip: &modelfox_kill_chip::KillChip,
 	) {
 		if kill_chip.is_activated() {
 			return;
 		}
-		let learning_rate = train_options.learning_rate;
+		let n_features = self.weights.nrows();
 		let n_classes = self.weights.ncols();
-		let mut logits = features.dot(&self.weights) + &self.biases;
+		let mut logits = Array2::zeros((features.nrows(), n_classes));
+		for mut row in logits.axis_iter_mut(Axis(0)) {
+			row.assign(&self.biases.view());
+		}
+		features.mul_matrix_add(self.weights.view(), logits.view_mut());
 		softmax(logits.view_mut());
 		for (probability, logit) in zip!(probabilities.iter_mut(), logits.iter()) {
 			*probability = *logit;
@@ -188,21 +228,23 @@ impl MulticlassClassifier {
 		}
 		let py = predictions;
 		for class_index in 0..n_classes {
-			let weight_gradients = (&features * &py.column(class_index).insert_axis(Axis(1)))
-				.mean_axis(Axis(0))
-				.unwrap();
-			for (weight, weight_gradient) in zip!(
+			let weight_gradients = features.scaled_row_mean(py.column(class_index));
+			for (feature_index, (weight, weight_gradient)) in zip!(
 				self.weights.column_mut(class_index),
 				weight_gradients.iter()
-			) {
-				*weight += -learning_rate * weight_gradient
+			)
+			.enumerate()
+			{
+				let index = feature_index * n_classes + class_index;
+				optimizer_step.update(index, weight, *weight_gradient);
 			}
 			let bias_gradients = py
 				.column(class_index)
 				.insert_axis(Axis(1))
 				.mean_axis(Axis(0))
 				.unwrap();
-			self.biases[class_index] += -learning_rate * bias_gradients[0];
+			let index = n_features * n_classes + class_index;
+			optimizer_step.update(index, &mut self.biases[class_index], bias_gradients[0]);
 		}
 	}
 
@@ -224,13 +266,13 @@ impl MulticlassClassifier {
 
 	fn compute_early_stopping_metric_value(
 		&self,
-		features: ArrayView2<f32>,
+		features: impl FeatureMatrix,
 		labels: ArrayView1<Option<NonZeroUsize>>,
 		train_options: &TrainOptions,
 	) -> f32 {
 		let n_classes = self.biases.len();
 		pzip!(
-			features.axis_chunks_iter(Axis(0), train_options.n_examples_per_batch),
+			features.row_chunks(train_options.n_examples_per_batch),
 			labels.axis_chunks_iter(Axis(0), train_options.n_examples_per_batch),
 		)
 		.fold(
@@ -245,7 +287,7 @@ impl MulticlassClassifier {
 			|(mut predictions, mut metric), (features, labels)| {
 				let slice = s![0..features.nrows(), ..];
 				let mut predictions_slice = predictions.slice_mut(slice);
-				self.predict(features, predictions_slice.view_mut());
+				self.predict_features(features, predictions_slice.view_mut());
 				for (prediction, label) in zip!(predictions_slice.axis_iter(Axis(0)), labels.iter())
 				{
 					metric.update(CrossEntropyInput {
@@ -267,11 +309,24 @@ impl MulticlassClassifier {
 	}
 
 	/// Write predicted probabilities into `probabilities` for the input `features`.
-	pub fn predict(&self, features: ArrayView2<f32>, mut probabilities: ArrayViewMut2<f32>) {
+	pub fn predict(&self, features: ArrayView2<f32>, probabilities: ArrayViewMut2<f32>) {
+		self.predict_features(features, probabilities);
+	}
+
+	/// Write predicted probabilities into `probabilities` for the input sparse `features`.
+	pub fn predict_sparse(&self, features: CsrMatrixView, probabilities: ArrayViewMut2<f32>) {
+		self.predict_features(features, probabilities);
+	}
+
+	fn predict_features(
+		&self,
+		features: impl FeatureMatrix,
+		mut probabilities: ArrayViewMut2<f32>,
+	) {
 		for mut row in probabilities.axis_iter_mut(Axis(0)) {
 			row.assign(&self.biases.view());
 		}
-		ndarray::linalg::general_mat_mul(1.0, &features, &self.weights, 1.0, &mut probabilities);
+		features.mul_matrix_add(self.weights.view(), probabilities.view_mut());
 		softmax(probabilities);
 	}
 
@@ -296,6 +351,17 @@ impl MulticlassClassifier {
 			.collect()
 	}
 
+	pub fn compute_feature_contributions_sparse(
+		&self,
+		features: CsrMatrixView,
+	) -> Vec<Vec<ComputeShapValuesForExampleOutput>> {
+		features
+			.row_chunks(1)
+			.into_iter()
+			.flat_map(|features| self.compute_feature_contributions(features.to_dense().view()))
+			.collect()
+	}
+
 	pub fn from_reader(
 		multiclass_classifier: crate::serialize::MulticlassClassifierReader,
 	) -> MulticlassClassifier {
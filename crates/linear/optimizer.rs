@@ -0,0 +1,68 @@
+use crate::Optimizer;
+use num::ToPrimitive;
+
+/// This struct holds the state an optimizer accumulates for each parameter over the course of training. Parameters are addressed by a flat index, with the weights first followed by the biases.
+pub struct OptimizerState {
+	first_moments: Vec<f32>,
+	second_moments: Vec<f32>,
+}
+
+impl OptimizerState {
+	pub fn new(optimizer: &Optimizer, n_parameters: usize) -> OptimizerState {
+		let (n_first_moments, n_second_moments) = match optimizer {
+			Optimizer::Sgd => (0, 0),
+			Optimizer::Momentum { .. } => (n_parameters, 0),
+			Optimizer::Adam { .. } => (n_parameters, n_parameters),
+		};
+		OptimizerState {
+			first_moments: vec![0.0; n_first_moments],
+			second_moments: vec![0.0; n_second_moments],
+		}
+	}
+}
+
+/// Batches are trained in parallel, so each batch needs a mutable reference to the shared optimizer state in the same way it needs one to the model.
+pub struct OptimizerStatePtr(pub *mut OptimizerState);
+unsafe impl Send for OptimizerStatePtr {}
+unsafe impl Sync for OptimizerStatePtr {}
+
+/// This struct is passed to `train_batch` to update the model parameters from the gradients of a single batch.
+pub struct OptimizerStep<'a> {
+	pub optimizer: &'a Optimizer,
+	pub state: &'a mut OptimizerState,
+	/// This is the learning rate for the current epoch after applying the learning rate schedule.
+	pub learning_rate: f32,
+	/// This is the one based index of the batch across all epochs. Adam uses it to correct the bias of its moment estimates toward zero early in training.
+	pub step: usize,
+}
+
+impl<'a> OptimizerStep<'a> {
+	/// Update the parameter at `index` given its gradient.
+	pub fn update(&mut self, index: usize, parameter: &mut f32, gradient: f32) {
+		let learning_rate = self.learning_rate;
+		match *self.optimizer {
+			Optimizer::Sgd => {
+				*parameter += -learning_rate * gradient;
+			}
+			Optimizer::Momentum { momentum } => {
+				let velocity = &mut self.state.first_moments[index];
+				*velocity = momentum * *velocity + gradient;
+				*parameter += -learning_rate * *velocity;
+			}
+			Optimizer::Adam {
+				beta_1,
+				beta_2,
+				epsilon,
+			} => {
+				let first_moment = &mut self.state.first_moments[index];
+				*first_moment = beta_1 * *first_moment + (1.0 - beta_1) * gradient;
+				let second_moment = &mut self.state.second_moments[index];
+				*second_moment = beta_2 * *second_moment + (1.0 - beta_2) * gradient * gradient;
+				let step = self.step.to_i32().unwrap_or(i32::MAX);
+				let first_moment = *first_moment / (1.0 - beta_1.powi(step));
+				let second_moment = *second_moment / (1.0 - beta_2.powi(step));
+				*parameter += -learning_rate * first_moment / (second_moment.sqrt() + epsilon);
+			}
+		}
+	}
+}
@@ -1,14 +1,17 @@
 use crate::Progress;
 
 use super::{
+	matrix::FeatureMatrix,
+	optimizer::{OptimizerState, OptimizerStatePtr, OptimizerStep},
 	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
 	train_early_stopping_split, EarlyStoppingMonitor, TrainOptions, TrainProgressEvent,
 };
+use modelfox_features::CsrMatrixView;
 use modelfox_metrics::MeanSquaredError;
 use modelfox_progress_counter::ProgressCounter;
 use modelfox_table::prelude::*;
 use modelfox_zip::{pzip, zip};
-use ndarray::{self, prelude::*};
+use ndarray::prelude::*;
 use num::ToPrimitive;
 use rayon::{self, prelude::*};
 
@@ -40,6 +43,25 @@ impl Regressor {
 		labels: NumberTableColumnView,
 		train_options: &TrainOptions,
 		progress: Progress,
+	) -> RegressorTrainOutput {
+		Regressor::train_features(features, labels, train_options, progress)
+	}
+
+	/// Train a linear regressor on sparse features.
+	pub fn train_sparse(
+		features: CsrMatrixView,
+		labels: NumberTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> RegressorTrainOutput {
+		Regressor::train_features(features, labels, train_options, progress)
+	}
+
+	fn train_features(
+		features: impl FeatureMatrix,
+		labels: NumberTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
 	) -> RegressorTrainOutput {
 		let n_features = features.ncols();
 		let (features_train, labels_train, features_early_stopping, labels_early_stopping) =
@@ -52,10 +74,7 @@ impl Regressor {
 					.map(|o| o.early_stopping_fraction)
 					.unwrap_or(0.0),
 			);
-		let means = features_train
-			.axis_iter(Axis(1))
-			.map(|column| column.mean().unwrap())
-			.collect();
+		let means = features_train.column_means();
 		let mut model = Regressor {
 			bias: 0.0,
 			weights: <Array1<f32>>::zeros(n_features),
@@ -79,28 +98,44 @@ impl Regressor {
 		} else {
 			None
 		};
+		let mut optimizer_state = OptimizerState::new(&train_options.optimizer, n_features + 1);
+		let n_batches_per_epoch = (features_train.nrows() + train_options.n_examples_per_batch - 1)
+			/ train_options.n_examples_per_batch;
 		let kill_chip = progress.kill_chip;
-		for _ in 0..train_options.max_epochs {
+		let features_train_chunks = features_train.row_chunks(train_options.n_examples_per_batch);
+		for epoch in 0..train_options.max_epochs {
 			progress_counter.inc(1);
 			let n_examples_per_batch = train_options.n_examples_per_batch;
 			struct RegressorPtr(*mut Regressor);
 			unsafe impl Send for RegressorPtr {}
 			unsafe impl Sync for RegressorPtr {}
 			let model_ptr = RegressorPtr(&mut model);
+			let optimizer_state_ptr = OptimizerStatePtr(&mut optimizer_state);
+			let learning_rate = train_options
+				.learning_rate_schedule
+				.learning_rate(train_options.learning_rate, epoch);
 			pzip!(
-				features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+				features_train_chunks.par_iter().copied(),
 				labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
 				predictions_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch),
 			)
-			.for_each(|(features, labels, predictions)| {
+			.enumerate()
+			.for_each(|(batch_index, (features, labels, predictions))| {
 				let model_ptr = &model_ptr;
 				let model = unsafe { &mut *model_ptr.0 };
+				let optimizer_state_ptr = &optimizer_state_ptr;
+				let optimizer_step = OptimizerStep {
+					optimizer: &train_options.optimizer,
+					state: unsafe { &mut *optimizer_state_ptr.0 },
+					learning_rate,
+					step: epoch * n_batches_per_epoch + batch_index + 1,
+				};
 				Regressor::train_batch(
 					model,
 					features,
 					labels,
 					predictions,
-					train_options,
+					optimizer_step,
 					kill_chip,
 				);
 			});
@@ -151,27 +186,30 @@ impl Regressor {
 
 	fn train_batch(
 		&mut self,
-		features: ArrayView2<f32>,
+		features: impl FeatureMatrix,
 		labels: ArrayView1<f32>,
 		mut predictions: ArrayViewMut1<f32>,
-		train_options: &TrainOptions,
+		mut optimizer_step: OptimizerStep,
 		kill_chip: &modelfox_kill_chip::KillChip,
 	) {
 		if kill_chip.is_activated() {
 			return;
 		}
-		let learning_rate = train_options.learning_rate;
-		let p = features.dot(&self.weights) + self.bias;
+		let mut p = Array1::from_elem(features.nrows(), self.bias);
+		features.mul_vector_add(self.weights.view(), p.view_mut());
 		for (prediction, p) in zip!(predictions.iter_mut(), p.iter()) {
 			*prediction = *p;
 		}
-		let py = (p - labels).insert_axis(Axis(1));
-		let weight_gradients = (&features * &py).mean_axis(Axis(0)).unwrap();
-		let bias_gradient = py.mean_axis(Axis(0)).unwrap()[0];
-		for (weight, weight_gradient) in zip!(self.weights.iter_mut(), weight_gradients.iter()) {
-			*weight += -learning_rate * weight_gradient;
+		let py = p - labels;
+		let weight_gradients = features.scaled_row_mean(py.view());
+		let bias_gradient = py.mean().unwrap();
+		let n_features = self.weights.len();
+		for (index, (weight, weight_gradient)) in
+			zip!(self.weights.iter_mut(), weight_gradients.iter()).enumerate()
+		{
+			optimizer_step.update(index, weight, *weight_gradient);
 		}
-		self.bias += -learning_rate * bias_gradient;
+		optimizer_step.update(n_features, &mut self.bias, bias_gradient);
 	}
 
 	fn compute_loss(predictions: ArrayView1<f32>, labels: ArrayView1<f32>) -> f32 {
@@ -184,12 +222,12 @@ impl Regressor {
 
 	fn compute_early_stopping_metric_value(
 		&self,
-		features: ArrayView2<f32>,
+		features: impl FeatureMatrix,
 		labels: ArrayView1<f32>,
 		train_options: &TrainOptions,
 	) -> f32 {
 		pzip!(
-			features.axis_chunks_iter(Axis(0), train_options.n_examples_per_batch),
+			features.row_chunks(train_options.n_examples_per_batch),
 			labels.axis_chunks_iter(Axis(0), train_options.n_examples_per_batch),
 		)
 		.fold(
@@ -203,7 +241,7 @@ impl Regressor {
 			|(mut predictions, mut metric), (features, labels)| {
 				let slice = s![0..features.nrows()];
 				let mut predictions_slice = predictions.slice_mut(slice);
-				self.predict(features, predictions_slice.view_mut());
+				self.predict_features(features, predictions_slice.view_mut());
 				for (prediction, label) in zip!(predictions_slice.iter(), labels.iter()) {
 					metric.update((*prediction, *label));
 				}
@@ -220,9 +258,18 @@ impl Regressor {
 	}
 
 	/// Write predictions into `predictions` for the input `features`.
-	pub fn predict(&self, features: ArrayView2<f32>, mut predictions: ArrayViewMut1<f32>) {
+	pub fn predict(&self, features: ArrayView2<f32>, predictions: ArrayViewMut1<f32>) {
+		self.predict_features(features, predictions);
+	}
+
+	/// Write predictions into `predictions` for the input sparse `features`.
+	pub fn predict_sparse(&self, features: CsrMatrixView, predictions: ArrayViewMut1<f32>) {
+		self.predict_features(features, predictions);
+	}
+
+	fn predict_features(&self, features: impl FeatureMatrix, mut predictions: ArrayViewMut1<f32>) {
 		predictions.fill(self.bias);
-		ndarray::linalg::general_mat_vec_mul(1.0, &features, &self.weights, 1.0, &mut predictions);
+		features.mul_vector_add(self.weights.view(), predictions);
 	}
 
 	pub fn compute_feature_contributions(
@@ -242,6 +289,24 @@ impl Regressor {
 			.collect()
 	}
 
+	pub fn compute_feature_contributions_sparse(
+		&self,
+		features: CsrMatrixView,
+	) -> Vec<ComputeShapValuesForExampleOutput> {
+		features
+			.row_chunks(1)
+			.into_iter()
+			.map(|features| {
+				compute_shap_values_for_example(
+					features.to_dense().as_slice().unwrap(),
+					self.bias,
+					self.weights.view(),
+					&self.means,
+				)
+			})
+			.collect()
+	}
+
 	pub fn from_reader(regressor: crate::serialize::RegressorReader) -> Regressor {
 		crate::serialize::deserialize_regressor(regressor)
 	}
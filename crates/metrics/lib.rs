@@ -1,5 +1,5 @@
 /*!
-This crate implements a number of metrics such as [`MeanSquaredError`](crate::mean_squared_error::MeanSquaredError) and [`Accuracy`](crate::accuracy::Accuracy).
+This crate implements a number of metrics such as [`MeanSquaredError`](crate::mean_squared_error::MeanSquaredError) and [`Accuracy`](crate::accuracy::Accuracy), and the [`QuantileSketch`], which estimates quantiles of large or streaming columns.
 */
 
 pub use self::{
@@ -17,9 +17,11 @@ pub use self::{
 	mode::Mode,
 	multiclass_classification::{
 		ClassMetrics, MulticlassClassificationMetrics, MulticlassClassificationMetricsInput,
-		MulticlassClassificationMetricsOutput,
+		MulticlassClassificationMetricsOutput, RocCurve, RocCurvePoint,
 	},
+	quantile_sketch::QuantileSketch,
 	regression::{RegressionMetrics, RegressionMetricsInput, RegressionMetricsOutput},
+	tolerance_accuracy::{Tolerance, ToleranceAccuracy},
 };
 
 mod accuracy;
@@ -32,4 +34,6 @@ mod mean_squared_error;
 mod mean_variance;
 mod mode;
 mod multiclass_classification;
+mod quantile_sketch;
 mod regression;
+mod tolerance_accuracy;
@@ -1,3 +1,7 @@
+use crate::{
+	BinaryClassificationMetrics, BinaryClassificationMetricsInput,
+	BinaryClassificationMetricsOutput,
+};
 use modelfox_zip::zip;
 use ndarray::prelude::*;
 use num::ToPrimitive;
@@ -7,6 +11,10 @@ use std::num::NonZeroUsize;
 pub struct MulticlassClassificationMetrics {
 	/// The shape of the confusion matrix is (n_classes x n_classes).
 	confusion_matrix: Array2<u64>,
+	/// These are the one-vs-rest metrics for each class, where the class is treated as the positive class and all other classes are treated as the negative class.
+	class_roc_metrics: Vec<BinaryClassificationMetrics>,
+	/// These are the one-vs-rest metrics pooled across all of the classes, which are used to compute the micro averaged AUC.
+	micro_roc_metrics: BinaryClassificationMetrics,
 }
 
 /// The input to [`MulticlassClassificationMetrics`].
@@ -32,6 +40,34 @@ pub struct MulticlassClassificationMetricsOutput {
 	pub recall_unweighted: f32,
 	/// The weighted recall is a weighted mean of each class's recall weighted by the fraction of the total examples in the class.
 	pub recall_weighted: f32,
+	/// The confusion matrix has one row for each predicted class and one column for each actual class. The entry at row i and column j is the number of examples of class j that the model predicted as class i.
+	pub confusion_matrix: Vec<Vec<u64>>,
+	/// This contains the one-vs-rest receiver operating characteristic curve for each class.
+	pub class_roc_curves: Vec<RocCurve>,
+	/// The macro averaged area under the receiver operating characteristic curve is the mean of each class's one-vs-rest AUC. Classes that do not appear in the dataset, or that every example belongs to, are not included.
+	pub auc_roc_macro: f32,
+	/// The micro averaged area under the receiver operating characteristic curve is computed by pooling the one-vs-rest predictions for every class into a single curve.
+	pub auc_roc_micro: f32,
+}
+
+/// A one-vs-rest receiver operating characteristic curve for a single class.
+#[derive(Debug)]
+pub struct RocCurve {
+	/// The area under the curve is computed using the fixed number of thresholds passed to [`MulticlassClassificationMetrics::new`].
+	pub auc_roc_approx: f32,
+	/// This contains a point on the curve for each threshold.
+	pub points: Vec<RocCurvePoint>,
+}
+
+/// A point on a [`RocCurve`].
+#[derive(Debug)]
+pub struct RocCurvePoint {
+	/// The classification threshold.
+	pub threshold: f32,
+	/// The true positive rate is the fraction of examples in the class that the model predicted as belonging to the class with a probability of at least the threshold.
+	pub true_positive_rate: f32,
+	/// The false positive rate is the fraction of examples not in the class that the model predicted as belonging to the class with a probability of at least the threshold.
+	pub false_positive_rate: f32,
 }
 
 /// ClassMetrics are class specific metrics used to evaluate the model's performance on each individual class.
@@ -56,9 +92,18 @@ pub struct ClassMetrics {
 }
 
 impl MulticlassClassificationMetrics {
-	pub fn new(n_classes: usize) -> MulticlassClassificationMetrics {
+	/// Create a new `MulticlassClassificationMetrics` for the specified number of classes. The one-vs-rest ROC curves are computed at `n_thresholds` thresholds, which are chosen the same way as in [`BinaryClassificationMetrics::new`].
+	pub fn new(n_classes: usize, n_thresholds: usize) -> MulticlassClassificationMetrics {
 		let confusion_matrix = Array::zeros((n_classes, n_classes));
-		MulticlassClassificationMetrics { confusion_matrix }
+		let class_roc_metrics = (0..n_classes)
+			.map(|_| BinaryClassificationMetrics::new(n_thresholds))
+			.collect();
+		let micro_roc_metrics = BinaryClassificationMetrics::new(n_thresholds);
+		MulticlassClassificationMetrics {
+			confusion_matrix,
+			class_roc_metrics,
+			micro_roc_metrics,
+		}
 	}
 
 	pub fn update(&mut self, value: MulticlassClassificationMetricsInput) {
@@ -83,10 +128,38 @@ impl MulticlassClassificationMetrics {
 			let label = label.unwrap().get() - 1;
 			self.confusion_matrix[(prediction, label)] += 1;
 		}
+		// Update the one-vs-rest metrics for each class. Binary labels are 1-indexed with the positive class equal to 2.
+		for (class_index, class_roc_metrics) in self.class_roc_metrics.iter_mut().enumerate() {
+			let probabilities = value.probabilities.column(class_index).to_vec();
+			let labels = value
+				.labels
+				.iter()
+				.map(|label| {
+					let is_positive = label.unwrap().get() - 1 == class_index;
+					NonZeroUsize::new(if is_positive { 2 } else { 1 })
+				})
+				.collect::<Vec<_>>();
+			class_roc_metrics.update(BinaryClassificationMetricsInput {
+				probabilities: &probabilities,
+				labels: &labels,
+			});
+			self.micro_roc_metrics
+				.update(BinaryClassificationMetricsInput {
+					probabilities: &probabilities,
+					labels: &labels,
+				});
+		}
 	}
 
 	pub fn merge(&mut self, other: MulticlassClassificationMetrics) {
 		self.confusion_matrix += &other.confusion_matrix;
+		for (class_roc_metrics_a, class_roc_metrics_b) in zip!(
+			self.class_roc_metrics.iter_mut(),
+			other.class_roc_metrics.into_iter()
+		) {
+			class_roc_metrics_a.merge(class_roc_metrics_b);
+		}
+		self.micro_roc_metrics.merge(other.micro_roc_metrics);
 	}
 
 	pub fn finalize(self) -> MulticlassClassificationMetricsOutput {
@@ -141,6 +214,22 @@ impl MulticlassClassificationMetrics {
 			})
 			.sum::<f32>()
 			/ n_examples.to_f32().unwrap();
+		let confusion_matrix = confusion_matrix
+			.axis_iter(Axis(0))
+			.map(|row| row.to_vec())
+			.collect();
+		let class_roc_curves: Vec<RocCurve> = self
+			.class_roc_metrics
+			.into_iter()
+			.map(|class_roc_metrics| roc_curve(class_roc_metrics.finalize()))
+			.collect();
+		let class_aucs = class_roc_curves
+			.iter()
+			.map(|roc_curve| roc_curve.auc_roc_approx)
+			.filter(|auc_roc| auc_roc.is_finite())
+			.collect::<Vec<_>>();
+		let auc_roc_macro = class_aucs.iter().sum::<f32>() / class_aucs.len().to_f32().unwrap();
+		let auc_roc_micro = self.micro_roc_metrics.finalize().auc_roc_approx;
 		MulticlassClassificationMetricsOutput {
 			class_metrics,
 			accuracy,
@@ -148,14 +237,34 @@ impl MulticlassClassificationMetrics {
 			precision_weighted,
 			recall_unweighted,
 			recall_weighted,
+			confusion_matrix,
+			class_roc_curves,
+			auc_roc_macro,
+			auc_roc_micro,
 		}
 	}
 }
 
+fn roc_curve(output: BinaryClassificationMetricsOutput) -> RocCurve {
+	let points = output
+		.thresholds
+		.iter()
+		.map(|threshold| RocCurvePoint {
+			threshold: threshold.threshold,
+			true_positive_rate: threshold.true_positive_rate,
+			false_positive_rate: threshold.false_positive_rate,
+		})
+		.collect();
+	RocCurve {
+		auc_roc_approx: output.auc_roc_approx,
+		points,
+	}
+}
+
 #[test]
 fn test_two() {
 	let classes = vec![String::from("Cat"), String::from("Dog")];
-	let mut metrics = MulticlassClassificationMetrics::new(classes.len());
+	let mut metrics = MulticlassClassificationMetrics::new(classes.len(), 1);
 	let labels = arr1(&[
 		Some(NonZeroUsize::new(1).unwrap()),
 		Some(NonZeroUsize::new(1).unwrap()),
@@ -220,6 +329,40 @@ fn test_two() {
      precision_weighted: 0.6318681,
      recall_unweighted: 0.6125,
      recall_weighted: 0.61538464,
+     confusion_matrix: [
+         [
+             5,
+             2,
+         ],
+         [
+             3,
+             3,
+         ],
+     ],
+     class_roc_curves: [
+         RocCurve {
+             auc_roc_approx: 0.61249995,
+             points: [
+                 RocCurvePoint {
+                     threshold: 0.5,
+                     true_positive_rate: 0.625,
+                     false_positive_rate: 0.4,
+                 },
+             ],
+         },
+         RocCurve {
+             auc_roc_approx: 0.6125,
+             points: [
+                 RocCurvePoint {
+                     threshold: 0.5,
+                     true_positive_rate: 0.6,
+                     false_positive_rate: 0.375,
+                 },
+             ],
+         },
+     ],
+     auc_roc_macro: 0.61249995,
+     auc_roc_micro: 0.61538464,
  }
  "###);
 }
@@ -232,7 +375,7 @@ fn test_three() {
 		String::from("Dog"),
 		String::from("Rabbit"),
 	];
-	let mut metrics = MulticlassClassificationMetrics::new(classes.len());
+	let mut metrics = MulticlassClassificationMetrics::new(classes.len(), 1);
 	let labels = arr1(&[
 		Some(NonZeroUsize::new(1).unwrap()),
 		Some(NonZeroUsize::new(1).unwrap()),
@@ -335,6 +478,57 @@ fn test_three() {
      precision_weighted: 0.7363316,
      recall_unweighted: 0.65705127,
      recall_weighted: 0.7037037,
+     confusion_matrix: [
+         [
+             5,
+             2,
+             0,
+         ],
+         [
+             3,
+             3,
+             2,
+         ],
+         [
+             0,
+             1,
+             11,
+         ],
+     ],
+     class_roc_curves: [
+         RocCurve {
+             auc_roc_approx: 0.75986844,
+             points: [
+                 RocCurvePoint {
+                     threshold: 0.5,
+                     true_positive_rate: 0.625,
+                     false_positive_rate: 0.10526316,
+                 },
+             ],
+         },
+         RocCurve {
+             auc_roc_approx: 0.6309524,
+             points: [
+                 RocCurvePoint {
+                     threshold: 0.5,
+                     true_positive_rate: 0.5,
+                     false_positive_rate: 0.23809524,
+                 },
+             ],
+         },
+         RocCurve {
+             auc_roc_approx: 0.88736266,
+             points: [
+                 RocCurvePoint {
+                     threshold: 0.5,
+                     true_positive_rate: 0.84615386,
+                     false_positive_rate: 0.071428575,
+                 },
+             ],
+         },
+     ],
+     auc_roc_macro: 0.75939447,
+     auc_roc_micro: 0.7777778,
  }
  "###);
 }
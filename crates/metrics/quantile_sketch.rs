@@ -0,0 +1,242 @@
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+/**
+A `QuantileSketch` estimates the quantiles of a stream of weighted values in a bounded amount of memory. It is a [t-digest](https://arxiv.org/abs/1902.04023), which groups values into centroids that are kept small near the minimum and maximum, where estimates need the most precision, and allowed to grow near the median.
+
+Sketches can be merged, so they can be computed in parallel or incrementally and combined later. Until more than `5 * compression` values are inserted, no values are grouped and the quantiles are exact.
+*/
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct QuantileSketch {
+	compression: f64,
+	/// These are the centroids, sorted by mean.
+	centroids: Vec<Centroid>,
+	/// These are the values that were inserted since the centroids were last compressed.
+	buffer: Vec<Centroid>,
+	min: Option<f32>,
+	max: Option<f32>,
+}
+
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+struct Centroid {
+	mean: f64,
+	weight: f64,
+}
+
+impl Default for QuantileSketch {
+	fn default() -> QuantileSketch {
+		QuantileSketch::new(100.0)
+	}
+}
+
+impl QuantileSketch {
+	/// Create a sketch that keeps about `compression` centroids once it is compressed. Larger values make the estimates more precise and the sketch larger.
+	pub fn new(compression: f64) -> QuantileSketch {
+		QuantileSketch {
+			compression,
+			centroids: Vec::new(),
+			buffer: Vec::new(),
+			min: None,
+			max: None,
+		}
+	}
+
+	pub fn insert(&mut self, value: f32) {
+		self.insert_weighted(value, 1.0);
+	}
+
+	/// Insert a value that stands for `weight` values, such as a value in a histogram along with its count. Values that are not finite and weights that are not positive are ignored.
+	pub fn insert_weighted(&mut self, value: f32, weight: f64) {
+		if !value.is_finite() || weight.is_nan() || weight <= 0.0 {
+			return;
+		}
+		self.min = Some(self.min.map_or(value, |min| min.min(value)));
+		self.max = Some(self.max.map_or(value, |max| max.max(value)));
+		self.buffer.push(Centroid {
+			mean: value as f64,
+			weight,
+		});
+		self.compress_if_full();
+	}
+
+	pub fn merge(&mut self, other: QuantileSketch) {
+		self.min = match (self.min, other.min) {
+			(Some(a), Some(b)) => Some(a.min(b)),
+			(a, b) => a.or(b),
+		};
+		self.max = match (self.max, other.max) {
+			(Some(a), Some(b)) => Some(a.max(b)),
+			(a, b) => a.or(b),
+		};
+		self.buffer.extend(other.centroids);
+		self.buffer.extend(other.buffer);
+		self.compress_if_full();
+	}
+
+	/// Retrieve the total weight of the values inserted into the sketch.
+	pub fn count(&self) -> f64 {
+		self.centroids
+			.iter()
+			.chain(self.buffer.iter())
+			.map(|centroid| centroid.weight)
+			.sum()
+	}
+
+	pub fn min(&self) -> Option<f32> {
+		self.min
+	}
+
+	pub fn max(&self) -> Option<f32> {
+		self.max
+	}
+
+	/// Estimate the value of the quantile, between 0 and 1. This is `None` if no values were inserted.
+	pub fn quantile(&self, quantile: f32) -> Option<f32> {
+		self.quantiles(&[quantile])
+			.map(|quantiles| quantiles.into_iter().next().unwrap())
+	}
+
+	/// Estimate the values of several quantiles at once, which is faster than calling [`quantile`](QuantileSketch::quantile) for each.
+	pub fn quantiles(&self, quantiles: &[f32]) -> Option<Vec<f32>> {
+		let (min, max) = match (self.min, self.max) {
+			(Some(min), Some(max)) => (min as f64, max as f64),
+			_ => return None,
+		};
+		let mut centroids = self.centroids.clone();
+		if !self.buffer.is_empty() {
+			centroids.extend(self.buffer.iter().copied());
+			sort_centroids(&mut centroids);
+		}
+		let total_weight = centroids
+			.iter()
+			.map(|centroid| centroid.weight)
+			.sum::<f64>();
+		let quantiles = quantiles
+			.iter()
+			.map(|quantile| {
+				let rank = (*quantile as f64).clamp(0.0, 1.0) * (total_weight - 1.0).max(0.0);
+				estimate_rank(&centroids, total_weight, min, max, rank) as f32
+			})
+			.collect();
+		Some(quantiles)
+	}
+
+	fn compress_if_full(&mut self) {
+		let max_len = (5.0 * self.compression) as usize;
+		if self.centroids.len() + self.buffer.len() > max_len {
+			self.compress();
+		}
+	}
+
+	/// Merge the buffer into the centroids, then merge adjacent centroids as long as each spans at most one unit of the scale function `k(q) = compression / 2π * asin(2q - 1)`.
+	fn compress(&mut self) {
+		let mut centroids = std::mem::take(&mut self.centroids);
+		centroids.append(&mut self.buffer);
+		sort_centroids(&mut centroids);
+		let total_weight = centroids
+			.iter()
+			.map(|centroid| centroid.weight)
+			.sum::<f64>();
+		let mut centroids = centroids.into_iter();
+		let mut current = match centroids.next() {
+			Some(centroid) => centroid,
+			None => return,
+		};
+		let mut weight_before_current = 0.0;
+		let mut weight_limit = total_weight * self.max_quantile_for_centroid_starting_at(0.0);
+		for centroid in centroids {
+			if weight_before_current + current.weight + centroid.weight <= weight_limit {
+				let weight = current.weight + centroid.weight;
+				current.mean += (centroid.mean - current.mean) * centroid.weight / weight;
+				current.weight = weight;
+			} else {
+				weight_before_current += current.weight;
+				self.centroids.push(current);
+				weight_limit = total_weight
+					* self.max_quantile_for_centroid_starting_at(
+						weight_before_current / total_weight,
+					);
+				current = centroid;
+			}
+		}
+		self.centroids.push(current);
+	}
+
+	fn max_quantile_for_centroid_starting_at(&self, quantile: f64) -> f64 {
+		let k = self.compression / TAU * (2.0 * quantile - 1.0).clamp(-1.0, 1.0).asin();
+		let angle = ((k + 1.0) * TAU / self.compression).min(FRAC_PI_2);
+		(angle.sin() + 1.0) / 2.0
+	}
+}
+
+fn sort_centroids(centroids: &mut [Centroid]) {
+	centroids.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap());
+}
+
+/// Estimate the value with the rank, between 0 and `total_weight - 1`, by interpolating between the centers of the centroids around it. The minimum and maximum are at the first and last ranks, so a sketch whose centroids each hold one value gives the same quantiles as sorting the values.
+fn estimate_rank(centroids: &[Centroid], total_weight: f64, min: f64, max: f64, rank: f64) -> f64 {
+	let mut previous = (0.0, min);
+	let mut weight_before = 0.0;
+	for centroid in centroids {
+		let center = weight_before + centroid.weight / 2.0 - 0.5;
+		if rank <= center {
+			return interpolate(previous, (center, centroid.mean), rank);
+		}
+		previous = (center, centroid.mean);
+		weight_before += centroid.weight;
+	}
+	interpolate(previous, (total_weight - 1.0, max), rank)
+}
+
+fn interpolate((x0, y0): (f64, f64), (x1, y1): (f64, f64), x: f64) -> f64 {
+	if x1 <= x0 {
+		y1
+	} else {
+		y0 + (x - x0) / (x1 - x0) * (y1 - y0)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_exact_until_compressed() {
+		let mut sketch = QuantileSketch::default();
+		for value in [5.0, 1.0, 4.0, 2.0, 3.0] {
+			sketch.insert(value);
+		}
+		assert_eq!(
+			sketch.quantiles(&[0.0, 0.25, 0.5, 0.875, 1.0]),
+			Some(vec![1.0, 2.0, 3.0, 4.5, 5.0])
+		);
+		assert_eq!(QuantileSketch::default().quantile(0.5), None);
+	}
+
+	#[test]
+	fn test_merge() {
+		let mut a = QuantileSketch::default();
+		let mut b = QuantileSketch::default();
+		for i in 0..100_000 {
+			let value = i as f32 / 100_000.0;
+			if i % 3 == 0 {
+				a.insert(value);
+			} else {
+				b.insert_weighted(value, 1.0);
+			}
+		}
+		a.merge(b);
+		assert_eq!(a.count(), 100_000.0);
+		assert!(a.centroids.len() + a.buffer.len() <= 500);
+		assert_eq!(a.min(), Some(0.0));
+		assert_eq!(a.max(), Some(0.99999));
+		for quantile in [0.001, 0.1, 0.5, 0.9, 0.999] {
+			let estimate = a.quantile(quantile).unwrap();
+			assert!(
+				(estimate - quantile).abs() < 0.002,
+				"{} {}",
+				quantile,
+				estimate
+			);
+		}
+	}
+}
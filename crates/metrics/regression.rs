@@ -1,4 +1,7 @@
-use super::mean_variance::{MeanVariance, MeanVarianceOutput};
+use super::{
+	mean_variance::{MeanVariance, MeanVarianceOutput},
+	tolerance_accuracy::{Tolerance, ToleranceAccuracy},
+};
 use modelfox_zip::zip;
 use num::ToPrimitive;
 
@@ -7,6 +10,7 @@ pub struct RegressionMetrics {
 	mean_variance: MeanVariance,
 	absolute_error: f64,
 	squared_error: f64,
+	tolerance_accuracy: Option<ToleranceAccuracy>,
 }
 
 /// The input to [`RegressionMetrics`].
@@ -26,6 +30,8 @@ pub struct RegressionMetricsOutput {
 	pub mae: f32,
 	/// The r-squared value. https://en.wikipedia.org/wiki/Coefficient_of_determination.
 	pub r2: f32,
+	/// The fraction of predictions within the tolerance passed to [`RegressionMetrics::with_tolerance`]. This is `None` if no tolerance was provided.
+	pub tolerance_accuracy: Option<f32>,
 }
 
 impl Default for RegressionMetrics {
@@ -34,6 +40,7 @@ impl Default for RegressionMetrics {
 			mean_variance: MeanVariance::default(),
 			absolute_error: 0.0,
 			squared_error: 0.0,
+			tolerance_accuracy: None,
 		}
 	}
 }
@@ -43,6 +50,14 @@ impl RegressionMetrics {
 		RegressionMetrics::default()
 	}
 
+	/// Create a `RegressionMetrics` that also computes the fraction of predictions within `tolerance` of the labels.
+	pub fn with_tolerance(tolerance: Option<Tolerance>) -> RegressionMetrics {
+		RegressionMetrics {
+			tolerance_accuracy: tolerance.map(ToleranceAccuracy::new),
+			..RegressionMetrics::default()
+		}
+	}
+
 	pub fn update(&mut self, input: RegressionMetricsInput) {
 		for (prediction, label) in zip!(input.predictions.iter(), input.labels.iter()) {
 			self.mean_variance.update(*label);
@@ -50,6 +65,9 @@ impl RegressionMetrics {
 			let squared_error = absolute_error * absolute_error;
 			self.absolute_error += absolute_error as f64;
 			self.squared_error += squared_error as f64;
+			if let Some(tolerance_accuracy) = self.tolerance_accuracy.as_mut() {
+				tolerance_accuracy.update((*prediction, *label));
+			}
 		}
 	}
 
@@ -57,6 +75,11 @@ impl RegressionMetrics {
 		self.mean_variance.merge(other.mean_variance);
 		self.absolute_error += other.absolute_error;
 		self.squared_error += other.squared_error;
+		if let (Some(tolerance_accuracy), Some(other)) =
+			(self.tolerance_accuracy.as_mut(), other.tolerance_accuracy)
+		{
+			tolerance_accuracy.merge(other);
+		}
 	}
 
 	pub fn finalize(self) -> RegressionMetricsOutput {
@@ -70,6 +93,9 @@ impl RegressionMetrics {
 			mse: mse.to_f32().unwrap(),
 			r2: r2.to_f32().unwrap(),
 			rmse: rmse.to_f32().unwrap(),
+			tolerance_accuracy: self
+				.tolerance_accuracy
+				.and_then(|tolerance_accuracy| tolerance_accuracy.finalize()),
 		}
 	}
 }
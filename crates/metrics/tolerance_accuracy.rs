@@ -0,0 +1,55 @@
+use super::mean::Mean;
+
+/// A tolerance determines how close a regressor's prediction must be to the true value to be counted as accurate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tolerance {
+	/// The prediction must be within this distance of the true value.
+	Absolute(f32),
+	/// The prediction must be within this fraction of the magnitude of the true value.
+	Relative(f32),
+}
+
+impl Tolerance {
+	pub fn contains(&self, prediction: f32, label: f32) -> bool {
+		let error = (prediction - label).abs();
+		match self {
+			Tolerance::Absolute(tolerance) => error <= *tolerance,
+			Tolerance::Relative(tolerance) => error <= *tolerance * label.abs(),
+		}
+	}
+}
+
+/// The tolerance accuracy is the proportion of examples where the prediction is within a [`Tolerance`] of the label.
+pub struct ToleranceAccuracy {
+	tolerance: Tolerance,
+	mean: Mean,
+}
+
+impl ToleranceAccuracy {
+	pub fn new(tolerance: Tolerance) -> ToleranceAccuracy {
+		ToleranceAccuracy {
+			tolerance,
+			mean: Mean::new(),
+		}
+	}
+}
+
+impl ToleranceAccuracy {
+	pub fn update(&mut self, value: (f32, f32)) {
+		let (prediction, label) = value;
+		self.mean
+			.update(if self.tolerance.contains(prediction, label) {
+				1.0
+			} else {
+				0.0
+			})
+	}
+
+	pub fn merge(&mut self, other: ToleranceAccuracy) {
+		self.mean.merge(other.mean)
+	}
+
+	pub fn finalize(self) -> Option<f32> {
+		self.mean.finalize()
+	}
+}
@@ -1,5 +1,6 @@
 use crate::{
-	ColumnStats, FeatureGroup, LinearModelTrainOptions, StatsSettings, TrainGridItemOutput,
+	ColumnCorrelation, ColumnInvalidValuePolicy, ColumnStats, DroppedColumn, FeatureGroup,
+	LinearModelTrainOptions, StatsSettings, TrainGridItemOutput, TreeBinThresholds,
 	TreeModelTrainOptions,
 };
 
@@ -44,6 +45,15 @@ pub struct BinaryClassifier {
 	pub model: BinaryClassificationModel,
 	#[buffalo(id = 18, required)]
 	pub test_metrics: BinaryClassificationMetrics,
+	/// These are the columns that the feature selection pass dropped. It is absent in models trained before feature selection was added.
+	#[buffalo(id = 19)]
+	pub dropped_columns: Vec<DroppedColumn>,
+	/// These are the invalid value policies of the columns whose policy is not to treat invalid values as missing. It is absent in models trained before invalid value policies were added.
+	#[buffalo(id = 20)]
+	pub invalid_value_policies: Vec<ColumnInvalidValuePolicy>,
+	/// These are the pairs of columns in the train table with the largest absolute correlation, starting with the largest. It is absent in models trained before column correlations were added.
+	#[buffalo(id = 21)]
+	pub column_correlations: Vec<ColumnCorrelation>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -51,6 +61,14 @@ pub struct BinaryClassifier {
 pub enum BinaryClassificationComparisonMetric {
 	#[buffalo(id = 0)]
 	Aucroc,
+	#[buffalo(id = 1)]
+	F1,
+	#[buffalo(id = 2)]
+	Precision,
+	#[buffalo(id = 3)]
+	Recall,
+	#[buffalo(id = 4)]
+	Accuracy,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -113,6 +131,9 @@ pub struct LinearBinaryClassifier {
 	pub losses: Option<Vec<f32>>,
 	#[buffalo(id = 4, required)]
 	pub feature_importances: Vec<f32>,
+	/// This is the standard deviation of each feature importance across the cross validation folds. It is absent when the model was evaluated on a single fold.
+	#[buffalo(id = 5)]
+	pub feature_importances_std: Vec<f32>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -128,4 +149,9 @@ pub struct TreeBinaryClassifier {
 	pub losses: Option<Vec<f32>>,
 	#[buffalo(id = 4, required)]
 	pub feature_importances: Vec<f32>,
+	#[buffalo(id = 5)]
+	pub bin_thresholds: Vec<TreeBinThresholds>,
+	/// This is the standard deviation of each feature importance across the cross validation folds. It is absent when the model was evaluated on a single fold.
+	#[buffalo(id = 6)]
+	pub feature_importances_std: Vec<f32>,
 }
@@ -0,0 +1,522 @@
+/*!
+This module compares two models, such as two versions of a model trained on the same dataset, and reports what changed between them.
+*/
+
+use crate::{
+	BinaryClassificationModelReader, FeatureGroupReader, ModelInnerReader, ModelReader,
+	MulticlassClassificationModelReader, RegressionModelReader,
+};
+use num::ToPrimitive;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, serde::Serialize)]
+pub struct ModelDiff {
+	pub hyperparameters: Vec<HyperparameterDiff>,
+	pub feature_groups: Vec<FeatureGroupDiff>,
+	pub metrics: Vec<MetricDiff>,
+	pub feature_importances: Vec<FeatureImportanceDiff>,
+}
+
+/// A hyperparameter whose value differs between the two models. The value is `None` if the hyperparameter does not apply to one of the models, for example because one is a linear model and the other is a tree model.
+#[derive(Debug, serde::Serialize)]
+pub struct HyperparameterDiff {
+	pub name: String,
+	pub a: Option<String>,
+	pub b: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum FeatureGroupDiff {
+	Added {
+		feature_group: String,
+	},
+	Removed {
+		feature_group: String,
+	},
+	/// The feature group exists in both models, but its variants or vocabulary changed.
+	Changed {
+		feature_group: String,
+		added_variants: Vec<String>,
+		removed_variants: Vec<String>,
+	},
+}
+
+/// A test metric reported by both models.
+#[derive(Debug, serde::Serialize)]
+pub struct MetricDiff {
+	pub name: String,
+	pub a: f32,
+	pub b: f32,
+	pub delta: f32,
+}
+
+/// A feature whose rank by importance differs between the two models. Ranks start at 1 for the most important feature. A rank is `None` if the feature does not exist in one of the models.
+#[derive(Debug, serde::Serialize)]
+pub struct FeatureImportanceDiff {
+	pub feature_name: String,
+	pub a_rank: Option<usize>,
+	pub b_rank: Option<usize>,
+}
+
+struct ModelSummary {
+	hyperparameters: Vec<(String, String)>,
+	feature_groups: BTreeMap<String, BTreeSet<String>>,
+	metrics: Vec<(&'static str, f32)>,
+	feature_importances: Vec<(String, f32)>,
+}
+
+/// Compare model `a` to model `b`.
+pub fn diff(a: ModelReader, b: ModelReader) -> ModelDiff {
+	let a = summarize(a);
+	let b = summarize(b);
+	ModelDiff {
+		hyperparameters: diff_hyperparameters(&a.hyperparameters, &b.hyperparameters),
+		feature_groups: diff_feature_groups(&a.feature_groups, &b.feature_groups),
+		metrics: diff_metrics(&a.metrics, &b.metrics),
+		feature_importances: diff_feature_importances(
+			&a.feature_importances,
+			&b.feature_importances,
+		),
+	}
+}
+
+fn summarize(model: ModelReader) -> ModelSummary {
+	match model.inner() {
+		ModelInnerReader::Regressor(regressor) => {
+			let regressor = regressor.read();
+			let best_grid_item = regressor
+				.train_grid_item_outputs()
+				.get(regressor.best_grid_item_index().to_usize().unwrap())
+				.unwrap();
+			let (model_type, feature_groups, feature_importances) = match regressor.model() {
+				RegressionModelReader::Linear(model) => {
+					let model = model.read();
+					(
+						"linear",
+						model.feature_groups(),
+						model.feature_importances(),
+					)
+				}
+				RegressionModelReader::Tree(model) => {
+					let model = model.read();
+					("tree", model.feature_groups(), model.feature_importances())
+				}
+			};
+			let test_metrics = regressor.test_metrics();
+			let mut metrics = vec![
+				("mse", test_metrics.mse()),
+				("rmse", test_metrics.rmse()),
+				("mae", test_metrics.mae()),
+				("r2", test_metrics.r2()),
+			];
+			if let Some(tolerance_accuracy) = test_metrics.tolerance_accuracy() {
+				metrics.push(("tolerance_accuracy", tolerance_accuracy));
+			}
+			ModelSummary {
+				hyperparameters: summarize_hyperparameters(
+					"regressor",
+					regressor.target_column_name(),
+					model_type,
+					best_grid_item.hyperparameters().hyperparameters(),
+				),
+				feature_groups: summarize_feature_groups(feature_groups.iter()),
+				metrics,
+				feature_importances: summarize_feature_importances(
+					feature_groups.iter(),
+					feature_importances.iter(),
+				),
+			}
+		}
+		ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			let binary_classifier = binary_classifier.read();
+			let best_grid_item = binary_classifier
+				.train_grid_item_outputs()
+				.get(binary_classifier.best_grid_item_index().to_usize().unwrap())
+				.unwrap();
+			let (model_type, feature_groups, feature_importances) = match binary_classifier.model()
+			{
+				BinaryClassificationModelReader::Linear(model) => {
+					let model = model.read();
+					(
+						"linear",
+						model.feature_groups(),
+						model.feature_importances(),
+					)
+				}
+				BinaryClassificationModelReader::Tree(model) => {
+					let model = model.read();
+					("tree", model.feature_groups(), model.feature_importances())
+				}
+			};
+			let test_metrics = binary_classifier.test_metrics();
+			let default_threshold_metrics = test_metrics.default_threshold();
+			let mut metrics = vec![
+				("auc_roc", test_metrics.auc_roc()),
+				("accuracy", default_threshold_metrics.accuracy()),
+			];
+			metrics.extend(
+				[
+					("precision", default_threshold_metrics.precision()),
+					("recall", default_threshold_metrics.recall()),
+					("f1_score", default_threshold_metrics.f1_score()),
+				]
+				.into_iter()
+				.filter_map(|(name, value)| value.map(|value| (name, value))),
+			);
+			ModelSummary {
+				hyperparameters: summarize_hyperparameters(
+					"binary_classifier",
+					binary_classifier.target_column_name(),
+					model_type,
+					best_grid_item.hyperparameters().hyperparameters(),
+				),
+				feature_groups: summarize_feature_groups(feature_groups.iter()),
+				metrics,
+				feature_importances: summarize_feature_importances(
+					feature_groups.iter(),
+					feature_importances.iter(),
+				),
+			}
+		}
+		ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier = multiclass_classifier.read();
+			let best_grid_item = multiclass_classifier
+				.train_grid_item_outputs()
+				.get(
+					multiclass_classifier
+						.best_grid_item_index()
+						.to_usize()
+						.unwrap(),
+				)
+				.unwrap();
+			let (model_type, feature_groups, feature_importances) =
+				match multiclass_classifier.model() {
+					MulticlassClassificationModelReader::Linear(model) => {
+						let model = model.read();
+						(
+							"linear",
+							model.feature_groups(),
+							model.feature_importances(),
+						)
+					}
+					MulticlassClassificationModelReader::Tree(model) => {
+						let model = model.read();
+						("tree", model.feature_groups(), model.feature_importances())
+					}
+				};
+			let test_metrics = multiclass_classifier.test_metrics();
+			ModelSummary {
+				hyperparameters: summarize_hyperparameters(
+					"multiclass_classifier",
+					multiclass_classifier.target_column_name(),
+					model_type,
+					best_grid_item.hyperparameters().hyperparameters(),
+				),
+				feature_groups: summarize_feature_groups(feature_groups.iter()),
+				metrics: vec![
+					("accuracy", test_metrics.accuracy()),
+					("precision_unweighted", test_metrics.precision_unweighted()),
+					("precision_weighted", test_metrics.precision_weighted()),
+					("recall_unweighted", test_metrics.recall_unweighted()),
+					("recall_weighted", test_metrics.recall_weighted()),
+				],
+				feature_importances: summarize_feature_importances(
+					feature_groups.iter(),
+					feature_importances.iter(),
+				),
+			}
+		}
+	}
+}
+
+fn summarize_hyperparameters(
+	task: &str,
+	target_column_name: &str,
+	model_type: &str,
+	hyperparameters: Vec<(String, String)>,
+) -> Vec<(String, String)> {
+	vec![
+		("task".to_owned(), task.to_owned()),
+		(
+			"target_column_name".to_owned(),
+			target_column_name.to_owned(),
+		),
+		("model_type".to_owned(), model_type.to_owned()),
+	]
+	.into_iter()
+	.chain(hyperparameters)
+	.collect()
+}
+
+/// Key each feature group by its type and source columns, and collect the variants or vocabulary it encodes.
+fn summarize_feature_groups<'a>(
+	feature_groups: impl Iterator<Item = FeatureGroupReader<'a>>,
+) -> BTreeMap<String, BTreeSet<String>> {
+	feature_groups
+		.map(|feature_group| match feature_group {
+			FeatureGroupReader::Identity(feature_group) => {
+				let feature_group = feature_group.read();
+				(
+					format!("identity({})", feature_group.source_column_name()),
+					BTreeSet::new(),
+				)
+			}
+			FeatureGroupReader::Normalized(feature_group) => {
+				let feature_group = feature_group.read();
+				(
+					format!("normalized({})", feature_group.source_column_name()),
+					BTreeSet::new(),
+				)
+			}
+			FeatureGroupReader::OneHotEncoded(feature_group) => {
+				let feature_group = feature_group.read();
+				(
+					format!("one_hot_encoded({})", feature_group.source_column_name()),
+					feature_group
+						.variants()
+						.iter()
+						.map(|variant| variant.to_owned())
+						.collect(),
+				)
+			}
+			FeatureGroupReader::BagOfWords(feature_group) => {
+				let feature_group = feature_group.read();
+				(
+					format!("bag_of_words({})", feature_group.source_column_name()),
+					feature_group
+						.ngrams()
+						.iter()
+						.map(|(ngram, _)| ngram.to_string())
+						.collect(),
+				)
+			}
+			FeatureGroupReader::BagOfWordsCosineSimilarity(feature_group) => {
+				let feature_group = feature_group.read();
+				(
+					format!(
+						"bag_of_words_cosine_similarity({}, {})",
+						feature_group.source_column_name_a(),
+						feature_group.source_column_name_b(),
+					),
+					feature_group
+						.ngrams()
+						.iter()
+						.map(|(ngram, _)| ngram.to_string())
+						.collect(),
+				)
+			}
+			FeatureGroupReader::WordEmbedding(feature_group) => {
+				let feature_group = feature_group.read();
+				(
+					format!("word_embedding({})", feature_group.source_column_name()),
+					BTreeSet::new(),
+				)
+			}
+			FeatureGroupReader::Cyclical(feature_group) => {
+				let feature_group = feature_group.read();
+				(
+					format!(
+						"cyclical({}, period = {}, offset = {})",
+						feature_group.source_column_name(),
+						feature_group.period(),
+						feature_group.offset(),
+					),
+					BTreeSet::new(),
+				)
+			}
+		})
+		.collect()
+}
+
+fn summarize_feature_importances<'a>(
+	feature_groups: impl Iterator<Item = FeatureGroupReader<'a>>,
+	feature_importances: impl Iterator<Item = f32>,
+) -> Vec<(String, f32)> {
+	feature_groups
+		.flat_map(|feature_group| feature_group.feature_names())
+		.zip(feature_importances)
+		.collect()
+}
+
+fn diff_hyperparameters(a: &[(String, String)], b: &[(String, String)]) -> Vec<HyperparameterDiff> {
+	let b_values: BTreeMap<&str, &str> = b
+		.iter()
+		.map(|(name, value)| (name.as_str(), value.as_str()))
+		.collect();
+	let a_values: BTreeMap<&str, &str> = a
+		.iter()
+		.map(|(name, value)| (name.as_str(), value.as_str()))
+		.collect();
+	// Preserve the order in which the hyperparameters are listed, with hyperparameters only in `b` last.
+	let names = a.iter().map(|(name, _)| name.as_str()).chain(
+		b.iter()
+			.map(|(name, _)| name.as_str())
+			.filter(|name| !a_values.contains_key(name)),
+	);
+	names
+		.filter_map(|name| {
+			let a_value = a_values.get(name).copied();
+			let b_value = b_values.get(name).copied();
+			if a_value == b_value {
+				return None;
+			}
+			Some(HyperparameterDiff {
+				name: name.to_owned(),
+				a: a_value.map(ToOwned::to_owned),
+				b: b_value.map(ToOwned::to_owned),
+			})
+		})
+		.collect()
+}
+
+fn diff_feature_groups(
+	a: &BTreeMap<String, BTreeSet<String>>,
+	b: &BTreeMap<String, BTreeSet<String>>,
+) -> Vec<FeatureGroupDiff> {
+	let names: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+	names
+		.into_iter()
+		.filter_map(|name| match (a.get(name), b.get(name)) {
+			(Some(_), None) => Some(FeatureGroupDiff::Removed {
+				feature_group: name.to_owned(),
+			}),
+			(None, Some(_)) => Some(FeatureGroupDiff::Added {
+				feature_group: name.to_owned(),
+			}),
+			(Some(a_variants), Some(b_variants)) => {
+				let added_variants = b_variants
+					.difference(a_variants)
+					.cloned()
+					.collect::<Vec<_>>();
+				let removed_variants = a_variants
+					.difference(b_variants)
+					.cloned()
+					.collect::<Vec<_>>();
+				if added_variants.is_empty() && removed_variants.is_empty() {
+					None
+				} else {
+					Some(FeatureGroupDiff::Changed {
+						feature_group: name.to_owned(),
+						added_variants,
+						removed_variants,
+					})
+				}
+			}
+			(None, None) => unreachable!(),
+		})
+		.collect()
+}
+
+fn diff_metrics(a: &[(&'static str, f32)], b: &[(&'static str, f32)]) -> Vec<MetricDiff> {
+	a.iter()
+		.filter_map(|(name, a_value)| {
+			let (_, b_value) = b.iter().find(|(b_name, _)| b_name == name)?;
+			Some(MetricDiff {
+				name: name.to_string(),
+				a: *a_value,
+				b: *b_value,
+				delta: b_value - a_value,
+			})
+		})
+		.collect()
+}
+
+fn diff_feature_importances(
+	a: &[(String, f32)],
+	b: &[(String, f32)],
+) -> Vec<FeatureImportanceDiff> {
+	let a_ranks = rank_feature_importances(a);
+	let b_ranks = rank_feature_importances(b);
+	let mut feature_importance_diffs = a_ranks
+		.keys()
+		.chain(b_ranks.keys().filter(|name| !a_ranks.contains_key(*name)))
+		.filter_map(|feature_name| {
+			let a_rank = a_ranks.get(feature_name).copied();
+			let b_rank = b_ranks.get(feature_name).copied();
+			if a_rank == b_rank {
+				return None;
+			}
+			Some(FeatureImportanceDiff {
+				feature_name: feature_name.to_owned(),
+				a_rank,
+				b_rank,
+			})
+		})
+		.collect::<Vec<_>>();
+	// List the features that are most important in model `b` first, followed by the features that were removed.
+	feature_importance_diffs.sort_by_key(|diff| (diff.b_rank.is_none(), diff.b_rank, diff.a_rank));
+	feature_importance_diffs
+}
+
+fn rank_feature_importances(feature_importances: &[(String, f32)]) -> BTreeMap<String, usize> {
+	let mut feature_importances = feature_importances.iter().collect::<Vec<_>>();
+	feature_importances
+		.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+	feature_importances
+		.into_iter()
+		.enumerate()
+		.map(|(index, (feature_name, _))| (feature_name.to_owned(), index + 1))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_diff_hyperparameters() {
+		let a = vec![
+			("model_type".to_owned(), "tree".to_owned()),
+			("learning_rate".to_owned(), "0.1".to_owned()),
+			("max_rounds".to_owned(), "100".to_owned()),
+		];
+		let b = vec![
+			("model_type".to_owned(), "tree".to_owned()),
+			("learning_rate".to_owned(), "0.05".to_owned()),
+			("max_depth".to_owned(), "4".to_owned()),
+		];
+		let diffs = diff_hyperparameters(&a, &b)
+			.into_iter()
+			.map(|diff| (diff.name, diff.a, diff.b))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			diffs,
+			vec![
+				(
+					"learning_rate".to_owned(),
+					Some("0.1".to_owned()),
+					Some("0.05".to_owned())
+				),
+				("max_rounds".to_owned(), Some("100".to_owned()), None),
+				("max_depth".to_owned(), None, Some("4".to_owned())),
+			]
+		);
+	}
+
+	#[test]
+	fn test_diff_feature_importances() {
+		let a = vec![
+			("age".to_owned(), 0.5),
+			("gender".to_owned(), 0.3),
+			("income".to_owned(), 0.2),
+		];
+		let b = vec![
+			("age".to_owned(), 0.2),
+			("gender".to_owned(), 0.3),
+			("zip".to_owned(), 0.5),
+		];
+		let diffs = diff_feature_importances(&a, &b)
+			.into_iter()
+			.map(|diff| (diff.feature_name, diff.a_rank, diff.b_rank))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			diffs,
+			vec![
+				("zip".to_owned(), None, Some(1)),
+				("age".to_owned(), Some(1), Some(3)),
+				("income".to_owned(), Some(3), None),
+			]
+		);
+	}
+}
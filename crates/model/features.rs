@@ -15,6 +15,8 @@ pub enum FeatureGroup {
 	WordEmbedding(WordEmbeddingFeatureGroup),
 	#[buffalo(id = 5)]
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureGroup),
+	#[buffalo(id = 6)]
+	Cyclical(CyclicalFeatureGroup),
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -76,6 +78,17 @@ pub struct BagOfWordsCosineSimilarityFeatureGroup {
 	pub ngrams: Vec<(NGram, BagOfWordsFeatureGroupNGramEntry)>,
 }
 
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct CyclicalFeatureGroup {
+	#[buffalo(id = 0, required)]
+	pub source_column_name: String,
+	#[buffalo(id = 1, required)]
+	pub period: f32,
+	#[buffalo(id = 2, required)]
+	pub offset: f32,
+}
+
 #[derive(buffalo::Read, buffalo::Write)]
 #[buffalo(size = "static", value_size = 0)]
 pub enum BagOfWordsFeatureGroupStrategy {
@@ -121,3 +134,64 @@ pub struct WordEmbeddingModel {
 	#[buffalo(id = 2, required)]
 	pub values: Vec<f32>,
 }
+
+impl<'a> FeatureGroupReader<'a> {
+	/// Retrieve a human readable name for each feature this feature group produces, in the order of the features.
+	pub fn feature_names(&self) -> Vec<String> {
+		match self {
+			FeatureGroupReader::Identity(feature_group) => {
+				let feature_group = feature_group.read();
+				vec![feature_group.source_column_name().to_owned()]
+			}
+			FeatureGroupReader::Normalized(feature_group) => {
+				let feature_group = feature_group.read();
+				vec![feature_group.source_column_name().to_owned()]
+			}
+			FeatureGroupReader::OneHotEncoded(feature_group) => {
+				let feature_group = feature_group.read();
+				vec!["OOV"]
+					.into_iter()
+					.chain(feature_group.variants().iter())
+					.map(|variant| format!("{} = {}", feature_group.source_column_name(), variant,))
+					.collect()
+			}
+			FeatureGroupReader::BagOfWords(feature_group) => {
+				let feature_group = feature_group.read();
+				feature_group
+					.ngrams()
+					.iter()
+					.map(|(ngram, _)| {
+						format!("{} contains {}", feature_group.source_column_name(), ngram)
+					})
+					.collect()
+			}
+			FeatureGroupReader::BagOfWordsCosineSimilarity(feature_group) => {
+				let feature_group = feature_group.read();
+				vec![format!(
+					"similarity of {} and {}",
+					feature_group.source_column_name_a(),
+					feature_group.source_column_name_b(),
+				)]
+			}
+			FeatureGroupReader::WordEmbedding(feature_group) => {
+				let feature_group = feature_group.read();
+				(0..feature_group.model().size())
+					.map(|i| {
+						format!(
+							"{} word embedding value {}",
+							feature_group.source_column_name(),
+							i
+						)
+					})
+					.collect()
+			}
+			FeatureGroupReader::Cyclical(feature_group) => {
+				let feature_group = feature_group.read();
+				vec![
+					format!("{} (sin)", feature_group.source_column_name()),
+					format!("{} (cos)", feature_group.source_column_name()),
+				]
+			}
+		}
+	}
+}
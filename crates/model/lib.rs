@@ -9,9 +9,10 @@ pub use self::{
 use anyhow::{bail, Result};
 use fnv::FnvHashMap;
 use num::ToPrimitive;
-use std::{io::prelude::*, path::Path};
+use std::{collections::BTreeMap, io::prelude::*, path::Path};
 
 mod binary_classifier;
+pub mod diff;
 mod features;
 mod grid;
 mod model_train_options;
@@ -21,20 +22,59 @@ mod stats;
 
 /// A .modelfox file is prefixed with this magic number followed by a 4-byte little endian revision number.
 const MAGIC_NUMBER: &[u8] = b"tangram\0";
-/// This is the revision number that this version of modelfox_model writes.
-const CURRENT_REVISION: u32 = 0;
+/// A .modelfox file with this revision number contains only the model. This version of modelfox_model writes it for models without metadata, so older versions can still read them.
+const MODEL_ONLY_REVISION: u32 = 0;
+/// A .modelfox file with this revision number has an 8-byte little endian length of the model, the model, and then the [`Metadata`].
+const METADATA_REVISION: u32 = 1;
+/// This is the newest revision number that this version of modelfox_model can read.
+const CURRENT_REVISION: u32 = METADATA_REVISION;
 /// This is the oldest revision number that this version of modelfox_model can read.
 const MIN_SUPPORTED_REVISION: u32 = 0;
 
+/// This is the metadata that the app adds to a .modelfox file when it is downloaded, after the model was trained.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metadata {
+	/// These are the class thresholds saved on the tuning page of the app for a multiclass classifier. The SDKs use them when no class thresholds are passed in the predict options.
+	pub class_thresholds: BTreeMap<String, f32>,
+}
+
 pub fn from_bytes(bytes: &[u8]) -> Result<ModelReader> {
+	let (bytes, _) = split_bytes(bytes)?;
+	let model = buffalo::read::<ModelReader>(bytes);
+	Ok(model)
+}
+
+/// Read the metadata from the contents of a .modelfox file. Files without metadata have the default metadata.
+pub fn metadata_from_bytes(bytes: &[u8]) -> Result<Metadata> {
+	match split_bytes(bytes)? {
+		(_, Some(metadata)) => read_metadata(metadata),
+		(_, None) => Ok(Metadata::default()),
+	}
+}
+
+/// Replace the metadata in the contents of a .modelfox file, producing the contents of a new .modelfox file with the same model.
+pub fn with_metadata(bytes: &[u8], metadata: &Metadata) -> Result<Vec<u8>> {
+	let (model, _) = split_bytes(bytes)?;
+	if *metadata == Metadata::default() {
+		return Ok(to_bytes(model));
+	}
+	let mut output = Vec::with_capacity(MAGIC_NUMBER.len() + 12 + model.len());
+	output.extend_from_slice(MAGIC_NUMBER);
+	output.extend_from_slice(&METADATA_REVISION.to_le_bytes());
+	output.extend_from_slice(&model.len().to_u64().unwrap().to_le_bytes());
+	output.extend_from_slice(model);
+	write_metadata(&mut output, metadata);
+	Ok(output)
+}
+
+/// Verify the magic number and revision number of the contents of a .modelfox file and split the rest into the model and, if the file has it, the metadata.
+fn split_bytes(bytes: &[u8]) -> Result<(&[u8], Option<&[u8]>)> {
 	// Verify the magic number.
-	let magic_number = &bytes[0..MAGIC_NUMBER.len()];
-	if magic_number != MAGIC_NUMBER {
+	if bytes.len() < MAGIC_NUMBER.len() + 4 || &bytes[0..MAGIC_NUMBER.len()] != MAGIC_NUMBER {
 		bail!("This model did not start with the modelfox magic number. Are you sure it is a .modelfox file?");
 	}
 	let bytes = &bytes[MAGIC_NUMBER.len()..];
 	let revision = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
-	#[allow(clippy::absurd_extreme_comparisons)]
 	if revision > CURRENT_REVISION {
 		bail!("This model has a revision number of {}, which is greater than the revision number of {} used by this version of modelfox. Your model is from the future! Please update to the latest version of modelfox to use it.", revision, CURRENT_REVISION);
 	}
@@ -43,8 +83,60 @@ pub fn from_bytes(bytes: &[u8]) -> Result<ModelReader> {
 		bail!("This model has a revision number of {}, which is lower than the minumum supported revision number of {} for this version of modelfox. Please downgrade to an earlier version of modelfox to use it.", revision, MIN_SUPPORTED_REVISION);
 	}
 	let bytes = &bytes[4..];
-	let model = buffalo::read::<ModelReader>(bytes);
-	Ok(model)
+	if revision == MODEL_ONLY_REVISION {
+		return Ok((bytes, None));
+	}
+	if bytes.len() < 8 {
+		bail!("This model is truncated.");
+	}
+	let model_len = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+	let bytes = &bytes[8..];
+	let model_len = match model_len.to_usize() {
+		Some(model_len) if model_len <= bytes.len() => model_len,
+		_ => bail!("This model is truncated."),
+	};
+	let (model, metadata) = bytes.split_at(model_len);
+	Ok((model, Some(metadata)))
+}
+
+/// The metadata is a 4-byte little endian count of class thresholds, followed by each class name as a 4-byte little endian length and its UTF-8 bytes, and its threshold as a 4-byte little endian float.
+fn write_metadata(output: &mut Vec<u8>, metadata: &Metadata) {
+	output.extend_from_slice(
+		&metadata
+			.class_thresholds
+			.len()
+			.to_u32()
+			.unwrap()
+			.to_le_bytes(),
+	);
+	for (class, threshold) in metadata.class_thresholds.iter() {
+		output.extend_from_slice(&class.len().to_u32().unwrap().to_le_bytes());
+		output.extend_from_slice(class.as_bytes());
+		output.extend_from_slice(&threshold.to_le_bytes());
+	}
+}
+
+fn read_metadata(mut bytes: &[u8]) -> Result<Metadata> {
+	let mut class_thresholds = BTreeMap::new();
+	let len = u32::from_le_bytes(take(&mut bytes, 4)?.try_into().unwrap());
+	for _ in 0..len {
+		let class_len = u32::from_le_bytes(take(&mut bytes, 4)?.try_into().unwrap());
+		let class = take(&mut bytes, class_len.to_usize().unwrap())?;
+		let class = std::str::from_utf8(class)?.to_owned();
+		let threshold = f32::from_le_bytes(take(&mut bytes, 4)?.try_into().unwrap());
+		class_thresholds.insert(class, threshold);
+	}
+	Ok(Metadata { class_thresholds })
+}
+
+/// Split the first `len` bytes off of `bytes`.
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+	if bytes.len() < len {
+		bail!("This model's metadata is truncated.");
+	}
+	let (taken, rest) = bytes.split_at(len);
+	*bytes = rest;
+	Ok(taken)
 }
 
 pub fn to_path(path: &Path, bytes: &[u8]) -> Result<()> {
@@ -53,12 +145,21 @@ pub fn to_path(path: &Path, bytes: &[u8]) -> Result<()> {
 	// Write the magic number.
 	file.write_all(MAGIC_NUMBER)?;
 	// Write the revision number.
-	file.write_all(&CURRENT_REVISION.to_le_bytes())?;
+	file.write_all(&MODEL_ONLY_REVISION.to_le_bytes())?;
 	// Write the bytes.
 	file.write_all(bytes)?;
 	Ok(())
 }
 
+/// Prepend the magic number and revision number to the serialized model `bytes` to produce the contents of a `.modelfox` file.
+pub fn to_bytes(bytes: &[u8]) -> Vec<u8> {
+	let mut output = Vec::with_capacity(MAGIC_NUMBER.len() + 4 + bytes.len());
+	output.extend_from_slice(MAGIC_NUMBER);
+	output.extend_from_slice(&MODEL_ONLY_REVISION.to_le_bytes());
+	output.extend_from_slice(bytes);
+	output
+}
+
 #[derive(buffalo::Read, buffalo::Write)]
 #[buffalo(size = "dynamic")]
 pub struct Model {
@@ -91,6 +192,7 @@ impl<'a> ColumnStatsReader<'a> {
 			ColumnStatsReader::NumberColumn(c) => c.read().column_name(),
 			ColumnStatsReader::EnumColumn(c) => c.read().column_name(),
 			ColumnStatsReader::TextColumn(c) => c.read().column_name(),
+			ColumnStatsReader::DateTimeColumn(c) => c.read().column_name(),
 		}
 	}
 }
@@ -22,6 +22,46 @@ pub struct LinearModelTrainOptions {
 	pub n_examples_per_batch: u64,
 	#[buffalo(id = 5, required)]
 	pub early_stopping_options: Option<LinearEarlyStoppingOptions>,
+	#[buffalo(id = 6)]
+	pub optimizer: LinearOptimizer,
+	#[buffalo(id = 7)]
+	pub momentum: f32,
+	#[buffalo(id = 8)]
+	pub adam_beta_1: f32,
+	#[buffalo(id = 9)]
+	pub adam_beta_2: f32,
+	#[buffalo(id = 10)]
+	pub adam_epsilon: f32,
+	#[buffalo(id = 11)]
+	pub learning_rate_schedule: LearningRateSchedule,
+	#[buffalo(id = 12)]
+	pub learning_rate_decay_rate: f32,
+	#[buffalo(id = 13)]
+	pub learning_rate_n_epochs_per_step: u64,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 0)]
+pub enum LinearOptimizer {
+	#[buffalo(id = 0)]
+	Sgd,
+	#[buffalo(id = 1)]
+	Momentum,
+	#[buffalo(id = 2)]
+	Adam,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 0)]
+pub enum LearningRateSchedule {
+	#[buffalo(id = 0)]
+	Constant,
+	#[buffalo(id = 1)]
+	Step,
+	#[buffalo(id = 2)]
+	Exponential,
+	#[buffalo(id = 3)]
+	InverseTime,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -68,6 +108,10 @@ pub struct TreeModelTrainOptions {
 	pub min_sum_hessians_per_node: f32,
 	#[buffalo(id = 14, required)]
 	pub smoothing_factor_for_discrete_bin_sorting: f32,
+	#[buffalo(id = 15)]
+	pub binning_strategy: BinningStrategy,
+	#[buffalo(id = 16)]
+	pub interaction_constraints: Vec<TreeInteractionConstraintGroup>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -79,6 +123,15 @@ pub enum BinnedFeaturesLayout {
 	ColumnMajor,
 }
 
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 0)]
+pub enum BinningStrategy {
+	#[buffalo(id = 0)]
+	Quantile,
+	#[buffalo(id = 1)]
+	Uniform,
+}
+
 #[derive(buffalo::Read, buffalo::Write)]
 #[buffalo(size = "dynamic")]
 pub struct TreeEarlyStoppingOptions {
@@ -89,3 +142,247 @@ pub struct TreeEarlyStoppingOptions {
 	#[buffalo(id = 2, required)]
 	pub min_decrease_in_loss_for_significant_change: f32,
 }
+
+/// These are the thresholds a tree model used to bin the values of a feature. Enum features are binned by variant and have no thresholds.
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct TreeBinThresholds {
+	#[buffalo(id = 0, required)]
+	pub thresholds: Option<Vec<f32>>,
+}
+
+/// These are the indexes of the features that may be split on together in a branch of a tree.
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct TreeInteractionConstraintGroup {
+	#[buffalo(id = 0, required)]
+	pub feature_indexes: Vec<u64>,
+}
+
+impl<'a> ModelTrainOptionsReader<'a> {
+	/// Retrieve the name and formatted value of each hyperparameter.
+	pub fn hyperparameters(&self) -> Vec<(String, String)> {
+		match self {
+			ModelTrainOptionsReader::Linear(hyperparameters) => {
+				let hyperparameters = hyperparameters.read();
+				vec![
+					(
+						"l2_regularization".to_owned(),
+						hyperparameters.l2_regularization().to_string(),
+					),
+					(
+						"learning_rate".to_owned(),
+						hyperparameters.learning_rate().to_string(),
+					),
+					(
+						"learning_rate_schedule".to_owned(),
+						match hyperparameters.learning_rate_schedule() {
+							Some(LearningRateScheduleReader::Step(_)) => format!(
+								"step (decay_rate: {}, n_epochs_per_step: {})",
+								format_optional(hyperparameters.learning_rate_decay_rate()),
+								format_optional(hyperparameters.learning_rate_n_epochs_per_step()),
+							),
+							Some(LearningRateScheduleReader::Exponential(_)) => format!(
+								"exponential (decay_rate: {})",
+								format_optional(hyperparameters.learning_rate_decay_rate()),
+							),
+							Some(LearningRateScheduleReader::InverseTime(_)) => format!(
+								"inverse time (decay_rate: {})",
+								format_optional(hyperparameters.learning_rate_decay_rate()),
+							),
+							Some(LearningRateScheduleReader::Constant(_)) | None => {
+								"constant".to_owned()
+							}
+						},
+					),
+					(
+						"optimizer".to_owned(),
+						match hyperparameters.optimizer() {
+							Some(LinearOptimizerReader::Momentum(_)) => format!(
+								"momentum (momentum: {})",
+								format_optional(hyperparameters.momentum()),
+							),
+							Some(LinearOptimizerReader::Adam(_)) => format!(
+								"adam (beta_1: {}, beta_2: {}, epsilon: {})",
+								format_optional(hyperparameters.adam_beta_1()),
+								format_optional(hyperparameters.adam_beta_2()),
+								format_optional(hyperparameters.adam_epsilon()),
+							),
+							Some(LinearOptimizerReader::Sgd(_)) | None => "sgd".to_owned(),
+						},
+					),
+					(
+						"max_epochs".to_owned(),
+						hyperparameters.max_epochs().to_string(),
+					),
+					(
+						"n_examples_per_batch".to_owned(),
+						hyperparameters.n_examples_per_batch().to_string(),
+					),
+					(
+						"early_stopping:early_stopping_fraction".to_owned(),
+						hyperparameters
+							.early_stopping_options()
+							.map(|options| options.early_stopping_fraction().to_string())
+							.unwrap_or_else(|| "None".to_string()),
+					),
+					(
+						"early_stopping:n_rounds_without_improvement_to_stop".to_owned(),
+						hyperparameters
+							.early_stopping_options()
+							.map(|options| {
+								options.n_rounds_without_improvement_to_stop().to_string()
+							})
+							.unwrap_or_else(|| "None".to_string()),
+					),
+					(
+						"early_stopping:min_decrease_in_loss_for_significant_change".to_owned(),
+						hyperparameters
+							.early_stopping_options()
+							.map(|options| {
+								options
+									.min_decrease_in_loss_for_significant_change()
+									.to_string()
+							})
+							.unwrap_or_else(|| "None".to_string()),
+					),
+				]
+			}
+			ModelTrainOptionsReader::Tree(hyperparameters) => {
+				let hyperparameters = hyperparameters.read();
+				vec![
+					(
+						"binned_features_layout".to_owned(),
+						match hyperparameters.binned_features_layout() {
+							BinnedFeaturesLayoutReader::RowMajor(_) => "row major".to_owned(),
+							BinnedFeaturesLayoutReader::ColumnMajor(_) => "column major".to_owned(),
+						},
+					),
+					(
+						"binning_strategy".to_owned(),
+						match hyperparameters.binning_strategy() {
+							Some(BinningStrategyReader::Uniform(_)) => "uniform".to_owned(),
+							Some(BinningStrategyReader::Quantile(_)) | None => {
+								"quantile".to_owned()
+							}
+						},
+					),
+					(
+						"early_stopping:early_stopping_fraction".to_owned(),
+						hyperparameters
+							.early_stopping_options()
+							.map(|options| options.early_stopping_fraction().to_string())
+							.unwrap_or_else(|| "None".to_string()),
+					),
+					(
+						"early_stopping:n_rounds_without_improvement_to_stop".to_owned(),
+						hyperparameters
+							.early_stopping_options()
+							.map(|options| {
+								options.n_rounds_without_improvement_to_stop().to_string()
+							})
+							.unwrap_or_else(|| "None".to_string()),
+					),
+					(
+						"early_stopping:min_decrease_in_loss_for_significant_change".to_owned(),
+						hyperparameters
+							.early_stopping_options()
+							.map(|options| {
+								options
+									.min_decrease_in_loss_for_significant_change()
+									.to_string()
+							})
+							.unwrap_or_else(|| "None".to_string()),
+					),
+					(
+						"interaction_constraints".to_owned(),
+						hyperparameters
+							.interaction_constraints()
+							.map(|groups| {
+								let groups = groups
+									.iter()
+									.map(|group| {
+										let feature_indexes = group
+											.feature_indexes()
+											.iter()
+											.map(|feature_index| feature_index.to_string())
+											.collect::<Vec<_>>();
+										format!("[{}]", feature_indexes.join(", "))
+									})
+									.collect::<Vec<_>>();
+								format!("[{}]", groups.join(", "))
+							})
+							.unwrap_or_else(|| "None".to_owned()),
+					),
+					(
+						"l2_regularization_for_continuous_splits".to_owned(),
+						hyperparameters
+							.l2_regularization_for_continuous_splits()
+							.to_string(),
+					),
+					(
+						"l2_regularization_for_discrete_splits".to_owned(),
+						hyperparameters
+							.l2_regularization_for_discrete_splits()
+							.to_string(),
+					),
+					(
+						"learning_rate".to_owned(),
+						hyperparameters.learning_rate().to_string(),
+					),
+					(
+						"max_depth".to_owned(),
+						hyperparameters
+							.max_depth()
+							.map(|max_depth| max_depth.to_string())
+							.unwrap_or_else(|| "None".to_owned()),
+					),
+					(
+						"max_examples_for_computing_bin_thresholds".to_owned(),
+						hyperparameters
+							.max_examples_for_computing_bin_thresholds()
+							.to_string(),
+					),
+					(
+						"max_leaf_nodes".to_owned(),
+						hyperparameters.max_leaf_nodes().to_string(),
+					),
+					(
+						"max_rounds".to_owned(),
+						hyperparameters.max_rounds().to_string(),
+					),
+					(
+						"max_valid_bins_for_number_features".to_owned(),
+						hyperparameters
+							.max_valid_bins_for_number_features()
+							.to_string(),
+					),
+					(
+						"min_examples_per_node".to_owned(),
+						hyperparameters.min_examples_per_node().to_string(),
+					),
+					(
+						"min_gain_to_split".to_owned(),
+						hyperparameters.min_gain_to_split().to_string(),
+					),
+					(
+						"min_sum_hessians_per_node".to_owned(),
+						hyperparameters.min_sum_hessians_per_node().to_string(),
+					),
+					(
+						"smoothing_factor_for_discrete_bin_sorting".to_owned(),
+						hyperparameters
+							.smoothing_factor_for_discrete_bin_sorting()
+							.to_string(),
+					),
+				]
+			}
+		}
+	}
+}
+
+fn format_optional<T: std::fmt::Display>(value: Option<T>) -> String {
+	value
+		.map(|value| value.to_string())
+		.unwrap_or_else(|| "None".to_owned())
+}
@@ -1,5 +1,6 @@
 use crate::{
-	ColumnStats, FeatureGroup, LinearModelTrainOptions, StatsSettings, TrainGridItemOutput,
+	ColumnCorrelation, ColumnInvalidValuePolicy, ColumnStats, DroppedColumn, FeatureGroup,
+	LinearModelTrainOptions, StatsSettings, TrainGridItemOutput, TreeBinThresholds,
 	TreeModelTrainOptions,
 };
 
@@ -42,6 +43,15 @@ pub struct MulticlassClassifier {
 	pub model: MulticlassClassificationModel,
 	#[buffalo(id = 17, required)]
 	pub test_metrics: MulticlassClassificationMetrics,
+	/// These are the columns that the feature selection pass dropped. It is absent in models trained before feature selection was added.
+	#[buffalo(id = 18)]
+	pub dropped_columns: Vec<DroppedColumn>,
+	/// These are the invalid value policies of the columns whose policy is not to treat invalid values as missing. It is absent in models trained before invalid value policies were added.
+	#[buffalo(id = 19)]
+	pub invalid_value_policies: Vec<ColumnInvalidValuePolicy>,
+	/// These are the pairs of columns in the train table with the largest absolute correlation, starting with the largest. It is absent in models trained before column correlations were added.
+	#[buffalo(id = 20)]
+	pub column_correlations: Vec<ColumnCorrelation>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -66,6 +76,36 @@ pub struct MulticlassClassificationMetrics {
 	pub recall_unweighted: f32,
 	#[buffalo(id = 5, required)]
 	pub recall_weighted: f32,
+	/// This is the confusion matrix in row major order, with one row for each predicted class and one column for each actual class. It is absent in models trained before it was added.
+	#[buffalo(id = 6)]
+	pub confusion_matrix: Vec<u64>,
+	/// These are the one-vs-rest ROC curves for each class. They are absent in models trained before they were added.
+	#[buffalo(id = 7)]
+	pub class_roc_curves: Vec<RocCurve>,
+	#[buffalo(id = 8)]
+	pub auc_roc_macro: f32,
+	#[buffalo(id = 9)]
+	pub auc_roc_micro: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct RocCurve {
+	#[buffalo(id = 0, required)]
+	pub auc_roc_approx: f32,
+	#[buffalo(id = 1, required)]
+	pub points: Vec<RocCurvePoint>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct RocCurvePoint {
+	#[buffalo(id = 0, required)]
+	pub threshold: f32,
+	#[buffalo(id = 1, required)]
+	pub true_positive_rate: f32,
+	#[buffalo(id = 2, required)]
+	pub false_positive_rate: f32,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -111,6 +151,9 @@ pub struct LinearMulticlassClassifier {
 	pub losses: Option<Vec<f32>>,
 	#[buffalo(id = 4, required)]
 	pub feature_importances: Vec<f32>,
+	/// This is the standard deviation of each feature importance across the cross validation folds. It is absent when the model was evaluated on a single fold.
+	#[buffalo(id = 5)]
+	pub feature_importances_std: Vec<f32>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -126,4 +169,9 @@ pub struct TreeMulticlassClassifier {
 	pub losses: Option<Vec<f32>>,
 	#[buffalo(id = 4, required)]
 	pub feature_importances: Vec<f32>,
+	#[buffalo(id = 5)]
+	pub bin_thresholds: Vec<TreeBinThresholds>,
+	/// This is the standard deviation of each feature importance across the cross validation folds. It is absent when the model was evaluated on a single fold.
+	#[buffalo(id = 6)]
+	pub feature_importances_std: Vec<f32>,
 }
@@ -1,5 +1,6 @@
 use crate::{
-	ColumnStats, FeatureGroup, LinearModelTrainOptions, StatsSettings, TrainGridItemOutput,
+	ColumnCorrelation, ColumnInvalidValuePolicy, ColumnStats, DroppedColumn, FeatureGroup,
+	LinearModelTrainOptions, StatsSettings, TrainGridItemOutput, TreeBinThresholds,
 	TreeModelTrainOptions,
 };
 
@@ -40,6 +41,18 @@ pub struct Regressor {
 	pub model: RegressionModel,
 	#[buffalo(id = 16, required)]
 	pub test_metrics: RegressionMetrics,
+	/// These are the columns that the feature selection pass dropped. It is absent in models trained before feature selection was added.
+	#[buffalo(id = 17)]
+	pub dropped_columns: Vec<DroppedColumn>,
+	/// This is the tolerance used to compute the tolerance accuracy. It is absent if no tolerance was configured.
+	#[buffalo(id = 18)]
+	pub tolerance: RegressionTolerance,
+	/// These are the invalid value policies of the columns whose policy is not to treat invalid values as missing. It is absent in models trained before invalid value policies were added.
+	#[buffalo(id = 19)]
+	pub invalid_value_policies: Vec<ColumnInvalidValuePolicy>,
+	/// These are the pairs of columns in the train table with the largest absolute correlation, starting with the largest. It is absent in models trained before column correlations were added.
+	#[buffalo(id = 20)]
+	pub column_correlations: Vec<ColumnCorrelation>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -64,6 +77,9 @@ pub struct LinearRegressor {
 	pub losses: Option<Vec<f32>>,
 	#[buffalo(id = 4, required)]
 	pub feature_importances: Vec<f32>,
+	/// This is the standard deviation of each feature importance across the cross validation folds. It is absent when the model was evaluated on a single fold.
+	#[buffalo(id = 5)]
+	pub feature_importances_std: Vec<f32>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -79,6 +95,11 @@ pub struct TreeRegressor {
 	pub losses: Option<Vec<f32>>,
 	#[buffalo(id = 4, required)]
 	pub feature_importances: Vec<f32>,
+	#[buffalo(id = 5)]
+	pub bin_thresholds: Vec<TreeBinThresholds>,
+	/// This is the standard deviation of each feature importance across the cross validation folds. It is absent when the model was evaluated on a single fold.
+	#[buffalo(id = 6)]
+	pub feature_importances_std: Vec<f32>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -105,4 +126,25 @@ pub struct RegressionMetrics {
 	pub mae: f32,
 	#[buffalo(id = 3, required)]
 	pub r2: f32,
+	/// This is the fraction of predictions within the model's tolerance. It is absent if no tolerance was configured.
+	#[buffalo(id = 4)]
+	pub tolerance_accuracy: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct RegressionTolerance {
+	#[buffalo(id = 0, required)]
+	pub kind: RegressionToleranceKind,
+	#[buffalo(id = 1, required)]
+	pub value: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 0)]
+pub enum RegressionToleranceKind {
+	#[buffalo(id = 0)]
+	Absolute,
+	#[buffalo(id = 1)]
+	Relative,
 }
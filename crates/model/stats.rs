@@ -3,6 +3,10 @@
 pub struct StatsSettings {
 	#[buffalo(id = 0, required)]
 	pub number_histogram_max_size: u64,
+	#[buffalo(id = 1)]
+	pub datetime_histogram_bin_count: u64,
+	#[buffalo(id = 2)]
+	pub number_quantiles: Vec<f32>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -16,6 +20,8 @@ pub enum ColumnStats {
 	EnumColumn(EnumColumnStats),
 	#[buffalo(id = 3)]
 	TextColumn(TextColumnStats),
+	#[buffalo(id = 4)]
+	DateTimeColumn(DateTimeColumnStats),
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -52,6 +58,8 @@ pub struct NumberColumnStats {
 	pub p50: f32,
 	#[buffalo(id = 11, required)]
 	pub p75: f32,
+	#[buffalo(id = 12)]
+	pub quantiles: Vec<(f32, f32)>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -80,6 +88,32 @@ pub struct TextColumnStats {
 	pub ngrams_count: u64,
 	#[buffalo(id = 4, required)]
 	pub top_ngrams: Vec<(NGram, TextColumnStatsTopNGramsEntry)>,
+	/// This is the detected language of the column, if any. It is absent for models trained before language detection.
+	#[buffalo(id = 5)]
+	pub language: String,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct DateTimeColumnStats {
+	#[buffalo(id = 0, required)]
+	pub column_name: String,
+	/// This is the name of the timezone in the IANA timezone database used to interpret values without a utc offset.
+	#[buffalo(id = 1, required)]
+	pub timezone: String,
+	#[buffalo(id = 2, required)]
+	pub invalid_count: u64,
+	#[buffalo(id = 3, required)]
+	pub unique_count: u64,
+	/// This is the earliest value as a unix timestamp in seconds.
+	#[buffalo(id = 4, required)]
+	pub min: Option<i64>,
+	/// This is the latest value as a unix timestamp in seconds.
+	#[buffalo(id = 5, required)]
+	pub max: Option<i64>,
+	/// This maps the start of each histogram bin, as a unix timestamp in seconds, to the number of values in the bin.
+	#[buffalo(id = 6, required)]
+	pub histogram: Vec<(i64, u64)>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -119,3 +153,91 @@ pub enum NGram {
 	#[buffalo(id = 1)]
 	Bigram((String, String)),
 }
+
+/// This is a column that the feature selection pass excluded from automatic feature engineering.
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct DroppedColumn {
+	#[buffalo(id = 0, required)]
+	pub column_name: String,
+	#[buffalo(id = 1, required)]
+	pub reason: DroppedColumnReason,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 8)]
+pub enum DroppedColumnReason {
+	#[buffalo(id = 0)]
+	LowVariance(LowVarianceDroppedColumnReason),
+	#[buffalo(id = 1)]
+	HighInvalidFraction(HighInvalidFractionDroppedColumnReason),
+	#[buffalo(id = 2)]
+	HighCorrelation(HighCorrelationDroppedColumnReason),
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct LowVarianceDroppedColumnReason {
+	#[buffalo(id = 0, required)]
+	pub variance: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct HighInvalidFractionDroppedColumnReason {
+	#[buffalo(id = 0, required)]
+	pub invalid_fraction: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct HighCorrelationDroppedColumnReason {
+	#[buffalo(id = 0, required)]
+	pub correlated_column_name: String,
+	#[buffalo(id = 1, required)]
+	pub correlation: f32,
+}
+
+/// This is the correlation between two columns of the train table.
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct ColumnCorrelation {
+	#[buffalo(id = 0, required)]
+	pub column_name_a: String,
+	#[buffalo(id = 1, required)]
+	pub column_name_b: String,
+	#[buffalo(id = 2, required)]
+	pub method: CorrelationMethod,
+	#[buffalo(id = 3, required)]
+	pub value: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 0)]
+pub enum CorrelationMethod {
+	#[buffalo(id = 0)]
+	Pearson,
+	#[buffalo(id = 1)]
+	CramersV,
+}
+
+/// This is the policy for values in a column that fail to parse for its type. Columns that are not listed in a model use the default policy of treating such values as missing.
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct ColumnInvalidValuePolicy {
+	#[buffalo(id = 0, required)]
+	pub column_name: String,
+	#[buffalo(id = 1, required)]
+	pub policy: InvalidValuePolicy,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 0)]
+pub enum InvalidValuePolicy {
+	#[buffalo(id = 0)]
+	Missing,
+	#[buffalo(id = 1)]
+	DropRow,
+	#[buffalo(id = 2)]
+	Error,
+}
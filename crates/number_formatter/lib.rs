@@ -36,6 +36,15 @@ impl NumberFormatter {
 		NumberFormatter::Percent(PercentFormatter::new(decimal_places))
 	}
 
+	/// Append `unit` to formatted values, such as `"ms"` for a latency. Percentages already have a unit, so this only applies to float formatters.
+	#[must_use]
+	pub fn with_unit(self, unit: Option<String>) -> NumberFormatter {
+		match self {
+			NumberFormatter::Float(formatter) => NumberFormatter::Float(formatter.with_unit(unit)),
+			NumberFormatter::Percent(formatter) => NumberFormatter::Percent(formatter),
+		}
+	}
+
 	pub fn format<F>(&self, value: F) -> String
 	where
 		F: Float,
@@ -59,19 +68,31 @@ impl NumberFormatter {
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct FloatFormatter {
+	/// This is the number of significant digits to show.
 	digits: u8,
+	/// If this is set, it is appended to each finite value after a space.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	unit: Option<String>,
 }
 
 impl Default for FloatFormatter {
 	fn default() -> Self {
-		FloatFormatter { digits: 6 }
+		FloatFormatter {
+			digits: 6,
+			unit: None,
+		}
 	}
 }
 
 impl FloatFormatter {
 	#[must_use]
 	pub fn new(digits: u8) -> FloatFormatter {
-		FloatFormatter { digits }
+		FloatFormatter { digits, unit: None }
+	}
+
+	#[must_use]
+	pub fn with_unit(self, unit: Option<String>) -> FloatFormatter {
+		FloatFormatter { unit, ..self }
 	}
 
 	/// # Panics
@@ -88,6 +109,15 @@ impl FloatFormatter {
 			}
 			return "-inf".to_owned();
 		}
+		let mut string = self.format_finite(value);
+		if let Some(unit) = &self.unit {
+			string.push(' ');
+			string.push_str(unit);
+		}
+		string
+	}
+
+	fn format_finite(&self, value: f64) -> String {
 		if value == 0.0 || value == -0.0 {
 			return "0".to_owned();
 		}
@@ -192,6 +222,12 @@ pub fn format_option_percent<T: Float>(value: Option<T>) -> String {
 	PercentFormatter::default().format_option(value)
 }
 
+/// Format a quantile between 0 and 1 as a percentile label, such as `p25` or `p99.9`.
+pub fn format_quantile<T: Float>(quantile: T) -> String {
+	let percentile = quantile * T::from(100.0).unwrap();
+	format!("p{}", format_float_with_digits(percentile, 4))
+}
+
 #[test]
 fn test_format_float() {
 	fn test(x: f64, p: u8, s: &str) {
@@ -228,6 +264,16 @@ fn test_format_float() {
 	test(-0.000_123_45, 3, "-1.23e-4");
 }
 
+#[test]
+fn test_format_float_with_unit() {
+	let formatter = NumberFormatter::float(4).with_unit(Some("ms".to_owned()));
+	assert_eq!(formatter.format(12.345_678), "12.35 ms");
+	assert_eq!(formatter.format(0.0), "0 ms");
+	assert_eq!(formatter.format(f64::NAN), "NaN");
+	let formatter = NumberFormatter::percent_default().with_unit(Some("ms".to_owned()));
+	assert_eq!(formatter.format(0.5), "50.00%");
+}
+
 #[test]
 fn test_format_percent() {
 	assert_eq!(format_percent(0.0), "0.00%");
@@ -237,6 +283,13 @@ fn test_format_percent() {
 	assert_eq!(format_percent(1.00), "100%");
 }
 
+#[test]
+fn test_format_quantile() {
+	assert_eq!(format_quantile(0.25), "p25");
+	assert_eq!(format_quantile(0.5), "p50");
+	assert_eq!(format_quantile(0.999), "p99.9");
+}
+
 fn digits_before_decimal(value: f64) -> usize {
 	let value = value.trunc().abs();
 	if value == 0.0 {
@@ -6,6 +6,11 @@ use futures::{future::FutureExt, Future};
 use hyper::http;
 use modelfox_id::Id;
 use std::{cell::RefCell, convert::Infallible, panic::AssertUnwindSafe, sync::Arc};
+use tracing::Instrument;
+
+/// The address of the client that sent a request. This is added to each request's extensions.
+#[derive(Clone, Copy, Debug)]
+pub struct RemoteAddr(pub std::net::SocketAddr);
 
 /// # Errors
 ///
@@ -27,6 +32,7 @@ where
 	async fn service<C, H, F>(
 		handler: Arc<H>,
 		context: Arc<C>,
+		remote_addr: RemoteAddr,
 		mut request: http::Request<hyper::Body>,
 	) -> Result<http::Response<hyper::Body>, Infallible>
 	where
@@ -36,32 +42,42 @@ where
 	{
 		let method = request.method().clone();
 		let path = request.uri().path_and_query().unwrap().path().to_owned();
-		tracing::info!(path = %path, method = %method, "request");
+		let request_id = Id::generate();
+		let span = tracing::info_span!(
+			"request",
+			%request_id,
+			%method,
+			%path,
+			status = tracing::field::Empty,
+			latency_us = tracing::field::Empty,
+		);
 		request.extensions_mut().insert(context);
-		request.extensions_mut().insert(Id::generate());
-		let result = AssertUnwindSafe(handler(request)).catch_unwind().await;
-		let start = std::time::SystemTime::now();
+		request.extensions_mut().insert(request_id);
+		request.extensions_mut().insert(remote_addr);
+		let start = std::time::Instant::now();
+		let result = AssertUnwindSafe(handler(request).instrument(span.clone()))
+			.catch_unwind()
+			.await;
 		let response = result.unwrap_or_else(|_| {
 			let body = PANIC_MESSAGE_AND_BACKTRACE.with(|panic_message_and_backtrace| {
 				let panic_message_and_backtrace = panic_message_and_backtrace.borrow();
 				let (message, backtrace) = panic_message_and_backtrace.as_ref().unwrap();
 				tracing::error!(
-					method = %method,
-					path = %path,
+					parent: &span,
 					backtrace = ?backtrace,
 					"500"
 				);
-				format!("{}\n{:?}", message, backtrace)
+				format!("{}\ntrace id: {}\n{:?}", message, request_id, backtrace)
 			});
 			http::Response::builder()
 				.status(http::StatusCode::INTERNAL_SERVER_ERROR)
 				.body(hyper::Body::from(body))
 				.unwrap()
 		});
-		tracing::info!(
-			"Response generated in {}μs",
-			start.elapsed().unwrap_or_default().as_micros()
-		);
+		let latency_us = u64::try_from(start.elapsed().as_micros()).unwrap_or(u64::MAX);
+		span.record("status", &response.status().as_u16());
+		span.record("latency_us", &latency_us);
+		tracing::info!(parent: &span, "response");
 		Ok(response)
 	}
 	// Install a panic hook that will record the panic message and backtrace if a panic occurs.
@@ -77,15 +93,16 @@ where
 	}));
 	// Wrap the request handler and context with Arc to allow sharing a reference to it with each task.
 	let handler = Arc::new(handler);
-	let service = hyper::service::make_service_fn(|_| {
+	let service = hyper::service::make_service_fn(|conn: &hyper::server::conn::AddrStream| {
 		let handler = handler.clone();
 		let context = context.clone();
+		let remote_addr = RemoteAddr(conn.remote_addr());
 		async move {
 			Ok::<_, Infallible>(hyper::service::service_fn(move |request| {
 				let handler = handler.clone();
 				let context = context.clone();
 				PANIC_MESSAGE_AND_BACKTRACE.scope(RefCell::new(None), async move {
-					service(handler, context, request).await
+					service(handler, context, remote_addr, request).await
 				})
 			}))
 		}
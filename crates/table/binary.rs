@@ -0,0 +1,277 @@
+/*!
+This module reads and writes tables in a compact columnar binary format, so a table parsed from a csv file can be saved and loaded again without parsing the csv. Each column is written as its type, its name, and then all of its values, with every number in little endian byte order. The format is only read by the same version of modelfox that wrote it.
+*/
+
+use super::{
+	DateTimeTableColumn, EnumTableColumn, NumberTableColumn, Table, TableColumn, TextTableColumn,
+	UnknownTableColumn,
+};
+use anyhow::{bail, Result};
+use chrono_tz::Tz;
+use num::ToPrimitive;
+use std::{
+	io::{Read, Write},
+	num::NonZeroUsize,
+};
+
+const MAGIC: &[u8; 4] = b"MFTB";
+const VERSION: u32 = 1;
+
+const UNKNOWN: u8 = 0;
+const NUMBER: u8 = 1;
+const ENUM: u8 = 2;
+const TEXT: u8 = 3;
+const DATETIME: u8 = 4;
+
+impl Table {
+	/// Write the table in the binary format read by [`Table::from_binary`].
+	///
+	/// # Errors
+	///
+	/// This function returns an error if writing to `writer` fails.
+	pub fn to_binary(&self, writer: &mut impl Write) -> Result<()> {
+		writer.write_all(MAGIC)?;
+		write_u32(writer, VERSION)?;
+		write_usize(writer, self.ncols())?;
+		write_usize(writer, self.nrows())?;
+		for column in &self.columns {
+			match column {
+				TableColumn::Unknown(column) => {
+					writer.write_all(&[UNKNOWN])?;
+					write_name(writer, column.name.as_deref())?;
+				}
+				TableColumn::Number(column) => {
+					writer.write_all(&[NUMBER])?;
+					write_name(writer, column.name.as_deref())?;
+					for value in &column.data {
+						writer.write_all(&value.to_le_bytes())?;
+					}
+				}
+				TableColumn::Enum(column) => {
+					writer.write_all(&[ENUM])?;
+					write_name(writer, column.name.as_deref())?;
+					write_usize(writer, column.variants.len())?;
+					for variant in &column.variants {
+						write_string(writer, variant)?;
+					}
+					// Zero marks a value that is not one of the variants.
+					for value in &column.data {
+						write_usize(writer, value.map_or(0, NonZeroUsize::get))?;
+					}
+				}
+				TableColumn::Text(column) => {
+					writer.write_all(&[TEXT])?;
+					write_name(writer, column.name.as_deref())?;
+					for value in &column.data {
+						write_string(writer, value)?;
+					}
+				}
+				TableColumn::DateTime(column) => {
+					writer.write_all(&[DATETIME])?;
+					write_name(writer, column.name.as_deref())?;
+					write_string(writer, column.timezone.name())?;
+					for value in &column.data {
+						match value {
+							Some(value) => {
+								writer.write_all(&[1])?;
+								writer.write_all(&value.to_le_bytes())?;
+							}
+							None => writer.write_all(&[0])?,
+						}
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Read a table written by [`Table::to_binary`].
+	///
+	/// # Errors
+	///
+	/// This function returns an error if reading from `reader` fails or the data was not written by [`Table::to_binary`] in this version of modelfox.
+	pub fn from_binary(reader: &mut impl Read) -> Result<Table> {
+		let mut magic = [0; 4];
+		reader.read_exact(&mut magic)?;
+		if &magic != MAGIC || read_u32(reader)? != VERSION {
+			bail!("The data is not a table in the binary format of this version of modelfox.");
+		}
+		let ncols = read_usize(reader)?;
+		let nrows = read_usize(reader)?;
+		let mut columns = Vec::with_capacity(ncols);
+		for _ in 0..ncols {
+			let column_type = read_u8(reader)?;
+			let name = read_name(reader)?;
+			let column = match column_type {
+				UNKNOWN => TableColumn::Unknown(UnknownTableColumn { name, len: nrows }),
+				NUMBER => {
+					let data = (0..nrows)
+						.map(|_| {
+							let mut bytes = [0; 4];
+							reader.read_exact(&mut bytes)?;
+							Ok(f32::from_le_bytes(bytes))
+						})
+						.collect::<Result<_>>()?;
+					TableColumn::Number(NumberTableColumn { name, data })
+				}
+				ENUM => {
+					let n_variants = read_usize(reader)?;
+					let variants = (0..n_variants)
+						.map(|_| read_string(reader))
+						.collect::<Result<Vec<_>>>()?;
+					let data = (0..nrows)
+						.map(|_| {
+							let value = read_usize(reader)?;
+							if value > n_variants {
+								bail!("An enum value is out of range.");
+							}
+							Ok(NonZeroUsize::new(value))
+						})
+						.collect::<Result<_>>()?;
+					TableColumn::Enum(EnumTableColumn::new(name, variants, data))
+				}
+				TEXT => {
+					let data = (0..nrows)
+						.map(|_| read_string(reader))
+						.collect::<Result<_>>()?;
+					TableColumn::Text(TextTableColumn { name, data })
+				}
+				DATETIME => {
+					let timezone = match read_string(reader)?.parse::<Tz>() {
+						Ok(timezone) => timezone,
+						Err(_) => bail!("A datetime column has an invalid timezone."),
+					};
+					let data = (0..nrows)
+						.map(|_| match read_u8(reader)? {
+							0 => Ok(None),
+							_ => {
+								let mut bytes = [0; 8];
+								reader.read_exact(&mut bytes)?;
+								Ok(Some(i64::from_le_bytes(bytes)))
+							}
+						})
+						.collect::<Result<_>>()?;
+					TableColumn::DateTime(DateTimeTableColumn::new(name, timezone, data))
+				}
+				_ => bail!("A column has an invalid type."),
+			};
+			columns.push(column);
+		}
+		Ok(Table { columns })
+	}
+}
+
+fn write_u32(writer: &mut impl Write, value: u32) -> Result<()> {
+	writer.write_all(&value.to_le_bytes())?;
+	Ok(())
+}
+
+fn write_usize(writer: &mut impl Write, value: usize) -> Result<()> {
+	writer.write_all(&value.to_u64().unwrap().to_le_bytes())?;
+	Ok(())
+}
+
+fn write_string(writer: &mut impl Write, value: &str) -> Result<()> {
+	write_usize(writer, value.len())?;
+	writer.write_all(value.as_bytes())?;
+	Ok(())
+}
+
+fn write_name(writer: &mut impl Write, name: Option<&str>) -> Result<()> {
+	match name {
+		Some(name) => {
+			writer.write_all(&[1])?;
+			write_string(writer, name)
+		}
+		None => {
+			writer.write_all(&[0])?;
+			Ok(())
+		}
+	}
+}
+
+fn read_u8(reader: &mut impl Read) -> Result<u8> {
+	let mut bytes = [0; 1];
+	reader.read_exact(&mut bytes)?;
+	Ok(bytes[0])
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32> {
+	let mut bytes = [0; 4];
+	reader.read_exact(&mut bytes)?;
+	Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_usize(reader: &mut impl Read) -> Result<usize> {
+	let mut bytes = [0; 8];
+	reader.read_exact(&mut bytes)?;
+	match u64::from_le_bytes(bytes).to_usize() {
+		Some(value) => Ok(value),
+		None => bail!("A length is too large."),
+	}
+}
+
+fn read_string(reader: &mut impl Read) -> Result<String> {
+	let len = read_usize(reader)?;
+	let mut bytes = vec![0; len];
+	reader.read_exact(&mut bytes)?;
+	Ok(String::from_utf8(bytes)?)
+}
+
+fn read_name(reader: &mut impl Read) -> Result<Option<String>> {
+	match read_u8(reader)? {
+		0 => Ok(None),
+		_ => Ok(Some(read_string(reader)?)),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_binary_round_trip() {
+		let table = Table {
+			columns: vec![
+				TableColumn::Unknown(UnknownTableColumn {
+					name: Some("unknown".to_owned()),
+					len: 3,
+				}),
+				TableColumn::Number(NumberTableColumn::new(
+					Some("number".to_owned()),
+					vec![1.0, f32::NAN, -2.5],
+				)),
+				TableColumn::Enum(EnumTableColumn::new(
+					Some("enum".to_owned()),
+					vec!["a".to_owned(), "b".to_owned()],
+					vec![NonZeroUsize::new(2), None, NonZeroUsize::new(1)],
+				)),
+				TableColumn::Text(TextTableColumn::new(
+					None,
+					vec!["one".to_owned(), String::new(), "três".to_owned()],
+				)),
+				TableColumn::DateTime(DateTimeTableColumn::new(
+					Some("datetime".to_owned()),
+					Tz::America__New_York,
+					vec![Some(0), None, Some(-86400)],
+				)),
+			],
+		};
+		let mut bytes = Vec::new();
+		table.to_binary(&mut bytes).unwrap();
+		let read_table = Table::from_binary(&mut bytes.as_slice()).unwrap();
+		// NaN is not equal to itself, so compare the number column's bits.
+		let number_bits = |table: &Table| {
+			table.columns[1]
+				.as_number()
+				.unwrap()
+				.iter()
+				.map(|value| value.to_bits())
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(number_bits(&read_table), number_bits(&table));
+		assert_eq!(read_table.columns[0], table.columns[0]);
+		assert_eq!(read_table.columns[2..], table.columns[2..]);
+		assert!(Table::from_binary(&mut &bytes[..bytes.len() - 1]).is_err());
+	}
+}
@@ -0,0 +1,62 @@
+use chrono::prelude::*;
+use chrono_tz::Tz;
+
+/// These are the formats tried, in order, for values without a utc offset. The value is interpreted as a local time in the column's timezone.
+const NAIVE_DATETIME_FORMATS: &[&str] = &[
+	"%Y-%m-%dT%H:%M:%S%.f",
+	"%Y-%m-%d %H:%M:%S%.f",
+	"%Y-%m-%dT%H:%M",
+	"%Y-%m-%d %H:%M",
+];
+
+/// This is the format for dates without a time. The value is interpreted as midnight in the column's timezone.
+const NAIVE_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/**
+Parse a date or datetime to a unix timestamp in seconds.
+
+Values with a utc offset, such as `2021-03-14T01:30:00-08:00` or `2021-03-14T09:30:00Z`, are parsed as RFC 3339 and `timezone` is ignored. Values without one, such as `2021-03-14 01:30` or `2021-03-14`, are interpreted as a local time in `timezone`. A local time that is ambiguous because of a daylight saving time transition resolves to the earlier instant, and a local time that does not exist because of one is invalid.
+*/
+#[must_use]
+pub fn parse_datetime(value: &str, timezone: Tz) -> Option<i64> {
+	let value = value.trim();
+	if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+		return Some(datetime.timestamp());
+	}
+	let naive_datetime = NAIVE_DATETIME_FORMATS
+		.iter()
+		.find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+		.or_else(|| {
+			NaiveDate::parse_from_str(value, NAIVE_DATE_FORMAT)
+				.ok()
+				.map(|date| date.and_hms(0, 0, 0))
+		})?;
+	timezone
+		.from_local_datetime(&naive_datetime)
+		.earliest()
+		.map(|datetime| datetime.timestamp())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_parse_datetime() {
+		let timezone: Tz = "America/New_York".parse().unwrap();
+		assert_eq!(parse_datetime("2021-03-14", Tz::UTC), Some(1_615_680_000));
+		assert_eq!(parse_datetime("2021-03-14", timezone), Some(1_615_698_000));
+		assert_eq!(
+			parse_datetime("2021-03-14T12:00:00Z", timezone),
+			Some(1_615_723_200)
+		);
+		assert_eq!(
+			parse_datetime("2021-03-14 12:00:00", timezone),
+			Some(1_615_737_600)
+		);
+		// 2:30 am does not exist in New York on the day daylight saving time starts.
+		assert_eq!(parse_datetime("2021-03-14 02:30", timezone), None);
+		assert_eq!(parse_datetime("20210314", timezone), None);
+		assert_eq!(parse_datetime("hello", timezone), None);
+	}
+}
@@ -0,0 +1,168 @@
+/*!
+This module reads compressed csv files, like `data.csv.gz` or `data.csv.zst`, by decompressing them as they are read. Exports from data warehouses are usually compressed, and decompressing a large file to disk before loading it would double the disk space needed.
+*/
+
+use anyhow::Result;
+use std::{
+	cell::Cell,
+	fs::File,
+	io::{BufReader, Read, Seek, SeekFrom},
+	path::{Path, PathBuf},
+	rc::Rc,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Compression {
+	Gzip,
+	Zstd,
+}
+
+impl Compression {
+	/// Determine the compression of the file at `path` from its extension. This returns `None` for files that are not compressed.
+	pub(crate) fn from_path(path: &Path) -> Option<Compression> {
+		match path.extension().and_then(std::ffi::OsStr::to_str) {
+			Some("gz" | "gzip") => Some(Compression::Gzip),
+			Some("zst" | "zstd") => Some(Compression::Zstd),
+			_ => None,
+		}
+	}
+}
+
+/**
+A `DecompressingReader` reads the decompressed contents of a compressed file.
+
+Loading a csv takes two passes over the file, one to infer the column types and one to load the values. Compressed streams cannot seek, so seeking backward starts decompressing again from the beginning of the file and seeking forward reads and discards the bytes in between.
+*/
+pub(crate) struct DecompressingReader {
+	path: PathBuf,
+	compression: Compression,
+	decoder: Box<dyn Read>,
+	/// This is the position in the decompressed contents.
+	position: u64,
+	/// This is the number of bytes of the compressed file that have been read, which is used to report progress.
+	compressed_position: Rc<Cell<u64>>,
+}
+
+impl DecompressingReader {
+	pub(crate) fn open(path: &Path, compression: Compression) -> Result<DecompressingReader> {
+		let compressed_position = Rc::new(Cell::new(0));
+		let decoder = decoder(path, compression, compressed_position.clone())?;
+		Ok(DecompressingReader {
+			path: path.to_owned(),
+			compression,
+			decoder,
+			position: 0,
+			compressed_position,
+		})
+	}
+
+	pub(crate) fn compressed_position(&self) -> u64 {
+		self.compressed_position.get()
+	}
+}
+
+impl Read for DecompressingReader {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let n = self.decoder.read(buf)?;
+		self.position += n as u64;
+		Ok(n)
+	}
+}
+
+impl Seek for DecompressingReader {
+	fn seek(&mut self, position: SeekFrom) -> std::io::Result<u64> {
+		let target = match position {
+			SeekFrom::Start(target) => target,
+			SeekFrom::Current(0) => return Ok(self.position),
+			_ => {
+				return Err(std::io::Error::new(
+					std::io::ErrorKind::Unsupported,
+					"compressed files only support seeking from the start",
+				))
+			}
+		};
+		if target < self.position {
+			self.compressed_position.set(0);
+			self.decoder = decoder(
+				&self.path,
+				self.compression,
+				self.compressed_position.clone(),
+			)
+			.map_err(|error| std::io::Error::new(std::io::ErrorKind::Other, error))?;
+			self.position = 0;
+		}
+		let n_bytes_to_skip = target - self.position;
+		let n_bytes_skipped = std::io::copy(&mut self.take(n_bytes_to_skip), &mut std::io::sink())?;
+		if n_bytes_skipped < n_bytes_to_skip {
+			return Err(std::io::Error::new(
+				std::io::ErrorKind::UnexpectedEof,
+				"attempted to seek past the end of the compressed file",
+			));
+		}
+		Ok(self.position)
+	}
+}
+
+/// This reader counts the bytes read from the compressed file.
+struct CountingReader<R> {
+	inner: R,
+	count: Rc<Cell<u64>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let n = self.inner.read(buf)?;
+		self.count.set(self.count.get() + n as u64);
+		Ok(n)
+	}
+}
+
+fn decoder(path: &Path, compression: Compression, count: Rc<Cell<u64>>) -> Result<Box<dyn Read>> {
+	let file = CountingReader {
+		inner: BufReader::new(File::open(path)?),
+		count,
+	};
+	decode(file, compression)
+}
+
+#[cfg(feature = "compression")]
+fn decode(file: impl Read + 'static, compression: Compression) -> Result<Box<dyn Read>> {
+	let decoder: Box<dyn Read> = match compression {
+		// Files written by tools that compress in parallel, like pigz, may contain more than one gzip member.
+		Compression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(file)),
+		Compression::Zstd => Box::new(zstd::stream::read::Decoder::new(file)?),
+	};
+	Ok(decoder)
+}
+
+#[cfg(not(feature = "compression"))]
+fn decode(_file: impl Read + 'static, _compression: Compression) -> Result<Box<dyn Read>> {
+	anyhow::bail!("this build does not support reading compressed files")
+}
+
+#[cfg(all(test, feature = "compression"))]
+mod test {
+	use super::*;
+	use std::io::Write;
+
+	#[test]
+	fn test_decompressing_reader_seek() {
+		let path = std::env::temp_dir().join("modelfox_table_test_decompressing_reader.csv.gz");
+		let mut encoder = flate2::write::GzEncoder::new(
+			File::create(&path).unwrap(),
+			flate2::Compression::fast(),
+		);
+		encoder.write_all(b"a,b\n1,2\n3,4\n").unwrap();
+		encoder.finish().unwrap();
+		let mut reader =
+			DecompressingReader::open(&path, Compression::from_path(&path).unwrap()).unwrap();
+		let mut contents = String::new();
+		reader.read_to_string(&mut contents).unwrap();
+		assert_eq!(contents, "a,b\n1,2\n3,4\n");
+		reader.seek(SeekFrom::Start(4)).unwrap();
+		let mut contents = String::new();
+		reader.read_to_string(&mut contents).unwrap();
+		assert_eq!(contents, "1,2\n3,4\n");
+		std::fs::remove_file(&path).unwrap();
+	}
+}
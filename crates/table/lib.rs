@@ -4,21 +4,28 @@ This crate implements two dimensional collections where each column can have a d
 
 #![warn(clippy::pedantic)]
 
-pub use self::load::{FromCsvOptions, ProgressEvent};
+pub use self::{
+	datetime::parse_datetime,
+	load::{FromCsvOptions, InvalidValuePolicy, ProgressEvent, DEFAULT_INVALID_VALUES},
+};
+use chrono_tz::Tz;
 use fnv::FnvHashMap;
 use modelfox_zip::zip;
 use ndarray::prelude::*;
 use num::ToPrimitive;
 use std::num::NonZeroUsize;
 
+mod binary;
+mod datetime;
+mod decompress;
 mod load;
 
 pub mod prelude {
 	pub use super::{
-		EnumTableColumn, EnumTableColumnView, NumberTableColumn, NumberTableColumnView, Table,
-		TableColumn, TableColumnType, TableColumnView, TableValue, TableView, TableViewMut,
-		TextTableColumn, TextTableColumnView, TextTableColumnViewMut, UnknownTableColumn,
-		UnknownTableColumnView,
+		DateTimeTableColumn, DateTimeTableColumnView, EnumTableColumn, EnumTableColumnView,
+		NumberTableColumn, NumberTableColumnView, Table, TableColumn, TableColumnType,
+		TableColumnView, TableValue, TableView, TableViewMut, TextTableColumn, TextTableColumnView,
+		TextTableColumnViewMut, UnknownTableColumn, UnknownTableColumnView,
 	};
 }
 
@@ -33,6 +40,7 @@ pub enum TableColumn {
 	Number(NumberTableColumn),
 	Enum(EnumTableColumn),
 	Text(TextTableColumn),
+	DateTime(DateTimeTableColumn),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -61,6 +69,14 @@ pub struct TextTableColumn {
 	data: Vec<String>,
 }
 
+/// A `DateTimeTableColumn` stores dates and datetimes as unix timestamps in seconds, with `None` for values that failed to parse. Its timezone is used to interpret values without a utc offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateTimeTableColumn {
+	name: Option<String>,
+	timezone: Tz,
+	data: Vec<Option<i64>>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TableView<'a> {
 	columns: Vec<TableColumnView<'a>>,
@@ -72,6 +88,7 @@ pub enum TableColumnView<'a> {
 	Number(NumberTableColumnView<'a>),
 	Enum(EnumTableColumnView<'a>),
 	Text(TextTableColumnView<'a>),
+	DateTime(DateTimeTableColumnView<'a>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -99,6 +116,13 @@ pub struct TextTableColumnView<'a> {
 	data: &'a [String],
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateTimeTableColumnView<'a> {
+	name: Option<&'a str>,
+	timezone: Tz,
+	data: &'a [Option<i64>],
+}
+
 #[derive(Debug, PartialEq)]
 pub struct TableViewMut<'a> {
 	columns: Vec<TableColumnViewMut<'a>>,
@@ -136,6 +160,7 @@ pub enum TableColumnType {
 	Number,
 	Enum { variants: Vec<String> },
 	Text,
+	DateTime { timezone: Tz },
 }
 
 #[derive(Debug, Clone)]
@@ -144,6 +169,7 @@ pub enum TableColumnTypeView<'a> {
 	Number,
 	Enum { variants: &'a [String] },
 	Text,
+	DateTime { timezone: Tz },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -152,6 +178,7 @@ pub enum TableValue<'a> {
 	Number(f32),
 	Enum(Option<NonZeroUsize>),
 	Text(&'a str),
+	DateTime(Option<i64>),
 }
 
 impl Table {
@@ -171,6 +198,9 @@ impl Table {
 				TableColumnType::Text => {
 					TableColumn::Text(TextTableColumn::new(column_name, Vec::new()))
 				}
+				TableColumnType::DateTime { timezone } => TableColumn::DateTime(
+					DateTimeTableColumn::new(column_name, timezone, Vec::new()),
+				),
 			})
 			.collect();
 		Table { columns }
@@ -256,6 +286,11 @@ impl Table {
 						*a = TableValue::Text(b);
 					}
 				}
+				TableColumn::DateTime(column) => {
+					for (a, b) in zip!(ndarray_column.iter_mut(), column.data.as_slice()) {
+						*a = TableValue::DateTime(*b);
+					}
+				}
 			}
 		}
 		rows
@@ -270,6 +305,7 @@ impl TableColumn {
 			TableColumn::Number(s) => s.len(),
 			TableColumn::Enum(s) => s.len(),
 			TableColumn::Text(s) => s.len(),
+			TableColumn::DateTime(s) => s.len(),
 		}
 	}
 
@@ -280,6 +316,7 @@ impl TableColumn {
 			TableColumn::Number(s) => s.data.is_empty(),
 			TableColumn::Enum(s) => s.data.is_empty(),
 			TableColumn::Text(s) => s.data.is_empty(),
+			TableColumn::DateTime(s) => s.data.is_empty(),
 		}
 	}
 
@@ -290,6 +327,7 @@ impl TableColumn {
 			TableColumn::Number(s) => s.name.as_deref(),
 			TableColumn::Enum(s) => s.name.as_deref(),
 			TableColumn::Text(s) => s.name.as_deref(),
+			TableColumn::DateTime(s) => s.name.as_deref(),
 		}
 	}
 
@@ -304,6 +342,9 @@ impl TableColumn {
 			TableColumn::Text(ttc) => {
 				let _data = ttc.data_mut().remove(idx);
 			}
+			TableColumn::DateTime(dtc) => {
+				let _data = dtc.data_mut().remove(idx);
+			}
 			TableColumn::Unknown(utc) => {
 				let len = utc.len_mut();
 				*len -= 1;
@@ -335,6 +376,14 @@ impl TableColumn {
 		}
 	}
 
+	#[must_use]
+	pub fn as_datetime(&self) -> Option<&DateTimeTableColumn> {
+		match self {
+			TableColumn::DateTime(s) => Some(s),
+			_ => None,
+		}
+	}
+
 	pub fn as_number_mut(&mut self) -> Option<&mut NumberTableColumn> {
 		match self {
 			TableColumn::Number(s) => Some(s),
@@ -356,6 +405,13 @@ impl TableColumn {
 		}
 	}
 
+	pub fn as_datetime_mut(&mut self) -> Option<&mut DateTimeTableColumn> {
+		match self {
+			TableColumn::DateTime(s) => Some(s),
+			_ => None,
+		}
+	}
+
 	#[must_use]
 	pub fn view(&self) -> TableColumnView {
 		match self {
@@ -363,6 +419,7 @@ impl TableColumn {
 			TableColumn::Number(column) => TableColumnView::Number(column.view()),
 			TableColumn::Enum(column) => TableColumnView::Enum(column.view()),
 			TableColumn::Text(column) => TableColumnView::Text(column.view()),
+			TableColumn::DateTime(column) => TableColumnView::DateTime(column.view()),
 		}
 	}
 }
@@ -548,6 +605,54 @@ impl TextTableColumn {
 	}
 }
 
+impl DateTimeTableColumn {
+	#[must_use]
+	pub fn new(name: Option<String>, timezone: Tz, data: Vec<Option<i64>>) -> DateTimeTableColumn {
+		DateTimeTableColumn {
+			name,
+			timezone,
+			data,
+		}
+	}
+
+	#[must_use]
+	pub fn name(&self) -> &Option<String> {
+		&self.name
+	}
+
+	#[must_use]
+	pub fn timezone(&self) -> Tz {
+		self.timezone
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.data.len() == 0
+	}
+
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.data.len()
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &Option<i64>> {
+		self.data.iter()
+	}
+
+	pub fn data_mut(&mut self) -> &mut Vec<Option<i64>> {
+		&mut self.data
+	}
+
+	#[must_use]
+	pub fn view(&self) -> DateTimeTableColumnView {
+		DateTimeTableColumnView {
+			name: self.name.as_deref(),
+			timezone: self.timezone,
+			data: &self.data,
+		}
+	}
+}
+
 impl<'a> TableView<'a> {
 	#[must_use]
 	pub fn columns(&self) -> &Vec<TableColumnView<'a>> {
@@ -585,6 +690,7 @@ impl<'a> TableView<'a> {
 				TableColumnView::Number(column) => TableValue::Number(column.data[index]),
 				TableColumnView::Enum(column) => TableValue::Enum(column.data[index]),
 				TableColumnView::Text(column) => TableValue::Text(&column.data[index]),
+				TableColumnView::DateTime(column) => TableValue::DateTime(column.data[index]),
 			}
 		}
 	}
@@ -653,6 +759,11 @@ impl<'a> TableView<'a> {
 						*a = TableValue::Text(b);
 					}
 				}
+				TableColumnView::DateTime(column) => {
+					for (a, b) in zip!(ndarray_column.iter_mut(), column.data) {
+						*a = TableValue::DateTime(*b);
+					}
+				}
 			}
 		}
 		rows
@@ -667,6 +778,7 @@ impl<'a> TableColumnView<'a> {
 			TableColumnView::Number(s) => s.data.len(),
 			TableColumnView::Enum(s) => s.data.len(),
 			TableColumnView::Text(s) => s.data.len(),
+			TableColumnView::DateTime(s) => s.data.len(),
 		}
 	}
 
@@ -677,6 +789,7 @@ impl<'a> TableColumnView<'a> {
 			TableColumnView::Number(s) => s.data.is_empty(),
 			TableColumnView::Enum(s) => s.data.is_empty(),
 			TableColumnView::Text(s) => s.data.is_empty(),
+			TableColumnView::DateTime(s) => s.data.is_empty(),
 		}
 	}
 
@@ -687,6 +800,7 @@ impl<'a> TableColumnView<'a> {
 			TableColumnView::Number(s) => s.name,
 			TableColumnView::Enum(s) => s.name,
 			TableColumnView::Text(s) => s.name,
+			TableColumnView::DateTime(s) => s.name,
 		}
 	}
 
@@ -699,6 +813,9 @@ impl<'a> TableColumnView<'a> {
 				variants: column.variants,
 			},
 			TableColumnView::Text(_) => TableColumnTypeView::Text,
+			TableColumnView::DateTime(column) => TableColumnTypeView::DateTime {
+				timezone: column.timezone,
+			},
 		}
 	}
 
@@ -726,6 +843,14 @@ impl<'a> TableColumnView<'a> {
 		}
 	}
 
+	#[must_use]
+	pub fn as_datetime(&self) -> Option<DateTimeTableColumnView> {
+		match self {
+			TableColumnView::DateTime(s) => Some(s.clone()),
+			_ => None,
+		}
+	}
+
 	#[must_use]
 	pub fn split_at_row(&self, index: usize) -> (TableColumnView<'a>, TableColumnView<'a>) {
 		match self {
@@ -780,6 +905,21 @@ impl<'a> TableColumnView<'a> {
 					}),
 				)
 			}
+			TableColumnView::DateTime(column) => {
+				let (data_a, data_b) = column.data.split_at(index);
+				(
+					TableColumnView::DateTime(DateTimeTableColumnView {
+						name: column.name,
+						timezone: column.timezone,
+						data: data_a,
+					}),
+					TableColumnView::DateTime(DateTimeTableColumnView {
+						name: column.name,
+						timezone: column.timezone,
+						data: data_b,
+					}),
+				)
+			}
 		}
 	}
 
@@ -790,6 +930,7 @@ impl<'a> TableColumnView<'a> {
 			TableColumnView::Number(s) => TableColumnView::Number(s.view()),
 			TableColumnView::Enum(s) => TableColumnView::Enum(s.view()),
 			TableColumnView::Text(s) => TableColumnView::Text(s.view()),
+			TableColumnView::DateTime(s) => TableColumnView::DateTime(s.view()),
 		}
 	}
 }
@@ -929,6 +1070,47 @@ impl<'a> TextTableColumnView<'a> {
 	}
 }
 
+impl<'a> DateTimeTableColumnView<'a> {
+	#[must_use]
+	pub fn name(&self) -> Option<&str> {
+		self.name
+	}
+
+	#[must_use]
+	pub fn timezone(&self) -> Tz {
+		self.timezone
+	}
+
+	#[must_use]
+	pub fn data(&self) -> &[Option<i64>] {
+		self.data
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.data.len() == 0
+	}
+
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.data.len()
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = &Option<i64>> {
+		self.data.iter()
+	}
+
+	#[must_use]
+	pub fn as_slice(&self) -> &[Option<i64>] {
+		self.data
+	}
+
+	#[must_use]
+	pub fn view(&self) -> DateTimeTableColumnView {
+		self.clone()
+	}
+}
+
 impl<'a> TableValue<'a> {
 	#[must_use]
 	pub fn as_number(&self) -> Option<&f32> {
@@ -974,4 +1156,19 @@ impl<'a> TableValue<'a> {
 			_ => None,
 		}
 	}
+
+	#[must_use]
+	pub fn as_datetime(&self) -> Option<&Option<i64>> {
+		match self {
+			TableValue::DateTime(s) => Some(s),
+			_ => None,
+		}
+	}
+
+	pub fn as_datetime_mut(&mut self) -> Option<&mut Option<i64>> {
+		match self {
+			TableValue::DateTime(s) => Some(s),
+			_ => None,
+		}
+	}
 }
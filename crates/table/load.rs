@@ -1,5 +1,9 @@
-use super::{Table, TableColumn, TableColumnType};
-use anyhow::Result;
+use super::{
+	decompress::{Compression, DecompressingReader},
+	parse_datetime, Table, TableColumn, TableColumnType,
+};
+use anyhow::{bail, Result};
+use chrono_tz::Tz;
 use modelfox_progress_counter::ProgressCounter;
 use modelfox_zip::zip;
 // NOTE - this import is actually used, false positive with the lint.
@@ -10,11 +14,13 @@ use std::{
 	path::Path,
 };
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct FromCsvOptions<'a> {
 	pub column_types: Option<BTreeMap<String, TableColumnType>>,
 	pub infer_options: InferOptions,
 	pub invalid_values: &'a [&'a str],
+	/// This is the policy for each column's values that are not one of the `invalid_values` but fail to parse for the column's type. Columns that are not present use [`InvalidValuePolicy::Missing`].
+	pub invalid_value_policies: BTreeMap<String, InvalidValuePolicy>,
 }
 
 impl<'a> Default for FromCsvOptions<'a> {
@@ -23,10 +29,28 @@ impl<'a> Default for FromCsvOptions<'a> {
 			column_types: None,
 			infer_options: InferOptions::default(),
 			invalid_values: DEFAULT_INVALID_VALUES,
+			invalid_value_policies: BTreeMap::new(),
 		}
 	}
 }
 
+/// This controls what happens when a value in a number, enum, or datetime column fails to parse, such as the value "abc" in a number column or a value that is not one of the variants of an enum column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidValuePolicy {
+	/// Treat the value as missing.
+	Missing,
+	/// Drop the row, and report the number of rows dropped for each column with [`ProgressEvent::InvalidValuesDropped`].
+	DropRow,
+	/// Stop loading and return an error.
+	Error,
+}
+
+impl Default for InvalidValuePolicy {
+	fn default() -> InvalidValuePolicy {
+		InvalidValuePolicy::Missing
+	}
+}
+
 #[derive(Clone, Debug)]
 pub struct InferOptions {
 	pub enum_max_unique_values: usize,
@@ -41,7 +65,7 @@ impl Default for InferOptions {
 }
 
 /// These values are the default values that are considered invalid.
-const DEFAULT_INVALID_VALUES: &[&str] = &[
+pub const DEFAULT_INVALID_VALUES: &[&str] = &[
 	"", "+Inf", "+inf", "-Inf", "-NaN", "-inf", "-nan", "?", "N/A", "NA", "NULL", "NaN", "n/a",
 	"nan", "null",
 ];
@@ -52,6 +76,7 @@ pub enum ProgressEvent {
 	InferDone,
 	LoadStarted(ProgressCounter),
 	LoadDone,
+	InvalidValuesDropped { column_name: String, n_rows: usize },
 }
 
 impl Table {
@@ -72,6 +97,8 @@ impl Table {
 		)
 	}
 
+	/// Load a table from the csv file at `path`. Files ending in `.gz` or `.zst` are decompressed as they are read, and progress is reported in bytes of the compressed file.
+	///
 	/// # Errors
 	///
 	/// Returns an error if unable to load CSV from reader.
@@ -81,22 +108,53 @@ impl Table {
 		handle_progress_event: &mut impl FnMut(ProgressEvent),
 	) -> Result<Table> {
 		let len = std::fs::metadata(path)?.len();
-		Table::from_csv(
-			&mut csv::Reader::from_path(path)?,
+		if let Some(compression) = Compression::from_path(path) {
+			let reader = DecompressingReader::open(path, compression)?;
+			Table::from_csv_with_progress(
+				&mut csv::Reader::from_reader(reader),
+				len,
+				options,
+				handle_progress_event,
+				|reader, _| reader.get_ref().compressed_position(),
+			)
+		} else {
+			Table::from_csv(
+				&mut csv::Reader::from_path(path)?,
+				len,
+				options,
+				handle_progress_event,
+			)
+		}
+	}
+
+	#[allow(clippy::missing_errors_doc)]
+	pub fn from_csv<R>(
+		reader: &mut csv::Reader<R>,
+		len: u64,
+		options: FromCsvOptions,
+		handle_progress_event: &mut impl FnMut(ProgressEvent),
+	) -> Result<Table>
+	where
+		R: std::io::Read + std::io::Seek,
+	{
+		Table::from_csv_with_progress(
+			reader,
 			len,
 			options,
 			handle_progress_event,
+			|_, position| position.byte(),
 		)
 	}
 
+	/// Load a table from a csv reader, calling `progress` after each record is read to determine how many of the `len` bytes have been read.
 	#[allow(clippy::too_many_lines)]
-	#[allow(clippy::missing_errors_doc)]
 	#[allow(clippy::missing_panics_doc)]
-	pub fn from_csv<R>(
+	fn from_csv_with_progress<R>(
 		reader: &mut csv::Reader<R>,
 		len: u64,
 		options: FromCsvOptions,
 		handle_progress_event: &mut impl FnMut(ProgressEvent),
+		progress: impl Fn(&csv::Reader<R>, &csv::Position) -> u64,
 	) -> Result<Table>
 	where
 		R: std::io::Read + std::io::Seek,
@@ -164,7 +222,7 @@ impl Table {
 			let progress_counter = ProgressCounter::new(len);
 			handle_progress_event(ProgressEvent::InferStarted(progress_counter.clone()));
 			while reader.read_record(&mut record)? {
-				progress_counter.set(record.position().unwrap().byte());
+				progress_counter.set(progress(reader, record.position().unwrap()));
 				for (index, infer_stats) in &mut infer_stats {
 					let value = record.get(*index).unwrap();
 					infer_stats.update(value);
@@ -208,44 +266,260 @@ impl Table {
 					TableColumn::Number(column) => column.data.reserve_exact(n_rows),
 					TableColumn::Enum(column) => column.data.reserve_exact(n_rows),
 					TableColumn::Text(column) => column.data.reserve_exact(n_rows),
+					TableColumn::DateTime(column) => column.data.reserve_exact(n_rows),
 				}
 			}
 		}
 		// Read each csv record and insert the values into the columns of the table.
+		let mut invalid_value_checker = InvalidValueChecker::new(
+			&table,
+			options.invalid_values,
+			&options.invalid_value_policies,
+		);
 		let mut record = csv::ByteRecord::new();
 		let progress_counter = ProgressCounter::new(len);
 		handle_progress_event(ProgressEvent::LoadStarted(progress_counter.clone()));
 		while reader.read_byte_record(&mut record)? {
-			progress_counter.set(record.position().unwrap().byte());
+			progress_counter.set(progress(reader, record.position().unwrap()));
+			let row_index = record.position().unwrap().record() - 1;
+			if !invalid_value_checker.check(&table, row_index, record.iter())? {
+				continue;
+			}
 			for (column, value) in zip!(table.columns.iter_mut(), record.iter()) {
-				match column {
-					TableColumn::Unknown(column) => {
-						column.len += 1;
-					}
-					TableColumn::Number(column) => {
-						let value = match fast_float::parse::<f32, &[u8]>(value) {
-							Ok(value) if value.is_finite() => value,
-							_ => std::f32::NAN,
-						};
-						column.data.push(value);
-					}
-					TableColumn::Enum(column) => {
-						let value = std::str::from_utf8(value)
-							.ok()
-							.and_then(|value| column.value_for_variant(value));
-						column.data.push(value);
-					}
-					TableColumn::Text(column) => {
-						column.data.push(std::str::from_utf8(value)?.to_owned());
+				push_value(column, value)?;
+			}
+		}
+		handle_progress_event(ProgressEvent::LoadDone);
+		invalid_value_checker.finish(&table, handle_progress_event);
+		Ok(table)
+	}
+
+	/// Load a table from rows of values that were already read into memory, such as the result of a database query. Each row must have one value per column name, and missing values should be represented by an empty string. Columns whose types are not present in the options are inferred the same way as with `from_csv`.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn from_rows(
+		column_names: Vec<String>,
+		rows: &[Vec<String>],
+		options: FromCsvOptions,
+		handle_progress_event: &mut impl FnMut(ProgressEvent),
+	) -> Result<Table> {
+		let n_rows = rows.len().to_u64().unwrap();
+		let infer_options = &options.infer_options;
+		let column_types: Vec<Option<TableColumnType>> = column_names
+			.iter()
+			.map(|column_name| {
+				options
+					.column_types
+					.as_ref()
+					.and_then(|column_types| column_types.get(column_name))
+					.cloned()
+			})
+			.collect();
+
+		// Infer the types of the columns whose types were not specified.
+		let needs_infer = column_types.iter().any(Option::is_none);
+		let column_types: Vec<TableColumnType> = if needs_infer {
+			let mut infer_stats: Vec<Option<InferStats>> = column_types
+				.iter()
+				.map(|column_type| match column_type {
+					Some(_) => None,
+					None => Some(InferStats::new(infer_options)),
+				})
+				.collect();
+			let progress_counter = ProgressCounter::new(n_rows);
+			handle_progress_event(ProgressEvent::InferStarted(progress_counter.clone()));
+			for row in rows {
+				progress_counter.inc(1);
+				for (infer_stats, value) in zip!(infer_stats.iter_mut(), row.iter()) {
+					if let Some(infer_stats) = infer_stats {
+						infer_stats.update(value);
 					}
 				}
 			}
+			handle_progress_event(ProgressEvent::InferDone);
+			zip!(column_types, infer_stats)
+				.map(|(column_type, infer_stats)| {
+					column_type.unwrap_or_else(|| infer_stats.unwrap().finalize())
+				})
+				.collect()
+		} else {
+			column_types.into_iter().map(Option::unwrap).collect()
+		};
+
+		// Create the table and insert the values of each row into its columns.
+		let column_names = column_names.into_iter().map(Some).collect();
+		let mut table = Table::new(column_names, column_types);
+		for column in &mut table.columns {
+			match column {
+				TableColumn::Unknown(_) => {}
+				TableColumn::Number(column) => column.data.reserve_exact(rows.len()),
+				TableColumn::Enum(column) => column.data.reserve_exact(rows.len()),
+				TableColumn::Text(column) => column.data.reserve_exact(rows.len()),
+				TableColumn::DateTime(column) => column.data.reserve_exact(rows.len()),
+			}
+		}
+		let mut invalid_value_checker = InvalidValueChecker::new(
+			&table,
+			options.invalid_values,
+			&options.invalid_value_policies,
+		);
+		let progress_counter = ProgressCounter::new(n_rows);
+		handle_progress_event(ProgressEvent::LoadStarted(progress_counter.clone()));
+		for (row_index, row) in rows.iter().enumerate() {
+			progress_counter.inc(1);
+			let values = row.iter().map(String::as_bytes);
+			if !invalid_value_checker.check(&table, row_index.to_u64().unwrap(), values)? {
+				continue;
+			}
+			for (column, value) in zip!(table.columns.iter_mut(), row.iter()) {
+				push_value(column, value.as_bytes())?;
+			}
 		}
 		handle_progress_event(ProgressEvent::LoadDone);
+		invalid_value_checker.finish(&table, handle_progress_event);
 		Ok(table)
 	}
 }
 
+fn push_value(column: &mut TableColumn, value: &[u8]) -> Result<()> {
+	match column {
+		TableColumn::Unknown(column) => {
+			column.len += 1;
+		}
+		TableColumn::Number(column) => {
+			let value = match fast_float::parse::<f32, &[u8]>(value) {
+				Ok(value) if value.is_finite() => value,
+				_ => std::f32::NAN,
+			};
+			column.data.push(value);
+		}
+		TableColumn::Enum(column) => {
+			let value = std::str::from_utf8(value)
+				.ok()
+				.and_then(|value| column.value_for_variant(value));
+			column.data.push(value);
+		}
+		TableColumn::Text(column) => {
+			column.data.push(std::str::from_utf8(value)?.to_owned());
+		}
+		TableColumn::DateTime(column) => {
+			let value = std::str::from_utf8(value)
+				.ok()
+				.and_then(|value| parse_datetime(value, column.timezone));
+			column.data.push(value);
+		}
+	}
+	Ok(())
+}
+
+/// An `InvalidValueChecker` enforces the invalid value policies of the columns whose policy is not [`InvalidValuePolicy::Missing`] before each row is inserted into the table.
+struct InvalidValueChecker<'a> {
+	invalid_values: &'a [&'a str],
+	/// These are the index and policy of each column to check.
+	policies: Vec<(usize, InvalidValuePolicy)>,
+	/// This is the number of rows dropped because of each column, in the same order as `policies`.
+	n_rows_dropped: Vec<usize>,
+}
+
+impl<'a> InvalidValueChecker<'a> {
+	fn new(
+		table: &Table,
+		invalid_values: &'a [&'a str],
+		invalid_value_policies: &BTreeMap<String, InvalidValuePolicy>,
+	) -> InvalidValueChecker<'a> {
+		let policies: Vec<(usize, InvalidValuePolicy)> = table
+			.columns
+			.iter()
+			.enumerate()
+			.filter_map(|(index, column)| {
+				let policy = column
+					.name()
+					.and_then(|name| invalid_value_policies.get(name))
+					.copied()
+					.unwrap_or_default();
+				match policy {
+					InvalidValuePolicy::Missing => None,
+					policy => Some((index, policy)),
+				}
+			})
+			.collect();
+		let n_rows_dropped = vec![0; policies.len()];
+		InvalidValueChecker {
+			invalid_values,
+			policies,
+			n_rows_dropped,
+		}
+	}
+
+	/// Check the values of the row at `row_index`, returning `false` if the row should be dropped.
+	fn check<'b>(
+		&mut self,
+		table: &Table,
+		row_index: u64,
+		values: impl Iterator<Item = &'b [u8]>,
+	) -> Result<bool> {
+		if self.policies.is_empty() {
+			return Ok(true);
+		}
+		let values: Vec<&[u8]> = values.collect();
+		for ((column_index, policy), n_rows_dropped) in
+			zip!(self.policies.iter(), self.n_rows_dropped.iter_mut())
+		{
+			let column = &table.columns[*column_index];
+			let value = values[*column_index];
+			if !is_invalid_value(column, value, self.invalid_values) {
+				continue;
+			}
+			match policy {
+				InvalidValuePolicy::Missing => {}
+				InvalidValuePolicy::DropRow => {
+					*n_rows_dropped += 1;
+					return Ok(false);
+				}
+				InvalidValuePolicy::Error => bail!(
+					"The value \"{}\" in row {} of column \"{}\" is invalid.",
+					String::from_utf8_lossy(value),
+					row_index + 1,
+					column.name().unwrap_or_default(),
+				),
+			}
+		}
+		Ok(true)
+	}
+
+	fn finish(self, table: &Table, handle_progress_event: &mut impl FnMut(ProgressEvent)) {
+		for ((column_index, _), n_rows) in zip!(self.policies, self.n_rows_dropped) {
+			if n_rows > 0 {
+				handle_progress_event(ProgressEvent::InvalidValuesDropped {
+					column_name: table.columns[column_index]
+						.name()
+						.unwrap_or_default()
+						.to_owned(),
+					n_rows,
+				});
+			}
+		}
+	}
+}
+
+/// Determine whether `value` fails to parse for the type of `column`. Values that are one of the `invalid_values` are missing values, not invalid ones.
+fn is_invalid_value(column: &TableColumn, value: &[u8], invalid_values: &[&str]) -> bool {
+	let value = match std::str::from_utf8(value) {
+		Ok(value) => value,
+		Err(_) => return true,
+	};
+	if invalid_values.contains(&value) {
+		return false;
+	}
+	match column {
+		TableColumn::Unknown(_) | TableColumn::Text(_) => false,
+		TableColumn::Number(_) => !fast_float::parse::<f32, &str>(value)
+			.map(f32::is_finite)
+			.unwrap_or(false),
+		TableColumn::Enum(column) => column.value_for_variant(value).is_none(),
+		TableColumn::DateTime(column) => parse_datetime(value, column.timezone).is_none(),
+	}
+}
+
 #[derive(Clone, Debug)]
 pub struct InferStats<'a> {
 	infer_options: &'a InferOptions,
@@ -257,6 +531,7 @@ pub struct InferStats<'a> {
 enum InferColumnType {
 	Unknown,
 	Number,
+	DateTime,
 	Enum,
 	Text,
 }
@@ -289,12 +564,26 @@ impl<'a> InferStats<'a> {
 					.unwrap_or(false)
 				{
 					self.column_type = InferColumnType::Number;
+				} else if self.column_type == InferColumnType::Unknown
+					&& parse_datetime(value, Tz::UTC).is_some()
+				{
+					self.column_type = InferColumnType::DateTime;
 				} else if self.unique_values.is_some() {
 					self.column_type = InferColumnType::Enum;
 				} else {
 					self.column_type = InferColumnType::Text;
 				}
 			}
+			// A column is only inferred to be a datetime column if every valid value parses as a date or datetime.
+			InferColumnType::DateTime => {
+				if parse_datetime(value, Tz::UTC).is_none() {
+					if self.unique_values.is_some() {
+						self.column_type = InferColumnType::Enum;
+					} else {
+						self.column_type = InferColumnType::Text;
+					}
+				}
+			}
 			InferColumnType::Enum => {
 				if self.unique_values.is_none() {
 					self.column_type = InferColumnType::Text;
@@ -323,6 +612,7 @@ impl<'a> InferStats<'a> {
 				}
 				TableColumnType::Number
 			}
+			InferColumnType::DateTime => TableColumnType::DateTime { timezone: Tz::UTC },
 			InferColumnType::Enum => TableColumnType::Enum {
 				variants: self.unique_values.unwrap().into_iter().collect(),
 			},
@@ -480,3 +770,129 @@ fn test_column_types() {
  }
  "###);
 }
+
+#[test]
+fn test_infer_datetime() {
+	let csv = r#"date,mixed
+2021-03-14,2021-03-14
+2021-03-15T08:30:00Z,yesterday
+"#;
+	let table = Table::from_csv(
+		&mut csv::Reader::from_reader(std::io::Cursor::new(csv)),
+		csv.len().to_u64().unwrap(),
+		FromCsvOptions::default(),
+		&mut |_| {},
+	)
+	.unwrap();
+	let date = table.columns()[0].as_datetime().unwrap();
+	assert_eq!(date.timezone(), Tz::UTC);
+	assert_eq!(
+		date.iter().copied().collect::<Vec<_>>(),
+		vec![Some(1_615_680_000), Some(1_615_797_000)]
+	);
+	assert!(table.columns()[1].as_enum().is_some());
+}
+
+#[test]
+fn test_from_rows() {
+	let column_names = vec!["number".to_owned(), "enum".to_owned(), "flag".to_owned()];
+	let rows = vec![
+		vec!["1".to_owned(), "a".to_owned(), "true".to_owned()],
+		vec!["".to_owned(), "b".to_owned(), "false".to_owned()],
+	];
+	let mut column_types = BTreeMap::new();
+	column_types.insert(
+		"flag".to_owned(),
+		TableColumnType::Enum {
+			variants: vec!["false".to_owned(), "true".to_owned()],
+		},
+	);
+	let table = Table::from_rows(
+		column_names,
+		&rows,
+		FromCsvOptions {
+			column_types: Some(column_types),
+			..Default::default()
+		},
+		&mut |_| {},
+	)
+	.unwrap();
+	assert_eq!(table.nrows(), 2);
+	let number_column = table.columns()[0].as_number().unwrap();
+	assert_eq!(number_column.len(), 2);
+	assert!(number_column.iter().nth(1).unwrap().is_nan());
+	let enum_column = table.columns()[1].as_enum().unwrap();
+	assert_eq!(enum_column.variants(), &["a".to_owned(), "b".to_owned()]);
+	let flag_column = table.columns()[2].as_enum().unwrap();
+	assert_eq!(
+		flag_column.iter().copied().collect::<Vec<_>>(),
+		vec![
+			flag_column.value_for_variant("true"),
+			flag_column.value_for_variant("false")
+		],
+	);
+}
+
+#[test]
+fn test_invalid_value_policies() {
+	let csv = "number,enum\n1,a\nabc,b\n3,c\n,a\n";
+	let load = |number_policy, enum_policy| {
+		let mut dropped = Vec::new();
+		let table = Table::from_csv(
+			&mut csv::Reader::from_reader(std::io::Cursor::new(csv)),
+			csv.len().to_u64().unwrap(),
+			FromCsvOptions {
+				column_types: Some(
+					vec![
+						("number".to_owned(), TableColumnType::Number),
+						(
+							"enum".to_owned(),
+							TableColumnType::Enum {
+								variants: vec!["a".to_owned(), "b".to_owned()],
+							},
+						),
+					]
+					.into_iter()
+					.collect(),
+				),
+				invalid_value_policies: vec![
+					("number".to_owned(), number_policy),
+					("enum".to_owned(), enum_policy),
+				]
+				.into_iter()
+				.collect(),
+				..Default::default()
+			},
+			&mut |progress_event| {
+				if let ProgressEvent::InvalidValuesDropped {
+					column_name,
+					n_rows,
+				} = progress_event
+				{
+					dropped.push((column_name, n_rows));
+				}
+			},
+		)?;
+		Ok::<_, anyhow::Error>((table.nrows(), dropped))
+	};
+	// Invalid values are treated as missing by default.
+	let (n_rows, dropped) = load(InvalidValuePolicy::Missing, InvalidValuePolicy::Missing).unwrap();
+	assert_eq!(n_rows, 4);
+	assert!(dropped.is_empty());
+	// Rows with invalid values are dropped, but rows with missing values are kept.
+	let (n_rows, dropped) = load(InvalidValuePolicy::DropRow, InvalidValuePolicy::Missing).unwrap();
+	assert_eq!(n_rows, 3);
+	assert_eq!(dropped, vec![("number".to_owned(), 1)]);
+	let (n_rows, dropped) = load(InvalidValuePolicy::DropRow, InvalidValuePolicy::DropRow).unwrap();
+	assert_eq!(n_rows, 2);
+	assert_eq!(
+		dropped,
+		vec![("number".to_owned(), 1), ("enum".to_owned(), 1)]
+	);
+	// The first invalid value is reported in the error.
+	let error = load(InvalidValuePolicy::Missing, InvalidValuePolicy::Error).unwrap_err();
+	assert_eq!(
+		error.to_string(),
+		"The value \"c\" in row 3 of column \"enum\" is invalid."
+	);
+}
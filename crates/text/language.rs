@@ -0,0 +1,165 @@
+use fnv::FnvHashSet;
+
+/// A language detected for a text column. Detection counts how often each language's stop words appear, which is cheap and reliable for the longer text bag of words features are built from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+	English,
+	German,
+	French,
+	Spanish,
+}
+
+/// A language is only detected when at least this fraction of tokens are its stop words. Natural language text is typically 30-50% stop words, so this rejects columns of names, codes, and other short values.
+const LANGUAGE_DETECTOR_MIN_STOP_WORD_FRACTION: f32 = 0.1;
+
+const ENGLISH_STOP_WORDS: &[&str] = &[
+	"a", "about", "all", "an", "and", "are", "as", "at", "be", "been", "but", "by", "can", "do",
+	"for", "from", "had", "has", "have", "he", "her", "his", "i", "if", "in", "is", "it", "its",
+	"me", "my", "of", "on", "or", "our", "she", "so", "that", "the", "their", "them", "there",
+	"they", "this", "to", "was", "we", "were", "what", "when", "which", "who", "will", "with",
+	"would", "you", "your",
+];
+
+const GERMAN_STOP_WORDS: &[&str] = &[
+	"als", "am", "an", "auch", "auf", "aus", "bei", "bin", "bis", "da", "das", "dass", "dem",
+	"den", "der", "des", "die", "du", "ein", "eine", "einem", "einen", "einer", "er", "es", "für",
+	"hat", "ich", "ihr", "im", "in", "ist", "mit", "nach", "nicht", "noch", "nur", "oder", "sich",
+	"sie", "sind", "so", "um", "und", "uns", "von", "vor", "war", "was", "wie", "wir", "zu", "zum",
+	"zur",
+];
+
+const FRENCH_STOP_WORDS: &[&str] = &[
+	"au", "aux", "avec", "ce", "ces", "cette", "dans", "de", "des", "du", "elle", "en", "est",
+	"et", "il", "ils", "je", "la", "le", "les", "leur", "lui", "ma", "mais", "me", "mes", "mon",
+	"ne", "nous", "on", "ou", "par", "pas", "pour", "qu", "que", "qui", "sa", "se", "ses", "son",
+	"sont", "sur", "ta", "te", "tu", "un", "une", "vous", "été", "être",
+];
+
+const SPANISH_STOP_WORDS: &[&str] = &[
+	"al", "como", "con", "de", "del", "el", "ella", "en", "es", "esta", "este", "fue", "ha", "la",
+	"las", "le", "les", "lo", "los", "me", "mi", "muy", "más", "no", "nos", "o", "para", "pero",
+	"por", "que", "se", "si", "sin", "su", "sus", "te", "tu", "un", "una", "uno", "y", "ya", "yo",
+	"él",
+];
+
+impl Language {
+	pub const ALL: [Language; 4] = [
+		Language::English,
+		Language::German,
+		Language::French,
+		Language::Spanish,
+	];
+
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Language::English => "english",
+			Language::German => "german",
+			Language::French => "french",
+			Language::Spanish => "spanish",
+		}
+	}
+
+	/// Get the language's stop words. Stop words are lowercase, matching the output of the default tokenizer.
+	pub fn stop_words(&self) -> &'static [&'static str] {
+		match self {
+			Language::English => ENGLISH_STOP_WORDS,
+			Language::German => GERMAN_STOP_WORDS,
+			Language::French => FRENCH_STOP_WORDS,
+			Language::Spanish => SPANISH_STOP_WORDS,
+		}
+	}
+
+	pub fn is_stop_word(&self, token: &str) -> bool {
+		self.stop_words().contains(&token)
+	}
+}
+
+impl std::fmt::Display for Language {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let name = match self {
+			Language::English => "English",
+			Language::German => "German",
+			Language::French => "French",
+			Language::Spanish => "Spanish",
+		};
+		write!(f, "{}", name)
+	}
+}
+
+impl std::str::FromStr for Language {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Language::ALL
+			.iter()
+			.find(|language| language.as_str() == s)
+			.copied()
+			.ok_or(())
+	}
+}
+
+/// A `LanguageDetector` accumulates stop word counts over the tokens it is updated with. Detectors for separate chunks of a column can be merged.
+#[derive(Clone, Debug)]
+pub struct LanguageDetector {
+	stop_words: Vec<(Language, FnvHashSet<&'static str>)>,
+	stop_word_counts: [u64; 4],
+	token_count: u64,
+}
+
+impl Default for LanguageDetector {
+	fn default() -> Self {
+		let stop_words = Language::ALL
+			.iter()
+			.map(|language| (*language, language.stop_words().iter().copied().collect()))
+			.collect();
+		LanguageDetector {
+			stop_words,
+			stop_word_counts: [0; 4],
+			token_count: 0,
+		}
+	}
+}
+
+impl LanguageDetector {
+	pub fn update(&mut self, token: &str) {
+		self.token_count += 1;
+		for (count, (_, stop_words)) in self.stop_word_counts.iter_mut().zip(self.stop_words.iter())
+		{
+			if stop_words.contains(token) {
+				*count += 1;
+			}
+		}
+	}
+
+	pub fn token_count(&self) -> u64 {
+		self.token_count
+	}
+
+	pub fn merge(&mut self, other: LanguageDetector) {
+		self.token_count += other.token_count;
+		for (count, other_count) in self
+			.stop_word_counts
+			.iter_mut()
+			.zip(other.stop_word_counts.iter())
+		{
+			*count += other_count;
+		}
+	}
+
+	/// Get the language whose stop words were most frequent, or `None` if no language's stop words were frequent enough for the text to be natural language.
+	pub fn detect(&self) -> Option<Language> {
+		if self.token_count == 0 {
+			return None;
+		}
+		let (language, count) = Language::ALL
+			.iter()
+			.zip(self.stop_word_counts.iter())
+			.max_by_key(|(_, count)| **count)?;
+		let fraction = *count as f32 / self.token_count as f32;
+		if fraction >= LANGUAGE_DETECTOR_MIN_STOP_WORD_FRACTION {
+			Some(*language)
+		} else {
+			None
+		}
+	}
+}
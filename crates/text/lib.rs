@@ -1,9 +1,11 @@
 pub use self::{
+	language::{Language, LanguageDetector},
 	ngram::{NGram, NGramRef, NGramType},
 	tokenizer::Tokenizer,
 	word_embedding::WordEmbeddingModel,
 };
 
+mod language;
 mod ngram;
 mod tokenizer;
 mod word_embedding;
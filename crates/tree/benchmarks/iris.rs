@@ -40,7 +40,7 @@ fn main() {
 		.predict(features_test.view(), probabilities.view_mut());
 
 	// Compute Metrics.
-	let mut metrics = modelfox_metrics::MulticlassClassificationMetrics::new(n_classes);
+	let mut metrics = modelfox_metrics::MulticlassClassificationMetrics::new(n_classes, 99);
 	metrics.update(modelfox_metrics::MulticlassClassificationMetricsInput {
 		probabilities: probabilities.view(),
 		labels: labels_test.view().as_slice().into(),
@@ -0,0 +1,55 @@
+use modelfox_table::prelude::*;
+use modelfox_tree::{Progress, ShapMode};
+use serde_json::json;
+use std::{path::Path, time::Instant};
+
+/// Compare the latency of computing exact and fast feature contributions for a single example, which is what the production prediction page does.
+fn main() {
+	// Load the data.
+	let csv_file_path_train = Path::new("data/boston_train.csv");
+	let csv_file_path_test = Path::new("data/boston_test.csv");
+	let target_column_index = 13;
+	let mut features_train =
+		Table::from_path(csv_file_path_train, Default::default(), &mut |_| {}).unwrap();
+	let labels_train = features_train.columns_mut().remove(target_column_index);
+	let mut features_test =
+		Table::from_path(csv_file_path_test, Default::default(), &mut |_| {}).unwrap();
+	features_test.columns_mut().remove(target_column_index);
+	let labels_train = labels_train.as_number().unwrap();
+
+	// Train a model with deep trees, where the difference between the modes is largest.
+	let train_output = modelfox_tree::Regressor::train(
+		features_train.view(),
+		labels_train.view(),
+		&modelfox_tree::TrainOptions {
+			learning_rate: 0.1,
+			max_leaf_nodes: 255,
+			max_rounds: 100,
+			min_examples_per_node: 1,
+			..Default::default()
+		},
+		Progress {
+			kill_chip: &modelfox_kill_chip::KillChip::default(),
+			handle_progress_event: &mut |_| {},
+		},
+	);
+
+	// Compute the feature contributions for each test example one at a time.
+	let features_test = features_test.to_rows();
+	let mut output = serde_json::Map::new();
+	for (name, mode) in [("exact", ShapMode::Exact), ("fast", ShapMode::Fast)] {
+		let start = Instant::now();
+		for example in features_test.outer_iter() {
+			let example = example.insert_axis(ndarray::Axis(0));
+			train_output
+				.model
+				.compute_feature_contributions(example, mode);
+		}
+		let duration = start.elapsed() / features_test.nrows() as u32;
+		output.insert(
+			format!("{}_duration_per_example_us", name),
+			json!(duration.as_secs_f64() * 1e6),
+		);
+	}
+	println!("{}", serde_json::Value::Object(output));
+}
@@ -1,11 +1,11 @@
-use crate::TrainOptions;
+use crate::{BinningStrategy, TrainOptions};
 use itertools::Itertools;
 use modelfox_finite::Finite;
+use modelfox_metrics::QuantileSketch;
 use modelfox_table::{NumberTableColumnView, TableColumnView, TableView};
-use modelfox_zip::zip;
 use num::ToPrimitive;
 use rayon::prelude::*;
-use std::{cmp::Ordering, collections::BTreeMap};
+use std::collections::BTreeMap;
 
 /*
 This struct specifies how to produce a binned feature from a feature.
@@ -68,109 +68,104 @@ fn compute_binning_instructions_for_number_feature(
 	column: NumberTableColumnView,
 	train_options: &TrainOptions,
 ) -> BinningInstruction {
-	// Create a histogram of values in the number feature.
-	let mut histogram: BTreeMap<Finite<f32>, usize> = BTreeMap::new();
-	let mut n_finite_values = 0;
+	let max_valid_bins = train_options
+		.max_valid_bins_for_number_features
+		.to_usize()
+		.unwrap();
 	let max = usize::min(
 		column.len(),
 		train_options.max_examples_for_computing_bin_thresholds,
 	);
+	// Count the unique values while there are few enough to give each its own bin. Once there are too many, summarize the values in a sketch instead, so the memory this takes does not grow with the number of examples.
+	let mut histogram: BTreeMap<Finite<f32>, usize> = BTreeMap::new();
+	let mut sketch: Option<NumberFeatureSketch> = None;
 	for value in column.iter().take(max) {
 		if let Ok(value) = Finite::new(*value) {
-			*histogram.entry(value).or_insert(0) += 1;
-			n_finite_values += 1;
+			match &mut sketch {
+				None => {
+					*histogram.entry(value).or_insert(0) += 1;
+					if histogram.len() >= max_valid_bins {
+						sketch = Some(NumberFeatureSketch::from_histogram(std::mem::take(
+							&mut histogram,
+						)));
+					}
+				}
+				Some(sketch) => sketch.insert(value.get()),
+			}
 		}
 	}
-	// If the number of unique values is less than `max_valid_bins_for_number_features`, then create one bin per unique value. Otherwise, create bins at quantiles.
-	let thresholds = if histogram.len()
-		< train_options
-			.max_valid_bins_for_number_features
-			.to_usize()
-			.unwrap()
-	{
-		histogram
+	// If the number of unique values is less than `max_valid_bins_for_number_features`, then create one bin per unique value. Otherwise, create bins according to the binning strategy.
+	let thresholds = match sketch {
+		None => histogram
 			.keys()
 			.tuple_windows()
 			.map(|(a, b)| (a.get() + b.get()) / 2.0)
-			.collect()
-	} else {
-		compute_binning_instruction_thresholds_for_number_feature_as_quantiles_from_histogram(
-			histogram,
-			n_finite_values,
-			train_options,
-		)
+			.collect(),
+		Some(sketch) => match train_options.binning_strategy {
+			BinningStrategy::Quantile => sketch.quantile_thresholds(max_valid_bins),
+			BinningStrategy::Uniform => sketch.uniform_thresholds(max_valid_bins),
+		},
 	};
 	BinningInstruction::Number { thresholds }
 }
 
-/// Compute the binning instruction thresholds for a number feature as quantiles from the histogram of its values.
-fn compute_binning_instruction_thresholds_for_number_feature_as_quantiles_from_histogram(
-	histogram: BTreeMap<Finite<f32>, usize>,
-	histogram_values_count: usize,
-	train_options: &TrainOptions,
-) -> Vec<f32> {
-	let first_hist_entry = histogram.iter().next().unwrap();
-	let num_zeros = if first_hist_entry.0.get() == 0.0 {
-		*first_hist_entry.1
-	} else {
-		0
-	};
-	let total_non_zero_values_count = histogram_values_count - num_zeros;
-	let total_non_zero_values_count = total_non_zero_values_count.to_f32().unwrap();
-	let quantiles: Vec<f32> = (1..train_options
-		.max_valid_bins_for_number_features
-		.to_usize()
-		.unwrap())
-		.map(|i| {
-			i.to_f32().unwrap()
-				/ train_options
-					.max_valid_bins_for_number_features
-					.to_f32()
-					.unwrap()
-		})
-		.collect();
-	let quantile_indexes: Vec<usize> = quantiles
-		.iter()
-		.map(|q| {
-			((total_non_zero_values_count - 1.0) * q)
-				.trunc()
-				.to_usize()
-				.unwrap()
-		})
-		.collect();
-	let quantile_fracts: Vec<f32> = quantiles
-		.iter()
-		.map(|q| ((total_non_zero_values_count - 1.0) * q).fract())
-		.collect();
-	let mut quantiles: Vec<Option<f32>> = vec![None; quantiles.len()];
-	let mut current_count: usize = 0;
-	let mut iter = histogram.iter().peekable();
-	if num_zeros > 0 {
-		iter.next();
-	}
-	while let Some((value, count)) = iter.next() {
-		let value = value.get();
-		current_count += count;
-		let quantiles_iter = zip!(
-			quantiles.iter_mut(),
-			quantile_indexes.iter(),
-			quantile_fracts.iter()
-		)
-		.filter(|(quantile, _, _)| quantile.is_none());
-		for (quantile, index, fract) in quantiles_iter {
-			match (current_count - 1).cmp(index) {
-				Ordering::Equal => {
-					if *fract > 0.0 {
-						let next_value = iter.peek().unwrap().0.get();
-						*quantile = Some(value * (1.0 - fract) + next_value * fract);
-					} else {
-						*quantile = Some(value);
-					}
-				}
-				Ordering::Greater => *quantile = Some(value),
-				Ordering::Less => {}
+/// A `NumberFeatureSketch` summarizes the values of a number feature with too many unique values to give each its own bin. Zeros are counted separately, because sparse features are mostly zeros, and the quantile thresholds should divide the other values.
+struct NumberFeatureSketch {
+	sketch: QuantileSketch,
+	n_zeros: usize,
+}
+
+impl NumberFeatureSketch {
+	fn from_histogram(histogram: BTreeMap<Finite<f32>, usize>) -> NumberFeatureSketch {
+		let mut sketch = NumberFeatureSketch {
+			sketch: QuantileSketch::default(),
+			n_zeros: 0,
+		};
+		for (value, count) in histogram {
+			if value.get() == 0.0 {
+				sketch.n_zeros += count;
+			} else {
+				sketch
+					.sketch
+					.insert_weighted(value.get(), count.to_f64().unwrap());
 			}
 		}
+		sketch
+	}
+
+	fn insert(&mut self, value: f32) {
+		if value == 0.0 {
+			self.n_zeros += 1;
+		} else {
+			self.sketch.insert(value);
+		}
+	}
+
+	/// Compute thresholds at evenly spaced quantiles of the values. Zeros are left out when they are the smallest value.
+	fn quantile_thresholds(mut self, max_valid_bins: usize) -> Vec<f32> {
+		let zeros_are_min = self.sketch.min().map(|min| min > 0.0).unwrap_or(true);
+		if self.n_zeros > 0 && !zeros_are_min {
+			self.sketch
+				.insert_weighted(0.0, self.n_zeros.to_f64().unwrap());
+		}
+		let quantiles = (1..max_valid_bins)
+			.map(|i| i.to_f32().unwrap() / max_valid_bins.to_f32().unwrap())
+			.collect::<Vec<_>>();
+		self.sketch.quantiles(&quantiles).unwrap_or_default()
+	}
+
+	/// Compute thresholds at evenly spaced values between the minimum and maximum.
+	fn uniform_thresholds(self, max_valid_bins: usize) -> Vec<f32> {
+		let zero = if self.n_zeros > 0 { Some(0.0) } else { None };
+		let min = self.sketch.min().into_iter().chain(zero).reduce(f32::min);
+		let max = self.sketch.max().into_iter().chain(zero).reduce(f32::max);
+		let (min, max) = match (min, max) {
+			(Some(min), Some(max)) => (min, max),
+			_ => return Vec::new(),
+		};
+		let bin_width = (max - min) / max_valid_bins.to_f32().unwrap();
+		(1..max_valid_bins)
+			.map(|i| min + i.to_f32().unwrap() * bin_width)
+			.collect()
 	}
-	quantiles.into_iter().map(|q| q.unwrap()).collect()
 }
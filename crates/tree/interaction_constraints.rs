@@ -0,0 +1,77 @@
+/// Interaction constraints restrict which features may be split on in the same branch of a tree. Each group is a set of features, and every feature split on along the path from the root to a node must belong to a single group. Features that are not in any group may only be split on along with themselves.
+#[derive(Debug)]
+pub struct InteractionConstraints {
+	/// These are the train feature indexes in each group.
+	groups: Vec<Vec<usize>>,
+	/// These are the indexes of the groups that contain each train feature.
+	groups_for_feature: Vec<Vec<usize>>,
+}
+
+impl InteractionConstraints {
+	/// Create the interaction constraints for training from groups of feature indexes. Features that were not used in training are dropped from the groups, so `train_feature_index_to_feature_index` maps each train feature index to its feature index.
+	pub fn new(
+		interaction_constraints: &[Vec<usize>],
+		train_feature_index_to_feature_index: &[usize],
+	) -> InteractionConstraints {
+		let n_train_features = train_feature_index_to_feature_index.len();
+		let mut groups: Vec<Vec<usize>> = interaction_constraints
+			.iter()
+			.map(|group| {
+				train_feature_index_to_feature_index
+					.iter()
+					.enumerate()
+					.filter(|(_, feature_index)| group.contains(feature_index))
+					.map(|(train_feature_index, _)| train_feature_index)
+					.collect()
+			})
+			.collect();
+		let mut groups_for_feature = vec![Vec::new(); n_train_features];
+		for (group_index, group) in groups.iter().enumerate() {
+			for train_feature_index in group.iter() {
+				groups_for_feature[*train_feature_index].push(group_index);
+			}
+		}
+		// Give each feature that is not in any group a group of its own.
+		for (train_feature_index, groups_for_feature) in groups_for_feature.iter_mut().enumerate() {
+			if groups_for_feature.is_empty() {
+				groups_for_feature.push(groups.len());
+				groups.push(vec![train_feature_index]);
+			}
+		}
+		InteractionConstraints {
+			groups,
+			groups_for_feature,
+		}
+	}
+
+	/// Retrieve the indexes of all the groups, which are the groups a split at the root of a tree may use.
+	pub fn root_groups(&self) -> Vec<usize> {
+		(0..self.groups.len()).collect()
+	}
+
+	/// Retrieve the groups that remain available to the children of a node after it splits on the feature with `train_feature_index`. These are the node's groups that contain the feature.
+	pub fn groups_after_split(&self, groups: &[usize], train_feature_index: usize) -> Vec<usize> {
+		groups
+			.iter()
+			.cloned()
+			.filter(|group_index| {
+				self.groups_for_feature[train_feature_index].contains(group_index)
+			})
+			.collect()
+	}
+
+	/// Mark the features that do not belong to any of `groups` as not splittable.
+	pub fn restrict_splittable_features(&self, groups: &[usize], splittable_features: &mut [bool]) {
+		let mut allowed_features = vec![false; splittable_features.len()];
+		for group_index in groups.iter() {
+			for train_feature_index in self.groups[*group_index].iter() {
+				allowed_features[*train_feature_index] = true;
+			}
+		}
+		for (splittable_feature, allowed_feature) in
+			splittable_features.iter_mut().zip(allowed_features)
+		{
+			*splittable_feature = *splittable_feature && allowed_feature;
+		}
+	}
+}
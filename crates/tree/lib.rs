@@ -18,6 +18,7 @@ mod compute_bin_stats;
 mod compute_binned_features;
 mod compute_binning_instructions;
 mod compute_feature_importances;
+mod interaction_constraints;
 mod multiclass_classifier;
 mod pool;
 mod rearrange_examples_index;
@@ -39,10 +40,14 @@ pub struct Progress<'a> {
 pub struct TrainOptions {
 	/// This option controls whether binned features will be laid out in row major or column major order. Each will produce the same result, but row major will be faster for datasets with more rows and fewer columns, while column major will be faster for datasets with fewer rows and more columns.
 	pub binned_features_layout: BinnedFeaturesLayout,
+	/// This option controls how the bin thresholds for number features with more unique values than `max_valid_bins_for_number_features` are chosen.
+	pub binning_strategy: BinningStrategy,
 	/// If true, the model will include the loss on the training data after each round.
 	pub compute_losses: bool,
 	/// This option controls early stopping. If it is `Some`, then early stopping will be enabled. If it is `None`, then early stopping will be disabled.
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
+	/// If this option is `Some`, it is a list of groups of feature indexes, and every feature split on along a path from the root of a tree to a leaf must belong to a single group. Features that are not in any group may only be split on along with themselves. If it is `None`, any features may be split on together.
+	pub interaction_constraints: Option<Vec<Vec<usize>>>,
 	/// This option sets the L2 regularization value for continuous splits, which helps avoid overfitting.
 	pub l2_regularization_for_continuous_splits: f32,
 	/// This option sets the L2 regularization value for discrete splits, which helps avoid overfitting.
@@ -73,8 +78,10 @@ impl Default for TrainOptions {
 	fn default() -> TrainOptions {
 		TrainOptions {
 			binned_features_layout: BinnedFeaturesLayout::ColumnMajor,
+			binning_strategy: BinningStrategy::Quantile,
 			compute_losses: false,
 			early_stopping_options: None,
+			interaction_constraints: None,
 			l2_regularization_for_continuous_splits: 0.0,
 			l2_regularization_for_discrete_splits: 10.0,
 			learning_rate: 0.1,
@@ -98,6 +105,30 @@ pub enum BinnedFeaturesLayout {
 	ColumnMajor,
 }
 
+/// This enum defines how the bin thresholds for number features are chosen.
+#[derive(Clone, Copy, Debug)]
+pub enum BinningStrategy {
+	/// Place the thresholds at evenly spaced quantiles of the feature's values, so each bin holds roughly the same number of examples.
+	Quantile,
+	/// Place the thresholds at evenly spaced values between the feature's minimum and maximum, which is faster to compute but can leave most examples in a few bins when the values are skewed.
+	Uniform,
+}
+
+/// This enum defines how feature contributions are computed.
+#[derive(Clone, Copy, Debug)]
+pub enum ShapMode {
+	/// Compute exact SHAP values with the polynomial time TreeSHAP algorithm. This takes time proportional to the number of leaves times the square of the depth of each tree.
+	Exact,
+	/// Attribute the change in each tree's expected output at every branch on the example's path to the feature the branch splits on. This takes time proportional to the number of nodes in each tree, but the contributions are only an approximation of SHAP values, and give too much credit to features split on near the leaves.
+	Fast,
+}
+
+impl Default for ShapMode {
+	fn default() -> ShapMode {
+		ShapMode::Exact
+	}
+}
+
 /// The parameters in this struct control how to determine whether training should stop early after each round or epoch.
 #[derive(Clone, Debug)]
 pub struct EarlyStoppingOptions {
@@ -2,7 +2,7 @@ use crate::{
 	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
 	train::TrainOutput,
 	train_tree::TrainTree,
-	Progress, TrainOptions, Tree,
+	Progress, ShapMode, TrainOptions, Tree,
 };
 use modelfox_table::prelude::*;
 use modelfox_zip::{pzip, zip};
@@ -29,6 +29,8 @@ pub struct MulticlassClassifierTrainOutput {
 	pub losses: Option<Vec<f32>>,
 	/// These are the importances of each feature as measured by the number of times each feature was used in a branch node.
 	pub feature_importances: Option<Vec<f32>>,
+	/// These are the thresholds used to bin the values of each feature. Enum features are binned by variant and have no thresholds.
+	pub bin_thresholds: Vec<Option<Vec<f32>>>,
 }
 
 impl MulticlassClassifier {
@@ -72,17 +74,23 @@ impl MulticlassClassifier {
 		});
 	}
 
-	/// Compute SHAP values.
+	/// Compute SHAP values, either exactly or approximately depending on `mode`.
 	pub fn compute_feature_contributions(
 		&self,
 		features: ArrayView2<TableValue>,
+		mode: ShapMode,
 	) -> Vec<Vec<ComputeShapValuesForExampleOutput>> {
 		features
 			.axis_iter(Axis(0))
 			.map(|features| {
 				zip!(self.trees.axis_iter(Axis(1)), self.biases.iter())
 					.map(|(tree, bias)| {
-						compute_shap_values_for_example(features.as_slice().unwrap(), tree, *bias)
+						compute_shap_values_for_example(
+							features.as_slice().unwrap(),
+							tree,
+							*bias,
+							mode,
+						)
 					})
 					.collect()
 			})
@@ -2,7 +2,7 @@ use crate::{
 	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
 	train::{train, Task, TrainOutput},
 	train_tree::TrainTree,
-	Progress, TrainOptions, Tree,
+	Progress, ShapMode, TrainOptions, Tree,
 };
 use modelfox_table::prelude::*;
 use modelfox_zip::{pzip, zip};
@@ -28,6 +28,8 @@ pub struct RegressorTrainOutput {
 	pub losses: Option<Vec<f32>>,
 	/// These are the importances of each feature as measured by the number of times each feature was used in a branch node.
 	pub feature_importances: Option<Vec<f32>>,
+	/// These are the thresholds used to bin the values of each feature. Enum features are binned by variant and have no thresholds.
+	pub bin_thresholds: Vec<Option<Vec<f32>>>,
 }
 
 impl Regressor {
@@ -65,16 +67,22 @@ impl Regressor {
 		}
 	}
 
-	/// Compute SHAP values.
+	/// Compute SHAP values, either exactly or approximately depending on `mode`.
 	pub fn compute_feature_contributions(
 		&self,
 		features: ArrayView2<TableValue>,
+		mode: ShapMode,
 	) -> Vec<ComputeShapValuesForExampleOutput> {
 		let trees = ArrayView1::from_shape(self.trees.len(), &self.trees).unwrap();
 		features
 			.axis_iter(Axis(0))
 			.map(|features| {
-				compute_shap_values_for_example(features.as_slice().unwrap(), trees, self.bias)
+				compute_shap_values_for_example(
+					features.as_slice().unwrap(),
+					trees,
+					self.bias,
+					mode,
+				)
 			})
 			.collect()
 	}
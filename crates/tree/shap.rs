@@ -1,5 +1,6 @@
 use crate::{
-	BranchNode, BranchSplit, BranchSplitContinuous, BranchSplitDiscrete, Node, SplitDirection, Tree,
+	BranchNode, BranchSplit, BranchSplitContinuous, BranchSplitDiscrete, Node, ShapMode,
+	SplitDirection, Tree,
 };
 use ndarray::prelude::*;
 use num::ToPrimitive;
@@ -15,14 +16,25 @@ pub fn compute_shap_values_for_example(
 	example: &[modelfox_table::TableValue],
 	trees: ArrayView1<Tree>,
 	bias: f32,
+	mode: ShapMode,
 ) -> ComputeShapValuesForExampleOutput {
 	let mut baseline_value = bias as f64;
-	for tree in trees {
-		baseline_value += compute_expectation(tree, 0);
-	}
 	let mut feature_contribution_values = vec![0.0; example.len()];
-	for tree in trees {
-		tree_shap(example, tree, feature_contribution_values.as_mut_slice());
+	match mode {
+		ShapMode::Exact => {
+			for tree in trees {
+				baseline_value += compute_expectation(tree, 0);
+			}
+			for tree in trees {
+				tree_shap(example, tree, feature_contribution_values.as_mut_slice());
+			}
+		}
+		ShapMode::Fast => {
+			for tree in trees {
+				baseline_value +=
+					path_contributions(example, tree, feature_contribution_values.as_mut_slice());
+			}
+		}
 	}
 	let output_value = baseline_value + feature_contribution_values.iter().sum::<f64>();
 	ComputeShapValuesForExampleOutput {
@@ -240,6 +252,19 @@ fn unwound_path_sum(unique_path: &[PathItem], unique_depth: usize, path_index: u
 	total * (unique_depth + 1).to_f64().unwrap()
 }
 
+/// Add the change in the tree's expected output at each branch on the example's path to the contribution of the branch's feature, and return the tree's expected output. The contributions sum to the tree's output minus its expected output, like SHAP values, but do not account for the order in which features are split on.
+fn path_contributions(example: &[modelfox_table::TableValue], tree: &Tree, phi: &mut [f64]) -> f64 {
+	let mut expectations = vec![0.0; tree.nodes.len()];
+	compute_expectations(tree, 0, expectations.as_mut_slice());
+	let mut node_index = 0;
+	while let Node::Branch(node) = &tree.nodes[node_index] {
+		let (hot_child_index, _) = compute_hot_cold_child(node, example);
+		phi[node.split.feature_index()] += expectations[hot_child_index] - expectations[node_index];
+		node_index = hot_child_index;
+	}
+	expectations[0]
+}
+
 fn compute_hot_cold_child(
 	node: &BranchNode,
 	example: &[modelfox_table::TableValue],
@@ -310,3 +335,21 @@ fn compute_expectation(tree: &Tree, node_index: usize) -> f64 {
 		+ (right_child.examples_fraction() as f64 / current_node.examples_fraction as f64)
 			* right_value
 }
+
+/// Compute the expected output of every node in the subtree rooted at `node_index`, and return the expected output of that node.
+fn compute_expectations(tree: &Tree, node_index: usize, expectations: &mut [f64]) -> f64 {
+	let expectation = match &tree.nodes[node_index] {
+		Node::Leaf(node) => node.value,
+		Node::Branch(node) => {
+			let left_value = compute_expectations(tree, node.left_child_index, expectations);
+			let right_value = compute_expectations(tree, node.right_child_index, expectations);
+			let left_fraction = tree.nodes[node.left_child_index].examples_fraction() as f64
+				/ node.examples_fraction as f64;
+			let right_fraction = tree.nodes[node.right_child_index].examples_fraction() as f64
+				/ node.examples_fraction as f64;
+			left_fraction * left_value + right_fraction * right_value
+		}
+	};
+	expectations[node_index] = expectation;
+	expectation
+}
@@ -6,8 +6,9 @@ use crate::{
 	compute_binned_features::{
 		compute_binned_features_column_major, compute_binned_features_row_major,
 	},
-	compute_binning_instructions::compute_binning_instructions,
+	compute_binning_instructions::{compute_binning_instructions, BinningInstruction},
 	compute_feature_importances::compute_feature_importances,
+	interaction_constraints::InteractionConstraints,
 	multiclass_classifier::{MulticlassClassifier, MulticlassClassifierTrainOutput},
 	pool::Pool,
 	regressor::{Regressor, RegressorTrainOutput},
@@ -131,6 +132,18 @@ pub fn train(
 	#[cfg(feature = "timing")]
 	timing.compute_binned_features.inc(start.elapsed());
 
+	// The interaction constraints refer to features by their index in `features`, so map them to the indexes of the features used in training.
+	let interaction_constraints =
+		train_options
+			.interaction_constraints
+			.as_ref()
+			.map(|interaction_constraints| {
+				InteractionConstraints::new(
+					interaction_constraints,
+					&compute_binned_features_column_major_output.used_feature_indexes,
+				)
+			});
+
 	// Regression and binary classification train one tree per round. Multiclass classification trains one tree per class per round.
 	let n_trees_per_round = match task {
 		Task::Regression => 1,
@@ -307,6 +320,7 @@ pub fn train(
 				examples_index_right_buffer: examples_index_right_buffer.as_slice_mut().unwrap(),
 				bin_stats_pool: &bin_stats_pool,
 				hessians_are_constant,
+				interaction_constraints: interaction_constraints.as_ref(),
 				train_options,
 				#[cfg(feature = "timing")]
 				timing: &timing,
@@ -380,6 +394,15 @@ pub fn train(
 	// Compute the feature importances.
 	let feature_importances = Some(compute_feature_importances(&trees, n_features));
 
+	// Report the bin thresholds chosen for each feature.
+	let bin_thresholds = binning_instructions
+		.into_iter()
+		.map(|binning_instruction| match binning_instruction {
+			BinningInstruction::Number { thresholds } => Some(thresholds),
+			BinningInstruction::Enum { .. } => None,
+		})
+		.collect();
+
 	// Print out the timing and tree information if the timing feature is enabled.
 	#[cfg(feature = "timing")]
 	eprintln!("{:?}", timing);
@@ -403,6 +426,7 @@ pub fn train(
 				trees,
 			},
 			feature_importances,
+			bin_thresholds,
 			losses,
 		}),
 		Task::BinaryClassification => TrainOutput::BinaryClassifier(BinaryClassifierTrainOutput {
@@ -411,6 +435,7 @@ pub fn train(
 				trees,
 			},
 			feature_importances,
+			bin_thresholds,
 			losses,
 		}),
 		Task::MulticlassClassification { .. } => {
@@ -419,6 +444,7 @@ pub fn train(
 			TrainOutput::MulticlassClassifier(MulticlassClassifierTrainOutput {
 				model: MulticlassClassifier { biases, trees },
 				feature_importances,
+				bin_thresholds,
 				losses,
 			})
 		}
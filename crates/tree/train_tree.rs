@@ -6,6 +6,7 @@ use crate::{
 	compute_bin_stats::BinStats,
 	compute_binned_features::{BinnedFeaturesColumnMajor, BinnedFeaturesRowMajor},
 	compute_binning_instructions::BinningInstruction,
+	interaction_constraints::InteractionConstraints,
 	pool::{Pool, PoolItem},
 	rearrange_examples_index::rearrange_examples_index,
 	SplitDirection, TrainOptions,
@@ -105,6 +106,15 @@ pub enum TrainBranchSplit {
 	Discrete(TrainBranchSplitDiscrete),
 }
 
+impl TrainBranchSplit {
+	pub fn feature_index(&self) -> usize {
+		match self {
+			TrainBranchSplit::Continuous(split) => split.feature_index,
+			TrainBranchSplit::Discrete(split) => split.feature_index,
+		}
+	}
+}
+
 #[derive(Clone, Debug)]
 pub struct TrainBranchSplitContinuous {
 	pub feature_index: usize,
@@ -158,6 +168,8 @@ struct QueueItem {
 	pub right_sum_hessians: f64,
 	/// These are the features that are still splittable.
 	pub splittable_features: Vec<bool>,
+	/// If interaction constraints are enabled, these are the indexes of the groups that are consistent with every split on the path to this node, including its own. It is empty otherwise.
+	pub interaction_groups: Vec<usize>,
 }
 
 impl PartialEq for QueueItem {
@@ -193,6 +205,7 @@ pub struct TrainTreeOptions<'a> {
 	pub hessians_are_constant: bool,
 	pub hessians_ordered_buffer: &'a mut [f32],
 	pub hessians: &'a [f32],
+	pub interaction_constraints: Option<&'a InteractionConstraints>,
 	#[cfg(feature = "timing")]
 	pub timing: &'a crate::timing::Timing,
 	pub train_options: &'a TrainOptions,
@@ -213,6 +226,7 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 		hessians_are_constant,
 		hessians_ordered_buffer,
 		hessians,
+		interaction_constraints,
 		train_options,
 		..
 	} = options;
@@ -246,9 +260,19 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 	// If we were able to find a split for the root node, add it to the queue and proceed to the loop. Otherwise, return a tree with a single node.
 	match choose_best_split_output_root {
 		ChooseBestSplitOutput::Success(output) => {
+			// The root may split on a feature from any group.
+			let interaction_groups = interaction_constraints
+				.map(|interaction_constraints| {
+					interaction_constraints.groups_after_split(
+						&interaction_constraints.root_groups(),
+						output.split.feature_index(),
+					)
+				})
+				.unwrap_or_default();
 			add_queue_item(AddQueueItemOptions {
 				depth: 0,
 				examples_index_range: examples_index_range_root,
+				interaction_groups,
 				output,
 				parent_index: None,
 				queue: &mut queue,
@@ -341,6 +365,20 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 		#[cfg(feature = "timing")]
 		timing.rearrange_examples_index.inc(start.elapsed());
 
+		// If interaction constraints are enabled, the children may only split on features from the groups that remain after this branch's split.
+		let constrained_splittable_features =
+			interaction_constraints.map(|interaction_constraints| {
+				let mut splittable_features = queue_item.splittable_features.clone();
+				interaction_constraints.restrict_splittable_features(
+					&queue_item.interaction_groups,
+					&mut splittable_features,
+				);
+				splittable_features
+			});
+		let splittable_features = constrained_splittable_features
+			.as_deref()
+			.unwrap_or(&queue_item.splittable_features);
+
 		// Choose the best splits for each of the right and left children of this new branch.
 		#[cfg(feature = "timing")]
 		let start = std::time::Instant::now();
@@ -356,7 +394,7 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 				hessians_ordered_buffer,
 				hessians,
 				left_child_examples_index,
-				splittable_features: queue_item.splittable_features.as_slice(),
+				splittable_features,
 				left_child_n_examples: queue_item.left_n_examples,
 				left_child_sum_gradients: queue_item.left_sum_gradients,
 				left_child_sum_hessians: queue_item.left_sum_hessians,
@@ -376,9 +414,18 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 		// Add a queue item or leaf for the left child.
 		match left_child_best_split_output {
 			ChooseBestSplitOutput::Success(output) => {
+				let interaction_groups = interaction_constraints
+					.map(|interaction_constraints| {
+						interaction_constraints.groups_after_split(
+							&queue_item.interaction_groups,
+							output.split.feature_index(),
+						)
+					})
+					.unwrap_or_default();
 				add_queue_item(AddQueueItemOptions {
 					depth: queue_item.depth + 1,
 					examples_index_range: left_child_examples_index_range,
+					interaction_groups,
 					output,
 					parent_index: Some(node_index),
 					queue: &mut queue,
@@ -403,9 +450,18 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 		// Add a queue item or leaf for the right child.
 		match right_child_best_split_output {
 			ChooseBestSplitOutput::Success(output) => {
+				let interaction_groups = interaction_constraints
+					.map(|interaction_constraints| {
+						interaction_constraints.groups_after_split(
+							&queue_item.interaction_groups,
+							output.split.feature_index(),
+						)
+					})
+					.unwrap_or_default();
 				add_queue_item(AddQueueItemOptions {
 					depth: queue_item.depth + 1,
 					examples_index_range: right_child_examples_index_range,
+					interaction_groups,
 					output,
 					parent_index: Some(node_index),
 					queue: &mut queue,
@@ -449,6 +505,7 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 struct AddQueueItemOptions<'a> {
 	depth: usize,
 	examples_index_range: Range<usize>,
+	interaction_groups: Vec<usize>,
 	output: ChooseBestSplitSuccess,
 	parent_index: Option<usize>,
 	queue: &'a mut BinaryHeap<QueueItem>,
@@ -460,6 +517,7 @@ fn add_queue_item(options: AddQueueItemOptions) {
 	options.queue.push(QueueItem {
 		gain: options.output.gain,
 		splittable_features: options.output.splittable_features,
+		interaction_groups: options.interaction_groups,
 		parent_index: options.parent_index,
 		split_direction: options.split_direction,
 		depth: options.depth,
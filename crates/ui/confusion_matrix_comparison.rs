@@ -37,6 +37,78 @@ pub struct ConfusionMatrixComparisonValue {
 
 impl Component for ConfusionMatrixComparison {
 	fn into_node(self) -> Node {
+		ConfusionMatrixMultiComparison {
+			class_label: self.class_label,
+			columns: vec![
+				ConfusionMatrixComparisonColumn {
+					color: self.color_a,
+					title: self.value_a_title,
+					value: self.value_a,
+				},
+				ConfusionMatrixComparisonColumn {
+					color: self.color_b,
+					title: self.value_b_title,
+					value: self.value_b,
+				},
+			],
+		}
+		.into_node()
+	}
+}
+
+/// A confusion matrix comparison with one column per value in each cell, for example to compare three or more models side by side.
+#[derive(builder)]
+pub struct ConfusionMatrixMultiComparison {
+	pub class_label: String,
+	pub columns: Vec<ConfusionMatrixComparisonColumn>,
+}
+
+#[derive(Clone)]
+pub struct ConfusionMatrixComparisonColumn {
+	pub color: String,
+	pub title: String,
+	pub value: Option<ConfusionMatrixComparisonValue>,
+}
+
+impl ConfusionMatrixMultiComparison {
+	fn item(
+		&self,
+		area: &str,
+		correct: bool,
+		label: &str,
+		value: fn(&ConfusionMatrixComparisonValue) -> f32,
+	) -> ConfusionMatrixComparisonItem {
+		ConfusionMatrixComparisonItem {
+			area: area.to_owned(),
+			columns: self
+				.columns
+				.iter()
+				.map(|column| ConfusionMatrixComparisonItemColumn {
+					color: column.color.clone(),
+					title: column.title.clone(),
+					value: column.value.as_ref().map(value),
+				})
+				.collect(),
+			correct,
+			label: label.to_owned(),
+		}
+	}
+}
+
+impl Component for ConfusionMatrixMultiComparison {
+	fn into_node(self) -> Node {
+		let true_positive = self.item("true-positive", true, "True Positives", |value| {
+			value.true_positive
+		});
+		let false_positive = self.item("false-positive", false, "False Positives", |value| {
+			value.false_positive
+		});
+		let false_negative = self.item("false-negative", false, "False Negatives", |value| {
+			value.false_negative
+		});
+		let true_negative = self.item("true-negative", true, "True Negatives", |value| {
+			value.true_negative
+		});
 		div()
 			.class("confusion-matrix-comparison-wrapper")
 			.child(
@@ -61,64 +133,25 @@ impl Component for ConfusionMatrixComparison {
 					.child(div().child("Predicted Not"))
 					.child(ui::Token::new().child(self.class_label)),
 			)
-			.child(ConfusionMatrixComparisonItem {
-				area: "true-positive".to_owned(),
-				color_a: self.color_a.clone(),
-				color_b: self.color_b.clone(),
-				correct: true,
-				label: "True Positives".to_owned(),
-				value_a_title: self.value_a_title.clone(),
-				value_a: self.value_a.as_ref().map(|value| value.true_positive),
-				value_b_title: self.value_b_title.clone(),
-				value_b: self.value_b.as_ref().map(|value| value.true_positive),
-			})
-			.child(ConfusionMatrixComparisonItem {
-				area: "false-positive".to_owned(),
-				color_a: self.color_a.clone(),
-				color_b: self.color_b.clone(),
-				correct: false,
-				label: "False Positives".to_owned(),
-				value_a_title: self.value_a_title.clone(),
-				value_a: self.value_a.as_ref().map(|value| value.false_positive),
-				value_b_title: self.value_b_title.clone(),
-				value_b: self.value_b.as_ref().map(|value| value.false_positive),
-			})
-			.child(ConfusionMatrixComparisonItem {
-				area: "false-negative".to_owned(),
-				color_a: self.color_a.clone(),
-				color_b: self.color_b.clone(),
-				correct: false,
-				label: "False Negatives".to_owned(),
-				value_a_title: self.value_a_title.clone(),
-				value_a: self.value_a.as_ref().map(|value| value.false_negative),
-				value_b_title: self.value_b_title.clone(),
-				value_b: self.value_b.as_ref().map(|value| value.false_negative),
-			})
-			.child(ConfusionMatrixComparisonItem {
-				area: "true-negative".to_owned(),
-				color_a: self.color_a,
-				color_b: self.color_b,
-				correct: true,
-				label: "True Negatives".to_owned(),
-				value_a_title: self.value_a_title,
-				value_a: self.value_a.as_ref().map(|value| value.true_negative),
-				value_b_title: self.value_b_title,
-				value_b: self.value_b.as_ref().map(|value| value.true_negative),
-			})
+			.child(true_positive)
+			.child(false_positive)
+			.child(false_negative)
+			.child(true_negative)
 			.into_node()
 	}
 }
 
 pub struct ConfusionMatrixComparisonItem {
 	pub area: String,
-	pub color_a: String,
-	pub color_b: String,
+	pub columns: Vec<ConfusionMatrixComparisonItemColumn>,
 	pub correct: bool,
 	pub label: String,
-	pub value_a_title: String,
-	pub value_a: Option<f32>,
-	pub value_b_title: String,
-	pub value_b: Option<f32>,
+}
+
+pub struct ConfusionMatrixComparisonItemColumn {
+	pub color: String,
+	pub title: String,
+	pub value: Option<f32>,
 }
 
 impl Component for ConfusionMatrixComparisonItem {
@@ -128,8 +161,22 @@ impl Component for ConfusionMatrixComparisonItem {
 		} else {
 			"confusion-matrix-comparison-item-incorrect-wrapper"
 		};
-		let value_a = format_option_percent(self.value_a);
-		let value_b = format_option_percent(self.value_b);
+		let grid_template_columns = format!("repeat({}, auto)", self.columns.len());
+		let values = self
+			.columns
+			.iter()
+			.enumerate()
+			.map(|(index, column)| {
+				div()
+					.class("confusion-matrix-comparison-item-value")
+					.attribute("data-field", format!("value-{}", index))
+					.child(format_option_percent(column.value))
+			})
+			.collect::<Vec<_>>();
+		let titles = self
+			.columns
+			.into_iter()
+			.map(|column| div().child(ui::Token::new().color(column.color).child(column.title)));
 		div()
 			.class("confusion-matrix-comparison-item-wrapper")
 			.class(class)
@@ -143,32 +190,9 @@ impl Component for ConfusionMatrixComparisonItem {
 			.child(
 				div()
 					.class("confusion-matrix-comparison-number-comparison-wrapper")
-					.child(
-						div()
-							.class("confusion-matrix-comparison-item-value")
-							.attribute("data-field", "value-a")
-							.child(value_a),
-					)
-					.child(
-						div()
-							.class("confusion-matrix-comparison-item-value")
-							.attribute("data-field", "value-b")
-							.child(value_b),
-					)
-					.child(
-						div().child(
-							ui::Token::new()
-								.color(self.color_a)
-								.child(self.value_a_title),
-						),
-					)
-					.child(
-						div().child(
-							ui::Token::new()
-								.color(self.color_b)
-								.child(self.value_b_title),
-						),
-					),
+					.style(style::GRID_TEMPLATE_COLUMNS, grid_template_columns)
+					.children(values)
+					.children(titles),
 			)
 			.into_node()
 	}
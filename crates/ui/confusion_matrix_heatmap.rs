@@ -0,0 +1,197 @@
+use modelfox_number_formatter::PercentFormatter;
+use num::ToPrimitive;
+use pinwheel::prelude::*;
+
+/// A confusion matrix for any number of classes, rendered as a heatmap with one row for each predicted class and one column for each actual class. The normalization toggles are radio inputs styled with css, so switching between them does not require javascript.
+pub struct ConfusionMatrixHeatmap {
+	/// This id must be unique on the page. It names the normalization radio inputs.
+	pub id: String,
+	pub classes: Vec<String>,
+	/// The entry at `confusion_matrix[i][j]` is the number of examples of class `j` that were predicted as class `i`.
+	pub confusion_matrix: Vec<Vec<u64>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Normalization {
+	None,
+	Row,
+	Column,
+	Overall,
+}
+
+impl Normalization {
+	const ALL: [Normalization; 4] = [
+		Normalization::None,
+		Normalization::Row,
+		Normalization::Column,
+		Normalization::Overall,
+	];
+
+	fn value(self) -> &'static str {
+		match self {
+			Normalization::None => "none",
+			Normalization::Row => "row",
+			Normalization::Column => "column",
+			Normalization::Overall => "overall",
+		}
+	}
+
+	fn title(self) -> &'static str {
+		match self {
+			Normalization::None => "Counts",
+			Normalization::Row => "Normalize by Predicted",
+			Normalization::Column => "Normalize by Actual",
+			Normalization::Overall => "Normalize Overall",
+		}
+	}
+}
+
+impl Component for ConfusionMatrixHeatmap {
+	fn into_node(self) -> Node {
+		let n_classes = self.classes.len();
+		let row_totals = self
+			.confusion_matrix
+			.iter()
+			.map(|row| row.iter().sum::<u64>())
+			.collect::<Vec<_>>();
+		let column_totals = (0..n_classes)
+			.map(|column_index| {
+				self.confusion_matrix
+					.iter()
+					.map(|row| row[column_index])
+					.sum::<u64>()
+			})
+			.collect::<Vec<_>>();
+		let total = row_totals.iter().sum::<u64>();
+		let max = self
+			.confusion_matrix
+			.iter()
+			.flatten()
+			.cloned()
+			.max()
+			.unwrap_or(0);
+		let toggles = Normalization::ALL.iter().map(|normalization| {
+			let input_id = format!("{}-{}", self.id, normalization.value());
+			let input = input()
+				.class("confusion-matrix-heatmap-input")
+				.attribute("checked", *normalization == Normalization::None)
+				.attribute("id", input_id.clone())
+				.attribute("name", self.id.clone())
+				.attribute("type", "radio")
+				.attribute("value", normalization.value());
+			let label = label()
+				.class("confusion-matrix-heatmap-toggle")
+				.attribute("for", input_id)
+				.child(normalization.title());
+			fragment().child(input).child(label)
+		});
+		let header = div()
+			.class("confusion-matrix-heatmap-corner")
+			.child("Predicted \\ Actual");
+		let column_labels = self.classes.iter().map(|class| {
+			div()
+				.class("confusion-matrix-heatmap-label")
+				.attribute("title", class.clone())
+				.child(class.clone())
+		});
+		let rows = self
+			.confusion_matrix
+			.iter()
+			.enumerate()
+			.map(|(row_index, row)| {
+				let predicted = &self.classes[row_index];
+				let row_label = div()
+					.class("confusion-matrix-heatmap-label")
+					.class("confusion-matrix-heatmap-row-label")
+					.attribute("title", predicted.clone())
+					.child(predicted.clone());
+				let cells = row.iter().enumerate().map(|(column_index, value)| {
+					let actual = &self.classes[column_index];
+					let values = Normalization::ALL.iter().map(|normalization| {
+						let (fraction, intensity, description) = match normalization {
+							Normalization::None => (None, normalize(*value, max), "".to_owned()),
+							Normalization::Row => {
+								let fraction = normalize(*value, row_totals[row_index]);
+								(
+									Some(fraction),
+									fraction,
+									format!(" of examples predicted as {}", predicted),
+								)
+							}
+							Normalization::Column => {
+								let fraction = normalize(*value, column_totals[column_index]);
+								(
+									Some(fraction),
+									fraction,
+									format!(" of examples whose actual class is {}", actual),
+								)
+							}
+							Normalization::Overall => {
+								let fraction = normalize(*value, total);
+								(Some(fraction), fraction, " of all examples".to_owned())
+							}
+						};
+						let formatted_value = match fraction {
+							Some(fraction) => PercentFormatter::default().format(fraction),
+							None => value.to_string(),
+						};
+						let tooltip = format!(
+							"Predicted {}, Actual {}: {}{}",
+							predicted, actual, formatted_value, description
+						);
+						// Use light text on the darkest cells so the value stays readable.
+						let dark = if intensity > 0.5 {
+							Some("confusion-matrix-heatmap-value-dark")
+						} else {
+							None
+						};
+						div()
+							.class("confusion-matrix-heatmap-value")
+							.class(format!(
+								"confusion-matrix-heatmap-value-{}",
+								normalization.value()
+							))
+							.class(dark)
+							.style(
+								"--confusion-matrix-heatmap-intensity",
+								intensity.to_string(),
+							)
+							.attribute("title", tooltip)
+							.child(formatted_value)
+					});
+					let correct = if row_index == column_index {
+						Some("confusion-matrix-heatmap-cell-correct")
+					} else {
+						None
+					};
+					div()
+						.class("confusion-matrix-heatmap-cell")
+						.class(correct)
+						.children(values)
+				});
+				fragment().child(row_label).children(cells)
+			});
+		let grid = div()
+			.class("confusion-matrix-heatmap-grid")
+			.style(
+				style::GRID_TEMPLATE_COLUMNS,
+				format!("auto repeat({}, minmax(3.5rem, 1fr))", n_classes),
+			)
+			.child(header)
+			.children(column_labels)
+			.children(rows);
+		div()
+			.class("confusion-matrix-heatmap")
+			.children(toggles)
+			.child(grid)
+			.into_node()
+	}
+}
+
+fn normalize(value: u64, total: u64) -> f32 {
+	if total == 0 {
+		0.0
+	} else {
+		value.to_f32().unwrap() / total.to_f32().unwrap()
+	}
+}
@@ -0,0 +1,89 @@
+use pinwheel::prelude::*;
+
+/// A symmetric matrix of correlations between columns, rendered as a heatmap with one row and one column for each column. Positive correlations are shaded blue and negative correlations are shaded red, more strongly the closer they are to 1 or -1.
+pub struct CorrelationHeatmap {
+	pub column_names: Vec<String>,
+	/// The entry at `correlations[i][j]` is the correlation between column `i` and column `j`, or `None` if it was not computed.
+	pub correlations: Vec<Vec<Option<f32>>>,
+}
+
+impl Component for CorrelationHeatmap {
+	fn into_node(self) -> Node {
+		let n_columns = self.column_names.len();
+		let corner = div().class("correlation-heatmap-corner");
+		let column_labels = self.column_names.iter().map(|column_name| {
+			div()
+				.class("correlation-heatmap-label")
+				.attribute("title", column_name.clone())
+				.child(column_name.clone())
+		});
+		let rows = self
+			.correlations
+			.iter()
+			.enumerate()
+			.map(|(row_index, row)| {
+				let row_column_name = &self.column_names[row_index];
+				let row_label = div()
+					.class("correlation-heatmap-label")
+					.class("correlation-heatmap-row-label")
+					.attribute("title", row_column_name.clone())
+					.child(row_column_name.clone());
+				let cells = row.iter().enumerate().map(|(column_index, value)| {
+					let column_column_name = &self.column_names[column_index];
+					if row_index == column_index {
+						return div()
+							.class("correlation-heatmap-cell")
+							.class("correlation-heatmap-cell-diagonal");
+					}
+					let value = match value {
+						Some(value) => *value,
+						None => {
+							return div().class("correlation-heatmap-cell").attribute(
+								"title",
+								format!(
+									"{} and {}: not computed",
+									row_column_name, column_column_name
+								),
+							)
+						}
+					};
+					let sign = if value < 0.0 {
+						"correlation-heatmap-cell-negative"
+					} else {
+						"correlation-heatmap-cell-positive"
+					};
+					// Use light text on the darkest cells so the value stays readable.
+					let dark = if value.abs() > 0.5 {
+						Some("correlation-heatmap-cell-dark")
+					} else {
+						None
+					};
+					let formatted_value = format!("{:.2}", value);
+					div()
+						.class("correlation-heatmap-cell")
+						.class(sign)
+						.class(dark)
+						.style("--correlation-heatmap-intensity", value.abs().to_string())
+						.attribute(
+							"title",
+							format!(
+								"{} and {}: {}",
+								row_column_name, column_column_name, formatted_value
+							),
+						)
+						.child(formatted_value)
+				});
+				fragment().child(row_label).children(cells)
+			});
+		div()
+			.class("correlation-heatmap")
+			.style(
+				style::GRID_TEMPLATE_COLUMNS,
+				format!("auto repeat({}, minmax(3rem, 1fr))", n_columns),
+			)
+			.child(corner)
+			.children(column_labels)
+			.children(rows)
+			.into_node()
+	}
+}
@@ -1,8 +1,9 @@
 pub use self::{
 	alert::*, alert_icon::*, asciicast::*, avatar::*, button::*, callout::*, card::*, code::*,
-	confusion_matrix::*, confusion_matrix_comparison::*, details::*, form::*, image::*, layout::*,
-	link::*, markdown::*, nav::*, number_card::*, number_comparison_card::*, slider::*, tab_bar::*,
-	table::*, theme::*, token::*, topbar::*, window::*,
+	confusion_matrix::*, confusion_matrix_comparison::*, confusion_matrix_heatmap::*,
+	correlation_heatmap::*, details::*, form::*, image::*, layout::*, link::*, markdown::*, nav::*,
+	number_card::*, number_comparison_card::*, slider::*, tab_bar::*, table::*, theme::*, token::*,
+	topbar::*, window::*,
 };
 pub use indoc::{formatdoc, indoc as doc};
 pub use modelfox_number_formatter::*;
@@ -17,6 +18,8 @@ mod card;
 mod code;
 mod confusion_matrix;
 mod confusion_matrix_comparison;
+mod confusion_matrix_heatmap;
+mod correlation_heatmap;
 mod details;
 mod form;
 mod image;
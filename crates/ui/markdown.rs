@@ -1,12 +1,19 @@
 use crate as ui;
 use convert_case::Casing;
 use pinwheel::prelude::*;
-use pulldown_cmark::{escape::escape_html, Alignment, CodeBlockKind, Event, Options, Parser, Tag};
+use pulldown_cmark::{
+	escape::{escape_href, escape_html},
+	Alignment, CodeBlockKind, Event, Options, Parser, Tag,
+};
 use std::{borrow::Cow, fmt::Write};
 
 #[derive(builder, new)]
 pub struct Markdown {
 	string: Cow<'static, str>,
+	/// Set this when rendering markdown written by users. Raw html is escaped instead of passed through, footnotes are disabled, and links with a `javascript:` scheme are removed.
+	#[builder]
+	#[new(default)]
+	untrusted: Option<bool>,
 }
 
 enum State {
@@ -34,7 +41,12 @@ impl Component for Markdown {
 	fn into_node(self) -> Node {
 		let markdown = div().class("markdown");
 		let mut state = State::Ground;
-		let parser = Parser::new_ext(&self.string, Options::all());
+		let untrusted = self.untrusted.unwrap_or(false);
+		let mut options = Options::all();
+		if untrusted {
+			options.remove(Options::ENABLE_FOOTNOTES);
+		}
+		let parser = Parser::new_ext(&self.string, options);
 		let mut html = String::new();
 		for event in parser {
 			match event {
@@ -141,7 +153,18 @@ impl Component for Markdown {
 						html.push_str("<del>");
 					}
 					Tag::Link(_, href, _) => {
-						write!(&mut html, "<a href=\"{}\">", href).unwrap();
+						if untrusted {
+							let href = if href.trim().to_lowercase().starts_with("javascript:") {
+								""
+							} else {
+								&href
+							};
+							html.push_str("<a href=\"");
+							escape_href(&mut html, href).unwrap();
+							html.push_str("\">");
+						} else {
+							write!(&mut html, "<a href=\"{}\">", href).unwrap();
+						}
 					}
 					Tag::Image(_, src, alt) => {
 						let node = ui::Img::new()
@@ -164,11 +187,21 @@ impl Component for Markdown {
 								})
 							});
 							if let Some(id) = id {
-								write!(&mut html, " id=\"{}\"", id).unwrap();
+								if untrusted {
+									html.push_str(" id=\"");
+									escape_html(&mut html, &id).unwrap();
+									html.push('"');
+								} else {
+									write!(&mut html, " id=\"{}\"", id).unwrap();
+								}
 							}
 							write!(&mut html, ">").unwrap();
 							if let Some(heading) = heading {
-								write!(&mut html, "{}", heading).unwrap();
+								if untrusted {
+									escape_html(&mut html, heading).unwrap();
+								} else {
+									write!(&mut html, "{}", heading).unwrap();
+								}
 							}
 							write!(&mut html, "</h{}>", level).unwrap();
 							state = State::Ground;
@@ -259,7 +292,11 @@ impl Component for Markdown {
 					html.push_str(r#"</span>"#);
 				}
 				Event::Html(raw) => {
-					html.push_str(&raw);
+					if untrusted {
+						escape_html(&mut html, &raw).unwrap();
+					} else {
+						html.push_str(&raw);
+					}
 				}
 				Event::FootnoteReference(_reference) => unimplemented!(),
 				Event::SoftBreak => {
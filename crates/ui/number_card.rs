@@ -1,4 +1,5 @@
 use crate as ui;
+use modelfox_number_formatter::NumberFormatter;
 use pinwheel::prelude::*;
 
 #[derive(builder, new)]
@@ -7,6 +8,20 @@ pub struct NumberCard {
 	pub value: String,
 }
 
+impl NumberCard {
+	/// Create a card showing `value` formatted with `number_formatter`, or "N/A" if there is no value.
+	pub fn formatted(
+		title: String,
+		value: Option<f32>,
+		number_formatter: &NumberFormatter,
+	) -> NumberCard {
+		NumberCard {
+			title,
+			value: number_formatter.format_option(value),
+		}
+	}
+}
+
 impl Component for NumberCard {
 	fn into_node(self) -> Node {
 		ui::Card::new()
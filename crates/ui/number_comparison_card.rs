@@ -30,49 +30,113 @@ pub struct NumberComparisonCard {
 }
 
 impl Component for NumberComparisonCard {
+	fn into_node(self) -> Node {
+		NumberMultiComparisonCard {
+			columns: vec![
+				NumberComparisonCardColumn {
+					color: self.color_a,
+					title: self.value_a_title,
+					value: self.value_a,
+				},
+				NumberComparisonCardColumn {
+					color: self.color_b,
+					title: self.value_b_title,
+					value: self.value_b,
+				},
+			],
+			id: self.id,
+			number_formatter: self.number_formatter,
+			title: self.title,
+		}
+		.into_node()
+	}
+}
+
+/// A number comparison card with one column per value. With two columns, the difference between them is shown above the values. With more, the difference of each value from the first is shown below its title.
+#[derive(builder, new)]
+pub struct NumberMultiComparisonCard {
+	pub columns: Vec<NumberComparisonCardColumn>,
+	#[builder]
+	#[new(default)]
+	pub id: Option<String>,
+	#[builder]
+	#[new(default)]
+	pub number_formatter: NumberFormatter,
+	#[builder]
+	#[new(default)]
+	pub title: Option<String>,
+}
+
+#[derive(Clone, Default)]
+pub struct NumberComparisonCardColumn {
+	pub color: Option<String>,
+	pub title: Option<String>,
+	pub value: Option<f32>,
+}
+
+impl Component for NumberMultiComparisonCard {
 	fn into_node(self) -> Node {
 		let number_formatter = self.number_formatter;
 		let number_formatter_string = serde_json::to_string(&number_formatter).unwrap();
-		let difference_string = difference_string(self.value_a, self.value_b, &number_formatter);
+		let baseline = self.columns.first().and_then(|column| column.value);
+		let grid_template_columns = format!("repeat({}, auto)", self.columns.len());
+		let summary_difference = if self.columns.len() == 2 {
+			let value = self.columns[1].value;
+			Some(
+				div()
+					.class("number-comparison-card-difference")
+					.class(difference_class(baseline, value))
+					.child(difference_string(baseline, value, &number_formatter)),
+			)
+		} else {
+			None
+		};
+		let column_differences = if self.columns.len() > 2 {
+			self.columns
+				.iter()
+				.enumerate()
+				.map(|(index, column)| {
+					if index == 0 {
+						div().class("number-comparison-card-column-difference")
+					} else {
+						div()
+							.class("number-comparison-card-column-difference")
+							.class(difference_class(baseline, column.value))
+							.child(difference_string(baseline, column.value, &number_formatter))
+					}
+				})
+				.collect()
+		} else {
+			Vec::new()
+		};
+		let values = self
+			.columns
+			.iter()
+			.map(|column| {
+				div()
+					.class("number-comparison-card-value")
+					.child(number_formatter.format_option(column.value))
+			})
+			.collect::<Vec<_>>();
+		let titles = self.columns.into_iter().map(|column| {
+			div()
+				.class("number-comparison-card-value-title")
+				.child(ui::Token::new().color(column.color).child(column.title))
+		});
 		let content = div()
 			.class("number-comparison-card-wrapper")
 			.attribute("id", self.id)
 			.attribute("data-number-formatter", number_formatter_string)
+			.style(style::GRID_TEMPLATE_COLUMNS, grid_template_columns)
 			.child(
 				div()
 					.class("number-comparison-card-title")
 					.child(self.title),
 			)
-			.child(
-				div()
-					.class("number-comparison-card-difference")
-					.class(difference_class(self.value_a, self.value_b))
-					.child(difference_string),
-			)
-			.child(
-				div()
-					.class("number-comparison-card-value a")
-					.child(number_formatter.format_option(self.value_a)),
-			)
-			.child(
-				div()
-					.class("number-comparison-card-value b")
-					.child(number_formatter.format_option(self.value_b)),
-			)
-			.child(
-				div().class("number-comparison-card-value-title a").child(
-					ui::Token::new()
-						.color(self.color_a)
-						.child(self.value_a_title),
-				),
-			)
-			.child(
-				div().class("number-comparison-card-value-title b").child(
-					ui::Token::new()
-						.color(self.color_b)
-						.child(self.value_b_title),
-				),
-			);
+			.child(summary_difference)
+			.children(values)
+			.children(titles)
+			.children(column_differences);
 		ui::Card::new().child(content).into_node()
 	}
 }
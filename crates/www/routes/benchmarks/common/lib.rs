@@ -334,6 +334,7 @@ impl Component for DurationChart {
 					label: dataset.to_string(),
 					x: 0.0,
 					y: Some(benchmark_data(self.cpu, dataset, library).duration),
+					error: None,
 				}];
 				BarChartSeries {
 					color: color_for_library(library).to_owned(),
@@ -413,6 +414,7 @@ impl Component for MemoryChart {
 					label: dataset.to_string(),
 					x: 0.0,
 					y: Some(benchmark_data(self.cpu, dataset, library).memory),
+					error: None,
 				}];
 				BarChartSeries {
 					color: color_for_library(library).to_owned(),
@@ -492,6 +494,7 @@ impl Component for AucChart {
 					label: dataset.to_string(),
 					x: 0.0,
 					y: Some(benchmark_data(self.cpu, dataset, library).metric),
+					error: None,
 				}];
 				BarChartSeries {
 					color: color_for_library(library).to_owned(),
@@ -571,6 +574,7 @@ impl Component for MseChart {
 					label: dataset.to_string(),
 					x: 0.0,
 					y: Some(benchmark_data(self.cpu, dataset, library).metric),
+					error: None,
 				}];
 				BarChartSeries {
 					color: color_for_library(library).to_owned(),
@@ -353,6 +353,7 @@ impl Component for TuningCode {
 					let options = modelfox::PredictOptions {
 						threshold: Some(0.5),
 						compute_feature_contributions: Some(true),
+						class_thresholds: None,
 					};
 					let output = model.predict_one(input.clone(), Some(options.clone()));
 				"#
@@ -205,6 +205,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "age".into(),
@@ -213,6 +214,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "gender".into(),
@@ -221,6 +223,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: Some("High Invalid Values Count".into()),
 				name: "chest_pain".into(),
@@ -229,6 +232,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "resting_blood_pressure".into(),
@@ -237,6 +241,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "cholesterol".into(),
@@ -245,6 +250,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "fasting_blood_sugar_greater_than_120".into(),
@@ -253,6 +259,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "resting_ecg_result".into(),
@@ -261,6 +268,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "exercise_max_heart_rate".into(),
@@ -269,6 +277,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "exercise_induced_angina".into(),
@@ -277,6 +286,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "exercise_st_depression".into(),
@@ -285,6 +295,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "exercise_st_slope".into(),
@@ -293,6 +304,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "fluoroscopy_vessels_colored".into(),
@@ -301,6 +313,7 @@ impl Component for ProductionStats {
 			ColumnStatsTableRow {
 				absent_count: 0,
 				invalid_count: 0,
+				invalid_value_policy: "Treat as Missing".into(),
 				href: None,
 				alert: None,
 				name: "thallium_stress_test".into(),
@@ -18,21 +18,25 @@ impl Component for ProductionStats {
 						label: "asymptomatic".to_owned(),
 						x: 0.0,
 						y: Some(0.4752),
+						error: None,
 					},
 					BarChartPoint {
 						label: "atypical angina".to_owned(),
 						x: 1.0,
 						y: Some(0.165),
+						error: None,
 					},
 					BarChartPoint {
 						label: "non-angina pain".to_owned(),
 						x: 2.0,
 						y: Some(0.2838),
+						error: None,
 					},
 					BarChartPoint {
 						label: "typical angina".to_owned(),
 						x: 3.0,
 						y: Some(0.07591),
+						error: None,
 					},
 				],
 				title: Some("Training".to_owned()),
@@ -44,21 +48,25 @@ impl Component for ProductionStats {
 						label: "asymptomatic".to_owned(),
 						x: 0.0,
 						y: Some(0.0),
+						error: None,
 					},
 					BarChartPoint {
 						label: "atypical angina".to_owned(),
 						x: 1.0,
 						y: Some(0.1622),
+						error: None,
 					},
 					BarChartPoint {
 						label: "non-angina pain".to_owned(),
 						x: 2.0,
 						y: Some(0.2903),
+						error: None,
 					},
 					BarChartPoint {
 						label: "typical angina".to_owned(),
 						x: 3.0,
 						y: Some(0.07508),
+						error: None,
 					},
 				],
 				title: Some("Production".to_owned()),
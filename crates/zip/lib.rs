@@ -1,2 +1,5 @@
 mod pzip;
 mod zip;
+mod zip_eq;
+
+pub use self::zip_eq::{LengthMismatchError, ZipEq, ZipEqIterators};
@@ -0,0 +1,141 @@
+/// Zip iterators that must have the same length. Unlike `zip!`, which stops at the end of the shortest iterator, `zip_eq!` yields `Result`s and yields a `LengthMismatchError` if any of the iterators ends before the others. In debug builds, it panics instead.
+///
+/// ```
+/// use modelfox_zip::zip_eq;
+/// let names = ["a", "b", "c"];
+/// let values = [1, 2, 3];
+/// let pairs = zip_eq!(names, values).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(pairs, vec![("a", 1), ("b", 2), ("c", 3)]);
+/// ```
+#[macro_export]
+macro_rules! zip_eq {
+	($($e:expr),+$(,)*) => {
+		$crate::ZipEq::new(($(::std::iter::IntoIterator::into_iter($e),)+))
+	};
+}
+
+/// This is the iterator returned by `zip_eq!`.
+pub struct ZipEq<T> {
+	iterators: T,
+	index: usize,
+	done: bool,
+}
+
+impl<T> ZipEq<T> {
+	pub fn new(iterators: T) -> ZipEq<T> {
+		ZipEq {
+			iterators,
+			index: 0,
+			done: false,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthMismatchError {
+	/// This is the index of the first item that some of the iterators had and the others did not.
+	pub index: usize,
+}
+
+impl std::fmt::Display for LengthMismatchError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"the zipped iterators have different lengths, some of them ended after {} items",
+			self.index
+		)
+	}
+}
+
+impl std::error::Error for LengthMismatchError {}
+
+/// This trait is implemented for tuples of iterators so `ZipEq` can advance all of them at once.
+pub trait ZipEqIterators {
+	type Item;
+	/// Advance each of the iterators. This returns `Some(Some(item))` if they all had an item, `None` if they all ended, and `Some(None)` if some of them ended and some did not.
+	fn next(&mut self) -> Option<Option<Self::Item>>;
+}
+
+macro_rules! impl_zip_eq_iterators {
+	($($t:ident),+) => {
+		impl<$($t),+> ZipEqIterators for ($($t,)+)
+		where
+			$($t: Iterator,)+
+		{
+			type Item = ($($t::Item,)+);
+			#[allow(non_snake_case)]
+			fn next(&mut self) -> Option<Option<Self::Item>> {
+				let ($($t,)+) = self;
+				let ($($t,)+) = ($($t.next(),)+);
+				if $($t.is_some())&&+ {
+					Some(Some(($($t.unwrap(),)+)))
+				} else if $($t.is_none())&&+ {
+					None
+				} else {
+					Some(None)
+				}
+			}
+		}
+	};
+}
+
+impl_zip_eq_iterators!(A);
+impl_zip_eq_iterators!(A, B);
+impl_zip_eq_iterators!(A, B, C);
+impl_zip_eq_iterators!(A, B, C, D);
+impl_zip_eq_iterators!(A, B, C, D, E);
+impl_zip_eq_iterators!(A, B, C, D, E, F);
+impl_zip_eq_iterators!(A, B, C, D, E, F, G);
+impl_zip_eq_iterators!(A, B, C, D, E, F, G, H);
+
+impl<T> Iterator for ZipEq<T>
+where
+	T: ZipEqIterators,
+{
+	type Item = Result<T::Item, LengthMismatchError>;
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			return None;
+		}
+		match self.iterators.next() {
+			Some(Some(item)) => {
+				self.index += 1;
+				Some(Ok(item))
+			}
+			None => {
+				self.done = true;
+				None
+			}
+			Some(None) => {
+				self.done = true;
+				let error = LengthMismatchError { index: self.index };
+				if cfg!(debug_assertions) {
+					panic!("{}", error);
+				}
+				Some(Err(error))
+			}
+		}
+	}
+}
+
+#[test]
+fn test_zip_eq() {
+	let x = &[1, 2, 3];
+	let y = &[3, 4, 5];
+	let z = &[6, 7, 8];
+	assert_eq!(
+		zip_eq!(x, y, z).collect::<Result<Vec<_>, _>>(),
+		Ok(vec![(&1, &3, &6), (&2, &4, &7), (&3, &5, &8)])
+	);
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn test_zip_eq_length_mismatch() {
+	let x = &[1, 2, 3];
+	let y = &[3, 4];
+	assert_eq!(
+		zip_eq!(x, y).collect::<Result<Vec<_>, _>>(),
+		Err(LengthMismatchError { index: 2 })
+	);
+}
@@ -10,8 +10,9 @@ This crate implements the C API for libmodelfox, the ModelFox C library.
 
 use memmap::Mmap;
 use std::{
+	collections::BTreeMap,
 	ffi::CStr,
-	os::raw::{c_char, c_double, c_float, c_void},
+	os::raw::{c_char, c_double, c_float, c_int, c_void},
 	panic::{catch_unwind, UnwindSafe},
 	ptr::{null, null_mut},
 };
@@ -71,7 +72,10 @@ pub unsafe extern "C" fn modelfox_error_get_message(
 }
 
 // A `modelfox_model` value is an opaque handle to a model loaded by libmodelfox.
-pub struct modelfox_model(modelfox_core::predict::Model);
+pub struct modelfox_model(
+	modelfox_core::predict::Model,
+	modelfox_core::predict_cache::PredictCache,
+);
 
 /// Load a model from the file at `path`. On success, a pointer to the loaded model will be written to `model_ptr`. You must call `modelfox_model_delete` when you are done with it.
 #[no_mangle]
@@ -83,9 +87,11 @@ pub unsafe extern "C" fn modelfox_model_from_path(
 		let path = std::path::Path::new(CStr::from_ptr(path).to_str()?);
 		let file = std::fs::File::open(path)?;
 		let bytes = Mmap::map(&file)?;
-		let model = ::modelfox_model::from_bytes(&bytes)?;
-		let model = modelfox_core::predict::Model::from(model);
-		*model_ptr = Box::into_raw(Box::new(modelfox_model(model)));
+		let model = modelfox_core::predict::Model::from_bytes(&bytes)?;
+		*model_ptr = Box::into_raw(Box::new(modelfox_model(
+			model,
+			modelfox_core::predict_cache::PredictCache::new(0),
+		)));
 		Ok(())
 	})
 }
@@ -99,9 +105,11 @@ pub unsafe extern "C" fn modelfox_model_from_bytes(
 ) -> *mut modelfox_error {
 	handle_error(|| {
 		let bytes = std::slice::from_raw_parts(model_bytes as *const u8, model_bytes_len);
-		let model = ::modelfox_model::from_bytes(bytes)?;
-		let model = modelfox_core::predict::Model::from(model);
-		*model_ptr = Box::into_raw(Box::new(modelfox_model(model)));
+		let model = modelfox_core::predict::Model::from_bytes(bytes)?;
+		*model_ptr = Box::into_raw(Box::new(modelfox_model(
+			model,
+			modelfox_core::predict_cache::PredictCache::new(0),
+		)));
 		Ok(())
 	})
 }
@@ -121,6 +129,25 @@ pub unsafe extern "C" fn modelfox_model_get_id(
 	*id_ptr = (*model).0.id.as_str().into();
 }
 
+/// Determine whether the prediction and true value events for `identifier` should be logged when logging only a fraction `sample_rate` of events. The decision is a deterministic function of the identifier, and every ModelFox library makes the same decision for the same identifier, so the prediction and true value events for an identifier are either both logged or both dropped. Numeric identifiers should be formatted without a fractional part if they are whole, like `42`, and otherwise in their shortest round trip form, like `0.5`. On success, the decision will be written to `is_sampled_ptr`. `sample_rate` must be greater than 0 and at most 1.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_is_sampled(
+	identifier: *const c_char,
+	sample_rate: c_float,
+	is_sampled_ptr: *mut bool,
+) -> *mut modelfox_error {
+	handle_error(|| {
+		let identifier = CStr::from_ptr(identifier).to_str()?;
+		if !modelfox_core::sampling::is_valid_sample_rate(sample_rate) {
+			return Err(::anyhow::anyhow!(
+				"The sample rate must be greater than 0 and at most 1."
+			));
+		}
+		*is_sampled_ptr = modelfox_core::sampling::is_sampled(identifier, sample_rate);
+		Ok(())
+	})
+}
+
 /// A `modelfox_task` identifies the task a model performs, one of regression, binary classification, or multiclass classification.
 #[repr(C)]
 pub enum modelfox_task {
@@ -195,6 +222,29 @@ pub unsafe extern "C" fn modelfox_predict_input_set_value_number(
 	})
 }
 
+/// Create a new predict input from `json`, a JSON object whose values are strings, numbers, or nested objects. Nested objects are flattened into columns named by joining their keys with `separator`, which defaults to "." if it is null. If `max_depth` is not negative, objects nested more than `max_depth` levels deep are dropped. On success, the predict input will be written to `predict_input_ptr`. You must add it to a `modelfox_predict_input_vec` or call `modelfox_predict_input_delete` when you are done with it.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_predict_input_new_from_json(
+	json: *const c_char,
+	separator: *const c_char,
+	max_depth: c_int,
+	predict_input_ptr: *mut *const modelfox_predict_input,
+) -> *mut modelfox_error {
+	handle_error(|| {
+		let json = CStr::from_ptr(json).to_str()?;
+		let input: BTreeMap<String, modelfox_core::flatten::NestedPredictInputValue> =
+			serde_json::from_str(json)?;
+		let mut options = modelfox_core::flatten::FlattenOptions::default();
+		if !separator.is_null() {
+			options.separator = CStr::from_ptr(separator).to_str()?.to_owned();
+		}
+		options.max_depth = usize::try_from(max_depth).ok();
+		let predict_input = modelfox_core::flatten::flatten_predict_input(input, &options);
+		*predict_input_ptr = Box::into_raw(Box::new(modelfox_predict_input(predict_input)));
+		Ok(())
+	})
+}
+
 /// A `modelfox_predict_input_vec` is an opaque handle to a vec of predict inputs.
 pub struct modelfox_predict_input_vec(Vec<modelfox_core::predict::PredictInput>);
 
@@ -260,8 +310,25 @@ pub unsafe extern "C" fn modelfox_predict_options_set_compute_feature_contributi
 	(*predict_options).0.compute_feature_contributions = compute_feature_contributions;
 }
 
-/// A `modelfox_predict_output_vec` is an opaque handle to a vec of predict outputs.
-pub struct modelfox_predict_output_vec(Vec<modelfox_core::predict::PredictOutput>);
+/// Set the threshold for the class `class_name` of a multiclass classifier. The class is only predicted if its probability is at least `threshold`. If no class thresholds are set, the thresholds saved in the model when it was downloaded from the app are used.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_predict_options_set_class_threshold(
+	predict_options: *mut modelfox_predict_options,
+	class_name: *const c_char,
+	threshold: c_float,
+) -> *mut modelfox_error {
+	handle_error(|| {
+		let class_name = CStr::from_ptr(class_name).to_str()?.to_owned();
+		(*predict_options)
+			.0
+			.class_thresholds
+			.insert(class_name, threshold);
+		Ok(())
+	})
+}
+
+/// A `modelfox_predict_output_vec` is an opaque handle to a vec of predict outputs. Inputs that were dropped because of an invalid value have no output.
+pub struct modelfox_predict_output_vec(Vec<Option<modelfox_core::predict::PredictOutput>>);
 
 /// A `modelfox_predict_output` value is an opaque handle to the output of `modelfox_model_predict`.
 pub struct modelfox_predict_output(modelfox_core::predict::PredictOutput);
@@ -315,6 +382,18 @@ pub struct modelfox_word_embedding_feature_contribution(
 	modelfox_core::predict::WordEmbeddingFeatureContribution,
 );
 
+/// `modelfox_cyclical_feature_contribution` is an opaque handle to a single modelfox cyclical feature contribution.
+pub struct modelfox_cyclical_feature_contribution(
+	modelfox_core::predict::CyclicalFeatureContribution,
+);
+
+/// `modelfox_cyclical_component` identifies which of a cyclical feature group's two features a feature contribution is for.
+#[repr(C)]
+pub enum modelfox_cyclical_component {
+	SIN,
+	COS,
+}
+
 /// `modelfox_ngram` is an opaque handle to an ngram.
 pub struct modelfox_ngram(modelfox_core::predict::NGram);
 
@@ -332,6 +411,8 @@ pub struct modelfox_unigram(String);
 pub struct modelfox_bigram((String, String));
 
 /// Make a prediction! `model` should point to a model loaded with `modelfox_model_load`. `input` should be a `modelfox_predict_input` value and options should be a `modelfox_predict_options` value. On success, a pointer to a `modelfox_predict_output` output will be written to `output_ptr`. You must call `modelfox_predict_output_delete` when you are done with it.
+///
+/// The invalid value policy each column was trained with is enforced. An input with an invalid value in a column whose policy is to drop the row has no output, so `modelfox_predict_output_vec_get_at_index` writes null for it. An invalid value in a column whose policy is to error fails the whole call, and the returned error's message names the column and the value.
 #[no_mangle]
 pub unsafe extern "C" fn modelfox_model_predict(
 	model: *const modelfox_model,
@@ -340,16 +421,203 @@ pub unsafe extern "C" fn modelfox_model_predict(
 	output_ptr: *mut *const modelfox_predict_output_vec,
 ) -> *mut modelfox_error {
 	handle_error(|| {
-		let output = modelfox_core::predict::predict(
+		let output = modelfox_core::predict_cache::try_predict_with_cache(
 			&(*model).0,
+			&(*model).1,
 			&input.as_ref().unwrap().0,
 			&options.as_ref().unwrap().0,
-		);
+		)?;
 		*output_ptr = Box::into_raw(Box::new(modelfox_predict_output_vec(output)));
 		Ok(())
 	})
 }
 
+/// A `modelfox_validation_issue` is an opaque handle to an issue with one value of a predict input found by `modelfox_model_validate_input`.
+pub struct modelfox_validation_issue(modelfox_core::predict::ValidationIssue);
+
+/// A `modelfox_validation_issue_vec` is an opaque handle to a vec of validation issues.
+pub struct modelfox_validation_issue_vec(Vec<modelfox_core::predict::ValidationIssue>);
+
+/// A `modelfox_validation_issue_type` identifies the kind of a validation issue.
+#[repr(C)]
+pub enum modelfox_validation_issue_type {
+	MISSING_COLUMN,
+	UNKNOWN_COLUMN,
+	UNKNOWN_ENUM_VARIANT,
+	INVALID_NUMBER,
+	INVALID_DATE_TIME,
+}
+
+/// Validate `input` against the columns `model` was trained with, without making a prediction. On success, a pointer to a `modelfox_validation_issue_vec` will be written to `issues_ptr`, which is empty if the input has no issues. You must call `modelfox_validation_issue_vec_delete` when you are done with it.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_model_validate_input(
+	model: *const modelfox_model,
+	input: *const modelfox_predict_input,
+	issues_ptr: *mut *const modelfox_validation_issue_vec,
+) -> *mut modelfox_error {
+	handle_error(|| {
+		let issues = modelfox_core::predict::validate_input(&(*model).0, &(*input).0);
+		*issues_ptr = Box::into_raw(Box::new(modelfox_validation_issue_vec(issues)));
+		Ok(())
+	})
+}
+
+/// Delete a validation issue vec.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_validation_issue_vec_delete(
+	issues: *mut modelfox_validation_issue_vec,
+) {
+	drop(Box::from_raw(issues));
+}
+
+/// Retrieve the len of the validation issue vec.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_validation_issue_vec_len(
+	issues: *const modelfox_validation_issue_vec,
+	len_ptr: *mut size_t,
+) {
+	*len_ptr = (*issues).0.len();
+}
+
+/// Get the validation issue at `index`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_validation_issue_vec_get_at_index(
+	issues: *const modelfox_validation_issue_vec,
+	index: size_t,
+	issue_ptr: *mut *const modelfox_validation_issue,
+) {
+	let issue = (*issues).0.get(index).unwrap();
+	*issue_ptr =
+		issue as *const modelfox_core::predict::ValidationIssue as *const modelfox_validation_issue;
+}
+
+/// Retrieve the type of the validation issue.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_validation_issue_get_type(
+	issue: *const modelfox_validation_issue,
+	type_ptr: *mut modelfox_validation_issue_type,
+) {
+	*type_ptr = match (*issue).0.kind {
+		modelfox_core::predict::ValidationIssueKind::MissingColumn => {
+			modelfox_validation_issue_type::MISSING_COLUMN
+		}
+		modelfox_core::predict::ValidationIssueKind::UnknownColumn => {
+			modelfox_validation_issue_type::UNKNOWN_COLUMN
+		}
+		modelfox_core::predict::ValidationIssueKind::UnknownEnumVariant => {
+			modelfox_validation_issue_type::UNKNOWN_ENUM_VARIANT
+		}
+		modelfox_core::predict::ValidationIssueKind::InvalidNumber => {
+			modelfox_validation_issue_type::INVALID_NUMBER
+		}
+		modelfox_core::predict::ValidationIssueKind::InvalidDateTime => {
+			modelfox_validation_issue_type::INVALID_DATE_TIME
+		}
+	};
+}
+
+/// Retrieve the name of the column the validation issue is for.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_validation_issue_get_column_name(
+	issue: *const modelfox_validation_issue,
+	column_name_ptr: *mut modelfox_string_view,
+) {
+	*column_name_ptr = (*issue).0.column_name.as_str().into();
+}
+
+/// Retrieve a message describing the validation issue and what predicting with the input will do about it.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_validation_issue_get_message(
+	issue: *const modelfox_validation_issue,
+	message_ptr: *mut modelfox_string_view,
+) {
+	*message_ptr = (*issue).0.message.as_str().into();
+}
+
+/// A `modelfox_validation_issue_value_type` identifies the type of the value a validation issue is for. Issues for missing columns have no value.
+#[repr(C)]
+pub enum modelfox_validation_issue_value_type {
+	NONE,
+	NUMBER,
+	STRING,
+}
+
+/// Retrieve the type of the value the validation issue is for. Use `modelfox_validation_issue_get_value_number` or `modelfox_validation_issue_get_value_string` to retrieve the value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_validation_issue_get_value_type(
+	issue: *const modelfox_validation_issue,
+	type_ptr: *mut modelfox_validation_issue_value_type,
+) {
+	*type_ptr = match (*issue).0.value {
+		None => modelfox_validation_issue_value_type::NONE,
+		Some(modelfox_core::predict::PredictInputValue::Number(_)) => {
+			modelfox_validation_issue_value_type::NUMBER
+		}
+		Some(modelfox_core::predict::PredictInputValue::String(_)) => {
+			modelfox_validation_issue_value_type::STRING
+		}
+	};
+}
+
+/// Retrieve the value the validation issue is for if it is a number. If it is, the value will be written to `value_ptr` and this function will return true. Otherwise, nothing will be written and this function will return false.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_validation_issue_get_value_number(
+	issue: *const modelfox_validation_issue,
+	value_ptr: *mut c_double,
+) -> bool {
+	match (*issue).0.value {
+		Some(modelfox_core::predict::PredictInputValue::Number(value)) => {
+			*value_ptr = value;
+			true
+		}
+		_ => false,
+	}
+}
+
+/// Retrieve the value the validation issue is for if it is a string. If it is, the value will be written to `value_ptr` and this function will return true. Otherwise, nothing will be written and this function will return false.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_validation_issue_get_value_string(
+	issue: *const modelfox_validation_issue,
+	value_ptr: *mut modelfox_string_view,
+) -> bool {
+	match &(*issue).0.value {
+		Some(modelfox_core::predict::PredictInputValue::String(value)) => {
+			*value_ptr = value.as_str().into();
+			true
+		}
+		_ => false,
+	}
+}
+
+/// Enable a cache of the `capacity` most recently used predictions for `model`, keyed on a hash of the input and options. Repeated identical predictions are then served from the cache instead of running the model. A capacity of zero, the default, disables the cache. Changing the capacity clears the cache.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_model_set_predict_cache_capacity(
+	model: *mut modelfox_model,
+	capacity: size_t,
+) {
+	(*model).1 = modelfox_core::predict_cache::PredictCache::new(capacity);
+}
+
+/// Retrieve statistics about `model`'s predict cache. The number of cached predictions will be written to `len_ptr`, and the number of cache hits and misses since the cache was enabled will be written to `hits_ptr` and `misses_ptr`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_model_get_predict_cache_stats(
+	model: *const modelfox_model,
+	len_ptr: *mut size_t,
+	hits_ptr: *mut u64,
+	misses_ptr: *mut u64,
+) {
+	let stats = (*model).1.stats();
+	*len_ptr = stats.len;
+	*hits_ptr = stats.hits;
+	*misses_ptr = stats.misses;
+}
+
+/// Remove all predictions from `model`'s predict cache and reset its statistics.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_model_clear_predict_cache(model: *const modelfox_model) {
+	(*model).1.clear();
+}
+
 /// Delete a predict output.
 #[no_mangle]
 pub unsafe extern "C" fn modelfox_predict_output_delete(
@@ -375,16 +643,20 @@ pub unsafe extern "C" fn modelfox_predict_output_vec_len(
 	*len_ptr = (*predict_output_vec).0.len();
 }
 
-/// Get the predict output at `index`.
+/// Get the predict output at `index`. If the input at `index` was dropped because of an invalid value, null will be written to `predict_output_ptr`.
 #[no_mangle]
 pub unsafe extern "C" fn modelfox_predict_output_vec_get_at_index(
 	predict_output_vec: *mut modelfox_predict_output_vec,
 	index: size_t,
 	predict_output_ptr: *mut *const modelfox_predict_output,
 ) {
-	let predict_output = (*predict_output_vec).0.get(index).unwrap();
-	*predict_output_ptr = predict_output as *const modelfox_core::predict::PredictOutput
-		as *const modelfox_predict_output;
+	*predict_output_ptr = match (*predict_output_vec).0.get(index).unwrap() {
+		Some(predict_output) => {
+			predict_output as *const modelfox_core::predict::PredictOutput
+				as *const modelfox_predict_output
+		}
+		None => null(),
+	};
 }
 
 /// Cast the predict output as `modelfox_regression_predict_output`. If this predict output is not for regression, null will be written to `regression_predict_output_ptr`.
@@ -674,6 +946,7 @@ pub enum modelfox_feature_contribution_entry_type {
 	BAG_OF_WORDS,
 	BAG_OF_WORDS_COSINE_SIMILARITY,
 	WORD_EMBEDDING,
+	CYCLICAL,
 }
 
 /// Retrieve the type of the feature contribution entry.
@@ -701,6 +974,9 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_get_type(
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => {
 			modelfox_feature_contribution_entry_type::WORD_EMBEDDING
 		}
+		modelfox_core::predict::FeatureContributionEntry::Cyclical(_) => {
+			modelfox_feature_contribution_entry_type::CYCLICAL
+		}
 	}
 }
 
@@ -720,6 +996,7 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_identity(
 		modelfox_core::predict::FeatureContributionEntry::BagOfWords(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::BagOfWordsCosineSimilarity(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Cyclical(_) => null(),
 	};
 }
 
@@ -739,6 +1016,7 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_normalized(
 		modelfox_core::predict::FeatureContributionEntry::BagOfWords(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::BagOfWordsCosineSimilarity(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Cyclical(_) => null(),
 	};
 }
 
@@ -758,6 +1036,7 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_one_hot_encoded(
 		modelfox_core::predict::FeatureContributionEntry::BagOfWords(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::BagOfWordsCosineSimilarity(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Cyclical(_) => null(),
 	};
 }
 
@@ -777,6 +1056,7 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_bag_of_words(
 		}
 		modelfox_core::predict::FeatureContributionEntry::BagOfWordsCosineSimilarity(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Cyclical(_) => null(),
 	};
 }
 
@@ -796,6 +1076,7 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_bag_of_words_cos
 				as *const modelfox_bag_of_words_cosine_similarity_feature_contribution
 		}
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Cyclical(_) => null(),
 	};
 }
 
@@ -815,6 +1096,27 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_word_embedding(
 			f as *const modelfox_core::predict::WordEmbeddingFeatureContribution
 				as *const modelfox_word_embedding_feature_contribution
 		}
+		modelfox_core::predict::FeatureContributionEntry::Cyclical(_) => null(),
+	};
+}
+
+/// Cast the feature contribution entry as `modelfox_cyclical_feature_contribution`. If this feature contribution is not a cyclical feature contribution, null will be written to `feature_contribution_ouput_ptr`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_cyclical(
+	feature_contribution_entry: *const modelfox_feature_contribution_entry,
+	feature_contribution_ptr: *mut *const modelfox_cyclical_feature_contribution,
+) {
+	*feature_contribution_ptr = match &(*feature_contribution_entry).0 {
+		modelfox_core::predict::FeatureContributionEntry::Identity(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Normalized(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::OneHotEncoded(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::BagOfWords(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::BagOfWordsCosineSimilarity(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Cyclical(f) => {
+			f as *const modelfox_core::predict::CyclicalFeatureContribution
+				as *const modelfox_cyclical_feature_contribution
+		}
 	};
 }
 
@@ -1059,6 +1361,45 @@ pub unsafe extern "C" fn modelfox_word_embedding_feature_contribution_get_featur
 	*feature_contribution_value = (*feature_contribution).0.feature_contribution_value;
 }
 
+/// Retrieve the column name.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_cyclical_feature_contribution_get_column_name(
+	feature_contribution: *const modelfox_cyclical_feature_contribution,
+	column_name_ptr: *mut modelfox_string_view,
+) {
+	*column_name_ptr = (*feature_contribution).0.column_name.as_str().into();
+}
+
+/// Retrieve the component.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_cyclical_feature_contribution_get_component(
+	feature_contribution: *const modelfox_cyclical_feature_contribution,
+	component_ptr: *mut modelfox_cyclical_component,
+) {
+	*component_ptr = match (*feature_contribution).0.component {
+		modelfox_core::predict::CyclicalComponent::Sin => modelfox_cyclical_component::SIN,
+		modelfox_core::predict::CyclicalComponent::Cos => modelfox_cyclical_component::COS,
+	};
+}
+
+/// Retrieve the feature value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_cyclical_feature_contribution_get_feature_value(
+	feature_contribution: *const modelfox_cyclical_feature_contribution,
+	feature_value: *mut c_float,
+) {
+	*feature_value = (*feature_contribution).0.feature_value;
+}
+
+/// Retrieve the feature contribution value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_cyclical_feature_contribution_get_feature_contribution_value(
+	feature_contribution: *const modelfox_cyclical_feature_contribution,
+	feature_contribution_value: *mut c_float,
+) {
+	*feature_contribution_value = (*feature_contribution).0.feature_contribution_value;
+}
+
 /// This function exposes the allocator used by libmodelfox. It is used by the wasm build of libmodelfox because WebAssembly does not include its own allocator.
 #[no_mangle]
 pub unsafe extern "C" fn modelfox_alloc(size: size_t, align: size_t) -> *mut c_void {
@@ -1090,3 +1431,94 @@ where
 		})),
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use modelfox_core::test_common::{
+		tfidf_prediction_reviews, tfidf_training_features, train_tfidf_model,
+	};
+	use std::ffi::CString;
+
+	#[test]
+	fn test_tfidf_features_match_training() {
+		let model = train_tfidf_model();
+		let reviews = tfidf_prediction_reviews();
+		let expected = tfidf_training_features(&model, &reviews);
+		let bytes = model.to_bytes();
+		unsafe {
+			let mut model = null();
+			let error =
+				modelfox_model_from_bytes(bytes.as_ptr() as *const c_void, bytes.len(), &mut model);
+			assert!(error.is_null());
+			let mut input_vec = null();
+			modelfox_predict_input_vec_new(&mut input_vec);
+			let column_name = CString::new("review").unwrap();
+			for review in reviews.iter() {
+				let mut input = null();
+				modelfox_predict_input_new(&mut input);
+				let value = CString::new(review.as_str()).unwrap();
+				let error = modelfox_predict_input_set_value_string(
+					input as *mut _,
+					column_name.as_ptr(),
+					value.as_ptr(),
+				);
+				assert!(error.is_null());
+				modelfox_predict_input_vec_push(input_vec as *mut _, input as *mut _);
+			}
+			let mut options = null();
+			modelfox_predict_options_new(&mut options);
+			modelfox_predict_options_set_compute_feature_contributions(options as *mut _, true);
+			let mut output_vec = null();
+			let error = modelfox_model_predict(model, input_vec, options, &mut output_vec);
+			assert!(error.is_null());
+			let mut len = 0;
+			modelfox_predict_output_vec_len(output_vec, &mut len);
+			assert_eq!(len, reviews.len());
+			for (index, expected) in expected.iter().enumerate() {
+				let mut output = null();
+				modelfox_predict_output_vec_get_at_index(output_vec as *mut _, index, &mut output);
+				let mut output_ptr = null();
+				modelfox_predict_output_as_binary_classification(output, &mut output_ptr);
+				let mut feature_contributions = null();
+				modelfox_binary_classification_predict_output_get_feature_contributions(
+					output_ptr,
+					&mut feature_contributions,
+				);
+				let mut entries_len = 0;
+				modelfox_feature_contributions_get_entries_len(
+					feature_contributions,
+					&mut entries_len,
+				);
+				let mut features = Vec::new();
+				for entry_index in 0..entries_len {
+					let mut entry = null();
+					modelfox_feature_contributions_get_entry_at_index(
+						feature_contributions,
+						entry_index,
+						&mut entry,
+					);
+					let mut feature_contribution = null();
+					modelfox_feature_contribution_entry_as_bag_of_words(
+						entry,
+						&mut feature_contribution,
+					);
+					let mut feature_value = 0.0;
+					modelfox_bag_of_words_feature_contribution_get_feature_value(
+						feature_contribution,
+						&mut feature_value,
+					);
+					features.push(feature_value);
+				}
+				assert_eq!(features.len(), expected.len());
+				for (value, expected_value) in features.iter().zip(expected.iter()) {
+					assert!((value - expected_value).abs() < f32::EPSILON);
+				}
+			}
+			modelfox_predict_output_vec_delete(output_vec as *mut _);
+			modelfox_predict_options_delete(options as *mut _);
+			modelfox_predict_input_vec_delete(input_vec as *mut _);
+			modelfox_model_delete(model as *mut _);
+		}
+	}
+}
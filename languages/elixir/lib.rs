@@ -5,12 +5,25 @@ use std::collections::BTreeMap;
 
 erl_nif::init!(
 	name: "Elixir.ModelFox",
-	funcs: [_load_model_from_path, _load_model_from_binary, _model_id, _predict],
+	funcs: [
+		_load_model_from_path,
+		_load_model_from_binary,
+		_model_id,
+		_predict,
+		_validate_input,
+		_predict_cache_stats,
+		_clear_predict_cache,
+		_is_sampled,
+	],
 	load: load,
 );
 
-static MODEL_RESOURCE_TYPE: OnceCell<erl_nif::ResourceType<modelfox_core::predict::Model>> =
-	OnceCell::new();
+static MODEL_RESOURCE_TYPE: OnceCell<erl_nif::ResourceType<Model>> = OnceCell::new();
+
+struct Model {
+	model: modelfox_core::predict::Model,
+	predict_cache: modelfox_core::predict_cache::PredictCache,
+}
 
 fn load<'a>(env: erl_nif::Env<'a>, _load_info: erl_nif::Term<'a>) -> Result<()> {
 	let model_resource_type = erl_nif::ResourceType::new(env, "Model")?;
@@ -25,11 +38,15 @@ fn load<'a>(env: erl_nif::Env<'a>, _load_info: erl_nif::Term<'a>) -> Result<()>
 fn _load_model_from_path<'a>(
 	env: erl_nif::Env<'a>,
 	path: Option<String>,
+	options: Option<LoadModelOptions>,
 ) -> Result<erl_nif::Term<'a>> {
 	let file = std::fs::File::open(path.unwrap())?;
 	let bytes = unsafe { Mmap::map(&file)? };
-	let model = modelfox_model::from_bytes(&bytes)?;
-	let model = modelfox_core::predict::Model::from(model);
+	let model = modelfox_core::predict::Model::from_bytes(&bytes)?;
+	let model = Model {
+		model,
+		predict_cache: options.unwrap_or_default().predict_cache(),
+	};
 	let resource_type = MODEL_RESOURCE_TYPE.get().unwrap();
 	let model = erl_nif::Resource::new(*resource_type, model);
 	let model = erl_nif::ResourceTerm::new(env, model)?;
@@ -40,10 +57,14 @@ fn _load_model_from_path<'a>(
 fn _load_model_from_binary<'a>(
 	env: erl_nif::Env<'a>,
 	binary: erl_nif::BinaryTerm<'a>,
+	options: Option<LoadModelOptions>,
 ) -> Result<erl_nif::Term<'a>> {
 	let bytes = binary.get()?;
-	let model = modelfox_model::from_bytes(bytes)?;
-	let model = modelfox_core::predict::Model::from(model);
+	let model = modelfox_core::predict::Model::from_bytes(bytes)?;
+	let model = Model {
+		model,
+		predict_cache: options.unwrap_or_default().predict_cache(),
+	};
 	let resource_type = MODEL_RESOURCE_TYPE.get().unwrap();
 	let model = erl_nif::Resource::new(*resource_type, model);
 	let model = erl_nif::ResourceTerm::new(env, model)?;
@@ -57,7 +78,7 @@ fn _model_id<'a>(env: erl_nif::Env<'a>, model: erl_nif::Term<'a>) -> Result<Stri
 		.expect("failed to get model resource type");
 	let model = model.as_resource(*resource_type)?;
 	let model = model.get()?;
-	let id = model.id.clone();
+	let id = model.model.id.clone();
 	Ok(id)
 }
 
@@ -73,26 +94,227 @@ fn _predict<'a>(
 		.expect("failed to get model resource type");
 	let model = model.as_resource(*resource_type)?;
 	let model = model.get()?;
+	let flatten_options = options
+		.as_ref()
+		.map(PredictOptions::flatten_options)
+		.unwrap_or_default();
 	let options = options.map(Into::into).unwrap_or_default();
 	match input {
 		PredictInputSingleOrMultiple::Single(input) => {
-			let input = input.into();
-			let mut output = modelfox_core::predict::predict(model, &[input], &options);
+			let input = input.flatten(&flatten_options);
+			let mut output = modelfox_core::predict_cache::try_predict_with_cache(
+				&model.model,
+				&model.predict_cache,
+				&[input],
+				&options,
+			)?;
 			let output = output.remove(0);
-			let output = output.into();
+			let output = output.map(Into::into);
 			let output = PredictOutputSingleOrMultiple::Single(output);
 			Ok(output)
 		}
 		PredictInputSingleOrMultiple::Multiple(input) => {
-			let input = input.into_iter().map(Into::into).collect::<Vec<_>>();
-			let output = modelfox_core::predict::predict(model, &input, &options);
-			let output = output.into_iter().map(Into::into).collect();
+			let input = input
+				.into_iter()
+				.map(|input| input.flatten(&flatten_options))
+				.collect::<Vec<_>>();
+			let output = modelfox_core::predict_cache::try_predict_with_cache(
+				&model.model,
+				&model.predict_cache,
+				&input,
+				&options,
+			)?;
+			let output = output
+				.into_iter()
+				.map(|output| output.map(Into::into))
+				.collect();
 			let output = PredictOutputSingleOrMultiple::Multiple(output);
 			Ok(output)
 		}
 	}
 }
 
+#[erl_nif::nif]
+fn _validate_input<'a>(
+	env: erl_nif::Env<'a>,
+	model: erl_nif::Term<'a>,
+	input: PredictInput,
+	options: Option<PredictOptions>,
+) -> Result<ValidationIssues> {
+	let resource_type = MODEL_RESOURCE_TYPE
+		.get()
+		.expect("failed to get model resource type");
+	let model = model.as_resource(*resource_type)?;
+	let model = model.get()?;
+	let flatten_options = options
+		.as_ref()
+		.map(PredictOptions::flatten_options)
+		.unwrap_or_default();
+	let input = input.flatten(&flatten_options);
+	let issues = modelfox_core::predict::validate_input(&model.model, &input)
+		.into_iter()
+		.map(Into::into)
+		.collect();
+	Ok(ValidationIssues(issues))
+}
+
+#[erl_nif::nif]
+fn _predict_cache_stats<'a>(
+	env: erl_nif::Env<'a>,
+	model: erl_nif::Term<'a>,
+) -> Result<PredictCacheStats> {
+	let resource_type = MODEL_RESOURCE_TYPE
+		.get()
+		.expect("failed to get model resource type");
+	let model = model.as_resource(*resource_type)?;
+	let model = model.get()?;
+	Ok(model.predict_cache.stats().into())
+}
+
+#[erl_nif::nif]
+fn _clear_predict_cache<'a>(env: erl_nif::Env<'a>, model: erl_nif::Term<'a>) -> Result<()> {
+	let resource_type = MODEL_RESOURCE_TYPE
+		.get()
+		.expect("failed to get model resource type");
+	let model = model.as_resource(*resource_type)?;
+	let model = model.get()?;
+	model.predict_cache.clear();
+	Ok(())
+}
+
+#[erl_nif::nif]
+fn _is_sampled<'a>(env: erl_nif::Env<'a>, identifier: String, sample_rate: f64) -> Result<bool> {
+	Ok(modelfox_core::sampling::is_sampled(
+		&identifier,
+		sample_rate as f32,
+	))
+}
+
+#[derive(Default, serde::Deserialize)]
+struct LoadModelOptions {
+	pub predict_cache_capacity: Option<usize>,
+}
+
+impl LoadModelOptions {
+	fn predict_cache(&self) -> modelfox_core::predict_cache::PredictCache {
+		modelfox_core::predict_cache::PredictCache::new(self.predict_cache_capacity.unwrap_or(0))
+	}
+}
+
+impl<'a> erl_nif::FromErlNif<'a> for LoadModelOptions {
+	fn from_erl_nif(term: erl_nif::Term) -> erl_nif::Result<Self> {
+		serde::Deserialize::deserialize(term)
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename = "Elixir.ModelFox.PredictCacheStats")]
+struct PredictCacheStats {
+	capacity: usize,
+	len: usize,
+	hits: u64,
+	misses: u64,
+}
+
+impl From<modelfox_core::predict_cache::PredictCacheStats> for PredictCacheStats {
+	fn from(value: modelfox_core::predict_cache::PredictCacheStats) -> PredictCacheStats {
+		PredictCacheStats {
+			capacity: value.capacity,
+			len: value.len,
+			hits: value.hits,
+			misses: value.misses,
+		}
+	}
+}
+
+impl<'a> erl_nif::IntoErlNif<'a> for PredictCacheStats {
+	fn into_erl_nif(self, env: erl_nif::Env<'a>) -> erl_nif::Result<erl_nif::Term<'a>> {
+		serde::Serialize::serialize(&self, env)
+	}
+}
+
+#[derive(serde::Serialize)]
+struct ValidationIssues(Vec<ValidationIssue>);
+
+impl<'a> erl_nif::IntoErlNif<'a> for ValidationIssues {
+	fn into_erl_nif(self, env: erl_nif::Env<'a>) -> erl_nif::Result<erl_nif::Term<'a>> {
+		serde::Serialize::serialize(&self, env)
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename = "Elixir.ModelFox.ValidationIssue")]
+struct ValidationIssue {
+	#[serde(rename = "type")]
+	kind: ValidationIssueType,
+	column_name: String,
+	value: Option<ValidationIssueValue>,
+	message: String,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ValidationIssueType {
+	MissingColumn,
+	UnknownColumn,
+	UnknownEnumVariant,
+	InvalidNumber,
+	InvalidDateTime,
+}
+
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+enum ValidationIssueValue {
+	Number(f64),
+	String(String),
+}
+
+impl From<modelfox_core::predict::ValidationIssue> for ValidationIssue {
+	fn from(value: modelfox_core::predict::ValidationIssue) -> ValidationIssue {
+		ValidationIssue {
+			kind: value.kind.into(),
+			column_name: value.column_name,
+			value: value.value.map(Into::into),
+			message: value.message,
+		}
+	}
+}
+
+impl From<modelfox_core::predict::ValidationIssueKind> for ValidationIssueType {
+	fn from(value: modelfox_core::predict::ValidationIssueKind) -> ValidationIssueType {
+		match value {
+			modelfox_core::predict::ValidationIssueKind::MissingColumn => {
+				ValidationIssueType::MissingColumn
+			}
+			modelfox_core::predict::ValidationIssueKind::UnknownColumn => {
+				ValidationIssueType::UnknownColumn
+			}
+			modelfox_core::predict::ValidationIssueKind::UnknownEnumVariant => {
+				ValidationIssueType::UnknownEnumVariant
+			}
+			modelfox_core::predict::ValidationIssueKind::InvalidNumber => {
+				ValidationIssueType::InvalidNumber
+			}
+			modelfox_core::predict::ValidationIssueKind::InvalidDateTime => {
+				ValidationIssueType::InvalidDateTime
+			}
+		}
+	}
+}
+
+impl From<modelfox_core::predict::PredictInputValue> for ValidationIssueValue {
+	fn from(value: modelfox_core::predict::PredictInputValue) -> ValidationIssueValue {
+		match value {
+			modelfox_core::predict::PredictInputValue::Number(value) => {
+				ValidationIssueValue::Number(value)
+			}
+			modelfox_core::predict::PredictInputValue::String(value) => {
+				ValidationIssueValue::String(value)
+			}
+		}
+	}
+}
+
 #[derive(serde::Deserialize)]
 #[serde(untagged)]
 enum PredictInputSingleOrMultiple {
@@ -109,17 +331,25 @@ impl<'a> erl_nif::FromErlNif<'a> for PredictInputSingleOrMultiple {
 #[derive(serde::Deserialize)]
 struct PredictInput(pub BTreeMap<String, PredictInputValue>);
 
+impl<'a> erl_nif::FromErlNif<'a> for PredictInput {
+	fn from_erl_nif(term: erl_nif::Term) -> erl_nif::Result<Self> {
+		serde::Deserialize::deserialize(term)
+	}
+}
+
 type PredictInputMultiple = Vec<PredictInput>;
 
-impl From<PredictInput> for modelfox_core::predict::PredictInput {
-	fn from(value: PredictInput) -> modelfox_core::predict::PredictInput {
-		modelfox_core::predict::PredictInput(
-			value
-				.0
-				.into_iter()
-				.map(|(key, value)| (key, value.into()))
-				.collect(),
-		)
+impl PredictInput {
+	fn flatten(
+		self,
+		options: &modelfox_core::flatten::FlattenOptions,
+	) -> modelfox_core::predict::PredictInput {
+		let input = self
+			.0
+			.into_iter()
+			.map(|(key, value)| (key, value.into()))
+			.collect();
+		modelfox_core::flatten::flatten_predict_input(input, options)
 	}
 }
 
@@ -128,16 +358,25 @@ impl From<PredictInput> for modelfox_core::predict::PredictInput {
 enum PredictInputValue {
 	Number(f64),
 	String(String),
+	Object(BTreeMap<String, PredictInputValue>),
 }
 
-impl From<PredictInputValue> for modelfox_core::predict::PredictInputValue {
-	fn from(value: PredictInputValue) -> modelfox_core::predict::PredictInputValue {
+impl From<PredictInputValue> for modelfox_core::flatten::NestedPredictInputValue {
+	fn from(value: PredictInputValue) -> modelfox_core::flatten::NestedPredictInputValue {
 		match value {
 			PredictInputValue::Number(value) => {
-				modelfox_core::predict::PredictInputValue::Number(value)
+				modelfox_core::flatten::NestedPredictInputValue::Number(value)
 			}
 			PredictInputValue::String(value) => {
-				modelfox_core::predict::PredictInputValue::String(value)
+				modelfox_core::flatten::NestedPredictInputValue::String(value)
+			}
+			PredictInputValue::Object(value) => {
+				modelfox_core::flatten::NestedPredictInputValue::Object(
+					value
+						.into_iter()
+						.map(|(key, value)| (key, value.into()))
+						.collect(),
+				)
 			}
 		}
 	}
@@ -147,6 +386,20 @@ impl From<PredictInputValue> for modelfox_core::predict::PredictInputValue {
 struct PredictOptions {
 	pub threshold: Option<f32>,
 	pub compute_feature_contributions: Option<bool>,
+	pub flatten_separator: Option<String>,
+	pub flatten_max_depth: Option<usize>,
+	pub class_thresholds: Option<BTreeMap<String, f32>>,
+}
+
+impl PredictOptions {
+	fn flatten_options(&self) -> modelfox_core::flatten::FlattenOptions {
+		let mut options = modelfox_core::flatten::FlattenOptions::default();
+		if let Some(flatten_separator) = &self.flatten_separator {
+			options.separator = flatten_separator.clone();
+		}
+		options.max_depth = self.flatten_max_depth;
+		options
+	}
 }
 
 impl<'a> erl_nif::FromErlNif<'a> for PredictOptions {
@@ -164,6 +417,9 @@ impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 		if let Some(compute_feature_contributions) = value.compute_feature_contributions {
 			options.compute_feature_contributions = compute_feature_contributions;
 		}
+		if let Some(class_thresholds) = value.class_thresholds {
+			options.class_thresholds = class_thresholds;
+		}
 		options
 	}
 }
@@ -171,7 +427,7 @@ impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 #[derive(serde::Serialize)]
 #[serde(untagged)]
 enum PredictOutputSingleOrMultiple {
-	Single(PredictOutput),
+	Single(Option<PredictOutput>),
 	Multiple(PredictOutputMultiple),
 }
 
@@ -189,7 +445,7 @@ enum PredictOutput {
 	MulticlassClassification(MulticlassClassificationPredictOutput),
 }
 
-type PredictOutputMultiple = Vec<PredictOutput>;
+type PredictOutputMultiple = Vec<Option<PredictOutput>>;
 
 impl From<modelfox_core::predict::PredictOutput> for PredictOutput {
 	fn from(value: modelfox_core::predict::PredictOutput) -> Self {
@@ -302,6 +558,8 @@ enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "cyclical")]
+	Cyclical(CyclicalFeatureContribution),
 }
 
 impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContributionEntry {
@@ -325,6 +583,9 @@ impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContribut
 			modelfox_core::predict::FeatureContributionEntry::WordEmbedding(value) => {
 				FeatureContributionEntry::WordEmbedding(value.into())
 			}
+			modelfox_core::predict::FeatureContributionEntry::Cyclical(value) => {
+				FeatureContributionEntry::Cyclical(value.into())
+			}
 		}
 	}
 }
@@ -464,3 +725,23 @@ impl From<modelfox_core::predict::WordEmbeddingFeatureContribution>
 		}
 	}
 }
+
+#[derive(serde::Serialize)]
+#[serde(rename = "Elixir.ModelFox.CyclicalFeatureContribution")]
+struct CyclicalFeatureContribution {
+	column_name: String,
+	component: String,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::CyclicalFeatureContribution> for CyclicalFeatureContribution {
+	fn from(value: modelfox_core::predict::CyclicalFeatureContribution) -> Self {
+		CyclicalFeatureContribution {
+			column_name: value.column_name,
+			component: value.component.to_string(),
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
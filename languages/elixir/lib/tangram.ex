@@ -10,12 +10,14 @@ defmodule ModelFox do
     @type t :: %__MODULE__{
             model: reference,
             log_queue: [ModelFox.event()],
-            modelfox_url: String.t()
+            modelfox_url: String.t(),
+            sample_rate: float
           }
     defstruct [
       :model,
       :log_queue,
-      :modelfox_url
+      :modelfox_url,
+      :sample_rate
     ]
   end
 
@@ -25,19 +27,94 @@ defmodule ModelFox do
 
     ## `modelfox_url`
     If you are running the app locally or on your own server, use this field to provide the url to it. If not specified, the default value is https://app.modelfox.dev.
+
+    ## `sample_rate`
+    If your service makes too many predictions to log every one, use this field to log only a fraction of prediction and true value events. The value must be greater than 0 and at most 1. Whether an event is logged is determined by its identifier, so the true value for a logged prediction is logged too. If not specified, every event is logged.
+
+    ## `predict_cache_capacity`
+    If your service makes many identical predictions, use this field to cache the outputs of this many of the most recently used inputs. Repeated predictions with the same input and options are then returned from the cache without running the model. The cache is disabled by default.
+    """
+    @type t :: %__MODULE__{
+            modelfox_url: String.t(),
+            sample_rate: float | nil,
+            predict_cache_capacity: non_neg_integer | nil
+          }
+    defstruct [
+      :modelfox_url,
+      :sample_rate,
+      :predict_cache_capacity
+    ]
+  end
+
+  defmodule PredictCacheStats do
+    @moduledoc """
+    These are the statistics returned by `ModelFox.predict_cache_stats`.
+
+    ## `capacity`
+    This is the maximum number of predictions the cache holds.
+
+    ## `len`
+    This is the number of predictions currently in the cache.
+
+    ## `hits`
+    This is the number of predictions served from the cache.
+
+    ## `misses`
+    This is the number of predictions that were not in the cache.
+    """
+    @type t :: %__MODULE__{
+            capacity: non_neg_integer,
+            len: non_neg_integer,
+            hits: non_neg_integer,
+            misses: non_neg_integer
+          }
+    defstruct [
+      :capacity,
+      :len,
+      :hits,
+      :misses
+    ]
+  end
+
+  defmodule ValidationIssue do
+    @moduledoc """
+    `ModelFox.validate_input` returns a `ValidationIssue` for each problem it finds with an input.
+
+    ## `type`
+    This is the kind of issue.
+
+    ## `column_name`
+    This is the name of the column the issue is for.
+
+    ## `value`
+    This is the value the issue is for, either a string or a float. It is `nil` for a missing column.
+
+    ## `message`
+    This is a message that describes the issue and what predicting with the input will do about it.
     """
     @type t :: %__MODULE__{
-            modelfox_url: String.t()
+            type:
+              :missing_column
+              | :unknown_column
+              | :unknown_enum_variant
+              | :invalid_number
+              | :invalid_date_time,
+            column_name: String.t(),
+            value: String.t() | float | nil,
+            message: String.t()
           }
     defstruct [
-      :modelfox_url
+      :type,
+      :column_name,
+      :value,
+      :message
     ]
   end
 
   @typedoc """
-  This is the input type of `ModelFox.predict`. A predict input is a map from atoms or strings to strings or floats. The keys should match the columns in the CSV file you trained your model with.
+  This is the input type of `ModelFox.predict`. A predict input is a map from atoms or strings to strings, floats, or nested predict inputs. Nested maps are flattened into columns as described in `ModelFox.PredictOptions`. The keys should match the columns in the CSV file you trained your model with.
   """
-  @type predict_input :: %{(atom | String.t()) => String.t() | float}
+  @type predict_input :: %{(atom | String.t()) => String.t() | float | predict_input}
 
   defmodule PredictOptions do
     @moduledoc """
@@ -48,15 +125,30 @@ defmodule ModelFox do
 
     ## `compute_feature_contributions`
     Computing feature contributions is disabled by default. If you set this field to `true`, you will be able to access the feature contributions with the `feature_contributions` field of the predict output.
+
+    ## `flatten_separator`
+    Nested maps in the input are flattened into columns named by joining their keys with this separator, so `%{user: %{age: 3}}` sets the column `user.age`. The default value is `"."`.
+
+    ## `flatten_max_depth`
+    This is the number of levels of nested maps to flatten. Maps nested more deeply are dropped. If it is `nil`, every level is flattened.
+
+    ## `class_thresholds`
+    If your model is a multiclass classifier, use this field to set a probability threshold for some classes, like the ones saved on the tuning page of the app. A class with a threshold is only predicted if its probability is at least the threshold. Classes without a threshold can always be predicted, so if no class with a threshold qualifies, the most probable class without one is predicted. If every class has a threshold and none qualifies, the most probable class is predicted. If you do not set this field, the thresholds saved in the model when it was downloaded from the app are used.
     """
     @type t :: %__MODULE__{
             threshold: float,
-            compute_feature_contributions: boolean
+            compute_feature_contributions: boolean,
+            flatten_separator: String.t(),
+            flatten_max_depth: non_neg_integer | nil,
+            class_thresholds: %{String.t() => float} | nil
           }
     @derive Jason.Encoder
     defstruct [
       threshold: 0.5,
-      compute_feature_contributions: false
+      compute_feature_contributions: false,
+      flatten_separator: ".",
+      flatten_max_depth: nil,
+      class_thresholds: nil
     ]
   end
 
@@ -414,7 +506,8 @@ defmodule ModelFox do
             identifier: String.t(),
             input: ModelFox.predict_input(),
             options: PredictOptions.t() | nil,
-            output: ModelFox.predict_output()
+            output: ModelFox.predict_output(),
+            sample_rate: float
           }
     @derive Jason.Encoder
     defstruct [
@@ -424,7 +517,8 @@ defmodule ModelFox do
       :identifier,
       :input,
       :options,
-      :output
+      :output,
+      :sample_rate
     ]
   end
 
@@ -436,6 +530,7 @@ defmodule ModelFox do
             model_id: String.t(),
             date: String.t(),
             identifier: String.t(),
+            sample_rate: float,
             true_value: ModelFox.true_value()
           }
     @derive Jason.Encoder
@@ -444,6 +539,7 @@ defmodule ModelFox do
       :model_id,
       :date,
       :identifier,
+      :sample_rate,
       :true_value
     ]
   end
@@ -488,13 +584,14 @@ defmodule ModelFox do
   """
   @spec load_model_from_path(String.t(), LoadModelOptions | nil) :: Model.t()
   def load_model_from_path(path, options \\ nil) do
-    model = _load_model_from_path(path)
+    model = _load_model_from_path(path, options)
     modelfox_url = if options, do: options.modelfox_url, else: "https://app.modelfox.dev"
 
     %Model{
       model: model,
       log_queue: [],
-      modelfox_url: modelfox_url
+      modelfox_url: modelfox_url,
+      sample_rate: sample_rate(options)
     }
   end
 
@@ -503,13 +600,14 @@ defmodule ModelFox do
   """
   @spec load_model_from_binary(String.t(), LoadModelOptions | nil) :: Model.t()
   def load_model_from_binary(binary, options \\ nil) do
-    model = _load_model_from_binary(binary)
+    model = _load_model_from_binary(binary, options)
     modelfox_url = if options, do: options.modelfox_url, else: "https://app.modelfox.dev"
 
     %Model{
       model: model,
       log_queue: [],
-      modelfox_url: modelfox_url
+      modelfox_url: modelfox_url,
+      sample_rate: sample_rate(options)
     }
   end
 
@@ -523,20 +621,52 @@ defmodule ModelFox do
 
   @doc """
   Make a prediction!
+
+  The invalid value policy each column was trained with is enforced. An input with an invalid value in a column whose policy is to drop the row gets an output of `nil`, and an invalid value in a column whose policy is to error raises an error naming the column and the value.
   """
   @spec predict(Model.t(), ModelFox.predict_input(), PredictOptions.t() | nil) ::
-          ModelFox.predict_output()
+          ModelFox.predict_output() | nil
   def predict(model, input, options \\ nil) do
     _predict(model.model, input, options)
   end
 
+  @doc """
+  Check an input against the columns the model was trained with, without making a prediction. This returns one `ModelFox.ValidationIssue` for each problem with the input, or an empty list if the input is valid.
+  """
+  @spec validate_input(Model.t(), ModelFox.predict_input(), PredictOptions.t() | nil) ::
+          [ValidationIssue.t()]
+  def validate_input(model, input, options \\ nil) do
+    _validate_input(model.model, input, options)
+  end
+
+  @doc """
+  Retrieve statistics about the predict cache enabled with the `predict_cache_capacity` load model option.
+  """
+  @spec predict_cache_stats(Model.t()) :: PredictCacheStats.t()
+  def predict_cache_stats(model) do
+    _predict_cache_stats(model.model)
+  end
+
+  @doc """
+  Remove all predictions from the predict cache and reset its statistics.
+  """
+  @spec clear_predict_cache(Model.t()) :: :ok
+  def clear_predict_cache(model) do
+    _clear_predict_cache(model.model)
+    :ok
+  end
+
   @doc """
   Send a prediction event to the app. If you want to batch events, you can use `ModelFox.enqueue_log_prediction` instead.
   """
   @spec log_prediction(Model.t(), LogPredictionArgs.t()) :: {:ok, any} | {:error, any}
   def log_prediction(model, args) do
-    event = prediction_event(model, args)
-    log_events(model.modelfox_url, [event])
+    if sampled?(model, args.identifier) do
+      event = prediction_event(model, args)
+      log_events(model.modelfox_url, [event])
+    else
+      {:ok, nil}
+    end
   end
 
   @doc """
@@ -544,8 +674,12 @@ defmodule ModelFox do
   """
   @spec enqueue_log_prediction(Model.t(), LogPredictionArgs.t()) :: Model.t()
   def enqueue_log_prediction(model, args) do
-    event = prediction_event(model, args)
-    %{model | log_queue: model.log_queue ++ [event]}
+    if sampled?(model, args.identifier) do
+      event = prediction_event(model, args)
+      %{model | log_queue: model.log_queue ++ [event]}
+    else
+      model
+    end
   end
 
   @doc """
@@ -553,8 +687,12 @@ defmodule ModelFox do
   """
   @spec log_true_value(Model.t(), LogTrueValueArgs.t()) :: {:ok, any} | {:error, any}
   def log_true_value(model, args) do
-    event = true_value_event(model, args)
-    log_events(model.modelfox_url, [event])
+    if sampled?(model, args.identifier) do
+      event = true_value_event(model, args)
+      log_events(model.modelfox_url, [event])
+    else
+      {:ok, nil}
+    end
   end
 
   @doc """
@@ -562,8 +700,12 @@ defmodule ModelFox do
   """
   @spec enqueue_log_true_value(Model.t(), LogTrueValueArgs.t()) :: Model.t()
   def enqueue_log_true_value(model, args) do
-    event = true_value_event(model, args)
-    %{model | log_queue: model.log_queue ++ [event]}
+    if sampled?(model, args.identifier) do
+      event = true_value_event(model, args)
+      %{model | log_queue: model.log_queue ++ [event]}
+    else
+      model
+    end
   end
 
   @doc """
@@ -575,6 +717,23 @@ defmodule ModelFox do
     %{model | log_queue: []}
   end
 
+  @spec sample_rate(LoadModelOptions.t() | nil) :: float
+  defp sample_rate(options) do
+    sample_rate = if options && options.sample_rate, do: options.sample_rate / 1, else: 1.0
+
+    unless sample_rate > 0 and sample_rate <= 1 do
+      raise ArgumentError, "The sample rate must be greater than 0 and at most 1."
+    end
+
+    sample_rate
+  end
+
+  # The decision is made by the native library, so every ModelFox library makes the same decision for the same identifier.
+  @spec sampled?(Model.t(), String.t()) :: boolean
+  defp sampled?(model, identifier) do
+    model.sample_rate >= 1 or _is_sampled(to_string(identifier), model.sample_rate)
+  end
+
   @spec log_events(String.t(), [ModelFox.event()]) :: {:ok, any} | {:error, any}
   defp log_events(modelfox_url, events) do
     url = modelfox_url <> "/track"
@@ -594,6 +753,7 @@ defmodule ModelFox do
       model_id: model_id,
       options: args.options,
       output: args.output,
+      sample_rate: model.sample_rate,
       type: :prediction
     }
   end
@@ -606,16 +766,17 @@ defmodule ModelFox do
       date: DateTime.utc_now() |> DateTime.to_iso8601(),
       identifier: args.identifier,
       model_id: model_id,
+      sample_rate: model.sample_rate,
       true_value: args.true_value,
       type: :true_value
     }
   end
 
-  defp _load_model_from_path(_) do
+  defp _load_model_from_path(_, _) do
     :erlang.nif_error(:nif_not_loaded)
   end
 
-  defp _load_model_from_binary(_) do
+  defp _load_model_from_binary(_, _) do
     :erlang.nif_error(:nif_not_loaded)
   end
 
@@ -626,4 +787,20 @@ defmodule ModelFox do
   defp _predict(_, _, _) do
     :erlang.nif_error(:nif_not_loaded)
   end
+
+  defp _validate_input(_, _, _) do
+    :erlang.nif_error(:nif_not_loaded)
+  end
+
+  defp _predict_cache_stats(_) do
+    :erlang.nif_error(:nif_not_loaded)
+  end
+
+  defp _clear_predict_cache(_) do
+    :erlang.nif_error(:nif_not_loaded)
+  end
+
+  defp _is_sampled(_, _) do
+    :erlang.nif_error(:nif_not_loaded)
+  end
 end
@@ -18,6 +18,7 @@ import (
 	"bytes"
 	"encoding/json"
 	"errors"
+	"hash/fnv"
 	"io/ioutil"
 	"log"
 	"net/http"
@@ -37,6 +38,22 @@ type Model struct {
 type LoadModelOptions struct {
 	// If you are running the app locally or on your own server, use this field to provide the url to it. If not specified, the default value is https://app.modelfox.dev.
 	ModelFoxURL string
+	// If your service makes too many predictions to log every one, use this field to log only a fraction of prediction and true value events. The value must be greater than 0 and at most 1. Whether an event is logged is determined by its identifier, so the true value for a logged prediction is logged too. If not specified, every event is logged.
+	SampleRate float32
+	// If your service makes many identical predictions, use this field to cache the outputs of this many of the most recently used inputs. Repeated predictions with the same input and options are then returned from the cache without running the model. The cache is disabled by default.
+	PredictCacheCapacity int
+}
+
+// These are the statistics returned by `PredictCacheStats`.
+type PredictCacheStats struct {
+	// The maximum number of predictions the cache holds.
+	Capacity int
+	// The number of predictions currently in the cache.
+	Len int
+	// The number of predictions served from the cache.
+	Hits uint64
+	// The number of predictions that were not in the cache.
+	Misses uint64
 }
 
 // These are the options passed to `Predict`.
@@ -45,9 +62,15 @@ type PredictOptions struct {
 	Threshold float32 `json:"threshold"`
 	// Computing feature contributions is disabled by default. If you set this field to `true`, you will be able to access the feature contributions with the `feature_contributions` field of the predict output.
 	ComputeFeatureContributions bool `json:"computeFeatureContributions"`
+	// Nested maps in the input are flattened into columns named by joining their keys with this separator, so `{"user": {"age": 3}}` sets the column `user.age`. The default value is `"."`.
+	FlattenSeparator string `json:"flattenSeparator,omitempty"`
+	// This is the number of levels of nested maps to flatten. Maps nested more deeply are dropped. If it is `0`, every level is flattened.
+	FlattenMaxDepth int `json:"flattenMaxDepth,omitempty"`
+	// If your model is a multiclass classifier, use this field to set a probability threshold for some classes, like the ones saved on the tuning page of the app. A class with a threshold is only predicted if its probability is at least the threshold. Classes without a threshold can always be predicted, so if no class with a threshold qualifies, the most probable class without one is predicted. If every class has a threshold and none qualifies, the most probable class is predicted. If you do not set this field, the thresholds saved in the model when it was downloaded from the app are used.
+	ClassThresholds map[string]float32 `json:"classThresholds,omitempty"`
 }
 
-// This is the input type of `Predict`. A predict input is a map from strings to strings or floats. The keys should match the columns in the CSV file you trained your model with.
+// This is the input type of `Predict`. A predict input is a map from strings to strings, floats, or nested predict inputs. The keys should match the columns in the CSV file you trained your model with.
 type PredictInput map[string]interface{}
 
 // TaskType is the type of the task corresponding to the model task, one of RegressionTaskType, BinaryClassificationTaskType, and MulticlassClassificationTaskType.
@@ -59,6 +82,29 @@ const (
 	MulticlassClassificationTaskType
 )
 
+// ValidationIssueType is the kind of a validation issue, one of MissingColumnValidationIssueType, UnknownColumnValidationIssueType, UnknownEnumVariantValidationIssueType, InvalidNumberValidationIssueType, and InvalidDateTimeValidationIssueType.
+type ValidationIssueType int
+
+const (
+	MissingColumnValidationIssueType ValidationIssueType = iota
+	UnknownColumnValidationIssueType
+	UnknownEnumVariantValidationIssueType
+	InvalidNumberValidationIssueType
+	InvalidDateTimeValidationIssueType
+)
+
+// `ValidateInput` returns a `ValidationIssue` for each problem it finds with an input.
+type ValidationIssue struct {
+	// This is the kind of issue.
+	Type ValidationIssueType
+	// This is the name of the column the issue is for.
+	ColumnName string
+	// This is the value the issue is for, either a string or a float64. It is nil for a missing column.
+	Value interface{}
+	// This is a message that describes the issue and what predicting with the input will do about it.
+	Message string
+}
+
 // This is the return type of `Predict`.
 type PredictOutput interface {
 	isPredictOutput()
@@ -268,6 +314,13 @@ func LoadModelFromPath(path string, options *LoadModelOptions) (*Model, error) {
 		errs := C.GoStringN(sv.ptr, C.int(sv.len))
 		return nil, errors.New(errs)
 	}
+	if err := validateSampleRate(options); err != nil {
+		C.modelfox_model_delete(cModel)
+		return nil, err
+	}
+	if options != nil && options.PredictCacheCapacity > 0 {
+		C.modelfox_model_set_predict_cache_capacity(cModel, C.size_t(options.PredictCacheCapacity))
+	}
 	queue := []event{}
 	model := Model{
 		cModel,
@@ -290,6 +343,13 @@ func LoadModelFromBytes(data []byte, options *LoadModelOptions) (*Model, error)
 		errs := C.GoStringN(sv.ptr, C.int(sv.len))
 		return nil, errors.New(errs)
 	}
+	if err := validateSampleRate(options); err != nil {
+		C.modelfox_model_delete(cModel)
+		return nil, err
+	}
+	if options != nil && options.PredictCacheCapacity > 0 {
+		C.modelfox_model_set_predict_cache_capacity(cModel, C.size_t(options.PredictCacheCapacity))
+	}
 	queue := []event{}
 	model := Model{
 		cModel,
@@ -312,17 +372,43 @@ func (m Model) ID() string {
 	return id
 }
 
-func newPredictInputVec(inputVec []PredictInput) *C.modelfox_predict_input_vec {
+// Retrieve statistics about the predict cache enabled with the `PredictCacheCapacity` load model option.
+func (m Model) PredictCacheStats() PredictCacheStats {
+	var len C.size_t
+	var hits C.uint64_t
+	var misses C.uint64_t
+	C.modelfox_model_get_predict_cache_stats(m.modelPtr, &len, &hits, &misses)
+	capacity := 0
+	if m.options != nil && m.options.PredictCacheCapacity > 0 {
+		capacity = m.options.PredictCacheCapacity
+	}
+	return PredictCacheStats{
+		Capacity: capacity,
+		Len:      int(len),
+		Hits:     uint64(hits),
+		Misses:   uint64(misses),
+	}
+}
+
+// Remove all predictions from the predict cache and reset its statistics.
+func (m Model) ClearPredictCache() {
+	C.modelfox_model_clear_predict_cache(m.modelPtr)
+}
+
+func newPredictInputVec(inputVec []PredictInput, options *PredictOptions) *C.modelfox_predict_input_vec {
 	var cInputVec *C.modelfox_predict_input_vec
 	C.modelfox_predict_input_vec_new(&cInputVec)
 	for i := 0; i < len(inputVec); i++ {
-		cInput := newPredictInput(inputVec[i])
+		cInput := newPredictInput(inputVec[i], options)
 		C.modelfox_predict_input_vec_push(cInputVec, cInput)
 	}
 	return cInputVec
 }
 
-func newPredictInput(input PredictInput) *C.modelfox_predict_input {
+func newPredictInput(input PredictInput, options *PredictOptions) *C.modelfox_predict_input {
+	if hasNestedValue(input) {
+		return newPredictInputFromJSON(input, options)
+	}
 	var cInput *C.modelfox_predict_input
 	C.modelfox_predict_input_new(&cInput)
 	var cKey *C.char
@@ -362,6 +448,61 @@ func newPredictInput(input PredictInput) *C.modelfox_predict_input {
 	return cInput
 }
 
+func hasNestedValue(input PredictInput) bool {
+	for _, value := range input {
+		switch value.(type) {
+		case PredictInput, map[string]interface{}:
+			return true
+		}
+	}
+	return false
+}
+
+// Create a predict input from an input with nested maps, which libmodelfox flattens into columns.
+func newPredictInputFromJSON(input PredictInput, options *PredictOptions) *C.modelfox_predict_input {
+	inputJSON, err := json.Marshal(normalizePredictInputValues(input))
+	if err != nil {
+		log.Fatal(err)
+	}
+	cInputJSON := C.CString(string(inputJSON))
+	defer C.free(unsafe.Pointer(cInputJSON))
+	var cSeparator *C.char
+	cMaxDepth := C.int(-1)
+	if options != nil {
+		if options.FlattenSeparator != "" {
+			cSeparator = C.CString(options.FlattenSeparator)
+			defer C.free(unsafe.Pointer(cSeparator))
+		}
+		if options.FlattenMaxDepth > 0 {
+			cMaxDepth = C.int(options.FlattenMaxDepth)
+		}
+	}
+	var cInput *C.modelfox_predict_input
+	cErr := C.modelfox_predict_input_new_from_json(cInputJSON, cSeparator, cMaxDepth, &cInput)
+	if cErr != nil {
+		logModelFoxError(cErr)
+	}
+	return cInput
+}
+
+// Convert the values of an input to the types libmodelfox accepts in JSON, formatting bools as strings like `newPredictInput` does.
+func normalizePredictInputValues(input map[string]interface{}) map[string]interface{} {
+	output := make(map[string]interface{}, len(input))
+	for key, value := range input {
+		switch value := value.(type) {
+		case string, float64, int:
+			output[key] = value
+		case bool:
+			output[key] = strconv.FormatBool(value)
+		case PredictInput:
+			output[key] = normalizePredictInputValues(value)
+		case map[string]interface{}:
+			output[key] = normalizePredictInputValues(value)
+		}
+	}
+	return output
+}
+
 func newPredictOptions(predictOptions *PredictOptions) *C.modelfox_predict_options {
 	var cPredictOptions *C.modelfox_predict_options
 	C.modelfox_predict_options_new(&cPredictOptions)
@@ -369,33 +510,104 @@ func newPredictOptions(predictOptions *PredictOptions) *C.modelfox_predict_optio
 		C.modelfox_predict_options_set_threshold(cPredictOptions, C.float(predictOptions.Threshold))
 
 		C.modelfox_predict_options_set_compute_feature_contributions(cPredictOptions, C.bool(predictOptions.ComputeFeatureContributions))
+
+		for className, threshold := range predictOptions.ClassThresholds {
+			cClassName := C.CString(className)
+			err := C.modelfox_predict_options_set_class_threshold(cPredictOptions, cClassName, C.float(threshold))
+			C.free(unsafe.Pointer(cClassName))
+			if err != nil {
+				logModelFoxError(err)
+			}
+		}
 	}
 	return cPredictOptions
 }
 
-// Make a prediction with a single input.
+// Make a prediction with a single input. See `Predict` for how invalid values are handled.
 func (m Model) PredictOne(input PredictInput, options *PredictOptions) PredictOutput {
 	return m.Predict([]PredictInput{input}, options)[0]
 }
 
+// Make a prediction with a single input, returning an error instead of exiting if the input has an invalid value in a column whose invalid value policy is to error. See `TryPredict`.
+func (m Model) TryPredictOne(input PredictInput, options *PredictOptions) (PredictOutput, error) {
+	output, err := m.TryPredict([]PredictInput{input}, options)
+	if err != nil {
+		return nil, err
+	}
+	return output[0], nil
+}
+
+// Check an input against the columns the model was trained with, without making a prediction. This returns an issue for each missing column, unknown column, and value that is invalid for its column's type. Each issue's message says what predicting with the input will do about it. Only the options that control how nested maps are flattened are used.
+func (m Model) ValidateInput(input PredictInput, options *PredictOptions) ([]ValidationIssue, error) {
+	cInput := newPredictInput(input, options)
+	defer C.modelfox_predict_input_delete(cInput)
+	var cIssues *C.modelfox_validation_issue_vec
+	err := C.modelfox_model_validate_input(m.modelPtr, cInput, &cIssues)
+	if err != nil {
+		return nil, modelFoxError(err)
+	}
+	defer C.modelfox_validation_issue_vec_delete(cIssues)
+	var cLen C.size_t
+	C.modelfox_validation_issue_vec_len(cIssues, &cLen)
+	issues := make([]ValidationIssue, int(cLen))
+	for i := range issues {
+		var cIssue *C.modelfox_validation_issue
+		var cType C.modelfox_validation_issue_type
+		var cColumnName C.modelfox_string_view
+		var cMessage C.modelfox_string_view
+		C.modelfox_validation_issue_vec_get_at_index(cIssues, C.size_t(i), &cIssue)
+		C.modelfox_validation_issue_get_type(cIssue, &cType)
+		C.modelfox_validation_issue_get_column_name(cIssue, &cColumnName)
+		C.modelfox_validation_issue_get_message(cIssue, &cMessage)
+		var value interface{}
+		var cNumber C.double
+		var cString C.modelfox_string_view
+		if C.modelfox_validation_issue_get_value_number(cIssue, &cNumber) {
+			value = float64(cNumber)
+		} else if C.modelfox_validation_issue_get_value_string(cIssue, &cString) {
+			value = C.GoStringN(cString.ptr, C.int(cString.len))
+		}
+		issues[i] = ValidationIssue{
+			Type:       ValidationIssueType(cType),
+			ColumnName: C.GoStringN(cColumnName.ptr, C.int(cColumnName.len)),
+			Value:      value,
+			Message:    C.GoStringN(cMessage.ptr, C.int(cMessage.len)),
+		}
+	}
+	return issues, nil
+}
+
 func logModelFoxError(cErr *C.modelfox_error) {
+	log.Fatal(modelFoxError(cErr))
+}
+
+// A helper function to convert a *C.modelfox_error to an error and delete it.
+func modelFoxError(cErr *C.modelfox_error) error {
 	var sv C.modelfox_string_view
 	defer C.modelfox_error_delete(cErr)
 	C.modelfox_error_get_message(cErr, &sv)
-	err := C.GoStringN(sv.ptr, C.int(sv.len))
-	log.Fatal(err)
+	return errors.New(C.GoStringN(sv.ptr, C.int(sv.len)))
 }
 
-// Make a prediction with multiple inputs.
+// Make a prediction with multiple inputs. The invalid value policy each column was trained with is enforced. An input with an invalid value in a column whose policy is to drop the row gets a nil output. An invalid value in a column whose policy is to error exits the program, so use `TryPredict` if you want to handle it.
 func (m Model) Predict(input []PredictInput, options *PredictOptions) []PredictOutput {
+	output, err := m.TryPredict(input, options)
+	if err != nil {
+		log.Fatal(err)
+	}
+	return output
+}
+
+// Make a prediction with multiple inputs like `Predict`, but return an error if an input has an invalid value in a column whose invalid value policy is to error. The error names the column and the value.
+func (m Model) TryPredict(input []PredictInput, options *PredictOptions) ([]PredictOutput, error) {
 	var cOutputVec *C.modelfox_predict_output_vec
-	cInputVec := newPredictInputVec(input)
+	cInputVec := newPredictInputVec(input, options)
 	cOptions := newPredictOptions(options)
 	defer C.modelfox_predict_options_delete(cOptions)
 	defer C.modelfox_predict_input_vec_delete(cInputVec)
 	err := C.modelfox_model_predict(m.modelPtr, cInputVec, cOptions, &cOutputVec)
 	if err != nil {
-		logModelFoxError(err)
+		return nil, modelFoxError(err)
 	}
 	defer C.modelfox_predict_output_vec_delete(cOutputVec)
 
@@ -405,9 +617,13 @@ func (m Model) Predict(input []PredictInput, options *PredictOptions) []PredictO
 	for i := 0; i < len(input); i++ {
 		var cOutput *C.modelfox_predict_output
 		C.modelfox_predict_output_vec_get_at_index(cOutputVec, C.size_t(i), &cOutput)
+		// The output is null if the input was dropped because of an invalid value.
+		if cOutput == nil {
+			continue
+		}
 		outputVec[i] = makePredictOutputFromModelFoxPredictOutput(cTaskType, cOutput)
 	}
-	return outputVec
+	return outputVec, nil
 }
 
 // A helper function to extract a PredictOutput from a *C.modelfox_predict_output.
@@ -688,24 +904,65 @@ func makeWordEmbeddingFeatureContribution(f *C.modelfox_feature_contribution_ent
 
 // Send a prediction event to the app. If you want to batch events, you can use `model.EnqueueLogPrediction` instead.
 func (m Model) LogPrediction(args LogPredictionArgs) error {
+	if !m.isSampled(args.Identifier) {
+		return nil
+	}
 	return m.logEvent(m.predictionEvent(args))
 }
 
 // Add a prediction event to the queue. Remember to call `model.FlushLogQueue` at a later point to send the event to the app.
 func (m Model) EnqueueLogPrediction(args LogPredictionArgs) {
+	if !m.isSampled(args.Identifier) {
+		return
+	}
 	m.logQueue = append(m.logQueue, m.predictionEvent(args))
 }
 
 //  Send a true value event to the app. If you want to batch events, you can use `model.EnqueueLogTrueValue` instead.
 func (m Model) LogTrueValue(args LogTrueValueArgs) error {
+	if !m.isSampled(args.Identifier) {
+		return nil
+	}
 	return m.logEvent(m.trueValueEvent(args))
 }
 
 // Add a true value event to the queue. Remember to call `model.FlushLogQueue` at a later point to send the event to the app.
 func (m Model) EnqueueLogTrueValue(args LogTrueValueArgs) {
+	if !m.isSampled(args.Identifier) {
+		return
+	}
 	m.logQueue = append(m.logQueue, m.trueValueEvent(args))
 }
 
+func validateSampleRate(options *LoadModelOptions) error {
+	if options == nil || options.SampleRate == 0 {
+		return nil
+	}
+	if !(options.SampleRate > 0 && options.SampleRate <= 1) {
+		return errors.New("The sample rate must be greater than 0 and at most 1.")
+	}
+	return nil
+}
+
+// The sample rate is 1 if it was not specified.
+func (m Model) sampleRate() float32 {
+	if m.options == nil || m.options.SampleRate == 0 {
+		return 1
+	}
+	return m.options.SampleRate
+}
+
+// Determine whether the event with this identifier should be logged. This hashes the identifier with 32 bit FNV-1a exactly like the `modelfox_core::sampling` module, so every ModelFox library makes the same decision for the same identifier.
+func (m Model) isSampled(identifier string) bool {
+	sampleRate := m.sampleRate()
+	if sampleRate >= 1 {
+		return true
+	}
+	hash := fnv.New32a()
+	hash.Write([]byte(identifier))
+	return float64(hash.Sum32())/4294967296.0 < float64(sampleRate)
+}
+
 // Send all events in the queue to the app.
 func (m Model) FlushLogQueue() error {
 	err := m.logEvents(m.logQueue)
@@ -757,6 +1014,7 @@ func (m Model) predictionEvent(args LogPredictionArgs) event {
 		"modelId":    m.ID(),
 		"options":    args.Options,
 		"output":     args.Output,
+		"sampleRate": m.sampleRate(),
 		"type":       "prediction",
 	}
 }
@@ -766,6 +1024,7 @@ func (m Model) trueValueEvent(args LogTrueValueArgs) event {
 		"date":       time.Now().Format(time.RFC3339),
 		"identifier": args.Identifier,
 		"modelId":    m.ID(),
+		"sampleRate": m.sampleRate(),
 		"trueValue":  args.TrueValue,
 		"type":       "true_value",
 	}
@@ -0,0 +1,208 @@
+// This file is generated by `scripts/app/openapi` from the app's OpenAPI document. Do not edit it by hand.
+
+/** A monitor event is a prediction or a true value, distinguished by its type. */
+export type MonitorEvent = PredictionMonitorEvent | TrueValueMonitorEvent
+
+export type PredictionMonitorEvent = {
+	type: "prediction"
+	model_id: string
+	date: string
+	/** This identifies the prediction so true values can be matched with it. */
+	identifier: NumberOrString
+	options?: PredictOptions
+	input: Record<string, unknown>
+	output: PredictOutput
+	/** This is the fraction of predictions the SDK was configured to log. */
+	sample_rate?: number
+}
+
+export type TrueValueMonitorEvent = {
+	type: "true_value"
+	model_id: string
+	date: string
+	/** This is the identifier of the prediction this is the true value for. */
+	identifier: NumberOrString
+	true_value: unknown
+	sample_rate?: number
+}
+
+export type PredictOptions = {
+	threshold: number
+	compute_feature_contributions: boolean
+	/** This is the separator the SDK used to flatten nested objects in the input. */
+	flatten_separator?: string
+	/** This is the number of levels of nested objects the SDK flattened in the input. */
+	flatten_max_depth?: number
+}
+
+export type PredictOutput = RegressionPredictOutput | BinaryClassificationPredictOutput | MulticlassClassificationPredictOutput
+
+export type RegressionPredictOutput = {
+	value: number
+}
+
+export type BinaryClassificationPredictOutput = {
+	class_name: string
+	probability: number
+}
+
+export type MulticlassClassificationPredictOutput = {
+	class_name: string
+	probabilities: Record<string, number>
+}
+
+export type NumberOrString = number | string
+
+export type DeletePredictionsResponse = {
+	dry_run: boolean
+	prediction_count: number
+	true_value_count: number
+}
+
+export type MonitorRequest = {
+	/** This is the id of the monitor to update. If it is absent, a monitor is created. */
+	id?: string
+	title?: string
+	/** This is one of hourly, daily, weekly, or monthly. */
+	cadence: string
+	metric: string
+	/** This is absolute or percentage. It defaults to absolute. */
+	mode?: string
+	threshold_lower?: number
+	threshold_upper?: number
+	methods?: Array<AlertMethod>
+	minimum_sample_size?: number
+	warn_below_minimum_sample_size?: boolean
+	cooldown_periods?: number
+	message_template?: string
+	require_confidence_interval?: boolean
+}
+
+export type AlertMethod = EmailAlertMethod | StdoutAlertMethod | WebhookAlertMethod | NotificationChannelAlertMethod
+
+export type EmailAlertMethod = {
+	type: "email"
+	email: string
+}
+
+export type StdoutAlertMethod = {
+	type: "stdout"
+}
+
+export type WebhookAlertMethod = {
+	type: "webhook"
+	url: string
+}
+
+export type NotificationChannelAlertMethod = {
+	type: "notification_channel"
+	notification_channel_id: string
+}
+
+export type MonitorResponse = {
+	id: string
+}
+
+export type RepoReadme = {
+	/** This is the README's markdown. It is empty if the repo has no README. */
+	readme: string
+}
+
+export type BatchPredictJob = {
+	id: string
+	model_id: string
+	/** This is one of queued, running, succeeded, and failed. */
+	status: string
+	/** This is csv or ndjson. The result has the same format as the input. */
+	format: string
+	created_at: string
+	started_at?: string
+	finished_at?: string
+	/** This is the number of rows that were scored. It is set when the job succeeds. */
+	row_count?: number
+	/** This is the reason the job failed. */
+	error?: string
+	/** This is the path to download the result from. It is set when the job succeeds. */
+	result_url?: string
+}
+
+/** A `Client` calls the app's HTTP API at `url`, authenticating with `token` if the app has auth enabled. */
+export class Client {
+	private url: string
+	private token: string | undefined
+
+	constructor(url: string, token?: string) {
+		this.url = url
+		this.token = token
+	}
+
+	/** Log a prediction or true value event, or a list of them. */
+	async track(body: MonitorEvent | Array<MonitorEvent>): Promise<void> {
+		await this.request("POST", `/track`, undefined, body)
+	}
+
+	/** Re-ingest prediction and true value events for a model, replacing events that were already logged with the same identifier and date. */
+	async replayPredictions(modelId: string, body: MonitorEvent | Array<MonitorEvent>): Promise<void> {
+		await this.request("PUT", `/api/models/${encodeURIComponent(modelId)}/predictions`, undefined, body)
+	}
+
+	/** Delete a model's predictions with dates in [after, before) and their true values. */
+	async deletePredictions(modelId: string, query: { after: string; before: string; dry_run?: boolean }): Promise<DeletePredictionsResponse> {
+		return (await this.request("DELETE", `/api/models/${encodeURIComponent(modelId)}/predictions`, query, undefined)) as DeletePredictionsResponse
+	}
+
+	/** Create a model's monitors, or update them if they have the id of one of the model's existing monitors. */
+	async putMonitors(modelId: string, body: Array<MonitorRequest>): Promise<Array<MonitorResponse>> {
+		return (await this.request("PUT", `/api/models/${encodeURIComponent(modelId)}/monitors`, undefined, body)) as Array<MonitorResponse>
+	}
+
+	/** Get a repo's markdown README. */
+	async getRepoReadme(repoId: string): Promise<RepoReadme> {
+		return (await this.request("GET", `/api/repos/${encodeURIComponent(repoId)}/readme`, undefined, undefined)) as RepoReadme
+	}
+
+	/** Replace a repo's markdown README, which is rendered at the top of the repo page. An empty README removes it. */
+	async putRepoReadme(repoId: string, body: RepoReadme): Promise<void> {
+		await this.request("PUT", `/api/repos/${encodeURIComponent(repoId)}/readme`, undefined, body)
+	}
+
+	/** Get the status of a batch predict job. */
+	async getBatchPredictJob(modelId: string, jobId: string): Promise<BatchPredictJob> {
+		return (await this.request("GET", `/api/models/${encodeURIComponent(modelId)}/batch_predict/${encodeURIComponent(jobId)}`, undefined, undefined)) as BatchPredictJob
+	}
+
+	private async request(
+		method: string,
+		path: string,
+		query: Record<string, string | number | boolean | undefined> | undefined,
+		body: unknown,
+	): Promise<unknown> {
+		let url = this.url + path
+		if (query !== undefined) {
+			let searchParams = new URLSearchParams()
+			for (let [key, value] of Object.entries(query)) {
+				if (value !== undefined) {
+					searchParams.set(key, value.toString())
+				}
+			}
+			url += "?" + searchParams.toString()
+		}
+		let headers: Record<string, string> = {}
+		if (body !== undefined) {
+			headers["Content-Type"] = "application/json"
+		}
+		if (this.token !== undefined) {
+			headers["Authorization"] = `Bearer ${this.token}`
+		}
+		let response = await fetch(url, {
+			body: body !== undefined ? JSON.stringify(body) : undefined,
+			headers,
+			method,
+		})
+		if (!response.ok) {
+			throw Error(await response.text())
+		}
+		let text = await response.text()
+		return text.length > 0 ? JSON.parse(text) : undefined
+	}
+}
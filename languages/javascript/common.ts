@@ -23,13 +23,70 @@ export type LoadModelOptions = {
 	 * If you are running the app locally or on your own server, use this field to provide a url that points to it. If not specified, the default value is https://app.modelfox.dev.
 	 */
 	modelfoxUrl?: string
+	/**
+	 * If your service makes too many predictions to log every one, use this field to log only a fraction of prediction and true value events. The value must be greater than 0 and at most 1. Whether an event is logged is determined by its identifier, so the true value for a logged prediction is logged too. If not specified, every event is logged.
+	 */
+	sampleRate?: number
+	/**
+	 * If your service makes many identical predictions, use this field to cache the outputs of this many of the most recently used inputs. Repeated predictions with the same input and options are then returned from the cache without running the model. The cache is disabled by default.
+	 */
+	predictCacheCapacity?: number
+}
+
+/**
+ * [[`Model.validateInput`]] returns a `ValidationIssue` for each problem it finds with an input.
+ */
+export type ValidationIssue = {
+	/**
+	 * This is the kind of issue.
+	 */
+	type:
+		| "missing_column"
+		| "unknown_column"
+		| "unknown_enum_variant"
+		| "invalid_number"
+		| "invalid_date_time"
+	/**
+	 * This is the name of the column the issue is for.
+	 */
+	columnName: string
+	/**
+	 * This is the value the issue is for, which is `null` for a missing column.
+	 */
+	value: number | string | null
+	/**
+	 * This is a message that describes the issue and what predicting with the input will do about it.
+	 */
+	message: string
+}
+
+/**
+ * These are the statistics returned by [[`Model.predictCacheStats`]].
+ */
+export type PredictCacheStats = {
+	/**
+	 * The maximum number of predictions the cache holds.
+	 */
+	capacity: number
+	/**
+	 * The number of predictions currently in the cache.
+	 */
+	len: number
+	/**
+	 * The number of predictions served from the cache.
+	 */
+	hits: number
+	/**
+	 * The number of predictions that were not in the cache.
+	 */
+	misses: number
 }
 
 /**
  * This is the input type of [[`Model.predict`]]. A predict input is an object whose keys are the same as the column names in the CSV the model was trained with, and whose values match the type for each column.
  */
 export type PredictInput = {
-	[key: string]: string | number | null | undefined
+	[key: string]: string | number | PredictInput | null | undefined
 }
 
 /**
@@ -44,6 +101,18 @@ export type PredictOptions = {
 	 * Computing feature contributions is disabled by default. If you set this field to `true`, you will be able to access the feature contributions with the `featureContributions` field of the predict output.
 	 */
 	computeFeatureContributions?: boolean
+	/**
+	 * Nested objects in the input are flattened into columns named by joining their keys with this separator, so `{ user: { age: 3 } }` sets the column `user.age`. The default value is `"."`.
+	 */
+	flattenSeparator?: string
+	/**
+	 * This is the number of levels of nested objects to flatten. Objects nested more deeply are dropped. By default, every level is flattened.
+	 */
+	flattenMaxDepth?: number
+	/**
+	 * If your model is a multiclass classifier, use this field to set a probability threshold for some classes, like the ones saved on the tuning page of the app. A class with a threshold is only predicted if its probability is at least the threshold. Classes without a threshold can always be predicted, so if no class with a threshold qualifies, the most probable class without one is predicted. If every class has a threshold and none qualifies, the most probable class is predicted. If you do not set this field, the thresholds saved in the model when it was downloaded from the app are used.
+	 */
+	classThresholds?: { [className: string]: number }
 }
 
 /**
@@ -331,6 +400,7 @@ type PredictionEvent<TaskType extends Task, InputType extends PredictInput> = {
 	modelId: string
 	options?: PredictOptions
 	output: PredictOutput<TaskType>
+	sampleRate: number
 	type: "prediction"
 }
 
@@ -338,6 +408,7 @@ type TrueValueEvent = {
 	date: String
 	identifier: number | string
 	modelId: string
+	sampleRate: number
 	trueValue: number | string
 	type: "true_value"
 }
@@ -352,6 +423,7 @@ export class Model<
 	> {
 	private model: unknown
 	private modelfoxUrl: string
+	private sampleRate: number
 	private logQueue: Event<TaskType, InputType>[] = []
 
 	/**
@@ -369,12 +441,19 @@ export class Model<
 	constructor(data: ArrayBuffer, options?: LoadModelOptions)
 
 	constructor(input: string | ArrayBuffer, options?: LoadModelOptions) {
+		let nativeOptions = {
+			predictCacheCapacity: options?.predictCacheCapacity,
+		}
 		if (typeof input === "string") {
-			this.model = native.loadModelFromPath(input)
+			this.model = native.loadModelFromPath(input, nativeOptions)
 		} else {
-			this.model = native.loadModelFromArrayBuffer(input)
+			this.model = native.loadModelFromArrayBuffer(input, nativeOptions)
 		}
 		this.modelfoxUrl = options?.modelfoxUrl ?? "https://app.modelfox.dev"
+		this.sampleRate = options?.sampleRate ?? 1
+		if (!(this.sampleRate > 0 && this.sampleRate <= 1)) {
+			throw Error("The sample rate must be greater than 0 and at most 1.")
+		}
 	}
 
 	/**
@@ -387,6 +466,8 @@ export class Model<
 
 	/**
 	 * Make a prediction!
+	 *
+	 * The invalid value policy each column was trained with is enforced. An input with an invalid value in a column whose policy is to drop the row gets an output of `null`, and an invalid value in a column whose policy is to error throws an error.
 	 * @param input The input to the prediction, either a single `PredictInput` or an array of `PredictInput`s.
 	 * @param options An optional [[`PredictOptions`]] value to set options for the prediction.
 	 * @returns A single [[`PredictOutput`]] if `input` was a single [[`PredictInput`]], or an array of [[`PredictOutput`]]s if `input` was an array of [[`PredictInput`]]s.
@@ -394,10 +475,40 @@ export class Model<
 	public predict<PredictInput extends InputType | InputType[]>(
 		input: PredictInput,
 		options?: PredictOptions,
-	): PredictInput extends InputType[] ? OutputType[] : OutputType {
+	): PredictInput extends InputType[]
+		? Array<OutputType | null>
+		: OutputType | null {
 		return native.predict(this.model, input, options)
 	}
 
+	/**
+	 * Check an input against the columns the model was trained with, without making a prediction. This returns an issue for each missing column, unknown column, and value that is invalid for its column's type. Each issue's message says what predicting with the input will do about it.
+	 * @param input A single [[`PredictInput`]].
+	 * @param options An optional [[`PredictOptions`]] value. Only the options that control how nested objects are flattened are used.
+	 * @returns The issues, which is empty if the input has none.
+	 */
+	public validateInput(
+		input: InputType,
+		options?: PredictOptions,
+	): ValidationIssue[] {
+		return native.validateInput(this.model, input, options)
+	}
+
+	/**
+	 * Retrieve statistics about the predict cache enabled with the `predictCacheCapacity` load model option.
+	 * @returns The cache's capacity, the number of predictions it holds, and the number of hits and misses since it was created or last cleared.
+	 */
+	public predictCacheStats(): PredictCacheStats {
+		return native.predictCacheStats(this.model)
+	}
+
+	/**
+	 * Remove all predictions from the predict cache and reset its statistics.
+	 */
+	public clearPredictCache(): void {
+		native.clearPredictCache(this.model)
+	}
+
 	/**
 	 * Send a prediction event to the app. If you want to batch events, you can use [[`Model.enqueueLogTrueValue`]] instead.
 	 * @param args The arguments to use to produce the prediction event.
@@ -405,6 +516,9 @@ export class Model<
 	public async logPrediction(
 		args: LogPredictionArgs<TaskType, InputType>,
 	): Promise<void> {
+		if (!this.isSampled(args.identifier)) {
+			return
+		}
 		this.logEvent(this.predictionEvent(args))
 	}
 
@@ -413,6 +527,9 @@ export class Model<
 	 * @param args The arguments to use to produce the true value event.
 	 */
 	public async logTrueValue(args: LogTrueValueArgs): Promise<void> {
+		if (!this.isSampled(args.identifier)) {
+			return
+		}
 		this.logEvent(this.trueValueEvent(args))
 	}
 
@@ -421,6 +538,9 @@ export class Model<
 	 * @param args The arguments to use to produce the prediction event.
 	 */
 	public enqueueLogPrediction(args: LogPredictionArgs<TaskType, InputType>) {
+		if (!this.isSampled(args.identifier)) {
+			return
+		}
 		this.logQueue.push(this.predictionEvent(args))
 	}
 
@@ -429,6 +549,9 @@ export class Model<
 	 * @param args The arguments to use to produce the true value event.
 	 */
 	public enqueueLogTrueValue(args: LogTrueValueArgs) {
+		if (!this.isSampled(args.identifier)) {
+			return
+		}
 		this.logQueue.push(this.trueValueEvent(args))
 	}
 
@@ -462,6 +585,23 @@ export class Model<
 		}
 	}
 
+	/**
+	 * Determine whether the event with this identifier should be logged. This hashes the identifier with 32 bit FNV-1a exactly like the `modelfox_core::sampling` module, so every SDK makes the same decision for the same identifier.
+	 */
+	private isSampled(identifier: string | undefined): boolean {
+		if (this.sampleRate >= 1) {
+			return true
+		}
+		if (identifier === undefined) {
+			return Math.random() < this.sampleRate
+		}
+		let hash = 0x811c9dc5
+		for (let byte of new TextEncoder().encode(identifier)) {
+			hash = Math.imul(hash ^ byte, 0x01000193) >>> 0
+		}
+		return hash / 0x100000000 < this.sampleRate
+	}
+
 	private predictionEvent(
 		args: LogPredictionArgs<TaskType, InputType>,
 	): PredictionEvent<TaskType, InputType> {
@@ -476,6 +616,7 @@ export class Model<
 				featureContributions: null,
 			},
 			options: args.options,
+			sampleRate: this.sampleRate,
 		}
 	}
 
@@ -485,6 +626,7 @@ export class Model<
 			type: "true_value" as const,
 			date: new Date().toISOString(),
 			identifier: args.identifier,
+			sampleRate: this.sampleRate,
 			trueValue: args.trueValue,
 		}
 	}
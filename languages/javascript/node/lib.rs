@@ -22,18 +22,36 @@ fn init<'a>(env: node_api::Env<'a>, exports: node_api::Value<'a>) -> Result<node
 	let predict_key = node_api::String::new(env, "predict")?;
 	let predict_value = node_api::Function::new(env, "predict", predict)?;
 	exports.set(predict_key, predict_value)?;
+	let key = node_api::String::new(env, "validateInput")?;
+	let value = node_api::Function::new(env, "validateInput", validate_input)?;
+	exports.set(key, value)?;
+	let key = node_api::String::new(env, "predictCacheStats")?;
+	let value = node_api::Function::new(env, "predictCacheStats", predict_cache_stats)?;
+	exports.set(key, value)?;
+	let key = node_api::String::new(env, "clearPredictCache")?;
+	let value = node_api::Function::new(env, "clearPredictCache", clear_predict_cache)?;
+	exports.set(key, value)?;
 	Ok(exports.value())
 }
 
+struct Model {
+	model: modelfox_core::predict::Model,
+	predict_cache: modelfox_core::predict_cache::PredictCache,
+}
+
 #[node_api::function]
 fn load_model_from_path<'a>(
 	env: node_api::Env<'a>,
 	path: String,
-) -> Result<node_api::External<'a, modelfox_core::predict::Model>> {
+	options: Option<LoadModelOptions>,
+) -> Result<node_api::External<'a, Model>> {
 	let file = std::fs::File::open(path)?;
 	let bytes = unsafe { Mmap::map(&file)? };
-	let model = modelfox_model::from_bytes(&bytes)?;
-	let model = modelfox_core::predict::Model::from(model);
+	let model = modelfox_core::predict::Model::from_bytes(&bytes)?;
+	let model = Model {
+		model,
+		predict_cache: options.unwrap_or_default().predict_cache(),
+	};
 	let model = node_api::External::new(env, model)?;
 	Ok(model)
 }
@@ -42,51 +60,185 @@ fn load_model_from_path<'a>(
 fn load_model_from_array_buffer<'a>(
 	env: node_api::Env<'a>,
 	array_buffer: node_api::ArrayBuffer<'a>,
-) -> Result<node_api::External<'a, modelfox_core::predict::Model>> {
+	options: Option<LoadModelOptions>,
+) -> Result<node_api::External<'a, Model>> {
 	let bytes = array_buffer.get()?;
-	let model = modelfox_model::from_bytes(bytes)?;
-	let model = modelfox_core::predict::Model::from(model);
+	let model = modelfox_core::predict::Model::from_bytes(bytes)?;
+	let model = Model {
+		model,
+		predict_cache: options.unwrap_or_default().predict_cache(),
+	};
 	let model = node_api::External::new(env, model)?;
 	Ok(model)
 }
 
 #[node_api::function]
-fn model_id<'a>(
-	env: node_api::Env<'a>,
-	model: node_api::External<'a, modelfox_core::predict::Model>,
-) -> Result<String> {
+fn model_id<'a>(env: node_api::Env<'a>, model: node_api::External<'a, Model>) -> Result<String> {
 	let model = model.get()?;
-	Ok(model.id.clone())
+	Ok(model.model.id.clone())
 }
 
 #[node_api::function]
 fn predict<'a>(
 	env: node_api::Env<'a>,
-	model: node_api::External<'a, modelfox_core::predict::Model>,
+	model: node_api::External<'a, Model>,
 	input: PredictInputSingleOrMultiple,
 	options: Option<PredictOptions>,
-) -> node_api::Result<PredictOutputSingleOrMultiple> {
+) -> Result<PredictOutputSingleOrMultiple> {
 	let model = model.get()?;
+	let flatten_options = options
+		.as_ref()
+		.map(PredictOptions::flatten_options)
+		.unwrap_or_default();
 	let options = options.map(Into::into).unwrap_or_default();
 	match input {
 		PredictInputSingleOrMultiple::Single(input) => {
-			let input = input.into();
-			let mut output = modelfox_core::predict::predict(model, &[input], &options);
+			let input = input.flatten(&flatten_options);
+			let mut output = modelfox_core::predict_cache::try_predict_with_cache(
+				&model.model,
+				&model.predict_cache,
+				&[input],
+				&options,
+			)?;
 			let output = output.remove(0);
-			let output = output.into();
+			let output = output.map(Into::into);
 			let output = PredictOutputSingleOrMultiple::Single(output);
 			Ok(output)
 		}
 		PredictInputSingleOrMultiple::Multiple(input) => {
-			let input = input.into_iter().map(Into::into).collect::<Vec<_>>();
-			let output = modelfox_core::predict::predict(model, &input, &options);
-			let output = output.into_iter().map(Into::into).collect();
+			let input = input
+				.into_iter()
+				.map(|input| input.flatten(&flatten_options))
+				.collect::<Vec<_>>();
+			let output = modelfox_core::predict_cache::try_predict_with_cache(
+				&model.model,
+				&model.predict_cache,
+				&input,
+				&options,
+			)?;
+			let output = output
+				.into_iter()
+				.map(|output| output.map(Into::into))
+				.collect();
 			let output = PredictOutputSingleOrMultiple::Multiple(output);
 			Ok(output)
 		}
 	}
 }
 
+#[node_api::function]
+fn validate_input<'a>(
+	env: node_api::Env<'a>,
+	model: node_api::External<'a, Model>,
+	input: PredictInput,
+	options: Option<PredictOptions>,
+) -> Result<ValidationIssues> {
+	let model = model.get()?;
+	let flatten_options = options
+		.as_ref()
+		.map(PredictOptions::flatten_options)
+		.unwrap_or_default();
+	let input = input.flatten(&flatten_options);
+	let issues = modelfox_core::predict::validate_input(&model.model, &input)
+		.into_iter()
+		.map(Into::into)
+		.collect();
+	Ok(ValidationIssues(issues))
+}
+
+#[node_api::function]
+fn predict_cache_stats<'a>(
+	env: node_api::Env<'a>,
+	model: node_api::External<'a, Model>,
+) -> Result<PredictCacheStats> {
+	let model = model.get()?;
+	Ok(model.predict_cache.stats().into())
+}
+
+#[node_api::function]
+fn clear_predict_cache<'a>(
+	env: node_api::Env<'a>,
+	model: node_api::External<'a, Model>,
+) -> Result<()> {
+	let model = model.get()?;
+	model.predict_cache.clear();
+	Ok(())
+}
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoadModelOptions {
+	pub predict_cache_capacity: Option<usize>,
+}
+
+impl LoadModelOptions {
+	fn predict_cache(&self) -> modelfox_core::predict_cache::PredictCache {
+		modelfox_core::predict_cache::PredictCache::new(self.predict_cache_capacity.unwrap_or(0))
+	}
+}
+
+impl<'a> node_api::FromNodeAPI<'a> for LoadModelOptions {
+	fn from_node_api(value: node_api::Value<'a>) -> node_api::Result<Self> {
+		serde::Deserialize::deserialize(value)
+	}
+}
+
+#[derive(serde::Serialize)]
+struct ValidationIssues(Vec<ValidationIssue>);
+
+impl<'a> node_api::IntoNodeApi<'a> for ValidationIssues {
+	fn into_node_api(self, env: node_api::Env<'a>) -> node_api::Result<node_api::Value<'a>> {
+		serde::Serialize::serialize(&self, env)
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ValidationIssue {
+	#[serde(rename = "type")]
+	kind: modelfox_core::predict::ValidationIssueKind,
+	column_name: String,
+	value: Option<modelfox_core::predict::PredictInputValue>,
+	message: String,
+}
+
+impl From<modelfox_core::predict::ValidationIssue> for ValidationIssue {
+	fn from(value: modelfox_core::predict::ValidationIssue) -> ValidationIssue {
+		ValidationIssue {
+			kind: value.kind,
+			column_name: value.column_name,
+			value: value.value,
+			message: value.message,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PredictCacheStats {
+	capacity: usize,
+	len: usize,
+	hits: u64,
+	misses: u64,
+}
+
+impl From<modelfox_core::predict_cache::PredictCacheStats> for PredictCacheStats {
+	fn from(value: modelfox_core::predict_cache::PredictCacheStats) -> PredictCacheStats {
+		PredictCacheStats {
+			capacity: value.capacity,
+			len: value.len,
+			hits: value.hits,
+			misses: value.misses,
+		}
+	}
+}
+
+impl<'a> node_api::IntoNodeApi<'a> for PredictCacheStats {
+	fn into_node_api(self, env: node_api::Env<'a>) -> node_api::Result<node_api::Value<'a>> {
+		serde::Serialize::serialize(&self, env)
+	}
+}
+
 #[derive(serde::Deserialize)]
 #[serde(untagged)]
 enum PredictInputSingleOrMultiple {
@@ -94,6 +246,12 @@ enum PredictInputSingleOrMultiple {
 	Multiple(PredictInputMultiple),
 }
 
+impl<'a> node_api::FromNodeAPI<'a> for PredictInput {
+	fn from_node_api(value: node_api::Value<'a>) -> node_api::Result<Self> {
+		serde::Deserialize::deserialize(value)
+	}
+}
+
 impl<'a> node_api::FromNodeAPI<'a> for PredictInputSingleOrMultiple {
 	fn from_node_api(value: node_api::Value<'a>) -> node_api::Result<Self> {
 		serde::Deserialize::deserialize(value)
@@ -105,15 +263,17 @@ struct PredictInput(pub BTreeMap<String, PredictInputValue>);
 
 type PredictInputMultiple = Vec<PredictInput>;
 
-impl From<PredictInput> for modelfox_core::predict::PredictInput {
-	fn from(value: PredictInput) -> modelfox_core::predict::PredictInput {
-		modelfox_core::predict::PredictInput(
-			value
-				.0
-				.into_iter()
-				.map(|(key, value)| (key, value.into()))
-				.collect(),
-		)
+impl PredictInput {
+	fn flatten(
+		self,
+		options: &modelfox_core::flatten::FlattenOptions,
+	) -> modelfox_core::predict::PredictInput {
+		let input = self
+			.0
+			.into_iter()
+			.map(|(key, value)| (key, value.into()))
+			.collect();
+		modelfox_core::flatten::flatten_predict_input(input, options)
 	}
 }
 
@@ -122,16 +282,25 @@ impl From<PredictInput> for modelfox_core::predict::PredictInput {
 enum PredictInputValue {
 	Number(f64),
 	String(String),
+	Object(BTreeMap<String, PredictInputValue>),
 }
 
-impl From<PredictInputValue> for modelfox_core::predict::PredictInputValue {
-	fn from(value: PredictInputValue) -> modelfox_core::predict::PredictInputValue {
+impl From<PredictInputValue> for modelfox_core::flatten::NestedPredictInputValue {
+	fn from(value: PredictInputValue) -> modelfox_core::flatten::NestedPredictInputValue {
 		match value {
 			PredictInputValue::Number(value) => {
-				modelfox_core::predict::PredictInputValue::Number(value)
+				modelfox_core::flatten::NestedPredictInputValue::Number(value)
 			}
 			PredictInputValue::String(value) => {
-				modelfox_core::predict::PredictInputValue::String(value)
+				modelfox_core::flatten::NestedPredictInputValue::String(value)
+			}
+			PredictInputValue::Object(value) => {
+				modelfox_core::flatten::NestedPredictInputValue::Object(
+					value
+						.into_iter()
+						.map(|(key, value)| (key, value.into()))
+						.collect(),
+				)
 			}
 		}
 	}
@@ -142,6 +311,20 @@ impl From<PredictInputValue> for modelfox_core::predict::PredictInputValue {
 struct PredictOptions {
 	pub threshold: Option<f32>,
 	pub compute_feature_contributions: Option<bool>,
+	pub flatten_separator: Option<String>,
+	pub flatten_max_depth: Option<usize>,
+	pub class_thresholds: Option<BTreeMap<String, f32>>,
+}
+
+impl PredictOptions {
+	fn flatten_options(&self) -> modelfox_core::flatten::FlattenOptions {
+		let mut options = modelfox_core::flatten::FlattenOptions::default();
+		if let Some(flatten_separator) = &self.flatten_separator {
+			options.separator = flatten_separator.clone();
+		}
+		options.max_depth = self.flatten_max_depth;
+		options
+	}
 }
 
 impl<'a> node_api::FromNodeAPI<'a> for PredictOptions {
@@ -159,6 +342,9 @@ impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 		if let Some(compute_feature_contributions) = value.compute_feature_contributions {
 			options.compute_feature_contributions = compute_feature_contributions;
 		}
+		if let Some(class_thresholds) = value.class_thresholds {
+			options.class_thresholds = class_thresholds;
+		}
 		options
 	}
 }
@@ -166,7 +352,7 @@ impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 #[derive(serde::Serialize)]
 #[serde(untagged)]
 enum PredictOutputSingleOrMultiple {
-	Single(PredictOutput),
+	Single(Option<PredictOutput>),
 	Multiple(PredictOutputMultiple),
 }
 
@@ -187,7 +373,7 @@ enum PredictOutput {
 	MulticlassClassification(MulticlassClassificationPredictOutput),
 }
 
-type PredictOutputMultiple = Vec<PredictOutput>;
+type PredictOutputMultiple = Vec<Option<PredictOutput>>;
 
 impl From<modelfox_core::predict::PredictOutput> for PredictOutput {
 	fn from(value: modelfox_core::predict::PredictOutput) -> Self {
@@ -301,6 +487,8 @@ enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "cyclical")]
+	Cyclical(CyclicalFeatureContribution),
 }
 
 impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContributionEntry {
@@ -324,6 +512,9 @@ impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContribut
 			modelfox_core::predict::FeatureContributionEntry::WordEmbedding(value) => {
 				FeatureContributionEntry::WordEmbedding(value.into())
 			}
+			modelfox_core::predict::FeatureContributionEntry::Cyclical(value) => {
+				FeatureContributionEntry::Cyclical(value.into())
+			}
 		}
 	}
 }
@@ -463,3 +654,23 @@ impl From<modelfox_core::predict::WordEmbeddingFeatureContribution>
 		}
 	}
 }
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CyclicalFeatureContribution {
+	column_name: String,
+	component: String,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::CyclicalFeatureContribution> for CyclicalFeatureContribution {
+	fn from(value: modelfox_core::predict::CyclicalFeatureContribution) -> Self {
+		CyclicalFeatureContribution {
+			column_name: value.column_name,
+			component: value.component.to_string(),
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
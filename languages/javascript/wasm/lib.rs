@@ -7,40 +7,98 @@ use std::collections::BTreeMap;
 use wasm_bindgen::{prelude::*, JsValue};
 
 #[wasm_bindgen(js_name = "loadModelFromArrayBuffer")]
-pub fn load_model_from_array_buffer(bytes: JsValue) -> Result<Model, JsValue> {
+pub fn load_model_from_array_buffer(bytes: JsValue, options: JsValue) -> Result<Model, JsValue> {
 	let bytes: serde_bytes::ByteBuf =
 		serde_wasm_bindgen::from_value(bytes).map_err(|e| e.to_string())?;
-	let model = modelfox_model::from_bytes(&bytes).map_err(|e| e.to_string())?;
-	let model = modelfox_core::predict::Model::from(model);
-	let model = Model(model);
+	let options: Option<LoadModelOptions> = options.into_serde().map_err(|e| e.to_string())?;
+	let model = modelfox_core::predict::Model::from_bytes(&bytes).map_err(|e| e.to_string())?;
+	let predict_cache = modelfox_core::predict_cache::PredictCache::new(
+		options
+			.and_then(|options| options.predict_cache_capacity)
+			.unwrap_or(0),
+	);
+	let model = Model {
+		model,
+		predict_cache,
+	};
 	Ok(model)
 }
 
 #[wasm_bindgen(js_name = "modelId")]
 pub fn model_id(model: &Model) -> Result<String, JsValue> {
-	Ok(model.0.id.to_string())
+	Ok(model.model.id.to_string())
+}
+
+#[wasm_bindgen(js_name = "validateInput")]
+pub fn validate_input(model: &Model, input: JsValue, options: JsValue) -> Result<JsValue, JsValue> {
+	let input: PredictInput = input.into_serde().map_err(|e| e.to_string())?;
+	let options: Option<PredictOptions> = options.into_serde().map_err(|e| e.to_string())?;
+	let flatten_options = options
+		.as_ref()
+		.map(PredictOptions::flatten_options)
+		.unwrap_or_default();
+	let input = input.flatten(&flatten_options);
+	let issues = modelfox_core::predict::validate_input(&model.model, &input)
+		.into_iter()
+		.map(ValidationIssue::from)
+		.collect::<Vec<_>>();
+	let issues = JsValue::from_serde(&issues).map_err(|e| e.to_string())?;
+	Ok(issues)
+}
+
+#[wasm_bindgen(js_name = "predictCacheStats")]
+pub fn predict_cache_stats(model: &Model) -> Result<JsValue, JsValue> {
+	let stats = PredictCacheStats::from(model.predict_cache.stats());
+	let stats = JsValue::from_serde(&stats).map_err(|e| e.to_string())?;
+	Ok(stats)
+}
+
+#[wasm_bindgen(js_name = "clearPredictCache")]
+pub fn clear_predict_cache(model: &Model) {
+	model.predict_cache.clear();
 }
 
 #[wasm_bindgen]
 pub fn predict(model: &Model, input: JsValue, options: JsValue) -> Result<JsValue, JsValue> {
 	let input: PredictInputSingleOrMultiple = input.into_serde().map_err(|e| e.to_string())?;
 	let options: Option<PredictOptions> = options.into_serde().map_err(|e| e.to_string())?;
+	let flatten_options = options
+		.as_ref()
+		.map(PredictOptions::flatten_options)
+		.unwrap_or_default();
 	let options = options.map(Into::into).unwrap_or_default();
-	let model = &model.0;
 	match input {
 		PredictInputSingleOrMultiple::Single(input) => {
-			let input = input.into();
-			let mut output = modelfox_core::predict::predict(model, &[input], &options);
+			let input = input.flatten(&flatten_options);
+			let mut output = modelfox_core::predict_cache::try_predict_with_cache(
+				&model.model,
+				&model.predict_cache,
+				&[input],
+				&options,
+			)
+			.map_err(|e| e.to_string())?;
 			let output = output.remove(0);
-			let output = output.into();
+			let output = output.map(Into::into);
 			let output = PredictOutputSingleOrMultiple::Single(output);
 			let output = JsValue::from_serde(&output).map_err(|e| e.to_string())?;
 			Ok(output)
 		}
 		PredictInputSingleOrMultiple::Multiple(input) => {
-			let input = input.into_iter().map(Into::into).collect::<Vec<_>>();
-			let output = modelfox_core::predict::predict(model, &input, &options);
-			let output = output.into_iter().map(Into::into).collect();
+			let input = input
+				.into_iter()
+				.map(|input| input.flatten(&flatten_options))
+				.collect::<Vec<_>>();
+			let output = modelfox_core::predict_cache::try_predict_with_cache(
+				&model.model,
+				&model.predict_cache,
+				&input,
+				&options,
+			)
+			.map_err(|e| e.to_string())?;
+			let output = output
+				.into_iter()
+				.map(|output| output.map(Into::into))
+				.collect();
 			let output = PredictOutputSingleOrMultiple::Multiple(output);
 			let output = JsValue::from_serde(&output).map_err(|e| e.to_string())?;
 			Ok(output)
@@ -49,7 +107,57 @@ pub fn predict(model: &Model, input: JsValue, options: JsValue) -> Result<JsValu
 }
 
 #[wasm_bindgen]
-pub struct Model(modelfox_core::predict::Model);
+pub struct Model {
+	model: modelfox_core::predict::Model,
+	predict_cache: modelfox_core::predict_cache::PredictCache,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoadModelOptions {
+	pub predict_cache_capacity: Option<usize>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ValidationIssue {
+	#[serde(rename = "type")]
+	kind: modelfox_core::predict::ValidationIssueKind,
+	column_name: String,
+	value: Option<modelfox_core::predict::PredictInputValue>,
+	message: String,
+}
+
+impl From<modelfox_core::predict::ValidationIssue> for ValidationIssue {
+	fn from(value: modelfox_core::predict::ValidationIssue) -> ValidationIssue {
+		ValidationIssue {
+			kind: value.kind,
+			column_name: value.column_name,
+			value: value.value,
+			message: value.message,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PredictCacheStats {
+	capacity: usize,
+	len: usize,
+	hits: u64,
+	misses: u64,
+}
+
+impl From<modelfox_core::predict_cache::PredictCacheStats> for PredictCacheStats {
+	fn from(value: modelfox_core::predict_cache::PredictCacheStats) -> PredictCacheStats {
+		PredictCacheStats {
+			capacity: value.capacity,
+			len: value.len,
+			hits: value.hits,
+			misses: value.misses,
+		}
+	}
+}
 
 #[derive(serde::Deserialize)]
 #[serde(untagged)]
@@ -63,15 +171,17 @@ struct PredictInput(pub BTreeMap<String, PredictInputValue>);
 
 type PredictInputMultiple = Vec<PredictInput>;
 
-impl From<PredictInput> for modelfox_core::predict::PredictInput {
-	fn from(value: PredictInput) -> modelfox_core::predict::PredictInput {
-		modelfox_core::predict::PredictInput(
-			value
-				.0
-				.into_iter()
-				.map(|(key, value)| (key, value.into()))
-				.collect(),
-		)
+impl PredictInput {
+	fn flatten(
+		self,
+		options: &modelfox_core::flatten::FlattenOptions,
+	) -> modelfox_core::predict::PredictInput {
+		let input = self
+			.0
+			.into_iter()
+			.map(|(key, value)| (key, value.into()))
+			.collect();
+		modelfox_core::flatten::flatten_predict_input(input, options)
 	}
 }
 
@@ -80,16 +190,25 @@ impl From<PredictInput> for modelfox_core::predict::PredictInput {
 enum PredictInputValue {
 	Number(f64),
 	String(String),
+	Object(BTreeMap<String, PredictInputValue>),
 }
 
-impl From<PredictInputValue> for modelfox_core::predict::PredictInputValue {
-	fn from(value: PredictInputValue) -> modelfox_core::predict::PredictInputValue {
+impl From<PredictInputValue> for modelfox_core::flatten::NestedPredictInputValue {
+	fn from(value: PredictInputValue) -> modelfox_core::flatten::NestedPredictInputValue {
 		match value {
 			PredictInputValue::Number(value) => {
-				modelfox_core::predict::PredictInputValue::Number(value)
+				modelfox_core::flatten::NestedPredictInputValue::Number(value)
 			}
 			PredictInputValue::String(value) => {
-				modelfox_core::predict::PredictInputValue::String(value)
+				modelfox_core::flatten::NestedPredictInputValue::String(value)
+			}
+			PredictInputValue::Object(value) => {
+				modelfox_core::flatten::NestedPredictInputValue::Object(
+					value
+						.into_iter()
+						.map(|(key, value)| (key, value.into()))
+						.collect(),
+				)
 			}
 		}
 	}
@@ -100,6 +219,20 @@ impl From<PredictInputValue> for modelfox_core::predict::PredictInputValue {
 struct PredictOptions {
 	pub threshold: Option<f32>,
 	pub compute_feature_contributions: Option<bool>,
+	pub flatten_separator: Option<String>,
+	pub flatten_max_depth: Option<usize>,
+	pub class_thresholds: Option<BTreeMap<String, f32>>,
+}
+
+impl PredictOptions {
+	fn flatten_options(&self) -> modelfox_core::flatten::FlattenOptions {
+		let mut options = modelfox_core::flatten::FlattenOptions::default();
+		if let Some(flatten_separator) = &self.flatten_separator {
+			options.separator = flatten_separator.clone();
+		}
+		options.max_depth = self.flatten_max_depth;
+		options
+	}
 }
 
 impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
@@ -111,6 +244,9 @@ impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 		if let Some(compute_feature_contributions) = value.compute_feature_contributions {
 			options.compute_feature_contributions = compute_feature_contributions;
 		}
+		if let Some(class_thresholds) = value.class_thresholds {
+			options.class_thresholds = class_thresholds;
+		}
 		options
 	}
 }
@@ -118,7 +254,7 @@ impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 #[derive(serde::Serialize)]
 #[serde(untagged)]
 enum PredictOutputSingleOrMultiple {
-	Single(PredictOutput),
+	Single(Option<PredictOutput>),
 	Multiple(PredictOutputMultiple),
 }
 
@@ -133,7 +269,7 @@ enum PredictOutput {
 	MulticlassClassification(MulticlassClassificationPredictOutput),
 }
 
-type PredictOutputMultiple = Vec<PredictOutput>;
+type PredictOutputMultiple = Vec<Option<PredictOutput>>;
 
 impl From<modelfox_core::predict::PredictOutput> for PredictOutput {
 	fn from(value: modelfox_core::predict::PredictOutput) -> Self {
@@ -247,6 +383,8 @@ enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "cyclical")]
+	Cyclical(CyclicalFeatureContribution),
 }
 
 impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContributionEntry {
@@ -270,6 +408,9 @@ impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContribut
 			modelfox_core::predict::FeatureContributionEntry::WordEmbedding(value) => {
 				FeatureContributionEntry::WordEmbedding(value.into())
 			}
+			modelfox_core::predict::FeatureContributionEntry::Cyclical(value) => {
+				FeatureContributionEntry::Cyclical(value.into())
+			}
 		}
 	}
 }
@@ -409,3 +550,23 @@ impl From<modelfox_core::predict::WordEmbeddingFeatureContribution>
 		}
 	}
 }
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CyclicalFeatureContribution {
+	column_name: String,
+	component: String,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::CyclicalFeatureContribution> for CyclicalFeatureContribution {
+	fn from(value: modelfox_core::predict::CyclicalFeatureContribution) -> Self {
+		CyclicalFeatureContribution {
+			column_name: value.column_name,
+			component: value.component.to_string(),
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
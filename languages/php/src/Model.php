@@ -143,6 +143,8 @@ final class Model
 
     /**
      * Make a prediction!
+     *
+     * The invalid value policy each column was trained with is enforced. An input with an invalid value in a column whose policy is to drop the row gets an output of `null`, and an invalid value in a column whose policy is to error throws an exception naming the column and the value.
      * @param array $input A predict input is either a single predict input which is a map from symbols or strings to strings or floats or an array of such maps. The keys should match the columns in the CSV file you trained your model with.
      * @param PredictOptions $options Optional predict options
      * @return PredictOutput Return a single output if `input` was a single input, or an array if `input` was an array of `input`s.
@@ -164,7 +166,11 @@ final class Model
         $c_output_vec = self::$ffi->new('modelfox_predict_output_vec*');
         $c_error = self::$ffi->modelfox_model_predict($this->model, $c_input_vec, $c_options, \FFI::addr($c_output_vec));
         if ($c_error != null) {
-            throw new \Exception('modelfox error');
+            $c_error_s = new ModelFoxStringView(self::$ffi);
+            self::$ffi->modelfox_error_get_message($c_error, $c_error_s->raw_ptr());
+            $error_s = $c_error_s->into_string();
+            self::$ffi->modelfox_error_delete($c_error);
+            throw new \Exception($error_s);
         }
         $output = $this->predict_output_vec_from_modelfox_predict_output_vec($c_output_vec);
         self::$ffi->modelfox_predict_output_vec_delete($c_output_vec);
@@ -314,7 +320,12 @@ final class Model
         for ($idx = 0; $idx < $len; $idx++) {
             $c_output = self::$ffi->new('modelfox_predict_output*');
             self::$ffi->modelfox_predict_output_vec_get_at_index($c_output_vec, $idx, \FFI::addr($c_output));
-            array_push($outputs, $this->predict_output_from_modelfox_predict_output($c_output));
+            // The output is null if the input was dropped because of an invalid value.
+            if (\FFI::isNull($c_output)) {
+                array_push($outputs, null);
+            } else {
+                array_push($outputs, $this->predict_output_from_modelfox_predict_output($c_output));
+            }
         }
 
         return $outputs;
@@ -1,15 +1,22 @@
 use anyhow::anyhow;
 use memmap::Mmap;
-use pyo3::{prelude::*, type_object::PyTypeObject, types::PyType};
+use pyo3::{
+	prelude::*,
+	type_object::PyTypeObject,
+	types::{IntoPyDict, PyDict, PyType},
+};
 use std::collections::BTreeMap;
 use url::Url;
 
 #[pymodule]
 #[pyo3(name = "modelfox_python")]
 fn modelfox(py: Python, m: &PyModule) -> PyResult<()> {
+	m.add_function(wrap_pyfunction!(train, m)?)?;
 	m.add_class::<LoadModelOptions>()?;
 	m.add_class::<Model>()?;
 	m.add_class::<PredictOptions>()?;
+	m.add_class::<PredictCacheStats>()?;
+	m.add_class::<ValidationIssue>()?;
 	m.add_class::<RegressionPredictOutput>()?;
 	m.add_class::<BinaryClassificationPredictOutput>()?;
 	m.add_class::<MulticlassClassificationPredictOutput>()?;
@@ -20,6 +27,7 @@ fn modelfox(py: Python, m: &PyModule) -> PyResult<()> {
 	m.add_class::<BagOfWordsFeatureContribution>()?;
 	m.add_class::<BagOfWordsCosineSimilarityFeatureContribution>()?;
 	m.add_class::<WordEmbeddingFeatureContribution>()?;
+	m.add_class::<CyclicalFeatureContribution>()?;
 	m.add("PredictInput", predict_input(py)?)?;
 	m.add("PredictOutput", predict_output(py)?)?;
 	m.add("FeatureContributionEntry", feature_contribution_entry(py)?)?;
@@ -27,6 +35,96 @@ fn modelfox(py: Python, m: &PyModule) -> PyResult<()> {
 	Ok(())
 }
 
+/**
+Train a model from a pandas DataFrame, the same way `modelfox train` does from a CSV file. The DataFrame is converted to CSV, so column types are inferred exactly as they are for a CSV file unless you set them in `config`.
+
+Args:
+	data (`pandas.DataFrame`): The dataset, which will be split into train, comparison, and test datasets.
+	target (str): The name of the column to predict.
+	config (Optional[Dict[str, Any]]): A training config with the same structure as the `.json` file passed to `modelfox train --config`.
+	output (Optional[str]): If set, the trained model is also written to a `.modelfox` file at this path.
+	options (Optional[`LoadModelOptions`]): The options to use when loading the trained model.
+
+Returns:
+	(`Model`, Dict[str, Any]): The trained model and its metrics on the test dataset.
+*/
+#[pyfunction(config = "None", output = "None", options = "None")]
+#[pyo3(text_signature = "(data, target, config=None, output=None, options=None)")]
+fn train(
+	py: Python,
+	data: &PyAny,
+	target: String,
+	config: Option<&PyAny>,
+	output: Option<String>,
+	options: Option<LoadModelOptions>,
+) -> PyResult<(Model, PyObject)> {
+	let csv = data
+		.call_method("to_csv", (), Some([("index", false)].into_py_dict(py)))?
+		.extract::<String>()?;
+	let mut trainer_builder = modelfox_core::train::TrainerBuilder::new(
+		modelfox_core::train::TrainingDataSource::Csv(csv.into_bytes()),
+		target,
+	);
+	if let Some(config) = config {
+		let config = py
+			.import("json")?
+			.call_method1("dumps", (config,))?
+			.extract::<String>()?;
+		let config = serde_json::from_str(&config).map_err(|err| ModelFoxError(err.into()))?;
+		trainer_builder = trainer_builder.config(config);
+	}
+	let model = py
+		.allow_threads(|| {
+			trainer_builder.train(&mut |_: modelfox_core::progress::ProgressEvent| {})
+		})
+		.map_err(ModelFoxError)?;
+	let metrics = test_metrics(py, &model)?;
+	let bytes = model.to_bytes();
+	if let Some(output) = output {
+		std::fs::write(output, &bytes)?;
+	}
+	let model = modelfox_core::predict::Model::from_bytes(&bytes).map_err(ModelFoxError)?;
+	let model = Model::new(model, options)?;
+	Ok((model, metrics.into()))
+}
+
+fn test_metrics<'a>(py: Python<'a>, model: &modelfox_core::model::Model) -> PyResult<&'a PyDict> {
+	let metrics = PyDict::new(py);
+	match &model.inner {
+		modelfox_core::model::ModelInner::Regressor(regressor) => {
+			let test_metrics = &regressor.test_metrics;
+			metrics.set_item("mse", test_metrics.mse)?;
+			metrics.set_item("rmse", test_metrics.rmse)?;
+			metrics.set_item("mae", test_metrics.mae)?;
+			metrics.set_item("r2", test_metrics.r2)?;
+			if let Some(tolerance_accuracy) = test_metrics.tolerance_accuracy {
+				metrics.set_item("tolerance_accuracy", tolerance_accuracy)?;
+			}
+		}
+		modelfox_core::model::ModelInner::BinaryClassifier(binary_classifier) => {
+			let test_metrics = &binary_classifier.test_metrics;
+			let default_threshold_metrics =
+				&test_metrics.thresholds[test_metrics.thresholds.len() / 2];
+			metrics.set_item("auc_roc", test_metrics.auc_roc_approx)?;
+			metrics.set_item("accuracy", default_threshold_metrics.accuracy)?;
+			metrics.set_item("precision", default_threshold_metrics.precision)?;
+			metrics.set_item("recall", default_threshold_metrics.recall)?;
+			metrics.set_item("f1_score", default_threshold_metrics.f1_score)?;
+		}
+		modelfox_core::model::ModelInner::MulticlassClassifier(multiclass_classifier) => {
+			let test_metrics = &multiclass_classifier.test_metrics;
+			metrics.set_item("accuracy", test_metrics.accuracy)?;
+			metrics.set_item("precision_unweighted", test_metrics.precision_unweighted)?;
+			metrics.set_item("precision_weighted", test_metrics.precision_weighted)?;
+			metrics.set_item("recall_unweighted", test_metrics.recall_unweighted)?;
+			metrics.set_item("recall_weighted", test_metrics.recall_weighted)?;
+			metrics.set_item("auc_roc_macro", test_metrics.auc_roc_macro)?;
+			metrics.set_item("auc_roc_micro", test_metrics.auc_roc_micro)?;
+		}
+	}
+	Ok(metrics)
+}
+
 /**
 Use this class to load a model, make predictions, and log events to the app.
 */
@@ -36,6 +134,8 @@ struct Model {
 	model: modelfox_core::predict::Model,
 	log_queue: Vec<Event>,
 	modelfox_url: Url,
+	sample_rate: f32,
+	predict_cache: modelfox_core::predict_cache::PredictCache,
 }
 
 #[pymethods]
@@ -60,20 +160,8 @@ impl Model {
 	) -> PyResult<Model> {
 		let file = std::fs::File::open(path)?;
 		let bytes = unsafe { Mmap::map(&file)? };
-		let model = modelfox_model::from_bytes(&bytes).map_err(ModelFoxError)?;
-		let model = modelfox_core::predict::Model::from(model);
-		let modelfox_url = options
-			.and_then(|options| options.modelfox_url)
-			.unwrap_or_else(|| "https://app.modelfox.dev".to_owned());
-		let modelfox_url = modelfox_url
-			.parse()
-			.map_err(|_| ModelFoxError(anyhow!("Failed to parse modelfox_url")))?;
-		let model = Model {
-			model,
-			log_queue: Vec::new(),
-			modelfox_url,
-		};
-		Ok(model)
+		let model = modelfox_core::predict::Model::from_bytes(&bytes).map_err(ModelFoxError)?;
+		Model::new(model, options)
 	}
 
 	/**
@@ -94,20 +182,8 @@ impl Model {
 		bytes: Vec<u8>,
 		options: Option<LoadModelOptions>,
 	) -> PyResult<Model> {
-		let model = modelfox_model::from_bytes(&bytes).map_err(ModelFoxError)?;
-		let model = modelfox_core::predict::Model::from(model);
-		let modelfox_url = options
-			.and_then(|options| options.modelfox_url)
-			.unwrap_or_else(|| "https://app.modelfox.dev".to_owned());
-		let modelfox_url = modelfox_url
-			.parse()
-			.map_err(|_| ModelFoxError(anyhow!("Failed to parse modelfox_url")))?;
-		let model = Model {
-			model,
-			log_queue: Vec::new(),
-			modelfox_url,
-		};
-		Ok(model)
+		let model = modelfox_core::predict::Model::from_bytes(&bytes).map_err(ModelFoxError)?;
+		Model::new(model, options)
 	}
 
 	/**
@@ -121,38 +197,106 @@ impl Model {
 	/**
 	Make a prediction!
 
+	The invalid value policy each column was trained with is enforced. An input with an invalid value in a column whose policy is to drop the row gets an output of `None`, and an invalid value in a column whose policy is to error raises a `ValueError`.
+
 	Args:
 		input (Union[List[`PredictInput`], `PredictInput`]): A predict input is either a single predict input which is a dict from strings to strings or floats or an array of such dicts. The keys should match the columns in the CSV file you trained your model with.
 		options (Optional[`PredictOptions`]): These are the predict options.
 
 	Returns:
-		[Union[List[`PredictOutput`], `PredictOutput`]). Return a single output if `input` was a single input, or an array if `input` was an array of `input`s.
+		[Union[List[Optional[`PredictOutput`]], Optional[`PredictOutput`]]). Return a single output if `input` was a single input, or an array if `input` was an array of `input`s.
 	*/
 	#[pyo3(text_signature = "(input, options=None)")]
 	fn predict(
 		&self,
 		input: PredictInputSingleOrMultiple,
 		options: Option<&PredictOptions>,
-	) -> PredictOutputSingleOrMultiple {
+	) -> PyResult<PredictOutputSingleOrMultiple> {
 		let model = &self.model;
+		let flatten_options = options
+			.map(PredictOptions::flatten_options)
+			.unwrap_or_default();
 		let options = options.map(Into::into).unwrap_or_default();
 		match input {
 			PredictInputSingleOrMultiple::Single(input) => {
-				let input = input.into();
-				let mut output = modelfox_core::predict::predict(model, &[input], &options);
+				let input = input.flatten(&flatten_options);
+				let mut output = modelfox_core::predict_cache::try_predict_with_cache(
+					model,
+					&self.predict_cache,
+					&[input],
+					&options,
+				)
+				.map_err(invalid_value_error)?;
 				let output = output.remove(0);
-				let output = output.into();
-				PredictOutputSingleOrMultiple::Single(output)
+				let output = output.map(Into::into);
+				Ok(PredictOutputSingleOrMultiple::Single(output))
 			}
 			PredictInputSingleOrMultiple::Multiple(input) => {
-				let input = input.into_iter().map(Into::into).collect::<Vec<_>>();
-				let output = modelfox_core::predict::predict(model, &input, &options);
-				let output = output.into_iter().map(Into::into).collect();
-				PredictOutputSingleOrMultiple::Multiple(output)
+				let input = input
+					.into_iter()
+					.map(|input| input.flatten(&flatten_options))
+					.collect::<Vec<_>>();
+				let output = modelfox_core::predict_cache::try_predict_with_cache(
+					model,
+					&self.predict_cache,
+					&input,
+					&options,
+				)
+				.map_err(invalid_value_error)?;
+				let output = output
+					.into_iter()
+					.map(|output| output.map(Into::into))
+					.collect();
+				Ok(PredictOutputSingleOrMultiple::Multiple(output))
 			}
 		}
 	}
 
+	/**
+	Check an input against the columns the model was trained with, without making a prediction. This returns an issue for each missing column, unknown column, and value that is invalid for its column's type. Each issue's message says what predicting with the input will do about it.
+
+	Args:
+		input (`PredictInput`): A single `PredictInput`.
+		options (Optional[`PredictOptions`]): These are the predict options. Only the options that control how nested dicts are flattened are used.
+
+	Returns:
+		issues (List[`ValidationIssue`]): The issues, which is empty if the input has none.
+	*/
+	#[pyo3(text_signature = "(input, options=None)")]
+	fn validate_input(
+		&self,
+		input: PredictInput,
+		options: Option<&PredictOptions>,
+	) -> Vec<ValidationIssue> {
+		let flatten_options = options
+			.map(PredictOptions::flatten_options)
+			.unwrap_or_default();
+		let input = input.flatten(&flatten_options);
+		modelfox_core::predict::validate_input(&self.model, &input)
+			.into_iter()
+			.map(Into::into)
+			.collect()
+	}
+
+	/**
+	Retrieve statistics about the predict cache enabled with the `predict_cache_capacity` load model option.
+
+	Returns:
+		stats (`PredictCacheStats`)
+	*/
+	#[pyo3(text_signature = "()")]
+	fn predict_cache_stats(&self) -> PredictCacheStats {
+		self.predict_cache.stats().into()
+	}
+
+	/**
+	Remove all predictions from the predict cache and reset its statistics.
+	*/
+	#[pyo3(text_signature = "()")]
+	fn clear_predict_cache(&self) {
+		self.predict_cache.clear()
+	}
+
 	/**
 	Send a prediction event to the app. If you want to batch events, you can use `enqueue_log_prediction` instead.
 
@@ -171,6 +315,9 @@ impl Model {
 		output: PredictOutput,
 		options: Option<PredictOptions>,
 	) -> PyResult<()> {
+		if !self.is_sampled(&identifier) {
+			return Ok(());
+		}
 		let event = Event::Prediction(self.prediction_event(identifier, input, output, options));
 		self.log_event(event)?;
 		Ok(())
@@ -192,6 +339,9 @@ impl Model {
 		output: PredictOutput,
 		options: Option<PredictOptions>,
 	) {
+		if !self.is_sampled(&identifier) {
+			return;
+		}
 		let event = Event::Prediction(self.prediction_event(identifier, input, output, options));
 		self.log_queue.push(event);
 	}
@@ -209,6 +359,9 @@ impl Model {
 		identifier: NumberOrString,
 		true_value: NumberOrString,
 	) -> PyResult<()> {
+		if !self.is_sampled(&identifier) {
+			return Ok(());
+		}
 		let event = Event::TrueValue(self.true_value_event(identifier, true_value));
 		self.log_event(event)?;
 		Ok(())
@@ -223,6 +376,9 @@ impl Model {
 	*/
 	#[pyo3(text_signature = "(identifier, true_value)")]
 	fn enqueue_log_true_value(&mut self, identifier: NumberOrString, true_value: NumberOrString) {
+		if !self.is_sampled(&identifier) {
+			return;
+		}
 		let event = Event::TrueValue(self.true_value_event(identifier, true_value));
 		self.log_queue.push(event);
 	}
@@ -238,6 +394,41 @@ impl Model {
 }
 
 impl Model {
+	fn new(
+		model: modelfox_core::predict::Model,
+		options: Option<LoadModelOptions>,
+	) -> PyResult<Model> {
+		let (modelfox_url, sample_rate, predict_cache_capacity) = match options {
+			Some(options) => (
+				options.modelfox_url,
+				options.sample_rate,
+				options.predict_cache_capacity,
+			),
+			None => (None, None, None),
+		};
+		let modelfox_url = modelfox_url.unwrap_or_else(|| "https://app.modelfox.dev".to_owned());
+		let modelfox_url = modelfox_url
+			.parse()
+			.map_err(|_| ModelFoxError(anyhow!("Failed to parse modelfox_url")))?;
+		let sample_rate = sample_rate.unwrap_or(1.0);
+		if !modelfox_core::sampling::is_valid_sample_rate(sample_rate) {
+			return Err(ModelFoxError(anyhow!(
+				"The sample rate must be greater than 0 and at most 1."
+			))
+			.into());
+		}
+		let model = Model {
+			model,
+			log_queue: Vec::new(),
+			modelfox_url,
+			sample_rate,
+			predict_cache: modelfox_core::predict_cache::PredictCache::new(
+				predict_cache_capacity.unwrap_or(0),
+			),
+		};
+		Ok(model)
+	}
+
 	fn log_event(&mut self, event: Event) -> PyResult<()> {
 		self.log_events(vec![event])
 	}
@@ -254,6 +445,14 @@ impl Model {
 		Ok(())
 	}
 
+	fn is_sampled(&self, identifier: &NumberOrString) -> bool {
+		let identifier = match identifier {
+			NumberOrString::Number(number) => number.to_string(),
+			NumberOrString::String(string) => string.to_owned(),
+		};
+		modelfox_core::sampling::is_sampled(&identifier, self.sample_rate)
+	}
+
 	fn prediction_event(
 		&self,
 		identifier: NumberOrString,
@@ -261,13 +460,19 @@ impl Model {
 		output: PredictOutput,
 		options: Option<PredictOptions>,
 	) -> PredictionEvent {
+		// Log the flattened input, so the columns in the app match the ones the model saw.
+		let flatten_options = options
+			.as_ref()
+			.map(PredictOptions::flatten_options)
+			.unwrap_or_default();
 		PredictionEvent {
 			date: chrono::Utc::now(),
 			identifier,
-			input,
+			input: input.flatten(&flatten_options),
 			options,
 			output,
 			model_id: self.id(),
+			sample_rate: self.sample_rate,
 		}
 	}
 
@@ -280,6 +485,7 @@ impl Model {
 			date: chrono::Utc::now(),
 			identifier,
 			model_id: self.id(),
+			sample_rate: self.sample_rate,
 			true_value,
 		}
 	}
@@ -290,19 +496,120 @@ These are the options passed when loading a model.
 
 Attributes:
 	modelfox_url (Optional[str]): If you are running the app locally or on your own server, use this field to provide the url to it.
+	sample_rate (Optional[float]): If your service makes too many predictions to log every one, use this field to log only a fraction of prediction and true value events. The value must be greater than 0 and at most 1. Whether an event is logged is determined by its identifier, so the true value for a logged prediction is logged too.
+	predict_cache_capacity (Optional[int]): If your service makes many identical predictions, use this field to cache the outputs of this many of the most recently used inputs. Repeated predictions with the same input and options are then returned from the cache without running the model. The cache is disabled by default.
 */
 #[pyclass]
 #[derive(Clone, Debug)]
 struct LoadModelOptions {
 	#[pyo3(get, set)]
 	modelfox_url: Option<String>,
+	#[pyo3(get, set)]
+	sample_rate: Option<f32>,
+	#[pyo3(get, set)]
+	predict_cache_capacity: Option<usize>,
 }
 
 #[pymethods]
 impl LoadModelOptions {
 	#[new]
-	fn new(modelfox_url: Option<String>) -> LoadModelOptions {
-		LoadModelOptions { modelfox_url }
+	#[args(
+		modelfox_url = "None",
+		sample_rate = "None",
+		predict_cache_capacity = "None"
+	)]
+	fn new(
+		modelfox_url: Option<String>,
+		sample_rate: Option<f32>,
+		predict_cache_capacity: Option<usize>,
+	) -> LoadModelOptions {
+		LoadModelOptions {
+			modelfox_url,
+			sample_rate,
+			predict_cache_capacity,
+		}
+	}
+}
+
+/**
+These are the statistics returned by `Model.predict_cache_stats`.
+
+Attributes:
+	capacity (int): The maximum number of predictions the cache holds.
+	len (int): The number of predictions currently in the cache.
+	hits (int): The number of predictions served from the cache.
+	misses (int): The number of predictions that were not in the cache.
+*/
+#[pyclass]
+#[derive(Clone, Debug)]
+struct PredictCacheStats {
+	#[pyo3(get)]
+	capacity: usize,
+	#[pyo3(get)]
+	len: usize,
+	#[pyo3(get)]
+	hits: u64,
+	#[pyo3(get)]
+	misses: u64,
+}
+
+impl From<modelfox_core::predict_cache::PredictCacheStats> for PredictCacheStats {
+	fn from(value: modelfox_core::predict_cache::PredictCacheStats) -> PredictCacheStats {
+		PredictCacheStats {
+			capacity: value.capacity,
+			len: value.len,
+			hits: value.hits,
+			misses: value.misses,
+		}
+	}
+}
+
+/**
+`Model.validate_input` returns a `ValidationIssue` for each problem it finds with an input.
+
+Attributes:
+	type (str): This is the kind of issue, one of `"missing_column"`, `"unknown_column"`, `"unknown_enum_variant"`, `"invalid_number"`, or `"invalid_date_time"`.
+	column_name (str): This is the name of the column the issue is for.
+	value (Optional[Union[str, float]]): This is the value the issue is for, which is `None` for a missing column.
+	message (str): This is a message that describes the issue and what predicting with the input will do about it.
+*/
+#[pyclass]
+#[derive(Clone, Debug)]
+struct ValidationIssue {
+	#[pyo3(get, name = "type")]
+	kind: String,
+	#[pyo3(get)]
+	column_name: String,
+	#[pyo3(get)]
+	value: Option<NumberOrString>,
+	#[pyo3(get)]
+	message: String,
+}
+
+impl From<modelfox_core::predict::ValidationIssue> for ValidationIssue {
+	fn from(value: modelfox_core::predict::ValidationIssue) -> ValidationIssue {
+		let kind = match value.kind {
+			modelfox_core::predict::ValidationIssueKind::MissingColumn => "missing_column",
+			modelfox_core::predict::ValidationIssueKind::UnknownColumn => "unknown_column",
+			modelfox_core::predict::ValidationIssueKind::UnknownEnumVariant => {
+				"unknown_enum_variant"
+			}
+			modelfox_core::predict::ValidationIssueKind::InvalidNumber => "invalid_number",
+			modelfox_core::predict::ValidationIssueKind::InvalidDateTime => "invalid_date_time",
+		};
+		ValidationIssue {
+			kind: kind.to_owned(),
+			column_name: value.column_name,
+			value: value.value.map(|value| match value {
+				modelfox_core::predict::PredictInputValue::Number(value) => {
+					NumberOrString::Number(value)
+				}
+				modelfox_core::predict::PredictInputValue::String(value) => {
+					NumberOrString::String(value)
+				}
+			}),
+			message: value.message,
+		}
 	}
 }
 
@@ -317,15 +624,17 @@ struct PredictInput(BTreeMap<String, PredictInputValue>);
 
 type PredictInputMultiple = Vec<PredictInput>;
 
-impl From<PredictInput> for modelfox_core::predict::PredictInput {
-	fn from(value: PredictInput) -> modelfox_core::predict::PredictInput {
-		modelfox_core::predict::PredictInput(
-			value
-				.0
-				.into_iter()
-				.map(|(key, value)| (key, value.into()))
-				.collect(),
-		)
+impl PredictInput {
+	fn flatten(
+		self,
+		options: &modelfox_core::flatten::FlattenOptions,
+	) -> modelfox_core::predict::PredictInput {
+		let input = self
+			.0
+			.into_iter()
+			.map(|(key, value)| (key, value.into()))
+			.collect();
+		modelfox_core::flatten::flatten_predict_input(input, options)
 	}
 }
 
@@ -334,16 +643,25 @@ impl From<PredictInput> for modelfox_core::predict::PredictInput {
 enum PredictInputValue {
 	Number(f64),
 	String(String),
+	Object(BTreeMap<String, PredictInputValue>),
 }
 
-impl From<PredictInputValue> for modelfox_core::predict::PredictInputValue {
-	fn from(value: PredictInputValue) -> modelfox_core::predict::PredictInputValue {
+impl From<PredictInputValue> for modelfox_core::flatten::NestedPredictInputValue {
+	fn from(value: PredictInputValue) -> modelfox_core::flatten::NestedPredictInputValue {
 		match value {
 			PredictInputValue::Number(value) => {
-				modelfox_core::predict::PredictInputValue::Number(value)
+				modelfox_core::flatten::NestedPredictInputValue::Number(value)
 			}
 			PredictInputValue::String(value) => {
-				modelfox_core::predict::PredictInputValue::String(value)
+				modelfox_core::flatten::NestedPredictInputValue::String(value)
+			}
+			PredictInputValue::Object(value) => {
+				modelfox_core::flatten::NestedPredictInputValue::Object(
+					value
+						.into_iter()
+						.map(|(key, value)| (key, value.into()))
+						.collect(),
+				)
 			}
 		}
 	}
@@ -356,6 +674,12 @@ Attributes:
 	threshold (Optional[float]): If your model is a binary classifier, use this field to make predictions using a threshold chosen on the tuning page of the app. The default value is `0.5`.
 
 	compute_feature_contributions (Optional[bool]): Computing feature contributions is disabled by default. If you set this field to `true`, you will be able to access the feature contributions with the `featureContributions` field of the predict output.
+
+	flatten_separator (Optional[str]): Nested dicts in the input are flattened into columns named by joining their keys with this separator, so `{"user": {"age": 3}}` sets the column `user.age`. The default value is `"."`.
+
+	flatten_max_depth (Optional[int]): This is the number of levels of nested dicts to flatten. Dicts nested more deeply are dropped. By default, every level is flattened.
+
+	class_thresholds (Optional[Dict[str, float]]): If your model is a multiclass classifier, use this field to set a probability threshold for some classes, like the ones saved on the tuning page of the app. A class with a threshold is only predicted if its probability is at least the threshold. Classes without a threshold can always be predicted, so if no class with a threshold qualifies, the most probable class without one is predicted. If every class has a threshold and none qualifies, the most probable class is predicted. If you do not set this field, the thresholds saved in the model when it was downloaded from the app are used.
 */
 #[pyclass]
 #[derive(Clone, Debug, serde::Serialize)]
@@ -364,19 +688,52 @@ struct PredictOptions {
 	threshold: Option<f32>,
 	#[pyo3(get, set)]
 	compute_feature_contributions: Option<bool>,
+	#[pyo3(get, set)]
+	flatten_separator: Option<String>,
+	#[pyo3(get, set)]
+	flatten_max_depth: Option<usize>,
+	#[pyo3(get, set)]
+	class_thresholds: Option<BTreeMap<String, f32>>,
 }
 
 #[pymethods]
 impl PredictOptions {
 	#[new]
-	fn new(threshold: Option<f32>, compute_feature_contributions: Option<bool>) -> PredictOptions {
+	#[args(
+		threshold = "None",
+		compute_feature_contributions = "None",
+		flatten_separator = "None",
+		flatten_max_depth = "None",
+		class_thresholds = "None"
+	)]
+	fn new(
+		threshold: Option<f32>,
+		compute_feature_contributions: Option<bool>,
+		flatten_separator: Option<String>,
+		flatten_max_depth: Option<usize>,
+		class_thresholds: Option<BTreeMap<String, f32>>,
+	) -> PredictOptions {
 		PredictOptions {
 			threshold,
 			compute_feature_contributions,
+			flatten_separator,
+			flatten_max_depth,
+			class_thresholds,
 		}
 	}
 }
 
+impl PredictOptions {
+	fn flatten_options(&self) -> modelfox_core::flatten::FlattenOptions {
+		let mut options = modelfox_core::flatten::FlattenOptions::default();
+		if let Some(flatten_separator) = &self.flatten_separator {
+			options.separator = flatten_separator.clone();
+		}
+		options.max_depth = self.flatten_max_depth;
+		options
+	}
+}
+
 impl From<&PredictOptions> for modelfox_core::predict::PredictOptions {
 	fn from(value: &PredictOptions) -> modelfox_core::predict::PredictOptions {
 		let mut options = modelfox_core::predict::PredictOptions::default();
@@ -386,12 +743,15 @@ impl From<&PredictOptions> for modelfox_core::predict::PredictOptions {
 		if let Some(compute_feature_contributions) = value.compute_feature_contributions {
 			options.compute_feature_contributions = compute_feature_contributions;
 		}
+		if let Some(class_thresholds) = &value.class_thresholds {
+			options.class_thresholds = class_thresholds.clone();
+		}
 		options
 	}
 }
 
 enum PredictOutputSingleOrMultiple {
-	Single(PredictOutput),
+	Single(Option<PredictOutput>),
 	Multiple(PredictOutputMultiple),
 }
 
@@ -412,7 +772,7 @@ enum PredictOutput {
 	MulticlassClassification(MulticlassClassificationPredictOutput),
 }
 
-type PredictOutputMultiple = Vec<PredictOutput>;
+type PredictOutputMultiple = Vec<Option<PredictOutput>>;
 
 impl IntoPy<PyObject> for PredictOutput {
 	fn into_py(self, py: Python) -> PyObject {
@@ -584,6 +944,8 @@ enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "cyclical")]
+	Cyclical(CyclicalFeatureContribution),
 }
 
 impl IntoPy<PyObject> for FeatureContributionEntry {
@@ -595,6 +957,7 @@ impl IntoPy<PyObject> for FeatureContributionEntry {
 			FeatureContributionEntry::BagOfWords(s) => s.into_py(py),
 			FeatureContributionEntry::BagOfWordsCosineSimilarity(s) => s.into_py(py),
 			FeatureContributionEntry::WordEmbedding(s) => s.into_py(py),
+			FeatureContributionEntry::Cyclical(s) => s.into_py(py),
 		}
 	}
 }
@@ -620,6 +983,9 @@ impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContribut
 			modelfox_core::predict::FeatureContributionEntry::WordEmbedding(value) => {
 				FeatureContributionEntry::WordEmbedding(value.into())
 			}
+			modelfox_core::predict::FeatureContributionEntry::Cyclical(value) => {
+				FeatureContributionEntry::Cyclical(value.into())
+			}
 		}
 	}
 }
@@ -844,6 +1210,39 @@ impl From<modelfox_core::predict::WordEmbeddingFeatureContribution>
 	}
 }
 
+/**
+This describes the contribution of a feature from a cyclical feature group.
+
+Attributes:
+	column_name (str): This is the name of the source column for the feature group.
+	component (str): This is either "sin" or "cos", identifying which of the feature group's two features this is.
+	feature_value (float): This is the value of the feature.
+	feature_contribution_value (float): This is the amount that the feature contributed to the output.
+*/
+#[pyclass]
+#[derive(Clone, Debug, serde::Serialize)]
+struct CyclicalFeatureContribution {
+	#[pyo3(get)]
+	column_name: String,
+	#[pyo3(get)]
+	component: String,
+	#[pyo3(get)]
+	feature_value: f32,
+	#[pyo3(get)]
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::CyclicalFeatureContribution> for CyclicalFeatureContribution {
+	fn from(value: modelfox_core::predict::CyclicalFeatureContribution) -> Self {
+		CyclicalFeatureContribution {
+			column_name: value.column_name,
+			component: value.component.to_string(),
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
 #[derive(Debug, serde::Serialize)]
 #[serde(tag = "type")]
 enum Event {
@@ -858,10 +1257,11 @@ enum Event {
 struct PredictionEvent {
 	date: chrono::DateTime<chrono::Utc>,
 	identifier: NumberOrString,
-	input: PredictInput,
+	input: modelfox_core::predict::PredictInput,
 	options: Option<PredictOptions>,
 	output: PredictOutput,
 	model_id: String,
+	sample_rate: f32,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -869,16 +1269,26 @@ struct TrueValueEvent {
 	date: chrono::DateTime<chrono::Utc>,
 	identifier: NumberOrString,
 	model_id: String,
+	sample_rate: f32,
 	true_value: NumberOrString,
 }
 
-#[derive(Debug, serde::Serialize, FromPyObject)]
+#[derive(Clone, Debug, serde::Serialize, FromPyObject)]
 #[serde(untagged)]
 enum NumberOrString {
 	Number(f64),
 	String(String),
 }
 
+impl IntoPy<PyObject> for NumberOrString {
+	fn into_py(self, py: Python) -> PyObject {
+		match self {
+			NumberOrString::Number(value) => value.into_py(py),
+			NumberOrString::String(value) => value.into_py(py),
+		}
+	}
+}
+
 fn predict_input(py: Python) -> PyResult<PyObject> {
 	let typing = py.import("typing")?;
 	let py_dict = typing.getattr("Dict")?;
@@ -909,6 +1319,7 @@ fn feature_contribution_entry(py: Python) -> PyResult<PyObject> {
 		BagOfWordsFeatureContribution::type_object(py),
 		BagOfWordsCosineSimilarityFeatureContribution::type_object(py),
 		WordEmbeddingFeatureContribution::type_object(py),
+		CyclicalFeatureContribution::type_object(py),
 	))?;
 	Ok(feature_contribution_entry.into())
 }
@@ -931,6 +1342,10 @@ impl std::fmt::Display for ModelFoxError {
 	}
 }
 
+fn invalid_value_error(error: modelfox_core::predict::InvalidValueError) -> PyErr {
+	PyErr::new::<pyo3::exceptions::PyValueError, _>(error.to_string())
+}
+
 impl From<ModelFoxError> for PyErr {
 	fn from(error: ModelFoxError) -> PyErr {
 		PyErr::new::<pyo3::exceptions::PyTypeError, _>(error.to_string())
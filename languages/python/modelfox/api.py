@@ -0,0 +1,198 @@
+# This file is generated by `scripts/app/openapi` from the app's OpenAPI document. Do not edit it by hand.
+
+import json
+import urllib.parse
+import urllib.request
+from typing import Any, Dict, List, Literal, Optional, TypedDict, Union
+
+
+MonitorEvent = Union["PredictionMonitorEvent", "TrueValueMonitorEvent"]
+
+
+class PredictionMonitorEvent(TypedDict):
+    type: Literal["prediction"]
+    model_id: str
+    date: str
+    # This identifies the prediction so true values can be matched with it.
+    identifier: "NumberOrString"
+    options: Optional["PredictOptions"]
+    input: Dict[str, Any]
+    output: "PredictOutput"
+    # This is the fraction of predictions the SDK was configured to log.
+    sample_rate: Optional[float]
+
+
+class TrueValueMonitorEvent(TypedDict):
+    type: Literal["true_value"]
+    model_id: str
+    date: str
+    # This is the identifier of the prediction this is the true value for.
+    identifier: "NumberOrString"
+    true_value: Any
+    sample_rate: Optional[float]
+
+
+class PredictOptions(TypedDict):
+    threshold: float
+    compute_feature_contributions: bool
+    # This is the separator the SDK used to flatten nested objects in the input.
+    flatten_separator: Optional[str]
+    # This is the number of levels of nested objects the SDK flattened in the input.
+    flatten_max_depth: Optional[int]
+
+
+PredictOutput = Union["RegressionPredictOutput", "BinaryClassificationPredictOutput", "MulticlassClassificationPredictOutput"]
+
+
+class RegressionPredictOutput(TypedDict):
+    value: float
+
+
+class BinaryClassificationPredictOutput(TypedDict):
+    class_name: str
+    probability: float
+
+
+class MulticlassClassificationPredictOutput(TypedDict):
+    class_name: str
+    probabilities: Dict[str, float]
+
+
+NumberOrString = Union[float, str]
+
+
+class DeletePredictionsResponse(TypedDict):
+    dry_run: bool
+    prediction_count: int
+    true_value_count: int
+
+
+class MonitorRequest(TypedDict):
+    # This is the id of the monitor to update. If it is absent, a monitor is created.
+    id: Optional[str]
+    title: Optional[str]
+    # This is one of hourly, daily, weekly, or monthly.
+    cadence: str
+    metric: str
+    # This is absolute or percentage. It defaults to absolute.
+    mode: Optional[str]
+    threshold_lower: Optional[float]
+    threshold_upper: Optional[float]
+    methods: Optional[List["AlertMethod"]]
+    minimum_sample_size: Optional[int]
+    warn_below_minimum_sample_size: Optional[bool]
+    cooldown_periods: Optional[int]
+    message_template: Optional[str]
+    require_confidence_interval: Optional[bool]
+
+
+AlertMethod = Union["EmailAlertMethod", "StdoutAlertMethod", "WebhookAlertMethod", "NotificationChannelAlertMethod"]
+
+
+class EmailAlertMethod(TypedDict):
+    type: Literal["email"]
+    email: str
+
+
+class StdoutAlertMethod(TypedDict):
+    type: Literal["stdout"]
+
+
+class WebhookAlertMethod(TypedDict):
+    type: Literal["webhook"]
+    url: str
+
+
+class NotificationChannelAlertMethod(TypedDict):
+    type: Literal["notification_channel"]
+    notification_channel_id: str
+
+
+class MonitorResponse(TypedDict):
+    id: str
+
+
+class RepoReadme(TypedDict):
+    # This is the README's markdown. It is empty if the repo has no README.
+    readme: str
+
+
+class BatchPredictJob(TypedDict):
+    id: str
+    model_id: str
+    # This is one of queued, running, succeeded, and failed.
+    status: str
+    # This is csv or ndjson. The result has the same format as the input.
+    format: str
+    created_at: str
+    started_at: Optional[str]
+    finished_at: Optional[str]
+    # This is the number of rows that were scored. It is set when the job succeeds.
+    row_count: Optional[int]
+    # This is the reason the job failed.
+    error: Optional[str]
+    # This is the path to download the result from. It is set when the job succeeds.
+    result_url: Optional[str]
+
+
+class Client:
+    """A `Client` calls the app's HTTP API at `url`, authenticating with `token` if the app has auth enabled."""
+
+    def __init__(self, url: str, token: Optional[str] = None):
+        self.url = url
+        self.token = token
+
+    def track(self, body: Union["MonitorEvent", List["MonitorEvent"]]) -> None:
+        """Log a prediction or true value event, or a list of them."""
+        self._request("POST", "/track", None, body)
+
+    def replay_predictions(self, model_id: str, body: Union["MonitorEvent", List["MonitorEvent"]]) -> None:
+        """Re-ingest prediction and true value events for a model, replacing events that were already logged with the same identifier and date."""
+        self._request("PUT", "/api/models/" + urllib.parse.quote(model_id, safe="") + "/predictions", None, body)
+
+    def delete_predictions(self, model_id: str, after: str, before: str, dry_run: Optional[bool] = None) -> "DeletePredictionsResponse":
+        """Delete a model's predictions with dates in [after, before) and their true values."""
+        return self._request("DELETE", "/api/models/" + urllib.parse.quote(model_id, safe="") + "/predictions", {"after": after, "before": before, "dry_run": dry_run}, None)
+
+    def put_monitors(self, model_id: str, body: List["MonitorRequest"]) -> List["MonitorResponse"]:
+        """Create a model's monitors, or update them if they have the id of one of the model's existing monitors."""
+        return self._request("PUT", "/api/models/" + urllib.parse.quote(model_id, safe="") + "/monitors", None, body)
+
+    def get_repo_readme(self, repo_id: str) -> "RepoReadme":
+        """Get a repo's markdown README."""
+        return self._request("GET", "/api/repos/" + urllib.parse.quote(repo_id, safe="") + "/readme", None, None)
+
+    def put_repo_readme(self, repo_id: str, body: "RepoReadme") -> None:
+        """Replace a repo's markdown README, which is rendered at the top of the repo page. An empty README removes it."""
+        self._request("PUT", "/api/repos/" + urllib.parse.quote(repo_id, safe="") + "/readme", None, body)
+
+    def get_batch_predict_job(self, model_id: str, job_id: str) -> "BatchPredictJob":
+        """Get the status of a batch predict job."""
+        return self._request("GET", "/api/models/" + urllib.parse.quote(model_id, safe="") + "/batch_predict/" + urllib.parse.quote(job_id, safe=""), None, None)
+
+    def _request(
+        self,
+        method: str,
+        path: str,
+        query: Optional[Dict[str, Any]],
+        body: Any,
+    ) -> Any:
+        url = self.url + path
+        if query is not None:
+            query = {
+                key: json.dumps(value) if isinstance(value, bool) else value
+                for key, value in query.items()
+                if value is not None
+            }
+            url += "?" + urllib.parse.urlencode(query)
+        headers = {}
+        data = None
+        if body is not None:
+            headers["Content-Type"] = "application/json"
+            data = json.dumps(body).encode()
+        if self.token is not None:
+            headers["Authorization"] = "Bearer " + self.token
+        request = urllib.request.Request(url, data=data, headers=headers, method=method)
+        with urllib.request.urlopen(request) as response:
+            text = response.read().decode()
+        return json.loads(text) if text else None
@@ -11,6 +11,14 @@ from typing import (
     Union,
 )
 
+def train(
+    data: Any,
+    target: str,
+    config: Optional[Dict[str, Any]] = None,
+    output: Optional[str] = None,
+    options: Optional[LoadModelOptions] = None,
+) -> Tuple["Model", Dict[str, Any]]: ...
+
 class Model:
     @classmethod
     def from_path(
@@ -31,13 +39,20 @@ class Model:
         self,
         input: PredictInput,
         options: Optional[PredictOptions] = None,
-    ) -> PredictOutput: ...
+    ) -> Optional[PredictOutput]: ...
     @overload
     def predict(
         self,
         input: List[PredictInput],
         options: Optional[PredictOptions] = None,
-    ) -> List[PredictOutput]: ...
+    ) -> List[Optional[PredictOutput]]: ...
+    def validate_input(
+        self,
+        input: PredictInput,
+        options: Optional[PredictOptions] = None,
+    ) -> List[ValidationIssue]: ...
+    def predict_cache_stats(self) -> PredictCacheStats: ...
+    def clear_predict_cache(self) -> None: ...
     def log_prediction(
         self,
         identifier: Identifier,
@@ -68,20 +83,48 @@ class Model:
 
 class LoadModelOptions:
     modelfox_url: Optional[str]
+    sample_rate: Optional[float]
+    predict_cache_capacity: Optional[int]
     def __new__(
         self,
-        modelfox_url: Optional[str],
+        modelfox_url: Optional[str] = None,
+        sample_rate: Optional[float] = None,
+        predict_cache_capacity: Optional[int] = None,
     ) -> LoadModelOptions: ...
 
+class PredictCacheStats:
+    capacity: int
+    len: int
+    hits: int
+    misses: int
+
+class ValidationIssue:
+    type: Literal[
+        "missing_column",
+        "unknown_column",
+        "unknown_enum_variant",
+        "invalid_number",
+        "invalid_date_time",
+    ]
+    column_name: str
+    value: Optional[Union[str, float]]
+    message: str
+
 PredictInput = Dict[str, Any]
 
 class PredictOptions:
     threshold: Optional[float]
     compute_feature_contributions: Optional[bool]
+    flatten_separator: Optional[str]
+    flatten_max_depth: Optional[int]
+    class_thresholds: Optional[Dict[str, float]]
     def __new__(
         self,
-        threshold: Optional[float],
-        compute_feature_contributions: Optional[bool],
+        threshold: Optional[float] = None,
+        compute_feature_contributions: Optional[bool] = None,
+        flatten_separator: Optional[str] = None,
+        flatten_max_depth: Optional[int] = None,
+        class_thresholds: Optional[Dict[str, float]] = None,
     ) -> PredictOptions: ...
 
 PredictOutput = Union[
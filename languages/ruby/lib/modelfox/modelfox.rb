@@ -11,8 +11,50 @@ module ModelFox
   class LoadModelOptions
     # If you are running the app locally or on your own server, use this field to provide the url to it. If not specified, the default value is https://app.modelfox.dev.
     attr_reader :modelfox_url
-    def initialize(modelfox_url:)
+    # If your service makes too many predictions to log every one, use this field to log only a fraction of prediction and true value events. The value must be greater than 0 and at most 1. Whether an event is logged is determined by its identifier, so the true value for a logged prediction is logged too. If not specified, every event is logged.
+    attr_reader :sample_rate
+    # If your service makes many identical predictions, use this field to cache the outputs of this many of the most recently used inputs. Repeated predictions with the same input and options are then returned from the cache without running the model. The cache is disabled by default.
+    attr_reader :predict_cache_capacity
+    def initialize(modelfox_url:, sample_rate: nil, predict_cache_capacity: nil)
       @modelfox_url = modelfox_url
+      @sample_rate = sample_rate
+      @predict_cache_capacity = predict_cache_capacity
+    end
+  end
+
+  # These are the statistics returned by `Model.predict_cache_stats`.
+  class PredictCacheStats
+    # The maximum number of predictions the cache holds.
+    attr_reader :capacity
+    # The number of predictions currently in the cache.
+    attr_reader :len
+    # The number of predictions served from the cache.
+    attr_reader :hits
+    # The number of predictions that were not in the cache.
+    attr_reader :misses
+    def initialize(capacity:, len:, hits:, misses:)
+      @capacity = capacity
+      @len = len
+      @hits = hits
+      @misses = misses
+    end
+  end
+
+  # `Model.validate_input` returns a `ValidationIssue` for each problem it finds with an input.
+  class ValidationIssue
+    # This is the kind of issue, one of `:missing_column`, `:unknown_column`, `:unknown_enum_variant`, `:invalid_number`, or `:invalid_date_time`.
+    attr_reader :type
+    # This is the name of the column the issue is for.
+    attr_reader :column_name
+    # This is the value the issue is for, either a string or a float. It is `nil` for a missing column.
+    attr_reader :value
+    # This is a message that describes the issue and what predicting with the input will do about it.
+    attr_reader :message
+    def initialize(type:, column_name:, value:, message:)
+      @type = type
+      @column_name = column_name
+      @value = value
+      @message = message
     end
   end
 
@@ -22,12 +64,25 @@ module ModelFox
     attr_reader :threshold
     # Computing feature contributions is disabled by default. If you set this field to `true`, you will be able to access the feature contributions with the `feature_contributions` field of the predict output.
     attr_reader :compute_feature_contributions
-    def initialize(compute_feature_contributions:, threshold: nil)
+    # Nested hashes in the input are flattened into columns named by joining their keys with this separator, so `{user: {age: 3}}` sets the column `user.age`. The default value is `"."`.
+    attr_reader :flatten_separator
+    # This is the number of levels of nested hashes to flatten. Hashes nested more deeply are dropped. By default, every level is flattened.
+    attr_reader :flatten_max_depth
+    # If your model is a multiclass classifier, use this field to set a probability threshold for some classes, like the ones saved on the tuning page of the app. A class with a threshold is only predicted if its probability is at least the threshold. Classes without a threshold can always be predicted, so if no class with a threshold qualifies, the most probable class without one is predicted. If every class has a threshold and none qualifies, the most probable class is predicted. If you do not set this field, the thresholds saved in the model when it was downloaded from the app are used.
+    attr_reader :class_thresholds
+    def initialize(compute_feature_contributions:, threshold: nil, flatten_separator: nil, flatten_max_depth: nil, class_thresholds: nil)
       @threshold = threshold
       @compute_feature_contributions = compute_feature_contributions
+      @flatten_separator = flatten_separator
+      @flatten_max_depth = flatten_max_depth
+      @class_thresholds = class_thresholds
     end
     def to_json(*args)
-      {'threshold' => @threshold, 'compute_feature_contributions' => @compute_feature_contributions}.to_json(*args)
+      json = {'threshold' => @threshold, 'compute_feature_contributions' => @compute_feature_contributions}
+      json['flatten_separator'] = @flatten_separator unless @flatten_separator.nil?
+      json['flatten_max_depth'] = @flatten_max_depth unless @flatten_max_depth.nil?
+      json['class_thresholds'] = @class_thresholds unless @class_thresholds.nil?
+      json.to_json(*args)
     end
   end
 
@@ -256,8 +311,12 @@ module ModelFox
 
     def initialize(c_model, options: nil)
       @modelfox_url = options&.modelfox_url.nil? ? 'https://app.modelfox.dev' : options&.modelfox_url
+      @sample_rate = options&.sample_rate.nil? ? 1.0 : options.sample_rate.to_f
+      raise 'The sample rate must be greater than 0 and at most 1.' unless @sample_rate > 0 && @sample_rate <= 1
+      @predict_cache_capacity = options&.predict_cache_capacity.nil? ? 0 : options.predict_cache_capacity
       @log_queue = []
       @model = FFI::AutoPointer.new(c_model.read_pointer, LibModelFox.method(:modelfox_model_delete))
+      LibModelFox.modelfox_model_set_predict_cache_capacity(@model, @predict_cache_capacity) if @predict_cache_capacity > 0
     end
 
     # Retrieve the model's id.
@@ -267,29 +326,94 @@ module ModelFox
       c_id.into_string
     end
 
+    # Retrieve statistics about the predict cache enabled with the `predict_cache_capacity` load model option.
+    # @return [PredictCacheStats]
+    def predict_cache_stats
+      c_len = FFI::MemoryPointer.new(:size_t)
+      c_hits = FFI::MemoryPointer.new(:uint64)
+      c_misses = FFI::MemoryPointer.new(:uint64)
+      LibModelFox.modelfox_model_get_predict_cache_stats(@model, c_len, c_hits, c_misses)
+      PredictCacheStats.new(
+        capacity: @predict_cache_capacity,
+        len: c_len.read(:size_t),
+        hits: c_hits.read(:uint64),
+        misses: c_misses.read(:uint64)
+      )
+    end
+
+    # Remove all predictions from the predict cache and reset its statistics.
+    def clear_predict_cache
+      LibModelFox.modelfox_model_clear_predict_cache(@model)
+    end
+
     # Make a prediction!
-    # @param input [Array<Hash{String, Symbol => String, Number}>, Hash{String, Symbol => String, Number}] A predict input is either a single predict input which is a map from symbols or strings to strings or floats or an array of such maps. The keys should match the columns in the CSV file you trained your model with.
+    #
+    # The invalid value policy each column was trained with is enforced. An input with an invalid value in a column whose policy is to drop the row gets an output of `nil`, and an invalid value in a column whose policy is to error raises an error naming the column and the value.
+    # @param input [Array<Hash{String, Symbol => String, Number, Hash}>, Hash{String, Symbol => String, Number, Hash}] A predict input is either a single predict input which is a map from symbols or strings to strings, floats, or nested maps, or an array of such maps. Nested maps are flattened into columns as described in `PredictOptions`. The keys should match the columns in the CSV file you trained your model with.
     # @param options [PredictOptions] These are the predict options.
-    # @return [Array<RegressionPredictOutput, BinaryClassificationPredictOutput, MulticlassClassificationPredictOutput>, RegressionPredictOutput, BinaryClassificationPredictOutput, MulticlassClassificationPredictOutput]. Return a single output if `input` was a single input, or an array if `input` was an array of `input`s.
+    # @return [Array<RegressionPredictOutput, BinaryClassificationPredictOutput, MulticlassClassificationPredictOutput, nil>, RegressionPredictOutput, BinaryClassificationPredictOutput, MulticlassClassificationPredictOutput, nil]. Return a single output if `input` was a single input, or an array if `input` was an array of `input`s.
     def predict(input, options: nil)
       is_array = input.is_a?(Array)
       input = is_array ? input : [input]
-      c_input_vec = new_predict_input_vec(input)
+      c_input_vec = new_predict_input_vec(input, options)
       c_options = new_predict_options(options)
       c_output_vec = FFI::MemoryPointer.new(:pointer)
       c_error = LibModelFox.modelfox_model_predict(@model, c_input_vec, c_options, c_output_vec)
-      raise 'modelfox error' unless c_error.null?
+      unless c_error.null?
+        c_error = FFI::AutoPointer.new(c_error, LibModelFox.method(:modelfox_error_delete))
+        c_error_s = LibModelFox::ModelFoxStringView.new
+        LibModelFox.modelfox_error_get_message(c_error, c_error_s)
+        raise c_error_s.into_string
+      end
       c_output_vec = FFI::AutoPointer.new(c_output_vec.read_pointer, LibModelFox.method(:modelfox_predict_output_vec_delete))
       output = predict_output_vec_from_modelfox_predict_output_vec(c_output_vec)
       is_array ? output : output[0]
     end
 
+    # Check an input against the columns the model was trained with, without making a prediction.
+    # @param input [Hash{String, Symbol => String, Number}] A single `PredictInput`.
+    # @param options [PredictOptions]
+    # @return [Array<ValidationIssue>] One `ValidationIssue` for each problem with the input. The array is empty if the input is valid.
+    def validate_input(input, options: nil)
+      c_input = FFI::AutoPointer.new(new_predict_input(input, options), LibModelFox.method(:modelfox_predict_input_delete))
+      c_issues = FFI::MemoryPointer.new(:pointer)
+      c_error = LibModelFox.modelfox_model_validate_input(@model, c_input, c_issues)
+      unless c_error.null?
+        c_error = FFI::AutoPointer.new(c_error, LibModelFox.method(:modelfox_error_delete))
+        c_error_s = LibModelFox::ModelFoxStringView.new
+        LibModelFox.modelfox_error_get_message(c_error, c_error_s)
+        raise c_error_s.into_string
+      end
+      c_issues = FFI::AutoPointer.new(c_issues.read_pointer, LibModelFox.method(:modelfox_validation_issue_vec_delete))
+      c_len = FFI::MemoryPointer.new(:size_t)
+      LibModelFox.modelfox_validation_issue_vec_len(c_issues, c_len)
+      (0...c_len.read(:size_t)).map do |index|
+        c_issue = FFI::MemoryPointer.new(:pointer)
+        LibModelFox.modelfox_validation_issue_vec_get_at_index(c_issues, index, c_issue)
+        c_issue = c_issue.read_pointer
+        c_type = FFI::MemoryPointer.new(:int)
+        LibModelFox.modelfox_validation_issue_get_type(c_issue, c_type)
+        c_column_name = LibModelFox::ModelFoxStringView.new
+        LibModelFox.modelfox_validation_issue_get_column_name(c_issue, c_column_name)
+        c_message = LibModelFox::ModelFoxStringView.new
+        LibModelFox.modelfox_validation_issue_get_message(c_issue, c_message)
+        ValidationIssue.new(
+          type: LibModelFox::ModelFoxValidationIssueType[c_type.read(:int)],
+          column_name: c_column_name.into_string,
+          value: validation_issue_value(c_issue),
+          message: c_message.into_string
+        )
+      end
+    end
+
     # Send a prediction event to the app. If you want to batch events, you can use `enqueue_log_prediction` instead.
     # @param identifier [String, Number] This is a unique identifier for the prediction, which will associate it with a true value event and allow you to look it up in the app.
     # @param input [Hash{String, Symbol => String, Number}] A single `PredictInput`.
     # @param output [PredictOutput] A single `PredictOutput`.
     # @param options [PredictOptions] This is the same `predictOptions` value that you passed to `predict`.
     def log_prediction(identifier:, input:, output:, options: nil)
+      return unless sampled?(identifier)
+
       event = prediction_event(
         identifier: identifier,
         input: input,
@@ -305,6 +429,8 @@ module ModelFox
     # @param output [PredictOutput] A single `PredictOutput`.
     # @param options [PredictOptions] This is the same `predictOptions` value that you passed to `predict`.
     def enqueue_log_prediction(identifier:, input:, output:, options: nil)
+      return unless sampled?(identifier)
+
       event = prediction_event(
         identifier: identifier,
         input: input,
@@ -318,6 +444,8 @@ module ModelFox
     # @param identifier [String, Number] This is a unique identifier for the prediction, which will associate it with a true value event and allow you to look it up in the app.
     # @param true_value [String, Number] This is the true value for the prediction.
     def log_true_value(identifier:, true_value:)
+      return unless sampled?(identifier)
+
       event = true_value_event(
         identifier: identifier,
         true_value: true_value
@@ -329,6 +457,8 @@ module ModelFox
     # @param identifier [String, Number] This is a unique identifier for the prediction, which will associate it with a true value event and allow you to look it up in the app.
     # @param true_value [String, Number] This is the true value for the prediction.
     def enqueue_log_true_value(identifier:, true_value:)
+      return unless sampled?(identifier)
+
       event = true_value_event(
         identifier: identifier,
         true_value: true_value
@@ -348,6 +478,22 @@ module ModelFox
       log_events([event])
     end
 
+    # Determine whether the event with this identifier should be logged. The decision is made by libmodelfox, so every ModelFox library makes the same decision for the same identifier.
+    def sampled?(identifier)
+      return true if @sample_rate >= 1
+
+      identifier = identifier.to_i if identifier.is_a?(Float) && identifier.finite? && identifier == identifier.floor
+      c_is_sampled = FFI::MemoryPointer.new(:bool)
+      c_err = LibModelFox.modelfox_is_sampled(identifier.to_s, @sample_rate, c_is_sampled)
+      unless c_err.null?
+        c_err = FFI::AutoPointer.new(c_err, LibModelFox.method(:modelfox_error_delete))
+        c_error_s = LibModelFox::ModelFoxStringView.new
+        LibModelFox.modelfox_error_get_message(c_err, c_error_s)
+        raise c_error_s.into_string
+      end
+      c_is_sampled.read(:bool)
+    end
+
     def log_events(events)
       headers = {
         'Content-Type': 'application/json'
@@ -368,6 +514,7 @@ module ModelFox
         model_id: id,
         options: options,
         output: output,
+        sample_rate: @sample_rate,
         type: 'prediction'
       }
     end
@@ -377,6 +524,7 @@ module ModelFox
         date: DateTime.now.rfc3339,
         identifier: identifier,
         model_id: id,
+        sample_rate: @sample_rate,
         true_value: true_value,
         type: 'true_value'
       }
@@ -393,23 +541,39 @@ module ModelFox
         unless options.compute_feature_contributions.nil?
           LibModelFox.modelfox_predict_options_set_compute_feature_contributions(c_options, options.compute_feature_contributions)
         end
+        unless options.class_thresholds.nil?
+          options.class_thresholds.each do |class_name, threshold|
+            LibModelFox.modelfox_predict_options_set_class_threshold(c_options, class_name.to_s, threshold)
+          end
+        end
       end
       c_options
     end
 
-    def new_predict_input_vec(input_vec)
+    def new_predict_input_vec(input_vec, options)
       c_inputs = FFI::MemoryPointer.new(:pointer)
       LibModelFox.modelfox_predict_input_vec_new(c_inputs)
       c_inputs = FFI::AutoPointer.new(c_inputs.read_pointer, LibModelFox.method(:modelfox_predict_input_vec_delete))
       (0...input_vec.length).each do |input_index|
         input = input_vec[input_index]
-        predict_input = new_predict_input(input)
+        predict_input = new_predict_input(input, options)
         LibModelFox.modelfox_predict_input_vec_push(c_inputs, predict_input)
       end
       c_inputs
     end
 
-    def new_predict_input(input)
+    def validation_issue_value(c_issue)
+      c_value_number = FFI::MemoryPointer.new(:double)
+      return c_value_number.read(:double) if LibModelFox.modelfox_validation_issue_get_value_number(c_issue, c_value_number)
+
+      c_value_string = LibModelFox::ModelFoxStringView.new
+      return c_value_string.into_string if LibModelFox.modelfox_validation_issue_get_value_string(c_issue, c_value_string)
+
+      nil
+    end
+
+    def new_predict_input(input, options)
+      return new_predict_input_from_json(input, options) if input.values.any? { |value| value.is_a?(Hash) }
       c_input = FFI::MemoryPointer.new(:pointer)
       LibModelFox.modelfox_predict_input_new(c_input)
       c_input = c_input.read_pointer
@@ -427,6 +591,21 @@ module ModelFox
       c_input
     end
 
+    # libmodelfox flattens inputs with nested hashes, so the column names match the other language libraries.
+    def new_predict_input_from_json(input, options)
+      separator = options&.flatten_separator
+      max_depth = options&.flatten_max_depth || -1
+      c_input = FFI::MemoryPointer.new(:pointer)
+      c_err = LibModelFox.modelfox_predict_input_new_from_json(JSON.generate(input), separator, max_depth, c_input)
+      unless c_err.null?
+        c_err = FFI::AutoPointer.new(c_err, LibModelFox.method(:modelfox_error_delete))
+        c_error_s = LibModelFox::ModelFoxStringView.new
+        LibModelFox.modelfox_error_get_message(c_err, c_error_s)
+        raise c_error_s.into_string
+      end
+      c_input.read_pointer
+    end
+
     def predict_output_vec_from_modelfox_predict_output_vec(c_output_vec)
       outputs = []
       c_output_len = FFI::MemoryPointer.new(:int)
@@ -436,7 +615,8 @@ module ModelFox
         c_output = FFI::MemoryPointer.new(:pointer)
         LibModelFox.modelfox_predict_output_vec_get_at_index(c_output_vec, output_index, c_output)
         c_output = c_output.read_pointer
-        outputs.push(predict_output_from_modelfox_predict_output(c_output))
+        # The output is null if the input was dropped because of an invalid value.
+        outputs.push(c_output.null? ? nil : predict_output_from_modelfox_predict_output(c_output))
       end
 
       outputs
@@ -768,16 +948,20 @@ module ModelFox
   module LibModelFox
     cpu = RbConfig::CONFIG['host_cpu']
     os = RbConfig::CONFIG['host_os']
-    if cpu == 'x86_64' && os =~ /linux/
-      library_path = 'libmodelfox/x86_64-unknown-linux-gnu/libmodelfox.so'
+    if cpu == 'x86_64' && os =~ /linux-musl/
+      library_path = 'libmodelfox/x86_64-linux-musl/libmodelfox.so'
+    elsif cpu == 'aarch64' && os =~ /linux-musl/
+      library_path = 'libmodelfox/aarch64-linux-musl/libmodelfox.so'
+    elsif cpu == 'x86_64' && os =~ /linux/
+      library_path = 'libmodelfox/x86_64-linux-gnu/libmodelfox.so'
     elsif cpu == 'aarch64' && os =~ /linux/
-      library_path = 'libmodelfox/aarch64-unknown-linux-gnu/libmodelfox.so'
+      library_path = 'libmodelfox/aarch64-linux-gnu/libmodelfox.so'
     elsif cpu == 'x86_64' && os =~ /darwin/
-      library_path = 'libmodelfox/x86_64-apple-darwin/libmodelfox.dylib'
-    elsif (cpu == 'arm' || cpu == 'arm64') && os =~ /darwin/
-      library_path = 'libmodelfox/aarch64-apple-darwin/libmodelfox.dylib'
+      library_path = 'libmodelfox/x86_64-macos/libmodelfox.dylib'
+    elsif (cpu == 'arm' || cpu == 'arm64' || cpu == 'aarch64') && os =~ /darwin/
+      library_path = 'libmodelfox/aarch64-macos/libmodelfox.dylib'
     elsif cpu == 'x86_64' && os =~ /mingw/
-      library_path = 'libmodelfox/x86_64-pc-windows-msvc/modelfox.dll'
+      library_path = 'libmodelfox/x86_64-windows-msvc/modelfox.dll'
     else
       raise 'ModelFox for Ruby does not yet support your combination of CPU architecture and operating system. Open an issue at https://github.com/modelfoxdotdev/modelfox/issues/new or email us at help@modelfox.dev to complain.'
     end
@@ -812,6 +996,14 @@ module ModelFox
       :bigram
     )
 
+    ModelFoxValidationIssueType = enum(
+      :missing_column,
+      :unknown_column,
+      :unknown_enum_variant,
+      :invalid_number,
+      :invalid_date_time
+    )
+
     typedef :pointer, :modelfox_error
 
     attach_function :modelfox_error_get_message, [:pointer, ModelFoxStringView.by_ref], :void
@@ -822,10 +1014,24 @@ module ModelFox
     attach_function :modelfox_model_delete, [:pointer], :void
     attach_function :modelfox_model_get_id, [:pointer, ModelFoxStringView.by_ref], :void
     attach_function :modelfox_model_get_task, [:pointer, :pointer], :void
+    attach_function :modelfox_is_sampled, [:string, :float, :pointer], :modelfox_error
+    attach_function :modelfox_model_set_predict_cache_capacity, [:pointer, :size_t], :void
+    attach_function :modelfox_model_get_predict_cache_stats, [:pointer, :pointer, :pointer, :pointer], :void
+    attach_function :modelfox_model_clear_predict_cache, [:pointer], :void
     attach_function :modelfox_predict_input_new, [:pointer], :void
     attach_function :modelfox_predict_input_delete, [:pointer], :void
+    attach_function :modelfox_model_validate_input, [:pointer, :pointer, :pointer], :modelfox_error
+    attach_function :modelfox_validation_issue_vec_delete, [:pointer], :void
+    attach_function :modelfox_validation_issue_vec_len, [:pointer, :pointer], :void
+    attach_function :modelfox_validation_issue_vec_get_at_index, [:pointer, :size_t, :pointer], :void
+    attach_function :modelfox_validation_issue_get_type, [:pointer, :pointer], :void
+    attach_function :modelfox_validation_issue_get_column_name, [:pointer, ModelFoxStringView.by_ref], :void
+    attach_function :modelfox_validation_issue_get_message, [:pointer, ModelFoxStringView.by_ref], :void
+    attach_function :modelfox_validation_issue_get_value_number, [:pointer, :pointer], :bool
+    attach_function :modelfox_validation_issue_get_value_string, [:pointer, ModelFoxStringView.by_ref], :bool
     attach_function :modelfox_predict_input_set_value_number, [:pointer, :string, :double], :int
     attach_function :modelfox_predict_input_set_value_string, [:pointer, :string, :string], :int
+    attach_function :modelfox_predict_input_new_from_json, [:string, :string, :int, :pointer], :modelfox_error
     attach_function :modelfox_predict_input_vec_new, [:pointer], :void
     attach_function :modelfox_predict_input_vec_delete, [:pointer], :void
     attach_function :modelfox_predict_input_vec_push, [:pointer, :pointer], :void
@@ -833,6 +1039,7 @@ module ModelFox
     attach_function :modelfox_predict_options_delete, [:pointer], :void
     attach_function :modelfox_predict_options_set_threshold, [:pointer, :float], :void
     attach_function :modelfox_predict_options_set_compute_feature_contributions, [:pointer, :bool], :void
+    attach_function :modelfox_predict_options_set_class_threshold, [:pointer, :string, :float], :int
     attach_function :modelfox_model_predict, [:pointer, :pointer, :pointer, :pointer], :modelfox_error
     attach_function :modelfox_predict_output_delete, [:pointer], :void
     attach_function :modelfox_predict_output_vec_delete, [:pointer], :void
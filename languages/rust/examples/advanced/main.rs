@@ -114,7 +114,11 @@ fn main() -> Result<()> {
 	};
 
 	// Load the model from the path.
-	let options = modelfox::LoadModelOptions { modelfox_url };
+	let options = modelfox::LoadModelOptions {
+		modelfox_url,
+		sample_rate: None,
+		predict_cache_capacity: None,
+	};
 	let mut model =
 		modelfox::Model::<Input, Output>::from_path("heart_disease.modelfox", Some(options))?;
 
@@ -139,6 +143,7 @@ fn main() -> Result<()> {
 	let options = modelfox::PredictOptions {
 		threshold: Some(0.25),
 		compute_feature_contributions: Some(true),
+		class_thresholds: None,
 	};
 	let output = model.predict_one(input.clone(), Some(options.clone()));
 
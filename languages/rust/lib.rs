@@ -20,27 +20,49 @@ let input = modelfox::predict_input! {
 let output = model.predict_one(input, None);
 ```
 
+To get type checked inputs, derive `PredictInput` for a struct whose fields are the model's columns. Use `#[modelfox(rename = "...")]` when a field name differs from its column name, and an `Option` field for a column that may be missing. Loading a model with a derived input type returns an error if the struct does not provide every column the model requires.
+
+```rust no_run
+#[derive(modelfox::PredictInput)]
+struct Input {
+  age: f32,
+  #[modelfox(rename = "chest_pain")]
+  chest_pain_type: String,
+  cholesterol: Option<f32>,
+  // ...
+}
+
+let model = modelfox::Model::<Input>::from_path("heart_disease.modelfox", None).unwrap();
+```
+
 For more information, [read the docs](https://www.modelfox.dev/docs).
 */
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use memmap::Mmap;
+pub use modelfox_core::predict::{ValidationIssue, ValidationIssueKind};
+pub use modelfox_core::predict_cache::PredictCacheStats;
 pub use modelfox_macro::{
 	predict_input, ClassificationOutputValue, PredictInput, PredictInputValue,
 };
 use std::path::Path;
-use std::{collections::BTreeMap, marker::PhantomData};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	marker::PhantomData,
+};
 use url::Url;
 
 /// Use this struct to load a model, make predictions, and log events to the app.
 pub struct Model<Input = PredictInput, Output = PredictOutput>
 where
-	Input: Into<PredictInput>,
+	Input: Into<PredictInput> + PredictInputColumns,
 	Output: From<PredictOutput> + Into<PredictOutput>,
 {
 	model: modelfox_core::predict::Model,
+	predict_cache: modelfox_core::predict_cache::PredictCache,
 	log_queue: Vec<Event>,
 	modelfox_url: Url,
+	sample_rate: f32,
 	input_marker: PhantomData<Input>,
 	output_marker: PhantomData<Output>,
 }
@@ -49,6 +71,10 @@ where
 pub struct LoadModelOptions {
 	/// If you are running the app locally or on your own server, use this field to provide a url that points to it. If not specified, the default value is `https://app.modelfox.dev`.
 	pub modelfox_url: Option<Url>,
+	/// If your service makes too many predictions to log every one, use this field to log only a fraction of prediction and true value events. The value must be greater than 0 and at most 1. Whether an event is logged is determined by its identifier, so the true value for a logged prediction is logged too. If not specified, every event is logged.
+	pub sample_rate: Option<f32>,
+	/// If your service makes many identical predictions, use this field to cache the outputs of this many of the most recently used inputs. Repeated predictions with the same input and options are then returned from the cache without running the model. The cache is disabled by default.
+	pub predict_cache_capacity: Option<usize>,
 }
 
 /// This is the input type of [`Model::predict`]. A predict input is a map whose keys are the same as the column names in the CSV the model was trained with, and whose values match the type for each column.
@@ -67,6 +93,16 @@ impl From<PredictInput> for modelfox_core::predict::PredictInput {
 	}
 }
 
+/// This trait describes the columns provided by an input type. `#[derive(PredictInput)]` implements it, so that loading a [`Model`] with a derived input type checks that the input provides every column the model computes features from.
+pub trait PredictInputColumns {
+	/// Return the names of the columns this input type provides, or `None` if they are only known when making a prediction, as with [`PredictInput`].
+	fn column_names() -> Option<&'static [&'static str]> {
+		None
+	}
+}
+
+impl PredictInputColumns for PredictInput {}
+
 #[derive(Clone, Debug, serde::Serialize)]
 #[serde(untagged)]
 pub enum PredictInputValue {
@@ -153,6 +189,8 @@ pub struct PredictOptions {
 	pub threshold: Option<f32>,
 	/// Computing feature contributions is disabled by default. If you set this field to `true`, you will be able to access the feature contributions with the `feature_contributions` field of the predict output.
 	pub compute_feature_contributions: Option<bool>,
+	/// If your model is a multiclass classifier, use this field to set a probability threshold for some classes, like the ones saved on the tuning page of the app. A class with a threshold is only predicted if its probability is at least the threshold. Classes without a threshold can always be predicted, so if no class with a threshold qualifies, the most probable class without one is predicted. If every class has a threshold and none qualifies, the most probable class is predicted. If you do not set this field, the thresholds saved in the model when it was downloaded from the app are used.
+	pub class_thresholds: Option<BTreeMap<String, f32>>,
 }
 
 impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
@@ -164,6 +202,9 @@ impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 		if let Some(compute_feature_contributions) = value.compute_feature_contributions {
 			options.compute_feature_contributions = compute_feature_contributions;
 		}
+		if let Some(class_thresholds) = value.class_thresholds {
+			options.class_thresholds = class_thresholds;
+		}
 		options
 	}
 }
@@ -427,6 +468,8 @@ pub enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "cyclical")]
+	Cyclical(CyclicalFeatureContribution),
 }
 
 impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContributionEntry {
@@ -450,6 +493,9 @@ impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContribut
 			modelfox_core::predict::FeatureContributionEntry::WordEmbedding(value) => {
 				FeatureContributionEntry::WordEmbedding(value.into())
 			}
+			modelfox_core::predict::FeatureContributionEntry::Cyclical(value) => {
+				FeatureContributionEntry::Cyclical(value.into())
+			}
 		}
 	}
 }
@@ -613,6 +659,30 @@ impl From<modelfox_core::predict::WordEmbeddingFeatureContribution>
 	}
 }
 
+/// This describes the contribution of a feature from a cyclical feature group.
+#[derive(Debug, serde::Serialize)]
+pub struct CyclicalFeatureContribution {
+	/// This is the name of the source column for the feature group.
+	pub column_name: String,
+	/// This is either "sin" or "cos", identifying which of the feature group's two features this is.
+	pub component: String,
+	/// This is the value of the feature.
+	pub feature_value: f32,
+	/// This is the amount that the feature contributed to the output.
+	pub feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::CyclicalFeatureContribution> for CyclicalFeatureContribution {
+	fn from(value: modelfox_core::predict::CyclicalFeatureContribution) -> Self {
+		CyclicalFeatureContribution {
+			column_name: value.column_name,
+			component: value.component.to_string(),
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
 /// This is the type of the argument to [`Model::log_prediction`] and [`Model::enqueue_log_prediction`] which specifies the details of the prediction to log.
 #[derive(Debug)]
 pub struct LogPredictionArgs<Input, Output>
@@ -656,6 +726,7 @@ struct PredictionEvent {
 	options: Option<PredictOptions>,
 	output: PredictOutput,
 	model_id: String,
+	sample_rate: f32,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -663,6 +734,7 @@ struct TrueValueEvent {
 	date: chrono::DateTime<chrono::Utc>,
 	identifier: NumberOrString,
 	model_id: String,
+	sample_rate: f32,
 	true_value: NumberOrString,
 }
 
@@ -694,7 +766,7 @@ impl From<&str> for NumberOrString {
 /// Use this struct to load a model, make predictions, and log events to the app.
 impl<Input, Output> Model<Input, Output>
 where
-	Input: Into<PredictInput>,
+	Input: Into<PredictInput> + PredictInputColumns,
 	Output: From<PredictOutput> + Into<PredictOutput>,
 {
 	/// Load a model from the `.modelfox` file at `path`.
@@ -712,15 +784,32 @@ where
 		bytes: &[u8],
 		options: Option<LoadModelOptions>,
 	) -> Result<Model<Input, Output>> {
-		let model = modelfox_model::from_bytes(bytes)?;
-		let model = modelfox_core::predict::Model::from(model);
-		let modelfox_url = options
-			.and_then(|options| options.modelfox_url)
-			.unwrap_or_else(|| "https://app.modelfox.dev".parse().unwrap());
+		let model = modelfox_core::predict::Model::from_bytes(bytes)?;
+		if let Some(column_names) = Input::column_names() {
+			validate_input_column_names(&model, column_names)?;
+		}
+		let (modelfox_url, sample_rate, predict_cache_capacity) = match options {
+			Some(options) => (
+				options.modelfox_url,
+				options.sample_rate,
+				options.predict_cache_capacity,
+			),
+			None => (None, None, None),
+		};
+		let modelfox_url =
+			modelfox_url.unwrap_or_else(|| "https://app.modelfox.dev".parse().unwrap());
+		let sample_rate = sample_rate.unwrap_or(1.0);
+		if !modelfox_core::sampling::is_valid_sample_rate(sample_rate) {
+			bail!("The sample rate must be greater than 0 and at most 1.");
+		}
+		let predict_cache =
+			modelfox_core::predict_cache::PredictCache::new(predict_cache_capacity.unwrap_or(0));
 		Ok(Model {
 			model,
+			predict_cache,
 			log_queue: Vec::new(),
 			modelfox_url,
+			sample_rate,
 			input_marker: PhantomData,
 			output_marker: PhantomData,
 		})
@@ -731,16 +820,21 @@ where
 		self.model.id.as_str()
 	}
 
-	/// Make a prediction with a single input.
+	/// Make a prediction with a single input. This does not enforce the invalid value policies the model's columns were trained with, so invalid values are treated as missing. Use [`Model::try_predict_one`] to enforce them.
 	pub fn predict_one(&self, input: Input, options: Option<PredictOptions>) -> Output {
 		let model = &self.model;
 		let options = options.map(Into::into).unwrap_or_default();
-		let output = modelfox_core::predict::predict(model, &[input.into().into()], &options);
+		let output = modelfox_core::predict_cache::predict_with_cache(
+			model,
+			&self.predict_cache,
+			&[input.into().into()],
+			&options,
+		);
 		let output: PredictOutput = output.into_iter().next().unwrap().into();
 		output.into()
 	}
 
-	/// Make a prediction with multiple inputs.
+	/// Make a prediction with multiple inputs. This does not enforce the invalid value policies the model's columns were trained with, so invalid values are treated as missing. Use [`Model::try_predict`] to enforce them.
 	pub fn predict(&self, input: Vec<Input>, options: Option<PredictOptions>) -> Vec<Output> {
 		let model = &self.model;
 		let options = options.map(Into::into).unwrap_or_default();
@@ -749,7 +843,12 @@ where
 			.map(Into::into)
 			.map(Into::into)
 			.collect::<Vec<_>>();
-		let output = modelfox_core::predict::predict(model, &input, &options);
+		let output = modelfox_core::predict_cache::predict_with_cache(
+			model,
+			&self.predict_cache,
+			&input,
+			&options,
+		);
 		output
 			.into_iter()
 			.map(|output| -> PredictOutput { output.into() })
@@ -757,9 +856,80 @@ where
 			.collect()
 	}
 
+	/// Retrieve statistics about the predict cache enabled with [`LoadModelOptions::predict_cache_capacity`].
+	pub fn predict_cache_stats(&self) -> PredictCacheStats {
+		self.predict_cache.stats()
+	}
+
+	/// Remove all predictions from the predict cache and reset its statistics.
+	pub fn clear_predict_cache(&self) {
+		self.predict_cache.clear()
+	}
+
+	/// Check an input against the columns the model was trained with, returning an issue for each missing column, unknown column, and value that does not parse for its column's type. Each issue's message says what predicting with the input will do about it.
+	pub fn validate_input(&self, input: Input) -> Vec<ValidationIssue> {
+		modelfox_core::predict::validate_input(&self.model, &input.into().into())
+	}
+
+	/// Make a prediction with a single input, enforcing the invalid value policy each column was trained with. This returns `Ok(None)` if the input has an invalid value in a column whose policy is to drop the row, and an error if the input has an invalid value in a column whose policy is to error.
+	pub fn try_predict_one(
+		&self,
+		input: Input,
+		options: Option<PredictOptions>,
+	) -> Result<Option<Output>> {
+		let model = &self.model;
+		let options = options.map(Into::into).unwrap_or_default();
+		let output = modelfox_core::predict_cache::try_predict_with_cache(
+			model,
+			&self.predict_cache,
+			&[input.into().into()],
+			&options,
+		)?;
+		let output = output
+			.into_iter()
+			.next()
+			.unwrap()
+			.map(|output| -> PredictOutput { output.into() })
+			.map(Into::into);
+		Ok(output)
+	}
+
+	/// Make a prediction with multiple inputs, enforcing the invalid value policy each column was trained with. Inputs with an invalid value in a column whose policy is to drop the row have an output of `None`, and an invalid value in a column whose policy is to error returns an error.
+	pub fn try_predict(
+		&self,
+		input: Vec<Input>,
+		options: Option<PredictOptions>,
+	) -> Result<Vec<Option<Output>>> {
+		let model = &self.model;
+		let options = options.map(Into::into).unwrap_or_default();
+		let input = input
+			.into_iter()
+			.map(Into::into)
+			.map(Into::into)
+			.collect::<Vec<_>>();
+		let output = modelfox_core::predict_cache::try_predict_with_cache(
+			model,
+			&self.predict_cache,
+			&input,
+			&options,
+		)?;
+		let output = output
+			.into_iter()
+			.map(|output| {
+				output
+					.map(|output| -> PredictOutput { output.into() })
+					.map(Into::into)
+			})
+			.collect();
+		Ok(output)
+	}
+
 	/// Send a prediction event to the app. If you want to batch events, you can use [`Model::enqueue_log_true_value`] instead.
 	#[cfg(not(feature = "tokio"))]
 	pub fn log_prediction(&mut self, args: LogPredictionArgs<Input, Output>) -> Result<()> {
+		if !self.is_sampled(&args.identifier) {
+			return Ok(());
+		}
 		let event = Event::Prediction(self.prediction_event(args));
 		self.log_event(event)?;
 		Ok(())
@@ -768,6 +938,9 @@ where
 	/// Send a prediction event to the app. If you want to batch events, you can use [`Model::enqueue_log_true_value`] instead.
 	#[cfg(feature = "tokio")]
 	pub async fn log_prediction(&mut self, args: LogPredictionArgs<Input, Output>) -> Result<()> {
+		if !self.is_sampled(&args.identifier) {
+			return Ok(());
+		}
 		let event = Event::Prediction(self.prediction_event(args));
 		self.log_event(event).await?;
 		Ok(())
@@ -776,6 +949,9 @@ where
 	/// Send a true value event to the app. If you want to batch events, you can use [`Model::enqueue_log_true_value`] instead.
 	#[cfg(not(feature = "tokio"))]
 	pub fn log_true_value(&mut self, args: LogTrueValueArgs) -> Result<()> {
+		if !self.is_sampled(&args.identifier) {
+			return Ok(());
+		}
 		let event = Event::TrueValue(self.true_value_event(args));
 		self.log_event(event)?;
 		Ok(())
@@ -784,6 +960,9 @@ where
 	/// Send a true value event to the app. If you want to batch events, you can use [`Model::enqueue_log_true_value`] instead.
 	#[cfg(feature = "tokio")]
 	pub async fn log_true_value(&mut self, args: LogTrueValueArgs) -> Result<()> {
+		if !self.is_sampled(&args.identifier) {
+			return Ok(());
+		}
 		let event = Event::TrueValue(self.true_value_event(args));
 		self.log_event(event).await?;
 		Ok(())
@@ -791,12 +970,18 @@ where
 
 	/// Add a prediction event to the queue. Remember to call [`Model::flush_log_queue`] at a later point to send the event to the app.
 	pub fn enqueue_log_prediction(&mut self, args: LogPredictionArgs<Input, Output>) {
+		if !self.is_sampled(&args.identifier) {
+			return;
+		}
 		let event = Event::Prediction(self.prediction_event(args));
 		self.log_queue.push(event);
 	}
 
 	/// Add a true value event to the queue. Remember to call [`Model::flush_log_queue`] at a later point to send the event to the app.
 	pub fn enqueue_log_true_value(&mut self, args: LogTrueValueArgs) {
+		if !self.is_sampled(&args.identifier) {
+			return;
+		}
 		let event = Event::TrueValue(self.true_value_event(args));
 		self.log_queue.push(event);
 	}
@@ -846,6 +1031,14 @@ where
 		Ok(())
 	}
 
+	fn is_sampled(&self, identifier: &NumberOrString) -> bool {
+		let identifier = match identifier {
+			NumberOrString::Number(number) => number.to_string(),
+			NumberOrString::String(string) => string.to_owned(),
+		};
+		modelfox_core::sampling::is_sampled(&identifier, self.sample_rate)
+	}
+
 	fn prediction_event(&self, args: LogPredictionArgs<Input, Output>) -> PredictionEvent {
 		PredictionEvent {
 			date: chrono::Utc::now(),
@@ -854,6 +1047,7 @@ where
 			options: args.options,
 			output: args.output.into(),
 			model_id: self.id().to_owned(),
+			sample_rate: self.sample_rate,
 		}
 	}
 
@@ -862,7 +1056,88 @@ where
 			date: chrono::Utc::now(),
 			identifier: args.identifier,
 			model_id: self.id().to_owned(),
+			sample_rate: self.sample_rate,
 			true_value: args.true_value,
 		}
 	}
 }
+
+/// Check that an input type provides every column that the model's feature groups are computed from.
+fn validate_input_column_names(
+	model: &modelfox_core::predict::Model,
+	column_names: &[&str],
+) -> Result<()> {
+	let feature_groups = match &model.inner {
+		modelfox_core::predict::ModelInner::Regressor(model) => &model.feature_groups,
+		modelfox_core::predict::ModelInner::BinaryClassifier(model) => &model.feature_groups,
+		modelfox_core::predict::ModelInner::MulticlassClassifier(model) => &model.feature_groups,
+	};
+	let missing_column_names = feature_groups
+		.iter()
+		.flat_map(|feature_group| feature_group.source_column_names())
+		.filter(|column_name| !column_names.contains(column_name))
+		.collect::<BTreeSet<_>>();
+	if !missing_column_names.is_empty() {
+		let missing_column_names = missing_column_names
+			.into_iter()
+			.map(|column_name| format!("\"{}\"", column_name))
+			.collect::<Vec<_>>()
+			.join(", ");
+		bail!(
+			"The input type does not provide the column(s) {}, which the model requires. Add a field for each one, using #[modelfox(rename = \"...\")] if the field name differs from the column name.",
+			missing_column_names
+		);
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use modelfox_core::test_common::{
+		tfidf_prediction_reviews, tfidf_training_features, train_tfidf_model,
+	};
+
+	#[test]
+	fn test_tfidf_features_match_training() {
+		let model = train_tfidf_model();
+		let reviews = tfidf_prediction_reviews();
+		let expected = tfidf_training_features(&model, &reviews);
+		let model: Model = Model::from_bytes(&model.to_bytes(), None).unwrap();
+		let input = reviews
+			.iter()
+			.map(|review| {
+				let mut input = PredictInput(BTreeMap::new());
+				input.0.insert("review".to_owned(), review.as_str().into());
+				input
+			})
+			.collect();
+		let options = PredictOptions {
+			threshold: None,
+			compute_feature_contributions: Some(true),
+			class_thresholds: None,
+		};
+		let outputs = model.predict(input, Some(options));
+		assert_eq!(outputs.len(), reviews.len());
+		for (output, expected) in outputs.iter().zip(expected.iter()) {
+			let feature_contributions = match output {
+				PredictOutput::BinaryClassification(output) => {
+					output.feature_contributions.as_ref().unwrap()
+				}
+				_ => unreachable!(),
+			};
+			let features = feature_contributions
+				.entries
+				.iter()
+				.map(|entry| match entry {
+					FeatureContributionEntry::BagOfWords(entry) => entry.feature_value,
+					_ => unreachable!(),
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(features.len(), expected.len());
+			for (value, expected_value) in features.iter().zip(expected.iter()) {
+				assert!((value - expected_value).abs() < f32::EPSILON);
+			}
+		}
+	}
+}
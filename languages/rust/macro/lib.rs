@@ -80,6 +80,7 @@ fn predict_input_derive_macro_impl(
 			))
 		}
 	};
+	let mut column_names: Vec<String> = Vec::new();
 	let insert_statements = data
 		.fields
 		.iter()
@@ -90,8 +91,24 @@ fn predict_input_derive_macro_impl(
 				.ok_or_else(|| syn::Error::new(field.span(), "field must have ident"))?;
 			let column_name =
 				predict_input_field_rename(field)?.unwrap_or_else(|| field_ident.to_string());
-			let code = quote! {
-				map.insert(#column_name.to_owned(), value.#field_ident.into());
+			if column_names.contains(&column_name) {
+				return Err(syn::Error::new_spanned(
+					field,
+					format!("multiple fields map to the column \"{}\"", column_name),
+				));
+			}
+			column_names.push(column_name.clone());
+			// A field whose type is an `Option` is omitted from the input when it is `None`, which the model treats as a missing value.
+			let code = if is_option_type(&field.ty) {
+				quote! {
+					if let Some(field_value) = value.#field_ident {
+						map.insert(#column_name.to_owned(), field_value.into());
+					}
+				}
+			} else {
+				quote! {
+					map.insert(#column_name.to_owned(), value.#field_ident.into());
+				}
 			};
 			Ok(code)
 		})
@@ -104,10 +121,27 @@ fn predict_input_derive_macro_impl(
 				modelfox::PredictInput(map)
 			}
 		}
+		impl modelfox::PredictInputColumns for #ident {
+			fn column_names() -> Option<&'static [&'static str]> {
+				Some(&[#(#column_names),*])
+			}
+		}
 	};
 	Ok(code)
 }
 
+fn is_option_type(ty: &syn::Type) -> bool {
+	match ty {
+		syn::Type::Path(ty) => ty
+			.path
+			.segments
+			.last()
+			.map(|segment| segment.ident == "Option")
+			.unwrap_or(false),
+		_ => false,
+	}
+}
+
 fn predict_input_field_rename(field: &syn::Field) -> syn::Result<Option<String>> {
 	let attr = field
 		.attrs
@@ -138,7 +172,10 @@ fn predict_input_field_rename(field: &syn::Field) -> syn::Result<Option<String>>
 					None
 				};
 				let value = value.ok_or_else(|| {
-					syn::Error::new_spanned(&item, "value for attribute \"value\" must be a string")
+					syn::Error::new_spanned(
+						&item,
+						"value for attribute \"rename\" must be a string",
+					)
 				})?;
 				rename = Some(value);
 			}
@@ -146,7 +183,7 @@ fn predict_input_field_rename(field: &syn::Field) -> syn::Result<Option<String>>
 		}
 	}
 	let rename = rename.ok_or_else(|| {
-		syn::Error::new_spanned(&list.nested, "an attribute with key \"value\" is required")
+		syn::Error::new_spanned(&list.nested, "an attribute with key \"rename\" is required")
 	})?;
 	let rename = rename.value();
 	Ok(Some(rename))